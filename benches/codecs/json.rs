@@ -0,0 +1,128 @@
+use std::{fmt, time::Duration};
+
+use bytes::Bytes;
+use codecs::{decoding::format::Deserializer, JsonDeserializer};
+use criterion::{
+    criterion_group, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId, Criterion,
+    SamplingMode, Throughput,
+};
+use value::Value;
+
+/// Parses the inputs with `simd-json`, which only has an effect with the `json-simd` feature.
+#[derive(Debug, Clone, Copy)]
+struct Param {
+    slug: &'static str,
+    simd: bool,
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.slug)
+    }
+}
+
+const PARAMS: [Param; 2] = [
+    Param {
+        slug: "serde_json",
+        simd: false,
+    },
+    Param {
+        slug: "simd_json",
+        simd: true,
+    },
+];
+
+/// A log event of a few hundred bytes, like those of an HTTP access log.
+fn small() -> Bytes {
+    Bytes::from(
+        serde_json::json!({
+            "timestamp": "2022-06-14T12:34:56.789Z",
+            "host": "web-01.example.com",
+            "method": "GET",
+            "path": "/api/v1/users/1234/orders?limit=50",
+            "status": 200,
+            "bytes": 5120,
+            "duration_ms": 12.5,
+            "user_agent": "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36",
+            "tags": ["frontend", "production", "eu-west-1"],
+        })
+        .to_string(),
+    )
+}
+
+/// A log event of tens of kilobytes, with a large nested payload.
+fn large() -> Bytes {
+    let items: Vec<_> = (0..500)
+        .map(|i| {
+            serde_json::json!({
+                "id": i,
+                "name": format!("item-{}", i),
+                "price": f64::from(i) * 1.25,
+                "in_stock": i % 3 != 0,
+                "attributes": {"color": "blue", "size": "XL", "weight": 0.5},
+            })
+        })
+        .collect();
+    Bytes::from(serde_json::json!({"message": "order received", "items": items}).to_string())
+}
+
+fn inputs() -> [(&'static str, Bytes); 2] {
+    [("small", small()), ("large", large())]
+}
+
+fn deserializer(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<WallTime> =
+        c.benchmark_group("vector::codecs::decoding::JsonDeserializer");
+    group.sampling_mode(SamplingMode::Auto);
+
+    for (size, input) in inputs() {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        for param in PARAMS {
+            value::json::set_simd(param.simd);
+            group.bench_with_input(BenchmarkId::new(size, param), &input, |b, input| {
+                let deserializer = JsonDeserializer::new();
+                b.iter_batched(
+                    || input.clone(),
+                    |input| deserializer.parse(input).unwrap(),
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+    }
+    value::json::set_simd(false);
+}
+
+/// Parses the inputs as the `parse_json` function of VRL does.
+fn parse_json(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<WallTime> = c.benchmark_group("vrl_stdlib::parse_json");
+    group.sampling_mode(SamplingMode::Auto);
+
+    for (size, input) in inputs() {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        for param in PARAMS {
+            value::json::set_simd(param.simd);
+            group.bench_with_input(BenchmarkId::new(size, param), &input, |b, input| {
+                b.iter(|| value::json::from_slice::<Value>(input).unwrap())
+            });
+        }
+    }
+    value::json::set_simd(false);
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default()
+        .warm_up_time(Duration::from_secs(5))
+        .measurement_time(Duration::from_secs(30))
+        // degree of noise to ignore in measurements, here 1%
+        .noise_threshold(0.01)
+        // likelihood of noise registering as difference, here 5%
+        .significance_level(0.05)
+        // likelihood of capturing the true runtime, here 95%
+        .confidence_level(0.95)
+        // total number of bootstrap resamples, higher is less noisy but slower
+        .nresamples(100_000)
+        // total samples to collect within the set measurement time
+        .sample_size(100);
+    targets = deserializer, parse_json
+);
@@ -59,6 +59,10 @@ fn benchmark_files_no_partitions(c: &mut Criterion) {
                         )
                         .into(),
                         compression: sinks::file::Compression::None,
+                        max_size_bytes: None,
+                        max_age_secs: None,
+                        final_path: None,
+                        atomic_rename: false,
                         acknowledgements: Default::default(),
                     },
                 );
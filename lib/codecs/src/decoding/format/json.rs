@@ -1,10 +1,11 @@
-use std::convert::TryInto;
+use std::borrow::Cow;
 
 use bytes::Bytes;
 use chrono::Utc;
+use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
-use value::Kind;
+use value::{json::NonFiniteFloats, Kind, Value};
 use vector_core::{
     config::{log_schema, DataType},
     event::Event,
@@ -14,8 +15,28 @@ use vector_core::{
 use super::Deserializer;
 
 /// Config used to build a `JsonDeserializer`.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct JsonDeserializerConfig;
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct JsonDeserializerConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the JSON deserializer.
+    pub json: JsonDeserializerOptions,
+}
+
+/// Options for building a `JsonDeserializer`.
+#[derive(Debug, Clone, Copy, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+#[serde(default)]
+pub struct JsonDeserializerOptions {
+    /// How the non-standard `NaN`, `Infinity` and `-Infinity` literals are decoded, since JSON has
+    /// no representation for floats that aren't finite.
+    ///
+    /// By default, frames holding them are rejected.
+    #[derivative(Default(value = "NonFiniteFloats::Error"))]
+    pub non_finite_floats: NonFiniteFloats,
+}
 
 impl JsonDeserializerConfig {
     /// Build the `JsonDeserializer` from this configuration.
@@ -51,7 +72,9 @@ impl JsonDeserializerConfig {
 
 /// Deserializer that builds `Event`s from a byte frame containing JSON.
 #[derive(Debug, Clone, Default)]
-pub struct JsonDeserializer;
+pub struct JsonDeserializer {
+    options: JsonDeserializerOptions,
+}
 
 impl JsonDeserializer {
     /// Creates a new `JsonDeserializer`.
@@ -68,15 +91,25 @@ impl Deserializer for JsonDeserializer {
             return Ok(smallvec![]);
         }
 
-        let json: serde_json::Value = serde_json::from_slice(&bytes)
+        // Non-finite floats are only replaced in a copy of the frame, and only if it holds any.
+        let bytes = match value::json::replace_non_finite_literals(
+            &bytes,
+            self.options.non_finite_floats,
+        ) {
+            Cow::Owned(replaced) => Bytes::from(replaced),
+            Cow::Borrowed(_) => bytes.clone(),
+        };
+
+        // Strings of the document are slices of the frame, rather than copies of it.
+        let value = value::json::from_bytes(&bytes)
             .map_err(|error| format!("Error parsing JSON: {:?}", error))?;
 
-        let mut events = match json {
-            serde_json::Value::Array(values) => values
+        let mut events = match value {
+            Value::Array(values) => values
                 .into_iter()
-                .map(TryInto::try_into)
+                .map(to_event)
                 .collect::<Result<SmallVec<[Event; 1]>, _>>()?,
-            _ => smallvec![json.try_into()?],
+            value => smallvec![to_event(value)?],
         };
 
         let timestamp = Utc::now();
@@ -94,9 +127,18 @@ impl Deserializer for JsonDeserializer {
     }
 }
 
+fn to_event(value: Value) -> vector_core::Result<Event> {
+    match value {
+        Value::Object(fields) => Ok(Event::from(fields)),
+        _ => Err("Attempted to convert non-Object JSON into an Event.".into()),
+    }
+}
+
 impl From<&JsonDeserializerConfig> for JsonDeserializer {
-    fn from(_: &JsonDeserializerConfig) -> Self {
-        Self
+    fn from(config: &JsonDeserializerConfig) -> Self {
+        Self {
+            options: config.json,
+        }
     }
 }
 
@@ -149,6 +191,16 @@ mod tests {
         assert_eq!(events.next(), None);
     }
 
+    #[test]
+    fn deserialize_json_shares_frame() {
+        let input = Bytes::from(r#"{ "message": "hello" }"#);
+        let deserializer = JsonDeserializer::new();
+
+        let events = deserializer.parse(input.clone()).unwrap();
+        let message = events[0].as_log()["message"].as_bytes().unwrap();
+        assert!(input.as_ptr_range().contains(&message.as_ptr()));
+    }
+
     #[test]
     fn deserialize_skip_empty() {
         let input = Bytes::from("");
@@ -160,6 +212,33 @@ mod tests {
         assert_eq!(events.next(), None);
     }
 
+    #[test]
+    fn deserialize_non_finite_floats() {
+        let input = Bytes::from(r#"{ "nan": NaN, "bounds": [-Infinity, Infinity] }"#);
+        let deserializer = |non_finite_floats| {
+            JsonDeserializerConfig {
+                json: JsonDeserializerOptions { non_finite_floats },
+            }
+            .build()
+        };
+
+        assert!(deserializer(NonFiniteFloats::Error)
+            .parse(input.clone())
+            .is_err());
+
+        let events = deserializer(NonFiniteFloats::Null)
+            .parse(input.clone())
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["nan"], Value::Null);
+        assert_eq!(log["bounds"], Value::from(vec![Value::Null, Value::Null]));
+
+        let events = deserializer(NonFiniteFloats::String).parse(input).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["nan"], "NaN".into());
+        assert_eq!(log["bounds"], Value::from(vec!["-Infinity", "Infinity"]));
+    }
+
     #[test]
     fn deserialize_error_invalid_json() {
         let input = Bytes::from("{ foo");
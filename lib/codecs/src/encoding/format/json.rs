@@ -1,21 +1,49 @@
 use bytes::{BufMut, BytesMut};
+use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::Encoder;
+use value::json::{NonFiniteFloats, WithNonFiniteFloats};
 use vector_core::{config::DataType, event::Event, schema};
 
 /// Config used to build a `JsonSerializer`.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct JsonSerializerConfig;
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct JsonSerializerConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the JSON serializer.
+    pub json: JsonSerializerOptions,
+}
+
+/// Options for building a `JsonSerializer`.
+#[derive(Debug, Clone, Copy, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+#[serde(default)]
+pub struct JsonSerializerOptions {
+    /// How floats that aren't finite are encoded in log and trace events, since JSON has no
+    /// representation for them.
+    ///
+    /// By default, they are encoded as `null`.
+    #[derivative(Default(value = "NonFiniteFloats::Null"))]
+    pub non_finite_floats: NonFiniteFloats,
+}
 
 impl JsonSerializerConfig {
     /// Creates a new `JsonSerializerConfig`.
     pub const fn new() -> Self {
-        Self
+        Self {
+            json: JsonSerializerOptions {
+                non_finite_floats: NonFiniteFloats::Null,
+            },
+        }
     }
 
     /// Build the `JsonSerializer` from this configuration.
     pub const fn build(&self) -> JsonSerializer {
-        JsonSerializer
+        JsonSerializer {
+            non_finite_floats: self.json.non_finite_floats,
+        }
     }
 
     /// The data type of events that are accepted by `JsonSerializer`.
@@ -33,12 +61,14 @@ impl JsonSerializerConfig {
 
 /// Serializer that converts an `Event` to bytes using the JSON format.
 #[derive(Debug, Clone)]
-pub struct JsonSerializer;
+pub struct JsonSerializer {
+    non_finite_floats: NonFiniteFloats,
+}
 
 impl JsonSerializer {
     /// Creates a new `JsonSerializer`.
     pub const fn new() -> Self {
-        Self
+        JsonSerializerConfig::new().build()
     }
 }
 
@@ -48,9 +78,15 @@ impl Encoder<Event> for JsonSerializer {
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         let writer = buffer.writer();
         match event {
-            Event::Log(log) => serde_json::to_writer(writer, &log),
+            Event::Log(log) => serde_json::to_writer(
+                writer,
+                &WithNonFiniteFloats(log.value(), self.non_finite_floats),
+            ),
             Event::Metric(metric) => serde_json::to_writer(writer, &metric),
-            Event::Trace(trace) => serde_json::to_writer(writer, &trace),
+            Event::Trace(trace) => serde_json::to_writer(
+                writer,
+                &WithNonFiniteFloats(trace.value(), self.non_finite_floats),
+            ),
         }
         .map_err(Into::into)
     }
@@ -76,4 +112,32 @@ mod tests {
 
         assert_eq!(bytes.freeze(), r#"{"foo":"bar"}"#);
     }
+
+    #[test]
+    fn serialize_non_finite_floats() {
+        let event = Event::from(btreemap! {
+            "rate" => Value::from(f64::INFINITY)
+        });
+        let encode = |non_finite_floats| {
+            let mut serializer = JsonSerializerConfig {
+                json: JsonSerializerOptions { non_finite_floats },
+            }
+            .build();
+            let mut bytes = BytesMut::new();
+            serializer
+                .encode(event.clone(), &mut bytes)
+                .map(|_| bytes.freeze())
+                .map_err(|error| error.to_string())
+        };
+
+        assert_eq!(encode(NonFiniteFloats::Null).unwrap(), r#"{"rate":null}"#);
+        assert_eq!(
+            encode(NonFiniteFloats::String).unwrap(),
+            r#"{"rate":"Infinity"}"#
+        );
+        assert_eq!(
+            encode(NonFiniteFloats::Error).unwrap_err(),
+            "unable to encode non-finite float Infinity as JSON"
+        );
+    }
 }
@@ -8,9 +8,10 @@ use std::fmt::Debug;
 
 use bytes::BytesMut;
 pub use format::{
-    JsonSerializer, JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig,
-    NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
-    RawMessageSerializer, RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    JsonSerializer, JsonSerializerConfig, JsonSerializerOptions, LogfmtSerializer,
+    LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
+    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
+    TextSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
@@ -182,7 +183,14 @@ impl tokio_util::codec::Encoder<()> for Framer {
 #[serde(tag = "codec", rename_all = "snake_case")]
 pub enum SerializerConfig {
     /// Configures the `JsonSerializer`.
-    Json,
+    Json {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// Options for the JSON serializer.
+        json: JsonSerializerOptions,
+    },
     /// Configures the `LogfmtSerializer`.
     Logfmt,
     /// Configures the `NativeSerializer`.
@@ -196,8 +204,8 @@ pub enum SerializerConfig {
 }
 
 impl From<JsonSerializerConfig> for SerializerConfig {
-    fn from(_: JsonSerializerConfig) -> Self {
-        Self::Json
+    fn from(config: JsonSerializerConfig) -> Self {
+        Self::Json { json: config.json }
     }
 }
 
@@ -235,7 +243,9 @@ impl SerializerConfig {
     /// Build the `Serializer` from this configuration.
     pub const fn build(&self) -> Serializer {
         match self {
-            SerializerConfig::Json => Serializer::Json(JsonSerializerConfig.build()),
+            SerializerConfig::Json { json } => {
+                Serializer::Json(JsonSerializerConfig { json: *json }.build())
+            }
             SerializerConfig::Logfmt => Serializer::Logfmt(LogfmtSerializerConfig.build()),
             SerializerConfig::Native => Serializer::Native(NativeSerializerConfig.build()),
             SerializerConfig::NativeJson => {
@@ -251,7 +261,7 @@ impl SerializerConfig {
     /// The data type of events that are accepted by this `Serializer`.
     pub fn input_type(&self) -> DataType {
         match self {
-            SerializerConfig::Json => JsonSerializerConfig.input_type(),
+            SerializerConfig::Json { .. } => JsonSerializerConfig::new().input_type(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
@@ -263,7 +273,7 @@ impl SerializerConfig {
     /// The schema required by the serializer.
     pub fn schema_requirement(&self) -> schema::Requirement {
         match self {
-            SerializerConfig::Json => JsonSerializerConfig.schema_requirement(),
+            SerializerConfig::Json { .. } => JsonSerializerConfig::new().schema_requirement(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
@@ -0,0 +1,82 @@
+//! Compares a pass over many files reading them ahead through io_uring with reading them one by
+//! one, as the file server does for the files it watches.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+};
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use file_source::uring::{ReadAhead, UringReader};
+use tempfile::TempDir;
+
+const LINE: &[u8] = b"2022-06-21T12:00:00.000Z INFO request handled method=GET status=200\n";
+
+fn open_files(count: usize) -> (TempDir, Vec<File>, Vec<File>) {
+    let dir = tempfile::tempdir().unwrap();
+    let (writers, readers) = (0..count)
+        .map(|i| {
+            let path = dir.path().join(format!("{}.log", i));
+            let writer = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .unwrap();
+            (writer, File::open(&path).unwrap())
+        })
+        .unzip();
+    (dir, writers, readers)
+}
+
+fn append_line(writers: &mut [File]) {
+    for writer in writers {
+        writer.write_all(LINE).unwrap();
+    }
+}
+
+fn read_lines<R: BufRead>(readers: &mut [R]) {
+    let mut line = Vec::new();
+    for reader in readers {
+        while reader.read_until(b'\n', &mut line).unwrap() > 0 {
+            line.clear();
+        }
+    }
+}
+
+fn read_pass(c: &mut Criterion) {
+    let mut group = c.benchmark_group("file-source-read-pass");
+
+    for count in [16, 128, 512] {
+        group.throughput(Throughput::Elements(count as u64));
+
+        let (_dir, mut writers, readers) = open_files(count);
+        let mut readers = readers.into_iter().map(BufReader::new).collect::<Vec<_>>();
+        group.bench_with_input(BenchmarkId::new("buffered", count), &count, |b, _| {
+            b.iter_batched(
+                || append_line(&mut writers),
+                |_| read_lines(&mut readers),
+                BatchSize::SmallInput,
+            )
+        });
+
+        let (_dir, mut writers, readers) = open_files(count);
+        let mut readers = readers
+            .into_iter()
+            .map(|file| UringReader::new(file, 0))
+            .collect::<Vec<_>>();
+        let mut ahead = ReadAhead::new().expect("io_uring must be available");
+        group.bench_with_input(BenchmarkId::new("io_uring", count), &count, |b, _| {
+            b.iter_batched(
+                || append_line(&mut writers),
+                |_| {
+                    ahead.fill(readers.iter_mut()).unwrap();
+                    read_lines(&mut readers);
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+criterion_group!(benches, read_pass);
+criterion_main!(benches);
@@ -15,8 +15,10 @@ use futures::{
 };
 use indexmap::IndexMap;
 use tokio::time::sleep;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
+#[cfg(all(target_os = "linux", feature = "io_uring"))]
+use crate::uring::ReadAhead;
 use crate::{
     checkpointer::{Checkpointer, CheckpointsView},
     file_watcher::FileWatcher,
@@ -52,6 +54,9 @@ where
     pub remove_after: Option<Duration>,
     pub emitter: E,
     pub handle: tokio::runtime::Handle,
+    /// Read ahead uncompressed files through io_uring, with a single submission for all the files
+    /// on each pass. Only supported on Linux, when built with the `io_uring` feature.
+    pub io_uring: bool,
 }
 
 /// `FileServer` as Source
@@ -97,6 +102,24 @@ where
         let mut backoff_cap: usize = 1;
         let mut lines = Vec::new();
 
+        #[cfg(all(target_os = "linux", feature = "io_uring"))]
+        let mut read_ahead = if self.io_uring {
+            ReadAhead::new()
+                .map_err(|error| {
+                    warn!(
+                        message = "Could not set up io_uring, reading files without reading ahead.",
+                        %error,
+                    );
+                })
+                .ok()
+        } else {
+            None
+        };
+        #[cfg(not(all(target_os = "linux", feature = "io_uring")))]
+        if self.io_uring {
+            warn!("io_uring is only supported on Linux builds with the `io_uring` feature, reading files without it.");
+        }
+
         checkpointer.read_checkpoints(self.ignore_before);
 
         let mut known_small_files = HashSet::new();
@@ -229,6 +252,23 @@ where
                 stats.record("discovery", start.elapsed());
             }
 
+            // Read ahead all the files that are due to be read at once.
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            if let Some(ahead) = read_ahead.as_mut() {
+                let start = time::Instant::now();
+                let readers = fp_map
+                    .values_mut()
+                    .filter_map(FileWatcher::read_ahead_reader);
+                if let Err(error) = ahead.fill(readers) {
+                    warn!(
+                        message = "Could not read ahead through io_uring, reading files without reading ahead.",
+                        %error,
+                    );
+                    read_ahead = None;
+                }
+                stats.record("reading", start.elapsed());
+            }
+
             // Collect lines by polling files.
             let mut global_bytes_read: usize = 0;
             let mut maxed_out_reading_single_file = false;
@@ -396,6 +436,7 @@ where
             self.ignore_before,
             self.max_line_bytes,
             self.line_delimiter.clone(),
+            self.io_uring,
         ) {
             Ok(mut watcher) => {
                 if let ReadFrom::Checkpoint(file_position) = read_from {
@@ -1,6 +1,6 @@
 use std::{
     fs::{self, File},
-    io::{self, BufRead, Seek},
+    io::{self, BufRead, Read, Seek},
     path::PathBuf,
     time::{Duration, Instant},
 };
@@ -10,6 +10,8 @@ use chrono::{DateTime, Utc};
 use flate2::bufread::MultiGzDecoder;
 use tracing::debug;
 
+#[cfg(all(target_os = "linux", feature = "io_uring"))]
+use crate::uring::UringReader;
 use crate::{
     buffer::read_until_with_max_size, metadata_ext::PortableFileExt, FilePosition, ReadFrom,
 };
@@ -26,7 +28,7 @@ mod tests;
 pub struct FileWatcher {
     pub path: PathBuf,
     findable: bool,
-    reader: Box<dyn BufRead>,
+    reader: FileReader,
     file_position: FilePosition,
     devno: u64,
     inode: u64,
@@ -36,6 +38,7 @@ pub struct FileWatcher {
     max_line_bytes: usize,
     line_delimiter: Bytes,
     buf: BytesMut,
+    io_uring: bool,
 }
 
 impl FileWatcher {
@@ -44,12 +47,16 @@ impl FileWatcher {
     /// The input path will be used by `FileWatcher` to prime its state
     /// machine. A `FileWatcher` tracks _only one_ file. This function returns
     /// None if the path does not exist or is not readable by the current process.
+    ///
+    /// If `io_uring` is true, uncompressed files are read ahead through io_uring
+    /// by the `FileServer`.
     pub fn new(
         path: PathBuf,
         read_from: ReadFrom,
         ignore_before: Option<DateTime<Utc>>,
         max_line_bytes: usize,
         line_delimiter: Bytes,
+        io_uring: bool,
     ) -> Result<FileWatcher, io::Error> {
         let f = fs::File::open(&path)?;
         let (devno, ino) = (f.portable_dev()?, f.portable_ino()?);
@@ -68,14 +75,14 @@ impl FileWatcher {
         let gzipped = is_gzipped(&mut reader)?;
 
         // Determine the actual position at which we should start reading
-        let (reader, file_position): (Box<dyn BufRead>, FilePosition) =
+        let (reader, file_position): (FileReader, FilePosition) =
             match (gzipped, too_old, read_from) {
                 (true, true, _) => {
                     debug!(
                         message = "Not reading gzipped file older than `ignore_older`.",
                         ?path,
                     );
-                    (Box::new(null_reader()), 0)
+                    (FileReader::null(), 0)
                 }
                 (true, _, ReadFrom::Checkpoint(file_position)) => {
                     debug!(
@@ -83,7 +90,7 @@ impl FileWatcher {
                         ?path,
                         %file_position
                     );
-                    (Box::new(null_reader()), file_position)
+                    (FileReader::null(), file_position)
                 }
                 // TODO: This may become the default, leading us to stop reading gzipped files that
                 // we were reading before. Should we merge this and the next branch to read
@@ -94,26 +101,27 @@ impl FileWatcher {
                         message = "Can't read from the end of already-compressed file.",
                         ?path,
                     );
-                    (Box::new(null_reader()), 0)
-                }
-                (true, false, ReadFrom::Beginning) => {
-                    (Box::new(io::BufReader::new(MultiGzDecoder::new(reader))), 0)
+                    (FileReader::null(), 0)
                 }
+                (true, false, ReadFrom::Beginning) => (
+                    FileReader::Buffered(Box::new(io::BufReader::new(MultiGzDecoder::new(reader)))),
+                    0,
+                ),
                 (false, true, _) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
-                    (Box::new(reader), pos)
+                    (FileReader::plain(reader, pos, io_uring), pos)
                 }
                 (false, false, ReadFrom::Checkpoint(file_position)) => {
                     let pos = reader.seek(io::SeekFrom::Start(file_position)).unwrap();
-                    (Box::new(reader), pos)
+                    (FileReader::plain(reader, pos, io_uring), pos)
                 }
                 (false, false, ReadFrom::Beginning) => {
                     let pos = reader.seek(io::SeekFrom::Start(0)).unwrap();
-                    (Box::new(reader), pos)
+                    (FileReader::plain(reader, pos, io_uring), pos)
                 }
                 (false, false, ReadFrom::End) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
-                    (Box::new(reader), pos)
+                    (FileReader::plain(reader, pos, io_uring), pos)
                 }
             };
 
@@ -137,6 +145,7 @@ impl FileWatcher {
             max_line_bytes,
             line_delimiter,
             buf: BytesMut::new(),
+            io_uring,
         })
     }
 
@@ -145,15 +154,15 @@ impl FileWatcher {
         if (file_handle.portable_dev()?, file_handle.portable_ino()?) != (self.devno, self.inode) {
             let mut reader = io::BufReader::new(fs::File::open(&path)?);
             let gzipped = is_gzipped(&mut reader)?;
-            let new_reader: Box<dyn BufRead> = if gzipped {
+            let new_reader = if gzipped {
                 if self.file_position != 0 {
-                    Box::new(null_reader())
+                    FileReader::null()
                 } else {
-                    Box::new(io::BufReader::new(MultiGzDecoder::new(reader)))
+                    FileReader::Buffered(Box::new(io::BufReader::new(MultiGzDecoder::new(reader))))
                 }
             } else {
                 reader.seek(io::SeekFrom::Start(self.file_position))?;
-                Box::new(reader)
+                FileReader::plain(reader, self.file_position, self.io_uring)
             };
             self.reader = new_reader;
             self.devno = file_handle.portable_dev()?;
@@ -250,6 +259,69 @@ impl FileWatcher {
         self.last_read_success.elapsed() < Duration::from_secs(10)
             || self.last_read_attempt.elapsed() > Duration::from_secs(10)
     }
+
+    /// The reader to read ahead through io_uring, if the file is read through
+    /// io_uring and is due to be read.
+    #[cfg(all(target_os = "linux", feature = "io_uring"))]
+    pub(crate) fn read_ahead_reader(&mut self) -> Option<&mut UringReader> {
+        let should_read = self.should_read();
+        match &mut self.reader {
+            FileReader::Uring(reader) if should_read => Some(reader),
+            _ => None,
+        }
+    }
+}
+
+/// The reader of a watched file.
+enum FileReader {
+    Buffered(Box<dyn BufRead>),
+    #[cfg(all(target_os = "linux", feature = "io_uring"))]
+    Uring(UringReader),
+}
+
+impl FileReader {
+    fn null() -> Self {
+        FileReader::Buffered(Box::new(null_reader()))
+    }
+
+    /// The reader of an uncompressed file, already positioned at `position`.
+    fn plain(reader: io::BufReader<fs::File>, position: FilePosition, io_uring: bool) -> Self {
+        #[cfg(all(target_os = "linux", feature = "io_uring"))]
+        if io_uring {
+            return FileReader::Uring(UringReader::new(reader.into_inner(), position));
+        }
+        #[cfg(not(all(target_os = "linux", feature = "io_uring")))]
+        let _ = (position, io_uring);
+        FileReader::Buffered(Box::new(reader))
+    }
+}
+
+impl Read for FileReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            FileReader::Buffered(reader) => reader.read(buf),
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            FileReader::Uring(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl BufRead for FileReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self {
+            FileReader::Buffered(reader) => reader.fill_buf(),
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            FileReader::Uring(reader) => reader.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amount: usize) {
+        match self {
+            FileReader::Buffered(reader) => reader.consume(amount),
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            FileReader::Uring(reader) => reader.consume(amount),
+        }
+    }
 }
 
 fn is_gzipped(r: &mut io::BufReader<fs::File>) -> io::Result<bool> {
@@ -24,6 +24,7 @@ fn experiment_no_truncations(actions: Vec<FileWatcherAction>) {
         None,
         100_000,
         Bytes::from("\n"),
+        false,
     )
     .expect("must be able to create");
 
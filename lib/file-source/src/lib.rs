@@ -11,6 +11,8 @@ mod fingerprinter;
 mod internal_events;
 mod metadata_ext;
 pub mod paths_provider;
+#[cfg(all(target_os = "linux", feature = "io_uring"))]
+pub mod uring;
 
 pub use self::{
     checkpointer::{Checkpointer, CheckpointsView, CHECKPOINT_FILE_NAME},
@@ -0,0 +1,222 @@
+//! Reads of the watched files through io_uring, on Linux.
+//!
+//! Before each pass over the watched files, the file server fills the buffers of all the files with
+//! nothing left buffered in a single submission, rather than with one `read` system call per file.
+//! Files that have more to read than fits in their buffer go on with regular reads for the rest of
+//! the pass.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, Read},
+    mem,
+    os::unix::{fs::FileExt, io::AsRawFd},
+};
+
+use io_uring::{opcode, types, IoUring};
+
+use crate::FilePosition;
+
+/// The number of reads submitted at once.
+const RING_ENTRIES: usize = 256;
+
+/// The size of the buffer of each file, which bounds how much is read ahead.
+const BUFFER_SIZE: usize = 16 * 1024;
+
+/// A reader of an uncompressed file whose buffer is filled through [`ReadAhead`].
+pub struct UringReader {
+    file: File,
+    offset: FilePosition,
+    buf: Box<[u8]>,
+    pos: usize,
+    filled: usize,
+    pending: bool,
+    at_eof: bool,
+    error: Option<io::Error>,
+}
+
+impl UringReader {
+    /// Creates a reader of the file, starting at the given position.
+    pub fn new(file: File, offset: FilePosition) -> Self {
+        Self {
+            file,
+            offset,
+            buf: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+            pending: false,
+            at_eof: false,
+            error: None,
+        }
+    }
+
+    fn is_drained(&self) -> bool {
+        self.pos == self.filled && self.error.is_none()
+    }
+
+    fn complete(&mut self, result: i32) {
+        self.pending = false;
+        if result < 0 {
+            self.error = Some(io::Error::from_raw_os_error(-result));
+        } else {
+            self.pos = 0;
+            self.filled = result as usize;
+            self.offset += result as u64;
+            self.at_eof = result == 0;
+        }
+    }
+
+    /// Gives up the buffer of a read that may still be in flight, so that the kernel can't write
+    /// into memory that is reused.
+    fn abandon_buffer(&mut self) {
+        mem::forget(mem::replace(
+            &mut self.buf,
+            vec![0; BUFFER_SIZE].into_boxed_slice(),
+        ));
+        self.pending = false;
+        self.pos = 0;
+        self.filled = 0;
+    }
+}
+
+impl Read for UringReader {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let amount = available.len().min(out.len());
+        out[..amount].copy_from_slice(&available[..amount]);
+        self.consume(amount);
+        Ok(amount)
+    }
+}
+
+impl BufRead for UringReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        if self.pos == self.filled {
+            // The file was at its end when it was read ahead during this pass, so don't read it
+            // again until the next one.
+            if mem::take(&mut self.at_eof) {
+                return Ok(&[]);
+            }
+            let read = self.file.read_at(&mut self.buf, self.offset)?;
+            self.pos = 0;
+            self.filled = read;
+            self.offset += read as u64;
+        }
+        Ok(&self.buf[self.pos..self.filled])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pos = (self.pos + amount).min(self.filled);
+    }
+}
+
+/// Fills the buffers of many files with a single submission to io_uring.
+pub struct ReadAhead {
+    ring: IoUring,
+}
+
+impl ReadAhead {
+    /// Sets up the ring.
+    ///
+    /// # Errors
+    ///
+    /// If io_uring isn't available, such as on kernels older than 5.6 or when it's forbidden by a
+    /// seccomp profile, an error is returned.
+    pub fn new() -> io::Result<Self> {
+        IoUring::new(RING_ENTRIES as u32).map(|ring| Self { ring })
+    }
+
+    /// Fills the buffers of the readers that have nothing left buffered.
+    ///
+    /// # Errors
+    ///
+    /// If the reads couldn't be submitted, or waited for, an error is returned, after which the
+    /// ring shouldn't be used anymore.
+    pub fn fill<'a>(
+        &mut self,
+        readers: impl IntoIterator<Item = &'a mut UringReader>,
+    ) -> io::Result<()> {
+        let mut batch = Vec::with_capacity(RING_ENTRIES);
+        for reader in readers.into_iter().filter(|reader| reader.is_drained()) {
+            batch.push(reader);
+            if batch.len() == RING_ENTRIES {
+                self.fill_batch(&mut batch)?;
+                batch.clear();
+            }
+        }
+        if !batch.is_empty() {
+            self.fill_batch(&mut batch)?;
+        }
+        Ok(())
+    }
+
+    fn fill_batch(&mut self, batch: &mut [&mut UringReader]) -> io::Result<()> {
+        for (index, reader) in batch.iter_mut().enumerate() {
+            let entry = opcode::Read::new(
+                types::Fd(reader.file.as_raw_fd()),
+                reader.buf.as_mut_ptr(),
+                reader.buf.len() as u32,
+            )
+            .offset(reader.offset as i64)
+            .build()
+            .user_data(index as u64);
+            // SAFETY: The buffers stay borrowed until their reads complete, or are leaked if it
+            // can't be known when they do.
+            unsafe { self.ring.submission().push(&entry) }
+                .expect("submission queue can't be full with at most as many reads as entries");
+            reader.pending = true;
+        }
+
+        let mut completed = 0;
+        while completed < batch.len() {
+            if let Err(error) = self.ring.submit_and_wait(batch.len() - completed) {
+                if error.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                for reader in batch.iter_mut().filter(|reader| reader.pending) {
+                    reader.abandon_buffer();
+                }
+                return Err(error);
+            }
+            for entry in self.ring.completion() {
+                batch[entry.user_data() as usize].complete(entry.result());
+                completed += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn reads_ahead_then_directly() {
+        let mut ahead = match ReadAhead::new() {
+            Ok(ahead) => ahead,
+            // io_uring isn't available in every environment tests run in.
+            Err(_) => return,
+        };
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(b"first\nsecond\n").unwrap();
+        let mut reader = UringReader::new(file.try_clone().unwrap(), 6);
+
+        ahead.fill([&mut reader]).unwrap();
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "second\n");
+
+        // The file was read to its end, until it is read ahead again.
+        ahead.fill([&mut reader]).unwrap();
+        file.write_all(b"third\n").unwrap();
+        assert_eq!(reader.fill_buf().unwrap(), b"");
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "third\n");
+    }
+}
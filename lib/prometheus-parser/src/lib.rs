@@ -29,6 +29,15 @@ pub mod proto {
             }
         }
     }
+
+    /// The protobuf exposition format, scraped by Prometheus as a stream of length-delimited
+    /// `MetricFamily` messages.
+    pub mod client {
+        include!(concat!(env!("OUT_DIR"), "/io.prometheus.client.rs"));
+
+        pub const CONTENT_TYPE: &str =
+            "application/vnd.google.protobuf; proto=io.prometheus.client.MetricFamily; encoding=delimited";
+    }
 }
 
 #[derive(Debug, snafu::Snafu, PartialEq)]
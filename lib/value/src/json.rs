@@ -0,0 +1,426 @@
+//! Parsing of JSON documents, through `serde_json` or, when built with the `simd` feature and
+//! enabled with [`set_simd`], through `simd-json`.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use bytes::Bytes;
+use ordered_float::NotNan;
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    Deserialize, Serialize,
+};
+
+use crate::Value;
+
+static SIMD: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether JSON documents are parsed with `simd-json`. This has no effect unless built with
+/// the `simd` feature.
+pub fn set_simd(enabled: bool) {
+    SIMD.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether JSON documents are parsed with `simd-json`.
+#[must_use]
+pub fn simd_enabled() -> bool {
+    cfg!(feature = "simd") && SIMD.load(Ordering::Relaxed)
+}
+
+/// Parses a JSON document.
+///
+/// When parsing with `simd-json`, documents that it rejects are parsed again with `serde_json`,
+/// which reports more helpful errors, and accepts the few documents `simd-json` doesn't support.
+///
+/// # Errors
+///
+/// Returns an error if the bytes aren't a valid JSON document of the given type.
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, serde_json::Error> {
+    parse(bytes, simd_enabled())
+}
+
+#[cfg_attr(not(feature = "simd"), allow(unused_variables))]
+fn parse<T: DeserializeOwned>(bytes: &[u8], simd: bool) -> Result<T, serde_json::Error> {
+    #[cfg(feature = "simd")]
+    if simd {
+        // `simd-json` parses in place.
+        let mut buffer = bytes.to_vec();
+        if let Ok(value) = simd_json::serde::from_slice(&mut buffer) {
+            return Ok(value);
+        }
+    }
+    serde_json::from_slice(bytes)
+}
+
+/// Parses a JSON document into a [`Value`], whose strings are slices of `bytes` rather than copies
+/// of them, wherever the document holds them verbatim, that is without escape sequences. Values
+/// parsed from a frame thus share its buffer, which they keep alive until they are all dropped.
+///
+/// Numbers are converted as from a [`serde_json::Value`]. When parsing with `simd-json`, strings
+/// are copied.
+///
+/// # Errors
+///
+/// Returns an error if the bytes aren't a valid JSON document.
+pub fn from_bytes(bytes: &Bytes) -> Result<Value, serde_json::Error> {
+    if simd_enabled() {
+        return from_slice::<serde_json::Value>(bytes).map(Value::from);
+    }
+    parse_shared(bytes)
+}
+
+fn parse_shared(bytes: &Bytes) -> Result<Value, serde_json::Error> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = SharedValue(bytes).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Deserializes a [`Value`] whose borrowed strings are sliced out of the buffer being parsed.
+#[derive(Clone, Copy)]
+struct SharedValue<'a>(&'a Bytes);
+
+impl<'de> DeserializeSeed<'de> for SharedValue<'_> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SharedValue<'_> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Boolean(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::Integer(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+        // Like `serde_json::Value`, which keeps integers too large for an `i64` as they are.
+        Ok(i64::try_from(value).map_or_else(|_| value.to_string().into(), Value::Integer))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Value, E> {
+        NotNan::new(value)
+            .map(Value::Float)
+            .map_err(|_| E::invalid_value(de::Unexpected::Float(value), &self))
+    }
+
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Value, E> {
+        Ok(Value::Bytes(self.0.slice_ref(value.as_bytes())))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::Bytes(Bytes::copy_from_slice(value.as_bytes())))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Value, E> {
+        Ok(Value::Bytes(value.into()))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element_seed(self)? {
+            values.push(value);
+        }
+        Ok(Value::Array(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut fields = BTreeMap::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(self)?;
+            fields.insert(key, value);
+        }
+        Ok(Value::Object(fields))
+    }
+}
+
+/// How floats that aren't finite are handled, since JSON has no representation for them.
+///
+/// Values never hold `NaN`, but can hold either infinity. Documents can hold the non-standard
+/// `NaN`, `Infinity` and `-Infinity` literals that some encoders write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonFiniteFloats {
+    /// Non-finite floats are rejected with an error.
+    Error,
+
+    /// Non-finite floats are replaced with `null`.
+    Null,
+
+    /// Non-finite floats are replaced with the strings `"NaN"`, `"Infinity"` and `"-Infinity"`.
+    String,
+}
+
+impl NonFiniteFloats {
+    /// All the ways non-finite floats can be handled.
+    pub const ALL: [Self; 3] = [Self::Error, Self::Null, Self::String];
+
+    /// The name of this way of handling non-finite floats.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Null => "null",
+            Self::String => "string",
+        }
+    }
+}
+
+impl FromStr for NonFiniteFloats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|non_finite_floats| non_finite_floats.as_str() == s)
+            .ok_or_else(|| format!("unknown handling of non-finite floats: {}", s))
+    }
+}
+
+impl fmt::Display for NonFiniteFloats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn non_finite_literal(value: f64) -> &'static str {
+    if value.is_nan() {
+        "NaN"
+    } else if value.is_sign_negative() {
+        "-Infinity"
+    } else {
+        "Infinity"
+    }
+}
+
+/// Serializes a [`Value`], handling the non-finite floats within it as configured.
+///
+/// Serializing a [`Value`] itself writes non-finite floats as `null`, as `serde_json` does for any
+/// float.
+#[derive(Clone, Copy, Debug)]
+pub struct WithNonFiniteFloats<'a>(pub &'a Value, pub NonFiniteFloats);
+
+impl Serialize for WithNonFiniteFloats<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Self(value, non_finite_floats) = *self;
+
+        match value {
+            Value::Float(float) if !float.is_finite() => {
+                let literal = non_finite_literal(float.into_inner());
+                match non_finite_floats {
+                    NonFiniteFloats::Error => Err(serde::ser::Error::custom(format!(
+                        "unable to encode non-finite float {} as JSON",
+                        literal
+                    ))),
+                    NonFiniteFloats::Null => serializer.serialize_none(),
+                    NonFiniteFloats::String => serializer.serialize_str(literal),
+                }
+            }
+            Value::Array(values) => {
+                serializer.collect_seq(values.iter().map(|value| Self(value, non_finite_floats)))
+            }
+            Value::Object(fields) => serializer.collect_map(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key, Self(value, non_finite_floats))),
+            ),
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+/// Replaces the non-standard `NaN`, `Infinity` and `-Infinity` literals within a JSON document,
+/// ahead of parsing it. The document is only copied if it holds any of them.
+///
+/// Without this, or when non-finite floats are rejected, parsing fails on these literals.
+#[must_use]
+pub fn replace_non_finite_literals(
+    bytes: &[u8],
+    non_finite_floats: NonFiniteFloats,
+) -> Cow<'_, [u8]> {
+    if non_finite_floats == NonFiniteFloats::Error {
+        return Cow::Borrowed(bytes);
+    }
+
+    let mut replaced: Option<Vec<u8>> = None;
+    let mut copied = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+
+        let (start, literal) = match byte {
+            b'"' => {
+                in_string = true;
+                i += 1;
+                continue;
+            }
+            b'N' if bytes[i..].starts_with(b"NaN") => (i, "NaN"),
+            b'-' if bytes[i..].starts_with(b"-Infinity") => (i, "-Infinity"),
+            b'I' if bytes[i..].starts_with(b"Infinity") => (i, "Infinity"),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        let output = replaced.get_or_insert_with(|| Vec::with_capacity(bytes.len()));
+        output.extend_from_slice(&bytes[copied..start]);
+        match non_finite_floats {
+            NonFiniteFloats::String => {
+                output.push(b'"');
+                output.extend_from_slice(literal.as_bytes());
+                output.push(b'"');
+            }
+            _ => output.extend_from_slice(b"null"),
+        }
+        i = start + literal.len();
+        copied = i;
+    }
+
+    replaced.map_or(Cow::Borrowed(bytes), |mut output| {
+        output.extend_from_slice(&bytes[copied..]);
+        Cow::Owned(output)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_both(json: &str) -> (Result<Value, String>, Result<Value, String>) {
+        let parse = |simd| parse::<Value>(json.as_bytes(), simd).map_err(|error| error.to_string());
+        (parse(false), parse(true))
+    }
+
+    #[test]
+    fn parses_the_same_with_simd() {
+        for json in [
+            r#"{"message": "hello", "count": 3, "ratio": 0.5, "tags": ["a", "b"], "nested": {"null": null, "bool": true}}"#,
+            r#"[1, -2, 3.25e2, "é\n"]"#,
+            r#""just a string""#,
+        ] {
+            let (serde, simd) = parse_both(json);
+            assert!(serde.is_ok(), "{:?}", serde);
+            assert_eq!(serde, simd);
+        }
+    }
+
+    #[test]
+    fn slices_verbatim_strings_out_of_the_buffer() {
+        let bytes =
+            Bytes::from(r#"{"message": "hello", "escaped": "a\"b", "big": 18446744073709551615}"#);
+        let value = parse_shared(&bytes).unwrap();
+        let json = serde_json::from_slice::<serde_json::Value>(&bytes).unwrap();
+        assert_eq!(value, Value::from(json));
+
+        let buffer = bytes.as_ptr_range();
+        let in_buffer = |field: &str| match &value.as_object().unwrap()[field] {
+            Value::Bytes(bytes) => buffer.contains(&bytes.as_ptr()),
+            value => panic!("{:?} isn't a string", value),
+        };
+        assert!(in_buffer("message"));
+        assert!(!in_buffer("escaped"));
+    }
+
+    #[test]
+    fn replaces_non_finite_literals() {
+        let json = br#"{"nan": NaN, "inf": [Infinity, -Infinity], "text": "NaN \" Infinity"}"#;
+
+        let null = replace_non_finite_literals(json, NonFiniteFloats::Null);
+        assert_eq!(
+            std::str::from_utf8(&null).unwrap(),
+            r#"{"nan": null, "inf": [null, null], "text": "NaN \" Infinity"}"#
+        );
+
+        let string = replace_non_finite_literals(json, NonFiniteFloats::String);
+        assert_eq!(
+            std::str::from_utf8(&string).unwrap(),
+            r#"{"nan": "NaN", "inf": ["Infinity", "-Infinity"], "text": "NaN \" Infinity"}"#
+        );
+
+        let error = replace_non_finite_literals(json, NonFiniteFloats::Error);
+        assert!(matches!(error, Cow::Borrowed(_)));
+        assert!(from_slice::<Value>(&error).is_err());
+    }
+
+    #[test]
+    fn borrows_documents_without_non_finite_literals() {
+        let json = br#"{"message": "NaN", "ratio": 1.5}"#;
+        let replaced = replace_non_finite_literals(json, NonFiniteFloats::String);
+        assert!(matches!(replaced, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn serializes_non_finite_floats() {
+        let value = Value::from(BTreeMap::from([
+            ("ratio".to_owned(), Value::from(1.5)),
+            (
+                "rates".to_owned(),
+                Value::Array(vec![
+                    Value::from(f64::INFINITY),
+                    Value::from(f64::NEG_INFINITY),
+                ]),
+            ),
+        ]));
+        let serialize = |non_finite_floats| {
+            serde_json::to_string(&WithNonFiniteFloats(&value, non_finite_floats))
+                .map_err(|error| error.to_string())
+        };
+
+        assert_eq!(
+            serialize(NonFiniteFloats::Null).unwrap(),
+            serde_json::to_string(&value).unwrap()
+        );
+        assert_eq!(
+            serialize(NonFiniteFloats::String).unwrap(),
+            r#"{"rates":["Infinity","-Infinity"],"ratio":1.5}"#
+        );
+        assert_eq!(
+            serialize(NonFiniteFloats::Error).unwrap_err(),
+            "unable to encode non-finite float Infinity as JSON"
+        );
+    }
+
+    #[test]
+    fn reports_serde_json_errors_with_simd() {
+        let (serde, simd) = parse_both(r#"{"message": "#);
+        assert!(serde.is_err());
+        assert_eq!(serde, simd);
+    }
+}
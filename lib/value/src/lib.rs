@@ -35,6 +35,8 @@
     unused_variables
 )]
 
+#[cfg(feature = "json")]
+pub mod json;
 pub mod kind;
 pub mod value;
 
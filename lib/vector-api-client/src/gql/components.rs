@@ -14,6 +14,15 @@ use crate::{BoxedSubscription, QueryResult};
 )]
 pub struct ComponentsQuery;
 
+/// Components query for returning the events held in the buffers of sinks
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/queries/component_buffer_events.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentBufferEventsQuery;
+
 /// Components subscription for notification when a component has been added
 #[derive(GraphQLQuery, Debug, Copy, Clone)]
 #[graphql(
@@ -35,6 +44,10 @@ pub struct ComponentRemovedSubscription;
 #[async_trait]
 pub trait ComponentsQueryExt {
     async fn components_query(&self, first: i64) -> crate::QueryResult<ComponentsQuery>;
+    async fn component_buffer_events_query(
+        &self,
+        first: i64,
+    ) -> crate::QueryResult<ComponentBufferEventsQuery>;
 }
 
 #[async_trait]
@@ -43,6 +56,18 @@ impl ComponentsQueryExt for crate::Client {
         let request_body = ComponentsQuery::build_query(components_query::Variables { first });
         self.query::<ComponentsQuery>(&request_body).await
     }
+
+    async fn component_buffer_events_query(
+        &self,
+        first: i64,
+    ) -> QueryResult<ComponentBufferEventsQuery> {
+        let request_body =
+            ComponentBufferEventsQuery::build_query(component_buffer_events_query::Variables {
+                first,
+            });
+        self.query::<ComponentBufferEventsQuery>(&request_body)
+            .await
+    }
 }
 
 pub trait ComponentsSubscriptionExt {
@@ -195,6 +220,24 @@ impl components_query::ComponentsQueryComponentsEdgesNodeOn {
     }
 }
 
+impl component_buffer_events_query::ComponentBufferEventsQueryComponentsEdgesNodeOn {
+    /// Events held in the buffer of a sink, or `None` for sources and transforms
+    pub fn buffer_events(&self) -> Option<i64> {
+        match self {
+            component_buffer_events_query::ComponentBufferEventsQueryComponentsEdgesNodeOn::Sink(
+                s,
+            ) => Some(
+                s.metrics
+                    .buffer_events
+                    .as_ref()
+                    .map(|b| b.buffer_events as i64)
+                    .unwrap_or(0),
+            ),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for components_query::ComponentsQueryComponentsEdgesNodeOn {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let res = match self {
@@ -53,6 +53,19 @@ impl From<TapEncodingFormat>
     }
 }
 
+/// Options applied to tapped events by the Vector instance, before they're sent to the client.
+#[derive(Debug, Clone, Default)]
+pub struct TapFilter {
+    /// A VRL predicate events must match.
+    pub predicate: Option<String>,
+
+    /// The fields logs are projected to.
+    pub fields: Vec<String>,
+
+    /// The most events tapped per second.
+    pub max_events_per_sec: Option<i64>,
+}
+
 pub trait TapSubscriptionExt {
     /// Executes an output events subscription.
     fn output_events_by_component_id_patterns_subscription(
@@ -62,6 +75,7 @@ pub trait TapSubscriptionExt {
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        filter: TapFilter,
     ) -> crate::BoxedSubscription<OutputEventsByComponentIdPatternsSubscription>;
 }
 
@@ -74,6 +88,7 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        filter: TapFilter,
     ) -> BoxedSubscription<OutputEventsByComponentIdPatternsSubscription> {
         let request_body = OutputEventsByComponentIdPatternsSubscription::build_query(
             output_events_by_component_id_patterns_subscription::Variables {
@@ -82,6 +97,9 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
                 limit,
                 interval,
                 encoding: encoding.into(),
+                filter: filter.predicate,
+                fields: (!filter.fields.is_empty()).then(|| filter.fields),
+                max_events_per_sec: filter.max_events_per_sec,
             },
         );
 
@@ -10,7 +10,7 @@ use criterion::{
     Criterion, SamplingMode, Throughput,
 };
 use tokio::runtime::{Handle, Runtime};
-use vector_buffers::{BufferType, WhenFull};
+use vector_buffers::{BufferType, DiskBufferCompression, WhenFull};
 
 use crate::common::{init_instrumentation, war_measurement, wtr_measurement};
 
@@ -83,6 +83,18 @@ fn create_disk_v2_variant(_max_events: usize, max_size: u64) -> BufferType {
     BufferType::DiskV2 {
         max_size: NonZeroU64::new(max_size).unwrap(),
         when_full: WhenFull::DropNewest,
+        compression: DiskBufferCompression::default(),
+        io_uring: false,
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "io_uring"))]
+fn create_disk_v2_io_uring_variant(_max_events: usize, max_size: u64) -> BufferType {
+    BufferType::DiskV2 {
+        max_size: NonZeroU64::new(max_size).unwrap(),
+        when_full: WhenFull::DropNewest,
+        compression: DiskBufferCompression::default(),
+        io_uring: true,
     }
 }
 
@@ -162,6 +174,16 @@ fn write_then_read(c: &mut Criterion) {
         create_disk_v2_variant
     );
 
+    #[cfg(all(target_os = "linux", feature = "io_uring"))]
+    experiment!(
+        c,
+        [32, 64, 128, 256, 512, 1024],
+        "buffer-disk-v2-io-uring",
+        "write-then-read",
+        wtr_measurement,
+        create_disk_v2_io_uring_variant
+    );
+
     experiment!(
         c,
         [32, 64, 128, 256, 512, 1024],
@@ -192,6 +214,16 @@ fn write_and_read(c: &mut Criterion) {
         create_disk_v2_variant
     );
 
+    #[cfg(all(target_os = "linux", feature = "io_uring"))]
+    experiment!(
+        c,
+        [32, 64, 128, 256, 512, 1024],
+        "buffer-disk-v2-io-uring",
+        "write-and-read",
+        war_measurement,
+        create_disk_v2_io_uring_variant
+    );
+
     experiment!(
         c,
         [32, 64, 128, 256, 512, 1024],
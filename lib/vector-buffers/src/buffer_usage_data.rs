@@ -11,7 +11,10 @@ use tracing::{Instrument, Span};
 use vector_common::internal_event::emit;
 
 use crate::{
-    internal_events::{BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent},
+    internal_events::{
+        BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent,
+        BufferRecordsCompressed,
+    },
     spawn_named, WhenFull,
 };
 
@@ -78,6 +81,23 @@ impl BufferUsageHandle {
             .fetch_add(byte_size, Ordering::Relaxed);
     }
 
+    /// Increments the logical (uncompressed) and compressed size of records written by this buffer
+    /// component.
+    ///
+    /// This is only used by buffer components which compress records before storing them.
+    pub fn increment_compressed_byte_sizes(
+        &self,
+        logical_byte_size: u64,
+        compressed_byte_size: u64,
+    ) {
+        self.state
+            .logical_byte_size
+            .fetch_add(logical_byte_size, Ordering::Relaxed);
+        self.state
+            .compressed_byte_size
+            .fetch_add(compressed_byte_size, Ordering::Relaxed);
+    }
+
     /// Attempts to increment the number of dropped events (and their total size) for this buffer component.
     ///
     /// If the component itself is not configured to drop events, this call does nothing.
@@ -99,6 +119,8 @@ pub struct BufferUsageData {
     sent_event_count: AtomicU64,
     sent_byte_size: AtomicU64,
     dropped_event_data: Option<BufferUsageDroppedEventData>,
+    logical_byte_size: AtomicU64,
+    compressed_byte_size: AtomicU64,
     max_size_bytes: AtomicU64,
     max_size_events: AtomicUsize,
 }
@@ -113,7 +135,9 @@ impl BufferUsageData {
     pub fn new(mode: WhenFull, idx: usize) -> Self {
         let dropped_event_data = match mode {
             WhenFull::Block | WhenFull::Overflow => None,
-            WhenFull::DropNewest => Some(BufferUsageDroppedEventData::default()),
+            WhenFull::DropNewest | WhenFull::DropOldest => {
+                Some(BufferUsageDroppedEventData::default())
+            }
         };
 
         Self {
@@ -123,6 +147,8 @@ impl BufferUsageData {
             sent_event_count: AtomicU64::new(0),
             sent_byte_size: AtomicU64::new(0),
             dropped_event_data,
+            logical_byte_size: AtomicU64::new(0),
+            compressed_byte_size: AtomicU64::new(0),
             max_size_bytes: AtomicU64::new(0),
             max_size_events: AtomicUsize::new(0),
         }
@@ -142,6 +168,8 @@ impl BufferUsageData {
                 .dropped_event_data
                 .as_ref()
                 .map(|inner| inner.size.load(Ordering::Relaxed)),
+            logical_byte_size: self.logical_byte_size.load(Ordering::Relaxed),
+            compressed_byte_size: self.compressed_byte_size.load(Ordering::Relaxed),
             max_size_bytes: self.max_size_bytes.load(Ordering::Relaxed),
             max_size_events: self.max_size_events.load(Ordering::Relaxed),
         }
@@ -156,6 +184,8 @@ pub struct BufferUsageSnapshot {
     pub sent_byte_size: u64,
     pub dropped_event_count: Option<u64>,
     pub dropped_event_size: Option<u64>,
+    pub logical_byte_size: u64,
+    pub compressed_byte_size: u64,
     pub max_size_bytes: u64,
     pub max_size_events: usize,
 }
@@ -236,6 +266,19 @@ impl BufferUsage {
                             byte_size: dropped_event_data.size.swap(0, Ordering::Relaxed),
                         });
                     }
+
+                    // Only buffers that compress records ever track these, so we avoid emitting
+                    // empty compression metrics for every other buffer.
+                    let logical_byte_size = stage.logical_byte_size.swap(0, Ordering::Relaxed);
+                    let compressed_byte_size =
+                        stage.compressed_byte_size.swap(0, Ordering::Relaxed);
+                    if logical_byte_size > 0 {
+                        emit(BufferRecordsCompressed {
+                            idx: stage.idx,
+                            logical_byte_size,
+                            compressed_byte_size,
+                        });
+                    }
                 }
             }
         };
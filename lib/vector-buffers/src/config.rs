@@ -13,7 +13,7 @@ use crate::{
         builder::{TopologyBuilder, TopologyError},
         channel::{BufferReceiver, BufferSender},
     },
-    variants::{DiskV1Buffer, DiskV2Buffer, MemoryBuffer},
+    variants::{DiskBufferCompression, DiskV1Buffer, DiskV2Buffer, MemoryBuffer},
     Acker, Bufferable, WhenFull,
 };
 
@@ -37,7 +37,14 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 6] = [
+    "type",
+    "max_events",
+    "max_size",
+    "when_full",
+    "compression",
+    "io_uring",
+];
 
 struct BufferTypeVisitor;
 
@@ -50,6 +57,8 @@ impl BufferTypeVisitor {
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut compression: Option<DiskBufferCompression> = None;
+        let mut io_uring: Option<bool> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -76,6 +85,18 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "compression" => {
+                    if compression.is_some() {
+                        return Err(de::Error::duplicate_field("compression"));
+                    }
+                    compression = Some(map.next_value()?);
+                }
+                "io_uring" => {
+                    if io_uring.is_some() {
+                        return Err(de::Error::duplicate_field("io_uring"));
+                    }
+                    io_uring = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -91,6 +112,18 @@ impl BufferTypeVisitor {
                         &["type", "max_events", "when_full"],
                     ));
                 }
+                if compression.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "compression",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
+                if io_uring.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "io_uring",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
                     when_full,
@@ -103,6 +136,18 @@ impl BufferTypeVisitor {
                         &["type", "max_size", "when_full"],
                     ));
                 }
+                if compression.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "compression",
+                        &["type", "max_size", "when_full"],
+                    ));
+                }
+                if io_uring.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "io_uring",
+                        &["type", "max_size", "when_full"],
+                    ));
+                }
                 Ok(BufferType::DiskV1 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
@@ -112,12 +157,14 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "when_full", "compression", "io_uring"],
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    compression: compression.unwrap_or_default(),
+                    io_uring: io_uring.unwrap_or_default(),
                 })
             }
         }
@@ -233,6 +280,11 @@ pub enum BufferType {
         max_size: NonZeroU64,
         #[serde(default)]
         when_full: WhenFull,
+        #[serde(default)]
+        compression: DiskBufferCompression,
+        /// Whether to write the data files of the buffer through `io_uring`, on Linux.
+        #[serde(default)]
+        io_uring: bool,
     },
 }
 
@@ -269,9 +321,14 @@ impl BufferType {
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                compression,
+                io_uring,
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                builder.stage(
+                    DiskV2Buffer::new(id, data_dir, max_size, compression, io_uring),
+                    when_full,
+                );
             }
         };
 
@@ -360,7 +417,7 @@ impl BufferConfig {
 mod test {
     use std::num::{NonZeroU64, NonZeroUsize};
 
-    use crate::{BufferConfig, BufferType, WhenFull};
+    use crate::{BufferConfig, BufferType, DiskBufferCompression, WhenFull};
 
     fn check_single_stage(source: &str, expected: BufferType) {
         let config: BufferConfig = serde_yaml::from_str(source).unwrap();
@@ -390,7 +447,7 @@ mod test {
         let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
         assert_eq!(
             error.to_string(),
-            "unknown field `foo`, expected one of `type`, `max_events`, `max_size`, `when_full` at line 1 column 4"
+            "unknown field `foo`, expected one of `type`, `max_events`, `max_size`, `when_full`, `compression`, `io_uring` at line 1 column 4"
         );
     }
 
@@ -440,6 +497,37 @@ max_events: 42
         );
     }
 
+    #[test]
+    fn parse_chained_stages() {
+        check_multiple_stages(
+            r#"
+          - max_events: 42
+            when_full: overflow
+          - type: disk
+            max_size: 1024
+            when_full: overflow
+          - max_events: 100
+            when_full: drop_oldest
+          "#,
+            &[
+                BufferType::Memory {
+                    max_events: NonZeroUsize::new(42).unwrap(),
+                    when_full: WhenFull::Overflow,
+                },
+                BufferType::DiskV2 {
+                    max_size: NonZeroU64::new(1024).unwrap(),
+                    when_full: WhenFull::Overflow,
+                    compression: DiskBufferCompression::None,
+                    io_uring: false,
+                },
+                BufferType::Memory {
+                    max_events: NonZeroUsize::new(100).unwrap(),
+                    when_full: WhenFull::DropOldest,
+                },
+            ],
+        );
+    }
+
     #[test]
     fn ensure_field_defaults_for_all_types() {
         check_single_stage(
@@ -504,7 +592,73 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
                 when_full: WhenFull::Block,
+                compression: DiskBufferCompression::None,
+                io_uring: false,
             },
         );
     }
+
+    #[test]
+    fn parse_disk_compression() {
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          compression:
+            algorithm: zstd
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                compression: DiskBufferCompression::Zstd { level: 3 },
+                io_uring: false,
+            },
+        );
+
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          compression:
+            algorithm: zstd
+            level: 9
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                compression: DiskBufferCompression::Zstd { level: 9 },
+                io_uring: false,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_disk_io_uring() {
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          io_uring: true
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                compression: DiskBufferCompression::None,
+                io_uring: true,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_compression_only_for_disk() {
+        let source = r#"type: memory
+compression:
+  algorithm: zstd
+"#;
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown field `compression`, expected one of `type`, `max_events`, `when_full` at line 1 column 5"
+        );
+    }
 }
@@ -2,7 +2,19 @@ use std::error;
 
 use bytes::{Buf, BufMut};
 
+/// Bits of the on-disk metadata representation that are reserved for use by buffers themselves.
+///
+/// Buffers may use these bits to describe how a record was stored, such as whether or not the disk
+/// buffer compressed it, independently of how the record itself was encoded.  They are set and
+/// cleared by the buffer, and are never visible to implementations of [`AsMetadata`].
+pub const RESERVED_METADATA_BITS: u32 = 1 << 31;
+
 /// Converts back and forth between user-friendly metadata types and the on-disk integer representation.
+///
+/// # Reserved bits
+///
+/// Implementations must never use any of the bits in [`RESERVED_METADATA_BITS`]: `into_u32` must
+/// not set them, and `from_u32` will never be given a value with them set.
 pub trait AsMetadata: Sized {
     /// Converts this metadata value into its integer representation.
     fn into_u32(self) -> u32;
@@ -1,5 +1,5 @@
 use metrics::{counter, decrement_gauge, gauge, increment_gauge};
-use vector_common::internal_event::InternalEvent;
+use vector_common::internal_event::{discard_reason, InternalEvent};
 
 pub struct BufferEventsReceived {
     pub idx: usize,
@@ -43,6 +43,7 @@ impl InternalEvent for BufferEventsDropped {
     #[allow(clippy::cast_precision_loss)]
     fn emit(self) {
         counter!("buffer_discarded_events_total", self.count, "stage" => self.idx.to_string());
+        counter!("component_discarded_events_total", self.count, "reason" => discard_reason::BUFFER_FULL);
         decrement_gauge!("buffer_events", self.count as f64, "stage" => self.idx.to_string());
         decrement_gauge!("buffer_byte_size", self.byte_size as f64, "stage" => self.idx.to_string());
     }
@@ -58,6 +59,19 @@ impl InternalEvent for EventsCorrupted {
     }
 }
 
+pub struct BufferRecordsCompressed {
+    pub idx: usize,
+    pub logical_byte_size: u64,
+    pub compressed_byte_size: u64,
+}
+
+impl InternalEvent for BufferRecordsCompressed {
+    fn emit(self) {
+        counter!("buffer_logical_bytes_total", self.logical_byte_size, "stage" => self.idx.to_string());
+        counter!("buffer_compressed_bytes_total", self.compressed_byte_size, "stage" => self.idx.to_string());
+    }
+}
+
 pub struct BufferCreated {
     pub idx: usize,
     pub max_size_events: Option<usize>,
@@ -20,6 +20,10 @@ mod buffer_usage_data;
 pub mod config;
 pub use config::{BufferConfig, BufferType};
 use encoding::Encodable;
+pub use variants::{
+    inspect_disk_buffer, purge_disk_buffer, DiskBufferCompression, DiskBufferDataFile,
+    DiskBufferInfo, PurgeError,
+};
 
 pub mod encoding;
 
@@ -43,6 +47,7 @@ use vector_common::byte_size_of::ByteSizeOf;
 pub enum WhenFull {
     Block,
     DropNewest,
+    DropOldest,
     Overflow,
 }
 
@@ -45,6 +45,15 @@ impl Model for InMemory {
                 }
                 Progress::Advanced
             }
+            WhenFull::DropOldest => {
+                // DropOldest never blocks either, instead it silently drops the
+                // oldest item in the buffer to make room when the buffer is too full.
+                if self.inner.len() >= self.capacity {
+                    self.inner.pop_front();
+                }
+                self.inner.push_back(item);
+                Progress::Advanced
+            }
             WhenFull::Block | WhenFull::Overflow => {
                 if self.inner.len() >= self.capacity {
                     Progress::Blocked(item)
@@ -50,6 +50,9 @@ impl Model for OnDiskV1 {
                 }
                 Progress::Advanced
             }
+            WhenFull::DropOldest => {
+                unreachable!("disk buffers do not support dropping the oldest items")
+            }
             WhenFull::Block | WhenFull::Overflow => {
                 if self.is_full() {
                     Progress::Blocked(item)
@@ -28,6 +28,15 @@ pub trait IntoBuffer<T: Bufferable> {
         false
     }
 
+    /// Gets whether or not this buffer stage can drop the oldest items it holds to make room for
+    /// new ones.
+    ///
+    /// Only stages that hold their items in memory can do so, as items that have already been
+    /// persisted elsewhere, such as on disk, cannot be cheaply evicted out of order.
+    fn supports_drop_oldest(&self) -> bool {
+        false
+    }
+
     /// Converts this value into a sender and receiver pair suitable for use in a buffer topology.
     async fn into_buffer_parts(
         self: Box<Self>,
@@ -40,12 +49,17 @@ pub enum TopologyError {
     #[snafu(display("buffer topology cannot be empty"))]
     EmptyTopology,
     #[snafu(display(
-        "stage {} configured with block/drop newest behavior in front of subsequent stage",
+        "stage {} configured with block/drop newest/drop oldest behavior in front of subsequent stage",
         stage_idx
     ))]
     NextStageNotUsed { stage_idx: usize },
     #[snafu(display("last stage in buffer topology cannot be set to overflow mode"))]
     OverflowWhenLast,
+    #[snafu(display(
+        "stage {} configured with drop oldest behavior, which is only supported by in-memory stages",
+        stage_idx
+    ))]
+    DropOldestUnsupported { stage_idx: usize },
     #[snafu(display("failed to build individual stage {}: {}", stage_idx, source))]
     FailedToBuildStage {
         stage_idx: usize,
@@ -76,17 +90,18 @@ impl<T: Bufferable> TopologyBuilder<T> {
     /// an overflow buffer is added to the topology after this, then the specified "when full"
     /// behavior will be ignored and will be set to "overflow" mode.
     ///
-    /// Callers can configure what to do when a buffer is full by setting `when_full`.  Three modes
-    /// are available -- block, drop newest, and overflow -- which are documented in more detail by
-    /// [`BufferSender`].
+    /// Callers can configure what to do when a buffer is full by setting `when_full`.  Four modes
+    /// are available -- block, drop newest, drop oldest, and overflow -- which are documented in
+    /// more detail by [`BufferSender`].
     ///
-    /// Two notes about what modes are not valid in certain scenarios:
+    /// A few notes about what modes are not valid in certain scenarios:
     /// - the innermost stage (the last stage given to the builder) cannot be set to "overflow" mode,
     ///   as there is no other stage to overflow to
-    /// - a stage cannot use the "block" or "drop newest" mode when there is a subsequent stage, and
-    ///   must user the "overflow" mode
+    /// - a stage cannot use the "block", "drop newest", or "drop oldest" mode when there is a
+    ///   subsequent stage, and must user the "overflow" mode
+    /// - only in-memory stages can use the "drop oldest" mode
     ///
-    /// Any occurrence of either of these scenarios will result in an error during build.
+    /// Any occurrence of these scenarios will result in an error during build.
     pub fn stage<S>(&mut self, stage: S, when_full: WhenFull) -> &mut Self
     where
         S: IntoBuffer<T> + 'static,
@@ -130,6 +145,15 @@ impl<T: Bufferable> TopologyBuilder<T> {
                         return Err(TopologyError::NextStageNotUsed { stage_idx });
                     }
                 }
+                // Dropping the oldest events is only possible when they're still held in memory.
+                WhenFull::DropOldest => {
+                    if current_stage.is_some() {
+                        return Err(TopologyError::NextStageNotUsed { stage_idx });
+                    }
+                    if !stage.untransformed.supports_drop_oldest() {
+                        return Err(TopologyError::DropOldestUnsupported { stage_idx });
+                    }
+                }
             };
 
             // Create the buffer usage handle for this stage and initialize it as we create the
@@ -276,15 +300,15 @@ impl<T: Bufferable> Default for TopologyBuilder<T> {
 
 #[cfg(test)]
 mod tests {
-    use std::num::NonZeroUsize;
+    use std::num::{NonZeroU64, NonZeroUsize};
 
     use tracing::Span;
 
     use super::TopologyBuilder;
     use crate::{
         topology::{builder::TopologyError, test_util::assert_current_send_capacity},
-        variants::MemoryBuffer,
-        WhenFull,
+        variants::{DiskV2Buffer, MemoryBuffer},
+        DiskBufferCompression, WhenFull,
     };
 
     #[tokio::test]
@@ -383,4 +407,65 @@ mod tests {
         let (mut sender, _, _) = result.unwrap();
         assert_current_send_capacity(&mut sender, Some(1), Some(1));
     }
+
+    #[tokio::test]
+    async fn three_stage_topology_drop_oldest() {
+        let mut builder = TopologyBuilder::<u64>::default();
+        builder.stage(
+            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            WhenFull::Overflow,
+        );
+        builder.stage(
+            MemoryBuffer::new(NonZeroUsize::new(2).unwrap()),
+            WhenFull::Overflow,
+        );
+        builder.stage(
+            MemoryBuffer::new(NonZeroUsize::new(3).unwrap()),
+            WhenFull::DropOldest,
+        );
+
+        let result = builder.build(String::from("test"), Span::none()).await;
+        assert!(result.is_ok());
+
+        let (mut sender, _, _) = result.unwrap();
+        assert_current_send_capacity(&mut sender, Some(1), Some(2));
+    }
+
+    #[tokio::test]
+    async fn two_stage_topology_drop_oldest() {
+        let mut builder = TopologyBuilder::<u64>::default();
+        builder.stage(
+            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            WhenFull::DropOldest,
+        );
+        builder.stage(
+            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            WhenFull::Block,
+        );
+        let result = builder.build(String::from("test"), Span::none()).await;
+        match result {
+            Err(TopologyError::NextStageNotUsed { stage_idx }) => assert_eq!(stage_idx, 0),
+            r => panic!("unexpected build result: {:?}", r),
+        }
+    }
+
+    #[tokio::test]
+    async fn single_stage_topology_disk_drop_oldest() {
+        let mut builder = TopologyBuilder::<u64>::default();
+        builder.stage(
+            DiskV2Buffer::new(
+                String::from("test"),
+                std::env::temp_dir(),
+                NonZeroU64::new(1024 * 1024).unwrap(),
+                DiskBufferCompression::None,
+                false,
+            ),
+            WhenFull::DropOldest,
+        );
+        let result = builder.build(String::from("test"), Span::none()).await;
+        match result {
+            Err(TopologyError::DropOldestUnsupported { stage_idx }) => assert_eq!(stage_idx, 0),
+            r => panic!("unexpected build result: {:?}", r),
+        }
+    }
 }
@@ -160,6 +160,60 @@ impl<T: Bufferable> LimitedSender<T> {
 
         Ok(())
     }
+
+    /// Sends an item into the channel, evicting the oldest items in the channel to make room for it
+    /// if necessary.
+    ///
+    /// The evicted items, if any, are returned in the order they were sent.  If there is nothing
+    /// left to evict, but there is still not enough capacity for the item, which can happen when
+    /// another sender is racing us for capacity, we simply wait for capacity as `send` would.
+    ///
+    /// # Errors
+    ///
+    /// If the receiver has disconnected (does not exist anymore), then `Err(SendError)` be returned
+    /// with the given `item`.
+    pub async fn send_evicting(&mut self, item: T) -> Result<Vec<T>, SendError<T>> {
+        let permits_required = self.get_required_permits_for_item(&item);
+        let mut evicted = Vec::new();
+        let permits = loop {
+            match self
+                .inner
+                .limiter
+                .clone()
+                .try_acquire_many_owned(permits_required)
+            {
+                Ok(permits) => break permits,
+                Err(TryAcquireError::Closed) => return Err(SendError(item)),
+                // Popping an item drops the permits it was holding, which returns them to the
+                // limiter for us to try and acquire again.
+                Err(TryAcquireError::NoPermits) => match self.inner.data.pop() {
+                    Some((_permits, oldest)) => evicted.push(oldest),
+                    None => {
+                        match self
+                            .inner
+                            .limiter
+                            .clone()
+                            .acquire_many_owned(permits_required)
+                            .await
+                        {
+                            Ok(permits) => break permits,
+                            Err(_) => return Err(SendError(item)),
+                        }
+                    }
+                },
+            }
+        };
+
+        self.inner
+            .data
+            .push((permits, item))
+            .expect("acquired permits but channel reported being full");
+        self.inner.read_waker.notify_one();
+
+        trace!(evicted = evicted.len(), "Sent item.");
+
+        Ok(evicted)
+    }
 }
 
 impl<T> Clone for LimitedSender<T> {
@@ -589,4 +643,30 @@ mod tests {
 
         assert_eq!(2, tx.available_capacity());
     }
+
+    #[test]
+    fn send_evicting_drops_oldest_items_when_full() {
+        let (mut tx, mut rx) = limited(2);
+
+        let mut send = spawn(async {
+            tx.send_evicting(MultiEventRecord(1)).await?;
+            tx.send_evicting(MultiEventRecord(1)).await?;
+            tx.send_evicting(MultiEventRecord(2)).await
+        });
+
+        // The last item needs the entire channel, so both of the items ahead of it are evicted.
+        assert_eq!(
+            Ok(vec![MultiEventRecord(1), MultiEventRecord(1)]),
+            assert_ready!(send.poll())
+        );
+        drop(send);
+
+        assert_eq!(0, tx.available_capacity());
+
+        let mut recv = spawn(async { rx.next().await });
+        assert_eq!(Some(MultiEventRecord(2)), assert_ready!(recv.poll()));
+        drop(recv);
+
+        assert_eq!(2, rx.available_capacity());
+    }
 }
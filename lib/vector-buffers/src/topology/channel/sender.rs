@@ -98,6 +98,16 @@ where
         }
     }
 
+    pub(crate) async fn send_evicting(&mut self, item: T) -> Result<Vec<T>, ()> {
+        match self {
+            Self::InMemory(tx) => tx.send_evicting(item).await.map_err(|_| ()),
+            // Items already written to disk can't be evicted, which is why the topology builder
+            // only allows in-memory stages to drop the oldest items.  Should we get here anyways,
+            // the best we can do is wait for capacity instead.
+            Self::DiskV1(_) | Self::DiskV2(_) => self.send(item).await.map(|()| Vec::new()),
+        }
+    }
+
     pub(crate) async fn flush(&mut self) -> Result<(), ()> {
         match self {
             Self::InMemory(_) => Ok(()),
@@ -132,16 +142,19 @@ where
 /// events when the internal channel is full.
 ///
 /// When creating a buffer sender/receiver pair, callers can specify the "when full" behavior of the
-/// sender.  This controls how events are handled when the internal channel is full.  Three modes
+/// sender.  This controls how events are handled when the internal channel is full.  Four modes
 /// are possible:
 /// - block
 /// - drop newest
+/// - drop oldest
 /// - overflow
 ///
 /// In "block" mode, callers are simply forced to wait until the channel has enough capacity to
 /// accept the event.  In "drop newest" mode, any event being sent when the channel is full will be
-/// dropped and proceed no further. In "overflow" mode, events will be sent to another buffer
-/// sender.  Callers can specify the overflow sender to use when constructing their buffers initially.
+/// dropped and proceed no further. In "drop oldest" mode, the oldest events in the channel are
+/// dropped to make room for the event being sent, which is only supported by in-memory channels.
+/// In "overflow" mode, events will be sent to another buffer sender.  Callers can specify the
+/// overflow sender to use when constructing their buffers initially.
 ///
 /// TODO: We should eventually rework `BufferSender`/`BufferReceiver` so that they contain a vector
 /// of the fields we already have here, but instead of cascading via calling into `overflow`, we'd
@@ -220,6 +233,17 @@ impl<T: Bufferable> BufferSender<T> {
                     was_dropped = true;
                 }
             }
+            WhenFull::DropOldest => {
+                let evicted = self.base.send_evicting(item).await?;
+                if let Some(instrumentation) = self.instrumentation.as_ref() {
+                    for evicted_item in evicted {
+                        instrumentation.try_increment_dropped_event_count_and_byte_size(
+                            evicted_item.event_count() as u64,
+                            evicted_item.size_of() as u64,
+                        );
+                    }
+                }
+            }
             WhenFull::Overflow => {
                 if let Some(item) = self.base.try_send(item).await? {
                     sent_to_base = false;
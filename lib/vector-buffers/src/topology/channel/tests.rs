@@ -132,6 +132,55 @@ async fn test_sender_drop_newest() {
     assert_eq!(results, vec![1, 2, 3]);
 }
 
+#[tokio::test]
+async fn test_sender_drop_oldest() {
+    // Get a non-overflow buffer in "drop oldest" mode with a capacity of 3.
+    let (mut tx, rx, _) = build_buffer(3, WhenFull::DropOldest, None).await;
+
+    // We should be able to send three messages through unimpeded.
+    assert_current_send_capacity(&mut tx, Some(3), None);
+    assert_send_ok_with_capacities(&mut tx, 1, Some(2), None).await;
+    assert_send_ok_with_capacities(&mut tx, 2, Some(1), None).await;
+    assert_send_ok_with_capacities(&mut tx, 3, Some(0), None).await;
+
+    // Then, since we're in "drop oldest" mode, we can continue to send without being blocked, with
+    // each new item pushing out the oldest item still in the buffer.
+    assert_send_ok_with_capacities(&mut tx, 7, Some(0), None).await;
+    assert_send_ok_with_capacities(&mut tx, 8, Some(0), None).await;
+
+    // Then, when we collect all of the messages from the receiver, we should only get back the
+    // last three of them.
+    let mut results = drain_receiver(tx, rx).await;
+    results.sort_unstable();
+    assert_eq!(results, vec![3, 7, 8]);
+}
+
+#[tokio::test]
+async fn test_sender_overflow_drop_oldest() {
+    // Get an overflow buffer, where the overflow buffer is in "drop oldest" mode, and both the
+    // base and overflow buffers have a capacity of 2.
+    let (mut tx, rx, _) = build_buffer(2, WhenFull::Overflow, Some(WhenFull::DropOldest)).await;
+
+    // We should be able to send four message through unimpeded -- two for the base sender, and
+    // two for the overflow sender.
+    assert_current_send_capacity(&mut tx, Some(2), Some(2));
+    assert_send_ok_with_capacities(&mut tx, 7, Some(1), Some(2)).await;
+    assert_send_ok_with_capacities(&mut tx, 8, Some(0), Some(2)).await;
+    assert_send_ok_with_capacities(&mut tx, 2, Some(0), Some(1)).await;
+    assert_send_ok_with_capacities(&mut tx, 1, Some(0), Some(0)).await;
+
+    // Then, since we're in "drop oldest" mode on the overflow side, we can continue to send
+    // without being blocked, with the oldest items on the overflow side being dropped.
+    assert_send_ok_with_capacities(&mut tx, 5, Some(0), Some(0)).await;
+    assert_send_ok_with_capacities(&mut tx, 6, Some(0), Some(0)).await;
+
+    // Then, when we collect all of the messages from the receiver, we should get back everything
+    // in the base buffer, and only the last two items sent to the overflow buffer.
+    let mut results = drain_receiver(tx, rx).await;
+    results.sort_unstable();
+    assert_eq!(results, vec![5, 6, 7, 8]);
+}
+
 #[tokio::test]
 async fn test_sender_overflow_block() {
     // Get an overflow buffer, where the overflow buffer is in blocking mode, and both the base
@@ -238,3 +287,30 @@ async fn test_buffer_metrics_drop_newest() {
     assert_eq!(2, snapshot.sent_event_count);
     assert_eq!(Some(1), snapshot.dropped_event_count);
 }
+
+#[tokio::test]
+async fn test_buffer_metrics_drop_oldest() {
+    // Get a buffer that drops the oldest items when full.
+    let (mut tx, rx, handle) = build_buffer(2, WhenFull::DropOldest, None).await;
+
+    // Send three items through, and make sure the buffer usage stats reflect that.
+    assert_current_send_capacity(&mut tx, Some(2), None);
+    assert_send_ok_with_capacities(&mut tx, 7, Some(1), None).await;
+    assert_send_ok_with_capacities(&mut tx, 8, Some(0), None).await;
+    assert_send_ok_with_capacities(&mut tx, 2, Some(0), None).await;
+
+    let snapshot = handle.snapshot();
+    assert_eq!(3, snapshot.received_event_count);
+    assert_eq!(0, snapshot.sent_event_count);
+    assert_eq!(Some(1), snapshot.dropped_event_count);
+
+    // Then, when we collect all of the messages from the receiver, the metrics should also reflect that.
+    let mut results = drain_receiver(tx, rx).await;
+    results.sort_unstable();
+    assert_eq!(results, vec![2, 8]);
+
+    let snapshot = handle.snapshot();
+    assert_eq!(3, snapshot.received_event_count);
+    assert_eq!(2, snapshot.sent_event_count);
+    assert_eq!(Some(1), snapshot.dropped_event_count);
+}
@@ -1,10 +1,12 @@
 use std::{
     cmp,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
     time::Duration,
 };
 
 use crc32fast::Hasher;
+use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 
 use super::io::{Filesystem, ProductionFilesystem};
@@ -27,10 +29,46 @@ pub const MAX_FILE_ID: u16 = u16::MAX;
 #[cfg(test)]
 pub const MAX_FILE_ID: u16 = 6;
 
+// The zstd compression level used when compression is enabled but no level is specified.  This
+// mirrors the default level of the `zstd` command-line tool, which is a good tradeoff between CPU
+// usage and compression ratio.
+pub const DEFAULT_ZSTD_COMPRESSION_LEVEL: i32 = 3;
+// zstd itself also accepts negative "fast" levels, as well as a level of zero which means "use the
+// default", but we stick to the conventional range to keep the configuration unambiguous.
+pub const ZSTD_COMPRESSION_LEVELS: RangeInclusive<i32> = 1..=21;
+
 pub(crate) fn create_crc32c_hasher() -> Hasher {
     crc32fast::Hasher::new()
 }
 
+pub const fn default_zstd_compression_level() -> i32 {
+    DEFAULT_ZSTD_COMPRESSION_LEVEL
+}
+
+/// Compression applied to the encoded form of records before they are written to disk.
+///
+/// Compression is applied per record, after encoding, and is recorded in the record metadata so
+/// that a buffer may contain a mix of compressed and uncompressed records, such as after
+/// compression is enabled for a buffer that already holds data.
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "algorithm", rename_all = "snake_case")]
+pub enum DiskBufferCompression {
+    /// Records are stored as-is.
+    None,
+    /// Records are compressed with [zstd](https://facebook.github.io/zstd/).
+    Zstd {
+        /// Compression level, from 1 (fastest) to 21 (smallest).
+        #[serde(default = "default_zstd_compression_level")]
+        level: i32,
+    },
+}
+
+impl Default for DiskBufferCompression {
+    fn default() -> Self {
+        DiskBufferCompression::None
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum BuildError {
     #[snafu(display("parameter '{}' was invalid: {}", param_name, reason))]
@@ -89,6 +127,20 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Compression applied to records before they are written to a data file.
+    ///
+    /// Compression trades CPU for disk space, which is most useful when a buffer is expected to
+    /// hold a large amount of data for an extended period, such as when the sink is unavailable.
+    pub(crate) compression: DiskBufferCompression,
+
+    /// Whether the reader seeks to where it left off when it's first read from, rather than when
+    /// the buffer is loaded.
+    ///
+    /// Seeking involves reading through the records of the data file the reader left off in, which
+    /// for a data file that was mostly read can take a while, so deferring it lets the buffer take
+    /// writes, and the topology start, without waiting on it.
+    pub(crate) lazy_recovery: bool,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -109,6 +161,8 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) compression: Option<DiskBufferCompression>,
+    pub(crate) lazy_recovery: Option<bool>,
     pub(crate) filesystem: FS,
 }
 
@@ -124,9 +178,22 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
-            filesystem: ProductionFilesystem,
+            compression: None,
+            lazy_recovery: None,
+            filesystem: ProductionFilesystem::default(),
         }
     }
+
+    /// Sets whether data files are written through `io_uring`.
+    ///
+    /// This requires Linux, and Vector built with the `io_uring` feature, and falls back to
+    /// regular file I/O otherwise.
+    ///
+    /// Defaults to `false`.
+    pub fn io_uring(mut self, io_uring: bool) -> Self {
+        self.filesystem = ProductionFilesystem::with_io_uring(io_uring);
+        self
+    }
 }
 
 impl<FS> DiskBufferConfigBuilder<FS>
@@ -204,6 +271,30 @@ where
         self
     }
 
+    /// Sets the compression applied to records before they are written to a data file.
+    ///
+    /// Compression is applied after a record is encoded, and before it is checksummed, so the
+    /// maximum record size limit applies to the uncompressed, encoded form of the record.
+    ///
+    /// Defaults to no compression.
+    pub fn compression(mut self, compression: DiskBufferCompression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Sets whether the reader seeks to where it left off when it's first read from, rather than
+    /// when the buffer is loaded.
+    ///
+    /// Seeking involves reading through the records of the data file the reader left off in, which
+    /// for a data file that was mostly read can take a while, so deferring it lets the buffer take
+    /// writes, and the topology start, without waiting on it.
+    ///
+    /// Defaults to `false`.
+    pub fn lazy_recovery(mut self, lazy_recovery: bool) -> Self {
+        self.lazy_recovery = Some(lazy_recovery);
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -223,6 +314,8 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            compression: self.compression,
+            lazy_recovery: self.lazy_recovery,
             filesystem,
         }
     }
@@ -238,6 +331,8 @@ where
         let flush_interval = self
             .flush_interval
             .unwrap_or_else(|| Duration::from_millis(500));
+        let compression = self.compression.unwrap_or_default();
+        let lazy_recovery = self.lazy_recovery.unwrap_or(false);
         let filesystem = self.filesystem;
 
         // Validate the input parameters.
@@ -269,6 +364,15 @@ where
             });
         }
 
+        if let DiskBufferCompression::Zstd { level } = compression {
+            if !ZSTD_COMPRESSION_LEVELS.contains(&level) {
+                return Err(BuildError::InvalidParameter {
+                    param_name: "compression.level",
+                    reason: "must be between 1 and 21",
+                });
+            }
+        }
+
         // The actual on-disk maximum buffer size will be the user-supplied `max_buffer_size`
         // rounded up to the next multiple of `DATA_FILE_TARGET_MAX_SIZE`.  Internally, we'll limit
         // ourselves to `max_buffer_size` rounded _down_ to the next multiple of
@@ -287,6 +391,8 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            compression,
+            lazy_recovery,
             filesystem,
         })
     }
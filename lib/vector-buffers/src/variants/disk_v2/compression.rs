@@ -0,0 +1,169 @@
+use std::{fmt, io};
+
+use zstd::bulk::{Compressor, Decompressor};
+
+use super::common::DiskBufferCompression;
+
+/// Record metadata bit indicating that the record payload was compressed with zstd.
+///
+/// This is set by the buffer itself, and is stripped before the metadata is handed to the decoder
+/// of the underlying record type.  See [`AsMetadata`](crate::encoding::AsMetadata) for the
+/// corresponding reservation on the record type side.
+pub(super) const RECORD_METADATA_COMPRESSED_ZSTD: u32 = crate::encoding::RESERVED_METADATA_BITS;
+
+/// Compresses encoded records before they are written to a data file.
+///
+/// The underlying compression context is created on first use and reused for every subsequent
+/// record, as creating a context allocates a non-trivial amount of memory, especially at higher
+/// compression levels.
+pub(super) struct RecordCompressor {
+    compression: DiskBufferCompression,
+    context: Option<Compressor<'static>>,
+    buf: Vec<u8>,
+    logical_byte_size: u64,
+    compressed_byte_size: u64,
+}
+
+impl RecordCompressor {
+    pub fn new(compression: DiskBufferCompression) -> Self {
+        Self {
+            compression,
+            context: None,
+            buf: Vec::new(),
+            logical_byte_size: 0,
+            compressed_byte_size: 0,
+        }
+    }
+
+    /// Whether or not records will be compressed.
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self.compression, DiskBufferCompression::None)
+    }
+
+    /// Compresses the given encoded record, returning the compressed form.
+    ///
+    /// If compression is disabled, the payload is returned as-is.
+    ///
+    /// # Errors
+    ///
+    /// If the compression context cannot be created, or compression itself fails, an error variant
+    /// will be returned describing the error.
+    pub fn compress<'a>(&'a mut self, payload: &'a [u8]) -> io::Result<&'a [u8]> {
+        let level = match self.compression {
+            DiskBufferCompression::None => return Ok(payload),
+            DiskBufferCompression::Zstd { level } => level,
+        };
+
+        if self.context.is_none() {
+            self.context = Some(Compressor::new(level)?);
+        }
+        let context = self.context.as_mut().expect("context must exist");
+
+        // The compressor writes into the spare capacity of the buffer, so we need to ensure there's
+        // enough room for the worst case.
+        self.buf.clear();
+        self.buf
+            .reserve_exact(zstd::zstd_safe::compress_bound(payload.len()));
+        context.compress_to_buffer(payload, &mut self.buf)?;
+
+        Ok(&self.buf)
+    }
+
+    /// Tracks the sizes of a record that was compressed and successfully written.
+    pub fn track_written(&mut self, logical_byte_size: usize, compressed_byte_size: usize) {
+        self.logical_byte_size += logical_byte_size as u64;
+        self.compressed_byte_size += compressed_byte_size as u64;
+    }
+
+    /// Takes the total logical and compressed sizes, in bytes, of all records written since the
+    /// last call.
+    pub fn take_totals(&mut self) -> (u64, u64) {
+        (
+            std::mem::take(&mut self.logical_byte_size),
+            std::mem::take(&mut self.compressed_byte_size),
+        )
+    }
+}
+
+impl fmt::Debug for RecordCompressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordCompressor")
+            .field("compression", &self.compression)
+            .field("buf_capacity", &self.buf.capacity())
+            .field("logical_byte_size", &self.logical_byte_size)
+            .field("compressed_byte_size", &self.compressed_byte_size)
+            .finish()
+    }
+}
+
+/// Decompresses record payloads read from a data file.
+///
+/// Like [`RecordCompressor`], the underlying decompression context is created on first use and
+/// then reused.  Decompression is bounded by the maximum record size, as the writer enforces that
+/// limit on the uncompressed form of a record, which means a valid record can never decompress to
+/// anything larger.
+pub(super) struct RecordDecompressor {
+    context: Option<Decompressor<'static>>,
+    buf: Vec<u8>,
+    max_record_size: usize,
+}
+
+impl RecordDecompressor {
+    pub fn new(max_record_size: usize) -> Self {
+        Self {
+            context: None,
+            buf: Vec::new(),
+            max_record_size,
+        }
+    }
+
+    /// Decompresses the given record payload, returning the decompressed form.
+    ///
+    /// # Errors
+    ///
+    /// If the decompression context cannot be created, the payload is not valid zstd data, or the
+    /// payload decompresses to more than the maximum record size, an error variant will be returned
+    /// describing the error.
+    pub fn decompress(&mut self, payload: &[u8]) -> io::Result<&[u8]> {
+        if self.context.is_none() {
+            self.context = Some(Decompressor::new()?);
+        }
+        let context = self.context.as_mut().expect("context must exist");
+
+        // We write the frame content size when compressing, so we can usually allocate exactly
+        // what we need.  If the frame claims to be bigger than the maximum record size, or doesn't
+        // specify its size at all, we only allow it to decompress up to the maximum record size.
+        let content_size = zstd::zstd_safe::get_frame_content_size(payload);
+        let capacity = usize::try_from(content_size)
+            .ok()
+            .filter(|size| *size <= self.max_record_size)
+            .unwrap_or(self.max_record_size);
+
+        self.buf.clear();
+        self.buf.reserve_exact(capacity);
+        context.decompress_to_buffer(payload, &mut self.buf)?;
+
+        // The buffer may have more spare capacity than we asked for if it was grown by a previous
+        // record, so we still need to check the final size against the limit.
+        if self.buf.len() > self.max_record_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "decompressed record exceeds maximum record size of {} bytes",
+                    self.max_record_size
+                ),
+            ));
+        }
+
+        Ok(&self.buf)
+    }
+}
+
+impl fmt::Debug for RecordDecompressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordDecompressor")
+            .field("buf_capacity", &self.buf.capacity())
+            .field("max_record_size", &self.max_record_size)
+            .finish()
+    }
+}
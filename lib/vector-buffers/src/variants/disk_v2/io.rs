@@ -1,7 +1,12 @@
-use std::{io, path::Path};
+use std::{
+    io,
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 use async_trait::async_trait;
-use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 
 /// File metadata.
 pub struct Metadata {
@@ -119,35 +124,73 @@ pub trait WritableMemoryMap: ReadableMemoryMap {
 /// A normal filesystem used for production operations.
 ///
 /// Uses Tokio's `File` for asynchronous file reading/writing, and `memmap2` for memory-mapped files.
-#[derive(Clone, Debug)]
-pub struct ProductionFilesystem;
+///
+/// When `io_uring` is enabled, and Vector is built with the `io_uring` feature on Linux, data files
+/// opened for writing go through `io_uring` instead of Tokio's `File`.
+#[derive(Clone, Debug, Default)]
+pub struct ProductionFilesystem {
+    #[cfg_attr(not(all(target_os = "linux", feature = "io_uring")), allow(dead_code))]
+    io_uring: bool,
+}
 
-#[async_trait]
-impl Filesystem for ProductionFilesystem {
-    type File = tokio::fs::File;
-    type MemoryMap = memmap2::Mmap;
-    type MutableMemoryMap = memmap2::MmapMut;
+impl ProductionFilesystem {
+    /// Creates a filesystem whose data files opened for writing go through `io_uring`, if
+    /// `io_uring` is true and `io_uring` is available.
+    pub fn with_io_uring(io_uring: bool) -> Self {
+        #[cfg(not(all(target_os = "linux", feature = "io_uring")))]
+        if io_uring {
+            warn!("io_uring is only supported on Linux builds with the `io_uring` feature, using regular file I/O for disk buffers.");
+        }
+        Self { io_uring }
+    }
+
+    async fn open_writable(&self, path: &Path, create_new: bool) -> io::Result<ProductionFile> {
+        #[cfg(all(target_os = "linux", feature = "io_uring"))]
+        if self.io_uring {
+            if let Some(file) = super::uring::open_writable(path, create_new) {
+                return file.map(ProductionFile::Uring);
+            }
+        }
 
-    async fn open_file_writable(&self, path: &Path) -> io::Result<Self::File> {
         tokio::fs::OpenOptions::new()
             .append(true)
             .read(true)
-            .create(true)
+            .create(!create_new)
+            .create_new(create_new)
             .open(path)
             .await
+            .map(ProductionFile::Tokio)
+    }
+}
+
+/// A data file opened by [`ProductionFilesystem`].
+#[derive(Debug)]
+pub enum ProductionFile {
+    Tokio(tokio::fs::File),
+    #[cfg(all(target_os = "linux", feature = "io_uring"))]
+    Uring(super::uring::UringFile),
+}
+
+#[async_trait]
+impl Filesystem for ProductionFilesystem {
+    type File = ProductionFile;
+    type MemoryMap = memmap2::Mmap;
+    type MutableMemoryMap = memmap2::MmapMut;
+
+    async fn open_file_writable(&self, path: &Path) -> io::Result<Self::File> {
+        self.open_writable(path, false).await
     }
 
     async fn open_file_writable_atomic(&self, path: &Path) -> io::Result<Self::File> {
+        self.open_writable(path, true).await
+    }
+
+    async fn open_file_readable(&self, path: &Path) -> io::Result<Self::File> {
         tokio::fs::OpenOptions::new()
-            .append(true)
             .read(true)
-            .create_new(true)
             .open(path)
             .await
-    }
-
-    async fn open_file_readable(&self, path: &Path) -> io::Result<Self::File> {
-        tokio::fs::OpenOptions::new().read(true).open(path).await
+            .map(ProductionFile::Tokio)
     }
 
     async fn open_mmap_readable(&self, path: &Path) -> io::Result<Self::MemoryMap> {
@@ -185,6 +228,69 @@ impl AsyncFile for tokio::fs::File {
     }
 }
 
+impl AsyncRead for ProductionFile {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tokio(file) => Pin::new(file).poll_read(cx, buf),
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            Self::Uring(file) => Pin::new(file).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for ProductionFile {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Self::Tokio(file) => Pin::new(file).poll_write(cx, buf),
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            Self::Uring(file) => Pin::new(file).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tokio(file) => Pin::new(file).poll_flush(cx),
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            Self::Uring(file) => Pin::new(file).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tokio(file) => Pin::new(file).poll_shutdown(cx),
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            Self::Uring(file) => Pin::new(file).poll_shutdown(cx),
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncFile for ProductionFile {
+    async fn metadata(&self) -> io::Result<Metadata> {
+        match self {
+            Self::Tokio(file) => AsyncFile::metadata(file).await,
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            Self::Uring(file) => file.metadata().await,
+        }
+    }
+
+    async fn sync_all(&self) -> io::Result<()> {
+        match self {
+            Self::Tokio(file) => AsyncFile::sync_all(file).await,
+            #[cfg(all(target_os = "linux", feature = "io_uring"))]
+            Self::Uring(file) => file.sync_all().await,
+        }
+    }
+}
+
 impl ReadableMemoryMap for memmap2::Mmap {}
 
 impl ReadableMemoryMap for memmap2::MmapMut {}
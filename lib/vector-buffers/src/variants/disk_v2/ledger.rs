@@ -385,6 +385,12 @@ where
             .increment_received_event_count_and_byte_size(event_count, record_size);
     }
 
+    /// Tracks the logical (uncompressed) and compressed sizes of written records.
+    pub fn track_compression(&self, logical_byte_size: u64, compressed_byte_size: u64) {
+        self.usage_handle
+            .increment_compressed_byte_sizes(logical_byte_size, compressed_byte_size);
+    }
+
     /// Tracks the statistics of multiple successful reads.
     pub fn track_reads(&self, event_count: u64, total_record_size: u64) {
         self.decrement_total_buffer_size(total_record_size);
@@ -392,6 +398,13 @@ where
             .increment_sent_event_count_and_byte_size(event_count, total_record_size);
     }
 
+    /// Tracks the size of records skipped over while seeking lazily, which were already read
+    /// before the buffer was loaded.
+    pub fn track_skipped_reads(&self, total_record_size: u64) {
+        self.usage_handle
+            .increment_sent_event_count_and_byte_size(0, total_record_size);
+    }
+
     /// Marks the writer as finished.
     ///
     /// If the writer was not yet marked done, `false` is returned.  Otherwise, `true` is returned,
@@ -512,7 +525,8 @@ where
     ///
     /// This should not be called until both the reader and writer have been initialized via
     /// [`Reader::seek_to_last_record`] and [`Writer::validate_last_write`], otherwise the values
-    /// will not be accurate.
+    /// will not be accurate.  When recovering lazily, the reader corrects the values once it has
+    /// sought on its first read.
     pub fn synchronize_buffer_usage(&self) {
         let initial_buffer_events = self.get_total_records();
         let initial_buffer_size = self.get_total_buffer_size();
@@ -0,0 +1,214 @@
+//! Inspection and management of disk buffers from outside of a running buffer.
+//!
+//! These operations work directly against the files of a buffer, and so are meant to be used by
+//! tooling, such as the `vector buffer` subcommands, when operators need to look at or clean up a
+//! buffer during an incident.  Anything that modifies the buffer first takes the same advisory lock
+//! that the buffer itself takes when it is loaded, so a buffer in use by a running Vector process
+//! is never modified underneath it.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use fslock::LockFile;
+use snafu::{ResultExt, Snafu};
+
+use super::get_disk_v2_data_dir_path;
+
+/// Error that occurred when purging a disk buffer.
+#[derive(Debug, Snafu)]
+pub enum PurgeError {
+    /// The buffer is in use by another Vector process.
+    #[snafu(display(
+        "failed to lock buffer.lock; is another Vector process running and using this buffer?"
+    ))]
+    BufferInUse,
+
+    /// A general I/O error occurred while removing the files of the buffer.
+    #[snafu(display("buffer I/O error: {}", source))]
+    Io { source: io::Error },
+}
+
+/// A data file belonging to a disk buffer.
+#[derive(Clone, Debug)]
+pub struct DiskBufferDataFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Information about the files of a disk buffer.
+#[derive(Clone, Debug)]
+pub struct DiskBufferInfo {
+    pub path: PathBuf,
+    /// Data files of the buffer, ordered from oldest to newest.
+    pub data_files: Vec<DiskBufferDataFile>,
+    /// Whether or not the buffer is currently locked by a Vector process.
+    pub in_use: bool,
+}
+
+impl DiskBufferInfo {
+    /// Gets the total size of the data files, in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.data_files.iter().map(|file| file.size).sum()
+    }
+
+    /// Gets the last modification time of the oldest data file.
+    ///
+    /// As data files are only ever appended to, and are deleted once all of their records have been
+    /// acknowledged, the oldest record in the buffer was written no later than this. The time since
+    /// then is a lower bound on the age of that record, which may be older if the file was written
+    /// to after it.
+    pub fn oldest_modified(&self) -> Option<SystemTime> {
+        self.data_files
+            .iter()
+            .filter_map(|file| file.modified)
+            .min()
+    }
+}
+
+/// Inspects the files of the disk buffer with the given ID.
+///
+/// If the buffer does not exist, `None` is returned.
+///
+/// # Errors
+///
+/// If the buffer directory, or any of the files within it, cannot be read, an error variant will be
+/// returned.
+pub fn inspect_disk_buffer(base_dir: &Path, buffer_id: &str) -> io::Result<Option<DiskBufferInfo>> {
+    let path = get_disk_v2_data_dir_path(base_dir, buffer_id);
+    if !path.is_dir() {
+        return Ok(None);
+    }
+
+    let data_files = get_data_files(&path)?;
+
+    // Only check the lock if there's already a lock file, as we don't want inspection to leave any
+    // files behind.
+    let lock_path = path.join("buffer.lock");
+    let in_use = if lock_path.exists() {
+        let mut lock = LockFile::open(&lock_path)?;
+        !lock.try_lock()?
+    } else {
+        false
+    };
+
+    Ok(Some(DiskBufferInfo {
+        path,
+        data_files,
+        in_use,
+    }))
+}
+
+/// Purges the disk buffer with the given ID, deleting all of the records it holds.
+///
+/// Returns the number of bytes of data files that were deleted, or `None` if the buffer does not
+/// exist.
+///
+/// # Errors
+///
+/// If the buffer is in use by a Vector process, or its files cannot be removed, an error variant
+/// will be returned.
+pub fn purge_disk_buffer(base_dir: &Path, buffer_id: &str) -> Result<Option<u64>, PurgeError> {
+    let path = get_disk_v2_data_dir_path(base_dir, buffer_id);
+    if !path.is_dir() {
+        return Ok(None);
+    }
+
+    // Hold the lock for as long as we're removing files, so that a Vector process can't load the
+    // buffer while it's only partially removed.
+    let lock_path = path.join("buffer.lock");
+    let mut lock = LockFile::open(&lock_path).context(IoSnafu)?;
+    if !lock.try_lock().context(IoSnafu)? {
+        return Err(PurgeError::BufferInUse);
+    }
+
+    let data_files = get_data_files(&path).context(IoSnafu)?;
+    for file in &data_files {
+        fs::remove_file(&file.path).context(IoSnafu)?;
+    }
+
+    // Without its data files, the ledger would point at records that no longer exist, so it has to
+    // go as well.  The buffer recreates a fresh ledger the next time it is loaded.
+    if let Err(e) = fs::remove_file(path.join("buffer.db")) {
+        if e.kind() != io::ErrorKind::NotFound {
+            return Err(PurgeError::Io { source: e });
+        }
+    }
+
+    drop(lock);
+    fs::remove_dir_all(&path).context(IoSnafu)?;
+
+    Ok(Some(data_files.iter().map(|file| file.size).sum()))
+}
+
+fn get_data_files(path: &Path) -> io::Result<Vec<DiskBufferDataFile>> {
+    let mut data_files = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        if !file_name.starts_with("buffer-data-") || !file_name.ends_with(".dat") {
+            continue;
+        }
+
+        let metadata = entry.metadata()?;
+        data_files.push(DiskBufferDataFile {
+            path: entry.path(),
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+        });
+    }
+
+    // File IDs wrap around, so the modification time is the only reliable way to order them.
+    data_files.sort_by_key(|file| file.modified);
+
+    Ok(data_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use temp_dir::TempDir;
+
+    use super::{inspect_disk_buffer, purge_disk_buffer, PurgeError};
+    use crate::variants::disk_v2::get_disk_v2_data_dir_path;
+
+    #[test]
+    fn inspect_and_purge() {
+        let dir = TempDir::new().expect("should not fail to create temp dir");
+        let buffer_path = get_disk_v2_data_dir_path(dir.path(), "test");
+        fs::create_dir_all(&buffer_path).unwrap();
+        fs::write(buffer_path.join("buffer-data-0.dat"), [0; 16]).unwrap();
+        fs::write(buffer_path.join("buffer-data-1.dat"), [0; 32]).unwrap();
+        fs::write(buffer_path.join("buffer.db"), [0; 8]).unwrap();
+
+        let info = inspect_disk_buffer(dir.path(), "test")
+            .unwrap()
+            .expect("buffer should exist");
+        assert_eq!(info.data_files.len(), 2);
+        assert_eq!(info.total_size(), 48);
+        assert!(!info.in_use);
+
+        assert_eq!(purge_disk_buffer(dir.path(), "test").unwrap(), Some(48));
+        assert!(!buffer_path.exists());
+        assert!(inspect_disk_buffer(dir.path(), "test").unwrap().is_none());
+    }
+
+    #[test]
+    fn purge_refuses_buffer_in_use() {
+        let dir = TempDir::new().expect("should not fail to create temp dir");
+        let buffer_path = get_disk_v2_data_dir_path(dir.path(), "test");
+        fs::create_dir_all(&buffer_path).unwrap();
+
+        let mut lock = fslock::LockFile::open(&buffer_path.join("buffer.lock")).unwrap();
+        assert!(lock.try_lock().unwrap());
+
+        match purge_disk_buffer(dir.path(), "test") {
+            Err(PurgeError::BufferInUse) => {}
+            r => panic!("unexpected purge result: {:?}", r),
+        }
+    }
+}
@@ -149,11 +149,15 @@ use snafu::{ResultExt, Snafu};
 mod acknowledgements;
 mod backed_archive;
 mod common;
+mod compression;
 mod io;
 mod ledger;
+mod management;
 mod reader;
 mod record;
 mod ser;
+#[cfg(all(target_os = "linux", feature = "io_uring"))]
+mod uring;
 mod v1_migration;
 mod writer;
 
@@ -164,9 +168,12 @@ use self::{
     acknowledgements::create_disk_v2_acker, ledger::Ledger, v1_migration::try_disk_v1_migration,
 };
 pub use self::{
-    common::{DiskBufferConfig, DiskBufferConfigBuilder},
-    io::{Filesystem, ProductionFilesystem},
+    common::{DiskBufferCompression, DiskBufferConfig, DiskBufferConfigBuilder},
+    io::{Filesystem, ProductionFile, ProductionFilesystem},
     ledger::LedgerLoadCreateError,
+    management::{
+        inspect_disk_buffer, purge_disk_buffer, DiskBufferDataFile, DiskBufferInfo, PurgeError,
+    },
     reader::{Reader, ReaderError},
     writer::{Writer, WriterError},
 };
@@ -227,11 +234,14 @@ where
             .await
             .context(WriterSeekFailedSnafu)?;
 
+        // When recovering lazily, the reader seeks to where it left off on its first read instead.
         let mut reader = Reader::new(Arc::clone(&ledger));
-        reader
-            .seek_to_next_record()
-            .await
-            .context(ReaderSeekFailedSnafu)?;
+        if !ledger.config().lazy_recovery {
+            reader
+                .seek_to_next_record()
+                .await
+                .context(ReaderSeekFailedSnafu)?;
+        }
 
         ledger.synchronize_buffer_usage();
 
@@ -270,14 +280,24 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    compression: DiskBufferCompression,
+    io_uring: bool,
 }
 
 impl DiskV2Buffer {
-    pub fn new(id: String, data_dir: PathBuf, max_size: NonZeroU64) -> Self {
+    pub fn new(
+        id: String,
+        data_dir: PathBuf,
+        max_size: NonZeroU64,
+        compression: DiskBufferCompression,
+        io_uring: bool,
+    ) -> Self {
         Self {
             id,
             data_dir,
             max_size,
+            compression,
+            io_uring,
         }
     }
 }
@@ -306,6 +326,8 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.compression,
+            self.io_uring,
         )
         .await?;
 
@@ -318,6 +340,8 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    compression: DiskBufferCompression,
+    io_uring: bool,
 ) -> Result<
     (
         Writer<T, ProductionFilesystem>,
@@ -334,6 +358,9 @@ where
     let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
     let config = DiskBufferConfigBuilder::from_path(buffer_path)
         .max_buffer_size(max_size.get())
+        .compression(compression)
+        .lazy_recovery(true)
+        .io_uring(io_uring)
         .build()?;
     Buffer::from_config(config, usage_handle)
         .await
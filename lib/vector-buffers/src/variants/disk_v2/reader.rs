@@ -15,6 +15,7 @@ use vector_common::internal_event::emit;
 
 use super::{
     common::create_crc32c_hasher,
+    compression::{RecordDecompressor, RECORD_METADATA_COMPRESSED_ZSTD},
     ledger::Ledger,
     record::{validate_record_archive, ArchivedRecord, Record, RecordStatus},
     Filesystem,
@@ -27,6 +28,7 @@ use crate::{
     Bufferable,
 };
 
+#[derive(Debug)]
 pub(super) struct ReadToken {
     record_id: u64,
     record_bytes: usize,
@@ -173,6 +175,7 @@ pub(super) struct RecordReader<R, T> {
     reader: BufReader<R>,
     aligned_buf: AlignedVec,
     checksummer: Hasher,
+    decompressor: RecordDecompressor,
     current_record_id: u64,
     _t: PhantomData<T>,
 }
@@ -186,11 +189,14 @@ where
     ///
     /// Internally, the reader is wrapped in a [`BufReader`], so callers should not pass in an
     /// already buffered reader.
-    pub fn new(reader: R) -> Self {
+    ///
+    /// Compressed records are never allowed to decompress to more than `max_record_size` bytes.
+    pub fn new(reader: R, max_record_size: usize) -> Self {
         Self {
             reader: BufReader::with_capacity(256 * 1024, reader),
             aligned_buf: AlignedVec::new(),
             checksummer: create_crc32c_hasher(),
+            decompressor: RecordDecompressor::new(max_record_size),
             current_record_id: 0,
             _t: PhantomData,
         }
@@ -350,7 +356,7 @@ where
         // - `try_next_record` does all the archive checks, checksum validation, etc
         let record = unsafe { archived_root::<Record<'_>>(&self.aligned_buf) };
 
-        decode_record_payload(record)
+        decode_record_payload(record, &mut self.decompressor)
     }
 }
 
@@ -363,6 +369,7 @@ where
             .field("reader", &self.reader)
             .field("aligned_buf", &self.aligned_buf)
             .field("checksummer", &self.checksummer)
+            .field("decompressor", &self.decompressor)
             .field("current_record_id", &self.current_record_id)
             .finish()
     }
@@ -382,6 +389,12 @@ where
     data_file_record_count: u64,
     data_file_marked_record_count: u64,
     ready_to_read: bool,
+    /// A record read while seeking that is past where the reader left off, kept for the first read.
+    unread: Option<ReadToken>,
+    /// The ID of the first record written after the buffer was loaded.
+    first_new_record_id: u64,
+    /// The bytes of the records, and data files, skipped over while seeking.
+    skipped_bytes: u64,
     record_acks: OrderedAcknowledgements<u64, u64>,
     data_file_acks: OrderedAcknowledgements<u64, (PathBuf, u64)>,
     _t: PhantomData<T>,
@@ -397,6 +410,7 @@ where
     pub(crate) fn new(ledger: Arc<Ledger<FS>>) -> Self {
         let ledger_last_reader_record_id = ledger.state().get_last_reader_record_id();
         let next_expected_record_id = ledger_last_reader_record_id.wrapping_add(1);
+        let first_new_record_id = ledger.state().get_next_writer_record_id();
 
         Self {
             ledger,
@@ -407,6 +421,9 @@ where
             data_file_record_count: 0,
             data_file_marked_record_count: 0,
             ready_to_read: false,
+            unread: None,
+            first_new_record_id,
+            skipped_bytes: 0,
             record_acks: OrderedAcknowledgements::from_acked(next_expected_record_id),
             data_file_acks: OrderedAcknowledgements::from_acked(0),
             _t: PhantomData,
@@ -441,6 +458,7 @@ where
         self.bytes_read += record_bytes;
         if !self.ready_to_read {
             self.ledger.decrement_total_buffer_size(record_bytes);
+            self.skipped_bytes += record_bytes;
             return;
         }
 
@@ -464,7 +482,7 @@ where
         &mut self,
         data_file_path: PathBuf,
         bytes_read: Option<u64>,
-    ) -> io::Result<()> {
+    ) -> io::Result<u64> {
         // TODO: Could we actually make this a background task to remove the tail latency from the
         // read path?  Technically all that's needed is a handle to the ledger and the data file
         // path, so as long as the logic is still right, we can notify writers out-of-band.
@@ -528,7 +546,7 @@ where
         // because they're looking to reuse the file ID of the file we just finished reading.
         self.ledger.notify_reader_waiters();
 
-        Ok(())
+        Ok(decrease_amount)
     }
 
     #[cfg_attr(test, instrument(skip(self), level = "debug"))]
@@ -770,7 +788,10 @@ where
                 "Opened data file for reading."
             );
 
-            self.reader = Some(RecordReader::new(data_file));
+            self.reader = Some(RecordReader::new(
+                data_file,
+                self.ledger.config().max_record_size,
+            ));
             return Ok(());
         }
     }
@@ -835,7 +856,9 @@ where
                 } => {
                     let record = try_as_record_archive(data_file_mmap.as_ref())
                         .expect("record was already validated");
-                    let item = match decode_record_payload::<T>(record) {
+                    let mut decompressor =
+                        RecordDecompressor::new(self.ledger.config().max_record_size);
+                    let item = match decode_record_payload::<T>(record, &mut decompressor) {
                         Ok(item) => item,
                         // If there's an error decoding the item, just fall back to the slow path,
                         // because this file might actually be where we left off, so we don't want
@@ -862,7 +885,8 @@ where
                         // By passing 0 bytes, `delete_completed_data_file` does the work of
                         // ensuring the buffer size is updated to reflect the data file being
                         // deleted in its entirety.
-                        self.delete_completed_data_file(data_file_path, None)
+                        self.skipped_bytes += self
+                            .delete_completed_data_file(data_file_path, None)
                             .await
                             .context(IoSnafu)?;
                         self.reset();
@@ -884,11 +908,13 @@ where
         // update `self.last_reader_record_id`, so basically... just keep reading records until
         // we're past the last record we had acknowledged.
         while self.last_reader_record_id < ledger_last {
-            if self.next().await?.is_none() && self.last_reader_record_id == 0 {
+            if self.next_record().await?.is_none()
+                && (self.last_reader_record_id == 0 || self.unread.is_some())
+            {
                 // We've hit a point where there's no more data to read.  If our "last reader record
                 // ID" hasn't moved at all, that means the buffer was already empty and we're caught
                 // up, so we just pin ourselves to where the ledger says we left off, and we're good
-                // to go.
+                // to go.  Likewise if the next record was written after the buffer was loaded.
                 self.last_reader_record_id = ledger_last;
                 break;
             }
@@ -909,15 +935,34 @@ where
     /// If the writer is closed and there is no more data in the buffer, `None` is returned.
     /// Otherwise, reads the next record or waits until the next record is available.
     ///
+    /// When the buffer recovers lazily, the first read seeks to where the reader left off first.
+    ///
     /// # Errors
     ///
     /// If an error occurred while reading a record, an error variant will be returned describing
     /// the error.
-    #[cfg_attr(test, instrument(skip(self), level = "trace"))]
     pub async fn next(&mut self) -> Result<Option<T>, ReaderError<T>> {
+        if !self.ready_to_read {
+            self.seek_to_next_record().await?;
+
+            // The records, and data files, skipped over were counted in the buffer usage when the
+            // buffer was loaded, so they have to be taken back out now.
+            self.ledger
+                .track_skipped_reads(std::mem::take(&mut self.skipped_bytes));
+        }
+
+        self.next_record().await
+    }
+
+    #[cfg_attr(test, instrument(skip(self), level = "trace"))]
+    async fn next_record(&mut self) -> Result<Option<T>, ReaderError<T>> {
         let mut force_check_pending_data_files = false;
 
         let token = loop {
+            if let Some(token) = self.unread.take() {
+                break token;
+            }
+
             // Handle any pending acknowledgements first.
             self.handle_pending_acknowledgements(force_check_pending_data_files)
                 .await
@@ -963,8 +1008,20 @@ where
                 // Not even enough data to read a length delimiter, so we need to wait for the
                 // writer to signal us that there's some actual data to read.
                 Ok(None) => {}
-                // We got a valid record, so keep the token.
-                Ok(Some(token)) => break token,
+                // We got a valid record, so keep the token.  While seeking lazily, though, records
+                // written since the buffer was loaded are past where the reader left off, so the
+                // record is kept for the first real read instead.  Record IDs only wrap around
+                // after 2^64 events, so comparing them directly is fine here.
+                Ok(Some(token)) => {
+                    if !self.ready_to_read
+                        && self.ledger.config().lazy_recovery
+                        && token.record_id() >= self.first_new_record_id
+                    {
+                        self.unread = Some(token);
+                        return Ok(None);
+                    }
+                    break token;
+                }
                 // A length-delimited payload was read, but we failed to deserialize it as a valid
                 // record, or we deseralized it and the checksum was invalid.  Either way, we're not
                 // sure the rest of the data file is even valid, so roll to the next file.
@@ -1083,10 +1140,15 @@ where
 
 pub(crate) fn decode_record_payload<T: Bufferable>(
     record: &ArchivedRecord<'_>,
+    decompressor: &mut RecordDecompressor,
 ) -> Result<T, ReaderError<T>> {
+    // Strip out the buffer-level compression flag, which is never part of the metadata of `T`.
+    let compressed = record.metadata() & RECORD_METADATA_COMPRESSED_ZSTD != 0;
+    let raw_metadata = record.metadata() & !RECORD_METADATA_COMPRESSED_ZSTD;
+
     // Try and convert the raw record metadata into the true metadata type used by `T`, and then
     // also verify that `T` is able to decode records with the metadata used for this record in particular.
-    let metadata = T::Metadata::from_u32(record.metadata()).ok_or(ReaderError::Incompatible {
+    let metadata = T::Metadata::from_u32(raw_metadata).ok_or(ReaderError::Incompatible {
         reason: format!("invalid metadata for {}", std::any::type_name::<T>()),
     })?;
 
@@ -1099,6 +1161,15 @@ pub(crate) fn decode_record_payload<T: Bufferable>(
         });
     }
 
-    // Now we can finally try decoding.
-    T::decode(metadata, record.payload()).context(DecodeSnafu)
+    // Now we can finally try decoding, decompressing the payload first if need be.
+    if compressed {
+        let payload = decompressor.decompress(record.payload()).map_err(|e| {
+            ReaderError::Deserialization {
+                reason: format!("failed to decompress record payload: {}", e),
+            }
+        })?;
+        T::decode(metadata, payload).context(DecodeSnafu)
+    } else {
+        T::decode(metadata, record.payload()).context(DecodeSnafu)
+    }
 }
@@ -4,11 +4,17 @@ use futures::{stream, StreamExt};
 use tokio_test::{assert_pending, assert_ready, task::spawn};
 use tracing::Instrument;
 
-use super::create_default_buffer_v2;
+use super::{
+    create_buffer_v2_with_compression, create_buffer_v2_with_lazy_recovery,
+    create_default_buffer_v2,
+};
 use crate::{
     assert_buffer_is_empty, assert_buffer_records,
     test::common::{install_tracing_helpers, with_temp_dir, MultiEventRecord, SizedRecord},
-    variants::disk_v2::{tests::create_default_buffer_v2_with_usage, writer::RecordWriter},
+    variants::disk_v2::{
+        tests::create_default_buffer_v2_with_usage, writer::RecordWriter, DiskBufferCompression,
+        DiskBufferConfigBuilder,
+    },
     EventCount,
 };
 
@@ -66,6 +72,133 @@ async fn basic_read_write_loop() {
     .await;
 }
 
+#[tokio::test]
+async fn compressed_read_write_loop() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Create a buffer that compresses every record it writes.
+            let (mut writer, mut reader, acker, ledger) = create_buffer_v2_with_compression(
+                data_dir,
+                DiskBufferCompression::Zstd { level: 3 },
+            )
+            .await;
+            assert_buffer_is_empty!(ledger);
+
+            let expected_items = (512..768)
+                .into_iter()
+                .cycle()
+                .take(10)
+                .map(SizedRecord)
+                .collect::<Vec<_>>();
+
+            for item in expected_items.clone() {
+                writer
+                    .write_record(item)
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("writer flush should not fail");
+            writer.close();
+
+            // Records should be transparently decompressed when read back.
+            let mut actual_items = Vec::new();
+            while let Some(record) = reader.next().await.expect("reader should not fail") {
+                actual_items.push(record);
+                acker.ack(1);
+            }
+
+            // All records should be consumed at this point.
+            assert_buffer_is_empty!(ledger);
+
+            // Make sure we got the right items.
+            assert_eq!(actual_items, expected_items);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn mixed_compressed_and_uncompressed_records() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let uncompressed_items = (512..768)
+                .into_iter()
+                .take(5)
+                .map(SizedRecord)
+                .collect::<Vec<_>>();
+            let compressed_items = (768..1024)
+                .into_iter()
+                .take(5)
+                .map(SizedRecord)
+                .collect::<Vec<_>>();
+
+            // Write some records without compression, and then close the buffer.
+            let (mut writer, _, _, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+            for item in uncompressed_items.clone() {
+                writer
+                    .write_record(item)
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("writer flush should not fail");
+            writer.close();
+            drop(writer);
+            drop(ledger);
+
+            // Reopen the same buffer with compression enabled, and write some more records.
+            let (mut writer, mut reader, acker, ledger) = create_buffer_v2_with_compression(
+                data_dir,
+                DiskBufferCompression::Zstd { level: 3 },
+            )
+            .await;
+            for item in compressed_items.clone() {
+                writer
+                    .write_record(item)
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("writer flush should not fail");
+            writer.close();
+
+            // Both the uncompressed and compressed records should be read back, in order.
+            let mut actual_items = Vec::new();
+            while let Some(record) = reader.next().await.expect("reader should not fail") {
+                actual_items.push(record);
+                acker.ack(1);
+            }
+            assert_buffer_is_empty!(ledger);
+
+            let expected_items = uncompressed_items
+                .into_iter()
+                .chain(compressed_items)
+                .collect::<Vec<_>>();
+            assert_eq!(actual_items, expected_items);
+        }
+    })
+    .await;
+}
+
+#[test]
+fn compression_level_must_be_in_range() {
+    for level in [-5, 0, 22] {
+        let result = DiskBufferConfigBuilder::from_path("/tmp/unused")
+            .compression(DiskBufferCompression::Zstd { level })
+            .build();
+        assert!(result.is_err(), "level {} should be rejected", level);
+    }
+
+    for level in [1, 3, 21] {
+        let result = DiskBufferConfigBuilder::from_path("/tmp/unused")
+            .compression(DiskBufferCompression::Zstd { level })
+            .build();
+        assert!(result.is_ok(), "level {} should be accepted", level);
+    }
+}
+
 #[tokio::test]
 async fn reader_exits_cleanly_when_writer_done_and_in_flight_acks() {
     let assertion_registry = install_tracing_helpers();
@@ -139,6 +272,56 @@ async fn reader_exits_cleanly_when_writer_done_and_in_flight_acks() {
     fut.instrument(parent.or_current()).await;
 }
 
+#[tokio::test]
+async fn reader_seeks_lazily_on_first_read() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Write three records, and read two of them, acknowledging only the first.
+            let (mut writer, mut reader, acker, ledger) =
+                create_default_buffer_v2(data_dir.clone()).await;
+            for size in [32, 33, 34] {
+                writer
+                    .write_record(SizedRecord(size))
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("writer flush should not fail");
+
+            let first_read = reader.next().await.expect("read should not fail");
+            assert_eq!(first_read, Some(SizedRecord(32)));
+            acker.ack(1);
+            let second_read = reader.next().await.expect("read should not fail");
+            assert_eq!(second_read, Some(SizedRecord(33)));
+            ledger.flush().expect("flush should not fail");
+
+            drop(writer);
+            drop(reader);
+            drop(acker);
+            drop(ledger);
+
+            // Reopen the buffer with lazy recovery, and write another record before reading
+            // anything, which the reader must not skip over when it seeks on its first read.
+            let (mut writer, mut reader, _, ledger) =
+                create_buffer_v2_with_lazy_recovery(data_dir).await;
+            writer
+                .write_record(SizedRecord(35))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("writer flush should not fail");
+            assert_buffer_records!(ledger, 3);
+
+            for size in [33, 34, 35] {
+                let read = reader.next().await.expect("read should not fail");
+                assert_eq!(read, Some(SizedRecord(size)));
+            }
+        }
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn initial_size_correct_with_multievents() {
     let _a = install_tracing_helpers();
@@ -170,8 +353,14 @@ async fn initial_size_correct_with_multievents() {
             // are identical:
             let expected_bytes = stream::iter(input_items.iter().copied())
                 .filter_map(|record| async move {
-                    let mut record_writer =
-                        RecordWriter::new(Cursor::new(Vec::new()), 0, 16_384, u64::MAX, usize::MAX);
+                    let mut record_writer = RecordWriter::new(
+                        Cursor::new(Vec::new()),
+                        0,
+                        16_384,
+                        u64::MAX,
+                        usize::MAX,
+                        DiskBufferCompression::None,
+                    );
                     let (bytes_written, flush_result) = record_writer
                         .write_record(0, record)
                         .await
@@ -9,7 +9,7 @@ use tokio::io::DuplexStream;
 
 use super::{
     io::{AsyncFile, Metadata, ProductionFilesystem, ReadableMemoryMap, WritableMemoryMap},
-    Buffer, DiskBufferConfigBuilder, Ledger, Reader, Writer,
+    Buffer, DiskBufferCompression, DiskBufferConfigBuilder, Ledger, Reader, Writer,
 };
 use crate::{buffer_usage_data::BufferUsageHandle, Acker, Bufferable, WhenFull};
 
@@ -210,6 +210,51 @@ where
     (writer, reader, acker, ledger, usage_handle)
 }
 
+pub(crate) async fn create_buffer_v2_with_compression<P, R>(
+    data_dir: P,
+    compression: DiskBufferCompression,
+) -> (
+    Writer<R, FilesystemUnderTest>,
+    Reader<R, FilesystemUnderTest>,
+    Acker,
+    Arc<Ledger<FilesystemUnderTest>>,
+)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .compression(compression)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop(WhenFull::Block);
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
+pub(crate) async fn create_buffer_v2_with_lazy_recovery<P, R>(
+    data_dir: P,
+) -> (
+    Writer<R, FilesystemUnderTest>,
+    Reader<R, FilesystemUnderTest>,
+    Acker,
+    Arc<Ledger<FilesystemUnderTest>>,
+)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .lazy_recovery(true)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop(WhenFull::Block);
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
 pub(crate) async fn create_buffer_v2_with_max_buffer_size<P, R>(
     data_dir: P,
     max_buffer_size: u64,
@@ -1,17 +1,50 @@
 use std::io::Cursor;
 
+use rkyv::AlignedVec;
+
 use crate::{
     test::common::SizedRecord,
-    variants::disk_v2::{reader::RecordReader, writer::RecordWriter},
+    variants::disk_v2::{
+        compression::RECORD_METADATA_COMPRESSED_ZSTD,
+        reader::{ReaderError, RecordReader},
+        record::try_as_record_archive,
+        writer::RecordWriter,
+        DiskBufferCompression,
+    },
 };
 
+/// Writes a single record with the given compression, returning the raw bytes written.
+async fn write_single_record(record: SizedRecord, compression: DiskBufferCompression) -> Vec<u8> {
+    let mut record_writer = RecordWriter::new(
+        Cursor::new(Vec::new()),
+        0,
+        16_384,
+        u64::MAX,
+        8192,
+        compression,
+    );
+    record_writer
+        .write_record(314, record)
+        .await
+        .expect("write should not fail");
+    record_writer.flush().await.expect("flush should not fail");
+    record_writer.get_ref().get_ref().clone()
+}
+
 #[tokio::test]
 async fn roundtrip_through_record_writer_and_record_reader() {
     // Create a duplex stream that's more than big enough to ship a record through.
     let (writer_io, reader_io) = tokio::io::duplex(4096);
 
-    let mut record_writer = RecordWriter::new(writer_io, 0, 16_384, u64::MAX, 2048);
-    let mut record_reader = RecordReader::new(reader_io);
+    let mut record_writer = RecordWriter::new(
+        writer_io,
+        0,
+        16_384,
+        u64::MAX,
+        2048,
+        DiskBufferCompression::None,
+    );
+    let mut record_reader = RecordReader::new(reader_io, 2048);
 
     let record = SizedRecord(73);
 
@@ -42,10 +75,61 @@ async fn roundtrip_through_record_writer_and_record_reader() {
 async fn record_reader_always_returns_none_when_no_data() {
     let reader_io = Cursor::new(Vec::new());
 
-    let mut record_reader = RecordReader::<_, SizedRecord>::new(reader_io);
+    let mut record_reader = RecordReader::<_, SizedRecord>::new(reader_io, 2048);
     let read_token = record_reader
         .try_next_record(false)
         .await
         .expect("read should not fail");
     assert!(read_token.is_none());
 }
+
+#[tokio::test]
+async fn compressed_record_is_flagged_and_smaller_on_disk() {
+    // `SizedRecord` encodes as a run of identical bytes, so it should compress very well.
+    let record = SizedRecord(4096);
+    let uncompressed = write_single_record(record.clone(), DiskBufferCompression::None).await;
+    let compressed =
+        write_single_record(record.clone(), DiskBufferCompression::Zstd { level: 3 }).await;
+    assert!(compressed.len() < uncompressed.len());
+
+    // Skip the length delimiter, and copy the archived record into an aligned buffer so that we
+    // can inspect it directly.
+    let mut archive_buf = AlignedVec::new();
+    archive_buf.extend_from_slice(&uncompressed[8..]);
+    let archive = try_as_record_archive(&archive_buf).expect("record should be valid");
+    assert_eq!(archive.metadata() & RECORD_METADATA_COMPRESSED_ZSTD, 0);
+
+    let mut archive_buf = AlignedVec::new();
+    archive_buf.extend_from_slice(&compressed[8..]);
+    let archive = try_as_record_archive(&archive_buf).expect("record should be valid");
+    assert_ne!(archive.metadata() & RECORD_METADATA_COMPRESSED_ZSTD, 0);
+    assert!(archive.payload().len() < 4096);
+
+    // And it should still read back as the original record.
+    let mut record_reader = RecordReader::new(Cursor::new(compressed), 8192);
+    let read_token = record_reader
+        .try_next_record(false)
+        .await
+        .expect("read should not fail")
+        .expect("record should be present");
+    let roundtrip_record = record_reader
+        .read_record(read_token)
+        .expect("read should not fail");
+    assert_eq!(record, roundtrip_record);
+}
+
+#[tokio::test]
+async fn compressed_record_cannot_decompress_past_max_record_size() {
+    let compressed =
+        write_single_record(SizedRecord(4096), DiskBufferCompression::Zstd { level: 3 }).await;
+
+    // The record is valid on disk, but decompresses to more than the reader will allow.
+    let mut record_reader = RecordReader::<_, SizedRecord>::new(Cursor::new(compressed), 1024);
+    let read_token = record_reader
+        .try_next_record(false)
+        .await
+        .expect("read should not fail")
+        .expect("record should be present");
+    let result = record_reader.read_record(read_token);
+    assert!(matches!(result, Err(ReaderError::Deserialization { .. })));
+}
@@ -0,0 +1,342 @@
+//! Data files whose reads, writes and syncs go through `io_uring`, on Linux.
+//!
+//! A single driver thread owns the ring and submits the operations of the data files of every disk
+//! buffer, so operations that are queued together reach the kernel with one system call rather than
+//! one each.  The driver is started the first time a data file is opened with `io_uring`, and if
+//! the ring can't be set up, such as on kernels older than 5.6 or when `io_uring` is forbidden by a
+//! seccomp profile, data files fall back to regular file I/O.
+
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    future::Future,
+    io,
+    os::unix::io::AsRawFd,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    thread,
+};
+
+use async_trait::async_trait;
+use futures::ready;
+use io_uring::{opcode, squeue, types, IoUring};
+use once_cell::sync::OnceCell;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    sync::{mpsc, oneshot},
+};
+
+use super::io::{AsyncFile, Metadata};
+
+/// The number of operations the ring holds at once.
+const RING_ENTRIES: u32 = 256;
+
+/// The largest read submitted at once.
+const MAX_READ_SIZE: usize = 256 * 1024;
+
+static DRIVER: OnceCell<Option<mpsc::UnboundedSender<Submission>>> = OnceCell::new();
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum OperationKind {
+    Read,
+    Write,
+    Fsync,
+}
+
+struct Submission {
+    // The file is kept open until its operation completes, even if it was dropped in the meantime,
+    // so that its descriptor can't be reused for another file.
+    file: Arc<File>,
+    kind: OperationKind,
+    buf: Vec<u8>,
+    done: oneshot::Sender<Completion>,
+}
+
+struct Completion {
+    result: io::Result<usize>,
+    buf: Vec<u8>,
+}
+
+impl Submission {
+    #[allow(clippy::cast_possible_truncation)]
+    fn entry(&mut self) -> squeue::Entry {
+        let fd = types::Fd(self.file.as_raw_fd());
+        // An offset of -1 uses, and advances, the position of the file, which for files opened in
+        // append mode means writes always go to the end of the file.
+        match self.kind {
+            OperationKind::Read => {
+                opcode::Read::new(fd, self.buf.as_mut_ptr(), self.buf.len() as u32)
+                    .offset(-1)
+                    .build()
+            }
+            OperationKind::Write => {
+                opcode::Write::new(fd, self.buf.as_ptr(), self.buf.len() as u32)
+                    .offset(-1)
+                    .build()
+            }
+            OperationKind::Fsync => opcode::Fsync::new(fd).build(),
+        }
+    }
+}
+
+/// Gets the queue of the driver, starting the driver if it isn't running yet.
+///
+/// Returns `None` if `io_uring` isn't available.
+fn driver() -> Option<&'static mpsc::UnboundedSender<Submission>> {
+    DRIVER
+        .get_or_init(|| {
+            let ring = match IoUring::new(RING_ENTRIES) {
+                Ok(ring) => ring,
+                Err(error) => {
+                    warn!(
+                        message = "Could not set up io_uring, using regular file I/O for disk buffers.",
+                        %error,
+                    );
+                    return None;
+                }
+            };
+            let (tx, rx) = mpsc::unbounded_channel();
+            let spawned = thread::Builder::new()
+                .name("vector-buffer-uring".to_owned())
+                .spawn(move || run_driver(ring, rx));
+            match spawned {
+                Ok(_) => Some(tx),
+                Err(error) => {
+                    warn!(
+                        message = "Could not start the io_uring driver, using regular file I/O for disk buffers.",
+                        %error,
+                    );
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+fn run_driver(mut ring: IoUring, mut rx: mpsc::UnboundedReceiver<Submission>) {
+    // The buffers of the operations in flight are kept here until the kernel is done with them.
+    let mut in_flight: HashMap<u64, Submission> = HashMap::new();
+    let mut next_id = 0_u64;
+
+    loop {
+        // Only block waiting for operations when none are in flight, otherwise submit whatever is
+        // queued and go on reaping completions.
+        let mut queued = Vec::new();
+        if in_flight.is_empty() {
+            match rx.blocking_recv() {
+                Some(submission) => queued.push(submission),
+                None => return,
+            }
+        }
+        while in_flight.len() + queued.len() < RING_ENTRIES as usize {
+            match rx.try_recv() {
+                Ok(submission) => queued.push(submission),
+                Err(_) => break,
+            }
+        }
+
+        for mut submission in queued {
+            let entry = submission.entry().user_data(next_id);
+            // SAFETY: The buffer of the operation is owned by the submission, which is kept in
+            // `in_flight` until the completion of the operation is reaped.
+            unsafe { ring.submission().push(&entry) }.expect(
+                "submission queue can't be full with fewer operations in flight than entries",
+            );
+            in_flight.insert(next_id, submission);
+            next_id = next_id.wrapping_add(1);
+        }
+
+        // Operations the kernel didn't take stay in the submission queue, and are submitted again
+        // on the next pass.
+        if let Err(error) = ring.submit_and_wait(1) {
+            if error.kind() != io::ErrorKind::Interrupted {
+                warn!(message = "Could not submit disk buffer operations to io_uring.", %error);
+            }
+        }
+
+        for entry in ring.completion() {
+            if let Some(submission) = in_flight.remove(&entry.user_data()) {
+                let result = usize::try_from(entry.result())
+                    .map_err(|_| io::Error::from_raw_os_error(-entry.result()));
+                // The file may have been dropped while the operation was in flight.
+                let _ = submission.done.send(Completion {
+                    result,
+                    buf: submission.buf,
+                });
+            }
+        }
+    }
+}
+
+/// Opens a file for writing, in append mode, whose operations go through `io_uring`.
+///
+/// Returns `None` if `io_uring` isn't available, in which case the file should be opened for
+/// regular file I/O instead.
+pub(super) fn open_writable(path: &Path, create_new: bool) -> Option<io::Result<UringFile>> {
+    let driver = driver()?;
+    let mut options = OpenOptions::new();
+    options.append(true).read(true);
+    if create_new {
+        options.create_new(true);
+    } else {
+        options.create(true);
+    }
+    Some(options.open(path).map(|file| UringFile {
+        file: Arc::new(file),
+        driver,
+        pending: None,
+    }))
+}
+
+struct Pending {
+    kind: OperationKind,
+    done: oneshot::Receiver<Completion>,
+}
+
+/// A data file whose operations go through `io_uring`.
+///
+/// Writes are acknowledged as soon as they are queued and are completed in the background, one at a
+/// time and in order.  Flushing or reading the file first waits for the write in flight, and returns
+/// its error if it failed.
+pub struct UringFile {
+    file: Arc<File>,
+    driver: &'static mpsc::UnboundedSender<Submission>,
+    pending: Option<Pending>,
+}
+
+impl UringFile {
+    fn submit(&mut self, kind: OperationKind, buf: Vec<u8>) -> io::Result<()> {
+        let (done, rx) = oneshot::channel();
+        self.driver
+            .send(Submission {
+                file: Arc::clone(&self.file),
+                kind,
+                buf,
+                done,
+            })
+            .map_err(|_| driver_stopped())?;
+        self.pending = Some(Pending { kind, done: rx });
+        Ok(())
+    }
+
+    /// Waits for the operation in flight, if any, to complete, and returns its completion.
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<Option<(OperationKind, Completion)>> {
+        let pending = match self.pending.as_mut() {
+            Some(pending) => pending,
+            None => return Poll::Ready(None),
+        };
+        let completion =
+            ready!(Pin::new(&mut pending.done).poll(cx)).unwrap_or_else(|_| Completion {
+                result: Err(driver_stopped()),
+                buf: Vec::new(),
+            });
+        let kind = pending.kind;
+        self.pending = None;
+        Poll::Ready(Some((kind, completion)))
+    }
+
+    /// Waits for the write or sync in flight, if any, to complete.
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while let Some((kind, completion)) = ready!(self.poll_pending(cx)) {
+            let written = completion.result?;
+            // Writes to regular files are rarely short, but if one is, write the remainder.
+            if kind == OperationKind::Write && written < completion.buf.len() {
+                let mut buf = completion.buf;
+                buf.drain(..written);
+                self.submit(OperationKind::Write, buf)?;
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncRead for UringFile {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            match this.pending.as_ref().map(|pending| pending.kind) {
+                Some(OperationKind::Read) => {
+                    if let Some((_, completion)) = ready!(this.poll_pending(cx)) {
+                        let read = completion.result?;
+                        buf.put_slice(&completion.buf[..read]);
+                    }
+                    return Poll::Ready(Ok(()));
+                }
+                Some(_) => ready!(this.poll_idle(cx))?,
+                None => {
+                    let len = buf.remaining().min(MAX_READ_SIZE);
+                    this.submit(OperationKind::Read, vec![0; len])?;
+                }
+            }
+        }
+    }
+}
+
+impl AsyncWrite for UringFile {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_idle(cx))?;
+        this.submit(OperationKind::Write, buf.to_vec())?;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_idle(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_idle(cx)
+    }
+}
+
+#[async_trait]
+impl AsyncFile for UringFile {
+    async fn metadata(&self) -> io::Result<Metadata> {
+        let metadata = self.file.metadata()?;
+        Ok(Metadata {
+            len: metadata.len(),
+        })
+    }
+
+    async fn sync_all(&self) -> io::Result<()> {
+        // The write in flight, if any, can't be waited for through a shared reference, so the
+        // sync is submitted on its own: the kernel doesn't order operations of a ring, but syncs
+        // cover every write that completed before them, and `Writer` flushes before it syncs.
+        let (done, rx) = oneshot::channel();
+        self.driver
+            .send(Submission {
+                file: Arc::clone(&self.file),
+                kind: OperationKind::Fsync,
+                buf: Vec::new(),
+                done,
+            })
+            .map_err(|_| driver_stopped())?;
+        rx.await.map_err(|_| driver_stopped())?.result.map(|_| ())
+    }
+}
+
+impl std::fmt::Debug for UringFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UringFile")
+            .field("file", &self.file)
+            .field(
+                "pending",
+                &self.pending.as_ref().map(|pending| pending.kind),
+            )
+            .finish()
+    }
+}
+
+fn driver_stopped() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "io_uring driver stopped")
+}
@@ -24,13 +24,14 @@ use snafu::{ResultExt, Snafu};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use super::{
-    common::{create_crc32c_hasher, DiskBufferConfig},
+    common::{create_crc32c_hasher, DiskBufferCompression, DiskBufferConfig},
+    compression::{RecordCompressor, RecordDecompressor, RECORD_METADATA_COMPRESSED_ZSTD},
     io::Filesystem,
     ledger::Ledger,
     record::{validate_record_archive, Record, RecordStatus},
 };
 use crate::{
-    encoding::{AsMetadata, Encodable},
+    encoding::{AsMetadata, Encodable, RESERVED_METADATA_BITS},
     variants::disk_v2::{
         io::AsyncFile, reader::decode_record_payload, record::try_as_record_archive,
     },
@@ -346,6 +347,8 @@ impl<W: fmt::Debug> fmt::Debug for TrackingBufWriter<W> {
 pub(super) struct RecordWriter<W, T> {
     writer: TrackingBufWriter<W>,
     encode_buf: Vec<u8>,
+    compressor: RecordCompressor,
+    last_payload_len: usize,
     ser_buf: AlignedVec,
     ser_scratch: AlignedVec,
     checksummer: Hasher,
@@ -370,10 +373,13 @@ where
         write_buffer_size: usize,
         max_data_file_size: u64,
         max_record_size: usize,
+        compression: DiskBufferCompression,
     ) -> Self {
         Self {
             writer: TrackingBufWriter::with_capacity(write_buffer_size, writer),
             encode_buf: Vec::with_capacity(16_384),
+            compressor: RecordCompressor::new(compression),
+            last_payload_len: 0,
             ser_buf: AlignedVec::with_capacity(16_384),
             ser_scratch: AlignedVec::with_capacity(16_384),
             checksummer: create_crc32c_hasher(),
@@ -443,9 +449,28 @@ where
             });
         }
 
-        let metadata = T::get_metadata().into_u32();
-        let wrapped_record =
-            Record::with_checksum(id, metadata, &self.encode_buf, &self.checksummer);
+        let mut metadata = T::get_metadata().into_u32();
+        if metadata & RESERVED_METADATA_BITS != 0 {
+            return Err(WriterError::InconsistentState {
+                reason: format!(
+                    "record metadata uses reserved bits (metadata: {:#034b})",
+                    metadata
+                ),
+            });
+        }
+
+        // If compression is enabled, compress the encoded record and flag it as such in the record
+        // metadata so that the reader knows to decompress it before decoding.
+        if self.compressor.is_enabled() {
+            metadata |= RECORD_METADATA_COMPRESSED_ZSTD;
+        }
+        let payload = self.compressor.compress(&self.encode_buf).map_err(|e| {
+            WriterError::FailedToSerialize {
+                reason: format!("failed to compress encoded record: {}", e),
+            }
+        })?;
+        let payload_len = payload.len();
+        let wrapped_record = Record::with_checksum(id, metadata, payload, &self.checksummer);
 
         // Push 8 dummy bytes where our length delimiter will sit.  We'll fix this up after
         // serialization.  Notably, `AlignedSerializer` will report the serializer position as
@@ -521,6 +546,8 @@ where
         let length_delimiter_dst = &mut self.ser_buf.as_mut_slice()[0..8];
         length_delimiter_dst.copy_from_slice(&archive_len_buf[..]);
 
+        self.last_payload_len = payload_len;
+
         Ok(serializer_pos)
     }
 
@@ -559,9 +586,22 @@ where
         // Update our current data file size.
         self.current_data_file_size += serialized_len as u64;
 
+        if self.compressor.is_enabled() {
+            self.compressor
+                .track_written(self.encode_buf.len(), self.last_payload_len);
+        }
+
         Ok((serialized_len, flush_result))
     }
 
+    /// Takes the total logical and compressed sizes, in bytes, of all records written since the
+    /// last call.
+    ///
+    /// If compression is disabled, both values will always be zero.
+    pub fn take_compression_totals(&mut self) -> (u64, u64) {
+        self.compressor.take_totals()
+    }
+
     /// Flushes the writer.
     ///
     /// This flushes both the internal buffered writer and the underlying writer object.
@@ -756,7 +796,8 @@ where
                 // next writer record ID should be.
                 let record = try_as_record_archive(data_file_mmap.as_ref())
                     .expect("record was already validated");
-                let item = decode_record_payload::<T>(record).map_err(|e| {
+                let mut decompressor = RecordDecompressor::new(self.config.max_record_size);
+                let item = decode_record_payload::<T>(record, &mut decompressor).map_err(|e| {
                     WriterError::FailedToValidate {
                         reason: e.to_string(),
                     }
@@ -1000,6 +1041,7 @@ where
                     self.config.write_buffer_size,
                     self.config.max_data_file_size,
                     self.config.max_record_size,
+                    self.config.compression,
                 ));
                 self.data_file_size = data_file_size;
 
@@ -1132,6 +1174,14 @@ where
         if let Some(writer) = self.writer.as_mut() {
             writer.flush().await?;
             self.ledger.notify_writer_waiters();
+
+            // Report the compression statistics for everything written since the last flush in
+            // one go, rather than for every single record.
+            let (logical_byte_size, compressed_byte_size) = writer.take_compression_totals();
+            if logical_byte_size > 0 {
+                self.ledger
+                    .track_compression(logical_byte_size, compressed_byte_size);
+            }
         }
 
         if self.ledger.should_flush() || force_full_flush {
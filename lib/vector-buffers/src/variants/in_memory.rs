@@ -26,6 +26,10 @@ impl<T> IntoBuffer<T> for MemoryBuffer
 where
     T: Bufferable,
 {
+    fn supports_drop_oldest(&self) -> bool {
+        true
+    }
+
     async fn into_buffer_parts(
         self: Box<Self>,
         usage_handle: BufferUsageHandle,
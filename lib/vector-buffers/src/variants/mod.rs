@@ -2,7 +2,10 @@ pub(crate) mod disk_v1;
 pub use disk_v1::DiskV1Buffer;
 
 pub(crate) mod disk_v2;
-pub use disk_v2::DiskV2Buffer;
+pub use disk_v2::{
+    inspect_disk_buffer, purge_disk_buffer, DiskBufferCompression, DiskBufferDataFile,
+    DiskBufferInfo, DiskV2Buffer, PurgeError,
+};
 
 pub(crate) mod in_memory;
 pub use in_memory::MemoryBuffer;
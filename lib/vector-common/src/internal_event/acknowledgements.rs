@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use metrics::{counter, histogram};
+use tracing::{trace, warn};
+
+use crate::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AcknowledgementReceived {
+    pub status: &'static str,
+    pub latency: Duration,
+}
+
+impl InternalEvent for AcknowledgementReceived {
+    fn emit(self) {
+        trace!(message = "Acknowledgement received.", status = %self.status, latency = ?self.latency);
+        histogram!("source_acknowledgement_latency_seconds", self.latency, "status" => self.status);
+    }
+}
+
+#[derive(Debug)]
+pub struct AcknowledgementTimedOut {
+    pub timeout: Duration,
+}
+
+impl InternalEvent for AcknowledgementTimedOut {
+    fn emit(self) {
+        warn!(
+            message = "Acknowledgement not received in time; treating the batch as errored.",
+            timeout_secs = %self.timeout.as_secs_f64(),
+            internal_log_rate_secs = 10,
+        );
+        counter!("source_acknowledgement_timeouts_total", 1);
+    }
+}
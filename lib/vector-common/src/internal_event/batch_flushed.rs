@@ -0,0 +1,45 @@
+use metrics::counter;
+use tracing::trace;
+
+use crate::internal_event::InternalEvent;
+
+/// Why a batch was flushed, which tells whether batches fill up to their limits or time out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchFlushReason {
+    /// The batch reached its limit of bytes.
+    MaxBytes,
+    /// The batch reached its limit of events.
+    MaxEvents,
+    /// The batch timed out before reaching its limits.
+    Timeout,
+    /// The stream of events ended.
+    Shutdown,
+}
+
+impl BatchFlushReason {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::MaxBytes => "max_bytes",
+            Self::MaxEvents => "max_events",
+            Self::Timeout => "timeout",
+            Self::Shutdown => "shutdown",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BatchFlushed {
+    pub reason: BatchFlushReason,
+    pub events: usize,
+}
+
+impl InternalEvent for BatchFlushed {
+    fn emit(self) {
+        trace!(message = "Batch flushed.", reason = %self.reason.as_str(), events = %self.events);
+        counter!("component_batches_flushed_total", 1, "reason" => self.reason.as_str());
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("BatchFlushed")
+    }
+}
@@ -1,11 +1,39 @@
+mod acknowledgements;
+mod batch_flushed;
 mod bytes_sent;
 mod events_received;
 mod events_sent;
 
+pub use acknowledgements::{AcknowledgementReceived, AcknowledgementTimedOut};
+pub use batch_flushed::{BatchFlushReason, BatchFlushed};
 pub use bytes_sent::BytesSent;
 pub use events_received::{EventsReceived, OldEventsReceived};
 pub use events_sent::{EventsSent, DEFAULT_OUTPUT};
 
+// Set of `reason` tags to use when emitting `component_discarded_events_total`, so that the
+// discarded events of every component can be broken down the same way.
+pub mod discard_reason {
+    // When a buffer or queue of the component was full.
+    pub const BUFFER_FULL: &str = "buffer_full";
+    // When the component failed to decode, parse or encode the event.
+    pub const CODEC_ERROR: &str = "codec_error";
+    // When the sink failed to deliver the event, and routed it to its dead letter output.
+    pub const DLQ_ROUTED: &str = "dlq_routed";
+    // When the event expired before the component could deliver it.
+    pub const EXPIRED: &str = "expired";
+    // When the event is of a kind or shape that the component can't handle, such as an event
+    // that is too large or out of order.
+    pub const INVALID_EVENT: &str = "invalid_event";
+    // When the memory taken up by the events in flight was over its hard limit.
+    pub const MEMORY_LIMIT: &str = "memory_limit";
+    // When the component failed to process the event, such as a script or template failing.
+    pub const PROCESSING_ERROR: &str = "processing_error";
+    // When the event was over the rate limits of the component or its tenant.
+    pub const RATE_LIMITED: &str = "rate_limited";
+    // When the component downstream was closed.
+    pub const STREAM_CLOSED: &str = "stream_closed";
+}
+
 pub trait InternalEvent: Sized {
     fn emit(self) {}
 
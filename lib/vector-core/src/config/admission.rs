@@ -0,0 +1,76 @@
+use std::num::NonZeroU64;
+
+use serde::{Deserialize, Serialize};
+
+/// Limits on the rate at which events are admitted into the topology.
+///
+/// When given globally, the limits apply to the events of all sources together. When given for a
+/// source, they apply to the events of that source alone.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AdmissionConfig {
+    /// The maximum number of events admitted per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events_per_sec: Option<NonZeroU64>,
+
+    /// The maximum number of bytes of events admitted per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes_per_sec: Option<NonZeroU64>,
+
+    /// A log field whose value identifies the tenant of an event.
+    ///
+    /// When set, the limits apply to the events of each tenant separately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_key: Option<String>,
+
+    /// What to do with events that exceed the limits.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub when_exceeded: WhenExceeded,
+}
+
+impl AdmissionConfig {
+    /// Whether or not any limit is set.
+    pub fn is_enabled(&self) -> bool {
+        self.events_per_sec.is_some() || self.bytes_per_sec.is_some()
+    }
+}
+
+/// What to do with events that exceed the admission limits.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WhenExceeded {
+    /// Wait until the events can be admitted, applying backpressure to the source.
+    #[derivative(Default)]
+    Block,
+
+    /// Drop the events.
+    Drop,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_limits() {
+        let config: AdmissionConfig = toml::from_str(
+            r#"
+            events_per_sec = 1000
+            tenant_key = "tenant"
+            when_exceeded = "drop"
+            "#,
+        )
+        .unwrap();
+        assert!(config.is_enabled());
+        assert_eq!(config.events_per_sec, NonZeroU64::new(1000));
+        assert_eq!(config.bytes_per_sec, None);
+        assert_eq!(config.tenant_key.as_deref(), Some("tenant"));
+        assert_eq!(config.when_exceeded, WhenExceeded::Drop);
+
+        assert!(!AdmissionConfig::default().is_enabled());
+    }
+}
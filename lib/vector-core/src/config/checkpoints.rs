@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Where sources keep their checkpoints, such as the offsets of the files they read or the
+/// cursor of the journal, besides their data directory.
+///
+/// By default, checkpoints only live in the data directory, so that an instance rescheduled to
+/// another node without its volume starts over. With a store, each source restores its
+/// checkpoints from the store when they are missing locally, and saves them there as they change.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointsConfig {
+    /// The store checkpoints are kept in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<CheckpointStoreConfig>,
+
+    /// How often the checkpoints of each source are saved to the store when they changed.
+    #[serde(default = "default_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+}
+
+impl Default for CheckpointsConfig {
+    fn default() -> Self {
+        Self {
+            store: None,
+            sync_interval_secs: default_sync_interval_secs(),
+        }
+    }
+}
+
+impl CheckpointsConfig {
+    /// Whether or not checkpoints are kept in a store.
+    pub fn is_enabled(&self) -> bool {
+        self.store.is_some()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum CheckpointStoreConfig {
+    /// A directory, such as one on a network volume that every node mounts.
+    Disk {
+        /// The directory checkpoints are kept in.
+        path: PathBuf,
+    },
+
+    /// Redis keys, one per checkpoint.
+    Redis {
+        /// The URL of the Redis server, such as `redis://127.0.0.1:6379/0`.
+        url: String,
+
+        /// The prefix of the keys of the checkpoints.
+        #[serde(default = "default_key_prefix")]
+        key_prefix: String,
+    },
+
+    /// S3 objects, one per checkpoint.
+    S3 {
+        /// The bucket checkpoints are kept in.
+        bucket: String,
+
+        /// The prefix of the keys of the checkpoints.
+        #[serde(default = "default_key_prefix")]
+        key_prefix: String,
+
+        /// The AWS region of the bucket.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        region: Option<String>,
+
+        /// A custom endpoint, such as that of an S3-compatible store.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
+    },
+}
+
+fn default_key_prefix() -> String {
+    "vector/checkpoints".to_owned()
+}
+
+const fn default_sync_interval_secs() -> u64 {
+    5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stores() {
+        let config: CheckpointsConfig = toml::from_str(
+            r#"
+            store.type = "redis"
+            store.url = "redis://127.0.0.1:6379/0"
+            "#,
+        )
+        .unwrap();
+        assert!(config.is_enabled());
+        assert_eq!(
+            config,
+            CheckpointsConfig {
+                store: Some(CheckpointStoreConfig::Redis {
+                    url: "redis://127.0.0.1:6379/0".into(),
+                    key_prefix: "vector/checkpoints".into(),
+                }),
+                sync_interval_secs: 5,
+            }
+        );
+
+        let config: CheckpointsConfig = toml::from_str(
+            r#"
+            sync_interval_secs = 30
+            store.type = "s3"
+            store.bucket = "checkpoints"
+            store.region = "us-east-1"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.sync_interval_secs, 30);
+        assert_eq!(
+            config.store,
+            Some(CheckpointStoreConfig::S3 {
+                bucket: "checkpoints".into(),
+                key_prefix: "vector/checkpoints".into(),
+                region: Some("us-east-1".into()),
+                endpoint: None,
+            })
+        );
+
+        assert!(!CheckpointsConfig::default().is_enabled());
+    }
+}
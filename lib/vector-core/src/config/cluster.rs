@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+/// Coordination of several Vector instances, so that the work of pull-based sources is shared
+/// between them rather than duplicated.
+///
+/// The members of the cluster agree on which of them owns each piece of work, such as an
+/// endpoint scraped by a source, by hashing it together with the identifiers of the live members.
+/// When a member joins or leaves, only the work it owned, or comes to own, moves.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ClusterConfig {
+    /// The identifier of this instance in the cluster. Defaults to the hostname.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+
+    /// How the live members of the cluster are found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<ClusterBackendConfig>,
+}
+
+impl ClusterConfig {
+    /// Whether or not this instance is a member of a cluster.
+    pub fn is_enabled(&self) -> bool {
+        self.backend.is_some()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ClusterBackendConfig {
+    /// A fixed list of members, all of which are assumed to be live.
+    Static {
+        /// The identifiers of the members, including this instance.
+        members: Vec<String>,
+    },
+
+    /// Members registered in Consul's key/value store, under keys held by sessions that expire
+    /// when a member stops renewing them.
+    Consul {
+        /// The address of the Consul agent.
+        #[serde(default = "default_consul_address")]
+        address: String,
+
+        /// The key prefix the members are registered under.
+        #[serde(default = "default_key_prefix")]
+        key_prefix: String,
+
+        /// The time after which a member that stops renewing its session leaves the cluster.
+        #[serde(default = "default_session_ttl_secs")]
+        session_ttl_secs: u64,
+    },
+
+    /// A single member, elected through a Kubernetes `Lease`: of the replicas of a deployment,
+    /// only the one holding the lease is a member, owning all of the work, until it stops
+    /// renewing the lease and another replica takes it over.
+    KubernetesLease {
+        /// The name of the lease.
+        #[serde(default = "default_lease_name")]
+        lease_name: String,
+
+        /// The namespace of the lease. Defaults to the namespace of the pod.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        namespace: Option<String>,
+
+        /// The time after which the lease can be taken over when its holder stops renewing it.
+        #[serde(default = "default_lease_duration_secs")]
+        lease_duration_secs: u64,
+    },
+}
+
+fn default_consul_address() -> String {
+    "http://127.0.0.1:8500".to_owned()
+}
+
+fn default_key_prefix() -> String {
+    "vector/cluster".to_owned()
+}
+
+const fn default_session_ttl_secs() -> u64 {
+    15
+}
+
+fn default_lease_name() -> String {
+    "vector-leader".to_owned()
+}
+
+const fn default_lease_duration_secs() -> u64 {
+    15
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_backends() {
+        let config: ClusterConfig = toml::from_str(
+            r#"
+            node_id = "aggregator-0"
+            backend.type = "static"
+            backend.members = ["aggregator-0", "aggregator-1"]
+            "#,
+        )
+        .unwrap();
+        assert!(config.is_enabled());
+        assert_eq!(
+            config.backend,
+            Some(ClusterBackendConfig::Static {
+                members: vec!["aggregator-0".into(), "aggregator-1".into()]
+            })
+        );
+
+        let config: ClusterConfig = toml::from_str(r#"backend.type = "consul""#).unwrap();
+        assert_eq!(
+            config.backend,
+            Some(ClusterBackendConfig::Consul {
+                address: "http://127.0.0.1:8500".into(),
+                key_prefix: "vector/cluster".into(),
+                session_ttl_secs: 15,
+            })
+        );
+
+        let config: ClusterConfig = toml::from_str(
+            r#"
+            backend.type = "kubernetes_lease"
+            backend.namespace = "observability"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.backend,
+            Some(ClusterBackendConfig::KubernetesLease {
+                lease_name: "vector-leader".into(),
+                namespace: Some("observability".into()),
+                lease_duration_secs: 15,
+            })
+        );
+
+        assert!(!ClusterConfig::default().is_enabled());
+    }
+}
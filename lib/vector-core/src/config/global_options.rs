@@ -1,10 +1,14 @@
-use std::{fs::DirBuilder, path::PathBuf};
+use std::{collections::BTreeMap, fs::DirBuilder, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use vector_common::TimeZone;
 
-use super::{proxy::ProxyConfig, AcknowledgementsConfig, LogSchema};
+use super::{
+    proxy::ProxyConfig, AcknowledgementsConfig, AdmissionConfig, CheckpointsConfig, ClusterConfig,
+    HttpClientConfig, InternalMetricsConfig, JsonParsingConfig, LogSchema, MemoryLimitConfig,
+    TelemetryConfig, TenancyConfig, ThreadPoolsConfig,
+};
 use crate::serde::bool_or_struct;
 
 #[derive(Debug, Snafu)]
@@ -39,12 +43,34 @@ pub struct GlobalOptions {
     pub timezone: TimeZone,
     #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
     pub proxy: ProxyConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub http_client: HttpClientConfig,
     #[serde(
         default,
         deserialize_with = "bool_or_struct",
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     pub acknowledgements: AcknowledgementsConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub admission: AdmissionConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub tenancy: TenancyConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub memory_limit: MemoryLimitConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub telemetry: TelemetryConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub internal_metrics: InternalMetricsConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub json_parsing: JsonParsingConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub thread_pools: ThreadPoolsConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub tags: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub cluster: ClusterConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub checkpoints: CheckpointsConfig,
 }
 
 impl GlobalOptions {
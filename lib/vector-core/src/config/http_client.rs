@@ -0,0 +1,77 @@
+use std::num::{NonZeroU64, NonZeroUsize};
+
+use serde::{Deserialize, Serialize};
+
+/// Tuning of the HTTP client shared by sinks, and by the other components that send HTTP requests.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct HttpClientConfig {
+    /// The HTTP version requests are sent with.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub version: HttpVersion,
+
+    /// The largest number of idle connections kept open to each host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_max_idle_per_host: Option<NonZeroUsize>,
+
+    /// How long idle connections are kept open, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_idle_timeout_secs: Option<NonZeroU64>,
+
+    /// The interval of TCP keepalive probes on idle connections, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_keepalive_secs: Option<NonZeroU64>,
+
+    /// The interval of HTTP/2 pings on connections, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http2_keepalive_secs: Option<NonZeroU64>,
+
+    /// How long to wait for a connection to the first address of a host before also trying the
+    /// next one, in milliseconds, or `0` to try the addresses one after the other.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub happy_eyeballs_timeout_ms: Option<u64>,
+
+    /// How often connections are replaced by new ones, resolving the hosts again, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_refresh_secs: Option<NonZeroU64>,
+}
+
+/// The HTTP version requests are sent with.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpVersion {
+    /// HTTP/1.1.
+    #[derivative(Default)]
+    Http1,
+
+    /// HTTP/2 when the server agrees to it while negotiating TLS, and HTTP/1.1 otherwise.
+    Auto,
+
+    /// HTTP/2, even without TLS, for servers known to support it.
+    Http2,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_options() {
+        let config: HttpClientConfig = toml::from_str(
+            r#"
+            version = "auto"
+            pool_max_idle_per_host = 8
+            dns_refresh_secs = 60
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.version, HttpVersion::Auto);
+        assert_eq!(config.pool_max_idle_per_host, NonZeroUsize::new(8));
+        assert_eq!(config.dns_refresh_secs, NonZeroU64::new(60));
+        assert_eq!(config.tcp_keepalive_secs, None);
+    }
+}
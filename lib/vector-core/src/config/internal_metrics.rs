@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::DEFAULT_MAX_LABEL_VALUES;
+
+/// Limits on the internal metrics of Vector.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct InternalMetricsConfig {
+    /// The maximum number of values of each label whose values come from events, templates or the
+    /// environment, such as `tenant` or `file`, across every internal metric. New values past the
+    /// maximum are recorded as `__overflow__`.
+    #[serde(default = "default_max_label_values")]
+    pub max_label_values: usize,
+}
+
+const fn default_max_label_values() -> usize {
+    DEFAULT_MAX_LABEL_VALUES
+}
+
+impl Default for InternalMetricsConfig {
+    fn default() -> Self {
+        Self {
+            max_label_values: default_max_label_values(),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// How JSON documents are parsed by the `json` codec and the `parse_json` function.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct JsonParsingConfig {
+    /// Whether JSON documents are parsed with `simd-json`, which is faster on large documents.
+    /// Documents it rejects are parsed again with `serde_json`, so they are parsed, or rejected
+    /// with the same errors, either way. Only has an effect when Vector is built with the
+    /// `json-simd` feature.
+    #[serde(default)]
+    pub simd: bool,
+}
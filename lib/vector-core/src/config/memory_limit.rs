@@ -0,0 +1,72 @@
+use std::num::NonZeroUsize;
+
+use serde::{Deserialize, Serialize};
+
+/// A budget for the memory taken up by the events in flight in the topology.
+///
+/// The events of every source count against the budget from the time they're admitted until
+/// they're finalized, by their in-memory size. Above the soft limit, sources wait until events are
+/// finalized, applying backpressure. Above the hard limit, which events fanned out to several sinks
+/// or created by transforms can take the topology to, incoming events are shed by the policy.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MemoryLimitConfig {
+    /// The number of bytes of events in flight above which sources wait.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_limit_bytes: Option<NonZeroUsize>,
+
+    /// The number of bytes of events in flight above which incoming events are shed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hard_limit_bytes: Option<NonZeroUsize>,
+
+    /// Which incoming events to shed above the hard limit.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub shed: ShedPolicy,
+}
+
+impl MemoryLimitConfig {
+    /// Whether or not any limit is set.
+    pub fn is_enabled(&self) -> bool {
+        self.soft_limit_bytes.is_some() || self.hard_limit_bytes.is_some()
+    }
+}
+
+/// Which incoming events to shed above the hard memory limit.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ShedPolicy {
+    /// Shed every incoming event.
+    #[derivative(Default)]
+    All,
+
+    /// Shed incoming events of low and normal priority, and make events of high priority wait as
+    /// they do above the soft limit.
+    Priority,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_limits() {
+        let config: MemoryLimitConfig = toml::from_str(
+            r#"
+            soft_limit_bytes = 1000
+            hard_limit_bytes = 2000
+            shed = "priority"
+            "#,
+        )
+        .unwrap();
+        assert!(config.is_enabled());
+        assert_eq!(config.soft_limit_bytes, NonZeroUsize::new(1000));
+        assert_eq!(config.hard_limit_bytes, NonZeroUsize::new(2000));
+        assert_eq!(config.shed, ShedPolicy::Priority);
+
+        assert!(!MemoryLimitConfig::default().is_enabled());
+    }
+}
@@ -1,16 +1,40 @@
-use std::{fmt, num::NonZeroUsize};
+use std::{
+    fmt,
+    num::{NonZeroU64, NonZeroUsize},
+    time::Duration,
+};
 
 use bitmask_enum::bitmask;
 use serde::{Deserialize, Serialize};
 
+mod admission;
+mod checkpoints;
+mod cluster;
 mod global_options;
+mod http_client;
 mod id;
+mod internal_metrics;
+mod json_parsing;
 mod log_schema;
+mod memory_limit;
 pub mod proxy;
+mod telemetry;
+mod tenancy;
+mod thread_pools;
 
+pub use admission::{AdmissionConfig, WhenExceeded};
+pub use checkpoints::{CheckpointStoreConfig, CheckpointsConfig};
+pub use cluster::{ClusterBackendConfig, ClusterConfig};
 pub use global_options::GlobalOptions;
+pub use http_client::{HttpClientConfig, HttpVersion};
 pub use id::ComponentKey;
+pub use internal_metrics::InternalMetricsConfig;
+pub use json_parsing::JsonParsingConfig;
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
+pub use memory_limit::{MemoryLimitConfig, ShedPolicy};
+pub use telemetry::TelemetryConfig;
+pub use tenancy::{TenancyConfig, TenantConfig};
+pub use thread_pools::{ThreadPoolConfig, ThreadPoolsConfig};
 
 use crate::schema;
 
@@ -40,6 +64,7 @@ impl fmt::Display for DataType {
 pub struct Input {
     ty: DataType,
     log_schema_requirement: schema::Requirement,
+    columnar: bool,
 }
 
 impl Input {
@@ -51,10 +76,16 @@ impl Input {
         &self.log_schema_requirement
     }
 
+    /// Whether the component accepts batches of metrics in columns, rather than only in rows.
+    pub fn accepts_columnar(&self) -> bool {
+        self.columnar
+    }
+
     pub fn new(ty: DataType) -> Self {
         Self {
             ty,
             log_schema_requirement: schema::Requirement::empty(),
+            columnar: false,
         }
     }
 
@@ -62,6 +93,7 @@ impl Input {
         Self {
             ty: DataType::Log,
             log_schema_requirement: schema::Requirement::empty(),
+            columnar: false,
         }
     }
 
@@ -69,6 +101,7 @@ impl Input {
         Self {
             ty: DataType::Metric,
             log_schema_requirement: schema::Requirement::empty(),
+            columnar: false,
         }
     }
 
@@ -76,6 +109,7 @@ impl Input {
         Self {
             ty: DataType::Trace,
             log_schema_requirement: schema::Requirement::empty(),
+            columnar: false,
         }
     }
 
@@ -83,6 +117,7 @@ impl Input {
         Self {
             ty: DataType::all(),
             log_schema_requirement: schema::Requirement::empty(),
+            columnar: false,
         }
     }
 
@@ -92,6 +127,14 @@ impl Input {
         self.log_schema_requirement = schema_requirement;
         self
     }
+
+    /// Accept batches of metrics in columns, which the component handles without turning them
+    /// into rows, or only turns into rows itself.
+    #[must_use]
+    pub fn with_columnar(mut self) -> Self {
+        self.columnar = true;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -144,23 +187,39 @@ impl Output {
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct AcknowledgementsConfig {
     enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout_secs: Option<NonZeroU64>,
 }
 
 impl AcknowledgementsConfig {
     #[must_use]
     pub fn merge_default(&self, other: &Self) -> Self {
         let enabled = self.enabled.or(other.enabled);
-        Self { enabled }
+        let timeout_secs = self.timeout_secs.or(other.timeout_secs);
+        Self {
+            enabled,
+            timeout_secs,
+        }
     }
 
     pub fn enabled(&self) -> bool {
         self.enabled.unwrap_or(false)
     }
+
+    /// Gets how long to wait for events to be acknowledged before treating them as errored, if at
+    /// all.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs
+            .map(|timeout_secs| Duration::from_secs(timeout_secs.get()))
+    }
 }
 
 impl From<Option<bool>> for AcknowledgementsConfig {
     fn from(enabled: Option<bool>) -> Self {
-        Self { enabled }
+        Self {
+            enabled,
+            timeout_secs: None,
+        }
     }
 }
 
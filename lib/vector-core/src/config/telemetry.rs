@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Tracing of the batches of events going through the topology, exported over OTLP.
+///
+/// A fraction of the batches received by sources are sampled into traces, with a span for each
+/// component they go through, so that their latency can be broken down per component.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryConfig {
+    /// The OTLP/HTTP endpoint to export the spans to, such as `http://localhost:4318/v1/traces`.
+    /// Batches aren't traced unless it's set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+
+    /// The fraction of the batches received by sources to trace, between 0 and 1.
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f64,
+
+    /// The name of the service the spans are reported for.
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+}
+
+const fn default_sample_rate() -> f64 {
+    0.01
+}
+
+fn default_service_name() -> String {
+    "vector".to_owned()
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            sample_rate: default_sample_rate(),
+            service_name: default_service_name(),
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// Whether or not batches are traced.
+    pub fn is_enabled(&self) -> bool {
+        self.endpoint.is_some() && self.sample_rate > 0.0
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    collections::BTreeMap,
+    num::{NonZeroU64, NonZeroUsize},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::WhenExceeded;
+
+/// Isolation of the tenants sharing a topology.
+///
+/// Each event is given the tenant named by its `tenant_key` field or, failing that, the tenant
+/// whose `sources` include the source of the event. The tenant is kept in the metadata of the
+/// event for the rest of the topology, and the limits of the tenant apply to its events.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TenancyConfig {
+    /// A log field whose value identifies the tenant of an event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_key: Option<String>,
+
+    /// What to do with events that exceed the limits of their tenant.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub when_exceeded: WhenExceeded,
+
+    /// The tenants, by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tenants: BTreeMap<String, TenantConfig>,
+}
+
+impl TenancyConfig {
+    /// Whether or not events are given tenants.
+    pub fn is_enabled(&self) -> bool {
+        self.tenant_key.is_some() || !self.tenants.is_empty()
+    }
+
+    /// Gets the tenant owning the given source, if any.
+    pub fn tenant_of_source(&self, source: &str) -> Option<&str> {
+        self.tenants
+            .iter()
+            .find(|(_, tenant)| tenant.sources.iter().any(|id| id == source))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// The sources and limits of a tenant.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TenantConfig {
+    /// The sources whose events belong to the tenant, unless their `tenant_key` field says
+    /// otherwise.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+
+    /// The maximum number of events of the tenant admitted per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events_per_sec: Option<NonZeroU64>,
+
+    /// The maximum number of bytes of events of the tenant admitted per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes_per_sec: Option<NonZeroU64>,
+
+    /// The maximum number of events of the tenant in flight in the topology at once, whether they
+    /// are in buffers or being processed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_buffered_events: Option<NonZeroUsize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_tenant_of_a_source() {
+        let config: TenancyConfig = toml::from_str(
+            r#"
+            tenant_key = "team"
+
+            [tenants.payments]
+            sources = ["payments_http"]
+            events_per_sec = 1000
+            max_buffered_events = 5000
+
+            [tenants.search]
+            sources = ["search_kafka", "search_http"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.tenant_of_source("search_http"), Some("search"));
+        assert_eq!(config.tenant_of_source("payments_http"), Some("payments"));
+        assert_eq!(config.tenant_of_source("other"), None);
+        assert_eq!(
+            config.tenants["payments"].max_buffered_events,
+            NonZeroUsize::new(5000)
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::num::NonZeroUsize;
+
+use serde::{Deserialize, Serialize};
+
+/// Dedicated runtimes for the tasks of each class of components, so that the CPU work of one class,
+/// such as heavy transforms, can't starve the others, such as the accept loops of sources.
+///
+/// Components of a class without a pool run on the main runtime.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ThreadPoolsConfig {
+    /// The pool running the tasks of sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sources: Option<ThreadPoolConfig>,
+
+    /// The pool running the tasks of transforms.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transforms: Option<ThreadPoolConfig>,
+
+    /// The pool running the tasks of sinks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sinks: Option<ThreadPoolConfig>,
+}
+
+/// A runtime with its own worker threads, optionally pinned to a set of CPUs.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ThreadPoolConfig {
+    /// The number of worker threads. Defaults to the number of CPUs the threads are pinned to, or
+    /// to the number of CPUs of the machine if they aren't pinned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<NonZeroUsize>,
+
+    /// The CPUs to pin the threads to. The threads aren't pinned if empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpus: Vec<usize>,
+
+    /// The NUMA node whose CPUs to pin the threads to, restricted to `cpus` if both are set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numa_node: Option<usize>,
+}
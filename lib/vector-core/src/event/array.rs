@@ -9,7 +9,9 @@ use futures::{stream, Stream};
 use quickcheck::{Arbitrary, Gen};
 use vector_buffers::EventCount;
 
-use super::{Event, EventDataEq, EventMutRef, EventRef, LogEvent, Metric, TraceEvent};
+use super::{
+    Event, EventDataEq, EventMutRef, EventRef, LogEvent, Metric, MetricColumns, TraceEvent,
+};
 use crate::ByteSizeOf;
 
 /// The type alias for an array of `LogEvent` elements.
@@ -131,6 +133,8 @@ pub enum EventArray {
     Metrics(MetricArray),
     /// An array of type `TraceEvent`
     Traces(TraceArray),
+    /// A batch of metrics in columns, handed over between components that accept it
+    MetricColumns(MetricColumns),
 }
 
 impl EventArray {
@@ -143,6 +147,7 @@ impl EventArray {
 
     /// Call the given update function over each `Metric` in this array.
     pub fn for_each_metric(&mut self, update: impl FnMut(&mut Metric)) {
+        self.materialize();
         if let Self::Metrics(metrics) = self {
             metrics.iter_mut().for_each(update);
         }
@@ -157,10 +162,12 @@ impl EventArray {
 
     /// Call the given update function over each event in this array.
     pub fn for_each_event(&mut self, mut update: impl FnMut(EventMutRef<'_>)) {
+        self.materialize();
         match self {
             Self::Logs(array) => array.iter_mut().for_each(|log| update(log.into())),
             Self::Metrics(array) => array.iter_mut().for_each(|metric| update(metric.into())),
             Self::Traces(array) => array.iter_mut().for_each(|trace| update(trace.into())),
+            Self::MetricColumns(_) => unreachable!("columns were turned into rows"),
         }
     }
 
@@ -170,6 +177,38 @@ impl EventArray {
             Self::Logs(array) => EventArrayIter::Logs(array.iter()),
             Self::Metrics(array) => EventArrayIter::Metrics(array.iter()),
             Self::Traces(array) => EventArrayIter::Traces(array.iter()),
+            Self::MetricColumns(columns) => EventArrayIter::Metrics(columns.rows().iter()),
+        }
+    }
+
+    /// Puts the metrics of this array in columns, if they can be.
+    #[must_use]
+    pub fn into_columnar(self) -> Self {
+        match self {
+            Self::Metrics(metrics) => {
+                MetricColumns::from_metrics(metrics).map_or_else(Self::Metrics, Self::MetricColumns)
+            }
+            array => array,
+        }
+    }
+
+    /// Turns a batch of metrics in columns back into rows, for components that don't accept
+    /// columns.
+    #[must_use]
+    pub fn into_rows(self) -> Self {
+        match self {
+            Self::MetricColumns(columns) => Self::Metrics(columns.into_metrics()),
+            array => array,
+        }
+    }
+
+    /// Turns a batch of metrics in columns back into rows in place, so they can be updated.
+    fn materialize(&mut self) {
+        if matches!(self, Self::MetricColumns(_)) {
+            if let Self::MetricColumns(columns) = std::mem::replace(self, Self::Metrics(Vec::new()))
+            {
+                *self = Self::Metrics(columns.into_metrics());
+            }
         }
     }
 }
@@ -220,6 +259,7 @@ impl ByteSizeOf for EventArray {
             Self::Logs(a) => a.allocated_bytes(),
             Self::Metrics(a) => a.allocated_bytes(),
             Self::Traces(a) => a.allocated_bytes(),
+            Self::MetricColumns(a) => a.allocated_bytes(),
         }
     }
 }
@@ -230,6 +270,7 @@ impl EventCount for EventArray {
             Self::Logs(a) => a.len(),
             Self::Metrics(a) => a.len(),
             Self::Traces(a) => a.len(),
+            Self::MetricColumns(a) => a.len(),
         }
     }
 }
@@ -242,6 +283,7 @@ impl EventContainer for EventArray {
             Self::Logs(a) => a.len(),
             Self::Metrics(a) => a.len(),
             Self::Traces(a) => a.len(),
+            Self::MetricColumns(a) => a.len(),
         }
     }
 
@@ -250,6 +292,7 @@ impl EventContainer for EventArray {
             Self::Logs(a) => EventArrayIntoIter::Logs(a.into_iter()),
             Self::Metrics(a) => EventArrayIntoIter::Metrics(a.into_iter()),
             Self::Traces(a) => EventArrayIntoIter::Traces(a.into_iter()),
+            Self::MetricColumns(a) => EventArrayIntoIter::Metrics(a.into_metrics().into_iter()),
         }
     }
 }
@@ -260,6 +303,9 @@ impl EventDataEq for EventArray {
             (Self::Logs(a), Self::Logs(b)) => a.event_data_eq(b),
             (Self::Metrics(a), Self::Metrics(b)) => a.event_data_eq(b),
             (Self::Traces(a), Self::Traces(b)) => a.event_data_eq(b),
+            (Self::MetricColumns(a), Self::MetricColumns(b)) => a.rows().event_data_eq(b.rows()),
+            (Self::MetricColumns(a), Self::Metrics(b))
+            | (Self::Metrics(b), Self::MetricColumns(a)) => a.rows().event_data_eq(b),
             _ => false,
         }
     }
@@ -292,6 +338,9 @@ impl Arbitrary for EventArray {
             EventArray::Logs(logs) => Box::new(logs.shrink().map(EventArray::Logs)),
             EventArray::Metrics(metrics) => Box::new(metrics.shrink().map(EventArray::Metrics)),
             EventArray::Traces(traces) => Box::new(traces.shrink().map(EventArray::Traces)),
+            EventArray::MetricColumns(columns) => {
+                Box::new(columns.rows().shrink().map(EventArray::Metrics))
+            }
         }
     }
 }
@@ -0,0 +1,346 @@
+#![deny(missing_docs)]
+//! A columnar representation of batches of metrics, in Arrow arrays, which components that support
+//! it hand over to each other instead of one `Metric` per event.
+
+use std::{cmp::Ordering, sync::Arc};
+
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Float64Array, MapArray, MapBuilder, StringArray,
+        StringBuilder, TimestampNanosecondArray,
+    },
+    record_batch::RecordBatch,
+};
+use chrono::{TimeZone, Utc};
+use once_cell::sync::OnceCell;
+
+use super::{metric::MetricTags, EventMetadata, Metric, MetricArray, MetricKind, MetricValue};
+use crate::ByteSizeOf;
+
+const NAME: usize = 0;
+const NAMESPACE: usize = 1;
+const TIMESTAMP: usize = 2;
+const INCREMENTAL: usize = 3;
+const GAUGE: usize = 4;
+const VALUE: usize = 5;
+const TAGS: usize = 6;
+
+/// A batch of counters and gauges, held in one Arrow array per field, along with the metadata the
+/// metrics of the batch share.
+///
+/// Handing a batch over clones the references to its arrays rather than its metrics. The metrics
+/// are only built again when a component needs them one by one, in which case they are built once
+/// per batch.
+#[derive(Clone, Debug)]
+pub struct MetricColumns {
+    batch: RecordBatch,
+    metadata: EventMetadata,
+    rows: OnceCell<MetricArray>,
+}
+
+impl MetricColumns {
+    /// Puts the metrics in columns, if they are all counters or gauges with the same metadata
+    /// apart from their finalizers, or gives them back otherwise.
+    ///
+    /// The finalizers of the metrics are merged into the metadata of the batch, so the delivery of
+    /// the batch is tracked as a whole.
+    pub fn from_metrics(metrics: MetricArray) -> Result<Self, MetricArray> {
+        let first = match metrics.first() {
+            Some(first) => first.metadata(),
+            None => return Err(metrics),
+        };
+        let supported = metrics.iter().all(|metric| {
+            matches!(
+                metric.value(),
+                MetricValue::Counter { .. } | MetricValue::Gauge { .. }
+            ) && metric.metadata().is_same_batch_as(first)
+        });
+        if !supported {
+            return Err(metrics);
+        }
+
+        let batch = match to_record_batch(&metrics) {
+            Ok(batch) => batch,
+            Err(_) => return Err(metrics),
+        };
+        let mut metrics = metrics.into_iter();
+        let mut metadata = metrics
+            .next()
+            .map(|metric| metric.into_parts().2)
+            .unwrap_or_default();
+        for metric in metrics {
+            metadata.merge_finalizers(metric.into_parts().2.take_finalizers());
+        }
+
+        Ok(Self {
+            batch,
+            metadata,
+            rows: OnceCell::new(),
+        })
+    }
+
+    /// The Arrow arrays of the batch.
+    pub fn record_batch(&self) -> &RecordBatch {
+        &self.batch
+    }
+
+    /// The metadata the metrics of the batch share.
+    pub fn metadata(&self) -> &EventMetadata {
+        &self.metadata
+    }
+
+    /// The mutable metadata the metrics of the batch share.
+    pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+        self.rows.take();
+        &mut self.metadata
+    }
+
+    /// The number of metrics in the batch.
+    pub fn len(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    /// Is the batch empty?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The metrics of the batch, built the first time they are needed.
+    pub fn rows(&self) -> &MetricArray {
+        self.rows.get_or_init(|| self.build_rows())
+    }
+
+    /// Turns the batch back into metrics, each with the metadata of the batch.
+    pub fn into_metrics(mut self) -> MetricArray {
+        match self.rows.take() {
+            Some(rows) => rows,
+            None => self.build_rows(),
+        }
+    }
+
+    fn build_rows(&self) -> MetricArray {
+        let names = column::<StringArray>(&self.batch, NAME);
+        let namespaces = column::<StringArray>(&self.batch, NAMESPACE);
+        let timestamps = column::<TimestampNanosecondArray>(&self.batch, TIMESTAMP);
+        let incremental = column::<BooleanArray>(&self.batch, INCREMENTAL);
+        let gauges = column::<BooleanArray>(&self.batch, GAUGE);
+        let values = column::<Float64Array>(&self.batch, VALUE);
+        let tags = column::<MapArray>(&self.batch, TAGS);
+        let tag_keys = tags.keys();
+        let tag_keys = downcast::<StringArray>(&tag_keys);
+        let tag_values = tags.values();
+        let tag_values = downcast::<StringArray>(&tag_values);
+        let offsets = tags.value_offsets();
+
+        (0..self.len())
+            .map(|i| {
+                let kind = if incremental.value(i) {
+                    MetricKind::Incremental
+                } else {
+                    MetricKind::Absolute
+                };
+                let value = if gauges.value(i) {
+                    MetricValue::Gauge {
+                        value: values.value(i),
+                    }
+                } else {
+                    MetricValue::Counter {
+                        value: values.value(i),
+                    }
+                };
+                let tags = (!tags.is_null(i)).then(|| {
+                    (offsets[i] as usize..offsets[i + 1] as usize)
+                        .map(|j| (tag_keys.value(j).to_owned(), tag_values.value(j).to_owned()))
+                        .collect::<MetricTags>()
+                });
+
+                Metric::new_with_metadata(names.value(i), kind, value, self.metadata.clone())
+                    .with_namespace((!namespaces.is_null(i)).then(|| namespaces.value(i)))
+                    .with_timestamp(
+                        (!timestamps.is_null(i)).then(|| Utc.timestamp_nanos(timestamps.value(i))),
+                    )
+                    .with_tags(tags)
+            })
+            .collect()
+    }
+}
+
+fn to_record_batch(metrics: &[Metric]) -> arrow::error::Result<RecordBatch> {
+    let mut tags = MapBuilder::new(
+        None,
+        StringBuilder::new(metrics.len()),
+        StringBuilder::new(metrics.len()),
+    );
+    for metric in metrics {
+        if let Some(metric_tags) = metric.tags() {
+            for (key, value) in metric_tags {
+                tags.keys().append_value(key)?;
+                tags.values().append_value(value)?;
+            }
+        }
+        tags.append(metric.tags().is_some())?;
+    }
+
+    let columns: [(&str, ArrayRef); 7] = [
+        (
+            "name",
+            Arc::new(StringArray::from_iter_values(
+                metrics.iter().map(Metric::name),
+            )),
+        ),
+        (
+            "namespace",
+            Arc::new(
+                metrics
+                    .iter()
+                    .map(Metric::namespace)
+                    .collect::<StringArray>(),
+            ),
+        ),
+        (
+            "timestamp",
+            Arc::new(TimestampNanosecondArray::from(
+                metrics
+                    .iter()
+                    .map(|metric| {
+                        metric
+                            .timestamp()
+                            .map(|timestamp| timestamp.timestamp_nanos())
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+        ),
+        (
+            "incremental",
+            Arc::new(BooleanArray::from(
+                metrics
+                    .iter()
+                    .map(|metric| metric.kind() == MetricKind::Incremental)
+                    .collect::<Vec<_>>(),
+            )),
+        ),
+        (
+            "gauge",
+            Arc::new(BooleanArray::from(
+                metrics
+                    .iter()
+                    .map(|metric| matches!(metric.value(), MetricValue::Gauge { .. }))
+                    .collect::<Vec<_>>(),
+            )),
+        ),
+        (
+            "value",
+            Arc::new(Float64Array::from(
+                metrics
+                    .iter()
+                    .map(|metric| match metric.value() {
+                        MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+                        _ => unreachable!("only counters and gauges are put in columns"),
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+        ),
+        ("tags", Arc::new(tags.finish())),
+    ];
+    RecordBatch::try_from_iter(columns)
+}
+
+fn column<T: 'static>(batch: &RecordBatch, index: usize) -> &T {
+    downcast(batch.column(index))
+}
+
+fn downcast<T: 'static>(array: &ArrayRef) -> &T {
+    array
+        .as_any()
+        .downcast_ref()
+        .expect("column of a metric batch has the wrong type")
+}
+
+impl ByteSizeOf for MetricColumns {
+    fn allocated_bytes(&self) -> usize {
+        self.batch
+            .columns()
+            .iter()
+            .map(|column| column.get_array_memory_size())
+            .sum::<usize>()
+            + self.metadata.allocated_bytes()
+    }
+}
+
+impl PartialEq for MetricColumns {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows() == other.rows()
+    }
+}
+
+impl PartialOrd for MetricColumns {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.rows().partial_cmp(other.rows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{BatchNotifier, BatchStatus, EventFinalizer, StatisticKind};
+
+    fn metrics() -> MetricArray {
+        vec![
+            Metric::new(
+                "requests_total",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 3.0 },
+            )
+            .with_namespace(Some("http"))
+            .with_tags(Some(MetricTags::from([(
+                "host".to_owned(),
+                "a".to_owned(),
+            )])))
+            .with_timestamp(Some(Utc.timestamp(1_655_000_000, 123))),
+            Metric::new(
+                "temperature",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: -1.5 },
+            ),
+        ]
+    }
+
+    #[test]
+    fn round_trips_counters_and_gauges() {
+        let columns = MetricColumns::from_metrics(metrics()).unwrap();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns.rows(), &metrics());
+        assert_eq!(columns.into_metrics(), metrics());
+    }
+
+    #[test]
+    fn keeps_other_metrics_in_rows() {
+        let mut metrics = metrics();
+        metrics.push(Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vec![],
+                statistic: StatisticKind::Histogram,
+            },
+        ));
+        assert_eq!(MetricColumns::from_metrics(metrics.clone()), Err(metrics));
+    }
+
+    #[tokio::test]
+    async fn tracks_delivery_of_the_batch() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let metrics = metrics()
+            .into_iter()
+            .map(|mut metric| {
+                metric.add_finalizer(EventFinalizer::new(Arc::clone(&batch)));
+                metric
+            })
+            .collect();
+        drop(batch);
+
+        let columns = MetricColumns::from_metrics(metrics).unwrap();
+        drop(columns.into_metrics());
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+    }
+}
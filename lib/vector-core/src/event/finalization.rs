@@ -267,6 +267,15 @@ pub enum BatchStatus {
 }
 
 impl BatchStatus {
+    /// Gets the name of this status, as used in metric tags.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Errored => "errored",
+            Self::Rejected => "rejected",
+        }
+    }
+
     /// Updates the delivery status based on another batch's delivery status, returning the result.
     ///
     /// As not every status has the same priority, some updates may end up being a no-op either due to not being any
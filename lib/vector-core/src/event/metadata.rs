@@ -1,7 +1,8 @@
 #![deny(missing_docs)]
 
-use std::sync::Arc;
+use std::{fmt, str::FromStr, sync::Arc, time::SystemTime};
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use vector_common::EventDataEq;
 
@@ -18,8 +19,27 @@ pub struct EventMetadata {
     /// Used to store the Splunk HEC auth token from sources to sinks
     #[serde(default, skip)]
     splunk_hec_token: Option<Arc<str>>,
+    /// Used to carry the consumer position of an event from the Kafka source to the Kafka sink
+    #[serde(default, skip)]
+    kafka_offset: Option<Arc<KafkaOffset>>,
     #[serde(default, skip)]
     finalizers: EventFinalizers,
+    /// The priority class of the event, used by sinks that schedule events by priority.
+    #[serde(default, skip)]
+    priority: EventPriority,
+    /// The tenant the event belongs to, used to apply the limits of the tenant.
+    #[serde(default, skip)]
+    tenant: Option<Arc<str>>,
+    /// The trace of the batch the event was sampled into, used to trace it through the topology.
+    #[serde(default, skip)]
+    batch_trace: Option<Arc<BatchTrace>>,
+    /// The named output of the remap transform the event is sent to, as set by its VRL program.
+    #[serde(default, skip)]
+    output: Option<Arc<str>>,
+    /// When the event expires, as set by the time-to-live of a VRL program. Buffers and sinks
+    /// discard expired events instead of delivering them.
+    #[serde(default, skip)]
+    expires_at: Option<DateTime<Utc>>,
 
     /// An identifier for a globaly registered schema definition which provides information about
     /// the event shape (type information, and semantic meaning of fields).
@@ -49,6 +69,164 @@ impl EventMetadata {
     pub fn set_splunk_hec_token(&mut self, token: Option<Arc<str>>) {
         self.splunk_hec_token = token;
     }
+
+    /// Return the Kafka consumer position the event was read from, if it exists
+    pub fn kafka_offset(&self) -> &Option<Arc<KafkaOffset>> {
+        &self.kafka_offset
+    }
+
+    /// Set the Kafka consumer position to passed value
+    pub fn set_kafka_offset(&mut self, offset: Option<Arc<KafkaOffset>>) {
+        self.kafka_offset = offset;
+    }
+
+    /// Return the priority class of the event
+    pub fn priority(&self) -> EventPriority {
+        self.priority
+    }
+
+    /// Set the priority class of the event to passed value
+    pub fn set_priority(&mut self, priority: EventPriority) {
+        self.priority = priority;
+    }
+
+    /// Return the tenant of the event, if it exists
+    pub fn tenant(&self) -> &Option<Arc<str>> {
+        &self.tenant
+    }
+
+    /// Set the tenant of the event to passed value
+    pub fn set_tenant(&mut self, tenant: Option<Arc<str>>) {
+        self.tenant = tenant;
+    }
+
+    /// Return the trace of the batch the event was sampled into, if it exists
+    pub fn batch_trace(&self) -> &Option<Arc<BatchTrace>> {
+        &self.batch_trace
+    }
+
+    /// Set the trace of the batch the event was sampled into to passed value
+    pub fn set_batch_trace(&mut self, trace: Option<Arc<BatchTrace>>) {
+        self.batch_trace = trace;
+    }
+
+    /// Return the named output the event is sent to, if it exists
+    pub fn output(&self) -> &Option<Arc<str>> {
+        &self.output
+    }
+
+    /// Set the named output the event is sent to to passed value
+    pub fn set_output(&mut self, output: Option<Arc<str>>) {
+        self.output = output;
+    }
+
+    /// Consumes the named output the event is sent to and returns it, leaving it unset.
+    pub fn take_output(&mut self) -> Option<Arc<str>> {
+        self.output.take()
+    }
+
+    /// Return when the event expires, if it does
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    /// Set when the event expires to passed value
+    pub fn set_expires_at(&mut self, expires_at: Option<DateTime<Utc>>) {
+        self.expires_at = expires_at;
+    }
+
+    /// Returns whether the event has expired at the given time.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+/// The position of an event in the trace of a batch of events.
+///
+/// This is attached to the events of the batches sampled by sources, and updated by each component
+/// that hands them over, so that the next component can report its span as a child of the span of
+/// the previous one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd)]
+pub struct BatchTrace {
+    /// The trace the batch belongs to.
+    pub trace_id: u128,
+    /// The span of the component that last handed the event over, or 0 for the source that
+    /// sampled it.
+    pub span_id: u64,
+    /// When the event was handed over to the next component.
+    pub handed_over_at: SystemTime,
+}
+
+/// The position of a Kafka consumer group in a topic partition.
+///
+/// This is attached to events by a Kafka source running in exactly-once mode, so that a Kafka sink
+/// can commit the source's offsets in the same transaction as it produces the events.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd)]
+pub struct KafkaOffset {
+    /// The consumer group that read the event.
+    pub group_id: Arc<str>,
+    /// The topic the event was read from.
+    pub topic: Arc<str>,
+    /// The partition the event was read from.
+    pub partition: i32,
+    /// The offset of the message the event was read from.
+    pub offset: i64,
+}
+
+/// The priority class of an event.
+///
+/// Sinks that are configured to schedule events by priority send events of a higher class before
+/// events of a lower class, and shed events of the lowest classes first when overloaded.
+#[derive(
+    Clone, Copy, Debug, Derivative, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EventPriority {
+    /// Events that can be shed first, such as debug logs.
+    Low,
+    /// The priority of events that were not given one.
+    #[derivative(Default)]
+    Normal,
+    /// Events that should be kept for as long as possible, such as audit or security logs.
+    High,
+}
+
+impl EventPriority {
+    /// All priority classes, from lowest to highest.
+    pub const ALL: [EventPriority; 3] = [Self::Low, Self::Normal, Self::High];
+
+    /// Returns the name of the priority class.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+}
+
+impl fmt::Display for EventPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EventPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Self::Low),
+            "normal" => Ok(Self::Normal),
+            "high" => Ok(Self::High),
+            _ => Err(format!(
+                "invalid priority {:?}, expected one of \"low\", \"normal\" or \"high\"",
+                s
+            )),
+        }
+    }
 }
 
 impl Default for EventMetadata {
@@ -56,7 +234,13 @@ impl Default for EventMetadata {
         Self {
             datadog_api_key: Default::default(),
             splunk_hec_token: Default::default(),
+            kafka_offset: Default::default(),
             finalizers: Default::default(),
+            priority: Default::default(),
+            tenant: Default::default(),
+            batch_trace: Default::default(),
+            output: Default::default(),
+            expires_at: Default::default(),
             schema_definition: default_schema_definition(),
         }
     }
@@ -115,14 +299,37 @@ impl EventMetadata {
     /// Merge the other `EventMetadata` into this.
     /// If a Datadog API key is not set in `self`, the one from `other` will be used.
     /// If a Splunk HEC token is not set in `self`, the one from `other` will be used.
+    /// If a Kafka offset is not set in `self`, the one from `other` will be used.
+    /// If a tenant is not set in `self`, the one from `other` will be used.
+    /// If a batch trace is not set in `self`, the one from `other` will be used.
+    /// If an output is not set in `self`, the one from `other` will be used.
+    /// The higher of the two priorities will be used.
+    /// The later of the two expiry times will be used, so the merged event only expires if both do.
     pub fn merge(&mut self, other: Self) {
         self.finalizers.merge(other.finalizers);
+        self.priority = self.priority.max(other.priority);
         if self.datadog_api_key.is_none() {
             self.datadog_api_key = other.datadog_api_key;
         }
         if self.splunk_hec_token.is_none() {
             self.splunk_hec_token = other.splunk_hec_token;
         }
+        if self.kafka_offset.is_none() {
+            self.kafka_offset = other.kafka_offset;
+        }
+        if self.tenant.is_none() {
+            self.tenant = other.tenant;
+        }
+        if self.batch_trace.is_none() {
+            self.batch_trace = other.batch_trace;
+        }
+        if self.output.is_none() {
+            self.output = other.output;
+        }
+        self.expires_at = match (self.expires_at, other.expires_at) {
+            (Some(expires_at), Some(other)) => Some(expires_at.max(other)),
+            _ => None,
+        };
     }
 
     /// Update the finalizer(s) status.
@@ -164,6 +371,29 @@ impl EventMetadata {
     pub fn set_schema_definition(&mut self, definition: &Arc<schema::Definition>) {
         self.schema_definition = Arc::clone(definition);
     }
+
+    /// Returns whether the two metadata are the same apart from their finalizers, such that events
+    /// with either can share the metadata of a batch.
+    pub(super) fn is_same_batch_as(&self, other: &Self) -> bool {
+        fn same<T: ?Sized + PartialEq>(a: &Option<Arc<T>>, b: &Option<Arc<T>>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b) || a == b,
+                (None, None) => true,
+                _ => false,
+            }
+        }
+
+        self.priority == other.priority
+            && same(&self.datadog_api_key, &other.datadog_api_key)
+            && same(&self.splunk_hec_token, &other.splunk_hec_token)
+            && same(&self.kafka_offset, &other.kafka_offset)
+            && same(&self.tenant, &other.tenant)
+            && same(&self.batch_trace, &other.batch_trace)
+            && same(&self.output, &other.output)
+            && self.expires_at == other.expires_at
+            && (Arc::ptr_eq(&self.schema_definition, &other.schema_definition)
+                || self.schema_definition == other.schema_definition)
+    }
 }
 
 impl EventDataEq for EventMetadata {
@@ -8,12 +8,13 @@ use std::{
 pub use ::value::Value;
 pub use array::{into_event_stream, EventArray, EventContainer, LogArray, MetricArray, TraceArray};
 use bytes::Bytes;
+pub use columnar::MetricColumns;
 pub use finalization::{
     BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer, EventFinalizers, EventStatus,
     Finalizable,
 };
 pub use log_event::LogEvent;
-pub use metadata::{EventMetadata, WithMetadata};
+pub use metadata::{BatchTrace, EventMetadata, EventPriority, KafkaOffset, WithMetadata};
 pub use metric::{Metric, MetricKind, MetricValue, StatisticKind};
 pub use r#ref::{EventMutRef, EventRef};
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,7 @@ pub use vrl_target::{TargetEvents, VrlTarget};
 use crate::ByteSizeOf;
 
 pub mod array;
+mod columnar;
 pub mod discriminant;
 pub mod error;
 mod finalization;
@@ -37,6 +37,9 @@ impl From<array::EventArray> for EventArray {
             array::EventArray::Logs(array) => event_array::Events::from_logs(array),
             array::EventArray::Metrics(array) => event_array::Events::from_metrics(array),
             array::EventArray::Traces(array) => event_array::Events::from_traces(array),
+            array::EventArray::MetricColumns(columns) => {
+                event_array::Events::from_metrics(columns.into_metrics())
+            }
         });
         Self { events }
     }
@@ -86,7 +89,8 @@ impl From<Trace> for Event {
 
 impl From<Log> for event::LogEvent {
     fn from(log: Log) -> Self {
-        if let Some(value) = log.value {
+        let priority = decode_priority(log.priority());
+        let mut log_event = if let Some(value) = log.value {
             Self::from(decode_value(value).unwrap_or(::value::Value::Null))
         } else {
             // This is for backwards compatibility. Only `value` should be set
@@ -97,19 +101,29 @@ impl From<Log> for event::LogEvent {
                 .collect::<BTreeMap<_, _>>();
 
             Self::from(fields)
-        }
+        };
+
+        let metadata = log_event.metadata_mut();
+        metadata.set_expires_at(decode_timestamp(log.expires_at));
+        metadata.set_priority(priority);
+        log_event
     }
 }
 
 impl From<Trace> for event::TraceEvent {
     fn from(trace: Trace) -> Self {
+        let priority = decode_priority(trace.priority());
         let fields = trace
             .fields
             .into_iter()
             .filter_map(|(k, v)| decode_value(v).map(|value| (k, value)))
             .collect::<BTreeMap<_, _>>();
 
-        Self::from(event::LogEvent::from(fields))
+        let mut trace_event = Self::from(event::LogEvent::from(fields));
+        let metadata = trace_event.metadata_mut();
+        metadata.set_expires_at(decode_timestamp(trace.expires_at));
+        metadata.set_priority(priority);
+        trace_event
     }
 }
 
@@ -119,6 +133,7 @@ impl From<Metric> for event::Metric {
             metric::Kind::Incremental => event::MetricKind::Incremental,
             metric::Kind::Absolute => event::MetricKind::Absolute,
         };
+        let priority = decode_priority(metric.priority());
 
         let name = metric.name;
 
@@ -128,9 +143,8 @@ impl From<Metric> for event::Metric {
             Some(metric.namespace)
         };
 
-        let timestamp = metric
-            .timestamp
-            .map(|ts| chrono::Utc.timestamp(ts.seconds, ts.nanos as u32));
+        let timestamp = decode_timestamp(metric.timestamp);
+        let expires_at = decode_timestamp(metric.expires_at);
 
         let tags = if metric.tags.is_empty() {
             None
@@ -181,10 +195,14 @@ impl From<Metric> for event::Metric {
             },
         };
 
-        Self::new(name, kind, value)
+        let mut metric = Self::new(name, kind, value)
             .with_namespace(namespace)
             .with_tags(tags)
-            .with_timestamp(timestamp)
+            .with_timestamp(timestamp);
+        let metadata = metric.metadata_mut();
+        metadata.set_expires_at(expires_at);
+        metadata.set_priority(priority);
+        metric
     }
 }
 
@@ -215,6 +233,8 @@ impl From<event::TraceEvent> for Trace {
 impl From<event::LogEvent> for WithMetadata<Log> {
     fn from(log_event: event::LogEvent) -> Self {
         let (value, metadata) = log_event.into_parts();
+        let expires_at = encode_timestamp(metadata.expires_at());
+        let priority = encode_priority(metadata.priority());
 
         // Due to the backwards compatibility requirement by the
         // "event_can_go_from_raw_prost_to_eventarray_encodable" test, "fields" must not
@@ -232,6 +252,8 @@ impl From<event::LogEvent> for WithMetadata<Log> {
                     .map(|(k, v)| (k, encode_value(v)))
                     .collect::<BTreeMap<_, _>>(),
                 value: None,
+                expires_at,
+                priority,
             }
         } else {
             let mut dummy = BTreeMap::new();
@@ -241,6 +263,8 @@ impl From<event::LogEvent> for WithMetadata<Log> {
             Log {
                 fields: dummy,
                 value: Some(encode_value(value)),
+                expires_at,
+                priority,
             }
         };
 
@@ -256,7 +280,13 @@ impl From<event::TraceEvent> for WithMetadata<Trace> {
             .map(|(k, v)| (k, encode_value(v)))
             .collect::<BTreeMap<_, _>>();
 
-        let data = Trace { fields };
+        let expires_at = encode_timestamp(metadata.expires_at());
+        let priority = encode_priority(metadata.priority());
+        let data = Trace {
+            fields,
+            expires_at,
+            priority,
+        };
         Self { data, metadata }
     }
 }
@@ -273,10 +303,9 @@ impl From<event::Metric> for WithMetadata<Metric> {
         let name = series.name.name;
         let namespace = series.name.namespace.unwrap_or_default();
 
-        let timestamp = data.timestamp.map(|ts| prost_types::Timestamp {
-            seconds: ts.timestamp(),
-            nanos: ts.timestamp_subsec_nanos() as i32,
-        });
+        let timestamp = encode_timestamp(data.timestamp);
+        let expires_at = encode_timestamp(metadata.expires_at());
+        let priority = encode_priority(metadata.priority());
 
         let tags = series.tags.unwrap_or_default();
 
@@ -349,6 +378,8 @@ impl From<event::Metric> for WithMetadata<Metric> {
             tags,
             kind,
             value: Some(metric),
+            expires_at,
+            priority,
         };
         Self { data, metadata }
     }
@@ -510,3 +541,31 @@ fn encode_array(items: Vec<event::Value>) -> ValueArray {
         items: items.into_iter().map(encode_value).collect(),
     }
 }
+
+fn decode_timestamp(ts: Option<prost_types::Timestamp>) -> Option<chrono::DateTime<chrono::Utc>> {
+    ts.map(|ts| chrono::Utc.timestamp(ts.seconds, ts.nanos as u32))
+}
+
+fn encode_timestamp(ts: Option<chrono::DateTime<chrono::Utc>>) -> Option<prost_types::Timestamp> {
+    ts.map(|ts| prost_types::Timestamp {
+        seconds: ts.timestamp(),
+        nanos: ts.timestamp_subsec_nanos() as i32,
+    })
+}
+
+fn decode_priority(priority: Priority) -> event::EventPriority {
+    match priority {
+        Priority::Low => event::EventPriority::Low,
+        Priority::Normal => event::EventPriority::Normal,
+        Priority::High => event::EventPriority::High,
+    }
+}
+
+fn encode_priority(priority: event::EventPriority) -> i32 {
+    match priority {
+        event::EventPriority::Low => Priority::Low,
+        event::EventPriority::Normal => Priority::Normal,
+        event::EventPriority::High => Priority::High,
+    }
+    .into()
+}
@@ -27,6 +27,9 @@ pub enum DecodeError {
 ///
 /// This enumeration should never have any flags removed, only added.  This ensures that previously
 /// used flags cannot have their meaning changed/repurposed after-the-fact.
+///
+/// The most significant bit (`1 << 31`) is reserved by the buffers, as described by
+/// `vector_buffers::encoding::RESERVED_METADATA_BITS`, and must never be used as a flag here.
 #[bitflags]
 #[repr(u32)]
 #[derive(Copy, Clone, Debug, PartialEq)]
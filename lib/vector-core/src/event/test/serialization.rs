@@ -111,6 +111,57 @@ fn event_can_go_from_raw_prost_to_eventarray_encodable() {
     assert_eq!(event, events.remove(0));
 }
 
+#[test]
+fn expiry_survives_encoding() {
+    use chrono::{TimeZone, Utc};
+
+    let expires_at = Utc.timestamp(1_650_000_000, 500);
+
+    let mut log = LogEvent::from("alert");
+    log.metadata_mut().set_expires_at(Some(expires_at));
+    let mut metric = Metric::new(
+        "alerts",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    );
+    metric.metadata_mut().set_expires_at(Some(expires_at));
+
+    for event in [Event::from(log), Event::from(metric)] {
+        let mut buffer = BytesMut::with_capacity(64);
+        encode_value(EventArray::from(event), &mut buffer);
+
+        let decoded = decode_value::<EventArray, _>(buffer);
+        for event in decoded.into_events() {
+            assert_eq!(event.metadata().expires_at(), Some(expires_at));
+        }
+    }
+}
+
+#[test]
+fn priority_survives_encoding() {
+    let mut log = LogEvent::from("audit");
+    log.metadata_mut().set_priority(EventPriority::High);
+    let mut trace = TraceEvent::from(LogEvent::from("span"));
+    trace.metadata_mut().set_priority(EventPriority::Low);
+    let mut metric = Metric::new(
+        "debug",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    );
+    metric.metadata_mut().set_priority(EventPriority::Low);
+
+    for event in [Event::from(log), Event::from(trace), Event::from(metric)] {
+        let priority = event.metadata().priority();
+        let mut buffer = BytesMut::with_capacity(64);
+        encode_value(EventArray::from(event), &mut buffer);
+
+        let decoded = decode_value::<EventArray, _>(buffer);
+        for event in decoded.into_events() {
+            assert_eq!(event.metadata().priority(), priority);
+        }
+    }
+}
+
 // Ser/De the EventArray never loses bytes
 #[test]
 fn serde_eventarray_no_size_loss() {
@@ -1,10 +1,11 @@
 use std::{collections::BTreeMap, convert::TryFrom, marker::PhantomData, sync::Arc};
 
+use chrono::{Duration, Utc};
 use lookup::{LookupBuf, SegmentBuf};
 use snafu::Snafu;
 use vrl_lib::{prelude::VrlValueConvert, ProgramInfo};
 
-use super::{Event, EventMetadata, LogEvent, Metric, MetricKind, TraceEvent, Value};
+use super::{Event, EventMetadata, EventPriority, LogEvent, Metric, MetricKind, TraceEvent, Value};
 use crate::config::log_schema;
 
 const VALID_METRIC_PATHS_SET: &str = ".name, .namespace, .timestamp, .kind, .tags";
@@ -297,6 +298,16 @@ impl vrl_lib::Target for VrlTarget {
                 .splunk_hec_token()
                 .as_ref()
                 .map(|token| ::value::Value::from(token.to_string()))),
+            "priority" => Ok(Some(::value::Value::from(metadata.priority().as_str()))),
+            "output" => Ok(metadata
+                .output()
+                .as_ref()
+                .map(|output| ::value::Value::from(output.to_string()))),
+            // The remaining time-to-live, rounded up to whole seconds.
+            "ttl" => Ok(metadata.expires_at().map(|expires_at| {
+                let remaining = (expires_at - Utc::now()).num_milliseconds().max(0);
+                ::value::Value::from(((remaining + 999) / 1000).to_string())
+            })),
             _ => Err(format!("key {} not available", key)),
         }
     }
@@ -316,6 +327,21 @@ impl vrl_lib::Target for VrlTarget {
                 metadata.set_splunk_hec_token(Some(Arc::from(value.as_str())));
                 Ok(())
             }
+            "priority" => {
+                metadata.set_priority(value.parse()?);
+                Ok(())
+            }
+            "output" => {
+                metadata.set_output(Some(Arc::from(value.as_str())));
+                Ok(())
+            }
+            "ttl" => {
+                let ttl = value.parse::<u32>().map_err(|_| {
+                    format!("invalid ttl {:?}, expected a number of seconds", value)
+                })?;
+                metadata.set_expires_at(Some(Utc::now() + Duration::seconds(ttl.into())));
+                Ok(())
+            }
             _ => Err(format!("key {} not available", key)),
         }
     }
@@ -335,6 +361,18 @@ impl vrl_lib::Target for VrlTarget {
                 metadata.set_splunk_hec_token(None);
                 Ok(())
             }
+            "priority" => {
+                metadata.set_priority(EventPriority::default());
+                Ok(())
+            }
+            "output" => {
+                metadata.set_output(None);
+                Ok(())
+            }
+            "ttl" => {
+                metadata.set_expires_at(None);
+                Ok(())
+            }
             _ => Err(format!("key {} not available", key)),
         }
     }
@@ -530,7 +568,7 @@ enum MetricPathError<'a> {
 
 #[cfg(test)]
 mod test {
-    use chrono::{offset::TimeZone, Utc};
+    use chrono::{offset::TimeZone, Duration, Utc};
     use pretty_assertions::assert_eq;
     use vector_common::btreemap;
     use vrl_lib::Target;
@@ -1076,4 +1114,91 @@ mod test {
             target.target_get(&LookupBuf::from_str("tags.foo.flork").unwrap())
         );
     }
+
+    #[test]
+    fn priority_metadata() {
+        let info = ProgramInfo {
+            fallible: false,
+            abortable: false,
+            target_queries: vec![],
+            target_assignments: vec![],
+        };
+        let mut target = VrlTarget::new(Event::Log(LogEvent::from("audit")), &info);
+
+        assert_eq!(
+            Ok(Some(::value::Value::from("normal"))),
+            target.get_metadata("priority")
+        );
+
+        target.set_metadata("priority", "high".to_owned()).unwrap();
+        assert_eq!(
+            Ok(Some(::value::Value::from("high"))),
+            target.get_metadata("priority")
+        );
+        assert!(target
+            .set_metadata("priority", "urgent".to_owned())
+            .is_err());
+
+        match target.into_events() {
+            TargetEvents::One(event) => {
+                assert_eq!(event.metadata().priority(), EventPriority::High);
+            }
+            _ => panic!("expected a single event"),
+        }
+    }
+
+    #[test]
+    fn output_metadata() {
+        let info = ProgramInfo {
+            fallible: false,
+            abortable: false,
+            target_queries: vec![],
+            target_assignments: vec![],
+        };
+        let mut target = VrlTarget::new(Event::Log(LogEvent::from("failed")), &info);
+
+        assert_eq!(Ok(None), target.get_metadata("output"));
+
+        target.set_metadata("output", "errors".to_owned()).unwrap();
+        assert_eq!(
+            Ok(Some(::value::Value::from("errors"))),
+            target.get_metadata("output")
+        );
+
+        match target.into_events() {
+            TargetEvents::One(event) => {
+                assert_eq!(event.metadata().output().as_deref(), Some("errors"));
+            }
+            _ => panic!("expected a single event"),
+        }
+    }
+
+    #[test]
+    fn ttl_metadata() {
+        let info = ProgramInfo {
+            fallible: false,
+            abortable: false,
+            target_queries: vec![],
+            target_assignments: vec![],
+        };
+        let mut target = VrlTarget::new(Event::Log(LogEvent::from("alert")), &info);
+
+        assert_eq!(Ok(None), target.get_metadata("ttl"));
+        assert!(target.set_metadata("ttl", "soon".to_owned()).is_err());
+
+        target.set_metadata("ttl", "300".to_owned()).unwrap();
+        assert_eq!(
+            Ok(Some(::value::Value::from("300"))),
+            target.get_metadata("ttl")
+        );
+
+        match target.into_events() {
+            TargetEvents::One(event) => {
+                let metadata = event.metadata();
+                assert!(!metadata.is_expired_at(Utc::now()));
+                assert!(metadata.is_expired_at(Utc::now() + Duration::seconds(300)));
+            }
+            _ => panic!("expected a single event"),
+        }
+    }
 }
@@ -1,9 +1,12 @@
 use std::marker::{PhantomData, Unpin};
+use std::time::Duration;
 use std::{fmt::Debug, future::Future, pin::Pin, task::Context, task::Poll};
 
 use futures::stream::{FuturesOrdered, FuturesUnordered};
 use futures::{FutureExt, Stream, StreamExt};
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::time::{sleep, Instant, Sleep};
+use vector_common::internal_event::{emit, AcknowledgementReceived, AcknowledgementTimedOut};
 
 use crate::event::{BatchStatus, BatchStatusReceiver};
 use crate::shutdown::ShutdownSignal;
@@ -28,6 +31,11 @@ pub type UnorderedFinalizer<T> = FinalizerSet<T, FuturesUnordered<FinalizerFutur
 /// stream of acknowledgements that comes out, extracting just the
 /// identifier and sending that into the returned stream. The type `T`
 /// is the source-specific data associated with each entry.
+///
+/// If a timeout is given, any batch that is not finalized within that
+/// time is reported as `BatchStatus::Errored`, so that sources stop
+/// waiting on batches stuck in a wedged sink and can redeliver them
+/// instead of holding their position forever.
 pub struct FinalizerSet<T, S> {
     sender: Option<UnboundedSender<(BatchStatusReceiver, T)>>,
     _phantom: PhantomData<S>,
@@ -40,7 +48,10 @@ where
 {
     /// Produce a finalizer set along with the output stream of
     /// received acknowledged batch identifiers.
-    pub fn new(shutdown: ShutdownSignal) -> (Self, impl Stream<Item = (BatchStatus, T)>) {
+    pub fn new(
+        shutdown: ShutdownSignal,
+        timeout: Option<Duration>,
+    ) -> (Self, impl Stream<Item = (BatchStatus, T)>) {
         let (todo_tx, todo_rx) = mpsc::unbounded_channel();
         (
             Self {
@@ -52,6 +63,7 @@ where
                 new_entries: todo_rx,
                 status_receivers: S::default(),
                 is_shutdown: false,
+                timeout,
             },
         )
     }
@@ -63,12 +75,13 @@ where
     pub fn maybe_new(
         maybe: bool,
         shutdown: ShutdownSignal,
+        timeout: Option<Duration>,
     ) -> (
         Option<Self>,
         Pin<Box<dyn Stream<Item = (BatchStatus, T)> + Send + 'static>>,
     ) {
         if maybe {
-            let (finalizer, stream) = Self::new(shutdown);
+            let (finalizer, stream) = Self::new(shutdown, timeout);
             (Some(finalizer), stream.boxed())
         } else {
             (None, EmptyStream::default().boxed())
@@ -91,6 +104,7 @@ struct FinalizerStream<T, S> {
     new_entries: UnboundedReceiver<(BatchStatusReceiver, T)>,
     status_receivers: S,
     is_shutdown: bool,
+    timeout: Option<Duration>,
 }
 
 impl<T, S> Stream for FinalizerStream<T, S>
@@ -112,9 +126,8 @@ where
                 match this.new_entries.poll_recv(ctx) {
                     Poll::Pending => break,
                     Poll::Ready(Some((receiver, entry))) => {
-                        let entry = Some(entry);
-                        this.status_receivers
-                            .push(FinalizerFuture { receiver, entry });
+                        let future = FinalizerFuture::new(receiver, entry, *this.timeout);
+                        this.status_receivers.push(future);
                     }
                     // The sender went away before shutdown, count it as a shutdown too.
                     Poll::Ready(None) => {
@@ -171,12 +184,43 @@ impl<Fut: Future> FuturesSet<Fut> for FuturesUnordered<Fut> {
 pub struct FinalizerFuture<T> {
     receiver: BatchStatusReceiver,
     entry: Option<T>,
+    started: Instant,
+    deadline: Option<(Pin<Box<Sleep>>, Duration)>,
+}
+
+impl<T> FinalizerFuture<T> {
+    fn new(receiver: BatchStatusReceiver, entry: T, timeout: Option<Duration>) -> Self {
+        Self {
+            receiver,
+            entry: Some(entry),
+            started: Instant::now(),
+            deadline: timeout.map(|timeout| (Box::pin(sleep(timeout)), timeout)),
+        }
+    }
 }
 
 impl<T> Future for FinalizerFuture<T> {
     type Output = (<BatchStatusReceiver as Future>::Output, T);
     fn poll(mut self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        let status = futures::ready!(self.receiver.poll_unpin(ctx));
+        let status = match self.receiver.poll_unpin(ctx) {
+            Poll::Ready(status) => {
+                emit(AcknowledgementReceived {
+                    status: status.as_str(),
+                    latency: self.started.elapsed(),
+                });
+                status
+            }
+            Poll::Pending => match &mut self.deadline {
+                Some((deadline, timeout)) => {
+                    futures::ready!(deadline.poll_unpin(ctx));
+                    // Dropping the receiver along with this future means a
+                    // late status from the sink is simply ignored.
+                    emit(AcknowledgementTimedOut { timeout: *timeout });
+                    BatchStatus::Errored
+                }
+                None => return Poll::Pending,
+            },
+        };
         // The use of this above in a `Futures{Ordered|Unordered|`
         // will only take this once before dropping the future.
         Poll::Ready((status, self.entry.take().unwrap_or_else(|| unreachable!())))
@@ -198,3 +242,40 @@ impl<T> Stream for EmptyStream<T> {
         (0, Some(0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+
+    use super::OrderedFinalizer;
+    use crate::{
+        event::{BatchNotifier, BatchStatus},
+        shutdown::ShutdownSignal,
+    };
+
+    #[tokio::test]
+    async fn reports_finalized_batches() {
+        let (finalizer, mut stream) =
+            OrderedFinalizer::<usize>::new(ShutdownSignal::noop(), Some(Duration::from_secs(60)));
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        finalizer.add(1, receiver);
+        drop(batch);
+
+        assert_eq!(stream.next().await, Some((BatchStatus::Delivered, 1)));
+    }
+
+    #[tokio::test]
+    async fn errors_batches_not_finalized_in_time() {
+        let (finalizer, mut stream) =
+            OrderedFinalizer::<usize>::new(ShutdownSignal::noop(), Some(Duration::from_millis(10)));
+
+        // Holding on to the batch keeps it from ever being finalized.
+        let (_batch, receiver) = BatchNotifier::new_with_receiver();
+        finalizer.add(1, receiver);
+
+        assert_eq!(stream.next().await, Some((BatchStatus::Errored, 1)));
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        RwLock,
+    },
+};
+
+use metrics::{GaugeValue, Key, Label, Recorder, Unit};
+
+/// Labels whose values come from events, templates or the environment rather than from a fixed
+/// set, such as the tenant of an event or the file it was read from.
+const GUARDED_LABELS: &[&str] = &[
+    "container_id",
+    "container_name",
+    "endpoint",
+    "file",
+    "http_path",
+    "key",
+    "partition",
+    "peer_addr",
+    "pod_name",
+    "pod_namespace",
+    "tenant",
+    "topic",
+    "uri",
+    "url",
+];
+
+/// The value that replaces the new values of a guarded label once it has reached the maximum.
+pub const OVERFLOW_VALUE: &str = "__overflow__";
+
+/// The default maximum number of values of each guarded label.
+pub const DEFAULT_MAX_LABEL_VALUES: usize = 1000;
+
+static MAX_LABEL_VALUES: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_LABEL_VALUES);
+
+/// Sets the maximum number of values of each guarded label. Values that were already recorded
+/// keep being recorded.
+pub fn set_max_label_values(max: usize) {
+    MAX_LABEL_VALUES.store(max, Ordering::Relaxed);
+}
+
+/// [`CardinalityGuard`] is a [`metrics::Recorder`] that bounds the number of values of the labels
+/// whose values aren't from a fixed set, across every metric. Once a label has reached the maximum
+/// number of values, its new values are recorded as [`OVERFLOW_VALUE`].
+pub(super) struct CardinalityGuard<R> {
+    inner: R,
+    values: RwLock<HashMap<&'static str, HashSet<String>>>,
+}
+
+impl<R> CardinalityGuard<R> {
+    pub(super) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            values: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn guard<'a>(&self, key: &'a Key) -> Cow<'a, Key> {
+        let overflowed: Vec<_> = key
+            .labels()
+            .filter_map(|label| {
+                let name = GUARDED_LABELS.iter().find(|name| **name == label.key())?;
+                (!self.admit(name, label.value())).then(|| label.key())
+            })
+            .collect();
+        if overflowed.is_empty() {
+            return Cow::Borrowed(key);
+        }
+
+        let labels: Vec<_> = key
+            .labels()
+            .map(|label| {
+                if overflowed.contains(&label.key()) {
+                    Label::new(label.key().to_owned(), OVERFLOW_VALUE)
+                } else {
+                    label.clone()
+                }
+            })
+            .collect();
+        Cow::Owned(Key::from_parts(key.name().to_owned(), labels))
+    }
+
+    /// Returns whether the value of the label is, or can become, one of its recorded values.
+    fn admit(&self, name: &'static str, value: &str) -> bool {
+        let max = MAX_LABEL_VALUES.load(Ordering::Relaxed);
+        let full = {
+            let values = self.values.read().expect("label values lock poisoned");
+            match values.get(name) {
+                Some(values) if values.contains(value) => return true,
+                Some(values) => values.len() >= max,
+                None => max == 0,
+            }
+        };
+
+        if !full {
+            let mut values = self.values.write().expect("label values lock poisoned");
+            let values = values.entry(name).or_default();
+            if values.contains(value) || values.len() < max {
+                values.insert(value.to_owned());
+                return true;
+            }
+        }
+
+        warn!(
+            message = "Label has too many values, recording new values as overflowed.",
+            label = name,
+            max_label_values = max,
+            internal_log_rate_secs = 60,
+        );
+        false
+    }
+}
+
+impl<R: Recorder> Recorder for CardinalityGuard<R> {
+    fn register_counter(&self, key: &Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner
+            .register_counter(&self.guard(key), unit, description);
+    }
+
+    fn register_gauge(&self, key: &Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner
+            .register_gauge(&self.guard(key), unit, description);
+    }
+
+    fn register_histogram(&self, key: &Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner
+            .register_histogram(&self.guard(key), unit, description);
+    }
+
+    fn increment_counter(&self, key: &Key, value: u64) {
+        self.inner.increment_counter(&self.guard(key), value);
+    }
+
+    fn update_gauge(&self, key: &Key, value: GaugeValue) {
+        self.inner.update_gauge(&self.guard(key), value);
+    }
+
+    fn record_histogram(&self, key: &Key, value: f64) {
+        self.inner.record_histogram(&self.guard(key), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tenant_key(tenant: String) -> Key {
+        Key::from_parts(
+            "tenant_events_total",
+            vec![
+                Label::new("component_id", "in"),
+                Label::new("tenant", tenant),
+            ],
+        )
+    }
+
+    #[test]
+    fn overflows_values_past_the_maximum() {
+        let guard = CardinalityGuard::new(metrics::NoopRecorder);
+        for i in 0..DEFAULT_MAX_LABEL_VALUES {
+            let key = tenant_key(i.to_string());
+            assert_eq!(*guard.guard(&key), key);
+        }
+
+        let guarded = guard.guard(&tenant_key("new".to_owned())).into_owned();
+        assert_eq!(guarded, tenant_key(OVERFLOW_VALUE.to_owned()));
+
+        // Values recorded before the maximum was reached keep their own series
+        let key = tenant_key("0".to_owned());
+        assert_eq!(*guard.guard(&key), key);
+    }
+
+    #[test]
+    fn leaves_other_labels_alone() {
+        let guard = CardinalityGuard::new(metrics::NoopRecorder);
+        for i in 0..=DEFAULT_MAX_LABEL_VALUES {
+            let key = Key::from_parts("events_total", vec![Label::new("stage", i.to_string())]);
+            assert_eq!(*guard.guard(&key), key);
+        }
+    }
+}
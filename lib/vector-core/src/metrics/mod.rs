@@ -1,3 +1,4 @@
+mod cardinality;
 mod ddsketch;
 mod handle;
 mod label_filter;
@@ -12,12 +13,15 @@ use once_cell::sync::OnceCell;
 use snafu::Snafu;
 
 pub use crate::metrics::{
+    cardinality::{set_max_label_values, DEFAULT_MAX_LABEL_VALUES, OVERFLOW_VALUE},
     ddsketch::{AgentDDSketch, BinMap, Config},
     handle::{Counter, Handle},
 };
 use crate::{
     event::Metric,
-    metrics::{label_filter::VectorLabelFilter, recorder::VectorRecorder},
+    metrics::{
+        cardinality::CardinalityGuard, label_filter::VectorLabelFilter, recorder::VectorRecorder,
+    },
 };
 
 pub(self) type Registry = metrics_util::Registry<Key, Handle, NotTracked<Handle>>;
@@ -84,7 +88,9 @@ fn init(recorder: VectorRecorder) -> Result<()> {
 
     // The recorder is the interface between metrics-rs and our registry. In our
     // case it doesn't _do_ much other than shepherd into the registry and
-    // update the cardinality counter, see above, as needed.
+    // update the cardinality counter, see above, as needed. The labels whose
+    // values come from events are bounded on the way in.
+    let recorder = CardinalityGuard::new(recorder);
     let recorder: Box<dyn metrics::Recorder> = if tracing_context_layer_enabled() {
         // Apply a layer to capture tracing span fields as labels.
         Box::new(TracingContextLayer::new(VectorLabelFilter).layer(recorder))
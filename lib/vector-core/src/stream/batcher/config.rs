@@ -2,6 +2,7 @@ use std::time::Duration;
 
 use data::BatchData;
 use limiter::BatchLimiter;
+use vector_common::internal_event::BatchFlushReason;
 
 use super::{data, limiter};
 
@@ -32,6 +33,9 @@ pub trait BatchConfig<T> {
     /// Returns true if it is not possible for another item to fit in the batch
     fn is_batch_full(&self) -> bool;
 
+    /// Returns which limit the batch reached, when it is full or the next item doesn't fit in it.
+    fn full_reason(&self) -> BatchFlushReason;
+
     /// It is safe to assume that `is_batch_full` would return `false` before this is called.
     /// You can return arbitrary metadata for an item that will be given back when the item
     /// is actually pushed onto the batch. This is useful if there is an expensive calculation
@@ -69,6 +73,10 @@ where
         self.batch_limiter.is_batch_full(&self.batch_data)
     }
 
+    fn full_reason(&self) -> BatchFlushReason {
+        self.batch_limiter.full_reason(&self.batch_data)
+    }
+
     fn item_fits_in_batch(&self, item: &T) -> (bool, Self::ItemMetadata) {
         self.batch_limiter
             .item_fits_in_batch(item, &self.batch_data)
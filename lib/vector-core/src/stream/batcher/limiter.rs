@@ -1,11 +1,24 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use vector_common::internal_event::BatchFlushReason;
+
 use crate::{stream::batcher::data::BatchData, ByteSizeOf};
 
+/// How much the ratio observed on a payload moves the estimate of `EncodedSizeRatio`.
+const RATIO_ALPHA: f64 = 0.2;
+
 pub trait BatchLimiter<T, B> {
     type ItemMetadata;
 
     /// Return true if it is not possible for another item to fit in the batch
     fn is_batch_full(&self, batch: &B) -> bool;
 
+    /// Which limit the batch reached, when it is full or the next item doesn't fit in it.
+    fn full_reason(&self, batch: &B) -> BatchFlushReason;
+
     /// It is safe to assume that `is_batch_full` would return `false` before this is called.
     /// You can return arbitrary metadata for an item that will be given back when the item
     /// is actually pushed onto the batch. This is useful if there is an expensive calculation
@@ -48,6 +61,14 @@ where
         batch.len() >= self.batch_item_limit || self.current_size >= self.batch_size_limit
     }
 
+    fn full_reason(&self, batch: &B) -> BatchFlushReason {
+        if batch.len() >= self.batch_item_limit {
+            BatchFlushReason::MaxEvents
+        } else {
+            BatchFlushReason::MaxBytes
+        }
+    }
+
     fn item_fits_in_batch(&self, item: &T, batch: &B) -> (bool, Self::ItemMetadata) {
         let item_size = self.item_size_calculator.size(item);
         if batch.len() == 0 {
@@ -80,6 +101,64 @@ impl<T: ByteSizeOf> ItemBatchSize<T> for ByteSizeOfItemSize {
     }
 }
 
+/// Estimates the size of items once encoded and compressed from their in-memory size, using the
+/// ratio between the two sizes observed on the payloads built so far, so that batches are limited
+/// by the size of the payloads they are sent in.
+///
+/// Clones share their estimate, so that the request builder of a sink can observe the payloads it
+/// builds while its batcher sizes items with the same estimate. Until a payload is observed, items
+/// are sized by their in-memory size.
+#[derive(Clone, Debug)]
+pub struct EncodedSizeRatio {
+    ratio: Arc<AtomicU64>,
+}
+
+impl EncodedSizeRatio {
+    pub fn new() -> Self {
+        Self {
+            ratio: Arc::new(AtomicU64::new(1.0_f64.to_bits())),
+        }
+    }
+
+    /// Updates the estimate with the in-memory size of the events of a payload and the size of
+    /// the payload once encoded and compressed.
+    pub fn observe(&self, in_memory_size: usize, encoded_size: usize) {
+        if in_memory_size == 0 {
+            return;
+        }
+        let observed = encoded_size as f64 / in_memory_size as f64;
+        // The closure always returns `Some`, so the update can't fail.
+        let _ = self
+            .ratio
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                let ratio = f64::from_bits(bits);
+                Some((ratio + RATIO_ALPHA * (observed - ratio)).to_bits())
+            });
+    }
+
+    /// The current ratio of encoded size to in-memory size.
+    pub fn ratio(&self) -> f64 {
+        f64::from_bits(self.ratio.load(Ordering::Relaxed))
+    }
+
+    /// The estimated encoded size of an item of the given in-memory size.
+    pub fn estimate(&self, in_memory_size: usize) -> usize {
+        (in_memory_size as f64 * self.ratio()).ceil() as usize
+    }
+}
+
+impl Default for EncodedSizeRatio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ByteSizeOf> ItemBatchSize<T> for EncodedSizeRatio {
+    fn size(&self, item: &T) -> usize {
+        self.estimate(item.size_of())
+    }
+}
+
 impl<T, F> ItemBatchSize<T> for F
 where
     F: Fn(&T) -> usize,
@@ -15,6 +15,9 @@ use futures::{
 };
 use pin_project::pin_project;
 use tokio::time::Sleep;
+use vector_common::internal_event::{BatchFlushReason, BatchFlushed};
+
+use crate::internal_event::emit;
 
 #[pin_project]
 pub struct Batcher<S, C> {
@@ -65,7 +68,7 @@ where
                         if this.state.len() == 0 {
                             Poll::Ready(None)
                         } else {
-                            Poll::Ready(Some(this.state.take_batch()))
+                            Poll::Ready(Some(take_batch(this.state, BatchFlushReason::Shutdown)))
                         }
                     }
                 }
@@ -75,13 +78,15 @@ where
                         this.state.push(item, item_metadata);
                         if this.state.is_batch_full() {
                             this.timer.set(Maybe::None);
-                            return Poll::Ready(Some(this.state.take_batch()));
+                            let reason = this.state.full_reason();
+                            return Poll::Ready(Some(take_batch(this.state, reason)));
                         } else if this.state.len() == 1 {
                             this.timer
                                 .set(Maybe::Some(tokio::time::sleep(this.state.timeout())));
                         }
                     } else {
-                        let output = Poll::Ready(Some(this.state.take_batch()));
+                        let reason = this.state.full_reason();
+                        let output = Poll::Ready(Some(take_batch(this.state, reason)));
                         this.state.push(item, item_metadata);
                         this.timer
                             .set(Maybe::Some(tokio::time::sleep(this.state.timeout())));
@@ -97,7 +102,7 @@ where
                                 this.state.len() != 0,
                                 "timer should have been cancelled"
                             );
-                            Poll::Ready(Some(this.state.take_batch()))
+                            Poll::Ready(Some(take_batch(this.state, BatchFlushReason::Timeout)))
                         } else {
                             Poll::Pending
                         }
@@ -112,6 +117,14 @@ where
     }
 }
 
+fn take_batch<T, C: BatchConfig<T>>(state: &mut C, reason: BatchFlushReason) -> C::Batch {
+    emit(BatchFlushed {
+        reason,
+        events: state.len(),
+    });
+    state.take_batch()
+}
+
 #[cfg(test)]
 #[allow(clippy::similar_names)]
 mod test {
@@ -164,6 +177,27 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn encoded_size_limit() {
+        let ratio = limiter::EncodedSizeRatio::new();
+        for _ in 0..200 {
+            ratio.observe(100, 50);
+        }
+        assert_eq!(ratio.estimate(8), 4);
+
+        let batcher = Batcher::new(
+            stream::iter([1_u64, 2, 3, 4, 5]),
+            BatcherSettings::new(
+                Duration::from_millis(100),
+                NonZeroUsize::new(16).unwrap(),
+                NonZeroUsize::new(100).unwrap(),
+            )
+            .into_encoded_size_config(ratio),
+        );
+        let batches: Vec<_> = batcher.collect().await;
+        assert_eq!(batches, vec![vec![1, 2, 3, 4], vec![5]]);
+    }
+
     #[tokio::test]
     async fn timeout_limit() {
         tokio::time::pause();
@@ -16,13 +16,15 @@ use futures::{
 use pin_project::pin_project;
 use tokio_util::time::{delay_queue::Key, DelayQueue};
 use twox_hash::XxHash64;
+use vector_common::internal_event::{BatchFlushReason, BatchFlushed};
 
 use crate::{
+    internal_event::emit,
     partition::Partitioner,
     stream::batcher::{
         config::BatchConfigParts,
         data::BatchReduce,
-        limiter::{ByteSizeOfItemSize, ItemBatchSize, SizeLimit},
+        limiter::{ByteSizeOfItemSize, EncodedSizeRatio, ItemBatchSize, SizeLimit},
     },
     time::KeyedTimer,
     ByteSizeOf,
@@ -164,13 +166,13 @@ where
         }
     }
 
-    /// Unconditionally insert an element into the batch
+    /// Unconditionally insert an element of the given size into the batch
     ///
     /// This function is similar to `push` except that the caller does not need
-    /// to call `has_space` prior to calling this and it will never
+    /// to call `overflow` prior to calling this and it will never
     /// panic. Intended to be used only when insertion must not fail.
-    fn with(mut self, value: I) -> Self {
-        self.allocated_bytes += value.size_of();
+    fn with(mut self, value: I, size: usize) -> Self {
+        self.allocated_bytes += size;
         self.elements.push(value);
         self
     }
@@ -184,29 +186,43 @@ where
         self.elements
     }
 
-    /// Whether the batch has space for a new item
+    /// Decompose the batch, recording why it was flushed
+    fn flush(self, reason: BatchFlushReason) -> Vec<I> {
+        emit(BatchFlushed {
+            reason,
+            events: self.elements.len(),
+        });
+        self.into_inner()
+    }
+
+    /// Which limit a new item of the given size would go over
     ///
-    /// This function returns true of there is space both in terms of item count
-    /// and byte count for the given item, false otherwise.
-    fn has_space(&self, value: &I) -> bool {
-        let too_many_elements = self.elements.len() + 1 > self.element_limit;
-        let too_many_bytes = self.allocated_bytes + value.size_of() > self.allocation_limit;
-        !(too_many_elements || too_many_bytes)
+    /// This function returns `None` if there is space both in terms of item
+    /// count and byte count for the given item, and the limit it would go
+    /// over otherwise.
+    fn overflow(&self, size: usize) -> Option<BatchFlushReason> {
+        if self.elements.len() + 1 > self.element_limit {
+            Some(BatchFlushReason::MaxEvents)
+        } else if self.allocated_bytes + size > self.allocation_limit {
+            Some(BatchFlushReason::MaxBytes)
+        } else {
+            None
+        }
     }
 
-    /// Push an element into the batch
+    /// Push an element of the given size into the batch
     ///
     /// This function pushes an element into the batch. Callers must be sure to
-    /// call `has_space` prior to calling this function and receive a positive
+    /// call `overflow` prior to calling this function and receive a negative
     /// result.
     ///
     /// # Panics
     ///
     /// This function will panic if there is not sufficient space in the batch
     /// for a new element to be inserted.
-    fn push(&mut self, value: I) {
-        assert!(self.has_space(&value));
-        self.allocated_bytes += value.size_of();
+    fn push(&mut self, value: I, size: usize) {
+        assert!(self.overflow(size).is_none());
+        self.allocated_bytes += size;
         self.elements.push(value);
     }
 }
@@ -246,6 +262,16 @@ impl BatcherSettings {
         self.into_item_size_config(ByteSizeOfItemSize)
     }
 
+    /// A batcher config using an estimate of the encoded, compressed size of items to determine
+    /// batch sizes, so that the size limit applies to the payloads the batches are sent in.
+    /// The output is a Vec<T>
+    pub fn into_encoded_size_config<T: ByteSizeOf>(
+        self,
+        ratio: EncodedSizeRatio,
+    ) -> BatchConfigParts<SizeLimit<EncodedSizeRatio>, Vec<T>> {
+        self.into_item_size_config(ratio)
+    }
+
     /// A batcher config using the `ItemBatchSize` trait to determine batch sizes.
     /// The output is a Vec<T>
     pub fn into_item_size_config<T, I>(self, item_size: I) -> BatchConfigParts<SizeLimit<I>, Vec<T>>
@@ -290,7 +316,7 @@ impl BatcherSettings {
 }
 
 #[pin_project]
-pub struct PartitionedBatcher<St, Prt, KT>
+pub struct PartitionedBatcher<St, Prt, KT, Sz = ByteSizeOfItemSize>
 where
     Prt: Partitioner,
 {
@@ -310,6 +336,9 @@ where
     timer: KT,
     /// The partitioner for this `Batcher`
     partitioner: Prt,
+    /// The calculator of the size of each item, counted against
+    /// `batch_allocation_limit`
+    item_size: Sz,
     #[pin]
     /// The stream this `Batcher` wraps
     stream: Fuse<St>,
@@ -330,6 +359,7 @@ where
             closed_batches: Vec::default(),
             timer: ExpirationQueue::new(settings.timeout),
             partitioner,
+            item_size: ByteSizeOfItemSize,
             stream: stream.fuse(),
         }
     }
@@ -357,18 +387,40 @@ where
             closed_batches: Vec::default(),
             timer,
             partitioner,
+            item_size: ByteSizeOfItemSize,
             stream: stream.fuse(),
         }
     }
 }
 
-impl<St, Prt, KT> Stream for PartitionedBatcher<St, Prt, KT>
+impl<St, Prt, KT, Sz> PartitionedBatcher<St, Prt, KT, Sz>
+where
+    Prt: Partitioner,
+{
+    /// Sizes items with the given calculator rather than by their in-memory size, such as by
+    /// an estimate of their encoded size.
+    pub fn with_item_size<Sz2>(self, item_size: Sz2) -> PartitionedBatcher<St, Prt, KT, Sz2> {
+        PartitionedBatcher {
+            batch_allocation_limit: self.batch_allocation_limit,
+            batch_item_limit: self.batch_item_limit,
+            batches: self.batches,
+            closed_batches: self.closed_batches,
+            timer: self.timer,
+            partitioner: self.partitioner,
+            item_size,
+            stream: self.stream,
+        }
+    }
+}
+
+impl<St, Prt, KT, Sz> Stream for PartitionedBatcher<St, Prt, KT, Sz>
 where
     St: Stream<Item = Prt::Item>,
     Prt: Partitioner + Unpin,
     Prt::Key: Eq + Hash + Clone,
     Prt::Item: ByteSizeOf,
     KT: KeyedTimer<Prt::Key>,
+    Sz: ItemBatchSize<Prt::Item>,
 {
     type Item = (Prt::Key, Vec<Prt::Item>);
 
@@ -392,7 +444,8 @@ where
                             .batches
                             .remove(&item_key)
                             .expect("batch should exist if it is set to expire");
-                        this.closed_batches.push((item_key, batch.into_inner()));
+                        this.closed_batches
+                            .push((item_key, batch.flush(BatchFlushReason::Timeout)));
 
                         continue;
                     }
@@ -408,7 +461,7 @@ where
                         this.closed_batches.extend(
                             this.batches
                                 .drain()
-                                .map(|(key, batch)| (key, batch.into_inner())),
+                                .map(|(key, batch)| (key, batch.flush(BatchFlushReason::Shutdown))),
                         );
                         continue;
                     }
@@ -416,31 +469,34 @@ where
                 }
                 Poll::Ready(Some(item)) => {
                     let item_key = this.partitioner.partition(&item);
+                    let item_size = this.item_size.size(&item);
                     let item_limit: usize = *this.batch_item_limit;
                     let alloc_limit: usize = *this.batch_allocation_limit;
 
                     if let Some(batch) = this.batches.get_mut(&item_key) {
-                        if batch.has_space(&item) {
+                        match batch.overflow(item_size) {
                             // When there's space in the partition batch just
                             // push the item in and loop back around.
-                            batch.push(item);
-                        } else {
-                            let new_batch = Batch::new(item_limit, alloc_limit).with(item);
-                            let batch = mem::replace(batch, new_batch);
-
-                            // The batch for this partition key was set to
-                            // expire, but now it's overflowed and must be
-                            // pushed out, so now we reset the batch timeout.
-                            this.timer.insert(item_key.clone());
-
-                            this.closed_batches.push((item_key, batch.into_inner()));
+                            None => batch.push(item, item_size),
+                            Some(reason) => {
+                                let new_batch =
+                                    Batch::new(item_limit, alloc_limit).with(item, item_size);
+                                let batch = mem::replace(batch, new_batch);
+
+                                // The batch for this partition key was set to
+                                // expire, but now it's overflowed and must be
+                                // pushed out, so now we reset the batch timeout.
+                                this.timer.insert(item_key.clone());
+
+                                this.closed_batches.push((item_key, batch.flush(reason)));
+                            }
                         }
                     } else {
                         // We have no batch yet for this partition key, so
                         // create one and create the expiration entries as well.
                         // This allows the batch to expire before filling up,
                         // and vise versa.
-                        let batch = Batch::new(item_limit, alloc_limit).with(item);
+                        let batch = Batch::new(item_limit, alloc_limit).with(item, item_size);
                         this.batches.insert(item_key.clone(), batch);
                         this.timer.insert(item_key);
                     }
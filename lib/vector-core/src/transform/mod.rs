@@ -369,6 +369,14 @@ impl TransformOutputsBuf {
         std::mem::take(&mut self.named_buffers)
     }
 
+    /// Iterates over the arrays of events of every output.
+    pub fn arrays_mut(&mut self) -> impl Iterator<Item = &mut EventArray> {
+        self.primary_buffer
+            .iter_mut()
+            .chain(self.named_buffers.values_mut())
+            .flat_map(|buf| buf.0.iter_mut())
+    }
+
     pub fn len(&self) -> usize {
         self.primary_buffer.as_ref().map_or(0, OutputBuffer::len)
             + self
@@ -449,6 +457,7 @@ impl OutputBuffer {
             EventArray::Logs(l) => l.first().map(Into::into),
             EventArray::Metrics(m) => m.first().map(Into::into),
             EventArray::Traces(t) => t.first().map(Into::into),
+            EventArray::MetricColumns(c) => c.rows().first().map(Into::into),
         })
     }
 
@@ -7,7 +7,13 @@ use ::value::Value;
 use vrl::prelude::*;
 
 pub(crate) fn keys() -> Vec<Value> {
-    vec![value!("datadog_api_key"), value!("splunk_hec_token")]
+    vec![
+        value!("datadog_api_key"),
+        value!("splunk_hec_token"),
+        value!("priority"),
+        value!("output"),
+        value!("ttl"),
+    ]
 }
 
 pub fn vrl_functions() -> Vec<Box<dyn vrl::Function>> {
@@ -94,6 +94,7 @@ impl Expression for SetMetadataFieldFn {
     }
 
     fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
-        TypeDef::null().infallible()
+        // Only the priority and ttl are parsed from the value, the other keys accept any string.
+        TypeDef::null().with_fallibility(matches!(self.key.as_str(), "priority" | "ttl"))
     }
 }
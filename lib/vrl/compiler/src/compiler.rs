@@ -1,12 +1,15 @@
+use std::{collections::HashMap, sync::Arc};
+
 use diagnostic::{DiagnosticList, DiagnosticMessage, Severity, Span};
 use lookup::LookupBuf;
-use parser::ast::{self, Node, QueryTarget};
+use parser::ast::{self, Ident, Node, QueryTarget};
+use value::Value;
 
 use crate::{
     expression::*,
     program::ProgramInfo,
     state::{ExternalEnv, LocalEnv},
-    Function, Program,
+    Function, Program, TypeDef,
 };
 
 pub(crate) type Diagnostics = Vec<Box<dyn DiagnosticMessage>>;
@@ -33,6 +36,56 @@ pub(crate) struct Compiler<'a> {
     /// back to `None`, if the parent expression of a fallible expression
     /// nullifies the fallibility of that expression.
     fallible_expression_error: Option<Box<dyn DiagnosticMessage>>,
+
+    /// The user-defined functions available to the expressions that follow
+    /// their definition.
+    ///
+    /// The body of a function is compiled for the types of the arguments of
+    /// its calls, so that the types of the parameters can be inferred from
+    /// them. Calls with the same argument types share the same compiled body.
+    user_functions: HashMap<Ident, UserFunctionDefinition>,
+
+    /// The user-defined functions whose bodies are currently being compiled.
+    ///
+    /// This is used to reject recursive calls, which would otherwise require
+    /// compiling the same body indefinitely.
+    user_function_calls: Vec<Ident>,
+
+    /// The values of the constants available to the expressions that follow
+    /// their definition.
+    ///
+    /// Each reference to a constant is compiled to its value, unless a
+    /// variable of the same name shadows it, such as a function parameter.
+    constants: HashMap<Ident, Value>,
+
+    /// The number of `for_each` loops enclosing the expression being compiled.
+    ///
+    /// Loop control statements are only valid within a loop. Closures and
+    /// function definitions start counting from zero, as they can't stop or
+    /// continue a loop outside of their body.
+    loop_depth: usize,
+
+    /// The merged type definitions of the values returned by the `return`
+    /// expressions of the program, or the function body being compiled.
+    ///
+    /// These are part of the type definition of the program or function, in
+    /// addition to the type definition of its last expression.
+    return_type_def: Option<TypeDef>,
+
+    /// The spans of the `return` expressions outside of function definitions
+    /// whose value isn't known to be `null`.
+    returned_values: Vec<Span>,
+}
+
+/// A user-defined function, along with the bodies compiled for its calls.
+#[cfg_attr(not(feature = "expr-function_call"), allow(dead_code))]
+struct UserFunctionDefinition {
+    parameters: Vec<Ident>,
+    block: Node<ast::Block>,
+
+    /// The compiled bodies, along with the argument types they were compiled
+    /// for. A body that failed to compile is `None`.
+    bodies: Vec<(Vec<TypeDef>, Option<Arc<user_function::Body>>)>,
 }
 
 impl<'a> Compiler<'a> {
@@ -47,6 +100,12 @@ impl<'a> Compiler<'a> {
             external_assignments: vec![],
             skip_missing_query_target: vec![],
             fallible_expression_error: None,
+            user_functions: HashMap::new(),
+            user_function_calls: vec![],
+            constants: HashMap::new(),
+            loop_depth: 0,
+            return_type_def: None,
+            returned_values: vec![],
         }
     }
 
@@ -87,8 +146,21 @@ impl<'a> Compiler<'a> {
         };
 
         let expressions = Block::new(expressions, self.local);
+        let type_def = expressions.type_def((&LocalEnv::default(), external));
+        let type_def = match self.return_type_def {
+            Some(return_type_def) => type_def.merge_deep(return_type_def),
+            None => type_def,
+        };
 
-        Ok((Program { expressions, info }, warnings.into()))
+        Ok((
+            Program {
+                expressions,
+                info,
+                type_def,
+                returned_values: self.returned_values,
+            },
+            warnings.into(),
+        ))
     }
 
     fn compile_root_exprs(
@@ -112,6 +184,22 @@ impl<'a> Compiler<'a> {
 
                     Some(expr)
                 }
+                FunctionDefinition(node) => {
+                    self.compile_function_definition(node);
+                    None
+                }
+                ConstDefinition(node) => {
+                    self.compile_const_definition(node, external);
+                    None
+                }
+                Import(node) => {
+                    // Imports are resolved by the caller before compilation,
+                    // as they require access to the file system.
+                    let span = node.span();
+                    let error = crate::expression::Error::UnresolvedImport { span };
+                    self.diagnostics.push(Box::new(error));
+                    None
+                }
                 Error(err) => {
                     self.handle_parser_error(err);
                     None
@@ -140,13 +228,20 @@ impl<'a> Compiler<'a> {
             Literal(node) => self.compile_literal(node, external),
             Container(node) => self.compile_container(node, external).map(Into::into),
             IfStatement(node) => self.compile_if_statement(node, external).map(Into::into),
+            Match(node) => self.compile_match(node, external).map(Into::into),
+            ForEach(node) => self.compile_for_each(node, external).map(Into::into),
+            LoopControl(node) => self.compile_loop_control(node).map(Into::into),
             Op(node) => self.compile_op(node, external).map(Into::into),
             Assignment(node) => self.compile_assignment(node, external).map(Into::into),
             Query(node) => self.compile_query(node, external).map(Into::into),
             FunctionCall(node) => self.compile_function_call(node, external).map(Into::into),
-            Variable(node) => self.compile_variable(node, external).map(Into::into),
+            Variable(node) => match self.constant(node.inner()) {
+                Some(value) => Some(value.clone().into()),
+                None => self.compile_variable(node, external).map(Into::into),
+            },
             Unary(node) => self.compile_unary(node, external).map(Into::into),
             Abort(node) => self.compile_abort(node, external).map(Into::into),
+            Return(node) => self.compile_return(node, external).map(Into::into),
         }?;
 
         // If the previously compiled expression is fallible, _and_ we are
@@ -391,6 +486,267 @@ impl<'a> Compiler<'a> {
         ))
     }
 
+    #[cfg(feature = "expr-match")]
+    fn compile_match(
+        &mut self,
+        node: Node<ast::Match>,
+        external: &mut ExternalEnv,
+    ) -> Option<Match> {
+        let ast::Match { subject, arms } = node.into_inner();
+
+        let subject = self.compile_expr(*subject, external)?;
+        let subject_kind = subject.type_def((&self.local, external)).kind().clone();
+
+        let original_locals = self.local.clone();
+        let original_external = external.target().clone();
+
+        let mut merged: Option<(LocalEnv, crate::type_def::Details)> = None;
+        let mut compiled_arms = Vec::with_capacity(arms.len());
+
+        for arm in arms {
+            let ast::MatchArm { patterns, expr } = arm.into_inner();
+
+            let patterns = patterns
+                .into_iter()
+                .map(|pattern| self.compile_match_pattern(pattern, &subject_kind, external))
+                .collect::<Vec<_>>();
+
+            // Each arm starts from the state before the match expression, as
+            // only one of the arms is executed.
+            self.local = original_locals.clone();
+            external.update_target(original_external.clone());
+
+            let expr = self.compile_expr(expr, external);
+
+            merged = Some(match merged {
+                None => (self.local.clone(), external.target().clone()),
+                Some((locals, target)) => (
+                    self.local.clone().merge(locals),
+                    external.target().clone().merge(target),
+                ),
+            });
+
+            compiled_arms.push((patterns, expr));
+        }
+
+        let arms = compiled_arms
+            .into_iter()
+            .map(|(patterns, expr)| {
+                Some(MatchArm::new(
+                    patterns.into_iter().collect::<Option<_>>()?,
+                    expr?,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let r#match = Match::new(subject, arms);
+
+        // assignments must be the result of one of the arms, or the original
+        // value if no arm is guaranteed to match
+        match merged {
+            Some((locals, target)) if r#match.is_exhaustive(&subject_kind) => {
+                self.local = locals;
+                external.update_target(target);
+            }
+            Some((locals, target)) => {
+                self.local = locals.merge(original_locals);
+                external.update_target(target.merge(original_external));
+            }
+            None => {
+                self.local = original_locals;
+                external.update_target(original_external);
+            }
+        }
+
+        Some(r#match)
+    }
+
+    #[cfg(not(feature = "expr-match"))]
+    fn compile_match(&mut self, node: Node<ast::Match>, _: &mut ExternalEnv) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-match")
+    }
+
+    #[cfg(feature = "expr-for_each")]
+    fn compile_for_each(
+        &mut self,
+        node: Node<ast::ForEach>,
+        external: &mut ExternalEnv,
+    ) -> Option<ForEach> {
+        let ast::ForEach {
+            key,
+            value,
+            subject,
+            block,
+        } = node.into_inner();
+
+        let subject_span = subject.span();
+        let subject = self.compile_expr(*subject, external)?;
+        let subject_kind = subject.type_def((&self.local, external)).kind().clone();
+
+        let (key_kind, value_kind) = match ForEach::variable_kinds(&subject_kind) {
+            Some(kinds) => kinds,
+            None => {
+                let error = for_each::Error::NonIterable {
+                    span: subject_span,
+                    kind: subject_kind,
+                };
+
+                self.diagnostics.push(Box::new(error));
+                return None;
+            }
+        };
+
+        let key = key.into_inner();
+        let value = value.into_inner();
+        let variables = [(&key, key_kind), (&value, value_kind)];
+
+        // The loop body runs zero or more times, so it has to be compiled
+        // against the state before the loop, merged with the state after any
+        // number of iterations. The first pass only determines the state
+        // after a single iteration, its diagnostics are reported by the second
+        // pass.
+        let original_locals = self.local.clone();
+        let original_external = external.target().clone();
+
+        let diagnostics = self.diagnostics.len();
+        let fallible_expression_error = self.fallible_expression_error.take();
+
+        self.compile_loop_block(block.clone(), &variables, external)?;
+
+        self.diagnostics.truncate(diagnostics);
+        self.fallible_expression_error = fallible_expression_error;
+
+        self.local = self.local.clone().merge(original_locals);
+        external.update_target(external.target().clone().merge(original_external));
+
+        let loop_locals = self.local.clone();
+        let loop_external = external.target().clone();
+
+        let block = self.compile_loop_block(block, &variables, external)?;
+
+        self.local = self.local.clone().merge(loop_locals);
+        external.update_target(external.target().clone().merge(loop_external));
+
+        Some(ForEach::new(key, value, subject, block))
+    }
+
+    #[cfg(not(feature = "expr-for_each"))]
+    fn compile_for_each(&mut self, node: Node<ast::ForEach>, _: &mut ExternalEnv) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-for_each")
+    }
+
+    /// Compile the body of a loop, with the loop variables bound to the given
+    /// kinds.
+    ///
+    /// Any existing variables shadowed by the loop variables are restored
+    /// afterwards.
+    #[cfg(feature = "expr-for_each")]
+    fn compile_loop_block(
+        &mut self,
+        block: Node<ast::Block>,
+        variables: &[(&Ident, value::Kind)],
+        external: &mut ExternalEnv,
+    ) -> Option<Block> {
+        let local_snapshot = self.local.clone();
+
+        // Variables named `_` are discarded.
+        for (ident, kind) in variables.iter().filter(|(ident, _)| !ident.is_empty()) {
+            let details = crate::type_def::Details {
+                type_def: crate::TypeDef::from(kind.clone()).infallible(),
+                value: None,
+            };
+
+            self.local.insert_variable((*ident).clone(), details);
+        }
+
+        self.loop_depth += 1;
+        let block = self.compile_block(block, external);
+        self.loop_depth -= 1;
+
+        for (ident, _) in variables {
+            match local_snapshot.variable(ident) {
+                Some(details) => self
+                    .local
+                    .insert_variable((*ident).clone(), details.clone()),
+                None => {
+                    self.local.remove_variable(ident);
+                }
+            }
+        }
+
+        block
+    }
+
+    #[cfg(feature = "expr-for_each")]
+    fn compile_loop_control(&mut self, node: Node<ast::LoopControl>) -> Option<LoopControl> {
+        let (span, variant) = node.take();
+
+        if self.loop_depth == 0 {
+            let error = for_each::Error::OutsideLoop { span, variant };
+
+            self.diagnostics.push(Box::new(error));
+            return None;
+        }
+
+        Some(LoopControl::new(span, variant))
+    }
+
+    #[cfg(not(feature = "expr-for_each"))]
+    fn compile_loop_control(&mut self, node: Node<ast::LoopControl>) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-for_each")
+    }
+
+    #[cfg(feature = "expr-match")]
+    fn compile_match_pattern(
+        &mut self,
+        node: Node<ast::MatchPattern>,
+        subject: &value::Kind,
+        external: &mut ExternalEnv,
+    ) -> Option<Pattern> {
+        use ast::MatchPattern::*;
+        use match_expression::Error;
+
+        let (span, pattern) = node.take();
+
+        let pattern = match pattern {
+            Wildcard => Ok(Pattern::Wildcard),
+            Literal(node) => match self.compile_literal(node, external)?.as_value() {
+                Some(value::Value::Regex(regex)) => Ok(Pattern::Regex(regex)),
+                Some(value) => Ok(Pattern::Value(value)),
+                None => Err(Error::DynamicPattern { span }),
+            },
+            Range(start, end) => {
+                let start = self.compile_literal(start, external)?.as_value();
+                let end = self.compile_literal(end, external)?.as_value();
+
+                start
+                    .zip(end)
+                    .and_then(|(start, end)| Pattern::range(start, end))
+                    .ok_or(Error::InvalidRange { span })
+            }
+            Kind(ident) => {
+                let ident = ident.into_inner();
+
+                KindPattern::from_ident(&ident)
+                    .map(Pattern::Kind)
+                    .ok_or(Error::UnknownKind { span, ident })
+            }
+        };
+
+        let error = match pattern {
+            Ok(pattern) if pattern.kind().intersects(subject) => return Some(pattern),
+            Ok(pattern) => Error::UnreachablePattern {
+                span,
+                pattern: pattern.kind(),
+                subject: subject.clone(),
+            },
+            Err(error) => error,
+        };
+
+        self.diagnostics.push(Box::new(error));
+        None
+    }
+
     #[cfg(feature = "expr-op")]
     fn compile_op(&mut self, node: Node<ast::Op>, external: &mut ExternalEnv) -> Option<Op> {
         use parser::ast::Opcode;
@@ -457,8 +813,33 @@ impl<'a> Compiler<'a> {
 
         let assignment = node.into_inner();
 
+        // Constants are resolved at compile-time, and can't be reassigned.
+        let targets = match &assignment {
+            Single { target, .. } => vec![target],
+            Infallible { ok, err, .. } => vec![ok, err],
+        };
+
+        for target in targets {
+            if let ast::AssignmentTarget::Internal(ident, _) = target.inner() {
+                if self.constant(ident).is_some() {
+                    let error = crate::expression::Error::ConstantAssignment {
+                        span: target.span(),
+                        ident: ident.clone(),
+                    };
+
+                    self.diagnostics.push(Box::new(error));
+                    return None;
+                }
+            }
+        }
+
         let node = match assignment {
-            Single { target, op, expr } => {
+            Single {
+                target,
+                op,
+                expr,
+                annotation,
+            } => {
                 let span = expr.span();
 
                 match op {
@@ -471,11 +852,44 @@ impl<'a> Compiler<'a> {
                                 None
                             })?;
 
-                        Node::new(span, Variant::Single { target, expr })
+                        let annotation = match annotation {
+                            Some(annotation) => Some(
+                                Assignment::annotation_kind(
+                                    target.span(),
+                                    annotation,
+                                    &expr,
+                                    (&self.local, external),
+                                )
+                                .map_err(|err| {
+                                    self.skip_missing_assignment_target(
+                                        target.clone().into_inner(),
+                                    );
+                                    self.diagnostics.push(Box::new(err));
+                                })
+                                .ok()?,
+                            ),
+                            None => None,
+                        };
+
+                        Node::new(
+                            span,
+                            Variant::Single {
+                                target,
+                                expr,
+                                annotation,
+                            },
+                        )
                     }
                     AssignmentOp::Merge => {
                         let expr = self.rewrite_to_merge(span, &target, expr, external)?;
-                        Node::new(span, Variant::Single { target, expr })
+                        Node::new(
+                            span,
+                            Variant::Single {
+                                target,
+                                expr,
+                                annotation: None,
+                            },
+                        )
                     }
                 }
             }
@@ -600,10 +1014,16 @@ impl<'a> Compiler<'a> {
 
         let target = match node.into_inner() {
             External => Target::External,
-            Internal(ident) => {
-                let variable = self.compile_variable(Node::new(span, ident), external)?;
-                Target::Internal(variable)
-            }
+            Internal(ident) => match self.constant(&ident) {
+                Some(value) => {
+                    let group = Group::new(value.clone().into());
+                    Target::Container(crate::expression::Container::new(group.into()))
+                }
+                None => {
+                    let variable = self.compile_variable(Node::new(span, ident), external)?;
+                    Target::Internal(variable)
+                }
+            },
             Container(container) => {
                 let container = self.compile_container(Node::new(span, container), external)?;
                 Target::Container(container)
@@ -652,6 +1072,17 @@ impl<'a> Compiler<'a> {
             self.fallible = true;
         }
 
+        if self.user_functions.contains_key(ident.inner()) {
+            return self.compile_user_function_call(
+                call_span,
+                ident,
+                abort_on_error,
+                arguments,
+                closure,
+                external,
+            );
+        }
+
         let (closure_variables, closure_block) = match closure {
             Some(closure) => {
                 let span = closure.span();
@@ -688,7 +1119,11 @@ impl<'a> Compiler<'a> {
                 None => None,
                 Some(block) => {
                     let span = block.span();
-                    match self.compile_block(block, external) {
+                    let loop_depth = std::mem::take(&mut self.loop_depth);
+                    let block = self.compile_block(block, external);
+                    self.loop_depth = loop_depth;
+
+                    match block {
                         Some(block) => Some(Node::new(span, block)),
                         None => return None,
                     }
@@ -708,6 +1143,262 @@ impl<'a> Compiler<'a> {
         })
     }
 
+    #[cfg(feature = "expr-function_call")]
+    fn compile_user_function_call(
+        &mut self,
+        call_span: Span,
+        ident: Node<Ident>,
+        abort_on_error: bool,
+        arguments: Vec<Node<FunctionArgument>>,
+        closure: Option<Node<ast::FunctionClosure>>,
+        external: &mut ExternalEnv,
+    ) -> Option<FunctionCall> {
+        let (ident_span, ident) = ident.take();
+
+        if let Some(closure) = closure {
+            let error = function_call::Error::UnexpectedClosure {
+                call_span,
+                closure_span: closure.span(),
+            };
+
+            self.diagnostics.push(Box::new(error));
+            return None;
+        }
+
+        if self.user_function_calls.contains(&ident) {
+            let error = user_function::Error::Recursion { call_span, ident };
+
+            self.diagnostics.push(Box::new(error));
+            return None;
+        }
+
+        let parameters = self.user_functions.get(&ident)?.parameters.clone();
+
+        let arguments = UserFunction::bind_arguments(call_span, ident_span, &parameters, arguments)
+            .map_err(|err| self.diagnostics.push(Box::new(err)))
+            .ok()?;
+
+        let mut argument_type_defs = Vec::with_capacity(arguments.len());
+        for argument in &arguments {
+            let type_def = argument.expr().type_def((&self.local, external));
+
+            if type_def.is_fallible() {
+                let error = function_call::Error::FallibleArgument {
+                    expr_span: argument.span(),
+                };
+
+                self.diagnostics.push(Box::new(error));
+                return None;
+            }
+
+            argument_type_defs.push(type_def);
+        }
+
+        let body = self.compile_user_function_body(&ident, argument_type_defs, external)?;
+
+        let expr = UserFunction::new(
+            body,
+            arguments.iter().map(|node| node.expr().clone()).collect(),
+        );
+
+        // Asking for an infallible function to abort on error makes no sense.
+        if abort_on_error && !expr.type_def((&self.local, external)).is_fallible() {
+            let error = function_call::Error::AbortInfallible {
+                ident_span,
+                abort_span: Span::new(ident_span.end(), ident_span.end() + 1),
+            };
+
+            self.diagnostics.push(Box::new(error));
+            return None;
+        }
+
+        Some(FunctionCall::user_defined(
+            call_span,
+            ident,
+            abort_on_error,
+            arguments,
+            Box::new(expr),
+        ))
+    }
+
+    /// Returns the body of a user-defined function compiled for the given
+    /// argument types, compiling it if no previous call passed arguments of
+    /// the same types.
+    #[cfg(feature = "expr-function_call")]
+    fn compile_user_function_body(
+        &mut self,
+        ident: &Ident,
+        argument_type_defs: Vec<TypeDef>,
+        external: &mut ExternalEnv,
+    ) -> Option<Arc<user_function::Body>> {
+        let definition = self.user_functions.get(ident)?;
+
+        if let Some((_, body)) = definition
+            .bodies
+            .iter()
+            .find(|(type_defs, _)| type_defs == &argument_type_defs)
+        {
+            return body.clone();
+        }
+
+        let parameters = definition.parameters.clone();
+        let block = definition.block.clone();
+
+        // The body of the function only has access to its parameters, which
+        // take on the type definitions of the arguments.
+        let mut local = LocalEnv::default();
+        for (parameter, type_def) in parameters.iter().zip(&argument_type_defs) {
+            let details = crate::type_def::Details {
+                type_def: type_def.clone(),
+                value: None,
+            };
+
+            local.insert_variable(parameter.clone(), details);
+        }
+
+        let caller_local = std::mem::replace(&mut self.local, local);
+        self.user_function_calls.push(ident.clone());
+        let loop_depth = std::mem::take(&mut self.loop_depth);
+        let caller_return_type_def = self.return_type_def.take();
+        let caller_fallible_expression_error = self.fallible_expression_error.take();
+
+        let block = self.compile_block(block, external);
+
+        self.fallible_expression_error = caller_fallible_expression_error;
+        let return_type_def = std::mem::replace(&mut self.return_type_def, caller_return_type_def);
+        self.loop_depth = loop_depth;
+        self.user_function_calls.pop();
+        self.local = caller_local;
+
+        // A body that fails to compile is kept too, so that its errors are
+        // only reported once.
+        let body = block
+            .map(|block| Arc::new(user_function::Body::new(parameters, block, return_type_def)));
+
+        self.user_functions
+            .get_mut(ident)
+            .expect("function is defined")
+            .bodies
+            .push((argument_type_defs, body.clone()));
+
+        body
+    }
+
+    #[cfg(feature = "expr-function_call")]
+    fn compile_function_definition(&mut self, node: Node<ast::FunctionDefinition>) {
+        let definition = node.into_inner();
+        let (ident_span, ident) = definition.ident.clone().take();
+
+        // User-defined functions can't shadow any of the provided functions, or
+        // any previously defined function.
+        if self.fns.iter().any(|f| f.identifier() == ident.as_ref())
+            || self.user_functions.contains_key(&ident)
+        {
+            let error = user_function::Error::Redefinition { ident_span, ident };
+            self.diagnostics.push(Box::new(error));
+            return;
+        }
+
+        for (index, parameter) in definition.parameters.iter().enumerate() {
+            if definition.parameters[..index]
+                .iter()
+                .any(|previous| previous.inner() == parameter.inner())
+            {
+                let error = user_function::Error::DuplicateParameter {
+                    parameter_span: parameter.span(),
+                    ident: parameter.inner().clone(),
+                };
+
+                self.diagnostics.push(Box::new(error));
+                return;
+            }
+        }
+
+        let parameters = definition
+            .parameters
+            .into_iter()
+            .map(Node::into_inner)
+            .collect();
+
+        self.user_functions.insert(
+            ident,
+            UserFunctionDefinition {
+                parameters,
+                block: definition.block,
+                bodies: vec![],
+            },
+        );
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn compile_function_definition(&mut self, node: Node<ast::FunctionDefinition>) {
+        // Guard against `dead_code` lint, to avoid having to sprinkle
+        // attributes all over the place.
+        let _ = (&self.user_functions, &self.user_function_calls);
+
+        self.handle_missing_feature_error(node.span(), "expr-function_call");
+    }
+
+    #[cfg(feature = "expr-literal")]
+    fn compile_const_definition(
+        &mut self,
+        node: Node<ast::ConstDefinition>,
+        external: &mut ExternalEnv,
+    ) {
+        let ast::ConstDefinition { ident, expr } = node.into_inner();
+        let (ident_span, ident) = ident.take();
+
+        // Constants can't shadow any previously defined constant or variable.
+        if self.constants.contains_key(&ident) || self.local.variable(&ident).is_some() {
+            let error = crate::expression::Error::ConstantRedefinition {
+                span: ident_span,
+                ident,
+            };
+
+            self.diagnostics.push(Box::new(error));
+            return;
+        }
+
+        let span = expr.span();
+        let value = self.compile_expr(expr, external).and_then(|expr| {
+            let value = expr.as_value();
+
+            if value.is_none() {
+                let error = crate::expression::Error::NonConstant { span };
+                self.diagnostics.push(Box::new(error));
+            }
+
+            value
+        });
+
+        // An expression that resolves at compile-time can't fail, so there's
+        // no need to report it as unhandled on top of the error above.
+        self.fallible_expression_error = None;
+
+        match value {
+            Some(value) => {
+                self.constants.insert(ident, value);
+            }
+            None => self
+                .skip_missing_query_target
+                .push((QueryTarget::Internal(ident), LookupBuf::root())),
+        }
+    }
+
+    #[cfg(not(feature = "expr-literal"))]
+    fn compile_const_definition(&mut self, node: Node<ast::ConstDefinition>, _: &mut ExternalEnv) {
+        self.handle_missing_feature_error(node.span(), "expr-literal");
+    }
+
+    /// The value of the constant the identifier refers to, if it isn't
+    /// shadowed by a variable.
+    fn constant(&self, ident: &Ident) -> Option<&Value> {
+        match self.local.variable(ident) {
+            Some(_) => None,
+            None => self.constants.get(ident),
+        }
+    }
+
     #[cfg(feature = "expr-function_call")]
     fn compile_function_argument(
         &mut self,
@@ -815,6 +1506,42 @@ impl<'a> Compiler<'a> {
         self.handle_missing_feature_error(node.span(), "expr-abort")
     }
 
+    #[cfg(feature = "expr-return")]
+    fn compile_return(
+        &mut self,
+        node: Node<ast::Return>,
+        external: &mut ExternalEnv,
+    ) -> Option<Return> {
+        let (span, ast::Return { expr }) = node.take();
+        let expr = match expr {
+            Some(node) => Some(self.compile_expr(*node, external)?),
+            None => None,
+        };
+
+        let expr = Return::new(span, expr);
+        let type_def = expr.value_type_def((&self.local, external));
+
+        if self.user_function_calls.is_empty() && !type_def.kind().is_null() {
+            self.returned_values.push(span);
+        }
+
+        self.return_type_def = Some(match self.return_type_def.take() {
+            Some(return_type_def) => return_type_def.merge_deep(type_def),
+            None => type_def,
+        });
+
+        Some(expr)
+    }
+
+    #[cfg(not(feature = "expr-return"))]
+    fn compile_return(&mut self, node: Node<ast::Return>, _: &mut ExternalEnv) -> Option<Expr> {
+        // Guard against `dead_code` lint, to avoid having to sprinkle
+        // attributes all over the place.
+        let _ = &self.return_type_def;
+
+        self.handle_missing_feature_error(node.span(), "expr-return")
+    }
+
     fn handle_parser_error(&mut self, error: parser::Error) {
         self.diagnostics.push(Box::new(error))
     }
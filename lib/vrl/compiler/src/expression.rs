@@ -1,10 +1,11 @@
 use std::fmt;
 
-use diagnostic::{DiagnosticMessage, Label, Note};
+use diagnostic::{DiagnosticMessage, Label, Note, Urls};
 use dyn_clone::{clone_trait_object, DynClone};
 use value::Value;
 
 use crate::{
+    parser::Ident,
     state::{ExternalEnv, LocalEnv},
     Context, Span, TypeDef,
 };
@@ -26,19 +27,27 @@ mod object;
 mod op;
 #[cfg(feature = "expr-unary")]
 mod unary;
+#[cfg(feature = "expr-function_call")]
+pub(crate) mod user_function;
 mod variable;
 
 #[cfg(feature = "expr-assignment")]
 pub(crate) mod assignment;
 pub(crate) mod container;
+#[cfg(feature = "expr-for_each")]
+pub(crate) mod for_each;
 #[cfg(feature = "expr-function_call")]
 pub(crate) mod function_call;
 #[cfg(feature = "expr-literal")]
 pub(crate) mod literal;
+#[cfg(feature = "expr-match")]
+pub(crate) mod match_expression;
 #[cfg(feature = "expr-if_statement")]
 pub(crate) mod predicate;
 #[cfg(feature = "expr-query")]
 pub(crate) mod query;
+#[cfg(feature = "expr-return")]
+pub(crate) mod return_expression;
 
 pub use core::{ExpressionError, Resolved};
 
@@ -49,6 +58,8 @@ pub use array::Array;
 pub use assignment::Assignment;
 pub use block::Block;
 pub use container::{Container, Variant};
+#[cfg(feature = "expr-for_each")]
+pub use for_each::{ForEach, LoopControl};
 pub use function_argument::FunctionArgument;
 #[cfg(feature = "expr-function_call")]
 pub use function_call::FunctionCall;
@@ -57,6 +68,8 @@ pub use group::Group;
 pub use if_statement::IfStatement;
 #[cfg(feature = "expr-literal")]
 pub use literal::Literal;
+#[cfg(feature = "expr-match")]
+pub use match_expression::{KindPattern, Match, MatchArm, Pattern};
 pub use noop::Noop;
 #[cfg(feature = "expr-unary")]
 pub use not::Not;
@@ -67,8 +80,12 @@ pub use op::Op;
 pub use predicate::Predicate;
 #[cfg(feature = "expr-query")]
 pub use query::{Query, Target};
+#[cfg(feature = "expr-return")]
+pub use return_expression::Return;
 #[cfg(feature = "expr-unary")]
 pub use unary::Unary;
+#[cfg(feature = "expr-function_call")]
+pub use user_function::UserFunction;
 pub use variable::Variable;
 
 pub trait Expression: Send + Sync + fmt::Debug + DynClone {
@@ -120,6 +137,12 @@ pub enum Expr {
     Container(Container),
     #[cfg(feature = "expr-if_statement")]
     IfStatement(IfStatement),
+    #[cfg(feature = "expr-match")]
+    Match(Match),
+    #[cfg(feature = "expr-for_each")]
+    ForEach(ForEach),
+    #[cfg(feature = "expr-for_each")]
+    LoopControl(LoopControl),
     #[cfg(feature = "expr-op")]
     Op(Op),
     #[cfg(feature = "expr-assignment")]
@@ -134,6 +157,8 @@ pub enum Expr {
     Unary(Unary),
     #[cfg(feature = "expr-abort")]
     Abort(Abort),
+    #[cfg(feature = "expr-return")]
+    Return(Return),
 }
 
 impl Expr {
@@ -152,6 +177,12 @@ impl Expr {
             },
             #[cfg(feature = "expr-if_statement")]
             IfStatement(..) => "if-statement",
+            #[cfg(feature = "expr-match")]
+            Match(..) => "match expression",
+            #[cfg(feature = "expr-for_each")]
+            ForEach(..) => "for-each loop",
+            #[cfg(feature = "expr-for_each")]
+            LoopControl(..) => "loop control",
             #[cfg(feature = "expr-op")]
             Op(..) => "operation",
             #[cfg(feature = "expr-assignment")]
@@ -166,6 +197,8 @@ impl Expr {
             Unary(..) => "unary operation",
             #[cfg(feature = "expr-abort")]
             Abort(..) => "abort operation",
+            #[cfg(feature = "expr-return")]
+            Return(..) => "return expression",
         }
     }
 
@@ -236,6 +269,12 @@ impl Expression for Expr {
             Container(v) => v.resolve(ctx),
             #[cfg(feature = "expr-if_statement")]
             IfStatement(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-match")]
+            Match(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-for_each")]
+            ForEach(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-for_each")]
+            LoopControl(v) => v.resolve(ctx),
             #[cfg(feature = "expr-op")]
             Op(v) => v.resolve(ctx),
             #[cfg(feature = "expr-assignment")]
@@ -250,6 +289,8 @@ impl Expression for Expr {
             Unary(v) => v.resolve(ctx),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-return")]
+            Return(v) => v.resolve(ctx),
         }
     }
 
@@ -262,6 +303,12 @@ impl Expression for Expr {
             Container(v) => Expression::as_value(v),
             #[cfg(feature = "expr-if_statement")]
             IfStatement(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-match")]
+            Match(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-for_each")]
+            ForEach(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-for_each")]
+            LoopControl(v) => Expression::as_value(v),
             #[cfg(feature = "expr-op")]
             Op(v) => Expression::as_value(v),
             #[cfg(feature = "expr-assignment")]
@@ -276,6 +323,8 @@ impl Expression for Expr {
             Unary(v) => Expression::as_value(v),
             #[cfg(feature = "expr-abort")]
             Abort(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-return")]
+            Return(v) => Expression::as_value(v),
         }
     }
 
@@ -288,6 +337,12 @@ impl Expression for Expr {
             Container(v) => v.type_def(state),
             #[cfg(feature = "expr-if_statement")]
             IfStatement(v) => v.type_def(state),
+            #[cfg(feature = "expr-match")]
+            Match(v) => v.type_def(state),
+            #[cfg(feature = "expr-for_each")]
+            ForEach(v) => v.type_def(state),
+            #[cfg(feature = "expr-for_each")]
+            LoopControl(v) => v.type_def(state),
             #[cfg(feature = "expr-op")]
             Op(v) => v.type_def(state),
             #[cfg(feature = "expr-assignment")]
@@ -302,6 +357,8 @@ impl Expression for Expr {
             Unary(v) => v.type_def(state),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.type_def(state),
+            #[cfg(feature = "expr-return")]
+            Return(v) => v.type_def(state),
         }
     }
 }
@@ -316,6 +373,12 @@ impl fmt::Display for Expr {
             Container(v) => v.fmt(f),
             #[cfg(feature = "expr-if_statement")]
             IfStatement(v) => v.fmt(f),
+            #[cfg(feature = "expr-match")]
+            Match(v) => v.fmt(f),
+            #[cfg(feature = "expr-for_each")]
+            ForEach(v) => v.fmt(f),
+            #[cfg(feature = "expr-for_each")]
+            LoopControl(v) => v.fmt(f),
             #[cfg(feature = "expr-op")]
             Op(v) => v.fmt(f),
             #[cfg(feature = "expr-assignment")]
@@ -330,6 +393,8 @@ impl fmt::Display for Expr {
             Unary(v) => v.fmt(f),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.fmt(f),
+            #[cfg(feature = "expr-return")]
+            Return(v) => v.fmt(f),
         }
     }
 }
@@ -356,6 +421,27 @@ impl From<IfStatement> for Expr {
     }
 }
 
+#[cfg(feature = "expr-match")]
+impl From<Match> for Expr {
+    fn from(r#match: Match) -> Self {
+        Expr::Match(r#match)
+    }
+}
+
+#[cfg(feature = "expr-for_each")]
+impl From<ForEach> for Expr {
+    fn from(for_each: ForEach) -> Self {
+        Expr::ForEach(for_each)
+    }
+}
+
+#[cfg(feature = "expr-for_each")]
+impl From<LoopControl> for Expr {
+    fn from(loop_control: LoopControl) -> Self {
+        Expr::LoopControl(loop_control)
+    }
+}
+
 #[cfg(feature = "expr-op")]
 impl From<Op> for Expr {
     fn from(op: Op) -> Self {
@@ -410,6 +496,13 @@ impl From<Abort> for Expr {
     }
 }
 
+#[cfg(feature = "expr-return")]
+impl From<Return> for Expr {
+    fn from(r#return: Return) -> Self {
+        Expr::Return(r#return)
+    }
+}
+
 #[cfg(feature = "expr-literal")]
 impl From<Value> for Expr {
     fn from(value: Value) -> Self {
@@ -461,6 +554,18 @@ pub enum Error {
 
     #[error("expression type unavailable")]
     Missing { span: Span, feature: &'static str },
+
+    #[error("unresolved import")]
+    UnresolvedImport { span: Span },
+
+    #[error("non-constant value")]
+    NonConstant { span: Span },
+
+    #[error("constant already defined")]
+    ConstantRedefinition { span: Span, ident: Ident },
+
+    #[error("assignment to constant")]
+    ConstantAssignment { span: Span, ident: Ident },
 }
 
 impl DiagnosticMessage for Error {
@@ -470,6 +575,10 @@ impl DiagnosticMessage for Error {
         match self {
             Fallible { .. } => 100,
             Missing { .. } => 900,
+            UnresolvedImport { .. } => 116,
+            NonConstant { .. } => 129,
+            ConstantRedefinition { .. } => 130,
+            ConstantAssignment { .. } => 131,
         }
     }
 
@@ -488,6 +597,25 @@ impl DiagnosticMessage for Error {
                     span,
                 ),
             ],
+            UnresolvedImport { span } => vec![
+                Label::primary("imports can't be resolved in this context", span),
+                Label::context("compile the program from a file to enable imports", span),
+            ],
+            NonConstant { span } => vec![
+                Label::primary("this expression can't be resolved at compile-time", span),
+                Label::context(
+                    "constants can only be assigned literals, other constants, or calls to pure functions with constant arguments",
+                    span,
+                ),
+            ],
+            ConstantRedefinition { span, ident } => vec![Label::primary(
+                format!(r#""{}" is already defined"#, ident),
+                span,
+            )],
+            ConstantAssignment { span, ident } => vec![
+                Label::primary(format!(r#"can't assign to constant "{}""#, ident), span),
+                Label::context("constants are resolved at compile-time", span),
+            ],
         }
     }
 
@@ -496,7 +624,13 @@ impl DiagnosticMessage for Error {
 
         match self {
             Fallible { .. } => vec![Note::SeeErrorDocs],
-            Missing { .. } => vec![],
+            Missing { .. } | UnresolvedImport { .. } => vec![],
+            NonConstant { .. } | ConstantRedefinition { .. } | ConstantAssignment { .. } => {
+                vec![Note::SeeDocs(
+                    "constants".to_owned(),
+                    Urls::expression_docs_url("#constant"),
+                )]
+            }
         }
     }
 }
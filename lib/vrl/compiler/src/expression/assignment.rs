@@ -1,8 +1,8 @@
 use std::{convert::TryFrom, fmt};
 
-use diagnostic::{DiagnosticMessage, Label, Note};
+use diagnostic::{DiagnosticMessage, Label, Note, Urls};
 use lookup::LookupBuf;
-use value::Value;
+use value::{kind::Collection, Kind, Value};
 
 use crate::{
     expression::{Expr, Resolved},
@@ -31,11 +31,15 @@ impl Assignment {
         let (_, variant) = node.take();
 
         let variant = match variant {
-            Variant::Single { target, expr } => {
+            Variant::Single {
+                target,
+                expr,
+                annotation,
+            } => {
                 let target_span = target.span();
                 let expr_span = expr.span();
                 let assignment_span = Span::new(target_span.start(), expr_span.start() - 1);
-                let type_def = expr.type_def((local, external));
+                let mut type_def = expr.type_def((local, external));
 
                 // Fallible expressions require infallible assignment.
                 if fallible_rhs.is_some() {
@@ -58,6 +62,15 @@ impl Assignment {
                     });
                 }
 
+                // A type annotation narrows the type of the variable to the
+                // declared types. This requires a runtime check, unless the
+                // expression is known to resolve to one of them.
+                let annotation = annotation.filter(|kind| !kind.is_superset(type_def.kind()));
+                if let Some(kind) = &annotation {
+                    type_def = TypeDef::from(narrow(type_def.kind(), kind))
+                        .with_fallibility(type_def.is_fallible());
+                }
+
                 let expr = expr.into_inner();
                 let target = Target::try_from(target.into_inner())?;
                 let value = expr.as_value();
@@ -67,6 +80,7 @@ impl Assignment {
                 Variant::Single {
                     target,
                     expr: Box::new(expr),
+                    annotation,
                 }
             }
 
@@ -134,6 +148,50 @@ impl Assignment {
         Ok(Self { variant })
     }
 
+    /// Resolve the type annotation of an assignment to the kind of values it
+    /// accepts.
+    ///
+    /// Returns an error if the annotation contains an unknown type, or if the
+    /// expression can never resolve to any of the annotated types.
+    pub(crate) fn annotation_kind(
+        target_span: Span,
+        annotation: Node<ast::TypeAnnotation>,
+        expr: &Node<Expr>,
+        state: (&LocalEnv, &ExternalEnv),
+    ) -> Result<Kind, Error> {
+        let expr_span = expr.span();
+        let assignment_span = Span::new(target_span.start(), expr_span.start() - 1);
+        let (annotation_span, ast::TypeAnnotation(types)) = annotation.take();
+
+        let mut kind = Kind::empty();
+        for node in types {
+            let (span, ident) = node.take();
+
+            if !add_annotation_type(&mut kind, &ident) {
+                return Err(Error {
+                    variant: ErrorVariant::UnknownType(ident, span),
+                    expr_span,
+                    assignment_span,
+                });
+            }
+        }
+
+        let type_def = expr.type_def(state);
+        if !kind.intersects(type_def.kind()) {
+            return Err(Error {
+                variant: ErrorVariant::TypeMismatch(
+                    kind.to_string(),
+                    type_def.kind().to_string(),
+                    annotation_span,
+                ),
+                expr_span,
+                assignment_span,
+            });
+        }
+
+        Ok(kind)
+    }
+
     /// Get a list of targets for this assignment.
     ///
     /// For regular assignments, this contains a single target, for infallible
@@ -168,7 +226,7 @@ impl fmt::Display for Assignment {
         use Variant::*;
 
         match &self.variant {
-            Single { target, expr } => write!(f, "{} = {}", target, expr),
+            Single { target, expr, .. } => write!(f, "{} = {}", target, expr),
             Infallible { ok, err, expr, .. } => write!(f, "{}, {} = {}", ok, err, expr),
         }
     }
@@ -179,7 +237,7 @@ impl fmt::Debug for Assignment {
         use Variant::*;
 
         match &self.variant {
-            Single { target, expr } => write!(f, "{:?} = {:?}", target, expr),
+            Single { target, expr, .. } => write!(f, "{:?} = {:?}", target, expr),
             Infallible { ok, err, expr, .. } => {
                 write!(f, "Ok({:?}), Err({:?}) = {:?}", ok, err, expr)
             }
@@ -340,6 +398,9 @@ pub(crate) enum Variant<T, U> {
     Single {
         target: T,
         expr: Box<U>,
+
+        /// The types the value is checked against before it is assigned.
+        annotation: Option<Kind>,
     },
     Infallible {
         ok: T,
@@ -359,8 +420,19 @@ where
         use Variant::*;
 
         let value = match self {
-            Single { target, expr } => {
+            Single {
+                target,
+                expr,
+                annotation,
+            } => {
                 let value = expr.resolve(ctx)?;
+
+                if let Some(kind) = annotation {
+                    if !is_instance(kind, &value) {
+                        return Err(format!("expected {}, got {}", kind, value.kind()).into());
+                    }
+                }
+
                 target.insert(value.clone(), ctx);
                 value
             }
@@ -375,6 +447,7 @@ where
                     err.insert(Value::Null, ctx);
                     value
                 }
+                Err(error) if error.is_control_flow() => return Err(error),
                 Err(error) => {
                     ok.insert(default.clone(), ctx);
                     let value = Value::from(error.to_string());
@@ -406,7 +479,7 @@ where
         use Variant::*;
 
         match self {
-            Single { target, expr } => write!(f, "{} = {}", target, expr),
+            Single { target, expr, .. } => write!(f, "{} = {}", target, expr),
             Infallible { ok, err, expr, .. } => write!(f, "{}, {} = {}", ok, err, expr),
         }
     }
@@ -414,6 +487,90 @@ where
 
 // -----------------------------------------------------------------------------
 
+/// The type names allowed in a type annotation.
+const ANNOTATION_TYPES: [&str; 9] = [
+    "string",
+    "integer",
+    "float",
+    "boolean",
+    "object",
+    "array",
+    "timestamp",
+    "regex",
+    "null",
+];
+
+/// Add the kind of values accepted by the given annotation type name.
+///
+/// Returns `false` if the type name is unknown.
+fn add_annotation_type(kind: &mut Kind, ident: &Ident) -> bool {
+    match ident.as_ref() {
+        "string" => kind.add_bytes(),
+        "integer" => kind.add_integer(),
+        "float" => kind.add_float(),
+        "boolean" => kind.add_boolean(),
+        "object" => kind.add_object(Collection::any()),
+        "array" => kind.add_array(Collection::any()),
+        "timestamp" => kind.add_timestamp(),
+        "regex" => kind.add_regex(),
+        "null" => kind.add_null(),
+        _ => return false,
+    };
+
+    true
+}
+
+/// The part of the inferred kind that is accepted by the annotated kind.
+fn narrow(inferred: &Kind, annotated: &Kind) -> Kind {
+    let mut kind = Kind::empty();
+
+    if inferred.contains_bytes() && annotated.contains_bytes() {
+        kind.add_bytes();
+    }
+    if inferred.contains_integer() && annotated.contains_integer() {
+        kind.add_integer();
+    }
+    if inferred.contains_float() && annotated.contains_float() {
+        kind.add_float();
+    }
+    if inferred.contains_boolean() && annotated.contains_boolean() {
+        kind.add_boolean();
+    }
+    if inferred.contains_timestamp() && annotated.contains_timestamp() {
+        kind.add_timestamp();
+    }
+    if inferred.contains_regex() && annotated.contains_regex() {
+        kind.add_regex();
+    }
+    if inferred.contains_null() && annotated.contains_null() {
+        kind.add_null();
+    }
+    if let (Some(array), true) = (inferred.as_array(), annotated.contains_array()) {
+        kind.add_array(array.clone());
+    }
+    if let (Some(object), true) = (inferred.as_object(), annotated.contains_object()) {
+        kind.add_object(object.clone());
+    }
+
+    kind
+}
+
+fn is_instance(kind: &Kind, value: &Value) -> bool {
+    match value {
+        Value::Bytes(_) => kind.contains_bytes(),
+        Value::Integer(_) => kind.contains_integer(),
+        Value::Float(_) => kind.contains_float(),
+        Value::Boolean(_) => kind.contains_boolean(),
+        Value::Timestamp(_) => kind.contains_timestamp(),
+        Value::Regex(_) => kind.contains_regex(),
+        Value::Null => kind.contains_null(),
+        Value::Array(_) => kind.contains_array(),
+        Value::Object(_) => kind.contains_object(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+
 #[derive(Debug)]
 pub(crate) struct Error {
     variant: ErrorVariant,
@@ -434,6 +591,12 @@ pub(crate) enum ErrorVariant {
 
     #[error("invalid assignment target")]
     InvalidTarget(Span),
+
+    #[error("unknown type annotation")]
+    UnknownType(Ident, Span),
+
+    #[error("type annotation mismatch")]
+    TypeMismatch(String, String, Span),
 }
 
 impl fmt::Display for Error {
@@ -457,6 +620,8 @@ impl DiagnosticMessage for Error {
             FallibleAssignment(..) => 103,
             InfallibleAssignment(..) => 104,
             InvalidTarget(..) => 641,
+            UnknownType(..) => 127,
+            TypeMismatch(..) => 128,
         }
     }
 
@@ -487,6 +652,23 @@ impl DiagnosticMessage for Error {
                 Label::primary("invalid assignment target", span),
                 Label::context("use one of variable or path", span),
             ],
+            UnknownType(ident, span) => vec![
+                Label::primary(format!(r#"unknown type "{}""#, ident), span),
+                Label::context(
+                    format!("expected one of: {}", ANNOTATION_TYPES.join(", ")),
+                    span,
+                ),
+            ],
+            TypeMismatch(annotated, inferred, span) => vec![
+                Label::primary(
+                    format!("this expression resolves to {}", inferred),
+                    self.expr_span,
+                ),
+                Label::context(
+                    format!("but the variable is declared as {}", annotated),
+                    span,
+                ),
+            ],
         }
     }
 
@@ -495,6 +677,10 @@ impl DiagnosticMessage for Error {
 
         match &self.variant {
             FallibleAssignment(..) | InfallibleAssignment(..) => vec![Note::SeeErrorDocs],
+            UnknownType(..) | TypeMismatch(..) => vec![Note::SeeDocs(
+                "type annotations".to_owned(),
+                Urls::expression_docs_url("#type-annotations"),
+            )],
             _ => vec![],
         }
     }
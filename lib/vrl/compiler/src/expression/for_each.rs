@@ -0,0 +1,238 @@
+use std::fmt;
+
+use diagnostic::{DiagnosticMessage, Label, Note, Urls};
+use value::{kind::merge, Kind, Value};
+
+use crate::{
+    expression::{Block, Expr, Resolved},
+    parser::{ast, Ident},
+    state::{ExternalEnv, LocalEnv, Runtime},
+    Context, Expression, ExpressionError, Span, TypeDef,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForEach {
+    key: Option<Ident>,
+    value: Option<Ident>,
+    subject: Box<Expr>,
+    block: Block,
+}
+
+impl ForEach {
+    pub(crate) fn new(key: Ident, value: Ident, subject: Expr, block: Block) -> Self {
+        // Variables named `_` are discarded.
+        let key = (!key.is_empty()).then(|| key);
+        let value = (!value.is_empty()).then(|| value);
+
+        Self {
+            key,
+            value,
+            subject: Box::new(subject),
+            block,
+        }
+    }
+
+    /// The kinds of the key and value variables, when iterating over a subject
+    /// of the given kind.
+    ///
+    /// Returns `None` if the subject isn't guaranteed to be an array or object.
+    pub(crate) fn variable_kinds(subject: &Kind) -> Option<(Kind, Kind)> {
+        if !subject.is_collection() {
+            return None;
+        }
+
+        let mut key = Kind::empty();
+        let mut value = Kind::empty();
+        let strategy = merge::Strategy {
+            depth: merge::Depth::Deep,
+            indices: merge::Indices::Keep,
+        };
+
+        if let Some(object) = subject.as_object() {
+            key.add_bytes();
+            value.merge(object.reduced_kind(), strategy);
+        }
+
+        if let Some(array) = subject.as_array() {
+            key.add_integer();
+            value.merge(array.reduced_kind(), strategy);
+        }
+
+        Some((key, value))
+    }
+}
+
+impl Expression for ForEach {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let items: Vec<(Value, Value)> = match self.subject.resolve(ctx)? {
+            Value::Object(object) => object
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+            Value::Array(array) => array
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| (index.into(), value))
+                .collect(),
+            value => return Err(format!("expected array or object, got {}", value.kind()).into()),
+        };
+
+        // The loop variables shadow any existing variables of the same name,
+        // which are restored once the loop ends.
+        let state = ctx.state();
+        let key = self
+            .key
+            .as_ref()
+            .and_then(|ident| state.variable(ident).cloned());
+        let value = self
+            .value
+            .as_ref()
+            .and_then(|ident| state.variable(ident).cloned());
+
+        let mut result = Ok(());
+        for (item_key, item_value) in items {
+            let state = ctx.state_mut();
+            insert(state, self.key.as_ref(), item_key);
+            insert(state, self.value.as_ref(), item_value);
+
+            match self.block.resolve(ctx) {
+                Ok(_) | Err(ExpressionError::Continue { .. }) => {}
+                Err(ExpressionError::Break { .. }) => break,
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+        }
+
+        let state = ctx.state_mut();
+        cleanup(state, self.key.as_ref(), key);
+        cleanup(state, self.value.as_ref(), value);
+
+        result.map(|_| Value::Null)
+    }
+
+    fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        let fallible =
+            self.subject.type_def(state).is_fallible() || self.block.type_def(state).is_fallible();
+
+        TypeDef::null().with_fallibility(fallible)
+    }
+}
+
+impl fmt::Display for ForEach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = self.key.as_ref().map_or("_", |ident| ident.as_ref());
+        let value = self.value.as_ref().map_or("_", |ident| ident.as_ref());
+
+        write!(
+            f,
+            "for_each {}, {} in {} {}",
+            key, value, self.subject, self.block
+        )
+    }
+}
+
+fn insert(state: &mut Runtime, ident: Option<&Ident>, data: Value) {
+    if let Some(ident) = ident {
+        state.insert_variable(ident.clone(), data);
+    }
+}
+
+fn cleanup(state: &mut Runtime, ident: Option<&Ident>, data: Option<Value>) {
+    match (ident, data) {
+        (Some(ident), Some(value)) => {
+            state.insert_variable(ident.clone(), value);
+        }
+        (Some(ident), None) => state.remove_variable(ident),
+        _ => {}
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopControl {
+    span: Span,
+    variant: ast::LoopControl,
+}
+
+impl LoopControl {
+    pub(crate) fn new(span: Span, variant: ast::LoopControl) -> Self {
+        Self { span, variant }
+    }
+}
+
+impl Expression for LoopControl {
+    fn resolve(&self, _: &mut Context) -> Resolved {
+        let span = self.span;
+
+        Err(match self.variant {
+            ast::LoopControl::Break => ExpressionError::Break { span },
+            ast::LoopControl::Continue => ExpressionError::Continue { span },
+        })
+    }
+
+    fn type_def(&self, _: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        TypeDef::null().infallible()
+    }
+}
+
+impl fmt::Display for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.variant.fmt(f)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("non-iterable for_each subject")]
+    NonIterable { span: Span, kind: Kind },
+
+    #[error("{variant} outside of a loop")]
+    OutsideLoop {
+        span: Span,
+        variant: ast::LoopControl,
+    },
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use Error::*;
+
+        match self {
+            NonIterable { .. } => 125,
+            OutsideLoop { .. } => 126,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::*;
+
+        match self {
+            NonIterable { span, kind } => vec![
+                Label::primary("for_each only iterates over an array or object", span),
+                Label::context(format!("this expression resolves to {}", kind), span),
+            ],
+            OutsideLoop { span, variant } => vec![
+                Label::primary(
+                    format!("{} can only be used within a for_each loop", variant),
+                    span,
+                ),
+                Label::context(
+                    "loops don't extend into closures or function definitions",
+                    span,
+                ),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        vec![Note::SeeDocs(
+            "for_each loops".to_owned(),
+            Urls::expression_docs_url("#for_each"),
+        )]
+    }
+}
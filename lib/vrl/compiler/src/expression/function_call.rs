@@ -1,7 +1,9 @@
-use std::{fmt, sync::Arc};
+use std::{collections::BTreeMap, fmt, sync::Arc};
 
 use anymap::AnyMap;
 use diagnostic::{DiagnosticMessage, Label, Note, Urls};
+use value::Value;
+use vector_common::TimeZone;
 
 use super::Block;
 use crate::{
@@ -11,7 +13,7 @@ use crate::{
         ArgumentList, Example, FunctionClosure, FunctionCompileContext, Parameter,
     },
     parser::{Ident, Node},
-    state::{ExternalEnv, LocalEnv},
+    state::{ExternalEnv, LocalEnv, Runtime},
     type_def::Details,
     value::Kind,
     Context, Expression, Function, Resolved, Span, TypeDef,
@@ -178,6 +180,9 @@ impl<'a> Builder<'a> {
                 });
             }
 
+            // No closure is provided for a function for which it is optional.
+            (Some(definition), None) if definition.is_optional => None,
+
             // Error if closure is missing from function that expects one.
             (Some(definition), None) => {
                 let example = definition.inputs.get(0).map(|input| input.example);
@@ -241,7 +246,14 @@ impl<'a> Builder<'a> {
                         //
                         // - check the arity of the closure arguments
                         // - set the expected type definition of each argument
-                        if input.variables.len() != variables.len() {
+                        //
+                        // Trailing optional variables can be left out of the closure.
+                        let required = input
+                            .variables
+                            .iter()
+                            .filter(|variable| variable.required)
+                            .count();
+                        if variables.len() < required || variables.len() > input.variables.len() {
                             let closure_arguments_span =
                                 variables.first().map_or(call_span, |node| {
                                     (node.span().start(), variables.last().unwrap().span().end())
@@ -251,6 +263,7 @@ impl<'a> Builder<'a> {
                             return Err(Error::ClosureArityMismatch {
                                 ident_span,
                                 closure_arguments_span,
+                                required,
                                 expected: input.variables.len(),
                                 supplied: variables.len(),
                             });
@@ -269,8 +282,15 @@ impl<'a> Builder<'a> {
                         //
                         // We set "bar" (index 0) to return bytes, and "baz" (index 1) to return an
                         // integer.
-                        for (index, input_var) in input.variables.clone().into_iter().enumerate() {
-                            let call_ident = &variables[index];
+                        //
+                        // Variables left out of the closure, or ignored using `_`, aren't set.
+                        for (input_var, call_ident) in
+                            input.variables.clone().into_iter().zip(variables.iter())
+                        {
+                            if call_ident.is_empty() {
+                                continue;
+                            }
+
                             let type_def = target.type_def((local, external));
 
                             let (type_def, value) = match input_var.kind {
@@ -477,17 +497,27 @@ impl<'a> Builder<'a> {
                 error: err.to_string(),
             })?;
 
-        Ok(FunctionCall {
+        let mut call = FunctionCall {
             abort_on_error: self.abort_on_error,
             expr,
             arguments_with_unknown_type_validity: self.arguments_with_unknown_type_validity,
             closure_fallible,
             closure,
             span: call_span,
-            ident: self.function.identifier(),
-            function_id: self.function_id,
+            ident: Ident::new(self.function.identifier()),
+            function_id: Some(self.function_id),
             arguments: self.arguments.clone(),
-        })
+            value: None,
+        };
+
+        // A pure function resolves to the same value every time it's called
+        // with the same arguments, so if all arguments are known at
+        // compile-time, there's no need to call the function at runtime.
+        if self.function.pure() {
+            call.value = call.fold((local, external));
+        }
+
+        Ok(call)
     }
 }
 
@@ -506,16 +536,85 @@ pub struct FunctionCall {
     span: Span,
 
     // used for equality check
-    ident: &'static str,
+    ident: Ident,
 
     // The index of the function in the list of stdlib functions.
     // Used by the VM to identify this function when called.
-    function_id: usize,
+    //
+    // This is `None` for calls to user-defined functions.
+    function_id: Option<usize>,
     arguments: Arc<Vec<Node<FunctionArgument>>>,
+
+    // The value this call resolves to, if it was resolved at compile-time.
+    value: Option<Value>,
 }
 
 #[allow(unused)] // will be used by LLVM runtime
 impl FunctionCall {
+    /// Create a call to a user-defined function.
+    ///
+    /// The given expression is the body of the function, compiled for this
+    /// specific call site.
+    pub(crate) fn user_defined(
+        span: Span,
+        ident: Ident,
+        abort_on_error: bool,
+        arguments: Vec<Node<FunctionArgument>>,
+        expr: Box<dyn Expression>,
+    ) -> Self {
+        Self {
+            abort_on_error,
+            expr,
+            arguments_with_unknown_type_validity: vec![],
+            closure_fallible: false,
+            closure: None,
+            span,
+            ident,
+            function_id: None,
+            arguments: Arc::new(arguments),
+            value: None,
+        }
+    }
+
+    /// Resolve the function call at compile-time.
+    ///
+    /// This returns `None` if any of the arguments (or the closure) can only
+    /// be resolved at runtime, or if the function call can fail.
+    fn fold(&self, (local, external): (&LocalEnv, &ExternalEnv)) -> Option<Value> {
+        if self.closure.is_some() || self.type_def((local, external)).is_fallible() {
+            return None;
+        }
+
+        if self
+            .arguments
+            .iter()
+            .any(|arg| arg.expr().as_value().is_none())
+        {
+            return None;
+        }
+
+        // Arguments can refer to variables whose value is known at
+        // compile-time, so those are made available to the function. Pure
+        // functions don't access the target or the timezone, so we can resolve
+        // them against empty ones.
+        let mut target = Value::Object(BTreeMap::new());
+        let mut state = Runtime::default();
+        let timezone = TimeZone::default();
+
+        for ident in local.variable_idents() {
+            if let Some(value) = local
+                .variable(ident)
+                .and_then(|details| details.value.clone())
+            {
+                state.insert_variable(ident.clone(), value);
+            }
+        }
+
+        let mut ctx = Context::new(&mut target, &mut state, &timezone);
+
+        self.expr.resolve(&mut ctx).ok()
+    }
+
     /// Takes the arguments passed and resolves them into the order they are defined
     /// in the function
     /// The error path in this function should never really be hit as the compiler should
@@ -584,11 +683,27 @@ impl FunctionCall {
 
 impl Expression for FunctionCall {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
+        if let Some(value) = &self.value {
+            return Ok(value.clone());
+        }
+
         self.expr.resolve(ctx).map_err(|err| match err {
+            // The body of a user-defined function is allowed to abort the
+            // program.
+            #[cfg(feature = "expr-abort")]
+            ExpressionError::Abort { .. } if self.function_id.is_none() => err,
             #[cfg(feature = "expr-abort")]
             ExpressionError::Abort { .. } => {
                 panic!("abort errors must only be defined by `abort` statement")
             }
+            // Loop control statements passed as an argument stop or continue
+            // the enclosing loop.
+            #[cfg(feature = "expr-for_each")]
+            ExpressionError::Break { .. } | ExpressionError::Continue { .. } => err,
+            // A `return` expression within an argument or closure stops the
+            // enclosing program or function.
+            #[cfg(feature = "expr-return")]
+            ExpressionError::Return { .. } => err,
             ExpressionError::Error {
                 message,
                 mut labels,
@@ -611,6 +726,10 @@ impl Expression for FunctionCall {
         })
     }
 
+    fn as_value(&self) -> Option<Value> {
+        self.value.clone()
+    }
+
     fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
         let mut type_def = self.expr.type_def(state);
 
@@ -812,6 +931,7 @@ pub(crate) enum Error {
     ClosureArityMismatch {
         ident_span: Span,
         closure_arguments_span: Span,
+        required: usize,
         expected: usize,
         supplied: usize,
     },
@@ -1002,8 +1122,12 @@ impl DiagnosticMessage for Error {
                 Label::context("this function does not accept a closure", call_span)
             ],
             MissingClosure { call_span, .. } => vec![Label::primary("this function expects a closure", call_span)],
-            ClosureArityMismatch { ident_span, closure_arguments_span, expected, supplied } => vec![
-                Label::primary(format!("this function requires a closure with {expected} argument(s)"), ident_span),
+            ClosureArityMismatch { ident_span, closure_arguments_span, required, expected, supplied } => vec![
+                if required == expected {
+                    Label::primary(format!("this function requires a closure with {expected} argument(s)"), ident_span)
+                } else {
+                    Label::primary(format!("this function requires a closure with {required} to {expected} argument(s)"), ident_span)
+                },
                 Label::context(format!("but {supplied} argument(s) are supplied"), closure_arguments_span)
             ],
             ClosureParameterTypeMismatch {
@@ -0,0 +1,374 @@
+use std::fmt;
+
+use diagnostic::{DiagnosticMessage, Label, Note, Urls};
+use value::{kind::Collection, Kind, Value, ValueRegex};
+
+use crate::{
+    expression::{Expr, Resolved},
+    parser::Ident,
+    state::{ExternalEnv, LocalEnv},
+    Context, Expression, Span, TypeDef,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    subject: Box<Expr>,
+    arms: Vec<MatchArm>,
+}
+
+impl Match {
+    pub(crate) fn new(subject: Expr, arms: Vec<MatchArm>) -> Self {
+        Self {
+            subject: Box::new(subject),
+            arms,
+        }
+    }
+
+    /// Returns `true` if any value of the given kind is matched by at least one
+    /// of the arms.
+    pub(crate) fn is_exhaustive(&self, subject: &Kind) -> bool {
+        let mut covered = Kind::empty();
+
+        for pattern in self.arms.iter().flat_map(|arm| &arm.patterns) {
+            match pattern {
+                Pattern::Wildcard => return true,
+                Pattern::Kind(kind) => kind.add_to(&mut covered),
+                Pattern::Value(Value::Null) => {
+                    covered.add_null();
+                }
+                _ => {}
+            }
+        }
+
+        covered.is_superset(subject)
+    }
+}
+
+impl Expression for Match {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let subject = self.subject.resolve(ctx)?;
+
+        for arm in &self.arms {
+            if arm.patterns.iter().any(|pattern| pattern.matches(&subject)) {
+                return arm.expr.resolve(ctx);
+            }
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        let subject = self.subject.type_def(state);
+
+        let type_def = self
+            .arms
+            .iter()
+            .map(|arm| arm.expr.type_def(state))
+            .reduce(TypeDef::merge_deep);
+
+        let type_def = match type_def {
+            Some(type_def) if self.is_exhaustive(subject.kind()) => type_def,
+            Some(type_def) => type_def.add_null(),
+            None => TypeDef::null(),
+        };
+
+        if subject.is_fallible() {
+            type_def.fallible()
+        } else {
+            type_def
+        }
+    }
+}
+
+impl fmt::Display for Match {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "match {} {{", self.subject)?;
+
+        let mut iter = self.arms.iter().peekable();
+        while let Some(arm) = iter.next() {
+            write!(f, " {}", arm)?;
+
+            if iter.peek().is_some() {
+                f.write_str(",")?;
+            }
+        }
+
+        if !self.arms.is_empty() {
+            f.write_str(" ")?;
+        }
+
+        f.write_str("}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    patterns: Vec<Pattern>,
+    expr: Expr,
+}
+
+impl MatchArm {
+    pub(crate) fn new(patterns: Vec<Pattern>, expr: Expr) -> Self {
+        Self { patterns, expr }
+    }
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.patterns.iter().peekable();
+        while let Some(pattern) = iter.next() {
+            pattern.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(" | ")?;
+            }
+        }
+
+        write!(f, " => {}", self.expr)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// Matches any value.
+    Wildcard,
+
+    /// Matches a value equal to the given value.
+    Value(Value),
+
+    /// Matches a string for which the regex has a match.
+    Regex(ValueRegex),
+
+    /// Matches an integer or float within the inclusive range.
+    Range(Value, Value),
+
+    /// Matches any value of the given kind.
+    Kind(KindPattern),
+}
+
+impl Pattern {
+    /// Create a range pattern from two numeric bounds.
+    ///
+    /// Returns `None` if either bound isn't a number, or if the range is empty.
+    pub(crate) fn range(start: Value, end: Value) -> Option<Self> {
+        let is_empty = match (&start, &end) {
+            (Value::Integer(start), Value::Integer(end)) => start > end,
+            _ => match (as_f64(&start), as_f64(&end)) {
+                (Some(start), Some(end)) => start > end,
+                _ => return None,
+            },
+        };
+
+        (!is_empty).then(|| Pattern::Range(start, end))
+    }
+
+    /// The kind of values this pattern can match.
+    pub(crate) fn kind(&self) -> Kind {
+        match self {
+            Pattern::Wildcard => Kind::any(),
+            Pattern::Value(value) => value.kind(),
+            Pattern::Regex(_) => Kind::bytes(),
+            Pattern::Range(..) => Kind::integer().or_float(),
+            Pattern::Kind(kind) => {
+                let mut covered = Kind::empty();
+                kind.add_to(&mut covered);
+                covered
+            }
+        }
+    }
+
+    fn matches(&self, value: &Value) -> bool {
+        match (self, value) {
+            (Pattern::Wildcard, _) => true,
+            (Pattern::Value(expected), value) => expected == value,
+            (Pattern::Regex(regex), Value::Bytes(bytes)) => {
+                regex.is_match(&String::from_utf8_lossy(bytes))
+            }
+            (Pattern::Regex(_), _) => false,
+            (Pattern::Range(Value::Integer(start), Value::Integer(end)), Value::Integer(value)) => {
+                start <= value && value <= end
+            }
+            (Pattern::Range(start, end), value) => {
+                match (as_f64(start), as_f64(end), as_f64(value)) {
+                    (Some(start), Some(end), Some(value)) => start <= value && value <= end,
+                    _ => false,
+                }
+            }
+            (Pattern::Kind(kind), value) => kind.matches(value),
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Wildcard => f.write_str("_"),
+            Pattern::Value(value) => value.fmt(f),
+            Pattern::Regex(regex) => write!(f, "r'{}'", regex.as_str()),
+            Pattern::Range(start, end) => write!(f, "{}..{}", start, end),
+            Pattern::Kind(kind) => kind.fmt(f),
+        }
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(v) => Some(*v as f64),
+        Value::Float(v) => Some(v.into_inner()),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KindPattern {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Object,
+    Array,
+    Timestamp,
+    Regex,
+}
+
+impl KindPattern {
+    const ALL: [KindPattern; 8] = [
+        KindPattern::String,
+        KindPattern::Integer,
+        KindPattern::Float,
+        KindPattern::Boolean,
+        KindPattern::Object,
+        KindPattern::Array,
+        KindPattern::Timestamp,
+        KindPattern::Regex,
+    ];
+
+    pub(crate) fn from_ident(ident: &Ident) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.as_str() == ident.as_ref())
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            KindPattern::String => "string",
+            KindPattern::Integer => "integer",
+            KindPattern::Float => "float",
+            KindPattern::Boolean => "boolean",
+            KindPattern::Object => "object",
+            KindPattern::Array => "array",
+            KindPattern::Timestamp => "timestamp",
+            KindPattern::Regex => "regex",
+        }
+    }
+
+    fn add_to(self, kind: &mut Kind) {
+        match self {
+            KindPattern::String => kind.add_bytes(),
+            KindPattern::Integer => kind.add_integer(),
+            KindPattern::Float => kind.add_float(),
+            KindPattern::Boolean => kind.add_boolean(),
+            KindPattern::Object => kind.add_object(Collection::any()),
+            KindPattern::Array => kind.add_array(Collection::any()),
+            KindPattern::Timestamp => kind.add_timestamp(),
+            KindPattern::Regex => kind.add_regex(),
+        };
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (KindPattern::String, Value::Bytes(_))
+                | (KindPattern::Integer, Value::Integer(_))
+                | (KindPattern::Float, Value::Float(_))
+                | (KindPattern::Boolean, Value::Boolean(_))
+                | (KindPattern::Object, Value::Object(_))
+                | (KindPattern::Array, Value::Array(_))
+                | (KindPattern::Timestamp, Value::Timestamp(_))
+                | (KindPattern::Regex, Value::Regex(_))
+        )
+    }
+}
+
+impl fmt::Display for KindPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("unreachable match pattern")]
+    UnreachablePattern {
+        span: Span,
+        pattern: Kind,
+        subject: Kind,
+    },
+
+    #[error("invalid match pattern")]
+    DynamicPattern { span: Span },
+
+    #[error("invalid match pattern")]
+    UnknownKind { span: Span, ident: Ident },
+
+    #[error("invalid match pattern")]
+    InvalidRange { span: Span },
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use Error::*;
+
+        match self {
+            UnreachablePattern { .. } => 123,
+            DynamicPattern { .. } | UnknownKind { .. } | InvalidRange { .. } => 124,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::*;
+
+        match self {
+            UnreachablePattern {
+                span,
+                pattern,
+                subject,
+            } => vec![
+                Label::primary(format!("this pattern only matches {}", pattern), span),
+                Label::context(format!("but the value resolves to {}", subject), span),
+            ],
+            DynamicPattern { span } => vec![
+                Label::primary("match patterns must be static values", span),
+                Label::context("remove the template from this string", span),
+            ],
+            UnknownKind { span, ident } => vec![
+                Label::primary(format!(r#"unknown value kind "{}""#, ident), span),
+                Label::context(
+                    format!(
+                        "expected one of: {}",
+                        KindPattern::ALL
+                            .iter()
+                            .map(|kind| kind.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    span,
+                ),
+            ],
+            InvalidRange { span } => vec![
+                Label::primary("this range can never match", span),
+                Label::context("the start of a range can't exceed its end", span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        vec![Note::SeeDocs(
+            "match expressions".to_owned(),
+            Urls::expression_docs_url("#match"),
+        )]
+    }
+}
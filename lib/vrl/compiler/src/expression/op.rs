@@ -83,7 +83,13 @@ impl Expression for Op {
         use value::Value::*;
 
         if let Err = self.opcode {
-            return self.lhs.resolve(ctx).or_else(|_| self.rhs.resolve(ctx));
+            return self.lhs.resolve(ctx).or_else(|error| {
+                if error.is_control_flow() {
+                    return Resolved::Err(error);
+                }
+
+                self.rhs.resolve(ctx)
+            });
         } else if let Or = self.opcode {
             return self
                 .lhs
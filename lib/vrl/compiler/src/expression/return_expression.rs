@@ -0,0 +1,68 @@
+use std::fmt;
+
+use value::Value;
+
+use super::Expr;
+use crate::{
+    expression::{ExpressionError, Resolved},
+    state::{ExternalEnv, LocalEnv},
+    Context, Expression, Span, TypeDef,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Return {
+    span: Span,
+    expr: Option<Box<Expr>>,
+}
+
+impl Return {
+    pub(crate) fn new(span: Span, expr: Option<Expr>) -> Self {
+        Self {
+            span,
+            expr: expr.map(Box::new),
+        }
+    }
+
+    /// The type definition of the value returned by this expression.
+    pub(crate) fn value_type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        self.expr
+            .as_ref()
+            .map_or_else(TypeDef::null, |expr| expr.type_def(state))
+            .infallible()
+    }
+}
+
+impl Expression for Return {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = match &self.expr {
+            Some(expr) => expr.resolve(ctx)?,
+            None => Value::Null,
+        };
+
+        Err(ExpressionError::Return {
+            span: self.span,
+            value,
+        })
+    }
+
+    /// The `return` expression itself never resolves to a value, its returned
+    /// value is part of the type definition of the enclosing program or
+    /// function instead.
+    fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        let fallible = self
+            .expr
+            .as_ref()
+            .map_or(false, |expr| expr.type_def(state).is_fallible());
+
+        TypeDef::null().with_fallibility(fallible)
+    }
+}
+
+impl fmt::Display for Return {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.expr {
+            Some(expr) => write!(f, "return {}", expr),
+            None => f.write_str("return"),
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use std::sync::Arc;
+
+use diagnostic::{DiagnosticMessage, Label, Note, Urls};
+
+use crate::{
+    expression::{Block, Expr, FunctionArgument, Resolved},
+    parser::{Ident, Node},
+    state::{ExternalEnv, LocalEnv, Runtime},
+    Context, Expression, Span, TypeDef,
+};
+
+/// The body of a user-defined function, compiled for a given set of argument
+/// types.
+///
+/// The block of a function definition is compiled with each parameter taking
+/// on the type definition of the argument passed in at the call site. This
+/// allows the compiler to infer both the parameter types and the return type
+/// of the function, without requiring any type annotations. The compiled body
+/// is shared by all calls passing arguments of the same types, so that each
+/// body is only compiled once, regardless of how deeply function calls are
+/// nested.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Body {
+    parameters: Vec<Ident>,
+    block: Block,
+
+    /// The merged type definitions of the values returned by the `return`
+    /// expressions in the function body.
+    return_type_def: Option<TypeDef>,
+}
+
+impl Body {
+    pub(crate) fn new(
+        parameters: Vec<Ident>,
+        block: Block,
+        return_type_def: Option<TypeDef>,
+    ) -> Self {
+        Self {
+            parameters,
+            block,
+            return_type_def,
+        }
+    }
+}
+
+/// A call to a user-defined function, binding the arguments of the call site
+/// to the compiled body of the function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunction {
+    body: Arc<Body>,
+    arguments: Vec<Expr>,
+}
+
+impl UserFunction {
+    pub(crate) fn new(body: Arc<Body>, arguments: Vec<Expr>) -> Self {
+        Self { body, arguments }
+    }
+
+    /// Order the arguments of a call to a user-defined function by the
+    /// position of their parameters.
+    ///
+    /// Keyword arguments can be used in any order, and don't count towards the
+    /// index of positional arguments.
+    pub(crate) fn bind_arguments(
+        call_span: Span,
+        ident_span: Span,
+        parameters: &[Ident],
+        arguments: Vec<Node<FunctionArgument>>,
+    ) -> Result<Vec<Node<FunctionArgument>>, Error> {
+        if arguments.len() > parameters.len() {
+            let arguments_span = {
+                let start = arguments.first().unwrap().span().start();
+                let end = arguments.last().unwrap().span().end();
+
+                Span::new(start, end)
+            };
+
+            return Err(Error::WrongNumberOfArgs {
+                arguments_span,
+                max: parameters.len(),
+            });
+        }
+
+        let mut index = 0;
+        let mut bound: Vec<Option<Node<FunctionArgument>>> = vec![None; parameters.len()];
+
+        for node in arguments {
+            let position = match node.keyword() {
+                // positional argument
+                None => {
+                    index += 1;
+                    index - 1
+                }
+
+                // keyword argument
+                Some(keyword) => {
+                    let position = parameters
+                        .iter()
+                        .position(|parameter| parameter.as_ref() == keyword)
+                        .ok_or_else(|| Error::UnknownKeyword {
+                            keyword_span: node.keyword_span().expect("exists"),
+                            ident_span,
+                            keywords: parameters.to_vec(),
+                        })?;
+
+                    if position == index {
+                        index += 1;
+                    }
+
+                    position
+                }
+            };
+
+            if let Some(previous) = &bound[position] {
+                return Err(Error::DuplicateArgument {
+                    argument_span: node.span(),
+                    previous_span: previous.span(),
+                    parameter: parameters[position].clone(),
+                });
+            }
+
+            bound[position] = Some(node);
+        }
+
+        bound
+            .into_iter()
+            .enumerate()
+            .map(|(position, argument)| {
+                argument.ok_or_else(|| Error::MissingArgument {
+                    call_span,
+                    parameter: parameters[position].clone(),
+                    position,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Expression for UserFunction {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut state = Runtime::default();
+
+        for (parameter, argument) in self.body.parameters.iter().zip(&self.arguments) {
+            state.insert_variable(parameter.clone(), argument.resolve(ctx)?);
+        }
+
+        // The function body runs against its own set of variables, so that it
+        // can't observe or overwrite the variables of its caller.
+        std::mem::swap(ctx.state_mut(), &mut state);
+        let result = self.body.block.resolve(ctx);
+        std::mem::swap(ctx.state_mut(), &mut state);
+
+        match result {
+            // A `return` expression only stops the function it's part of.
+            #[cfg(feature = "expr-return")]
+            Err(crate::ExpressionError::Return { value, .. }) => Ok(value),
+            result => result,
+        }
+    }
+
+    fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        let type_def = self.body.block.type_def(state);
+
+        match &self.body.return_type_def {
+            Some(return_type_def) => type_def.merge_deep(return_type_def.clone()),
+            None => type_def,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("function already defined")]
+    Redefinition { ident_span: Span, ident: Ident },
+
+    #[error("duplicate function parameter")]
+    DuplicateParameter { parameter_span: Span, ident: Ident },
+
+    #[error("recursive function call")]
+    Recursion { call_span: Span, ident: Ident },
+
+    #[error("wrong number of function arguments")]
+    WrongNumberOfArgs { arguments_span: Span, max: usize },
+
+    #[error("unknown function argument keyword")]
+    UnknownKeyword {
+        keyword_span: Span,
+        ident_span: Span,
+        keywords: Vec<Ident>,
+    },
+
+    #[error("missing function argument")]
+    MissingArgument {
+        call_span: Span,
+        parameter: Ident,
+        position: usize,
+    },
+
+    #[error("duplicate function argument")]
+    DuplicateArgument {
+        argument_span: Span,
+        previous_span: Span,
+        parameter: Ident,
+    },
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use Error::*;
+
+        match self {
+            Redefinition { .. } => 112,
+            DuplicateParameter { .. } => 113,
+            Recursion { .. } => 114,
+            WrongNumberOfArgs { .. } => 106,
+            UnknownKeyword { .. } => 108,
+            MissingArgument { .. } => 107,
+            DuplicateArgument { .. } => 132,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::*;
+
+        match self {
+            Redefinition { ident_span, ident } => vec![
+                Label::primary(
+                    format!(r#"function "{}" is already defined"#, ident),
+                    ident_span,
+                ),
+                Label::context("use a different name for this function", ident_span),
+            ],
+
+            DuplicateParameter {
+                parameter_span,
+                ident,
+            } => vec![Label::primary(
+                format!(r#"parameter "{}" is defined more than once"#, ident),
+                parameter_span,
+            )],
+
+            Recursion { call_span, ident } => vec![
+                Label::primary(format!(r#"function "{}" calls itself"#, ident), call_span),
+                Label::context("user-defined functions can't be recursive", call_span),
+            ],
+
+            WrongNumberOfArgs {
+                arguments_span,
+                max,
+            } => {
+                let arg = if *max == 1 { "argument" } else { "arguments" };
+
+                vec![
+                    Label::primary("too many function arguments", arguments_span),
+                    Label::context(
+                        format!("this function takes a maximum of {} {}", max, arg),
+                        arguments_span,
+                    ),
+                ]
+            }
+
+            UnknownKeyword {
+                keyword_span,
+                ident_span,
+                keywords,
+            } => vec![
+                Label::primary("unknown keyword", keyword_span),
+                Label::context(
+                    format!(
+                        "this function accepts the following keywords: {}",
+                        keywords
+                            .iter()
+                            .map(|k| format!(r#""{}""#, k))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    ident_span,
+                ),
+            ],
+
+            MissingArgument {
+                call_span,
+                parameter,
+                position,
+            } => vec![Label::primary(
+                format!(
+                    r#"required argument missing: "{}" (position {})"#,
+                    parameter, position
+                ),
+                call_span,
+            )],
+
+            DuplicateArgument {
+                argument_span,
+                previous_span,
+                parameter,
+            } => vec![
+                Label::primary(
+                    format!(r#"argument "{}" is passed more than once"#, parameter),
+                    argument_span,
+                ),
+                Label::context("the argument is first passed here", previous_span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        use Error::*;
+
+        match self {
+            WrongNumberOfArgs { .. } => vec![Note::SeeDocs(
+                "function arguments".to_owned(),
+                Urls::expression_docs_url("#arguments"),
+            )],
+            _ => vec![],
+        }
+    }
+}
@@ -78,10 +78,22 @@ pub trait Function: Send + Sync + fmt::Debug {
     /// An optional closure definition for the function.
     ///
     /// This returns `None` by default, indicating the function doesn't accept
-    /// a closure.
+    /// a closure. See `closure::Definition::is_optional` for functions that
+    /// accept, but don't require, a closure.
     fn closure(&self) -> Option<closure::Definition> {
         None
     }
+
+    /// Whether the function always resolves to the same value when called
+    /// with the same arguments, without accessing the target, the runtime
+    /// state or the timezone of the program.
+    ///
+    /// Calls to pure functions with arguments that are known at compile-time
+    /// are resolved once at compile-time, instead of every time the program
+    /// runs.
+    fn pure(&self) -> bool {
+        false
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -29,6 +29,13 @@ pub struct Definition {
     /// collection elements to determine the eventual type definition of the
     /// closure variable(s) (see `Variable`).
     pub is_iterator: bool,
+
+    /// Defines whether the closure can be omitted from the function call.
+    ///
+    /// If this is `false`, the compiler rejects any call to the function
+    /// without a closure. Otherwise, the function is expected to check for the
+    /// presence of the closure using `ArgumentList::optional_closure`.
+    pub is_optional: bool,
 }
 
 /// One input variant for a function-closure.
@@ -69,6 +76,13 @@ pub struct Variable {
     /// If set to `None`, the compiler is expected to provide this value at
     /// compile-time, or resort to `Kind::any()` if no information is known.
     pub kind: VariableKind,
+
+    /// Whether the closure has to declare the variable.
+    ///
+    /// Optional variables come after the required ones, so that a closure can
+    /// leave out trailing optional variables it doesn't need. Variables can
+    /// also be ignored using `_` in their place.
+    pub required: bool,
 }
 
 /// The [`Value`] kind expected to be returned by a [`Variable`].
@@ -3,13 +3,15 @@ use lookup::LookupBuf;
 use crate::{
     expression::{Block, Resolved},
     state::LocalEnv,
-    Context, Expression,
+    Context, Expression, Span, TypeDef,
 };
 
 #[derive(Debug, Clone)]
 pub struct Program {
     pub(crate) expressions: Block,
     pub(crate) info: ProgramInfo,
+    pub(crate) type_def: TypeDef,
+    pub(crate) returned_values: Vec<Span>,
 }
 
 impl Program {
@@ -31,9 +33,29 @@ impl Program {
         &self.info
     }
 
+    /// Get the type definition of the value the program resolves to.
+    ///
+    /// This includes the values returned early by any `return` expression.
+    pub fn type_def(&self) -> &TypeDef {
+        &self.type_def
+    }
+
+    /// Get the spans of the `return` expressions outside of function
+    /// definitions that return a value which isn't known to be `null`.
+    ///
+    /// Hosts that don't use the value the program resolves to can reject
+    /// these, instead of discarding the returned values.
+    pub fn returned_values(&self) -> &[Span] {
+        &self.returned_values
+    }
+
     /// Resolve the program to its final [`Value`].
     pub fn resolve(&self, ctx: &mut Context) -> Resolved {
-        self.expressions.resolve(ctx)
+        match self.expressions.resolve(ctx) {
+            #[cfg(feature = "expr-return")]
+            Err(crate::ExpressionError::Return { value, .. }) => Ok(value),
+            result => result,
+        }
     }
 }
 
@@ -21,12 +21,16 @@ impl LocalEnv {
         self.bindings.get(ident)
     }
 
-    #[cfg(any(feature = "expr-assignment", feature = "expr-function_call"))]
+    #[cfg(any(
+        feature = "expr-assignment",
+        feature = "expr-for_each",
+        feature = "expr-function_call"
+    ))]
     pub(crate) fn insert_variable(&mut self, ident: Ident, details: Details) {
         self.bindings.insert(ident, details);
     }
 
-    #[cfg(feature = "expr-function_call")]
+    #[cfg(any(feature = "expr-for_each", feature = "expr-function_call"))]
     pub(crate) fn remove_variable(&mut self, ident: &Ident) -> Option<Details> {
         self.bindings.remove(ident)
     }
@@ -10,6 +10,15 @@ pub enum ExpressionError {
         span: diagnostic::Span,
         message: Option<String>,
     },
+    #[cfg(feature = "expr-for_each")]
+    Break { span: diagnostic::Span },
+    #[cfg(feature = "expr-for_each")]
+    Continue { span: diagnostic::Span },
+    #[cfg(feature = "expr-return")]
+    Return {
+        span: diagnostic::Span,
+        value: Value,
+    },
     Error {
         message: String,
         labels: Vec<Label>,
@@ -17,6 +26,27 @@ pub enum ExpressionError {
     },
 }
 
+impl ExpressionError {
+    /// Returns `true` if the error changes the control flow of the program,
+    /// such as a `break` or `return` statement, instead of reporting a failure.
+    ///
+    /// These errors must be propagated as-is, even by expressions that handle
+    /// errors, such as error coalescing or fallible assignments.
+    pub fn is_control_flow(&self) -> bool {
+        use ExpressionError::*;
+
+        match self {
+            #[cfg(feature = "expr-abort")]
+            Abort { .. } => false,
+            #[cfg(feature = "expr-for_each")]
+            Break { .. } | Continue { .. } => true,
+            #[cfg(feature = "expr-return")]
+            Return { .. } => true,
+            Error { .. } => false,
+        }
+    }
+}
+
 impl std::fmt::Display for ExpressionError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.message().fmt(f)
@@ -40,6 +70,12 @@ impl DiagnosticMessage for ExpressionError {
         match self {
             #[cfg(feature = "expr-abort")]
             Abort { message, .. } => message.clone().unwrap_or_else(|| "aborted".to_owned()),
+            #[cfg(feature = "expr-for_each")]
+            Break { .. } => "break outside of a loop".to_owned(),
+            #[cfg(feature = "expr-for_each")]
+            Continue { .. } => "continue outside of a loop".to_owned(),
+            #[cfg(feature = "expr-return")]
+            Return { .. } => "returned".to_owned(),
             Error { message, .. } => message.clone(),
         }
     }
@@ -52,6 +88,12 @@ impl DiagnosticMessage for ExpressionError {
             Abort { span, .. } => {
                 vec![Label::primary("aborted", span)]
             }
+            #[cfg(feature = "expr-for_each")]
+            Break { span } | Continue { span } => {
+                vec![Label::primary("not within a for_each loop", span)]
+            }
+            #[cfg(feature = "expr-return")]
+            Return { span, .. } => vec![Label::primary("returned", span)],
             Error { labels, .. } => labels.clone(),
         }
     }
@@ -62,6 +104,10 @@ impl DiagnosticMessage for ExpressionError {
         match self {
             #[cfg(feature = "expr-abort")]
             Abort { .. } => vec![],
+            #[cfg(feature = "expr-for_each")]
+            Break { .. } | Continue { .. } => vec![],
+            #[cfg(feature = "expr-return")]
+            Return { .. } => vec![],
             Error { notes, .. } => notes.clone(),
         }
     }
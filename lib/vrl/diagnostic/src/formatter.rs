@@ -1,23 +1,51 @@
-use std::fmt;
+use std::{fmt, ops::Range};
 
 use crate::DiagnosticList;
 
 /// A formatter to display diagnostics tied to a given source.
 pub struct Formatter<'a> {
     source: &'a str,
+    files: Vec<File>,
     diagnostics: DiagnosticList,
     color: bool,
 }
 
+/// A source other than the main one, such as an imported module.
+///
+/// Spans starting at or after the offset of the file belong to it, up to the
+/// offset of the next file.
+struct File {
+    name: String,
+    offset: usize,
+    source: String,
+}
+
 impl<'a> Formatter<'a> {
     pub fn new(source: &'a str, diagnostics: impl Into<DiagnosticList>) -> Self {
         Self {
             source,
+            files: vec![],
             diagnostics: diagnostics.into(),
             color: false,
         }
     }
 
+    /// Add another source the diagnostics can refer to, whose spans start at
+    /// the given offset.
+    pub fn with_file(
+        mut self,
+        name: impl Into<String>,
+        offset: usize,
+        source: impl Into<String>,
+    ) -> Self {
+        self.files.push(File {
+            name: name.into(),
+            offset,
+            source: source.into(),
+        });
+        self
+    }
+
     pub fn colored(mut self) -> Self {
         self.color = true;
         self
@@ -30,20 +58,50 @@ impl<'a> Formatter<'a> {
     pub fn diagnostics(&self) -> &DiagnosticList {
         &self.diagnostics
     }
+
+    /// The id of the file the given range belongs to, and the range within
+    /// that file.
+    ///
+    /// The range is clamped to the source of the file, so that rendering can't
+    /// fail on a span that doesn't belong to any known source.
+    fn locate(&self, range: Range<usize>) -> (usize, Range<usize>) {
+        let (id, offset, source) = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| file.offset <= range.start)
+            .max_by_key(|(_, file)| file.offset)
+            .map(|(index, file)| (index + 1, file.offset, file.source.as_str()))
+            .unwrap_or((0, 0, self.source));
+
+        let end = range.end.saturating_sub(offset).min(source.len());
+        let start = range.start.saturating_sub(offset).min(end);
+
+        (id, start..end)
+    }
 }
 
 impl<'a> fmt::Display for Formatter<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use std::str::from_utf8;
 
-        use codespan_reporting::{files::SimpleFile, term};
+        use codespan_reporting::{
+            diagnostic::{Diagnostic, Label},
+            files::SimpleFiles,
+            term,
+        };
         use termcolor::Buffer;
 
         if self.diagnostics.is_empty() {
             return Ok(());
         }
 
-        let file = SimpleFile::new("", self.source);
+        let mut files = SimpleFiles::new();
+        files.add("", self.source);
+        for file in &self.files {
+            files.add(file.name.as_str(), file.source.as_str());
+        }
+
         let config = term::Config::default();
         let mut buffer = if self.color {
             Buffer::ansi()
@@ -54,8 +112,25 @@ impl<'a> fmt::Display for Formatter<'a> {
         f.write_str("\n")?;
 
         for diagnostic in self.diagnostics.iter() {
-            term::emit(&mut buffer, &config, &file, &diagnostic.to_owned().into())
-                .map_err(|_| fmt::Error)?;
+            let diagnostic: Diagnostic<()> = diagnostic.to_owned().into();
+            let labels = diagnostic
+                .labels
+                .into_iter()
+                .map(|label| {
+                    let (file_id, range) = self.locate(label.range);
+
+                    Label::new(label.style, file_id, range).with_message(label.message)
+                })
+                .collect();
+            let diagnostic = Diagnostic {
+                severity: diagnostic.severity,
+                code: diagnostic.code,
+                message: diagnostic.message,
+                labels,
+                notes: diagnostic.notes,
+            };
+
+            term::emit(&mut buffer, &config, &files, &diagnostic).map_err(|_| fmt::Error)?;
         }
 
         // Diagnostic messages can contain whitespace at the end of some lines.
@@ -179,7 +179,12 @@ impl<'a> ArbitraryDepth<'a> for Assignment {
         ));
 
         if u8::arbitrary(u)? % 2 == 0 {
-            Ok(Assignment::Single { target, op, expr })
+            Ok(Assignment::Single {
+                target,
+                op,
+                expr,
+                annotation: None,
+            })
         } else {
             let err = Node::new(Span::new(0, 5), AssignmentTarget::arbitrary(u)?);
             Ok(Assignment::Infallible {
@@ -187,6 +187,18 @@ impl IntoIterator for Program {
 pub enum RootExpr {
     Expr(Node<Expr>),
 
+    /// A user-defined function, which can be called by any expression that
+    /// follows the definition.
+    FunctionDefinition(Node<FunctionDefinition>),
+
+    /// An import of a VRL module, which has to be resolved before the program
+    /// is compiled.
+    Import(Node<Import>),
+
+    /// A constant, which is resolved at compile-time and can be referenced by
+    /// any expression that follows the definition.
+    ConstDefinition(Node<ConstDefinition>),
+
     /// A special expression that is returned if a given expression could not be
     /// parsed. This allows the parser to continue on to the next expression.
     Error(Error),
@@ -198,6 +210,9 @@ impl fmt::Debug for RootExpr {
 
         let value = match self {
             Expr(v) => format!("{:?}", v),
+            FunctionDefinition(v) => format!("{:?}", v),
+            Import(v) => format!("{:?}", v),
+            ConstDefinition(v) => format!("{:?}", v),
             Error(v) => format!("{:?}", v),
         };
 
@@ -211,11 +226,64 @@ impl fmt::Display for RootExpr {
 
         match self {
             Expr(v) => v.fmt(f),
+            FunctionDefinition(v) => v.fmt(f),
+            Import(v) => v.fmt(f),
+            ConstDefinition(v) => v.fmt(f),
             Error(v) => v.fmt(f),
         }
     }
 }
 
+// -----------------------------------------------------------------------------
+// import
+// -----------------------------------------------------------------------------
+
+/// An import of a VRL module.
+///
+/// The path is relative to the location the importing program is loaded from.
+#[derive(Clone, PartialEq)]
+pub struct Import {
+    pub path: Node<String>,
+}
+
+impl fmt::Display for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "import {:?}", self.path.inner())
+    }
+}
+
+impl fmt::Debug for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Import({:?})", self.path.inner())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// const
+// -----------------------------------------------------------------------------
+
+/// A constant definition.
+///
+/// The expression has to resolve to a value at compile-time, which takes the
+/// place of each reference to the constant.
+#[derive(Clone, PartialEq)]
+pub struct ConstDefinition {
+    pub ident: Node<Ident>,
+    pub expr: Node<Expr>,
+}
+
+impl fmt::Display for ConstDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "const {} = {}", self.ident, self.expr)
+    }
+}
+
+impl fmt::Debug for ConstDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ConstDefinition({:?} = {:?})", self.ident, self.expr)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // expression
 // -----------------------------------------------------------------------------
@@ -226,6 +294,9 @@ pub enum Expr {
     Literal(Node<Literal>),
     Container(Node<Container>),
     IfStatement(Node<IfStatement>),
+    Match(Node<Match>),
+    ForEach(Node<ForEach>),
+    LoopControl(Node<LoopControl>),
     Op(Node<Op>),
     Assignment(Node<Assignment>),
     Query(Node<Query>),
@@ -233,6 +304,7 @@ pub enum Expr {
     Variable(Node<Ident>),
     Unary(Node<Unary>),
     Abort(Node<Abort>),
+    Return(Node<Return>),
 }
 
 impl fmt::Debug for Expr {
@@ -244,12 +316,16 @@ impl fmt::Debug for Expr {
             Container(v) => format!("{:?}", v),
             Op(v) => format!("{:?}", v),
             IfStatement(v) => format!("{:?}", v),
+            Match(v) => format!("{:?}", v),
+            ForEach(v) => format!("{:?}", v),
+            LoopControl(v) => format!("{:?}", v),
             Assignment(v) => format!("{:?}", v),
             Query(v) => format!("{:?}", v),
             FunctionCall(v) => format!("{:?}", v),
             Variable(v) => format!("{:?}", v),
             Unary(v) => format!("{:?}", v),
             Abort(v) => format!("{:?}", v),
+            Return(v) => format!("{:?}", v),
         };
 
         write!(f, "Expr({})", value)
@@ -265,12 +341,16 @@ impl fmt::Display for Expr {
             Container(v) => v.fmt(f),
             Op(v) => v.fmt(f),
             IfStatement(v) => v.fmt(f),
+            Match(v) => v.fmt(f),
+            ForEach(v) => v.fmt(f),
+            LoopControl(v) => v.fmt(f),
             Assignment(v) => v.fmt(f),
             Query(v) => v.fmt(f),
             FunctionCall(v) => v.fmt(f),
             Variable(v) => v.fmt(f),
             Unary(v) => v.fmt(f),
             Abort(v) => v.fmt(f),
+            Return(v) => v.fmt(f),
         }
     }
 }
@@ -658,6 +738,200 @@ impl fmt::Debug for Predicate {
     }
 }
 
+// -----------------------------------------------------------------------------
+// match expression
+// -----------------------------------------------------------------------------
+
+/// A `match` expression.
+///
+/// The subject is resolved once, after which the expression of the first arm
+/// with a pattern matching the subject is resolved.
+#[derive(Clone, PartialEq)]
+pub struct Match {
+    pub subject: Box<Node<Expr>>,
+    pub arms: Vec<Node<MatchArm>>,
+}
+
+impl fmt::Display for Match {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "match {} {{", self.subject)?;
+
+        let mut iter = self.arms.iter().peekable();
+        while let Some(arm) = iter.next() {
+            write!(f, " {}", arm)?;
+
+            if iter.peek().is_some() {
+                f.write_str(",")?;
+            }
+        }
+
+        if !self.arms.is_empty() {
+            f.write_str(" ")?;
+        }
+
+        f.write_str("}")
+    }
+}
+
+impl fmt::Debug for Match {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Match({:?}; ", self.subject)?;
+
+        let mut iter = self.arms.iter().peekable();
+        while let Some(arm) = iter.next() {
+            arm.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(")")
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct MatchArm {
+    pub patterns: Vec<Node<MatchPattern>>,
+    pub expr: Node<Expr>,
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.patterns.iter().peekable();
+        while let Some(pattern) = iter.next() {
+            pattern.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(" | ")?;
+            }
+        }
+
+        write!(f, " => {}", self.expr)
+    }
+}
+
+impl fmt::Debug for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.patterns.iter().peekable();
+        while let Some(pattern) = iter.next() {
+            pattern.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(" | ")?;
+            }
+        }
+
+        write!(f, " => {:?}", self.expr)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum MatchPattern {
+    /// `_`, matching any value.
+    Wildcard,
+
+    /// A literal value, or a regex literal matching a string.
+    Literal(Node<Literal>),
+
+    /// An inclusive range of numbers, such as `200..299`.
+    Range(Node<Literal>, Node<Literal>),
+
+    /// The kind of a value, such as `string` or `object`.
+    Kind(Node<Ident>),
+}
+
+impl fmt::Display for MatchPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use MatchPattern::*;
+
+        match self {
+            Wildcard => f.write_str("_"),
+            Literal(literal) => literal.fmt(f),
+            Range(start, end) => write!(f, "{}..{}", start, end),
+            Kind(ident) => ident.fmt(f),
+        }
+    }
+}
+
+impl fmt::Debug for MatchPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use MatchPattern::*;
+
+        match self {
+            Wildcard => f.write_str("Wildcard"),
+            Literal(literal) => write!(f, "{:?}", literal),
+            Range(start, end) => write!(f, "Range({:?}, {:?})", start, end),
+            Kind(ident) => write!(f, "Kind({})", ident),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// for-each loop
+// -----------------------------------------------------------------------------
+
+/// A `for_each` loop.
+///
+/// The block is resolved once for each item of the array or object the
+/// subject resolves to, with the key (or index) and value of the item assigned
+/// to the given variables.
+#[derive(Clone, PartialEq)]
+pub struct ForEach {
+    pub key: Node<Ident>,
+    pub value: Node<Ident>,
+    pub subject: Box<Node<Expr>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Display for ForEach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "for_each {}, {} in {} {}",
+            self.key, self.value, self.subject, self.block
+        )
+    }
+}
+
+impl fmt::Debug for ForEach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ForEach({:?}, {:?} in {:?} {:?})",
+            self.key, self.value, self.subject, self.block
+        )
+    }
+}
+
+/// A `break` or `continue` statement within a loop.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LoopControl {
+    /// Stop the loop.
+    Break,
+
+    /// Skip to the next iteration of the loop.
+    Continue,
+}
+
+impl fmt::Display for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopControl::Break => f.write_str("break"),
+            LoopControl::Continue => f.write_str("continue"),
+        }
+    }
+}
+
+impl fmt::Debug for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopControl::Break => f.write_str("Break"),
+            LoopControl::Continue => f.write_str("Continue"),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // operation
 // -----------------------------------------------------------------------------
@@ -776,6 +1050,9 @@ pub enum Assignment {
         target: Node<AssignmentTarget>,
         op: AssignmentOp,
         expr: Box<Node<Expr>>,
+
+        /// The declared type of the assigned variable, if any.
+        annotation: Option<Node<TypeAnnotation>>,
     },
     Infallible {
         ok: Node<AssignmentTarget>,
@@ -825,7 +1102,15 @@ impl fmt::Display for Assignment {
         use Assignment::*;
 
         match self {
-            Single { target, op, expr } => write!(f, "{} {} {}", target, op, expr),
+            Single {
+                target,
+                op,
+                expr,
+                annotation: Some(annotation),
+            } => write!(f, "{}: {} {} {}", target, annotation, op, expr),
+            Single {
+                target, op, expr, ..
+            } => write!(f, "{} {} {}", target, op, expr),
             Infallible { ok, err, op, expr } => write!(f, "{}, {} {} {}", ok, err, op, expr),
         }
     }
@@ -836,7 +1121,15 @@ impl fmt::Debug for Assignment {
         use Assignment::*;
 
         match self {
-            Single { target, op, expr } => write!(f, "{:?} {:?} {:?}", target, op, expr),
+            Single {
+                target,
+                op,
+                expr,
+                annotation: Some(annotation),
+            } => write!(f, "{:?}: {:?} {:?} {:?}", target, annotation, op, expr),
+            Single {
+                target, op, expr, ..
+            } => write!(f, "{:?} {:?} {:?}", target, op, expr),
             Infallible { ok, err, op, expr } => {
                 write!(f, "Ok({:?}), Err({:?}) {:?} {:?}", ok, err, op, expr)
             }
@@ -908,6 +1201,31 @@ impl fmt::Debug for AssignmentTarget {
     }
 }
 
+/// The types a variable is declared to have, such as `string | null`.
+#[derive(Clone, PartialEq)]
+pub struct TypeAnnotation(pub Vec<Node<Ident>>);
+
+impl fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.0.iter().peekable();
+        while let Some(ident) = iter.next() {
+            ident.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(" | ")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TypeAnnotation({})", self)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // query
 // -----------------------------------------------------------------------------
@@ -964,6 +1282,62 @@ impl fmt::Debug for QueryTarget {
     }
 }
 
+// -----------------------------------------------------------------------------
+// function definition
+// -----------------------------------------------------------------------------
+
+/// A user-defined function.
+///
+/// It contains the identifier of the function, the identifiers of its
+/// parameters, and the block that is resolved when the function is called.
+#[derive(Clone, PartialEq)]
+pub struct FunctionDefinition {
+    pub ident: Node<Ident>,
+    pub parameters: Vec<Node<Ident>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Display for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fn {}(", self.ident)?;
+
+        let mut iter = self.parameters.iter().peekable();
+        while let Some(parameter) = iter.next() {
+            parameter.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(") ")?;
+        self.block.fmt(f)
+    }
+}
+
+impl fmt::Debug for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FunctionDefinition(")?;
+        self.ident.fmt(f)?;
+
+        f.write_str("(")?;
+
+        let mut iter = self.parameters.iter().peekable();
+        while let Some(parameter) = iter.next() {
+            parameter.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(") ")?;
+        self.block.fmt(f)?;
+
+        f.write_str(")")
+    }
+}
+
 // -----------------------------------------------------------------------------
 // function call
 // -----------------------------------------------------------------------------
@@ -1188,6 +1562,34 @@ impl fmt::Debug for Abort {
     }
 }
 
+// -----------------------------------------------------------------------------
+// return
+// -----------------------------------------------------------------------------
+
+/// A `return` expression.
+///
+/// Stops the program, or the user-defined function it's part of, resolving
+/// to the value of the given expression, or `null` if there is none.
+#[derive(Clone, PartialEq)]
+pub struct Return {
+    pub expr: Option<Box<Node<Expr>>>,
+}
+
+impl fmt::Display for Return {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.expr {
+            Some(expr) => write!(f, "return {}", expr),
+            None => f.write_str("return"),
+        }
+    }
+}
+
+impl fmt::Debug for Return {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Return({:?})", self.expr)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // testing utilities
 // -----------------------------------------------------------------------------
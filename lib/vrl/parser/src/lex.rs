@@ -45,6 +45,42 @@ pub enum Error {
     UnexpectedParseError(String),
 }
 
+impl Error {
+    /// Shift the positions of an error emitted by the lexer by the given
+    /// offset, matching the positions of the tokens it emits.
+    pub(crate) fn with_offset(self, offset: usize) -> Self {
+        use Error::*;
+
+        match self {
+            ReservedKeyword {
+                start,
+                keyword,
+                end,
+            } => ReservedKeyword {
+                start: start + offset,
+                keyword,
+                end: end + offset,
+            },
+            NumericLiteral { start, error, end } => NumericLiteral {
+                start: start + offset,
+                error,
+                end: end + offset,
+            },
+            StringLiteral { start } => StringLiteral {
+                start: start + offset,
+            },
+            Literal { start } => Literal {
+                start: start + offset,
+            },
+            EscapeChar { start, ch } => EscapeChar {
+                start: start + offset,
+                ch,
+            },
+            error @ (ParseError { .. } | UnexpectedParseError(_)) => error,
+        }
+    }
+}
+
 impl DiagnosticMessage for Error {
     fn code(&self) -> usize {
         use Error::*;
@@ -245,6 +281,15 @@ pub enum Token<S> {
     False,
     True,
     Abort,
+    Fn,
+    Import,
+    Const,
+    Match,
+    ForEach,
+    In,
+    Break,
+    Continue,
+    Return,
 
     // tokens
     Colon,
@@ -261,6 +306,8 @@ pub enum Token<S> {
     Underscore,
     Escape,
     Arrow,
+    FatArrow,
+    DotDot,
 
     Equals,
     MergeEquals,
@@ -331,6 +378,15 @@ impl<S> Token<S> {
             Null => Null,
             True => True,
             Abort => Abort,
+            Fn => Fn,
+            Import => Import,
+            Const => Const,
+            Match => Match,
+            ForEach => ForEach,
+            In => In,
+            Break => Break,
+            Continue => Continue,
+            Return => Return,
 
             // tokens
             Colon => Colon,
@@ -347,6 +403,8 @@ impl<S> Token<S> {
             Underscore => Underscore,
             Escape => Escape,
             Arrow => Arrow,
+            FatArrow => FatArrow,
+            DotDot => DotDot,
 
             Equals => Equals,
             MergeEquals => MergeEquals,
@@ -386,6 +444,15 @@ where
             Null => "Null",
             True => "True",
             Abort => "Abort",
+            Fn => "Fn",
+            Import => "Import",
+            Const => "Const",
+            Match => "Match",
+            ForEach => "ForEach",
+            In => "In",
+            Break => "Break",
+            Continue => "Continue",
+            Return => "Return",
 
             // tokens
             Colon => "Colon",
@@ -402,6 +469,8 @@ where
             Underscore => "Underscore",
             Escape => "Escape",
             Arrow => "Arrow",
+            FatArrow => "FatArrow",
+            DotDot => "DotDot",
 
             Equals => "Equals",
             MergeEquals => "MergeEquals",
@@ -428,14 +497,21 @@ impl<'input> Token<&'input str> {
             "false" => False,
             "null" => Null,
             "abort" => Abort,
+            "fn" => Fn,
+            "import" => Import,
+            "const" => Const,
+            "match" => Match,
+            "for_each" => ForEach,
+            "in" => In,
+            "break" => Break,
+            "continue" => Continue,
+            "return" => Return,
 
             // reserved identifiers
-            "array" | "bool" | "boolean" | "break" | "continue" | "do" | "emit" | "float"
-            | "for" | "forall" | "foreach" | "all" | "each" | "any" | "try" | "undefined"
-            | "int" | "integer" | "iter" | "object" | "regex" | "return" | "string"
-            | "traverse" | "timestamp" | "duration" | "unless" | "walk" | "while" | "loop" => {
-                ReservedIdentifier(s)
-            }
+            "array" | "bool" | "boolean" | "do" | "emit" | "float" | "for" | "forall"
+            | "foreach" | "all" | "each" | "any" | "try" | "undefined" | "int" | "integer"
+            | "iter" | "object" | "regex" | "string" | "traverse" | "timestamp" | "duration"
+            | "unless" | "walk" | "while" | "loop" => ReservedIdentifier(s),
 
             _ if s.contains('@') => PathField(s),
 
@@ -584,6 +660,11 @@ impl<'input> Iterator for Lexer<'input> {
                     ']' => Some(Ok(self.close(start, RBracket))),
                     ')' => Some(Ok(self.close(start, RParen))),
 
+                    '.' if self.test_peek(|ch| ch == '.') => {
+                        let _ = self.bump();
+                        Some(Ok(self.token(start, DotDot)))
+                    }
+
                     '.' => Some(Ok(self.token(start, Dot))),
                     ':' => Some(Ok(self.token(start, Colon))),
                     ',' => Some(Ok(self.token(start, Comma))),
@@ -697,6 +778,11 @@ impl<'input> Lexer<'input> {
             return Ok(false);
         }
 
+        // A range (e.g. `1..5`) is never the start of a query.
+        if self.input[start..].starts_with("..") {
+            return Ok(false);
+        }
+
         // Take a clone of the existing chars iterator, to allow us to look
         // ahead without advancing the lexer's iterator. This is cheap, since
         // the original iterator only holds references.
@@ -1035,7 +1121,8 @@ impl<'input> Lexer<'input> {
                 let (end, ident) = self.take_while(start, is_ident_continue);
                 Ok((start, Token::ident(ident), end))
             }
-            Some((_, '.')) => {
+            // A range (e.g. `1..5`) starts with an integer, not a float.
+            Some((pos, '.')) if !self.input[pos + 1..].starts_with('.') => {
                 self.bump();
                 let (end, float) = self.take_while(start, |ch| is_digit(ch) || ch == '_');
 
@@ -1079,6 +1166,7 @@ impl<'input> Lexer<'input> {
 
         let token = match op {
             "=" => Token::Equals,
+            "=>" => Token::FatArrow,
             "|=" => Token::MergeEquals,
             "?" => Token::Question,
             op => Token::Operator(op),
@@ -1503,6 +1591,112 @@ mod test {
         );
     }
 
+    #[test]
+    fn imports() {
+        use StringLiteral as L;
+        use StringLiteralToken as S;
+
+        test(
+            data(r#"import "lib/common.vrl""#),
+            vec![
+                (r#"~~~~~~                 "#, Import),
+                (r#"       ~~~~~~~~~~~~~~~~"#, L(S("lib/common.vrl"))),
+            ],
+        );
+    }
+
+    #[test]
+    fn const_definitions() {
+        test(
+            data(r#"const LIMIT = 10"#),
+            vec![
+                (r#"~~~~~           "#, Const),
+                (r#"      ~~~~~     "#, Identifier("LIMIT")),
+                (r#"            ~   "#, Equals),
+                (r#"              ~~"#, IntegerLiteral(10)),
+            ],
+        );
+    }
+
+    #[test]
+    fn return_expressions() {
+        test(
+            data(r#"if x { return y }"#),
+            vec![
+                (r#"~~               "#, If),
+                (r#"   ~             "#, Identifier("x")),
+                (r#"     ~           "#, LBrace),
+                (r#"       ~~~~~~    "#, Return),
+                (r#"              ~  "#, Identifier("y")),
+                (r#"                ~"#, RBrace),
+            ],
+        );
+    }
+
+    #[test]
+    fn function_definitions() {
+        test(
+            data(r#"fn add(a, b) { a }"#),
+            vec![
+                (r#"~~                "#, Fn),
+                (r#"   ~~~            "#, FunctionCall("add")),
+                (r#"      ~           "#, LParen),
+                (r#"       ~          "#, Identifier("a")),
+                (r#"        ~         "#, Comma),
+                (r#"          ~       "#, Identifier("b")),
+                (r#"           ~      "#, RParen),
+                (r#"             ~    "#, LBrace),
+                (r#"               ~  "#, Identifier("a")),
+                (r#"                 ~"#, RBrace),
+            ],
+        );
+    }
+
+    #[test]
+    fn match_expressions() {
+        test(
+            data(r#"match .a { 2..5 => x, _ => y }"#),
+            vec![
+                (r#"~~~~~                         "#, Match),
+                (r#"      ~                       "#, LQuery),
+                (r#"      ~                       "#, Dot),
+                (r#"       ~                      "#, Identifier("a")),
+                (r#"       ~                      "#, RQuery),
+                (r#"         ~                    "#, LBrace),
+                (r#"           ~                  "#, IntegerLiteral(2)),
+                (r#"            ~~                "#, DotDot),
+                (r#"              ~               "#, IntegerLiteral(5)),
+                (r#"                ~~            "#, FatArrow),
+                (r#"                   ~          "#, Identifier("x")),
+                (r#"                    ~         "#, Comma),
+                (r#"                      ~       "#, Underscore),
+                (r#"                        ~~    "#, FatArrow),
+                (r#"                           ~  "#, Identifier("y")),
+                (r#"                             ~"#, RBrace),
+            ],
+        );
+    }
+
+    #[test]
+    fn for_each_loops() {
+        test(
+            data(r#"for_each k, v in x { break; continue }"#),
+            vec![
+                (r#"~~~~~~~~                              "#, ForEach),
+                (r#"         ~                            "#, Identifier("k")),
+                (r#"          ~                           "#, Comma),
+                (r#"            ~                         "#, Identifier("v")),
+                (r#"              ~~                      "#, In),
+                (r#"                 ~                    "#, Identifier("x")),
+                (r#"                   ~                  "#, LBrace),
+                (r#"                     ~~~~~            "#, Break),
+                (r#"                          ~           "#, SemiColon),
+                (r#"                            ~~~~~~~~  "#, Continue),
+                (r#"                                     ~"#, RBrace),
+            ],
+        );
+    }
+
     #[test]
     fn single_query() {
         test(
@@ -19,12 +19,24 @@ pub use lex::{Error, Token};
 use lookup::LookupBuf;
 
 pub fn parse(input: impl AsRef<str>) -> Result<Program, Error> {
-    let lexer = lex::Lexer::new(input.as_ref());
+    parse_with_offset(input, 0)
+}
+
+/// Parse a program whose spans start at the given offset, instead of at zero.
+///
+/// This allows spans of different sources, such as a program and the modules
+/// it imports, to be told apart.
+pub fn parse_with_offset(input: impl AsRef<str>, offset: usize) -> Result<Program, Error> {
+    let lexer = lex::Lexer::new(input.as_ref()).map(|result| {
+        result
+            .map(|(start, token, end)| (start + offset, token, end + offset))
+            .map_err(|error| error.with_offset(offset))
+    });
 
     parser::ProgramParser::new()
         .parse(input.as_ref(), lexer)
         .map_err(|source| Error::ParseError {
-            span: Span::new(0, input.as_ref().len()),
+            span: Span::new(offset, offset + input.as_ref().len()),
             source: source
                 .map_token(|t| t.map(|s| s.to_owned()))
                 .map_error(|err| err.to_string()),
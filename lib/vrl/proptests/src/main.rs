@@ -247,6 +247,7 @@ fn expr() -> impl Strategy<Value = Expr> {
                     target: node(target),
                     op,
                     expr: Box::new(node(expr)),
+                    annotation: None,
                 })))
             }),
             (ident(), prop::bool::ANY, prop::collection::vec(inner, 1..3)).prop_map(
@@ -13,25 +13,36 @@ criterion_group!(
     targets = array,
               assert,
               assert_eq,
+              bit_and,
+              bit_or,
+              bit_shl,
+              bit_shr,
+              bit_xor,
               r#bool,
               ceil,
               compact,
               contains,
+              crc16,
+              crc32,
               decode_base64,
               decode_percent,
               decrypt,
               // TODO: Cannot pass a Path to bench_function
               //del,
+              detect_language,
               downcase,
               encode_base64,
               encode_key_value,
               encode_json,
               encode_logfmt,
               encode_percent,
+              encode_syslog_priority,
+              encode_syslog_structured_data,
               encrypt,
               ends_with,
               // TODO: Cannot pass a Path to bench_function
               //exists
+              extract_bits,
               find,
               flatten,
               floor,
@@ -42,6 +53,7 @@ criterion_group!(
               get,
               get_env_var,
               get_hostname,
+              get_paths,
               includes,
               int,
               ip_aton,
@@ -67,27 +79,35 @@ criterion_group!(
               join,
               length,
               log,
+              luhn_valid,
               r#match,
               match_any,
               match_array,
               match_datadog_query,
+              match_path,
               md5,
               merge,
               // TODO: value is dynamic so we cannot assert equality
               //now,
               object,
+              only_fields,
               parse_apache_log,
               parse_aws_alb_log,
+              parse_aws_cloudfront_log,
               parse_aws_cloudwatch_log_subscription_message,
               parse_aws_vpc_flow_log,
+              parse_binary,
               parse_common_log,
               parse_csv,
               parse_duration,
+              parse_envoy_log,
               parse_glog,
               parse_grok,
               parse_groks,
               parse_key_value,
               parse_klog,
+              parse_haproxy_log,
+              parse_human_size,
               parse_int,
               parse_json,
               parse_nginx_log,
@@ -104,6 +124,7 @@ criterion_group!(
               push,
               redact,
               remove,
+              remove_paths,
               replace,
               reverse_dns,
               round,
@@ -127,17 +148,21 @@ criterion_group!(
               to_regex,
               to_string,
               to_syslog_facility,
+              to_syslog_facility_code,
               to_syslog_level,
               to_syslog_severity,
               to_timestamp,
               to_unix_timestamp,
+              transliterate,
               truncate,
+              type_of,
               unique,
               // TODO: Cannot pass a Path to bench_function
               //unnest
               // TODO: value is dynamic so we cannot assert equality
               //uuidv4,
               upcase,
+              verify_ean,
 );
 criterion_main!(benches);
 
@@ -195,6 +220,51 @@ bench_function! {
     }
 }
 
+bench_function! {
+    bit_and => vrl_stdlib::BitAnd;
+
+    literal {
+        args: func_args![value: 12, other: 10],
+        want: Ok(8),
+    }
+}
+
+bench_function! {
+    bit_or => vrl_stdlib::BitOr;
+
+    literal {
+        args: func_args![value: 12, other: 10],
+        want: Ok(14),
+    }
+}
+
+bench_function! {
+    bit_shl => vrl_stdlib::BitShl;
+
+    literal {
+        args: func_args![value: 1, amount: 4],
+        want: Ok(16),
+    }
+}
+
+bench_function! {
+    bit_shr => vrl_stdlib::BitShr;
+
+    literal {
+        args: func_args![value: 256, amount: 4],
+        want: Ok(16),
+    }
+}
+
+bench_function! {
+    bit_xor => vrl_stdlib::BitXor;
+
+    literal {
+        args: func_args![value: 12, other: 10],
+        want: Ok(6),
+    }
+}
+
 bench_function! {
     r#bool => vrl_stdlib::Boolean;
 
@@ -245,6 +315,24 @@ bench_function! {
     }
 }
 
+bench_function! {
+    crc16 => vrl_stdlib::Crc16;
+
+    literal {
+        args: func_args![value: "123456789"],
+        want: Ok(47933),
+    }
+}
+
+bench_function! {
+    crc32 => vrl_stdlib::Crc32;
+
+    literal {
+        args: func_args![value: "123456789"],
+        want: Ok(3_421_780_262_i64),
+    }
+}
+
 bench_function! {
     decode_base64 => vrl_stdlib::DecodeBase64;
 
@@ -263,6 +351,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    detect_language => vrl_stdlib::DetectLanguage;
+
+    literal {
+        args: func_args![value: "The quick brown fox jumps over the lazy dog, and then it runs into the forest."],
+        want: Ok(value!({ "language": "eng", "script": "Latin", "confidence": 1.0, "reliable": true })),
+    }
+}
+
 bench_function! {
     downcase => vrl_stdlib::Downcase;
 
@@ -382,6 +479,29 @@ bench_function! {
     }
 }
 
+bench_function! {
+    encode_syslog_priority => vrl_stdlib::EncodeSyslogPriority;
+
+    names {
+        args: func_args![facility: "local0", severity: "info"],
+        want: Ok(134),
+    }
+
+    codes {
+        args: func_args![facility: 4, severity: 2],
+        want: Ok(34),
+    }
+}
+
+bench_function! {
+    encode_syslog_structured_data => vrl_stdlib::EncodeSyslogStructuredData;
+
+    literal {
+        args: func_args![value: value!({"exampleSDID@32473": {"iut": "3", "eventSource": "Application"}})],
+        want: Ok(r#"[exampleSDID@32473 eventSource="Application" iut="3"]"#),
+    }
+}
+
 bench_function! {
     ends_with => vrl_stdlib::EndsWith;
 
@@ -396,6 +516,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    extract_bits => vrl_stdlib::ExtractBits;
+
+    literal {
+        args: func_args![value: 171, offset: 4, length: 4],
+        want: Ok(10),
+    }
+}
+
 bench_function! {
     find => vrl_stdlib::Find;
 
@@ -519,6 +648,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    get_paths => vrl_stdlib::GetPaths;
+
+    object {
+        args: func_args![value: value!({"foo": {"bar": true, "baz": [1, 2]}})],
+        want: Ok(value!(["foo.bar", "foo.baz[0]", "foo.baz[1]"])),
+    }
+}
+
 bench_function! {
     includes => vrl_stdlib::Includes;
 
@@ -904,6 +1042,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    luhn_valid => vrl_stdlib::LuhnValid;
+
+    literal {
+        args: func_args![value: "4111 1111 1111 1111"],
+        want: Ok(true),
+    }
+}
+
 bench_function! {
     get => vrl_stdlib::Get;
 
@@ -1066,6 +1213,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    match_path => vrl_stdlib::MatchPath;
+
+    glob {
+        args: func_args![path: "user.emails[1].domain", pattern: "user.**.domain"],
+        want: Ok(true),
+    }
+}
+
 bench_function! {
     md5  => vrl_stdlib::Md5;
 
@@ -1139,6 +1295,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    only_fields => vrl_stdlib::OnlyFields;
+
+    glob {
+        args: func_args![value: value!({"a": {"x_id": 1, "y": 2}, "b_id": 3}), patterns: value!(["**_id"])],
+        want: Ok(value!({"a": {"x_id": 1}, "b_id": 3})),
+    }
+}
+
 bench_function! {
     parse_aws_alb_log => vrl_stdlib::ParseAwsAlbLog;
 
@@ -1182,6 +1347,48 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_aws_cloudfront_log => vrl_stdlib::ParseAwsCloudFrontLog;
+
+    literal {
+        args: func_args![value: "2019-12-04\t21:02:31\tLAX1\t392\t192.0.2.100\tGET\td111111abcdef8.cloudfront.net\t/index.html\t200\t-\tcurl/7.68.0\t-\t-\tHit\tSOX4xwn4XV6Q4rgb7XiVGOHms_BGlTAC4KyHmureZmBNrjGdRLiNIQ==\td111111abcdef8.cloudfront.net\thttps\t23\t0.001\t-\tTLSv1.2\tECDHE-RSA-AES128-GCM-SHA256\tHit\tHTTP/2.0\t-\t-\t11040\t0.001\tHit\ttext/html\t78\t-\t-"],
+        want: Ok(value!({
+            "c_ip": "192.0.2.100",
+            "c_port": 11040,
+            "cs_bytes": 23,
+            "cs_cookie": null,
+            "cs_host": "d111111abcdef8.cloudfront.net",
+            "cs_method": "GET",
+            "cs_protocol": "https",
+            "cs_protocol_version": "HTTP/2.0",
+            "cs_referer": null,
+            "cs_uri_query": null,
+            "cs_uri_stem": "/index.html",
+            "cs_user_agent": "curl/7.68.0",
+            "edge_detailed_result_type": "Hit",
+            "edge_location": "LAX1",
+            "edge_request_id": "SOX4xwn4XV6Q4rgb7XiVGOHms_BGlTAC4KyHmureZmBNrjGdRLiNIQ==",
+            "edge_response_result_type": "Hit",
+            "edge_result_type": "Hit",
+            "fle_encrypted_fields": null,
+            "fle_status": null,
+            "forwarded_for": null,
+            "host_header": "d111111abcdef8.cloudfront.net",
+            "sc_bytes": 392,
+            "sc_content_len": 78,
+            "sc_content_type": "text/html",
+            "sc_range_end": null,
+            "sc_range_start": null,
+            "sc_status": 200,
+            "ssl_cipher": "ECDHE-RSA-AES128-GCM-SHA256",
+            "ssl_protocol": "TLSv1.2",
+            "time_taken": 0.001,
+            "time_to_first_byte": 0.001,
+            "timestamp": (Utc.ymd(2019, 12, 4).and_hms(21, 2, 31)),
+        })),
+    }
+}
+
 bench_function! {
     parse_aws_cloudwatch_log_subscription_message => vrl_stdlib::ParseAwsCloudWatchLogSubscriptionMessage;
 
@@ -1316,6 +1523,22 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_binary => vrl_stdlib::ParseBinary;
+
+    record {
+        args: func_args![
+            value: Bytes::from_static(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x2a, b'f', b'o', b'o', 0x00]),
+            layout: value!({
+                "version": { "type": "u16", "offset": 0 },
+                "count": { "type": "u32", "offset": 2 },
+                "name": { "type": "string", "offset": 6, "length": 4 },
+            }),
+        ],
+        want: Ok(value!({ "version": 1, "count": 42, "name": "foo" })),
+    }
+}
+
 bench_function! {
     parse_common_log => vrl_stdlib::ParseCommonLog;
 
@@ -1354,6 +1577,31 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_envoy_log => vrl_stdlib::ParseEnvoyLog;
+
+    literal {
+        args: func_args![value: r#"[2016-04-15T20:17:00.310Z] "POST /api/v1/locations HTTP/2" 204 - 154 0 226 100 "10.0.35.28" "nsq2http" "cc21d9b0-cf5c-432b-8c7e-98aeb7988cd2" "locations" "tcp://10.0.2.1:80""#],
+        want: Ok(value!({
+            "timestamp": (DateTime::parse_from_rfc3339("2016-04-15T20:17:00.310Z").unwrap().with_timezone(&Utc)),
+            "request": "POST /api/v1/locations HTTP/2",
+            "method": "POST",
+            "path": "/api/v1/locations",
+            "protocol": "HTTP/2",
+            "status": 204,
+            "bytes_received": 154,
+            "bytes_sent": 0,
+            "duration": 226,
+            "upstream_service_time": 100,
+            "forwarded_for": "10.0.35.28",
+            "agent": "nsq2http",
+            "request_id": "cc21d9b0-cf5c-432b-8c7e-98aeb7988cd2",
+            "authority": "locations",
+            "upstream_host": "tcp://10.0.2.1:80",
+        })),
+    }
+}
+
 bench_function! {
     parse_glog  => vrl_stdlib::ParseGlog;
 
@@ -1412,6 +1660,56 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_haproxy_log => vrl_stdlib::ParseHaproxyLog;
+
+    literal {
+        args: func_args![value: r#"10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 {1wt.eu} {} "GET /index.html HTTP/1.1""#],
+        want: Ok(value!({
+            "client": "10.0.1.2",
+            "client_port": 33317,
+            "timestamp": (DateTime::parse_from_rfc3339("2009-02-06T12:14:14.655Z").unwrap().with_timezone(&Utc)),
+            "frontend": "http-in",
+            "backend": "static",
+            "server": "srv1",
+            "request_time": 10,
+            "queue_time": 0,
+            "connect_time": 30,
+            "response_time": 69,
+            "total_time": 109,
+            "status": 200,
+            "size": 2750,
+            "termination_state": "----",
+            "active_connections": 1,
+            "frontend_connections": 1,
+            "backend_connections": 1,
+            "server_connections": 1,
+            "retries": 0,
+            "server_queue": 0,
+            "backend_queue": 0,
+            "request_headers": "1wt.eu",
+            "request": "GET /index.html HTTP/1.1",
+            "method": "GET",
+            "path": "/index.html",
+            "protocol": "HTTP/1.1",
+        })),
+    }
+}
+
+bench_function! {
+    parse_human_size => vrl_stdlib::ParseHumanSize;
+
+    bytes {
+        args: func_args![value: "1.5GiB"],
+        want: Ok(1610612736.0),
+    }
+
+    duration {
+        args: func_args![value: "300ms", unit: "s"],
+        want: Ok(0.3),
+    }
+}
+
 bench_function! {
     parse_int => vrl_stdlib::ParseInt;
 
@@ -1696,6 +1994,16 @@ bench_function! {
         args: func_args![value: "Wed, 16 Oct 2019 12:00:00 +0000", format: "%a, %e %b %Y %T %z"],
         want: Ok(DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000").unwrap().with_timezone(&Utc))
     }
+
+    formats {
+        args: func_args![value: "Wed, 16 Oct 2019 12:00:00 +0000", format: value!(["%+", "%a, %e %b %Y %T %z"])],
+        want: Ok(DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000").unwrap().with_timezone(&Utc))
+    }
+
+    epoch_millis {
+        args: func_args![value: 1571227200000_i64],
+        want: Ok(DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000").unwrap().with_timezone(&Utc))
+    }
 }
 
 bench_function! {
@@ -1975,6 +2283,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    remove_paths => vrl_stdlib::RemovePaths;
+
+    glob {
+        args: func_args![value: value!({"user": {"name": "foo", "password": "bar"}, "token": "baz"}), patterns: value!(["**password", "token"])],
+        want: Ok(value!({"user": {"name": "foo"}})),
+    }
+}
+
 bench_function! {
     replace => vrl_stdlib::Replace;
 
@@ -2341,6 +2658,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    to_syslog_facility_code => vrl_stdlib::ToSyslogFacilityCode;
+
+    literal {
+        args: func_args![value: value!("local7")],
+        want: Ok(value!(23)),
+    }
+}
+
 bench_function! {
     to_syslog_level => vrl_stdlib::ToSyslogLevel;
 
@@ -2387,6 +2713,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    transliterate => vrl_stdlib::Transliterate;
+
+    literal {
+        args: func_args![value: "Жизнь прекрасна"],
+        want: Ok("Zhizn' prekrasna"),
+    }
+}
+
 bench_function! {
     truncate => vrl_stdlib::Truncate;
 
@@ -2409,6 +2744,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    type_of => vrl_stdlib::TypeOf;
+
+    object {
+        args: func_args![value: value!({"a": "foo", "b": [1, 2, 3], "c": {"d": null}})],
+        want: Ok(value!({"a": "string", "b": ["integer"], "c": {"d": "null"}})),
+    }
+}
+
 bench_function! {
     unique => vrl_stdlib::Unique;
 
@@ -2435,3 +2779,12 @@ bench_function! {
         want: Ok("FOO")
     }
 }
+
+bench_function! {
+    verify_ean => vrl_stdlib::VerifyEan;
+
+    literal {
+        args: func_args![value: "4006381333931"],
+        want: Ok(true),
+    }
+}
@@ -0,0 +1,93 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn bit_and(value: Value, other: Value) -> Resolved {
+    let value = value.try_integer()?;
+    let other = other.try_integer()?;
+
+    Ok((value & other).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BitAnd;
+
+impl Function for BitAnd {
+    fn identifier(&self) -> &'static str {
+        "bit_and"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "bitwise AND",
+            source: "bit_and(12, 10)",
+            result: Ok("8"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(BitAndFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BitAndFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for BitAndFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let other = self.other.resolve(ctx)?;
+
+        bit_and(value, other)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        bit_and => BitAnd;
+
+        positive {
+            args: func_args![value: 12, other: 10],
+            want: Ok(8),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        negative {
+            args: func_args![value: -1, other: 255],
+            want: Ok(255),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
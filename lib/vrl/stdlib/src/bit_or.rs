@@ -0,0 +1,93 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn bit_or(value: Value, other: Value) -> Resolved {
+    let value = value.try_integer()?;
+    let other = other.try_integer()?;
+
+    Ok((value | other).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BitOr;
+
+impl Function for BitOr {
+    fn identifier(&self) -> &'static str {
+        "bit_or"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "bitwise OR",
+            source: "bit_or(12, 10)",
+            result: Ok("14"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(BitOrFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BitOrFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for BitOrFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let other = self.other.resolve(ctx)?;
+
+        bit_or(value, other)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        bit_or => BitOr;
+
+        positive {
+            args: func_args![value: 12, other: 10],
+            want: Ok(14),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        zero {
+            args: func_args![value: 0, other: 0],
+            want: Ok(0),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,116 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn bit_shl(value: Value, amount: Value) -> Resolved {
+    let value = value.try_integer()?;
+    let amount = amount.try_integer()?;
+
+    if !(0..64).contains(&amount) {
+        return Err(format!("shift amount must be between 0 and 63, got {}", amount).into());
+    }
+
+    // Bits shifted past the most significant bit are dropped.
+    Ok((value << amount).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BitShl;
+
+impl Function for BitShl {
+    fn identifier(&self) -> &'static str {
+        "bit_shl"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "amount",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "shift left",
+            source: "bit_shl!(1, 4)",
+            result: Ok("16"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let amount = arguments.required("amount");
+
+        Ok(Box::new(BitShlFn { value, amount }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BitShlFn {
+    value: Box<dyn Expression>,
+    amount: Box<dyn Expression>,
+}
+
+impl Expression for BitShlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let amount = self.amount.resolve(ctx)?;
+
+        bit_shl(value, amount)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        bit_shl => BitShl;
+
+        positive {
+            args: func_args![value: 1, amount: 4],
+            want: Ok(16),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        overflow {
+            args: func_args![value: 0x4000000000000000_i64, amount: 1],
+            want: Ok(i64::MIN),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        drops_high_bits {
+            args: func_args![value: -1, amount: 63],
+            want: Ok(i64::MIN),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        negative_amount {
+            args: func_args![value: 1, amount: -1],
+            want: Err("shift amount must be between 0 and 63, got -1"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        amount_too_large {
+            args: func_args![value: 1, amount: 64],
+            want: Err("shift amount must be between 0 and 63, got 64"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
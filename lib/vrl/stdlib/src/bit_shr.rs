@@ -0,0 +1,110 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn bit_shr(value: Value, amount: Value) -> Resolved {
+    let value = value.try_integer()?;
+    let amount = amount.try_integer()?;
+
+    if !(0..64).contains(&amount) {
+        return Err(format!("shift amount must be between 0 and 63, got {}", amount).into());
+    }
+
+    // This is a logical shift, the sign bit isn't extended.
+    Ok((((value as u64) >> amount) as i64).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BitShr;
+
+impl Function for BitShr {
+    fn identifier(&self) -> &'static str {
+        "bit_shr"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "amount",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "shift right",
+            source: "bit_shr!(256, 4)",
+            result: Ok("16"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let amount = arguments.required("amount");
+
+        Ok(Box::new(BitShrFn { value, amount }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BitShrFn {
+    value: Box<dyn Expression>,
+    amount: Box<dyn Expression>,
+}
+
+impl Expression for BitShrFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let amount = self.amount.resolve(ctx)?;
+
+        bit_shr(value, amount)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        bit_shr => BitShr;
+
+        positive {
+            args: func_args![value: 256, amount: 4],
+            want: Ok(16),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        negative {
+            args: func_args![value: -1, amount: 60],
+            want: Ok(15),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        negative_amount {
+            args: func_args![value: 1, amount: -1],
+            want: Err("shift amount must be between 0 and 63, got -1"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        amount_too_large {
+            args: func_args![value: 1, amount: 64],
+            want: Err("shift amount must be between 0 and 63, got 64"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
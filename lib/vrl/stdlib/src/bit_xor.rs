@@ -0,0 +1,93 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn bit_xor(value: Value, other: Value) -> Resolved {
+    let value = value.try_integer()?;
+    let other = other.try_integer()?;
+
+    Ok((value ^ other).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BitXor;
+
+impl Function for BitXor {
+    fn identifier(&self) -> &'static str {
+        "bit_xor"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "bitwise XOR",
+            source: "bit_xor(12, 10)",
+            result: Ok("6"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(BitXorFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BitXorFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for BitXorFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let other = self.other.resolve(ctx)?;
+
+        bit_xor(value, other)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        bit_xor => BitXor;
+
+        positive {
+            args: func_args![value: 12, other: 10],
+            want: Ok(6),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        negative {
+            args: func_args![value: 255, other: -1],
+            want: Ok(-256),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
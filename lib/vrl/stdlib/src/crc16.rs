@@ -0,0 +1,177 @@
+use ::value::Value;
+use crc::{Crc, CRC_16_ARC, CRC_16_IBM_3740, CRC_16_KERMIT, CRC_16_MODBUS, CRC_16_XMODEM};
+use vrl::prelude::*;
+
+const ARC: Crc<u16> = Crc::<u16>::new(&CRC_16_ARC);
+const CCITT_FALSE: Crc<u16> = Crc::<u16>::new(&CRC_16_IBM_3740);
+const KERMIT: Crc<u16> = Crc::<u16>::new(&CRC_16_KERMIT);
+const MODBUS: Crc<u16> = Crc::<u16>::new(&CRC_16_MODBUS);
+const XMODEM: Crc<u16> = Crc::<u16>::new(&CRC_16_XMODEM);
+
+fn algorithms() -> Vec<Value> {
+    vec![
+        value!("arc"),
+        value!("ccitt_false"),
+        value!("kermit"),
+        value!("modbus"),
+        value!("xmodem"),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Algorithm {
+    Arc,
+    CcittFalse,
+    Kermit,
+    Modbus,
+    Xmodem,
+}
+
+impl Algorithm {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            b"arc" => Some(Algorithm::Arc),
+            b"ccitt_false" => Some(Algorithm::CcittFalse),
+            b"kermit" => Some(Algorithm::Kermit),
+            b"modbus" => Some(Algorithm::Modbus),
+            b"xmodem" => Some(Algorithm::Xmodem),
+            _ => None,
+        }
+    }
+
+    fn crc(self) -> &'static Crc<u16> {
+        match self {
+            Algorithm::Arc => &ARC,
+            Algorithm::CcittFalse => &CCITT_FALSE,
+            Algorithm::Kermit => &KERMIT,
+            Algorithm::Modbus => &MODBUS,
+            Algorithm::Xmodem => &XMODEM,
+        }
+    }
+}
+
+fn crc16(value: Value, algorithm: Algorithm) -> Resolved {
+    let value = value.try_bytes()?;
+
+    Ok(i64::from(algorithm.crc().checksum(&value)).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Crc16;
+
+impl Function for Crc16 {
+    fn identifier(&self) -> &'static str {
+        "crc16"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "crc16",
+                source: r#"crc16("123456789")"#,
+                result: Ok("47933"),
+            },
+            Example {
+                title: "modbus",
+                source: r#"crc16("123456789", algorithm: "modbus")"#,
+                result: Ok("19255"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let algorithm = arguments
+            .optional_enum("algorithm", &algorithms())?
+            .map(|value| {
+                Algorithm::from_bytes(&value.try_bytes().expect("algorithm not bytes"))
+                    .expect("validated enum")
+            })
+            .unwrap_or(Algorithm::Arc);
+
+        Ok(Box::new(Crc16Fn { value, algorithm }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Crc16Fn {
+    value: Box<dyn Expression>,
+    algorithm: Algorithm,
+}
+
+impl Expression for Crc16Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        crc16(value, self.algorithm)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        crc16 => Crc16;
+
+        arc {
+            args: func_args![value: "123456789"],
+            want: Ok(47933),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        ccitt_false {
+            args: func_args![value: "123456789", algorithm: "ccitt_false"],
+            want: Ok(10673),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        kermit {
+            args: func_args![value: "123456789", algorithm: "kermit"],
+            want: Ok(8585),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        modbus {
+            args: func_args![value: "123456789", algorithm: "modbus"],
+            want: Ok(19255),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xmodem {
+            args: func_args![value: "123456789", algorithm: "xmodem"],
+            want: Ok(12739),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        invalid_algorithm {
+            args: func_args![value: "123456789", algorithm: "crc32"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
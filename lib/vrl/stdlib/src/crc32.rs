@@ -0,0 +1,151 @@
+use ::value::Value;
+use crc::{Crc, CRC_32_ISCSI, CRC_32_ISO_HDLC};
+use vrl::prelude::*;
+
+const IEEE: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+const CASTAGNOLI: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+
+fn algorithms() -> Vec<Value> {
+    vec![value!("ieee"), value!("castagnoli")]
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Algorithm {
+    Ieee,
+    Castagnoli,
+}
+
+impl Algorithm {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            b"ieee" => Some(Algorithm::Ieee),
+            b"castagnoli" => Some(Algorithm::Castagnoli),
+            _ => None,
+        }
+    }
+
+    fn crc(self) -> &'static Crc<u32> {
+        match self {
+            Algorithm::Ieee => &IEEE,
+            Algorithm::Castagnoli => &CASTAGNOLI,
+        }
+    }
+}
+
+fn crc32(value: Value, algorithm: Algorithm) -> Resolved {
+    let value = value.try_bytes()?;
+
+    Ok(i64::from(algorithm.crc().checksum(&value)).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32;
+
+impl Function for Crc32 {
+    fn identifier(&self) -> &'static str {
+        "crc32"
+    }
+
+    fn pure(&self) -> bool {
+        true
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "crc32",
+                source: r#"crc32("123456789")"#,
+                result: Ok("3421780262"),
+            },
+            Example {
+                title: "castagnoli",
+                source: r#"crc32("123456789", algorithm: "castagnoli")"#,
+                result: Ok("3808858755"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let algorithm = arguments
+            .optional_enum("algorithm", &algorithms())?
+            .map(|value| {
+                Algorithm::from_bytes(&value.try_bytes().expect("algorithm not bytes"))
+                    .expect("validated enum")
+            })
+            .unwrap_or(Algorithm::Ieee);
+
+        Ok(Box::new(Crc32Fn { value, algorithm }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Crc32Fn {
+    value: Box<dyn Expression>,
+    algorithm: Algorithm,
+}
+
+impl Expression for Crc32Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        crc32(value, self.algorithm)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        crc32 => Crc32;
+
+        ieee {
+            args: func_args![value: "123456789"],
+            want: Ok(3_421_780_262_i64),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Ok(0),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        castagnoli {
+            args: func_args![value: "123456789", algorithm: "castagnoli"],
+            want: Ok(3_808_858_755_i64),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        invalid_algorithm {
+            args: func_args![value: "123456789", algorithm: "adler"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
@@ -32,6 +32,10 @@ impl Function for DecodeBase64 {
         "decode_base64"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+fn detect_language(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    let info = match whatlang::detect(&text) {
+        Some(info) => info,
+        None => return Ok(Value::Null),
+    };
+
+    Ok(BTreeMap::from([
+        ("language".to_owned(), Value::from(info.lang().code())),
+        ("script".to_owned(), Value::from(info.script().name())),
+        (
+            "confidence".to_owned(),
+            Value::from_f64_or_zero(info.confidence()),
+        ),
+        ("reliable".to_owned(), Value::from(info.is_reliable())),
+    ])
+    .into())
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("language".into(), Kind::bytes()),
+        ("script".into(), Kind::bytes()),
+        ("confidence".into(), Kind::float()),
+        ("reliable".into(), Kind::boolean()),
+    ])
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DetectLanguage;
+
+impl Function for DetectLanguage {
+    fn identifier(&self) -> &'static str {
+        "detect_language"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "english",
+                source: r#"detect_language("The quick brown fox jumps over the lazy dog, and then it runs into the forest.")"#,
+                result: Ok(
+                    r#"{ "language": "eng", "script": "Latin", "confidence": 1.0, "reliable": true }"#,
+                ),
+            },
+            Example {
+                title: "undetectable",
+                source: r#"detect_language("1234")"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DetectLanguageFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DetectLanguageFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DetectLanguageFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        detect_language(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).add_null().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        detect_language => DetectLanguage;
+
+        english {
+            args: func_args![value: "The quick brown fox jumps over the lazy dog, and then it runs into the forest."],
+            want: Ok(value!({ "language": "eng", "script": "Latin", "confidence": 1.0, "reliable": true })),
+            tdef: TypeDef::object(inner_kind()).add_null().infallible(),
+        }
+
+        russian {
+            args: func_args![value: "Съешь же ещё этих мягких французских булок, да выпей чаю."],
+            want: Ok(value!({ "language": "rus", "script": "Cyrillic", "confidence": 1.0, "reliable": true })),
+            tdef: TypeDef::object(inner_kind()).add_null().infallible(),
+        }
+
+        japanese {
+            args: func_args![value: "これは日本語の文章です。"],
+            want: Ok(value!({ "language": "jpn", "script": "Hiragana", "confidence": 1.0, "reliable": true })),
+            tdef: TypeDef::object(inner_kind()).add_null().infallible(),
+        }
+
+        undetectable {
+            args: func_args![value: "1234 !!"],
+            want: Ok(value!(null)),
+            tdef: TypeDef::object(inner_kind()).add_null().infallible(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Ok(value!(null)),
+            tdef: TypeDef::object(inner_kind()).add_null().infallible(),
+        }
+    ];
+}
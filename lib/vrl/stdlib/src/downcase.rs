@@ -13,6 +13,10 @@ impl Function for Downcase {
         "downcase"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[Parameter {
             keyword: "value",
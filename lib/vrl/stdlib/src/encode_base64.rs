@@ -30,6 +30,10 @@ impl Function for EncodeBase64 {
         "encode_base64"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
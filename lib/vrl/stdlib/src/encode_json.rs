@@ -1,11 +1,114 @@
-use ::value::Value;
+use std::io;
+
+use ::value::{
+    json::{NonFiniteFloats, WithNonFiniteFloats},
+    Value,
+};
+use serde::Serialize;
+use serde_json::ser::{CompactFormatter, Formatter, PrettyFormatter, Serializer};
 use vrl::prelude::*;
 
-fn encode_json(value: Value) -> Resolved {
-    // With `vrl::Value` it should not be possible to get `Err`.
-    match serde_json::to_string(&value) {
-        Ok(value) => Ok(value.into()),
-        Err(error) => unreachable!("unable encode to json: {}", error),
+use crate::util;
+
+/// The most decimal places floats can be written with, beyond which `f64` has no precision left.
+const MAX_FLOAT_PRECISION: i64 = 17;
+
+fn encode_json(
+    value: Value,
+    pretty: Value,
+    float_precision: Option<Value>,
+    non_finite_floats: NonFiniteFloats,
+) -> Resolved {
+    let float_precision = match float_precision {
+        Some(precision) => Some(precision.try_integer()?.clamp(0, MAX_FLOAT_PRECISION) as usize),
+        None => None,
+    };
+    let value = WithNonFiniteFloats(&value, non_finite_floats);
+    let json = if pretty.try_boolean()? {
+        to_vec(value, PrettyFormatter::new(), float_precision)
+    } else {
+        to_vec(value, CompactFormatter, float_precision)
+    };
+    Ok(json.map_err(|error| error.to_string())?.into())
+}
+
+/// Writes the value as JSON. Object keys are always in lexicographical order, since objects are
+/// kept sorted.
+///
+/// Fails only if the value holds a non-finite float, and they are rejected.
+fn to_vec<F: Formatter>(
+    value: WithNonFiniteFloats<'_>,
+    formatter: F,
+    float_precision: Option<usize>,
+) -> serde_json::Result<Vec<u8>> {
+    let mut json = Vec::new();
+    let formatter = FloatFormatter {
+        inner: formatter,
+        float_precision,
+    };
+    value.serialize(&mut Serializer::with_formatter(&mut json, formatter))?;
+    Ok(json)
+}
+
+/// Writes floats with a fixed number of decimal places, if any, rather than the shortest that
+/// reads back as the same float, and everything else like the inner formatter.
+struct FloatFormatter<F> {
+    inner: F,
+    float_precision: Option<usize>,
+}
+
+impl<F: Formatter> Formatter for FloatFormatter<F> {
+    fn write_f64<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        // Non-finite floats are written as `null`, or handled by `WithNonFiniteFloats`, without
+        // getting here.
+        match self.float_precision {
+            Some(precision) => write!(writer, "{:.*}", precision, value),
+            None => self.inner.write_f64(writer, value),
+        }
+    }
+
+    fn begin_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_array(writer)
+    }
+
+    fn end_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_array(writer)
+    }
+
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.inner.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_object(writer)
+    }
+
+    fn end_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_object(writer)
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.inner.begin_object_key(writer, first)
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_object_value(writer)
+    }
+
+    fn end_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_object_value(writer)
     }
 }
 
@@ -18,11 +121,28 @@ impl Function for EncodeJson {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::ANY,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "pretty",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "float_precision",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "non_finite_floats",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
     }
 
     fn compile(
@@ -32,32 +152,56 @@ impl Function for EncodeJson {
         mut arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let pretty = arguments.optional("pretty").unwrap_or_else(|| expr!(false));
+        let float_precision = arguments.optional("float_precision");
+        let non_finite_floats = util::non_finite_floats(&mut arguments, NonFiniteFloats::Null)?;
 
-        Ok(Box::new(EncodeJsonFn { value }))
+        Ok(Box::new(EncodeJsonFn {
+            value,
+            pretty,
+            float_precision,
+            non_finite_floats,
+        }))
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "encode object",
-            source: r#"encode_json({"field": "value", "another": [1,2,3]})"#,
-            result: Ok(r#"s'{"another":[1,2,3],"field":"value"}'"#),
-        }]
+        &[
+            Example {
+                title: "encode object",
+                source: r#"encode_json({"field": "value", "another": [1,2,3]})"#,
+                result: Ok(r#"s'{"another":[1,2,3],"field":"value"}'"#),
+            },
+            Example {
+                title: "encode with float precision",
+                source: r#"encode_json({"ratio": 0.5, "total": 3}, float_precision: 2)"#,
+                result: Ok(r#"s'{"ratio":0.50,"total":3}'"#),
+            },
+        ]
     }
 }
 
 #[derive(Clone, Debug)]
 struct EncodeJsonFn {
     value: Box<dyn Expression>,
+    pretty: Box<dyn Expression>,
+    float_precision: Option<Box<dyn Expression>>,
+    non_finite_floats: NonFiniteFloats,
 }
 
 impl Expression for EncodeJsonFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        encode_json(value)
+        let pretty = self.pretty.resolve(ctx)?;
+        let float_precision = self
+            .float_precision
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        encode_json(value, pretty, float_precision, self.non_finite_floats)
     }
 
     fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
-        TypeDef::bytes().infallible()
+        TypeDef::bytes().with_fallibility(self.non_finite_floats == NonFiniteFloats::Error)
     }
 }
 
@@ -68,6 +212,10 @@ mod tests {
 
     use super::*;
 
+    fn infinities() -> Value {
+        value!({"ratio": 0.5, "rates": [(f64::INFINITY), (f64::NEG_INFINITY)]})
+    }
+
     test_function![
         encode_json => EncodeJson;
 
@@ -128,5 +276,59 @@ mod tests {
             want: Ok("null"),
             tdef: TypeDef::bytes().infallible(),
         }
+
+        sorted_keys {
+            args: func_args![value: value!({"b": {"z": 1, "a": 2}, "a": null})],
+            want: Ok(r#"{"a":null,"b":{"a":2,"z":1}}"#),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        pretty {
+            args: func_args![value: value!({"field": [1, 2]}), pretty: true],
+            want: Ok("{\n  \"field\": [\n    1,\n    2\n  ]\n}"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        float_precision {
+            args: func_args![value: value!([1.0, 0.126, 2, 3.14159]), float_precision: 2],
+            want: Ok("[1.00,0.13,2,3.14]"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        zero_float_precision {
+            args: func_args![value: 2.4, float_precision: 0],
+            want: Ok("2"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        pretty_float_precision {
+            args: func_args![value: value!({"ratio": 0.5}), pretty: true, float_precision: 3],
+            want: Ok("{\n  \"ratio\": 0.500\n}"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        non_finite_floats_null {
+            args: func_args![value: infinities()],
+            want: Ok(r#"{"ratio":0.5,"rates":[null,null]}"#),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        non_finite_floats_string {
+            args: func_args![value: infinities(), non_finite_floats: "string"],
+            want: Ok(r#"{"ratio":0.5,"rates":["Infinity","-Infinity"]}"#),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        non_finite_floats_error {
+            args: func_args![value: infinities(), non_finite_floats: "error"],
+            want: Err("unable to encode non-finite float Infinity as JSON"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        finite_floats_error {
+            args: func_args![value: value!({"ratio": 0.5}), non_finite_floats: "error"],
+            want: Ok(r#"{"ratio":0.5}"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
 }
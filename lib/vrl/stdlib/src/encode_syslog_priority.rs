@@ -0,0 +1,174 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::{
+    to_syslog_facility_code::to_syslog_facility_code, to_syslog_severity::to_syslog_severity,
+};
+
+/// Resolves either a numeric code or a keyword into a code within `0..=max`.
+fn resolve_code(
+    value: Value,
+    max: i64,
+    kind: &str,
+    from_name: fn(Value) -> Resolved,
+) -> Result<i64, ExpressionError> {
+    let code = match value {
+        Value::Integer(code) => code,
+        value @ Value::Bytes(_) => from_name(value)?.try_integer()?,
+        value => {
+            return Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::integer() | Kind::bytes(),
+            }
+            .into())
+        }
+    };
+
+    if !(0..=max).contains(&code) {
+        return Err(format!("syslog {} code {} not valid", kind, code).into());
+    }
+
+    Ok(code)
+}
+
+fn encode_syslog_priority(facility: Value, severity: Value) -> Resolved {
+    // PRI = facility * 8 + severity: https://datatracker.ietf.org/doc/html/rfc5424#section-6.2.1
+    let facility = resolve_code(facility, 23, "facility", to_syslog_facility_code)?;
+    let severity = resolve_code(severity, 7, "severity", to_syslog_severity)?;
+
+    Ok((facility * 8 + severity).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeSyslogPriority;
+
+impl Function for EncodeSyslogPriority {
+    fn identifier(&self) -> &'static str {
+        "encode_syslog_priority"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "facility",
+                kind: kind::INTEGER | kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "severity",
+                kind: kind::INTEGER | kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "from names",
+                source: r#"encode_syslog_priority!("local0", "info")"#,
+                result: Ok("134"),
+            },
+            Example {
+                title: "from codes",
+                source: "encode_syslog_priority!(4, 2)",
+                result: Ok("34"),
+            },
+            Example {
+                title: "invalid",
+                source: "encode_syslog_priority!(24, 2)",
+                result: Err(
+                    r#"function call error for "encode_syslog_priority" at (0:30): syslog facility code 24 not valid"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let facility = arguments.required("facility");
+        let severity = arguments.required("severity");
+
+        Ok(Box::new(EncodeSyslogPriorityFn { facility, severity }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodeSyslogPriorityFn {
+    facility: Box<dyn Expression>,
+    severity: Box<dyn Expression>,
+}
+
+impl Expression for EncodeSyslogPriorityFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let facility = self.facility.resolve(ctx)?;
+        let severity = self.severity.resolve(ctx)?;
+
+        encode_syslog_priority(facility, severity)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        encode_syslog_priority => EncodeSyslogPriority;
+
+        names {
+            args: func_args![facility: value!("local0"), severity: value!("info")],
+            want: Ok(value!(134)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        codes {
+            args: func_args![facility: value!(4), severity: value!(2)],
+            want: Ok(value!(34)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        mixed {
+            args: func_args![facility: value!("kern"), severity: value!(0)],
+            want: Ok(value!(0)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        highest {
+            args: func_args![facility: value!("local7"), severity: value!("debug")],
+            want: Ok(value!(191)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        facility_out_of_range {
+            args: func_args![facility: value!(24), severity: value!(0)],
+            want: Err("syslog facility code 24 not valid"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        severity_out_of_range {
+            args: func_args![facility: value!(1), severity: value!(-1)],
+            want: Err("syslog severity code -1 not valid"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        unknown_facility {
+            args: func_args![facility: value!("oopsie"), severity: value!(0)],
+            want: Err("syslog facility oopsie not valid"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        unknown_severity {
+            args: func_args![facility: value!(1), severity: value!("oopsie")],
+            want: Err("syslog level oopsie not valid"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,242 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+/// The RFC 5424 NILVALUE, used when there are no SD-ELEMENTs.
+const NIL_VALUE: &str = "-";
+
+fn validate_sd_name(name: &str, kind: &str) -> Result<(), ExpressionError> {
+    // SD-NAME = 1*32PRINTUSASCII except '=', SP, ']', %d34 (")
+    let valid = !name.is_empty()
+        && name.len() <= 32
+        && name
+            .bytes()
+            .all(|b| (33..=126).contains(&b) && !matches!(b, b'=' | b']' | b'"'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("invalid syslog structured data {}: {:?}", kind, name).into())
+    }
+}
+
+fn write_param(output: &mut String, name: &str, value: &Value) {
+    output.push(' ');
+    output.push_str(name);
+    output.push_str("=\"");
+    for c in value.to_string_lossy().chars() {
+        // '"', '\' and ']' must be escaped inside PARAM-VALUE.
+        if matches!(c, '"' | '\\' | ']') {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output.push('"');
+}
+
+fn encode_syslog_structured_data(value: Value) -> Resolved {
+    let elements = value.try_object()?;
+
+    if elements.is_empty() {
+        return Ok(NIL_VALUE.into());
+    }
+
+    let mut output = String::new();
+    for (id, params) in elements {
+        validate_sd_name(&id, "id")?;
+
+        let params = params.try_object().map_err(|err| {
+            format!(
+                "invalid params for syslog structured data id {}: {}",
+                id, err
+            )
+        })?;
+
+        output.push('[');
+        output.push_str(&id);
+        for (name, value) in params {
+            validate_sd_name(&name, "param name")?;
+
+            // Arrays are encoded as repeated params, which RFC 5424 allows.
+            let values = match value {
+                Value::Array(values) => values,
+                value => vec![value],
+            };
+
+            for value in values {
+                match value {
+                    Value::Null => {}
+                    Value::Array(_) | Value::Object(_) => {
+                        return Err(format!(
+                            "invalid value for syslog structured data param {}: nested values are not supported",
+                            name
+                        )
+                        .into())
+                    }
+                    value => write_param(&mut output, &name, &value),
+                }
+            }
+        }
+        output.push(']');
+    }
+
+    Ok(output.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeSyslogStructuredData;
+
+impl Function for EncodeSyslogStructuredData {
+    fn identifier(&self) -> &'static str {
+        "encode_syslog_structured_data"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "encode structured data",
+                source: r#"encode_syslog_structured_data!({"exampleSDID@32473": {"iut": "3", "eventSource": "Application"}})"#,
+                result: Ok(r#"s'[exampleSDID@32473 eventSource="Application" iut="3"]'"#),
+            },
+            Example {
+                title: "escape param values",
+                source: r#"encode_syslog_structured_data!({"origin": {"software": "v[1]"}})"#,
+                result: Ok(r#"s'[origin software="v[1\]"]'"#),
+            },
+            Example {
+                title: "empty",
+                source: "encode_syslog_structured_data!({})",
+                result: Ok("s'-'"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(EncodeSyslogStructuredDataFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodeSyslogStructuredDataFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for EncodeSyslogStructuredDataFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_syslog_structured_data(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    test_function![
+        encode_syslog_structured_data => EncodeSyslogStructuredData;
+
+        empty {
+            args: func_args![value: btreemap! {}],
+            want: Ok("-"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        single_element {
+            args: func_args![value: btreemap! {
+                "exampleSDID@32473" => btreemap! {
+                    "iut" => "3",
+                    "eventSource" => "Application",
+                    "eventID" => 1011,
+                }
+            }],
+            want: Ok(r#"[exampleSDID@32473 eventID="1011" eventSource="Application" iut="3"]"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        multiple_elements {
+            args: func_args![value: btreemap! {
+                "origin" => btreemap! { "ip" => "192.0.2.1" },
+                "meta" => btreemap! {},
+            }],
+            want: Ok(r#"[meta][origin ip="192.0.2.1"]"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        escaped_values {
+            args: func_args![value: btreemap! {
+                "x" => btreemap! { "v" => r#"a"b\c]d"# }
+            }],
+            want: Ok(r#"[x v="a\"b\\c\]d"]"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        repeated_params {
+            args: func_args![value: btreemap! {
+                "origin" => btreemap! { "ip" => vec!["192.0.2.1", "192.0.2.2"] }
+            }],
+            want: Ok(r#"[origin ip="192.0.2.1" ip="192.0.2.2"]"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        null_params_skipped {
+            args: func_args![value: btreemap! {
+                "x" => btreemap! { "a" => Value::Null, "b" => true }
+            }],
+            want: Ok(r#"[x b="true"]"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_id {
+            args: func_args![value: btreemap! {
+                "bad id" => btreemap! {}
+            }],
+            want: Err(r#"invalid syslog structured data id: "bad id""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_param_name {
+            args: func_args![value: btreemap! {
+                "x" => btreemap! { "a=b" => "c" }
+            }],
+            want: Err(r#"invalid syslog structured data param name: "a=b""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        params_not_object {
+            args: func_args![value: btreemap! {
+                "x" => "y"
+            }],
+            want: Err(r#"invalid params for syslog structured data id x: expected object, got string"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        nested_value {
+            args: func_args![value: btreemap! {
+                "x" => btreemap! { "a" => btreemap! { "b" => "c" } }
+            }],
+            want: Err("invalid value for syslog structured data param a: nested values are not supported"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,173 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn extract_bits(value: Value, offset: Value, length: Value) -> Resolved {
+    let value = value.try_integer()?;
+    let offset = offset.try_integer()?;
+    let length = length.try_integer()?;
+
+    if !(0..64).contains(&offset) {
+        return Err(format!("offset must be between 0 and 63, got {}", offset).into());
+    }
+
+    if length < 1 || length > 64 - offset {
+        return Err(format!(
+            "length must be between 1 and {}, got {}",
+            64 - offset,
+            length
+        )
+        .into());
+    }
+
+    // Offsets count from the least significant bit.
+    let bits = (value as u64) >> offset;
+    let mask = u64::MAX >> (64 - length);
+
+    Ok(((bits & mask) as i64).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ExtractBits;
+
+impl Function for ExtractBits {
+    fn identifier(&self) -> &'static str {
+        "extract_bits"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "offset",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "length",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "extract flags",
+                source: "extract_bits!(18, 1, 3)",
+                result: Ok("1"),
+            },
+            Example {
+                title: "extract nibble",
+                source: "extract_bits!(171, 4, 4)",
+                result: Ok("10"),
+            },
+            Example {
+                title: "invalid length",
+                source: "extract_bits!(171, 60, 8)",
+                result: Err(
+                    r#"function call error for "extract_bits" at (0:25): length must be between 1 and 4, got 8"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let offset = arguments.required("offset");
+        let length = arguments.required("length");
+
+        Ok(Box::new(ExtractBitsFn {
+            value,
+            offset,
+            length,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExtractBitsFn {
+    value: Box<dyn Expression>,
+    offset: Box<dyn Expression>,
+    length: Box<dyn Expression>,
+}
+
+impl Expression for ExtractBitsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let offset = self.offset.resolve(ctx)?;
+        let length = self.length.resolve(ctx)?;
+
+        extract_bits(value, offset, length)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        extract_bits => ExtractBits;
+
+        low_bits {
+            args: func_args![value: 171, offset: 0, length: 4],
+            want: Ok(11),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        middle_bits {
+            args: func_args![value: 171, offset: 4, length: 4],
+            want: Ok(10),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        single_bit {
+            args: func_args![value: 0b100, offset: 2, length: 1],
+            want: Ok(1),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        negative_value {
+            args: func_args![value: -1, offset: 56, length: 8],
+            want: Ok(255),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        full_width {
+            args: func_args![value: -1, offset: 0, length: 64],
+            want: Ok(-1),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        invalid_offset {
+            args: func_args![value: 1, offset: 64, length: 1],
+            want: Err("offset must be between 0 and 63, got 64"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        invalid_length {
+            args: func_args![value: 1, offset: 60, length: 8],
+            want: Err("length must be between 1 and 4, got 8"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        zero_length {
+            args: func_args![value: 1, offset: 0, length: 0],
+            want: Err("length must be between 1 and 64, got 0"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
@@ -69,9 +69,11 @@ impl Function for ForEach {
                 variables: vec![
                     Variable {
                         kind: VariableKind::TargetInnerKey,
+                        required: true,
                     },
                     Variable {
                         kind: VariableKind::TargetInnerValue,
+                        required: false,
                     },
                 ],
                 output: Output::Kind(Kind::any()),
@@ -82,6 +84,7 @@ impl Function for ForEach {
                 },
             }],
             is_iterator: true,
+            is_optional: false,
         })
     }
 }
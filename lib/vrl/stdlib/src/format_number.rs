@@ -61,19 +61,20 @@ fn format_number(
     // Manipulate integral part based on configuration.
     if let Some(sep) = grouping_separator.as_deref() {
         let sep = String::from_utf8_lossy(sep);
-        let start = parts[0].len() % 3;
+        let (sign, digits) = match parts[0].strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", &parts[0][..]),
+        };
 
-        let positions: Vec<usize> = parts[0]
-            .chars()
-            .skip(start)
-            .enumerate()
-            .map(|(i, _)| i)
-            .filter(|i| i % 3 == 0)
-            .collect();
-
-        for (i, pos) in positions.iter().enumerate() {
-            parts[0].insert_str(pos + (i * sep.len()) + start, &sep);
+        let mut grouped = sign.to_owned();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push_str(&sep);
+            }
+            grouped.push(c);
         }
+
+        parts[0] = grouped;
     }
     // Join results, using configured decimal separator.
     Ok(parts
@@ -250,5 +251,34 @@ mod tests {
             want: Ok(value!("12345.00")),
             tdef: TypeDef::bytes().infallible(),
         }
+
+        grouping_multiple_of_three {
+            args: func_args![value: 123456,
+                             grouping_separator: ","],
+            want: Ok(value!("123,456")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        grouping_short {
+            args: func_args![value: 123,
+                             grouping_separator: ","],
+            want: Ok(value!("123")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        grouping_negative {
+            args: func_args![value: -1234567.891,
+                             scale: 2,
+                             grouping_separator: ","],
+            want: Ok(value!("-1,234,567.89")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        grouping_negative_multiple_of_three {
+            args: func_args![value: -123456,
+                             grouping_separator: ","],
+            want: Ok(value!("-123,456")),
+            tdef: TypeDef::bytes().infallible(),
+        }
     ];
 }
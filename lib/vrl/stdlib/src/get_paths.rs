@@ -0,0 +1,146 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::{path_with_field, path_with_index};
+
+fn get_paths(value: Value) -> Resolved {
+    let mut paths = Vec::new();
+
+    match value {
+        Value::Object(map) => {
+            for (field, value) in map {
+                collect_paths(value, path_with_field("", &field), &mut paths)
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.into_iter().enumerate() {
+                collect_paths(value, path_with_index("", index), &mut paths)
+            }
+        }
+        value => {
+            return Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::array(Collection::any()) | Kind::object(Collection::any()),
+            }
+            .into())
+        }
+    }
+
+    Ok(paths.into())
+}
+
+/// Collects the paths of all leaves, where empty objects and arrays count as leaves.
+fn collect_paths(value: Value, path: String, paths: &mut Vec<Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (field, value) in map {
+                collect_paths(value, path_with_field(&path, &field), paths)
+            }
+        }
+        Value::Array(array) if !array.is_empty() => {
+            for (index, value) in array.into_iter().enumerate() {
+                collect_paths(value, path_with_index(&path, index), paths)
+            }
+        }
+        _ => paths.push(path.into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetPaths;
+
+impl Function for GetPaths {
+    fn identifier(&self) -> &'static str {
+        "get_paths"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: r#"get_paths({"foo": {"bar": true, "baz": [1, 2]}})"#,
+                result: Ok(r#"["foo.bar", "foo.baz[0]", "foo.baz[1]"]"#),
+            },
+            Example {
+                title: "quoted field",
+                source: r#"get_paths({"foo bar": {"baz": null}})"#,
+                result: Ok(r#"[s'"foo bar".baz']"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(GetPathsFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetPathsFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for GetPathsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        get_paths(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::bytes())).infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        get_paths => GetPaths;
+
+        object {
+            args: func_args![value: value!({"foo": {"bar": true, "baz": [1, {"qux": 2}]}, "quux": 3})],
+            want: Ok(value!(["foo.bar", "foo.baz[0]", "foo.baz[1].qux", "quux"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        array {
+            args: func_args![value: value!([{"foo": 1}, 2])],
+            want: Ok(value!(["[0].foo", "[1]"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        empty_containers {
+            args: func_args![value: value!({"foo": {}, "bar": []})],
+            want: Ok(value!(["bar", "foo"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        empty {
+            args: func_args![value: value!({})],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        quoted_fields {
+            args: func_args![value: value!({"foo bar": {"a.b": 1}})],
+            want: Ok(value!([r#""foo bar"."a.b""#])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+    ];
+}
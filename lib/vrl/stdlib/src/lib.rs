@@ -15,6 +15,16 @@ mod array;
 mod assert;
 #[cfg(feature = "assert_eq")]
 mod assert_eq;
+#[cfg(feature = "bit_and")]
+mod bit_and;
+#[cfg(feature = "bit_or")]
+mod bit_or;
+#[cfg(feature = "bit_shl")]
+mod bit_shl;
+#[cfg(feature = "bit_shr")]
+mod bit_shr;
+#[cfg(feature = "bit_xor")]
+mod bit_xor;
 #[cfg(feature = "boolean")]
 mod boolean;
 #[cfg(feature = "ceil")]
@@ -23,6 +33,10 @@ mod ceil;
 mod compact;
 #[cfg(feature = "contains")]
 mod contains;
+#[cfg(feature = "crc16")]
+mod crc16;
+#[cfg(feature = "crc32")]
+mod crc32;
 #[cfg(feature = "decode_base64")]
 mod decode_base64;
 #[cfg(feature = "decode_percent")]
@@ -31,6 +45,8 @@ mod decode_percent;
 mod decrypt;
 #[cfg(feature = "del")]
 mod del;
+#[cfg(feature = "detect_language")]
+mod detect_language;
 #[cfg(feature = "downcase")]
 mod downcase;
 #[cfg(feature = "encode_base64")]
@@ -43,12 +59,18 @@ mod encode_key_value;
 mod encode_logfmt;
 #[cfg(feature = "encode_percent")]
 mod encode_percent;
+#[cfg(feature = "encode_syslog_priority")]
+mod encode_syslog_priority;
+#[cfg(feature = "encode_syslog_structured_data")]
+mod encode_syslog_structured_data;
 #[cfg(feature = "encrypt")]
 mod encrypt;
 #[cfg(feature = "ends_with")]
 mod ends_with;
 #[cfg(feature = "exists")]
 mod exists;
+#[cfg(feature = "extract_bits")]
+mod extract_bits;
 #[cfg(feature = "find")]
 mod find;
 #[cfg(feature = "flatten")]
@@ -71,6 +93,8 @@ mod get;
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
 mod get_hostname;
+#[cfg(feature = "get_paths")]
+mod get_paths;
 #[cfg(feature = "includes")]
 mod includes;
 #[cfg(feature = "integer")]
@@ -121,9 +145,13 @@ mod join;
 mod length;
 #[cfg(feature = "log")]
 mod log;
+#[cfg(feature = "luhn_valid")]
+mod luhn_valid;
 #[cfg(any(
     feature = "parse_common_log",
     feature = "parse_apache_log",
+    feature = "parse_envoy_log",
+    feature = "parse_haproxy_log",
     feature = "parse_nginx_log"
 ))]
 mod log_util;
@@ -139,6 +167,8 @@ mod match_any;
 mod match_array;
 #[cfg(feature = "match_datadog_query")]
 mod match_datadog_query;
+#[cfg(feature = "match_path")]
+mod match_path;
 #[cfg(feature = "md5")]
 mod md5;
 #[cfg(feature = "merge")]
@@ -153,22 +183,32 @@ mod only_fields;
 mod parse_apache_log;
 #[cfg(feature = "parse_aws_alb_log")]
 mod parse_aws_alb_log;
+#[cfg(feature = "parse_aws_cloudfront_log")]
+mod parse_aws_cloudfront_log;
 #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
 mod parse_aws_cloudwatch_log_subscription_message;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 mod parse_aws_vpc_flow_log;
+#[cfg(feature = "parse_binary")]
+mod parse_binary;
 #[cfg(feature = "parse_common_log")]
 mod parse_common_log;
 #[cfg(feature = "parse_csv")]
 mod parse_csv;
 #[cfg(feature = "parse_duration")]
 mod parse_duration;
+#[cfg(feature = "parse_envoy_log")]
+mod parse_envoy_log;
 #[cfg(feature = "parse_glog")]
 mod parse_glog;
 #[cfg(feature = "parse_grok")]
 mod parse_grok;
 #[cfg(feature = "parse_groks")]
 mod parse_groks;
+#[cfg(feature = "parse_haproxy_log")]
+mod parse_haproxy_log;
+#[cfg(feature = "parse_human_size")]
+mod parse_human_size;
 #[cfg(feature = "parse_int")]
 mod parse_int;
 #[cfg(feature = "parse_json")]
@@ -211,6 +251,8 @@ mod random_bytes;
 mod redact;
 #[cfg(feature = "remove")]
 mod remove;
+#[cfg(feature = "remove_paths")]
+mod remove_paths;
 #[cfg(feature = "replace")]
 mod replace;
 #[cfg(feature = "reverse_dns")]
@@ -259,6 +301,8 @@ mod to_regex;
 mod to_string;
 #[cfg(feature = "to_syslog_facility")]
 mod to_syslog_facility;
+#[cfg(feature = "to_syslog_facility_code")]
+mod to_syslog_facility_code;
 #[cfg(feature = "to_syslog_level")]
 mod to_syslog_level;
 #[cfg(feature = "to_syslog_severity")]
@@ -267,10 +311,14 @@ mod to_syslog_severity;
 mod to_timestamp;
 #[cfg(feature = "to_unix_timestamp")]
 mod to_unix_timestamp;
+#[cfg(feature = "transliterate")]
+mod transliterate;
 #[cfg(feature = "truncate")]
 mod truncate;
 #[cfg(feature = "type_def")]
 mod type_def;
+#[cfg(feature = "type_of")]
+mod type_of;
 #[cfg(feature = "unique")]
 mod unique;
 #[cfg(feature = "unnest")]
@@ -279,6 +327,8 @@ mod unnest;
 mod upcase;
 #[cfg(feature = "uuid_v4")]
 mod uuid_v4;
+#[cfg(feature = "verify_ean")]
+mod verify_ean;
 
 // -----------------------------------------------------------------------------
 
@@ -288,6 +338,16 @@ pub use append::Append;
 pub use assert::Assert;
 #[cfg(feature = "assert_eq")]
 pub use assert_eq::AssertEq;
+#[cfg(feature = "bit_and")]
+pub use bit_and::BitAnd;
+#[cfg(feature = "bit_or")]
+pub use bit_or::BitOr;
+#[cfg(feature = "bit_shl")]
+pub use bit_shl::BitShl;
+#[cfg(feature = "bit_shr")]
+pub use bit_shr::BitShr;
+#[cfg(feature = "bit_xor")]
+pub use bit_xor::BitXor;
 #[cfg(feature = "boolean")]
 pub use boolean::Boolean;
 #[cfg(feature = "ceil")]
@@ -296,6 +356,10 @@ pub use ceil::Ceil;
 pub use compact::Compact;
 #[cfg(feature = "contains")]
 pub use contains::Contains;
+#[cfg(feature = "crc16")]
+pub use crc16::Crc16;
+#[cfg(feature = "crc32")]
+pub use crc32::Crc32;
 #[cfg(feature = "decode_base64")]
 pub use decode_base64::DecodeBase64;
 #[cfg(feature = "decode_percent")]
@@ -304,6 +368,8 @@ pub use decode_percent::DecodePercent;
 pub use decrypt::Decrypt;
 #[cfg(feature = "del")]
 pub use del::Del;
+#[cfg(feature = "detect_language")]
+pub use detect_language::DetectLanguage;
 #[cfg(feature = "downcase")]
 pub use downcase::Downcase;
 #[cfg(feature = "encode_base64")]
@@ -316,12 +382,18 @@ pub use encode_key_value::EncodeKeyValue;
 pub use encode_logfmt::EncodeLogfmt;
 #[cfg(feature = "encode_percent")]
 pub use encode_percent::EncodePercent;
+#[cfg(feature = "encode_syslog_priority")]
+pub use encode_syslog_priority::EncodeSyslogPriority;
+#[cfg(feature = "encode_syslog_structured_data")]
+pub use encode_syslog_structured_data::EncodeSyslogStructuredData;
 #[cfg(feature = "encrypt")]
 pub use encrypt::Encrypt;
 #[cfg(feature = "ends_with")]
 pub use ends_with::EndsWith;
 #[cfg(feature = "exists")]
 pub use exists::Exists;
+#[cfg(feature = "extract_bits")]
+pub use extract_bits::ExtractBits;
 #[cfg(feature = "find")]
 pub use find::Find;
 #[cfg(feature = "flatten")]
@@ -344,6 +416,8 @@ pub use get::Get;
 pub use get_env_var::GetEnvVar;
 #[cfg(feature = "get_hostname")]
 pub use get_hostname::GetHostname;
+#[cfg(feature = "get_paths")]
+pub use get_paths::GetPaths;
 #[cfg(feature = "includes")]
 pub use includes::Includes;
 #[cfg(feature = "integer")]
@@ -394,6 +468,8 @@ pub use join::Join;
 pub use length::Length;
 #[cfg(feature = "log")]
 pub use log::Log;
+#[cfg(feature = "luhn_valid")]
+pub use luhn_valid::LuhnValid;
 #[cfg(feature = "map_keys")]
 pub use map_keys::MapKeys;
 #[cfg(feature = "map_values")]
@@ -404,6 +480,8 @@ pub use match_any::MatchAny;
 pub use match_array::MatchArray;
 #[cfg(feature = "match_datadog_query")]
 pub use match_datadog_query::MatchDatadogQuery;
+#[cfg(feature = "match_path")]
+pub use match_path::MatchPath;
 #[cfg(feature = "merge")]
 pub use merge::Merge;
 #[cfg(feature = "now")]
@@ -416,22 +494,32 @@ pub use only_fields::OnlyFields;
 pub use parse_apache_log::ParseApacheLog;
 #[cfg(feature = "parse_aws_alb_log")]
 pub use parse_aws_alb_log::ParseAwsAlbLog;
+#[cfg(feature = "parse_aws_cloudfront_log")]
+pub use parse_aws_cloudfront_log::ParseAwsCloudFrontLog;
 #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
 pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 pub use parse_aws_vpc_flow_log::ParseAwsVpcFlowLog;
+#[cfg(feature = "parse_binary")]
+pub use parse_binary::ParseBinary;
 #[cfg(feature = "parse_common_log")]
 pub use parse_common_log::ParseCommonLog;
 #[cfg(feature = "parse_csv")]
 pub use parse_csv::ParseCsv;
 #[cfg(feature = "parse_duration")]
 pub use parse_duration::ParseDuration;
+#[cfg(feature = "parse_envoy_log")]
+pub use parse_envoy_log::ParseEnvoyLog;
 #[cfg(feature = "parse_glog")]
 pub use parse_glog::ParseGlog;
 #[cfg(feature = "parse_grok")]
 pub use parse_grok::ParseGrok;
 #[cfg(feature = "parse_groks")]
 pub use parse_groks::ParseGroks;
+#[cfg(feature = "parse_haproxy_log")]
+pub use parse_haproxy_log::ParseHaproxyLog;
+#[cfg(feature = "parse_human_size")]
+pub use parse_human_size::ParseHumanSize;
 #[cfg(feature = "parse_int")]
 pub use parse_int::ParseInt;
 #[cfg(feature = "parse_json")]
@@ -476,6 +564,8 @@ pub use random_bytes::RandomBytes;
 pub use redact::Redact;
 #[cfg(feature = "remove")]
 pub use remove::Remove;
+#[cfg(feature = "remove_paths")]
+pub use remove_paths::RemovePaths;
 #[cfg(feature = "replace")]
 pub use replace::Replace;
 #[cfg(feature = "reverse_dns")]
@@ -522,6 +612,8 @@ pub use to_regex::ToRegex;
 pub use to_string::ToString;
 #[cfg(feature = "to_syslog_facility")]
 pub use to_syslog_facility::ToSyslogFacility;
+#[cfg(feature = "to_syslog_facility_code")]
+pub use to_syslog_facility_code::ToSyslogFacilityCode;
 #[cfg(feature = "to_syslog_level")]
 pub use to_syslog_level::ToSyslogLevel;
 #[cfg(feature = "to_syslog_severity")]
@@ -530,10 +622,14 @@ pub use to_syslog_severity::ToSyslogSeverity;
 pub use to_timestamp::ToTimestamp;
 #[cfg(feature = "to_unix_timestamp")]
 pub use to_unix_timestamp::ToUnixTimestamp;
+#[cfg(feature = "transliterate")]
+pub use transliterate::Transliterate;
 #[cfg(feature = "truncate")]
 pub use truncate::Truncate;
 #[cfg(feature = "type_def")]
 pub use type_def::TypeDef;
+#[cfg(feature = "type_of")]
+pub use type_of::TypeOf;
 #[cfg(feature = "unique")]
 pub use unique::Unique;
 #[cfg(feature = "unnest")]
@@ -542,6 +638,8 @@ pub use unnest::Unnest;
 pub use upcase::Upcase;
 #[cfg(feature = "uuid_v4")]
 pub use uuid_v4::UuidV4;
+#[cfg(feature = "verify_ean")]
+pub use verify_ean::VerifyEan;
 
 #[cfg(feature = "array")]
 pub use crate::array::Array;
@@ -560,6 +658,16 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Assert),
         #[cfg(feature = "assert_eq")]
         Box::new(AssertEq),
+        #[cfg(feature = "bit_and")]
+        Box::new(BitAnd),
+        #[cfg(feature = "bit_or")]
+        Box::new(BitOr),
+        #[cfg(feature = "bit_shl")]
+        Box::new(BitShl),
+        #[cfg(feature = "bit_shr")]
+        Box::new(BitShr),
+        #[cfg(feature = "bit_xor")]
+        Box::new(BitXor),
         #[cfg(feature = "boolean")]
         Box::new(Boolean),
         #[cfg(feature = "ceil")]
@@ -568,6 +676,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Compact),
         #[cfg(feature = "contains")]
         Box::new(Contains),
+        #[cfg(feature = "crc16")]
+        Box::new(Crc16),
+        #[cfg(feature = "crc32")]
+        Box::new(Crc32),
         #[cfg(feature = "decode_base64")]
         Box::new(DecodeBase64),
         #[cfg(feature = "decode_percent")]
@@ -576,6 +688,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Decrypt),
         #[cfg(feature = "del")]
         Box::new(Del),
+        #[cfg(feature = "detect_language")]
+        Box::new(DetectLanguage),
         #[cfg(feature = "downcase")]
         Box::new(Downcase),
         #[cfg(feature = "encode_base64")]
@@ -588,12 +702,18 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(EncodeLogfmt),
         #[cfg(feature = "encode_percent")]
         Box::new(EncodePercent),
+        #[cfg(feature = "encode_syslog_priority")]
+        Box::new(EncodeSyslogPriority),
+        #[cfg(feature = "encode_syslog_structured_data")]
+        Box::new(EncodeSyslogStructuredData),
         #[cfg(feature = "encrypt")]
         Box::new(Encrypt),
         #[cfg(feature = "ends_with")]
         Box::new(EndsWith),
         #[cfg(feature = "exists")]
         Box::new(Exists),
+        #[cfg(feature = "extract_bits")]
+        Box::new(ExtractBits),
         #[cfg(feature = "find")]
         Box::new(Find),
         #[cfg(feature = "flatten")]
@@ -616,6 +736,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(GetEnvVar),
         #[cfg(feature = "get_hostname")]
         Box::new(GetHostname),
+        #[cfg(feature = "get_paths")]
+        Box::new(GetPaths),
         #[cfg(feature = "includes")]
         Box::new(Includes),
         #[cfg(feature = "integer")]
@@ -666,6 +788,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Length),
         #[cfg(feature = "log")]
         Box::new(Log),
+        #[cfg(feature = "luhn_valid")]
+        Box::new(LuhnValid),
         #[cfg(feature = "map_keys")]
         Box::new(MapKeys),
         #[cfg(feature = "map_values")]
@@ -678,38 +802,48 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(MatchArray),
         #[cfg(feature = "match_datadog_query")]
         Box::new(MatchDatadogQuery),
+        #[cfg(feature = "match_path")]
+        Box::new(MatchPath),
         #[cfg(feature = "md5")]
         Box::new(Md5),
         #[cfg(feature = "merge")]
         Box::new(Merge),
         #[cfg(feature = "now")]
         Box::new(Now),
-        // We are not sure if this is the way we want to expose this functionality yet
-        // https://github.com/vectordotdev/vector/issues/5607
-        //#[cfg(feature = "only_fields")]
-        //Box::new(OnlyFields),
         #[cfg(feature = "object")]
         Box::new(Object),
+        #[cfg(feature = "only_fields")]
+        Box::new(OnlyFields),
         #[cfg(feature = "parse_apache_log")]
         Box::new(ParseApacheLog),
         #[cfg(feature = "parse_aws_alb_log")]
         Box::new(ParseAwsAlbLog),
+        #[cfg(feature = "parse_aws_cloudfront_log")]
+        Box::new(ParseAwsCloudFrontLog),
         #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
         #[cfg(feature = "parse_aws_vpc_flow_log")]
         Box::new(ParseAwsVpcFlowLog),
+        #[cfg(feature = "parse_binary")]
+        Box::new(ParseBinary),
         #[cfg(feature = "parse_common_log")]
         Box::new(ParseCommonLog),
         #[cfg(feature = "parse_csv")]
         Box::new(ParseCsv),
         #[cfg(feature = "parse_duration")]
         Box::new(ParseDuration),
+        #[cfg(feature = "parse_envoy_log")]
+        Box::new(ParseEnvoyLog),
         #[cfg(feature = "parse_glog")]
         Box::new(ParseGlog),
         #[cfg(feature = "parse_grok")]
         Box::new(ParseGrok),
         #[cfg(feature = "parse_groks")]
         Box::new(ParseGroks),
+        #[cfg(feature = "parse_haproxy_log")]
+        Box::new(ParseHaproxyLog),
+        #[cfg(feature = "parse_human_size")]
+        Box::new(ParseHumanSize),
         #[cfg(feature = "parse_int")]
         Box::new(ParseInt),
         #[cfg(feature = "parse_json")]
@@ -752,6 +886,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Redact),
         #[cfg(feature = "remove")]
         Box::new(Remove),
+        #[cfg(feature = "remove_paths")]
+        Box::new(RemovePaths),
         #[cfg(feature = "replace")]
         Box::new(Replace),
         #[cfg(feature = "reverse_dns")]
@@ -800,6 +936,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ToString),
         #[cfg(feature = "to_syslog_facility")]
         Box::new(ToSyslogFacility),
+        #[cfg(feature = "to_syslog_facility_code")]
+        Box::new(ToSyslogFacilityCode),
         #[cfg(feature = "to_syslog_level")]
         Box::new(ToSyslogLevel),
         #[cfg(feature = "to_syslog_severity")]
@@ -808,10 +946,14 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ToTimestamp),
         #[cfg(feature = "to_unix_timestamp")]
         Box::new(ToUnixTimestamp),
+        #[cfg(feature = "transliterate")]
+        Box::new(Transliterate),
         #[cfg(feature = "truncate")]
         Box::new(Truncate),
         #[cfg(feature = "type_def")]
         Box::new(TypeDef),
+        #[cfg(feature = "type_of")]
+        Box::new(TypeOf),
         #[cfg(feature = "unique")]
         Box::new(Unique),
         #[cfg(feature = "unnest")]
@@ -820,5 +962,7 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Upcase),
         #[cfg(feature = "uuid_v4")]
         Box::new(UuidV4),
+        #[cfg(feature = "verify_ean")]
+        Box::new(VerifyEan),
     ]
 }
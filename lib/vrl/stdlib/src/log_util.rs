@@ -131,6 +131,69 @@ pub(crate) static REGEX_NGINX_ERROR_LOG: Lazy<Regex> = Lazy::new(|| {
     .expect("failed compiling regex for Nginx error log")
 });
 
+// - HAProxy docs: https://docs.haproxy.org/2.6/configuration.html#8.2.3
+#[cfg(feature = "parse_haproxy_log")]
+pub(crate) static REGEX_HAPROXY_HTTP_LOG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?x)                                                                  # Ignore whitespace and comments in the regex expression.
+        ^\s*                                                                     # Start with any number of whitespaces.
+        (.*?\[(?P<pid>\d+)\]:\s+)?                                               # Match an optional syslog header ending with the process id.
+        (?P<client>\S+):(?P<client_port>\d+)\s+                                  # Match any non space character, `:` and the port.
+        \[(?P<timestamp>[^\]]+)\]\s+                                             # Match date between brackets
+        (?P<frontend>\S+)\s+                                                     # Match any non space character
+        (?P<backend>[^/\s]+)/(?P<server>\S+)\s+                                  # Match the backend and server separated by `/`.
+        (?P<request_time>-?\d+)/(?P<queue_time>-?\d+)/                           # Match the timers, which are `-1` when the step
+        (?P<connect_time>-?\d+)/(?P<response_time>-?\d+)/                        # wasn't reached...
+        (?P<total_time>\+?-?\d+)\s+                                              # ...with the total prefixed by `+` when logged early.
+        (?P<status>-?\d+)\s+                                                     # Match numbers
+        (?P<size>\+?\d+)\s+                                                      # Match numbers, prefixed by `+` when logged early.
+        (-|(?P<request_cookie>\S+))\s+                                           # Match `-` or any non space character
+        (-|(?P<response_cookie>\S+))\s+                                          # Match `-` or any non space character
+        (?P<termination_state>\S{4})\s+                                          # Match four non space characters
+        (?P<active_connections>\d+)/(?P<frontend_connections>\d+)/               # Match the connection counts...
+        (?P<backend_connections>\d+)/(?P<server_connections>\d+)/                # ...
+        (?P<retries>\+?\d+)\s+                                                   # ...and the retries, prefixed by `+` when redispatched.
+        (?P<server_queue>\d+)/(?P<backend_queue>\d+)                             # Match the queue lengths
+        (\s+\{(?P<request_headers>[^}]+)?\})?                                    # Match the optional captured request headers
+        (\s+\{(?P<response_headers>[^}]+)?\})?                                   # Match the optional captured response headers
+        \s+"(?P<request>                                                         # Match a request with...
+        (?P<method>\w+)\s+                                                       # Match at least a word
+        (?P<path>\S+)                                                            # Match any non space character
+        (\s+(?P<protocol>[^"]+))?                                                # Match any non double-quote character
+        |[^"]*)"                                                                 # ...Or match any non double-quote character.
+        \s*$                                                                     # Match any number of whitespaces (to be discarded).
+    "#)
+    .expect("failed compiling regex for HAProxy HTTP log")
+});
+
+// - Envoy docs: https://www.envoyproxy.io/docs/envoy/latest/configuration/observability/access_log/usage#default-format-string
+#[cfg(feature = "parse_envoy_log")]
+pub(crate) static REGEX_ENVOY_ACCESS_LOG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?x)                                 # Ignore whitespace and comments in the regex expression.
+        ^\s*                                    # Start with any number of whitespaces.
+        \[(?P<timestamp>[^\]]+)\]\s+            # Match date between brackets
+        "(-(\s+-\s+-)?|(?P<request>             # Match `-`, `- - -` for TCP connections, or a request with...
+        (?P<method>\w+)\s+                      # Match at least a word
+        (?P<path>\S+)\s+                        # Match any non space character
+        (?P<protocol>[^"]+)                     # Match any non double-quote character
+        |[^"]+))?"\s+                           # ...Or match any non double-quote character.
+        (?P<status>\d+)\s+                      # Match numbers
+        (-|(?P<response_flags>\S+))\s+          # Match `-` or any non space character
+        (?P<bytes_received>\d+)\s+              # Match numbers
+        (?P<bytes_sent>\d+)\s+                  # Match numbers
+        (-|(?P<duration>\d+))\s+                # Match `-` or numbers
+        (-|(?P<upstream_service_time>\d+))\s+   # Match `-` or numbers
+        "(-|(?P<forwarded_for>[^"]+))?"\s+      # Match `-` or any non double-quote character
+        "(-|(?P<agent>[^"]+))?"\s+              # Match `-` or any non double-quote character
+        "(-|(?P<request_id>[^"]+))?"\s+         # Match `-` or any non double-quote character
+        "(-|(?P<authority>[^"]+))?"\s+          # Match `-` or any non double-quote character
+        "(-|(?P<upstream_host>[^"]+))?"         # Match `-` or any non double-quote character
+        \s*$                                    # Match any number of whitespaces (to be discarded).
+    "#)
+    .expect("failed compiling regex for Envoy access log")
+});
+
 // Parse the time as Utc from the given timezone
 fn parse_time(
     time: &str,
@@ -159,7 +222,29 @@ fn capture_value(
 ) -> std::result::Result<Value, String> {
     Ok(match name {
         "timestamp" => Value::Timestamp(parse_time(value, timestamp_format, timezone)?),
-        "status" | "size" | "pid" | "tid" | "cid" | "port" => Value::Integer(
+        "status"
+        | "size"
+        | "pid"
+        | "tid"
+        | "cid"
+        | "port"
+        | "client_port"
+        | "request_time"
+        | "queue_time"
+        | "connect_time"
+        | "response_time"
+        | "total_time"
+        | "active_connections"
+        | "frontend_connections"
+        | "backend_connections"
+        | "server_connections"
+        | "retries"
+        | "server_queue"
+        | "backend_queue"
+        | "bytes_received"
+        | "bytes_sent"
+        | "duration"
+        | "upstream_service_time" => Value::Integer(
             value
                 .parse()
                 .map_err(|_| format!("failed parsing {}", name))?,
@@ -0,0 +1,143 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+/// Check whether the digits of the given number pass the Luhn (mod 10)
+/// checksum. Spaces and dashes are ignored, any other non-digit character
+/// makes the number invalid.
+fn is_luhn_valid(bytes: &[u8]) -> bool {
+    let mut digits = 0;
+    let mut sum = 0;
+
+    for byte in bytes.iter().rev() {
+        let digit = match byte {
+            b'0'..=b'9' => u32::from(byte - b'0'),
+            b' ' | b'-' => continue,
+            _ => return false,
+        };
+
+        sum += if digits % 2 == 1 {
+            let doubled = digit * 2;
+            if doubled > 9 {
+                doubled - 9
+            } else {
+                doubled
+            }
+        } else {
+            digit
+        };
+        digits += 1;
+    }
+
+    digits > 1 && sum % 10 == 0
+}
+
+fn luhn_valid(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+
+    Ok(is_luhn_valid(&value).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LuhnValid;
+
+impl Function for LuhnValid {
+    fn identifier(&self) -> &'static str {
+        "luhn_valid"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid",
+                source: r#"luhn_valid("4111 1111 1111 1111")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "invalid",
+                source: r#"luhn_valid("4111 1111 1111 1112")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(LuhnValidFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LuhnValidFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for LuhnValidFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        luhn_valid(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::boolean().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        luhn_valid => LuhnValid;
+
+        valid_card_number {
+            args: func_args![value: "4111111111111111"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        valid_with_separators {
+            args: func_args![value: "4111-1111 1111-1111"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        valid_odd_length {
+            args: func_args![value: "79927398713"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        invalid_check_digit {
+            args: func_args![value: "79927398710"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        invalid_character {
+            args: func_args![value: "4111x111111111111"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        too_short {
+            args: func_args![value: "0"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+    ];
+}
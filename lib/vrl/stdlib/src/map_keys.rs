@@ -90,6 +90,7 @@ impl Function for MapKeys {
                 kind: Kind::object(Collection::any()),
                 variables: vec![Variable {
                     kind: VariableKind::Exact(Kind::bytes()),
+                    required: true,
                 }],
                 output: Output::Kind(Kind::bytes()),
                 example: Example {
@@ -99,6 +100,7 @@ impl Function for MapKeys {
                 },
             }],
             is_iterator: true,
+            is_optional: false,
         })
     }
 }
@@ -89,6 +89,7 @@ impl Function for MapValues {
                 kind: Kind::object(Collection::any()).or_array(Collection::any()),
                 variables: vec![Variable {
                     kind: VariableKind::TargetInnerValue,
+                    required: true,
                 }],
                 output: Output::Kind(Kind::any()),
                 example: Example {
@@ -98,6 +99,7 @@ impl Function for MapValues {
                 },
             }],
             is_iterator: true,
+            is_optional: false,
         })
     }
 }
@@ -0,0 +1,171 @@
+use ::value::Value;
+use regex::Regex;
+use vrl::prelude::*;
+
+use crate::util::path_pattern;
+
+fn match_path(path: Value, pattern: &Regex) -> Resolved {
+    let path = path.try_bytes_utf8_lossy()?;
+
+    Ok(pattern.is_match(&path).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MatchPath;
+
+impl Function for MatchPath {
+    fn identifier(&self) -> &'static str {
+        "match_path"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "path",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "pattern",
+                kind: kind::BYTES | kind::REGEX,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "glob",
+                source: r#"match_path("user.address.city", "user.*.city")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "glob across segments",
+                source: r#"match_path("user.emails[1].domain", "user.**.domain")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "regex",
+                source: r#"match_path("user.password", r'\.(password|token)$')"#,
+                result: Ok("true"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let path = arguments.required("path");
+        let pattern = arguments.required("pattern");
+
+        // Literal patterns are compiled once, instead of for every event.
+        let static_pattern = pattern
+            .as_value()
+            .map(|value| {
+                path_pattern(&value).map_err(|_| vrl::function::Error::InvalidArgument {
+                    keyword: "pattern",
+                    value,
+                    error: "path pattern must be either string or regex",
+                })
+            })
+            .transpose()?;
+
+        Ok(Box::new(MatchPathFn {
+            path,
+            pattern,
+            static_pattern,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MatchPathFn {
+    path: Box<dyn Expression>,
+    pattern: Box<dyn Expression>,
+    static_pattern: Option<Regex>,
+}
+
+impl Expression for MatchPathFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let path = self.path.resolve(ctx)?;
+
+        match &self.static_pattern {
+            Some(pattern) => match_path(path, pattern),
+            None => {
+                let pattern = path_pattern(&self.pattern.resolve(ctx)?)?;
+                match_path(path, &pattern)
+            }
+        }
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::boolean().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        match_path => MatchPath;
+
+        exact {
+            args: func_args![path: "foo.bar", pattern: "foo.bar"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        prefix_does_not_match {
+            args: func_args![path: "foo.bar.baz", pattern: "foo.bar"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        single_segment_wildcard {
+            args: func_args![path: "foo.bar.baz", pattern: "foo.*.baz"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        single_segment_wildcard_does_not_cross {
+            args: func_args![path: "foo.bar.qux.baz", pattern: "foo.*.baz"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        partial_segment_wildcard {
+            args: func_args![path: "user_id", pattern: "*_id"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        multi_segment_wildcard {
+            args: func_args![path: "foo.bar[2].baz", pattern: "foo.**.baz"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        index_wildcard {
+            args: func_args![path: "foo[12].bar", pattern: "foo[*].bar"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        question_mark {
+            args: func_args![path: "foo.ab", pattern: "foo.a?"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        regex {
+            args: func_args![path: "user.password", pattern: Value::Regex(Regex::new(r"\.(password|token)$").unwrap().into())],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+    ];
+}
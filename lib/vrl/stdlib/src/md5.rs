@@ -15,6 +15,10 @@ impl Function for Md5 {
         "md5"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[Parameter {
             keyword: "value",
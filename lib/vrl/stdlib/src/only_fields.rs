@@ -1,5 +1,68 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use regex::Regex;
 use vrl::prelude::*;
 
+use crate::util::{path_patterns, path_with_field, path_with_index};
+
+fn only_fields(value: Value, patterns: &[Regex]) -> Resolved {
+    match value {
+        Value::Object(_) => {
+            Ok(keep_matching(value, "", patterns).unwrap_or_else(|| BTreeMap::new().into()))
+        }
+        Value::Array(_) => {
+            Ok(keep_matching(value, "", patterns).unwrap_or_else(|| Vec::<Value>::new().into()))
+        }
+        value => Err(value::Error::Expected {
+            got: value.kind(),
+            expected: Kind::array(Collection::any()) | Kind::object(Collection::any()),
+        }
+        .into()),
+    }
+}
+
+/// Keeps every field or element whose path matches one of the patterns, along with
+/// everything nested below it. Objects and arrays left empty are dropped.
+fn keep_matching(value: Value, path: &str, patterns: &[Regex]) -> Option<Value> {
+    let is_match = |path: &str| patterns.iter().any(|pattern| pattern.is_match(path));
+
+    match value {
+        Value::Object(map) => {
+            let map = map
+                .into_iter()
+                .filter_map(|(field, value)| {
+                    let path = path_with_field(path, &field);
+                    if is_match(&path) {
+                        Some((field, value))
+                    } else {
+                        keep_matching(value, &path, patterns).map(|value| (field, value))
+                    }
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            (!map.is_empty()).then(|| map.into())
+        }
+        Value::Array(array) => {
+            let array = array
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, value)| {
+                    let path = path_with_index(path, index);
+                    if is_match(&path) {
+                        Some(value)
+                    } else {
+                        keep_matching(value, &path, patterns)
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            (!array.is_empty()).then(|| array.into())
+        }
+        _ => None,
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct OnlyFields;
 
@@ -9,13 +72,33 @@ impl Function for OnlyFields {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        generate_param_list! {
-            accepts = |_| true,
-            required = false,
-            keywords = [
-                "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16",
-            ],
-        }
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT | kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "patterns",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "allow-list",
+                source: r#"only_fields!({"user": {"id": 1, "password": "foo"}, "message": "bar", "tmp": true}, ["user.id", "message"])"#,
+                result: Ok(r#"{"message": "bar", "user": {"id": 1}}"#),
+            },
+            Example {
+                title: "glob",
+                source: r#"only_fields!({"a": {"x_id": 1, "y": 2}, "b_id": 3}, ["**_id"])"#,
+                result: Ok(r#"{"a": {"x_id": 1}, "b_id": 3}"#),
+            },
+        ]
     }
 
     fn compile(
@@ -24,44 +107,63 @@ impl Function for OnlyFields {
         _ctx: &mut FunctionCompileContext,
         mut arguments: ArgumentList,
     ) -> Compiled {
-        let mut paths = vec![];
-        paths.push(arguments.required_path("1")?);
+        let value = arguments.required("value");
+        let patterns = arguments.required("patterns");
 
-        for i in 2..=16 {
-            if let Some(path) = arguments.optional_path(&i.to_string())? {
-                paths.push(path)
-            }
-        }
+        // Literal patterns are compiled once, instead of for every event.
+        let static_patterns = patterns
+            .as_value()
+            .map(|value| {
+                path_patterns(value.clone()).map_err(|_| vrl::function::Error::InvalidArgument {
+                    keyword: "patterns",
+                    value,
+                    error: "path patterns must be either strings or regexes",
+                })
+            })
+            .transpose()?;
 
-        Ok(Box::new(OnlyFieldsFn { paths }))
+        Ok(Box::new(OnlyFieldsFn {
+            value,
+            patterns,
+            static_patterns,
+        }))
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct OnlyFieldsFn {
-    paths: Vec<Path>,
+struct OnlyFieldsFn {
+    value: Box<dyn Expression>,
+    patterns: Box<dyn Expression>,
+    static_patterns: Option<Vec<Regex>>,
 }
 
 impl Expression for OnlyFieldsFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
-        let paths = self.paths.iter().map(Path::to_string).collect::<Vec<_>>();
-
-        object
-            .paths()?
-            .iter()
-            .map(|path| (path, path.to_string()))
-            .filter(|(_, key)| paths.iter().find(|p| key.starts_with(p.as_str())).is_none())
-            .try_for_each(|(path, _)| object.remove(&path, true).map(|_| ()))?;
+        let value = self.value.resolve(ctx)?;
 
-        Ok(Value::Null)
+        match &self.static_patterns {
+            Some(patterns) => only_fields(value, patterns),
+            None => {
+                let patterns = path_patterns(self.patterns.resolve(ctx)?)?;
+                only_fields(value, &patterns)
+            }
+        }
     }
 
-    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
-        TypeDef {
-            fallible: true,
-            kind: value::Kind::null(),
-            ..Default::default()
-        }
+    fn type_def(&self, state: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let value_td = self.value.type_def(state);
+
+        let mut td = TypeDef::from(Kind::empty()).fallible();
+
+        if value_td.is_array() {
+            td = td.add_array(Collection::any())
+        };
+
+        if value_td.is_object() {
+            td = td.add_object(Collection::any())
+        };
+
+        td
     }
 }
 
@@ -69,14 +171,49 @@ impl Expression for OnlyFieldsFn {
 mod tests {
     use super::*;
 
-    test_type_def![static_type_def {
-        expr: |_| OnlyFieldsFn {
-            paths: vec![Path::from("foo")]
-        },
-        def: TypeDef {
-            fallible: true,
-            kind: value::Kind::null(),
-            ..Default::default()
-        },
-    }];
+    test_function![
+        only_fields => OnlyFields;
+
+        fields {
+            args: func_args![value: value!({"foo": {"bar": 1, "baz": 2}, "qux": 3}), patterns: value!(["foo.bar", "qux"])],
+            want: Ok(value!({"foo": {"bar": 1}, "qux": 3})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        subtree {
+            args: func_args![value: value!({"foo": {"bar": 1, "baz": 2}, "qux": 3}), patterns: value!(["foo"])],
+            want: Ok(value!({"foo": {"bar": 1, "baz": 2}})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        glob {
+            args: func_args![value: value!({"a": {"x_id": 1, "y": 2}, "b_id": 3, "c": {"d": 4}}), patterns: value!(["**_id"])],
+            want: Ok(value!({"a": {"x_id": 1}, "b_id": 3})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        array_elements {
+            args: func_args![value: value!({"foo": [{"id": 1, "tmp": true}, {"id": 2}]}), patterns: value!(["foo[*].id"])],
+            want: Ok(value!({"foo": [{"id": 1}, {"id": 2}]})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        no_match {
+            args: func_args![value: value!({"foo": 1}), patterns: value!(["bar"])],
+            want: Ok(value!({})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        no_match_array {
+            args: func_args![value: value!([1, 2]), patterns: value!(["foo"])],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::any()).fallible(),
+        }
+
+        invalid_pattern {
+            args: func_args![value: value!({"foo": 1}), patterns: value!([true])],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+    ];
 }
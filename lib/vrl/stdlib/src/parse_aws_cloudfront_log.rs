@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use vrl::prelude::*;
+
+fn parse_aws_cloudfront_log(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    parse_log(&String::from_utf8_lossy(&bytes)).map_err(Into::into)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseAwsCloudFrontLog;
+
+impl Function for ParseAwsCloudFrontLog {
+    fn identifier(&self) -> &'static str {
+        "parse_aws_cloudfront_log"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "valid",
+            source: r#"parse_aws_cloudfront_log!("2019-12-04\t21:02:31\tLAX1\t392\t192.0.2.100\tGET\td111111abcdef8.cloudfront.net\t/index.html\t200\t-\tMozilla/5.0%20(Windows%20NT%2010.0;%20Win64;%20x64)\t-\t-\tHit\tSOX4xwn4XV6Q4rgb7XiVGOHms_BGlTAC4KyHmureZmBNrjGdRLiNIQ==\td111111abcdef8.cloudfront.net\thttps\t23\t0.001\t-\tTLSv1.2\tECDHE-RSA-AES128-GCM-SHA256\tHit\tHTTP/2.0\t-\t-\t11040\t0.001\tHit\ttext/html\t78\t-\t-")"#,
+            result: Ok(
+                r#"{ "c_ip": "192.0.2.100", "c_port": 11040, "cs_bytes": 23, "cs_cookie": null, "cs_host": "d111111abcdef8.cloudfront.net", "cs_method": "GET", "cs_protocol": "https", "cs_protocol_version": "HTTP/2.0", "cs_referer": null, "cs_uri_query": null, "cs_uri_stem": "/index.html", "cs_user_agent": "Mozilla/5.0%20(Windows%20NT%2010.0;%20Win64;%20x64)", "edge_detailed_result_type": "Hit", "edge_location": "LAX1", "edge_request_id": "SOX4xwn4XV6Q4rgb7XiVGOHms_BGlTAC4KyHmureZmBNrjGdRLiNIQ==", "edge_response_result_type": "Hit", "edge_result_type": "Hit", "fle_encrypted_fields": null, "fle_status": null, "forwarded_for": null, "host_header": "d111111abcdef8.cloudfront.net", "sc_bytes": 392, "sc_content_len": 78, "sc_content_type": "text/html", "sc_range_end": null, "sc_range_start": null, "sc_status": 200, "ssl_cipher": "ECDHE-RSA-AES128-GCM-SHA256", "ssl_protocol": "TLSv1.2", "time_taken": 0.001, "time_to_first_byte": 0.001, "timestamp": t'2019-12-04T21:02:31Z' }"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseAwsCloudFrontLogFn::new(value)))
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseAwsCloudFrontLogFn {
+    value: Box<dyn Expression>,
+}
+
+impl ParseAwsCloudFrontLogFn {
+    fn new(value: Box<dyn Expression>) -> Self {
+        Self { value }
+    }
+}
+
+impl Expression for ParseAwsCloudFrontLogFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        parse_aws_cloudfront_log(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible(/* log parsing error */)
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        (Field::from("c_ip"), Kind::bytes() | Kind::null()),
+        (Field::from("c_port"), Kind::integer() | Kind::null()),
+        (Field::from("cs_bytes"), Kind::integer() | Kind::null()),
+        (Field::from("cs_cookie"), Kind::bytes() | Kind::null()),
+        (Field::from("cs_host"), Kind::bytes() | Kind::null()),
+        (Field::from("cs_method"), Kind::bytes() | Kind::null()),
+        (Field::from("cs_protocol"), Kind::bytes() | Kind::null()),
+        (
+            Field::from("cs_protocol_version"),
+            Kind::bytes() | Kind::null(),
+        ),
+        (Field::from("cs_referer"), Kind::bytes() | Kind::null()),
+        (Field::from("cs_uri_query"), Kind::bytes() | Kind::null()),
+        (Field::from("cs_uri_stem"), Kind::bytes() | Kind::null()),
+        (Field::from("cs_user_agent"), Kind::bytes() | Kind::null()),
+        (
+            Field::from("edge_detailed_result_type"),
+            Kind::bytes() | Kind::null(),
+        ),
+        (Field::from("edge_location"), Kind::bytes() | Kind::null()),
+        (Field::from("edge_request_id"), Kind::bytes() | Kind::null()),
+        (
+            Field::from("edge_response_result_type"),
+            Kind::bytes() | Kind::null(),
+        ),
+        (
+            Field::from("edge_result_type"),
+            Kind::bytes() | Kind::null(),
+        ),
+        (
+            Field::from("fle_encrypted_fields"),
+            Kind::integer() | Kind::null(),
+        ),
+        (Field::from("fle_status"), Kind::bytes() | Kind::null()),
+        (Field::from("forwarded_for"), Kind::bytes() | Kind::null()),
+        (Field::from("host_header"), Kind::bytes() | Kind::null()),
+        (Field::from("sc_bytes"), Kind::integer() | Kind::null()),
+        (
+            Field::from("sc_content_len"),
+            Kind::integer() | Kind::null(),
+        ),
+        (Field::from("sc_content_type"), Kind::bytes() | Kind::null()),
+        (Field::from("sc_range_end"), Kind::integer() | Kind::null()),
+        (
+            Field::from("sc_range_start"),
+            Kind::integer() | Kind::null(),
+        ),
+        (Field::from("sc_status"), Kind::integer() | Kind::null()),
+        (Field::from("ssl_cipher"), Kind::bytes() | Kind::null()),
+        (Field::from("ssl_protocol"), Kind::bytes() | Kind::null()),
+        (Field::from("time_taken"), Kind::float() | Kind::null()),
+        (
+            Field::from("time_to_first_byte"),
+            Kind::float() | Kind::null(),
+        ),
+        (Field::from("timestamp"), Kind::timestamp()),
+    ])
+}
+
+type ParseResult<T> = std::result::Result<T, String>;
+
+#[allow(clippy::unnecessary_wraps)] // match other parse methods
+fn identity<'a>(_key: &'a str, value: &'a str) -> ParseResult<&'a str> {
+    Ok(value)
+}
+
+fn parse_i64(key: &str, value: &str) -> ParseResult<i64> {
+    value
+        .parse()
+        .map_err(|_| format!("failed to parse value as i64 (key: `{}`): `{}`", key, value))
+}
+
+fn parse_f64(key: &str, value: &str) -> ParseResult<f64> {
+    value
+        .parse()
+        .map_err(|_| format!("failed to parse value as f64 (key: `{}`): `{}`", key, value))
+}
+
+/// The fields of a standard log line after the date and time, in the order CloudFront writes
+/// them. Lines written before fields were added to the format stop early, so the trailing fields
+/// may be missing.
+const FIELDS: &[&str] = &[
+    "edge_location",
+    "sc_bytes",
+    "c_ip",
+    "cs_method",
+    "cs_host",
+    "cs_uri_stem",
+    "sc_status",
+    "cs_referer",
+    "cs_user_agent",
+    "cs_uri_query",
+    "cs_cookie",
+    "edge_result_type",
+    "edge_request_id",
+    "host_header",
+    "cs_protocol",
+    "cs_bytes",
+    "time_taken",
+    "forwarded_for",
+    "ssl_protocol",
+    "ssl_cipher",
+    "edge_response_result_type",
+    "cs_protocol_version",
+    "fle_status",
+    "fle_encrypted_fields",
+    "c_port",
+    "time_to_first_byte",
+    "edge_detailed_result_type",
+    "sc_content_type",
+    "sc_content_len",
+    "sc_range_start",
+    "sc_range_end",
+];
+
+macro_rules! create_match {
+    ($log:expr, $key:expr, $value:expr, $($name:expr => $transform:expr),+) => {
+        match $key {
+            $($name => {
+                let value = match $value {
+                    "-" => Value::Null,
+                    value => $transform($name, value)?.into(),
+                };
+                $log.insert($name.into(), value);
+            })+
+            key => unreachable!("unknown key: `{}`", key)
+        };
+    };
+}
+
+fn parse_timestamp(date: &str, time: &str) -> ParseResult<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M:%S")
+        .map(|timestamp| DateTime::from_utc(timestamp, Utc))
+        .map_err(|error| format!("failed to parse timestamp `{} {}`: {}", date, time, error))
+}
+
+fn parse_log(input: &str) -> ParseResult<Value> {
+    if input.starts_with('#') {
+        return Err("line is a header, not a log entry".to_owned());
+    }
+
+    let mut log = BTreeMap::new();
+    let mut input = input.trim_end_matches(&['\r', '\n'][..]).split('\t');
+
+    let date = input.next().ok_or("no item for key: `date`")?;
+    let time = input.next().ok_or("no item for key: `time`")?;
+    log.insert("timestamp".into(), parse_timestamp(date, time)?.into());
+
+    let mut fields = FIELDS.iter();
+    loop {
+        return match (fields.next(), input.next()) {
+            (Some(&key), Some(value)) => {
+                create_match!(
+                    log, key, value,
+                    "c_ip" => identity,
+                    "c_port" => parse_i64,
+                    "cs_bytes" => parse_i64,
+                    "cs_cookie" => identity,
+                    "cs_host" => identity,
+                    "cs_method" => identity,
+                    "cs_protocol" => identity,
+                    "cs_protocol_version" => identity,
+                    "cs_referer" => identity,
+                    "cs_uri_query" => identity,
+                    "cs_uri_stem" => identity,
+                    "cs_user_agent" => identity,
+                    "edge_detailed_result_type" => identity,
+                    "edge_location" => identity,
+                    "edge_request_id" => identity,
+                    "edge_response_result_type" => identity,
+                    "edge_result_type" => identity,
+                    "fle_encrypted_fields" => parse_i64,
+                    "fle_status" => identity,
+                    "forwarded_for" => identity,
+                    "host_header" => identity,
+                    "sc_bytes" => parse_i64,
+                    "sc_content_len" => parse_i64,
+                    "sc_content_type" => identity,
+                    "sc_range_end" => parse_i64,
+                    "sc_range_start" => parse_i64,
+                    "sc_status" => parse_i64,
+                    "ssl_cipher" => identity,
+                    "ssl_protocol" => identity,
+                    "time_taken" => parse_f64,
+                    "time_to_first_byte" => parse_f64
+                );
+
+                continue;
+            }
+            (None, Some(value)) => Err(format!("no key for value: `{}`", value)),
+            (_, None) => Ok(log.into()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_aws_cloudfront_log() {
+        // Examples from https://docs.aws.amazon.com/AmazonCloudFront/latest/DeveloperGuide/AccessLogs.html
+        let logs = vec![
+            "2019-12-04\t21:02:31\tLAX1\t392\t192.0.2.100\tGET\td111111abcdef8.cloudfront.net\t/index.html\t200\t-\tMozilla/5.0%20(Windows%20NT%2010.0;%20Win64;%20x64)%20AppleWebKit/537.36%20(KHTML,%20like%20Gecko)%20Chrome/78.0.3904.108%20Safari/537.36\t-\t-\tHit\tSOX4xwn4XV6Q4rgb7XiVGOHms_BGlTAC4KyHmureZmBNrjGdRLiNIQ==\td111111abcdef8.cloudfront.net\thttps\t23\t0.001\t-\tTLSv1.2\tECDHE-RSA-AES128-GCM-SHA256\tHit\tHTTP/2.0\t-\t-\t11040\t0.001\tHit\ttext/html\t78\t-\t-",
+            "2019-12-04\t21:02:31\tLAX1\t392\t192.0.2.100\tGET\td111111abcdef8.cloudfront.net\t/index.html\t200\t-\tMozilla/5.0%20(Windows%20NT%2010.0;%20Win64;%20x64)%20AppleWebKit/537.36%20(KHTML,%20like%20Gecko)%20Chrome/78.0.3904.108%20Safari/537.36\t-\t-\tHit\tk6WGMNkEzR5BEM_SaF47gjtX9zBDO2m349OY2an0QPEaUum1ZOLrow==\td111111abcdef8.cloudfront.net\thttps\t23\t0.000\t-\tTLSv1.2\tECDHE-RSA-AES128-GCM-SHA256\tHit\tHTTP/2.0\t-\t-\t11040\t0.000\tHit\ttext/html\t78\t-\t-",
+            "2019-12-13\t22:36:27\tSEA19-C1\t900\t192.0.2.200\tGET\td111111abcdef8.cloudfront.net\t/favicon.ico\t502\thttp://www.example.com/\tMozilla/5.0%20(Windows%20NT%2010.0;%20Win64;%20x64)%20AppleWebKit/537.36%20(KHTML,%20like%20Gecko)%20Chrome/78.0.3904.108%20Safari/537.36\t-\t-\tError\t1pkpNfBQ39sYMnjjUQjmH2w1wdJnbHYTbag21o_3OfcQgPzdL2RSSQ==\twww.example.com\thttp\t675\t0.102\t-\t-\t-\tError\tHTTP/1.1\t-\t-\t25260\t0.102\tOriginDnsError\ttext/html\t507\t-\t-",
+            // Before the last fields were added to the format.
+            "2014-05-23\t01:13:11\tFRA2\t182\t192.0.2.10\tGET\td111111abcdef8.cloudfront.net\t/view/my/file.html\t200\twww.displaymyfiles.com\tMozilla/4.0%20(compatible;%20MSIE%205.0b1;%20Mac_PowerPC)\t-\tzip=98101\tRefreshHit\tMRVMF7KydIvxMWfJIglgwHQwZsbG2IhRJ07sn9AkKUFSHS9EXAMPLE==\td111111abcdef8.cloudfront.net\thttp\t-\t0.001",
+        ];
+
+        for log in logs {
+            assert!(parse_log(log).is_ok());
+        }
+
+        assert!(parse_log("#Version: 1.0").is_err());
+        assert!(parse_log("2019-12-04 21:02:31 LAX1").is_err());
+    }
+
+    test_function![
+        parse_aws_cloudfront_log => ParseAwsCloudFrontLog;
+
+        default {
+            args: func_args![value: "2019-12-13\t22:36:27\tSEA19-C1\t900\t192.0.2.200\tGET\td111111abcdef8.cloudfront.net\t/favicon.ico\t502\thttp://www.example.com/\tcurl/7.68.0\t-\t-\tError\t1pkpNfBQ39sYMnjjUQjmH2w1wdJnbHYTbag21o_3OfcQgPzdL2RSSQ==\twww.example.com\thttp\t675\t0.102\t-\t-\t-\tError\tHTTP/1.1\t-\t-\t25260\t0.102\tOriginDnsError\ttext/html\t507\t-\t-"],
+            want: Ok(value!({
+                "c_ip": "192.0.2.200",
+                "c_port": 25260,
+                "cs_bytes": 675,
+                "cs_cookie": null,
+                "cs_host": "d111111abcdef8.cloudfront.net",
+                "cs_method": "GET",
+                "cs_protocol": "http",
+                "cs_protocol_version": "HTTP/1.1",
+                "cs_referer": "http://www.example.com/",
+                "cs_uri_query": null,
+                "cs_uri_stem": "/favicon.ico",
+                "cs_user_agent": "curl/7.68.0",
+                "edge_detailed_result_type": "OriginDnsError",
+                "edge_location": "SEA19-C1",
+                "edge_request_id": "1pkpNfBQ39sYMnjjUQjmH2w1wdJnbHYTbag21o_3OfcQgPzdL2RSSQ==",
+                "edge_response_result_type": "Error",
+                "edge_result_type": "Error",
+                "fle_encrypted_fields": null,
+                "fle_status": null,
+                "forwarded_for": null,
+                "host_header": "www.example.com",
+                "sc_bytes": 900,
+                "sc_content_len": 507,
+                "sc_content_type": "text/html",
+                "sc_range_end": null,
+                "sc_range_start": null,
+                "sc_status": 502,
+                "ssl_cipher": null,
+                "ssl_protocol": null,
+                "time_taken": 0.102,
+                "time_to_first_byte": 0.102,
+                "timestamp": (DateTime::parse_from_rfc3339("2019-12-13T22:36:27Z").unwrap().with_timezone(&Utc)),
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_number {
+            args: func_args![value: "2019-12-13\t22:36:27\tSEA19-C1\tlots"],
+            want: Err("failed to parse value as i64 (key: `sc_bytes`): `lots`"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        too_many_values {
+            args: func_args![value: "2019-12-13\t22:36:27\tSEA19-C1\t900\t192.0.2.200\tGET\td111111abcdef8.cloudfront.net\t/favicon.ico\t502\t-\tcurl/7.68.0\t-\t-\tError\t1pkpNfBQ39sYMnjjUQjmH2w1wdJnbHYTbag21o_3OfcQgPzdL2RSSQ==\twww.example.com\thttp\t675\t0.102\t-\t-\t-\tError\tHTTP/1.1\t-\t-\t25260\t0.102\tOriginDnsError\ttext/html\t507\t-\t-\textra"],
+            want: Err("no key for value: `extra`"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,438 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Endianness {
+    Big,
+    Little,
+}
+
+impl Endianness {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            b"big" => Some(Endianness::Big),
+            b"little" => Some(Endianness::Little),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FieldType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    Bytes,
+    String,
+}
+
+impl FieldType {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        use FieldType::*;
+
+        let r#type = match bytes {
+            b"u8" => U8,
+            b"u16" => U16,
+            b"u32" => U32,
+            b"u64" => U64,
+            b"i8" => I8,
+            b"i16" => I16,
+            b"i32" => I32,
+            b"i64" => I64,
+            b"f32" => F32,
+            b"f64" => F64,
+            b"bytes" => Bytes,
+            b"string" => String,
+            _ => return None,
+        };
+
+        Some(r#type)
+    }
+
+    /// The width in bytes of fixed-width types.
+    fn width(self) -> Option<usize> {
+        use FieldType::*;
+
+        match self {
+            U8 | I8 => Some(1),
+            U16 | I16 => Some(2),
+            U32 | I32 | F32 => Some(4),
+            U64 | I64 | F64 => Some(8),
+            Bytes | String => None,
+        }
+    }
+
+    fn kind(self) -> Kind {
+        use FieldType::*;
+
+        match self {
+            U8 | U16 | U32 | U64 | I8 | I16 | I32 | I64 => Kind::integer(),
+            F32 | F64 => Kind::float(),
+            Bytes | String => Kind::bytes(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct LayoutField {
+    r#type: FieldType,
+    offset: usize,
+    length: usize,
+    endianness: Endianness,
+}
+
+impl LayoutField {
+    fn from_value(value: &Value, default: Endianness) -> std::result::Result<Self, &'static str> {
+        let object = value
+            .as_object()
+            .ok_or("each layout field must be an object")?;
+
+        let r#type = match object.get("type") {
+            Some(Value::Bytes(bytes)) => FieldType::from_bytes(bytes),
+            Some(_) => None,
+            None => return Err("layout fields must have a type"),
+        }
+        .ok_or("layout field type must be one of u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, bytes or string")?;
+
+        let offset = match object.get("offset") {
+            Some(Value::Integer(offset)) if *offset >= 0 => *offset as usize,
+            Some(_) => return Err("layout field offset must be a non-negative integer"),
+            None => return Err("layout fields must have an offset"),
+        };
+
+        let length = match (r#type.width(), object.get("length")) {
+            (Some(width), None) => width,
+            (Some(_), Some(_)) => {
+                return Err("layout field length is only supported for bytes and string types")
+            }
+            (None, Some(Value::Integer(length))) if *length > 0 => *length as usize,
+            (None, Some(_)) => return Err("layout field length must be a positive integer"),
+            (None, None) => return Err("bytes and string layout fields must have a length"),
+        };
+
+        let endianness = match object.get("endianness") {
+            Some(Value::Bytes(bytes)) => Endianness::from_bytes(bytes)
+                .ok_or("layout field endianness must be either big or little")?,
+            Some(_) => return Err("layout field endianness must be either big or little"),
+            None => default,
+        };
+
+        Ok(Self {
+            r#type,
+            offset,
+            length,
+            endianness,
+        })
+    }
+
+    fn decode(&self, name: &str, payload: &[u8]) -> Resolved {
+        let end = self.offset.saturating_add(self.length);
+        if end > payload.len() {
+            return Err(format!(
+                "field {:?} ends at byte {}, but the payload is only {} bytes long",
+                name,
+                end,
+                payload.len()
+            )
+            .into());
+        }
+
+        let data = &payload[self.offset..end];
+
+        let bytes = match self.r#type {
+            FieldType::Bytes => return Ok(Value::from(Bytes::copy_from_slice(data))),
+            FieldType::String => {
+                // Fixed-width strings are commonly padded with trailing NUL
+                // bytes.
+                let length = data.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+                return Ok(Value::from(Bytes::copy_from_slice(&data[..length])));
+            }
+            _ => data,
+        };
+
+        let raw = match self.endianness {
+            Endianness::Big => bytes.iter().fold(0u64, |acc, b| acc << 8 | u64::from(*b)),
+            Endianness::Little => bytes
+                .iter()
+                .rev()
+                .fold(0u64, |acc, b| acc << 8 | u64::from(*b)),
+        };
+
+        let value = match self.r#type {
+            FieldType::U8 | FieldType::U16 | FieldType::U32 | FieldType::U64 => {
+                let integer = i64::try_from(raw).map_err(|_| {
+                    format!(
+                        "field {:?} value {} does not fit in a 64-bit signed integer",
+                        name, raw
+                    )
+                })?;
+
+                Value::from(integer)
+            }
+            FieldType::I8 | FieldType::I16 | FieldType::I32 | FieldType::I64 => {
+                // Sign-extend the value from its own width to 64 bits.
+                let shift = 64 - 8 * self.length as u32;
+                Value::from(((raw << shift) as i64) >> shift)
+            }
+            FieldType::F32 => float(name, f64::from(f32::from_bits(raw as u32)))?,
+            FieldType::F64 => float(name, f64::from_bits(raw))?,
+            FieldType::Bytes | FieldType::String => unreachable!("handled above"),
+        };
+
+        Ok(value)
+    }
+}
+
+fn float(name: &str, value: f64) -> Resolved {
+    NotNan::new(value)
+        .map(Value::Float)
+        .map_err(|_| format!("field {:?} is not a number", name).into())
+}
+
+fn parse_binary(value: Value, layout: &BTreeMap<String, LayoutField>) -> Resolved {
+    let bytes = value.try_bytes()?;
+
+    layout
+        .iter()
+        .map(|(name, field)| Ok((name.clone(), field.decode(name, &bytes)?)))
+        .collect::<std::result::Result<BTreeMap<_, _>, ExpressionError>>()
+        .map(Value::Object)
+}
+
+fn endiannesses() -> Vec<Value> {
+    vec![value!("big"), value!("little")]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseBinary;
+
+impl Function for ParseBinary {
+    fn identifier(&self) -> &'static str {
+        "parse_binary"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "layout",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "endianness",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "big-endian record",
+                source: r#"parse_binary!(decode_base64!("AAEAAAAqZm9vAA=="), { "version": { "type": "u16", "offset": 0 }, "count": { "type": "u32", "offset": 2 }, "name": { "type": "string", "offset": 6, "length": 4 } })"#,
+                result: Ok(r#"{ "count": 42, "name": "foo", "version": 1 }"#),
+            },
+            Example {
+                title: "little-endian record",
+                source: r#"parse_binary!(decode_base64!("/v8="), { "delta": { "type": "i16", "offset": 0 } }, endianness: "little")"#,
+                result: Ok(r#"{ "delta": -2 }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let endianness = arguments
+            .optional_enum("endianness", &endiannesses())?
+            .map(|value| {
+                Endianness::from_bytes(&value.try_bytes().expect("endianness not bytes"))
+                    .expect("validated enum")
+            })
+            .unwrap_or(Endianness::Big);
+
+        let layout = arguments
+            .required_object("layout")?
+            .into_iter()
+            .map(|(name, expr)| {
+                let value = expr.as_value().ok_or(
+                    vrl::function::Error::ExpectedStaticExpression {
+                        keyword: "layout",
+                        expr,
+                    },
+                )?;
+
+                let field = LayoutField::from_value(&value, endianness).map_err(|error| {
+                    vrl::function::Error::InvalidArgument {
+                        keyword: "layout",
+                        value,
+                        error,
+                    }
+                })?;
+
+                Ok((name, field))
+            })
+            .collect::<std::result::Result<BTreeMap<_, _>, vrl::function::Error>>()?;
+
+        Ok(Box::new(ParseBinaryFn { value, layout }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseBinaryFn {
+    value: Box<dyn Expression>,
+    layout: BTreeMap<String, LayoutField>,
+}
+
+impl Expression for ParseBinaryFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_binary(value, &self.layout)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let fields = self
+            .layout
+            .iter()
+            .map(|(name, field)| (Field::from(name.as_str()), field.r#type.kind()))
+            .collect::<BTreeMap<_, _>>();
+
+        TypeDef::object(fields).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_binary => ParseBinary;
+
+        unsigned_big_endian {
+            args: func_args![
+                value: Bytes::from_static(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x2a]),
+                layout: value!({
+                    "version": { "type": "u16", "offset": 0 },
+                    "count": { "type": "u32", "offset": 2 },
+                }),
+            ],
+            want: Ok(value!({ "version": 1, "count": 42 })),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("count".into(), Kind::integer()),
+                ("version".into(), Kind::integer()),
+            ])).fallible(),
+        }
+
+        signed_little_endian {
+            args: func_args![
+                value: Bytes::from_static(&[0xfe, 0xff, 0x80]),
+                layout: value!({
+                    "delta": { "type": "i16", "offset": 0 },
+                    "flag": { "type": "i8", "offset": 2 },
+                }),
+                endianness: "little",
+            ],
+            want: Ok(value!({ "delta": -2, "flag": -128 })),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("delta".into(), Kind::integer()),
+                ("flag".into(), Kind::integer()),
+            ])).fallible(),
+        }
+
+        per_field_endianness {
+            args: func_args![
+                value: Bytes::from_static(&[0x01, 0x02, 0x01, 0x02]),
+                layout: value!({
+                    "big": { "type": "u16", "offset": 0 },
+                    "little": { "type": "u16", "offset": 2, "endianness": "little" },
+                }),
+            ],
+            want: Ok(value!({ "big": 258, "little": 513 })),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("big".into(), Kind::integer()),
+                ("little".into(), Kind::integer()),
+            ])).fallible(),
+        }
+
+        floats {
+            args: func_args![
+                value: Bytes::from_static(&[0x3f, 0xc0, 0x00, 0x00, 0x40, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+                layout: value!({
+                    "single": { "type": "f32", "offset": 0 },
+                    "double": { "type": "f64", "offset": 4 },
+                }),
+            ],
+            want: Ok(value!({ "single": 1.5, "double": 2.5 })),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("double".into(), Kind::float()),
+                ("single".into(), Kind::float()),
+            ])).fallible(),
+        }
+
+        strings {
+            args: func_args![
+                value: Bytes::from_static(b"foo\0\0bar"),
+                layout: value!({
+                    "padded": { "type": "string", "offset": 0, "length": 5 },
+                    "raw": { "type": "bytes", "offset": 3, "length": 5 },
+                }),
+            ],
+            want: Ok(value!({ "padded": "foo", "raw": "\0\0bar" })),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("padded".into(), Kind::bytes()),
+                ("raw".into(), Kind::bytes()),
+            ])).fallible(),
+        }
+
+        unsigned_overflow {
+            args: func_args![
+                value: Bytes::from_static(&[0xff; 8]),
+                layout: value!({ "id": { "type": "u64", "offset": 0 } }),
+            ],
+            want: Err(r#"field "id" value 18446744073709551615 does not fit in a 64-bit signed integer"#),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("id".into(), Kind::integer()),
+            ])).fallible(),
+        }
+
+        payload_too_short {
+            args: func_args![
+                value: Bytes::from_static(&[0x00, 0x01]),
+                layout: value!({ "count": { "type": "u32", "offset": 0 } }),
+            ],
+            want: Err(r#"field "count" ends at byte 4, but the payload is only 2 bytes long"#),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("count".into(), Kind::integer()),
+            ])).fallible(),
+        }
+
+        invalid_layout {
+            args: func_args![
+                value: Bytes::from_static(&[0x00]),
+                layout: value!({ "name": { "type": "string", "offset": 0 } }),
+            ],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(BTreeMap::new()).fallible(),
+        }
+    ];
+}
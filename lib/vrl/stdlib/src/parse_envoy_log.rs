@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::log_util;
+
+fn parse_envoy_log(bytes: Value, timestamp_format: Option<Value>, ctx: &Context) -> Resolved {
+    let message = bytes.try_bytes_utf8_lossy()?;
+    let timestamp_format = match timestamp_format {
+        None => "%+".to_owned(),
+        Some(timestamp_format) => timestamp_format.try_bytes_utf8_lossy()?.to_string(),
+    };
+    let captures = log_util::REGEX_ENVOY_ACCESS_LOG
+        .captures(&message)
+        .ok_or("failed parsing Envoy access log line")?;
+    log_util::log_fields(
+        &log_util::REGEX_ENVOY_ACCESS_LOG,
+        &captures,
+        &timestamp_format,
+        ctx.timezone(),
+    )
+    .map_err(Into::into)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseEnvoyLog;
+
+impl Function for ParseEnvoyLog {
+    fn identifier(&self) -> &'static str {
+        "parse_envoy_log"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "timestamp_format",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timestamp_format = arguments.optional("timestamp_format");
+
+        Ok(Box::new(ParseEnvoyLogFn {
+            value,
+            timestamp_format,
+        }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse Envoy access log",
+            source: r#"encode_json(parse_envoy_log!(s'[2016-04-15T20:17:00.310Z] "POST /api/v1/locations HTTP/2" 204 - 154 0 226 100 "10.0.35.28" "nsq2http" "cc21d9b0-cf5c-432b-8c7e-98aeb7988cd2" "locations" "tcp://10.0.2.1:80"'))"#,
+            result: Ok(
+                r#"s'{"agent":"nsq2http","authority":"locations","bytes_received":154,"bytes_sent":0,"duration":226,"forwarded_for":"10.0.35.28","method":"POST","path":"/api/v1/locations","protocol":"HTTP/2","request":"POST /api/v1/locations HTTP/2","request_id":"cc21d9b0-cf5c-432b-8c7e-98aeb7988cd2","status":204,"timestamp":"2016-04-15T20:17:00.310Z","upstream_host":"tcp://10.0.2.1:80","upstream_service_time":100}'"#,
+            ),
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseEnvoyLogFn {
+    value: Box<dyn Expression>,
+    timestamp_format: Option<Box<dyn Expression>>,
+}
+
+impl Expression for ParseEnvoyLogFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        let timestamp_format = self
+            .timestamp_format
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        parse_envoy_log(bytes, timestamp_format, ctx)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("timestamp".into(), Kind::timestamp()),
+        ("request".into(), Kind::bytes().or_null()),
+        ("method".into(), Kind::bytes().or_null()),
+        ("path".into(), Kind::bytes().or_null()),
+        ("protocol".into(), Kind::bytes().or_null()),
+        ("status".into(), Kind::integer()),
+        ("response_flags".into(), Kind::bytes().or_null()),
+        ("bytes_received".into(), Kind::integer()),
+        ("bytes_sent".into(), Kind::integer()),
+        ("duration".into(), Kind::integer().or_null()),
+        ("upstream_service_time".into(), Kind::integer().or_null()),
+        ("forwarded_for".into(), Kind::bytes().or_null()),
+        ("agent".into(), Kind::bytes().or_null()),
+        ("request_id".into(), Kind::bytes().or_null()),
+        ("authority".into(), Kind::bytes().or_null()),
+        ("upstream_host".into(), Kind::bytes().or_null()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::prelude::*;
+    use vector_common::btreemap;
+
+    use super::*;
+
+    test_function![
+        parse_envoy_log => ParseEnvoyLog;
+
+        http_line_valid {
+            args: func_args![value: r#"[2016-04-15T20:17:00.310Z] "POST /api/v1/locations HTTP/2" 204 - 154 0 226 100 "10.0.35.28" "nsq2http" "cc21d9b0-cf5c-432b-8c7e-98aeb7988cd2" "locations" "tcp://10.0.2.1:80""#],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2016-04-15T20:17:00.310Z").unwrap().into()),
+                "request" => "POST /api/v1/locations HTTP/2",
+                "method" => "POST",
+                "path" => "/api/v1/locations",
+                "protocol" => "HTTP/2",
+                "status" => 204,
+                "bytes_received" => 154,
+                "bytes_sent" => 0,
+                "duration" => 226,
+                "upstream_service_time" => 100,
+                "forwarded_for" => "10.0.35.28",
+                "agent" => "nsq2http",
+                "request_id" => "cc21d9b0-cf5c-432b-8c7e-98aeb7988cd2",
+                "authority" => "locations",
+                "upstream_host" => "tcp://10.0.2.1:80",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        tcp_line_valid {
+            args: func_args![value: r#"[2016-04-15T20:17:00.310Z] "- - -" 0 UF,URX 0 0 1 - "-" "-" "-" "-" "10.0.2.1:80""#],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2016-04-15T20:17:00.310Z").unwrap().into()),
+                "status" => 0,
+                "response_flags" => "UF,URX",
+                "bytes_received" => 0,
+                "bytes_sent" => 0,
+                "duration" => 1,
+                "upstream_host" => "10.0.2.1:80",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        log_line_invalid {
+            args: func_args![value: r#"[2016-04-15T20:17:00.310Z] "POST /api/v1/locations HTTP/2" 204"#],
+            want: Err("failed parsing Envoy access log line"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
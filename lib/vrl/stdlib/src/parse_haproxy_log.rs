@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::log_util;
+
+fn parse_haproxy_log(bytes: Value, timestamp_format: Option<Value>, ctx: &Context) -> Resolved {
+    let message = bytes.try_bytes_utf8_lossy()?;
+    let timestamp_format = match timestamp_format {
+        None => "%d/%b/%Y:%H:%M:%S%.f".to_owned(),
+        Some(timestamp_format) => timestamp_format.try_bytes_utf8_lossy()?.to_string(),
+    };
+    let captures = log_util::REGEX_HAPROXY_HTTP_LOG
+        .captures(&message)
+        .ok_or("failed parsing HAProxy log line")?;
+    log_util::log_fields(
+        &log_util::REGEX_HAPROXY_HTTP_LOG,
+        &captures,
+        &timestamp_format,
+        ctx.timezone(),
+    )
+    .map_err(Into::into)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseHaproxyLog;
+
+impl Function for ParseHaproxyLog {
+    fn identifier(&self) -> &'static str {
+        "parse_haproxy_log"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "timestamp_format",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timestamp_format = arguments.optional("timestamp_format");
+
+        Ok(Box::new(ParseHaproxyLogFn {
+            value,
+            timestamp_format,
+        }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse HAProxy HTTP log",
+            source: r#"encode_json(parse_haproxy_log!(s'10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 {1wt.eu} {} "GET /index.html HTTP/1.1"'))"#,
+            result: Ok(
+                r#"s'{"active_connections":1,"backend":"static","backend_connections":1,"backend_queue":0,"client":"10.0.1.2","client_port":33317,"connect_time":30,"frontend":"http-in","frontend_connections":1,"method":"GET","path":"/index.html","protocol":"HTTP/1.1","queue_time":0,"request":"GET /index.html HTTP/1.1","request_headers":"1wt.eu","request_time":10,"response_time":69,"retries":0,"server":"srv1","server_connections":1,"server_queue":0,"size":2750,"status":200,"termination_state":"----","timestamp":"2009-02-06T12:14:14.655Z","total_time":109}'"#,
+            ),
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseHaproxyLogFn {
+    value: Box<dyn Expression>,
+    timestamp_format: Option<Box<dyn Expression>>,
+}
+
+impl Expression for ParseHaproxyLogFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        let timestamp_format = self
+            .timestamp_format
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        parse_haproxy_log(bytes, timestamp_format, ctx)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("pid".into(), Kind::integer().or_null()),
+        ("client".into(), Kind::bytes()),
+        ("client_port".into(), Kind::integer()),
+        ("timestamp".into(), Kind::timestamp()),
+        ("frontend".into(), Kind::bytes()),
+        ("backend".into(), Kind::bytes()),
+        ("server".into(), Kind::bytes()),
+        ("request_time".into(), Kind::integer()),
+        ("queue_time".into(), Kind::integer()),
+        ("connect_time".into(), Kind::integer()),
+        ("response_time".into(), Kind::integer()),
+        ("total_time".into(), Kind::integer()),
+        ("status".into(), Kind::integer()),
+        ("size".into(), Kind::integer()),
+        ("request_cookie".into(), Kind::bytes().or_null()),
+        ("response_cookie".into(), Kind::bytes().or_null()),
+        ("termination_state".into(), Kind::bytes()),
+        ("active_connections".into(), Kind::integer()),
+        ("frontend_connections".into(), Kind::integer()),
+        ("backend_connections".into(), Kind::integer()),
+        ("server_connections".into(), Kind::integer()),
+        ("retries".into(), Kind::integer()),
+        ("server_queue".into(), Kind::integer()),
+        ("backend_queue".into(), Kind::integer()),
+        ("request_headers".into(), Kind::bytes().or_null()),
+        ("response_headers".into(), Kind::bytes().or_null()),
+        ("request".into(), Kind::bytes()),
+        ("method".into(), Kind::bytes().or_null()),
+        ("path".into(), Kind::bytes().or_null()),
+        ("protocol".into(), Kind::bytes().or_null()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::prelude::*;
+    use vector_common::btreemap;
+
+    use super::*;
+
+    test_function![
+        parse_haproxy_log => ParseHaproxyLog;
+
+        log_line_valid {
+            args: func_args![value: r#"10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 {1wt.eu} {} "GET /index.html HTTP/1.1""#],
+            want: Ok(btreemap! {
+                "client" => "10.0.1.2",
+                "client_port" => 33317,
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2009-02-06T12:14:14.655Z").unwrap().into()),
+                "frontend" => "http-in",
+                "backend" => "static",
+                "server" => "srv1",
+                "request_time" => 10,
+                "queue_time" => 0,
+                "connect_time" => 30,
+                "response_time" => 69,
+                "total_time" => 109,
+                "status" => 200,
+                "size" => 2750,
+                "termination_state" => "----",
+                "active_connections" => 1,
+                "frontend_connections" => 1,
+                "backend_connections" => 1,
+                "server_connections" => 1,
+                "retries" => 0,
+                "server_queue" => 0,
+                "backend_queue" => 0,
+                "request_headers" => "1wt.eu",
+                "request" => "GET /index.html HTTP/1.1",
+                "method" => "GET",
+                "path" => "/index.html",
+                "protocol" => "HTTP/1.1",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        log_line_with_syslog_header {
+            args: func_args![value: r#"Feb  6 12:14:14 localhost haproxy[14389]: 10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in~ static/srv1 10/0/30/69/109 304 168 session=abc - --NI 1/1/1/1/0 0/0 "GET /index.html HTTP/1.1""#],
+            want: Ok(btreemap! {
+                "pid" => 14389,
+                "client" => "10.0.1.2",
+                "client_port" => 33317,
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2009-02-06T12:14:14.655Z").unwrap().into()),
+                "frontend" => "http-in~",
+                "backend" => "static",
+                "server" => "srv1",
+                "request_time" => 10,
+                "queue_time" => 0,
+                "connect_time" => 30,
+                "response_time" => 69,
+                "total_time" => 109,
+                "status" => 304,
+                "size" => 168,
+                "request_cookie" => "session=abc",
+                "termination_state" => "--NI",
+                "active_connections" => 1,
+                "frontend_connections" => 1,
+                "backend_connections" => 1,
+                "server_connections" => 1,
+                "retries" => 0,
+                "server_queue" => 0,
+                "backend_queue" => 0,
+                "request" => "GET /index.html HTTP/1.1",
+                "method" => "GET",
+                "path" => "/index.html",
+                "protocol" => "HTTP/1.1",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        log_line_aborted {
+            args: func_args![value: r#"10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/<NOSRV> -1/-1/-1/-1/+3 -1 +0 - - CR-- 1/1/0/0/+1 0/0 "<BADREQ>""#],
+            want: Ok(btreemap! {
+                "client" => "10.0.1.2",
+                "client_port" => 33317,
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2009-02-06T12:14:14.655Z").unwrap().into()),
+                "frontend" => "http-in",
+                "backend" => "static",
+                "server" => "<NOSRV>",
+                "request_time" => -1,
+                "queue_time" => -1,
+                "connect_time" => -1,
+                "response_time" => -1,
+                "total_time" => 3,
+                "status" => -1,
+                "size" => 0,
+                "termination_state" => "CR--",
+                "active_connections" => 1,
+                "frontend_connections" => 1,
+                "backend_connections" => 0,
+                "server_connections" => 0,
+                "retries" => 1,
+                "server_queue" => 0,
+                "backend_queue" => 0,
+                "request" => "<BADREQ>",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        log_line_invalid {
+            args: func_args![value: r#"10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 200 2750"#],
+            want: Err("failed parsing HAProxy log line"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        log_line_invalid_timestamp {
+            args: func_args![
+                value: r#"10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 "GET /index.html HTTP/1.1""#,
+                timestamp_format: "%d/%b/%Y:%T %z"
+            ],
+            want: Err("failed parsing timestamp 06/Feb/2009:12:14:14.655 using format %d/%b/%Y:%T %z: input contains invalid characters"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
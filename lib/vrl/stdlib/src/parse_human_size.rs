@@ -0,0 +1,314 @@
+use std::{collections::HashMap, str::FromStr};
+
+use ::value::Value;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Dimension {
+    Count,
+    Bytes,
+    Duration,
+}
+
+impl Dimension {
+    fn as_str(self) -> &'static str {
+        match self {
+            Dimension::Count => "count",
+            Dimension::Bytes => "bytes",
+            Dimension::Duration => "duration",
+        }
+    }
+}
+
+fn parse_human_size(value: Value, unit: Option<Value>) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let value = String::from_utf8_lossy(&bytes);
+    let captures = RE
+        .captures(&value)
+        .ok_or(format!("unable to parse size: '{}'", value))?;
+    let number = Decimal::from_str(&captures["value"])
+        .map_err(|error| format!("unable to parse number: {}", error))?;
+    let (dimension, factor) = UNITS
+        .get(&captures["unit"])
+        .ok_or(format!("unknown size unit: '{}'", &captures["unit"]))?;
+    let number = number
+        .checked_mul(*factor)
+        .ok_or(format!("size is out of range: '{}'", value))?;
+
+    let number = match unit {
+        None => number,
+        Some(unit) => {
+            let bytes = unit.try_bytes()?;
+            let unit = String::from_utf8_lossy(&bytes);
+            let (target, conversion_factor) = UNITS
+                .get(unit.as_ref())
+                .ok_or(format!("unknown unit format: '{}'", unit))?;
+
+            if target != dimension {
+                return Err(format!(
+                    "unable to convert {} into {}: '{}'",
+                    dimension.as_str(),
+                    target.as_str(),
+                    unit
+                )
+                .into());
+            }
+
+            number
+                .checked_div(*conversion_factor)
+                .ok_or(format!("size is out of range: '{}'", value))?
+        }
+    };
+
+    let number = number
+        .to_f64()
+        .ok_or(format!("unable to format size: '{}'", number))?;
+    Ok(Value::from_f64_or_zero(number))
+}
+
+static RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)                         # x: ignore whitespace + comments
+            \A
+            (?P<value>[0-9]*\.?[0-9]+) # value: integer or float
+            \s?                        # optional space between value and unit
+            (?P<unit>[µa-zA-Z]{0,3})   # unit: up to three letters, may be omitted
+            \z",
+    )
+    .unwrap()
+});
+
+/// Units are case-sensitive, so that `m` (minute) and `M` (mega) can be told apart. Each
+/// unit maps to its dimension and its factor relative to the dimension's canonical unit:
+/// one, a byte or a second.
+static UNITS: Lazy<HashMap<&'static str, (Dimension, Decimal)>> = Lazy::new(|| {
+    use Dimension::*;
+
+    let decimal = |exp: u32| Decimal::from(1000_u64.pow(exp));
+    let binary = |exp: u32| Decimal::from(1024_u64.pow(exp));
+
+    vec![
+        ("", (Count, Decimal::ONE)),
+        ("k", (Count, decimal(1))),
+        ("K", (Count, decimal(1))),
+        ("M", (Count, decimal(2))),
+        ("G", (Count, decimal(3))),
+        ("T", (Count, decimal(4))),
+        ("P", (Count, decimal(5))),
+        ("B", (Bytes, Decimal::ONE)),
+        ("kB", (Bytes, decimal(1))),
+        ("KB", (Bytes, decimal(1))),
+        ("MB", (Bytes, decimal(2))),
+        ("GB", (Bytes, decimal(3))),
+        ("TB", (Bytes, decimal(4))),
+        ("PB", (Bytes, decimal(5))),
+        ("KiB", (Bytes, binary(1))),
+        ("MiB", (Bytes, binary(2))),
+        ("GiB", (Bytes, binary(3))),
+        ("TiB", (Bytes, binary(4))),
+        ("PiB", (Bytes, binary(5))),
+        ("ns", (Duration, Decimal::new(1, 9))),
+        ("us", (Duration, Decimal::new(1, 6))),
+        ("µs", (Duration, Decimal::new(1, 6))),
+        ("ms", (Duration, Decimal::new(1, 3))),
+        ("s", (Duration, Decimal::ONE)),
+        ("m", (Duration, Decimal::new(60, 0))),
+        ("h", (Duration, Decimal::new(3_600, 0))),
+        ("d", (Duration, Decimal::new(86_400, 0))),
+        ("w", (Duration, Decimal::new(604_800, 0))),
+    ]
+    .into_iter()
+    .collect()
+});
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseHumanSize;
+
+impl Function for ParseHumanSize {
+    fn identifier(&self) -> &'static str {
+        "parse_human_size"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "binary bytes",
+                source: r#"parse_human_size!("1.5GiB")"#,
+                result: Ok("1610612736.0"),
+            },
+            Example {
+                title: "duration",
+                source: r#"parse_human_size!("300ms")"#,
+                result: Ok("0.3"),
+            },
+            Example {
+                title: "count",
+                source: r#"parse_human_size!("2k")"#,
+                result: Ok("2000.0"),
+            },
+            Example {
+                title: "with unit",
+                source: r#"parse_human_size!("1.5GiB", unit: "MiB")"#,
+                result: Ok("1536.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let unit = arguments.optional("unit");
+
+        Ok(Box::new(ParseHumanSizeFn { value, unit }))
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseHumanSizeFn {
+    value: Box<dyn Expression>,
+    unit: Option<Box<dyn Expression>>,
+}
+
+impl Expression for ParseHumanSizeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let unit = self
+            .unit
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        parse_human_size(value, unit)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_human_size => ParseHumanSize;
+
+        binary_bytes {
+            args: func_args![value: "1.5GiB"],
+            want: Ok(1610612736.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        decimal_bytes {
+            args: func_args![value: "2 MB"],
+            want: Ok(2000000.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        plain_bytes {
+            args: func_args![value: "512B"],
+            want: Ok(512.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        milliseconds {
+            args: func_args![value: "300ms"],
+            want: Ok(0.3),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        minutes {
+            args: func_args![value: "5m"],
+            want: Ok(300.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        count {
+            args: func_args![value: "2k"],
+            want: Ok(2000.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        mega_count {
+            args: func_args![value: "1.2M"],
+            want: Ok(1200000.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        no_unit {
+            args: func_args![value: "42"],
+            want: Ok(42.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        bytes_to_unit {
+            args: func_args![value: "1.5GiB", unit: "MiB"],
+            want: Ok(1536.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        duration_to_unit {
+            args: func_args![value: "1h", unit: "m"],
+            want: Ok(60.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_invalid {
+            args: func_args![value: "foo"],
+            want: Err("unable to parse size: 'foo'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_unknown_unit {
+            args: func_args![value: "1xb"],
+            want: Err("unknown size unit: 'xb'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_unknown_target_unit {
+            args: func_args![value: "1GiB", unit: "xb"],
+            want: Err("unknown unit format: 'xb'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_mismatched_unit {
+            args: func_args![value: "1GiB", unit: "s"],
+            want: Err("unable to convert bytes into duration: 's'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_out_of_range {
+            args: func_args![value: "100000000000000PiB"],
+            want: Err("size is out of range: '100000000000000PiB'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_out_of_range_unit {
+            args: func_args![value: "1000000000000000000000w", unit: "ns"],
+            want: Err("size is out of range: '1000000000000000000000w'"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
@@ -1,21 +1,29 @@
 use std::collections::HashMap;
 
-use ::value::Value;
+use ::value::{json::NonFiniteFloats, Value};
 use serde_json::value::{RawValue, Value as JsonValue};
 use serde_json::{Error, Map};
 use vrl::prelude::*;
 
-fn parse_json(value: Value) -> Resolved {
+use crate::util;
+
+fn parse_json(value: Value, non_finite_floats: NonFiniteFloats) -> Resolved {
     let bytes = value.try_bytes()?;
-    let value = serde_json::from_slice::<'_, Value>(&bytes)
+    let bytes = ::value::json::replace_non_finite_literals(&bytes, non_finite_floats);
+    let value = ::value::json::from_slice::<Value>(&bytes)
         .map_err(|e| format!("unable to parse json: {}", e))?;
     Ok(value)
 }
 
 // parse_json_with_depth method recursively traverses the value and returns raw JSON-formatted bytes
 // after reaching provided depth.
-fn parse_json_with_depth(value: Value, max_depth: Value) -> Resolved {
+fn parse_json_with_depth(
+    value: Value,
+    max_depth: Value,
+    non_finite_floats: NonFiniteFloats,
+) -> Resolved {
     let bytes = value.try_bytes()?;
+    let bytes = ::value::json::replace_non_finite_literals(&bytes, non_finite_floats);
     let parsed_depth = validate_depth(max_depth)?;
 
     let raw_value = serde_json::from_slice::<'_, &RawValue>(&bytes)
@@ -105,6 +113,10 @@ impl Function for ParseJson {
 
             Only JSON types are returned. If you need to convert a `string` into a `timestamp`,
             consider the `parse_timestamp` function.
+
+            An optional closure can be provided to handle invalid JSON. It receives the error
+            message, and the function resolves to the value returned by the closure instead of
+            returning an error.
         "#}
     }
 
@@ -120,6 +132,11 @@ impl Function for ParseJson {
                 kind: kind::INTEGER,
                 required: false,
             },
+            Parameter {
+                keyword: "non_finite_floats",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
@@ -167,6 +184,18 @@ impl Function for ParseJson {
                 source: r#"parse_json!(s'{"first_level":{"second_level":"finish"}}', max_depth: 1)"#,
                 result: Ok(r#"{"first_level":"{\"second_level\":\"finish\"}"}"#),
             },
+            Example {
+                title: "non-finite floats",
+                source: r#"parse_json!(s'{"rate": NaN, "max": Infinity}', non_finite_floats: "null")"#,
+                result: Ok(r#"{"max": null, "rate": null}"#),
+            },
+            Example {
+                title: "error handler",
+                source: r#"parse_json("{ INVALID }") -> |err| { { "error": err } }"#,
+                result: Ok(
+                    r#"{ "error": "unable to parse json: key must be a string at line 1 column 3" }"#,
+                ),
+            },
         ]
     }
 
@@ -178,27 +207,95 @@ impl Function for ParseJson {
     ) -> Compiled {
         let value = arguments.required("value");
         let max_depth = arguments.optional("max_depth");
+        let non_finite_floats = util::non_finite_floats(&mut arguments, NonFiniteFloats::Error)?;
+        let closure = arguments.optional_closure().cloned();
 
         match max_depth {
-            Some(max_depth) => Ok(Box::new(ParseJsonMaxDepthFn { value, max_depth })),
-            None => Ok(Box::new(ParseJsonFn { value })),
+            Some(max_depth) => Ok(Box::new(ParseJsonMaxDepthFn {
+                value,
+                max_depth,
+                non_finite_floats,
+                closure,
+            })),
+            None => Ok(Box::new(ParseJsonFn {
+                value,
+                non_finite_floats,
+                closure,
+            })),
+        }
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::any(),
+                variables: vec![Variable {
+                    kind: VariableKind::Exact(Kind::bytes()),
+                    required: false,
+                }],
+                output: Output::Kind(Kind::any()),
+                example: Example {
+                    title: "error handler",
+                    source: r#"parse_json(.message) -> |err| { { "error": err } }"#,
+                    result: Ok("null"),
+                },
+            }],
+            is_iterator: false,
+            is_optional: true,
+        })
+    }
+}
+
+/// Resolves parse errors to the value returned by the error handler closure, if
+/// there is one.
+fn handle_error(
+    ctx: &mut Context,
+    closure: Option<&FunctionClosure>,
+    result: Resolved,
+) -> Resolved {
+    match (result, closure) {
+        (Err(error), Some(FunctionClosure { variables, block })) => {
+            let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+            let mut value = Value::from(error.to_string());
+            runner.map_value(ctx, &mut value)?;
+
+            Ok(value)
         }
+        (result, _) => result,
+    }
+}
+
+fn handled_type_def(
+    closure: Option<&FunctionClosure>,
+    state: (&state::LocalEnv, &state::ExternalEnv),
+) -> TypeDef {
+    match closure {
+        Some(closure) => type_def()
+            .infallible()
+            .merge_deep(closure.block.type_def(state)),
+        None => type_def(),
     }
 }
 
 #[derive(Debug, Clone)]
 struct ParseJsonFn {
     value: Box<dyn Expression>,
+    non_finite_floats: NonFiniteFloats,
+    closure: Option<FunctionClosure>,
 }
 
 impl Expression for ParseJsonFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        parse_json(value)
+        let result = parse_json(value, self.non_finite_floats);
+        handle_error(ctx, self.closure.as_ref(), result)
     }
 
-    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
-        type_def()
+    fn type_def(&self, state: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        handled_type_def(self.closure.as_ref(), state)
     }
 }
 
@@ -206,17 +303,20 @@ impl Expression for ParseJsonFn {
 struct ParseJsonMaxDepthFn {
     value: Box<dyn Expression>,
     max_depth: Box<dyn Expression>,
+    non_finite_floats: NonFiniteFloats,
+    closure: Option<FunctionClosure>,
 }
 
 impl Expression for ParseJsonMaxDepthFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
         let max_depth = self.max_depth.resolve(ctx)?;
-        parse_json_with_depth(value, max_depth)
+        let result = parse_json_with_depth(value, max_depth, self.non_finite_floats);
+        handle_error(ctx, self.closure.as_ref(), result)
     }
 
-    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
-        type_def()
+    fn type_def(&self, state: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        handled_type_def(self.closure.as_ref(), state)
     }
 }
 
@@ -302,6 +402,30 @@ mod tests {
                 .add_object(Collection::from_unknown(inner_kind())),
         }
 
+        non_finite_floats_error {
+            args: func_args![ value: r#"{"rate": NaN}"# ],
+            want: Err("unable to parse json: expected value at line 1 column 10"),
+            tdef: type_def(),
+        }
+
+        non_finite_floats_null {
+            args: func_args![ value: r#"{"rate": NaN, "bounds": [-Infinity, Infinity]}"#, non_finite_floats: "null" ],
+            want: Ok(value!({ rate: null, bounds: [null, null] })),
+            tdef: type_def(),
+        }
+
+        non_finite_floats_string {
+            args: func_args![ value: r#"{"rate": NaN, "bounds": [-Infinity, Infinity], "text": "NaN"}"#, non_finite_floats: "string" ],
+            want: Ok(value!({ rate: "NaN", bounds: ["-Infinity", "Infinity"], text: "NaN" })),
+            tdef: type_def(),
+        }
+
+        non_finite_floats_max_depth {
+            args: func_args![ value: r#"{"top_layer": {"rate": NaN}}"#, max_depth: 1, non_finite_floats: "string" ],
+            want: Ok(value!({ top_layer: r#"{"rate": "NaN"}"# })),
+            tdef: type_def(),
+        }
+
         invalid_input_max_depth {
             args: func_args![ value: r#"{"top_layer": "finish"}"#, max_depth: 129],
             want: Err("max_depth value should be greater than 0 and less than 128, got 129"),
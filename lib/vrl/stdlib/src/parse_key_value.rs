@@ -1,4 +1,8 @@
-use std::{iter::FromIterator, str::FromStr};
+use std::{
+    borrow::Cow,
+    collections::{btree_map::Entry, BTreeMap},
+    str::FromStr,
+};
 
 use ::value::Value;
 use nom::{
@@ -7,32 +11,48 @@ use nom::{
     bytes::complete::{escaped, tag, take_until, take_while1},
     character::complete::{char, satisfy, space0},
     combinator::{eof, map, opt, peek, recognize, rest, verify},
-    error::{ContextError, ParseError, VerboseError},
+    error::{ContextError, ErrorKind, ParseError, VerboseError},
     multi::{many1, many_m_n, separated_list1},
     sequence::{delimited, preceded, terminated, tuple},
     IResult,
 };
 use vrl::prelude::*;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn parse_key_value(
     bytes: Value,
     key_value_delimiter: Value,
     field_delimiter: Value,
     standalone_key: Value,
     whitespace: Whitespace,
+    quote_chars: Value,
+    unescape: Value,
+    infer_types: Value,
+    duplicate_keys: DuplicateKeys,
 ) -> Resolved {
     let bytes = bytes.try_bytes_utf8_lossy()?;
     let key_value_delimiter = key_value_delimiter.try_bytes_utf8_lossy()?;
     let field_delimiter = field_delimiter.try_bytes_utf8_lossy()?;
     let standalone_key = standalone_key.try_boolean()?;
+    let quote_chars = quote_chars
+        .try_bytes_utf8_lossy()?
+        .chars()
+        .collect::<Vec<_>>();
+    let quoting = Quoting {
+        chars: &quote_chars,
+        unescape: unescape.try_boolean()?,
+    };
+    let infer_types = infer_types.try_boolean()?;
     let values = parse(
         &bytes,
         &key_value_delimiter,
         &field_delimiter,
         whitespace,
         standalone_key,
+        quoting,
+        infer_types,
     )?;
-    Ok(Value::from_iter(values))
+    Ok(duplicate_keys.collect(values))
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -70,6 +90,26 @@ impl Function for ParseKeyValue {
                 kind: kind::BOOLEAN,
                 required: false,
             },
+            Parameter {
+                keyword: "quote_chars",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "unescape",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "infer_types",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "duplicate_keys",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
@@ -97,6 +137,21 @@ impl Function for ParseKeyValue {
                 source: r#"parse_key_value!(s'foo=bar foobar', whitespace: "strict")"#,
                 result: Ok(r#"{"foo": "bar", "foobar": true}"#),
             },
+            Example {
+                title: "nested quotes",
+                source: r#"parse_key_value!("msg='said \"hi\"' path=\"C:\\\\temp\"", quote_chars: "\"'", unescape: true)"#,
+                result: Ok(r#"{"msg": "said \"hi\"", "path": "C:\\temp"}"#),
+            },
+            Example {
+                title: "inferred types",
+                source: r#"parse_key_value!(s'status=200 took=1.5 cached=false id="42"', infer_types: true)"#,
+                result: Ok(r#"{"status": 200, "took": 1.5, "cached": false, "id": "42"}"#),
+            },
+            Example {
+                title: "duplicate keys",
+                source: r#"parse_key_value!("tag=a tag=b env=prod", duplicate_keys: "array")"#,
+                result: Ok(r#"{"tag": ["a", "b"], "env": "prod"}"#),
+            },
         ]
     }
 
@@ -128,12 +183,38 @@ impl Function for ParseKeyValue {
             .optional("accept_standalone_key")
             .unwrap_or_else(|| expr!(true));
 
+        let quote_chars = arguments
+            .optional("quote_chars")
+            .unwrap_or_else(|| expr!("\""));
+
+        let unescape = arguments
+            .optional("unescape")
+            .unwrap_or_else(|| expr!(false));
+
+        let infer_types = arguments
+            .optional("infer_types")
+            .unwrap_or_else(|| expr!(false));
+
+        let duplicate_keys = arguments
+            .optional_enum("duplicate_keys", &DuplicateKeys::all_value())?
+            .map(|s| {
+                DuplicateKeys::from_str(
+                    &s.try_bytes_utf8_lossy().expect("duplicate_keys not bytes"),
+                )
+                .expect("validated enum")
+            })
+            .unwrap_or_default();
+
         Ok(Box::new(ParseKeyValueFn {
             value,
             key_value_delimiter,
             field_delimiter,
             whitespace,
             standalone_key,
+            quote_chars,
+            unescape,
+            infer_types,
+            duplicate_keys,
         }))
     }
 
@@ -159,6 +240,24 @@ impl Function for ParseKeyValue {
                     })?,
                 )),
             },
+            ("duplicate_keys", Some(expr)) => match expr.as_value() {
+                None => Ok(None),
+                Some(value) => Ok(Some(
+                    DuplicateKeys::from_str(
+                        &value
+                            .try_bytes_utf8_lossy()
+                            .expect("duplicate_keys not bytes"),
+                    )
+                    .map(|duplicate_keys| {
+                        Box::new(duplicate_keys) as Box<dyn std::any::Any + Send + Sync>
+                    })
+                    .map_err(|_| vrl::function::Error::InvalidEnumVariant {
+                        keyword: "duplicate_keys",
+                        value,
+                        variants: DuplicateKeys::all_value().to_vec(),
+                    })?,
+                )),
+            },
             _ => Ok(None),
         }
     }
@@ -210,6 +309,78 @@ impl FromStr for Whitespace {
     }
 }
 
+/// What to keep of a key that appears more than once in the line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DuplicateKeys {
+    First,
+    Last,
+    Array,
+}
+
+impl DuplicateKeys {
+    fn all_value() -> Vec<Value> {
+        use DuplicateKeys::*;
+
+        vec![First, Last, Array]
+            .into_iter()
+            .map(|u| u.as_str().into())
+            .collect::<Vec<_>>()
+    }
+
+    const fn as_str(self) -> &'static str {
+        use DuplicateKeys::*;
+
+        match self {
+            First => "first",
+            Last => "last",
+            Array => "array",
+        }
+    }
+
+    /// Collects the fields into an object, keeping the first or last value of a duplicated key,
+    /// or all of its values in an array.
+    fn collect(self, fields: Vec<(String, Value)>) -> Value {
+        let mut object = BTreeMap::new();
+        for (key, value) in fields {
+            match (self, object.entry(key)) {
+                (_, Entry::Vacant(entry)) => {
+                    entry.insert(value);
+                }
+                (DuplicateKeys::First, Entry::Occupied(_)) => {}
+                (DuplicateKeys::Last, Entry::Occupied(mut entry)) => {
+                    entry.insert(value);
+                }
+                (DuplicateKeys::Array, Entry::Occupied(mut entry)) => match entry.get_mut() {
+                    Value::Array(values) => values.push(value),
+                    first => *first = Value::Array(vec![first.clone(), value]),
+                },
+            }
+        }
+        Value::Object(object)
+    }
+}
+
+impl Default for DuplicateKeys {
+    fn default() -> Self {
+        Self::Last
+    }
+}
+
+impl FromStr for DuplicateKeys {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        use DuplicateKeys::*;
+
+        match s {
+            "first" => Ok(First),
+            "last" => Ok(Last),
+            "array" => Ok(Array),
+            _ => Err("unknown duplicate_keys variant"),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct ParseKeyValueFn {
     pub(crate) value: Box<dyn Expression>,
@@ -217,6 +388,10 @@ pub(crate) struct ParseKeyValueFn {
     pub(crate) field_delimiter: Box<dyn Expression>,
     pub(crate) whitespace: Whitespace,
     pub(crate) standalone_key: Box<dyn Expression>,
+    pub(crate) quote_chars: Box<dyn Expression>,
+    pub(crate) unescape: Box<dyn Expression>,
+    pub(crate) infer_types: Box<dyn Expression>,
+    pub(crate) duplicate_keys: DuplicateKeys,
 }
 
 impl Expression for ParseKeyValueFn {
@@ -226,6 +401,10 @@ impl Expression for ParseKeyValueFn {
         let field_delimiter = self.field_delimiter.resolve(ctx)?;
         let standalone_key = self.standalone_key.resolve(ctx)?;
         let whitespace = self.whitespace;
+        let quote_chars = self.quote_chars.resolve(ctx)?;
+        let unescape = self.unescape.resolve(ctx)?;
+        let infer_types = self.infer_types.resolve(ctx)?;
+        let duplicate_keys = self.duplicate_keys;
 
         parse_key_value(
             bytes,
@@ -233,6 +412,10 @@ impl Expression for ParseKeyValueFn {
             field_delimiter,
             standalone_key,
             whitespace,
+            quote_chars,
+            unescape,
+            infer_types,
+            duplicate_keys,
         )
     }
 
@@ -241,12 +424,22 @@ impl Expression for ParseKeyValueFn {
     }
 }
 
+/// The characters keys and values can be wrapped with, and whether to remove the `\` of the
+/// escaped characters within them.
+#[derive(Clone, Copy, Debug)]
+struct Quoting<'a> {
+    chars: &'a [char],
+    unescape: bool,
+}
+
 fn parse<'a>(
     input: &'a str,
     key_value_delimiter: &'a str,
     field_delimiter: &'a str,
     whitespace: Whitespace,
     standalone_key: bool,
+    quoting: Quoting<'a>,
+    infer_types: bool,
 ) -> Result<Vec<(String, Value)>> {
     let (rest, result) = parse_line(
         input,
@@ -254,6 +447,8 @@ fn parse<'a>(
         field_delimiter,
         whitespace,
         standalone_key,
+        quoting,
+        infer_types,
     )
     .map_err(|e| match e {
         nom::Err::Error(e) | nom::Err::Failure(e) => {
@@ -277,6 +472,8 @@ fn parse_line<'a>(
     field_delimiter: &'a str,
     whitespace: Whitespace,
     standalone_key: bool,
+    quoting: Quoting<'a>,
+    infer_types: bool,
 ) -> IResult<&'a str, Vec<(String, Value)>, VerboseError<&'a str>> {
     separated_list1(
         parse_field_delimiter(field_delimiter),
@@ -285,6 +482,8 @@ fn parse_line<'a>(
             field_delimiter,
             whitespace,
             standalone_key,
+            quoting,
+            infer_types,
         ),
     )(input)
 }
@@ -312,6 +511,8 @@ fn parse_key_value_<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     field_delimiter: &'a str,
     whitespace: Whitespace,
     standalone_key: bool,
+    quoting: Quoting<'a>,
+    infer_types: bool,
 ) -> impl Fn(&'a str) -> IResult<&'a str, (String, Value), E> {
     move |input| {
         map(
@@ -319,29 +520,39 @@ fn parse_key_value_<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
                 Whitespace::Strict => tuple((
                     preceded(
                         space0,
-                        parse_key(key_value_delimiter, field_delimiter, standalone_key),
+                        parse_key(
+                            key_value_delimiter,
+                            field_delimiter,
+                            standalone_key,
+                            quoting,
+                        ),
                     ),
                     many_m_n(!standalone_key as usize, 1, tag(key_value_delimiter)),
-                    parse_value(field_delimiter),
+                    parse_value(field_delimiter, quoting, infer_types),
                 ))(input),
                 Whitespace::Lenient => tuple((
                     preceded(
                         space0,
-                        parse_key(key_value_delimiter, field_delimiter, standalone_key),
+                        parse_key(
+                            key_value_delimiter,
+                            field_delimiter,
+                            standalone_key,
+                            quoting,
+                        ),
                     ),
                     many_m_n(
                         !standalone_key as usize,
                         1,
                         delimited(space0, tag(key_value_delimiter), space0),
                     ),
-                    parse_value(field_delimiter),
+                    parse_value(field_delimiter, quoting, infer_types),
                 ))(input),
             },
-            |(field, sep, value): (&str, Vec<&str>, Value)| {
+            |(field, sep, value): (Cow<'a, str>, Vec<&str>, Value)| {
                 if sep.len() == 1 {
-                    (field.to_string(), value)
+                    (field.into_owned(), value)
                 } else {
-                    (field.to_string(), value!(true))
+                    (field.into_owned(), value!(true))
                 }
             },
         )(input)
@@ -389,6 +600,54 @@ fn parse_delimited<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     }
 }
 
+/// Parses a string delimited by the first of the quote characters it starts with, so that values
+/// wrapped in one quote character can contain the others unescaped.
+fn parse_quoted<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    quoting: Quoting<'a>,
+    field_terminator: &'a str,
+) -> impl Fn(&'a str) -> IResult<&'a str, Cow<'a, str>, E> {
+    move |input| {
+        let mut result = Err(nom::Err::Error(E::from_error_kind(input, ErrorKind::Char)));
+        for &quote in quoting.chars {
+            result = parse_delimited(quote, field_terminator)(input).map(|(rest, inner)| {
+                if quoting.unescape {
+                    (rest, unescape(inner, quote))
+                } else {
+                    (rest, Cow::from(inner))
+                }
+            });
+            if !matches!(result, Err(nom::Err::Error(_))) {
+                break;
+            }
+        }
+        result
+    }
+}
+
+/// Removes the `\` escaping a `\` or the quote character. Any other `\` is kept.
+fn unescape(input: &str, quote: char) -> Cow<'_, str> {
+    if !input.contains('\\') {
+        return input.into();
+    }
+
+    let mut unescaped = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some(escaped) if escaped == '\\' || escaped == quote => unescaped.push(escaped),
+            next => {
+                unescaped.push(c);
+                unescaped.extend(next);
+            }
+        }
+    }
+    unescaped.into()
+}
+
 /// An undelimited value is all the text until our field_delimiter, or if it is the last value in the line,
 /// just take the rest of the string.
 fn parse_undelimited<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
@@ -397,24 +656,57 @@ fn parse_undelimited<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     move |input| map(alt((take_until(field_delimiter), rest)), |s: &str| s.trim())(input)
 }
 
+/// Reads an undelimited value as a boolean, an integer or a float if it is written as one, and as
+/// a string otherwise.
+fn infer_type(value: &str) -> Value {
+    match value {
+        "true" => return true.into(),
+        "false" => return false.into(),
+        _ => {}
+    }
+
+    // Leave out the words Rust reads as floats, such as `inf` and `NaN`.
+    let numeric = value.bytes().any(|c| c.is_ascii_digit())
+        && value
+            .bytes()
+            .all(|c| c.is_ascii_digit() || matches!(c, b'-' | b'+' | b'.' | b'e' | b'E'));
+    if numeric {
+        if let Ok(integer) = value.parse::<i64>() {
+            return integer.into();
+        }
+        if let Ok(float) = value.parse::<f64>() {
+            return float.into();
+        }
+    }
+    value.into()
+}
+
 /// Parses the value.
 /// The value has two parsing strategies.
 ///
-/// 1. Parse as a delimited field - currently the delimiter is hardcoded to a `"`.
+/// 1. Parse as a delimited field - the delimiter is any of the quote characters.
 /// 2. If it does not start with one of the trim values, it is not a delimited field and we parse up to
-///    the next field_delimiter or the eof.
+///    the next field_delimiter or the eof. If `infer_types` is set, the value is then read as a
+///    boolean, integer or float if it looks like one.
 ///
 fn parse_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     field_delimiter: &'a str,
+    quoting: Quoting<'a>,
+    infer_types: bool,
 ) -> impl Fn(&'a str) -> IResult<&'a str, Value, E> {
     move |input| {
-        map(
-            alt((
-                parse_delimited('"', field_delimiter),
-                parse_undelimited(field_delimiter),
-            )),
-            Into::into,
-        )(input)
+        alt((
+            map(parse_quoted(quoting, field_delimiter), |value| {
+                Value::from(value.into_owned())
+            }),
+            map(parse_undelimited(field_delimiter), |value| {
+                if infer_types {
+                    infer_type(value)
+                } else {
+                    Value::from(value)
+                }
+            }),
+        ))(input)
     }
 }
 
@@ -425,23 +717,27 @@ fn parse_key<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     key_value_delimiter: &'a str,
     field_delimiter: &'a str,
     standalone_key: bool,
-) -> Box<dyn Fn(&'a str) -> IResult<&'a str, &'a str, E> + 'a> {
+    quoting: Quoting<'a>,
+) -> Box<dyn Fn(&'a str) -> IResult<&'a str, Cow<'a, str>, E> + 'a> {
     if standalone_key {
         Box::new(move |input| {
             alt((
-                parse_delimited('"', key_value_delimiter),
-                parse_delimited('"', field_delimiter),
-                verify(parse_undelimited(key_value_delimiter), |s: &str| {
-                    !s.contains(field_delimiter)
-                }),
-                parse_undelimited(field_delimiter),
+                parse_quoted(quoting, key_value_delimiter),
+                parse_quoted(quoting, field_delimiter),
+                map(
+                    verify(parse_undelimited(key_value_delimiter), |s: &str| {
+                        !s.contains(field_delimiter)
+                    }),
+                    Cow::from,
+                ),
+                map(parse_undelimited(field_delimiter), Cow::from),
             ))(input)
         })
     } else {
         Box::new(move |input| {
             alt((
-                parse_delimited('"', key_value_delimiter),
-                parse_undelimited(key_value_delimiter),
+                parse_quoted(quoting, key_value_delimiter),
+                map(parse_undelimited(key_value_delimiter), Cow::from),
             ))(input)
         })
     }
@@ -451,16 +747,37 @@ fn parse_key<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
 mod test {
     use super::*;
 
+    const QUOTING: Quoting<'static> = Quoting {
+        chars: &['"'],
+        unescape: false,
+    };
+
     #[test]
     fn test_quote_and_escape_char() {
         assert_eq!(
             Ok(vec![("key".to_string(), r#"a\a"#.into()),]),
-            parse(r#"key="a\a""#, "=", " ", Whitespace::Strict, true,)
+            parse(
+                r#"key="a\a""#,
+                "=",
+                " ",
+                Whitespace::Strict,
+                true,
+                QUOTING,
+                false
+            )
         );
 
         assert_eq!(
             Ok(vec![(r#"a\ a"#.to_string(), r#"val"#.into()),]),
-            parse(r#""a\ a"=val"#, "=", " ", Whitespace::Strict, true,)
+            parse(
+                r#""a\ a"=val"#,
+                "=",
+                " ",
+                Whitespace::Strict,
+                true,
+                QUOTING,
+                false
+            )
         );
     }
 
@@ -483,6 +800,8 @@ mod test {
                 " ",
                 Whitespace::Lenient,
                 false,
+                QUOTING,
+                false
             )
         );
     }
@@ -491,14 +810,26 @@ mod test {
     fn test_parse_key_value() {
         assert_eq!(
             Ok(("", ("ook".to_string(), "pook".into()))),
-            parse_key_value_::<VerboseError<&str>>("=", " ", Whitespace::Lenient, false)(
-                "ook=pook"
-            )
+            parse_key_value_::<VerboseError<&str>>(
+                "=",
+                " ",
+                Whitespace::Lenient,
+                false,
+                QUOTING,
+                false
+            )("ook=pook")
         );
 
         assert_eq!(
             Ok(("", ("key".to_string(), "".into()))),
-            parse_key_value_::<VerboseError<&str>>("=", " ", Whitespace::Strict, false)("key=")
+            parse_key_value_::<VerboseError<&str>>(
+                "=",
+                " ",
+                Whitespace::Strict,
+                false,
+                QUOTING,
+                false
+            )("key=")
         );
     }
 
@@ -509,7 +840,15 @@ mod test {
                 ("ook".to_string(), "pook".into()),
                 ("onk".to_string(), "ponk".into())
             ]),
-            parse("ook=pook onk=ponk", "=", " ", Whitespace::Lenient, false)
+            parse(
+                "ook=pook onk=ponk",
+                "=",
+                " ",
+                Whitespace::Lenient,
+                false,
+                QUOTING,
+                false
+            )
         );
     }
 
@@ -520,7 +859,15 @@ mod test {
                 ("ook".to_string(), "".into()),
                 ("onk".to_string(), "ponk".into())
             ]),
-            parse("ook= onk=ponk", "=", " ", Whitespace::Strict, false)
+            parse(
+                "ook= onk=ponk",
+                "=",
+                " ",
+                Whitespace::Strict,
+                false,
+                QUOTING,
+                false
+            )
         );
     }
 
@@ -531,7 +878,15 @@ mod test {
                 ("foo".to_string(), "bar".into()),
                 ("foobar".to_string(), value!(true))
             ]),
-            parse("foo:bar ,   foobar   ", ":", ",", Whitespace::Lenient, true)
+            parse(
+                "foo:bar ,   foobar   ",
+                ":",
+                ",",
+                Whitespace::Lenient,
+                true,
+                QUOTING,
+                false
+            )
         );
     }
 
@@ -549,7 +904,9 @@ mod test {
                 "=",
                 " ",
                 Whitespace::Lenient,
-                true
+                true,
+                QUOTING,
+                false
             )
         );
     }
@@ -569,7 +926,9 @@ mod test {
                 "=",
                 " ",
                 Whitespace::Lenient,
-                true
+                true,
+                QUOTING,
+                false
             )
         );
     }
@@ -578,7 +937,15 @@ mod test {
     fn test_parse_single_standalone_key() {
         assert_eq!(
             Ok(vec![("foobar".to_string(), value!(true))]),
-            parse("foobar", ":", ",", Whitespace::Lenient, true)
+            parse(
+                "foobar",
+                ":",
+                ",",
+                Whitespace::Lenient,
+                true,
+                QUOTING,
+                false
+            )
         );
     }
 
@@ -589,7 +956,15 @@ mod test {
                 ("foo".to_string(), "bar".into()),
                 ("foobar".to_string(), value!(true))
             ]),
-            parse("foo:bar ,   foobar   ", ":", ",", Whitespace::Strict, true)
+            parse(
+                "foo:bar ,   foobar   ",
+                ":",
+                ",",
+                Whitespace::Strict,
+                true,
+                QUOTING,
+                false
+            )
         );
     }
 
@@ -597,38 +972,38 @@ mod test {
     fn test_parse_key() {
         // delimited
         assert_eq!(
-            Ok(("", "noog")),
-            parse_key::<VerboseError<&str>>("=", " ", false)(r#""noog""#)
+            Ok(("", "noog".into())),
+            parse_key::<VerboseError<&str>>("=", " ", false, QUOTING)(r#""noog""#)
         );
 
         // undelimited
         assert_eq!(
-            Ok(("", "noog")),
-            parse_key::<VerboseError<&str>>("=", " ", false)("noog")
+            Ok(("", "noog".into())),
+            parse_key::<VerboseError<&str>>("=", " ", false, QUOTING)("noog")
         );
 
         // delimited with escaped char (1)
         assert_eq!(
-            Ok(("=baz", r#"foo \" bar"#)),
-            parse_key::<VerboseError<&str>>("=", " ", false)(r#""foo \" bar"=baz"#)
+            Ok(("=baz", r#"foo \" bar"#.into())),
+            parse_key::<VerboseError<&str>>("=", " ", false, QUOTING)(r#""foo \" bar"=baz"#)
         );
 
         // delimited with escaped char (2)
         assert_eq!(
-            Ok(("=baz", r#"foo \\ \" \ bar"#)),
-            parse_key::<VerboseError<&str>>("=", " ", false)(r#""foo \\ \" \ bar"=baz"#)
+            Ok(("=baz", r#"foo \\ \" \ bar"#.into())),
+            parse_key::<VerboseError<&str>>("=", " ", false, QUOTING)(r#""foo \\ \" \ bar"=baz"#)
         );
 
         // delimited with escaped char (3)
         assert_eq!(
-            Ok(("=baz", r#"foo \ bar"#)),
-            parse_key::<VerboseError<&str>>("=", " ", false)(r#""foo \ bar"=baz"#)
+            Ok(("=baz", r#"foo \ bar"#.into())),
+            parse_key::<VerboseError<&str>>("=", " ", false, QUOTING)(r#""foo \ bar"=baz"#)
         );
 
         // Standalone key
         assert_eq!(
-            Ok((" bar=baz", "foo")),
-            parse_key::<VerboseError<&str>>("=", " ", true)(r#"foo bar=baz"#)
+            Ok((" bar=baz", "foo".into())),
+            parse_key::<VerboseError<&str>>("=", " ", true, QUOTING)(r#"foo bar=baz"#)
         );
     }
 
@@ -637,25 +1012,25 @@ mod test {
         // delimited
         assert_eq!(
             Ok(("", "noog".into())),
-            parse_value::<VerboseError<&str>>(" ")(r#""noog""#)
+            parse_value::<VerboseError<&str>>(" ", QUOTING, false)(r#""noog""#)
         );
 
         // undelimited
         assert_eq!(
             Ok(("", "noog".into())),
-            parse_value::<VerboseError<&str>>(" ")("noog")
+            parse_value::<VerboseError<&str>>(" ", QUOTING, false)("noog")
         );
 
         // empty delimited
         assert_eq!(
             Ok(("", "".into())),
-            parse_value::<VerboseError<&str>>(" ")(r#""""#)
+            parse_value::<VerboseError<&str>>(" ", QUOTING, false)(r#""""#)
         );
 
         // empty undelimited
         assert_eq!(
             Ok(("", "".into())),
-            parse_value::<VerboseError<&str>>(" ")("")
+            parse_value::<VerboseError<&str>>(" ", QUOTING, false)("")
         );
     }
 
@@ -680,6 +1055,52 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_nested_quotes() {
+        let quoting = Quoting {
+            chars: &['"', '\''],
+            unescape: true,
+        };
+        assert_eq!(
+            Ok(vec![
+                ("msg".to_string(), r#"said "hi""#.into()),
+                ("id".to_string(), r#"a"b\c"#.into()),
+                ("it's".to_string(), "ok".into()),
+            ]),
+            parse(
+                r#"msg='said "hi"' id="a\"b\\c" "it's"=ok"#,
+                "=",
+                " ",
+                Whitespace::Lenient,
+                true,
+                quoting,
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape(r#"no escapes"#, '"'), "no escapes");
+        assert_eq!(unescape(r#"a\"b\\c"#, '"'), r#"a"b\c"#);
+        assert_eq!(unescape(r#"a\'b\"c\n"#, '\''), r#"a'b\"c\n"#);
+        assert_eq!(unescape(r#"trailing\"#, '"'), r#"trailing\"#);
+    }
+
+    #[test]
+    fn test_infer_type() {
+        assert_eq!(infer_type("200"), value!(200));
+        assert_eq!(infer_type("-1.5"), value!(-1.5));
+        assert_eq!(infer_type("1e3"), value!(1000.0));
+        assert_eq!(infer_type("true"), value!(true));
+        assert_eq!(infer_type("false"), value!(false));
+        assert_eq!(infer_type("inf"), value!("inf"));
+        assert_eq!(infer_type("NaN"), value!("NaN"));
+        assert_eq!(infer_type("1-2"), value!("1-2"));
+        assert_eq!(infer_type("1.2.3.4"), value!("1.2.3.4"));
+        assert_eq!(infer_type(""), value!(""));
+    }
+
     test_function![
         parse_key_value => ParseKeyValue;
 
@@ -837,6 +1258,70 @@ mod test {
             tdef: TypeDef::object(Collection::any()).fallible(),
         }
 
+        infer_types {
+            args: func_args! [
+                value: r#"status=200 took=1.5 cached=false id="42" level=info flag"#,
+                infer_types: true,
+            ],
+            want: Ok(value!({status: 200,
+                             took: 1.5,
+                             cached: false,
+                             id: "42",
+                             level: "info",
+                             flag: true})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        quote_chars {
+            args: func_args! [
+                value: r#"msg='said "hi"' other="it's""#,
+                quote_chars: "'\"",
+            ],
+            want: Ok(value!({msg: r#"said "hi""#,
+                             other: "it's"})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        unescape {
+            args: func_args! [
+                value: r#"msg="say \"hi\"" path="C:\\temp" raw=a\"b"#,
+                unescape: true,
+            ],
+            want: Ok(value!({msg: r#"say "hi""#,
+                             path: r#"C:\temp"#,
+                             raw: r#"a\"b"#})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        duplicate_keys_last {
+            args: func_args! [
+                value: "tag=a tag=b env=prod",
+            ],
+            want: Ok(value!({tag: "b",
+                             env: "prod"})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        duplicate_keys_first {
+            args: func_args! [
+                value: "tag=a tag=b env=prod",
+                duplicate_keys: "first",
+            ],
+            want: Ok(value!({tag: "a",
+                             env: "prod"})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        duplicate_keys_array {
+            args: func_args! [
+                value: "tag=a tag=b env=prod tag=c",
+                duplicate_keys: "array",
+            ],
+            want: Ok(value!({tag: ["a", "b", "c"],
+                             env: "prod"})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
         multi_line_with_quotes_spaces {
             args: func_args! [
                 value: "To: tom\ntest: \"tom test\"  ",
@@ -1,6 +1,6 @@
 use vrl::prelude::*;
 
-use crate::parse_key_value::{ParseKeyValueFn, Whitespace};
+use crate::parse_key_value::{DuplicateKeys, ParseKeyValueFn, Whitespace};
 
 #[derive(Clone, Copy, Debug)]
 pub struct ParseLogFmt;
@@ -47,6 +47,10 @@ impl Function for ParseLogFmt {
         let field_delimiter = expr!(" ");
         let whitespace = Whitespace::Lenient;
         let standalone_key = expr!(true);
+        let quote_chars = expr!("\"");
+        let unescape = expr!(false);
+        let infer_types = expr!(false);
+        let duplicate_keys = DuplicateKeys::Last;
 
         Ok(Box::new(ParseKeyValueFn {
             value,
@@ -54,6 +58,10 @@ impl Function for ParseLogFmt {
             field_delimiter,
             whitespace,
             standalone_key,
+            quote_chars,
+            unescape,
+            infer_types,
+            duplicate_keys,
         }))
     }
 }
@@ -22,6 +22,10 @@ impl Function for ParseRegex {
         "parse_regex"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
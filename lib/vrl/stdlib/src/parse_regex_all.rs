@@ -22,6 +22,10 @@ impl Function for ParseRegexAll {
         "parse_regex_all"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
@@ -1,21 +1,82 @@
 use ::value::Value;
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
 use vector_common::conversion::Conversion;
 use vrl::prelude::*;
 
-fn parse_timestamp(value: Value, format: Value, ctx: &Context) -> Resolved {
+fn parse_timestamp(value: Value, format: Option<Value>, ctx: &Context) -> Resolved {
     match value {
-        Value::Bytes(v) => {
-            let format = format.try_bytes_utf8_lossy()?;
-            Conversion::parse(format!("timestamp|{}", format), ctx.timezone().to_owned())
-                .map_err(|e| e.to_string())?
-                .convert(v)
-                .map_err(|e| e.to_string().into())
-        }
+        Value::Bytes(v) => match format {
+            // Formats are tried in order, the first one that matches wins.
+            Some(Value::Array(formats)) => {
+                let mut last_error = None;
+                for format in formats {
+                    match parse_with_format(v.clone(), format, ctx) {
+                        Ok(timestamp) => return Ok(timestamp),
+                        Err(error) => last_error = Some(error),
+                    }
+                }
+
+                match last_error {
+                    Some(error) => Err(format!(
+                        "unable to parse timestamp {:?} with any of the given formats: {}",
+                        String::from_utf8_lossy(&v),
+                        error
+                    )
+                    .into()),
+                    None => Err("no timestamp formats given".into()),
+                }
+            }
+            Some(format) => parse_with_format(v, format, ctx),
+            None => Err("format is required to parse a string into a timestamp".into()),
+        },
+        Value::Integer(v) => from_epoch(v).map(Into::into),
+        Value::Float(v) => from_epoch_float(*v).map(Into::into),
         Value::Timestamp(_) => Ok(value),
         _ => Err("unable to convert value to timestamp".into()),
     }
 }
 
+fn parse_with_format(value: Bytes, format: Value, ctx: &Context) -> Resolved {
+    let format = format.try_bytes_utf8_lossy()?;
+    Conversion::parse(format!("timestamp|{}", format), ctx.timezone().to_owned())
+        .map_err(|e| e.to_string())?
+        .convert(value)
+        .map_err(|e| e.to_string().into())
+}
+
+/// Detects the unit of a Unix epoch by its magnitude, returning the number of units per
+/// second. Anything below 1e11 (the year 5138 in seconds) is taken as seconds, and so on
+/// for milliseconds, microseconds and nanoseconds.
+fn epoch_units_per_second(magnitude: f64) -> i64 {
+    match magnitude {
+        v if v < 1e11 => 1,
+        v if v < 1e14 => 1_000,
+        v if v < 1e17 => 1_000_000,
+        _ => 1_000_000_000,
+    }
+}
+
+fn from_epoch(epoch: i64) -> Result<DateTime<Utc>, ExpressionError> {
+    let per_second = epoch_units_per_second((epoch as f64).abs());
+    let seconds = epoch.div_euclid(per_second);
+    let nanos = epoch.rem_euclid(per_second) * (1_000_000_000 / per_second);
+
+    Utc.timestamp_opt(seconds, nanos as u32)
+        .single()
+        .ok_or_else(|| format!("unable to convert epoch {} to timestamp", epoch).into())
+}
+
+fn from_epoch_float(epoch: f64) -> Result<DateTime<Utc>, ExpressionError> {
+    let seconds = epoch / epoch_units_per_second(epoch.abs()) as f64;
+    let whole = seconds.floor();
+    let nanos = ((seconds - whole) * 1e9).round().min(999_999_999.0);
+
+    Utc.timestamp_opt(whole as i64, nanos as u32)
+        .single()
+        .ok_or_else(|| format!("unable to convert epoch {} to timestamp", epoch).into())
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct ParseTimestamp;
 
@@ -25,11 +86,23 @@ impl Function for ParseTimestamp {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "valid",
-            source: r#"parse_timestamp!("11-Feb-2021 16:00 +00:00", format: "%v %R %z")"#,
-            result: Ok("t'2021-02-11T16:00:00Z'"),
-        }]
+        &[
+            Example {
+                title: "valid",
+                source: r#"parse_timestamp!("11-Feb-2021 16:00 +00:00", format: "%v %R %z")"#,
+                result: Ok("t'2021-02-11T16:00:00Z'"),
+            },
+            Example {
+                title: "multiple formats",
+                source: r#"parse_timestamp!("2021-02-11 16:00:00 +00:00", format: ["%v %R %z", "%F %T %z"])"#,
+                result: Ok("t'2021-02-11T16:00:00Z'"),
+            },
+            Example {
+                title: "epoch milliseconds",
+                source: "parse_timestamp!(1613059200000)",
+                result: Ok("t'2021-02-11T16:00:00Z'"),
+            },
+        ]
     }
 
     fn compile(
@@ -39,7 +112,7 @@ impl Function for ParseTimestamp {
         mut arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        let format = arguments.required("format");
+        let format = arguments.optional("format");
 
         Ok(Box::new(ParseTimestampFn { value, format }))
     }
@@ -48,13 +121,13 @@ impl Function for ParseTimestamp {
         &[
             Parameter {
                 keyword: "value",
-                kind: kind::BYTES | kind::TIMESTAMP,
+                kind: kind::BYTES | kind::INTEGER | kind::FLOAT | kind::TIMESTAMP,
                 required: true,
             },
             Parameter {
                 keyword: "format",
-                kind: kind::BYTES,
-                required: true,
+                kind: kind::BYTES | kind::ARRAY,
+                required: false,
             },
         ]
     }
@@ -63,13 +136,17 @@ impl Function for ParseTimestamp {
 #[derive(Debug, Clone)]
 struct ParseTimestampFn {
     value: Box<dyn Expression>,
-    format: Box<dyn Expression>,
+    format: Option<Box<dyn Expression>>,
 }
 
 impl Expression for ParseTimestampFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        let format = self.format.resolve(ctx)?;
+        let format = self
+            .format
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
         parse_timestamp(value, format, ctx)
     }
 
@@ -80,8 +157,6 @@ impl Expression for ParseTimestampFn {
 
 #[cfg(test)]
 mod tests {
-    use chrono::{DateTime, Utc};
-
     use super::*;
 
     test_function![
@@ -130,5 +205,71 @@ mod tests {
             tdef: TypeDef::timestamp().fallible(),
             tz: vector_common::TimeZone::Named(chrono_tz::Europe::Paris),
         }
+
+        parse_text_formats {
+            args: func_args![
+                value: "2019-10-16 12:00:00 +0000",
+                format: value!(["%d/%m/%Y:%H:%M:%S %z", "%F %T %z"])
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
+
+        parse_text_no_format_matches {
+            args: func_args![
+                value: "foo",
+                format: value!(["%F %T %z", "%d/%m/%Y"])
+            ],
+            want: Err(r#"unable to parse timestamp "foo" with any of the given formats: Invalid timestamp "foo": input contains invalid characters"#),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
+
+        parse_text_without_format {
+            args: func_args![value: "16/10/2019:12:00:00"],
+            want: Err("format is required to parse a string into a timestamp"),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
+
+        parse_epoch_seconds {
+            args: func_args![value: 1571227200],
+            want: Ok(value!(Utc.ymd(2019, 10, 16).and_hms(12, 0, 0))),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
+
+        parse_epoch_seconds_float {
+            args: func_args![value: 1571227200.5],
+            want: Ok(value!(Utc.ymd(2019, 10, 16).and_hms_milli(12, 0, 0, 500))),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
+
+        parse_epoch_millis {
+            args: func_args![value: 1571227200123_i64],
+            want: Ok(value!(Utc.ymd(2019, 10, 16).and_hms_milli(12, 0, 0, 123))),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
+
+        parse_epoch_micros {
+            args: func_args![value: 1571227200123456_i64],
+            want: Ok(value!(Utc.ymd(2019, 10, 16).and_hms_micro(12, 0, 0, 123456))),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
+
+        parse_epoch_nanos {
+            args: func_args![value: 1571227200123456000_i64],
+            want: Ok(value!(Utc.ymd(2019, 10, 16).and_hms_micro(12, 0, 0, 123456))),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
     ];
 }
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use regex::Regex;
+use vrl::prelude::*;
+
+use crate::util::{path_patterns, path_with_field, path_with_index};
+
+fn remove_paths(value: Value, patterns: &[Regex]) -> Resolved {
+    match value {
+        value @ (Value::Object(_) | Value::Array(_)) => Ok(remove_matching(value, "", patterns)),
+        value => Err(value::Error::Expected {
+            got: value.kind(),
+            expected: Kind::array(Collection::any()) | Kind::object(Collection::any()),
+        }
+        .into()),
+    }
+}
+
+/// Removes every field or element whose path matches one of the patterns, along with
+/// everything nested below it.
+fn remove_matching(value: Value, path: &str, patterns: &[Regex]) -> Value {
+    let is_match = |path: &str| patterns.iter().any(|pattern| pattern.is_match(path));
+
+    match value {
+        Value::Object(map) => map
+            .into_iter()
+            .filter_map(|(field, value)| {
+                let path = path_with_field(path, &field);
+                (!is_match(&path)).then(|| (field, remove_matching(value, &path, patterns)))
+            })
+            .collect::<BTreeMap<_, _>>()
+            .into(),
+        Value::Array(array) => array
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, value)| {
+                let path = path_with_index(path, index);
+                (!is_match(&path)).then(|| remove_matching(value, &path, patterns))
+            })
+            .collect::<Vec<_>>()
+            .into(),
+        value => value,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RemovePaths;
+
+impl Function for RemovePaths {
+    fn identifier(&self) -> &'static str {
+        "remove_paths"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT | kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "patterns",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "glob",
+                source: r#"remove_paths!({"user": {"name": "foo", "password": "bar"}, "token": "baz"}, ["**password", "token"])"#,
+                result: Ok(r#"{"user": {"name": "foo"}}"#),
+            },
+            Example {
+                title: "regex",
+                source: r#"remove_paths!({"a": {"tmp_x": 1, "y": 2}}, [r'\.tmp_'])"#,
+                result: Ok(r#"{"a": {"y": 2}}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let patterns = arguments.required("patterns");
+
+        // Literal patterns are compiled once, instead of for every event.
+        let static_patterns = patterns
+            .as_value()
+            .map(|value| {
+                path_patterns(value.clone()).map_err(|_| vrl::function::Error::InvalidArgument {
+                    keyword: "patterns",
+                    value,
+                    error: "path patterns must be either strings or regexes",
+                })
+            })
+            .transpose()?;
+
+        Ok(Box::new(RemovePathsFn {
+            value,
+            patterns,
+            static_patterns,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RemovePathsFn {
+    value: Box<dyn Expression>,
+    patterns: Box<dyn Expression>,
+    static_patterns: Option<Vec<Regex>>,
+}
+
+impl Expression for RemovePathsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        match &self.static_patterns {
+            Some(patterns) => remove_paths(value, patterns),
+            None => {
+                let patterns = path_patterns(self.patterns.resolve(ctx)?)?;
+                remove_paths(value, &patterns)
+            }
+        }
+    }
+
+    fn type_def(&self, state: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let value_td = self.value.type_def(state);
+
+        let mut td = TypeDef::from(Kind::empty()).fallible();
+
+        if value_td.is_array() {
+            td = td.add_array(Collection::any())
+        };
+
+        if value_td.is_object() {
+            td = td.add_object(Collection::any())
+        };
+
+        td
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        remove_paths => RemovePaths;
+
+        field {
+            args: func_args![value: value!({"foo": {"bar": 1, "baz": 2}}), patterns: value!(["foo.bar"])],
+            want: Ok(value!({"foo": {"baz": 2}})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        subtree {
+            args: func_args![value: value!({"foo": {"bar": 1}, "baz": 2}), patterns: value!(["foo"])],
+            want: Ok(value!({"baz": 2})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        glob {
+            args: func_args![value: value!({"a": {"password": 1, "b": {"password": 2, "c": 3}}}), patterns: value!(["**password"])],
+            want: Ok(value!({"a": {"b": {"c": 3}}})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        array_elements {
+            args: func_args![value: value!({"foo": [{"id": 1, "tmp": true}, {"id": 2, "tmp": false}]}), patterns: value!(["foo[*].tmp"])],
+            want: Ok(value!({"foo": [{"id": 1}, {"id": 2}]})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        array_root {
+            args: func_args![value: value!([1, 2, 3]), patterns: value!(["[1]"])],
+            want: Ok(value!([1, 3])),
+            tdef: TypeDef::array(Collection::any()).fallible(),
+        }
+
+        no_match {
+            args: func_args![value: value!({"foo": 1}), patterns: value!(["bar"])],
+            want: Ok(value!({"foo": 1})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        invalid_pattern {
+            args: func_args![value: value!({"foo": 1}), patterns: value!([1])],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+    ];
+}
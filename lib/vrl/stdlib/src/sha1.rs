@@ -15,6 +15,10 @@ impl Function for Sha1 {
         "sha1"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[Parameter {
             keyword: "value",
@@ -35,6 +35,10 @@ impl Function for Sha2 {
         "sha2"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
@@ -31,6 +31,10 @@ impl Function for Sha3 {
         "sha3"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
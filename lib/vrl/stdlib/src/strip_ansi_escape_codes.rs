@@ -1,14 +1,153 @@
+use std::{collections::BTreeMap, iter::Peekable, str::Chars};
+
 use ::value::Value;
-use bytes::Bytes;
 use vrl::prelude::*;
 
-fn strip_ansi_escape_codes(bytes: Value) -> Resolved {
-    let bytes = bytes.try_bytes()?;
-    strip_ansi_escapes::strip(&bytes)
-        .map(Bytes::from)
-        .map(Value::from)
-        .map(Into::into)
-        .map_err(|e| e.to_string().into())
+/// The severity a line of output most likely has, based on the colors used to
+/// print it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Map a "select graphic rendition" foreground color to a severity.
+    fn from_sgr(code: u16) -> Option<Self> {
+        match code {
+            31 | 35 | 91 | 95 => Some(Severity::Error),
+            33 | 93 => Some(Severity::Warning),
+            32 | 34 | 36 | 92 | 94 | 96 => Some(Severity::Info),
+            90 => Some(Severity::Debug),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Severity::Debug => "debug",
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// Remove all escape sequences and control characters from the input, except
+/// for tabs and line breaks.
+///
+/// Returns the remaining text, and the most severe color used in the input.
+fn strip(input: &str) -> (String, Option<Severity>) {
+    let mut text = String::with_capacity(input.len());
+    let mut severity = None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x1b' => match chars.next() {
+                Some('[') => severity = severity.max(control_sequence(&mut chars)),
+                Some(']' | 'P' | 'X' | '^' | '_') => control_string(&mut chars),
+                // Sequences such as `ESC ( B` have intermediate bytes before
+                // their final byte.
+                Some('\x20'..='\x2f') => {
+                    while chars.next_if(|ch| matches!(ch, '\x20'..='\x2f')).is_some() {}
+                    chars.next();
+                }
+                // Any other byte completes a two byte sequence, such as `ESC 7`.
+                _ => {}
+            },
+            '\u{9b}' => severity = severity.max(control_sequence(&mut chars)),
+            '\u{90}' | '\u{98}' | '\u{9d}' | '\u{9e}' | '\u{9f}' => control_string(&mut chars),
+            '\t' | '\n' | '\r' => text.push(ch),
+            ch if ch.is_control() => {}
+            ch => text.push(ch),
+        }
+    }
+
+    (text, severity)
+}
+
+/// Skip a control sequence (`CSI`), such as a cursor movement or a color.
+///
+/// Returns the severity of the colors set by the sequence, if any.
+fn control_sequence(chars: &mut Peekable<Chars<'_>>) -> Option<Severity> {
+    let mut parameters = String::new();
+
+    while let Some(ch) = chars.next_if(|ch| matches!(ch, '\x20'..='\x3f')) {
+        parameters.push(ch);
+    }
+
+    // A malformed sequence ends at the first unexpected character, which is
+    // kept.
+    if chars.next_if(|ch| matches!(ch, '\x40'..='\x7e')) != Some('m') {
+        return None;
+    }
+
+    let mut codes = parameters
+        .split(|ch| ch == ';' || ch == ':')
+        .map(|code| code.parse::<u16>().unwrap_or_default());
+    let mut severity = None;
+
+    while let Some(code) = codes.next() {
+        match code {
+            // Extended colors are followed by a palette index, or by the red,
+            // green and blue components of the color.
+            38 | 48 | 58 => match codes.next() {
+                Some(5) => {
+                    codes.next();
+                }
+                Some(2) => {
+                    codes.nth(2);
+                }
+                _ => {}
+            },
+            code => severity = severity.max(Severity::from_sgr(code)),
+        }
+    }
+
+    severity
+}
+
+/// Skip a control string, such as an operating system command (`OSC`) setting
+/// the window title or a hyperlink, up to and including its terminator.
+fn control_string(chars: &mut Peekable<Chars<'_>>) {
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x07' | '\u{9c}' => return,
+            '\x1b' => {
+                chars.next_if_eq(&'\\');
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn strip_ansi_escape_codes(value: Value, severity_hint: bool) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let (text, severity) = strip(&String::from_utf8_lossy(&bytes));
+
+    if !severity_hint {
+        return Ok(text.into());
+    }
+
+    Ok(BTreeMap::from([
+        ("message".to_owned(), Value::from(text)),
+        (
+            "severity_hint".to_owned(),
+            severity.map(Severity::as_str).into(),
+        ),
+    ])
+    .into())
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("message".into(), Kind::bytes()),
+        ("severity_hint".into(), Kind::bytes().or_null()),
+    ])
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -20,11 +159,18 @@ impl Function for StripAnsiEscapeCodes {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "severity_hint",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
     }
 
     fn examples(&self) -> &'static [Example] {
@@ -38,28 +184,40 @@ impl Function for StripAnsiEscapeCodes {
         mut arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let severity_hint = arguments.optional("severity_hint");
 
-        Ok(Box::new(StripAnsiEscapeCodesFn { value }))
+        Ok(Box::new(StripAnsiEscapeCodesFn {
+            value,
+            severity_hint,
+        }))
     }
 }
 
 #[derive(Debug, Clone)]
 struct StripAnsiEscapeCodesFn {
     value: Box<dyn Expression>,
+    severity_hint: Option<Box<dyn Expression>>,
 }
 
 impl Expression for StripAnsiEscapeCodesFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let bytes = self.value.resolve(ctx)?;
+        let severity_hint = match &self.severity_hint {
+            Some(expr) => expr.resolve(ctx)?.try_boolean()?,
+            None => false,
+        };
 
-        strip_ansi_escape_codes(bytes)
+        strip_ansi_escape_codes(bytes, severity_hint)
     }
 
     fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
-        // We're marking this as infallible, because `strip_ansi_escapes` only
-        // fails if it can't write to the buffer, which is highly unlikely to
-        // occur.
-        TypeDef::bytes().infallible()
+        // The returned type is only known up front if the `severity_hint`
+        // argument is static.
+        match self.severity_hint.as_ref().map(|expr| expr.as_value()) {
+            None | Some(Some(Value::Boolean(false))) => TypeDef::bytes().infallible(),
+            Some(Some(Value::Boolean(true))) => TypeDef::object(inner_kind()).infallible(),
+            Some(_) => TypeDef::bytes().add_object(inner_kind()).infallible(),
+        }
     }
 }
 
@@ -93,5 +251,41 @@ mod tests {
             want: Ok("foo bar"),
             tdef: TypeDef::bytes().infallible(),
         }
+
+        strip_osc {
+            args: func_args![value: "\x1b]0;title\x07foo \x1b]8;;https://vector.dev\x1b\\bar\x1b]8;;\x1b\\"],
+            want: Ok("foo bar"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        strip_cursor_control {
+            args: func_args![value: "\x1b7\x1b[2K\x1b[1Gfoo\x1b8\x1b(B bar\x1b[?25h"],
+            want: Ok("foo bar"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        strip_control_characters {
+            args: func_args![value: "foo\x08\x00\u{9b}1m\tbar\r\n"],
+            want: Ok("foo\tbar\r\n"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        severity_hint {
+            args: func_args![value: "\x1b[32m2022-06-01\x1b[0m \x1b[1;31mERROR\x1b[0m failed", severity_hint: true],
+            want: Ok(value!({ "message": "2022-06-01 ERROR failed", "severity_hint": "error" })),
+            tdef: TypeDef::object(inner_kind()).infallible(),
+        }
+
+        severity_hint_extended_color {
+            args: func_args![value: "\x1b[38;5;31mfoo\x1b[33m bar", severity_hint: true],
+            want: Ok(value!({ "message": "foo bar", "severity_hint": "warning" })),
+            tdef: TypeDef::object(inner_kind()).infallible(),
+        }
+
+        severity_hint_no_color {
+            args: func_args![value: "\x1b[1mfoo bar", severity_hint: true],
+            want: Ok(value!({ "message": "foo bar", "severity_hint": null })),
+            tdef: TypeDef::object(inner_kind()).infallible(),
+        }
     ];
 }
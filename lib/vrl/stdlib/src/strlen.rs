@@ -15,6 +15,10 @@ impl Function for Strlen {
         "strlen"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[Parameter {
             keyword: "value",
@@ -0,0 +1,136 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+pub(crate) fn to_syslog_facility_code(facility: Value) -> Resolved {
+    let facility = facility.try_bytes_utf8_lossy()?;
+    // Facility codes: https://en.wikipedia.org/wiki/Syslog#Facility
+    let code = match &facility[..] {
+        "kern" => 0,
+        "user" => 1,
+        "mail" => 2,
+        "daemon" => 3,
+        "auth" => 4,
+        "syslog" => 5,
+        "lpr" => 6,
+        "news" => 7,
+        "uucp" => 8,
+        "cron" => 9,
+        "authpriv" => 10,
+        "ftp" => 11,
+        "ntp" => 12,
+        "security" => 13,
+        "console" => 14,
+        "solaris-cron" => 15,
+        "local0" => 16,
+        "local1" => 17,
+        "local2" => 18,
+        "local3" => 19,
+        "local4" => 20,
+        "local5" => 21,
+        "local6" => 22,
+        "local7" => 23,
+        _ => return Err(format!("syslog facility {} not valid", facility).into()),
+    };
+    Ok(code.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ToSyslogFacilityCode;
+
+impl Function for ToSyslogFacilityCode {
+    fn identifier(&self) -> &'static str {
+        "to_syslog_facility_code"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid",
+                source: "to_syslog_facility_code!(s'local0')",
+                result: Ok("16"),
+            },
+            Example {
+                title: "invalid",
+                source: "to_syslog_facility_code!(s'foobar')",
+                result: Err(
+                    r#"function call error for "to_syslog_facility_code" at (0:35): syslog facility foobar not valid"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ToSyslogFacilityCodeFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ToSyslogFacilityCodeFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ToSyslogFacilityCodeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let facility = self.value.resolve(ctx)?;
+
+        to_syslog_facility_code(facility)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        to_syslog_facility_code => ToSyslogFacilityCode;
+
+        kern {
+            args: func_args![value: value!("kern")],
+            want: Ok(value!(0)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        auth {
+            args: func_args![value: value!("auth")],
+            want: Ok(value!(4)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        solaris_cron {
+            args: func_args![value: value!("solaris-cron")],
+            want: Ok(value!(15)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        local7 {
+            args: func_args![value: value!("local7")],
+            want: Ok(value!(23)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        invalid_facility {
+            args: func_args![value: value!("oopsie")],
+            want: Err("syslog facility oopsie not valid"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
@@ -1,7 +1,7 @@
 use ::value::Value;
 use vrl::prelude::*;
 
-fn to_syslog_severity(level: Value) -> Resolved {
+pub(crate) fn to_syslog_severity(level: Value) -> Resolved {
     let level = level.try_bytes_utf8_lossy()?;
     // Severity levels: https://en.wikipedia.org/wiki/Syslog#Severity_level
     let severity = match &level[..] {
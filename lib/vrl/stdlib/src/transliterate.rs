@@ -0,0 +1,107 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn transliterate(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+
+    Ok(deunicode::deunicode(&String::from_utf8_lossy(&bytes)).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Transliterate;
+
+impl Function for Transliterate {
+    fn identifier(&self) -> &'static str {
+        "transliterate"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "cyrillic",
+                source: r#"transliterate("Жизнь прекрасна")"#,
+                result: Ok("Zhizn' prekrasna"),
+            },
+            Example {
+                title: "accents",
+                source: r#"transliterate("Ærøskøbing straße")"#,
+                result: Ok("AEroskobing strasse"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(TransliterateFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TransliterateFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for TransliterateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        transliterate(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        transliterate => Transliterate;
+
+        ascii {
+            args: func_args![value: "foo bar"],
+            want: Ok("foo bar"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        cyrillic {
+            args: func_args![value: "Жизнь прекрасна"],
+            want: Ok("Zhizn' prekrasna"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        greek {
+            args: func_args![value: "Αθήνα"],
+            want: Ok("Athena"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        accents {
+            args: func_args![value: "café naïve Ærøskøbing straße"],
+            want: Ok("cafe naive AEroskobing strasse"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        chinese {
+            args: func_args![value: "北京"],
+            want: Ok("Bei Jing"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+/// Returns the structural type signature of a value. Scalars are replaced by the name of
+/// their type, objects keep their fields and arrays hold the distinct signatures of their
+/// elements, in the order they first appear, so that the signature doesn't depend on the
+/// number of elements.
+fn type_of(value: Value) -> Value {
+    match value {
+        Value::Bytes(_) => "string".into(),
+        Value::Integer(_) => "integer".into(),
+        Value::Float(_) => "float".into(),
+        Value::Boolean(_) => "boolean".into(),
+        Value::Timestamp(_) => "timestamp".into(),
+        Value::Regex(_) => "regex".into(),
+        Value::Null => "null".into(),
+        Value::Object(object) => object
+            .into_iter()
+            .map(|(key, value)| (key, type_of(value)))
+            .collect::<BTreeMap<String, Value>>()
+            .into(),
+        Value::Array(array) => {
+            let mut signatures = Vec::new();
+            for signature in array.into_iter().map(type_of) {
+                if !signatures.contains(&signature) {
+                    signatures.push(signature);
+                }
+            }
+
+            signatures.into()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TypeOf;
+
+impl Function for TypeOf {
+    fn identifier(&self) -> &'static str {
+        "type_of"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "scalar",
+                source: "type_of(123)",
+                result: Ok("integer"),
+            },
+            Example {
+                title: "object",
+                source: r#"type_of({"a": "foo", "b": [1, 2, 3], "c": {"d": null}})"#,
+                result: Ok(r#"{ "a": "string", "b": ["integer"], "c": { "d": "null" } }"#),
+            },
+            Example {
+                title: "mixed array",
+                source: r#"type_of([1, "foo", 2, {"a": true}])"#,
+                result: Ok(r#"["integer", "string", { "a": "boolean" }]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(TypeOfFn { value }))
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ANY,
+            required: true,
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TypeOfFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for TypeOfFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(type_of(value))
+    }
+
+    fn type_def(&self, state: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let value_td = self.value.type_def(state);
+        let kind = value_td.kind();
+
+        let mut td = TypeDef::from(Kind::empty());
+
+        if kind.contains_bytes()
+            || kind.contains_integer()
+            || kind.contains_float()
+            || kind.contains_boolean()
+            || kind.contains_timestamp()
+            || kind.contains_regex()
+            || kind.contains_null()
+        {
+            td = td.add_bytes();
+        }
+
+        if kind.contains_array() {
+            td = td.add_array(Collection::any());
+        }
+
+        if kind.contains_object() {
+            td = td.add_object(Collection::any());
+        }
+
+        td
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use regex::Regex;
+
+    use super::*;
+
+    test_function![
+        type_of => TypeOf;
+
+        string {
+            args: func_args![value: "foo"],
+            want: Ok("string"),
+            tdef: TypeDef::bytes(),
+        }
+
+        integer {
+            args: func_args![value: 123],
+            want: Ok("integer"),
+            tdef: TypeDef::bytes(),
+        }
+
+        float {
+            args: func_args![value: 123.45],
+            want: Ok("float"),
+            tdef: TypeDef::bytes(),
+        }
+
+        boolean {
+            args: func_args![value: true],
+            want: Ok("boolean"),
+            tdef: TypeDef::bytes(),
+        }
+
+        timestamp {
+            args: func_args![value: Utc.ymd(2021, 1, 1).and_hms_milli(0, 0, 0, 0)],
+            want: Ok("timestamp"),
+            tdef: TypeDef::bytes(),
+        }
+
+        regex {
+            args: func_args![value: Regex::new(".*").unwrap()],
+            want: Ok("regex"),
+            tdef: TypeDef::bytes(),
+        }
+
+        null {
+            args: func_args![value: Value::Null],
+            want: Ok("null"),
+            tdef: TypeDef::bytes(),
+        }
+
+        object {
+            args: func_args![value: value!({"a": "foo", "b": [1, 2, 3], "c": {"d": null}})],
+            want: Ok(value!({"a": "string", "b": ["integer"], "c": {"d": "null"}})),
+            tdef: TypeDef::object(Collection::any()),
+        }
+
+        array_deduplicated {
+            args: func_args![value: value!([1, "foo", 2, {"a": true}, {"a": false}, {"a": 1}])],
+            want: Ok(value!(["integer", "string", {"a": "boolean"}, {"a": "integer"}])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        empty_array {
+            args: func_args![value: value!([])],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+    ];
+}
@@ -13,6 +13,10 @@ impl Function for Upcase {
         "upcase"
     }
 
+    fn pure(&self) -> bool {
+        true
+    }
+
     fn examples(&self) -> &'static [Example] {
         &[Example {
             title: "upcase",
@@ -122,3 +122,105 @@ impl std::str::FromStr for Base64Charset {
         }
     }
 }
+
+/// Reads the literal `non_finite_floats` argument of the JSON functions, which sets how floats
+/// that aren't finite are handled.
+#[cfg(any(feature = "encode_json", feature = "parse_json"))]
+pub(crate) fn non_finite_floats(
+    arguments: &mut vrl::function::ArgumentList,
+    default: ::value::json::NonFiniteFloats,
+) -> Result<::value::json::NonFiniteFloats, vrl::function::Error> {
+    use ::value::json::NonFiniteFloats;
+
+    let variants = NonFiniteFloats::ALL
+        .iter()
+        .map(|variant| ::value::Value::from(variant.as_str()))
+        .collect::<Vec<_>>();
+
+    Ok(arguments
+        .optional_enum("non_finite_floats", &variants)?
+        .map(|value| {
+            String::from_utf8_lossy(&value.try_bytes().expect("non_finite_floats not bytes"))
+                .parse()
+                .expect("validated enum")
+        })
+        .unwrap_or(default))
+}
+
+/// Appends a field segment to a path string, quoting the field if it isn't a plain
+/// identifier, i.e. `foo.bar` or `foo."bar baz"`.
+#[cfg(any(
+    feature = "get_paths",
+    feature = "only_fields",
+    feature = "remove_paths"
+))]
+pub(crate) fn path_with_field(path: &str, field: &str) -> String {
+    let plain = !field.is_empty()
+        && field
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '@');
+
+    match (path.is_empty(), plain) {
+        (true, true) => field.to_owned(),
+        (true, false) => format!("{:?}", field),
+        (false, true) => format!("{}.{}", path, field),
+        (false, false) => format!("{}.{:?}", path, field),
+    }
+}
+
+/// Appends an index segment to a path string, i.e. `foo[0]`.
+#[cfg(any(
+    feature = "get_paths",
+    feature = "only_fields",
+    feature = "remove_paths"
+))]
+pub(crate) fn path_with_index(path: &str, index: usize) -> String {
+    format!("{}[{}]", path, index)
+}
+
+/// Compiles a pattern matching path strings. A regex is used as-is, while a string is
+/// taken as a glob matching the whole path, where `*` matches within a single segment,
+/// `**` matches across segments and `?` matches a single character.
+#[cfg(any(
+    feature = "match_path",
+    feature = "only_fields",
+    feature = "remove_paths"
+))]
+pub(crate) fn path_pattern(pattern: &::value::Value) -> Result<regex::Regex, String> {
+    match pattern {
+        ::value::Value::Bytes(glob) => {
+            let glob = String::from_utf8_lossy(glob);
+            let mut chars = glob.chars().peekable();
+            let mut re = String::from("^");
+
+            while let Some(c) = chars.next() {
+                match c {
+                    '*' if chars.peek() == Some(&'*') => {
+                        chars.next();
+                        re.push_str(".*");
+                    }
+                    '*' => re.push_str(r"[^.\[\]]*"),
+                    '?' => re.push_str(r"[^.\[\]]"),
+                    c => re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+                }
+            }
+            re.push('$');
+
+            regex::Regex::new(&re).map_err(|err| err.to_string())
+        }
+        ::value::Value::Regex(regex) => Ok((**regex).clone()),
+        value => Err(format!(
+            "path pattern must be either string or regex, not {}",
+            value.kind()
+        )),
+    }
+}
+
+/// Compiles an array of patterns, as accepted by [`path_pattern`].
+#[cfg(any(feature = "only_fields", feature = "remove_paths"))]
+pub(crate) fn path_patterns(patterns: ::value::Value) -> Result<Vec<regex::Regex>, String> {
+    match patterns {
+        ::value::Value::Array(patterns) => patterns.iter().map(path_pattern).collect(),
+        value => Err(format!("patterns must be an array, not {}", value.kind())),
+    }
+}
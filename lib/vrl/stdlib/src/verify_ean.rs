@@ -0,0 +1,144 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+/// Check whether the given number is a valid EAN-8, UPC-A (12 digits), EAN-13
+/// or GTIN-14 code, by verifying its trailing check digit.
+fn is_ean_valid(bytes: &[u8]) -> bool {
+    if !matches!(bytes.len(), 8 | 12 | 13 | 14) || !bytes.iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+
+    // Starting from the check digit, digits are alternately weighted 1 and 3.
+    let sum: u32 = bytes
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, byte)| {
+            let digit = u32::from(byte - b'0');
+            if i % 2 == 1 {
+                digit * 3
+            } else {
+                digit
+            }
+        })
+        .sum();
+
+    sum % 10 == 0
+}
+
+fn verify_ean(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+
+    Ok(is_ean_valid(&value).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyEan;
+
+impl Function for VerifyEan {
+    fn identifier(&self) -> &'static str {
+        "verify_ean"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid EAN-13",
+                source: r#"verify_ean("4006381333931")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "invalid check digit",
+                source: r#"verify_ean("4006381333932")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(VerifyEanFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VerifyEanFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for VerifyEanFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        verify_ean(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::boolean().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        verify_ean => VerifyEan;
+
+        ean13 {
+            args: func_args![value: "4006381333931"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        ean8 {
+            args: func_args![value: "73513537"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        upc_a {
+            args: func_args![value: "036000291452"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        gtin14 {
+            args: func_args![value: "10614141000415"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        invalid_check_digit {
+            args: func_args![value: "4006381333932"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        invalid_length {
+            args: func_args![value: "12345"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        invalid_character {
+            args: func_args![value: "400638133393a"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+    ];
+}
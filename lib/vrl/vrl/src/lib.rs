@@ -6,6 +6,9 @@
 #![deny(unused_comparisons)]
 #![allow(clippy::module_name_repetitions)]
 
+use std::path::Path;
+
+mod module;
 pub mod prelude;
 mod runtime;
 
@@ -13,6 +16,7 @@ pub use compiler::{
     function, state, value, Context, Expression, Function, Program, ProgramInfo, Target, VrlRuntime,
 };
 pub use diagnostic;
+pub use parser;
 pub use runtime::{Runtime, RuntimeResult, Terminate};
 
 /// Compile a given source into the final [`Program`].
@@ -33,6 +37,31 @@ pub fn compile_with_state(
     compiler::compile_with_state(ast, fns, state)
 }
 
+/// Compile a given source into the final [`Program`], resolving any `import`
+/// statements relative to the given directory.
+pub fn compile_with_imports(
+    source: &str,
+    fns: &[Box<dyn Function>],
+    state: &mut state::ExternalEnv,
+    directory: &Path,
+) -> compiler::Result {
+    let ast = parser::parse(source)
+        .map_err(|err| diagnostic::DiagnosticList::from(vec![Box::new(err) as Box<_>]))?;
+    let ast = module::resolve(ast, directory).map_err(diagnostic::DiagnosticList::from)?;
+
+    compiler::compile_with_state(ast, fns, state)
+}
+
+/// Create a formatter for the diagnostics of a program compiled with
+/// [`compile_with_imports`], which renders diagnostics within imported modules
+/// against the source of those modules.
+pub fn formatter_with_imports(
+    source: &str,
+    diagnostics: impl Into<diagnostic::DiagnosticList>,
+) -> diagnostic::Formatter<'_> {
+    module::formatter(source, diagnostics)
+}
+
 pub fn compile_for_repl(
     source: &str,
     fns: &[Box<dyn Function>],
@@ -0,0 +1,367 @@
+//! Resolution of `import` statements.
+//!
+//! Imports are resolved before a program is compiled, by splicing the
+//! function definitions, constants and variable assignments of each imported
+//! module into the importing program, in place of the `import` statement.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::SystemTime,
+};
+
+use diagnostic::{DiagnosticList, DiagnosticMessage, Formatter, Label, Note, Span};
+use once_cell::sync::Lazy;
+use parser::ast::{self, Node, RootExpr};
+
+/// Modules that were parsed before, keyed by their canonical path.
+///
+/// A module is only parsed again once its file is modified, which allows many
+/// programs to share the same modules without parsing them for every program.
+static MODULES: Lazy<Mutex<HashMap<PathBuf, (SystemTime, Arc<Module>)>>> =
+    Lazy::new(Default::default);
+
+/// The offset at which the spans of the next parsed module start.
+///
+/// Each module gets its own range of spans, after those of any program, so that
+/// diagnostics can be rendered against the source they refer to.
+static NEXT_OFFSET: AtomicUsize = AtomicUsize::new(usize::MAX / 2);
+
+/// A parsed module.
+struct Module {
+    /// The offset at which the spans of the module start.
+    offset: usize,
+    source: String,
+    items: Vec<Item>,
+}
+
+impl Module {
+    fn contains(&self, span: Span) -> bool {
+        (self.offset..=self.offset + self.source.len()).contains(&span.start())
+    }
+}
+
+/// A root expression that is allowed within a module.
+#[derive(Clone)]
+enum Item {
+    Import(Node<ast::Import>),
+    FunctionDefinition(Node<ast::FunctionDefinition>),
+    ConstDefinition(Node<ast::ConstDefinition>),
+    Assignment(Node<ast::Expr>),
+}
+
+impl From<Item> for Node<RootExpr> {
+    fn from(item: Item) -> Self {
+        match item {
+            Item::Import(node) => Node::new(node.span(), RootExpr::Import(node)),
+            Item::FunctionDefinition(node) => {
+                Node::new(node.span(), RootExpr::FunctionDefinition(node))
+            }
+            Item::ConstDefinition(node) => Node::new(node.span(), RootExpr::ConstDefinition(node)),
+            Item::Assignment(node) => Node::new(node.span(), RootExpr::Expr(node)),
+        }
+    }
+}
+
+/// Create a formatter for the diagnostics of a program, which renders
+/// diagnostics within imported modules against the source of those modules.
+pub(crate) fn formatter(source: &str, diagnostics: impl Into<DiagnosticList>) -> Formatter<'_> {
+    let formatter = Formatter::new(source, diagnostics);
+    let spans = formatter
+        .diagnostics()
+        .iter()
+        .flat_map(|diagnostic| diagnostic.labels().iter().map(|label| label.span))
+        .collect::<Vec<_>>();
+
+    let modules = MODULES.lock().expect("poisoned lock");
+    modules
+        .iter()
+        .filter(|(_, (_, module))| spans.iter().any(|span| module.contains(*span)))
+        .fold(formatter, |formatter, (path, (_, module))| {
+            formatter.with_file(path.display().to_string(), module.offset, &module.source)
+        })
+}
+
+/// Resolve all imports of the given program, relative to `directory`.
+pub(crate) fn resolve(
+    program: parser::Program,
+    directory: &Path,
+) -> Result<parser::Program, Vec<Box<dyn DiagnosticMessage>>> {
+    let mut resolver = Resolver::default();
+    let mut root_exprs = vec![];
+
+    for node in program {
+        resolver.resolve(node, directory, &mut root_exprs);
+    }
+
+    if resolver.errors.is_empty() {
+        Ok(parser::Program(root_exprs))
+    } else {
+        Err(resolver.errors)
+    }
+}
+
+#[derive(Default)]
+struct Resolver {
+    /// The modules that are already part of the program.
+    ///
+    /// Each module is included once, even if it's imported multiple times.
+    included: HashSet<PathBuf>,
+
+    /// The chain of modules currently being resolved, used to detect circular
+    /// imports.
+    stack: Vec<PathBuf>,
+
+    errors: Vec<Box<dyn DiagnosticMessage>>,
+}
+
+impl Resolver {
+    fn resolve(&mut self, node: Node<RootExpr>, directory: &Path, out: &mut Vec<Node<RootExpr>>) {
+        let span = node.span();
+
+        let import = match node.into_inner() {
+            RootExpr::Import(import) => import,
+            root_expr => {
+                out.push(Node::new(span, root_expr));
+                return;
+            }
+        };
+
+        if let Err(error) = self.import(import, directory, out) {
+            self.errors.push(Box::new(error));
+        }
+    }
+
+    fn import(
+        &mut self,
+        import: Node<ast::Import>,
+        directory: &Path,
+        out: &mut Vec<Node<RootExpr>>,
+    ) -> Result<(), Error> {
+        let span = import.span();
+        let path = directory.join(import.into_inner().path.into_inner());
+        let path = fs::canonicalize(&path).map_err(|source| Error::Read {
+            span,
+            path: path.clone(),
+            source,
+        })?;
+
+        if self.stack.contains(&path) {
+            return Err(Error::Circular { span, path });
+        }
+
+        if !self.included.insert(path.clone()) {
+            return Ok(());
+        }
+
+        let module = load(&path).map_err(|variant| variant.into_error(span, path.clone()))?;
+        let directory = path.parent().unwrap_or_else(|| Path::new("")).to_owned();
+
+        self.stack.push(path);
+        for item in module.items.iter().cloned() {
+            self.resolve(item.into(), &directory, out);
+        }
+        self.stack.pop();
+
+        Ok(())
+    }
+}
+
+/// Load the module at the given canonical path, either from the cache, or by
+/// parsing the module.
+fn load(path: &Path) -> Result<Arc<Module>, LoadError> {
+    let modified = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(LoadError::Read)?;
+
+    let mut modules = MODULES.lock().expect("poisoned lock");
+    if let Some((cached, module)) = modules.get(path) {
+        if *cached == modified {
+            return Ok(Arc::clone(module));
+        }
+    }
+
+    let source = fs::read_to_string(path).map_err(LoadError::Read)?;
+    let offset = NEXT_OFFSET.fetch_add(source.len() + 1, Ordering::Relaxed);
+    let program = parser::parse_with_offset(&source, offset)
+        .map_err(|error| LoadError::parse(path, offset, &source, error))?;
+
+    let items = program
+        .into_iter()
+        .map(|node| {
+            let span = node.span();
+
+            match node.into_inner() {
+                RootExpr::Import(node) => Ok(Item::Import(node)),
+                RootExpr::FunctionDefinition(node) => Ok(Item::FunctionDefinition(node)),
+                RootExpr::ConstDefinition(node) => Ok(Item::ConstDefinition(node)),
+                RootExpr::Expr(node) if matches!(node.inner(), ast::Expr::Assignment(_)) => {
+                    Ok(Item::Assignment(node))
+                }
+                RootExpr::Expr(_) => Err(LoadError::Item(Span::new(
+                    span.start() - offset,
+                    span.end() - offset,
+                ))),
+                RootExpr::Error(error) => Err(LoadError::parse(path, offset, &source, error)),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let module = Arc::new(Module {
+        offset,
+        source,
+        items,
+    });
+    modules.insert(path.to_owned(), (modified, Arc::clone(&module)));
+
+    Ok(module)
+}
+
+enum LoadError {
+    Read(io::Error),
+    Parse(String),
+    Item(Span),
+}
+
+impl LoadError {
+    /// Render the parse error against the source of the module, as the spans
+    /// of the error don't apply to the importing program.
+    fn parse(path: &Path, offset: usize, source: &str, error: parser::Error) -> Self {
+        let diagnostics = vec![Box::new(error) as Box<dyn DiagnosticMessage>];
+
+        LoadError::Parse(
+            Formatter::new("", diagnostics)
+                .with_file(path.display().to_string(), offset, source)
+                .to_string(),
+        )
+    }
+
+    fn into_error(self, span: Span, path: PathBuf) -> Error {
+        match self {
+            LoadError::Read(source) => Error::Read { span, path, source },
+            LoadError::Parse(diagnostics) => Error::Parse {
+                span,
+                path,
+                diagnostics,
+            },
+            LoadError::Item(item_span) => Error::Item {
+                span,
+                path,
+                item_span,
+            },
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub(crate) enum Error {
+    Read {
+        span: Span,
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        span: Span,
+        path: PathBuf,
+        diagnostics: String,
+    },
+    Item {
+        span: Span,
+        path: PathBuf,
+        item_span: Span,
+    },
+    Circular {
+        span: Span,
+        path: PathBuf,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Error::*;
+
+        match self {
+            Read { .. } => f.write_str("unable to read module"),
+            Parse { .. } => f.write_str("module contains syntax errors"),
+            Item { .. } => f.write_str("unsupported module expression"),
+            Circular { .. } => f.write_str("circular module import"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Read { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use Error::*;
+
+        match self {
+            Read { .. } => 115,
+            Parse { .. } => 117,
+            Item { .. } => 118,
+            Circular { .. } => 119,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::*;
+
+        match self {
+            Read { span, path, source } => vec![
+                Label::primary(format!("unable to read module {:?}", path), span),
+                Label::context(source.to_string(), span),
+            ],
+            Parse { span, path, .. } => vec![Label::primary(
+                format!("module {:?} can't be parsed", path),
+                span,
+            )],
+            Item {
+                span,
+                path,
+                item_span,
+            } => vec![
+                Label::primary(
+                    format!(
+                        "module {:?} contains an unsupported expression at ({}:{})",
+                        path,
+                        item_span.start(),
+                        item_span.end()
+                    ),
+                    span,
+                ),
+                Label::context(
+                    "modules can only contain imports, function definitions, constants and variable assignments",
+                    span,
+                ),
+            ],
+            Circular { span, path } => vec![Label::primary(
+                format!("module {:?} is already being imported", path),
+                span,
+            )],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        match self {
+            Error::Parse { diagnostics, .. } => diagnostics
+                .lines()
+                .map(|line| Note::Basic(line.to_owned()))
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
@@ -93,6 +93,13 @@ impl Runtime {
         program.resolve(&mut ctx).map_err(|err| match err {
             #[cfg(feature = "expr-abort")]
             ExpressionError::Abort { .. } => Terminate::Abort(err),
+            #[cfg(feature = "expr-for_each")]
+            err @ (ExpressionError::Break { .. } | ExpressionError::Continue { .. }) => {
+                Terminate::Error(err)
+            }
+            // Returned values are resolved by the program itself.
+            #[cfg(feature = "expr-return")]
+            err @ ExpressionError::Return { .. } => Terminate::Error(err),
             err @ ExpressionError::Error { .. } => Terminate::Error(err),
         })
     }
@@ -3,12 +3,55 @@ use std::sync::{
     Arc,
 };
 
-use serde_json::json;
+use serde_json::{json, Map, Value};
 use warp::{reply::json, Rejection, Reply};
 
-// Health handler, responds with '{ ok: true }' when running and '{ ok: false}'
-// when shutting down
-pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
+use crate::{config::api::HealthPolicy, topology::health};
+
+// Health handler, responds with '{ ok: true }' when running and none of the readiness policies is
+// violated, and '{ ok: false }' otherwise, along with the health of every component and the
+// reasons Vector isn't ready
+pub(super) async fn health(
+    running: Arc<AtomicBool>,
+    policies: Arc<Vec<HealthPolicy>>,
+) -> Result<impl Reply, Rejection> {
+    let mut reasons = Vec::new();
+    if !running.load(atomic::Ordering::Relaxed) {
+        reasons.push("Vector is shutting down.".to_owned());
+    }
+    if let Err(violations) = health::check_readiness(&policies) {
+        reasons.extend(violations);
+    }
+
+    let components: Map<String, Value> = health::components()
+        .into_iter()
+        .map(|component| {
+            (
+                component.key.id().to_owned(),
+                json!({
+                    "kind": component.kind,
+                    "state": component.state,
+                    "since": component.since,
+                    "reason": component.reason,
+                }),
+            )
+        })
+        .collect();
+
+    let ok = reasons.is_empty();
+    Ok(warp::reply::with_status(
+        json(&json!({"ok": ok, "components": components, "reasons": reasons})),
+        if ok {
+            warp::http::StatusCode::OK
+        } else {
+            warp::http::StatusCode::SERVICE_UNAVAILABLE
+        },
+    ))
+}
+
+// Liveness handler, responds with '{ ok: true }' when running and '{ ok: false}'
+// when shutting down, regardless of the health of the components
+pub(super) async fn live(running: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
     if running.load(atomic::Ordering::Relaxed) {
         Ok(warp::reply::with_status(
             json(&json!({"ok": true})),
@@ -1,4 +1,6 @@
 mod handler;
+#[cfg(feature = "api-profiling")]
+mod profiling;
 mod schema;
 mod server;
 pub mod tap;
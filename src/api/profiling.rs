@@ -0,0 +1,206 @@
+//! CPU and heap profiles of the running instance, served under `/debug/pprof`. CPU profiles are
+//! in the protobuf format of `pprof`, and heap profiles in the format of jemalloc's `jeprof`.
+
+use std::{
+    convert::Infallible,
+    ffi::CString,
+    fs,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use hyper::Body;
+use pprof::protos::Message;
+use serde::Deserialize;
+use warp::{
+    filters::BoxedFilter,
+    http::{Response, StatusCode},
+    Filter,
+};
+
+use crate::config::api::Options;
+
+/// Longest CPU profile that can be requested, in seconds.
+const MAX_SECONDS: u64 = 300;
+
+/// Highest sampling frequency of CPU profiles, in Hz.
+const MAX_FREQUENCY: i32 = 1000;
+
+/// Whether a CPU profile is being taken. The profiler samples the whole process, so only one
+/// profile can be taken at a time.
+static CPU_PROFILING: AtomicBool = AtomicBool::new(false);
+
+/// Sequence number of heap profiles, to name their files.
+static HEAP_PROFILES: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Deserialize)]
+struct CpuProfileQuery {
+    #[serde(default = "default_seconds")]
+    seconds: u64,
+    #[serde(default = "default_frequency")]
+    frequency: i32,
+}
+
+const fn default_seconds() -> u64 {
+    30
+}
+
+const fn default_frequency() -> i32 {
+    99
+}
+
+/// Returns the profiling routes, which answer only when profiling is enabled and requests hold
+/// the token of `api.auth`. Enabling profiling activates heap profiling in jemalloc.
+pub(super) fn routes(options: &Options) -> BoxedFilter<(Response<Body>,)> {
+    let token = match (options.profiling, &options.auth.token) {
+        (true, Some(token)) => token.clone(),
+        (true, None) => {
+            warn!(message = "Profiling endpoints are disabled, as `api.auth.token` isn't set.");
+            return not_found();
+        }
+        (false, _) => return not_found(),
+    };
+
+    // SAFETY: `prof.active` takes a bool.
+    if let Err(error) = unsafe { tikv_jemalloc_ctl::raw::write(b"prof.active\0", true) } {
+        warn!(message = "Couldn't activate heap profiling.", %error);
+    }
+
+    let expected = format!("Bearer {}", token);
+    let authorized = warp::header::optional::<String>("authorization")
+        .map(move |header: Option<String>| header.as_deref() == Some(expected.as_str()));
+
+    let cpu = warp::path!("debug" / "pprof" / "profile")
+        .and(warp::get())
+        .and(authorized.clone())
+        .and(warp::query::<CpuProfileQuery>())
+        .and_then(cpu_profile);
+    let heap = warp::path!("debug" / "pprof" / "heap")
+        .and(warp::get())
+        .and(authorized)
+        .and_then(heap_profile);
+
+    cpu.or(heap).unify().boxed()
+}
+
+fn not_found() -> BoxedFilter<(Response<Body>,)> {
+    warp::any()
+        .and_then(|| async { Err(warp::reject::not_found()) })
+        .boxed()
+}
+
+async fn cpu_profile(
+    authorized: bool,
+    query: CpuProfileQuery,
+) -> Result<Response<Body>, Infallible> {
+    if !authorized {
+        return Ok(error_response(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid token.",
+        ));
+    }
+    if query.seconds == 0 || query.seconds > MAX_SECONDS {
+        return Ok(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("`seconds` must be between 1 and {}.", MAX_SECONDS),
+        ));
+    }
+    if query.frequency <= 0 || query.frequency > MAX_FREQUENCY {
+        return Ok(error_response(
+            StatusCode::BAD_REQUEST,
+            format!("`frequency` must be between 1 and {}.", MAX_FREQUENCY),
+        ));
+    }
+    if CPU_PROFILING.swap(true, Ordering::AcqRel) {
+        return Ok(error_response(
+            StatusCode::CONFLICT,
+            "A CPU profile is already being taken.",
+        ));
+    }
+
+    // The profile is taken on a blocking thread, which also clears the flag, so that a client
+    // going away doesn't allow a second profile to start before the first one is done.
+    let result = tokio::task::spawn_blocking(move || {
+        let result = take_cpu_profile(&query).map_err(|error| error.to_string());
+        CPU_PROFILING.store(false, Ordering::Release);
+        result
+    })
+    .await;
+
+    Ok(match result {
+        Ok(Ok(profile)) => attachment(profile, "profile.pb"),
+        Ok(Err(message)) => error_response(StatusCode::INTERNAL_SERVER_ERROR, message),
+        Err(join_error) => {
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, join_error.to_string())
+        }
+    })
+}
+
+fn take_cpu_profile(query: &CpuProfileQuery) -> Result<Vec<u8>, pprof::Error> {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(query.frequency)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+    std::thread::sleep(Duration::from_secs(query.seconds));
+
+    let profile = guard.report().build()?.pprof()?;
+    Ok(profile.encode_to_vec())
+}
+
+async fn heap_profile(authorized: bool) -> Result<Response<Body>, Infallible> {
+    if !authorized {
+        return Ok(error_response(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid token.",
+        ));
+    }
+
+    Ok(match tokio::task::spawn_blocking(dump_heap_profile).await {
+        Ok(Ok(profile)) => attachment(profile, "heap.prof"),
+        Ok(Err(message)) => error_response(StatusCode::INTERNAL_SERVER_ERROR, message),
+        Err(join_error) => {
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, join_error.to_string())
+        }
+    })
+}
+
+/// Has jemalloc dump the heap profile to a temporary file, and reads it back.
+fn dump_heap_profile() -> Result<Vec<u8>, String> {
+    let path = std::env::temp_dir().join(format!(
+        "vector-{}-heap-{}.prof",
+        std::process::id(),
+        HEAP_PROFILES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let c_path = path
+        .to_str()
+        .and_then(|path| CString::new(path).ok())
+        .ok_or_else(|| format!("Invalid path for heap profile: {:?}.", path))?;
+
+    // SAFETY: `prof.dump` takes the path of the file to write the profile to, as a C string
+    // which outlives the call.
+    unsafe { tikv_jemalloc_ctl::raw::write(b"prof.dump\0", c_path.as_ptr()) }
+        .map_err(|error| format!("Couldn't dump heap profile: {}.", error))?;
+
+    let profile =
+        fs::read(&path).map_err(|error| format!("Couldn't read heap profile: {}.", error));
+    let _ = fs::remove_file(&path);
+    profile
+}
+
+fn attachment(profile: Vec<u8>, filename: &str) -> Response<Body> {
+    Response::builder()
+        .header("content-type", "application/octet-stream")
+        .header(
+            "content-disposition",
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(Body::from(profile))
+        .expect("Valid response")
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::from(message.into()))
+        .expect("Valid response")
+}
@@ -0,0 +1,93 @@
+//! The resolved configuration of components, as exposed through the API.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config::{runtime_options, ComponentKey, OutputId};
+
+/// Parts of option names that mark them as holding secrets.
+const SENSITIVE: [&str; 9] = [
+    "password",
+    "passphrase",
+    "secret",
+    "token",
+    "api_key",
+    "access_key",
+    "private_key",
+    "key_pass",
+    "authorization",
+];
+
+const REDACTED: &str = "**REDACTED**";
+
+/// Serializes the configuration of a component, with secrets redacted and its inputs written as
+/// they're configured.
+pub fn to_json<T: Serialize>(component: &T, inputs: &[OutputId]) -> Value {
+    let mut value = serde_json::to_value(component).unwrap_or(Value::Null);
+    redact(&mut value);
+    if let Value::Object(object) = &mut value {
+        if object.contains_key("inputs") {
+            let inputs = inputs.iter().map(|input| input.to_string().into());
+            object.insert("inputs".to_owned(), Value::Array(inputs.collect()));
+        }
+    }
+    value
+}
+
+/// Applies the options of a component changed at runtime to its configuration.
+pub fn with_runtime_options(key: &ComponentKey, mut value: Value) -> Value {
+    if let Value::Object(object) = &mut value {
+        for (option, current) in runtime_options::values(key) {
+            object.insert(option.name().to_owned(), current.into());
+        }
+    }
+    value
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (name, value) in object.iter_mut() {
+                let name = name.to_lowercase();
+                if SENSITIVE.iter().any(|sensitive| name.contains(sensitive)) && !value.is_null() {
+                    *value = REDACTED.into();
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn redacts_secrets() {
+        let config = json!({
+            "type": "http",
+            "inputs": [{"component": "in", "port": null}],
+            "auth": {"strategy": "basic", "user": "vector", "password": "hunter2"},
+            "request": {"headers": {"Authorization": "Bearer abc"}},
+            "tls": {"key_file": "/etc/vector/key.pem", "key_pass": "hunter2"},
+            "api_key": null,
+        });
+
+        assert_eq!(
+            to_json(&config, &[OutputId::from(&ComponentKey::from("in"))]),
+            json!({
+                "type": "http",
+                "inputs": ["in"],
+                "auth": {"strategy": "basic", "user": "vector", "password": "**REDACTED**"},
+                "request": {"headers": {"Authorization": "**REDACTED**"}},
+                "tls": {"key_file": "/etc/vector/key.pem", "key_pass": "**REDACTED**"},
+                "api_key": null,
+            })
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod component_config;
 pub mod sink;
 pub mod source;
 pub mod state;
@@ -11,12 +12,13 @@ use std::{
 use async_graphql::{Enum, InputObject, Interface, Object, Subscription};
 use once_cell::sync::Lazy;
 use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
-use vector_core::internal_event::DEFAULT_OUTPUT;
+use vector_core::{buffers::BufferType, internal_event::DEFAULT_OUTPUT};
 
 use crate::{
     api::schema::{
         components::state::component_by_component_key,
         filter::{self, filter_items},
+        health::ComponentHealth,
         relay, sort,
     },
     config::{ComponentKey, Config},
@@ -26,7 +28,9 @@ use crate::{
 #[derive(Debug, Clone, Interface)]
 #[graphql(
     field(name = "component_id", type = "String"),
-    field(name = "component_type", type = "String")
+    field(name = "component_type", type = "String"),
+    field(name = "config", type = "Option<String>"),
+    field(name = "health", type = "Option<ComponentHealth>")
 )]
 pub enum Component {
     Source(source::Source),
@@ -253,9 +257,15 @@ impl ComponentsSubscription {
 /// Update the 'global' configuration that will be consumed by component queries
 pub fn update_config(config: &Config) {
     let mut new_components = HashMap::new();
+    let mut new_configs = HashMap::new();
+    let mut new_disk_buffers = HashMap::new();
 
     // Sources
     for (component_key, source) in config.sources() {
+        new_configs.insert(
+            component_key.clone(),
+            component_config::to_json(source, &[]),
+        );
         new_components.insert(
             component_key.clone(),
             Component::Source(source::Source(source::Data {
@@ -277,6 +287,10 @@ pub fn update_config(config: &Config) {
 
     // Transforms
     for (component_key, transform) in config.transforms() {
+        new_configs.insert(
+            component_key.clone(),
+            component_config::to_json(transform, &transform.inputs),
+        );
         new_components.insert(
             component_key.clone(),
             Component::Transform(transform::Transform(transform::Data {
@@ -295,6 +309,10 @@ pub fn update_config(config: &Config) {
 
     // Sinks
     for (component_key, sink) in config.sinks() {
+        new_configs.insert(
+            component_key.clone(),
+            component_config::to_json(sink, &sink.inputs),
+        );
         new_components.insert(
             component_key.clone(),
             Component::Sink(sink::Sink(sink::Data {
@@ -303,6 +321,15 @@ pub fn update_config(config: &Config) {
                 inputs: sink.inputs.clone(),
             })),
         );
+
+        let has_disk_buffer = sink
+            .buffer
+            .stages()
+            .iter()
+            .any(|stage| matches!(stage, BufferType::DiskV2 { .. }));
+        if let (true, Some(data_dir)) = (has_disk_buffer, &config.global.data_dir) {
+            new_disk_buffers.insert(component_key.clone(), data_dir.clone());
+        }
     }
 
     // Get the component_ids of existing components
@@ -333,6 +360,8 @@ pub fn update_config(config: &Config) {
 
     // Override the old component state
     state::update(new_components);
+    state::update_configs(new_configs);
+    state::update_disk_buffers(new_disk_buffers);
 }
 
 #[cfg(test)]
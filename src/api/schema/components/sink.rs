@@ -6,6 +6,7 @@ use super::{source, state, transform, Component};
 use crate::{
     api::schema::{
         filter,
+        health::ComponentHealth,
         metrics::{self, IntoSinkMetrics},
         sort,
     },
@@ -89,6 +90,16 @@ impl Sink {
         &*self.get_component_type()
     }
 
+    /// Sink configuration as JSON, with secrets redacted and options changed at runtime applied
+    pub async fn config(&self) -> Option<String> {
+        state::component_config(self.get_component_key())
+    }
+
+    /// Sink health
+    pub async fn health(&self) -> Option<ComponentHealth> {
+        ComponentHealth::of(self.get_component_key())
+    }
+
     /// Source inputs
     pub async fn sources(&self) -> Vec<source::Source> {
         self.0
@@ -118,6 +129,11 @@ impl Sink {
         metrics::by_component_key(self.get_component_key())
             .into_sink_metrics(self.get_component_type())
     }
+
+    /// Age of the oldest event held in the disk buffer of the sink, if it has one
+    pub async fn buffer_oldest_event_age(&self) -> Option<metrics::BufferOldestEventAge> {
+        metrics::BufferOldestEventAge::of(self.get_component_key())
+    }
 }
 
 #[cfg(test)]
@@ -8,6 +8,7 @@ use super::{sink, state, transform, Component};
 use crate::{
     api::schema::{
         filter,
+        health::ComponentHealth,
         metrics::{self, outputs_by_component_key, IntoSourceMetrics, Output},
         sort,
     },
@@ -98,6 +99,16 @@ impl Source {
         self.get_component_type()
     }
 
+    /// Source configuration as JSON, with secrets redacted and options changed at runtime applied
+    pub async fn config(&self) -> Option<String> {
+        state::component_config(self.get_component_key())
+    }
+
+    /// Source health
+    pub async fn health(&self) -> Option<ComponentHealth> {
+        ComponentHealth::of(self.get_component_key())
+    }
+
     /// Source output type
     pub async fn output_types(&self) -> Vec<SourceOutputType> {
         self.get_output_types()
@@ -1,11 +1,12 @@
 use std::{
     collections::{HashMap, HashSet},
+    path::PathBuf,
     sync::{Arc, RwLock},
 };
 
 use once_cell::sync::Lazy;
 
-use super::{sink, source, transform, Component};
+use super::{component_config, sink, source, transform, Component};
 use crate::config::{ComponentKey, OutputId};
 
 pub const INVARIANT: &str = "Couldn't acquire lock on Vector components. Please report this.";
@@ -13,6 +14,14 @@ pub const INVARIANT: &str = "Couldn't acquire lock on Vector components. Please
 pub static COMPONENTS: Lazy<Arc<RwLock<HashMap<ComponentKey, Component>>>> =
     Lazy::new(|| Arc::new(RwLock::new(HashMap::new())));
 
+/// The resolved configuration of each component, with secrets redacted
+static CONFIGS: Lazy<RwLock<HashMap<ComponentKey, serde_json::Value>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// The data directory of each sink with a disk buffer
+static DISK_BUFFERS: Lazy<RwLock<HashMap<ComponentKey, PathBuf>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
 /// Filter components with the provided `map_func`
 pub fn filter_components<T>(map_func: impl Fn((&ComponentKey, &Component)) -> Option<T>) -> Vec<T> {
     COMPONENTS
@@ -85,7 +94,32 @@ pub fn component_by_output_id(output_id: &OutputId) -> Option<Component> {
     .pop()
 }
 
+/// Gets the configuration of a component as JSON, with the options changed at runtime applied
+pub fn component_config(component_key: &ComponentKey) -> Option<String> {
+    let config = CONFIGS.read().expect(INVARIANT).get(component_key)?.clone();
+    Some(component_config::with_runtime_options(component_key, config).to_string())
+}
+
 /// Overwrites component state with new components.
 pub fn update(new_components: HashMap<ComponentKey, Component>) {
     *COMPONENTS.write().expect(INVARIANT) = new_components
 }
+
+/// Overwrites the configurations of components with new ones.
+pub fn update_configs(new_configs: HashMap<ComponentKey, serde_json::Value>) {
+    *CONFIGS.write().expect(INVARIANT) = new_configs
+}
+
+/// Gets the data directory of the disk buffer of a sink, if it has one
+pub fn disk_buffer_data_dir(component_key: &ComponentKey) -> Option<PathBuf> {
+    DISK_BUFFERS
+        .read()
+        .expect(INVARIANT)
+        .get(component_key)
+        .cloned()
+}
+
+/// Overwrites the data directories of the sinks with disk buffers with new ones.
+pub fn update_disk_buffers(new_disk_buffers: HashMap<ComponentKey, PathBuf>) {
+    *DISK_BUFFERS.write().expect(INVARIANT) = new_disk_buffers
+}
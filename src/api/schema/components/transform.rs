@@ -6,6 +6,7 @@ use super::{sink, source, state, Component};
 use crate::{
     api::schema::{
         filter,
+        health::ComponentHealth,
         metrics::{self, outputs_by_component_key, IntoTransformMetrics, Output},
         sort,
     },
@@ -67,6 +68,16 @@ impl Transform {
         &*self.get_component_type()
     }
 
+    /// Transform configuration as JSON, with secrets redacted and options changed at runtime applied
+    pub async fn config(&self) -> Option<String> {
+        state::component_config(self.get_component_key())
+    }
+
+    /// Transform health
+    pub async fn health(&self) -> Option<ComponentHealth> {
+        ComponentHealth::of(self.get_component_key())
+    }
+
     /// Transform output streams
     pub async fn outputs(&self) -> Vec<Output> {
         outputs_by_component_key(self.get_component_key(), self.get_outputs())
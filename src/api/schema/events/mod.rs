@@ -5,9 +5,9 @@ pub mod notification;
 pub mod output;
 pub mod trace;
 
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
-use async_graphql::{Context, Subscription};
+use async_graphql::{Context, Error, Result, Subscription};
 use encoding::EventEncodingType;
 use futures::{stream, Stream, StreamExt};
 use output::OutputEventsPayload;
@@ -15,7 +15,11 @@ use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{select, sync::mpsc, time};
 use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::tap::{RateLimit, TapController, TapOptions},
+    conditions::{ConditionConfig, VrlConfig},
+    topology::WatchRx,
+};
 
 /// Patterns (glob) used by tap to match against components and access events
 /// flowing into (for_inputs) or out of (for_outputs) specified components
@@ -48,7 +52,11 @@ pub struct EventsSubscription;
 
 #[Subscription]
 impl EventsSubscription {
-    /// A stream of events emitted from matched component ID patterns
+    /// A stream of events emitted from matched component ID patterns. Events can be narrowed
+    /// down by a VRL predicate (`filter`), projected to some of their fields (`fields`, for logs)
+    /// and capped at a number per second (`maxEventsPerSec`), all applied by the running instance
+    /// before events are sampled
+    #[allow(clippy::too_many_arguments)]
     pub async fn output_events_by_component_id_patterns<'a>(
         &'a self,
         ctx: &'a Context<'a>,
@@ -56,15 +64,39 @@ impl EventsSubscription {
         inputs_patterns: Option<Vec<String>>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
-    ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
+        filter: Option<String>,
+        fields: Option<Vec<String>>,
+        #[graphql(validator(minimum = 1))] max_events_per_sec: Option<u32>,
+    ) -> Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
         let patterns = TapPatterns {
             for_outputs: outputs_patterns.into_iter().collect(),
             for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
         };
+        let filter = filter
+            .map(|source| {
+                VrlConfig {
+                    source,
+                    runtime: Default::default(),
+                }
+                .build(&Default::default())
+            })
+            .transpose()
+            .map_err(|error| Error::new(format!("Invalid filter: {}", error)))?;
+        let options = TapOptions {
+            filter,
+            fields: fields.unwrap_or_default(),
+            rate_limit: max_events_per_sec.map(|max| Arc::new(RateLimit::new(max as usize))),
+        };
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        Ok(create_events_stream_with_options(
+            watch_rx,
+            patterns,
+            options,
+            interval as u64,
+            limit as usize,
+        ))
     }
 }
 
@@ -76,6 +108,18 @@ pub(crate) fn create_events_stream(
     patterns: TapPatterns,
     interval: u64,
     limit: usize,
+) -> impl Stream<Item = Vec<OutputEventsPayload>> {
+    create_events_stream_with_options(watch_rx, patterns, TapOptions::default(), interval, limit)
+}
+
+/// Creates an events stream, like `create_events_stream`, applying the given options to events
+/// before they're sampled.
+pub(crate) fn create_events_stream_with_options(
+    watch_rx: WatchRx,
+    patterns: TapPatterns,
+    options: TapOptions,
+    interval: u64,
+    limit: usize,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
     // Channel for receiving individual tap payloads. Since we can process at most `limit` per
     // interval, this is capped to the same value.
@@ -91,7 +135,7 @@ pub(crate) fn create_events_stream(
     tokio::spawn(async move {
         // Create a tap controller. When this drops out of scope, clean up will be performed on the
         // event handlers and topology observation that the tap controller provides.
-        let _tap_controller = TapController::new(watch_rx, tap_tx, patterns);
+        let _tap_controller = TapController::with_options(watch_rx, tap_tx, patterns, options);
 
         // A tick interval to represent when to 'cut' the results back to the client.
         let mut interval = time::interval(time::Duration::from_millis(interval));
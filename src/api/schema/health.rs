@@ -1,8 +1,13 @@
-use async_graphql::{Object, SimpleObject, Subscription};
+use async_graphql::{Enum, Object, SimpleObject, Subscription};
 use chrono::{DateTime, Utc};
 use tokio::time::Duration;
 use tokio_stream::{wrappers::IntervalStream, Stream, StreamExt};
 
+use crate::{
+    config::ComponentKey,
+    topology::health::{self, HealthState},
+};
+
 #[derive(SimpleObject)]
 pub struct Heartbeat {
     utc: DateTime<Utc>,
@@ -14,6 +19,53 @@ impl Heartbeat {
     }
 }
 
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ComponentHealthState {
+    Healthy,
+    Starting,
+    Degraded,
+    Failed,
+}
+
+impl From<HealthState> for ComponentHealthState {
+    fn from(state: HealthState) -> Self {
+        match state {
+            HealthState::Healthy => Self::Healthy,
+            HealthState::Starting => Self::Starting,
+            HealthState::Degraded => Self::Degraded,
+            HealthState::Failed => Self::Failed,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ComponentHealth(health::ComponentHealth);
+
+impl ComponentHealth {
+    /// Returns the health of a component, if it is part of the running topology.
+    pub fn of(key: &ComponentKey) -> Option<Self> {
+        health::component(key).map(Self)
+    }
+}
+
+#[Object]
+impl ComponentHealth {
+    /// Health state of the component, which is degraded when a component upstream of it failed
+    async fn state(&self) -> ComponentHealthState {
+        self.0.state.into()
+    }
+
+    /// When the component entered its state
+    async fn since(&self) -> DateTime<Utc> {
+        self.0.since
+    }
+
+    /// Why the component is in its state, if it isn't healthy or starting
+    async fn reason(&self) -> Option<&str> {
+        self.0.reason.as_deref()
+    }
+}
+
 #[derive(Default)]
 pub(super) struct HealthQuery;
 
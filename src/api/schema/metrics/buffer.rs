@@ -0,0 +1,106 @@
+use std::time::{Duration, SystemTime};
+
+use async_graphql::Object;
+use chrono::{DateTime, Utc};
+use vector_core::buffers::inspect_disk_buffer;
+
+use crate::{
+    api::schema::components::state,
+    config::ComponentKey,
+    event::{Metric, MetricValue},
+};
+
+pub struct BufferEvents(Metric);
+
+impl BufferEvents {
+    pub const fn new(m: Metric) -> Self {
+        Self(m)
+    }
+
+    pub fn get_buffer_events(&self) -> f64 {
+        match self.0.value() {
+            MetricValue::Gauge { value } => *value,
+            _ => 0.00,
+        }
+    }
+}
+
+#[Object]
+impl BufferEvents {
+    /// Metric timestamp
+    pub async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.timestamp()
+    }
+
+    /// Number of events currently held in the buffer, across all buffer stages
+    pub async fn buffer_events(&self) -> f64 {
+        self.get_buffer_events()
+    }
+}
+
+pub struct BufferByteSize(Metric);
+
+impl BufferByteSize {
+    pub const fn new(m: Metric) -> Self {
+        Self(m)
+    }
+
+    pub fn get_buffer_byte_size(&self) -> f64 {
+        match self.0.value() {
+            MetricValue::Gauge { value } => *value,
+            _ => 0.00,
+        }
+    }
+}
+
+#[Object]
+impl BufferByteSize {
+    /// Metric timestamp
+    pub async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.timestamp()
+    }
+
+    /// Size, in bytes, of the events currently held in the buffer, across all buffer stages
+    pub async fn buffer_byte_size(&self) -> f64 {
+        self.get_buffer_byte_size()
+    }
+}
+
+pub struct BufferOldestEventAge {
+    timestamp: DateTime<Utc>,
+    age: Duration,
+}
+
+impl BufferOldestEventAge {
+    /// Inspects the disk buffer of the given sink, if it has one that holds any events.
+    ///
+    /// Records aren't timestamped, so the age is measured from the last modification time of the
+    /// oldest data file of the buffer, which is a lower bound on the age of the oldest event.
+    pub fn of(component_key: &ComponentKey) -> Option<Self> {
+        let data_dir = state::disk_buffer_data_dir(component_key)?;
+        let info = inspect_disk_buffer(&data_dir, component_key.id())
+            .ok()
+            .flatten()?;
+        let modified = info.oldest_modified()?;
+
+        Some(Self {
+            timestamp: Utc::now(),
+            age: SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[Object]
+impl BufferOldestEventAge {
+    /// Time at which the buffer was inspected
+    pub async fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Lower bound on the age, in seconds, of the oldest event held in the disk buffer
+    pub async fn buffer_oldest_event_age_seconds(&self) -> f64 {
+        self.age.as_secs_f64()
+    }
+}
@@ -0,0 +1,50 @@
+use async_graphql::{Enum, Object};
+use chrono::{DateTime, Utc};
+
+use crate::{
+    event::{Metric, MetricValue},
+    topology::CircuitBreakerState as State,
+};
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum CircuitBreakerStateName {
+    /// Events are handed to the sink
+    Closed,
+    /// A batch of events was handed to the sink to probe whether it has recovered
+    HalfOpen,
+    /// Events are rerouted to the fallback output of the sink
+    Open,
+}
+
+pub struct CircuitBreakerState(Metric);
+
+impl CircuitBreakerState {
+    pub const fn new(m: Metric) -> Self {
+        Self(m)
+    }
+
+    pub fn get_state(&self) -> CircuitBreakerStateName {
+        match self.0.value() {
+            MetricValue::Gauge { value } if *value >= State::Open.as_gauge() => {
+                CircuitBreakerStateName::Open
+            }
+            MetricValue::Gauge { value } if *value >= State::HalfOpen.as_gauge() => {
+                CircuitBreakerStateName::HalfOpen
+            }
+            _ => CircuitBreakerStateName::Closed,
+        }
+    }
+}
+
+#[Object]
+impl CircuitBreakerState {
+    /// Metric timestamp
+    pub async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.timestamp()
+    }
+
+    /// State of the circuit breaker of the sink
+    pub async fn state(&self) -> CircuitBreakerStateName {
+        self.get_state()
+    }
+}
@@ -5,8 +5,9 @@ use tokio::time::Duration;
 use tokio_stream::{Stream, StreamExt};
 
 use super::{
-    filter_output_metric, EventsInTotal, EventsOutTotal, OutputThroughput, ProcessedBytesTotal,
-    ProcessedEventsTotal, ReceivedEventsTotal, SentEventsTotal,
+    filter_output_metric, BufferByteSize, BufferEvents, CircuitBreakerState, EventsInTotal,
+    EventsOutTotal, OutputThroughput, ProcessedBytesTotal, ProcessedEventsTotal,
+    ReceivedEventsTotal, SentEventsTotal,
 };
 use crate::{
     config::ComponentKey,
@@ -52,6 +53,9 @@ pub trait MetricsFilter<'a> {
     fn events_in_total(&self) -> Option<EventsInTotal>;
     fn events_out_total(&self) -> Option<EventsOutTotal>;
     fn sent_events_total(&self) -> Option<SentEventsTotal>;
+    fn buffer_events(&self) -> Option<BufferEvents>;
+    fn buffer_byte_size(&self) -> Option<BufferByteSize>;
+    fn circuit_breaker_state(&self) -> Option<CircuitBreakerState>;
 }
 
 impl<'a> MetricsFilter<'a> for Vec<Metric> {
@@ -96,6 +100,27 @@ impl<'a> MetricsFilter<'a> for Vec<Metric> {
 
         Some(SentEventsTotal::new(sum))
     }
+
+    fn buffer_events(&self) -> Option<BufferEvents> {
+        let sum = sum_metrics(self.iter().filter(|m| m.name() == "buffer_events"))?;
+
+        Some(BufferEvents::new(sum))
+    }
+
+    fn buffer_byte_size(&self) -> Option<BufferByteSize> {
+        let sum = sum_metrics(self.iter().filter(|m| m.name() == "buffer_byte_size"))?;
+
+        Some(BufferByteSize::new(sum))
+    }
+
+    fn circuit_breaker_state(&self) -> Option<CircuitBreakerState> {
+        let metric = self
+            .iter()
+            .find(|m| m.name() == "circuit_breaker_state")?
+            .clone();
+
+        Some(CircuitBreakerState::new(metric))
+    }
 }
 
 impl<'a> MetricsFilter<'a> for Vec<&'a Metric> {
@@ -158,6 +183,28 @@ impl<'a> MetricsFilter<'a> for Vec<&'a Metric> {
 
         Some(SentEventsTotal::new(sum))
     }
+
+    fn buffer_events(&self) -> Option<BufferEvents> {
+        let sum = sum_metrics(self.iter().filter(|m| m.name() == "buffer_events").copied())?;
+
+        Some(BufferEvents::new(sum))
+    }
+
+    fn buffer_byte_size(&self) -> Option<BufferByteSize> {
+        let sum = sum_metrics(
+            self.iter()
+                .filter(|m| m.name() == "buffer_byte_size")
+                .copied(),
+        )?;
+
+        Some(BufferByteSize::new(sum))
+    }
+
+    fn circuit_breaker_state(&self) -> Option<CircuitBreakerState> {
+        let metric = self.iter().find(|m| m.name() == "circuit_breaker_state")?;
+
+        Some(CircuitBreakerState::new((*metric).clone()))
+    }
 }
 
 /// Returns a stream of `Metric`s, collected at the provided millisecond interval.
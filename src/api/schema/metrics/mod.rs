@@ -1,3 +1,5 @@
+mod buffer;
+mod circuit_breaker;
 mod errors;
 mod events_in;
 mod events_out;
@@ -16,7 +18,9 @@ mod uptime;
 mod host;
 
 use async_graphql::{Interface, Object, Subscription};
+pub use buffer::{BufferByteSize, BufferEvents, BufferOldestEventAge};
 use chrono::{DateTime, Utc};
+pub use circuit_breaker::CircuitBreakerState;
 pub use errors::{ComponentErrorsTotal, ErrorsTotal};
 pub use events_in::EventsInTotal;
 pub use events_out::EventsOutTotal;
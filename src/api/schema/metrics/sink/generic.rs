@@ -45,4 +45,19 @@ impl GenericSinkMetrics {
     pub async fn sent_events_total(&self) -> Option<metrics::SentEventsTotal> {
         self.0.sent_events_total()
     }
+
+    /// Events currently held in the buffer of the current sink
+    pub async fn buffer_events(&self) -> Option<metrics::BufferEvents> {
+        self.0.buffer_events()
+    }
+
+    /// Bytes currently held in the buffer of the current sink
+    pub async fn buffer_byte_size(&self) -> Option<metrics::BufferByteSize> {
+        self.0.buffer_byte_size()
+    }
+
+    /// State of the circuit breaker of the current sink, if it has a fallback output
+    pub async fn circuit_breaker_state(&self) -> Option<metrics::CircuitBreakerState> {
+        self.0.circuit_breaker_state()
+    }
 }
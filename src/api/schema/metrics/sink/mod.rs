@@ -3,8 +3,8 @@ mod generic;
 use async_graphql::Interface;
 
 use super::{
-    EventsInTotal, EventsOutTotal, ProcessedBytesTotal, ProcessedEventsTotal, ReceivedEventsTotal,
-    SentEventsTotal,
+    BufferEvents, EventsInTotal, EventsOutTotal, ProcessedBytesTotal, ProcessedEventsTotal,
+    ReceivedEventsTotal, SentEventsTotal,
 };
 use crate::event::Metric;
 
@@ -23,7 +23,8 @@ use crate::event::Metric;
         name = "events_out_total",
         type = "Option<EventsOutTotal>",
         deprecation = "Use sent_events_total instead"
-    )
+    ),
+    field(name = "buffer_events", type = "Option<BufferEvents>")
 )]
 pub enum SinkMetrics {
     GenericSinkMetrics(generic::GenericSinkMetrics),
@@ -0,0 +1,54 @@
+use std::sync::RwLock;
+
+use async_graphql::{Object, SimpleObject};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+use crate::{config::ComponentKey, topology::ReloadResult};
+
+/// The outcome of the last reload, to be consumed by queries
+static LAST_RELOAD: Lazy<RwLock<Option<Reload>>> = Lazy::new(|| RwLock::new(None));
+
+#[derive(SimpleObject, Clone)]
+pub struct Reload {
+    /// When the reload finished
+    at: DateTime<Utc>,
+
+    /// Whether the new configuration was applied, rather than the previous one restored
+    applied: bool,
+
+    /// IDs of the components added by the new configuration
+    added: Vec<String>,
+
+    /// IDs of the components changed by the new configuration
+    changed: Vec<String>,
+
+    /// IDs of the components removed by the new configuration
+    removed: Vec<String>,
+}
+
+fn component_ids(keys: &[ComponentKey]) -> Vec<String> {
+    keys.iter().map(|key| key.id().to_owned()).collect()
+}
+
+#[derive(Default)]
+pub(super) struct ReloadQuery;
+
+#[Object]
+impl ReloadQuery {
+    /// The outcome of the last configuration reload, if there was one
+    async fn last_reload(&self) -> Option<Reload> {
+        LAST_RELOAD.read().expect("lock poisoned").clone()
+    }
+}
+
+/// Update the 'global' outcome of the last reload that will be consumed by reload queries
+pub fn update_reload(result: &ReloadResult) {
+    *LAST_RELOAD.write().expect("lock poisoned") = Some(Reload {
+        at: result.at,
+        applied: result.applied,
+        added: component_ids(&result.changes.added),
+        changed: component_ids(&result.changes.changed),
+        removed: component_ids(&result.changes.removed),
+    });
+}
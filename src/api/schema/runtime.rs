@@ -0,0 +1,71 @@
+use async_graphql::{Error, Object, Result};
+
+use crate::{
+    audit::{self, AuditAction},
+    config::{
+        runtime_options::{self, RuntimeOption},
+        ComponentKey,
+    },
+    trace,
+};
+
+#[derive(Default)]
+pub(super) struct RuntimeMutation;
+
+#[Object]
+impl RuntimeMutation {
+    /// Changes the rate of a running `sample` transform, without reloading the configuration
+    async fn set_sample_rate(&self, component_id: String, rate: u64) -> Result<bool> {
+        set_option(
+            "setSampleRate",
+            &component_id,
+            RuntimeOption::SampleRate,
+            rate,
+        )
+    }
+
+    /// Changes the threshold of a running `throttle` transform, without reloading the configuration
+    async fn set_throttle_threshold(&self, component_id: String, threshold: u64) -> Result<bool> {
+        set_option(
+            "setThrottleThreshold",
+            &component_id,
+            RuntimeOption::ThrottleThreshold,
+            threshold,
+        )
+    }
+
+    /// Changes the filter of Vector's logs, given in the same format as `VECTOR_LOG`, such as `vector=debug`
+    async fn set_log_level(&self, level: String) -> Result<bool> {
+        trace::set_levels(&level).map_err(Error::new)?;
+        info!(message = "Log level changed.", level = ?level);
+        audit::record(AuditAction::ApiMutation {
+            mutation: "setLogLevel",
+            arguments: vec![("level", level.into())],
+        });
+        Ok(true)
+    }
+}
+
+fn set_option(
+    mutation: &'static str,
+    component_id: &str,
+    option: RuntimeOption,
+    value: u64,
+) -> Result<bool> {
+    let component_key = ComponentKey::from(component_id);
+    runtime_options::set(&component_key, option, value).map_err(Error::new)?;
+    info!(
+        message = "Runtime option changed.",
+        component_id = %component_key,
+        option = option.name(),
+        value,
+    );
+    audit::record(AuditAction::ApiMutation {
+        mutation,
+        arguments: vec![
+            ("component_id", component_id.into()),
+            (option.name(), value.into()),
+        ],
+    });
+    Ok(true)
+}
@@ -28,7 +28,7 @@ impl Server {
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
     ) -> Self {
-        let routes = make_routes(config.api.playground, watch_rx, running);
+        let routes = make_routes(&config.api, watch_rx, running);
 
         let (_shutdown, rx) = oneshot::channel();
         let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(
@@ -58,19 +58,29 @@ impl Server {
     pub fn update_config(&self, config: &config::Config) {
         schema::components::update_config(config)
     }
+
+    /// Update the outcome of the last reload reported by a running server.
+    pub fn update_reload(&self, result: &topology::ReloadResult) {
+        schema::reload::update_reload(result)
+    }
 }
 
 fn make_routes(
-    playground: bool,
+    options: &config::api::Options,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
 ) -> BoxedFilter<(impl Reply,)> {
     // Routes...
 
     // Health.
-    let health = warp::path("health")
-        .and(with_shared(running))
+    let policies = Arc::new(options.health.readiness.clone());
+    let health = warp::path!("health")
+        .and(with_shared(Arc::clone(&running)))
+        .and(with_shared(policies))
         .and_then(handler::health);
+    let live = warp::path!("health" / "live")
+        .and(with_shared(running))
+        .and_then(handler::live);
 
     // 404.
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
@@ -114,7 +124,7 @@ fn make_routes(
     ));
 
     // Provide a playground for executing GraphQL queries/mutations/subscriptions.
-    let graphql_playground = if playground {
+    let graphql_playground = if options.playground {
         warp::path("playground")
             .map(move || {
                 Response::builder()
@@ -128,11 +138,19 @@ fn make_routes(
         not_found.boxed()
     };
 
+    let routes = health.or(live).or(graphql_handler).or(graphql_playground);
+
+    // CPU and heap profiles, when enabled.
+    #[cfg(feature = "api-profiling")]
+    let routes = routes.or(super::profiling::routes(options));
+    #[cfg(not(feature = "api-profiling"))]
+    if options.profiling {
+        warn!(message = "Profiling endpoints are disabled, as Vector was built without them.");
+    }
+
     // Wire up the health + GraphQL endpoints. Provides a permissive CORS policy to allow for
     // cross-origin interaction with the Vector API.
-    health
-        .or(graphql_handler)
-        .or(graphql_playground)
+    routes
         .or(not_found)
         .with(
             warp::cors()
@@ -151,14 +169,15 @@ fn make_routes(
                     "Host",
                     "Connection",
                     "Cache-Control",
+                    "Authorization", // for the profiling endpoints
                 ])
                 .allow_methods(vec!["POST", "GET"]),
         )
         .boxed()
 }
 
-fn with_shared(
-    shared: Arc<AtomicBool>,
-) -> impl Filter<Extract = (Arc<AtomicBool>,), Error = Infallible> + Clone {
-    warp::any().map(move || Arc::<AtomicBool>::clone(&shared))
+fn with_shared<T: Send + Sync>(
+    shared: Arc<T>,
+) -> impl Filter<Extract = (Arc<T>,), Error = Infallible> + Clone {
+    warp::any().map(move || Arc::<T>::clone(&shared))
 }
@@ -1,6 +1,8 @@
 use std::{
     collections::{HashMap, HashSet},
     num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use futures::{future::try_join_all, FutureExt};
@@ -20,8 +22,10 @@ use super::{
     ShutdownRx, ShutdownTx,
 };
 use crate::{
+    audit::{self, AuditAction},
+    conditions::Condition,
     config::ComponentKey,
-    event::{EventArray, LogArray, MetricArray, TraceArray},
+    event::{Event, EventArray, LogArray, LogEvent, MetricArray, TraceArray},
     topology::{fanout, fanout::ControlChannel, TapOutput, TapResource, WatchRx},
 };
 
@@ -83,6 +87,16 @@ pub enum TapPayload {
 }
 
 impl TapPayload {
+    /// Whether the payload holds no events, nor a notification.
+    fn is_empty(&self) -> bool {
+        match self {
+            TapPayload::Log(_, logs) => logs.is_empty(),
+            TapPayload::Metric(_, metrics) => metrics.is_empty(),
+            TapPayload::Trace(_, traces) => traces.is_empty(),
+            TapPayload::Notification(_) => false,
+        }
+    }
+
     /// Raise a `matched` event against the provided pattern.
     pub fn matched<T: Into<String>>(pattern: T) -> Self {
         Self::Notification(Notification::Matched(Matched::new(pattern.into())))
@@ -125,24 +139,125 @@ impl TapPayload {
     }
 }
 
+/// Options applied to tapped events in the running instance, before they're sent to the client,
+/// so that tapping a busy component doesn't flood the tap receiver.
+#[derive(Clone, Debug, Default)]
+pub struct TapOptions {
+    /// A predicate events must match to be tapped.
+    pub filter: Option<Condition>,
+
+    /// The fields tapped logs are projected to, if any.
+    pub fields: Vec<String>,
+
+    /// A limit on the number of events tapped per second, shared by all of the tapped outputs.
+    pub rate_limit: Option<Arc<RateLimit>>,
+}
+
+impl TapOptions {
+    /// Keeps the events matching the filter that fit in the rate limit.
+    fn select<T: Into<Event>>(&self, mut events: Vec<T>, from_event: fn(Event) -> T) -> Vec<T> {
+        if let Some(filter) = &self.filter {
+            events = events
+                .into_iter()
+                .map(Into::into)
+                .filter(|event| filter.check(event))
+                .map(from_event)
+                .collect();
+        }
+        if let Some(rate_limit) = &self.rate_limit {
+            events.truncate(rate_limit.take(events.len()));
+        }
+        events
+    }
+
+    /// Projects a log to the configured fields.
+    fn project(&self, log: LogEvent) -> LogEvent {
+        if self.fields.is_empty() {
+            return log;
+        }
+
+        let mut projected = LogEvent::default();
+        for field in &self.fields {
+            if let Some(value) = log.get(field.as_str()) {
+                projected.insert(field.as_str(), value.clone());
+            }
+        }
+        projected
+    }
+}
+
+/// Bounds the number of events let through in each second.
+#[derive(Debug)]
+pub struct RateLimit {
+    events_per_sec: usize,
+    window: Mutex<(Instant, usize)>,
+}
+
+impl RateLimit {
+    pub fn new(events_per_sec: usize) -> Self {
+        Self {
+            events_per_sec,
+            window: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    /// Takes room for up to the given number of events in the current second, returning how many
+    /// fit.
+    fn take(&self, count: usize) -> usize {
+        let mut window = self.window.lock().expect("rate limit lock poisoned");
+        let (started, taken) = &mut *window;
+        if started.elapsed() >= Duration::from_secs(1) {
+            *started = Instant::now();
+            *taken = 0;
+        }
+        let count = count.min(self.events_per_sec - *taken);
+        *taken += count;
+        count
+    }
+}
+
 /// A `TapTransformer` transforms raw events and ships them to the global tap receiver.
 #[derive(Clone)]
 pub struct TapTransformer {
     tap_tx: TapSender,
     output: TapOutput,
+    options: TapOptions,
 }
 
 impl TapTransformer {
-    pub const fn new(tap_tx: TapSender, output: TapOutput) -> Self {
-        Self { tap_tx, output }
+    pub const fn new(tap_tx: TapSender, output: TapOutput, options: TapOptions) -> Self {
+        Self {
+            tap_tx,
+            output,
+            options,
+        }
     }
 
     pub fn try_send(&mut self, events: EventArray) {
         let payload = match events {
-            EventArray::Logs(logs) => TapPayload::Log(self.output.clone(), logs),
-            EventArray::Metrics(metrics) => TapPayload::Metric(self.output.clone(), metrics),
-            EventArray::Traces(traces) => TapPayload::Trace(self.output.clone(), traces),
+            EventArray::Logs(logs) => {
+                let logs = self.options.select(logs, Event::into_log);
+                let logs = logs.into_iter().map(|log| self.options.project(log));
+                TapPayload::Log(self.output.clone(), logs.collect())
+            }
+            EventArray::Metrics(metrics) => {
+                let metrics = self.options.select(metrics, Event::into_metric);
+                TapPayload::Metric(self.output.clone(), metrics)
+            }
+            EventArray::Traces(traces) => {
+                let traces = self.options.select(traces, Event::into_trace);
+                TapPayload::Trace(self.output.clone(), traces)
+            }
+            EventArray::MetricColumns(columns) => {
+                let metrics = self
+                    .options
+                    .select(columns.into_metrics(), Event::into_metric);
+                TapPayload::Metric(self.output.clone(), metrics)
+            }
         };
+        if payload.is_empty() {
+            return;
+        }
 
         if let Err(TrySendError::Closed(payload)) = self.tap_tx.try_send(payload) {
             debug!(
@@ -159,6 +274,7 @@ impl TapTransformer {
 #[derive(Debug)]
 pub struct TapController {
     _shutdown: ShutdownTx,
+    id: String,
 }
 
 impl TapController {
@@ -166,11 +282,45 @@ impl TapController {
     /// and a separate inner handler for events. Uses a oneshot channel to trigger shutdown
     /// of handlers when the `TapSink` drops out of scope.
     pub fn new(watch_rx: WatchRx, tap_tx: TapSender, patterns: TapPatterns) -> Self {
+        Self::with_options(watch_rx, tap_tx, patterns, TapOptions::default())
+    }
+
+    /// Creates a new tap sink, like `new`, applying the given options to tapped events.
+    pub fn with_options(
+        watch_rx: WatchRx,
+        tap_tx: TapSender,
+        patterns: TapPatterns,
+        options: TapOptions,
+    ) -> Self {
         let (_shutdown, shutdown_rx) = oneshot::channel();
 
-        tokio::spawn(tap_handler(patterns, tap_tx, watch_rx, shutdown_rx));
+        let id = Uuid::new_v4().to_string();
+        let sorted = |patterns: &HashSet<String>| {
+            let mut patterns: Vec<_> = patterns.iter().cloned().collect();
+            patterns.sort_unstable();
+            patterns
+        };
+        audit::record(AuditAction::TapStarted {
+            id: &id,
+            outputs_patterns: sorted(&patterns.for_outputs),
+            inputs_patterns: sorted(&patterns.for_inputs),
+        });
+
+        tokio::spawn(tap_handler(
+            patterns,
+            options,
+            tap_tx,
+            watch_rx,
+            shutdown_rx,
+        ));
 
-        Self { _shutdown }
+        Self { _shutdown, id }
+    }
+}
+
+impl Drop for TapController {
+    fn drop(&mut self) {
+        audit::record(AuditAction::TapStopped { id: &self.id });
     }
 }
 
@@ -237,6 +387,7 @@ async fn send_invalid_output_pattern_match(
 /// `LogEvent`s` when a component matches one or more of the provided patterns.
 async fn tap_handler(
     patterns: TapPatterns,
+    options: TapOptions,
     tx: TapSender,
     mut watch_rx: WatchRx,
     mut shutdown_rx: ShutdownRx,
@@ -316,7 +467,7 @@ async fn tap_handler(
                             // wrap each event payload with the necessary metadata before forwarding
                             // it to our global tap receiver.
                             let (tap_buffer_tx, mut tap_buffer_rx) = TopologyBuilder::standalone_memory(TAP_BUFFER_SIZE, WhenFull::DropNewest).await;
-                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone());
+                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone(), options.clone());
 
                             tokio::spawn(async move {
                                 while let Some(events) = tap_buffer_rx.next().await {
@@ -983,4 +1134,57 @@ mod tests {
 
         assert!(default_output_found && dropped_output_found);
     }
+
+    #[tokio::test]
+    async fn applies_tap_options_before_sending() {
+        use crate::conditions::{ConditionConfig, VrlConfig};
+
+        let filter = VrlConfig {
+            source: r#".level == "error""#.to_owned(),
+            runtime: Default::default(),
+        }
+        .build(&Default::default())
+        .unwrap();
+        let options = TapOptions {
+            filter: Some(filter),
+            fields: vec!["message".to_owned()],
+            rate_limit: Some(Arc::new(RateLimit::new(2))),
+        };
+        let (tap_tx, mut tap_rx) = tokio_mpsc::channel(10);
+        let output = TapOutput {
+            output_id: OutputId::from(&ComponentKey::from("in")),
+            component_kind: "source",
+            component_type: "demo_logs".to_owned(),
+        };
+        let mut transformer = TapTransformer::new(tap_tx, output, options);
+
+        let logs = ["error", "info", "error", "error"]
+            .iter()
+            .enumerate()
+            .map(|(index, level)| {
+                let mut log = LogEvent::from(format!("event {}", index));
+                log.insert("level", *level);
+                log
+            })
+            .collect::<Vec<_>>();
+        transformer.try_send(EventArray::Logs(logs.clone()));
+        // The rate limit is used up, so no payload is sent for later events.
+        transformer.try_send(EventArray::Logs(logs));
+        drop(transformer);
+
+        match tap_rx.recv().await {
+            Some(TapPayload::Log(_, logs)) => {
+                let messages = logs
+                    .iter()
+                    .map(|log| {
+                        assert!(log.get("level").is_none());
+                        log.get("message").unwrap().to_string_lossy()
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(messages, vec!["event 0", "event 2"]);
+            }
+            payload => panic!("unexpected payload: {:?}", payload),
+        }
+        assert!(tap_rx.recv().await.is_none());
+    }
 }
@@ -22,13 +22,18 @@ use crate::service;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
 use crate::{
+    bench, buffer,
     cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubCommand},
+    cluster,
     config::{self},
-    generate, graph, heartbeat, list,
+    convert, generate, graph, heartbeat, list,
     signal::{self, SignalTo},
+    simulate,
     topology::{self, RunningTopology},
     trace, unit_test, validate,
 };
+#[cfg(feature = "sources-replay")]
+use crate::replay;
 #[cfg(feature = "api-client")]
 use crate::{tap, top};
 
@@ -129,6 +134,8 @@ impl Application {
 
         let rt = rt_builder.build().expect("Unable to create async runtime");
 
+        config::set_profile(root_opts.profile.clone());
+
         let config = {
             let config_paths = root_opts.config_paths_with_formats();
             let watch_config = root_opts.watch_config;
@@ -143,7 +150,13 @@ impl Application {
                 if let Some(s) = sub_command {
                     let code = match s {
                         SubCommand::Generate(g) => generate::cmd(&g),
-                        SubCommand::Graph(g) => graph::cmd(&g),
+                        SubCommand::Graph(g) => graph::cmd(&g).await,
+                        SubCommand::Buffer(b) => buffer::cmd(&b).await,
+                        SubCommand::Simulate(s) => simulate::cmd(&s).await,
+                        SubCommand::Bench(b) => bench::cmd(&b).await,
+                        SubCommand::Convert(c) => convert::cmd(&c),
+                        #[cfg(feature = "sources-replay")]
+                        SubCommand::Replay(r) => replay::cmd(&r).await,
                         SubCommand::Config(c) => config::cmd(&c),
                         SubCommand::List(l) => list::cmd(&l),
                         SubCommand::Test(t) => unit_test::cmd(&t, &mut signal_handler).await,
@@ -219,7 +232,7 @@ impl Application {
                     .ok_or(exitcode::CONFIG)?;
 
                 #[cfg(feature = "api")]
-                let api = config.api;
+                let api = config.api.clone();
 
                 let result = topology::start_validated(config, diff, pieces).await;
                 let (topology, graceful_crash) = result.ok_or(exitcode::CONFIG)?;
@@ -264,9 +277,10 @@ impl Application {
         let mut signal_handler = self.config.signal_handler;
         let mut signal_rx = self.config.signal_rx;
 
-        // Any internal_logs sources will have grabbed a copy of the
-        // early buffer by this point and set up a subscriber.
+        // Any internal_logs and internal_audit sources will have grabbed a
+        // copy of the early buffer by this point and set up a subscriber.
         crate::trace::stop_early_buffering();
+        crate::audit::stop_early_buffering();
 
         rt.block_on(async move {
             emit!(VectorStarted);
@@ -336,6 +350,12 @@ impl Application {
                                                 break SignalTo::Shutdown;
                                             }
                                         }
+                                        #[cfg(feature = "api")]
+                                        // Pass the outcome of the reload to the API server.
+                                        if let (Some(api_server), Some(reload)) = (&api_server, topology.last_reload()) {
+                                            api_server.update_reload(reload);
+                                        }
+
                                         sources_finished = topology.sources_finished();
                                     },
                                     Err(_) => {
@@ -391,6 +411,12 @@ impl Application {
                                             break SignalTo::Shutdown;
                                         }
                                     }
+                                    #[cfg(feature = "api")]
+                                    // Pass the outcome of the reload to the API server.
+                                    if let (Some(api_server), Some(reload)) = (&api_server, topology.last_reload()) {
+                                        api_server.update_reload(reload);
+                                    }
+
                                     sources_finished = topology.sources_finished();
                                 } else {
                                     emit!(VectorConfigLoadError);
@@ -410,7 +436,7 @@ impl Application {
                 SignalTo::Shutdown => {
                     emit!(VectorStopped);
                     tokio::select! {
-                        _ = topology.stop() => (), // Graceful shutdown finished
+                        _ = topology.stop() => cluster::leave().await, // Graceful shutdown finished
                         _ = signal_rx.recv() => {
                             // It is highly unlikely that this event will exit from topology.
                             emit!(VectorQuit);
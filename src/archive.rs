@@ -0,0 +1,179 @@
+//! The format of event archives, written by the `archive` sink and read back by the `replay`
+//! source: a directory of zstd-compressed files of newline-delimited JSON records, each holding an
+//! event in Vector's native JSON along with the metadata it would otherwise lose.
+//!
+//! Files are named after the time they were opened, so that their names sort in the order they
+//! were written, and are written under a hidden name until they are complete.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_compression::tokio::bufread::ZstdDecoder;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs::{self, File},
+    io::{AsyncBufReadExt, BufReader, Lines},
+};
+use vector_core::{config::log_schema, event::EventPriority};
+
+use crate::event::Event;
+
+/// The extension of archive files.
+pub const EXTENSION: &str = "ndjson.zst";
+
+/// An archived event.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Record {
+    /// The time of the event, or when it was archived if it has none.
+    pub timestamp: DateTime<Utc>,
+
+    /// When the event was archived.
+    pub archived_at: DateTime<Utc>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub priority: EventPriority,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+
+    pub event: Event,
+}
+
+impl Record {
+    pub fn new(event: Event, archived_at: DateTime<Utc>) -> Self {
+        let timestamp = match &event {
+            Event::Log(log) => log
+                .get(log_schema().timestamp_key())
+                .and_then(|value| value.as_timestamp())
+                .copied(),
+            Event::Metric(metric) => metric.timestamp(),
+            Event::Trace(trace) => trace
+                .get(log_schema().timestamp_key())
+                .and_then(|value| value.as_timestamp())
+                .copied(),
+        };
+        let metadata = event.metadata();
+        Self {
+            timestamp: timestamp.unwrap_or(archived_at),
+            archived_at,
+            priority: metadata.priority(),
+            tenant: metadata.tenant().as_deref().map(Into::into),
+            event,
+        }
+    }
+
+    /// The archived event, with its metadata restored.
+    pub fn into_event(self) -> Event {
+        let mut event = self.event;
+        let metadata = event.metadata_mut();
+        metadata.set_priority(self.priority);
+        metadata.set_tenant(self.tenant.map(Arc::from));
+        event
+    }
+}
+
+/// The name of an archive file opened at the given time, which is the `sequence`th file opened
+/// at that same millisecond.
+pub fn file_name(opened_at: DateTime<Utc>, sequence: usize) -> String {
+    format!(
+        "{}-{:04}.{}",
+        opened_at.format("%Y%m%dT%H%M%S%.3fZ"),
+        sequence,
+        EXTENSION
+    )
+}
+
+/// The complete archive files of a directory, in the order they were written.
+pub async fn files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir).await?;
+    let mut files = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.starts_with('.') && name.ends_with(EXTENSION) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Reads the records of an archive file one at a time.
+pub struct Reader {
+    lines: Lines<BufReader<ZstdDecoder<BufReader<File>>>>,
+}
+
+impl Reader {
+    pub async fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path).await?;
+        let decoder = ZstdDecoder::new(BufReader::new(file));
+        Ok(Self {
+            lines: BufReader::new(decoder).lines(),
+        })
+    }
+
+    /// The next record, or none at the end of the file. A file cut short, as when Vector crashed
+    /// while writing it, fails after its last complete record.
+    pub async fn next(&mut self) -> io::Result<Option<Record>> {
+        loop {
+            match self.lines.next_line().await? {
+                Some(line) if line.trim().is_empty() => continue,
+                Some(line) => {
+                    return serde_json::from_str(&line)
+                        .map(Some)
+                        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_compression::tokio::write::ZstdEncoder;
+    use chrono::TimeZone;
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+    use crate::{event::LogEvent, test_util::temp_dir};
+
+    #[tokio::test]
+    async fn round_trips_records() {
+        let archived_at = Utc.ymd(2022, 6, 1).and_hms(12, 0, 0);
+        let mut log = LogEvent::from("hello");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 6, 1).and_hms(11, 59, 0),
+        );
+        log.metadata_mut().set_priority(EventPriority::High);
+        log.metadata_mut().set_tenant(Some("team-a".into()));
+        let record = Record::new(Event::from(log.clone()), archived_at);
+        assert_eq!(record.timestamp, Utc.ymd(2022, 6, 1).and_hms(11, 59, 0));
+
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join(file_name(archived_at, 0));
+        let mut encoder = ZstdEncoder::new(File::create(&path).await.unwrap());
+        let mut line = serde_json::to_vec(&record).unwrap();
+        line.push(b'\n');
+        encoder.write_all(&line).await.unwrap();
+        encoder.shutdown().await.unwrap();
+        File::create(dir.join(".pending.ndjson.zst")).await.unwrap();
+
+        assert_eq!(files(&dir).await.unwrap(), vec![path.clone()]);
+        let mut reader = Reader::open(&path).await.unwrap();
+        let event = reader.next().await.unwrap().unwrap().into_event();
+        assert_eq!(event.metadata().priority(), EventPriority::High);
+        assert_eq!(event.metadata().tenant().as_deref(), Some("team-a"));
+        assert_eq!(event.into_log(), log);
+        assert!(reader.next().await.unwrap().is_none());
+    }
+}
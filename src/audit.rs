@@ -0,0 +1,247 @@
+//! Audit trail of the actions that change how Vector runs, such as configuration reloads, API
+//! mutations, tap sessions and the resolution of secrets, which `internal_audit` sources emit as
+//! log events.
+//!
+//! Actions recorded before the topology first starts, such as the resolution of the secrets of the
+//! initial configuration, are buffered until then, so that sources built with that topology
+//! receive them.
+
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use value::Value;
+
+use crate::{
+    config::{ComponentChanges, ComponentKey, Config},
+    event::LogEvent,
+};
+
+/// The number of audit events kept for each subscriber that is behind, and before the topology
+/// first starts.
+const CAPACITY: usize = 1000;
+
+static BUFFER: Lazy<Mutex<Option<Vec<LogEvent>>>> = Lazy::new(|| Mutex::new(Some(Vec::new())));
+
+static SENDER: Lazy<broadcast::Sender<LogEvent>> = Lazy::new(|| broadcast::channel(CAPACITY).0);
+
+/// An action to record in the audit trail.
+#[derive(Debug)]
+pub enum AuditAction<'a> {
+    /// The configuration was reloaded, which `outcome` tells whether it was `applied`,
+    /// `rolled_back` to the previous configuration, `rejected` before any component changed, or
+    /// `failed` altogether.
+    ConfigReload {
+        outcome: &'static str,
+        changes: &'a ComponentChanges,
+        /// The names of the options that changed, for each changed component.
+        changed_options: &'a [(ComponentKey, Vec<String>)],
+    },
+    /// A mutation of the API changed the running instance.
+    ApiMutation {
+        mutation: &'static str,
+        arguments: Vec<(&'static str, Value)>,
+    },
+    /// A client started tapping the events of the components matching the patterns.
+    TapStarted {
+        id: &'a str,
+        outputs_patterns: Vec<String>,
+        inputs_patterns: Vec<String>,
+    },
+    /// A client stopped tapping events.
+    TapStopped { id: &'a str },
+    /// Secrets were retrieved from a backend. Only the keys of the secrets are recorded.
+    SecretsResolved {
+        backend: &'a str,
+        keys: &'a [String],
+        success: bool,
+    },
+}
+
+impl AuditAction<'_> {
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::ConfigReload { .. } => "config_reload",
+            Self::ApiMutation { .. } => "api_mutation",
+            Self::TapStarted { .. } => "tap_started",
+            Self::TapStopped { .. } => "tap_stopped",
+            Self::SecretsResolved { .. } => "secrets_resolved",
+        }
+    }
+
+    fn into_log(self) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert("action", self.name());
+        match self {
+            Self::ConfigReload {
+                outcome,
+                changes,
+                changed_options,
+            } => {
+                log.insert("message", "Configuration reloaded.");
+                log.insert("outcome", outcome);
+                log.insert("components.added", ids(&changes.added));
+                log.insert("components.changed", ids(&changes.changed));
+                log.insert("components.removed", ids(&changes.removed));
+                for (key, options) in changed_options {
+                    let path = format!("changed_options.\"{}\"", key.id());
+                    log.insert(path.as_str(), options.clone());
+                }
+            }
+            Self::ApiMutation {
+                mutation,
+                arguments,
+            } => {
+                log.insert("message", "API mutation applied.");
+                log.insert("mutation", mutation);
+                for (name, value) in arguments {
+                    let path = format!("arguments.{}", name);
+                    log.insert(path.as_str(), value);
+                }
+            }
+            Self::TapStarted {
+                id,
+                outputs_patterns,
+                inputs_patterns,
+            } => {
+                log.insert("message", "Tap session started.");
+                log.insert("tap_id", id);
+                log.insert("outputs_patterns", outputs_patterns);
+                log.insert("inputs_patterns", inputs_patterns);
+            }
+            Self::TapStopped { id } => {
+                log.insert("message", "Tap session stopped.");
+                log.insert("tap_id", id);
+            }
+            Self::SecretsResolved {
+                backend,
+                keys,
+                success,
+            } => {
+                log.insert("message", "Secrets resolved.");
+                log.insert("backend", backend);
+                log.insert("secret_keys", keys.to_vec());
+                log.insert("outcome", if success { "success" } else { "failure" });
+            }
+        }
+        log.insert(
+            crate::config::log_schema().source_type_key(),
+            Bytes::from("internal_audit"),
+        );
+        log.insert(crate::config::log_schema().timestamp_key(), Utc::now());
+        log
+    }
+}
+
+fn ids(keys: &[ComponentKey]) -> Vec<&str> {
+    keys.iter().map(ComponentKey::id).collect()
+}
+
+/// Records an action in the audit trail.
+pub fn record(action: AuditAction<'_>) {
+    let log = action.into_log();
+    let mut buffer = BUFFER.lock().expect("audit buffer lock poisoned");
+    if let Some(buffer) = buffer.as_mut() {
+        if buffer.len() < CAPACITY {
+            buffer.push(log.clone());
+        }
+    }
+    // Nothing may be listening, which is fine.
+    let _ = SENDER.send(log);
+}
+
+/// Subscribes to the audit trail, returning the actions recorded before the topology first
+/// started if it hasn't yet, along with a receiver of the actions recorded from now on.
+pub fn subscribe() -> (Vec<LogEvent>, broadcast::Receiver<LogEvent>) {
+    let buffer = BUFFER.lock().expect("audit buffer lock poisoned");
+    let buffered = buffer.clone().unwrap_or_default();
+    (buffered, SENDER.subscribe())
+}
+
+/// Stops buffering the actions recorded, once the sources of the first topology have subscribed.
+pub fn stop_early_buffering() {
+    BUFFER.lock().expect("audit buffer lock poisoned").take();
+}
+
+/// Returns the names of the top-level options that differ between the old and new configuration
+/// of each of the given components.
+pub fn changed_options(
+    old: &Config,
+    new: &Config,
+    keys: &[ComponentKey],
+) -> Vec<(ComponentKey, Vec<String>)> {
+    keys.iter()
+        .map(|key| {
+            let old = component_json(old, key);
+            let new = component_json(new, key);
+            (key.clone(), differing_keys(&old, &new))
+        })
+        .collect()
+}
+
+fn component_json(config: &Config, key: &ComponentKey) -> serde_json::Value {
+    fn to_json(component: impl Serialize) -> serde_json::Value {
+        serde_json::to_value(component).unwrap_or(serde_json::Value::Null)
+    }
+
+    if let Some(source) = config.source(key) {
+        to_json(source)
+    } else if let Some(transform) = config.transform(key) {
+        to_json(transform)
+    } else if let Some(sink) = config.sink(key) {
+        to_json(sink)
+    } else {
+        serde_json::Value::Null
+    }
+}
+
+fn differing_keys(old: &serde_json::Value, new: &serde_json::Value) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let old = old.as_object().unwrap_or(&empty);
+    let new = new.as_object().unwrap_or(&empty);
+    let mut keys: Vec<String> = old
+        .keys()
+        .chain(new.keys())
+        .filter(|key| old.get(*key) != new.get(*key))
+        .cloned()
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn finds_changed_options() {
+        let old = json!({"type": "http", "uri": "http://a", "compression": "none"});
+        let new = json!({"type": "http", "uri": "http://b", "batch": {"max_events": 10}});
+        assert_eq!(
+            differing_keys(&old, &new),
+            vec!["batch", "compression", "uri"]
+        );
+    }
+
+    #[test]
+    fn records_secret_keys_without_values() {
+        let log = AuditAction::SecretsResolved {
+            backend: "vault",
+            keys: &["db_password".to_owned()],
+            success: true,
+        }
+        .into_log();
+        assert_eq!(log["action"], "secrets_resolved".into());
+        assert_eq!(
+            log["secret_keys"],
+            Value::from(vec![Value::from("db_password")])
+        );
+        assert_eq!(log["outcome"], "success".into());
+    }
+}
@@ -1,15 +1,28 @@
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use aws_config::{
-    default_provider::credentials::DefaultCredentialsChain, sts::AssumeRoleProviderBuilder,
+    default_provider::credentials::DefaultCredentialsChain,
+    meta::credentials::LazyCachingCredentialsProvider,
+    provider_config::ProviderConfig,
+    sts::AssumeRoleProviderBuilder,
+    web_identity_token::{StaticConfiguration, WebIdentityTokenCredentialsProvider},
+};
+use aws_types::{
+    credentials::{ProvideCredentials, SharedCredentialsProvider},
+    region::Region,
+    Credentials,
 };
-use aws_types::{credentials::SharedCredentialsProvider, region::Region, Credentials};
 use serde::{Deserialize, Serialize};
 
+use super::container_credentials::ContainerCredentialsProvider;
+
 // matches default load timeout from the SDK as of 0.10.1, but lets us confidently document the
 // default rather than relying on the SDK default to not change
 const DEFAULT_LOAD_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// The name of the sessions of the roles assumed by Vector, which shows up in AWS CloudTrail.
+const DEFAULT_SESSION_NAME: &str = "vector";
+
 /// Configuration for configuring authentication strategy for AWS.
 #[derive(Serialize, Deserialize, Clone, Debug, Derivative)]
 #[derivative(Default)]
@@ -26,6 +39,24 @@ pub enum AwsAuthentication {
     },
     Role {
         assume_role: String,
+        external_id: Option<String>,
+        #[serde(default)]
+        chained_roles: Vec<ChainedRole>,
+        load_timeout_secs: Option<u64>,
+    },
+    WebIdentity {
+        web_identity_token_file: PathBuf,
+        role_arn: String,
+        session_name: Option<String>,
+        #[serde(default)]
+        chained_roles: Vec<ChainedRole>,
+        load_timeout_secs: Option<u64>,
+    },
+    Container {
+        container_credentials_uri: String,
+        container_authorization_token_file: Option<PathBuf>,
+        #[serde(default)]
+        chained_roles: Vec<ChainedRole>,
         load_timeout_secs: Option<u64>,
     },
     // Default variant is used instead of Option<AWSAuthentication> since even for
@@ -34,6 +65,14 @@ pub enum AwsAuthentication {
     Default { load_timeout_secs: Option<u64> },
 }
 
+/// A role assumed with the credentials of the role, or other credentials, that come before it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ChainedRole {
+    pub assume_role: String,
+    pub external_id: Option<String>,
+}
+
 impl AwsAuthentication {
     pub async fn credentials_provider(
         &self,
@@ -53,13 +92,57 @@ impl AwsAuthentication {
             }
             AwsAuthentication::Role {
                 assume_role,
+                external_id,
+                chained_roles,
+                load_timeout_secs,
+            } => {
+                let provider =
+                    default_credentials_provider(region.clone(), *load_timeout_secs).await;
+                let provider = assume_role_provider(
+                    provider,
+                    &ChainedRole {
+                        assume_role: assume_role.clone(),
+                        external_id: external_id.clone(),
+                    },
+                    &region,
+                );
+
+                Ok(chain_roles(provider, chained_roles, &region))
+            }
+            AwsAuthentication::WebIdentity {
+                web_identity_token_file,
+                role_arn,
+                session_name,
+                chained_roles,
+                load_timeout_secs,
+            } => {
+                let provider = WebIdentityTokenCredentialsProvider::builder()
+                    .configure(&ProviderConfig::without_region().with_region(Some(region.clone())))
+                    .static_configuration(StaticConfiguration {
+                        web_identity_token_file: web_identity_token_file.clone(),
+                        role_arn: role_arn.clone(),
+                        session_name: session_name
+                            .clone()
+                            .unwrap_or_else(|| DEFAULT_SESSION_NAME.to_owned()),
+                    })
+                    .build();
+                let provider = caching_provider(provider, *load_timeout_secs);
+
+                Ok(chain_roles(provider, chained_roles, &region))
+            }
+            AwsAuthentication::Container {
+                container_credentials_uri,
+                container_authorization_token_file,
+                chained_roles,
                 load_timeout_secs,
             } => {
-                let provider = AssumeRoleProviderBuilder::new(assume_role)
-                    .region(region.clone())
-                    .build(default_credentials_provider(region, *load_timeout_secs).await);
+                let provider = ContainerCredentialsProvider::new(
+                    container_credentials_uri,
+                    container_authorization_token_file.clone(),
+                )?;
+                let provider = caching_provider(provider, *load_timeout_secs);
 
-                Ok(SharedCredentialsProvider::new(provider))
+                Ok(chain_roles(provider, chained_roles, &region))
             }
             AwsAuthentication::Default { load_timeout_secs } => Ok(SharedCredentialsProvider::new(
                 default_credentials_provider(region, *load_timeout_secs).await,
@@ -76,6 +159,52 @@ impl AwsAuthentication {
     }
 }
 
+/// Wraps a provider that loads credentials on every call, so that they are only loaded again
+/// shortly before they expire.
+fn caching_provider(
+    provider: impl ProvideCredentials + 'static,
+    load_timeout_secs: Option<u64>,
+) -> SharedCredentialsProvider {
+    SharedCredentialsProvider::new(
+        LazyCachingCredentialsProvider::builder()
+            .load(provider)
+            .load_timeout(
+                load_timeout_secs
+                    .map(Duration::from_secs)
+                    .unwrap_or(DEFAULT_LOAD_TIMEOUT),
+            )
+            .build(),
+    )
+}
+
+/// Assumes the role with the credentials of the given provider. The credentials of the role are
+/// refreshed automatically before they expire.
+fn assume_role_provider(
+    provider: SharedCredentialsProvider,
+    role: &ChainedRole,
+    region: &Region,
+) -> SharedCredentialsProvider {
+    let mut builder = AssumeRoleProviderBuilder::new(&role.assume_role)
+        .region(region.clone())
+        .session_name(DEFAULT_SESSION_NAME);
+    if let Some(external_id) = &role.external_id {
+        builder = builder.external_id(external_id);
+    }
+
+    SharedCredentialsProvider::new(builder.build(provider))
+}
+
+/// Assumes each of the roles in turn, starting with the credentials of the given provider.
+fn chain_roles(
+    provider: SharedCredentialsProvider,
+    roles: &[ChainedRole],
+    region: &Region,
+) -> SharedCredentialsProvider {
+    roles.iter().fold(provider, |provider, role| {
+        assume_role_provider(provider, role, region)
+    })
+}
+
 async fn default_credentials_provider(
     region: Region,
     load_timeout_secs: Option<u64>,
@@ -170,6 +299,7 @@ mod tests {
             AwsAuthentication::Role {
                 assume_role,
                 load_timeout_secs,
+                ..
             } => {
                 assert_eq!(&assume_role, "auth.root");
                 assert_eq!(load_timeout_secs, Some(10));
@@ -230,4 +360,77 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn parsing_chained_roles() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.assume_role = "arn:aws:iam::111111111111:role/first"
+            auth.chained_roles = [
+                { assume_role = "arn:aws:iam::222222222222:role/second", external_id = "id" },
+            ]
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::Role {
+                assume_role,
+                external_id,
+                chained_roles,
+                ..
+            } => {
+                assert_eq!(&assume_role, "arn:aws:iam::111111111111:role/first");
+                assert_eq!(external_id, None);
+                assert_eq!(chained_roles.len(), 1);
+                assert_eq!(
+                    &chained_roles[0].assume_role,
+                    "arn:aws:iam::222222222222:role/second"
+                );
+                assert_eq!(chained_roles[0].external_id.as_deref(), Some("id"));
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parsing_web_identity() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.web_identity_token_file = "/var/run/secrets/token"
+            auth.role_arn = "arn:aws:iam::111111111111:role/irsa"
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::WebIdentity {
+                web_identity_token_file,
+                role_arn,
+                session_name,
+                ..
+            } => {
+                assert_eq!(
+                    web_identity_token_file,
+                    PathBuf::from("/var/run/secrets/token")
+                );
+                assert_eq!(&role_arn, "arn:aws:iam::111111111111:role/irsa");
+                assert_eq!(session_name, None);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parsing_container() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.container_credentials_uri = "http://169.254.170.23/v1/credentials"
+            auth.container_authorization_token_file = "/var/run/secrets/pods.eks.amazonaws.com/serviceaccount/eks-pod-identity-token"
+        "#,
+        )
+        .unwrap();
+
+        assert!(matches!(config.auth, AwsAuthentication::Container { .. }));
+    }
 }
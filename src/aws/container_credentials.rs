@@ -0,0 +1,121 @@
+//! Credentials served over HTTP by an agent running next to the container, such as the EKS Pod
+//! Identity agent.
+
+use std::{path::PathBuf, time::SystemTime};
+
+use aws_types::{
+    credentials::{future, CredentialsError, ProvideCredentials},
+    Credentials,
+};
+use chrono::{DateTime, Utc};
+use http::Uri;
+use hyper::Body;
+use serde::Deserialize;
+
+use crate::{config::ProxyConfig, http::HttpClient};
+
+/// Loads credentials from a container credentials endpoint. These aren't cached, so the provider
+/// is meant to be wrapped in a caching provider, which refreshes them before they expire.
+#[derive(Debug)]
+pub(super) struct ContainerCredentialsProvider {
+    client: HttpClient,
+    uri: Uri,
+    authorization_token_file: Option<PathBuf>,
+}
+
+/// The credentials as served by the endpoint.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    token: Option<String>,
+    expiration: Option<DateTime<Utc>>,
+}
+
+impl ContainerCredentialsProvider {
+    pub(super) fn new(uri: &str, authorization_token_file: Option<PathBuf>) -> crate::Result<Self> {
+        Ok(Self {
+            // The endpoint is local to the container, so requests never go through a proxy.
+            client: HttpClient::new(None, &ProxyConfig::default())?,
+            uri: uri.parse()?,
+            authorization_token_file,
+        })
+    }
+
+    async fn credentials(&self) -> crate::Result<Credentials> {
+        let mut request = http::Request::get(&self.uri);
+        if let Some(path) = &self.authorization_token_file {
+            // The token is rotated by the agent, so it's read again for every request.
+            let token = tokio::fs::read_to_string(path).await?;
+            request = request.header(http::header::AUTHORIZATION, token.trim());
+        }
+
+        let response = self.client.send(request.body(Body::empty())?).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if !status.is_success() {
+            return Err(format!(
+                "Container credentials endpoint responded with {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )
+            .into());
+        }
+
+        parse_credentials(&body)
+    }
+}
+
+fn parse_credentials(body: &[u8]) -> crate::Result<Credentials> {
+    let credentials: ContainerCredentials = serde_json::from_slice(body)?;
+    Ok(Credentials::new(
+        credentials.access_key_id,
+        credentials.secret_access_key,
+        credentials.token,
+        credentials.expiration.map(SystemTime::from),
+        "ContainerCredentials",
+    ))
+}
+
+impl ProvideCredentials for ContainerCredentialsProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(async move {
+            self.credentials()
+                .await
+                .map_err(CredentialsError::provider_error)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_credentials() {
+        let credentials = parse_credentials(
+            br#"{
+                "AccessKeyId": "key",
+                "SecretAccessKey": "secret",
+                "Token": "token",
+                "AccountId": "123456789012",
+                "Expiration": "2030-01-01T00:00:00Z"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(credentials.access_key_id(), "key");
+        assert_eq!(credentials.secret_access_key(), "secret");
+        assert_eq!(credentials.session_token(), Some("token"));
+        assert_eq!(
+            credentials.expiry(),
+            Some(SystemTime::from(
+                "2030-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+            ))
+        );
+    }
+}
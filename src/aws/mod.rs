@@ -1,5 +1,8 @@
 pub mod auth;
+mod container_credentials;
 pub mod region;
+#[cfg(feature = "aws-sigv4")]
+pub mod sigv4;
 
 use std::future::Future;
 use std::pin::Pin;
@@ -0,0 +1,812 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use futures::{future, stream::BoxStream, FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use vector_core::{
+    config::{DataType, Input, Output},
+    event::{BatchNotifier, BatchStatus, Event, Metric, MetricKind, MetricValue},
+    schema,
+    sink::{StreamSink, VectorSink},
+    ByteSizeOf,
+};
+
+use crate::{
+    config::{
+        self, AcknowledgementsConfig, ComponentKey, ConfigBuilder, SinkConfig, SinkContext,
+        SourceConfig, SourceContext,
+    },
+    sinks::Healthcheck,
+    sources,
+    topology::{self, builder},
+};
+
+const INPUT_ID: &str = "vector_bench_input";
+const OUTPUT_ID: &str = "vector_bench_output";
+// How often events are sent at a target rate.
+const TICK: Duration = Duration::from_millis(10);
+// How many events are sent at once without a target rate.
+const BATCH_SIZE: u64 = 1000;
+const ALLOCATION_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[clap(
+        name = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        use_value_delimiter(true)
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[clap(name = "config-toml", long, use_value_delimiter(true))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[clap(name = "config-json", long, use_value_delimiter(true))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[clap(name = "config-yaml", long, use_value_delimiter(true))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[clap(
+        name = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        use_value_delimiter(true)
+    )]
+    config_dirs: Vec<PathBuf>,
+
+    /// The ID of the source, transform or sink to benchmark. Sources run on their own inputs,
+    /// while transforms and sinks are sent generated or replayed events.
+    component: String,
+
+    /// A file of events to replay, one event per line, sent over and over. Lines are read as
+    /// messages of logs, or as JSON metrics for components that only accept metrics. Without it,
+    /// generated access logs or counters are sent.
+    #[clap(short, long)]
+    input: Option<PathBuf>,
+
+    /// The number of events to send per second, rather than as many as the component takes.
+    #[clap(short, long)]
+    rate: Option<u64>,
+
+    /// How long to run the benchmark for, in seconds.
+    #[clap(short, long, default_value = "10")]
+    duration: u64,
+
+    /// The number of events to send, stopping before the duration is up once they are sent.
+    #[clap(short = 'n', long)]
+    events: Option<u64>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+
+    fn settings(&self) -> Settings {
+        Settings {
+            input: self.input.clone(),
+            rate: self.rate,
+            duration: Duration::from_secs(self.duration),
+            events: self.events,
+        }
+    }
+}
+
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let result = async {
+        let paths = config::process_paths(&opts.paths_with_formats())
+            .ok_or_else(|| vec!["No config paths found.".to_owned()])?;
+        config::init_log_schema(&paths, false)?;
+        let (builder, warnings) = config::load_builder_from_paths(&paths)?;
+        for warning in warnings {
+            eprintln!("~ {}", warning);
+        }
+        bench(
+            builder,
+            &ComponentKey::from(opts.component.as_str()),
+            opts.settings(),
+        )
+        .await
+    };
+
+    match result.await {
+        Ok(report) => {
+            println!(
+                "Benchmarked {} {:?} ({}) for {:.2}s.",
+                report.kind,
+                opts.component,
+                report.component_type,
+                report.elapsed.as_secs_f64()
+            );
+            if let Some(sent) = &report.sent {
+                println!("  {:<10} {}", "sent", sent.display(report.elapsed));
+            }
+            let received = if report.kind == "sink" {
+                "delivered"
+            } else {
+                "received"
+            };
+            println!(
+                "  {:<10} {}",
+                received,
+                report.received.display(report.elapsed)
+            );
+            if report.failed > 0 {
+                println!("  {:<10} {:>12} events", "failed", report.failed);
+            }
+            if let Some(latency) = &report.latency {
+                println!(
+                    "  {:<10} p50 {:.2?}, p90 {:.2?}, p99 {:.2?}, max {:.2?}",
+                    "latency", latency.p50, latency.p90, latency.p99, latency.max
+                );
+            }
+            match &report.allocated {
+                Some(allocated) => println!(
+                    "  {:<10} {} at the start, {} at the peak, {} at the end",
+                    "allocated",
+                    mebibytes(allocated.start),
+                    mebibytes(allocated.peak),
+                    mebibytes(allocated.end)
+                ),
+                None => println!(
+                    "  {:<10} unknown, as Vector wasn't built with the jemalloc allocator",
+                    "allocated"
+                ),
+            }
+            exitcode::OK
+        }
+        Err(errors) => {
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            exitcode::CONFIG
+        }
+    }
+}
+
+fn mebibytes(bytes: u64) -> String {
+    format!("{:.2} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// How to drive the component being benchmarked.
+#[derive(Debug, Default)]
+struct Settings {
+    input: Option<PathBuf>,
+    rate: Option<u64>,
+    duration: Duration,
+    events: Option<u64>,
+}
+
+/// The results of a benchmark.
+#[derive(Debug)]
+struct Report {
+    kind: &'static str,
+    component_type: &'static str,
+    elapsed: Duration,
+    /// The events sent to the component, unless it's a source.
+    sent: Option<Throughput>,
+    /// The events that came out of the component, or that a sink delivered.
+    received: Throughput,
+    /// The events a sink failed to deliver.
+    failed: u64,
+    /// The time it took for the events sent to the component to be processed, unless it's a
+    /// source.
+    latency: Option<Latency>,
+    allocated: Option<Allocated>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Throughput {
+    events: u64,
+    /// The in-memory size of the events.
+    bytes: u64,
+}
+
+impl Throughput {
+    fn display(&self, elapsed: Duration) -> String {
+        let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+        format!(
+            "{:>12} events, {:>12.1} events/s, {}/s",
+            self.events,
+            self.events as f64 / secs,
+            mebibytes((self.bytes as f64 / secs) as u64)
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Latency {
+    p50: Duration,
+    p90: Duration,
+    p99: Duration,
+    max: Duration,
+}
+
+impl Latency {
+    /// Computes the percentiles of the latencies of batches of events, weighted by the number
+    /// of events in each batch.
+    fn new(mut latencies: Vec<(Duration, u64)>) -> Option<Self> {
+        latencies.sort_unstable();
+        let total: u64 = latencies.iter().map(|(_, events)| events).sum();
+        if total == 0 {
+            return None;
+        }
+        let percentile = |percentile: f64| {
+            let rank = ((total as f64 * percentile).ceil() as u64).max(1);
+            let mut seen = 0;
+            latencies
+                .iter()
+                .find(|(_, events)| {
+                    seen += events;
+                    seen >= rank
+                })
+                .map_or(Duration::ZERO, |(latency, _)| *latency)
+        };
+        Some(Self {
+            p50: percentile(0.5),
+            p90: percentile(0.9),
+            p99: percentile(0.99),
+            max: percentile(1.0),
+        })
+    }
+}
+
+/// The bytes allocated by Vector over the course of a benchmark.
+#[derive(Debug)]
+struct Allocated {
+    start: u64,
+    peak: u64,
+    end: u64,
+}
+
+#[cfg(feature = "tikv-jemalloc-ctl")]
+fn allocated() -> Option<u64> {
+    tikv_jemalloc_ctl::epoch::advance().ok()?;
+    tikv_jemalloc_ctl::stats::allocated::read()
+        .ok()
+        .map(|bytes| bytes as u64)
+}
+
+#[cfg(not(feature = "tikv-jemalloc-ctl"))]
+const fn allocated() -> Option<u64> {
+    None
+}
+
+/// The counts shared between the components driving and capturing the events of a benchmark.
+#[derive(Debug, Default)]
+struct Stats {
+    sent: Counts,
+    received: Counts,
+    delivered: Counts,
+    failed: AtomicU64,
+    latencies: Mutex<Vec<(Duration, u64)>>,
+    /// The tasks awaiting the acknowledgement of the batches of events sent.
+    acknowledgements: Mutex<Vec<JoinHandle<()>>>,
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    events: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl Counts {
+    fn add(&self, events: u64, bytes: u64) {
+        self.events.fetch_add(events, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> Throughput {
+        Throughput {
+            events: self.events.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Stats {
+    fn acknowledged(&self, status: BatchStatus, events: u64, bytes: u64, latency: Duration) {
+        if status == BatchStatus::Delivered {
+            self.delivered.add(events, bytes);
+        } else {
+            self.failed.fetch_add(events, Ordering::Relaxed);
+        }
+        self.latencies.lock().unwrap().push((latency, events));
+    }
+}
+
+/// The events sent to a transform or sink.
+#[derive(Debug, Default)]
+struct Events {
+    /// The events replayed over and over, or none to generate them.
+    replayed: Vec<Event>,
+    metrics: bool,
+}
+
+impl Events {
+    fn load(input: Option<&Path>, metrics: bool) -> Result<Self, Vec<String>> {
+        let path = match input {
+            Some(path) => path,
+            None => {
+                return Ok(Self {
+                    replayed: Vec::new(),
+                    metrics,
+                })
+            }
+        };
+        let content = std::fs::read_to_string(path)
+            .map_err(|error| vec![format!("Couldn't read input {:?}: {}.", path, error)])?;
+
+        let mut replayed = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if metrics {
+                let metric = serde_json::from_str::<Metric>(line).map_err(|error| {
+                    vec![format!(
+                        "Invalid metric on line {} of {:?}: {}.",
+                        i + 1,
+                        path,
+                        error
+                    )]
+                })?;
+                replayed.push(Event::Metric(metric));
+            } else {
+                replayed.push(Event::from(line));
+            }
+        }
+        if replayed.is_empty() {
+            return Err(vec![format!("Input {:?} has no events.", path)]);
+        }
+        Ok(Self { replayed, metrics })
+    }
+
+    /// Makes the `n`th event sent.
+    fn event(&self, n: u64, timestamp: &str) -> Event {
+        if !self.replayed.is_empty() {
+            return self.replayed[(n % self.replayed.len() as u64) as usize].clone();
+        }
+        if self.metrics {
+            Event::Metric(Metric::new(
+                "bench_events_total",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            ))
+        } else {
+            Event::from(format!(
+                "10.0.{}.{} - - [{}] \"GET /items/{} HTTP/1.1\" 200 {}",
+                n / 256 % 256,
+                n % 256,
+                timestamp,
+                n,
+                512 + n % 1024
+            ))
+        }
+    }
+}
+
+/// Runs a component of a configuration on its own for a while, with the events it receives sent
+/// by a source driving it and the events it sends captured by a sink, and reports its
+/// throughput and latency.
+async fn bench(
+    mut builder: ConfigBuilder,
+    key: &ComponentKey,
+    settings: Settings,
+) -> Result<Report, Vec<String>> {
+    let stats = Arc::new(Stats::default());
+
+    let (kind, component_type, input, outputs) = if let Some(source) = builder.sources.get(key) {
+        if settings.input.is_some() || settings.rate.is_some() || settings.events.is_some() {
+            return Err(vec![
+                "Sources run on their own inputs, without `--input`, `--rate` or `--events`."
+                    .to_owned(),
+            ]);
+        }
+        (
+            "source",
+            source.inner.source_type(),
+            None,
+            Some(source.inner.outputs()),
+        )
+    } else if let Some(transform) = builder.transforms.get(key) {
+        (
+            "transform",
+            transform.inner.transform_type(),
+            Some(transform.inner.input().data_type()),
+            Some(transform.inner.outputs(&schema::Definition::empty())),
+        )
+    } else if let Some(sink) = builder.sinks.get(key) {
+        (
+            "sink",
+            sink.inner.sink_type(),
+            Some(sink.inner.input().data_type()),
+            None,
+        )
+    } else {
+        return Err(vec![format!("Unknown component {:?}.", key.id())]);
+    };
+
+    // Only the component being benchmarked is kept, along with the enrichment tables it may use.
+    builder.sources.retain(|id, _| id == key);
+    builder.transforms.retain(|id, _| id == key);
+    builder.sinks.retain(|id, _| id == key);
+    builder.tests.clear();
+    #[cfg(feature = "enterprise")]
+    {
+        builder.enterprise = None;
+    }
+
+    if let Some(input) = input {
+        let metrics = if input.contains(DataType::Log) {
+            false
+        } else if input.contains(DataType::Metric) {
+            true
+        } else {
+            return Err(vec![format!(
+                "Benchmarking {}s that only accept traces isn't supported.",
+                kind
+            )]);
+        };
+        let source = BenchSourceConfig {
+            events: Arc::new(Events::load(settings.input.as_deref(), metrics)?),
+            rate: settings.rate,
+            duration: settings.duration,
+            limit: settings.events,
+            stats: Arc::clone(&stats),
+        };
+        builder.add_source(INPUT_ID, source);
+        if let Some(transform) = builder.transforms.get_mut(key) {
+            transform.inputs = vec![INPUT_ID.to_owned()];
+        }
+        if let Some(sink) = builder.sinks.get_mut(key) {
+            sink.inputs = vec![INPUT_ID.to_owned()];
+        }
+    }
+    if let Some(outputs) = outputs {
+        let inputs = outputs
+            .into_iter()
+            .map(|output| match output.port {
+                Some(port) => format!("{}.{}", key, port),
+                None => key.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let inputs = inputs.iter().map(String::as_str).collect::<Vec<_>>();
+        let sink = CapturingSinkConfig {
+            stats: Arc::clone(&stats),
+        };
+        builder.add_sink(OUTPUT_ID, &inputs, sink);
+    }
+
+    let config = builder.build()?;
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = builder::build_pieces(&config, &diff, HashMap::new()).await?;
+
+    let start_allocated = allocated();
+    let peak_allocated = Arc::new(AtomicU64::new(start_allocated.unwrap_or(0)));
+    let sampler = start_allocated.map(|_| {
+        let peak_allocated = Arc::clone(&peak_allocated);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(ALLOCATION_SAMPLE_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Some(bytes) = allocated() {
+                    peak_allocated.fetch_max(bytes, Ordering::Relaxed);
+                }
+            }
+        })
+    });
+
+    let started = Instant::now();
+    let (topology, _) = topology::start_validated(config, diff, pieces)
+        .await
+        .ok_or_else(|| vec!["Couldn't start the topology.".to_owned()])?;
+    if kind == "source" {
+        let _ = tokio::time::timeout(settings.duration, topology.sources_finished()).await;
+    } else {
+        topology.sources_finished().await;
+    }
+    topology.stop().await;
+    let elapsed = started.elapsed();
+
+    // Every event has been processed or dropped once the topology is stopped, so the
+    // acknowledgements of all the batches come in.
+    let acknowledgements = std::mem::take(&mut *stats.acknowledgements.lock().unwrap());
+    future::join_all(acknowledgements).await;
+
+    if let Some(sampler) = sampler {
+        sampler.abort();
+    }
+    let allocated = start_allocated.and_then(|start| {
+        let end = allocated()?;
+        Some(Allocated {
+            start,
+            peak: peak_allocated.load(Ordering::Relaxed).max(end),
+            end,
+        })
+    });
+
+    let latencies = std::mem::take(&mut *stats.latencies.lock().unwrap());
+    Ok(Report {
+        kind,
+        component_type,
+        elapsed,
+        sent: (kind != "source").then(|| stats.sent.get()),
+        received: if kind == "sink" {
+            stats.delivered.get()
+        } else {
+            stats.received.get()
+        },
+        failed: stats.failed.load(Ordering::Relaxed),
+        latency: Latency::new(latencies),
+        allocated,
+    })
+}
+
+/// Sends events to the component being benchmarked, at a target rate or as fast as it takes
+/// them, attaching a batch notifier to each batch to time how long it takes to be processed.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct BenchSourceConfig {
+    #[serde(skip)]
+    events: Arc<Events>,
+    #[serde(skip)]
+    rate: Option<u64>,
+    #[serde(skip)]
+    duration: Duration,
+    #[serde(skip)]
+    limit: Option<u64>,
+    #[serde(skip)]
+    stats: Arc<Stats>,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "bench")]
+impl SourceConfig for BenchSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let events = Arc::clone(&self.events);
+        let stats = Arc::clone(&self.stats);
+        let (rate, duration, limit) = (self.rate, self.duration, self.limit);
+
+        Ok(Box::pin(async move {
+            let mut out = cx.out;
+            // The topology is shut down once the benchmark is over.
+            let _shutdown = cx.shutdown;
+
+            let started = Instant::now();
+            let mut interval = tokio::time::interval(TICK);
+            // The fraction of an event owed at a target rate, carried over to the next tick.
+            let mut owed = 0.0;
+            let mut sent = 0;
+            while started.elapsed() < duration && limit.map_or(true, |limit| sent < limit) {
+                let mut size = match rate {
+                    Some(rate) => {
+                        interval.tick().await;
+                        owed += rate as f64 * TICK.as_secs_f64();
+                        let size = owed as u64;
+                        owed -= size as f64;
+                        size
+                    }
+                    None => BATCH_SIZE,
+                };
+                if let Some(limit) = limit {
+                    size = size.min(limit - sent);
+                }
+                if size == 0 {
+                    continue;
+                }
+
+                let timestamp = chrono::Utc::now()
+                    .format("%d/%b/%Y:%H:%M:%S %z")
+                    .to_string();
+                let mut batch = (sent..sent + size)
+                    .map(|n| events.event(n, &timestamp))
+                    .collect::<Vec<_>>();
+                sent += size;
+                let bytes = batch.size_of() as u64;
+                let (notifier, receiver) = BatchNotifier::new_with_receiver();
+                for event in &mut batch {
+                    event.add_batch_notifier(Arc::clone(&notifier));
+                }
+                drop(notifier);
+
+                let sent_at = Instant::now();
+                out.send_batch(batch).await.map_err(|_| ())?;
+                stats.sent.add(size, bytes);
+                let acknowledgement = {
+                    let stats = Arc::clone(&stats);
+                    tokio::spawn(async move {
+                        let status = receiver.await;
+                        stats.acknowledged(status, size, bytes, sent_at.elapsed());
+                    })
+                };
+                stats.acknowledgements.lock().unwrap().push(acknowledgement);
+            }
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        let ty = if self.events.metrics {
+            DataType::Metric
+        } else {
+            DataType::Log
+        };
+        vec![Output::default(ty)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "bench"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Counts the events sent by the component being benchmarked, then drops them.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CapturingSinkConfig {
+    #[serde(skip)]
+    stats: Arc<Stats>,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "bench_capture")]
+impl SinkConfig for CapturingSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = CapturingSink {
+            stats: Arc::clone(&self.stats),
+        };
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            future::ok(()).boxed(),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "bench_capture"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        None
+    }
+}
+
+struct CapturingSink {
+    stats: Arc<Stats>,
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for CapturingSink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        while let Some(event) = input.next().await {
+            self.stats.received.add(1, event.size_of() as u64);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighs_latencies_by_events() {
+        let ms = Duration::from_millis;
+        let latency = Latency::new(vec![(ms(30), 1), (ms(10), 89), (ms(20), 10)]).unwrap();
+        assert_eq!(
+            latency,
+            Latency {
+                p50: ms(10),
+                p90: ms(20),
+                p99: ms(20),
+                max: ms(30),
+            }
+        );
+        assert_eq!(Latency::new(Vec::new()), None);
+    }
+
+    #[test]
+    fn replays_inputs() {
+        let events = Events {
+            replayed: vec![Event::from("first"), Event::from("second")],
+            metrics: false,
+        };
+        let messages = (0..3)
+            .map(|n| events.event(n, "").as_log()["message"].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["first", "second", "first"]);
+    }
+
+    #[cfg(all(feature = "sources-stdin", feature = "transforms-remap"))]
+    #[tokio::test]
+    async fn benches_transforms() {
+        crate::test_util::components::init_test();
+
+        let builder: ConfigBuilder = toml::from_str(indoc::indoc! {r#"
+            [sources.in]
+            type = "stdin"
+
+            [transforms.parse]
+            type = "remap"
+            inputs = ["in"]
+            source = '''
+            if match(string!(.message), r'/items/\d*0 ') { abort }
+            .parsed = true
+            '''
+            drop_on_abort = true
+        "#})
+        .unwrap();
+
+        let report = bench(
+            builder,
+            &ComponentKey::from("parse"),
+            Settings {
+                duration: Duration::from_secs(10),
+                events: Some(2500),
+                ..Settings::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.kind, "transform");
+        assert_eq!(report.component_type, "remap");
+        assert_eq!(report.sent.unwrap().events, 2500);
+        // One in ten of the generated access logs is for an item whose number ends with 0.
+        assert_eq!(report.received.events, 2250);
+        assert_eq!(report.failed, 0);
+        assert!(report.latency.is_some());
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_components() {
+        let builder = ConfigBuilder::default();
+        let error = bench(builder, &ComponentKey::from("parse"), Settings::default())
+            .await
+            .unwrap_err();
+        assert_eq!(error, vec!["Unknown component \"parse\"."]);
+    }
+}
@@ -0,0 +1,412 @@
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use clap::Parser;
+use tracing::Span;
+use vector_core::buffers::{
+    inspect_disk_buffer, purge_disk_buffer, BufferConfig, BufferType, DiskBufferInfo, WhenFull,
+};
+
+use crate::{
+    config::{self, ComponentKey, Config},
+    event::{EventArray, EventContainer},
+};
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+enum Command {
+    /// List the disk buffers of all sinks, along with their size and the age of their oldest data.
+    Ls(ConfigOpts),
+
+    /// Delete all of the events held in the disk buffer of a sink.
+    ///
+    /// The buffer must not be in use by a running Vector process.
+    Purge(TargetOpts),
+
+    /// Read all of the events held in the disk buffer of a sink, writing them to stdout as JSON,
+    /// one event per line, and removing them from the buffer.
+    ///
+    /// The buffer must not be in use by a running Vector process.
+    Drain(TargetOpts),
+}
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+struct TargetOpts {
+    /// The ID of the sink whose buffer to operate on.
+    component: String,
+
+    #[clap(flatten)]
+    config: ConfigOpts,
+}
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+struct ConfigOpts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[clap(
+        name = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        use_value_delimiter(true)
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[clap(name = "config-toml", long, use_value_delimiter(true))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[clap(name = "config-json", long, use_value_delimiter(true))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[clap(name = "config-yaml", long, use_value_delimiter(true))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[clap(
+        name = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        use_value_delimiter(true)
+    )]
+    config_dirs: Vec<PathBuf>,
+}
+
+impl ConfigOpts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+
+    fn load(&self) -> Result<Config, exitcode::ExitCode> {
+        let paths = self.paths_with_formats();
+        let paths = config::process_paths(&paths).ok_or(exitcode::CONFIG)?;
+
+        config::load_from_paths(&paths).map_err(|errs| {
+            #[allow(clippy::print_stderr)]
+            for err in errs {
+                eprintln!("{}", err);
+            }
+            exitcode::CONFIG
+        })
+    }
+}
+
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let result = match &opts.command {
+        Command::Ls(config) => ls(config),
+        Command::Purge(target) => purge(target),
+        Command::Drain(target) => drain(target).await,
+    };
+
+    result.unwrap_or_else(|code| code)
+}
+
+/// Gets the configured disk buffer stage of each sink, along with the base data directory.
+fn disk_buffers(config: &Config) -> Result<(PathBuf, Vec<(ComponentKey, BufferType)>), String> {
+    let data_dir = config
+        .global
+        .data_dir
+        .clone()
+        .ok_or_else(|| "No data directory configured.".to_string())?;
+
+    let buffers = config
+        .sinks()
+        .flat_map(|(key, sink)| {
+            sink.buffer
+                .stages()
+                .iter()
+                .filter(|stage| matches!(stage, BufferType::DiskV2 { .. }))
+                .map(move |stage| (key.clone(), stage.clone()))
+        })
+        .collect();
+
+    Ok((data_dir, buffers))
+}
+
+fn find_disk_buffer(config: &Config, component: &str) -> Result<(PathBuf, BufferType), String> {
+    let (data_dir, buffers) = disk_buffers(config)?;
+    buffers
+        .into_iter()
+        .find(|(key, _)| key.id() == component)
+        .map(|(_, stage)| (data_dir, stage))
+        .ok_or_else(|| format!("Sink {:?} does not have a disk buffer.", component))
+}
+
+fn format_age(info: &DiskBufferInfo) -> String {
+    info.oldest_modified()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map_or_else(|| "-".to_string(), |age| format!("{}s", age.as_secs()))
+}
+
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+fn ls(opts: &ConfigOpts) -> Result<exitcode::ExitCode, exitcode::ExitCode> {
+    let config = opts.load()?;
+    let (data_dir, buffers) = disk_buffers(&config).map_err(|error| {
+        eprintln!("{}", error);
+        exitcode::CONFIG
+    })?;
+
+    println!(
+        "{:<32} {:>8} {:>16} {:>12} {:>8}  PATH",
+        "COMPONENT", "FILES", "SIZE (BYTES)", "OLDEST AGE", "IN USE"
+    );
+    for (key, _) in buffers {
+        match inspect_disk_buffer(&data_dir, key.id()) {
+            Ok(Some(info)) => println!(
+                "{:<32} {:>8} {:>16} {:>12} {:>8}  {}",
+                key,
+                info.data_files.len(),
+                info.total_size(),
+                format_age(&info),
+                if info.in_use { "yes" } else { "no" },
+                info.path.display(),
+            ),
+            Ok(None) => println!("{:<32} {:>8} {:>16} {:>12} {:>8}  -", key, 0, 0, "-", "no"),
+            Err(error) => {
+                eprintln!("Failed to inspect buffer of sink {:?}: {}", key.id(), error);
+                return Err(exitcode::IOERR);
+            }
+        }
+    }
+
+    Ok(exitcode::OK)
+}
+
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+fn purge(opts: &TargetOpts) -> Result<exitcode::ExitCode, exitcode::ExitCode> {
+    let config = opts.config.load()?;
+    let (data_dir, _) = find_disk_buffer(&config, &opts.component).map_err(|error| {
+        eprintln!("{}", error);
+        exitcode::CONFIG
+    })?;
+
+    match purge_disk_buffer(&data_dir, &opts.component) {
+        Ok(Some(size)) => {
+            println!(
+                "Purged buffer of sink {:?}, removing {} bytes of data.",
+                opts.component, size
+            );
+            Ok(exitcode::OK)
+        }
+        Ok(None) => {
+            println!("Buffer of sink {:?} is already empty.", opts.component);
+            Ok(exitcode::OK)
+        }
+        Err(error) => {
+            eprintln!(
+                "Failed to purge buffer of sink {:?}: {}",
+                opts.component, error
+            );
+            Err(exitcode::IOERR)
+        }
+    }
+}
+
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+async fn drain(opts: &TargetOpts) -> Result<exitcode::ExitCode, exitcode::ExitCode> {
+    let config = opts.config.load()?;
+    let (data_dir, stage) = find_disk_buffer(&config, &opts.component).map_err(|error| {
+        eprintln!("{}", error);
+        exitcode::CONFIG
+    })?;
+
+    let events = drain_disk_buffer(&data_dir, &opts.component, stage, |events| {
+        for event in events.into_events() {
+            match serde_json::to_string(&event) {
+                Ok(json) => println!("{}", json),
+                Err(error) => error!(message = "Failed to encode drained event.", %error),
+            }
+        }
+    })
+    .await
+    .map_err(|error| {
+        eprintln!(
+            "Failed to drain buffer of sink {:?}: {}",
+            opts.component, error
+        );
+        exitcode::IOERR
+    })?;
+
+    eprintln!(
+        "Drained {} events from buffer of sink {:?}.",
+        events, opts.component
+    );
+    Ok(exitcode::OK)
+}
+
+/// Reads every event out of the given disk buffer, acknowledging them so that they're removed from
+/// the buffer, and returns the number of events read.
+async fn drain_disk_buffer(
+    data_dir: &Path,
+    id: &str,
+    stage: BufferType,
+    mut f: impl FnMut(EventArray),
+) -> crate::Result<usize> {
+    // We only ever read from the buffer here, so its "when full" behavior doesn't matter.
+    let stage = match stage {
+        BufferType::DiskV2 {
+            max_size,
+            compression,
+            io_uring,
+            ..
+        } => BufferType::DiskV2 {
+            max_size,
+            compression,
+            when_full: WhenFull::Block,
+            io_uring,
+        },
+        stage => stage,
+    };
+    let buffer = BufferConfig {
+        stages: vec![stage],
+    };
+    let (sender, mut receiver, acker) = buffer
+        .build::<EventArray>(Some(data_dir.to_path_buf()), id.to_string(), Span::none())
+        .await?;
+
+    // Closing the writer lets the reader know that nothing else is coming, so it finishes once it
+    // has read everything that's already in the buffer.
+    drop(sender);
+
+    let mut count = 0;
+    while let Some(events) = receiver.next().await {
+        let len = events.len();
+        f(events);
+        acker.ack(len);
+        count += len;
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use vector_core::buffers::DiskBufferCompression;
+
+    use super::*;
+    use crate::event::{EventPriority, LogEvent};
+
+    fn disk_stage() -> BufferType {
+        BufferType::DiskV2 {
+            max_size: NonZeroU64::new(256 * 1024 * 1024).unwrap(),
+            when_full: WhenFull::Block,
+            compression: DiskBufferCompression::None,
+            io_uring: false,
+        }
+    }
+
+    /// Fills the buffer the same way a sink would, and then closes it.
+    async fn fill_disk_buffer(data_dir: &Path, stage: BufferType, events: EventArray) {
+        let buffer = BufferConfig {
+            stages: vec![stage],
+        };
+        let (mut sender, _receiver, _acker) = buffer
+            .build::<EventArray>(
+                Some(data_dir.to_path_buf()),
+                "out".to_string(),
+                Span::none(),
+            )
+            .await
+            .unwrap();
+        sender.send(events).await.unwrap();
+        sender.flush().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn drain_reads_all_buffered_events() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let stage = disk_stage();
+        fill_disk_buffer(
+            data_dir.path(),
+            stage.clone(),
+            EventArray::from(vec![LogEvent::from("one"), LogEvent::from("two")]),
+        )
+        .await;
+
+        let mut drained = Vec::new();
+        let count = drain_disk_buffer(data_dir.path(), "out", stage.clone(), |events| {
+            drained.extend(events.into_events());
+        })
+        .await
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(drained.len(), 2);
+
+        // Everything was acknowledged, so draining again finds nothing.
+        let count = drain_disk_buffer(data_dir.path(), "out", stage, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn disk_buffer_keeps_event_priority() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let stage = disk_stage();
+        let events = [
+            EventPriority::Low,
+            EventPriority::Normal,
+            EventPriority::High,
+        ]
+        .iter()
+        .map(|priority| {
+            let mut log = LogEvent::from(priority.as_str());
+            log.metadata_mut().set_priority(*priority);
+            log
+        })
+        .collect::<Vec<_>>();
+        fill_disk_buffer(data_dir.path(), stage.clone(), EventArray::from(events)).await;
+
+        let mut priorities = Vec::new();
+        drain_disk_buffer(data_dir.path(), "out", stage, |events| {
+            priorities.extend(
+                events
+                    .into_events()
+                    .map(|event| event.metadata().priority()),
+            );
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            priorities,
+            vec![
+                EventPriority::Low,
+                EventPriority::Normal,
+                EventPriority::High
+            ]
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::{io, path::PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use tokio::fs;
+
+use super::CheckpointStore;
+
+/// Keeps each checkpoint in a file of a directory, named after its key.
+pub(super) struct DiskStore {
+    path: PathBuf,
+}
+
+impl DiskStore {
+    pub(super) const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for DiskStore {
+    async fn load(&self, key: &str) -> crate::Result<Option<Bytes>> {
+        match fs::read(self.path.join(key)).await {
+            Ok(checkpoint) => Ok(Some(checkpoint.into())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn save(&self, key: &str, checkpoint: Bytes) -> crate::Result<()> {
+        let path = self.path.join(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        // Written aside and moved into place, so that a checkpoint is never read half written.
+        let temp_path = path.with_extension("saving");
+        fs::write(&temp_path, &checkpoint).await?;
+        fs::rename(&temp_path, &path).await?;
+        Ok(())
+    }
+}
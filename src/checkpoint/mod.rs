@@ -0,0 +1,250 @@
+//! Stores of source checkpoints besides the data directory, so that an instance rescheduled to
+//! another node, without its volume, resumes where it left off rather than starting over.
+//!
+//! Sources keep writing their checkpoints to their data directory as usual, and a
+//! [`CheckpointMirror`] restores them from the store before the source starts, when they are
+//! missing, and saves them to the store as they change.
+
+use std::{
+    io,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use once_cell::sync::Lazy;
+use tokio::{fs, time};
+use vector_core::config::{CheckpointStoreConfig, ComponentKey, GlobalOptions};
+
+use crate::{internal_events::CheckpointStoreError, sources::Source};
+
+mod disk;
+#[cfg(any(feature = "sources-redis", feature = "sinks-redis"))]
+mod redis;
+#[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
+mod s3;
+
+/// A store of checkpoints, each saved under a key unique to the source and checkpoint.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// The checkpoint saved under the key, if any.
+    async fn load(&self, key: &str) -> crate::Result<Option<Bytes>>;
+
+    /// Saves the checkpoint under the key, replacing the one saved before.
+    async fn save(&self, key: &str, checkpoint: Bytes) -> crate::Result<()>;
+}
+
+/// The store of this instance, shared by the sources of every topology built with the same store
+/// configuration, so that they share their connections.
+static GLOBAL: Lazy<Mutex<Option<(CheckpointStoreConfig, Arc<dyn CheckpointStore>)>>> =
+    Lazy::new(Default::default);
+
+/// Gets the checkpoint store for the given options, if checkpoints are kept in one, connecting to
+/// it if it isn't yet.
+pub async fn global(globals: &GlobalOptions) -> crate::Result<Option<Arc<dyn CheckpointStore>>> {
+    let config = match &globals.checkpoints.store {
+        Some(config) => config,
+        None => return Ok(None),
+    };
+
+    {
+        let global = GLOBAL.lock().expect("checkpoint store lock poisoned");
+        if let Some((current, store)) = global.as_ref() {
+            if current == config {
+                return Ok(Some(Arc::clone(store)));
+            }
+        }
+    }
+
+    // Connecting is asynchronous, so it's done outside of the lock. Sources built at the same
+    // time may each connect, with the last one connected kept.
+    let store: Arc<dyn CheckpointStore> = match config {
+        CheckpointStoreConfig::Disk { path } => Arc::new(disk::DiskStore::new(path.clone())),
+        #[cfg(any(feature = "sources-redis", feature = "sinks-redis"))]
+        CheckpointStoreConfig::Redis { url, key_prefix } => {
+            Arc::new(redis::RedisStore::connect(url, key_prefix).await?)
+        }
+        #[cfg(not(any(feature = "sources-redis", feature = "sinks-redis")))]
+        CheckpointStoreConfig::Redis { .. } => {
+            return Err("Vector was built without Redis support.".into());
+        }
+        #[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
+        CheckpointStoreConfig::S3 {
+            bucket,
+            key_prefix,
+            region,
+            endpoint,
+        } => Arc::new(
+            s3::S3Store::connect(
+                bucket,
+                key_prefix,
+                crate::aws::RegionOrEndpoint {
+                    region: region.clone(),
+                    endpoint: endpoint.clone(),
+                },
+                &globals.proxy,
+            )
+            .await?,
+        ),
+        #[cfg(not(any(feature = "sources-aws_s3", feature = "sinks-aws_s3")))]
+        CheckpointStoreConfig::S3 { .. } => {
+            return Err("Vector was built without S3 support.".into());
+        }
+    };
+    *GLOBAL.lock().expect("checkpoint store lock poisoned") =
+        Some((config.clone(), Arc::clone(&store)));
+    Ok(Some(store))
+}
+
+/// Mirrors a checkpoint file of a source to the checkpoint store.
+pub struct CheckpointMirror {
+    store: Arc<dyn CheckpointStore>,
+    key: String,
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl CheckpointMirror {
+    /// A mirror of the checkpoint file at the path, written by the given source, if checkpoints
+    /// are kept in a store. The checkpoint is saved under the identifier of the source and the
+    /// name of the file.
+    pub async fn new(
+        globals: &GlobalOptions,
+        source: &ComponentKey,
+        path: PathBuf,
+    ) -> crate::Result<Option<Self>> {
+        let store = match global(globals).await? {
+            Some(store) => store,
+            None => return Ok(None),
+        };
+        let name = path
+            .file_name()
+            .ok_or("Checkpoint paths must name a file.")?
+            .to_string_lossy();
+        Ok(Some(Self {
+            store,
+            key: format!("{}/{}", source.id(), name),
+            interval: Duration::from_secs(globals.checkpoints.sync_interval_secs),
+            path,
+        }))
+    }
+
+    /// Writes the checkpoint saved in the store to the file, unless the file exists, as it does
+    /// when the source already ran on this node.
+    pub async fn restore(&self) -> crate::Result<()> {
+        match fs::metadata(&self.path).await {
+            Ok(_) => return Ok(()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error.into()),
+        }
+        if let Some(checkpoint) = self.store.load(&self.key).await? {
+            let temp_path = self.path.with_extension("restoring");
+            fs::write(&temp_path, &checkpoint).await?;
+            fs::rename(&temp_path, &self.path).await?;
+            info!(message = "Restored checkpoint from the store.", key = %self.key);
+        }
+        Ok(())
+    }
+
+    /// Runs the source, saving its checkpoint to the store every interval in which it changed,
+    /// and once more after the source stops.
+    pub fn wrap(self, mut source: Source) -> Source {
+        Box::pin(async move {
+            let mut interval = time::interval(self.interval);
+            let mut saved = None;
+            let result = loop {
+                tokio::select! {
+                    result = &mut source => break result,
+                    _ = interval.tick() => self.sync(&mut saved).await,
+                }
+            };
+            self.sync(&mut saved).await;
+            result
+        })
+    }
+
+    async fn sync(&self, saved: &mut Option<Bytes>) {
+        let checkpoint = match fs::read(&self.path).await {
+            Ok(checkpoint) => Bytes::from(checkpoint),
+            // The source hasn't written its first checkpoint yet.
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+            Err(error) => {
+                emit!(CheckpointStoreError {
+                    error: error.into(),
+                    key: &self.key,
+                });
+                return;
+            }
+        };
+        if saved.as_ref() == Some(&checkpoint) {
+            return;
+        }
+        match self.store.save(&self.key, checkpoint.clone()).await {
+            Ok(()) => *saved = Some(checkpoint),
+            Err(error) => emit!(CheckpointStoreError {
+                error,
+                key: &self.key,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+    use vector_core::config::CheckpointsConfig;
+
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[tokio::test]
+    async fn mirrors_checkpoints() {
+        let globals = GlobalOptions {
+            checkpoints: CheckpointsConfig {
+                store: Some(CheckpointStoreConfig::Disk { path: temp_dir() }),
+                sync_interval_secs: 1,
+            },
+            ..Default::default()
+        };
+        let source = ComponentKey::from("in");
+
+        // The node the source first ran on.
+        let first = temp_dir();
+        fs::create_dir_all(&first).await.unwrap();
+        let mirror = CheckpointMirror::new(&globals, &source, first.join("checkpoint.txt"))
+            .await
+            .unwrap()
+            .unwrap();
+        mirror.restore().await.unwrap();
+        assert!(!first.join("checkpoint.txt").exists());
+        let path = first.join("checkpoint.txt");
+        mirror
+            .wrap(async move { fs::write(path, "cursor=42").await.map_err(|_| ()) }.boxed())
+            .await
+            .unwrap();
+
+        // The node it was rescheduled to.
+        let second = temp_dir();
+        fs::create_dir_all(&second).await.unwrap();
+        let mirror = CheckpointMirror::new(&globals, &source, second.join("checkpoint.txt"))
+            .await
+            .unwrap()
+            .unwrap();
+        mirror.restore().await.unwrap();
+        assert_eq!(
+            fs::read_to_string(second.join("checkpoint.txt"))
+                .await
+                .unwrap(),
+            "cursor=42"
+        );
+
+        assert!(
+            CheckpointMirror::new(&GlobalOptions::default(), &source, second)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use redis::{aio::ConnectionManager, AsyncCommands};
+
+use super::CheckpointStore;
+
+/// Keeps each checkpoint in a Redis key, under a prefix.
+pub(super) struct RedisStore {
+    conn: ConnectionManager,
+    key_prefix: String,
+}
+
+impl RedisStore {
+    pub(super) async fn connect(url: &str, key_prefix: &str) -> crate::Result<Self> {
+        let client = redis::Client::open(url)?;
+        let conn = client.get_tokio_connection_manager().await?;
+        Ok(Self {
+            conn,
+            key_prefix: key_prefix.trim_end_matches('/').to_owned(),
+        })
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}/{}", self.key_prefix, key)
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for RedisStore {
+    async fn load(&self, key: &str) -> crate::Result<Option<Bytes>> {
+        let mut conn = self.conn.clone();
+        let checkpoint: Option<Vec<u8>> = conn.get(self.key(key)).await?;
+        Ok(checkpoint.map(Into::into))
+    }
+
+    async fn save(&self, key: &str, checkpoint: Bytes) -> crate::Result<()> {
+        let mut conn = self.conn.clone();
+        conn.set::<_, _, ()>(self.key(key), checkpoint.as_ref())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use aws_sdk_s3::{
+    types::{ByteStream, SdkError},
+    Client,
+};
+use bytes::Bytes;
+use vector_core::config::proxy::ProxyConfig;
+
+use super::CheckpointStore;
+use crate::{
+    aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    common::s3::S3ClientBuilder,
+};
+
+/// Keeps each checkpoint in an S3 object, under a prefix.
+pub(super) struct S3Store {
+    client: Client,
+    bucket: String,
+    key_prefix: String,
+}
+
+impl S3Store {
+    pub(super) async fn connect(
+        bucket: &str,
+        key_prefix: &str,
+        region: RegionOrEndpoint,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<Self> {
+        let client = create_client::<S3ClientBuilder>(
+            &AwsAuthentication::default(),
+            region.region(),
+            region.endpoint()?,
+            proxy,
+            &None,
+            false,
+        )
+        .await?;
+        Ok(Self {
+            client,
+            bucket: bucket.to_owned(),
+            key_prefix: key_prefix.trim_end_matches('/').to_owned(),
+        })
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}/{}", self.key_prefix, key)
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for S3Store {
+    async fn load(&self, key: &str) -> crate::Result<Option<Bytes>> {
+        let result = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(self.key(key))
+            .send()
+            .await;
+        match result {
+            Ok(object) => Ok(Some(object.body.collect().await?.into_bytes())),
+            Err(SdkError::ServiceError { err, .. }) if err.is_no_such_key() => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn save(&self, key: &str, checkpoint: Bytes) -> crate::Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(self.key(key))
+            .body(ByteStream::from(checkpoint))
+            .send()
+            .await?;
+        Ok(())
+    }
+}
@@ -2,13 +2,18 @@ use std::path::PathBuf;
 
 use clap::{AppSettings, FromArgMatches, IntoApp, Parser};
 
+#[cfg(feature = "sources-replay")]
+use crate::replay;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, generate, get_version, graph, list, unit_test, validate};
+use crate::{
+    bench, buffer, config, convert, generate, get_version, graph, list, simulate, unit_test,
+    validate,
+};
 
 #[derive(Parser, Debug)]
 #[clap(rename_all = "kebab-case")]
@@ -33,6 +38,10 @@ impl Opts {
         let (quiet_level, verbose_level) = match self.sub_command {
             Some(SubCommand::Validate(_))
             | Some(SubCommand::Graph(_))
+            | Some(SubCommand::Buffer(_))
+            | Some(SubCommand::Simulate(_))
+            | Some(SubCommand::Bench(_))
+            | Some(SubCommand::Convert(_))
             | Some(SubCommand::Generate(_))
             | Some(SubCommand::List(_))
             | Some(SubCommand::Test(_)) => {
@@ -149,6 +158,11 @@ pub struct RootOpts {
     /// Watch for changes in configuration file, and reload accordingly.
     #[clap(short, long, env = "VECTOR_WATCH_CONFIG")]
     pub watch_config: bool,
+
+    /// Apply the profile-scoped sections of the given profile, such as `[profile.production]`,
+    /// over the rest of the configuration.
+    #[clap(long, env = "VECTOR_PROFILE")]
+    pub profile: Option<String>,
 }
 
 impl RootOpts {
@@ -193,6 +207,26 @@ pub enum SubCommand {
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
+    /// Inspect and manage the disk buffers of sinks, such as to purge or drain them during incidents.
+    Buffer(buffer::Opts),
+
+    /// Run a configuration against sample input files offline, reporting the events each component
+    /// received and sent, then exit.
+    Simulate(simulate::Opts),
+
+    /// Drive a source, transform or sink of a configuration on its own with generated or replayed
+    /// events, reporting its throughput, latency and allocations.
+    Bench(bench::Opts),
+
+    /// Convert the configuration of Logstash, Fluentd or Fluent Bit to a Vector configuration,
+    /// reporting what couldn't be converted.
+    Convert(convert::Opts),
+
+    /// Re-inject the events of an archive written by an `archive` sink into a configuration in
+    /// place of one of its sources, such as to reprocess them after a bad deploy, then exit.
+    #[cfg(feature = "sources-replay")]
+    Replay(replay::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
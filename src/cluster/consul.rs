@@ -0,0 +1,177 @@
+//! Membership kept in Consul: each member holds a key under the prefix with a session it renews,
+//! and Consul deletes the key when the member stops renewing the session, taking it out of the
+//! cluster.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use http::{Method, Request, StatusCode};
+use hyper::Body;
+use serde_json::json;
+use tokio::{sync::watch, task::JoinHandle, time};
+
+use super::Cluster;
+use crate::{config::ProxyConfig, http::HttpClient};
+
+pub(super) fn join(
+    node_id: String,
+    address: &str,
+    key_prefix: &str,
+    ttl: Duration,
+) -> crate::Result<(Cluster, JoinHandle<()>)> {
+    let consul = Consul {
+        client: HttpClient::new(None, &ProxyConfig::from_env())?,
+        address: address.trim_end_matches('/').to_owned(),
+        key_prefix: key_prefix.trim_matches('/').to_owned(),
+        node_id: node_id.clone(),
+        ttl,
+    };
+    let (sender, members) = watch::channel(Vec::new());
+    let task = tokio::spawn(consul.run(sender));
+    Ok((Cluster::new(node_id, members), task))
+}
+
+struct Consul {
+    client: HttpClient,
+    address: String,
+    key_prefix: String,
+    node_id: String,
+    ttl: Duration,
+}
+
+impl Consul {
+    /// Keeps this instance registered and the live members up to date, until every handle on the
+    /// membership is dropped.
+    async fn run(self, members: watch::Sender<Vec<String>>) {
+        let mut session = None;
+        loop {
+            if let Err(error) = self.sync(&mut session, &members).await {
+                error!(message = "Failed to update cluster membership.", %error, internal_log_rate_secs = 30);
+            }
+            tokio::select! {
+                _ = members.closed() => break,
+                // Renewing well within the TTL keeps the session alive through a failed attempt.
+                _ = time::sleep(self.ttl / 3) => {}
+            }
+        }
+
+        if let Some(session) = session {
+            let path = format!("/v1/session/destroy/{}", session);
+            if let Err(error) = self.request(Method::PUT, &path, Body::empty()).await {
+                warn!(message = "Failed to leave cluster.", %error);
+            }
+        }
+    }
+
+    async fn sync(
+        &self,
+        session: &mut Option<String>,
+        members: &watch::Sender<Vec<String>>,
+    ) -> crate::Result<()> {
+        let id = match session.take() {
+            Some(id) if self.renew(&id).await? => id,
+            // The session expired, taking the key of this instance with it.
+            _ => self.create_session().await?,
+        };
+        *session = Some(id.clone());
+
+        let path = format!(
+            "/v1/kv/{}/members/{}?acquire={}",
+            self.key_prefix, self.node_id, id
+        );
+        let body = self
+            .request(Method::PUT, &path, Body::from(self.node_id.clone()))
+            .await?;
+        if std::str::from_utf8(&body).map(str::trim) != Ok("true") {
+            return Err(format!(
+                "Member {:?} is already registered by another instance.",
+                self.node_id
+            )
+            .into());
+        }
+
+        let live = self.members().await?;
+        if *members.borrow() != live {
+            let _ = members.send(live);
+        }
+        Ok(())
+    }
+
+    async fn create_session(&self) -> crate::Result<String> {
+        let body = json!({
+            "Name": format!("vector-{}", self.node_id),
+            "TTL": format!("{}s", self.ttl.as_secs()),
+            "Behavior": "delete",
+            "LockDelay": "0s",
+        });
+        let body = self
+            .request(
+                Method::PUT,
+                "/v1/session/create",
+                Body::from(body.to_string()),
+            )
+            .await?;
+        let session: serde_json::Value = serde_json::from_slice(&body)?;
+        session["ID"]
+            .as_str()
+            .map(Into::into)
+            .ok_or_else(|| "Consul returned no session ID.".into())
+    }
+
+    /// Renews the session, returning whether it's still valid.
+    async fn renew(&self, session: &str) -> crate::Result<bool> {
+        let path = format!("/v1/session/renew/{}", session);
+        match self.request(Method::PUT, &path, Body::empty()).await {
+            Ok(_) => Ok(true),
+            Err(error) => match error.downcast_ref::<UnexpectedStatus>() {
+                Some(UnexpectedStatus(StatusCode::NOT_FOUND)) => Ok(false),
+                _ => Err(error),
+            },
+        }
+    }
+
+    async fn members(&self) -> crate::Result<Vec<String>> {
+        let prefix = format!("{}/members/", self.key_prefix);
+        let path = format!("/v1/kv/{}?keys", prefix);
+        let keys: Vec<String> = match self.request(Method::GET, &path, Body::empty()).await {
+            Ok(body) => serde_json::from_slice(&body)?,
+            Err(error) => match error.downcast_ref::<UnexpectedStatus>() {
+                // No member at all.
+                Some(UnexpectedStatus(StatusCode::NOT_FOUND)) => Vec::new(),
+                _ => return Err(error),
+            },
+        };
+        let mut members = keys
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&prefix).map(Into::into))
+            .filter(|member: &String| !member.is_empty())
+            .collect::<Vec<_>>();
+        members.sort();
+        Ok(members)
+    }
+
+    async fn request(&self, method: Method, path: &str, body: Body) -> crate::Result<Bytes> {
+        let request = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.address, path))
+            .body(body)?;
+        let response = self.client.send(request).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if !status.is_success() {
+            return Err(UnexpectedStatus(status).into());
+        }
+        Ok(body)
+    }
+}
+
+#[derive(Debug)]
+struct UnexpectedStatus(StatusCode);
+
+impl std::fmt::Display for UnexpectedStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Consul responded with {}.", self.0)
+    }
+}
+
+impl std::error::Error for UnexpectedStatus {}
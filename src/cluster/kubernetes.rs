@@ -0,0 +1,182 @@
+//! Leader election with a Kubernetes `Lease`: the replica holding the lease is the only member of
+//! the cluster, so that it does all of the work, and the others take the lease over once it has
+//! gone unrenewed for its duration.
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use k8s_openapi::{
+    api::coordination::v1::{Lease, LeaseSpec},
+    apimachinery::pkg::apis::meta::v1::{MicroTime, ObjectMeta},
+};
+use kube::{api::PostParams, Api, Client};
+use tokio::{sync::watch, task::JoinHandle, time};
+
+use super::Cluster;
+
+pub(super) fn join(
+    node_id: String,
+    lease_name: &str,
+    namespace: Option<&str>,
+    duration: Duration,
+) -> (Cluster, JoinHandle<()>) {
+    let election = Election {
+        node_id: node_id.clone(),
+        lease_name: lease_name.to_owned(),
+        namespace: namespace.map(Into::into),
+        duration,
+    };
+    let (sender, members) = watch::channel(Vec::new());
+    let task = tokio::spawn(election.run(sender));
+    (Cluster::new(node_id, members), task)
+}
+
+struct Election {
+    node_id: String,
+    lease_name: String,
+    namespace: Option<String>,
+    duration: Duration,
+}
+
+impl Election {
+    /// Competes for the lease, keeping the members to its holder, until every handle on the
+    /// membership is dropped.
+    async fn run(self, members: watch::Sender<Vec<String>>) {
+        let mut api = None;
+        // When this replica last renewed the lease, while it's the leader.
+        let mut renewed = None;
+        loop {
+            if api.is_none() {
+                match self.api().await {
+                    Ok(new) => api = Some(new),
+                    Err(error) => {
+                        error!(message = "Failed to connect to Kubernetes.", %error, internal_log_rate_secs = 30)
+                    }
+                }
+            }
+            if let Some(api) = &api {
+                match self.try_acquire_or_renew(api).await {
+                    Ok(holder) => {
+                        renewed =
+                            (holder.as_deref() == Some(self.node_id.as_str())).then(Instant::now);
+                        let live = holder.into_iter().collect::<Vec<_>>();
+                        if *members.borrow() != live {
+                            info!(message = "Leader changed.", leader = ?live.first());
+                            let _ = members.send(live);
+                        }
+                    }
+                    Err(error) => {
+                        error!(message = "Failed to update leader election lease.", %error, internal_log_rate_secs = 30);
+                        // Step down before the lease can expire and be taken over, so that two
+                        // replicas never both act as the leader.
+                        if renewed.map_or(false, |at| at.elapsed() >= self.duration * 2 / 3) {
+                            renewed = None;
+                            let _ = members.send(Vec::new());
+                        }
+                    }
+                }
+            }
+            tokio::select! {
+                _ = members.closed() => break,
+                // Retrying well within the duration keeps the lease through failed attempts, and
+                // bounds how long it stays with a leader that is gone.
+                _ = time::sleep(self.duration / 5) => {}
+            }
+        }
+
+        if let (Some(api), Some(_)) = (api, renewed) {
+            if let Err(error) = self.release(&api).await {
+                warn!(message = "Failed to release leader election lease.", %error);
+            }
+        }
+    }
+
+    async fn api(&self) -> kube::Result<Api<Lease>> {
+        let client = Client::try_default().await?;
+        Ok(match &self.namespace {
+            Some(namespace) => Api::namespaced(client, namespace),
+            None => Api::default_namespaced(client),
+        })
+    }
+
+    /// Renews the lease if this replica holds it, or takes it over if it has expired, returning
+    /// its holder as of now, if any.
+    async fn try_acquire_or_renew(&self, api: &Api<Lease>) -> kube::Result<Option<String>> {
+        let now = Utc::now();
+        let mut lease = match api.get(&self.lease_name).await {
+            Ok(lease) => lease,
+            Err(kube::Error::Api(response)) if response.code == 404 => {
+                let lease = Lease {
+                    metadata: ObjectMeta {
+                        name: Some(self.lease_name.clone()),
+                        ..Default::default()
+                    },
+                    spec: Some(self.spec(now, now, 0)),
+                };
+                return match api.create(&PostParams::default(), &lease).await {
+                    Ok(_) => Ok(Some(self.node_id.clone())),
+                    // Another replica created it first.
+                    Err(kube::Error::Api(response)) if response.code == 409 => Ok(None),
+                    Err(error) => Err(error),
+                };
+            }
+            Err(error) => return Err(error),
+        };
+
+        let spec = lease.spec.take().unwrap_or_default();
+        let holder = spec.holder_identity.filter(|holder| !holder.is_empty());
+        let held = holder.as_deref() == Some(self.node_id.as_str());
+        let duration = spec.lease_duration_seconds.map_or(self.duration, |secs| {
+            Duration::from_secs(secs.max(0) as u64)
+        });
+        let expired = spec.renew_time.map_or(true, |MicroTime(renewed)| {
+            renewed
+                + chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::zero())
+                < now
+        });
+        if holder.is_some() && !held && !expired {
+            return Ok(holder);
+        }
+
+        let (acquired, transitions) = match (held, spec.acquire_time) {
+            (true, Some(MicroTime(acquired))) => (acquired, spec.lease_transitions.unwrap_or(0)),
+            _ => (now, spec.lease_transitions.unwrap_or(0) + 1),
+        };
+        lease.spec = Some(self.spec(acquired, now, transitions));
+        // The lease keeps the resource version it was read with, so this fails with a conflict
+        // if another replica updated it in between.
+        match api
+            .replace(&self.lease_name, &PostParams::default(), &lease)
+            .await
+        {
+            Ok(_) => Ok(Some(self.node_id.clone())),
+            Err(kube::Error::Api(response)) if response.code == 409 => Ok(holder.filter(|_| !held)),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn spec(&self, acquired: DateTime<Utc>, renewed: DateTime<Utc>, transitions: i32) -> LeaseSpec {
+        LeaseSpec {
+            holder_identity: Some(self.node_id.clone()),
+            lease_duration_seconds: Some(self.duration.as_secs() as i32),
+            acquire_time: Some(MicroTime(acquired)),
+            renew_time: Some(MicroTime(renewed)),
+            lease_transitions: Some(transitions),
+        }
+    }
+
+    /// Gives the lease up, so that another replica takes it over on its next attempt rather than
+    /// once it expires.
+    async fn release(&self, api: &Api<Lease>) -> kube::Result<()> {
+        let mut lease = api.get(&self.lease_name).await?;
+        if let Some(spec) = &mut lease.spec {
+            if spec.holder_identity.as_deref() == Some(self.node_id.as_str()) {
+                spec.holder_identity = None;
+                spec.lease_duration_seconds = Some(1);
+                api.replace(&self.lease_name, &PostParams::default(), &lease)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+//! Membership in a cluster of Vector instances, which pull-based sources use to share their work
+//! between the members rather than each doing all of it.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use tokio::{sync::watch, task::JoinHandle, time};
+use vector_core::config::{ClusterBackendConfig, ClusterConfig};
+
+mod consul;
+#[cfg(feature = "kubernetes")]
+mod kubernetes;
+
+/// The membership of this instance, shared by the sources of every topology built with the same
+/// cluster configuration, so that reloads don't make it leave and rejoin the cluster, along with
+/// the task keeping it up to date, if any.
+static GLOBAL: Lazy<Mutex<Option<(ClusterConfig, Cluster, Option<JoinHandle<()>>)>>> =
+    Lazy::new(Default::default);
+
+/// How long leaving the cluster on shutdown may take.
+const LEAVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Gets the membership of this instance for the given configuration, if clustering is enabled,
+/// joining the cluster if it isn't a member yet.
+pub fn global(config: &ClusterConfig) -> crate::Result<Option<Cluster>> {
+    let backend = match &config.backend {
+        Some(backend) => backend,
+        None => return Ok(None),
+    };
+
+    let mut global = GLOBAL.lock().expect("cluster lock poisoned");
+    if let Some((current, cluster, _)) = global.as_ref() {
+        if current == config {
+            return Ok(Some(cluster.clone()));
+        }
+    }
+
+    let node_id = match &config.node_id {
+        Some(node_id) => node_id.clone(),
+        None => crate::get_hostname()?,
+    };
+    let (cluster, task) = match backend {
+        ClusterBackendConfig::Static { members } => {
+            let (_, members) = watch::channel(members.clone());
+            (Cluster::new(node_id, members), None)
+        }
+        ClusterBackendConfig::Consul {
+            address,
+            key_prefix,
+            session_ttl_secs,
+        } => consul::join(
+            node_id,
+            address,
+            key_prefix,
+            Duration::from_secs(*session_ttl_secs),
+        )
+        .map(|(cluster, task)| (cluster, Some(task)))?,
+        #[cfg(feature = "kubernetes")]
+        ClusterBackendConfig::KubernetesLease {
+            lease_name,
+            namespace,
+            lease_duration_secs,
+        } => {
+            let (cluster, task) = kubernetes::join(
+                node_id,
+                lease_name,
+                namespace.as_deref(),
+                Duration::from_secs(*lease_duration_secs),
+            );
+            (cluster, Some(task))
+        }
+        #[cfg(not(feature = "kubernetes"))]
+        ClusterBackendConfig::KubernetesLease { .. } => {
+            return Err("Vector was built without Kubernetes support.".into());
+        }
+    };
+    info!(message = "Joined cluster.", node_id = %cluster.node_id());
+    *global = Some((config.clone(), cluster.clone(), task));
+    Ok(Some(cluster))
+}
+
+/// Leaves the cluster on shutdown, once the sources are stopped, so that the others can take
+/// over the work of this instance right away rather than once it's found to be gone.
+pub async fn leave() {
+    let task = match GLOBAL.lock().expect("cluster lock poisoned").take() {
+        Some((_, _, Some(task))) => task,
+        _ => return,
+    };
+    // The task leaves once the last handle on the membership is dropped.
+    if time::timeout(LEAVE_TIMEOUT, task).await.is_err() {
+        warn!(message = "Timed out leaving cluster.");
+    }
+}
+
+/// The membership of this instance in a cluster, following which members are live.
+#[derive(Clone, Debug)]
+pub struct Cluster {
+    node_id: Arc<str>,
+    members: watch::Receiver<Vec<String>>,
+}
+
+impl Cluster {
+    fn new(node_id: String, members: watch::Receiver<Vec<String>>) -> Self {
+        Self {
+            node_id: node_id.into(),
+            members,
+        }
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// The identifiers of the live members, as last known.
+    pub fn members(&self) -> Vec<String> {
+        self.members.borrow().clone()
+    }
+
+    /// Whether this instance owns the work identified by the key. While the members agree on
+    /// which of them are live, each key is owned by exactly one of them.
+    ///
+    /// Until this instance knows itself to be a member, it owns nothing, so that it doesn't
+    /// duplicate the work of the others while it joins.
+    pub fn owns(&self, key: &str) -> bool {
+        owner(&self.members.borrow(), key) == Some(&*self.node_id)
+    }
+}
+
+/// The member owning a key, by rendezvous hashing: the member for which the hash of its identifier
+/// and the key is the highest. When a member leaves, only the keys it owned move, spread over the
+/// others, and when one joins, it only takes keys over from the others.
+fn owner<'a>(members: &'a [String], key: &str) -> Option<&'a str> {
+    members
+        .iter()
+        .max_by_key(|member| (score(member, key), *member))
+        .map(String::as_str)
+}
+
+/// A hash of a member and key that is stable across instances and versions, unlike the hashers
+/// of the standard library, so that every member computes the same owners.
+fn score(member: &str, key: &str) -> u64 {
+    // FNV-1a, followed by the finalizer of SplitMix64, as FNV-1a alone spreads similar inputs
+    // poorly over the high bits compared.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in member.bytes().chain(Some(0xff)).chain(key.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+/// Pieces of work of a source, each done only by the member of the cluster owning it, or all done
+/// by this instance outside of a cluster.
+#[derive(Clone, Debug)]
+pub struct SharedWork<T> {
+    cluster: Option<Cluster>,
+    items: Vec<(String, T)>,
+}
+
+impl<T: Clone> SharedWork<T> {
+    /// Shares the items, identified by the key given by the function within the scope, which is
+    /// normally the ID of the source, so that the items of different sources are spread
+    /// independently.
+    pub fn new(
+        cluster: Option<Cluster>,
+        scope: &str,
+        items: impl IntoIterator<Item = T>,
+        key: impl Fn(&T) -> String,
+    ) -> Self {
+        let items = items
+            .into_iter()
+            .map(|item| (format!("{}/{}", scope, key(&item)), item))
+            .collect();
+        Self { cluster, items }
+    }
+
+    /// The items owned by this instance as of now.
+    pub fn owned(&self) -> Vec<T> {
+        self.items
+            .iter()
+            .filter(|(key, _)| {
+                self.cluster
+                    .as_ref()
+                    .map_or(true, |cluster| cluster.owns(key))
+            })
+            .map(|(_, item)| item.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn cluster(node_id: &str, members: &[&str]) -> Cluster {
+        let members = members.iter().map(|member| member.to_string()).collect();
+        Cluster::new(node_id.into(), watch::channel(members).1)
+    }
+
+    #[test]
+    fn each_key_has_one_owner() {
+        let members = ["a", "b", "c"];
+        let clusters = members
+            .iter()
+            .map(|node_id| cluster(node_id, &members))
+            .collect::<Vec<_>>();
+
+        let mut owned = HashMap::<&str, usize>::new();
+        for n in 0..300 {
+            let key = format!("source/http://10.0.0.{}/metrics", n);
+            let owners = clusters
+                .iter()
+                .filter(|cluster| cluster.owns(&key))
+                .collect::<Vec<_>>();
+            assert_eq!(owners.len(), 1, "{}", key);
+            *owned.entry(owners[0].node_id()).or_default() += 1;
+        }
+        // Each member gets a fair share.
+        for member in members {
+            assert!(owned[member] > 60, "{:?}", owned);
+        }
+
+        assert!(!cluster("d", &members).owns("source/anything"));
+    }
+
+    #[test]
+    fn leaving_members_only_move_their_keys() {
+        let all = ["a", "b", "c"].map(String::from);
+        let remaining = ["a", "c"].map(String::from);
+        for n in 0..100 {
+            let key = n.to_string();
+            let before = owner(&all, &key).unwrap();
+            let after = owner(&remaining, &key).unwrap();
+            if before != "b" {
+                assert_eq!(before, after);
+            }
+        }
+    }
+
+    #[test]
+    fn shares_work() {
+        let work = SharedWork::new(Some(cluster("a", &["a"])), "scrape", vec![1, 2, 3], |n| {
+            n.to_string()
+        });
+        assert_eq!(work.owned(), vec![1, 2, 3]);
+
+        let work = SharedWork::new(Some(cluster("a", &[])), "scrape", vec![1, 2, 3], |n| {
+            n.to_string()
+        });
+        assert!(work.owned().is_empty());
+
+        let work = SharedWork::new(None, "scrape", vec![1, 2, 3], |n| n.to_string());
+        assert_eq!(work.owned(), vec![1, 2, 3]);
+    }
+}
@@ -93,7 +93,7 @@ mod test {
         let encoding = serde_json::from_str::<EncodingConfig>(string).unwrap();
         let serializer = encoding.config();
 
-        assert!(matches!(serializer, SerializerConfig::Json));
+        assert!(matches!(serializer, SerializerConfig::Json { .. }));
 
         let transformer = encoding.transformer();
 
@@ -125,7 +125,7 @@ mod test {
         let (framing, serializer) = encoding.config();
 
         assert!(matches!(framing, Some(FramingConfig::NewlineDelimited)));
-        assert!(matches!(serializer, SerializerConfig::Json));
+        assert!(matches!(serializer, SerializerConfig::Json { .. }));
 
         let transformer = encoding.transformer();
 
@@ -154,7 +154,7 @@ mod test {
         let (framing, serializer) = encoding.config();
 
         assert!(matches!(framing, None));
-        assert!(matches!(serializer, SerializerConfig::Json));
+        assert!(matches!(serializer, SerializerConfig::Json { .. }));
 
         let transformer = encoding.transformer();
 
@@ -7,6 +7,7 @@ pub(self) mod datadog_search;
 pub(crate) mod is_log;
 pub(crate) mod is_metric;
 pub mod not;
+pub(crate) mod shared;
 mod vrl;
 
 pub use self::vrl::VrlConfig;
@@ -19,6 +20,7 @@ pub enum Condition {
     Vrl(vrl::Vrl),
     CheckFields(check_fields::CheckFields),
     DatadogSearch(datadog_search::DatadogSearchRunner),
+    Shared(shared::Shared),
 
     // used for benchmarks
     AlwaysPass,
@@ -44,6 +46,7 @@ impl Condition {
             Condition::CheckFields(x) => x.check(e),
             Condition::DatadogSearch(x) => x.check(e),
             Condition::Vrl(x) => x.check(e),
+            Condition::Shared(x) => x.check(e),
             Condition::AlwaysPass => true,
             Condition::AlwaysFail => false,
         }
@@ -59,6 +62,7 @@ impl Condition {
             Condition::CheckFields(x) => x.check_with_context(e),
             Condition::DatadogSearch(x) => x.check_with_context(e),
             Condition::Vrl(x) => x.check_with_context(e),
+            Condition::Shared(x) => x.check_with_context(e),
             Condition::AlwaysPass => Ok(()),
             Condition::AlwaysFail => Ok(()),
         }
@@ -82,6 +86,18 @@ pub trait Conditional {
 #[typetag::serde(tag = "type")]
 pub trait ConditionConfig: std::fmt::Debug + Send + Sync + dyn_clone::DynClone {
     fn build(&self, enrichment_tables: &enrichment::TableRegistry) -> crate::Result<Condition>;
+
+    /// Builds a condition for each of the disjuncts of the condition, if it is
+    /// a disjunction.
+    ///
+    /// Checking these in order, until one of them passes, is equivalent to
+    /// checking the whole condition, but tells which of them an event passed.
+    fn build_disjuncts(
+        &self,
+        _enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Option<Vec<Condition>>> {
+        Ok(None)
+    }
 }
 
 dyn_clone::clone_trait_object!(ConditionConfig);
@@ -127,6 +143,20 @@ impl AnyCondition {
             AnyCondition::Map(m) => m.build(enrichment_tables),
         }
     }
+
+    pub fn build_disjuncts(
+        &self,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Option<Vec<Condition>>> {
+        match self {
+            AnyCondition::String(s) => VrlConfig {
+                source: s.clone(),
+                runtime: Default::default(),
+            }
+            .build_disjuncts(enrichment_tables),
+            AnyCondition::Map(m) => m.build_disjuncts(enrichment_tables),
+        }
+    }
 }
 
 #[cfg(test)]
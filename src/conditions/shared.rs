@@ -0,0 +1,128 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{vrl::Vrl, Condition, ConditionConfig, ConditionDescription, Conditional},
+    event::Event,
+    vrl_library,
+};
+
+/// The conditions defined in the top-level `conditions` of the running
+/// topology, compiled once and shared by all the components referring to them.
+static SHARED_CONDITIONS: Lazy<RwLock<HashMap<String, Arc<Condition>>>> =
+    Lazy::new(Default::default);
+
+/// Replaces the shared conditions that `shared` conditions are built from.
+///
+/// Components that were already built keep the conditions they were built with.
+pub(crate) fn load(conditions: HashMap<String, Arc<Condition>>) {
+    *SHARED_CONDITIONS
+        .write()
+        .expect("shared conditions lock poisoned") = conditions;
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SharedConfig {
+    /// The name of the condition in the top-level `conditions`, or of the program in the top-level
+    /// `vrl_library`.
+    pub(crate) source: String,
+}
+
+inventory::submit! {
+    ConditionDescription::new::<SharedConfig>("shared")
+}
+
+impl_generate_config_from_default!(SharedConfig);
+
+#[typetag::serde(name = "shared")]
+impl ConditionConfig for SharedConfig {
+    fn build(&self, _enrichment_tables: &enrichment::TableRegistry) -> crate::Result<Condition> {
+        let condition = SHARED_CONDITIONS
+            .read()
+            .expect("shared conditions lock poisoned")
+            .get(&self.source)
+            .cloned();
+
+        match condition {
+            Some(condition) => Ok(Condition::Shared(Shared { condition })),
+            None => {
+                let library_program = vrl_library::get(&self.source)
+                    .map_err(|_| format!("unknown shared condition {:?}", self.source))?;
+
+                Ok(Condition::Vrl(Vrl {
+                    program: library_program.program.clone(),
+                    source: library_program.source.clone(),
+                }))
+            }
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct Shared {
+    condition: Arc<Condition>,
+}
+
+impl Conditional for Shared {
+    fn check(&self, e: &Event) -> bool {
+        self.condition.check(e)
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        self.condition.check_with_context(e)
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::event::{
+        metric::{Metric, MetricKind, MetricValue},
+        Event, LogEvent,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SharedConfig>();
+    }
+
+    #[test]
+    fn shared_condition() {
+        let cond = Condition::Shared(Shared {
+            condition: Arc::new(Condition::is_log()),
+        });
+
+        assert!(cond.check(&Event::from(LogEvent::from("just a log"))));
+        assert_eq!(
+            cond.check_with_context(&Event::from(Metric::new(
+                "test metric",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            ))),
+            Err("event is not a log type".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_shared_condition() {
+        let error = SharedConfig {
+            source: "unknown".to_owned(),
+        }
+        .build(&Default::default())
+        .unwrap_err();
+
+        assert_eq!(error.to_string(), r#"unknown shared condition "unknown""#);
+    }
+}
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 use value::Value;
 use vector_common::TimeZone;
-use vrl::{diagnostic::Formatter, Program, Runtime, VrlRuntime};
+use vrl::{
+    diagnostic::{DiagnosticList, Formatter, Span},
+    parser::ast::{Expr, Node, Op, Opcode, RootExpr},
+    Program, Runtime, VrlRuntime,
+};
 
 use crate::{
     conditions::{Condition, ConditionConfig, ConditionDescription, Conditional},
@@ -24,9 +28,11 @@ inventory::submit! {
 
 impl_generate_config_from_default!(VrlConfig);
 
-#[typetag::serde(name = "vrl")]
-impl ConditionConfig for VrlConfig {
-    fn build(&self, enrichment_tables: &enrichment::TableRegistry) -> crate::Result<Condition> {
+impl VrlConfig {
+    fn compile(
+        &self,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> Result<(Program, DiagnosticList), DiagnosticList> {
         // TODO(jean): re-add this to VRL
         // let constraint = TypeConstraint {
         //     allow_any: false,
@@ -52,24 +58,91 @@ impl ConditionConfig for VrlConfig {
         let mut state = vrl::state::ExternalEnv::default();
         state.set_external_context(enrichment_tables.clone());
 
-        let (program, warnings) = vrl::compile_with_state(&self.source, &functions, &mut state)
-            .map_err(|diagnostics| {
-                Formatter::new(&self.source, diagnostics)
-                    .colored()
-                    .to_string()
-            })?;
+        vrl::compile_with_state(&self.source, &functions, &mut state)
+    }
+
+    fn into_condition(self, program: Program) -> Condition {
+        match self.runtime {
+            VrlRuntime::Ast => Condition::Vrl(Vrl {
+                program,
+                source: self.source,
+            }),
+        }
+    }
+}
+
+#[typetag::serde(name = "vrl")]
+impl ConditionConfig for VrlConfig {
+    fn build(&self, enrichment_tables: &enrichment::TableRegistry) -> crate::Result<Condition> {
+        let (program, warnings) = self.compile(enrichment_tables).map_err(|diagnostics| {
+            Formatter::new(&self.source, diagnostics)
+                .colored()
+                .to_string()
+        })?;
 
         if !warnings.is_empty() {
             let warnings = Formatter::new(&self.source, warnings).colored().to_string();
             warn!(message = "VRL compilation warning.", %warnings);
         }
 
-        match self.runtime {
-            VrlRuntime::Ast => Ok(Condition::Vrl(Vrl {
-                program,
-                source: self.source.clone(),
-            })),
+        Ok(self.clone().into_condition(program))
+    }
+
+    /// The disjuncts of a source such as `.status == 500 || .level == "error"`
+    /// are compiled on their own, as long as each of them compiles to a boolean
+    /// expression, so that checking them in order behaves the same as checking
+    /// the whole source.
+    fn build_disjuncts(
+        &self,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Option<Vec<Condition>>> {
+        let program = match vrl::parser::parse(&self.source) {
+            Ok(program) => program,
+            Err(_) => return Ok(None),
+        };
+
+        let mut disjuncts = Vec::new();
+        match program.0.as_slice() {
+            [root] => match root.inner() {
+                RootExpr::Expr(expr) => collect_disjuncts(expr, &mut disjuncts),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        }
+
+        if disjuncts.len() < 2 {
+            return Ok(None);
+        }
+
+        let mut conditions = Vec::with_capacity(disjuncts.len());
+        for span in disjuncts {
+            let config = VrlConfig {
+                source: self.source[span.range()].to_owned(),
+                runtime: self.runtime,
+            };
+
+            match config.compile(enrichment_tables) {
+                Ok((program, _)) if program.type_def().kind().is_boolean() => {
+                    conditions.push(config.into_condition(program));
+                }
+                _ => return Ok(None),
+            }
         }
+
+        Ok(Some(conditions))
+    }
+}
+
+/// Collects the spans of the operands of the chain of `||` operators the
+/// expression consists of.
+fn collect_disjuncts(expr: &Node<Expr>, disjuncts: &mut Vec<Span>) {
+    match expr.inner() {
+        Expr::Op(op) if op.inner().1.inner() == &Opcode::Or => {
+            let Op(lhs, _, rhs) = op.inner();
+            collect_disjuncts(lhs, disjuncts);
+            collect_disjuncts(rhs, disjuncts);
+        }
+        _ => disjuncts.push(expr.span()),
     }
 }
 
@@ -252,4 +325,48 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn build_disjuncts() {
+        let checks = vec![
+            // source, number of disjuncts
+            (r#".status == 500 || .level == "error""#, Some(2)),
+            (r#".a == 1 || (.b == 2 && .c == 3) || exists(.d)"#, Some(3)),
+            (r#".status == 500"#, None),
+            (r#".status == 500 && .level == "error""#, None),
+            // A disjunct doesn't resolve to a boolean.
+            (r#".a || .b == 1"#, None),
+            // A disjunct can't be compiled on its own.
+            (r#"(x = .a) == 1 || x == 2"#, None),
+        ];
+
+        for (source, disjuncts) in checks {
+            let config = VrlConfig {
+                source: source.to_owned(),
+                runtime: Default::default(),
+            };
+
+            assert_eq!(
+                config
+                    .build_disjuncts(&Default::default())
+                    .unwrap()
+                    .map(|disjuncts| disjuncts.len()),
+                disjuncts,
+                "{}",
+                source
+            );
+        }
+
+        let config = VrlConfig {
+            source: r#".status == 500 || .level == "error""#.to_owned(),
+            runtime: Default::default(),
+        };
+        let disjuncts = config
+            .build_disjuncts(&Default::default())
+            .unwrap()
+            .unwrap();
+        let event = log_event!["status" => 200, "level" => "error"];
+        assert!(!disjuncts[0].check(&event));
+        assert!(disjuncts[1].check(&event));
+    }
 }
@@ -2,7 +2,9 @@ use std::net::{Ipv4Addr, SocketAddr};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Copy, Clone)]
+use crate::topology::health::{ComponentKind, HealthState};
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     #[serde(default = "default_enabled")]
@@ -13,6 +15,52 @@ pub struct Options {
 
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    #[serde(default)]
+    pub profiling: bool,
+
+    #[serde(default)]
+    pub auth: AuthOptions,
+
+    #[serde(default)]
+    pub health: HealthOptions,
+}
+
+/// Authentication required by the endpoints of the API exposing the internals of Vector, such as
+/// the profiling endpoints.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthOptions {
+    /// Token to send as `Authorization: Bearer <token>`.
+    pub token: Option<String>,
+}
+
+/// How the health of the components makes up the readiness reported by `/health`.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthOptions {
+    /// Vector isn't ready while any of these policies is violated.
+    pub readiness: Vec<HealthPolicy>,
+}
+
+/// A policy violated when a component has been in a state, or a less healthy one, for a while.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct HealthPolicy {
+    /// The kind of components the policy applies to, or all of them if unset.
+    #[serde(default)]
+    pub component_kind: Option<ComponentKind>,
+
+    #[serde(default = "default_policy_state")]
+    pub state: HealthState,
+
+    /// How long a component must have been in the state for the policy to be violated.
+    #[serde(default)]
+    pub for_secs: u64,
+}
+
+const fn default_policy_state() -> HealthState {
+    HealthState::Failed
 }
 
 impl Default for Options {
@@ -21,6 +69,9 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            profiling: false,
+            auth: AuthOptions::default(),
+            health: HealthOptions::default(),
         }
     }
 }
@@ -61,10 +112,23 @@ impl Options {
             }
         };
 
+        let token = match (self.auth.token.clone(), other.auth.token) {
+            (Some(a), Some(b)) if a != b => {
+                return Err("Conflicting `api.auth.token` values.".to_owned())
+            }
+            (a, b) => a.or(b),
+        };
+
+        let mut readiness = std::mem::take(&mut self.health.readiness);
+        readiness.extend(other.health.readiness);
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            profiling: self.profiling | other.profiling,
+            auth: AuthOptions { token },
+            health: HealthOptions { readiness },
         };
 
         *self = options;
@@ -78,6 +142,9 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        profiling: false,
+        auth: AuthOptions::default(),
+        health: HealthOptions::default(),
     };
 
     a.merge(Options::default()).unwrap();
@@ -88,6 +155,9 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            profiling: false,
+            auth: AuthOptions::default(),
+            health: HealthOptions::default(),
         }
     );
 }
@@ -99,6 +169,9 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        profiling: false,
+        auth: AuthOptions::default(),
+        health: HealthOptions::default(),
     };
 
     a.merge(Options::default()).unwrap();
@@ -109,6 +182,9 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            profiling: false,
+            auth: AuthOptions::default(),
+            health: HealthOptions::default(),
         }
     );
 }
@@ -127,3 +203,22 @@ fn bind_conflict() {
 
     assert!(a.merge(b).is_err());
 }
+
+#[test]
+fn auth_token_conflict() {
+    let mut a = Options {
+        auth: AuthOptions {
+            token: Some("a".to_owned()),
+        },
+        ..Options::default()
+    };
+
+    let b = Options {
+        auth: AuthOptions {
+            token: Some("b".to_owned()),
+        },
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
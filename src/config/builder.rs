@@ -6,6 +6,8 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use vector_core::{config::GlobalOptions, default_data_dir, transform::TransformConfig};
 
+use crate::conditions::AnyCondition;
+
 #[cfg(feature = "api")]
 use super::api;
 #[cfg(feature = "enterprise")]
@@ -33,6 +35,12 @@ pub struct ConfigBuilder {
     pub healthchecks: HealthcheckOptions,
     #[serde(default)]
     pub enrichment_tables: IndexMap<ComponentKey, EnrichmentTableOuter>,
+    /// Conditions defined once, to be referred to by name by the components.
+    #[serde(default)]
+    pub conditions: IndexMap<String, AnyCondition>,
+    /// VRL programs defined once, to be referred to by name by the components.
+    #[serde(default)]
+    pub vrl_library: IndexMap<String, String>,
     #[serde(default)]
     pub sources: IndexMap<ComponentKey, SourceOuter>,
     #[serde(default)]
@@ -55,6 +63,10 @@ struct ConfigBuilderHash<'a> {
     global: &'a GlobalOptions,
     healthchecks: &'a HealthcheckOptions,
     enrichment_tables: BTreeMap<&'a ComponentKey, &'a EnrichmentTableOuter>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    conditions: BTreeMap<&'a String, &'a AnyCondition>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    vrl_library: BTreeMap<&'a String, &'a String>,
     sources: BTreeMap<&'a ComponentKey, &'a SourceOuter>,
     sinks: BTreeMap<&'a ComponentKey, &'a SinkOuter<String>>,
     transforms: BTreeMap<&'a ComponentKey, &'a TransformOuter<String>>,
@@ -86,6 +98,8 @@ impl From<Config> for ConfigBuilder {
             enterprise,
             healthchecks,
             enrichment_tables,
+            conditions,
+            vrl_library,
             sources,
             sinks,
             transforms,
@@ -115,6 +129,8 @@ impl From<Config> for ConfigBuilder {
             enterprise,
             healthchecks,
             enrichment_tables,
+            conditions,
+            vrl_library,
             sources,
             sinks,
             transforms,
@@ -222,10 +238,18 @@ impl ConfigBuilder {
             errors.push("conflicting values for 'proxy.https' found".to_owned());
         }
 
+        if self.global.proxy.socks5.is_some() && with.global.proxy.socks5.is_some() {
+            errors.push("conflicting values for 'proxy.socks5' found".to_owned());
+        }
+
         if !self.global.proxy.no_proxy.is_empty() && !with.global.proxy.no_proxy.is_empty() {
             errors.push("conflicting values for 'proxy.no_proxy' found".to_owned());
         }
 
+        if self.global.proxy.auth.is_some() && with.global.proxy.auth.is_some() {
+            errors.push("conflicting values for 'proxy.auth' found".to_owned());
+        }
+
         self.global.proxy = self.global.proxy.merge(&with.global.proxy);
 
         if self.global.data_dir.is_none() || self.global.data_dir == default_data_dir() {
@@ -238,6 +262,88 @@ impl ConfigBuilder {
             errors.push("conflicting values for 'data_dir' found".to_owned());
         }
 
+        if with.global.admission != Default::default() {
+            if self.global.admission != Default::default() {
+                errors.push("conflicting values for 'admission' found".to_owned());
+            }
+            self.global.admission = with.global.admission;
+        }
+
+        if with.global.tenancy != Default::default() {
+            if self.global.tenancy != Default::default() {
+                errors.push("conflicting values for 'tenancy' found".to_owned());
+            }
+            self.global.tenancy = with.global.tenancy;
+        }
+
+        if with.global.memory_limit != Default::default() {
+            if self.global.memory_limit != Default::default() {
+                errors.push("conflicting values for 'memory_limit' found".to_owned());
+            }
+            self.global.memory_limit = with.global.memory_limit;
+        }
+
+        if with.global.telemetry != Default::default() {
+            if self.global.telemetry != Default::default() {
+                errors.push("conflicting values for 'telemetry' found".to_owned());
+            }
+            self.global.telemetry = with.global.telemetry;
+        }
+
+        if with.global.internal_metrics != Default::default() {
+            if self.global.internal_metrics != Default::default() {
+                errors.push("conflicting values for 'internal_metrics' found".to_owned());
+            }
+            self.global.internal_metrics = with.global.internal_metrics;
+        }
+
+        if with.global.http_client != Default::default() {
+            if self.global.http_client != Default::default() {
+                errors.push("conflicting values for 'http_client' found".to_owned());
+            }
+            self.global.http_client = with.global.http_client;
+        }
+
+        if with.global.json_parsing != Default::default() {
+            if self.global.json_parsing != Default::default() {
+                errors.push("conflicting values for 'json_parsing' found".to_owned());
+            }
+            self.global.json_parsing = with.global.json_parsing;
+        }
+
+        if with.global.thread_pools != Default::default() {
+            if self.global.thread_pools != Default::default() {
+                errors.push("conflicting values for 'thread_pools' found".to_owned());
+            }
+            self.global.thread_pools = with.global.thread_pools;
+        }
+
+        if with.global.cluster != Default::default() {
+            if self.global.cluster != Default::default() {
+                errors.push("conflicting values for 'cluster' found".to_owned());
+            }
+            self.global.cluster = with.global.cluster;
+        }
+
+        if with.global.checkpoints != Default::default() {
+            if self.global.checkpoints != Default::default() {
+                errors.push("conflicting values for 'checkpoints' found".to_owned());
+            }
+            self.global.checkpoints = with.global.checkpoints;
+        }
+
+        // Tags are merged, so that each config file can add its own.
+        for (name, value) in with.global.tags {
+            match self.global.tags.get(&name) {
+                Some(current) if *current != value => {
+                    errors.push(format!("conflicting values for 'tags.{}' found", name));
+                }
+                _ => {
+                    self.global.tags.insert(name, value);
+                }
+            }
+        }
+
         // If the user has multiple config files, we must *merge* log schemas
         // until we meet a conflict, then we are allowed to error.
         if let Err(merge_errors) = self.global.log_schema.merge(&with.global.log_schema) {
@@ -251,6 +357,16 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate enrichment_table name found: {}", k));
             }
         });
+        with.conditions.keys().for_each(|k| {
+            if self.conditions.contains_key(k) {
+                errors.push(format!("duplicate condition name found: {}", k));
+            }
+        });
+        with.vrl_library.keys().for_each(|k| {
+            if self.vrl_library.contains_key(k) {
+                errors.push(format!("duplicate vrl_library name found: {}", k));
+            }
+        });
         with.sources.keys().for_each(|k| {
             if self.sources.contains_key(k) {
                 errors.push(format!("duplicate source id found: {}", k));
@@ -281,6 +397,8 @@ impl ConfigBuilder {
         }
 
         self.enrichment_tables.extend(with.enrichment_tables);
+        self.conditions.extend(with.conditions);
+        self.vrl_library.extend(with.vrl_library);
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
@@ -303,6 +421,8 @@ impl ConfigBuilder {
             global: &self.global,
             healthchecks: &self.healthchecks,
             enrichment_tables: self.enrichment_tables.iter().collect(),
+            conditions: self.conditions.iter().collect(),
+            vrl_library: self.vrl_library.iter().collect(),
             sources: self.sources.iter().collect(),
             sinks: self.sinks.iter().collect(),
             transforms: self.transforms.iter().collect(),
@@ -364,6 +484,8 @@ mod tests {
             global: &builder.global,
             healthchecks: &builder.healthchecks,
             enrichment_tables: builder.enrichment_tables.iter().collect(),
+            conditions: builder.conditions.iter().collect(),
+            vrl_library: builder.vrl_library.iter().collect(),
             sources: builder.sources.iter().collect(),
             sinks: builder.sinks.iter().collect(),
             transforms: builder.transforms.iter().collect(),
@@ -1,15 +1,21 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
 use serde_json::Value;
 
-use super::{load_builder_from_paths, load_source_from_paths, process_paths, ConfigBuilder};
+use super::{
+    format, lint, load_builder_from_paths, load_from_paths, load_source_from_paths, process_paths,
+    ConfigBuilder, Format,
+};
 use crate::cli::handle_config_errors;
 use crate::config;
 
 #[derive(Parser, Debug, Clone)]
 #[clap(rename_all = "kebab-case")]
 pub struct Opts {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// Pretty print JSON
     #[clap(short, long)]
     pretty: bool,
@@ -27,20 +33,21 @@ pub struct Opts {
         short,
         long,
         env = "VECTOR_CONFIG",
-        use_value_delimiter(true)
+        use_value_delimiter(true),
+        global = true
     )]
     paths: Vec<PathBuf>,
 
     /// Vector config files in TOML format.
-    #[clap(name = "config-toml", long, use_value_delimiter(true))]
+    #[clap(name = "config-toml", long, use_value_delimiter(true), global = true)]
     paths_toml: Vec<PathBuf>,
 
     /// Vector config files in JSON format.
-    #[clap(name = "config-json", long, use_value_delimiter(true))]
+    #[clap(name = "config-json", long, use_value_delimiter(true), global = true)]
     paths_json: Vec<PathBuf>,
 
     /// Vector config files in YAML format.
-    #[clap(name = "config-yaml", long, use_value_delimiter(true))]
+    #[clap(name = "config-yaml", long, use_value_delimiter(true), global = true)]
     paths_yaml: Vec<PathBuf>,
 
     /// Read configuration from files in one or more directories.
@@ -52,11 +59,32 @@ pub struct Opts {
         short = 'C',
         long,
         env = "VECTOR_CONFIG_DIR",
-        use_value_delimiter(true)
+        use_value_delimiter(true),
+        global = true
     )]
     pub config_dirs: Vec<PathBuf>,
 }
 
+#[derive(Parser, Debug, Clone)]
+#[clap(rename_all = "kebab-case")]
+enum Command {
+    /// Print the configuration as it is after merging all config files and the files they
+    /// include, without interpolating environment variables.
+    Render {
+        /// The format to print the configuration in.
+        #[clap(long, default_value = "toml", possible_values = &["toml", "json", "yaml"])]
+        format: Format,
+    },
+
+    /// Check the configuration for likely mistakes, such as components whose events never reach
+    /// a sink, and for breaches of policy rules. Exits with an error when anything is found.
+    Lint {
+        /// A TOML, YAML or JSON file of policy rules to check the configuration against.
+        #[clap(long)]
+        policy: Option<PathBuf>,
+    },
+}
+
 impl Opts {
     fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
         config::merge_path_lists(vec![
@@ -163,6 +191,12 @@ fn serialize_to_json(
 /// Pipelines expansions, etc. The JSON result of this serialization can itself be used as a config,
 /// which also makes it useful for version control or treating as a singular unit of configuration.
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.command {
+        Some(Command::Render { format }) => return render(opts, *format),
+        Some(Command::Lint { policy }) => return lint(opts, policy.as_deref()),
+        None => {}
+    }
+
     let paths = opts.paths_with_formats();
     // Start by serializing to a `ConfigBuilder`. This will leverage validation in config
     // builder fields which we'll use to error out if required.
@@ -190,6 +224,77 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
     exitcode::OK
 }
 
+/// Function used by the `vector config render` subcommand for printing the merged configuration.
+fn render(opts: &Opts, format: Format) -> exitcode::ExitCode {
+    let paths = match process_paths(&opts.paths_with_formats()) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    let source = match load_source_from_paths(&paths) {
+        Ok((map, _)) => map,
+        Err(errs) => return handle_config_errors(errs),
+    };
+
+    match format::serialize(&source, format) {
+        Ok(rendered) => {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("{}", rendered.trim_end());
+            }
+            exitcode::OK
+        }
+        Err(error) => {
+            error!(message = "Failed to render config.", %error);
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+/// Function used by the `vector config lint` subcommand for reporting likely mistakes in the
+/// configuration.
+fn lint(opts: &Opts, policy: Option<&Path>) -> exitcode::ExitCode {
+    let policy = match policy.map(lint::load_policy).transpose() {
+        Ok(policy) => policy.unwrap_or_default(),
+        Err(errs) => return handle_config_errors(errs),
+    };
+
+    let paths = match process_paths(&opts.paths_with_formats()) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    let config = match load_from_paths(&paths) {
+        Ok(config) => config,
+        Err(errs) => return handle_config_errors(errs),
+    };
+
+    let source = match load_source_from_paths(&paths) {
+        Ok((map, _)) => map,
+        Err(errs) => return handle_config_errors(errs),
+    };
+
+    let findings = lint::lint(&config, &source, &policy);
+
+    #[allow(clippy::print_stdout)]
+    {
+        for finding in &findings {
+            println!("~ {}", finding);
+        }
+        if findings.is_empty() {
+            println!("√ No lint findings.");
+        } else {
+            println!("x Found {} lint finding(s).", findings.len());
+        }
+    }
+
+    if findings.is_empty() {
+        exitcode::OK
+    } else {
+        exitcode::CONFIG
+    }
+}
+
 #[cfg(all(test, feature = "sources", feature = "transforms", feature = "sinks"))]
 mod tests {
     use std::collections::HashMap;
@@ -68,6 +68,8 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         enterprise,
         healthchecks,
         enrichment_tables,
+        conditions,
+        vrl_library,
         sources,
         sinks,
         transforms,
@@ -125,6 +127,8 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             version,
             healthchecks,
             enrichment_tables,
+            conditions,
+            vrl_library,
             sources,
             sinks,
             transforms,
@@ -134,6 +138,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         };
 
         config.propagate_acknowledgements()?;
+        validation::check_transactions(&config)?;
 
         let warnings = validation::warnings(&config);
 
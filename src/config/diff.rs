@@ -18,9 +18,28 @@ impl ConfigDiff {
     }
 
     pub fn new(old: &Config, new: &Config) -> Self {
+        let mut transforms = Difference::new(&old.transforms, &new.transforms);
+
+        // Transforms hold on to the shared conditions and VRL library programs they were built
+        // with, so the ones referring to these are rebuilt whenever they change.
+        let old_shared = serde_json::to_vec(&(&old.conditions, &old.vrl_library)).unwrap();
+        let new_shared = serde_json::to_vec(&(&new.conditions, &new.vrl_library)).unwrap();
+        if old_shared != new_shared {
+            let refer_to_shared_definitions = new
+                .transforms
+                .iter()
+                .filter(|(key, _)| old.transforms.contains_key(*key))
+                .filter(|(_, transform)| {
+                    let json = serde_json::to_value(transform).unwrap();
+                    refers_to_shared_definition(&json)
+                })
+                .map(|(key, _)| key.clone());
+            transforms.to_change.extend(refer_to_shared_definitions);
+        }
+
         ConfigDiff {
             sources: Difference::new(&old.sources, &new.sources),
-            transforms: Difference::new(&old.transforms, &new.transforms),
+            transforms,
             sinks: Difference::new(&old.sinks, &new.sinks),
             enrichment_tables: Difference::new(&old.enrichment_tables, &new.enrichment_tables),
         }
@@ -52,6 +71,49 @@ impl ConfigDiff {
             || self.transforms.is_removed(key)
             || self.sinks.is_removed(key)
     }
+
+    /// Lists the sources, transforms and sinks that are added, changed and removed, sorted by ID.
+    pub fn component_changes(&self) -> ComponentChanges {
+        let differences = [&self.sources, &self.transforms, &self.sinks];
+        let collect = |select: fn(&Difference) -> &HashSet<ComponentKey>| {
+            let mut keys = differences
+                .iter()
+                .flat_map(|difference| select(difference).iter().cloned())
+                .collect::<Vec<_>>();
+            keys.sort_by(|a, b| a.id().cmp(b.id()));
+            keys
+        };
+
+        ComponentChanges {
+            added: collect(|difference| &difference.to_add),
+            changed: collect(|difference| &difference.to_change),
+            removed: collect(|difference| &difference.to_remove),
+        }
+    }
+}
+
+/// Checks whether the serialized configuration holds a `shared` condition or refers to a VRL
+/// library program.
+fn refers_to_shared_definition(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(fields) => {
+            fields.get("type").and_then(serde_json::Value::as_str) == Some("shared")
+                || fields
+                    .get("library")
+                    .map_or(false, serde_json::Value::is_string)
+                || fields.values().any(refers_to_shared_definition)
+        }
+        serde_json::Value::Array(values) => values.iter().any(refers_to_shared_definition),
+        _ => false,
+    }
+}
+
+/// The components that are added, changed and removed by a `ConfigDiff`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ComponentChanges {
+    pub added: Vec<ComponentKey>,
+    pub changed: Vec<ComponentKey>,
+    pub removed: Vec<ComponentKey>,
 }
 
 #[derive(Debug)]
@@ -841,6 +841,7 @@ mod test {
             log_format: LogFormat::from_str("text").unwrap(),
             color: Color::from_str("auto").unwrap(),
             watch_config: false,
+            profile: None,
         }
     }
 
@@ -2,9 +2,9 @@
 
 #![deny(missing_docs, missing_debug_implementations)]
 
-use std::path::Path;
+use std::{path::Path, str::FromStr};
 
-use serde::de;
+use serde::{de, Serialize};
 
 /// A type alias to better capture the semantics.
 pub type FormatHint = Option<Format>;
@@ -38,6 +38,22 @@ impl Format {
     }
 }
 
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "toml" => Ok(Format::Toml),
+            "yaml" => Ok(Format::Yaml),
+            "json" => Ok(Format::Json),
+            s => Err(format!(
+                "{} is not a valid option, expected `toml`, `json` or `yaml`",
+                s
+            )),
+        }
+    }
+}
+
 /// Parse the string represented in the specified format.
 /// If the format is unknown - fallback to the default format and attempt
 /// parsing using that.
@@ -52,6 +68,18 @@ where
     }
 }
 
+/// Serialize the value to a string in the specified format.
+pub fn serialize<T>(value: &T, format: Format) -> Result<String, String>
+where
+    T: Serialize,
+{
+    match format {
+        Format::Toml => toml::to_string_pretty(value).map_err(|e| e.to_string()),
+        Format::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        Format::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,397 @@
+//! Semantic checks of a configuration, run by `vector config lint`.
+//!
+//! Unlike validation, which rejects configurations that can't run, linting reports configurations
+//! that run but are likely to be mistakes, along with breaches of the rules of a policy.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use toml::value::{Table, Value};
+use vector_buffers::BufferType;
+
+use super::{format, ComponentKey, Config, Format};
+
+/// Options that are deprecated, by the kind of component they belong to.
+const DEPRECATED_OPTIONS: [(&str, &str, &str); 2] = [
+    (
+        "sources",
+        "acknowledgements",
+        "enable `acknowledgements` in the sink configuration instead",
+    ),
+    ("sinks", "healthcheck_uri", "use `healthcheck.uri` instead"),
+];
+
+/// The rules a configuration is checked against.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct Policy {
+    /// The most events a memory buffer may hold.
+    pub max_memory_buffer_events: usize,
+
+    /// Rules defined by the user.
+    pub rules: Vec<Rule>,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            max_memory_buffer_events: 10_000,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// A rule defined by the user, reported under its name when it's broken.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Rule {
+    pub name: String,
+
+    #[serde(flatten)]
+    pub check: Check,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum Check {
+    /// Every source must feed a sink of one of the given types.
+    RequireSink { sink_types: Vec<String> },
+
+    /// Components of the given types must not be used.
+    ForbidType { types: Vec<String> },
+}
+
+/// Loads a policy from a file, whose format is detected from its name.
+pub fn load_policy(path: &Path) -> Result<Policy, Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| vec![format!("Couldn't read policy {:?}: {}.", path, error)])?;
+    format::deserialize(&content, Format::from_path(path).unwrap_or_default())
+}
+
+/// A likely mistake in a configuration, or a breach of a rule of the policy.
+#[derive(Debug, PartialEq)]
+pub struct Finding {
+    pub rule: String,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(rule: impl Into<String>, message: String) -> Self {
+        Self {
+            rule: rule.into(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.message)
+    }
+}
+
+/// Checks a configuration, along with the configuration source it was loaded from, against a
+/// policy.
+pub fn lint(config: &Config, source: &Table, policy: &Policy) -> Vec<Finding> {
+    let graph = Graph::new(config);
+    let mut findings = Vec::new();
+
+    for (kind, key) in config
+        .sources()
+        .map(|(key, _)| ("Source", key))
+        .chain(config.transforms().map(|(key, _)| ("Transform", key)))
+    {
+        if graph.sinks_fed_by(key).is_empty() {
+            findings.push(Finding::new(
+                "unreachable",
+                format!("{} \"{}\" doesn't feed any sink.", kind, key),
+            ));
+        }
+    }
+
+    for (key, source) in config.sources() {
+        if !source.sink_acknowledgements {
+            continue;
+        }
+        for sink_key in graph.sinks_fed_by(key) {
+            let sink = config.sink(sink_key).expect("sink must exist");
+            let acknowledgements = sink
+                .inner
+                .acknowledgements()
+                .unwrap_or(&config.global.acknowledgements)
+                .merge_default(&config.global.acknowledgements);
+            if !acknowledgements.enabled() {
+                findings.push(Finding::new(
+                    "acknowledgements",
+                    format!(
+                        "Sink \"{}\" doesn't enable acknowledgements, so source \"{}\" acknowledges the events it sends there before they're delivered.",
+                        sink_key, key
+                    ),
+                ));
+            }
+        }
+    }
+
+    for (key, sink) in config.sinks() {
+        for stage in sink.buffer.stages() {
+            if let BufferType::Memory { max_events, .. } = stage {
+                if max_events.get() > policy.max_memory_buffer_events {
+                    findings.push(Finding::new(
+                        "memory_buffer",
+                        format!(
+                            "Sink \"{}\" has a memory buffer of {} events, over the limit of {}.",
+                            key, max_events, policy.max_memory_buffer_events
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    for (kind, option, advice) in DEPRECATED_OPTIONS {
+        let components = source.get(kind).and_then(Value::as_table);
+        for (id, component) in components.into_iter().flatten() {
+            if component.get(option).is_some() {
+                findings.push(Finding::new(
+                    "deprecated",
+                    format!(
+                        "Option `{}` of \"{}\" is deprecated; {}.",
+                        option, id, advice
+                    ),
+                ));
+            }
+        }
+    }
+
+    for rule in &policy.rules {
+        match &rule.check {
+            Check::RequireSink { sink_types } => {
+                for (key, _) in config.sources() {
+                    let feeds_required = graph.sinks_fed_by(key).into_iter().any(|sink_key| {
+                        let sink = config.sink(sink_key).expect("sink must exist");
+                        sink_types
+                            .iter()
+                            .any(|required| required == sink.inner.sink_type())
+                    });
+                    if !feeds_required {
+                        findings.push(Finding::new(
+                            &rule.name,
+                            format!(
+                                "Source \"{}\" doesn't feed a sink of type {}.",
+                                key,
+                                sink_types.join(" or ")
+                            ),
+                        ));
+                    }
+                }
+            }
+            Check::ForbidType { types } => {
+                let components = config
+                    .sources()
+                    .map(|(key, source)| ("Source", key, source.inner.source_type()))
+                    .chain(config.transforms().map(|(key, transform)| {
+                        ("Transform", key, transform.inner.transform_type())
+                    }))
+                    .chain(
+                        config
+                            .sinks()
+                            .map(|(key, sink)| ("Sink", key, sink.inner.sink_type())),
+                    );
+                for (kind, key, component_type) in components {
+                    if types.iter().any(|forbidden| forbidden == component_type) {
+                        findings.push(Finding::new(
+                            &rule.name,
+                            format!(
+                                "{} \"{}\" is of forbidden type \"{}\".",
+                                kind, key, component_type
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    findings
+}
+
+/// The consumers of each component of a configuration.
+struct Graph<'a> {
+    config: &'a Config,
+    consumers: HashMap<&'a ComponentKey, Vec<&'a ComponentKey>>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(config: &'a Config) -> Self {
+        let mut consumers = HashMap::<_, Vec<_>>::new();
+        let inputs = config
+            .transforms()
+            .map(|(key, transform)| (key, &transform.inputs))
+            .chain(config.sinks().map(|(key, sink)| (key, &sink.inputs)));
+        for (key, inputs) in inputs {
+            for input in inputs {
+                consumers.entry(&input.component).or_default().push(key);
+            }
+        }
+
+        Self { config, consumers }
+    }
+
+    /// Lists the sinks the events of a component end up in, directly or through transforms.
+    fn sinks_fed_by(&self, key: &'a ComponentKey) -> Vec<&'a ComponentKey> {
+        let mut sinks = Vec::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![key];
+        while let Some(key) = pending.pop() {
+            if !visited.insert(key) {
+                continue;
+            }
+            if self.config.sink(key).is_some() {
+                sinks.push(key);
+            }
+            if let Some(consumers) = self.consumers.get(key) {
+                pending.extend(consumers);
+            }
+        }
+        sinks
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "sources-demo_logs",
+    feature = "sources-http",
+    feature = "sinks-blackhole",
+    feature = "sinks-console",
+    feature = "transforms-remap"
+))]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::config::load_from_str;
+
+    fn lint_str(input: &str, policy: &Policy) -> Vec<String> {
+        let config = load_from_str(input, Format::Toml).unwrap();
+        let source = toml::from_str(input).unwrap();
+        lint(&config, &source, policy)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn reports_likely_mistakes() {
+        let findings = lint_str(
+            indoc! {r#"
+                [sources.in]
+                type = "http"
+                address = "127.0.0.1:8080"
+                acknowledgements = true
+
+                [sources.orphan]
+                type = "demo_logs"
+                format = "json"
+
+                [transforms.dead_end]
+                type = "remap"
+                inputs = ["orphan"]
+                source = "."
+
+                [sinks.out]
+                type = "blackhole"
+                inputs = ["in"]
+                buffer.type = "memory"
+                buffer.max_events = 50000
+            "#},
+            &Policy::default(),
+        );
+
+        assert_eq!(
+            findings,
+            vec![
+                "[unreachable] Source \"orphan\" doesn't feed any sink.",
+                "[unreachable] Transform \"dead_end\" doesn't feed any sink.",
+                "[memory_buffer] Sink \"out\" has a memory buffer of 50000 events, over the limit of 10000.",
+                "[deprecated] Option `acknowledgements` of \"in\" is deprecated; enable `acknowledgements` in the sink configuration instead.",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_sinks_without_acknowledgements() {
+        let findings = lint_str(
+            indoc! {r#"
+                [sources.in]
+                type = "http"
+                address = "127.0.0.1:8080"
+
+                [sinks.acked]
+                type = "console"
+                inputs = ["in"]
+                encoding = "json"
+                acknowledgements.enabled = true
+
+                [sinks.unacked]
+                type = "console"
+                inputs = ["in"]
+                encoding = "json"
+            "#},
+            &Policy::default(),
+        );
+
+        assert_eq!(
+            findings,
+            vec!["[acknowledgements] Sink \"unacked\" doesn't enable acknowledgements, so source \"in\" acknowledges the events it sends there before they're delivered."]
+        );
+    }
+
+    #[test]
+    fn checks_policy_rules() {
+        let policy: Policy = toml::from_str(indoc! {r#"
+            [[rules]]
+            name = "archive"
+            check = "require_sink"
+            sink_types = ["console"]
+
+            [[rules]]
+            name = "no_blackhole"
+            check = "forbid_type"
+            types = ["blackhole"]
+        "#})
+        .unwrap();
+
+        let findings = lint_str(
+            indoc! {r#"
+                [sources.archived]
+                type = "demo_logs"
+                format = "json"
+
+                [sources.discarded]
+                type = "demo_logs"
+                format = "json"
+
+                [sinks.archive]
+                type = "console"
+                inputs = ["archived"]
+                encoding = "json"
+
+                [sinks.discard]
+                type = "blackhole"
+                inputs = ["discarded"]
+            "#},
+            &policy,
+        );
+
+        assert_eq!(
+            findings,
+            vec![
+                "[archive] Source \"discarded\" doesn't feed a sink of type console.",
+                "[no_blackhole] Sink \"discard\" is of forbidden type \"blackhole\".",
+            ]
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::{collections::HashMap, io::Read};
 use indexmap::IndexMap;
 use toml::value::Table;
 
-use super::{deserialize_table, loader, prepare_input, secret};
+use super::{deserialize_table, loader, prepare_input, secret, templates};
 use super::{ComponentHint, Process};
 use crate::config::{
     ComponentKey, ConfigBuilder, EnrichmentTableOuter, SinkOuter, SourceOuter, TestDefinition,
@@ -44,7 +44,7 @@ impl Process for ConfigBuilderLoader {
     }
 
     /// Merge a TOML `Table` with a `ConfigBuilder`. Component types extend specific keys.
-    fn merge(&mut self, table: Table, hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
+    fn merge(&mut self, mut table: Table, hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
         match hint {
             Some(ComponentHint::Source) => {
                 self.builder.sources.extend(deserialize_table::<
@@ -76,6 +76,7 @@ impl Process for ConfigBuilderLoader {
                 );
             }
             None => {
+                templates::expand(&mut table)?;
                 self.builder.append(deserialize_table(table)?)?;
             }
         };
@@ -0,0 +1,232 @@
+//! Support for config files including other config files.
+//!
+//! A file can list glob patterns of other files under its top-level `includes` key, relative to
+//! the directory of the file itself. The matching files are merged over the including file in the
+//! order they are listed, with the files matching a single pattern in alphabetical order, so that
+//! files later in the order override the settings of those before them:
+//!
+//! * Tables are merged key by key.
+//! * Any other value replaces the value that was there before, including lists.
+//! * A key suffixed with `+=` appends its list to the list under the key without the suffix,
+//!   rather than replacing it, such as with `"inputs+=" = ["more"]`.
+
+use std::path::{Path, PathBuf};
+
+use glob::glob;
+use toml::value::{Table, Value};
+
+/// The key listing the files a config file includes.
+const INCLUDES_KEY: &str = "includes";
+
+/// The suffix of keys that append to a list instead of replacing it.
+const APPEND_SUFFIX: &str = "+=";
+
+/// Takes the patterns of the files included by a config file out of its table.
+pub(super) fn take_patterns(table: &mut Table) -> Result<Vec<String>, Vec<String>> {
+    match table.remove(INCLUDES_KEY) {
+        None => Ok(Vec::new()),
+        Some(Value::Array(patterns)) => patterns
+            .into_iter()
+            .map(|pattern| match pattern {
+                Value::String(pattern) => Ok(pattern),
+                _ => Err(vec![format!(
+                    "`{}` must be a list of file paths.",
+                    INCLUDES_KEY
+                )]),
+            })
+            .collect(),
+        Some(_) => Err(vec![format!(
+            "`{}` must be a list of file paths.",
+            INCLUDES_KEY
+        )]),
+    }
+}
+
+/// Expands a pattern of included files, relative to the directory of the including file.
+///
+/// Returns the matching paths in alphabetical order, adding a warning if there are none.
+pub(super) fn expand(
+    path: &Path,
+    pattern: &str,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<PathBuf>, Vec<String>> {
+    let pattern = path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(pattern);
+    let pattern = pattern.to_str().ok_or_else(|| {
+        vec![format!(
+            "Included config path {:?} is not valid UTF-8.",
+            pattern
+        )]
+    })?;
+
+    let paths = glob(pattern)
+        .map_err(|error| {
+            vec![format!(
+                "Invalid included config path {:?}: {}.",
+                pattern, error
+            )]
+        })?
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+
+    if paths.is_empty() {
+        warnings.push(format!(
+            "Included config path {:?} did not match any files.",
+            pattern
+        ));
+    }
+    Ok(paths)
+}
+
+/// Merges a table over another, following the override rules of included files.
+pub(super) fn merge(base: &mut Table, overlay: Table) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (key, value) in overlay {
+        if let Some(key) = key.strip_suffix(APPEND_SUFFIX) {
+            let key = key.trim_end();
+            let existing = base.entry(key).or_insert_with(|| Value::Array(Vec::new()));
+            match (existing, value) {
+                (Value::Array(existing), Value::Array(more)) => existing.extend(more),
+                _ => errors.push(format!(
+                    "Cannot append to `{}`, as only lists can be appended to.",
+                    key
+                )),
+            }
+            continue;
+        }
+
+        match value {
+            Value::Table(more) => {
+                // Tables replacing other values are still merged into an empty table, to resolve
+                // any appending keys nested in them.
+                let existing = base
+                    .entry(key)
+                    .or_insert_with(|| Value::Table(Table::new()));
+                if !existing.is_table() {
+                    *existing = Value::Table(Table::new());
+                }
+                if let Value::Table(existing) = existing {
+                    if let Err(errs) = merge(existing, more) {
+                        errors.extend(errs);
+                    }
+                }
+            }
+            value => {
+                base.insert(key, value);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(input: &str) -> Table {
+        toml::from_str(input).unwrap()
+    }
+
+    #[test]
+    fn later_values_override_earlier_ones() {
+        let mut base = table(
+            r#"
+            [sinks.out]
+            type = "console"
+            inputs = ["in"]
+            encoding.codec = "json"
+            "#,
+        );
+        merge(
+            &mut base,
+            table(
+                r#"
+                [sinks.out]
+                inputs = ["other"]
+                encoding.codec = "text"
+                "#,
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            base,
+            table(
+                r#"
+                [sinks.out]
+                type = "console"
+                inputs = ["other"]
+                encoding.codec = "text"
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn appends_to_lists() {
+        let mut base = table(
+            r#"
+            [sinks.out]
+            inputs = ["in"]
+            "#,
+        );
+        merge(
+            &mut base,
+            table(
+                r#"
+                [sinks.out]
+                "inputs+=" = ["other"]
+
+                [sinks.archive]
+                "inputs +=" = ["in"]
+                "#,
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            base,
+            table(
+                r#"
+                [sinks.out]
+                inputs = ["in", "other"]
+
+                [sinks.archive]
+                inputs = ["in"]
+                "#
+            )
+        );
+
+        let errors = merge(&mut base, table(r#""sinks+=" = ["out"]"#)).unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["Cannot append to `sinks`, as only lists can be appended to.".to_owned()]
+        );
+    }
+
+    #[test]
+    fn takes_patterns() {
+        let mut config = table(
+            r#"
+            includes = ["conf.d/*.toml", "extra.yaml"]
+            data_dir = "/var/lib/vector"
+            "#,
+        );
+        assert_eq!(
+            take_patterns(&mut config).unwrap(),
+            vec!["conf.d/*.toml".to_owned(), "extra.yaml".to_owned()]
+        );
+        assert!(!config.contains_key(INCLUDES_KEY));
+
+        assert!(take_patterns(&mut table(r#"includes = "conf.d/*.toml""#)).is_err());
+    }
+}
@@ -5,7 +5,7 @@ use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 use toml::value::{Table, Value};
 
-use super::{component_name, open_file, read_dir, Format};
+use super::{component_name, includes, open_file, profiles, read_dir, selected_profile, Format};
 use crate::config::format;
 
 /// Provides a hint to the loading system of the type of components that should be found
@@ -175,13 +175,73 @@ pub(super) mod process {
             format: Format,
         ) -> Result<Option<(String, Table, Vec<String>)>, Vec<String>> {
             if let (Ok(name), Some(file)) = (component_name(path), open_file(path)) {
-                self.load(file, format)
-                    .map(|(value, warnings)| Some((name, value, warnings)))
+                let (table, mut warnings) = self.load(file, format)?;
+                let mut result = Table::new();
+                self.merge_with_includes(path, table, &mut result, &mut Vec::new(), &mut warnings)?;
+                Ok(Some((name, result, warnings)))
             } else {
                 Ok(None)
             }
         }
 
+        /// Merges the loaded `Table` of a config file into `result`, followed by the section of
+        /// the selected profile and the files it includes, recursively merging the files they include in turn. `stack` holds the files
+        /// currently being included, to detect files including themselves.
+        fn merge_with_includes(
+            &mut self,
+            path: &Path,
+            mut table: Table,
+            result: &mut Table,
+            stack: &mut Vec<PathBuf>,
+            warnings: &mut Vec<String>,
+        ) -> Result<(), Vec<String>> {
+            let patterns = includes::take_patterns(&mut table)?;
+            let profile = profiles::take_selected(&mut table, selected_profile().as_deref())?;
+            includes::merge(result, table)?;
+            // The section of the selected profile overrides the rest of the file, but not the
+            // files it includes.
+            if let Some(profile) = profile {
+                includes::merge(result, profile)?;
+            }
+            if patterns.is_empty() {
+                return Ok(());
+            }
+
+            let canonical = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+            if stack.contains(&canonical) {
+                return Err(vec![format!("Config file {:?} includes itself.", path)]);
+            }
+            stack.push(canonical);
+
+            for pattern in patterns {
+                for included in includes::expand(path, &pattern, warnings)? {
+                    // If the file doesn't contain a known extension, skip it.
+                    let format = match Format::from_path(&included) {
+                        Ok(format) => format,
+                        _ => {
+                            warnings.push(format!(
+                                "Skipping included config file {:?} of unknown format.",
+                                included
+                            ));
+                            continue;
+                        }
+                    };
+                    let file = open_file(&included).ok_or_else(|| {
+                        vec![format!(
+                            "Could not open included config file {:?}.",
+                            included
+                        )]
+                    })?;
+                    let (table, warns) = self.load(file, format)?;
+                    warnings.extend(warns);
+                    self.merge_with_includes(&included, table, result, stack, warnings)?;
+                }
+            }
+
+            stack.pop();
+            Ok(())
+        }
+
         /// Loads a file, and if the path provided contains a sub-folder by the same name as the
         /// component, descend into it recursively, returning a TOML `Table`.
         fn load_file_recursive(
@@ -1,7 +1,10 @@
 mod config_builder;
+mod includes;
 mod loader;
+mod profiles;
 mod secret;
 mod source;
+mod templates;
 
 use std::{
     collections::HashMap,
@@ -27,6 +30,19 @@ use crate::signal;
 
 pub static CONFIG_PATHS: Lazy<Mutex<Vec<ConfigPath>>> = Lazy::new(Mutex::default);
 
+/// The profile whose sections are applied when loading config files, if any.
+static PROFILE: Lazy<Mutex<Option<String>>> = Lazy::new(Mutex::default);
+
+/// Selects the profile whose sections are applied when loading config files.
+pub fn set_profile(profile: Option<String>) {
+    // Ignore poison error and let the current main thread continue running to do the cleanup.
+    std::mem::drop(PROFILE.lock().map(|mut guard| *guard = profile));
+}
+
+pub(super) fn selected_profile() -> Option<String> {
+    PROFILE.lock().ok().and_then(|guard| guard.clone())
+}
+
 pub(super) fn read_dir<P: AsRef<Path> + Debug>(path: P) -> Result<ReadDir, Vec<String>> {
     path.as_ref()
         .read_dir()
@@ -154,6 +170,11 @@ pub async fn load_from_paths_with_provider_and_secrets(
     validation::check_provider(&builder)?;
     signal_handler.clear();
 
+    // Keep the retrieved secrets valid until the next reload.
+    for rotation in secrets_backends_loader.rotations() {
+        signal_handler.add(rotation);
+    }
+
     // If there's a provider, overwrite the existing config builder with the remote variant.
     if let Some(mut provider) = builder.provider {
         builder = provider.build(signal_handler).await?;
@@ -333,7 +354,7 @@ fn default_config_paths() -> Vec<ConfigPath> {
 mod tests {
     use std::path::PathBuf;
 
-    use super::load_builder_from_paths;
+    use super::{load_builder_from_paths, load_source_from_paths};
     use crate::{
         config::{ComponentKey, ConfigPath},
         transforms::pipelines::PipelinesConfig,
@@ -391,4 +412,25 @@ mod tests {
         let (_, warnings) = load_builder_from_paths(&configs).unwrap();
         assert!(warnings.is_empty());
     }
+
+    #[test]
+    fn load_includes() {
+        let path = PathBuf::from(".")
+            .join("tests")
+            .join("includes")
+            .join("vector.toml");
+        let configs = vec![ConfigPath::File(path, None)];
+        let (builder, warnings) = load_builder_from_paths(&configs).unwrap();
+        assert!(warnings.is_empty());
+        assert!(builder.sources.contains_key(&ComponentKey::from("more")));
+
+        let sink = builder.sinks.get(&ComponentKey::from("out")).unwrap();
+        assert_eq!(sink.inputs, vec!["in".to_owned(), "more".to_owned()]);
+
+        let (source, _) = load_source_from_paths(&configs).unwrap();
+        assert_eq!(
+            source["sinks"]["out"]["encoding"]["codec"].as_str(),
+            Some("text")
+        );
+    }
 }
@@ -0,0 +1,88 @@
+//! Support for profile-scoped sections of config files.
+//!
+//! A file can hold sections under its top-level `profile` key that only apply when their profile
+//! is selected, such as `[profile.production.sinks.s3]`. The section of the selected profile is
+//! merged over the rest of the file, following the override rules of included files, while the
+//! sections of the other profiles are discarded.
+
+use toml::value::{Table, Value};
+
+/// The key holding the profile-scoped sections of a config file.
+const PROFILE_KEY: &str = "profile";
+
+/// Takes the profile-scoped sections out of the table of a config file, returning the section of
+/// the selected profile, if there is one.
+pub(super) fn take_selected(
+    table: &mut Table,
+    selected: Option<&str>,
+) -> Result<Option<Table>, Vec<String>> {
+    let profiles = match table.remove(PROFILE_KEY) {
+        None => return Ok(None),
+        Some(Value::Table(profiles)) => profiles,
+        Some(_) => {
+            return Err(vec![format!(
+                "`{}` must be a table of profiles.",
+                PROFILE_KEY
+            )])
+        }
+    };
+
+    let mut section = None;
+    for (name, value) in profiles {
+        match value {
+            Value::Table(table) if Some(name.as_str()) == selected => section = Some(table),
+            Value::Table(_) => {}
+            _ => {
+                return Err(vec![format!(
+                    "Profile `{}` must be a table of settings.",
+                    name
+                )])
+            }
+        }
+    }
+    Ok(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(input: &str) -> Table {
+        toml::from_str(input).unwrap()
+    }
+
+    #[test]
+    fn takes_the_selected_profile() {
+        let input = r#"
+            [sinks.out]
+            type = "console"
+
+            [profile.production.sinks.out]
+            type = "aws_s3"
+
+            [profile.development.sinks.out]
+            type = "blackhole"
+            "#;
+
+        let mut config = table(input);
+        assert_eq!(
+            take_selected(&mut config, Some("production")).unwrap(),
+            Some(table(r#"sinks.out.type = "aws_s3""#))
+        );
+        assert_eq!(config, table(r#"sinks.out.type = "console""#));
+
+        let mut config = table(input);
+        assert_eq!(take_selected(&mut config, Some("staging")).unwrap(), None);
+        assert_eq!(config, table(r#"sinks.out.type = "console""#));
+
+        let mut config = table(input);
+        assert_eq!(take_selected(&mut config, None).unwrap(), None);
+        assert_eq!(config, table(r#"sinks.out.type = "console""#));
+    }
+
+    #[test]
+    fn rejects_profiles_that_are_not_tables() {
+        assert!(take_selected(&mut table(r#"profile = "production""#), None).is_err());
+        assert!(take_selected(&mut table(r#"profile.production = 1"#), None).is_err());
+    }
+}
@@ -1,7 +1,17 @@
-use std::{collections::HashMap, io::Read};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    time::Duration,
+};
+
+#[cfg(feature = "secrets-aws")]
+mod aws;
+#[cfg(feature = "secrets-gcp")]
+mod gcp;
+mod vault;
 
 use bytes::BytesMut;
-use futures::{executor, StreamExt};
+use futures::{executor, stream::BoxStream, Future, StreamExt};
 use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
@@ -13,6 +23,7 @@ use typetag::serde;
 
 use super::{loader, prepare_input};
 use crate::{
+    audit::{self, AuditAction},
     config::{
         loading::{deserialize_table, ComponentHint, Process},
         ComponentKey,
@@ -37,6 +48,13 @@ pub trait SecretBackend: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
         secret_keys: Vec<String>,
         signal_rx: &mut signal::SignalRx,
     ) -> crate::Result<HashMap<String, String>>;
+
+    /// Returns a stream that keeps the secrets retrieved last valid, such as by renewing their
+    /// leases, and signals to reload the config once they must be retrieved again. The stream is
+    /// dropped on the next reload.
+    fn rotation(&mut self) -> Option<BoxStream<'static, signal::SignalTo>> {
+        None
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -64,7 +82,13 @@ impl SecretBackendLoader {
                 },
                 Some(backend) => {
                     debug!(message = "Retrieving secret from a backend.", backend = ?backend_name);
-                    match backend.retrieve(keys.to_vec(), signal_rx) {
+                    let result = backend.retrieve(keys.to_vec(), signal_rx);
+                    audit::record(AuditAction::SecretsResolved {
+                        backend: backend_name,
+                        keys,
+                        success: result.is_ok(),
+                    });
+                    match result {
                         Err(e) => {
                             vec![Err(format!("Error while retrieving secret from backend \"{}\": {}.", backend_name, e))]
                         },
@@ -84,6 +108,16 @@ impl SecretBackendLoader {
     pub(crate) fn has_secrets_to_retrieve(&self) -> bool {
         !self.secret_keys.is_empty()
     }
+
+    /// Takes the streams rotating the secrets retrieved from each backend, if any.
+    pub(crate) fn rotations(&mut self) -> Vec<BoxStream<'static, signal::SignalTo>> {
+        let secret_keys = &self.secret_keys;
+        self.backends
+            .iter_mut()
+            .filter(|(name, _)| secret_keys.contains_key(name.id()))
+            .filter_map(|(_, backend)| backend.rotation())
+            .collect()
+    }
 }
 
 impl Process for SecretBackendLoader {
@@ -153,6 +187,132 @@ fn collect_secret_keys(input: &str, keys: &mut HashMap<String, Vec<String>>) {
     });
 }
 
+/// Splits a secret key into the path of the secret, with its segments separated by slashes rather
+/// than dots, and the field holding the secret.
+fn split_key(key: &str) -> Option<(String, String)> {
+    let (path, field) = key.rsplit_once('.')?;
+    (!path.is_empty() && !field.is_empty()).then(|| (path.replace('.', "/"), field.to_owned()))
+}
+
+/// Gets the secret held by a field of a structured secret.
+fn secret_from_field(key: &str, value: Option<&serde_json::Value>) -> crate::Result<String> {
+    match value {
+        Some(serde_json::Value::String(value)) if value.is_empty() => {
+            Err(format!("secret for key '{}' was empty", key).into())
+        }
+        Some(serde_json::Value::String(value)) => Ok(value.clone()),
+        Some(serde_json::Value::Null) | None => {
+            Err(format!("secret for key '{}' was not retrieved", key).into())
+        }
+        Some(value) => Ok(value.to_string()),
+    }
+}
+
+/// Runs the retrieval of secrets from a backend, giving up once it times out or Vector shuts
+/// down.
+fn block_on_retrieval(
+    retrieval: impl Future<Output = crate::Result<HashMap<String, String>>>,
+    timeout: u64,
+    signal_rx: &mut signal::SignalRx,
+) -> crate::Result<HashMap<String, String>> {
+    executor::block_on(async {
+        tokio::select! {
+            biased;
+            Ok(signal::SignalTo::Shutdown | signal::SignalTo::Quit) = signal_rx.recv() => {
+                Err("Secret retrieval was interrupted.".into())
+            }
+            result = time::timeout(Duration::from_secs(timeout), retrieval) => {
+                result.map_err(|_| "Secret retrieval timed-out")?
+            }
+        }
+    })
+}
+
+/// Groups secret keys by the name of the secret holding them. When secrets hold JSON objects, the
+/// last segment of each key is the field holding the secret.
+#[cfg(any(feature = "secrets-aws", feature = "secrets-gcp"))]
+fn group_by_secret(
+    secret_keys: Vec<String>,
+    json: bool,
+) -> crate::Result<BTreeMap<String, Vec<(String, Option<String>)>>> {
+    let mut secrets = BTreeMap::<_, Vec<_>>::new();
+    for key in secret_keys {
+        let (name, field) = if json {
+            let (name, field) = split_key(&key).ok_or_else(|| {
+                format!("secret key '{}' has no field, such as `name.field`", key)
+            })?;
+            (name, Some(field))
+        } else {
+            (key.replace('.', "/"), None)
+        };
+        secrets.entry(name).or_default().push((key, field));
+    }
+    Ok(secrets)
+}
+
+/// Gets the secret held by the value of a secret, or by a field of it if it holds a JSON object.
+#[cfg(any(feature = "secrets-aws", feature = "secrets-gcp"))]
+fn secret_from_value(key: &str, value: &str, field: Option<&str>) -> crate::Result<String> {
+    match field {
+        Some(field) => {
+            let fields: serde_json::Map<String, serde_json::Value> = serde_json::from_str(value)
+                .map_err(|error| {
+                    format!(
+                        "secret for key '{}' does not hold a JSON object: {}",
+                        key, error
+                    )
+                })?;
+            secret_from_field(key, fields.get(field))
+        }
+        None => secret_from_field(key, Some(&serde_json::Value::String(value.to_owned()))),
+    }
+}
+
+#[cfg(any(feature = "secrets-aws", feature = "secrets-gcp"))]
+const fn default_refresh_interval_secs() -> u64 {
+    300
+}
+
+/// Retrieves the secrets retrieved last again every `interval_secs`, signaling to reload the
+/// config once any of them has changed.
+#[cfg(any(feature = "secrets-aws", feature = "secrets-gcp"))]
+fn watch_for_changes<F, Fut>(
+    interval_secs: u64,
+    retrieved: HashMap<String, String>,
+    retrieve: F,
+) -> Option<BoxStream<'static, signal::SignalTo>>
+where
+    F: Fn(Vec<String>) -> Fut + Send + 'static,
+    Fut: Future<Output = crate::Result<HashMap<String, String>>> + Send,
+{
+    if interval_secs == 0 || retrieved.is_empty() {
+        return None;
+    }
+
+    let period = Duration::from_secs(interval_secs);
+    Some(
+        async_stream::stream! {
+            let mut interval = time::interval_at(time::Instant::now() + period, period);
+            loop {
+                interval.tick().await;
+                match retrieve(retrieved.keys().cloned().collect()).await {
+                    Ok(secrets) if secrets != retrieved => {
+                        info!(message = "Secrets have changed, reloading configuration.");
+                        yield signal::SignalTo::ReloadFromDisk;
+                        break;
+                    }
+                    Ok(_) => {}
+                    Err(error) => warn!(message = "Failed to retrieve secrets again.", %error),
+                }
+            }
+
+            // The stream ending is reported as an error, so wait for the next reload to drop it.
+            futures::future::pending::<()>().await;
+        }
+        .boxed(),
+    )
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct ExecBackend {
     pub command: Vec<String>,
@@ -314,7 +474,7 @@ mod test {
 
     use indoc::indoc;
 
-    use super::{collect_secret_keys, interpolate};
+    use super::{collect_secret_keys, interpolate, split_key};
 
     #[test]
     fn replacement() {
@@ -386,4 +546,39 @@ mod test {
         assert!(second_backend_keys.contains(&"secret_key".into()));
         assert!(second_backend_keys.contains(&"secret.key".into()));
     }
+
+    #[test]
+    fn splits_keys_into_paths_and_fields() {
+        assert_eq!(
+            split_key("creds.readonly.password"),
+            Some(("creds/readonly".to_owned(), "password".to_owned()))
+        );
+        assert_eq!(
+            split_key("app.token"),
+            Some(("app".to_owned(), "token".to_owned()))
+        );
+        assert_eq!(split_key("token"), None);
+        assert_eq!(split_key("app."), None);
+    }
+
+    #[cfg(any(feature = "secrets-aws", feature = "secrets-gcp"))]
+    #[test]
+    fn secrets_from_values() {
+        use super::secret_from_value;
+
+        assert_eq!(secret_from_value("a", "value", None).unwrap(), "value");
+        assert!(secret_from_value("a", "", None).is_err());
+
+        let value = r#"{"username": "vector", "port": 5432, "password": null}"#;
+        assert_eq!(
+            secret_from_value("a.username", value, Some("username")).unwrap(),
+            "vector"
+        );
+        assert_eq!(
+            secret_from_value("a.port", value, Some("port")).unwrap(),
+            "5432"
+        );
+        assert!(secret_from_value("a.password", value, Some("password")).is_err());
+        assert!(secret_from_value("a.username", "vector", Some("username")).is_err());
+    }
 }
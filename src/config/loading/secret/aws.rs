@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use futures::stream::BoxStream;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    block_on_retrieval, default_refresh_interval_secs, default_timeout_secs, group_by_secret,
+    secret_from_value, watch_for_changes, SecretBackend,
+};
+use crate::{
+    aws::{create_client, AwsAuthentication, ClientBuilder, RegionOrEndpoint},
+    config::ProxyConfig,
+    signal,
+};
+
+/// The most parameters SSM returns in a single request.
+const MAX_PARAMETERS_PER_REQUEST: usize = 10;
+
+pub struct SecretsManagerClientBuilder;
+
+impl ClientBuilder for SecretsManagerClientBuilder {
+    type Config = aws_sdk_secretsmanager::config::Config;
+    type Client = aws_sdk_secretsmanager::client::Client;
+    type DefaultMiddleware = aws_sdk_secretsmanager::middleware::DefaultMiddleware;
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_secretsmanager::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_secretsmanager::client::Client::with_config(client, config.into())
+    }
+}
+
+pub struct SsmClientBuilder;
+
+impl ClientBuilder for SsmClientBuilder {
+    type Config = aws_sdk_ssm::config::Config;
+    type Client = aws_sdk_ssm::client::Client;
+    type DefaultMiddleware = aws_sdk_ssm::middleware::DefaultMiddleware;
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_ssm::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_ssm::client::Client::with_config(client, config.into())
+    }
+}
+
+/// Retrieves secrets from AWS Secrets Manager.
+///
+/// The key of a secret is the name of the secret, with dots in place of slashes. When `json` is
+/// set, secrets hold JSON objects, and the last segment of a key is the field holding the secret.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct AwsSecretsManagerBackend {
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+
+    #[serde(default)]
+    auth: AwsAuthentication,
+
+    /// Whether secrets hold JSON objects whose fields are retrieved separately.
+    #[serde(default)]
+    json: bool,
+
+    /// How often to retrieve the secrets again, reloading the config when they change. Zero
+    /// disables retrieving them again.
+    #[serde(default = "default_refresh_interval_secs")]
+    refresh_interval_secs: u64,
+
+    #[serde(default = "default_timeout_secs")]
+    timeout: u64,
+
+    /// The secrets retrieved last, to compare with when retrieving them again.
+    #[serde(skip)]
+    retrieved: HashMap<String, String>,
+}
+
+impl AwsSecretsManagerBackend {
+    async fn fetch(&self, secret_keys: Vec<String>) -> crate::Result<HashMap<String, String>> {
+        let client = create_client::<SecretsManagerClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &ProxyConfig::from_env(),
+            &None,
+            false,
+        )
+        .await?;
+
+        let mut secrets = HashMap::new();
+        for (name, keys) in group_by_secret(secret_keys, self.json)? {
+            let output = client.get_secret_value().secret_id(&name).send().await?;
+            let value = output
+                .secret_string()
+                .ok_or_else(|| format!("secret '{}' does not hold a string", name))?;
+            for (key, field) in keys {
+                let secret = secret_from_value(&key, value, field.as_deref())?;
+                secrets.insert(key, secret);
+            }
+        }
+        Ok(secrets)
+    }
+}
+
+#[typetag::serde(name = "aws_secrets_manager")]
+impl SecretBackend for AwsSecretsManagerBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: Vec<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let secrets = block_on_retrieval(self.fetch(secret_keys), self.timeout, signal_rx)?;
+        self.retrieved = secrets.clone();
+        Ok(secrets)
+    }
+
+    fn rotation(&mut self) -> Option<BoxStream<'static, signal::SignalTo>> {
+        let backend = self.clone();
+        watch_for_changes(
+            self.refresh_interval_secs,
+            std::mem::take(&mut self.retrieved),
+            move |keys| {
+                let backend = backend.clone();
+                async move { backend.fetch(keys).await }
+            },
+        )
+    }
+}
+
+/// Retrieves secrets from the AWS Systems Manager Parameter Store.
+///
+/// The key of a secret is the name of the parameter holding it, with dots in place of slashes,
+/// such that the key `prod.db.password` retrieves the parameter `/prod/db/password`. Secure
+/// string parameters are decrypted.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct AwsSsmParameterStoreBackend {
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+
+    #[serde(default)]
+    auth: AwsAuthentication,
+
+    /// How often to retrieve the secrets again, reloading the config when they change. Zero
+    /// disables retrieving them again.
+    #[serde(default = "default_refresh_interval_secs")]
+    refresh_interval_secs: u64,
+
+    #[serde(default = "default_timeout_secs")]
+    timeout: u64,
+
+    /// The secrets retrieved last, to compare with when retrieving them again.
+    #[serde(skip)]
+    retrieved: HashMap<String, String>,
+}
+
+/// Gets the name of the parameter holding a secret.
+fn parameter_name(key: &str) -> String {
+    format!("/{}", key.replace('.', "/"))
+}
+
+impl AwsSsmParameterStoreBackend {
+    async fn fetch(&self, secret_keys: Vec<String>) -> crate::Result<HashMap<String, String>> {
+        let client = create_client::<SsmClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &ProxyConfig::from_env(),
+            &None,
+            false,
+        )
+        .await?;
+
+        let mut secrets = HashMap::new();
+        for keys in secret_keys.chunks(MAX_PARAMETERS_PER_REQUEST) {
+            let names = keys.iter().map(|key| parameter_name(key)).collect();
+            let output = client
+                .get_parameters()
+                .set_names(Some(names))
+                .with_decryption(true)
+                .send()
+                .await?;
+            if let Some(invalid) = output
+                .invalid_parameters()
+                .filter(|names| !names.is_empty())
+            {
+                return Err(format!("parameters {:?} were not found", invalid).into());
+            }
+
+            let values = output
+                .parameters()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|parameter| Some((parameter.name()?, parameter.value()?)))
+                .collect::<HashMap<_, _>>();
+            for key in keys {
+                match values.get(parameter_name(key).as_str()) {
+                    Some(value) if !value.is_empty() => {
+                        secrets.insert(key.clone(), (*value).to_owned());
+                    }
+                    Some(_) => return Err(format!("secret for key '{}' was empty", key).into()),
+                    None => {
+                        return Err(format!("secret for key '{}' was not retrieved", key).into())
+                    }
+                }
+            }
+        }
+        Ok(secrets)
+    }
+}
+
+#[typetag::serde(name = "aws_ssm_parameter_store")]
+impl SecretBackend for AwsSsmParameterStoreBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: Vec<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let secrets = block_on_retrieval(self.fetch(secret_keys), self.timeout, signal_rx)?;
+        self.retrieved = secrets.clone();
+        Ok(secrets)
+    }
+
+    fn rotation(&mut self) -> Option<BoxStream<'static, signal::SignalTo>> {
+        let backend = self.clone();
+        watch_for_changes(
+            self.refresh_interval_secs,
+            std::mem::take(&mut self.retrieved),
+            move |keys| {
+                let backend = backend.clone();
+                async move { backend.fetch(keys).await }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_keys_to_parameter_names() {
+        assert_eq!(parameter_name("prod.db.password"), "/prod/db/password");
+        assert_eq!(parameter_name("token"), "/token");
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use futures::stream::BoxStream;
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    block_on_retrieval, default_refresh_interval_secs, default_timeout_secs, group_by_secret,
+    secret_from_value, watch_for_changes, SecretBackend,
+};
+use crate::{
+    config::ProxyConfig,
+    gcp::{GcpAuthConfig, Scope},
+    http::HttpClient,
+    signal,
+};
+
+const SECRET_MANAGER_URL: &str = "https://secretmanager.googleapis.com";
+
+/// Retrieves secrets from GCP Secret Manager.
+///
+/// The key of a secret is the ID of the secret. When `json` is set, secrets hold JSON objects, and
+/// the last segment of a key is the field holding the secret.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct GcpSecretManagerBackend {
+    /// The project holding the secrets.
+    project: String,
+
+    /// The version of the secrets to retrieve.
+    #[serde(default = "default_version")]
+    version: String,
+
+    #[serde(flatten)]
+    auth: GcpAuthConfig,
+
+    /// Whether secrets hold JSON objects whose fields are retrieved separately.
+    #[serde(default)]
+    json: bool,
+
+    /// How often to retrieve the secrets again, reloading the config when they change. Zero
+    /// disables retrieving them again.
+    #[serde(default = "default_refresh_interval_secs")]
+    refresh_interval_secs: u64,
+
+    #[serde(default = "default_timeout_secs")]
+    timeout: u64,
+
+    /// The secrets retrieved last, to compare with when retrieving them again.
+    #[serde(skip)]
+    retrieved: HashMap<String, String>,
+}
+
+fn default_version() -> String {
+    "latest".to_owned()
+}
+
+#[derive(Deserialize, Debug)]
+struct AccessResponse {
+    payload: Payload,
+}
+
+#[derive(Deserialize, Debug)]
+struct Payload {
+    data: String,
+}
+
+impl GcpSecretManagerBackend {
+    async fn fetch(&self, secret_keys: Vec<String>) -> crate::Result<HashMap<String, String>> {
+        let creds = self.auth.make_credentials(Scope::CloudPlatform).await?;
+        let client = HttpClient::new(None, &ProxyConfig::from_env())?;
+
+        let mut secrets = HashMap::new();
+        for (id, keys) in group_by_secret(secret_keys, self.json)? {
+            let mut uri = format!(
+                "{}/v1/projects/{}/secrets/{}/versions/{}:access",
+                SECRET_MANAGER_URL, self.project, id, self.version
+            );
+            if creds.is_none() {
+                if let Some(api_key) = &self.auth.api_key {
+                    uri = format!("{}?key={}", uri, api_key);
+                }
+            }
+            let mut request = http::Request::get(uri).body(Body::empty())?;
+            if let Some(creds) = &creds {
+                creds.apply(&mut request);
+            }
+
+            let response = client.send(request).await?;
+            let status = response.status();
+            let body = hyper::body::to_bytes(response.into_body()).await?;
+            if !status.is_success() {
+                return Err(format!(
+                    "GCP Secret Manager responded with {} for secret '{}': {}",
+                    status,
+                    id,
+                    String::from_utf8_lossy(&body)
+                )
+                .into());
+            }
+            let response: AccessResponse = serde_json::from_slice(&body)?;
+            let value = String::from_utf8(base64::decode(&response.payload.data)?)?;
+
+            for (key, field) in keys {
+                let secret = secret_from_value(&key, &value, field.as_deref())?;
+                secrets.insert(key, secret);
+            }
+        }
+        Ok(secrets)
+    }
+}
+
+#[typetag::serde(name = "gcp_secret_manager")]
+impl SecretBackend for GcpSecretManagerBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: Vec<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let secrets = block_on_retrieval(self.fetch(secret_keys), self.timeout, signal_rx)?;
+        self.retrieved = secrets.clone();
+        Ok(secrets)
+    }
+
+    fn rotation(&mut self) -> Option<BoxStream<'static, signal::SignalTo>> {
+        let backend = self.clone();
+        watch_for_changes(
+            self.refresh_interval_secs,
+            std::mem::take(&mut self.retrieved),
+            move |keys| {
+                let backend = backend.clone();
+                async move { backend.fetch(keys).await }
+            },
+        )
+    }
+}
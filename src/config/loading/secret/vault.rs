@@ -0,0 +1,439 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use async_stream::stream;
+use bytes::Buf;
+use futures::{stream::BoxStream, StreamExt};
+use http::{Method, Request};
+use hyper::Body;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tokio::time::{self, Instant};
+
+use super::{
+    block_on_retrieval, default_timeout_secs, secret_from_field, split_key, SecretBackend,
+};
+use crate::{
+    config::ProxyConfig,
+    http::HttpClient,
+    signal,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Retrieves secrets from HashiCorp Vault.
+///
+/// The key of a secret is the path of the secret under the configured mount, with its segments
+/// separated by dots, followed by the field holding the secret. For example, the key
+/// `database.creds.readonly.password` reads the `password` field of `creds/readonly` in a
+/// `database` mount. All of the fields of a path are read at once, so that credentials issued
+/// together, such as a username and a password, match.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct VaultBackend {
+    /// The address of the Vault server, such as `https://vault.example.com:8200`.
+    address: String,
+
+    /// The token to authenticate with. Defaults to the `VAULT_TOKEN` environment variable.
+    #[serde(default)]
+    token: Option<String>,
+
+    /// The Vault Enterprise namespace to operate in.
+    #[serde(default)]
+    namespace: Option<String>,
+
+    /// The secrets engine mounted at `mount`.
+    #[serde(default)]
+    engine: VaultEngine,
+
+    /// The path the secrets engine is mounted at.
+    #[serde(default = "default_mount")]
+    mount: String,
+
+    #[serde(default = "default_timeout_secs")]
+    timeout: u64,
+
+    #[serde(flatten)]
+    tls: Option<TlsConfig>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
+
+    /// The leases of the secrets retrieved last, to renew while they're in use.
+    #[serde(skip)]
+    leases: Vec<Lease>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+enum VaultEngine {
+    /// Version 2 of the key/value secrets engine.
+    #[derivative(Default)]
+    KvV2,
+
+    /// Any secrets engine issuing credentials on read, such as the database or AWS engines.
+    Dynamic,
+}
+
+fn default_mount() -> String {
+    "secret".to_owned()
+}
+
+#[derive(Debug, Clone)]
+struct Lease {
+    id: String,
+    duration: Duration,
+}
+
+#[derive(Deserialize, Debug)]
+struct SecretResponse {
+    #[serde(default)]
+    lease_id: String,
+    #[serde(default)]
+    lease_duration: u64,
+    #[serde(default)]
+    renewable: bool,
+    data: Option<BTreeMap<String, Value>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenLookupResponse {
+    data: TokenLookup,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenLookup {
+    #[serde(default)]
+    ttl: u64,
+    #[serde(default)]
+    renewable: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenRenewResponse {
+    auth: TokenRenewal,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenRenewal {
+    lease_duration: u64,
+}
+
+/// A client for the Vault HTTP API.
+#[derive(Clone)]
+struct VaultClient {
+    client: HttpClient,
+    address: String,
+    token: String,
+    namespace: Option<String>,
+}
+
+impl VaultClient {
+    fn new(backend: &VaultBackend) -> crate::Result<Self> {
+        let token = match &backend.token {
+            Some(token) => token.clone(),
+            None => std::env::var("VAULT_TOKEN")
+                .map_err(|_| "no `token` is set and `VAULT_TOKEN` is not set either")?,
+        };
+        let tls = TlsSettings::from_options(&backend.tls)?;
+        let proxy = ProxyConfig::from_env().merge(&backend.proxy);
+        Ok(Self {
+            client: HttpClient::new(tls, &proxy)?,
+            address: backend.address.trim_end_matches('/').to_owned(),
+            token,
+            namespace: backend.namespace.clone(),
+        })
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> crate::Result<T> {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(format!("{}/v1/{}", self.address, path))
+            .header("X-Vault-Token", &self.token);
+        if let Some(namespace) = &self.namespace {
+            builder = builder.header("X-Vault-Namespace", namespace);
+        }
+        let body = match body {
+            Some(body) => Body::from(serde_json::to_vec(&body)?),
+            None => Body::empty(),
+        };
+
+        let response = self.client.send(builder.body(body)?).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if !status.is_success() {
+            return Err(format!(
+                "Vault responded with {} for {:?}: {}",
+                status,
+                path,
+                String::from_utf8_lossy(&body)
+            )
+            .into());
+        }
+        Ok(serde_json::from_reader(body.reader())?)
+    }
+
+    async fn read(&self, path: &str) -> crate::Result<SecretResponse> {
+        self.request(Method::GET, path, None).await
+    }
+
+    async fn lookup_token(&self) -> crate::Result<TokenLookup> {
+        let response: TokenLookupResponse = self
+            .request(Method::GET, "auth/token/lookup-self", None)
+            .await?;
+        Ok(response.data)
+    }
+
+    /// Renews the token, returning its new time to live.
+    async fn renew_token(&self) -> crate::Result<Duration> {
+        let response: TokenRenewResponse = self
+            .request(Method::POST, "auth/token/renew-self", Some(Value::Null))
+            .await?;
+        Ok(Duration::from_secs(response.auth.lease_duration))
+    }
+
+    /// Renews a lease by its original duration, returning the duration it was renewed by, which
+    /// is shorter once the lease reaches its maximum time to live.
+    async fn renew_lease(&self, lease: &Lease) -> crate::Result<Duration> {
+        let response: SecretResponse = self
+            .request(
+                Method::PUT,
+                "sys/leases/renew",
+                Some(serde_json::json!({
+                    "lease_id": lease.id,
+                    "increment": lease.duration.as_secs(),
+                })),
+            )
+            .await?;
+        Ok(Duration::from_secs(response.lease_duration))
+    }
+}
+
+impl VaultBackend {
+    /// Gets the API path to read a secret path from.
+    fn api_path(&self, path: &str) -> String {
+        let mount = self.mount.trim_matches('/');
+        match self.engine {
+            VaultEngine::KvV2 => format!("{}/data/{}", mount, path),
+            VaultEngine::Dynamic => format!("{}/{}", mount, path),
+        }
+    }
+
+    /// Extracts the fields of a secret from a response, which key/value secrets nest one level
+    /// deeper.
+    fn fields(&self, response: SecretResponse) -> BTreeMap<String, Value> {
+        let data = response.data.unwrap_or_default();
+        match self.engine {
+            VaultEngine::KvV2 => match data.get("data") {
+                Some(Value::Object(fields)) => fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+                _ => BTreeMap::new(),
+            },
+            VaultEngine::Dynamic => data,
+        }
+    }
+
+    async fn retrieve_from(
+        &mut self,
+        client: &VaultClient,
+        secret_keys: Vec<String>,
+    ) -> crate::Result<HashMap<String, String>> {
+        let mut paths = BTreeMap::<String, Vec<(String, String)>>::new();
+        for key in secret_keys {
+            let (path, field) = split_key(&key).ok_or_else(|| {
+                format!("secret key '{}' has no field, such as `path.field`", key)
+            })?;
+            paths.entry(path).or_default().push((key, field));
+        }
+
+        let mut secrets = HashMap::new();
+        self.leases.clear();
+        for (path, keys) in paths {
+            let response = client.read(&self.api_path(&path)).await?;
+            if response.renewable && !response.lease_id.is_empty() {
+                self.leases.push(Lease {
+                    id: response.lease_id.clone(),
+                    duration: Duration::from_secs(response.lease_duration),
+                });
+            }
+
+            let fields = self.fields(response);
+            for (key, field) in keys {
+                let secret = secret_from_field(&key, fields.get(&field))?;
+                secrets.insert(key, secret);
+            }
+        }
+        Ok(secrets)
+    }
+}
+
+/// Gets when to renew something that expires after the given time to live, leaving a third of it
+/// as a margin for the renewal.
+fn renew_at(now: Instant, ttl: Duration) -> Instant {
+    now + ttl * 2 / 3
+}
+
+/// Keeps the token and the leases of the retrieved secrets alive, signaling to reload the config
+/// once a lease can't be renewed any longer, so that sinks get new credentials before the old ones
+/// expire.
+fn renewals(
+    client: VaultClient,
+    token_ttl: Option<Duration>,
+    leases: Vec<Lease>,
+) -> impl futures::Stream<Item = signal::SignalTo> {
+    stream! {
+        let now = Instant::now();
+        let mut token_renewal = token_ttl.map(|ttl| renew_at(now, ttl));
+        let mut lease_renewal = leases
+            .iter()
+            .map(|lease| lease.duration)
+            .min()
+            .map(|ttl| renew_at(now, ttl));
+
+        loop {
+            let next = match (token_renewal, lease_renewal) {
+                (Some(token), Some(lease)) => token.min(lease),
+                (Some(next), None) | (None, Some(next)) => next,
+                (None, None) => break,
+            };
+            time::sleep_until(next).await;
+            let now = Instant::now();
+
+            if token_renewal.map_or(false, |at| at <= now) {
+                token_renewal = match client.renew_token().await {
+                    Ok(ttl) => {
+                        debug!(message = "Renewed Vault token.", ttl_secs = ttl.as_secs());
+                        Some(renew_at(now, ttl))
+                    }
+                    Err(error) => {
+                        error!(message = "Failed to renew Vault token.", %error);
+                        None
+                    }
+                };
+            }
+
+            if lease_renewal.map_or(false, |at| at <= now) {
+                let mut expiring = false;
+                let mut ttl = None::<Duration>;
+                for lease in &leases {
+                    match client.renew_lease(lease).await {
+                        Ok(renewed) => {
+                            // A lease renewed by less than asked for has reached its maximum
+                            // time to live, and must be replaced before it runs out.
+                            expiring |= renewed < lease.duration;
+                            ttl = Some(ttl.map_or(renewed, |ttl| ttl.min(renewed)));
+                        }
+                        Err(error) => {
+                            warn!(message = "Failed to renew Vault lease.", lease_id = %lease.id, %error);
+                            expiring = true;
+                        }
+                    }
+                }
+
+                if expiring {
+                    info!(message = "Vault leases are expiring, reloading configuration to rotate secrets.");
+                    yield signal::SignalTo::ReloadFromDisk;
+                    break;
+                }
+                lease_renewal = ttl.map(|ttl| renew_at(now, ttl));
+            }
+        }
+
+        // The stream ending is reported as an error, so wait for the next reload to drop it.
+        futures::future::pending::<()>().await;
+    }
+}
+
+#[typetag::serde(name = "vault")]
+impl SecretBackend for VaultBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: Vec<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let client = VaultClient::new(self)?;
+        let timeout = self.timeout;
+        block_on_retrieval(self.retrieve_from(&client, secret_keys), timeout, signal_rx)
+    }
+
+    fn rotation(&mut self) -> Option<BoxStream<'static, signal::SignalTo>> {
+        let client = match VaultClient::new(self) {
+            Ok(client) => client,
+            Err(error) => {
+                error!(message = "Failed to create Vault client for renewals.", %error);
+                return None;
+            }
+        };
+        let leases = std::mem::take(&mut self.leases);
+        Some(
+            stream! {
+                let token_ttl = match client.lookup_token().await {
+                    Ok(token) if token.renewable && token.ttl > 0 => {
+                        Some(Duration::from_secs(token.ttl))
+                    }
+                    Ok(_) => None,
+                    Err(error) => {
+                        warn!(message = "Failed to look up Vault token.", %error);
+                        None
+                    }
+                };
+                let renewals = renewals(client, token_ttl, leases);
+                tokio::pin!(renewals);
+                while let Some(signal) = renewals.next().await {
+                    yield signal;
+                }
+            }
+            .boxed(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(engine: VaultEngine) -> VaultBackend {
+        toml::from_str::<VaultBackend>(r#"address = "http://127.0.0.1:8200""#)
+            .map(|backend| VaultBackend { engine, ..backend })
+            .unwrap()
+    }
+
+    #[test]
+    fn reads_fields_per_engine() {
+        let kv = backend(VaultEngine::KvV2);
+        assert_eq!(kv.api_path("app"), "secret/data/app");
+        let response: SecretResponse = serde_json::from_str(
+            r#"{"data": {"data": {"token": "abc"}, "metadata": {"version": 3}}}"#,
+        )
+        .unwrap();
+        assert_eq!(kv.fields(response)["token"], "abc");
+
+        let dynamic = VaultBackend {
+            mount: "database/".to_owned(),
+            ..backend(VaultEngine::Dynamic)
+        };
+        assert_eq!(
+            dynamic.api_path("creds/readonly"),
+            "database/creds/readonly"
+        );
+        let response: SecretResponse = serde_json::from_str(
+            r#"{"lease_id": "database/creds/readonly/x", "lease_duration": 3600, "renewable": true,
+                "data": {"username": "v-user", "password": "hunter2"}}"#,
+        )
+        .unwrap();
+        let fields = dynamic.fields(response);
+        assert_eq!(fields["username"], "v-user");
+        assert_eq!(fields["password"], "hunter2");
+    }
+}
@@ -0,0 +1,365 @@
+//! Support for templates of components that are instantiated several times.
+//!
+//! A template under the top-level `templates` key declares sources, transforms and sinks once,
+//! along with defaults for its `params` and the `instances` to create, each giving its own values
+//! to the parameters. Every instance gets a copy of the components of the template, with:
+//!
+//! * IDs of the form `<template>-<instance>-<component>`, so that the template and instance a
+//!   component comes from can be told from its ID.
+//! * Every `%{param}` in a string replaced by the value of the parameter. A string holding nothing
+//!   but the reference takes the value as is, keeping its type.
+//! * Inputs naming components of the template pointing to the copies of the same instance, while
+//!   other inputs are left untouched, so that templates can consume components declared outside.
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use toml::value::{Table, Value};
+
+/// The key holding the templates of a config file.
+const TEMPLATES_KEY: &str = "templates";
+
+/// The kinds of components a template can hold.
+const COMPONENT_KINDS: [&str; 3] = ["sources", "transforms", "sinks"];
+
+static PARAM: Lazy<Regex> = Lazy::new(|| Regex::new(r"%\{\s*([[:word:]]+)\s*\}").unwrap());
+
+/// Takes the templates out of the table of a config file, adding the components of their
+/// instances to it in their place.
+pub(super) fn expand(table: &mut Table) -> Result<(), Vec<String>> {
+    let templates = match table.remove(TEMPLATES_KEY) {
+        None => return Ok(()),
+        Some(Value::Table(templates)) => templates,
+        Some(_) => {
+            return Err(vec![format!(
+                "`{}` must be a table of templates.",
+                TEMPLATES_KEY
+            )])
+        }
+    };
+
+    let mut errors = Vec::new();
+    for (name, template) in templates {
+        let template = match Template::parse(&name, template) {
+            Ok(template) => template,
+            Err(error) => {
+                errors.push(error);
+                continue;
+            }
+        };
+        for (instance, params) in &template.instances {
+            if let Err(errs) = template.instantiate(instance, params, table) {
+                errors.extend(errs);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+struct Template {
+    name: String,
+    params: Table,
+    components: Vec<(&'static str, Table)>,
+    instances: Table,
+}
+
+impl Template {
+    fn parse(name: &str, template: Value) -> Result<Self, String> {
+        let mut template = match template {
+            Value::Table(template) => template,
+            _ => return Err(format!("Template \"{}\" must be a table.", name)),
+        };
+
+        let mut take_table = |key: &str| match template.remove(key) {
+            None => Ok(Table::new()),
+            Some(Value::Table(table)) => Ok(table),
+            Some(_) => Err(format!(
+                "`{}` of template \"{}\" must be a table.",
+                key, name
+            )),
+        };
+        let params = take_table("params")?;
+        let instances = take_table("instances")?;
+        let components = COMPONENT_KINDS
+            .iter()
+            .map(|kind| Ok((*kind, take_table(kind)?)))
+            .collect::<Result<_, String>>()?;
+
+        if let Some(key) = template.keys().next() {
+            return Err(format!(
+                "Template \"{}\" has unknown option `{}`.",
+                name, key
+            ));
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            params,
+            components,
+            instances,
+        })
+    }
+
+    /// Adds the components of an instance of the template to the table of a config file.
+    fn instantiate(
+        &self,
+        instance: &str,
+        params: &Value,
+        table: &mut Table,
+    ) -> Result<(), Vec<String>> {
+        let mut values = self.params.clone();
+        match params {
+            Value::Table(params) => {
+                for (param, value) in params {
+                    values.insert(param.clone(), value.clone());
+                }
+            }
+            _ => {
+                return Err(vec![format!(
+                    "Instance \"{}\" of template \"{}\" must be a table of parameters.",
+                    instance, self.name
+                )])
+            }
+        }
+
+        let mut errors = Vec::new();
+        for (kind, components) in &self.components {
+            for (id, component) in components {
+                let mut component = match substitute(component.clone(), &values) {
+                    Ok(component) => component,
+                    Err(param) => {
+                        errors.push(format!(
+                            "Component \"{}\" of template \"{}\" uses parameter \"{}\", which instance \"{}\" doesn't set.",
+                            id, self.name, param, instance
+                        ));
+                        continue;
+                    }
+                };
+                if let Some(Value::Array(inputs)) = component.get_mut("inputs") {
+                    for input in inputs {
+                        if let Value::String(input) = input {
+                            *input = self.input_id(instance, input);
+                        }
+                    }
+                }
+
+                let id = self.component_id(instance, id);
+                let components = table.entry(*kind).or_insert(Value::Table(Table::new()));
+                match components {
+                    Value::Table(components) if components.contains_key(&id) => {
+                        errors.push(format!(
+                            "Component \"{}\" of template \"{}\" conflicts with an existing component.",
+                            id, self.name
+                        ));
+                    }
+                    Value::Table(components) => {
+                        components.insert(id, component);
+                    }
+                    _ => errors.push(format!("`{}` must be a table of components.", kind)),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn component_id(&self, instance: &str, id: &str) -> String {
+        format!("{}-{}-{}", self.name, instance, id)
+    }
+
+    /// Points an input at the copy of the same instance if it names a component of the template,
+    /// keeping the output it names, if any.
+    fn input_id(&self, instance: &str, input: &str) -> String {
+        let (id, port) = match input.split_once('.') {
+            Some((id, port)) => (id, Some(port)),
+            None => (input, None),
+        };
+        if !self
+            .components
+            .iter()
+            .any(|(_, components)| components.contains_key(id))
+        {
+            return input.to_owned();
+        }
+
+        let id = self.component_id(instance, id);
+        match port {
+            Some(port) => format!("{}.{}", id, port),
+            None => id,
+        }
+    }
+}
+
+/// Replaces the references to parameters in a value, returning the name of the first parameter
+/// without a value, if any.
+fn substitute(value: Value, params: &Table) -> Result<Value, String> {
+    match value {
+        Value::String(string) => {
+            if let Some(captures) = PARAM.captures(&string) {
+                if captures[0].len() == string.len() {
+                    let param = &captures[1];
+                    return params.get(param).cloned().ok_or_else(|| param.to_owned());
+                }
+            }
+
+            let mut missing = None;
+            let string = PARAM.replace_all(&string, |captures: &Captures<'_>| {
+                match params.get(&captures[1]) {
+                    Some(Value::String(value)) => value.clone(),
+                    Some(value) => value.to_string(),
+                    None => {
+                        missing.get_or_insert_with(|| captures[1].to_owned());
+                        String::new()
+                    }
+                }
+            });
+            match missing {
+                Some(param) => Err(param),
+                None => Ok(Value::String(string.into_owned())),
+            }
+        }
+        Value::Array(values) => values
+            .into_iter()
+            .map(|value| substitute(value, params))
+            .collect::<Result<_, _>>()
+            .map(Value::Array),
+        Value::Table(table) => table
+            .into_iter()
+            .map(|(key, value)| Ok((key, substitute(value, params)?)))
+            .collect::<Result<_, _>>()
+            .map(Value::Table),
+        value => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(input: &str) -> Table {
+        toml::from_str(input).unwrap()
+    }
+
+    #[test]
+    fn expands_instances_of_templates() {
+        let mut config = table(
+            r#"
+            [sinks.archive]
+            type = "aws_s3"
+            inputs = ["tenant-*-parse"]
+
+            [templates.tenant.params]
+            port = 8080
+
+            [templates.tenant.sources.in]
+            type = "http"
+            address = "0.0.0.0:%{port}"
+
+            [templates.tenant.transforms.parse]
+            type = "remap"
+            inputs = ["in", "enrich.dropped"]
+            source = '.tenant = "%{name}"'
+
+            [templates.tenant.sinks.out]
+            type = "loki"
+            inputs = ["parse"]
+            batch.max_events = "%{batch}"
+
+            [templates.tenant.instances.acme]
+            name = "acme"
+            batch = 100
+
+            [templates.tenant.instances.globex]
+            name = "globex"
+            port = 9000
+            batch = 500
+            "#,
+        );
+
+        expand(&mut config).unwrap();
+        assert_eq!(
+            config,
+            table(
+                r#"
+                [sinks.archive]
+                type = "aws_s3"
+                inputs = ["tenant-*-parse"]
+
+                [sources.tenant-acme-in]
+                type = "http"
+                address = "0.0.0.0:8080"
+
+                [sources.tenant-globex-in]
+                type = "http"
+                address = "0.0.0.0:9000"
+
+                [transforms.tenant-acme-parse]
+                type = "remap"
+                inputs = ["tenant-acme-in", "enrich.dropped"]
+                source = '.tenant = "acme"'
+
+                [transforms.tenant-globex-parse]
+                type = "remap"
+                inputs = ["tenant-globex-in", "enrich.dropped"]
+                source = '.tenant = "globex"'
+
+                [sinks.tenant-acme-out]
+                type = "loki"
+                inputs = ["tenant-acme-parse"]
+                batch.max_events = 100
+
+                [sinks.tenant-globex-out]
+                type = "loki"
+                inputs = ["tenant-globex-parse"]
+                batch.max_events = 500
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_missing_parameters() {
+        let mut config = table(
+            r#"
+            [templates.tenant.sources.in]
+            type = "http"
+            address = "0.0.0.0:%{port}"
+
+            [templates.tenant.instances.acme]
+            "#,
+        );
+
+        assert_eq!(
+            expand(&mut config).unwrap_err(),
+            vec!["Component \"in\" of template \"tenant\" uses parameter \"port\", which instance \"acme\" doesn't set."]
+        );
+    }
+
+    #[test]
+    fn rejects_conflicting_components() {
+        let mut config = table(
+            r#"
+            [sources.tenant-acme-in]
+            type = "stdin"
+
+            [templates.tenant.sources.in]
+            type = "stdin"
+
+            [templates.tenant.instances.acme]
+            "#,
+        );
+
+        assert_eq!(
+            expand(&mut config).unwrap_err(),
+            vec!["Component \"tenant-acme-in\" of template \"tenant\" conflicts with an existing component."]
+        );
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     hash::Hash,
     net::SocketAddr,
     path::PathBuf,
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -26,8 +27,10 @@ pub mod enterprise;
 pub mod format;
 mod graph;
 mod id;
+mod lint;
 mod loading;
 pub mod provider;
+pub mod runtime_options;
 mod schema;
 mod sink;
 mod source;
@@ -39,18 +42,22 @@ pub mod watcher;
 
 pub use builder::ConfigBuilder;
 pub use cmd::{cmd, Opts};
-pub use diff::ConfigDiff;
+pub use diff::{ComponentChanges, ConfigDiff};
 pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, OutputId};
 pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
-    load_from_str, load_source_from_paths, merge_path_lists, process_paths, SecretBackend,
-    CONFIG_PATHS,
+    load_from_str, load_source_from_paths, merge_path_lists, process_paths, set_profile,
+    SecretBackend, CONFIG_PATHS,
+};
+pub use schema::LogNamespace;
+pub use sink::{
+    SinkConfig, SinkContext, SinkDeadLetterOptions, SinkDescription, SinkFallbackOptions,
+    SinkHealthcheckOptions, SinkOuter, SinkPriorityOptions, DEAD_LETTER_OUTPUT, FALLBACK_OUTPUT,
 };
-pub use sink::{SinkConfig, SinkContext, SinkDescription, SinkHealthcheckOptions, SinkOuter};
 pub use source::{SourceConfig, SourceContext, SourceDescription, SourceOuter};
 pub use transform::{TransformDescription, TransformOuter};
-pub use unit_test::{build_unit_tests, build_unit_tests_main, UnitTestResult};
+pub use unit_test::{build_unit_tests, build_unit_tests_main, GoldenMode, UnitTestResult};
 pub use validation::warnings;
 pub use vector_core::config::{log_schema, proxy::ProxyConfig, LogSchema};
 
@@ -106,6 +113,8 @@ pub struct Config {
     sinks: IndexMap<ComponentKey, SinkOuter<OutputId>>,
     transforms: IndexMap<ComponentKey, TransformOuter<OutputId>>,
     pub enrichment_tables: IndexMap<ComponentKey, EnrichmentTableOuter>,
+    pub conditions: IndexMap<String, conditions::AnyCondition>,
+    pub vrl_library: IndexMap<String, String>,
     tests: Vec<TestDefinition>,
     expansions: IndexMap<ComponentKey, Vec<ComponentKey>>,
     secret: IndexMap<ComponentKey, Box<dyn SecretBackend>>,
@@ -172,29 +181,39 @@ impl Config {
         let inputs: Vec<_> = self
             .sinks
             .iter()
-            .filter(|(_, sink)| {
-                sink.inner
+            .filter_map(|(name, sink)| {
+                let acknowledgements = sink
+                    .inner
                     .acknowledgements()
                     .unwrap_or(&self.global.acknowledgements)
-                    .merge_default(&self.global.acknowledgements)
+                    .merge_default(&self.global.acknowledgements);
+                acknowledgements
                     .enabled()
+                    .then(|| (name, sink, acknowledgements.timeout()))
             })
-            .flat_map(|(name, sink)| {
+            .flat_map(|(name, sink, timeout)| {
                 sink.inputs
                     .iter()
-                    .map(|input| (name.clone(), input.clone()))
+                    .map(move |input| (name.clone(), input.clone(), timeout))
             })
             .collect();
         self.propagate_acks_rec(inputs);
         Ok(())
     }
 
-    fn propagate_acks_rec(&mut self, sink_inputs: Vec<(ComponentKey, OutputId)>) {
-        for (sink, input) in sink_inputs {
+    fn propagate_acks_rec(&mut self, sink_inputs: Vec<(ComponentKey, OutputId, Option<Duration>)>) {
+        for (sink, input, timeout) in sink_inputs {
             let component = &input.component;
             if let Some(source) = self.sources.get_mut(component) {
                 if source.inner.can_acknowledge() {
                     source.sink_acknowledgements = true;
+                    // A source feeding several sinks has to settle its events by the earliest of
+                    // their deadlines.
+                    source.sink_acknowledgements_timeout =
+                        match (source.sink_acknowledgements_timeout, timeout) {
+                            (Some(current), Some(timeout)) => Some(current.min(timeout)),
+                            (current, timeout) => current.or(timeout),
+                        };
                 } else {
                     warn!(
                         message = "Source has acknowledgements enabled by a sink, but acknowledgements are not supported by this source. Silent data loss could occur.",
@@ -206,7 +225,7 @@ impl Config {
                 let inputs = transform
                     .inputs
                     .iter()
-                    .map(|input| (sink.clone(), input.clone()))
+                    .map(|input| (sink.clone(), input.clone(), timeout))
                     .collect();
                 self.propagate_acks_rec(inputs);
             }
@@ -380,6 +399,8 @@ pub struct TestDefinition<T = OutputId> {
     pub outputs: Vec<TestOutput<T>>,
     #[serde(default)]
     pub no_outputs_from: Vec<T>,
+    #[serde(default)]
+    pub internal_metrics: Vec<TestInternalMetric>,
 }
 
 impl TestDefinition<String> {
@@ -394,6 +415,7 @@ impl TestDefinition<String> {
             inputs,
             outputs,
             no_outputs_from,
+            internal_metrics,
         } = self;
         let mut errors = Vec::new();
 
@@ -405,6 +427,8 @@ impl TestDefinition<String> {
                 let TestOutput {
                     extract_from,
                     conditions,
+                    golden,
+                    ignore_fields,
                 } = old;
 
                 let extract_from = extract_from
@@ -419,9 +443,9 @@ impl TestDefinition<String> {
                     })
                     .collect::<Vec<_>>();
 
-                (extract_from, conditions)
+                (extract_from, conditions, golden, ignore_fields)
             })
-            .filter_map(|(extract_from, conditions)| {
+            .filter_map(|(extract_from, conditions, golden, ignore_fields)| {
                 let mut outputs = Vec::new();
                 for from in extract_from {
                     if let Some(output_id) = output_map.get(&from) {
@@ -439,6 +463,8 @@ impl TestDefinition<String> {
                     Some(TestOutput {
                         extract_from: outputs.into(),
                         conditions,
+                        golden,
+                        ignore_fields,
                     })
                 }
             })
@@ -466,6 +492,7 @@ impl TestDefinition<String> {
                 inputs,
                 outputs,
                 no_outputs_from,
+                internal_metrics,
             })
         } else {
             Err(errors)
@@ -481,6 +508,7 @@ impl TestDefinition<OutputId> {
             inputs,
             outputs,
             no_outputs_from,
+            internal_metrics,
         } = self;
 
         let outputs = outputs
@@ -495,6 +523,8 @@ impl TestDefinition<OutputId> {
                         .into(),
                 },
                 conditions: old.conditions,
+                golden: old.golden,
+                ignore_fields: old.ignore_fields,
             })
             .collect();
 
@@ -506,6 +536,7 @@ impl TestDefinition<OutputId> {
             inputs,
             outputs,
             no_outputs_from,
+            internal_metrics,
         }
     }
 }
@@ -528,6 +559,9 @@ pub struct TestInput {
     pub value: Option<String>,
     pub log_fields: Option<IndexMap<String, TestInputValue>>,
     pub metric: Option<Metric>,
+    /// A file of events to insert, one per line, as raw messages or as JSON logs or metrics
+    /// depending on the type of the input.
+    pub file: Option<PathBuf>,
 }
 
 fn default_test_input_type() -> String {
@@ -539,6 +573,28 @@ fn default_test_input_type() -> String {
 pub struct TestOutput<T = OutputId> {
     pub extract_from: OneOrMany<T>,
     pub conditions: Option<Vec<conditions::AnyCondition>>,
+    /// A file of the expected events, one JSON event per line.
+    pub golden: Option<PathBuf>,
+    /// Fields of logs left out of the comparison with the golden file, such as timestamps.
+    #[serde(default)]
+    pub ignore_fields: Vec<String>,
+}
+
+/// An assertion on the internal counters incremented while a test runs.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TestInternalMetric {
+    /// The name of the counter, such as `component_discarded_events_total`.
+    pub name: String,
+    /// The component whose counter it is, if not summed over every component.
+    pub component_id: Option<ComponentKey>,
+    /// Tags the counter must have, in addition to the component.
+    #[serde(default)]
+    pub tags: IndexMap<String, String>,
+    /// The exact amount the counter must be incremented by.
+    pub value: Option<f64>,
+    /// The least amount the counter must be incremented by.
+    pub at_least: Option<f64>,
 }
 
 #[cfg(all(
@@ -552,7 +608,9 @@ mod tests {
 
     use indoc::indoc;
 
-    use super::{builder::ConfigBuilder, format, load_from_str, ComponentKey, Format};
+    use super::{
+        builder::ConfigBuilder, format, load_from_str, warnings, ComponentKey, Format, LogNamespace,
+    };
 
     #[test]
     fn default_data_dir() {
@@ -632,6 +690,38 @@ mod tests {
         assert_eq!("then", config.global.log_schema.timestamp_key().to_string());
     }
 
+    #[test]
+    fn log_namespace_policy() {
+        let config = load_from_str(
+            indoc! {r#"
+                [schema]
+                  log_namespace = "vector"
+
+                [sources.in]
+                  type = "file"
+                  include = ["/var/log/messages"]
+                  log_namespace = "legacy"
+
+                [sinks.out]
+                  type = "console"
+                  inputs = ["in"]
+                  encoding = "json"
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        assert_eq!(config.schema.log_namespace, LogNamespace::Vector);
+        assert_eq!(
+            config.sources[&ComponentKey::from("in")].log_namespace,
+            Some(LogNamespace::Legacy)
+        );
+        assert!(warnings(&config).contains(
+            &"Source \"in\" still uses the legacy log namespace while the policy is \"vector\""
+                .to_owned()
+        ));
+    }
+
     #[test]
     fn config_append() {
         let mut config: ConfigBuilder = format::deserialize(
@@ -735,6 +825,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn config_append_conditions() {
+        let mut config: ConfigBuilder = format::deserialize(
+            indoc! {r#"
+                [conditions]
+                  server_errors = ".status >= 500"
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.append(
+                format::deserialize(
+                    indoc! {r#"
+                        [conditions.debug_logs]
+                          type = "vrl"
+                          source = '.level == "debug"'
+                    "#},
+                    Format::Toml,
+                )
+                .unwrap()
+            ),
+            Ok(())
+        );
+        assert!(config.conditions.contains_key("server_errors"));
+        assert!(config.conditions.contains_key("debug_logs"));
+
+        assert_eq!(
+            config.append(
+                format::deserialize(
+                    indoc! {r#"
+                        [conditions]
+                          server_errors = ".status == 500"
+                    "#},
+                    Format::Toml,
+                )
+                .unwrap()
+            ),
+            Err(vec!["duplicate condition name found: server_errors".into()])
+        );
+    }
+
+    #[test]
+    fn config_append_vrl_library() {
+        let mut config: ConfigBuilder = format::deserialize(
+            indoc! {r#"
+                [vrl_library]
+                  parse_message = ". = parse_json!(.message)"
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.append(
+                format::deserialize(
+                    indoc! {r#"
+                        [vrl_library]
+                          is_error = '.level == "error"'
+                    "#},
+                    Format::Toml,
+                )
+                .unwrap()
+            ),
+            Ok(())
+        );
+        assert!(config.vrl_library.contains_key("parse_message"));
+        assert!(config.vrl_library.contains_key("is_error"));
+
+        assert_eq!(
+            config.append(
+                format::deserialize(
+                    indoc! {r#"
+                        [vrl_library]
+                          is_error = '.level == "err"'
+                    "#},
+                    Format::Toml,
+                )
+                .unwrap()
+            ),
+            Err(vec!["duplicate vrl_library name found: is_error".into()])
+        );
+    }
+
     #[test]
     fn with_proxy() {
         let config: ConfigBuilder = format::deserialize(
@@ -990,6 +1165,122 @@ mod acknowledgements_tests {
         assert!(get("in2").sink_acknowledgements);
         assert!(get("in3").sink_acknowledgements);
     }
+
+    #[test]
+    fn propagates_earliest_timeout() {
+        // The topology:
+        // in1 => out1 (acks enabled, 30s timeout)
+        //     => parse1 => out2 (acks enabled, 10s timeout)
+        // in2 => out3 (acks enabled)
+        let config: ConfigBuilder = format::deserialize(
+            indoc! {r#"
+                data_dir = "/tmp"
+                [sources.in1]
+                    type = "file"
+                [sources.in2]
+                    type = "file"
+                [transforms.parse1]
+                    type = "json_parser"
+                    inputs = ["in1"]
+                [sinks.out1]
+                    type = "file"
+                    inputs = ["in1"]
+                    encoding = "text"
+                    path = "/path/to/out1"
+                    acknowledgements.enabled = true
+                    acknowledgements.timeout_secs = 30
+                [sinks.out2]
+                    type = "file"
+                    inputs = ["parse1"]
+                    encoding = "text"
+                    path = "/path/to/out2"
+                    acknowledgements.enabled = true
+                    acknowledgements.timeout_secs = 10
+                [sinks.out3]
+                    type = "file"
+                    inputs = ["in2"]
+                    encoding = "text"
+                    path = "/path/to/out3"
+                    acknowledgements = true
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        let config = config.build().unwrap();
+
+        let get = |key: &str| config.sources.get(&ComponentKey::from(key)).unwrap();
+        assert_eq!(
+            get("in1").sink_acknowledgements_timeout,
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(get("in2").sink_acknowledgements_timeout, None);
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "sources-kafka",
+    feature = "sinks-kafka",
+    feature = "transforms-json_parser"
+))]
+mod transactions_tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn build(out2_transactional_id: Option<&str>) -> Result<Config, Vec<String>> {
+        // The topology:
+        // in (exactly-once) => out1 (transactional)
+        //                   => parse => out2
+        let mut config = indoc! {r#"
+            [sources.in]
+                type = "kafka"
+                bootstrap_servers = "localhost:9092"
+                group_id = "vector"
+                topics = ["in"]
+                exactly_once = true
+            [transforms.parse]
+                type = "json_parser"
+                inputs = ["in"]
+            [sinks.out1]
+                type = "kafka"
+                inputs = ["in"]
+                bootstrap_servers = "localhost:9092"
+                topic = "out1"
+                encoding.codec = "json"
+                transactional_id = "vector-out1"
+            [sinks.out2]
+                type = "kafka"
+                inputs = ["parse"]
+                bootstrap_servers = "localhost:9092"
+                topic = "out2"
+                encoding.codec = "json"
+        "#}
+        .to_owned();
+        if let Some(transactional_id) = out2_transactional_id {
+            config.push_str(&format!("    transactional_id = {:?}\n", transactional_id));
+        }
+
+        let config: ConfigBuilder = format::deserialize(&config, Format::Toml).unwrap();
+        config.build()
+    }
+
+    #[test]
+    fn rejects_exactly_once_source_feeding_non_transactional_sink() {
+        assert_eq!(
+            build(None).unwrap_err(),
+            vec![
+                "Source in can only feed transactional sinks in exactly-once mode, but feeds sink out2"
+                    .to_owned()
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_exactly_once_source_feeding_transactional_sinks() {
+        assert!(build(Some("vector-out2")).is_ok());
+    }
 }
 
 #[cfg(all(test, feature = "sources-stdin", feature = "sinks-console"))]
@@ -0,0 +1,120 @@
+//! Options of running components that can be changed without reloading the topology.
+//!
+//! Only a safelisted set of options can be changed this way. Components register the ones they
+//! support when they're built, getting a receiver of the values the options are set to, which the
+//! API sets through `set`.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use once_cell::sync::Lazy;
+use tokio::sync::watch;
+
+use super::ComponentKey;
+
+static OPTIONS: Lazy<Mutex<HashMap<(ComponentKey, RuntimeOption), watch::Sender<u64>>>> =
+    Lazy::new(Default::default);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RuntimeOption {
+    /// The `rate` of the `sample` transform.
+    SampleRate,
+
+    /// The `threshold` of the `throttle` transform.
+    ThrottleThreshold,
+}
+
+impl RuntimeOption {
+    /// The name of the option in the configuration of the component.
+    pub const fn name(self) -> &'static str {
+        match self {
+            RuntimeOption::SampleRate => "rate",
+            RuntimeOption::ThrottleThreshold => "threshold",
+        }
+    }
+
+    const fn max(self) -> u64 {
+        match self {
+            RuntimeOption::SampleRate => u64::MAX,
+            RuntimeOption::ThrottleThreshold => u32::MAX as u64,
+        }
+    }
+}
+
+/// Registers an option of a component as changeable, replacing any earlier registration of the
+/// same option, such as by the component before a reload.
+pub fn register(key: &ComponentKey, option: RuntimeOption, value: u64) -> watch::Receiver<u64> {
+    let (sender, receiver) = watch::channel(value);
+    OPTIONS
+        .lock()
+        .expect("runtime options lock poisoned")
+        .insert((key.clone(), option), sender);
+    receiver
+}
+
+/// Changes an option of a running component.
+pub fn set(key: &ComponentKey, option: RuntimeOption, value: u64) -> Result<(), String> {
+    if value == 0 || value > option.max() {
+        return Err(format!(
+            "`{}` must be between 1 and {}.",
+            option.name(),
+            option.max()
+        ));
+    }
+
+    let mut options = OPTIONS.lock().expect("runtime options lock poisoned");
+    let entry = (key.clone(), option);
+    match options.get(&entry) {
+        Some(sender) if sender.send(value).is_ok() => Ok(()),
+        Some(_) => {
+            // Every receiver is gone, so the component has stopped running.
+            options.remove(&entry);
+            Err(format!("Component \"{}\" isn't running.", key))
+        }
+        None => Err(format!(
+            "Component \"{}\" has no runtime option `{}`.",
+            key,
+            option.name()
+        )),
+    }
+}
+
+/// The current values of the changeable options of a component.
+pub fn values(key: &ComponentKey) -> Vec<(RuntimeOption, u64)> {
+    OPTIONS
+        .lock()
+        .expect("runtime options lock poisoned")
+        .iter()
+        .filter(|((component, _), sender)| component == key && !sender.is_closed())
+        .map(|((_, option), sender)| (*option, *sender.borrow()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_registered_options() {
+        let key = ComponentKey::from("runtime_options_sample");
+        let receiver = register(&key, RuntimeOption::SampleRate, 10);
+
+        set(&key, RuntimeOption::SampleRate, 5).unwrap();
+        assert_eq!(*receiver.borrow(), 5);
+        assert_eq!(values(&key), vec![(RuntimeOption::SampleRate, 5)]);
+
+        assert_eq!(
+            set(&key, RuntimeOption::SampleRate, 0).unwrap_err(),
+            format!("`rate` must be between 1 and {}.", u64::MAX)
+        );
+        assert_eq!(
+            set(&key, RuntimeOption::ThrottleThreshold, 5).unwrap_err(),
+            "Component \"runtime_options_sample\" has no runtime option `threshold`."
+        );
+
+        drop(receiver);
+        assert_eq!(
+            set(&key, RuntimeOption::SampleRate, 5).unwrap_err(),
+            "Component \"runtime_options_sample\" isn't running."
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use vector_core::config::log_schema;
 
+use crate::event::LogEvent;
 pub(crate) use crate::schema::Definition;
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Copy, Clone)]
@@ -7,12 +9,18 @@ pub(crate) use crate::schema::Definition;
 pub struct Options {
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+
+    /// Where sources place the host, timestamp, source type and metadata fields of the logs they
+    /// produce, unless they set a namespace of their own.
+    #[serde(default)]
+    pub log_namespace: LogNamespace,
 }
 
 impl Default for Options {
     fn default() -> Self {
         Self {
             enabled: default_enabled(),
+            log_namespace: LogNamespace::default(),
         }
     }
 }
@@ -20,3 +28,68 @@ impl Default for Options {
 const fn default_enabled() -> bool {
     false
 }
+
+/// The field in which the fields of the Vector namespace are nested.
+const VECTOR_NAMESPACE: &str = "vector";
+
+/// The layout of the fields sources add to the logs they produce.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LogNamespace {
+    /// At the root of the event, under the keys of the global log schema.
+    #[derivative(Default)]
+    Legacy,
+
+    /// Nested in the `vector` field, under their own names, so that they can't collide with the
+    /// fields of the logs themselves.
+    Vector,
+}
+
+impl LogNamespace {
+    /// Moves the fields sources add to the log into this namespace.
+    ///
+    /// Sources place them in the legacy layout, so the log is left untouched for that namespace.
+    pub fn apply(self, log: &mut LogEvent) {
+        if self == Self::Legacy {
+            return;
+        }
+
+        let schema = log_schema();
+        for (key, name) in [
+            (schema.host_key(), "host"),
+            (schema.timestamp_key(), "timestamp"),
+            (schema.source_type_key(), "source_type"),
+            (schema.metadata_key(), "metadata"),
+        ] {
+            if let Some(value) = log.remove(key) {
+                log.insert(format!("{}.{}", VECTOR_NAMESPACE, name).as_str(), value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vector_namespace_nests_fields() {
+        let mut log = LogEvent::from("hello");
+        log.insert(log_schema().host_key(), "example.com");
+        log.insert(log_schema().source_type_key(), "demo_logs");
+        log.insert("service", "api");
+
+        let mut legacy = log.clone();
+        LogNamespace::Legacy.apply(&mut legacy);
+        assert_eq!(legacy, log);
+
+        LogNamespace::Vector.apply(&mut log);
+        assert!(!log.contains(log_schema().host_key()));
+        assert!(!log.contains(log_schema().source_type_key()));
+        assert_eq!(log["vector.host"], "example.com".into());
+        assert_eq!(log["vector.source_type"], "demo_logs".into());
+        assert_eq!(log["service"], "api".into());
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+    }
+}
@@ -1,12 +1,20 @@
+use std::num::{NonZeroU64, NonZeroUsize};
+
 use async_trait::async_trait;
 use component::ComponentDescription;
 use serde::{Deserialize, Serialize};
 use vector_buffers::{Acker, BufferConfig, BufferType};
 use vector_core::config::{AcknowledgementsConfig, GlobalOptions, Input};
 
-use super::{component, schema, ComponentKey, ProxyConfig, Resource};
+use super::{component, schema, ComponentKey, Output, ProxyConfig, Resource};
 use crate::sinks::{self, util::UriSerde};
 
+/// The name of the output that events a sink failed to deliver are routed to.
+pub const DEAD_LETTER_OUTPUT: &str = "dead_letter";
+
+/// The name of the output that events are rerouted to while the circuit breaker of a sink is open.
+pub const FALLBACK_OUTPUT: &str = "fallback";
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SinkOuter<T> {
     #[serde(default = "Default::default")] // https://github.com/serde-rs/serde/issues/1541
@@ -28,6 +36,24 @@ pub struct SinkOuter<T> {
     )]
     proxy: ProxyConfig,
 
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub dead_letter: SinkDeadLetterOptions,
+
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub priority: SinkPriorityOptions,
+
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub fallback: SinkFallbackOptions,
+
     #[serde(flatten)]
     pub inner: Box<dyn SinkConfig>,
 }
@@ -41,6 +67,9 @@ impl<T> SinkOuter<T> {
             healthcheck_uri: None,
             inner,
             proxy: Default::default(),
+            dead_letter: Default::default(),
+            priority: Default::default(),
+            fallback: Default::default(),
         }
     }
 
@@ -79,6 +108,23 @@ impl<T> SinkOuter<T> {
         &self.proxy
     }
 
+    /// The outputs of this sink.
+    ///
+    /// Sinks only have outputs when their dead letter or fallback outputs are enabled. Any events
+    /// the sink rejects, or fails to deliver, are sent to the former, while the latter receives
+    /// the events rerouted around the sink while its circuit breaker is open.
+    pub fn outputs(&self) -> Vec<Output> {
+        let data_type = self.inner.input().data_type();
+        let mut outputs = Vec::new();
+        if self.dead_letter.enabled {
+            outputs.push(Output::default(data_type).with_port(DEAD_LETTER_OUTPUT));
+        }
+        if self.fallback.enabled {
+            outputs.push(Output::default(data_type).with_port(FALLBACK_OUTPUT));
+        }
+        outputs
+    }
+
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> SinkOuter<U> {
         let inputs = self.inputs.iter().map(f).collect();
         self.with_inputs(inputs)
@@ -92,6 +138,84 @@ impl<T> SinkOuter<T> {
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            dead_letter: self.dead_letter,
+            priority: self.priority,
+            fallback: self.fallback,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SinkDeadLetterOptions {
+    /// Whether or not events that the sink rejects, or fails to deliver, are routed to its
+    /// `dead_letter` output.
+    ///
+    /// Only sinks that support end-to-end acknowledgements report the delivery status of events,
+    /// so events sent to any other sink are never routed.
+    pub enabled: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SinkPriorityOptions {
+    /// Whether or not the sink is sent higher priority events before lower priority ones.
+    ///
+    /// Events are held in a window of up to `max_events` events in front of the sink, and are
+    /// handed to the sink from the highest priority class first. When the window is full, lower
+    /// priority events in it are shed to make room for higher priority ones.
+    pub enabled: bool,
+
+    /// The maximum number of events held for scheduling.
+    pub max_events: NonZeroUsize,
+}
+
+impl Default for SinkPriorityOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_events: NonZeroUsize::new(1_000).expect("must be non-zero"),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SinkFallbackOptions {
+    /// Whether or not events are rerouted to the `fallback` output of the sink while its circuit
+    /// breaker is open.
+    ///
+    /// The breaker opens once too many of the most recent events handed to the sink failed to be
+    /// delivered, or took too long to be. Like the dead letter output, this relies on the sink
+    /// reporting the delivery status of events, so only sinks that support end-to-end
+    /// acknowledgements ever trip the breaker.
+    pub enabled: bool,
+
+    /// The ratio of failed events, out of the most recent `window_events` events, at which the
+    /// breaker opens.
+    pub failure_ratio: f64,
+
+    /// The number of most recent events the failure ratio is computed over.
+    ///
+    /// The breaker does not open before the sink has finalized at least this many events.
+    pub window_events: NonZeroUsize,
+
+    /// How long the sink may take to deliver events before they are counted as failed.
+    pub max_latency_secs: Option<NonZeroU64>,
+
+    /// How long the breaker stays open before events are let through to probe whether the sink
+    /// has recovered.
+    pub open_secs: NonZeroU64,
+}
+
+impl Default for SinkFallbackOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            failure_ratio: 0.5,
+            window_events: NonZeroUsize::new(100).expect("must be non-zero"),
+            max_latency_secs: None,
+            open_secs: NonZeroU64::new(30).expect("must be non-zero"),
         }
     }
 }
@@ -145,6 +269,12 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
     }
 
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig>;
+
+    /// Whether the sink delivers events in transactions, which also commit the position of the
+    /// sources the events were read from.
+    fn is_transactional(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1,11 +1,18 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use component::ComponentDescription;
 use serde::{Deserialize, Serialize};
-use vector_core::config::{AcknowledgementsConfig, GlobalOptions, Output};
+use vector_core::config::{AcknowledgementsConfig, AdmissionConfig, GlobalOptions, Output};
 
-use super::{component, schema, ComponentKey, ProxyConfig, Resource};
+use super::{
+    component,
+    schema::{self, LogNamespace},
+    ComponentKey, ProxyConfig, Resource,
+};
 use crate::{shutdown::ShutdownSignal, sources, SourceSender};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -15,10 +22,23 @@ pub struct SourceOuter {
         skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
     )]
     pub proxy: ProxyConfig,
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub admission: AdmissionConfig,
+    /// Overrides the `schema.log_namespace` policy for the logs of this source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_namespace: Option<LogNamespace>,
+    /// Tags attached to the events of this source, overriding the global tags of the same name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
     #[serde(flatten)]
     pub(crate) inner: Box<dyn SourceConfig>,
     #[serde(default, skip)]
     pub sink_acknowledgements: bool,
+    #[serde(default, skip)]
+    pub sink_acknowledgements_timeout: Option<Duration>,
 }
 
 impl SourceOuter {
@@ -26,7 +46,11 @@ impl SourceOuter {
         Self {
             inner: Box::new(source),
             proxy: Default::default(),
+            admission: Default::default(),
+            log_namespace: None,
+            tags: BTreeMap::new(),
             sink_acknowledgements: false,
+            sink_acknowledgements_timeout: None,
         }
     }
 }
@@ -46,6 +70,12 @@ pub trait SourceConfig: core::fmt::Debug + Send + Sync {
     }
 
     fn can_acknowledge(&self) -> bool;
+
+    /// Whether the source leaves committing its position to the sinks it feeds, which then all
+    /// have to be transactional.
+    fn requires_transactional_sinks(&self) -> bool {
+        false
+    }
 }
 
 pub struct SourceContext {
@@ -55,6 +85,8 @@ pub struct SourceContext {
     pub out: SourceSender,
     pub proxy: ProxyConfig,
     pub acknowledgements: bool,
+    /// The earliest acknowledgement timeout of the sinks this source feeds, if any.
+    pub acknowledgements_timeout: Option<Duration>,
 
     /// Tracks the schema IDs assigned to schemas exposed by the source.
     ///
@@ -79,6 +111,7 @@ impl SourceContext {
                 out,
                 proxy: Default::default(),
                 acknowledgements: false,
+                acknowledgements_timeout: None,
                 schema_definitions: HashMap::default(),
             },
             shutdown,
@@ -97,6 +130,7 @@ impl SourceContext {
             out,
             proxy: Default::default(),
             acknowledgements: false,
+            acknowledgements_timeout: None,
             schema_definitions: schema_definitions.unwrap_or_default(),
         }
     }
@@ -114,6 +148,18 @@ impl SourceContext {
             .merge_default(&self.acknowledgements.into())
             .enabled()
     }
+
+    /// Gets how long the source should wait for its events to be acknowledged before treating them
+    /// as errored, taking the earliest of its own, the global, and its sinks' timeouts.
+    pub fn acknowledgements_timeout(&self, config: &AcknowledgementsConfig) -> Option<Duration> {
+        let timeout = config
+            .merge_default(&self.globals.acknowledgements)
+            .timeout();
+        match (timeout, self.acknowledgements_timeout) {
+            (Some(timeout), Some(sink_timeout)) => Some(timeout.min(sink_timeout)),
+            (timeout, sink_timeout) => timeout.or(sink_timeout),
+        }
+    }
 }
 
 pub type SourceDescription = ComponentDescription<Box<dyn SourceConfig>>;
@@ -4,6 +4,7 @@ mod unit_test_components;
 
 use std::{
     collections::{HashMap, HashSet},
+    path::Path,
     sync::Arc,
 };
 
@@ -17,16 +18,18 @@ use tokio::sync::{
 use uuid::Uuid;
 
 use self::unit_test_components::{
-    UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult, UnitTestSourceConfig,
+    GoldenFile, UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult, UnitTestSourceConfig,
 };
 use super::{compiler::expand_globs, graph::Graph, OutputId};
 use crate::{
     conditions::Condition,
     config::{
         self, compiler::expand_macros, loading, ComponentKey, Config, ConfigBuilder, ConfigPath,
-        SinkOuter, SourceOuter, TestDefinition, TestInput, TestInputValue, TestOutput,
+        SinkOuter, SourceOuter, TestDefinition, TestInput, TestInputValue, TestInternalMetric,
+        TestOutput,
     },
-    event::{Event, Value},
+    event::{Event, LogEvent, Metric, MetricValue, Value},
+    metrics::Controller,
     schema,
     serde::OneOrMany,
     signal,
@@ -41,14 +44,25 @@ pub struct UnitTest {
     config: Config,
     pieces: Pieces,
     test_result_rxs: Vec<Receiver<UnitTestSinkResult>>,
+    internal_metrics: Vec<TestInternalMetric>,
 }
 
 pub struct UnitTestResult {
     pub errors: Vec<String>,
 }
 
+/// How the golden files of test outputs are used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GoldenMode {
+    /// Compare the outputs against the golden files.
+    Compare,
+    /// Overwrite the golden files with the outputs.
+    Update,
+}
+
 impl UnitTest {
     pub async fn run(self) -> UnitTestResult {
+        let metrics_before = capture_internal_metrics(&self.internal_metrics);
         let diff = config::ConfigDiff::initial(&self.config);
         let (topology, _) = topology::start_validated(self.config, diff, self.pieces)
             .await
@@ -69,6 +83,17 @@ impl UnitTest {
             errors.extend(partial_result.test_errors);
         }
 
+        if let (Some(before), Some(after)) = (
+            metrics_before,
+            capture_internal_metrics(&self.internal_metrics),
+        ) {
+            errors.extend(check_internal_metrics(
+                &self.internal_metrics,
+                &before,
+                &after,
+            ));
+        }
+
         UnitTestResult { errors }
     }
 }
@@ -76,6 +101,7 @@ impl UnitTest {
 pub async fn build_unit_tests_main(
     paths: &[ConfigPath],
     signal_handler: &mut signal::SignalHandler,
+    golden_mode: GoldenMode,
 ) -> Result<Vec<UnitTest>, Vec<String>> {
     config::init_log_schema(paths, false)?;
     let (mut secrets_backends_loader, _) = loading::load_secret_backends_from_paths(paths)?;
@@ -88,11 +114,16 @@ pub async fn build_unit_tests_main(
         loading::load_builder_from_paths(paths)?
     };
 
-    build_unit_tests(config_builder).await
+    build_unit_tests_with_golden_mode(config_builder, golden_mode).await
+}
+
+pub async fn build_unit_tests(config_builder: ConfigBuilder) -> Result<Vec<UnitTest>, Vec<String>> {
+    build_unit_tests_with_golden_mode(config_builder, GoldenMode::Compare).await
 }
 
-pub async fn build_unit_tests(
+async fn build_unit_tests_with_golden_mode(
     mut config_builder: ConfigBuilder,
+    golden_mode: GoldenMode,
 ) -> Result<Vec<UnitTest>, Vec<String>> {
     // Sanitize config by removing existing sources and sinks
     config_builder.sources = Default::default();
@@ -101,7 +132,7 @@ pub async fn build_unit_tests(
     let test_definitions = std::mem::take(&mut config_builder.tests);
     let mut tests = Vec::new();
     let mut build_errors = Vec::new();
-    let metadata = UnitTestBuildMetadata::initialize(&mut config_builder)?;
+    let metadata = UnitTestBuildMetadata::initialize(&mut config_builder, golden_mode)?;
 
     for mut test_definition in test_definitions {
         let test_name = test_definition.name.clone();
@@ -139,10 +170,15 @@ pub struct UnitTestBuildMetadata {
     template_sources: IndexMap<ComponentKey, UnitTestSourceConfig>,
     // A mapping from transform name to unit test sink name.
     sink_ids: HashMap<OutputId, String>,
+    // Whether golden files are compared against or updated.
+    golden_mode: GoldenMode,
 }
 
 impl UnitTestBuildMetadata {
-    pub fn initialize(config_builder: &mut ConfigBuilder) -> Result<Self, Vec<String>> {
+    pub fn initialize(
+        config_builder: &mut ConfigBuilder,
+        golden_mode: GoldenMode,
+    ) -> Result<Self, Vec<String>> {
         // A unique id used to name test sources and sinks to avoid name clashes
         let random_id = Uuid::new_v4().to_string();
 
@@ -208,6 +244,7 @@ impl UnitTestBuildMetadata {
             source_ids,
             template_sources,
             sink_ids,
+            golden_mode,
         })
     }
 
@@ -260,12 +297,12 @@ impl UnitTestBuildMetadata {
                     .to_string(),
             ]);
         }
-        let outputs = build_outputs(outputs)?;
+        let outputs = build_outputs(outputs, self.golden_mode)?;
 
         let mut template_sinks = IndexMap::new();
         let mut test_result_rxs = Vec::new();
         // Add sinks with checks
-        for (ids, checks) in outputs {
+        for (ids, (checks, golden)) in outputs {
             let (tx, rx) = oneshot::channel();
             let sink_ids = ids.clone();
             let sink_config = UnitTestSinkConfig {
@@ -273,6 +310,7 @@ impl UnitTestBuildMetadata {
                 transform_ids: ids.stringify().into_vec(),
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::Checks(checks),
+                golden,
             };
 
             test_result_rxs.push(rx);
@@ -287,6 +325,7 @@ impl UnitTestBuildMetadata {
                 transform_ids: vec![id.to_string()],
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::NoOutputs,
+                golden: None,
             };
 
             test_result_rxs.push(rx);
@@ -367,6 +406,18 @@ async fn build_unit_test(
         &expansions,
     );
     let test = test.resolve_outputs(&transform_only_graph, &expansions)?;
+    validate_internal_metrics(&test.internal_metrics, &metadata.available_insert_targets)?;
+
+    // Test conditions may refer to the shared conditions and VRL library, which are otherwise only
+    // loaded when the topology is built.
+    let errors = builder::load_shared_definitions(
+        &config_builder.vrl_library,
+        &config_builder.conditions,
+        &Default::default(),
+    );
+    if !errors.is_empty() {
+        return Err(errors);
+    }
 
     let sources = metadata.hydrate_into_sources(&test.inputs)?;
     let (test_result_rxs, sinks) =
@@ -442,9 +493,108 @@ async fn build_unit_test(
         config,
         pieces,
         test_result_rxs,
+        internal_metrics: test.internal_metrics,
     })
 }
 
+fn validate_internal_metrics(
+    internal_metrics: &[TestInternalMetric],
+    components: &HashSet<ComponentKey>,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    for (index, metric) in internal_metrics.iter().enumerate() {
+        if metric.value.is_none() && metric.at_least.is_none() {
+            errors.push(format!(
+                "internal_metrics[{}]: must specify one of `value` or `at_least`",
+                index
+            ));
+        }
+        if let Some(component_id) = &metric.component_id {
+            if !components.contains(component_id) {
+                errors.push(format!(
+                    "internal_metrics[{}]: unable to locate target transform '{}'",
+                    index, component_id
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Captures the internal metrics, if the test asserts on any.
+fn capture_internal_metrics(internal_metrics: &[TestInternalMetric]) -> Option<Vec<Metric>> {
+    if internal_metrics.is_empty() {
+        return None;
+    }
+    match Controller::get() {
+        Ok(controller) => Some(controller.capture_metrics()),
+        Err(error) => {
+            error!(message = "Internal metrics are unavailable.", %error);
+            None
+        }
+    }
+}
+
+/// Sums the counters matching an assertion on internal metrics.
+fn sum_counters(metrics: &[Metric], assertion: &TestInternalMetric) -> f64 {
+    metrics
+        .iter()
+        .filter(|metric| metric.name() == assertion.name)
+        .filter(|metric| {
+            assertion
+                .component_id
+                .as_ref()
+                .map_or(true, |id| metric.tag_matches("component_id", id.id()))
+                && assertion
+                    .tags
+                    .iter()
+                    .all(|(name, value)| metric.tag_matches(name, value))
+        })
+        .map(|metric| match metric.value() {
+            MetricValue::Counter { value } => *value,
+            _ => 0.0,
+        })
+        .sum()
+}
+
+/// Checks the amounts counters were incremented by between two captures of the internal metrics.
+fn check_internal_metrics(
+    internal_metrics: &[TestInternalMetric],
+    before: &[Metric],
+    after: &[Metric],
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    for assertion in internal_metrics {
+        let increase = sum_counters(after, assertion) - sum_counters(before, assertion);
+        let target = match &assertion.component_id {
+            Some(id) => format!("'{}' of component '{}'", assertion.name, id),
+            None => format!("'{}'", assertion.name),
+        };
+        if let Some(value) = assertion.value {
+            if (increase - value).abs() > f64::EPSILON {
+                errors.push(format!(
+                    "check for internal metric {} failed: expected an increase of {}, got {}",
+                    target, value, increase
+                ));
+            }
+        }
+        if let Some(at_least) = assertion.at_least {
+            if increase < at_least {
+                errors.push(format!(
+                    "check for internal metric {} failed: expected an increase of at least {}, got {}",
+                    target, at_least, increase
+                ));
+            }
+        }
+    }
+    errors
+}
+
 /// Near the end of building a unit test, it's possible that we've included a
 /// transform(s) with multiple outputs where at least one of its output is
 /// consumed but its other outputs are left unconsumed.
@@ -492,6 +642,7 @@ fn get_loose_end_outputs_sink(config: &ConfigBuilder) -> Option<SinkOuter<String
             transform_ids: vec![],
             result_tx: Arc::new(Mutex::new(None)),
             check: UnitTestSinkCheck::NoOp,
+            golden: None,
         };
         Some(SinkOuter::new(loose_end_outputs, Box::new(noop_sink)))
     }
@@ -510,14 +661,12 @@ fn build_and_validate_inputs(
 
     for (index, input) in test_inputs.iter().enumerate() {
         if available_insert_targets.contains(&input.insert_at) {
-            match build_input_event(input) {
-                Ok(input_event) => {
+            match build_input_events(input) {
+                Ok(input_events) => {
                     inputs
                         .entry(input.insert_at.clone())
-                        .and_modify(|events: &mut Vec<Event>| {
-                            events.push(input_event.clone());
-                        })
-                        .or_insert_with(|| vec![input_event]);
+                        .or_insert_with(Vec::new)
+                        .extend(input_events);
                 }
                 Err(error) => errors.push(error),
             }
@@ -536,10 +685,13 @@ fn build_and_validate_inputs(
     }
 }
 
+type OutputChecks = (Vec<Vec<Condition>>, Option<GoldenFile>);
+
 fn build_outputs(
     test_outputs: &[TestOutput],
-) -> Result<IndexMap<OneOrMany<OutputId>, Vec<Vec<Condition>>>, Vec<String>> {
-    let mut outputs: IndexMap<OneOrMany<OutputId>, Vec<Vec<Condition>>> = IndexMap::new();
+    golden_mode: GoldenMode,
+) -> Result<IndexMap<OneOrMany<OutputId>, OutputChecks>, Vec<String>> {
+    let mut outputs: IndexMap<OneOrMany<OutputId>, OutputChecks> = IndexMap::new();
     let mut errors = Vec::new();
 
     for output in test_outputs {
@@ -560,10 +712,24 @@ fn build_outputs(
             }
         }
 
-        outputs
+        let golden = output.golden.as_ref().map(|path| GoldenFile {
+            path: path.clone(),
+            ignore_fields: output.ignore_fields.clone(),
+            mode: golden_mode,
+        });
+        let (existing_conditions, existing_golden) = outputs
             .entry(output.extract_from.clone())
-            .and_modify(|existing_conditions| existing_conditions.push(conditions.clone()))
-            .or_insert(vec![conditions.clone()]);
+            .or_insert_with(|| (Vec::new(), None));
+        existing_conditions.push(conditions);
+        if let Some(golden) = golden {
+            if existing_golden.is_some() {
+                errors.push(format!(
+                    "multiple golden files for outputs of {:?}",
+                    output.extract_from.stringify().into_vec()
+                ));
+            }
+            *existing_golden = Some(golden);
+        }
     }
 
     if errors.is_empty() {
@@ -573,6 +739,43 @@ fn build_outputs(
     }
 }
 
+fn build_input_events(input: &TestInput) -> Result<Vec<Event>, String> {
+    match &input.file {
+        Some(path) => read_input_file(&input.type_str, path),
+        None => build_input_event(input).map(|event| vec![event]),
+    }
+}
+
+/// Reads the events of an input file, holding one event per line.
+fn read_input_file(type_str: &str, path: &Path) -> Result<Vec<Event>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| format!("unable to read input file {:?}: {}", path, error))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let event = match type_str {
+                "raw" => Ok(Event::from(line)),
+                "log" => serde_json::from_str::<serde_json::Value>(line)
+                    .map_err(|error| error.to_string())
+                    .and_then(|value| LogEvent::try_from(value).map_err(|error| error.to_string()))
+                    .map(Event::Log),
+                "metric" => serde_json::from_str::<Metric>(line)
+                    .map(Event::Metric)
+                    .map_err(|error| error.to_string()),
+                _ => {
+                    return Err(format!(
+                        "unrecognized input type '{}', expected one of: 'raw', 'log' or 'metric'",
+                        type_str
+                    ))
+                }
+            };
+            event.map_err(|error| format!("invalid event at {:?}:{}: {}", path, index + 1, error))
+        })
+        .collect()
+}
+
 fn build_input_event(input: &TestInput) -> Result<Event, String> {
     match input.type_str.as_ref() {
         "raw" => match input.value.as_ref() {
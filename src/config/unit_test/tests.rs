@@ -1198,3 +1198,154 @@ async fn test_glob_input() {
     let mut tests = build_unit_tests(config).await.unwrap();
     assert!(tests.remove(0).run().await.errors.is_empty());
 }
+
+#[tokio::test]
+async fn test_internal_metrics() {
+    crate::test_util::components::init_test();
+
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+    [transforms.foo]
+      type = "remap"
+      inputs = [ "ignored" ]
+      source = ".new_field = 1"
+
+    [[tests]]
+      name = "internal metrics test"
+
+      [[tests.inputs]]
+        type = "raw"
+        insert_at = "foo"
+        value = "test1"
+
+      [[tests.inputs]]
+        type = "raw"
+        insert_at = "foo"
+        value = "test2"
+
+      [[tests.outputs]]
+        extract_from = "foo"
+
+        [[tests.outputs.conditions]]
+          type = "vrl"
+          source = "assert_eq!(.new_field, 1)"
+
+      [[tests.internal_metrics]]
+        name = "component_received_events_total"
+        component_id = "foo"
+        value = 2
+
+    [[tests]]
+      name = "internal metrics test failure"
+
+      [[tests.inputs]]
+        type = "raw"
+        insert_at = "foo"
+        value = "test1"
+
+      [[tests.outputs]]
+        extract_from = "foo"
+
+        [[tests.outputs.conditions]]
+          type = "vrl"
+          source = "assert_eq!(.new_field, 1)"
+
+      [[tests.internal_metrics]]
+        name = "component_received_events_total"
+        component_id = "foo"
+        at_least = 2
+  "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert_eq!(tests.remove(0).run().await.errors, Vec::<String>::new());
+    assert_eq!(
+        tests.remove(0).run().await.errors,
+        vec!["check for internal metric 'component_received_events_total' of component 'foo' failed: expected an increase of at least 2, got 1"]
+    );
+}
+
+#[tokio::test]
+async fn test_internal_metrics_invalid() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+    [transforms.foo]
+      type = "remap"
+      inputs = [ "ignored" ]
+      source = ".new_field = 1"
+
+    [[tests]]
+      name = "broken test"
+      no_outputs_from = [ "foo" ]
+
+      [[tests.inputs]]
+        type = "raw"
+        insert_at = "foo"
+        value = "test1"
+
+      [[tests.internal_metrics]]
+        name = "component_received_events_total"
+        component_id = "bar"
+  "#})
+    .unwrap();
+
+    let errs = build_unit_tests(config).await.err().unwrap();
+    assert_eq!(
+        errs,
+        vec![indoc! {r#"
+            Failed to build test 'broken test':
+              internal_metrics[0]: must specify one of `value` or `at_least`
+              internal_metrics[0]: unable to locate target transform 'bar'"#}
+        .to_owned(),]
+    );
+}
+
+#[tokio::test]
+async fn test_input_and_golden_files() {
+    let dir = crate::test_util::temp_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("input.ndjson");
+    let golden = dir.join("golden").join("foo.ndjson");
+    std::fs::write(
+        &input,
+        "{\"message\":\"test1\"}\n\n{\"message\":\"test2\"}\n",
+    )
+    .unwrap();
+
+    let config = indoc::formatdoc! {r#"
+        [transforms.foo]
+          type = "remap"
+          inputs = [ "ignored" ]
+          source = ".new_field = 1"
+
+        [[tests]]
+          name = "golden file test"
+
+          [[tests.inputs]]
+            type = "log"
+            insert_at = "foo"
+            file = {:?}
+
+          [[tests.outputs]]
+            extract_from = "foo"
+            golden = {:?}
+      "#,
+        input, golden
+    };
+
+    let config: ConfigBuilder = toml::from_str(&config).unwrap();
+    let mut tests = build_unit_tests_with_golden_mode(config.clone(), GoldenMode::Update)
+        .await
+        .unwrap();
+    assert!(tests.remove(0).run().await.errors.is_empty());
+    assert_eq!(
+        std::fs::read_to_string(&golden).unwrap(),
+        "{\"message\":\"test1\",\"new_field\":1}\n{\"message\":\"test2\",\"new_field\":1}\n"
+    );
+
+    let mut tests = build_unit_tests(config.clone()).await.unwrap();
+    assert!(tests.remove(0).run().await.errors.is_empty());
+
+    std::fs::write(&golden, "{\"message\":\"test1\",\"new_field\":1}\n").unwrap();
+    let mut tests = build_unit_tests(config).await.unwrap();
+    let errors = tests.remove(0).run().await.errors;
+    assert!(errors[0].ends_with("failed: expected 1 events, got 2"));
+}
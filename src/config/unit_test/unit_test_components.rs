@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{fs, path::PathBuf, sync::Arc};
 
 use futures_util::{future, stream::BoxStream, FutureExt, StreamExt};
 use serde::{Deserialize, Serialize};
@@ -9,6 +9,7 @@ use vector_core::{
     sink::{StreamSink, VectorSink},
 };
 
+use super::GoldenMode;
 use crate::{
     conditions::Condition,
     config::{AcknowledgementsConfig, SinkConfig, SinkContext, SourceConfig, SourceContext},
@@ -87,6 +88,9 @@ pub struct UnitTestSinkConfig {
     #[derivative(Debug = "ignore")]
     // Check applied to incoming events
     pub check: UnitTestSinkCheck,
+    #[serde(skip)]
+    // Golden file the incoming events are compared against
+    pub golden: Option<GoldenFile>,
 }
 
 #[async_trait::async_trait]
@@ -99,6 +103,7 @@ impl SinkConfig for UnitTestSinkConfig {
             transform_ids: self.transform_ids.clone(),
             result_tx: tx,
             check: self.check.clone(),
+            golden: self.golden.clone(),
         };
         let healthcheck = future::ok(()).boxed();
 
@@ -124,6 +129,7 @@ pub struct UnitTestSink {
     // None for NoOp test sinks
     pub result_tx: Option<oneshot::Sender<UnitTestSinkResult>>,
     pub check: UnitTestSinkCheck,
+    pub golden: Option<GoldenFile>,
 }
 
 #[async_trait::async_trait]
@@ -178,6 +184,12 @@ impl StreamSink<Event> for UnitTestSink {
                         result.test_errors.extend(check_errors);
                     }
 
+                    if let Some(golden) = &self.golden {
+                        result
+                            .test_errors
+                            .extend(golden.check(&self.transform_ids, &output_events));
+                    }
+
                     // If there are errors, add a summary of events received
                     if !result.test_errors.is_empty() {
                         result.test_errors.push(format!(
@@ -208,6 +220,111 @@ impl StreamSink<Event> for UnitTestSink {
     }
 }
 
+/// A file holding the expected events of a test output, one JSON event per line.
+#[derive(Clone, Debug)]
+pub struct GoldenFile {
+    pub path: PathBuf,
+    // Fields of logs left out of the comparison
+    pub ignore_fields: Vec<String>,
+    pub mode: GoldenMode,
+}
+
+impl GoldenFile {
+    /// Compares the events against the golden file, or overwrites the golden file with them,
+    /// returning the errors found.
+    fn check(&self, transform_ids: &[String], events: &[Event]) -> Vec<String> {
+        let events = events
+            .iter()
+            .map(|event| self.to_json(event))
+            .collect::<Vec<_>>();
+
+        match self.mode {
+            GoldenMode::Update => {
+                let mut content = String::new();
+                for event in &events {
+                    content.push_str(&event.to_string());
+                    content.push('\n');
+                }
+                let written = self
+                    .path
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|()| fs::write(&self.path, content));
+                match written {
+                    Ok(()) => Vec::new(),
+                    Err(error) => vec![format!(
+                        "unable to update golden file {:?}: {}",
+                        self.path, error
+                    )],
+                }
+            }
+            GoldenMode::Compare => {
+                let content = match fs::read_to_string(&self.path) {
+                    Ok(content) => content,
+                    Err(error) => {
+                        return vec![format!(
+                            "unable to read golden file {:?}: {}",
+                            self.path, error
+                        )]
+                    }
+                };
+                let mut expected = Vec::new();
+                for (index, line) in content.lines().enumerate() {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    match serde_json::from_str::<serde_json::Value>(line) {
+                        Ok(event) => expected.push(event),
+                        Err(error) => {
+                            return vec![format!(
+                                "invalid event at {:?}:{}: {}",
+                                self.path,
+                                index + 1,
+                                error
+                            )]
+                        }
+                    }
+                }
+
+                let mut errors = Vec::new();
+                if expected.len() != events.len() {
+                    errors.push(format!(
+                        "golden file {:?} for transforms {:?} failed: expected {} events, got {}",
+                        self.path,
+                        transform_ids,
+                        expected.len(),
+                        events.len()
+                    ));
+                }
+                for (index, (expected, actual)) in expected.iter().zip(&events).enumerate() {
+                    if expected != actual {
+                        errors.push(format!(
+                            "golden file {:?} for transforms {:?} failed: event {} differs:\n  expected: {}\n  actual:   {}",
+                            self.path, transform_ids, index, expected, actual
+                        ));
+                    }
+                }
+                errors
+            }
+        }
+    }
+
+    fn to_json(&self, event: &Event) -> serde_json::Value {
+        let value = match event {
+            Event::Log(log) => {
+                let mut log = log.clone();
+                for field in &self.ignore_fields {
+                    log.remove(field.as_str());
+                }
+                serde_json::to_value(&log)
+            }
+            Event::Metric(metric) => serde_json::to_value(metric),
+            Event::Trace(trace) => serde_json::to_value(trace),
+        };
+        value.unwrap_or_else(|_| serde_json::Value::Object(Default::default()))
+    }
+}
+
 fn events_to_string(events: &[Event]) -> String {
     events
         .iter()
@@ -1,6 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use vector_core::internal_event::DEFAULT_OUTPUT;
+use vector_core::{
+    config::{CheckpointStoreConfig, ClusterBackendConfig},
+    internal_event::DEFAULT_OUTPUT,
+};
 
 use super::{builder::ConfigBuilder, schema, ComponentKey, Config, OutputId, Resource};
 
@@ -72,6 +75,72 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         ));
     }
 
+    let sample_rate = config.global.telemetry.sample_rate;
+    if !(0.0..=1.0).contains(&sample_rate) {
+        errors.push(format!(
+            "Telemetry sample rate must be between 0 and 1, got {}.",
+            sample_rate
+        ));
+    }
+
+    let limits = &config.global.memory_limit;
+    if let (Some(soft), Some(hard)) = (limits.soft_limit_bytes, limits.hard_limit_bytes) {
+        if soft > hard {
+            errors.push(format!(
+                "Memory soft limit ({} bytes) must not be above the hard limit ({} bytes).",
+                soft, hard
+            ));
+        }
+    }
+
+    match &config.global.cluster.backend {
+        Some(ClusterBackendConfig::Static { members }) if members.is_empty() => {
+            errors.push("Static cluster backends need at least one member.".to_owned());
+        }
+        Some(ClusterBackendConfig::Static { members }) => {
+            if let Some(node_id) = &config.global.cluster.node_id {
+                if !members.contains(node_id) {
+                    errors.push(format!(
+                        "Cluster node ID \"{}\" is not one of the static members.",
+                        node_id
+                    ));
+                }
+            }
+        }
+        Some(ClusterBackendConfig::Consul {
+            session_ttl_secs, ..
+        }) if *session_ttl_secs < 10 => {
+            errors.push(format!(
+                "Consul session TTLs must be at least 10 seconds, got {}.",
+                session_ttl_secs
+            ));
+        }
+        Some(ClusterBackendConfig::KubernetesLease {
+            lease_duration_secs,
+            ..
+        }) if *lease_duration_secs < 5 => {
+            errors.push(format!(
+                "Kubernetes lease durations must be at least 5 seconds, got {}.",
+                lease_duration_secs
+            ));
+        }
+        _ => {}
+    }
+
+    let checkpoints = &config.global.checkpoints;
+    if checkpoints.is_enabled() && checkpoints.sync_interval_secs == 0 {
+        errors.push("Checkpoint sync intervals must be at least 1 second.".to_owned());
+    }
+    match &checkpoints.store {
+        Some(CheckpointStoreConfig::Redis { url, .. }) if url.is_empty() => {
+            errors.push("Redis checkpoint stores need a URL.".to_owned());
+        }
+        Some(CheckpointStoreConfig::S3 { bucket, .. }) if bucket.is_empty() => {
+            errors.push("S3 checkpoint stores need a bucket.".to_owned());
+        }
+        _ => {}
+    }
+
     // Warnings and errors
     let sink_inputs = config
         .sinks
@@ -175,6 +244,53 @@ pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         }
     }
 
+    for (key, sink) in config.sinks.iter() {
+        let failure_ratio = sink.fallback.failure_ratio;
+        if sink.fallback.enabled && !(failure_ratio > 0.0 && failure_ratio <= 1.0) {
+            errors.push(format!(
+                "Sink {key} must have a `fallback.failure_ratio` greater than 0 and at most 1"
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that sources which leave committing their position to transactional sinks don't feed
+/// any other sink, as their position would then never be committed for the events delivered by it.
+pub fn check_transactions(config: &Config) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    for (key, sink) in config.sinks.iter() {
+        if sink.inner.is_transactional() {
+            continue;
+        }
+
+        // Walk the topology upstream of the sink, through any components in between.
+        let mut visited = HashSet::new();
+        let mut pending: Vec<_> = sink.inputs.iter().map(|input| &input.component).collect();
+        while let Some(component) = pending.pop() {
+            if !visited.insert(component) {
+                continue;
+            }
+
+            if let Some(source) = config.sources.get(component) {
+                if source.inner.requires_transactional_sinks() {
+                    errors.push(format!(
+                        "Source {component} can only feed transactional sinks in exactly-once mode, but feeds sink {key}"
+                    ));
+                }
+            } else if let Some(transform) = config.transforms.get(component) {
+                pending.extend(transform.inputs.iter().map(|input| &input.component));
+            } else if let Some(sink) = config.sinks.get(component) {
+                pending.extend(sink.inputs.iter().map(|input| &input.component));
+            }
+        }
+    }
+
     if errors.is_empty() {
         Ok(())
     } else {
@@ -214,7 +330,20 @@ pub fn warnings(config: &Config) -> Vec<String> {
             .collect::<Vec<_>>()
     });
 
-    for (input_type, id) in transform_ids.chain(source_ids) {
+    let sink_ids = config.sinks.iter().flat_map(|(key, sink)| {
+        sink.outputs()
+            .iter()
+            .map(|output| {
+                if let Some(port) = &output.port {
+                    ("sink", OutputId::from((key, port.clone())))
+                } else {
+                    ("sink", OutputId::from(key))
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    for (input_type, id) in transform_ids.chain(source_ids).chain(sink_ids) {
         if !config
             .transforms
             .iter()
@@ -232,6 +361,24 @@ pub fn warnings(config: &Config) -> Vec<String> {
         }
     }
 
+    #[cfg(not(feature = "telemetry"))]
+    if config.global.telemetry.is_enabled() {
+        warnings.push("Telemetry is configured, but Vector was built without it".to_owned());
+    }
+
+    // Sources pinned to the legacy layout are the ones left to migrate once the policy moves to the
+    // Vector namespace.
+    if config.schema.log_namespace == schema::LogNamespace::Vector {
+        for (key, source) in &config.sources {
+            if source.log_namespace == Some(schema::LogNamespace::Legacy) {
+                warnings.push(format!(
+                    "Source \"{}\" still uses the legacy log namespace while the policy is \"vector\"",
+                    key
+                ));
+            }
+        }
+    }
+
     warnings
 }
 
@@ -0,0 +1,666 @@
+//! Conversion of Fluent Bit configurations, in the classic format.
+//!
+//! See <https://docs.fluentbit.io/manual/administration/configuring-fluent-bit/classic-mode>.
+
+use regex::Regex;
+
+use super::{
+    component, encoding, port, strings, table, vrl_path, vrl_regex, vrl_string, Pipeline, Router,
+    Settings,
+};
+
+#[derive(Debug)]
+struct Section {
+    name: String,
+    line: usize,
+    entries: Vec<(String, String)>,
+}
+
+impl Section {
+    fn settings(&self, kind: &'static str) -> Settings<'_, String> {
+        Settings::new(
+            kind,
+            self.plugin(),
+            self.line,
+            self.entries
+                .iter()
+                .map(|(key, value)| (key.as_str(), value)),
+        )
+        .ignore_case()
+    }
+
+    /// The name of the plugin of the section.
+    fn plugin(&self) -> &str {
+        self.entries
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("name"))
+            .map_or("", |(_, value)| value.as_str())
+    }
+}
+
+fn parse(config: &str, pipeline: &mut Pipeline) -> Result<Vec<Section>, String> {
+    let mut sections: Vec<Section> = Vec::new();
+    for (i, line) in config.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('@') {
+            pipeline.unsupported(
+                line_number,
+                format!("the `{}` directive isn't converted", line),
+            );
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            sections.push(Section {
+                name: name.trim().to_ascii_uppercase(),
+                line: line_number,
+                entries: Vec::new(),
+            });
+            continue;
+        }
+        let section = match sections.last_mut() {
+            Some(section) => section,
+            None => return Err(format!("line {}: expected a section", line_number)),
+        };
+        let (key, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        section
+            .entries
+            .push((key.to_owned(), value.trim().to_owned()));
+    }
+    Ok(sections)
+}
+
+/// Converts the `Match` or `Match_Regex` setting of a section to a regex matching tags.
+fn tag_pattern(settings: &mut Settings<String>) -> Result<Regex, String> {
+    if let Some(regex) = settings.get("match_regex") {
+        return Regex::new(regex)
+            .map_err(|error| format!("line {}: invalid `Match_Regex`: {}", settings.line, error));
+    }
+    let pattern = settings.get("match").map_or("", String::as_str);
+    let regex = pattern
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*");
+    Ok(Regex::new(&format!("^{}$", regex)).expect("escaped patterns are valid"))
+}
+
+pub(super) fn convert(config: &str) -> Result<Pipeline, String> {
+    let mut pipeline = Pipeline::default();
+    let sections = parse(config, &mut pipeline)?;
+
+    let mut router = Router::default();
+    for (index, section) in sections
+        .iter()
+        .filter(|section| section.name == "INPUT")
+        .enumerate()
+    {
+        if let Some((tag, source)) = input(&mut pipeline, section, index) {
+            router.add(tag.as_deref(), &source);
+        }
+    }
+    for section in &sections {
+        match section.name.as_str() {
+            "INPUT" => {}
+            "FILTER" => filter(&mut pipeline, &mut router, section)?,
+            "OUTPUT" => output(&mut pipeline, &router, section)?,
+            // The settings of the service are about Fluent Bit itself, rather than the pipeline,
+            // apart from the parsers.
+            "SERVICE" => {
+                if let Some(parsers) = section.settings("service").get("parsers_file") {
+                    pipeline.unsupported(
+                        section.line,
+                        format!(
+                            "the parsers of `{}` aren't converted, only the built-in `json` parser is",
+                            parsers
+                        ),
+                    );
+                }
+            }
+            name => pipeline.unsupported(
+                section.line,
+                format!("the `[{}]` section isn't converted", name),
+            ),
+        }
+    }
+    Ok(pipeline)
+}
+
+fn unsupported(pipeline: &mut Pipeline, section: &Section, kind: &str) {
+    pipeline.unsupported(
+        section.line,
+        format!(
+            "the `{}` {} has no Vector equivalent, and was left out",
+            section.plugin(),
+            kind
+        ),
+    );
+}
+
+/// The address to listen on, from the `Listen` and `Port` settings.
+fn listen_address(
+    pipeline: &mut Pipeline,
+    settings: &mut Settings<String>,
+    default_port: u16,
+) -> Option<String> {
+    let host = settings.get("listen").map_or("0.0.0.0", String::as_str);
+    let port = match settings.get("port") {
+        Some(value) => port(pipeline, settings.line, value)?,
+        None => default_port,
+    };
+    Some(format!("{}:{}", host, port))
+}
+
+fn is_on(value: Option<&String>) -> bool {
+    matches!(
+        value.map(|value| value.to_ascii_lowercase()).as_deref(),
+        Some("on" | "true" | "yes")
+    )
+}
+
+/// Converts an input, returning its tag, unless it's the one clients send, along with the ID of
+/// the source.
+fn input(
+    pipeline: &mut Pipeline,
+    section: &Section,
+    index: usize,
+) -> Option<(Option<String>, String)> {
+    let mut settings = section.settings("input");
+    settings.ignore(&["name"]);
+    let plugin = section.plugin();
+    let source = match plugin {
+        "tail" => {
+            let mut source = component("file");
+            let include = settings
+                .get_all("path")
+                .into_iter()
+                .flat_map(|paths| paths.split(','))
+                .map(str::trim)
+                .collect::<Vec<_>>();
+            source.insert("include".into(), strings(&include));
+            if let Some(exclude) = settings.get("exclude_path") {
+                let exclude = exclude.split(',').map(str::trim).collect::<Vec<_>>();
+                source.insert("exclude".into(), strings(&exclude));
+            }
+            if is_on(settings.get("read_from_head")) {
+                source.insert("read_from".into(), "beginning".into());
+            }
+            settings.ignore(&["db", "db.sync", "mem_buf_limit", "refresh_interval"]);
+            source
+        }
+        "stdin" => component("stdin"),
+        "tcp" | "udp" => {
+            let mut source = component("socket");
+            source.insert("mode".into(), plugin.into());
+            let address = listen_address(pipeline, &mut settings, 5170)?;
+            source.insert("address".into(), address.into());
+            if settings.get("format").map(String::as_str) != Some("none") {
+                source.insert("decoding".into(), table([("codec", "json".into())]));
+            }
+            source
+        }
+        "syslog" => {
+            let mut source = component("syslog");
+            let mode = settings.get("mode").map_or("unix_udp", String::as_str);
+            match mode {
+                "tcp" | "udp" => {
+                    source.insert("mode".into(), mode.into());
+                    let address = listen_address(pipeline, &mut settings, 5140)?;
+                    source.insert("address".into(), address.into());
+                }
+                _ => {
+                    source.insert("mode".into(), "unix".into());
+                    let path = settings
+                        .get("path")
+                        .map_or("/tmp/fluent-bit.sock", String::as_str);
+                    source.insert("path".into(), path.into());
+                    if mode != "unix_tcp" {
+                        pipeline.unsupported(
+                            section.line,
+                            "the `syslog` input listens on a Unix datagram socket, which the `syslog` source doesn't, so it listens on a stream socket instead",
+                        );
+                    }
+                }
+            }
+            settings.ignore(&["parser"]);
+            source
+        }
+        "forward" => {
+            let mut source = component("fluent");
+            let address = listen_address(pipeline, &mut settings, 24224)?;
+            source.insert("address".into(), address.into());
+            source
+        }
+        "http" => {
+            let mut source = component("http");
+            let address = listen_address(pipeline, &mut settings, 9880)?;
+            source.insert("address".into(), address.into());
+            source.insert("decoding".into(), table([("codec", "json".into())]));
+            source
+        }
+        "systemd" => {
+            let mut source = component("journald");
+            let units = settings
+                .get_all("systemd_filter")
+                .into_iter()
+                .filter_map(|filter| filter.strip_prefix("_SYSTEMD_UNIT="))
+                .collect::<Vec<_>>();
+            if !units.is_empty() {
+                source.insert("include_units".into(), strings(&units));
+            }
+            if let Some(path) = settings.get("path") {
+                source.insert("journal_directory".into(), path.as_str().into());
+            }
+            settings.ignore(&["db", "read_from_tail"]);
+            source
+        }
+        _ => {
+            unsupported(pipeline, section, "input");
+            return None;
+        }
+    };
+    let tag = match settings.get("tag") {
+        Some(tag) => Some(tag.clone()),
+        // The records forwarded by clients keep the tag they were sent with.
+        None if plugin == "forward" => None,
+        None => Some(format!("{}.{}", plugin, index)),
+    };
+    let name = settings
+        .get("alias")
+        .map_or(plugin, String::as_str)
+        .to_owned();
+    settings.finish(pipeline);
+    Some((tag, pipeline.add_source(&name, source)))
+}
+
+fn filter(pipeline: &mut Pipeline, router: &mut Router, section: &Section) -> Result<(), String> {
+    let mut settings = section.settings("filter");
+    settings.ignore(&["name"]);
+    let pattern = tag_pattern(&mut settings)?;
+    let plugin = section.plugin();
+    let transform = match plugin {
+        "grep" => {
+            let mut conditions = Vec::new();
+            for (setting, negate) in [("regex", ""), ("exclude", "!")] {
+                for rule in settings.get_all(setting) {
+                    let (key, regex) = rule.split_once(char::is_whitespace).unwrap_or((rule, ""));
+                    conditions.push(format!(
+                        "{}match(to_string({}) ?? \"\", {})",
+                        negate,
+                        vrl_path(&[key]),
+                        vrl_regex(regex.trim())
+                    ));
+                }
+            }
+            let mut transform = component("filter");
+            transform.insert("condition".into(), conditions.join(" && ").into());
+            transform
+        }
+        "modify" | "record_modifier" => {
+            let mut statements = Vec::new();
+            for (setting, only_missing) in [("set", false), ("add", true), ("record", false)] {
+                for rule in settings.get_all(setting) {
+                    let (key, value) = rule.split_once(char::is_whitespace).unwrap_or((rule, ""));
+                    let field = vrl_path(&[key]);
+                    let value = vrl_string(value.trim());
+                    statements.push(if only_missing {
+                        format!("if !exists({}) {{ {} = {} }}", field, field, value)
+                    } else {
+                        format!("{} = {}", field, value)
+                    });
+                }
+            }
+            for rule in settings.get_all("rename") {
+                let (from, to) = rule.split_once(char::is_whitespace).unwrap_or((rule, ""));
+                statements.push(format!(
+                    "{} = del({})",
+                    vrl_path(&[to.trim()]),
+                    vrl_path(&[from])
+                ));
+            }
+            for setting in ["remove", "remove_key"] {
+                for key in settings.get_all(setting) {
+                    statements.push(format!("del({})", vrl_path(&[key])));
+                }
+            }
+            let mut transform = component("remap");
+            transform.insert("source".into(), statements.join("\n").into());
+            transform
+        }
+        "parser" => {
+            let key = settings.get("key_name").map_or("log", String::as_str);
+            let parsers = settings.get_all("parser");
+            if parsers.iter().any(|parser| parser.as_str() != "json") {
+                pipeline.unsupported(
+                    section.line,
+                    "only the `json` parser of the `parser` filter is converted",
+                );
+            }
+            let source = if is_on(settings.get("reserve_data")) {
+                format!(". |= object!(parse_json!({}))", vrl_path(&[key]))
+            } else {
+                format!(". = object!(parse_json!({}))", vrl_path(&[key]))
+            };
+            settings.ignore(&["preserve_key"]);
+            let mut transform = component("remap");
+            transform.insert("source".into(), source.into());
+            transform
+        }
+        _ => {
+            unsupported(pipeline, section, "filter");
+            return Ok(());
+        }
+    };
+    let name = settings
+        .get("alias")
+        .map_or(plugin, String::as_str)
+        .to_owned();
+    settings.finish(pipeline);
+
+    if router.mixes(&pattern) {
+        pipeline.unsupported(
+            section.line,
+            format!(
+                "the `{}` filter also gets the records of tags it doesn't match, as they come out of the same components",
+                plugin
+            ),
+        );
+    }
+    let inputs = router.inputs(&pattern);
+    let id = pipeline.add_transform(&name, &inputs, transform);
+    router.through(&pattern, &id);
+    Ok(())
+}
+
+fn output(pipeline: &mut Pipeline, router: &Router, section: &Section) -> Result<(), String> {
+    let mut settings = section.settings("output");
+    settings.ignore(&["name", "retry_limit", "workers"]);
+    let pattern = tag_pattern(&mut settings)?;
+    let plugin = section.plugin();
+    let host = settings.get("host").map_or("127.0.0.1", String::as_str);
+    let tls = is_on(settings.get("tls"));
+    settings.ignore(&["tls.verify"]);
+    let scheme = if tls { "https" } else { "http" };
+    let sink = match plugin {
+        "es" | "elasticsearch" => {
+            let mut sink = component("elasticsearch");
+            let port = settings.get("port").map_or("9200", String::as_str);
+            sink.insert(
+                "endpoint".into(),
+                format!("{}://{}:{}", scheme, host, port).into(),
+            );
+            let index = settings.get("index").map_or("fluent-bit", String::as_str);
+            sink.insert("bulk".into(), table([("index", index.into())]));
+            let user = settings.get("http_user");
+            let password = settings.get("http_passwd");
+            if let (Some(user), Some(password)) = (user, password) {
+                sink.insert(
+                    "auth".into(),
+                    table([
+                        ("strategy", "basic".into()),
+                        ("user", user.as_str().into()),
+                        ("password", password.as_str().into()),
+                    ]),
+                );
+            }
+            if let Some(pipeline_name) = settings.get("pipeline") {
+                sink.insert("pipeline".into(), pipeline_name.as_str().into());
+            }
+            settings.ignore(&["type", "suppress_type_name", "logstash_format"]);
+            sink
+        }
+        "stdout" => {
+            let mut sink = component("console");
+            sink.insert("encoding".into(), encoding("json"));
+            settings.ignore(&["format"]);
+            sink
+        }
+        "file" => {
+            let mut sink = component("file");
+            let directory = settings.get("path").map_or(".", String::as_str);
+            let file = settings
+                .get("file")
+                .map_or("fluent-bit.log", String::as_str);
+            sink.insert(
+                "path".into(),
+                format!("{}/{}", directory.trim_end_matches('/'), file).into(),
+            );
+            sink.insert("encoding".into(), encoding("json"));
+            settings.ignore(&["format"]);
+            sink
+        }
+        "http" => {
+            let mut sink = component("http");
+            let port = settings.get("port").map_or("80", String::as_str);
+            let uri = settings.get("uri").map_or("/", String::as_str);
+            sink.insert(
+                "uri".into(),
+                format!("{}://{}:{}{}", scheme, host, port, uri).into(),
+            );
+            match settings.get("format").map(String::as_str) {
+                None | Some("json" | "json_lines") => {}
+                Some(format) => pipeline.unsupported(
+                    section.line,
+                    format!(
+                        "the `{}` format of the `http` output isn't converted",
+                        format
+                    ),
+                ),
+            }
+            sink.insert("encoding".into(), encoding("json"));
+            sink
+        }
+        "kafka" => {
+            let mut sink = component("kafka");
+            let brokers = settings
+                .get("brokers")
+                .map_or("localhost:9092", String::as_str);
+            sink.insert("bootstrap_servers".into(), brokers.into());
+            let topics = settings.get("topics").map_or("fluent-bit", String::as_str);
+            let topic = topics.split(',').next().unwrap_or(topics).trim();
+            sink.insert("topic".into(), topic.into());
+            if topic != topics.trim() {
+                pipeline.unsupported(
+                    section.line,
+                    "only the first of the `Topics` of the `kafka` output is converted",
+                );
+            }
+            sink.insert("encoding".into(), encoding("json"));
+            settings.ignore(&["format"]);
+            sink
+        }
+        "loki" => {
+            let mut sink = component("loki");
+            let port = settings.get("port").map_or("3100", String::as_str);
+            sink.insert(
+                "endpoint".into(),
+                format!("{}://{}:{}", scheme, host, port).into(),
+            );
+            let mut labels = toml::map::Map::new();
+            for label in settings
+                .get_all("labels")
+                .into_iter()
+                .flat_map(|labels| labels.split(','))
+            {
+                if let Some((key, value)) = label.split_once('=') {
+                    let value = match value.trim().strip_prefix('$') {
+                        Some(field) => format!("{{{{ {} }}}}", field),
+                        None => value.trim().to_owned(),
+                    };
+                    labels.insert(key.trim().to_owned(), value.into());
+                }
+            }
+            sink.insert("labels".into(), toml::Value::Table(labels));
+            sink.insert("encoding".into(), encoding("json"));
+            sink
+        }
+        "s3" => {
+            let mut sink = component("aws_s3");
+            let bucket = settings.get("bucket").map_or("", String::as_str);
+            sink.insert("bucket".into(), bucket.into());
+            if let Some(region) = settings.get("region") {
+                sink.insert("region".into(), region.as_str().into());
+            }
+            sink.insert("encoding".into(), encoding("json"));
+            settings.ignore(&["total_file_size", "upload_timeout"]);
+            sink
+        }
+        "null" => component("blackhole"),
+        _ => {
+            unsupported(pipeline, section, "output");
+            return Ok(());
+        }
+    };
+    let name = settings
+        .get("alias")
+        .map_or(plugin, String::as_str)
+        .to_owned();
+    settings.finish(pipeline);
+
+    let inputs = router.inputs(&pattern);
+    if inputs.is_empty() {
+        pipeline.unsupported(
+            section.line,
+            format!("the `{}` output doesn't match the tag of any input", plugin),
+        );
+    } else if router.mixes(&pattern) {
+        pipeline.unsupported(
+            section.line,
+            format!(
+                "the `{}` output also gets the records of tags it doesn't match, as they come out of the same components",
+                plugin
+            ),
+        );
+    }
+    pipeline.add_sink(&name, &inputs, sink);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[SERVICE]
+    Flush        1
+    Log_Level    info
+
+[INPUT]
+    Name              tail
+    Path              /var/log/app/*.log
+    Tag               app.*
+    Read_from_Head    On
+    DB                /var/lib/fluent-bit/tail.db
+
+[INPUT]
+    Name              systemd
+    Tag               host.*
+    Systemd_Filter    _SYSTEMD_UNIT=docker.service
+
+[FILTER]
+    Name    parser
+    Match   app.*
+    Key_Name log
+    Parser  json
+    Reserve_Data On
+
+[FILTER]
+    Name    grep
+    Match   app.*
+    Exclude level debug
+
+[FILTER]
+    Name    lua
+    Match   *
+    script  enrich.lua
+
+[OUTPUT]
+    Name            es
+    Match           *
+    Host            es.example.com
+    Port            9243
+    tls             On
+    HTTP_User       elastic
+    HTTP_Passwd     changeme
+    Index           logs
+
+[OUTPUT]
+    Name    stdout
+    Match   host.*
+"#;
+
+    #[test]
+    fn converts_configs() {
+        let pipeline = convert(CONFIG).unwrap();
+
+        assert_eq!(
+            pipeline.sources.keys().collect::<Vec<_>>(),
+            vec!["tail", "systemd"]
+        );
+        assert_eq!(
+            pipeline.sources["tail"]["include"],
+            strings(&["/var/log/app/*.log"])
+        );
+        assert_eq!(pipeline.sources["tail"]["read_from"], "beginning".into());
+        assert_eq!(
+            pipeline.sources["systemd"]["include_units"],
+            strings(&["docker.service"])
+        );
+
+        assert_eq!(pipeline.transforms["parser"]["inputs"], strings(&["tail"]));
+        assert_eq!(
+            pipeline.transforms["parser"]["source"],
+            ". |= object!(parse_json!(.log))".into()
+        );
+        assert_eq!(pipeline.transforms["grep"]["inputs"], strings(&["parser"]));
+        assert_eq!(
+            pipeline.transforms["grep"]["condition"],
+            r#"!match(to_string(.level) ?? "", r'debug')"#.into()
+        );
+
+        let es = &pipeline.sinks["es"];
+        assert_eq!(es["inputs"], strings(&["grep", "systemd"]));
+        assert_eq!(es["endpoint"], "https://es.example.com:9243".into());
+        assert_eq!(es["bulk"]["index"], "logs".into());
+        assert_eq!(es["auth"]["user"], "elastic".into());
+        assert_eq!(pipeline.sinks["stdout"]["inputs"], strings(&["systemd"]));
+
+        let unsupported = pipeline
+            .unsupported
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unsupported,
+            vec!["line 30: the `lua` filter has no Vector equivalent, and was left out"]
+        );
+    }
+
+    #[test]
+    fn matches_tags() {
+        let pattern = |pattern: &str| {
+            let section = Section {
+                name: "OUTPUT".to_owned(),
+                line: 1,
+                entries: vec![("Match".to_owned(), pattern.to_owned())],
+            };
+            tag_pattern(&mut section.settings("output")).unwrap()
+        };
+        assert!(pattern("*").is_match("app.web"));
+        assert!(pattern("app.*").is_match("app.web.1"));
+        assert!(!pattern("app.*").is_match("host.web"));
+        assert!(pattern("kube.var.log").is_match("kube.var.log"));
+        assert!(!pattern("kube.var.log").is_match("kube_var_log"));
+    }
+
+    #[test]
+    fn rejects_entries_outside_sections() {
+        assert!(convert("Name tail\n").is_err());
+    }
+}
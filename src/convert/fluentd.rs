@@ -0,0 +1,845 @@
+//! Conversion of Fluentd configurations.
+//!
+//! See <https://docs.fluentd.org/configuration/config-file>.
+
+use regex::Regex;
+
+use super::{
+    component, encoding, port, strings, table, vrl_path, vrl_regex, vrl_string, Component,
+    Pipeline, Router, Settings,
+};
+
+#[derive(Debug, Default)]
+struct Directive {
+    name: String,
+    /// The argument of the directive, such as the tag pattern of a `<match>`.
+    arg: String,
+    line: usize,
+    params: Vec<(String, String)>,
+    children: Vec<Directive>,
+}
+
+impl Directive {
+    fn settings(&self, kind: &'static str) -> Settings<'_, String> {
+        let mut settings = Settings::new(
+            kind,
+            self.plugin(),
+            self.line,
+            self.params.iter().map(|(key, value)| (key.as_str(), value)),
+        );
+        settings.ignore(&["@type", "@log_level"]);
+        settings
+    }
+
+    /// The name of the plugin of the directive.
+    fn plugin(&self) -> &str {
+        self.param("@type").unwrap_or("")
+    }
+
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .rev()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Directive> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// Reports the sections of the directive that weren't converted.
+    fn finish_children(&self, pipeline: &mut Pipeline, kind: &str, converted: &[&str]) {
+        for child in &self.children {
+            if !converted.contains(&child.name.as_str()) {
+                pipeline.unsupported(
+                    child.line,
+                    format!(
+                        "the `<{}>` section of the `{}` {} isn't converted",
+                        child.name,
+                        self.plugin(),
+                        kind
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(value) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return value;
+        }
+    }
+    value
+}
+
+fn parse(config: &str, pipeline: &mut Pipeline) -> Result<Vec<Directive>, String> {
+    let mut stack = vec![Directive::default()];
+    for (i, line) in config.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix("</")
+            .and_then(|line| line.strip_suffix('>'))
+        {
+            let directive = stack.pop().filter(|_| !stack.is_empty());
+            match directive {
+                Some(directive) if directive.name == name.trim() => stack
+                    .last_mut()
+                    .expect("the stack keeps its root")
+                    .children
+                    .push(directive),
+                _ => return Err(format!("line {}: unexpected `</{}>`", line_number, name)),
+            }
+        } else if let Some(directive) = line
+            .strip_prefix('<')
+            .and_then(|line| line.strip_suffix('>'))
+        {
+            let (name, arg) = directive
+                .split_once(char::is_whitespace)
+                .unwrap_or((directive, ""));
+            stack.push(Directive {
+                name: name.to_owned(),
+                arg: arg.trim().to_owned(),
+                line: line_number,
+                ..Directive::default()
+            });
+        } else if line.starts_with("@include") {
+            pipeline.unsupported(
+                line_number,
+                format!("the `{}` directive isn't converted", line),
+            );
+        } else if stack.len() == 1 {
+            return Err(format!("line {}: expected a directive", line_number));
+        } else {
+            let (key, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            stack
+                .last_mut()
+                .expect("the stack keeps its root")
+                .params
+                .push((key.to_owned(), unquote(value.trim()).to_owned()));
+        }
+    }
+    match stack.pop() {
+        Some(root) if stack.is_empty() => Ok(root.children),
+        _ => Err("unterminated directive".to_owned()),
+    }
+}
+
+/// Converts the tag patterns of a `<filter>` or `<match>` directive to a regex matching tags.
+///
+/// See <https://docs.fluentd.org/configuration/config-file#how-do-the-match-patterns-work>.
+fn tag_pattern(patterns: &str) -> Result<Regex, String> {
+    fn convert(pattern: &str) -> Result<String, String> {
+        let mut regex = String::new();
+        let mut rest = pattern;
+        while let Some(c) = rest.chars().next() {
+            if let Some(after) = rest.strip_prefix(".**") {
+                regex.push_str(r"(\..*)?");
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("**") {
+                regex.push_str(".*");
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('*') {
+                regex.push_str("[^.]+");
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('{') {
+                let end = after
+                    .find('}')
+                    .ok_or_else(|| format!("unterminated `{{` in `{}`", pattern))?;
+                let alternatives = after[..end]
+                    .split(',')
+                    .map(convert)
+                    .collect::<Result<Vec<_>, _>>()?;
+                regex.push_str(&format!("(?:{})", alternatives.join("|")));
+                rest = &after[end + 1..];
+            } else {
+                regex.push_str(&regex::escape(&c.to_string()));
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        Ok(regex)
+    }
+
+    let patterns = patterns
+        .split_whitespace()
+        .map(convert)
+        .collect::<Result<Vec<_>, _>>()?;
+    Regex::new(&format!("^(?:{})$", patterns.join("|"))).map_err(|error| error.to_string())
+}
+
+pub(super) fn convert(config: &str) -> Result<Pipeline, String> {
+    let mut pipeline = Pipeline::default();
+    let directives = parse(config, &mut pipeline)?;
+
+    let mut router = Router::default();
+    for directive in &directives {
+        match directive.name.as_str() {
+            "source" => {
+                if let Some((tag, id)) = source(&mut pipeline, directive) {
+                    router.add(tag, &id);
+                }
+            }
+            "filter" => filter(&mut pipeline, &mut router, directive)?,
+            "match" => output(&mut pipeline, &mut router, directive)?,
+            "system" => {}
+            name => pipeline.unsupported(
+                directive.line,
+                format!(
+                    "the `<{}>` directive isn't converted, nor are the events routed to it",
+                    name
+                ),
+            ),
+        }
+    }
+    Ok(pipeline)
+}
+
+fn unsupported(pipeline: &mut Pipeline, directive: &Directive, kind: &str) {
+    pipeline.unsupported(
+        directive.line,
+        format!(
+            "the `{}` {} has no Vector equivalent, and was left out",
+            directive.plugin(),
+            kind
+        ),
+    );
+}
+
+/// The address to listen on, from the `bind` and `port` parameters.
+fn listen_address(
+    pipeline: &mut Pipeline,
+    settings: &mut Settings<String>,
+    default_port: u16,
+) -> Option<String> {
+    let host = settings.get("bind").map_or("0.0.0.0", String::as_str);
+    let port = match settings.get("port") {
+        Some(value) => port(pipeline, settings.line, value)?,
+        None => default_port,
+    };
+    Some(format!("{}:{}", host, port))
+}
+
+fn is_true(value: Option<&String>) -> bool {
+    value.map(String::as_str) == Some("true")
+}
+
+/// Splits a list parameter, given either comma separated or as a JSON array.
+fn list(value: &str) -> Vec<String> {
+    match serde_json::from_str::<Vec<String>>(value) {
+        Ok(values) => values,
+        Err(_) => value
+            .split(',')
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+            .collect(),
+    }
+}
+
+/// The VRL statement parsing a field as the `<parse>` section of a directive does, if it
+/// parses at all.
+fn parse_statement(
+    pipeline: &mut Pipeline,
+    directive: &Directive,
+    field: &str,
+    keep_fields: bool,
+) -> Option<String> {
+    let parse = directive.children("parse").next()?;
+    let mut settings = parse.settings("parser");
+    let field = vrl_path(&[field]);
+    let parsed = match parse.plugin() {
+        "none" => return None,
+        "json" => format!("object!(parse_json!({}))", field),
+        "regexp" => {
+            let expression = settings.get("expression").map_or("", String::as_str);
+            let expression = expression
+                .strip_prefix('/')
+                .and_then(|expression| expression.strip_suffix('/'))
+                .unwrap_or(expression);
+            // Ruby writes named groups without the `P` that VRL needs.
+            let expression = expression.replace("(?<", "(?P<");
+            format!("parse_regex!({}, {})", field, vrl_regex(&expression))
+        }
+        "nginx" => format!("parse_nginx_log!({}, \"combined\")", field),
+        "apache2" => format!("parse_apache_log!({}, \"combined\")", field),
+        "syslog" => format!("parse_syslog!({})", field),
+        "ltsv" => format!(
+            "parse_key_value!({}, key_value_delimiter: \":\", field_delimiter: \"\\t\")",
+            field
+        ),
+        _ => {
+            unsupported(pipeline, parse, "parser");
+            return None;
+        }
+    };
+    settings.ignore(&["time_key", "time_format", "keep_time_key"]);
+    settings.finish(pipeline);
+    Some(if keep_fields {
+        format!(". |= {}", parsed)
+    } else {
+        format!(". = {}", parsed)
+    })
+}
+
+/// Converts a source, returning its tag, unless it's the one clients send, along with the ID
+/// of the component its records come out of.
+fn source<'a>(
+    pipeline: &mut Pipeline,
+    directive: &'a Directive,
+) -> Option<(Option<&'a str>, String)> {
+    let mut settings = directive.settings("input");
+    let plugin = directive.plugin();
+    let mut parses = false;
+    let source = match plugin {
+        "tail" => {
+            let mut source = component("file");
+            let include = settings
+                .get("path")
+                .map(|path| list(path))
+                .unwrap_or_default();
+            source.insert("include".into(), strings(&include));
+            if let Some(exclude) = settings.get("exclude_path") {
+                source.insert("exclude".into(), strings(&list(exclude)));
+            }
+            if is_true(settings.get("read_from_head")) {
+                source.insert("read_from".into(), "beginning".into());
+            }
+            settings.ignore(&["pos_file", "refresh_interval"]);
+            parses = true;
+            source
+        }
+        "forward" => {
+            let mut source = component("fluent");
+            let address = listen_address(pipeline, &mut settings, 24224)?;
+            source.insert("address".into(), address.into());
+            source
+        }
+        "http" => {
+            let mut source = component("http");
+            let address = listen_address(pipeline, &mut settings, 9880)?;
+            source.insert("address".into(), address.into());
+            source.insert("decoding".into(), table([("codec", "json".into())]));
+            source
+        }
+        "syslog" => {
+            let mut source = component("syslog");
+            let transport = directive
+                .children("transport")
+                .next()
+                .map(|transport| transport.arg.as_str())
+                .or_else(|| settings.get("protocol_type").map(String::as_str))
+                .unwrap_or("udp");
+            source.insert("mode".into(), transport.into());
+            let address = listen_address(pipeline, &mut settings, 5140)?;
+            source.insert("address".into(), address.into());
+            source
+        }
+        "tcp" | "udp" => {
+            let mut source = component("socket");
+            source.insert("mode".into(), plugin.into());
+            let address = listen_address(pipeline, &mut settings, 5170)?;
+            source.insert("address".into(), address.into());
+            parses = true;
+            source
+        }
+        "systemd" => {
+            let mut source = component("journald");
+            if let Some(matches) = settings.get("matches") {
+                let unit = Regex::new(r#""_SYSTEMD_UNIT"\s*:\s*"([^"]+)""#).expect("valid regex");
+                let units = unit
+                    .captures_iter(matches)
+                    .map(|captures| captures[1].to_owned())
+                    .collect::<Vec<_>>();
+                source.insert("include_units".into(), strings(&units));
+            }
+            if let Some(path) = settings.get("path") {
+                source.insert("journal_directory".into(), path.as_str().into());
+            }
+            settings.ignore(&["read_from_head"]);
+            source
+        }
+        _ => {
+            unsupported(pipeline, directive, "input");
+            return None;
+        }
+    };
+    let tag = settings.get("tag").map(String::as_str);
+    let name = settings.get("@id").map_or(plugin, String::as_str);
+    settings.finish(pipeline);
+    let mut id = pipeline.add_source(name, source);
+    if parses {
+        if let Some(statement) = parse_statement(pipeline, directive, "message", false) {
+            let mut transform = component("remap");
+            transform.insert("source".into(), statement.into());
+            id = pipeline.add_transform(&format!("{}_parse", id), &[id], transform);
+        }
+        directive.finish_children(pipeline, "input", &["parse"]);
+    } else {
+        directive.finish_children(pipeline, "input", &["transport"]);
+    }
+    // Clients tag the records they send, and syslog messages are tagged with their facility and
+    // priority after the tag of the source.
+    let known = !matches!(plugin, "forward" | "http" | "syslog");
+    Some((tag.filter(|_| known), id))
+}
+
+/// Converts a value of `record_transformer`, in which `${...}` placeholders are replaced, to a
+/// VRL expression.
+fn vrl_placeholders(value: &str) -> Option<String> {
+    let field = Regex::new(r#"^record\[["']([^"']+)["']\]$"#).expect("valid regex");
+    let mut parts = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        if start > 0 {
+            parts.push(vrl_string(&rest[..start]));
+        }
+        let end = rest[start..].find('}')? + start;
+        let placeholder = &rest[start + 2..end];
+        parts.push(match placeholder {
+            "hostname" => "get_hostname!()".to_owned(),
+            "time" => "(to_string(.timestamp) ?? \"\")".to_owned(),
+            placeholder => {
+                let captures = field.captures(placeholder)?;
+                format!("(to_string({}) ?? \"\")", vrl_path(&[&captures[1]]))
+            }
+        });
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() || parts.is_empty() {
+        parts.push(vrl_string(rest));
+    }
+    Some(parts.join(" + "))
+}
+
+fn filter(
+    pipeline: &mut Pipeline,
+    router: &mut Router,
+    directive: &Directive,
+) -> Result<(), String> {
+    let pattern = tag_pattern(&directive.arg)?;
+    let mut settings = directive.settings("filter");
+    let plugin = directive.plugin();
+    let (transform, children): (Component, &[&str]) = match plugin {
+        "grep" => {
+            let mut conditions = Vec::new();
+            for (section, negate) in [("regexp", ""), ("exclude", "!")] {
+                for rule in directive.children(section) {
+                    let key = rule.param("key").unwrap_or("message");
+                    let regex = rule.param("pattern").unwrap_or("");
+                    let regex = regex
+                        .strip_prefix('/')
+                        .and_then(|regex| regex.strip_suffix('/'))
+                        .unwrap_or(regex);
+                    conditions.push(format!(
+                        "{}match(to_string({}) ?? \"\", {})",
+                        negate,
+                        vrl_path(&[key]),
+                        vrl_regex(regex)
+                    ));
+                }
+            }
+            let mut transform = component("filter");
+            transform.insert("condition".into(), conditions.join(" && ").into());
+            (transform, &["regexp", "exclude"])
+        }
+        "record_transformer" | "record_modifier" => {
+            let mut statements = Vec::new();
+            for record in directive.children("record") {
+                for (key, value) in &record.params {
+                    match vrl_placeholders(value) {
+                        Some(value) => {
+                            statements.push(format!("{} = {}", vrl_path(&[key]), value));
+                        }
+                        None => pipeline.unsupported(
+                            record.line,
+                            format!("the value of the `{}` field isn't converted", key),
+                        ),
+                    }
+                }
+            }
+            if let Some(keys) = settings.get("remove_keys") {
+                for key in list(keys) {
+                    statements.push(format!("del({})", vrl_path(&[key])));
+                }
+            }
+            let mut transform = component("remap");
+            transform.insert("source".into(), statements.join("\n").into());
+            (transform, &["record"])
+        }
+        "parser" => {
+            let key = settings.get("key_name").map_or("message", String::as_str);
+            let reserve_data = is_true(settings.get("reserve_data"));
+            let statement = match parse_statement(pipeline, directive, key, reserve_data) {
+                Some(statement) => statement,
+                None => return Ok(()),
+            };
+            let mut transform = component("remap");
+            transform.insert("source".into(), statement.into());
+            (transform, &["parse"])
+        }
+        _ => {
+            unsupported(pipeline, directive, "filter");
+            return Ok(());
+        }
+    };
+    let name = settings
+        .get("@id")
+        .map_or(plugin, String::as_str)
+        .to_owned();
+    settings.finish(pipeline);
+    directive.finish_children(pipeline, "filter", children);
+
+    if router.mixes(&pattern) {
+        pipeline.unsupported(
+            directive.line,
+            format!(
+                "the `{}` filter also gets the events of tags it doesn't match, as they come out of the same components",
+                plugin
+            ),
+        );
+    }
+    let inputs = router.inputs(&pattern);
+    let id = pipeline.add_transform(&name, &inputs, transform);
+    router.through(&pattern, &id);
+    Ok(())
+}
+
+fn output(
+    pipeline: &mut Pipeline,
+    router: &mut Router,
+    directive: &Directive,
+) -> Result<(), String> {
+    let pattern = tag_pattern(&directive.arg)?;
+    let inputs = router.inputs(&pattern);
+    if router.mixes(&pattern) {
+        pipeline.unsupported(
+            directive.line,
+            format!(
+                "the `{}` output also gets the events of tags it doesn't match, as they come out of the same components",
+                directive.plugin()
+            ),
+        );
+    }
+    // The events are routed to the first output they match.
+    router.remove(&pattern);
+
+    if directive.plugin() == "copy" {
+        for store in directive.children("store") {
+            sink(pipeline, store, &inputs);
+        }
+        directive.finish_children(pipeline, "output", &["store"]);
+    } else {
+        sink(pipeline, directive, &inputs);
+    }
+    Ok(())
+}
+
+fn sink(pipeline: &mut Pipeline, directive: &Directive, inputs: &[String]) {
+    let mut settings = directive.settings("output");
+    let plugin = directive.plugin();
+    let (sink, children): (Component, &[&str]) = match plugin {
+        "stdout" => {
+            let mut sink = component("console");
+            sink.insert("encoding".into(), encoding("json"));
+            (sink, &[])
+        }
+        "file" => {
+            let mut sink = component("file");
+            let path = settings.get("path").map_or("", String::as_str);
+            sink.insert("path".into(), format!("{}.%Y%m%d.log", path).into());
+            sink.insert("encoding".into(), encoding("json"));
+            settings.ignore(&["append"]);
+            (sink, &["buffer", "format"])
+        }
+        "elasticsearch" => {
+            let mut sink = component("elasticsearch");
+            let scheme = settings.get("scheme").map_or("http", String::as_str);
+            let endpoint = match settings.get("hosts") {
+                Some(hosts) => {
+                    let hosts = list(hosts);
+                    if hosts.len() > 1 {
+                        pipeline.unsupported(
+                            directive.line,
+                            "only the first of the `hosts` of the `elasticsearch` output is converted",
+                        );
+                    }
+                    let host = hosts.into_iter().next().unwrap_or_default();
+                    if host.contains("://") {
+                        host
+                    } else {
+                        format!("{}://{}", scheme, host)
+                    }
+                }
+                None => {
+                    let host = settings.get("host").map_or("localhost", String::as_str);
+                    let port = settings.get("port").map_or("9200", String::as_str);
+                    format!("{}://{}:{}", scheme, host, port)
+                }
+            };
+            sink.insert("endpoint".into(), endpoint.into());
+            let index = if is_true(settings.get("logstash_format")) {
+                let prefix = settings
+                    .get("logstash_prefix")
+                    .map_or("logstash", String::as_str);
+                format!("{}-%Y.%m.%d", prefix)
+            } else {
+                settings
+                    .get("index_name")
+                    .map_or("fluentd", String::as_str)
+                    .to_owned()
+            };
+            sink.insert("bulk".into(), table([("index", index.into())]));
+            let user = settings.get("user");
+            let password = settings.get("password");
+            if let (Some(user), Some(password)) = (user, password) {
+                sink.insert(
+                    "auth".into(),
+                    table([
+                        ("strategy", "basic".into()),
+                        ("user", user.as_str().into()),
+                        ("password", password.as_str().into()),
+                    ]),
+                );
+            }
+            if let Some(pipeline_name) = settings.get("pipeline") {
+                sink.insert("pipeline".into(), pipeline_name.as_str().into());
+            }
+            if let Some(ca_file) = settings.get("ca_file") {
+                sink.insert("tls".into(), table([("ca_file", ca_file.as_str().into())]));
+            }
+            settings.ignore(&["include_tag_key", "type_name", "ssl_verify"]);
+            (sink, &["buffer"])
+        }
+        "http" => {
+            let mut sink = component("http");
+            let endpoint = settings.get("endpoint").map_or("", String::as_str);
+            sink.insert("uri".into(), endpoint.into());
+            if let Some(method) = settings.get("http_method") {
+                sink.insert("method".into(), method.as_str().into());
+            }
+            sink.insert("encoding".into(), encoding("json"));
+            settings.ignore(&["open_timeout", "json_array"]);
+            (sink, &["buffer", "format"])
+        }
+        "kafka" | "kafka2" | "kafka_buffered" => {
+            let mut sink = component("kafka");
+            let brokers = settings
+                .get("brokers")
+                .map_or("localhost:9092", String::as_str);
+            sink.insert("bootstrap_servers".into(), brokers.into());
+            let topic = settings.get("default_topic").map_or("", String::as_str);
+            sink.insert("topic".into(), topic.into());
+            sink.insert("encoding".into(), encoding("json"));
+            (sink, &["buffer", "format"])
+        }
+        "s3" => {
+            let mut sink = component("aws_s3");
+            let bucket = settings.get("s3_bucket").map_or("", String::as_str);
+            sink.insert("bucket".into(), bucket.into());
+            if let Some(region) = settings.get("s3_region") {
+                sink.insert("region".into(), region.as_str().into());
+            }
+            if let Some(path) = settings.get("path") {
+                sink.insert("key_prefix".into(), path.as_str().into());
+            }
+            sink.insert("encoding".into(), encoding("json"));
+            settings.ignore(&["aws_key_id", "aws_sec_key"]);
+            (sink, &["buffer", "format"])
+        }
+        "loki" => {
+            let mut sink = component("loki");
+            let url = settings.get("url").map_or("", String::as_str);
+            sink.insert("endpoint".into(), url.into());
+            let mut labels = toml::map::Map::new();
+            for label in directive.children("label") {
+                for (key, value) in &label.params {
+                    let field = if value.is_empty() { key } else { value };
+                    labels.insert(key.clone(), format!("{{{{ {} }}}}", field).into());
+                }
+            }
+            sink.insert("labels".into(), toml::Value::Table(labels));
+            sink.insert("encoding".into(), encoding("json"));
+            (sink, &["buffer", "label"])
+        }
+        "null" => (component("blackhole"), &[]),
+        _ => {
+            unsupported(pipeline, directive, "output");
+            return;
+        }
+    };
+    let name = settings
+        .get("@id")
+        .map_or(plugin, String::as_str)
+        .to_owned();
+    settings.finish(pipeline);
+    // Vector buffers the events of sinks in its own way, so the buffers of outputs aren't
+    // reported as left out.
+    directive.finish_children(pipeline, "output", children);
+
+    if inputs.is_empty() {
+        pipeline.unsupported(
+            directive.line,
+            format!("the `{}` output doesn't match the tag of any input", plugin),
+        );
+    }
+    pipeline.add_sink(&name, inputs, sink);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+<source>
+  @type tail
+  path /var/log/app/*.log
+  pos_file /var/log/td-agent/app.pos
+  tag app.web
+  <parse>
+    @type json
+  </parse>
+</source>
+
+<source>
+  @type forward
+  port 24224
+</source>
+
+<filter app.**>
+  @type record_transformer
+  <record>
+    hostname "${hostname}"
+    service web-${record["kind"]}
+  </record>
+  remove_keys secret,token
+</filter>
+
+<match app.web>
+  @type copy
+  <store>
+    @type elasticsearch
+    host es.example.com
+    logstash_format true
+    <buffer>
+      flush_interval 5s
+    </buffer>
+  </store>
+  <store>
+    @type stdout
+  </store>
+</match>
+
+<match **>
+  @type forward
+  <server>
+    host aggregator
+  </server>
+</match>
+"#;
+
+    #[test]
+    fn parses_directives() {
+        let mut pipeline = Pipeline::default();
+        let directives = parse(CONFIG, &mut pipeline).unwrap();
+        assert_eq!(
+            directives
+                .iter()
+                .map(|directive| (directive.name.as_str(), directive.arg.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("source", ""),
+                ("source", ""),
+                ("filter", "app.**"),
+                ("match", "app.web"),
+                ("match", "**"),
+            ]
+        );
+        assert_eq!(
+            directives[2].children[0].param("hostname"),
+            Some("${hostname}")
+        );
+
+        assert!(parse("<source>\n@type tail\n", &mut pipeline).is_err());
+        assert!(parse("<source>\n</match>\n", &mut pipeline).is_err());
+    }
+
+    #[test]
+    fn converts_configs() {
+        let pipeline = convert(CONFIG).unwrap();
+
+        assert_eq!(
+            pipeline.sources.keys().collect::<Vec<_>>(),
+            vec!["tail", "forward"]
+        );
+        assert_eq!(
+            pipeline.sources["forward"]["address"],
+            "0.0.0.0:24224".into()
+        );
+        assert_eq!(
+            pipeline.transforms["tail_parse"]["source"],
+            ". = object!(parse_json!(.message))".into()
+        );
+
+        let record_transformer = &pipeline.transforms["record_transformer"];
+        assert_eq!(
+            record_transformer["inputs"],
+            strings(&["tail_parse", "forward"])
+        );
+        assert_eq!(
+            record_transformer["source"],
+            [
+                ".hostname = get_hostname!()",
+                r#".service = "web-" + (to_string(.kind) ?? "")"#,
+                "del(.secret)",
+                "del(.token)",
+            ]
+            .join("\n")
+            .into()
+        );
+
+        let elasticsearch = &pipeline.sinks["elasticsearch"];
+        assert_eq!(elasticsearch["inputs"], strings(&["record_transformer"]));
+        assert_eq!(
+            elasticsearch["endpoint"],
+            "http://es.example.com:9200".into()
+        );
+        assert_eq!(elasticsearch["bulk"]["index"], "logstash-%Y.%m.%d".into());
+        assert_eq!(
+            pipeline.sinks["stdout"]["inputs"],
+            strings(&["record_transformer"])
+        );
+
+        let unsupported = pipeline
+            .unsupported
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unsupported,
+            vec!["line 41: the `forward` output has no Vector equivalent, and was left out"]
+        );
+    }
+
+    #[test]
+    fn matches_tags() {
+        let matches = |pattern: &str, tag: &str| tag_pattern(pattern).unwrap().is_match(tag);
+        assert!(matches("app.*", "app.web"));
+        assert!(!matches("app.*", "app.web.1"));
+        assert!(!matches("app.*", "app"));
+        assert!(matches("app.**", "app"));
+        assert!(matches("app.**", "app.web.1"));
+        assert!(matches("**", "anything.at.all"));
+        assert!(matches("app.{web,db}", "app.db"));
+        assert!(!matches("app.{web,db}", "app.cache"));
+        assert!(matches("app.web system.*", "system.auth"));
+        assert!(tag_pattern("app.{web").is_err());
+    }
+}
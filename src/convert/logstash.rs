@@ -0,0 +1,1254 @@
+//! Conversion of Logstash pipelines.
+//!
+//! See <https://www.elastic.co/guide/en/logstash/current/configuration-file-structure.html>.
+
+use toml::Value as TomlValue;
+
+use super::{
+    component, encoding, port, strings, table, vrl_path, vrl_regex, vrl_string, Component,
+    Pipeline, Settings,
+};
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    String(String),
+    Number(String),
+    Bareword(String),
+    Array(Vec<Value>),
+    Hash(Vec<(String, Value)>),
+    /// A plugin given as the value of a setting, such as a codec with settings of its own.
+    Plugin(String, Vec<(String, Value)>),
+}
+
+impl Value {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) | Value::Number(s) | Value::Bareword(s) | Value::Plugin(s, _) => {
+                Some(s)
+            }
+            Value::Array(_) | Value::Hash(_) => None,
+        }
+    }
+
+    /// The strings of an array, or the string of a single value.
+    fn as_strs(&self) -> Vec<&str> {
+        match self {
+            Value::Array(values) => values.iter().filter_map(Value::as_str).collect(),
+            value => value.as_str().into_iter().collect(),
+        }
+    }
+
+    /// The entries of a hash, or the pairs of an array, which Logstash accepts in place of hashes.
+    fn as_pairs(&self) -> Vec<(&str, &Value)> {
+        match self {
+            Value::Hash(entries) => entries
+                .iter()
+                .map(|(key, value)| (key.as_str(), value))
+                .collect(),
+            Value::Array(values) => values
+                .chunks_exact(2)
+                .filter_map(|pair| Some((pair[0].as_str()?, &pair[1])))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn as_bool(&self) -> bool {
+        self.as_str() == Some("true")
+    }
+}
+
+#[derive(Debug)]
+struct Plugin {
+    name: String,
+    line: usize,
+    settings: Vec<(String, Value)>,
+}
+
+impl Plugin {
+    fn settings(&self, kind: &'static str) -> Settings<'_, Value> {
+        Settings::new(
+            kind,
+            &self.name,
+            self.line,
+            self.settings
+                .iter()
+                .map(|(key, value)| (key.as_str(), value)),
+        )
+    }
+}
+
+#[derive(Debug)]
+enum Item {
+    Plugin(Plugin),
+    Conditional { line: usize },
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    inputs: Vec<Item>,
+    filters: Vec<Item>,
+    outputs: Vec<Item>,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn new(config: &str) -> Self {
+        Self {
+            chars: config.chars().collect(),
+            pos: 0,
+            line: 1,
+        }
+    }
+
+    fn error<T>(&self, message: impl AsRef<str>) -> Result<T, String> {
+        Err(format!("line {}: {}", self.line, message.as_ref()))
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.get(self.pos).copied()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    /// Skips whitespace and comments, then peeks at the next character.
+    fn peek(&mut self) -> Option<char> {
+        while let Some(&c) = self.chars.get(self.pos) {
+            if c == '#' {
+                while !matches!(self.chars.get(self.pos), None | Some('\n')) {
+                    self.bump();
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                return Some(c);
+            }
+        }
+        None
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let eaten = self.peek() == Some(c);
+        if eaten {
+            self.bump();
+        }
+        eaten
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            self.error(format!("expected `{}`", c))
+        }
+    }
+
+    fn expect_arrow(&mut self) -> Result<(), String> {
+        if self.eat('=') && self.bump() == Some('>') {
+            Ok(())
+        } else {
+            self.error("expected `=>`")
+        }
+    }
+
+    fn bareword(&mut self) -> Result<String, String> {
+        self.peek();
+        let mut word = String::new();
+        while let Some(&c) = self.chars.get(self.pos) {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '@') {
+                word.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        if word.is_empty() {
+            self.error("expected a name")
+        } else {
+            Ok(word)
+        }
+    }
+
+    /// Peeks at the next bareword without consuming it.
+    fn peek_bareword(&mut self) -> Option<String> {
+        let (pos, line) = (self.pos, self.line);
+        let word = self.bareword().ok();
+        self.pos = pos;
+        self.line = line;
+        word
+    }
+
+    /// Reads a quoted string. Escapes are kept as they are, as Logstash does by default, which
+    /// keeps the backslashes of regular expressions.
+    fn string(&mut self) -> Result<String, String> {
+        let quote = self.bump().expect("string starts with a quote");
+        let mut string = String::new();
+        loop {
+            match self.bump() {
+                None => return self.error("unterminated string"),
+                Some('\\') => {
+                    match self.bump() {
+                        Some(c) if c == quote => {}
+                        Some(c) => {
+                            string.push('\\');
+                            string.push(c);
+                            continue;
+                        }
+                        None => return self.error("unterminated string"),
+                    }
+                    string.push(quote);
+                }
+                Some(c) if c == quote => return Ok(string),
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    fn key(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some('"' | '\'') => self.string(),
+            _ => self.bareword(),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some('"' | '\'') => self.string().map(Value::String),
+            Some('[') => {
+                self.bump();
+                let mut values = Vec::new();
+                while !self.eat(']') {
+                    values.push(self.value()?);
+                    if !self.eat(',') && self.peek() != Some(']') {
+                        return self.error("expected `,` or `]`");
+                    }
+                }
+                Ok(Value::Array(values))
+            }
+            Some('{') => self.settings().map(Value::Hash),
+            _ => {
+                let word = self.bareword()?;
+                if self.peek() == Some('{') {
+                    Ok(Value::Plugin(word, self.settings()?))
+                } else if word.parse::<f64>().is_ok() {
+                    Ok(Value::Number(word))
+                } else {
+                    Ok(Value::Bareword(word))
+                }
+            }
+        }
+    }
+
+    /// Reads the settings of a plugin, or the entries of a hash.
+    fn settings(&mut self) -> Result<Vec<(String, Value)>, String> {
+        self.expect('{')?;
+        let mut settings = Vec::new();
+        while !self.eat('}') {
+            let key = self.key()?;
+            self.expect_arrow()?;
+            settings.push((key, self.value()?));
+            self.eat(',');
+        }
+        Ok(settings)
+    }
+
+    /// Skips the condition of a conditional, up to its block.
+    fn skip_condition(&mut self) -> Result<(), String> {
+        loop {
+            match self.peek() {
+                None => return self.error("expected `{`"),
+                Some('{') => return Ok(()),
+                Some('"' | '\'') => {
+                    self.string()?;
+                }
+                Some(_) => {
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    /// Skips a block along with the blocks nested in it.
+    fn skip_block(&mut self) -> Result<(), String> {
+        self.expect('{')?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.peek() {
+                None => return self.error("expected `}`"),
+                Some('"' | '\'') => {
+                    self.string()?;
+                }
+                Some(c) => {
+                    self.bump();
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn items(&mut self) -> Result<Vec<Item>, String> {
+        self.expect('{')?;
+        let mut items = Vec::new();
+        while !self.eat('}') {
+            let line = self.line;
+            let name = self.bareword()?;
+            if name == "if" {
+                self.skip_condition()?;
+                self.skip_block()?;
+                while self.peek_bareword().as_deref() == Some("else") {
+                    self.bareword()?;
+                    if self.peek_bareword().as_deref() == Some("if") {
+                        self.bareword()?;
+                        self.skip_condition()?;
+                    }
+                    self.skip_block()?;
+                }
+                items.push(Item::Conditional { line });
+            } else {
+                let settings = self.settings()?;
+                items.push(Item::Plugin(Plugin {
+                    name,
+                    line,
+                    settings,
+                }));
+            }
+        }
+        Ok(items)
+    }
+
+    fn config(mut self) -> Result<Config, String> {
+        let mut config = Config::default();
+        while self.peek().is_some() {
+            let section = self.bareword()?;
+            let items = self.items()?;
+            match section.as_str() {
+                "input" => config.inputs.extend(items),
+                "filter" => config.filters.extend(items),
+                "output" => config.outputs.extend(items),
+                section => return self.error(format!("unknown section `{}`", section)),
+            }
+        }
+        Ok(config)
+    }
+}
+
+pub(super) fn convert(config: &str) -> Result<Pipeline, String> {
+    let config = Parser::new(config).config()?;
+    let mut pipeline = Pipeline::default();
+
+    let mut inputs = Vec::new();
+    for item in &config.inputs {
+        match item {
+            Item::Plugin(plugin) => inputs.extend(input(&mut pipeline, plugin)),
+            Item::Conditional { line } => conditional(&mut pipeline, *line),
+        }
+    }
+    for item in &config.filters {
+        match item {
+            Item::Plugin(plugin) => {
+                if let Some(filter) = filter(&mut pipeline, plugin, &inputs) {
+                    inputs = vec![filter];
+                }
+            }
+            Item::Conditional { line } => conditional(&mut pipeline, *line),
+        }
+    }
+    for item in &config.outputs {
+        match item {
+            Item::Plugin(plugin) => output(&mut pipeline, plugin, &inputs),
+            Item::Conditional { line } => conditional(&mut pipeline, *line),
+        }
+    }
+    Ok(pipeline)
+}
+
+fn conditional(pipeline: &mut Pipeline, line: usize) {
+    pipeline.unsupported(
+        line,
+        "the conditional and the plugins in it were left out, a `route` transform can split the events the same way",
+    );
+}
+
+fn unsupported(pipeline: &mut Pipeline, plugin: &Plugin, kind: &str) {
+    pipeline.unsupported(
+        plugin.line,
+        format!(
+            "the `{}` {} has no Vector equivalent, and was left out",
+            plugin.name, kind
+        ),
+    );
+}
+
+/// The address to listen on, from the `host` and `port` settings.
+fn listen_address(
+    pipeline: &mut Pipeline,
+    settings: &mut Settings<Value>,
+    default_port: Option<u16>,
+) -> Option<TomlValue> {
+    let host = settings
+        .get("host")
+        .and_then(Value::as_str)
+        .unwrap_or("0.0.0.0");
+    let port = match settings.get("port").and_then(Value::as_str) {
+        Some(value) => port(pipeline, settings.line, value)?,
+        None => match default_port {
+            Some(port) => port,
+            None => {
+                pipeline.unsupported(
+                    settings.line,
+                    format!("the `{}` {} has no `port`", settings.plugin, settings.kind),
+                );
+                return None;
+            }
+        },
+    };
+    Some(format!("{}:{}", host, port).into())
+}
+
+/// Sets the decoding of a source from the `codec` setting.
+fn decoding(pipeline: &mut Pipeline, settings: &mut Settings<Value>, source: &mut Component) {
+    match settings.get("codec").and_then(Value::as_str) {
+        None | Some("plain" | "line") => {}
+        Some("json" | "json_lines") => {
+            source.insert("decoding".into(), table([("codec", "json".into())]));
+        }
+        Some(codec) => pipeline.unsupported(
+            settings.line,
+            format!(
+                "the `{}` codec of the `{}` input isn't converted",
+                codec, settings.plugin
+            ),
+        ),
+    }
+}
+
+/// The encoding of a sink, from the `codec` setting.
+fn sink_encoding(
+    pipeline: &mut Pipeline,
+    settings: &mut Settings<Value>,
+    default: &str,
+) -> TomlValue {
+    let codec = match settings.get("codec").and_then(Value::as_str) {
+        None => default,
+        Some("json" | "json_lines" | "rubydebug") => "json",
+        Some("plain" | "line") => "text",
+        Some(codec) => {
+            pipeline.unsupported(
+                settings.line,
+                format!(
+                    "the `{}` codec of the `{}` output isn't converted",
+                    codec, settings.plugin
+                ),
+            );
+            default
+        }
+    };
+    encoding(codec)
+}
+
+fn input(pipeline: &mut Pipeline, plugin: &Plugin) -> Option<String> {
+    let mut settings = plugin.settings("input");
+    let source = match plugin.name.as_str() {
+        "beats" | "elastic_agent" => {
+            let mut source = component("logstash");
+            source.insert(
+                "address".into(),
+                listen_address(pipeline, &mut settings, Some(5044))?,
+            );
+            source
+        }
+        "file" => {
+            let mut source = component("file");
+            let include = settings.get("path").map(Value::as_strs).unwrap_or_default();
+            source.insert("include".into(), strings(&include));
+            if let Some(exclude) = settings.get("exclude") {
+                source.insert("exclude".into(), strings(&exclude.as_strs()));
+            }
+            if let Some(position) = settings.get("start_position").and_then(Value::as_str) {
+                source.insert("read_from".into(), position.into());
+            }
+            settings.ignore(&["sincedb_path"]);
+            source
+        }
+        "stdin" => {
+            let mut source = component("stdin");
+            decoding(pipeline, &mut settings, &mut source);
+            source
+        }
+        "tcp" | "udp" => {
+            let mut source = component("socket");
+            source.insert("mode".into(), plugin.name.as_str().into());
+            source.insert(
+                "address".into(),
+                listen_address(pipeline, &mut settings, None)?,
+            );
+            decoding(pipeline, &mut settings, &mut source);
+            source
+        }
+        "syslog" => {
+            let mut source = component("syslog");
+            source.insert("mode".into(), "tcp".into());
+            source.insert(
+                "address".into(),
+                listen_address(pipeline, &mut settings, Some(514))?,
+            );
+            pipeline.unsupported(
+                plugin.line,
+                "the `syslog` input also listens over UDP, which takes a second `syslog` source with `mode = \"udp\"`",
+            );
+            source
+        }
+        "http" => {
+            let mut source = component("http");
+            source.insert(
+                "address".into(),
+                listen_address(pipeline, &mut settings, Some(8080))?,
+            );
+            decoding(pipeline, &mut settings, &mut source);
+            source
+        }
+        "kafka" => {
+            let mut source = component("kafka");
+            let servers = settings.get("bootstrap_servers").and_then(Value::as_str);
+            source.insert(
+                "bootstrap_servers".into(),
+                servers.unwrap_or("localhost:9092").into(),
+            );
+            let topics = settings.get("topics").map(Value::as_strs);
+            source.insert(
+                "topics".into(),
+                strings(&topics.unwrap_or_else(|| vec!["logstash"])),
+            );
+            let group_id = settings.get("group_id").and_then(Value::as_str);
+            source.insert("group_id".into(), group_id.unwrap_or("logstash").into());
+            decoding(pipeline, &mut settings, &mut source);
+            source
+        }
+        _ => {
+            unsupported(pipeline, plugin, "input");
+            return None;
+        }
+    };
+    let name = settings
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or(&plugin.name)
+        .to_owned();
+    settings.ignore(&["type"]);
+    settings.finish(pipeline);
+    Some(pipeline.add_source(&name, source))
+}
+
+/// Splits a field reference, such as `[http][status]`, into the segments of its path. The
+/// `@timestamp` field is the `timestamp` field in Vector.
+fn field_path(field: &str) -> Vec<&str> {
+    let segments = if field.starts_with('[') {
+        field
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split("][")
+            .collect()
+    } else {
+        vec![field]
+    };
+    if segments == ["@timestamp"] {
+        vec!["timestamp"]
+    } else {
+        segments
+    }
+}
+
+fn vrl_field(field: &str) -> String {
+    vrl_path(&field_path(field))
+}
+
+/// Converts a Joda-Time format, as used by Logstash for dates, to a strftime format.
+fn strftime(joda: &str) -> Option<String> {
+    const FIELDS: &[(&str, &str)] = &[
+        ("yyyy", "%Y"),
+        ("YYYY", "%Y"),
+        ("yy", "%y"),
+        ("MMM", "%b"),
+        ("MM", "%m"),
+        ("dd", "%d"),
+        ("d", "%-d"),
+        ("HH", "%H"),
+        ("mm", "%M"),
+        ("ss", "%S"),
+        ("SSS", "%3f"),
+        ("Z", "%z"),
+    ];
+    let mut format = String::new();
+    let mut rest = joda;
+    'outer: while let Some(c) = rest.chars().next() {
+        if c.is_ascii_alphabetic() {
+            for (field, strftime) in FIELDS {
+                if let Some(after) = rest.strip_prefix(field) {
+                    format.push_str(strftime);
+                    rest = after;
+                    continue 'outer;
+                }
+            }
+            return None;
+        }
+        format.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    Some(format)
+}
+
+/// Converts a Logstash sprintf format, such as `logs-%{[service]}-%{+yyyy.MM.dd}`, to a Vector
+/// template.
+fn template(format: &str) -> Option<String> {
+    let mut template = String::new();
+    let mut rest = format;
+    while let Some(start) = rest.find("%{") {
+        template.push_str(&rest[..start]);
+        let end = rest[start..].find('}')? + start;
+        let reference = &rest[start + 2..end];
+        match reference.strip_prefix('+') {
+            Some(joda) => template.push_str(&strftime(joda)?),
+            None => template.push_str(&format!("{{{{ {} }}}}", field_path(reference).join("."))),
+        }
+        rest = &rest[end + 1..];
+    }
+    template.push_str(rest);
+    Some(template)
+}
+
+/// Converts a Logstash sprintf format to a VRL expression building the string.
+fn vrl_sprintf(format: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    let mut rest = format;
+    while let Some(start) = rest.find("%{") {
+        if start > 0 {
+            parts.push(vrl_string(&rest[..start]));
+        }
+        let end = rest[start..].find('}')? + start;
+        let reference = &rest[start + 2..end];
+        parts.push(match reference.strip_prefix('+') {
+            Some(joda) => format!(
+                "format_timestamp!(.timestamp, format: {})",
+                vrl_string(&strftime(joda)?)
+            ),
+            None => format!("(to_string({}) ?? \"\")", vrl_field(reference)),
+        });
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() || parts.is_empty() {
+        parts.push(vrl_string(rest));
+    }
+    Some(parts.join(" + "))
+}
+
+/// Converts the settings all filters share.
+fn common_options(
+    pipeline: &mut Pipeline,
+    settings: &mut Settings<Value>,
+    statements: &mut Vec<String>,
+) {
+    if let Some(fields) = settings.get("add_field") {
+        for (field, value) in fields.as_pairs() {
+            match value.as_str().and_then(vrl_sprintf) {
+                Some(value) => statements.push(format!("{} = {}", vrl_field(field), value)),
+                None => pipeline.unsupported(
+                    settings.line,
+                    format!("the value of the `{}` field to add isn't converted", field),
+                ),
+            }
+        }
+    }
+    if let Some(fields) = settings.get("remove_field") {
+        for field in fields.as_strs() {
+            statements.push(format!("del({})", vrl_field(field)));
+        }
+    }
+    if let Some(tags) = settings.get("add_tag") {
+        for tag in tags.as_strs() {
+            statements.push(format!(
+                ".tags = push(array(.tags) ?? [], {})",
+                vrl_string(tag)
+            ));
+        }
+    }
+    settings.ignore(&["tag_on_failure", "periodic_flush"]);
+}
+
+fn filter(pipeline: &mut Pipeline, plugin: &Plugin, inputs: &[String]) -> Option<String> {
+    let mut settings = plugin.settings("filter");
+    let mut statements = Vec::new();
+    match plugin.name.as_str() {
+        "grok" => {
+            for (field, patterns) in settings
+                .get("match")
+                .map(Value::as_pairs)
+                .unwrap_or_default()
+            {
+                let patterns = patterns.as_strs();
+                statements.push(match patterns[..] {
+                    [pattern] => format!(
+                        ". |= parse_grok!({}, {})",
+                        vrl_field(field),
+                        vrl_string(pattern)
+                    ),
+                    _ => format!(
+                        ". |= parse_groks!({}, patterns: [{}])",
+                        vrl_field(field),
+                        patterns
+                            .iter()
+                            .map(|pattern| vrl_string(pattern))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                });
+            }
+            settings.ignore(&["overwrite", "break_on_match"]);
+        }
+        "json" => {
+            let source = settings
+                .get("source")
+                .and_then(Value::as_str)
+                .unwrap_or("message");
+            statements.push(match settings.get("target").and_then(Value::as_str) {
+                Some(target) => {
+                    format!("{} = parse_json!({})", vrl_field(target), vrl_field(source))
+                }
+                None => format!(". |= object!(parse_json!({}))", vrl_field(source)),
+            });
+            settings.ignore(&["skip_on_invalid_json"]);
+        }
+        "kv" => {
+            let source = settings
+                .get("source")
+                .and_then(Value::as_str)
+                .unwrap_or("message");
+            let field_split = settings
+                .get("field_split")
+                .and_then(Value::as_str)
+                .unwrap_or(" ");
+            let value_split = settings
+                .get("value_split")
+                .and_then(Value::as_str)
+                .unwrap_or("=");
+            let parsed = format!(
+                "parse_key_value!({}, key_value_delimiter: {}, field_delimiter: {})",
+                vrl_field(source),
+                vrl_string(value_split),
+                vrl_string(field_split)
+            );
+            statements.push(match settings.get("target").and_then(Value::as_str) {
+                Some(target) => format!("{} = {}", vrl_field(target), parsed),
+                None => format!(". |= {}", parsed),
+            });
+        }
+        "date" => {
+            let mut formats = settings
+                .get("match")
+                .map(Value::as_strs)
+                .unwrap_or_default();
+            if formats.is_empty() {
+                pipeline.unsupported(plugin.line, "the `date` filter has no `match`");
+                return None;
+            }
+            let field = vrl_field(formats.remove(0));
+            let target = settings
+                .get("target")
+                .and_then(Value::as_str)
+                .unwrap_or("@timestamp");
+            let mut parsed = Vec::new();
+            for format in formats {
+                match format {
+                    "ISO8601" => parsed.push(format!("parse_timestamp({}, \"%+\")", field)),
+                    "UNIX" => parsed.push(format!("to_timestamp({})", field)),
+                    "UNIX_MS" => {
+                        parsed.push(format!("to_timestamp({}, unit: \"milliseconds\")", field))
+                    }
+                    format => match strftime(format) {
+                        Some(strftime) => parsed.push(format!(
+                            "parse_timestamp({}, {})",
+                            field,
+                            vrl_string(&strftime)
+                        )),
+                        None => pipeline.unsupported(
+                            plugin.line,
+                            format!("the date format `{}` isn't converted", format),
+                        ),
+                    },
+                }
+            }
+            // Logstash leaves the timestamp as it is when the date doesn't parse.
+            parsed.push(vrl_field(target));
+            statements.push(format!("{} = {}", vrl_field(target), parsed.join(" ?? ")));
+            settings.ignore(&["timezone", "locale"]);
+        }
+        "mutate" => mutate(pipeline, &mut settings, &mut statements),
+        _ => {
+            unsupported(pipeline, plugin, "filter");
+            return None;
+        }
+    }
+    common_options(pipeline, &mut settings, &mut statements);
+    let name = settings
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or(&plugin.name)
+        .to_owned();
+    settings.finish(pipeline);
+
+    let mut transform = component("remap");
+    transform.insert("source".into(), statements.join("\n").into());
+    Some(pipeline.add_transform(&name, inputs, transform))
+}
+
+fn mutate(pipeline: &mut Pipeline, settings: &mut Settings<Value>, statements: &mut Vec<String>) {
+    // The operations of the filter run in this order, whatever the order of the settings.
+    if let Some(fields) = settings.get("rename") {
+        for (from, to) in fields.as_pairs() {
+            if let Some(to) = to.as_str() {
+                statements.push(format!("{} = del({})", vrl_field(to), vrl_field(from)));
+            }
+        }
+    }
+    for (setting, only_existing) in [("update", true), ("replace", false)] {
+        if let Some(fields) = settings.get(setting) {
+            for (field, value) in fields.as_pairs() {
+                let value = match value.as_str().and_then(vrl_sprintf) {
+                    Some(value) => value,
+                    None => {
+                        pipeline.unsupported(
+                            settings.line,
+                            format!(
+                                "the value to {} the `{}` field with isn't converted",
+                                setting, field
+                            ),
+                        );
+                        continue;
+                    }
+                };
+                let field = vrl_field(field);
+                statements.push(if only_existing {
+                    format!("if exists({}) {{ {} = {} }}", field, field, value)
+                } else {
+                    format!("{} = {}", field, value)
+                });
+            }
+        }
+    }
+    if let Some(fields) = settings.get("convert") {
+        for (field, kind) in fields.as_pairs() {
+            let function = match kind.as_str() {
+                Some("integer") => "to_int",
+                Some("float") => "to_float",
+                Some("string") => "to_string",
+                Some("boolean") => "to_bool",
+                _ => {
+                    pipeline.unsupported(
+                        settings.line,
+                        format!("the conversion of the `{}` field isn't converted", field),
+                    );
+                    continue;
+                }
+            };
+            let field = vrl_field(field);
+            statements.push(format!("{} = {}({}) ?? {}", field, function, field, field));
+        }
+    }
+    if let Some(substitutions) = settings.get("gsub") {
+        for substitution in substitutions.as_strs().chunks_exact(3) {
+            let field = vrl_field(substitution[0]);
+            statements.push(format!(
+                "{} = replace(string!({}), {}, {})",
+                field,
+                field,
+                vrl_regex(substitution[1]),
+                vrl_string(substitution[2])
+            ));
+        }
+    }
+    for (setting, function) in [
+        ("uppercase", "upcase"),
+        ("lowercase", "downcase"),
+        ("strip", "strip_whitespace"),
+    ] {
+        if let Some(fields) = settings.get(setting) {
+            for field in fields.as_strs() {
+                let field = vrl_field(field);
+                statements.push(format!("{} = {}(string!({}))", field, function, field));
+            }
+        }
+    }
+    if let Some(fields) = settings.get("split") {
+        for (field, separator) in fields.as_pairs() {
+            if let Some(separator) = separator.as_str() {
+                let field = vrl_field(field);
+                statements.push(format!(
+                    "{} = split(string!({}), {})",
+                    field,
+                    field,
+                    vrl_string(separator)
+                ));
+            }
+        }
+    }
+    if let Some(fields) = settings.get("join") {
+        for (field, separator) in fields.as_pairs() {
+            if let Some(separator) = separator.as_str() {
+                let field = vrl_field(field);
+                statements.push(format!(
+                    "{} = join!({}, {})",
+                    field,
+                    field,
+                    vrl_string(separator)
+                ));
+            }
+        }
+    }
+    if let Some(fields) = settings.get("copy") {
+        for (from, to) in fields.as_pairs() {
+            if let Some(to) = to.as_str() {
+                statements.push(format!("{} = {}", vrl_field(to), vrl_field(from)));
+            }
+        }
+    }
+}
+
+fn output(pipeline: &mut Pipeline, plugin: &Plugin, inputs: &[String]) {
+    let mut settings = plugin.settings("output");
+    let sink = match plugin.name.as_str() {
+        "elasticsearch" => {
+            let mut sink = component("elasticsearch");
+            let hosts = settings
+                .get("hosts")
+                .map(Value::as_strs)
+                .unwrap_or_default();
+            let host = hosts.first().copied().unwrap_or("localhost:9200");
+            let endpoint = if host.contains("://") {
+                host.to_owned()
+            } else {
+                format!("http://{}", host)
+            };
+            sink.insert("endpoint".into(), endpoint.into());
+            if hosts.len() > 1 {
+                pipeline.unsupported(
+                    plugin.line,
+                    "only the first of the `hosts` of the `elasticsearch` output is converted",
+                );
+            }
+            if let Some(pipeline_name) = settings.get("pipeline").and_then(Value::as_str) {
+                sink.insert("pipeline".into(), pipeline_name.into());
+            }
+            if let Some(id) = settings.get("document_id").and_then(Value::as_str) {
+                match id.strip_prefix("%{").and_then(|id| id.strip_suffix('}')) {
+                    Some(field) => {
+                        sink.insert("id_key".into(), field_path(field).join(".").into());
+                    }
+                    None => pipeline.unsupported(
+                        plugin.line,
+                        "the `document_id` of the `elasticsearch` output isn't a field reference",
+                    ),
+                }
+            }
+            if settings.get("data_stream").map_or(false, Value::as_bool) {
+                sink.insert("mode".into(), "data_stream".into());
+            }
+            let index = settings.get("index").and_then(Value::as_str);
+            let index = index.unwrap_or("logstash-%{+yyyy.MM.dd}");
+            match template(index) {
+                Some(index) => {
+                    sink.insert("bulk".into(), table([("index", index.into())]));
+                }
+                None => pipeline.unsupported(
+                    plugin.line,
+                    format!("the `{}` index isn't converted", index),
+                ),
+            }
+            let user = settings.get("user").and_then(Value::as_str);
+            let password = settings.get("password").and_then(Value::as_str);
+            if let (Some(user), Some(password)) = (user, password) {
+                sink.insert(
+                    "auth".into(),
+                    table([
+                        ("strategy", "basic".into()),
+                        ("user", user.into()),
+                        ("password", password.into()),
+                    ]),
+                );
+            }
+            let ca_file = settings
+                .get("cacert")
+                .or_else(|| settings.get("ssl_certificate_authorities"))
+                .and_then(Value::as_str);
+            if let Some(ca_file) = ca_file {
+                sink.insert("tls".into(), table([("ca_file", ca_file.into())]));
+            }
+            settings.ignore(&["ssl", "ssl_enabled", "manage_template"]);
+            sink
+        }
+        "stdout" => {
+            let mut sink = component("console");
+            let encoding = sink_encoding(pipeline, &mut settings, "json");
+            sink.insert("encoding".into(), encoding);
+            sink
+        }
+        "file" => {
+            let mut sink = component("file");
+            let path = settings.get("path").and_then(Value::as_str).unwrap_or("");
+            match template(path) {
+                Some(path) => {
+                    sink.insert("path".into(), path.into());
+                }
+                None => pipeline
+                    .unsupported(plugin.line, format!("the `{}` path isn't converted", path)),
+            }
+            let encoding = sink_encoding(pipeline, &mut settings, "json");
+            sink.insert("encoding".into(), encoding);
+            sink
+        }
+        "kafka" => {
+            let mut sink = component("kafka");
+            let servers = settings.get("bootstrap_servers").and_then(Value::as_str);
+            sink.insert(
+                "bootstrap_servers".into(),
+                servers.unwrap_or("localhost:9092").into(),
+            );
+            let topic = settings.get("topic_id").and_then(Value::as_str);
+            sink.insert(
+                "topic".into(),
+                topic.and_then(template).unwrap_or_default().into(),
+            );
+            let encoding = sink_encoding(pipeline, &mut settings, "text");
+            sink.insert("encoding".into(), encoding);
+            sink
+        }
+        "http" => {
+            let mut sink = component("http");
+            let url = settings.get("url").and_then(Value::as_str).unwrap_or("");
+            sink.insert("uri".into(), url.into());
+            if let Some(method) = settings.get("http_method").and_then(Value::as_str) {
+                sink.insert("method".into(), method.into());
+            }
+            match settings.get("format").and_then(Value::as_str) {
+                None | Some("json" | "json_batch") => {}
+                Some(format) => pipeline.unsupported(
+                    plugin.line,
+                    format!(
+                        "the `{}` format of the `http` output isn't converted",
+                        format
+                    ),
+                ),
+            }
+            sink.insert("encoding".into(), encoding("json"));
+            sink
+        }
+        "tcp" | "udp" => {
+            let mut sink = component("socket");
+            sink.insert("mode".into(), plugin.name.as_str().into());
+            let host = settings.get("host").and_then(Value::as_str).unwrap_or("");
+            let port = settings.get("port").and_then(Value::as_str).unwrap_or("");
+            sink.insert("address".into(), format!("{}:{}", host, port).into());
+            let encoding = sink_encoding(pipeline, &mut settings, "json");
+            sink.insert("encoding".into(), encoding);
+            sink
+        }
+        "s3" => {
+            let mut sink = component("aws_s3");
+            let bucket = settings.get("bucket").and_then(Value::as_str).unwrap_or("");
+            sink.insert("bucket".into(), bucket.into());
+            if let Some(region) = settings.get("region").and_then(Value::as_str) {
+                sink.insert("region".into(), region.into());
+            }
+            if let Some(prefix) = settings.get("prefix").and_then(Value::as_str) {
+                sink.insert("key_prefix".into(), prefix.into());
+            }
+            let encoding = sink_encoding(pipeline, &mut settings, "text");
+            sink.insert("encoding".into(), encoding);
+            sink
+        }
+        "null" => component("blackhole"),
+        _ => {
+            unsupported(pipeline, plugin, "output");
+            return;
+        }
+    };
+    let name = settings
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or(&plugin.name)
+        .to_owned();
+    settings.finish(pipeline);
+    pipeline.add_sink(&name, inputs, sink);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIPELINE: &str = r#"
+        # Ship the logs of the web servers.
+        input {
+          beats { port => 5044 }
+          file {
+            path => ["/var/log/nginx/*.log"]
+            start_position => "beginning"
+            sincedb_path => "/dev/null"
+          }
+        }
+
+        filter {
+          grok {
+            match => { "message" => "%{COMBINEDAPACHELOG}" }
+          }
+          date {
+            match => [ "timestamp", "dd/MMM/yyyy:HH:mm:ss Z" ]
+          }
+          mutate {
+            rename => { "[response]" => "[http][status]" }
+            convert => { "bytes" => "integer" }
+            add_field => { "env" => "prod-%{host}" }
+            remove_field => [ "timestamp" ]
+          }
+          if [http][status] == "404" {
+            drop { }
+          }
+          ruby { code => "event.set('x', 1)" }
+        }
+
+        output {
+          elasticsearch {
+            hosts => ["https://es.example.com:9200"]
+            index => "web-%{+YYYY.MM.dd}"
+            user => "elastic"
+            password => "changeme"
+          }
+          stdout { codec => rubydebug }
+        }
+    "#;
+
+    #[test]
+    fn parses_pipelines() {
+        let config = Parser::new(PIPELINE).config().unwrap();
+        assert_eq!(config.inputs.len(), 2);
+        assert_eq!(config.filters.len(), 5);
+        assert_eq!(config.outputs.len(), 2);
+
+        let grok = match &config.filters[0] {
+            Item::Plugin(plugin) => plugin,
+            item => panic!("unexpected item {:?}", item),
+        };
+        assert_eq!(grok.line, 13);
+        assert_eq!(
+            grok.settings,
+            vec![(
+                "match".to_owned(),
+                Value::Hash(vec![(
+                    "message".to_owned(),
+                    Value::String("%{COMBINEDAPACHELOG}".to_owned())
+                )])
+            )]
+        );
+        assert!(matches!(config.filters[3], Item::Conditional { line: 25 }));
+
+        let config = Parser::new(r#"output { stdout { codec => json { charset => "UTF-8" } } }"#)
+            .config()
+            .unwrap();
+        match &config.outputs[0] {
+            Item::Plugin(plugin) => assert_eq!(plugin.settings[0].1.as_str(), Some("json")),
+            item => panic!("unexpected item {:?}", item),
+        }
+
+        assert!(Parser::new("input { file { path => } }").config().is_err());
+        assert!(Parser::new("input { stdin { }").config().is_err());
+    }
+
+    #[test]
+    fn converts_pipelines() {
+        let pipeline = convert(PIPELINE).unwrap();
+
+        assert_eq!(
+            pipeline.sources.keys().collect::<Vec<_>>(),
+            vec!["beats", "file"]
+        );
+        assert_eq!(pipeline.sources["beats"]["type"], "logstash".into());
+        assert_eq!(pipeline.sources["beats"]["address"], "0.0.0.0:5044".into());
+        assert_eq!(pipeline.sources["file"]["read_from"], "beginning".into());
+
+        assert_eq!(
+            pipeline.transforms.keys().collect::<Vec<_>>(),
+            vec!["grok", "date", "mutate"]
+        );
+        assert_eq!(
+            pipeline.transforms["grok"]["inputs"],
+            strings(&["beats", "file"])
+        );
+        assert_eq!(
+            pipeline.transforms["grok"]["source"],
+            r#". |= parse_grok!(.message, "%{COMBINEDAPACHELOG}")"#.into()
+        );
+        assert_eq!(
+            pipeline.transforms["date"]["source"],
+            r#".timestamp = parse_timestamp(.timestamp, "%d/%b/%Y:%H:%M:%S %z") ?? .timestamp"#
+                .into()
+        );
+        assert_eq!(
+            pipeline.transforms["mutate"]["source"],
+            [
+                ".http.status = del(.response)",
+                ".bytes = to_int(.bytes) ?? .bytes",
+                r#".env = "prod-" + (to_string(.host) ?? "")"#,
+                "del(.timestamp)",
+            ]
+            .join("\n")
+            .into()
+        );
+
+        let elasticsearch = &pipeline.sinks["elasticsearch"];
+        assert_eq!(elasticsearch["inputs"], strings(&["mutate"]));
+        assert_eq!(
+            elasticsearch["endpoint"],
+            "https://es.example.com:9200".into()
+        );
+        assert_eq!(elasticsearch["bulk"]["index"], "web-%Y.%m.%d".into());
+        assert_eq!(elasticsearch["auth"]["strategy"], "basic".into());
+        assert_eq!(pipeline.sinks["stdout"]["encoding"]["codec"], "json".into());
+
+        let unsupported = pipeline
+            .unsupported
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unsupported,
+            vec![
+                "line 25: the conditional and the plugins in it were left out, a `route` transform can split the events the same way",
+                "line 28: the `ruby` filter has no Vector equivalent, and was left out",
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_sprintf_formats() {
+        assert_eq!(
+            template("logs-%{[service][name]}-%{+yyyy.MM.dd}").as_deref(),
+            Some("logs-{{ service.name }}-%Y.%m.%d")
+        );
+        assert_eq!(template("%{+EEE}"), None);
+        assert_eq!(
+            vrl_sprintf("%{[a][b]}/x").as_deref(),
+            Some(r#"(to_string(.a.b) ?? "") + "/x""#)
+        );
+        assert_eq!(vrl_sprintf("").as_deref(), Some(r#""""#));
+    }
+}
@@ -0,0 +1,559 @@
+//! Best-effort conversion of the configurations of other log shippers to Vector configurations,
+//! with a report of the constructs that couldn't be converted.
+
+mod fluent_bit;
+mod fluentd;
+mod logstash;
+
+use std::{
+    collections::HashSet,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use colored::*;
+use indexmap::IndexMap;
+use regex::Regex;
+use serde::Serialize;
+use toml::{map::Map, Value};
+
+use crate::config::{format, ConfigBuilder, Format};
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The log shipper the configuration to convert is written for.
+    #[clap(long, possible_values = &["logstash", "fluentd", "fluent-bit"])]
+    from: Shipper,
+
+    /// The configuration to convert.
+    #[clap(parse(from_os_str))]
+    input: PathBuf,
+
+    /// Write the Vector configuration to a file rather than to stdout, in the format given by the
+    /// extension of the file.
+    #[clap(short, long, parse(from_os_str))]
+    output: Option<PathBuf>,
+
+    /// The format of the Vector configuration, when it isn't given by the output file.
+    #[clap(long, default_value = "toml", possible_values = &["toml", "yaml", "json"])]
+    format: Format,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Shipper {
+    Logstash,
+    Fluentd,
+    FluentBit,
+}
+
+impl std::str::FromStr for Shipper {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "logstash" => Ok(Shipper::Logstash),
+            "fluentd" => Ok(Shipper::Fluentd),
+            "fluent-bit" => Ok(Shipper::FluentBit),
+            s => Err(format!(
+                "{} is not a valid option, expected `logstash`, `fluentd` or `fluent-bit`",
+                s
+            )),
+        }
+    }
+}
+
+impl Shipper {
+    fn convert(self, config: &str) -> Result<Pipeline, String> {
+        match self {
+            Shipper::Logstash => logstash::convert(config),
+            Shipper::Fluentd => fluentd::convert(config),
+            Shipper::FluentBit => fluent_bit::convert(config),
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Shipper::Logstash => "Logstash",
+            Shipper::Fluentd => "Fluentd",
+            Shipper::FluentBit => "Fluent Bit",
+        }
+    }
+}
+
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let config = match fs::read_to_string(&opts.input) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!(
+                "{}",
+                format!("Could not read {:?}: {}", opts.input, error).red()
+            );
+            return exitcode::NOINPUT;
+        }
+    };
+    let mut pipeline = match opts.from.convert(&config) {
+        Ok(pipeline) => pipeline,
+        Err(error) => {
+            eprintln!(
+                "{}",
+                format!("Could not parse {:?}: {}", opts.input, error).red()
+            );
+            return exitcode::DATAERR;
+        }
+    };
+
+    let format = opts.output.as_deref().map_or(opts.format, |path| {
+        Format::from_path(path).unwrap_or(opts.format)
+    });
+    let converted = match pipeline.render(format, opts.from, &opts.input) {
+        Ok(converted) => converted,
+        Err(error) => {
+            eprintln!(
+                "{}",
+                format!("Could not serialize the configuration: {}", error).red()
+            );
+            return exitcode::SOFTWARE;
+        }
+    };
+    if let Err(errors) = format::deserialize::<ConfigBuilder>(&converted, format) {
+        for error in errors {
+            pipeline.unsupported(
+                0,
+                format!("the converted configuration doesn't load: {}", error),
+            );
+        }
+    }
+
+    match &opts.output {
+        Some(path) => {
+            if let Err(error) = fs::write(path, &converted) {
+                eprintln!("{}", format!("Could not write {:?}: {}", path, error).red());
+                return exitcode::IOERR;
+            }
+        }
+        None => print!("{}", converted),
+    }
+
+    if pipeline.unsupported.is_empty() {
+        eprintln!("{}", "Converted the whole configuration.".green());
+    } else {
+        eprintln!(
+            "{}",
+            format!(
+                "Converted the configuration, leaving out {} construct(s) to convert by hand:",
+                pipeline.unsupported.len()
+            )
+            .yellow()
+        );
+        for unsupported in &pipeline.unsupported {
+            eprintln!("  - {}", unsupported);
+        }
+    }
+    exitcode::OK
+}
+
+type Component = IndexMap<String, Value>;
+
+/// A Vector configuration being converted to.
+#[derive(Debug, Default, Serialize)]
+struct Pipeline {
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    sources: IndexMap<String, Component>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    transforms: IndexMap<String, Component>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    sinks: IndexMap<String, Component>,
+    /// The constructs of the original configuration that weren't converted.
+    #[serde(skip)]
+    unsupported: Vec<Unsupported>,
+}
+
+#[derive(Debug)]
+struct Unsupported {
+    /// The line of the original configuration the construct is on, or 0 for the whole of it.
+    line: usize,
+    message: String,
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line == 0 {
+            self.message.fmt(f)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl Pipeline {
+    fn add_source(&mut self, name: &str, source: Component) -> String {
+        let id = self.unique_id(name);
+        self.sources.insert(id.clone(), source);
+        id
+    }
+
+    fn add_transform(&mut self, name: &str, inputs: &[String], transform: Component) -> String {
+        let id = self.unique_id(name);
+        self.transforms
+            .insert(id.clone(), with_inputs(transform, inputs));
+        id
+    }
+
+    fn add_sink(&mut self, name: &str, inputs: &[String], sink: Component) -> String {
+        let id = self.unique_id(name);
+        self.sinks.insert(id.clone(), with_inputs(sink, inputs));
+        id
+    }
+
+    fn unsupported(&mut self, line: usize, message: impl Into<String>) {
+        self.unsupported.push(Unsupported {
+            line,
+            message: message.into(),
+        });
+    }
+
+    /// Gets an ID for a component, suffixed with a number if the name is already taken.
+    fn unique_id(&self, name: &str) -> String {
+        let name = name.replace('.', "_");
+        let taken = |id: &str| {
+            self.sources.contains_key(id)
+                || self.transforms.contains_key(id)
+                || self.sinks.contains_key(id)
+        };
+        if !taken(&name) {
+            return name;
+        }
+        (2..)
+            .map(|n| format!("{}_{}", name, n))
+            .find(|id| !taken(id))
+            .expect("ran out of component IDs")
+    }
+
+    /// Renders the configuration, headed by comments listing the constructs that weren't
+    /// converted when the format allows for comments.
+    fn render(&self, format: Format, from: Shipper, path: &Path) -> Result<String, String> {
+        let body = match format {
+            // Tables have to come after the plain values of a table in TOML, which the
+            // serializer only takes care of for its own values.
+            Format::Toml => Value::try_from(self)
+                .and_then(|value| toml::to_string_pretty(&value))
+                .map_err(|error| error.to_string())?,
+            format => format::serialize(self, format)?,
+        };
+        if format == Format::Json {
+            return Ok(body);
+        }
+
+        let mut header = format!(
+            "# Converted from the {} configuration {:?} by `vector convert`.\n",
+            from.name(),
+            path
+        );
+        if !self.unsupported.is_empty() {
+            header.push_str("#\n# Left out, to convert by hand:\n");
+            for unsupported in &self.unsupported {
+                header.push_str(&format!("#   - {}\n", unsupported));
+            }
+        }
+        Ok(format!("{}\n{}", header, body))
+    }
+}
+
+/// Creates a component of the given type.
+fn component(component_type: &str) -> Component {
+    let mut component = Component::new();
+    component.insert("type".into(), component_type.into());
+    component
+}
+
+/// Sets the inputs of a component, right after its type.
+fn with_inputs(component: Component, inputs: &[String]) -> Component {
+    let mut component = component.into_iter();
+    let mut with_inputs = Component::new();
+    with_inputs.extend(component.next());
+    with_inputs.insert("inputs".into(), strings(inputs));
+    with_inputs.extend(component);
+    with_inputs
+}
+
+fn strings<S: AsRef<str>>(strings: &[S]) -> Value {
+    Value::Array(
+        strings
+            .iter()
+            .map(|string| Value::from(string.as_ref()))
+            .collect(),
+    )
+}
+
+fn table<'a>(entries: impl IntoIterator<Item = (&'a str, Value)>) -> Value {
+    Value::Table(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+/// The `encoding` option of a sink, with the given codec.
+fn encoding(codec: &str) -> Value {
+    table([("codec", codec.into())])
+}
+
+/// Parses a port, reporting it if it isn't one.
+fn port(pipeline: &mut Pipeline, line: usize, port: &str) -> Option<u16> {
+    let parsed = port.parse().ok();
+    if parsed.is_none() {
+        pipeline.unsupported(line, format!("`{}` isn't a valid port", port));
+    }
+    parsed
+}
+
+/// Writes a path to a field in VRL, quoting the segments that aren't plain identifiers.
+fn vrl_path<S: AsRef<str>>(segments: &[S]) -> String {
+    let mut path = String::from(".");
+    for (i, segment) in segments.iter().enumerate() {
+        let segment = segment.as_ref();
+        if i > 0 {
+            path.push('.');
+        }
+        if !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            path.push_str(segment);
+        } else {
+            path.push_str(&vrl_string(segment));
+        }
+    }
+    path
+}
+
+/// Writes a string literal in VRL.
+fn vrl_string(string: &str) -> String {
+    serde_json::to_string(string).expect("strings always serialize")
+}
+
+/// Writes a regex literal in VRL.
+fn vrl_regex(regex: &str) -> String {
+    format!("r'{}'", regex.replace('\'', "\\'"))
+}
+
+/// Tracks the component the records of each tag currently come out of, as they are routed
+/// through the filters and outputs matching their tag, as Fluentd and Fluent Bit do.
+#[derive(Debug, Default)]
+struct Router {
+    routes: Vec<Route>,
+}
+
+#[derive(Debug)]
+struct Route {
+    /// The tag of the records, if it's known ahead of time, as it isn't for the records that
+    /// clients send along with their tags.
+    tag: Option<String>,
+    component: String,
+}
+
+impl Route {
+    fn matches(&self, pattern: &Regex) -> bool {
+        self.tag
+            .as_deref()
+            .map_or(true, |tag| pattern.is_match(tag))
+    }
+}
+
+impl Router {
+    fn add(&mut self, tag: Option<&str>, source: &str) {
+        self.routes.push(Route {
+            tag: tag.map(ToOwned::to_owned),
+            component: source.to_owned(),
+        });
+    }
+
+    /// The components the records with a matching tag come out of.
+    fn inputs(&self, pattern: &Regex) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.routes
+            .iter()
+            .filter(|route| route.matches(pattern))
+            .filter(|route| seen.insert(&route.component))
+            .map(|route| route.component.clone())
+            .collect()
+    }
+
+    /// Whether the records of other tags come out of the same components as those with a matching
+    /// tag, such that a component taking the latter as inputs would get the former too.
+    fn mixes(&self, pattern: &Regex) -> bool {
+        let inputs = self.inputs(pattern);
+        self.routes
+            .iter()
+            .any(|route| !route.matches(pattern) && inputs.contains(&route.component))
+    }
+
+    /// Routes the records with a matching tag through the transform.
+    fn through(&mut self, pattern: &Regex, transform: &str) {
+        for route in &mut self.routes {
+            if route.matches(pattern) {
+                route.component = transform.to_owned();
+            }
+        }
+    }
+
+    /// Stops routing the records with a matching tag any further, as a Fluentd output does, apart
+    /// from those with unknown tags, which may or may not match.
+    fn remove(&mut self, pattern: &Regex) {
+        self.routes
+            .retain(|route| route.tag.is_none() || !route.matches(pattern));
+    }
+}
+
+/// The settings of a plugin, keeping track of those that were converted, so that the rest can be
+/// reported as left out.
+struct Settings<'a, V> {
+    kind: &'static str,
+    plugin: &'a str,
+    line: usize,
+    entries: Vec<(&'a str, &'a V)>,
+    used: Vec<bool>,
+    /// Whether the names of the settings are case insensitive, as in Fluent Bit.
+    ignore_case: bool,
+}
+
+impl<'a, V> Settings<'a, V> {
+    fn new(
+        kind: &'static str,
+        plugin: &'a str,
+        line: usize,
+        entries: impl IntoIterator<Item = (&'a str, &'a V)>,
+    ) -> Self {
+        let entries: Vec<_> = entries.into_iter().collect();
+        Self {
+            kind,
+            plugin,
+            line,
+            used: vec![false; entries.len()],
+            entries,
+            ignore_case: false,
+        }
+    }
+
+    const fn ignore_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    fn matches(&self, name: &str, key: &str) -> bool {
+        if self.ignore_case {
+            name.eq_ignore_ascii_case(key)
+        } else {
+            name == key
+        }
+    }
+
+    /// Gets the value of a setting, the last one if it's set several times.
+    fn get(&mut self, key: &str) -> Option<&'a V> {
+        self.get_all(key).pop()
+    }
+
+    /// Gets all the values of a setting that can be set several times.
+    fn get_all(&mut self, key: &str) -> Vec<&'a V> {
+        let mut values = Vec::new();
+        for (i, (name, value)) in self.entries.iter().enumerate() {
+            if self.matches(name, key) {
+                self.used[i] = true;
+                values.push(*value);
+            }
+        }
+        values
+    }
+
+    /// Marks settings as converted, for those that have no bearing on the Vector configuration.
+    fn ignore(&mut self, keys: &[&str]) {
+        for key in keys {
+            self.get_all(key);
+        }
+    }
+
+    /// Reports the settings that weren't converted.
+    fn finish(self, pipeline: &mut Pipeline) {
+        let mut reported = HashSet::new();
+        for ((name, _), used) in self.entries.iter().zip(self.used) {
+            if !used && reported.insert(*name) {
+                pipeline.unsupported(
+                    self.line,
+                    format!(
+                        "the `{}` setting of the `{}` {} isn't converted",
+                        name, self.plugin, self.kind
+                    ),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_vrl_paths() {
+        assert_eq!(vrl_path(&["message"]), ".message");
+        assert_eq!(vrl_path(&["http", "status_code"]), ".http.status_code");
+        assert_eq!(vrl_path(&["@timestamp"]), r#"."@timestamp""#);
+        assert_eq!(
+            vrl_path(&["kubernetes", "pod-name"]),
+            r#".kubernetes."pod-name""#
+        );
+    }
+
+    #[test]
+    fn routes_records_by_tag() {
+        let mut router = Router::default();
+        router.add(Some("app.web"), "web");
+        router.add(Some("app.db"), "db");
+        router.add(Some("system"), "syslog");
+
+        let app = Regex::new(r"^app\..*$").unwrap();
+        let web = Regex::new(r"^app\.web$").unwrap();
+        assert_eq!(router.inputs(&app), vec!["web", "db"]);
+        assert!(!router.mixes(&app));
+
+        router.through(&app, "parse");
+        assert_eq!(router.inputs(&app), vec!["parse"]);
+        assert!(router.mixes(&web));
+
+        router.remove(&web);
+        assert_eq!(router.inputs(&app), vec!["parse"]);
+        assert_eq!(router.routes.len(), 2);
+
+        router.add(None, "forward");
+        assert_eq!(router.inputs(&web), vec!["forward"]);
+        router.remove(&web);
+        assert_eq!(router.inputs(&web), vec!["forward"]);
+    }
+
+    #[test]
+    fn renders_tables_after_values() {
+        let mut pipeline = Pipeline::default();
+        let mut sink = component("console");
+        sink.insert("encoding".into(), encoding("json"));
+        sink.insert("target".into(), "stdout".into());
+        let source = pipeline.add_source("in", component("stdin"));
+        pipeline.add_sink("out", &[source], sink);
+        pipeline.unsupported(3, "the `ruby` filter has no Vector equivalent");
+
+        let rendered = pipeline
+            .render(Format::Toml, Shipper::Logstash, Path::new("pipeline.conf"))
+            .unwrap();
+        assert!(rendered.starts_with("# Converted from the Logstash configuration"));
+        assert!(rendered.contains("#   - line 3: the `ruby` filter has no Vector equivalent\n"));
+
+        let config: toml::Value = toml::from_str(&rendered).unwrap();
+        assert_eq!(config["sinks"]["out"]["inputs"], strings(&["in"]));
+        assert_eq!(config["sinks"]["out"]["encoding"]["codec"], "json".into());
+        assert_eq!(config["sinks"]["out"]["target"], "stdout".into());
+    }
+}
@@ -0,0 +1,293 @@
+//! External account credentials, as used by workload identity federation, which exchange a token
+//! issued by another identity provider for a GCP token with the Security Token Service.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use goauth::{auth::Token, scopes::Scope};
+use hyper::{header::CONTENT_TYPE, Body};
+use serde::Deserialize;
+
+use super::{bearer_token, impersonation, send, send_json};
+
+const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+/// The `external_account` credentials file, as generated by `gcloud iam workload-identity-pools
+/// create-cred-config`.
+#[derive(Deserialize, Debug)]
+pub(super) struct ExternalAccount {
+    audience: String,
+    subject_token_type: String,
+    token_url: String,
+    service_account_impersonation_url: Option<String>,
+    credential_source: CredentialSource,
+}
+
+/// Where the token of the other identity provider is read from.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum CredentialSource {
+    Aws {
+        environment_id: String,
+        regional_cred_verification_url: String,
+    },
+    File {
+        file: PathBuf,
+        #[serde(default)]
+        format: SubjectTokenFormat,
+    },
+    Url {
+        url: String,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        #[serde(default)]
+        format: SubjectTokenFormat,
+    },
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SubjectTokenFormat {
+    Text,
+    Json { subject_token_field_name: String },
+}
+
+impl Default for SubjectTokenFormat {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+impl SubjectTokenFormat {
+    fn subject_token(&self, contents: &[u8]) -> crate::Result<String> {
+        match self {
+            Self::Text => Ok(String::from_utf8(contents.to_vec())?.trim().to_owned()),
+            Self::Json {
+                subject_token_field_name,
+            } => {
+                let contents: serde_json::Value = serde_json::from_slice(contents)?;
+                contents
+                    .get(subject_token_field_name)
+                    .and_then(serde_json::Value::as_str)
+                    .map(ToOwned::to_owned)
+                    .ok_or_else(|| {
+                        format!(
+                            "Missing subject token field {:?} in external account credentials",
+                            subject_token_field_name
+                        )
+                        .into()
+                    })
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct StsResponse {
+    access_token: String,
+    expires_in: u32,
+}
+
+impl ExternalAccount {
+    /// Exchanges the token of the other identity provider for a token for the given scope.
+    pub(super) async fn fetch_token(&self, scope: &Scope) -> crate::Result<Token> {
+        debug!(
+            message = "Fetching GCP authentication token for external account.",
+            audience = %self.audience,
+            token_url = %self.token_url,
+        );
+        let subject_token = self.subject_token().await?;
+
+        // The federated token can only be used for the `cloud-platform` scope, and is narrowed down
+        // to the requested one when impersonating a service account.
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", TOKEN_EXCHANGE_GRANT_TYPE)
+            .append_pair("audience", &self.audience)
+            .append_pair("scope", Scope::CloudPlatform.url().as_str())
+            .append_pair("requested_token_type", ACCESS_TOKEN_TYPE)
+            .append_pair("subject_token", &subject_token)
+            .append_pair("subject_token_type", &self.subject_token_type)
+            .finish();
+        let request = http::Request::post(&self.token_url)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(body))?;
+
+        let response: StsResponse = send_json(request).await?;
+        let token = bearer_token(response.access_token, response.expires_in)?;
+
+        match &self.service_account_impersonation_url {
+            Some(url) => impersonation::generate_access_token(url, &token, &[], scope).await,
+            None => Ok(token),
+        }
+    }
+
+    async fn subject_token(&self) -> crate::Result<String> {
+        match &self.credential_source {
+            CredentialSource::Aws {
+                environment_id,
+                regional_cred_verification_url,
+            } => {
+                if !environment_id.starts_with("aws") {
+                    return Err(format!(
+                        "Unsupported external account environment {:?}",
+                        environment_id
+                    )
+                    .into());
+                }
+                aws_subject_token(regional_cred_verification_url, &self.audience).await
+            }
+            CredentialSource::File { file, format } => {
+                format.subject_token(&tokio::fs::read(file).await?)
+            }
+            CredentialSource::Url {
+                url,
+                headers,
+                format,
+            } => {
+                let mut request = http::Request::get(url);
+                for (name, value) in headers {
+                    request = request.header(name.as_str(), value.as_str());
+                }
+                format.subject_token(&send(request.body(Body::empty())?).await?)
+            }
+        }
+    }
+}
+
+/// Builds the subject token of AWS credentials, which is a `GetCallerIdentity` request signed with
+/// the credentials of the environment, for the Security Token Service to send on to AWS.
+#[cfg(all(feature = "aws-config", feature = "aws-sigv4"))]
+async fn aws_subject_token(verification_url: &str, audience: &str) -> crate::Result<String> {
+    use crate::aws::{resolve_region, sigv4::sign_request, AwsAuthentication};
+
+    let region = resolve_region(None).await?;
+    let credentials_provider = AwsAuthentication::default()
+        .credentials_provider(region.clone())
+        .await?;
+
+    let url = verification_url.replace("{region}", region.as_ref());
+    let uri: http::Uri = url.parse()?;
+    let host = uri
+        .host()
+        .ok_or_else(|| format!("Missing host in AWS verification URL {:?}", url))?
+        .to_owned();
+    let mut request = http::Request::post(uri)
+        .header("host", host)
+        .header("x-goog-cloud-target-resource", audience)
+        .body(())?;
+    sign_request(
+        &mut request,
+        &[],
+        &credentials_provider,
+        Some(&region),
+        "sts",
+    )
+    .await?;
+
+    let headers = request
+        .headers()
+        .iter()
+        .map(|(key, value)| -> crate::Result<_> {
+            Ok(serde_json::json!({
+                "key": key.as_str(),
+                "value": value.to_str()?,
+            }))
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+    let token = serde_json::json!({
+        "url": url,
+        "method": "POST",
+        "headers": headers,
+    });
+
+    Ok(url::form_urlencoded::byte_serialize(token.to_string().as_bytes()).collect())
+}
+
+#[cfg(not(all(feature = "aws-config", feature = "aws-sigv4")))]
+async fn aws_subject_token(_verification_url: &str, _audience: &str) -> crate::Result<String> {
+    Err("AWS credential sources are not supported by this build".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_file_credential_source() {
+        let account: ExternalAccount = serde_json::from_str(
+            r#"{
+                "type": "external_account",
+                "audience": "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/pool/providers/oidc",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": "https://sts.googleapis.com/v1/token",
+                "service_account_impersonation_url": "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/vector@project.iam.gserviceaccount.com:generateAccessToken",
+                "credential_source": {
+                    "file": "/var/run/secrets/token",
+                    "format": {
+                        "type": "json",
+                        "subject_token_field_name": "id_token"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert!(account.service_account_impersonation_url.is_some());
+        match account.credential_source {
+            CredentialSource::File { file, format } => {
+                assert_eq!(file, PathBuf::from("/var/run/secrets/token"));
+                assert_eq!(
+                    format,
+                    SubjectTokenFormat::Json {
+                        subject_token_field_name: "id_token".to_owned()
+                    }
+                );
+            }
+            source => panic!("unexpected credential source {:?}", source),
+        }
+    }
+
+    #[test]
+    fn parses_url_and_aws_credential_sources() {
+        let source: CredentialSource = serde_json::from_str(
+            r#"{
+                "url": "http://localhost:5000/token",
+                "headers": { "Metadata": "True" }
+            }"#,
+        )
+        .unwrap();
+        assert!(
+            matches!(source, CredentialSource::Url { headers, format, .. } if headers.len() == 1 && format == SubjectTokenFormat::Text)
+        );
+
+        let source: CredentialSource = serde_json::from_str(
+            r#"{
+                "environment_id": "aws1",
+                "region_url": "http://169.254.169.254/latest/meta-data/placement/availability-zone",
+                "url": "http://169.254.169.254/latest/meta-data/iam/security-credentials",
+                "regional_cred_verification_url": "https://sts.{region}.amazonaws.com?Action=GetCallerIdentity&Version=2011-06-15"
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(source, CredentialSource::Aws { .. }));
+    }
+
+    #[test]
+    fn reads_subject_tokens() {
+        assert_eq!(
+            SubjectTokenFormat::Text.subject_token(b"token\n").unwrap(),
+            "token"
+        );
+
+        let format = SubjectTokenFormat::Json {
+            subject_token_field_name: "id_token".to_owned(),
+        };
+        assert_eq!(
+            format.subject_token(br#"{"id_token": "token"}"#).unwrap(),
+            "token"
+        );
+        assert!(format
+            .subject_token(br#"{"access_token": "token"}"#)
+            .is_err());
+    }
+}
@@ -0,0 +1,94 @@
+//! Impersonation of service accounts, which issues a token for a service account to the holder of
+//! a token with the permission to create tokens for it.
+
+use chrono::{DateTime, Utc};
+use goauth::{auth::Token, scopes::Scope};
+use hyper::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Body,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{bearer_token, send_json};
+
+const IAM_CREDENTIALS_URL: &str =
+    "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts";
+
+/// The lifetime of the impersonated tokens, which is the longest allowed by default.
+const TOKEN_LIFETIME: &str = "3600s";
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GenerateAccessTokenResponse {
+    access_token: String,
+    expire_time: DateTime<Utc>,
+}
+
+/// Impersonates the target service account with the given token, through the delegate service
+/// accounts, each of which has the permission to create tokens for the next one.
+pub(super) async fn impersonate(
+    token: &Token,
+    target: &str,
+    delegates: &[String],
+    scope: &Scope,
+) -> crate::Result<Token> {
+    let url = format!("{}/{}:generateAccessToken", IAM_CREDENTIALS_URL, target);
+    generate_access_token(&url, token, delegates, scope).await
+}
+
+/// Creates a token with the given `generateAccessToken` endpoint of a service account.
+pub(super) async fn generate_access_token(
+    url: &str,
+    token: &Token,
+    delegates: &[String],
+    scope: &Scope,
+) -> crate::Result<Token> {
+    debug!(message = "Impersonating GCP service account.", %url);
+    let request = http::Request::post(url)
+        .header(
+            AUTHORIZATION,
+            format!("{} {}", token.token_type(), token.access_token()),
+        )
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(request_body(delegates, scope).to_string()))?;
+
+    let response: GenerateAccessTokenResponse = send_json(request).await?;
+    let expires_in = (response.expire_time - Utc::now()).num_seconds().max(0);
+    bearer_token(response.access_token, expires_in as u32)
+}
+
+fn request_body(delegates: &[String], scope: &Scope) -> serde_json::Value {
+    let delegates = delegates
+        .iter()
+        .map(|delegate| format!("projects/-/serviceAccounts/{}", delegate))
+        .collect::<Vec<_>>();
+
+    json!({
+        "delegates": delegates,
+        "scope": [scope.url()],
+        "lifetime": TOKEN_LIFETIME,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_request_body() {
+        let body = request_body(
+            &["delegate@project.iam.gserviceaccount.com".to_owned()],
+            &Scope::DevStorageReadWrite,
+        );
+
+        assert_eq!(
+            body,
+            json!({
+                "delegates": ["projects/-/serviceAccounts/delegate@project.iam.gserviceaccount.com"],
+                "scope": ["https://www.googleapis.com/auth/devstorage.read_write"],
+                "lifetime": "3600s",
+            })
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
+use bytes::Bytes;
 use futures::{Stream, StreamExt};
 pub use goauth::scopes::Scope;
 use goauth::{
@@ -10,7 +11,7 @@ use goauth::{
 };
 use hyper::header::AUTHORIZATION;
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use smpl_jwt::Jwt;
 use snafu::{ResultExt, Snafu};
 use tokio::time::Instant;
@@ -18,6 +19,11 @@ use tokio_stream::wrappers::IntervalStream;
 
 use crate::{config::ProxyConfig, http::HttpClient, http::HttpError};
 
+mod external_account;
+mod impersonation;
+
+use external_account::ExternalAccount;
+
 const SERVICE_ACCOUNT_TOKEN_URL: &str =
     "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
 
@@ -56,16 +62,20 @@ pub enum GcpError {
 pub struct GcpAuthConfig {
     pub api_key: Option<String>,
     pub credentials_path: Option<String>,
+    /// The service accounts to impersonate in turn, the last of which the token is issued for.
+    #[serde(default)]
+    pub impersonation_chain: Vec<String>,
 }
 
 impl GcpAuthConfig {
     pub async fn make_credentials(&self, scope: Scope) -> crate::Result<Option<GcpCredentials>> {
         let gap = std::env::var("GOOGLE_APPLICATION_CREDENTIALS").ok();
         let creds_path = self.credentials_path.as_ref().or_else(|| gap.as_ref());
+        let chain = self.impersonation_chain.clone();
         Ok(match (&creds_path, &self.api_key) {
-            (Some(path), _) => Some(GcpCredentials::from_file(path, scope).await?),
+            (Some(path), _) => Some(GcpCredentials::from_file(path, scope, chain).await?),
             (None, Some(_)) => None,
-            (None, None) => Some(GcpCredentials::new_implicit(scope).await?),
+            (None, None) => Some(GcpCredentials::new_implicit(scope, chain).await?),
         })
     }
 }
@@ -75,27 +85,59 @@ pub struct GcpCredentials(Arc<Inner>);
 
 #[derive(Debug)]
 struct Inner {
-    creds: Option<Credentials>,
+    source: TokenSource,
     scope: Scope,
+    impersonation_chain: Vec<String>,
     token: RwLock<Token>,
 }
 
+/// Where tokens are fetched from, before impersonating any service accounts.
+#[derive(Debug)]
+enum TokenSource {
+    ServiceAccount(Credentials),
+    ExternalAccount(ExternalAccount),
+    Implicit,
+}
+
+/// The type of a credentials file.
+#[derive(Deserialize)]
+struct CredentialsType {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
 impl GcpCredentials {
-    async fn from_file(path: &str, scope: Scope) -> crate::Result<Self> {
-        let creds = Credentials::from_file(path).context(InvalidCredentialsSnafu)?;
-        let token = fetch_token(&creds, &scope).await?;
-        Ok(Self(Arc::new(Inner {
-            creds: Some(creds),
-            scope,
-            token: RwLock::new(token),
-        })))
+    async fn from_file(
+        path: &str,
+        scope: Scope,
+        impersonation_chain: Vec<String>,
+    ) -> crate::Result<Self> {
+        let contents = tokio::fs::read(path).await?;
+        let source = match serde_json::from_slice::<CredentialsType>(&contents) {
+            Ok(credentials) if credentials.kind == "external_account" => {
+                TokenSource::ExternalAccount(serde_json::from_slice(&contents)?)
+            }
+            _ => TokenSource::ServiceAccount(
+                Credentials::from_file(path).context(InvalidCredentialsSnafu)?,
+            ),
+        };
+        Self::new(source, scope, impersonation_chain).await
     }
 
-    async fn new_implicit(scope: Scope) -> crate::Result<Self> {
-        let token = get_token_implicit().await?;
+    async fn new_implicit(scope: Scope, impersonation_chain: Vec<String>) -> crate::Result<Self> {
+        Self::new(TokenSource::Implicit, scope, impersonation_chain).await
+    }
+
+    async fn new(
+        source: TokenSource,
+        scope: Scope,
+        impersonation_chain: Vec<String>,
+    ) -> crate::Result<Self> {
+        let token = fetch_token(&source, &scope, &impersonation_chain).await?;
         Ok(Self(Arc::new(Inner {
-            creds: None,
+            source,
             scope,
+            impersonation_chain,
             token: RwLock::new(token),
         })))
     }
@@ -112,10 +154,7 @@ impl GcpCredentials {
     }
 
     async fn regenerate_token(&self) -> crate::Result<()> {
-        let token = match &self.0.creds {
-            Some(creds) => fetch_token(creds, &self.0.scope).await?,
-            None => get_token_implicit().await?,
-        };
+        let token = fetch_token(&self.0.source, &self.0.scope, &self.0.impersonation_chain).await?;
         *self.0.token.write().unwrap() = token;
         Ok(())
     }
@@ -145,7 +184,37 @@ impl GcpCredentials {
     }
 }
 
-async fn fetch_token(creds: &Credentials, scope: &Scope) -> crate::Result<Token> {
+async fn fetch_token(
+    source: &TokenSource,
+    scope: &Scope,
+    impersonation_chain: &[String],
+) -> crate::Result<Token> {
+    // Impersonating service accounts requires a token for the `cloud-platform` scope, while the
+    // impersonated service account is issued a token for the requested scope.
+    let cloud_platform = Scope::CloudPlatform;
+    let source_scope = if impersonation_chain.is_empty() {
+        scope
+    } else {
+        &cloud_platform
+    };
+
+    let token = match source {
+        TokenSource::ServiceAccount(creds) => {
+            fetch_service_account_token(creds, source_scope).await?
+        }
+        TokenSource::ExternalAccount(account) => account.fetch_token(source_scope).await?,
+        TokenSource::Implicit => get_token_implicit().await?,
+    };
+
+    match impersonation_chain.split_last() {
+        Some((target, delegates)) => {
+            impersonation::impersonate(&token, target, delegates, scope).await
+        }
+        None => Ok(token),
+    }
+}
+
+async fn fetch_service_account_token(creds: &Credentials, scope: &Scope) -> crate::Result<Token> {
     let claims = JwtClaims::new(creds.iss(), scope, creds.token_uri(), None, None);
     let rsa_key = creds.rsa_key().context(InvalidRsaKeySnafu)?;
     let jwt = Jwt::new(claims, rsa_key, None);
@@ -191,11 +260,66 @@ async fn get_token_implicit() -> Result<Token, GcpError> {
     }
 }
 
+/// Sends the request, returning the body of its successful response.
+async fn send(request: http::Request<hyper::Body>) -> crate::Result<Bytes> {
+    let uri = request.uri().clone();
+    let response = HttpClient::new(None, &ProxyConfig::from_env())?
+        .send(request)
+        .await?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    if !status.is_success() {
+        return Err(format!(
+            "{} responded with {}: {}",
+            uri,
+            status,
+            String::from_utf8_lossy(&body)
+        )
+        .into());
+    }
+    Ok(body)
+}
+
+/// Sends the request, returning the JSON body of its successful response.
+async fn send_json<T: DeserializeOwned>(request: http::Request<hyper::Body>) -> crate::Result<T> {
+    Ok(serde_json::from_slice(&send(request).await?)?)
+}
+
+/// Builds a bearer token, as issued by the token endpoints other than the OAuth one.
+fn bearer_token(access_token: String, expires_in: u32) -> crate::Result<Token> {
+    Ok(serde_json::from_value(serde_json::json!({
+        "access_token": access_token,
+        "token_type": "Bearer",
+        "expires_in": expires_in,
+    }))?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::assert_downcast_matches;
 
+    #[test]
+    fn builds_bearer_tokens() {
+        let token = bearer_token("token".to_owned(), 3600).unwrap();
+        assert_eq!(token.token_type(), "Bearer");
+        assert_eq!(token.access_token(), "token");
+        assert_eq!(token.expires_in(), 3600);
+    }
+
+    #[test]
+    fn parses_impersonation_chain() {
+        let config: GcpAuthConfig = toml::from_str(
+            r#"
+            credentials_path = "/path/to/credentials.json"
+            impersonation_chain = ["delegate@project.iam.gserviceaccount.com", "target@project.iam.gserviceaccount.com"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.impersonation_chain.len(), 2);
+    }
+
     #[tokio::test]
     #[ignore]
     async fn fails_missing_creds() {
@@ -1,8 +1,10 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, fmt::Write, path::PathBuf};
 
 use clap::Parser;
+#[cfg(feature = "api-client")]
+use url::Url;
 
-use crate::config;
+use crate::config::{self, ComponentKey, Config, OutputId};
 
 #[derive(Parser, Debug)]
 #[clap(rename_all = "kebab-case")]
@@ -44,6 +46,45 @@ pub struct Opts {
         use_value_delimiter(true)
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// Format of the graph.
+    #[clap(long, default_value = "dot", possible_values = &["dot", "mermaid", "d2"])]
+    format: Format,
+
+    /// Vector GraphQL API server endpoint of a running instance. If given, the edges of the graph
+    /// are annotated with the events per second flowing through them, and the components with the
+    /// errors per second they run into.
+    #[cfg(feature = "api-client")]
+    #[clap(short, long)]
+    url: Option<Url>,
+
+    /// Interval to sample metrics over when annotating the graph, in milliseconds
+    #[cfg(feature = "api-client")]
+    #[clap(default_value = "1000", short, long)]
+    interval: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Dot,
+    Mermaid,
+    D2,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(Format::Dot),
+            "mermaid" => Ok(Format::Mermaid),
+            "d2" => Ok(Format::D2),
+            s => Err(format!(
+                "{} is not a valid option, expected `dot`, `mermaid` or `d2`",
+                s
+            )),
+        }
+    }
 }
 
 impl Opts {
@@ -64,7 +105,7 @@ impl Opts {
     }
 }
 
-pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
     let paths = opts.paths_with_formats();
     let paths = match config::process_paths(&paths) {
         Some(paths) => paths,
@@ -82,48 +123,403 @@ pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
         }
     };
 
+    #[cfg(feature = "api-client")]
+    let rates = match &opts.url {
+        Some(url) => match sample_rates(url, opts.interval).await {
+            Ok(rates) => Some(rates),
+            Err(error) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("{}", error);
+                }
+                return exitcode::UNAVAILABLE;
+            }
+        },
+        None => None,
+    };
+    #[cfg(not(feature = "api-client"))]
+    let rates: Option<Rates> = None;
+
+    let graph = render(&config, opts.format, rates.as_ref());
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{}", graph);
+    }
+
+    exitcode::OK
+}
+
+/// The rates measured on a running instance, in events per second for each output of a component
+/// and in errors per second for each component.
+#[derive(Debug, Default)]
+struct Rates {
+    events: HashMap<OutputId, f64>,
+    errors: HashMap<ComponentKey, f64>,
+}
+
+impl Rates {
+    fn edge_label(&self, input: &OutputId) -> String {
+        let events = self.events.get(input).copied().unwrap_or_default();
+        format!("{:.1} events/s", events)
+    }
+
+    fn node_label(&self, id: &ComponentKey) -> Option<String> {
+        self.errors
+            .get(id)
+            .filter(|errors| **errors > 0.0)
+            .map(|errors| format!("{} ({:.1} errors/s)", id, errors))
+    }
+}
+
+/// Samples the rates of the components of a running instance through its API.
+#[cfg(feature = "api-client")]
+async fn sample_rates(url: &Url, interval: u32) -> Result<Rates, String> {
+    use tokio_stream::StreamExt;
+    use vector_api_client::{connect_subscription_client, gql::MetricsSubscriptionExt, Client};
+    use vector_core::internal_event::DEFAULT_OUTPUT;
+
+    if Client::new_with_healthcheck(url.clone()).await.is_none() {
+        return Err(format!("Couldn't connect to the Vector API at {}.", url));
+    }
+
+    let mut ws_url = url.clone();
+    ws_url
+        .set_scheme(match url.scheme() {
+            "https" => "wss",
+            _ => "ws",
+        })
+        .expect("Couldn't build WebSocket URL. Please report.");
+    // Each subscription goes through a client of its own, as `vector top` does, since subscriptions
+    // don't multiplex over a single client.
+    let mut clients = Vec::new();
+    for _ in 0..2 {
+        let client = connect_subscription_client(ws_url.clone())
+            .await
+            .map_err(|error| {
+                format!("Couldn't connect to the Vector API at {}: {}.", url, error)
+            })?;
+        clients.push(client);
+    }
+    let (throughputs_client, errors_client) = (&clients[0], &clients[1]);
+
+    let interval = i64::from(interval.max(1));
+    let per_sec = 1000.0 / interval as f64;
+    let throughputs = throughputs_client.component_sent_events_throughputs_subscription(interval);
+    let errors = errors_client.component_errors_totals_subscription(interval);
+    tokio::pin!(throughputs, errors);
+
+    let mut rates = Rates::default();
+    let data = match throughputs.next().await {
+        Some(Some(response)) => response.data,
+        _ => None,
+    };
+    for component in data
+        .map(|data| data.component_sent_events_throughputs)
+        .into_iter()
+        .flatten()
+    {
+        let component_key = ComponentKey::from(component.component_id.as_str());
+        for (port, throughput) in component.outputs() {
+            let output = OutputId {
+                component: component_key.clone(),
+                port: (port != DEFAULT_OUTPUT).then(|| port),
+            };
+            rates.events.insert(output, throughput as f64 * per_sec);
+        }
+    }
+
+    // Errors are only reported as totals, so their rate is measured between two samples.
+    let mut samples = Vec::new();
+    while samples.len() < 2 {
+        match errors.next().await {
+            Some(Some(response)) => samples.push(
+                response
+                    .data
+                    .map(|data| data.component_errors_totals)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|component| {
+                        let component_key = ComponentKey::from(component.component_id.as_str());
+                        (component_key, component.metric.errors_total)
+                    })
+                    .collect::<HashMap<_, _>>(),
+            ),
+            _ => return Err("The Vector API closed the connection.".to_owned()),
+        }
+    }
+    for (component_key, total) in samples.pop().unwrap_or_default() {
+        let previous = samples[0].get(&component_key).copied().unwrap_or(total);
+        rates
+            .errors
+            .insert(component_key, (total - previous).max(0.0) * per_sec);
+    }
+
+    Ok(rates)
+}
+
+/// Renders the topology of a configuration in the given format, annotated with the given rates.
+fn render(config: &Config, format: Format, rates: Option<&Rates>) -> String {
+    let nodes = config
+        .sources()
+        .map(|(id, _)| (id, Kind::Source))
+        .chain(config.transforms().map(|(id, _)| (id, Kind::Transform)))
+        .chain(config.sinks().map(|(id, _)| (id, Kind::Sink)));
+    let edges = config
+        .transforms()
+        .flat_map(|(id, transform)| transform.inputs.iter().map(move |input| (input, id)))
+        .chain(
+            config
+                .sinks()
+                .flat_map(|(id, sink)| sink.inputs.iter().map(move |input| (input, id))),
+        );
+
+    match format {
+        Format::Dot => render_dot(config, rates),
+        Format::Mermaid => render_mermaid(nodes, edges, rates),
+        Format::D2 => render_d2(nodes, edges, rates),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Source,
+    Transform,
+    Sink,
+}
+
+/// Joins the port an edge starts from, if any, with the rate flowing through it.
+fn edge_label(input: &OutputId, rates: Option<&Rates>) -> Option<String> {
+    let rate = rates.map(|rates| rates.edge_label(input));
+    match (&input.port, rate) {
+        (Some(port), Some(rate)) => Some(format!("{} {}", port, rate)),
+        (Some(port), None) => Some(port.clone()),
+        (None, rate) => rate,
+    }
+}
+
+fn render_dot(config: &Config, rates: Option<&Rates>) -> String {
     let mut dot = String::from("digraph {\n");
 
+    let node = |id: &ComponentKey, shape: &str| match rates.and_then(|rates| rates.node_label(id)) {
+        Some(label) => format!(
+            "  \"{}\" [shape={} label=\"{}\" color=red]\n",
+            id, shape, label
+        ),
+        None => format!("  \"{}\" [shape={}]\n", id, shape),
+    };
+    let edge = |input: &OutputId, id: &ComponentKey| match edge_label(input, rates) {
+        Some(label) => format!(
+            "  \"{}\" -> \"{}\" [label=\"{}\"]\n",
+            input.component, id, label
+        ),
+        None => format!("  \"{}\" -> \"{}\"\n", input, id),
+    };
+
     for (id, _source) in config.sources() {
-        dot += &format!("  \"{}\" [shape=trapezium]\n", id);
+        dot += &node(id, "trapezium");
     }
 
     for (id, transform) in config.transforms() {
-        dot += &format!("  \"{}\" [shape=diamond]\n", id);
+        dot += &node(id, "diamond");
 
         for input in transform.inputs.iter() {
-            if let Some(port) = &input.port {
-                dot += &format!(
-                    "  \"{}\" -> \"{}\" [label=\"{}\"]\n",
-                    input.component, id, port
-                );
-            } else {
-                dot += &format!("  \"{}\" -> \"{}\"\n", input, id);
-            }
+            dot += &edge(input, id);
         }
     }
 
     for (id, sink) in config.sinks() {
-        dot += &format!("  \"{}\" [shape=invtrapezium]\n", id);
+        dot += &node(id, "invtrapezium");
 
         for input in &sink.inputs {
-            if let Some(port) = &input.port {
-                dot += &format!(
-                    "  \"{}\" -> \"{}\" [label=\"{}\"]\n",
-                    input.component, id, port
+            dot += &edge(input, id);
+        }
+    }
+
+    dot += "}";
+    dot
+}
+
+fn render_mermaid<'a>(
+    nodes: impl Iterator<Item = (&'a ComponentKey, Kind)>,
+    edges: impl Iterator<Item = (&'a OutputId, &'a ComponentKey)>,
+    rates: Option<&Rates>,
+) -> String {
+    // Component IDs may hold characters Mermaid doesn't allow in node IDs, so nodes are named by
+    // their position and labeled with their component ID.
+    let mut ids = HashMap::new();
+    let mut mermaid = String::from("flowchart LR\n");
+
+    for (index, (id, kind)) in nodes.enumerate() {
+        let node = format!("n{}", index);
+        let label = rates
+            .and_then(|rates| rates.node_label(id))
+            .unwrap_or_else(|| id.to_string())
+            .replace('"', "#quot;");
+        let (open, close) = match kind {
+            Kind::Source => ("[/", "\\]"),
+            Kind::Transform => ("{", "}"),
+            Kind::Sink => ("[\\", "/]"),
+        };
+        let _ = writeln!(mermaid, "  {}{}\"{}\"{}", node, open, label, close);
+        if rates.and_then(|rates| rates.node_label(id)).is_some() {
+            let _ = writeln!(mermaid, "  style {} stroke:red", node);
+        }
+        ids.insert(id, node);
+    }
+
+    for (input, id) in edges {
+        let (from, to) = match (ids.get(&input.component), ids.get(id)) {
+            (Some(from), Some(to)) => (from, to),
+            _ => continue,
+        };
+        match edge_label(input, rates) {
+            Some(label) => {
+                let _ = writeln!(mermaid, "  {} -->|\"{}\"| {}", from, label, to);
+            }
+            None => {
+                let _ = writeln!(mermaid, "  {} --> {}", from, to);
+            }
+        }
+    }
+
+    mermaid.trim_end().to_owned()
+}
+
+fn render_d2<'a>(
+    nodes: impl Iterator<Item = (&'a ComponentKey, Kind)>,
+    edges: impl Iterator<Item = (&'a OutputId, &'a ComponentKey)>,
+    rates: Option<&Rates>,
+) -> String {
+    let mut d2 = String::new();
+
+    for (id, kind) in nodes {
+        let shape = match kind {
+            Kind::Source => "parallelogram",
+            Kind::Transform => "diamond",
+            Kind::Sink => "cylinder",
+        };
+        match rates.and_then(|rates| rates.node_label(id)) {
+            Some(label) => {
+                let _ = writeln!(
+                    d2,
+                    "{:?}: {:?} {{shape: {}; style.stroke: red}}",
+                    id.id(),
+                    label,
+                    shape
                 );
-            } else {
-                dot += &format!("  \"{}\" -> \"{}\"\n", input, id);
+            }
+            None => {
+                let _ = writeln!(d2, "{:?}: {{shape: {}}}", id.id(), shape);
             }
         }
     }
 
-    dot += "}";
+    for (input, id) in edges {
+        match edge_label(input, rates) {
+            Some(label) => {
+                let _ = writeln!(
+                    d2,
+                    "{:?} -> {:?}: {:?}",
+                    input.component.id(),
+                    id.id(),
+                    label
+                );
+            }
+            None => {
+                let _ = writeln!(d2, "{:?} -> {:?}", input.component.id(), id.id());
+            }
+        }
+    }
 
-    #[allow(clippy::print_stdout)]
-    {
-        println!("{}", dot);
+    d2.trim_end().to_owned()
+}
+
+#[cfg(all(
+    test,
+    feature = "sources-demo_logs",
+    feature = "sinks-console",
+    feature = "transforms-route"
+))]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::config::{load_from_str, Format as ConfigFormat};
+
+    fn config() -> Config {
+        load_from_str(
+            indoc! {r#"
+                [sources.in]
+                type = "demo_logs"
+                format = "json"
+
+                [transforms.split]
+                type = "route"
+                inputs = ["in"]
+                route.errors = '.level == "error"'
+
+                [sinks.out]
+                type = "console"
+                inputs = ["split.errors"]
+                encoding = "json"
+            "#},
+            ConfigFormat::Toml,
+        )
+        .unwrap()
     }
 
-    exitcode::OK
+    fn rates() -> Rates {
+        Rates {
+            events: HashMap::from([
+                (OutputId::from(&ComponentKey::from("in")), 120.0),
+                (
+                    OutputId {
+                        component: ComponentKey::from("split"),
+                        port: Some("errors".to_owned()),
+                    },
+                    4.5,
+                ),
+            ]),
+            errors: HashMap::from([(ComponentKey::from("out"), 0.5)]),
+        }
+    }
+
+    #[test]
+    fn renders_mermaid() {
+        assert_eq!(
+            render(&config(), Format::Mermaid, None),
+            indoc! {r#"
+                flowchart LR
+                  n0[/"in"\]
+                  n1{"split"}
+                  n2[\"out"/]
+                  n0 --> n1
+                  n1 -->|"errors"| n2"#}
+        );
+        assert_eq!(
+            render(&config(), Format::Mermaid, Some(&rates())),
+            indoc! {r#"
+                flowchart LR
+                  n0[/"in"\]
+                  n1{"split"}
+                  n2[\"out (0.5 errors/s)"/]
+                  style n2 stroke:red
+                  n0 -->|"120.0 events/s"| n1
+                  n1 -->|"errors 4.5 events/s"| n2"#}
+        );
+    }
+
+    #[test]
+    fn renders_d2() {
+        assert_eq!(
+            render(&config(), Format::D2, Some(&rates())),
+            indoc! {r#"
+                "in": {shape: parallelogram}
+                "split": {shape: diamond}
+                "out": "out (0.5 errors/s)" {shape: cylinder; style.stroke: red}
+                "in" -> "split": "120.0 events/s"
+                "split" -> "out": "errors 4.5 events/s""#}
+        );
+    }
 }
@@ -1,26 +1,51 @@
 use std::{
-    fmt,
+    fmt, io,
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex, RwLock},
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
 use futures::future::BoxFuture;
 use headers::{Authorization, HeaderMapExt};
-use http::{header::HeaderValue, request::Builder, uri::InvalidUri, HeaderMap, Request, Uri};
+use http::{
+    header::HeaderValue,
+    request::Builder,
+    uri::{InvalidUri, Scheme},
+    HeaderMap, Request, Uri,
+};
 use hyper::{
     body::{Body, HttpBody},
     client,
-    client::{Client, HttpConnector},
+    client::{
+        connect::{Connected, Connection},
+        Client, HttpConnector,
+    },
 };
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
+#[cfg(windows)]
+use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeClient};
+#[cfg(unix)]
+use tokio::net::UnixStream;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::{lookup_host, TcpStream},
+};
 use tower::Service;
 use tracing::Instrument;
+use vector_core::config::{proxy::Socks5Proxy, HttpClientConfig, HttpVersion};
 
 use crate::{
     config::ProxyConfig,
     internal_events::http_client,
+    oauth2::OAuth2Config,
+    socks5::{self, TargetAddr},
     tls::{tls_connector_builder, MaybeTlsSettings, TlsError},
 };
 
@@ -41,11 +66,39 @@ pub enum HttpError {
 
 pub type HttpClientFuture = <HttpClient as Service<http::Request<Body>>>::Future;
 
+type Connector = ProxyConnector<HttpsConnector<Transport>>;
+
+/// The tuning of the HTTP clients created from now on, set from the global `http_client` options.
+static CLIENT_CONFIG: Lazy<RwLock<HttpClientConfig>> = Lazy::new(Default::default);
+
+/// Sets the tuning of the HTTP clients created from now on.
+pub fn set_client_config(config: HttpClientConfig) {
+    *CLIENT_CONFIG
+        .write()
+        .expect("HTTP client config lock poisoned") = config;
+}
+
+fn client_config() -> HttpClientConfig {
+    CLIENT_CONFIG
+        .read()
+        .expect("HTTP client config lock poisoned")
+        .clone()
+}
+
 pub struct HttpClient<B = Body> {
-    client: Client<ProxyConnector<HttpsConnector<HttpConnector>>, B>,
+    pool: Arc<Mutex<Pool<B>>>,
+    builder: client::Builder,
+    connector: Connector,
+    dns_refresh: Option<Duration>,
     user_agent: HeaderValue,
 }
 
+/// A client, and so a pool of connections, along with when it was created.
+struct Pool<B> {
+    client: Client<Connector, B>,
+    created: Instant,
+}
+
 impl<B> HttpClient<B>
 where
     B: fmt::Debug + HttpBody + Send + 'static,
@@ -56,7 +109,30 @@ where
         tls_settings: impl Into<MaybeTlsSettings>,
         proxy_config: &ProxyConfig,
     ) -> Result<HttpClient<B>, HttpError> {
-        HttpClient::new_with_custom_client(tls_settings, proxy_config, &mut Client::builder())
+        let config = client_config();
+        HttpClient::with_config(
+            tls_settings.into(),
+            Transport::Tcp(http_connector(&config)),
+            Some(proxy_config),
+            &config,
+            client_builder(&config),
+        )
+    }
+
+    /// Creates a client that sends every request over the given local socket, a Unix domain socket
+    /// or a Windows named pipe, whatever the host of its URI.
+    pub fn new_with_socket(
+        tls_settings: impl Into<MaybeTlsSettings>,
+        socket_path: &Path,
+    ) -> Result<HttpClient<B>, HttpError> {
+        let config = client_config();
+        HttpClient::with_config(
+            tls_settings.into(),
+            Transport::Local(socket_path.to_owned()),
+            None,
+            &config,
+            client_builder(&config),
+        )
     }
 
     pub fn new_with_custom_client(
@@ -64,14 +140,78 @@ where
         proxy_config: &ProxyConfig,
         client_builder: &mut client::Builder,
     ) -> Result<HttpClient<B>, HttpError> {
-        let proxy = build_proxy_connector(tls_settings.into(), proxy_config)?;
-        let client = client_builder.build(proxy);
+        let config = client_config();
+        HttpClient::with_config(
+            tls_settings.into(),
+            Transport::Tcp(http_connector(&config)),
+            Some(proxy_config),
+            &config,
+            client_builder.clone(),
+        )
+    }
+
+    /// Creates a client connecting over the given transport, with the given proxy settings, if
+    /// any.
+    fn with_config(
+        tls_settings: MaybeTlsSettings,
+        transport: Transport,
+        proxy_config: Option<&ProxyConfig>,
+        config: &HttpClientConfig,
+        builder: client::Builder,
+    ) -> Result<HttpClient<B>, HttpError> {
+        let socks5 = match proxy_config {
+            Some(proxy_config) => proxy_config
+                .socks5_proxy()
+                .context(MakeProxyConnectorSnafu)?,
+            None => None,
+        };
+        // Requests go either through the SOCKS5 proxy, which tunnels connections for every
+        // scheme, or through the HTTP(S) proxies.
+        let (transport, proxy_config) = match (transport, socks5) {
+            (Transport::Tcp(http), Some(proxy)) => (Transport::Socks5(http, Arc::new(proxy)), None),
+            (transport, _) => (transport, proxy_config),
+        };
+        let https = build_https_connector(transport, tls_settings, config)?;
+        let mut connector = ProxyConnector::new(https).unwrap();
+        if let Some(proxy_config) = proxy_config {
+            proxy_config
+                .configure(&mut connector)
+                .context(MakeProxyConnectorSnafu)?;
+        }
+        let pool = Pool {
+            client: builder.build(connector.clone()),
+            created: Instant::now(),
+        };
 
         let version = crate::get_version();
         let user_agent = HeaderValue::from_str(&format!("Vector/{}", version))
             .expect("Invalid header value for version!");
 
-        Ok(HttpClient { client, user_agent })
+        Ok(HttpClient {
+            pool: Arc::new(Mutex::new(pool)),
+            builder,
+            connector,
+            dns_refresh: config
+                .dns_refresh_secs
+                .map(|secs| Duration::from_secs(secs.get())),
+            user_agent,
+        })
+    }
+
+    /// Gets the client to send a request with, replacing it with a new one, with a pool of new
+    /// connections to hosts resolved again, once it's older than the DNS refresh interval.
+    fn client(&self) -> Client<Connector, B> {
+        let mut pool = self.pool.lock().expect("HTTP client pool lock poisoned");
+        if let Some(refresh) = self.dns_refresh {
+            if pool.created.elapsed() >= refresh {
+                // Requests in flight keep the connections of the old client until they're done.
+                *pool = Pool {
+                    client: self.builder.build(self.connector.clone()),
+                    created: Instant::now(),
+                };
+            }
+        }
+        pool.client.clone()
     }
 
     pub fn send(
@@ -85,7 +225,7 @@ where
 
         emit!(http_client::AboutToSendHttpRequest { request: &request });
 
-        let response = self.client.request(request);
+        let response = self.client().request(request);
 
         let fut = async move {
             // Capture the time right before we issue the request.
@@ -124,6 +264,24 @@ where
     }
 }
 
+/// Builds the settings of a client from the tuning of the HTTP client.
+fn client_builder(config: &HttpClientConfig) -> client::Builder {
+    let mut builder = Client::builder();
+    if let Some(max) = config.pool_max_idle_per_host {
+        builder.pool_max_idle_per_host(max.get());
+    }
+    if let Some(secs) = config.pool_idle_timeout_secs {
+        builder.pool_idle_timeout(Duration::from_secs(secs.get()));
+    }
+    if let Some(secs) = config.http2_keepalive_secs {
+        builder.http2_keep_alive_interval(Duration::from_secs(secs.get()));
+    }
+    if config.version == HttpVersion::Http2 {
+        builder.http2_only(true);
+    }
+    builder
+}
+
 pub fn build_proxy_connector(
     tls_settings: MaybeTlsSettings,
     proxy_config: &ProxyConfig,
@@ -139,11 +297,42 @@ pub fn build_proxy_connector(
 pub fn build_tls_connector(
     tls_settings: MaybeTlsSettings,
 ) -> Result<HttpsConnector<HttpConnector>, HttpError> {
+    let config = HttpClientConfig::default();
+    build_https_connector(http_connector(&config), tls_settings, &config)
+}
+
+fn http_connector(config: &HttpClientConfig) -> HttpConnector {
     let mut http = HttpConnector::new();
     http.enforce_http(false);
+    if let Some(secs) = config.tcp_keepalive_secs {
+        http.set_keepalive(Some(Duration::from_secs(secs.get())));
+    }
+    if let Some(ms) = config.happy_eyeballs_timeout_ms {
+        http.set_happy_eyeballs_timeout((ms > 0).then(|| Duration::from_millis(ms)));
+    }
+    http
+}
 
-    let tls = tls_connector_builder(&tls_settings).context(BuildTlsConnectorSnafu)?;
-    let mut https = HttpsConnector::with_connector(http, tls).context(MakeHttpsConnectorSnafu)?;
+fn build_https_connector<T>(
+    transport: T,
+    tls_settings: MaybeTlsSettings,
+    config: &HttpClientConfig,
+) -> Result<HttpsConnector<T>, HttpError>
+where
+    T: Service<Uri> + Send,
+    T::Response: AsyncRead + AsyncWrite + Connection + Unpin + fmt::Debug + Sync + Send + 'static,
+    T::Error: Into<crate::Error>,
+    T::Future: Unpin + Send + 'static,
+{
+    let mut tls = tls_connector_builder(&tls_settings).context(BuildTlsConnectorSnafu)?;
+    if config.version == HttpVersion::Auto {
+        // Offer HTTP/2 while negotiating TLS, falling back to HTTP/1.1 if the server doesn't
+        // pick it.
+        tls.set_alpn_protos(b"\x02h2\x08http/1.1")
+            .context(MakeHttpsConnectorSnafu)?;
+    }
+    let mut https =
+        HttpsConnector::with_connector(transport, tls).context(MakeHttpsConnectorSnafu)?;
 
     let settings = tls_settings.tls().cloned();
     https.set_callback(move |c, _uri| {
@@ -156,6 +345,168 @@ pub fn build_tls_connector(
     Ok(https)
 }
 
+/// Connects to the host of a URI over TCP, directly or through a SOCKS5 proxy, or to a local
+/// socket whatever the host.
+#[derive(Clone, Debug)]
+pub enum Transport {
+    Tcp(HttpConnector),
+    Socks5(HttpConnector, Arc<Socks5Proxy>),
+    Local(PathBuf),
+}
+
+impl Service<Uri> for Transport {
+    type Response = TransportStream;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self {
+            Self::Tcp(http) | Self::Socks5(http, _) => http.poll_ready(cx).map_err(Into::into),
+            Self::Local(_) => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        match self {
+            Self::Tcp(http) => {
+                let connecting = http.call(uri);
+                Box::pin(async move { Ok(TransportStream::Tcp(connecting.await?)) })
+            }
+            Self::Socks5(http, proxy) => {
+                Box::pin(connect_socks5(http.clone(), Arc::clone(proxy), uri))
+            }
+            Self::Local(path) => Box::pin(connect_local(path.clone())),
+        }
+    }
+}
+
+/// Connects to the host of the URI through the SOCKS5 proxy, or directly if the host is excluded
+/// from the proxy.
+async fn connect_socks5(
+    mut http: HttpConnector,
+    proxy: Arc<Socks5Proxy>,
+    uri: Uri,
+) -> crate::Result<TransportStream> {
+    let host = uri
+        .host()
+        .ok_or("URI has no host")?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_owned();
+    let port = uri.port_u16().unwrap_or_else(|| {
+        if uri.scheme() == Some(&Scheme::HTTPS) {
+            443
+        } else {
+            80
+        }
+    });
+    if !proxy.intercepts(&host, port) {
+        return Ok(TransportStream::Tcp(http.call(uri).await?));
+    }
+
+    let proxy_uri = format!("http://{}:{}", proxy.host, proxy.port).parse::<Uri>()?;
+    let mut stream = http.call(proxy_uri).await?;
+    let target = match host.parse::<IpAddr>() {
+        Ok(ip) => TargetAddr::Ip(SocketAddr::new(ip, port)),
+        Err(_) if proxy.remote_dns => TargetAddr::Domain(host, port),
+        Err(_) => TargetAddr::Ip(
+            lookup_host((host.as_str(), port))
+                .await?
+                .next()
+                .ok_or("Host has no addresses")?,
+        ),
+    };
+    socks5::connect(&mut stream, &target, proxy.auth.as_ref()).await?;
+    Ok(TransportStream::Tcp(stream))
+}
+
+#[cfg(unix)]
+async fn connect_local(path: PathBuf) -> crate::Result<TransportStream> {
+    Ok(TransportStream::Unix(UnixStream::connect(path).await?))
+}
+
+#[cfg(windows)]
+async fn connect_local(path: PathBuf) -> crate::Result<TransportStream> {
+    // Returned while every instance of the pipe is connected to another client.
+    const ERROR_PIPE_BUSY: i32 = 231;
+
+    loop {
+        match ClientOptions::new().open(&path) {
+            Ok(client) => return Ok(TransportStream::Pipe(client)),
+            Err(error) if error.raw_os_error() == Some(ERROR_PIPE_BUSY) => {
+                tokio::time::sleep(Duration::from_millis(50)).await
+            }
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+async fn connect_local(_path: PathBuf) -> crate::Result<TransportStream> {
+    Err("Local sockets are not supported on this platform.".into())
+}
+
+/// A connection made by [`Transport`].
+#[derive(Debug)]
+pub enum TransportStream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+    #[cfg(windows)]
+    Pipe(NamedPipeClient),
+}
+
+/// Calls the same method on whichever stream the connection is.
+macro_rules! with_stream {
+    ($this:expr, $stream:ident => $body:expr) => {
+        match $this.get_mut() {
+            TransportStream::Tcp($stream) => $body,
+            #[cfg(unix)]
+            TransportStream::Unix($stream) => $body,
+            #[cfg(windows)]
+            TransportStream::Pipe($stream) => $body,
+        }
+    };
+}
+
+impl AsyncRead for TransportStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        with_stream!(self, stream => Pin::new(stream).poll_read(cx, buf))
+    }
+}
+
+impl AsyncWrite for TransportStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        with_stream!(self, stream => Pin::new(stream).poll_write(cx, buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        with_stream!(self, stream => Pin::new(stream).poll_flush(cx))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        with_stream!(self, stream => Pin::new(stream).poll_shutdown(cx))
+    }
+}
+
+impl Connection for TransportStream {
+    fn connected(&self) -> Connected {
+        match self {
+            Self::Tcp(stream) => stream.connected(),
+            #[cfg(any(unix, windows))]
+            _ => Connected::new(),
+        }
+    }
+}
+
 fn default_request_headers<B>(request: &mut Request<B>, user_agent: &HeaderValue) {
     if !request.headers().contains_key("User-Agent") {
         request
@@ -194,7 +545,10 @@ where
 impl<B> Clone for HttpClient<B> {
     fn clone(&self) -> Self {
         Self {
-            client: self.client.clone(),
+            pool: Arc::clone(&self.pool),
+            builder: self.builder.clone(),
+            connector: self.connector.clone(),
+            dns_refresh: self.dns_refresh,
             user_agent: self.user_agent.clone(),
         }
     }
@@ -203,7 +557,8 @@ impl<B> Clone for HttpClient<B> {
 impl<B> fmt::Debug for HttpClient<B> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("HttpClient")
-            .field("client", &self.client)
+            .field("builder", &self.builder)
+            .field("dns_refresh", &self.dns_refresh)
             .field("user_agent", &self.user_agent)
             .finish()
     }
@@ -212,8 +567,15 @@ impl<B> fmt::Debug for HttpClient<B> {
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 #[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
 pub enum Auth {
-    Basic { user: String, password: String },
-    Bearer { token: String },
+    Basic {
+        user: String,
+        password: String,
+    },
+    Bearer {
+        token: String,
+    },
+    #[serde(rename = "oauth2")]
+    OAuth2(OAuth2Config),
 }
 
 pub trait MaybeAuth: Sized {
@@ -231,6 +593,15 @@ impl MaybeAuth for Option<Auth> {
 }
 
 impl Auth {
+    /// Fetches the first token of OAuth2 authentication and keeps it refreshed, so that it's
+    /// applied to requests from then on. Other strategies need nothing of the kind.
+    pub async fn prepare(&self, client: &HttpClient) -> crate::Result<()> {
+        match self {
+            Auth::OAuth2(config) => config.start(client).await,
+            Auth::Basic { .. } | Auth::Bearer { .. } => Ok(()),
+        }
+    }
+
     pub fn apply<B>(&self, req: &mut Request<B>) {
         self.apply_headers_map(req.headers_mut())
     }
@@ -252,6 +623,7 @@ impl Auth {
                 Ok(auth) => map.typed_insert(auth),
                 Err(error) => error!(message = "Invalid bearer token.", token = %token, %error),
             },
+            Auth::OAuth2(config) => config.apply_headers_map(map),
         }
     }
 }
@@ -1,10 +1,13 @@
 use std::time::Duration;
 
-use metrics::histogram;
+use metrics::{counter, histogram};
 use vector_core::internal_event::InternalEvent;
 
 #[derive(Debug)]
-pub struct AdaptiveConcurrencyLimit {
+pub struct AdaptiveConcurrencyLimit<'a> {
+    pub endpoint: Option<&'a str>,
+    pub decision: &'static str,
+    pub reason: &'static str,
     pub concurrency: u64,
     pub reached_limit: bool,
     pub had_back_pressure: bool,
@@ -13,10 +16,13 @@ pub struct AdaptiveConcurrencyLimit {
     pub past_rtt_deviation: Duration,
 }
 
-impl InternalEvent for AdaptiveConcurrencyLimit {
+impl<'a> InternalEvent for AdaptiveConcurrencyLimit<'a> {
     fn emit(self) {
-        trace!(
-            message = "Changed concurrency.",
+        debug!(
+            message = "Adjusted concurrency.",
+            endpoint = ?self.endpoint,
+            decision = %self.decision,
+            reason = %self.reason,
             concurrency = %self.concurrency,
             reached_limit = %self.reached_limit,
             had_back_pressure = %self.had_back_pressure,
@@ -24,10 +30,31 @@ impl InternalEvent for AdaptiveConcurrencyLimit {
             past_rtt = ?self.past_rtt,
             past_rtt_deviation = ?self.past_rtt_deviation,
         );
+        match self.endpoint {
+            Some(endpoint) => {
+                counter!(
+                    "adaptive_concurrency_decisions_total", 1,
+                    "decision" => self.decision,
+                    "reason" => self.reason,
+                    "endpoint" => endpoint.to_owned(),
+                );
+                histogram!(
+                    "adaptive_concurrency_limit", self.concurrency as f64,
+                    "endpoint" => endpoint.to_owned(),
+                );
+            }
+            None => {
+                counter!(
+                    "adaptive_concurrency_decisions_total", 1,
+                    "decision" => self.decision,
+                    "reason" => self.reason,
+                );
+                histogram!("adaptive_concurrency_limit", self.concurrency as f64);
+            }
+        }
         // These are histograms, as they may have a number of different
         // values over each reporting interval, and each of those values
         // is valuable for diagnosis.
-        histogram!("adaptive_concurrency_limit", self.concurrency as f64);
         let reached_limit = self.reached_limit.then(|| 1.0).unwrap_or_default();
         histogram!("adaptive_concurrency_reached_limit", reached_limit);
         let back_pressure = self.had_back_pressure.then(|| 1.0).unwrap_or_default();
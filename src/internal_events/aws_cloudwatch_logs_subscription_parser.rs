@@ -1,7 +1,7 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
-use super::prelude::{error_stage, error_type, io_error_code};
+use super::prelude::{discard_reason, error_stage, error_type, io_error_code};
 
 #[derive(Debug)]
 pub struct AwsCloudwatchLogsSubscriptionParserError {
@@ -24,6 +24,7 @@ impl InternalEvent for AwsCloudwatchLogsSubscriptionParserError {
         );
         counter!(
             "component_discarded_events_total", 1,
+            "reason" => discard_reason::CODEC_ERROR,
             "error_type" => error_type::PARSER_FAILED,
             "stage" => error_stage::PROCESSING,
         );
@@ -59,6 +60,7 @@ impl InternalEvent for AwsCloudwatchLogsMessageSizeError {
         );
         counter!(
             "component_discarded_events_total", 1,
+            "reason" => discard_reason::INVALID_EVENT,
             "error_code" => "message_too_long",
             "error_type" => error_type::ENCODER_FAILED,
             "stage" => error_stage::PROCESSING,
@@ -90,6 +92,7 @@ impl InternalEvent for AwsCloudwatchLogsEncoderError {
         );
         counter!(
             "component_discarded_events_total", 1,
+            "reason" => discard_reason::CODEC_ERROR,
             "error_type" => error_type::ENCODER_FAILED,
             "error_code" => error_code,
             "stage" => error_stage::PROCESSING,
@@ -1,7 +1,7 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
-use super::prelude::{error_stage, error_type};
+use super::prelude::{discard_reason, error_stage, error_type};
 
 #[derive(Debug)]
 pub struct LargeEventDroppedError {
@@ -27,6 +27,7 @@ impl InternalEvent for LargeEventDroppedError {
         );
         counter!(
             "component_discarded_events_total", 1,
+            "reason" => discard_reason::INVALID_EVENT,
             "error_code" => "oversized",
             "error_type" => error_type::CONDITION_FAILED,
             "stage" => error_stage::SENDING,
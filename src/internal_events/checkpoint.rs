@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct CheckpointStoreError<'a> {
+    pub error: crate::Error,
+    pub key: &'a str,
+}
+
+impl<'a> InternalEvent for CheckpointStoreError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to save checkpoint to the store.",
+            error = %self.error,
+            key = %self.key,
+            error_code = "failed_saving_checkpoint",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_saving_checkpoint",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
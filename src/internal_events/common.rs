@@ -1,10 +1,11 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use metrics::{counter, histogram};
-use vector_core::internal_event::InternalEvent;
+use metrics::{counter, gauge, histogram};
 pub use vector_core::internal_event::{EventsReceived, OldEventsReceived};
+use vector_core::{event::EventPriority, internal_event::InternalEvent};
 
-use super::prelude::{error_stage, error_type};
+use super::prelude::{discard_reason, error_stage, error_type};
+use crate::topology::{health::HealthState, CircuitBreakerState};
 
 #[derive(Debug)]
 pub struct BytesReceived<'a> {
@@ -90,6 +91,7 @@ impl InternalEvent for StreamClosedError {
         );
         counter!(
             "component_discarded_events_total", self.count as u64,
+            "reason" => discard_reason::STREAM_CLOSED,
             "error_code" => STREAM_CLOSED,
             "error_type" => error_type::WRITER_FAILED,
             "stage" => error_stage::SENDING,
@@ -97,6 +99,266 @@ impl InternalEvent for StreamClosedError {
     }
 }
 
+#[derive(Debug)]
+pub struct PrioritizedEventsShed {
+    pub count: usize,
+    pub priority: EventPriority,
+}
+
+impl InternalEvent for PrioritizedEventsShed {
+    fn emit(self) {
+        debug!(
+            message = "Shed lower priority events to make room for higher priority events.",
+            count = %self.count,
+            priority = %self.priority,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_discarded_events_total", self.count as u64,
+            "reason" => discard_reason::BUFFER_FULL,
+            "priority" => self.priority.as_str(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AdmissionEventsDropped<'a> {
+    pub tenant: Option<&'a str>,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for AdmissionEventsDropped<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Dropped events over the admission limits.",
+            count = %self.count,
+            tenant = ?self.tenant,
+            internal_log_rate_secs = 10,
+        );
+        match self.tenant {
+            Some(tenant) => counter!(
+                "component_discarded_events_total", self.count as u64,
+                "reason" => discard_reason::RATE_LIMITED,
+                "tenant" => tenant.to_owned(),
+            ),
+            None => counter!(
+                "component_discarded_events_total", self.count as u64,
+                "reason" => discard_reason::RATE_LIMITED,
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AdmissionEventsThrottled<'a> {
+    pub tenant: Option<&'a str>,
+    pub wait: Duration,
+}
+
+impl<'a> InternalEvent for AdmissionEventsThrottled<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Waiting for events to be admitted.",
+            wait = ?self.wait,
+            tenant = ?self.tenant,
+        );
+        match self.tenant {
+            Some(tenant) => histogram!(
+                "admission_wait_seconds", self.wait,
+                "tenant" => tenant.to_owned(),
+            ),
+            None => histogram!("admission_wait_seconds", self.wait),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TenantEventsAdmitted<'a> {
+    pub tenant: &'a str,
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl<'a> InternalEvent for TenantEventsAdmitted<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Events admitted.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+            tenant = %self.tenant,
+        );
+        counter!(
+            "tenant_events_total", self.count as u64,
+            "tenant" => self.tenant.to_owned(),
+        );
+        counter!(
+            "tenant_event_bytes_total", self.byte_size as u64,
+            "tenant" => self.tenant.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TenantQuotaEventsDropped<'a> {
+    pub tenant: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for TenantQuotaEventsDropped<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Dropped events over the quota of their tenant.",
+            count = %self.count,
+            tenant = %self.tenant,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_discarded_events_total", self.count as u64,
+            "reason" => discard_reason::BUFFER_FULL,
+            "tenant" => self.tenant.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DeadLetterEventsRouted {
+    pub count: usize,
+}
+
+impl InternalEvent for DeadLetterEventsRouted {
+    fn emit(self) {
+        debug!(
+            message = "Routed events the sink failed to deliver to its dead letter output.",
+            count = %self.count,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_discarded_events_total", self.count as u64,
+            "reason" => discard_reason::DLQ_ROUTED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TenantBufferedEvents<'a> {
+    pub tenant: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for TenantBufferedEvents<'a> {
+    fn emit(self) {
+        gauge!(
+            "tenant_buffered_events", self.count as f64,
+            "tenant" => self.tenant.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ComponentMemoryBytes {
+    pub bytes: usize,
+}
+
+impl InternalEvent for ComponentMemoryBytes {
+    fn emit(self) {
+        gauge!("component_memory_bytes", self.bytes as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExpiredEventsDiscarded {
+    pub count: usize,
+}
+
+impl InternalEvent for ExpiredEventsDiscarded {
+    fn emit(self) {
+        debug!(
+            message = "Discarded events that expired before they could be delivered.",
+            count = %self.count,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_discarded_events_total", self.count as u64,
+            "reason" => discard_reason::EXPIRED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryLimitEventsShed {
+    pub count: usize,
+}
+
+impl InternalEvent for MemoryLimitEventsShed {
+    fn emit(self) {
+        warn!(
+            message = "Shed events over the hard memory limit.",
+            count = %self.count,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_discarded_events_total", self.count as u64,
+            "reason" => discard_reason::MEMORY_LIMIT,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryLimitThrottled {
+    pub wait: Duration,
+}
+
+impl InternalEvent for MemoryLimitThrottled {
+    fn emit(self) {
+        trace!(message = "Waited for memory to be released.", wait = ?self.wait);
+        histogram!("memory_limit_wait_seconds", self.wait);
+    }
+}
+
+#[derive(Debug)]
+pub struct CircuitBreakerStateChanged {
+    pub state: CircuitBreakerState,
+}
+
+impl InternalEvent for CircuitBreakerStateChanged {
+    fn emit(self) {
+        match self.state {
+            CircuitBreakerState::Open => {
+                warn!(message = "Circuit breaker opened, rerouting events to the fallback output.");
+                counter!("circuit_breaker_opened_total", 1);
+            }
+            state => debug!(
+                message = "Circuit breaker state changed.",
+                state = state.as_str()
+            ),
+        }
+        gauge!("circuit_breaker_state", self.state.as_gauge());
+    }
+}
+
+#[derive(Debug)]
+pub struct ComponentHealthChanged<'a> {
+    pub state: HealthState,
+    pub reason: Option<&'a str>,
+}
+
+impl InternalEvent for ComponentHealthChanged<'_> {
+    fn emit(self) {
+        match self.state {
+            HealthState::Failed => warn!(
+                message = "Component failed.",
+                reason = self.reason.unwrap_or_default(),
+            ),
+            state => debug!(
+                message = "Component health changed.",
+                state = state.as_str(),
+                reason = self.reason.unwrap_or_default(),
+            ),
+        }
+        gauge!("component_health_state", self.state.as_gauge());
+    }
+}
+
 #[derive(Debug)]
 pub struct FieldOverwritten<'a> {
     pub(crate) field: &'a str,
@@ -1,7 +1,7 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
-use super::prelude::{error_stage, error_type};
+use super::prelude::{discard_reason, error_stage, error_type};
 
 #[derive(Debug)]
 pub struct DatadogMetricsEncodingError {
@@ -29,6 +29,7 @@ impl InternalEvent for DatadogMetricsEncodingError {
         if self.dropped_events > 0 {
             counter!(
                 "component_discarded_events_total", self.dropped_events,
+                "reason" => discard_reason::CODEC_ERROR,
                 "error_code" => self.error_code,
                 "error_type" => error_type::ENCODER_FAILED,
                 "stage" => error_stage::PROCESSING,
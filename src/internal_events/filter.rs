@@ -11,3 +11,17 @@ impl InternalEvent for FilterEventDiscarded {
         counter!("events_discarded_total", self.total);
     }
 }
+
+#[derive(Debug)]
+pub struct FilterConditionMatched {
+    pub(crate) index: usize,
+}
+
+impl InternalEvent for FilterConditionMatched {
+    fn emit(self) {
+        counter!(
+            "filter_condition_matches_total", 1,
+            "condition_index" => self.index.to_string(),
+        );
+    }
+}
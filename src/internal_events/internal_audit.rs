@@ -0,0 +1,26 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct InternalAuditEventsLost {
+    pub count: u64,
+}
+
+impl InternalEvent for InternalAuditEventsLost {
+    fn emit(self) {
+        error!(
+            message = "Audit events lost, as the source fell behind.",
+            count = %self.count,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
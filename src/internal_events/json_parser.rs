@@ -2,7 +2,7 @@ use metrics::counter;
 use serde_json::Error;
 use vector_core::internal_event::InternalEvent;
 
-use super::prelude::{error_stage, error_type};
+use super::prelude::{discard_reason, error_stage, error_type};
 
 #[derive(Debug)]
 pub struct JsonParserError<'a> {
@@ -35,6 +35,7 @@ impl<'a> InternalEvent for JsonParserError<'a> {
         if self.drop_invalid {
             counter!(
                 "component_discarded_events_total", 1,
+                "reason" => discard_reason::CODEC_ERROR,
                 "error_code" => "invalid_json",
                 "error_type" => error_type::PARSER_FAILED,
                 "stage" => error_stage::PROCESSING,
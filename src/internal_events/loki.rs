@@ -1,6 +1,8 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
+use super::prelude::discard_reason;
+
 #[derive(Debug)]
 pub struct LokiEventUnlabeled;
 
@@ -28,7 +30,8 @@ impl InternalEvent for LokiOutOfOrderEventDropped {
         counter!("processing_errors_total", 1,
                 "error_type" => "out_of_order"); // deprecated
         counter!("component_discarded_events_total", self.count as u64,
-                "reason" => "out_of_order");
+                "reason" => discard_reason::INVALID_EVENT,
+                "error_type" => "out_of_order");
     }
 }
 
@@ -1,7 +1,7 @@
 use metrics::{counter, gauge};
 use vector_core::internal_event::InternalEvent;
 
-use super::prelude::{error_stage, error_type};
+use super::prelude::{discard_reason, error_stage, error_type};
 use crate::transforms::lua::v2::BuildError;
 
 #[derive(Debug)]
@@ -38,6 +38,7 @@ impl InternalEvent for LuaScriptError {
         );
         counter!(
             "component_discarded_events_total", 1,
+            "reason" => discard_reason::PROCESSING_ERROR,
             "error_code" => mlua_error_code(&self.error),
             "error_type" => error_type::SCRIPT_FAILED,
             "stage" => error_stage::PROCESSING,
@@ -70,6 +71,7 @@ impl InternalEvent for LuaBuildError {
         );
         counter!(
             "component_discarded_events_total", 1,
+            "reason" => discard_reason::PROCESSING_ERROR,
             "error_code" => lua_build_error_code(&self.error),
             "error_type" => error_type::SCRIPT_FAILED,
             "stage" => error_stage::PROCESSING,
@@ -27,6 +27,7 @@ mod aws_sqs;
 #[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub(crate) mod azure_blob;
 mod batch;
+mod checkpoint;
 #[cfg(feature = "transforms-coercer")]
 mod coercer;
 mod common;
@@ -63,6 +64,8 @@ mod geoip;
 mod heartbeat;
 mod http;
 pub mod http_client;
+#[cfg(feature = "sources-internal_audit")]
+mod internal_audit;
 #[cfg(feature = "sources-internal_logs")]
 mod internal_logs;
 #[cfg(all(unix, feature = "sources-journald"))]
@@ -90,6 +93,7 @@ mod mongodb_metrics;
 mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
+mod oauth2;
 mod open;
 #[cfg(any(
     feature = "sinks-datadog_events",
@@ -119,6 +123,8 @@ mod remap;
 mod remove_fields;
 #[cfg(feature = "transforms-rename_fields")]
 mod rename_fields;
+#[cfg(feature = "sources-replay")]
+mod replay;
 mod sample;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
@@ -137,6 +143,7 @@ mod tcp;
 mod template;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+mod tls;
 mod udp;
 mod unix;
 mod vector;
@@ -146,6 +153,7 @@ mod websocket;
 #[cfg(any(
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
+    feature = "sinks-archive",
     feature = "sinks-file",
 ))]
 mod file;
@@ -181,6 +189,7 @@ pub(crate) use self::aws_ecs_metrics::*;
 pub(crate) use self::aws_kinesis_firehose::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+pub(crate) use self::checkpoint::*;
 #[cfg(feature = "transforms-coercer")]
 pub(crate) use self::coercer::*;
 #[cfg(feature = "transforms-concat")]
@@ -207,6 +216,7 @@ pub(crate) use self::exec::*;
 #[cfg(any(
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
+    feature = "sinks-archive",
     feature = "sinks-file",
 ))]
 pub(crate) use self::file::*;
@@ -225,6 +235,8 @@ pub(crate) use self::geoip::*;
     feature = "sources-splunk_hec",
 ))]
 pub(crate) use self::http::*;
+#[cfg(feature = "sources-internal_audit")]
+pub(crate) use self::internal_audit::*;
 #[cfg(feature = "sources-internal_logs")]
 pub(crate) use self::internal_logs::*;
 #[cfg(all(unix, feature = "sources-journald"))]
@@ -280,6 +292,8 @@ pub(crate) use self::remap::*;
 pub(crate) use self::remove_fields::*;
 #[cfg(feature = "transforms-rename_fields")]
 pub(crate) use self::rename_fields::*;
+#[cfg(feature = "sources-replay")]
+pub(crate) use self::replay::*;
 #[cfg(feature = "transforms-sample")]
 pub(crate) use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
@@ -304,7 +318,8 @@ pub(crate) use self::throttle::*;
         feature = "sources-metrics",
         feature = "sources-statsd",
         feature = "sources-syslog",
-        feature = "sources-socket"
+        feature = "sources-socket",
+        feature = "sources-utils-http-prelude"
     ),
     unix
 ))]
@@ -317,7 +332,7 @@ pub(crate) use self::websocket::*;
 pub(crate) use self::windows::*;
 pub(crate) use self::{
     adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    heartbeat::*, oauth2::*, open::*, process::*, socket::*, tcp::*, template::*, tls::*, udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
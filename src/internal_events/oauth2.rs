@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct OAuth2TokenRequestError<'a> {
+    pub error: &'a crate::Error,
+    pub token_endpoint: &'a str,
+}
+
+impl<'a> InternalEvent for OAuth2TokenRequestError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to refresh OAuth2 token, retrying.",
+            error = %self.error,
+            token_endpoint = %self.token_endpoint,
+            error_code = "oauth2_token_request",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "oauth2_token_request",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
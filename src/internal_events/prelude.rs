@@ -1,3 +1,5 @@
+pub use vector_core::internal_event::discard_reason;
+
 // Set of `stage` tags to use when emiting error events.
 pub mod error_stage {
     pub const RECEIVING: &str = "receiving";
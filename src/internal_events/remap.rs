@@ -1,7 +1,7 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
-use super::prelude::{error_stage, error_type};
+use super::prelude::{discard_reason, error_stage, error_type};
 
 #[derive(Debug)]
 pub struct RemapMappingError {
@@ -34,6 +34,7 @@ impl InternalEvent for RemapMappingError {
         if self.event_dropped {
             counter!(
                 "component_discarded_events_total", 1,
+                "reason" => discard_reason::PROCESSING_ERROR,
                 "error_type" => error_type::CONVERSION_FAILED,
                 "stage" => error_stage::PROCESSING,
             );
@@ -63,6 +64,7 @@ impl InternalEvent for RemapMappingAbort {
         if self.event_dropped {
             counter!(
                 "component_discarded_events_total", 1,
+                "reason" => discard_reason::PROCESSING_ERROR,
                 "error_type" => error_type::CONVERSION_FAILED,
                 "stage" => error_stage::PROCESSING,
             );
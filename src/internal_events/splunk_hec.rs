@@ -9,7 +9,7 @@ mod sink {
     use serde_json::Error;
     use vector_core::internal_event::InternalEvent;
 
-    use crate::internal_events::prelude::{error_stage, error_type};
+    use crate::internal_events::prelude::{discard_reason, error_stage, error_type};
     use crate::{
         event::metric::{MetricKind, MetricValue},
         sinks::splunk_hec::common::acknowledgements::HecAckApiError,
@@ -64,6 +64,7 @@ mod sink {
             );
             counter!(
                 "component_discarded_events_total", 1,
+                "reason" => discard_reason::INVALID_EVENT,
                 "error_type" => error_type::INVALID_METRIC,
                 "stage" => error_stage::PROCESSING,
             );
@@ -2,6 +2,7 @@ use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
 pub struct TagCardinalityLimitRejectingEvent<'a> {
+    pub metric_name: &'a str,
     pub tag_key: &'a str,
     pub tag_value: &'a str,
 }
@@ -10,15 +11,21 @@ impl<'a> InternalEvent for TagCardinalityLimitRejectingEvent<'a> {
     fn emit(self) {
         debug!(
             message = "Event containing tag with new value after hitting configured 'value_limit'; discarding event.",
+            metric_name = self.metric_name,
             tag_key = self.tag_key,
             tag_value = self.tag_value,
             internal_log_rate_secs = 10,
         );
-        counter!("tag_value_limit_exceeded_total", 1);
+        counter!(
+            "tag_value_limit_exceeded_total", 1,
+            "metric_name" => self.metric_name.to_owned(),
+            "tag_key" => self.tag_key.to_owned(),
+        );
     }
 }
 
 pub struct TagCardinalityLimitRejectingTag<'a> {
+    pub metric_name: &'a str,
     pub tag_key: &'a str,
     pub tag_value: &'a str,
 }
@@ -27,11 +34,16 @@ impl<'a> InternalEvent for TagCardinalityLimitRejectingTag<'a> {
     fn emit(self) {
         debug!(
             message = "Rejecting tag after hitting configured 'value_limit'.",
+            metric_name = self.metric_name,
             tag_key = self.tag_key,
             tag_value = self.tag_value,
             internal_log_rate_secs = 10,
         );
-        counter!("tag_value_limit_exceeded_total", 1);
+        counter!(
+            "tag_value_limit_exceeded_total", 1,
+            "metric_name" => self.metric_name.to_owned(),
+            "tag_key" => self.tag_key.to_owned(),
+        );
     }
 }
 
@@ -45,6 +57,6 @@ impl<'a> InternalEvent for TagCardinalityValueLimitReached<'a> {
             "Value_limit reached for key {}. New values for this key will be rejected.",
             key = self.key,
         );
-        counter!("value_limit_reached_total", 1);
+        counter!("value_limit_reached_total", 1, "tag_key" => self.key.to_owned());
     }
 }
@@ -1,7 +1,7 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
-use super::prelude::{error_stage, error_type};
+use super::prelude::{discard_reason, error_stage, error_type};
 
 pub struct TemplateRenderingError<'a> {
     pub field: Option<&'a str>,
@@ -38,6 +38,7 @@ impl<'a> InternalEvent for TemplateRenderingError<'a> {
         if self.drop_event {
             counter!(
                 "component_discarded_events_total", 1,
+                "reason" => discard_reason::PROCESSING_ERROR,
                 "error_type" => error_type::TEMPLATE_FAILED,
                 "stage" => error_stage::PROCESSING,
             );
@@ -1,6 +1,8 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
+use super::prelude::discard_reason;
+
 #[derive(Debug)]
 pub(crate) struct ThrottleEventDiscarded {
     pub key: String,
@@ -12,6 +14,10 @@ impl InternalEvent for ThrottleEventDiscarded {
         counter!(
             "events_discarded_total", 1,
             "key" => self.key,
+        ); // deprecated
+        counter!(
+            "component_discarded_events_total", 1,
+            "reason" => discard_reason::RATE_LIMITED,
         );
     }
 }
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+use crate::tls::TlsError;
+
+#[derive(Debug)]
+pub struct TlsCertificateReloaded<'a> {
+    pub crt_file: Option<&'a Path>,
+}
+
+impl<'a> InternalEvent for TlsCertificateReloaded<'a> {
+    fn emit(self) {
+        info!(
+            message = "Reloaded TLS certificates.",
+            crt_file = ?self.crt_file,
+        );
+        counter!("tls_certificate_reloads_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct TlsCertificateReloadError<'a> {
+    pub error: &'a TlsError,
+    pub for_server: bool,
+}
+
+impl<'a> InternalEvent for TlsCertificateReloadError<'a> {
+    fn emit(self) {
+        let stage = if self.for_server {
+            error_stage::RECEIVING
+        } else {
+            error_stage::SENDING
+        };
+        error!(
+            message = "Failed to reload TLS certificates, keeping the current ones.",
+            error = %self.error,
+            error_code = "tls_certificate_reload",
+            error_type = error_type::CONFIGURATION_FAILED,
+            stage,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "tls_certificate_reload",
+            "error_type" => error_type::CONFIGURATION_FAILED,
+            "stage" => stage,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TlsCertificateExpiry {
+    pub seconds_remaining: f64,
+}
+
+impl InternalEvent for TlsCertificateExpiry {
+    fn emit(self) {
+        if self.seconds_remaining <= 0.0 {
+            warn!(
+                message = "TLS certificate has expired.",
+                internal_log_rate_secs = 3600,
+            );
+        }
+        gauge!("tls_certificate_expiry_seconds", self.seconds_remaining);
+    }
+}
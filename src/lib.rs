@@ -25,11 +25,19 @@ extern crate derivative;
 #[global_allocator]
 static ALLOC: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
+// Makes heap profiling available to jemalloc without activating it, which is left to the API
+// when its profiling endpoints are enabled. Samples an allocation every 512 KiB on average.
+#[cfg(feature = "api-profiling")]
+#[allow(non_upper_case_globals)]
+#[export_name = "_rjem_malloc_conf"]
+pub static malloc_conf: &u8 = &b"prof:true,prof_active:false,lg_prof_sample:19\0"[0];
+
 #[macro_use]
 #[allow(unreachable_pub)]
 pub mod config;
 pub mod cli;
 pub mod conditions;
+pub(crate) mod convert;
 pub mod dns;
 #[cfg(feature = "docker")]
 pub mod docker;
@@ -42,9 +50,16 @@ pub mod internal_events;
 #[allow(unreachable_pub)]
 pub mod api;
 pub mod app;
+#[cfg(any(feature = "sinks-archive", feature = "sources-replay"))]
+pub mod archive;
 pub mod async_read;
+pub mod audit;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub(crate) mod bench;
+pub(crate) mod buffer;
+pub mod checkpoint;
+pub mod cluster;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub(crate) mod common;
@@ -63,16 +78,22 @@ pub mod line_agg;
 pub mod list;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) mod nats;
+pub mod oauth2;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
+pub(crate) mod proxy_protocol;
 pub mod providers;
+#[cfg(feature = "sources-replay")]
+pub(crate) mod replay;
 pub mod serde;
 #[cfg(windows)]
 pub mod service;
 pub mod signal;
+pub(crate) mod simulate;
 pub(crate) mod sink;
 #[allow(unreachable_pub)]
 pub mod sinks;
+pub(crate) mod socks5;
 pub mod source_sender;
 #[allow(unreachable_pub)]
 pub mod sources;
@@ -98,6 +119,7 @@ pub mod udp;
 pub mod unit_test;
 pub(crate) mod utilization;
 pub mod validate;
+pub(crate) mod vrl_library;
 #[cfg(windows)]
 pub mod vector_windows;
 
@@ -0,0 +1,423 @@
+//! OAuth2 authentication, with which requests carry a bearer token fetched from the token endpoint
+//! of an authorization server and refreshed before it expires.
+//!
+//! Tokens are granted either for client credentials, see
+//! <https://www.rfc-editor.org/rfc/rfc6749#section-4.4>, or for a JWT assertion signed by Vector,
+//! see <https://www.rfc-editor.org/rfc/rfc7523#section-2.1>.
+
+use std::{
+    fmt,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock, Weak,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use http::{header, HeaderValue, Request};
+use hyper::Body;
+use openssl::{hash::MessageDigest, pkey::PKey, sign::Signer};
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+
+use crate::{http::HttpClient, internal_events::OAuth2TokenRequestError};
+
+const JWT_BEARER_GRANT: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+// How long tokens are assumed to be valid for when the authorization server doesn't say.
+const DEFAULT_EXPIRES_IN: Duration = Duration::from_secs(3600);
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The settings of OAuth2 authentication.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct OAuth2Config {
+    /// The URL of the token endpoint of the authorization server.
+    pub token_endpoint: String,
+    /// The ID of the client Vector authenticates as.
+    pub client_id: String,
+    /// The secret of the client, sent to the token endpoint with HTTP basic authentication to be
+    /// granted a token for the client credentials.
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// The JWT assertion to be granted a token for, rather than for the client credentials.
+    #[serde(default)]
+    pub assertion: Option<JwtAssertion>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// The audience to request a token for, as some authorization servers require.
+    #[serde(default)]
+    pub audience: Option<String>,
+    #[serde(skip)]
+    token: OAuth2Token,
+}
+
+/// The settings of the JWT assertion a token is granted for.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct JwtAssertion {
+    /// The path of the RSA private key, in PEM format, to sign the assertion with using `RS256`.
+    pub key_file: PathBuf,
+    /// The ID of the key, sent in the `kid` header of the assertion.
+    #[serde(default)]
+    pub key_id: Option<String>,
+    /// The subject of the assertion, which defaults to the client ID.
+    #[serde(default)]
+    pub subject: Option<String>,
+}
+
+/// The current token, shared by the clones of the settings it was fetched for.
+#[derive(Clone, Default)]
+struct OAuth2Token(Arc<TokenState>);
+
+#[derive(Default)]
+struct TokenState {
+    authorization: RwLock<Option<HeaderValue>>,
+    started: AtomicBool,
+}
+
+// The token is state rather than configuration, so it doesn't take part in comparisons.
+impl PartialEq for OAuth2Token {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for OAuth2Token {}
+
+impl fmt::Debug for OAuth2Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuth2Token").finish_non_exhaustive()
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+impl OAuth2Config {
+    /// Fetches the first token, unless that's already been done for these settings, and spawns a
+    /// task refreshing it halfway through its lifetime for as long as the settings are in use.
+    pub async fn start(&self, client: &HttpClient) -> crate::Result<()> {
+        let state = &self.token.0;
+        if state.started.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        let (authorization, expires_in) = match self.fetch_token(client).await {
+            Ok(token) => token,
+            Err(error) => {
+                state.started.store(false, Ordering::SeqCst);
+                return Err(error);
+            }
+        };
+        *state.authorization.write().unwrap() = Some(authorization);
+
+        let config = self.detached();
+        let client = client.clone();
+        let state = Arc::downgrade(state);
+        tokio::spawn(
+            async move { config.refresh(client, state, expires_in).await }.in_current_span(),
+        );
+        Ok(())
+    }
+
+    /// Inserts the current token into the headers, if one has been fetched.
+    pub fn apply_headers_map(&self, map: &mut http::HeaderMap) {
+        match &*self.token.0.authorization.read().unwrap() {
+            Some(authorization) => {
+                map.insert(header::AUTHORIZATION, authorization.clone());
+            }
+            None => warn!(
+                message = "No OAuth2 token has been fetched yet.",
+                internal_log_rate_secs = 10,
+            ),
+        }
+    }
+
+    /// A copy of the settings with a token of its own, so that it doesn't keep alive the token
+    /// of these ones.
+    fn detached(&self) -> Self {
+        Self {
+            token: OAuth2Token::default(),
+            ..self.clone()
+        }
+    }
+
+    async fn refresh(self, client: HttpClient, state: Weak<TokenState>, expires_in: Duration) {
+        let mut delay = expires_in / 2;
+        loop {
+            tokio::time::sleep(delay).await;
+            let state = match state.upgrade() {
+                Some(state) => state,
+                None => break,
+            };
+            delay = match self.fetch_token(&client).await {
+                Ok((authorization, expires_in)) => {
+                    debug!("Refreshed OAuth2 token.");
+                    *state.authorization.write().unwrap() = Some(authorization);
+                    expires_in / 2
+                }
+                Err(error) => {
+                    emit!(OAuth2TokenRequestError {
+                        error: &error,
+                        token_endpoint: &self.token_endpoint,
+                    });
+                    RETRY_INTERVAL
+                }
+            };
+        }
+    }
+
+    /// Requests a token from the token endpoint, returning the authorization header to send it in
+    /// and how long it's valid for.
+    async fn fetch_token(&self, client: &HttpClient) -> crate::Result<(HeaderValue, Duration)> {
+        let response = client.send(self.token_request()?).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if !status.is_success() {
+            return Err(format!(
+                "Token endpoint responded with {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )
+            .into());
+        }
+
+        let token: TokenResponse = serde_json::from_slice(&body)?;
+        let mut authorization = HeaderValue::from_str(&format!("Bearer {}", token.access_token))?;
+        authorization.set_sensitive(true);
+        let expires_in = token
+            .expires_in
+            .map_or(DEFAULT_EXPIRES_IN, Duration::from_secs);
+        Ok((authorization, expires_in))
+    }
+
+    fn token_request(&self) -> crate::Result<Request<Body>> {
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        let mut builder = Request::post(&self.token_endpoint)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::ACCEPT, "application/json");
+
+        match &self.assertion {
+            Some(assertion) => {
+                form.append_pair("grant_type", JWT_BEARER_GRANT);
+                form.append_pair("assertion", &self.sign_assertion(assertion)?);
+            }
+            None => {
+                form.append_pair("grant_type", "client_credentials");
+            }
+        }
+        match &self.client_secret {
+            Some(secret) => {
+                // The credentials are form encoded before being encoded in the header.
+                let credentials =
+                    format!("{}:{}", form_encode(&self.client_id), form_encode(secret));
+                let mut authorization = HeaderValue::from_str(&format!(
+                    "Basic {}",
+                    openssl::base64::encode_block(credentials.as_bytes())
+                ))?;
+                authorization.set_sensitive(true);
+                builder = builder.header(header::AUTHORIZATION, authorization);
+            }
+            None => {
+                form.append_pair("client_id", &self.client_id);
+            }
+        }
+        if !self.scopes.is_empty() {
+            form.append_pair("scope", &self.scopes.join(" "));
+        }
+        if let Some(audience) = &self.audience {
+            form.append_pair("audience", audience);
+        }
+
+        Ok(builder.body(Body::from(form.finish()))?)
+    }
+
+    /// Signs a JWT asserting the client to the token endpoint, valid for five minutes.
+    fn sign_assertion(&self, assertion: &JwtAssertion) -> crate::Result<String> {
+        let key = std::fs::read(&assertion.key_file).map_err(|error| {
+            format!(
+                "Failed to read JWT assertion key {:?}: {}",
+                assertion.key_file, error
+            )
+        })?;
+        let key = PKey::private_key_from_pem(&key)?;
+        if key.rsa().is_err() {
+            return Err("JWT assertion key must be an RSA key.".into());
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let mut header = serde_json::json!({ "alg": "RS256", "typ": "JWT" });
+        if let Some(key_id) = &assertion.key_id {
+            header["kid"] = key_id.as_str().into();
+        }
+        let claims = serde_json::json!({
+            "iss": self.client_id,
+            "sub": assertion.subject.as_ref().unwrap_or(&self.client_id),
+            "aud": self.audience.as_ref().unwrap_or(&self.token_endpoint),
+            "iat": now,
+            "exp": now + 300,
+        });
+        let message = format!(
+            "{}.{}",
+            base64_url(&serde_json::to_vec(&header)?),
+            base64_url(&serde_json::to_vec(&claims)?)
+        );
+
+        let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+        signer.update(message.as_bytes())?;
+        Ok(format!(
+            "{}.{}",
+            message,
+            base64_url(&signer.sign_to_vec()?)
+        ))
+    }
+}
+
+fn form_encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Encodes in the URL-safe base64 alphabet, without padding, as JWTs are.
+fn base64_url(data: &[u8]) -> String {
+    openssl::base64::encode_block(data)
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use hyper::Response;
+    use openssl::{rsa::Rsa, sign::Verifier};
+
+    use super::*;
+    use crate::{
+        config::ProxyConfig, http::Auth, sinks::util::test::build_test_server_generic, test_util,
+    };
+
+    fn config(extra: &str, addr: std::net::SocketAddr) -> OAuth2Config {
+        toml::from_str(&format!(
+            r#"
+            token_endpoint = "http://{}/token"
+            client_id = "vector"
+            scopes = ["logs.write", "metrics.write"]
+            {}
+            "#,
+            addr, extra
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_auth_strategy() {
+        let auth: Auth = toml::from_str(
+            r#"
+            strategy = "oauth2"
+            token_endpoint = "https://auth.example.com/token"
+            client_id = "vector"
+            client_secret = "secret"
+            "#,
+        )
+        .unwrap();
+        match auth {
+            Auth::OAuth2(config) => assert_eq!(config.client_secret.as_deref(), Some("secret")),
+            auth => panic!("unexpected strategy: {:?}", auth),
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_tokens_for_client_credentials() {
+        let addr = test_util::next_addr();
+        let (mut rx, trigger, server) = build_test_server_generic(addr, || {
+            Response::new(Body::from(
+                r#"{"access_token":"token","token_type":"Bearer","expires_in":3600}"#,
+            ))
+        });
+        tokio::spawn(server);
+
+        let config = config(r#"client_secret = "s3cr:t""#, addr);
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
+        config.clone().start(&client).await.unwrap();
+
+        let (parts, body) = rx.next().await.unwrap();
+        assert_eq!(parts.uri.path(), "/token");
+        assert_eq!(
+            parts.headers.get("authorization").unwrap(),
+            &format!(
+                "Basic {}",
+                openssl::base64::encode_block(b"vector:s3cr%3At")
+            )
+        );
+        assert_eq!(
+            body,
+            "grant_type=client_credentials&scope=logs.write+metrics.write"
+        );
+
+        // Clones of the settings share the token.
+        let mut headers = http::HeaderMap::new();
+        config.apply_headers_map(&mut headers);
+        assert_eq!(headers.get("authorization").unwrap(), "Bearer token");
+
+        drop(trigger);
+    }
+
+    #[tokio::test]
+    async fn fails_when_the_token_endpoint_does() {
+        let addr = test_util::next_addr();
+        let (_rx, trigger, server) = build_test_server_generic(addr, || {
+            Response::builder()
+                .status(401)
+                .body(Body::from(r#"{"error":"invalid_client"}"#))
+                .unwrap()
+        });
+        tokio::spawn(server);
+
+        let config = config(r#"client_secret = "secret""#, addr);
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
+        let error = config.start(&client).await.unwrap_err();
+        assert!(error.to_string().contains("invalid_client"));
+
+        drop(trigger);
+    }
+
+    #[test]
+    fn signs_jwt_assertions() {
+        let config = config(
+            r#"
+            assertion.key_file = "tests/data/localhost.key"
+            assertion.key_id = "key"
+            "#,
+            "127.0.0.1:8080".parse().unwrap(),
+        );
+        let jwt = config
+            .sign_assertion(config.assertion.as_ref().unwrap())
+            .unwrap();
+
+        let parts = jwt.split('.').collect::<Vec<_>>();
+        let decode = |part: &str| {
+            let part = part.replace('-', "+").replace('_', "/");
+            let padding = "=".repeat((4 - part.len() % 4) % 4);
+            openssl::base64::decode_block(&(part + &padding)).unwrap()
+        };
+        let header: serde_json::Value = serde_json::from_slice(&decode(parts[0])).unwrap();
+        assert_eq!(header["alg"], "RS256");
+        assert_eq!(header["kid"], "key");
+        let claims: serde_json::Value = serde_json::from_slice(&decode(parts[1])).unwrap();
+        assert_eq!(claims["iss"], "vector");
+        assert_eq!(claims["sub"], "vector");
+        assert_eq!(claims["aud"], "http://127.0.0.1:8080/token");
+
+        let key =
+            Rsa::private_key_from_pem(&std::fs::read("tests/data/localhost.key").unwrap()).unwrap();
+        let key = PKey::from_rsa(key).unwrap();
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &key).unwrap();
+        verifier
+            .update(format!("{}.{}", parts[0], parts[1]).as_bytes())
+            .unwrap();
+        assert!(verifier.verify(&decode(parts[2])).unwrap());
+    }
+}
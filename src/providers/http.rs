@@ -1,8 +1,15 @@
+use std::path::PathBuf;
+
 use async_stream::stream;
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 use futures::Stream;
 use hyper::Body;
 use indexmap::IndexMap;
+use openssl::{
+    hash::MessageDigest,
+    pkey::{Id, PKey, Public},
+    sign::Verifier,
+};
 use serde::{Deserialize, Serialize};
 use tokio::time;
 use url::Url;
@@ -33,12 +40,27 @@ impl Default for RequestConfig {
     }
 }
 
+/// Options for verifying a detached signature of the remote configuration.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SignatureConfig {
+    /// The URL of the signature. Defaults to the URL of the configuration with `.sig` appended.
+    #[serde(default)]
+    pub url: Option<Url>,
+
+    /// The path of the PEM encoded public key to verify the signature with. RSA and ECDSA
+    /// signatures are expected over the SHA-256 digest of the configuration, while Ed25519
+    /// signatures are expected over the configuration itself.
+    pub public_key_path: PathBuf,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
 pub(crate) struct HttpConfig {
     url: Option<Url>,
     request: RequestConfig,
     poll_interval_secs: u64,
+    signature: Option<SignatureConfig>,
     #[serde(flatten)]
     tls_options: Option<TlsConfig>,
     #[serde(
@@ -54,6 +76,7 @@ impl Default for HttpConfig {
             url: None,
             request: RequestConfig::default(),
             poll_interval_secs: 30,
+            signature: None,
             tls_options: None,
             proxy: Default::default(),
         }
@@ -113,17 +136,76 @@ async fn http_request(
         })
 }
 
-/// Calls `http_request`, serializing the result to a `ConfigBuilder`.
-async fn http_request_to_config_builder(
+/// Verifies detached signatures of the remote configuration.
+#[derive(Clone)]
+struct SignatureVerifier {
+    url: Url,
+    public_key: PKey<Public>,
+}
+
+impl SignatureVerifier {
+    fn new(config: &SignatureConfig, url: &Url) -> std::result::Result<Self, String> {
+        let url = match &config.url {
+            Some(url) => url.clone(),
+            None => {
+                let mut signature_url = url.clone();
+                signature_url.set_path(&format!("{}.sig", url.path()));
+                signature_url
+            }
+        };
+
+        let path = &config.public_key_path;
+        let pem = std::fs::read(path)
+            .map_err(|error| format!("Couldn't read public key {:?}: {}.", path, error))?;
+        let public_key = PKey::public_key_from_pem(&pem)
+            .map_err(|error| format!("Invalid public key {:?}: {}.", path, error))?;
+
+        Ok(Self { url, public_key })
+    }
+
+    fn verify(&self, config: &[u8], signature: &[u8]) -> bool {
+        let verifier = match self.public_key.id() {
+            Id::ED25519 => Verifier::new_without_digest(&self.public_key),
+            _ => Verifier::new(MessageDigest::sha256(), &self.public_key),
+        };
+        verifier
+            .and_then(|mut verifier| verifier.verify_oneshot(signature, config))
+            .unwrap_or(false)
+    }
+}
+
+/// Calls `http_request` for the configuration, verifying its signature if required.
+async fn fetch_config(
     url: &Url,
     tls_options: &Option<TlsConfig>,
     headers: &IndexMap<String, String>,
     proxy: &ProxyConfig,
-) -> Result {
+    signature: &Option<SignatureVerifier>,
+) -> std::result::Result<Bytes, Vec<String>> {
     let config_str = http_request(url, tls_options, headers, proxy)
         .await
         .map_err(|e| vec![e.to_owned()])?;
 
+    if let Some(signature) = signature {
+        let signature_bytes = http_request(&signature.url, tls_options, headers, proxy)
+            .await
+            .map_err(|e| vec![e.to_owned()])?;
+
+        if !signature.verify(&config_str, &signature_bytes) {
+            let message = "Signature verification failed.";
+            error!(
+                message = ?message,
+                url = ?url.as_str(),
+                signature_url = ?signature.url.as_str());
+            return Err(vec![message.to_owned()]);
+        }
+    }
+
+    Ok(config_str)
+}
+
+/// Serializes the configuration returned by `fetch_config` to a `ConfigBuilder`.
+fn config_builder_from_bytes(config_str: &Bytes) -> Result {
     let (config_builder, warnings) =
         config::load(config_str.chunk(), crate::config::format::Format::Toml)?;
 
@@ -135,12 +217,16 @@ async fn http_request_to_config_builder(
 }
 
 /// Polls the HTTP endpoint after/every `poll_interval_secs`, returning a stream of `ConfigBuilder`.
+/// Only configurations that differ from the last one applied are returned, so that polling doesn't
+/// reload an unchanged topology.
 fn poll_http(
     poll_interval_secs: u64,
     url: Url,
     tls_options: Option<TlsConfig>,
     headers: IndexMap<String, String>,
     proxy: ProxyConfig,
+    signature: Option<SignatureVerifier>,
+    mut last_config: Bytes,
 ) -> impl Stream<Item = signal::SignalTo> {
     let duration = time::Duration::from_secs(poll_interval_secs);
     let mut interval = time::interval_at(time::Instant::now() + duration, duration);
@@ -149,8 +235,16 @@ fn poll_http(
         loop {
             interval.tick().await;
 
-            match http_request_to_config_builder(&url, &tls_options, &headers, &proxy).await {
-                Ok(config_builder) => yield signal::SignalTo::ReloadFromConfigBuilder(config_builder),
+            match fetch_config(&url, &tls_options, &headers, &proxy, &signature).await {
+                Ok(config_str) if config_str == last_config => {
+                    debug!(message = "Configuration is unchanged.", url = ?url.as_str());
+                }
+                Ok(config_str) => {
+                    if let Ok(config_builder) = config_builder_from_bytes(&config_str) {
+                        last_config = config_str;
+                        yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                    }
+                }
                 Err(_) => {},
             };
 
@@ -176,8 +270,15 @@ impl ProviderConfig for HttpConfig {
         let request = self.request.clone();
 
         let proxy = ProxyConfig::from_env().merge(&self.proxy);
-        let config_builder =
-            http_request_to_config_builder(&url, &tls_options, &request.headers, &proxy).await?;
+        let signature = self
+            .signature
+            .as_ref()
+            .map(|signature| SignatureVerifier::new(signature, &url))
+            .transpose()
+            .map_err(|error| vec![error])?;
+        let config_str =
+            fetch_config(&url, &tls_options, &request.headers, &proxy, &signature).await?;
+        let config_builder = config_builder_from_bytes(&config_str)?;
 
         // Poll for changes to remote configuration.
         signal_handler.add(poll_http(
@@ -186,6 +287,8 @@ impl ProviderConfig for HttpConfig {
             tls_options,
             request.headers.clone(),
             proxy.clone(),
+            signature,
+            config_str,
         ));
 
         Ok(config_builder)
@@ -201,3 +304,62 @@ inventory::submit! {
 }
 
 impl_generate_config_from_default!(HttpConfig);
+
+#[cfg(test)]
+mod tests {
+    use openssl::{pkey::Private, rsa::Rsa, sign::Signer};
+
+    use super::*;
+
+    fn verifier(key: &PKey<Private>) -> SignatureVerifier {
+        let pem = key.public_key_to_pem().unwrap();
+        SignatureVerifier {
+            url: Url::parse("http://localhost/vector.toml.sig").unwrap(),
+            public_key: PKey::public_key_from_pem(&pem).unwrap(),
+        }
+    }
+
+    #[test]
+    fn verifies_rsa_signatures() {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let config = b"[sources.in]\ntype = \"stdin\"\n";
+        let mut signer = Signer::new(MessageDigest::sha256(), &key).unwrap();
+        let signature = signer.sign_oneshot_to_vec(config).unwrap();
+
+        let verifier = verifier(&key);
+        assert!(verifier.verify(config, &signature));
+        assert!(!verifier.verify(b"[sources.in]\ntype = \"file\"\n", &signature));
+    }
+
+    #[test]
+    fn verifies_ed25519_signatures() {
+        let key = PKey::generate_ed25519().unwrap();
+        let config = b"[sources.in]\ntype = \"stdin\"\n";
+        let mut signer = Signer::new_without_digest(&key).unwrap();
+        let signature = signer.sign_oneshot_to_vec(config).unwrap();
+
+        let verifier = verifier(&key);
+        assert!(verifier.verify(config, &signature));
+        assert!(!verifier.verify(b"[sources.in]\ntype = \"file\"\n", &signature));
+        assert!(!verifier.verify(config, b"not a signature"));
+    }
+
+    #[test]
+    fn defaults_signature_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.pem");
+        let key = PKey::generate_ed25519().unwrap();
+        std::fs::write(&path, key.public_key_to_pem().unwrap()).unwrap();
+
+        let config = SignatureConfig {
+            url: None,
+            public_key_path: path,
+        };
+        let url = Url::parse("https://example.com/vector.toml?env=prod").unwrap();
+        let verifier = SignatureVerifier::new(&config, &url).unwrap();
+        assert_eq!(
+            verifier.url.as_str(),
+            "https://example.com/vector.toml.sig?env=prod"
+        );
+    }
+}
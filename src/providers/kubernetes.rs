@@ -0,0 +1,518 @@
+//! A provider that reads the configuration from a Kubernetes ConfigMap or `VectorPipeline` custom
+//! resource, watching it for changes.
+//!
+//! Each change is validated component by component before it's handed to the reload machinery.
+//! Invalid components, and the components consuming their outputs, are left out, so that the
+//! rest of the pipeline is still applied. The outcome of the validation is written to the status
+//! conditions of a `VectorPipeline`, and logged for ConfigMaps.
+
+use std::{path::PathBuf, time::Duration};
+
+use async_stream::stream;
+use chrono::{SecondsFormat, Utc};
+use futures::{stream::BoxStream, Stream, StreamExt};
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::{
+    api::{Api, ListParams, Patch, PatchParams},
+    config::{self as kube_config, KubeConfigOptions},
+    core::{ApiResource, DynamicObject, GroupVersionKind},
+    runtime::{utils::try_flatten_applied, watcher},
+    Client, Config as ClientConfig,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+
+use super::Result;
+use crate::{
+    config::{
+        self,
+        provider::{ProviderConfig, ProviderDescription},
+        ConfigBuilder, Format,
+    },
+    signal,
+};
+
+const PIPELINE_GROUP: &str = "vector.dev";
+const PIPELINE_VERSION: &str = "v1alpha1";
+const PIPELINE_KIND: &str = "VectorPipeline";
+
+/// The kinds of components that are validated separately.
+const COMPONENT_KINDS: [&str; 3] = ["sources", "transforms", "sinks"];
+
+/// How long to wait before watching again after the watch failed.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ResourceKind {
+    /// A ConfigMap holding the configuration under `key`.
+    ConfigMap,
+
+    /// A `VectorPipeline` custom resource holding the configuration as its `spec`.
+    VectorPipeline,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub(crate) struct KubernetesConfig {
+    kind: ResourceKind,
+    name: Option<String>,
+    namespace: Option<String>,
+    key: String,
+    kube_config_file: Option<PathBuf>,
+}
+
+impl Default for KubernetesConfig {
+    fn default() -> Self {
+        Self {
+            kind: ResourceKind::ConfigMap,
+            name: None,
+            namespace: None,
+            key: "vector.toml".to_owned(),
+            kube_config_file: None,
+        }
+    }
+}
+
+/// The errors of a component that was left out of the configuration.
+#[derive(Debug, PartialEq)]
+struct ComponentError {
+    kind: &'static str,
+    id: String,
+    errors: Vec<String>,
+}
+
+impl ComponentError {
+    /// Whether `input` refers to this component, or one of its named outputs.
+    fn feeds(&self, input: &str) -> bool {
+        input
+            .strip_prefix(self.id.as_str())
+            .map_or(false, |rest| rest.is_empty() || rest.starts_with('.'))
+    }
+}
+
+type Validated = std::result::Result<(ConfigBuilder, Vec<ComponentError>), Vec<String>>;
+
+fn components_mut<'a>(
+    table: &'a mut JsonMap<String, JsonValue>,
+    kind: &str,
+) -> Option<&'a mut JsonMap<String, JsonValue>> {
+    table.get_mut(kind).and_then(JsonValue::as_object_mut)
+}
+
+/// Builds a configuration holding only the given component.
+fn single_component(kind: &str, id: &str, component: &JsonValue) -> JsonValue {
+    let mut components = JsonMap::new();
+    components.insert(id.to_owned(), component.clone());
+    let mut table = JsonMap::new();
+    table.insert(kind.to_owned(), JsonValue::Object(components));
+    JsonValue::Object(table)
+}
+
+/// Validates each component of the configuration, leaving out the invalid ones along with the
+/// components consuming their outputs.
+fn validate(mut config: JsonValue) -> Validated {
+    let table = config
+        .as_object_mut()
+        .ok_or_else(|| vec!["Configuration must be a table.".to_owned()])?;
+
+    let mut invalid = Vec::new();
+    for kind in COMPONENT_KINDS {
+        if let Some(components) = components_mut(table, kind) {
+            let ids = components.keys().cloned().collect::<Vec<_>>();
+            for id in ids {
+                let component = single_component(kind, &id, &components[&id]);
+                if let Err(error) = serde_json::from_value::<ConfigBuilder>(component) {
+                    components.remove(&id);
+                    invalid.push(ComponentError {
+                        kind,
+                        id,
+                        errors: vec![error.to_string()],
+                    });
+                }
+            }
+        }
+    }
+
+    loop {
+        let mut dangling = Vec::new();
+        for kind in ["transforms", "sinks"] {
+            if let Some(components) = components_mut(table, kind) {
+                for (id, component) in components.iter() {
+                    let input = component
+                        .get("inputs")
+                        .and_then(JsonValue::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(JsonValue::as_str)
+                        .find(|input| invalid.iter().any(|error| error.feeds(input)));
+                    if let Some(input) = input {
+                        dangling.push(ComponentError {
+                            kind,
+                            id: id.clone(),
+                            errors: vec![format!("Input {:?} is an invalid component.", input)],
+                        });
+                    }
+                }
+            }
+        }
+
+        if dangling.is_empty() {
+            break;
+        }
+        for error in dangling {
+            if let Some(components) = components_mut(table, error.kind) {
+                components.remove(&error.id);
+            }
+            invalid.push(error);
+        }
+    }
+
+    let builder = serde_json::from_value(config).map_err(|error| vec![error.to_string()])?;
+    Ok((builder, invalid))
+}
+
+fn log_validation(name: &str, validated: &Validated) {
+    match validated {
+        Ok((_, invalid)) => {
+            for component in invalid {
+                for error in &component.errors {
+                    error!(
+                        message = "Component is invalid and was left out.",
+                        resource = %name,
+                        component = %format!("{}.{}", component.kind, component.id),
+                        %error);
+                }
+            }
+        }
+        Err(errors) => {
+            for error in errors {
+                error!(message = "Configuration is invalid.", resource = %name, %error);
+            }
+        }
+    }
+}
+
+/// Builds the status conditions of a `VectorPipeline` reporting the outcome of the validation.
+fn conditions(validated: &Validated) -> Vec<JsonValue> {
+    let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    let condition = |type_: &str, accepted: bool, reason: &str, message: String| {
+        let status = if accepted { "True" } else { "False" };
+        json!({
+            "type": type_,
+            "status": status,
+            "reason": reason,
+            "message": message,
+            "lastTransitionTime": now,
+        })
+    };
+
+    match validated {
+        Ok((_, invalid)) if invalid.is_empty() => vec![condition(
+            "Accepted",
+            true,
+            "Valid",
+            "Configuration is valid.".to_owned(),
+        )],
+        Ok((_, invalid)) => std::iter::once(condition(
+            "Accepted",
+            true,
+            "PartiallyValid",
+            format!("{} invalid components were left out.", invalid.len()),
+        ))
+        .chain(invalid.iter().map(|component| {
+            condition(
+                &format!("{}.{}", component.kind, component.id),
+                false,
+                "ValidationFailed",
+                component.errors.join(" "),
+            )
+        }))
+        .collect(),
+        Err(errors) => vec![condition("Accepted", false, "Invalid", errors.join(" "))],
+    }
+}
+
+async fn report_status(
+    api: &Api<DynamicObject>,
+    name: &str,
+    generation: Option<i64>,
+    validated: &Validated,
+) {
+    let patch = json!({
+        "status": {
+            "observedGeneration": generation,
+            "conditions": conditions(validated),
+        }
+    });
+    if let Err(error) = api
+        .patch_status(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+    {
+        warn!(message = "Couldn't update status.", resource = %name, %error);
+    }
+}
+
+/// A configuration read from a watched resource, along with the generation of the resource.
+type Update = std::result::Result<(JsonValue, Option<i64>), Vec<String>>;
+
+/// Validates the configuration of each update, returning a stream of `ConfigBuilder`. Only
+/// configurations that differ from the last one read are validated, which also keeps status
+/// updates of the watched resource from triggering a reload.
+fn watch(
+    name: String,
+    mut updates: BoxStream<'static, Update>,
+    status: Option<Api<DynamicObject>>,
+    mut last_config: JsonValue,
+) -> impl Stream<Item = signal::SignalTo> {
+    stream! {
+        while let Some(update) = updates.next().await {
+            match update {
+                Ok((config, _)) if config == last_config => {
+                    debug!(message = "Configuration is unchanged.", resource = %name);
+                }
+                Ok((config, generation)) => {
+                    let validated = validate(config.clone());
+                    log_validation(&name, &validated);
+                    if let Some(api) = &status {
+                        report_status(api, &name, generation, &validated).await;
+                    }
+
+                    last_config = config;
+                    if let Ok((config_builder, _)) = validated {
+                        yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                    }
+                }
+                Err(errors) => {
+                    for error in errors {
+                        error!(message = "Couldn't watch configuration.", resource = %name, %error);
+                    }
+                    tokio::time::sleep(RETRY_DELAY).await;
+                }
+            }
+        }
+
+        // The watch never ends on its own, and the end of the stream is reported as an error.
+        futures::future::pending::<()>().await;
+    }
+}
+
+impl KubernetesConfig {
+    async fn client(&self) -> crate::Result<Client> {
+        // If the user passed a custom Kubeconfig use it, otherwise
+        // we attempt to load the local kubec-config, followed by the
+        // in-cluster environment variables
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    kube_config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        Ok(Client::try_from(client_config)?)
+    }
+
+    fn read_config_map(
+        &self,
+        config_map: &ConfigMap,
+    ) -> std::result::Result<JsonValue, Vec<String>> {
+        let data = config_map
+            .data
+            .as_ref()
+            .and_then(|data| data.get(&self.key))
+            .ok_or_else(|| vec![format!("ConfigMap has no key {:?}.", self.key)])?;
+
+        let format = Format::from_path(&self.key).unwrap_or_default();
+        let (config, warnings) = config::load(data.as_bytes(), format)?;
+        for warning in warnings.into_iter() {
+            warn!("{}", warning);
+        }
+
+        Ok(config)
+    }
+}
+
+fn read_pipeline(pipeline: &DynamicObject) -> std::result::Result<JsonValue, Vec<String>> {
+    pipeline
+        .data
+        .get("spec")
+        .cloned()
+        .ok_or_else(|| vec![format!("{} has no `spec`.", PIPELINE_KIND)])
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes")]
+impl ProviderConfig for KubernetesConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> Result {
+        let name = self
+            .name
+            .clone()
+            .ok_or_else(|| vec!["`name` is required for the `kubernetes` provider.".to_owned()])?;
+        let client = self
+            .client()
+            .await
+            .map_err(|error| vec![format!("Couldn't create Kubernetes client: {}.", error)])?;
+        let params = ListParams::default().fields(&format!("metadata.name={}", name));
+
+        let (config, generation, updates, status) = match self.kind {
+            ResourceKind::ConfigMap => {
+                let api: Api<ConfigMap> = match &self.namespace {
+                    Some(namespace) => Api::namespaced(client, namespace),
+                    None => Api::default_namespaced(client),
+                };
+                let config_map = api.get(&name).await.map_err(|error| {
+                    vec![format!("Couldn't get ConfigMap {:?}: {}.", name, error)]
+                })?;
+                let config = self.read_config_map(&config_map)?;
+
+                let this = self.clone();
+                let updates = try_flatten_applied(watcher(api, params))
+                    .map(move |result| {
+                        result
+                            .map_err(|error| vec![error.to_string()])
+                            .and_then(|config_map| this.read_config_map(&config_map))
+                            .map(|config| (config, None))
+                    })
+                    .boxed();
+                (config, None, updates, None)
+            }
+            ResourceKind::VectorPipeline => {
+                let resource = ApiResource::from_gvk(&GroupVersionKind::gvk(
+                    PIPELINE_GROUP,
+                    PIPELINE_VERSION,
+                    PIPELINE_KIND,
+                ));
+                let api: Api<DynamicObject> = match &self.namespace {
+                    Some(namespace) => Api::namespaced_with(client, namespace, &resource),
+                    None => Api::default_namespaced_with(client, &resource),
+                };
+                let pipeline = api.get(&name).await.map_err(|error| {
+                    vec![format!(
+                        "Couldn't get {} {:?}: {}.",
+                        PIPELINE_KIND, name, error
+                    )]
+                })?;
+                let config = read_pipeline(&pipeline)?;
+
+                let updates = try_flatten_applied(watcher(api.clone(), params))
+                    .map(|result| -> Update {
+                        let pipeline = result.map_err(|error| vec![error.to_string()])?;
+                        Ok((read_pipeline(&pipeline)?, pipeline.metadata.generation))
+                    })
+                    .boxed();
+                (config, pipeline.metadata.generation, updates, Some(api))
+            }
+        };
+
+        let validated = validate(config.clone());
+        log_validation(&name, &validated);
+        if let Some(api) = &status {
+            report_status(api, &name, generation, &validated).await;
+        }
+        let (config_builder, _) = validated?;
+
+        // Watch for changes to the configuration.
+        signal_handler.add(watch(name, updates, status, config));
+
+        Ok(config_builder)
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "kubernetes"
+    }
+}
+
+inventory::submit! {
+    ProviderDescription::new::<KubernetesConfig>("kubernetes")
+}
+
+impl_generate_config_from_default!(KubernetesConfig);
+
+#[cfg(all(test, feature = "sources-stdin", feature = "sinks-console"))]
+mod tests {
+    use super::*;
+
+    fn config(input: &str) -> JsonValue {
+        config::format::deserialize(input, Format::Toml).unwrap()
+    }
+
+    #[test]
+    fn accepts_valid_config() {
+        let (builder, invalid) = validate(config(
+            r#"
+            [sources.in]
+            type = "stdin"
+
+            [sinks.out]
+            type = "console"
+            inputs = ["in"]
+            encoding = "json"
+            "#,
+        ))
+        .unwrap();
+
+        assert!(invalid.is_empty());
+        assert_eq!(builder.sources.len(), 1);
+        assert_eq!(builder.sinks.len(), 1);
+    }
+
+    #[test]
+    fn leaves_out_invalid_components_and_their_consumers() {
+        let (builder, invalid) = validate(config(
+            r#"
+            [sources.in]
+            type = "stdin"
+
+            [sources.broken]
+            type = "stdin"
+            no_such_option = true
+
+            [sinks.out]
+            type = "console"
+            inputs = ["in"]
+            encoding = "json"
+
+            [sinks.broken_out]
+            type = "console"
+            inputs = ["broken"]
+            encoding = "json"
+            "#,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            builder
+                .sources
+                .keys()
+                .map(|key| key.id())
+                .collect::<Vec<_>>(),
+            vec!["in"]
+        );
+        assert_eq!(
+            builder.sinks.keys().map(|key| key.id()).collect::<Vec<_>>(),
+            vec!["out"]
+        );
+        assert_eq!(
+            invalid
+                .iter()
+                .map(|error| format!("{}.{}", error.kind, error.id))
+                .collect::<Vec<_>>(),
+            vec!["sources.broken", "sinks.broken_out"]
+        );
+    }
+
+    #[test]
+    fn matches_named_outputs() {
+        let error = ComponentError {
+            kind: "transforms",
+            id: "route".to_owned(),
+            errors: Vec::new(),
+        };
+        assert!(error.feeds("route"));
+        assert!(error.feeds("route.errors"));
+        assert!(!error.feeds("router"));
+    }
+}
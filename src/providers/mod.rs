@@ -1,4 +1,6 @@
 pub mod http;
+#[cfg(feature = "providers-kubernetes")]
+pub mod kubernetes;
 
 use super::config::ConfigBuilder;
 
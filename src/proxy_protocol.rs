@@ -0,0 +1,241 @@
+//! The PROXY protocol, with which load balancers pass on the address of the client a TCP connection
+//! came from in a header sent ahead of the data.
+//!
+//! See <https://www.haproxy.org/download/2.6/doc/proxy-protocol.txt>.
+
+use std::{
+    io,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const V1_PREFIX: &[u8] = b"PROXY";
+// The longest header allowed, including the terminating CRLF.
+const V1_MAX_LENGTH: usize = 107;
+const V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+
+/// The version of the PROXY protocol headers are sent with.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyProtocolVersion {
+    /// The human-readable version 1.
+    V1,
+    /// The binary version 2.
+    V2,
+}
+
+/// Reads a PROXY protocol header of either version from the start of the stream, without reading
+/// past it.
+///
+/// Returns the address of the client, or `None` if the header doesn't carry one, such as for the
+/// health checks of a load balancer.
+#[cfg_attr(not(feature = "sources-utils-tls"), allow(dead_code))]
+pub async fn read_header<S>(stream: &mut S) -> io::Result<Option<SocketAddr>>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut start = [0; 5];
+    stream.read_exact(&mut start).await?;
+    if start == V1_PREFIX {
+        let mut line = start.to_vec();
+        while !line.ends_with(b"\r\n") {
+            if line.len() == V1_MAX_LENGTH {
+                return Err(invalid("PROXY protocol v1 header is too long"));
+            }
+            line.push(stream.read_u8().await?);
+        }
+        parse_v1(&line[..line.len() - 2])
+    } else if start == V2_SIGNATURE[..5] {
+        let mut header = [0; 11];
+        stream.read_exact(&mut header).await?;
+        if header[..7] != V2_SIGNATURE[5..] {
+            return Err(invalid("invalid PROXY protocol v2 signature"));
+        }
+        let mut addresses = vec![0; u16::from_be_bytes([header[9], header[10]]) as usize];
+        stream.read_exact(&mut addresses).await?;
+        parse_v2(header[7], header[8], &addresses)
+    } else {
+        Err(invalid(
+            "connection doesn't start with a PROXY protocol header",
+        ))
+    }
+}
+
+fn parse_v1(line: &[u8]) -> io::Result<Option<SocketAddr>> {
+    let line =
+        std::str::from_utf8(line).map_err(|_| invalid("invalid PROXY protocol v1 header"))?;
+    let mut fields = line.split(' ').skip(1);
+    match fields.next() {
+        Some("TCP4" | "TCP6") => {}
+        Some("UNKNOWN") => return Ok(None),
+        _ => return Err(invalid("unsupported PROXY protocol v1 protocol")),
+    }
+    let fields = fields.collect::<Vec<_>>();
+    match fields[..] {
+        [source_ip, _, source_port, _] => {
+            let ip = source_ip
+                .parse::<IpAddr>()
+                .map_err(|_| invalid("invalid PROXY protocol v1 source address"))?;
+            let port = source_port
+                .parse::<u16>()
+                .map_err(|_| invalid("invalid PROXY protocol v1 source port"))?;
+            Ok(Some(SocketAddr::new(ip, port)))
+        }
+        _ => Err(invalid("invalid PROXY protocol v1 header")),
+    }
+}
+
+fn parse_v2(version_command: u8, family: u8, addresses: &[u8]) -> io::Result<Option<SocketAddr>> {
+    if version_command >> 4 != 2 {
+        return Err(invalid("unsupported PROXY protocol v2 version"));
+    }
+    match version_command & 0x0f {
+        // Connections made by the load balancer itself, such as health checks.
+        0 => return Ok(None),
+        1 => {}
+        _ => return Err(invalid("unsupported PROXY protocol v2 command")),
+    }
+    // The low bits of the family are the transport protocol, TCP or UDP, either of which carries
+    // the same addresses.
+    let source = match (family >> 4, addresses) {
+        (1, [a, b, c, d, _, _, _, _, p1, p2, ..]) => {
+            SocketAddr::from(([*a, *b, *c, *d], u16::from_be_bytes([*p1, *p2])))
+        }
+        (2, addresses) if addresses.len() >= 36 => {
+            let mut ip = [0; 16];
+            ip.copy_from_slice(&addresses[..16]);
+            let port = u16::from_be_bytes([addresses[32], addresses[33]]);
+            SocketAddr::from((ip, port))
+        }
+        (1 | 2, _) => return Err(invalid("PROXY protocol v2 addresses are too short")),
+        // Unix sockets, or addresses of an unspecified family.
+        _ => return Ok(None),
+    };
+    Ok(Some(source))
+}
+
+/// Encodes a PROXY protocol header for a connection from the source to the destination address.
+pub fn encode_header(
+    version: ProxyProtocolVersion,
+    source: SocketAddr,
+    destination: SocketAddr,
+) -> Vec<u8> {
+    // Both addresses must be of the same family, so IPv4 addresses are mapped to IPv6 ones if the
+    // other address is an IPv6 one.
+    let (source_ip, destination_ip) = match (source.ip(), destination.ip()) {
+        (IpAddr::V4(source_ip), IpAddr::V6(destination_ip)) => (
+            IpAddr::V6(source_ip.to_ipv6_mapped()),
+            IpAddr::V6(destination_ip),
+        ),
+        (IpAddr::V6(source_ip), IpAddr::V4(destination_ip)) => (
+            IpAddr::V6(source_ip),
+            IpAddr::V6(destination_ip.to_ipv6_mapped()),
+        ),
+        ips => ips,
+    };
+
+    match version {
+        ProxyProtocolVersion::V1 => {
+            let protocol = if source_ip.is_ipv4() { "TCP4" } else { "TCP6" };
+            format!(
+                "PROXY {} {} {} {} {}\r\n",
+                protocol,
+                source_ip,
+                destination_ip,
+                source.port(),
+                destination.port()
+            )
+            .into_bytes()
+        }
+        ProxyProtocolVersion::V2 => {
+            let mut header = V2_SIGNATURE.to_vec();
+            // Version 2, with the PROXY command.
+            header.push(0x21);
+            match (source_ip, destination_ip) {
+                (IpAddr::V4(source_ip), IpAddr::V4(destination_ip)) => {
+                    header.push(0x11);
+                    header.extend_from_slice(&12u16.to_be_bytes());
+                    header.extend_from_slice(&source_ip.octets());
+                    header.extend_from_slice(&destination_ip.octets());
+                }
+                (source_ip, destination_ip) => {
+                    header.push(0x21);
+                    header.extend_from_slice(&36u16.to_be_bytes());
+                    header.extend_from_slice(&ipv6(source_ip).octets());
+                    header.extend_from_slice(&ipv6(destination_ip).octets());
+                }
+            }
+            header.extend_from_slice(&source.port().to_be_bytes());
+            header.extend_from_slice(&destination.port().to_be_bytes());
+            header
+        }
+    }
+}
+
+fn ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    }
+}
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn read(mut header: &[u8]) -> io::Result<Option<SocketAddr>> {
+        read_header(&mut header).await
+    }
+
+    #[tokio::test]
+    async fn reads_encoded_headers() {
+        let source = "192.0.2.1:56324".parse().unwrap();
+        let destination = "198.51.100.2:9000".parse().unwrap();
+        let source_v6 = "[2001:db8::1]:56324".parse().unwrap();
+        for version in [ProxyProtocolVersion::V1, ProxyProtocolVersion::V2] {
+            let header = encode_header(version, source, destination);
+            assert_eq!(read(&header).await.unwrap(), Some(source));
+
+            let header = encode_header(version, source_v6, destination);
+            assert_eq!(read(&header).await.unwrap(), Some(source_v6));
+        }
+        assert_eq!(
+            encode_header(ProxyProtocolVersion::V1, source, destination),
+            b"PROXY TCP4 192.0.2.1 198.51.100.2 56324 9000\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_only_the_header() {
+        let mut stream = &b"PROXY TCP4 192.0.2.1 198.51.100.2 56324 9000\r\nmessage"[..];
+        read_header(&mut stream).await.unwrap();
+        assert_eq!(stream, b"message");
+    }
+
+    #[tokio::test]
+    async fn reads_headers_without_a_client_address() {
+        assert_eq!(read(b"PROXY UNKNOWN\r\n").await.unwrap(), None);
+
+        let mut local = V2_SIGNATURE.to_vec();
+        local.extend_from_slice(&[0x20, 0x00, 0x00, 0x00]);
+        assert_eq!(read(&local).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_headers() {
+        assert!(read(b"message\r\n").await.is_err());
+        assert!(read(b"PROXY TCP4 192.0.2.1\r\n").await.is_err());
+        assert!(read(b"PROXY TCP4 192.0.2.1 198.51.100.2 56324")
+            .await
+            .is_err());
+        assert!(read(&[&b"PROXY "[..], &[b'1'; 200]].concat())
+            .await
+            .is_err());
+    }
+}
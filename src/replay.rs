@@ -0,0 +1,261 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use vector_core::event::{Metric, MetricValue};
+
+use crate::{
+    config::{self, ComponentKey, ConfigBuilder},
+    metrics::Controller,
+    simulate::SimulatedSourceConfig,
+    sources::replay::ReplayConfig,
+    topology::{self, builder},
+};
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[clap(
+        name = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        use_value_delimiter(true)
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[clap(name = "config-toml", long, use_value_delimiter(true))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[clap(name = "config-json", long, use_value_delimiter(true))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[clap(name = "config-yaml", long, use_value_delimiter(true))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[clap(
+        name = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        use_value_delimiter(true)
+    )]
+    config_dirs: Vec<PathBuf>,
+
+    /// The directory of the archive, as written by an `archive` sink.
+    #[clap(short, long)]
+    archive: PathBuf,
+
+    /// The source the archived events are re-injected in place of, so that they go through the
+    /// components downstream of it. The other sources of the configuration emit no events.
+    #[clap(short, long)]
+    source: String,
+
+    /// Replays only the events from this time on, as an RFC 3339 timestamp.
+    #[clap(long)]
+    start: Option<DateTime<Utc>>,
+
+    /// Replays only the events before this time, as an RFC 3339 timestamp.
+    #[clap(long)]
+    end: Option<DateTime<Utc>>,
+
+    /// Replays the events as far apart as they happened, divided by this speed, rather than as
+    /// fast as possible.
+    #[clap(long)]
+    speed: Option<f64>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+
+    fn replay_config(&self) -> ReplayConfig {
+        ReplayConfig {
+            path: self.archive.clone(),
+            start: self.start,
+            end: self.end,
+            speed: self.speed,
+        }
+    }
+}
+
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let result = async {
+        let paths = config::process_paths(&opts.paths_with_formats())
+            .ok_or_else(|| vec!["No config paths found.".to_owned()])?;
+        config::init_log_schema(&paths, false)?;
+        let (builder, warnings) = config::load_builder_from_paths(&paths)?;
+        for warning in warnings {
+            eprintln!("~ {}", warning);
+        }
+        replay(
+            builder,
+            ComponentKey::from(opts.source.as_str()),
+            opts.replay_config(),
+        )
+        .await
+    };
+
+    match result.await {
+        Ok(count) => {
+            println!("Replayed {} events.", count);
+            exitcode::OK
+        }
+        Err(errors) => {
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            exitcode::CONFIG
+        }
+    }
+}
+
+/// Runs the topology of a configuration with the given source replaced by one replaying an
+/// archive, and the other sources by ones emitting nothing, until the archive is replayed,
+/// returning the number of events replayed.
+async fn replay(
+    mut builder: ConfigBuilder,
+    source: ComponentKey,
+    replay: ReplayConfig,
+) -> Result<u64, Vec<String>> {
+    if !builder.sources.contains_key(&source) {
+        return Err(vec![format!("Unknown source {:?}.", source.id())]);
+    }
+    for (key, outer) in builder.sources.iter_mut() {
+        outer.inner = if *key == source {
+            Box::new(replay.clone())
+        } else {
+            Box::new(SimulatedSourceConfig::idle(outer.inner.outputs()))
+        };
+    }
+
+    #[cfg(feature = "enterprise")]
+    {
+        builder.enterprise = None;
+    }
+
+    let config = builder.build()?;
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = builder::build_pieces(&config, &diff, HashMap::new()).await?;
+    let (topology, _) = topology::start_validated(config, diff, pieces)
+        .await
+        .ok_or_else(|| vec!["Couldn't start the topology.".to_owned()])?;
+    topology.sources_finished().await;
+    topology.stop().await;
+
+    let metrics = Controller::get()
+        .map(Controller::capture_metrics)
+        .unwrap_or_default();
+    Ok(sent_events(&metrics, &source))
+}
+
+fn sent_events(metrics: &[Metric], key: &ComponentKey) -> u64 {
+    metrics
+        .iter()
+        .filter(|metric| {
+            metric.name() == "component_sent_events_total"
+                && metric.tag_matches("component_id", key.id())
+        })
+        .map(|metric| match metric.value() {
+            MetricValue::Counter { value } => *value as u64,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use async_compression::tokio::write::ZstdEncoder;
+    use indoc::indoc;
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+    use crate::{
+        archive::{self, Record},
+        event::Event,
+        test_util::{components::init_test, temp_dir},
+    };
+
+    #[tokio::test]
+    async fn replays_archive_in_place_of_source() {
+        init_test();
+
+        let builder = || -> ConfigBuilder {
+            toml::from_str(indoc! {r#"
+                [sources.archived]
+                type = "stdin"
+
+                [sources.other]
+                type = "stdin"
+
+                [transforms.parse]
+                type = "remap"
+                inputs = ["archived", "other"]
+                source = ".parsed = true"
+
+                [sinks.out]
+                type = "blackhole"
+                inputs = ["parse"]
+            "#})
+            .unwrap()
+        };
+
+        let dir = temp_dir();
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join(archive::file_name(Utc::now(), 0));
+        let mut out = ZstdEncoder::new(tokio::fs::File::create(path).await.unwrap());
+        for message in ["first", "second"] {
+            let mut line =
+                serde_json::to_vec(&Record::new(Event::from(message), Utc::now())).unwrap();
+            line.push(b'\n');
+            out.write_all(&line).await.unwrap();
+        }
+        out.shutdown().await.unwrap();
+
+        let replay_config = ReplayConfig {
+            path: dir,
+            start: None,
+            end: None,
+            speed: None,
+        };
+        let count = replay(
+            builder(),
+            ComponentKey::from("archived"),
+            replay_config.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(count, 2);
+
+        assert!(
+            replay(builder(), ComponentKey::from("missing"), replay_config)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,492 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use futures::{future, stream::BoxStream, FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use vector_core::{
+    config::{DataType, Input, Output},
+    event::{Event, Metric, MetricValue},
+    sink::{StreamSink, VectorSink},
+};
+
+use crate::{
+    config::{
+        self, AcknowledgementsConfig, ComponentKey, ConfigBuilder, SinkConfig, SinkContext,
+        SinkOuter, SourceConfig, SourceContext,
+    },
+    metrics::Controller,
+    sinks::Healthcheck,
+    sources,
+    topology::{self, builder},
+};
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[clap(
+        name = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        use_value_delimiter(true)
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[clap(name = "config-toml", long, use_value_delimiter(true))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[clap(name = "config-json", long, use_value_delimiter(true))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[clap(name = "config-yaml", long, use_value_delimiter(true))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[clap(
+        name = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        use_value_delimiter(true)
+    )]
+    config_dirs: Vec<PathBuf>,
+
+    /// The events a source emits, as `<source>=<file>`, read from the file one event per line, or
+    /// from stdin if the file is `-`. Lines are read as messages of logs, or as JSON metrics for
+    /// sources that only emit metrics. Sources without input emit no events.
+    #[clap(short, long = "input", value_name = "SOURCE=FILE")]
+    inputs: Vec<String>,
+
+    /// A directory to write the events each sink receives to, as JSON, one event per line, in a
+    /// file named after the sink.
+    #[clap(short, long)]
+    output_dir: Option<PathBuf>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+
+    fn inputs(&self) -> Result<HashMap<ComponentKey, PathBuf>, Vec<String>> {
+        self.inputs
+            .iter()
+            .map(|input| match input.split_once('=') {
+                Some((source, path)) => Ok((ComponentKey::from(source), PathBuf::from(path))),
+                None => Err(vec![format!(
+                    "Invalid input {:?}, expected `<source>=<file>`.",
+                    input
+                )]),
+            })
+            .collect()
+    }
+}
+
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let result = async {
+        let paths = config::process_paths(&opts.paths_with_formats())
+            .ok_or_else(|| vec!["No config paths found.".to_owned()])?;
+        config::init_log_schema(&paths, false)?;
+        let (builder, warnings) = config::load_builder_from_paths(&paths)?;
+        for warning in warnings {
+            eprintln!("~ {}", warning);
+        }
+        simulate(builder, opts.inputs()?, opts.output_dir.as_deref()).await
+    };
+
+    match result.await {
+        Ok(counts) => {
+            println!(
+                "{:<32} {:<10} {:<24} {:>12} {:>12}",
+                "COMPONENT", "KIND", "TYPE", "RECEIVED", "SENT"
+            );
+            for count in counts {
+                println!(
+                    "{:<32} {:<10} {:<24} {:>12} {:>12}",
+                    count.key.to_string(),
+                    count.kind,
+                    count.component_type,
+                    count
+                        .received
+                        .map_or_else(|| "-".to_owned(), |n| n.to_string()),
+                    count.sent.map_or_else(|| "-".to_owned(), |n| n.to_string()),
+                );
+            }
+            exitcode::OK
+        }
+        Err(errors) => {
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            exitcode::CONFIG
+        }
+    }
+}
+
+/// The number of events a component received and sent during a simulation.
+#[derive(Debug, PartialEq)]
+struct ComponentCounts {
+    key: ComponentKey,
+    kind: &'static str,
+    component_type: &'static str,
+    received: Option<u64>,
+    sent: Option<u64>,
+}
+
+/// Runs the topology of a configuration, with its sources replaced by sources emitting the events
+/// of the given files and its sinks replaced by sinks capturing the events they receive, until the
+/// sources run out of events.
+async fn simulate(
+    mut builder: ConfigBuilder,
+    mut inputs: HashMap<ComponentKey, PathBuf>,
+    output_dir: Option<&Path>,
+) -> Result<Vec<ComponentCounts>, Vec<String>> {
+    let mut components = Vec::new();
+
+    for (key, source) in builder.sources.iter_mut() {
+        components.push((key.clone(), "source", source.inner.source_type()));
+        let simulated = SimulatedSourceConfig {
+            input: inputs.remove(key),
+            outputs: source.inner.outputs(),
+        };
+        source.inner = Box::new(simulated);
+    }
+    if let Some(key) = inputs.keys().next() {
+        return Err(vec![format!("Input for unknown source {:?}.", key.id())]);
+    }
+
+    for (key, transform) in &builder.transforms {
+        components.push((key.clone(), "transform", transform.inner.transform_type()));
+    }
+
+    if let Some(output_dir) = output_dir {
+        std::fs::create_dir_all(output_dir).map_err(|error| {
+            vec![format!(
+                "Couldn't create output directory {:?}: {}.",
+                output_dir, error
+            )]
+        })?;
+    }
+    for (key, sink) in builder.sinks.iter_mut() {
+        components.push((key.clone(), "sink", sink.inner.sink_type()));
+        let captured = CapturedSinkConfig {
+            output: output_dir.map(|dir| dir.join(format!("{}.ndjson", key.id()))),
+        };
+        *sink = SinkOuter::new(std::mem::take(&mut sink.inputs), Box::new(captured));
+    }
+
+    builder.healthchecks.enabled = false;
+    #[cfg(feature = "enterprise")]
+    {
+        builder.enterprise = None;
+    }
+
+    let config = builder.build()?;
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = builder::build_pieces(&config, &diff, HashMap::new()).await?;
+    let (topology, _) = topology::start_validated(config, diff, pieces)
+        .await
+        .ok_or_else(|| vec!["Couldn't start the topology.".to_owned()])?;
+    topology.sources_finished().await;
+    topology.stop().await;
+
+    let metrics = Controller::get()
+        .map(Controller::capture_metrics)
+        .unwrap_or_default();
+    Ok(components
+        .into_iter()
+        .map(|(key, kind, component_type)| ComponentCounts {
+            received: (kind != "source")
+                .then(|| sum_counters(&metrics, "component_received_events_total", &key)),
+            sent: (kind != "sink")
+                .then(|| sum_counters(&metrics, "component_sent_events_total", &key)),
+            key,
+            kind,
+            component_type,
+        })
+        .collect())
+}
+
+fn sum_counters(metrics: &[Metric], name: &str, key: &ComponentKey) -> u64 {
+    metrics
+        .iter()
+        .filter(|metric| metric.name() == name && metric.tag_matches("component_id", key.id()))
+        .map(|metric| match metric.value() {
+            MetricValue::Counter { value } => *value as u64,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Emits the events read from a file in place of a source.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(crate) struct SimulatedSourceConfig {
+    #[serde(skip)]
+    input: Option<PathBuf>,
+    #[serde(skip)]
+    outputs: Vec<Output>,
+}
+
+impl SimulatedSourceConfig {
+    /// A source with the given outputs that emits no events, and stops right away.
+    pub(crate) fn idle(outputs: Vec<Output>) -> Self {
+        Self {
+            input: None,
+            outputs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "simulated")]
+impl SourceConfig for SimulatedSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let input = self.input.clone();
+        let metrics_only = self
+            .outputs
+            .iter()
+            .find(|output| output.port.is_none())
+            .map_or(false, |output| !output.ty.contains(DataType::Log));
+
+        Ok(Box::pin(async move {
+            let mut out = cx.out;
+            // The topology is shut down once every source has run out of events.
+            let _shutdown = cx.shutdown;
+
+            let content = match input {
+                None => return Ok(()),
+                Some(path) if path == Path::new("-") => {
+                    let mut content = String::new();
+                    tokio::io::stdin()
+                        .read_to_string(&mut content)
+                        .await
+                        .map(|_| content)
+                }
+                Some(path) => tokio::fs::read_to_string(path).await,
+            };
+            let content = content.map_err(|error| {
+                error!(message = "Couldn't read input.", %error);
+            })?;
+
+            let mut events = Vec::new();
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                if metrics_only {
+                    match serde_json::from_str::<Metric>(line) {
+                        Ok(metric) => events.push(Event::Metric(metric)),
+                        Err(error) => warn!(message = "Skipping invalid metric.", %error),
+                    }
+                } else {
+                    events.push(Event::from(line));
+                }
+            }
+            out.send_batch(events).await.map_err(|_| ())
+        }))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        self.outputs.clone()
+    }
+
+    fn source_type(&self) -> &'static str {
+        "simulated"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Captures the events received in place of a sink, writing them to a file if one is given.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CapturedSinkConfig {
+    #[serde(skip)]
+    output: Option<PathBuf>,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "captured")]
+impl SinkConfig for CapturedSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let file = match &self.output {
+            Some(path) => Some(tokio::fs::File::create(path).await?),
+            None => None,
+        };
+        let sink = CapturedSink { file };
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            future::ok(()).boxed(),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "captured"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        None
+    }
+}
+
+struct CapturedSink {
+    file: Option<tokio::fs::File>,
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for CapturedSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        while let Some(event) = input.next().await {
+            let file = match &mut self.file {
+                Some(file) => file,
+                None => continue,
+            };
+            let json = match &event {
+                Event::Log(log) => serde_json::to_string(log),
+                Event::Metric(metric) => serde_json::to_string(metric),
+                Event::Trace(trace) => serde_json::to_string(trace),
+            };
+            let mut line = json.map_err(|error| {
+                error!(message = "Couldn't encode event.", %error);
+            })?;
+            line.push('\n');
+            file.write_all(line.as_bytes()).await.map_err(|error| {
+                error!(message = "Couldn't write event.", %error);
+            })?;
+        }
+        if let Some(file) = &mut self.file {
+            file.flush().await.map_err(|error| {
+                error!(message = "Couldn't write events.", %error);
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "sources-stdin",
+    feature = "sinks-console",
+    feature = "transforms-remap"
+))]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::test_util::{components::init_test, temp_dir};
+
+    #[tokio::test]
+    async fn runs_the_topology_against_the_inputs() {
+        init_test();
+
+        let builder: ConfigBuilder = toml::from_str(indoc! {r#"
+            [sources.in]
+            type = "stdin"
+
+            [sources.idle]
+            type = "stdin"
+
+            [transforms.parse]
+            type = "remap"
+            inputs = ["in", "idle"]
+            source = ".parsed = true"
+
+            [sinks.out]
+            type = "console"
+            inputs = ["parse"]
+            encoding = "json"
+        "#})
+        .unwrap();
+
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.log");
+        std::fs::write(&input, "first\nsecond\n").unwrap();
+        let output_dir = dir.join("output");
+
+        let counts = simulate(
+            builder,
+            HashMap::from([(ComponentKey::from("in"), input)]),
+            Some(&output_dir),
+        )
+        .await
+        .unwrap();
+
+        let counts: Vec<_> = counts
+            .iter()
+            .map(|count| (count.key.id(), count.received, count.sent))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("in", None, Some(2)),
+                ("idle", None, Some(0)),
+                ("parse", Some(2), Some(2)),
+                ("out", Some(2), None),
+            ]
+        );
+
+        let output = std::fs::read_to_string(output_dir.join("out.ndjson")).unwrap();
+        let messages: Vec<_> = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .map(|event| {
+                assert_eq!(event["parsed"], true);
+                event["message"].as_str().unwrap().to_owned()
+            })
+            .collect();
+        assert_eq!(messages, vec!["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_inputs_of_unknown_sources() {
+        let builder: ConfigBuilder = toml::from_str(indoc! {r#"
+            [sources.in]
+            type = "stdin"
+
+            [sinks.out]
+            type = "console"
+            inputs = ["in"]
+            encoding = "json"
+        "#})
+        .unwrap();
+
+        let errors = simulate(
+            builder,
+            HashMap::from([(ComponentKey::from("other"), PathBuf::from("input.log"))]),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(errors, vec!["Input for unknown source \"other\"."]);
+    }
+}
@@ -0,0 +1,319 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use async_compression::tokio::write::ZstdEncoder;
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::{future, stream::BoxStream, FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs::{self, File},
+    io::AsyncWriteExt,
+};
+use vector_core::{buffers::Acker, internal_event::EventsSent, ByteSizeOf};
+
+use crate::{
+    archive::{self, Record},
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
+    },
+    event::{Event, EventStatus, Finalizable},
+    internal_events::{FileBytesSent, FileIoError, FileOpen},
+    sinks::util::StreamSink,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ArchiveSinkConfig {
+    /// The directory the archive is written to.
+    pub path: PathBuf,
+    /// Completes a file once this many bytes of records, before compression, were written to
+    /// it, and writes the events that follow to a new one.
+    #[serde(default = "default_max_size_bytes")]
+    pub max_size_bytes: u64,
+    /// Completes a file once it has been open this many seconds, and writes the events that
+    /// follow to a new one.
+    #[serde(default = "default_max_age_secs")]
+    pub max_age_secs: u64,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_max_size_bytes() -> u64 {
+    256 * 1024 * 1024
+}
+
+const fn default_max_age_secs() -> u64 {
+    3600
+}
+
+inventory::submit! {
+    SinkDescription::new::<ArchiveSinkConfig>("archive")
+}
+
+impl GenerateConfig for ArchiveSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            path: PathBuf::from("/var/lib/vector/archive"),
+            max_size_bytes: default_max_size_bytes(),
+            max_age_secs: default_max_age_secs(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "archive")]
+impl SinkConfig for ArchiveSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        fs::create_dir_all(&self.path).await?;
+        let sink = ArchiveSink {
+            acker: cx.acker(),
+            path: self.path.clone(),
+            max_size: self.max_size_bytes,
+            max_age: Duration::from_secs(self.max_age_secs),
+            file: None,
+            last_opened: None,
+        };
+        Ok((
+            super::VectorSink::from_event_streamsink(sink),
+            future::ok(()).boxed(),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "archive"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+/// An archive file being written, under a hidden name until it's complete.
+struct ArchiveFile {
+    out: ZstdEncoder<File>,
+    temp_path: PathBuf,
+    final_path: PathBuf,
+    opened: Instant,
+    written: u64,
+}
+
+struct ArchiveSink {
+    acker: Acker,
+    path: PathBuf,
+    max_size: u64,
+    max_age: Duration,
+    file: Option<ArchiveFile>,
+    /// The millisecond the last file was opened at and how many were opened within it, to name
+    /// the next one uniquely.
+    last_opened: Option<(i64, usize)>,
+}
+
+impl ArchiveSink {
+    async fn write(&mut self, mut event: Event) {
+        let finalizers = event.take_finalizers();
+        let event_size = event.size_of();
+        let mut line = match serde_json::to_vec(&Record::new(event, Utc::now())) {
+            Ok(line) => line,
+            Err(error) => {
+                finalizers.update_status(EventStatus::Errored);
+                emit!(FileIoError {
+                    error: error.into(),
+                    code: "failed_encoding_event",
+                    message: "Failed to encode the event.",
+                    path: None,
+                });
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        if self.file.is_none() {
+            match self.open().await {
+                Ok(file) => {
+                    self.file = Some(file);
+                    emit!(FileOpen { count: 1 });
+                }
+                Err(error) => {
+                    finalizers.update_status(EventStatus::Errored);
+                    emit!(FileIoError {
+                        error,
+                        code: "failed_opening_file",
+                        message: "Unable to open the file.",
+                        path: None,
+                    });
+                    return;
+                }
+            }
+        }
+        let file = self.file.as_mut().expect("file was just opened");
+
+        match file.out.write_all(&line).await {
+            Ok(()) => {
+                finalizers.update_status(EventStatus::Delivered);
+                emit!(EventsSent {
+                    count: 1,
+                    byte_size: event_size,
+                    output: None,
+                });
+                emit!(FileBytesSent {
+                    byte_size: line.len(),
+                    file: file.final_path.to_string_lossy(),
+                });
+                file.written += line.len() as u64;
+                if file.written >= self.max_size {
+                    self.complete().await;
+                }
+            }
+            Err(error) => {
+                finalizers.update_status(EventStatus::Errored);
+                emit!(FileIoError {
+                    error,
+                    code: "failed_writing_file",
+                    message: "Failed to write the file.",
+                    path: None,
+                });
+            }
+        }
+    }
+
+    async fn open(&mut self) -> std::io::Result<ArchiveFile> {
+        let now = Utc::now();
+        // Names only have millisecond precision, which several files can share.
+        let sequence = match self.last_opened {
+            Some((last, sequence)) if last == now.timestamp_millis() => sequence + 1,
+            _ => 0,
+        };
+        self.last_opened = Some((now.timestamp_millis(), sequence));
+
+        let name = archive::file_name(now, sequence);
+        let temp_path = self.path.join(format!(".{}", name));
+        let file = File::create(&temp_path).await?;
+        Ok(ArchiveFile {
+            out: ZstdEncoder::new(file),
+            temp_path,
+            final_path: self.path.join(name),
+            opened: Instant::now(),
+            written: 0,
+        })
+    }
+
+    /// Finishes writing the open file, if any, and moves it into place, so that the events that
+    /// follow are written to a new one.
+    async fn complete(&mut self) {
+        if let Some(mut file) = self.file.take() {
+            emit!(FileOpen { count: 0 });
+            if let Err(error) = finish(&mut file.out, &file.temp_path, &file.final_path).await {
+                emit!(FileIoError {
+                    error,
+                    code: "failed_finalizing_file",
+                    message: "Failed to finalize file.",
+                    path: None,
+                });
+            }
+        }
+    }
+}
+
+async fn finish(
+    out: &mut ZstdEncoder<File>,
+    temp_path: &Path,
+    final_path: &Path,
+) -> std::io::Result<()> {
+    out.shutdown().await?;
+    out.get_mut().sync_all().await?;
+    fs::rename(temp_path, final_path).await
+}
+
+#[async_trait]
+impl StreamSink<Event> for ArchiveSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut rotation = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            tokio::select! {
+                event = input.next() => match event {
+                    Some(event) => {
+                        self.write(event).await;
+                        self.acker.ack(1);
+                    }
+                    None => break,
+                },
+                _ = rotation.tick(), if self.file.is_some() => {
+                    let expired = self
+                        .file
+                        .as_ref()
+                        .map_or(false, |file| file.opened.elapsed() >= self.max_age);
+                    if expired {
+                        self.complete().await;
+                    }
+                }
+            }
+        }
+        self.complete().await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::{
+        config::log_schema,
+        test_util::{
+            components::{run_and_assert_sink_compliance, FILE_SINK_TAGS},
+            random_lines_with_stream, temp_dir,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ArchiveSinkConfig>();
+    }
+
+    #[tokio::test]
+    async fn archives_events() {
+        let path = temp_dir();
+        let config: ArchiveSinkConfig = toml::from_str(&format!(
+            r#"
+            path = "{}"
+            max_size_bytes = 2048
+            "#,
+            path.display()
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (lines, _) = random_lines_with_stream(100, 64, None);
+        let events = Box::pin(stream::iter(lines.clone().into_iter().map(Event::from)));
+        run_and_assert_sink_compliance(sink, events, &FILE_SINK_TAGS).await;
+
+        let files = archive::files(&path).await.unwrap();
+        // Each file was completed once it reached the maximum size.
+        assert!(files.len() > 1, "{:?}", files);
+        let mut archived = Vec::new();
+        for file in files {
+            let mut reader = archive::Reader::open(&file).await.unwrap();
+            while let Some(record) = reader.next().await.unwrap() {
+                let event = record.into_event();
+                archived.push(event.as_log()[log_schema().message_key()].to_string_lossy());
+            }
+        }
+        assert_eq!(archived, lines);
+    }
+}
@@ -1,6 +1,5 @@
-use std::{convert::TryInto, sync::Arc};
+use std::convert::TryInto;
 
-use azure_storage_blobs::prelude::*;
 use codecs::{
     encoding::{Framer, Serializer},
     CharacterDelimitedEncoder, NewlineDelimitedEncoder,
@@ -14,7 +13,11 @@ use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         azure_common::{
-            self, config::AzureBlobRetryLogic, service::AzureBlobService, sink::AzureBlobSink,
+            self,
+            auth::AzureAuthentication,
+            config::{AzureBlobRetryLogic, SharedContainerClient},
+            service::AzureBlobService,
+            sink::AzureBlobSink,
         },
         util::{
             encoding::{
@@ -35,6 +38,7 @@ use crate::{
 pub struct AzureBlobSinkConfig {
     pub connection_string: Option<String>,
     pub storage_account: Option<String>,
+    pub auth: Option<AzureAuthentication>,
     pub(super) container_name: String,
     pub blob_prefix: Option<String>,
     pub blob_time_format: Option<String>,
@@ -63,6 +67,7 @@ impl GenerateConfig for AzureBlobSinkConfig {
         toml::Value::try_from(Self {
             connection_string: Some(String::from("DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=some-account-key;")),
             storage_account: Some(String::from("some-account-name")),
+            auth: None,
             container_name: String::from("logs"),
             blob_prefix: Some(String::from("blob")),
             blob_time_format: Some(String::from("%s")),
@@ -84,13 +89,12 @@ impl SinkConfig for AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone(),
             self.storage_account.clone(),
+            self.auth.as_ref(),
             self.container_name.clone(),
         )?;
 
-        let healthcheck = azure_common::config::build_healthcheck(
-            self.container_name.clone(),
-            Arc::clone(&client),
-        )?;
+        let healthcheck =
+            azure_common::config::build_healthcheck(self.container_name.clone(), client.clone())?;
         let sink = self.build_processor(client, cx)?;
         Ok((sink, healthcheck))
     }
@@ -118,7 +122,7 @@ const DEFAULT_FILENAME_APPEND_UUID: bool = true;
 impl AzureBlobSinkConfig {
     pub fn build_processor(
         &self,
-        client: Arc<ContainerClient>,
+        client: SharedContainerClient,
         cx: SinkContext,
     ) -> crate::Result<VectorSink> {
         let request_limits = self.request.unwrap_with(&DEFAULT_REQUEST_LIMITS);
@@ -35,6 +35,7 @@ async fn azure_blob_healthcheck_passed() {
     let client = azure_common::config::build_client(
         config.connection_string,
         None,
+        None,
         config.container_name.clone(),
     )
     .expect("Failed to create client");
@@ -54,6 +55,7 @@ async fn azure_blob_healthcheck_unknown_container() {
     let client = azure_common::config::build_client(
         config.connection_string,
         config.storage_account,
+        config.auth.as_ref(),
         config.container_name.clone(),
     )
     .expect("Failed to create client");
@@ -216,6 +218,7 @@ impl AzureBlobSinkConfig {
         let config = AzureBlobSinkConfig {
                 connection_string: Some(format!("UseDevelopmentStorage=true;DefaultEndpointsProtocol=http;AccountName=devstoreaccount1;AccountKey=Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==;BlobEndpoint=http://{}:10000/devstoreaccount1;QueueEndpoint=http://{}:10001/devstoreaccount1;TableEndpoint=http://{}:10002/devstoreaccount1;", address, address, address)),
                 storage_account: None,
+                auth: None,
                 container_name: "logs".to_string(),
                 blob_prefix: None,
                 blob_time_format: None,
@@ -237,6 +240,7 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone(),
             self.storage_account.clone(),
+            self.auth.as_ref(),
             self.container_name.clone(),
         )
         .expect("Failed to create client");
@@ -249,10 +253,12 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone(),
             self.storage_account.clone(),
+            self.auth.as_ref(),
             self.container_name.clone(),
         )
         .unwrap();
         let response = client
+            .get()
             .list_blobs()
             .prefix(prefix)
             .max_results(NonZeroU32::new(1000).unwrap())
@@ -276,10 +282,12 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone(),
             self.storage_account.clone(),
+            self.auth.as_ref(),
             self.container_name.clone(),
         )
         .unwrap();
         let response = client
+            .get()
             .as_blob_client(blob.as_str())
             .get()
             .range(Range::new(0, 1024 * 1024))
@@ -307,10 +315,15 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone(),
             self.storage_account.clone(),
+            self.auth.as_ref(),
             self.container_name.clone(),
         )
         .unwrap();
-        let request = client.create().public_access(PublicAccess::None).execute();
+        let request = client
+            .get()
+            .create()
+            .public_access(PublicAccess::None)
+            .execute();
 
         let response = match request.await {
             Ok(_) => Ok(()),
@@ -6,6 +6,7 @@ use vector_core::partition::Partitioner;
 use super::config::AzureBlobSinkConfig;
 use super::request_builder::AzureBlobRequestOptions;
 use crate::event::Event;
+use crate::sinks::azure_common::{auth::AzureAuthentication, config::build_client};
 use crate::sinks::util::{
     encoding::{EncodingConfig, StandardEncodings},
     request_builder::RequestBuilder,
@@ -17,6 +18,7 @@ fn default_config(e: StandardEncodings) -> AzureBlobSinkConfig {
     AzureBlobSinkConfig {
         connection_string: Default::default(),
         storage_account: Default::default(),
+        auth: Default::default(),
         container_name: Default::default(),
         blob_prefix: Default::default(),
         blob_time_format: Default::default(),
@@ -200,3 +202,41 @@ fn azure_blob_build_request_with_uuid() {
     assert_eq!(request.content_encoding, None);
     assert_eq!(request.content_type, "text/plain");
 }
+
+#[test]
+fn azure_blob_auth_requires_storage_account() {
+    let error = build_client(
+        Some(String::from("DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=some-account-key;")),
+        None,
+        Some(&AzureAuthentication::Default),
+        String::from("logs"),
+    )
+    .map(|_| ())
+    .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "`auth` can't be provided with `connection_string`"
+    );
+}
+
+#[tokio::test]
+async fn azure_blob_sas_token_requires_one_source() {
+    let error = build_client(
+        None,
+        Some(String::from("some-account-name")),
+        Some(&AzureAuthentication::SasToken {
+            sas_token: Some(String::from("sv=2021-06-08&sig=signature")),
+            sas_token_file: Some("/var/run/secrets/azure/sas-token".into()),
+            refresh_secs: 300,
+        }),
+        String::from("logs"),
+    )
+    .map(|_| ())
+    .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Exactly one of `sas_token` or `sas_token_file` has to be provided"
+    );
+}
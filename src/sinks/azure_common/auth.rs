@@ -0,0 +1,423 @@
+//! Authentication of the Azure components with a storage account, either with the credentials of
+//! an identity, as bearer tokens, or with a shared access signature (SAS) token.
+
+use std::{path::PathBuf, sync::Arc};
+
+use azure_core::auth::{AccessToken, TokenCredential, TokenResponse};
+use azure_identity::DefaultAzureCredential;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use http::header::CONTENT_TYPE;
+use hyper::Body;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{config::ProxyConfig, http::HttpClient};
+
+const DEFAULT_AUTHORITY_HOST: &str = "https://login.microsoftonline.com";
+const IMDS_TOKEN_URL: &str = "http://169.254.169.254/metadata/identity/oauth2/token";
+const IMDS_API_VERSION: &str = "2018-02-01";
+const CLIENT_ASSERTION_TYPE: &str = "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+
+const fn default_sas_token_refresh_secs() -> u64 {
+    300
+}
+
+/// How to authenticate with the storage account.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+pub enum AzureAuthentication {
+    /// Tries workload identity, when the environment is set up for it, then the client secret of
+    /// the environment, managed identity and the Azure CLI, in this order.
+    Default,
+    /// Authenticates as a service principal with a client secret.
+    ClientSecret {
+        tenant_id: String,
+        client_id: String,
+        client_secret: String,
+    },
+    /// Authenticates as the application federated with the identity of the Kubernetes service
+    /// account, as projected into the pod by the workload identity webhook.
+    WorkloadIdentity {
+        #[serde(default)]
+        tenant_id: Option<String>,
+        #[serde(default)]
+        client_id: Option<String>,
+        #[serde(default)]
+        federated_token_file: Option<PathBuf>,
+    },
+    /// Authenticates with the managed identity of the host, which is the user-assigned identity
+    /// with the given client ID, if any, or the system-assigned one.
+    ManagedIdentity {
+        #[serde(default)]
+        client_id: Option<String>,
+    },
+    /// Authenticates with a SAS token, which is read again periodically when it is read from a
+    /// file, so that it can be rotated without reloading Vector.
+    SasToken {
+        #[serde(default)]
+        sas_token: Option<String>,
+        #[serde(default)]
+        sas_token_file: Option<PathBuf>,
+        #[serde(default = "default_sas_token_refresh_secs")]
+        refresh_secs: u64,
+    },
+}
+
+impl Default for AzureAuthentication {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+impl AzureAuthentication {
+    /// Returns the credential issuing the bearer tokens of the strategy, which is `None` for SAS
+    /// tokens.
+    pub(super) fn token_credential(&self) -> crate::Result<Option<Arc<dyn TokenCredential>>> {
+        Ok(Some(match self {
+            Self::Default => {
+                let mut credentials: Vec<Arc<dyn TokenCredential>> = vec![];
+                if std::env::var_os("AZURE_FEDERATED_TOKEN_FILE").is_some() {
+                    credentials.push(Arc::new(ClientCredential::workload_identity(
+                        None, None, None,
+                    )?));
+                }
+                credentials.push(Arc::new(DefaultAzureCredential::default()));
+                Arc::new(ChainedCredential(credentials))
+            }
+            Self::ClientSecret {
+                tenant_id,
+                client_id,
+                client_secret,
+            } => Arc::new(ClientCredential::new(
+                tenant_id.clone(),
+                client_id.clone(),
+                ClientAuthentication::Secret(client_secret.clone()),
+            )),
+            Self::WorkloadIdentity {
+                tenant_id,
+                client_id,
+                federated_token_file,
+            } => Arc::new(ClientCredential::workload_identity(
+                tenant_id.clone(),
+                client_id.clone(),
+                federated_token_file.clone(),
+            )?),
+            Self::ManagedIdentity { client_id } => Arc::new(ManagedIdentityCredential {
+                client_id: client_id.clone(),
+            }),
+            Self::SasToken { .. } => return Ok(None),
+        }))
+    }
+}
+
+/// Reads a SAS token, which may be given with the leading `?` of a query string.
+pub(super) fn parse_sas_token(token: &str) -> crate::Result<String> {
+    let token = token.trim().trim_start_matches('?');
+    if token.is_empty() {
+        return Err("The SAS token is empty".into());
+    }
+    Ok(token.to_owned())
+}
+
+/// Tries each credential in turn, returning the token of the first one that issues one.
+struct ChainedCredential(Vec<Arc<dyn TokenCredential>>);
+
+#[async_trait::async_trait]
+impl TokenCredential for ChainedCredential {
+    async fn get_token(&self, resource: &str) -> Result<TokenResponse, azure_core::Error> {
+        let mut errors = vec![];
+        for credential in &self.0 {
+            match credential.get_token(resource).await {
+                Ok(token) => return Ok(token),
+                Err(error) => errors.push(error.to_string()),
+            }
+        }
+        Err(azure_core::Error::GetToken(
+            format!("No Azure credentials were found: {}", errors.join(", ")).into(),
+        ))
+    }
+}
+
+/// How an application authenticates with the Microsoft identity platform.
+enum ClientAuthentication {
+    Secret(String),
+    /// A token issued by another identity provider, which is read again for every request, as
+    /// it is rotated by whatever wrote it.
+    FederatedTokenFile(PathBuf),
+}
+
+/// Issues tokens to an application with the OAuth client credentials flow.
+struct ClientCredential {
+    authority_host: String,
+    tenant_id: String,
+    client_id: String,
+    authentication: ClientAuthentication,
+}
+
+#[derive(Deserialize, Debug)]
+struct ClientCredentialResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+impl ClientCredential {
+    fn new(tenant_id: String, client_id: String, authentication: ClientAuthentication) -> Self {
+        Self {
+            authority_host: std::env::var("AZURE_AUTHORITY_HOST")
+                .unwrap_or_else(|_| DEFAULT_AUTHORITY_HOST.to_owned()),
+            tenant_id,
+            client_id,
+            authentication,
+        }
+    }
+
+    /// Builds the workload identity credential, defaulting to the settings that the workload
+    /// identity webhook sets in the environment of the pod.
+    fn workload_identity(
+        tenant_id: Option<String>,
+        client_id: Option<String>,
+        federated_token_file: Option<PathBuf>,
+    ) -> crate::Result<Self> {
+        let from_env = |name: &str| {
+            std::env::var(name).map_err(|_| {
+                format!(
+                    "Workload identity requires the {} environment variable to be set",
+                    name
+                )
+            })
+        };
+        let tenant_id = tenant_id.map_or_else(|| from_env("AZURE_TENANT_ID"), Ok)?;
+        let client_id = client_id.map_or_else(|| from_env("AZURE_CLIENT_ID"), Ok)?;
+        let federated_token_file = federated_token_file.map_or_else(
+            || from_env("AZURE_FEDERATED_TOKEN_FILE").map(PathBuf::from),
+            Ok,
+        )?;
+
+        Ok(Self::new(
+            tenant_id,
+            client_id,
+            ClientAuthentication::FederatedTokenFile(federated_token_file),
+        ))
+    }
+
+    async fn fetch_token(&self, resource: &str) -> crate::Result<TokenResponse> {
+        debug!(
+            message = "Fetching Azure authentication token.",
+            tenant_id = %self.tenant_id,
+            client_id = %self.client_id,
+        );
+        let mut body = url::form_urlencoded::Serializer::new(String::new());
+        body.append_pair("grant_type", "client_credentials")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("scope", &scope(resource));
+        match &self.authentication {
+            ClientAuthentication::Secret(secret) => {
+                body.append_pair("client_secret", secret);
+            }
+            ClientAuthentication::FederatedTokenFile(path) => {
+                let assertion = tokio::fs::read_to_string(path).await?;
+                body.append_pair("client_assertion_type", CLIENT_ASSERTION_TYPE)
+                    .append_pair("client_assertion", assertion.trim());
+            }
+        }
+
+        let url = format!(
+            "{}/{}/oauth2/v2.0/token",
+            self.authority_host.trim_end_matches('/'),
+            self.tenant_id
+        );
+        let request = http::Request::post(url)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(body.finish()))?;
+        let response: ClientCredentialResponse =
+            send_json(request, &ProxyConfig::from_env()).await?;
+
+        Ok(TokenResponse::new(
+            AccessToken::new(response.access_token),
+            Utc::now() + Duration::seconds(response.expires_in),
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenCredential for ClientCredential {
+    async fn get_token(&self, resource: &str) -> Result<TokenResponse, azure_core::Error> {
+        self.fetch_token(resource)
+            .await
+            .map_err(azure_core::Error::GetToken)
+    }
+}
+
+/// Issues tokens to the managed identity of the host with the Azure Instance Metadata Service.
+struct ManagedIdentityCredential {
+    client_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ManagedIdentityResponse {
+    access_token: String,
+    /// The expiration time of the token, in seconds since the epoch.
+    expires_on: String,
+}
+
+impl ManagedIdentityCredential {
+    async fn fetch_token(&self, resource: &str) -> crate::Result<TokenResponse> {
+        debug!(
+            message = "Fetching Azure managed identity token.",
+            client_id = ?self.client_id,
+        );
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query
+            .append_pair("api-version", IMDS_API_VERSION)
+            .append_pair("resource", resource);
+        if let Some(client_id) = &self.client_id {
+            query.append_pair("client_id", client_id);
+        }
+
+        let request = http::Request::get(format!("{}?{}", IMDS_TOKEN_URL, query.finish()))
+            .header("Metadata", "true")
+            .body(Body::empty())?;
+        // The metadata service is local to the host, so requests never go through a proxy.
+        let response: ManagedIdentityResponse = send_json(request, &ProxyConfig::default()).await?;
+
+        Ok(TokenResponse::new(
+            AccessToken::new(response.access_token),
+            parse_expires_on(&response.expires_on)?,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenCredential for ManagedIdentityCredential {
+    async fn get_token(&self, resource: &str) -> Result<TokenResponse, azure_core::Error> {
+        self.fetch_token(resource)
+            .await
+            .map_err(azure_core::Error::GetToken)
+    }
+}
+
+/// Returns the scope of the Microsoft identity platform for the resource, such as
+/// `https://storage.azure.com/.default` for `https://storage.azure.com/`.
+fn scope(resource: &str) -> String {
+    format!("{}/.default", resource.trim_end_matches('/'))
+}
+
+fn parse_expires_on(expires_on: &str) -> crate::Result<DateTime<Utc>> {
+    Ok(Utc.timestamp(expires_on.parse()?, 0))
+}
+
+/// Sends the request, returning the JSON body of its successful response.
+async fn send_json<T: DeserializeOwned>(
+    request: http::Request<Body>,
+    proxy: &ProxyConfig,
+) -> crate::Result<T> {
+    let uri = request.uri().clone();
+    let response = HttpClient::new(None, proxy)?.send(request).await?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    if !status.is_success() {
+        return Err(format!(
+            "{} responded with {}: {}",
+            uri,
+            status,
+            String::from_utf8_lossy(&body)
+        )
+        .into());
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_authentication_strategies() {
+        let auth: AzureAuthentication = toml::from_str(r#"strategy = "default""#).unwrap();
+        assert_eq!(auth, AzureAuthentication::Default);
+
+        let auth: AzureAuthentication = toml::from_str(
+            r#"
+            strategy = "client_secret"
+            tenant_id = "tenant"
+            client_id = "client"
+            client_secret = "secret"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(auth, AzureAuthentication::ClientSecret { .. }));
+
+        let auth: AzureAuthentication = toml::from_str(
+            r#"
+            strategy = "managed_identity"
+            client_id = "client"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            auth,
+            AzureAuthentication::ManagedIdentity {
+                client_id: Some("client".to_owned())
+            }
+        );
+
+        let auth: AzureAuthentication = toml::from_str(
+            r#"
+            strategy = "sas_token"
+            sas_token_file = "/var/run/secrets/azure/sas-token"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            auth,
+            AzureAuthentication::SasToken {
+                sas_token: None,
+                sas_token_file: Some("/var/run/secrets/azure/sas-token".into()),
+                refresh_secs: 300,
+            }
+        );
+        assert!(auth.token_credential().unwrap().is_none());
+    }
+
+    #[test]
+    fn workload_identity_requires_settings() {
+        let error = ClientCredential::workload_identity(
+            Some("tenant".to_owned()),
+            Some("client".to_owned()),
+            None,
+        )
+        .map(|_| ())
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Workload identity requires the AZURE_FEDERATED_TOKEN_FILE environment variable to be set"
+        );
+
+        assert!(ClientCredential::workload_identity(
+            Some("tenant".to_owned()),
+            Some("client".to_owned()),
+            Some("/var/run/secrets/azure/tokens/azure-identity-token".into()),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn parses_sas_tokens() {
+        assert_eq!(
+            parse_sas_token("?sv=2021-06-08&sig=signature\n").unwrap(),
+            "sv=2021-06-08&sig=signature"
+        );
+        assert!(parse_sas_token(" \n").is_err());
+    }
+
+    #[test]
+    fn builds_scopes_and_expiration_times() {
+        assert_eq!(
+            scope("https://storage.azure.com/"),
+            "https://storage.azure.com/.default"
+        );
+        assert_eq!(
+            parse_expires_on("1586984735").unwrap(),
+            Utc.timestamp(1586984735, 0)
+        );
+    }
+}
@@ -1,15 +1,21 @@
-use std::sync::Arc;
+use std::{
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use azure_core::{new_http_client, HttpError};
-use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
+use azure_identity::AutoRefreshingTokenCredential;
 use azure_storage::prelude::*;
 use azure_storage_blobs::{blob::responses::PutBlockBlobResponse, prelude::*};
 use bytes::Bytes;
 use futures::FutureExt;
 use http::StatusCode;
 use snafu::Snafu;
+use tokio::time::Instant;
 use vector_core::{buffers::Ackable, internal_event::EventsSent, stream::DriverResponse};
 
+use super::auth::{parse_sas_token, AzureAuthentication};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     sinks::{util::retries::RetryLogic, Healthcheck},
@@ -93,10 +99,10 @@ pub enum HealthcheckError {
 
 pub fn build_healthcheck(
     container_name: String,
-    client: Arc<ContainerClient>,
+    client: SharedContainerClient,
 ) -> crate::Result<Healthcheck> {
     let healthcheck = async move {
-        let request = client.get_properties().execute().await;
+        let request = client.get().get_properties().execute().await;
 
         match request {
             Ok(_) => Ok(()),
@@ -117,14 +123,83 @@ pub fn build_healthcheck(
     Ok(healthcheck.boxed())
 }
 
+/// The container client of a sink, shared by its service and healthcheck, which is replaced
+/// whenever the SAS token it authenticates with is rotated.
+#[derive(Clone)]
+pub struct SharedContainerClient(Arc<RwLock<Arc<ContainerClient>>>);
+
+impl SharedContainerClient {
+    fn new(client: Arc<ContainerClient>) -> Self {
+        Self(Arc::new(RwLock::new(client)))
+    }
+
+    /// Returns the current client.
+    pub fn get(&self) -> Arc<ContainerClient> {
+        Arc::clone(&self.0.read().expect("container client lock poisoned"))
+    }
+
+    /// Reads the SAS token file again every period, replacing the client when the token changed,
+    /// until the client is dropped.
+    fn spawn_sas_token_refresh(
+        &self,
+        storage_account: String,
+        container_name: String,
+        sas_token_file: PathBuf,
+        mut sas_token: String,
+        period: Duration,
+    ) {
+        let client = Arc::downgrade(&self.0);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+            loop {
+                interval.tick().await;
+                let client = match client.upgrade() {
+                    Some(client) => client,
+                    None => break,
+                };
+
+                let refreshed = tokio::fs::read_to_string(&sas_token_file)
+                    .await
+                    .map_err(Into::into)
+                    .and_then(|token| parse_sas_token(&token));
+                match refreshed {
+                    Ok(token) if token == sas_token => {}
+                    Ok(token) => {
+                        match build_sas_client(&storage_account, &token, container_name.clone()) {
+                            Ok(new_client) => {
+                                debug!("Rotated Azure SAS token.");
+                                *client.write().expect("container client lock poisoned") =
+                                    new_client;
+                                sas_token = token;
+                            }
+                            Err(error) => {
+                                error!(message = "Failed to rotate Azure SAS token.", %error)
+                            }
+                        }
+                    }
+                    Err(error) => error!(
+                        message = "Failed to read Azure SAS token.",
+                        %error,
+                        path = ?sas_token_file,
+                    ),
+                }
+            }
+        });
+    }
+}
+
 pub fn build_client(
     connection_string: Option<String>,
     storage_account: Option<String>,
+    auth: Option<&AzureAuthentication>,
     container_name: String,
-) -> crate::Result<Arc<ContainerClient>> {
+) -> crate::Result<SharedContainerClient> {
     let client;
     match (connection_string, storage_account) {
         (Some(connection_string_p), None) => {
+            if auth.is_some() {
+                return Err("`auth` can't be provided with `connection_string`".into());
+            }
             client = StorageAccountClient::new_connection_string(
                 new_http_client(),
                 &connection_string_p,
@@ -133,7 +208,25 @@ pub fn build_client(
             .as_container_client(container_name);
         }
         (None, Some(storage_account_p)) => {
-            let creds = std::sync::Arc::new(DefaultAzureCredential::default());
+            let auth = auth.cloned().unwrap_or_default();
+            if let AzureAuthentication::SasToken {
+                sas_token,
+                sas_token_file,
+                refresh_secs,
+            } = auth
+            {
+                return build_sas_token_client(
+                    storage_account_p,
+                    sas_token,
+                    sas_token_file,
+                    refresh_secs,
+                    container_name,
+                );
+            }
+
+            let creds = auth
+                .token_credential()?
+                .expect("only SAS tokens have no token credential");
             let auto_creds = Box::new(AutoRefreshingTokenCredential::new(creds));
 
             client = StorageAccountClient::new_token_credential(
@@ -154,5 +247,53 @@ pub fn build_client(
             )
         }
     }
-    Ok(client)
+    Ok(SharedContainerClient::new(client))
+}
+
+fn build_sas_token_client(
+    storage_account: String,
+    sas_token: Option<String>,
+    sas_token_file: Option<PathBuf>,
+    refresh_secs: u64,
+    container_name: String,
+) -> crate::Result<SharedContainerClient> {
+    match (sas_token, sas_token_file) {
+        (Some(sas_token), None) => {
+            let client = build_sas_client(
+                &storage_account,
+                &parse_sas_token(&sas_token)?,
+                container_name,
+            )?;
+            Ok(SharedContainerClient::new(client))
+        }
+        (None, Some(sas_token_file)) => {
+            let sas_token = parse_sas_token(&std::fs::read_to_string(&sas_token_file)?)?;
+            let client = SharedContainerClient::new(build_sas_client(
+                &storage_account,
+                &sas_token,
+                container_name.clone(),
+            )?);
+            client.spawn_sas_token_refresh(
+                storage_account,
+                container_name,
+                sas_token_file,
+                sas_token,
+                Duration::from_secs(refresh_secs),
+            );
+            Ok(client)
+        }
+        _ => Err("Exactly one of `sas_token` or `sas_token_file` has to be provided".into()),
+    }
+}
+
+fn build_sas_client(
+    storage_account: &str,
+    sas_token: &str,
+    container_name: String,
+) -> crate::Result<Arc<ContainerClient>> {
+    Ok(
+        StorageAccountClient::new_sas_token(new_http_client(), storage_account, sas_token)?
+            .as_storage_client()
+            .as_container_client(container_name),
+    )
 }
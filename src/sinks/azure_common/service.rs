@@ -1,6 +1,5 @@
 use std::{
     result::Result as StdResult,
-    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -12,17 +11,17 @@ use tracing::Instrument;
 
 use crate::{
     internal_events::azure_blob::{AzureBlobHttpError, AzureBlobResponseError},
-    sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse},
+    sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse, SharedContainerClient},
 };
 use vector_common::internal_event::BytesSent;
 
 #[derive(Clone)]
 pub(crate) struct AzureBlobService {
-    pub(self) client: Arc<ContainerClient>,
+    pub(self) client: SharedContainerClient,
 }
 
 impl AzureBlobService {
-    pub const fn new(client: Arc<ContainerClient>) -> AzureBlobService {
+    pub const fn new(client: SharedContainerClient) -> AzureBlobService {
         AzureBlobService { client }
     }
 }
@@ -37,8 +36,10 @@ impl Service<AzureBlobRequest> for AzureBlobService {
     }
 
     fn call(&mut self, request: AzureBlobRequest) -> Self::Future {
-        let client =
-            Arc::clone(&self.client).as_blob_client(request.metadata.partition_key.as_str());
+        let client = self
+            .client
+            .get()
+            .as_blob_client(request.metadata.partition_key.as_str());
 
         Box::pin(async move {
             let byte_size = request.blob_data.len();
@@ -37,7 +37,7 @@ impl SinkConfig for BlackholeConfig {
     }
 
     fn input(&self) -> Input {
-        Input::all()
+        Input::all().with_columnar()
     }
 
     fn sink_type(&self) -> &'static str {
@@ -80,6 +80,9 @@ impl SinkConfig for ClickhouseConfig {
             auth: self.auth.choose_one(&self.endpoint.auth)?,
             ..self.clone()
         };
+        if let Some(auth) = &config.auth {
+            auth.prepare(&client).await?;
+        }
 
         let sink = BatchedHttpSink::with_logic(
             config.clone(),
@@ -10,7 +10,10 @@ use crate::{
     codecs::Encoder,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
-        console::sink::WriterSink,
+        console::{
+            display::{Display, DisplayConfig},
+            sink::WriterSink,
+        },
         util::encoding::{
             EncodingConfig, EncodingConfigWithFramingAdapter, StandardEncodings,
             StandardEncodingsWithFramingMigrator,
@@ -28,6 +31,22 @@ pub enum Target {
     Stderr,
 }
 
+impl Target {
+    #[cfg(unix)]
+    fn is_terminal(&self) -> bool {
+        match self {
+            Target::Stdout => atty::is(atty::Stream::Stdout),
+            Target::Stderr => atty::is(atty::Stream::Stderr),
+        }
+    }
+
+    #[cfg(windows)]
+    const fn is_terminal(&self) -> bool {
+        // ANSI colors are not supported by cmd.exe
+        false
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct ConsoleSinkConfig {
@@ -38,6 +57,9 @@ pub struct ConsoleSinkConfig {
         EncodingConfig<StandardEncodings>,
         StandardEncodingsWithFramingMigrator,
     >,
+    /// Renders events for people reading them, rather than with the encoding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<DisplayConfig>,
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -51,6 +73,7 @@ impl GenerateConfig for ConsoleSinkConfig {
         toml::Value::try_from(Self {
             target: Target::Stdout,
             encoding: EncodingConfig::from(StandardEncodings::Json).into(),
+            display: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -77,18 +100,25 @@ impl SinkConfig for ConsoleSinkConfig {
         };
         let encoder = Encoder::<Framer>::new(framer, serializer);
 
+        let display = self
+            .display
+            .as_ref()
+            .map(|display| Display::new(display, self.target.is_terminal()));
+
         let sink: VectorSink = match self.target {
             Target::Stdout => VectorSink::from_event_streamsink(WriterSink {
                 acker: cx.acker(),
                 output: io::stdout(),
                 transformer,
                 encoder,
+                display,
             }),
             Target::Stderr => VectorSink::from_event_streamsink(WriterSink {
                 acker: cx.acker(),
                 output: io::stderr(),
                 transformer,
                 encoder,
+                display,
             }),
         };
 
@@ -0,0 +1,327 @@
+use std::fmt::Write;
+
+use colored::{Color, Colorize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+};
+
+/// How events are rendered for people reading the console, in place of the encoding.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DisplayConfig {
+    pub mode: DisplayMode,
+    #[serde(default)]
+    pub color: ColorMode,
+    /// The fields rendered, in order. All of the fields of an event by default.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+    /// The field holding the level of an event, which it's colored by.
+    #[serde(default = "default_level_field")]
+    pub level_field: String,
+}
+
+fn default_level_field() -> String {
+    "level".to_owned()
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    /// The timestamp, level and message of an event on a line, followed by its other fields with
+    /// their keys aligned.
+    Pretty,
+    /// A row per event, with a column per field.
+    Table,
+    /// The `key=value` pairs of the fields of an event on a line.
+    Logfmt,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorMode {
+    /// Color the output if the target is a terminal.
+    #[derivative(Default)]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Renders events as configured by a [`DisplayConfig`], keeping track of the columns of the
+/// table mode across events.
+#[derive(Debug)]
+pub struct Display {
+    mode: DisplayMode,
+    color: bool,
+    fields: Vec<String>,
+    level_field: String,
+    /// The names and widths of the columns of the table, once its header is rendered.
+    columns: Option<Vec<(String, usize)>>,
+}
+
+impl Display {
+    pub fn new(config: &DisplayConfig, terminal: bool) -> Self {
+        Self {
+            mode: config.mode,
+            color: match config.color {
+                ColorMode::Auto => terminal,
+                ColorMode::Always => true,
+                ColorMode::Never => false,
+            },
+            fields: config.fields.clone(),
+            level_field: config.level_field.clone(),
+            columns: None,
+        }
+    }
+
+    /// Renders an event, ending in a newline.
+    pub fn render(&mut self, event: &Event) -> String {
+        let log = match event {
+            Event::Log(log) => log,
+            Event::Trace(trace) => trace.as_ref(),
+            Event::Metric(metric) => return format!("{}\n", metric),
+        };
+        let fields = self.select(log);
+        match self.mode {
+            DisplayMode::Pretty => self.pretty(fields),
+            DisplayMode::Table => self.table(fields),
+            DisplayMode::Logfmt => self.logfmt(fields),
+        }
+    }
+
+    /// The fields rendered for the event, with their values as text.
+    fn select(&self, log: &LogEvent) -> Vec<(String, Option<String>)> {
+        if self.fields.is_empty() {
+            log.all_fields()
+                .into_iter()
+                .flatten()
+                .map(|(key, value)| (key, Some(value.to_string_lossy())))
+                .collect()
+        } else {
+            self.fields
+                .iter()
+                .map(|field| {
+                    (
+                        field.clone(),
+                        log.get(field.as_str()).map(Value::to_string_lossy),
+                    )
+                })
+                .collect()
+        }
+    }
+
+    fn pretty(&self, mut fields: Vec<(String, Option<String>)>) -> String {
+        let mut header = Vec::new();
+        for key in [
+            log_schema().timestamp_key(),
+            self.level_field.as_str(),
+            log_schema().message_key(),
+        ] {
+            if let Some(index) = fields.iter().position(|(field, _)| field == key) {
+                if let (field, Some(value)) = fields.remove(index) {
+                    header.push(self.value(&field, &value, &value));
+                }
+            }
+        }
+
+        let mut output = header.join(" ");
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        let width = fields.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+        for (key, value) in fields {
+            if let Some(value) = value {
+                let key = format!("{:width$}", key, width = width);
+                let _ = writeln!(output, "    {} = {}", self.key(&key), value);
+            }
+        }
+        output
+    }
+
+    fn table(&mut self, fields: Vec<(String, Option<String>)>) -> String {
+        let mut output = String::new();
+        if self.columns.is_none() {
+            let columns = fields
+                .iter()
+                .map(|(key, value)| {
+                    let width = value.as_ref().map_or(0, |value| value.chars().count());
+                    (key.clone(), key.len().max(width))
+                })
+                .collect::<Vec<_>>();
+            let header = columns
+                .iter()
+                .map(|(key, width)| self.key(&format!("{:width$}", key, width = width)))
+                .collect::<Vec<_>>();
+            output.push_str(header.join("  ").trim_end());
+            output.push('\n');
+            self.columns = Some(columns);
+        }
+
+        let columns = self.columns.as_mut().expect("columns are set above");
+        let mut cells = Vec::with_capacity(columns.len());
+        for (key, width) in columns.iter_mut() {
+            let value = fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .and_then(|(_, value)| value.clone())
+                .unwrap_or_default();
+            // Columns grow to fit the widest of their values so far.
+            *width = (*width).max(value.chars().count());
+            let padded = format!("{:width$}", value, width = *width);
+            cells.push((key.clone(), value, padded));
+        }
+        let row = cells
+            .iter()
+            .map(|(key, value, padded)| self.value(key, value, padded))
+            .collect::<Vec<_>>();
+        output.push_str(row.join("  ").trim_end());
+        output.push('\n');
+        output
+    }
+
+    fn logfmt(&self, fields: Vec<(String, Option<String>)>) -> String {
+        let pairs = fields
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let value = value?;
+                let quoted = if value.is_empty() || value.contains(&[' ', '=', '"'][..]) {
+                    format!("{:?}", value)
+                } else {
+                    value.clone()
+                };
+                Some(format!(
+                    "{}={}",
+                    self.key(&key),
+                    self.value(&key, &value, &quoted)
+                ))
+            })
+            .collect::<Vec<_>>();
+        let mut output = pairs.join(" ");
+        output.push('\n');
+        output
+    }
+
+    fn key(&self, key: &str) -> String {
+        if self.color {
+            key.dimmed().to_string()
+        } else {
+            key.to_owned()
+        }
+    }
+
+    /// The rendering of a value, colored by the level if it's the level field.
+    fn value(&self, key: &str, value: &str, rendered: &str) -> String {
+        match level_color(value) {
+            Some(color) if self.color && key == self.level_field => {
+                rendered.color(color).bold().to_string()
+            }
+            _ => rendered.to_owned(),
+        }
+    }
+}
+
+fn level_color(level: &str) -> Option<Color> {
+    let level = level.to_ascii_lowercase();
+    let color = if ["emerg", "alert", "crit", "fatal", "err", "panic"]
+        .iter()
+        .any(|prefix| level.starts_with(prefix))
+    {
+        Color::Red
+    } else if level.starts_with("warn") {
+        Color::Yellow
+    } else if level.starts_with("info") || level.starts_with("notice") {
+        Color::Green
+    } else if level.starts_with("debug") {
+        Color::Blue
+    } else if level.starts_with("trace") {
+        Color::Magenta
+    } else {
+        return None;
+    };
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(config: &str) -> Display {
+        Display::new(&toml::from_str(config).unwrap(), false)
+    }
+
+    fn event(fields: &[(&str, Value)]) -> Event {
+        let mut log = LogEvent::default();
+        for (key, value) in fields {
+            log.insert(*key, value.clone());
+        }
+        log.into()
+    }
+
+    #[test]
+    fn renders_pretty() {
+        let mut display = display(r#"mode = "pretty""#);
+        let output = display.render(&event(&[
+            ("message", "started".into()),
+            ("level", "info".into()),
+            ("host", "web-1".into()),
+            ("status_code", 200.into()),
+        ]));
+        assert_eq!(
+            output,
+            "info started\n    host        = web-1\n    status_code = 200\n"
+        );
+    }
+
+    #[test]
+    fn renders_tables() {
+        let mut display = display(
+            r#"
+            mode = "table"
+            fields = ["level", "message", "user.id"]
+            "#,
+        );
+        let first = display.render(&event(&[
+            ("message", "hi".into()),
+            ("level", "warn".into()),
+            ("user.id", 7.into()),
+        ]));
+        assert_eq!(first, "level  message  user.id\nwarn   hi       7\n");
+
+        let second = display.render(&event(&[("message", "a longer message".into())]));
+        assert_eq!(second, "       a longer message\n");
+    }
+
+    #[test]
+    fn renders_logfmt() {
+        let mut display = display(
+            r#"
+            mode = "logfmt"
+            fields = ["level", "message", "missing"]
+            "#,
+        );
+        let output = display.render(&event(&[
+            ("message", "user logged in".into()),
+            ("level", "debug".into()),
+            ("user", "alice".into()),
+        ]));
+        assert_eq!(output, "level=debug message=\"user logged in\"\n");
+    }
+
+    #[test]
+    fn colors_levels() {
+        let mut config: DisplayConfig = toml::from_str(r#"mode = "logfmt""#).unwrap();
+        config.color = ColorMode::Always;
+        colored::control::set_override(true);
+        let output = Display::new(&config, false).render(&event(&[("level", "ERROR".into())]));
+        colored::control::unset_override();
+        assert_eq!(
+            output,
+            format!("{}={}\n", "level".dimmed(), "ERROR".red().bold())
+        );
+        assert_eq!(level_color("Warning"), Some(Color::Yellow));
+        assert_eq!(level_color("user"), None);
+    }
+}
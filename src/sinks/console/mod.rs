@@ -1,7 +1,9 @@
 mod config;
+mod display;
 mod sink;
 
 pub use config::{ConsoleSinkConfig, Target};
+pub use display::{ColorMode, DisplayConfig, DisplayMode};
 
 use crate::config::SinkDescription;
 
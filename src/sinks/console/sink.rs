@@ -13,7 +13,10 @@ use vector_core::{
 use crate::{
     codecs::Encoder,
     event::{Event, EventStatus, Finalizable},
-    sinks::util::{encoding::Transformer, StreamSink},
+    sinks::{
+        console::display::Display,
+        util::{encoding::Transformer, StreamSink},
+    },
 };
 
 pub struct WriterSink<T> {
@@ -21,6 +24,7 @@ pub struct WriterSink<T> {
     pub output: T,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
+    pub display: Option<Display>,
 }
 
 #[async_trait]
@@ -35,10 +39,13 @@ where
 
             let finalizers = event.take_finalizers();
             let mut bytes = BytesMut::new();
-            self.encoder.encode(event, &mut bytes).map_err(|_| {
-                // Error is handled by `Encoder`.
-                finalizers.update_status(EventStatus::Errored);
-            })?;
+            match self.display.as_mut() {
+                Some(display) => bytes.extend_from_slice(display.render(&event).as_bytes()),
+                None => self.encoder.encode(event, &mut bytes).map_err(|_| {
+                    // Error is handled by `Encoder`.
+                    finalizers.update_status(EventStatus::Errored);
+                })?,
+            }
 
             match self.output.write_all(&bytes).await {
                 Err(error) => {
@@ -123,6 +130,7 @@ mod test {
             output: Vec::new(),
             transformer,
             encoder,
+            display: None,
         };
 
         run_and_assert_sink_compliance(
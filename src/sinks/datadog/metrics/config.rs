@@ -205,7 +205,7 @@ impl DatadogMetricsConfig {
         let request_limits = self.request.unwrap_with(&DEFAULT_REQUEST_LIMITS);
         let endpoint_configuration = self.generate_metrics_endpoint_configuration()?;
         let service = ServiceBuilder::new()
-            .settings(request_limits, DatadogMetricsRetryLogic)
+            .endpoint_settings(request_limits, DatadogMetricsRetryLogic)
             .service(DatadogMetricsService::new(
                 client,
                 self.default_api_key.as_str(),
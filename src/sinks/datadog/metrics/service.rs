@@ -19,7 +19,10 @@ use vector_core::{
 
 use crate::{
     http::{BuildRequestSnafu, CallRequestSnafu, HttpClient, HttpError},
-    sinks::util::retries::{RetryAction, RetryLogic},
+    sinks::util::{
+        adaptive_concurrency::Endpoint,
+        retries::{RetryAction, RetryLogic},
+    },
 };
 
 /// Retry logic specific to the Datadog metrics endpoints.
@@ -68,6 +71,14 @@ pub struct DatadogMetricsRequest {
     pub raw_bytes: usize,
 }
 
+// The series and sketches endpoints are limited separately, as they
+// don't respond alike to the same load.
+impl Endpoint for DatadogMetricsRequest {
+    fn endpoint(&self) -> &str {
+        self.uri.path()
+    }
+}
+
 impl DatadogMetricsRequest {
     /// Converts this request to a `hyper`-compatible request.
     ///
@@ -7,13 +7,9 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
     io::{self, Write},
-    sync::{
-        atomic::{AtomicU32, Ordering},
-        Arc,
-    },
+    sync::atomic::{AtomicU32, Ordering},
 };
 
-use azure_storage_blobs::prelude::ContainerClient;
 use bytes::{BufMut, Bytes, BytesMut};
 use chrono::{SecondsFormat, Utc};
 use goauth::scopes::Scope;
@@ -45,7 +41,10 @@ use crate::{
     sinks::{
         azure_common::{
             self,
-            config::{AzureBlobMetadata, AzureBlobRequest, AzureBlobRetryLogic},
+            auth::AzureAuthentication,
+            config::{
+                AzureBlobMetadata, AzureBlobRequest, AzureBlobRetryLogic, SharedContainerClient,
+            },
             service::AzureBlobService,
             sink::AzureBlobSink,
         },
@@ -139,7 +138,9 @@ pub struct S3Options {
 #[derive(Deserialize, Serialize, Default, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct AzureBlobConfig {
-    pub connection_string: String,
+    pub connection_string: Option<String>,
+    pub storage_account: Option<String>,
+    pub auth: Option<AzureAuthentication>,
 }
 
 #[derive(Deserialize, Serialize, Default, Debug, Clone)]
@@ -205,12 +206,13 @@ impl DatadogArchivesSinkConfig {
                     .as_ref()
                     .expect("azire blob config wasn't provided");
                 let client = azure_common::config::build_client(
-                    Some(azure_config.connection_string.clone()),
-                    None,
+                    azure_config.connection_string.clone(),
+                    azure_config.storage_account.clone(),
+                    azure_config.auth.as_ref(),
                     self.bucket.clone(),
                 )?;
                 let svc = self
-                    .build_azure_sink(Arc::<ContainerClient>::clone(&client), cx)
+                    .build_azure_sink(client.clone(), cx)
                     .map_err(|error| error.to_string())?;
                 let healthcheck =
                     azure_common::config::build_healthcheck(self.bucket.clone(), client)?;
@@ -344,7 +346,7 @@ impl DatadogArchivesSinkConfig {
 
     fn build_azure_sink(
         &self,
-        client: Arc<ContainerClient>,
+        client: SharedContainerClient,
         cx: SinkContext,
     ) -> crate::Result<VectorSink> {
         let request_limits = self.request.unwrap_with(&Default::default());
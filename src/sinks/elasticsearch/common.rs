@@ -1,9 +1,6 @@
 use std::collections::HashMap;
-use std::time::SystemTime;
 
-use aws_sigv4::http_request::{SignableRequest, SigningSettings};
-use aws_sigv4::SigningParams;
-use aws_types::credentials::{ProvideCredentials, SharedCredentialsProvider};
+use aws_types::credentials::SharedCredentialsProvider;
 use aws_types::region::Region;
 use bytes::Bytes;
 use http::{StatusCode, Uri};
@@ -11,6 +8,7 @@ use snafu::ResultExt;
 
 use super::{InvalidHostSnafu, Request};
 use crate::{
+    aws,
     http::{Auth, HttpClient, MaybeAuth},
     sinks::{
         elasticsearch::{
@@ -64,6 +62,7 @@ impl ElasticsearchCommon {
                 user: user.clone(),
                 password: password.clone(),
             }),
+            Some(ElasticsearchAuth::OAuth2(config)) => Some(Auth::OAuth2(config.clone())),
             _ => None,
         };
         let uri = config.endpoint.parse::<UriSerde>()?;
@@ -71,7 +70,7 @@ impl ElasticsearchCommon {
         let base_url = uri.uri.to_string().trim_end_matches('/').to_owned();
 
         let aws_auth = match &config.auth {
-            Some(ElasticsearchAuth::Basic { .. }) | None => None,
+            Some(ElasticsearchAuth::Basic { .. } | ElasticsearchAuth::OAuth2(_)) | None => None,
             Some(ElasticsearchAuth::Aws(aws)) => {
                 let region = config
                     .aws
@@ -166,22 +165,6 @@ pub async fn sign_request(
     credentials_provider: &SharedCredentialsProvider,
     region: &Option<Region>,
 ) -> crate::Result<()> {
-    let signable_request = SignableRequest::from(&*request);
-    let credentials = credentials_provider.provide_credentials().await?;
-    let mut signing_params_builder = SigningParams::builder()
-        .access_key(credentials.access_key_id())
-        .secret_key(credentials.secret_access_key())
-        .region(region.as_ref().map(|r| r.as_ref()).unwrap_or(""))
-        .service_name("es")
-        .time(SystemTime::now())
-        .settings(SigningSettings::default());
-
-    signing_params_builder.set_security_token(credentials.session_token());
-
-    let (signing_instructions, _signature) =
-        aws_sigv4::http_request::sign(signable_request, &signing_params_builder.build()?)?
-            .into_parts();
-    signing_instructions.apply_to_request(request);
-
-    Ok(())
+    let body = request.body().clone();
+    aws::sigv4::sign_request(request, &body, credentials_provider, region.as_ref(), "es").await
 }
@@ -305,6 +305,9 @@ impl SinkConfig for ElasticsearchConfig {
         let common = ElasticsearchCommon::parse_config(self).await?;
 
         let http_client = HttpClient::new(common.tls_settings.clone(), cx.proxy())?;
+        if let Some(auth) = &common.http_auth {
+            auth.prepare(&http_client).await?;
+        }
         let batch_settings = self.batch.into_batcher_settings()?;
 
         // This is a bit ugly, but removes a String allocation on every event
@@ -315,6 +318,7 @@ impl SinkConfig for ElasticsearchConfig {
         let request_builder = ElasticsearchRequestBuilder {
             compression: self.compression,
             encoder: encoding,
+            encoded_size: Default::default(),
         };
 
         let request_limits = self
@@ -27,14 +27,20 @@ use crate::{
     config::SinkDescription,
     event::{EventRef, LogEvent},
     internal_events::TemplateRenderingError,
+    oauth2::OAuth2Config,
     template::{Template, TemplateParseError},
 };
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
 pub enum ElasticsearchAuth {
-    Basic { user: String, password: String },
+    Basic {
+        user: String,
+        password: String,
+    },
     Aws(AwsAuthentication),
+    #[serde(rename = "oauth2")]
+    OAuth2(OAuth2Config),
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
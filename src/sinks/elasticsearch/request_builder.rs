@@ -1,5 +1,5 @@
 use bytes::Bytes;
-use vector_core::ByteSizeOf;
+use vector_core::{stream::batcher::limiter::EncodedSizeRatio, ByteSizeOf};
 
 use crate::{
     event::{EventFinalizers, Finalizable},
@@ -18,11 +18,15 @@ use crate::{
 pub struct ElasticsearchRequestBuilder {
     pub compression: Compression,
     pub encoder: EncodingConfigFixed<ElasticsearchEncoder>,
+    /// The estimate of the size of the bulk requests events end up in, which the sink batches
+    /// events by.
+    pub encoded_size: EncodedSizeRatio,
 }
 
 pub struct Metadata {
     finalizers: EventFinalizers,
     batch_size: usize,
+    batch_byte_size: usize,
     events_byte_size: usize,
 }
 
@@ -43,6 +47,7 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
     }
 
     fn split_input(&self, mut events: Vec<ProcessedEvent>) -> (Self::Metadata, Self::Events) {
+        let batch_byte_size = events.iter().map(ByteSizeOf::size_of).sum();
         let events_byte_size = events
             .iter()
             .map(|x| x.log.size_of())
@@ -52,6 +57,7 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
         let metadata = Metadata {
             finalizers: events.take_finalizers(),
             batch_size: events.len(),
+            batch_byte_size,
             events_byte_size,
         };
         (metadata, events)
@@ -62,6 +68,8 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
         metadata: Self::Metadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
+        self.encoded_size
+            .observe(metadata.batch_byte_size, payload.payload.len());
         ElasticsearchRequest {
             payload: payload.into_payload(),
             finalizers: metadata.finalizers,
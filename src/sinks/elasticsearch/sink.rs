@@ -5,6 +5,7 @@ use futures::{future, stream::BoxStream, StreamExt};
 use tower::Service;
 use vector_core::{
     buffers::Acker,
+    partition::Partitioner,
     stream::{BatcherSettings, DriverResponse},
     ByteSizeOf,
 };
@@ -38,6 +39,23 @@ impl ByteSizeOf for BatchedEvents {
     }
 }
 
+/// Batches the events of each index and bulk action separately, so that a busy index doesn't
+/// flush the batches of the others early.
+#[derive(Default)]
+struct ElasticsearchPartitioner;
+
+impl Partitioner for ElasticsearchPartitioner {
+    type Item = ProcessedEvent;
+    type Key = PartitionKey;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        PartitionKey {
+            index: item.index.clone(),
+            bulk_action: item.bulk_action,
+        }
+    }
+}
+
 pub struct ElasticsearchSink<S> {
     pub batch_settings: BatcherSettings,
     pub request_builder: ElasticsearchRequestBuilder,
@@ -61,6 +79,7 @@ where
 
         let mode = self.mode;
         let id_key_field = self.id_key_field;
+        let encoded_size = self.request_builder.encoded_size.clone();
 
         let sink = input
             .scan(self.metric_to_log, |metric_to_log, event| {
@@ -72,7 +91,9 @@ where
             })
             .filter_map(|x| async move { x })
             .filter_map(move |log| future::ready(process_log(log, &mode, &id_key_field)))
-            .batched(self.batch_settings.into_byte_size_config())
+            .batched_partitioned(ElasticsearchPartitioner, self.batch_settings)
+            .with_item_size(encoded_size)
+            .map(|(_, events)| events)
             .request_builder(request_builder_concurrency_limit, self.request_builder)
             .filter_map(|request| async move {
                 match request {
@@ -1,6 +1,6 @@
 use std::time::{Duration, Instant};
 
-use async_compression::tokio::write::GzipEncoder;
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
 use codecs::{
@@ -39,9 +39,11 @@ use crate::{
     template::Template,
 };
 mod bytes_path;
+mod rotation;
 use std::convert::TryFrom;
 
 use bytes_path::BytesPath;
+use rotation::{PendingFile, Rotation};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncodingMigrator;
@@ -72,6 +74,22 @@ pub struct FileSinkConfig {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     pub compression: Compression,
+    /// Finalizes a file once it has grown to this many bytes, and writes the events that follow
+    /// to a new one.
+    pub max_size_bytes: Option<u64>,
+    /// Finalizes a file once it has been open this many seconds, and writes the events that
+    /// follow to a new one.
+    pub max_age_secs: Option<u64>,
+    /// The path a file is renamed to once it's finalized, rendered with the first event written
+    /// to it. Defaults to `path`.
+    pub final_path: Option<Template>,
+    /// Writes files to a hidden temporary path, renaming them to their final path once they are
+    /// finalized, so that their readers never see a partial file.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub atomic_rename: bool,
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -91,6 +109,10 @@ impl GenerateConfig for FileSinkConfig {
             idle_timeout_secs: None,
             encoding: EncodingConfig::from(Encoding::Text).into(),
             compression: Default::default(),
+            max_size_bytes: None,
+            max_age_secs: None,
+            final_path: None,
+            atomic_rename: false,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -108,9 +130,20 @@ pub enum Encoding {
 #[serde(rename_all = "snake_case")]
 pub enum Compression {
     Gzip,
+    Zstd,
     None,
 }
 
+impl Compression {
+    const fn extension(self) -> Option<&'static str> {
+        match self {
+            Compression::Gzip => Some("gz"),
+            Compression::Zstd => Some("zst"),
+            Compression::None => None,
+        }
+    }
+}
+
 impl Default for Compression {
     fn default() -> Self {
         Compression::None
@@ -120,6 +153,7 @@ impl Default for Compression {
 enum OutFile {
     Regular(File),
     Gzip(GzipEncoder<File>),
+    Zstd(ZstdEncoder<File>),
 }
 
 impl OutFile {
@@ -127,6 +161,7 @@ impl OutFile {
         match compression {
             Compression::None => OutFile::Regular(file),
             Compression::Gzip => OutFile::Gzip(GzipEncoder::new(file)),
+            Compression::Zstd => OutFile::Zstd(ZstdEncoder::new(file)),
         }
     }
 
@@ -134,6 +169,7 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.sync_all().await,
             OutFile::Gzip(gzip) => gzip.get_mut().sync_all().await,
+            OutFile::Zstd(zstd) => zstd.get_mut().sync_all().await,
         }
     }
 
@@ -141,6 +177,7 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.shutdown().await,
             OutFile::Gzip(gzip) => gzip.shutdown().await,
+            OutFile::Zstd(zstd) => zstd.shutdown().await,
         }
     }
 
@@ -148,6 +185,7 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.write_all(src).await,
             OutFile::Gzip(gzip) => gzip.write_all(src).await,
+            OutFile::Zstd(zstd) => zstd.write_all(src).await,
         }
     }
 
@@ -159,6 +197,12 @@ impl OutFile {
     }
 }
 
+struct OpenFile {
+    out: OutFile,
+    /// Set when the file is written to a temporary path and finalized into place.
+    pending: Option<PendingFile>,
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "file")]
 impl SinkConfig for FileSinkConfig {
@@ -193,8 +237,13 @@ pub struct FileSink {
     transformer: Transformer,
     encoder: Encoder<Framer>,
     idle_timeout: Duration,
-    files: ExpiringHashMap<Bytes, OutFile>,
+    files: ExpiringHashMap<Bytes, OpenFile>,
     compression: Compression,
+    final_path: Option<Template>,
+    rotation: Rotation,
+    /// Whether files are written to a temporary path and finalized, rather than appended to in
+    /// place.
+    finalize: bool,
 }
 
 impl FileSink {
@@ -212,6 +261,15 @@ impl FileSink {
             idle_timeout: Duration::from_secs(config.idle_timeout_secs.unwrap_or(30)),
             files: ExpiringHashMap::default(),
             compression: config.compression,
+            final_path: config.final_path.clone(),
+            rotation: Rotation {
+                max_size: config.max_size_bytes,
+                max_age: config.max_age_secs.map(Duration::from_secs),
+            },
+            finalize: config.max_size_bytes.is_some()
+                || config.max_age_secs.is_some()
+                || config.final_path.is_some()
+                || config.atomic_rename,
         }
     }
 
@@ -240,6 +298,7 @@ impl FileSink {
     }
 
     async fn run(&mut self, mut input: BoxStream<'_, Event>) -> crate::Result<()> {
+        let mut rotation_interval = tokio::time::interval(Duration::from_secs(1));
         loop {
             tokio::select! {
                 event = input.next() => {
@@ -254,16 +313,10 @@ impl FileSink {
 
                             // Close all the open files.
                             debug!(message = "Closing all the open files.");
-                            for (path, file) in self.files.iter_mut() {
-                                if let Err(error) = file.close().await {
-                                    emit!(FileIoError {
-                                        error,
-                                        code: "failed_closing_file",
-                                        message: "Failed to close file.",
-                                        path: Some(path),
-                                    });
-                                } else{
-                                    trace!(message = "Successfully closed file.", path = ?path);
+                            let paths = self.files.iter_mut().map(|(path, _)| path.clone()).collect::<Vec<_>>();
+                            for path in paths {
+                                if let Some((file, _)) = self.files.remove(&path) {
+                                    self.close_file(&path, file).await;
                                 }
                             }
 
@@ -280,25 +333,127 @@ impl FileSink {
                         // We do not poll map when it's empty, so we should
                         // never reach this branch.
                         None => unreachable!(),
-                        Some((mut expired_file, path)) => {
+                        Some((expired_file, path)) => {
                             // We got an expired file. All we really want is to
                             // flush and close it.
-                            if let Err(error) = expired_file.close().await {
-                                error!(message = "Failed to close file.", path = ?path, %error);
-                            }
-                            drop(expired_file); // ignore close error
+                            self.close_file(path.get_ref(), expired_file).await;
                             emit!(FileOpen {
                                 count: self.files.len()
                             });
                         }
                     }
                 }
+                _ = rotation_interval.tick(), if self.rotation.max_age.is_some() && !self.files.is_empty() => {
+                    let rotation = self.rotation;
+                    let paths = self
+                        .files
+                        .iter_mut()
+                        .filter(|(_, file)| file.pending.as_ref().map_or(false, |pending| pending.should_rotate(rotation)))
+                        .map(|(path, _)| path.clone())
+                        .collect::<Vec<_>>();
+                    for path in paths {
+                        self.rotate(&path).await;
+                    }
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Flushes and closes a file, and finalizes it if it was written to a temporary path.
+    async fn close_file(&mut self, path: &Bytes, mut file: OpenFile) {
+        if let Err(error) = file.out.close().await {
+            emit!(FileIoError {
+                error,
+                code: "failed_closing_file",
+                message: "Failed to close file.",
+                path: Some(path),
+            });
+            return;
+        }
+        trace!(message = "Successfully closed file.", path = ?path);
+
+        if let Some(pending) = file.pending {
+            match pending.finalize(self.compression).await {
+                Ok(final_path) => {
+                    debug!(message = "Finalized file.", path = ?path, final_path = ?final_path)
+                }
+                Err(error) => emit!(FileIoError {
+                    error,
+                    code: "failed_finalizing_file",
+                    message: "Failed to finalize file.",
+                    path: Some(path),
+                }),
+            }
+        }
+    }
+
+    /// Finalizes the open file of the path, so that the next event is written to a new one.
+    async fn rotate(&mut self, path: &Bytes) {
+        if let Some((file, _)) = self.files.remove(path) {
+            self.close_file(path, file).await;
+            emit!(FileOpen {
+                count: self.files.len()
+            });
+        }
+    }
+
+    /// Opens the file for the path, or when files are finalized, the temporary file for its final
+    /// path rendered from the event.
+    async fn open(&mut self, path: &Bytes, event: &Event) -> Option<OpenFile> {
+        if !self.finalize {
+            let file = match open_file(BytesPath::new(path.clone())).await {
+                Ok(file) => file,
+                Err(error) => {
+                    emit!(FileIoError {
+                        code: "failed_opening_file",
+                        message: "Unable to open the file.",
+                        error,
+                        path: Some(path),
+                    });
+                    return None;
+                }
+            };
+            return Some(OpenFile {
+                out: OutFile::new(file, self.compression),
+                pending: None,
+            });
+        }
+
+        let final_path = match &self.final_path {
+            Some(template) => match template.render(event) {
+                Ok(final_path) => final_path,
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("final_path"),
+                        drop_event: true,
+                    });
+                    return None;
+                }
+            },
+            None => path.clone(),
+        };
+        let pending = PendingFile::new(BytesPath::new(final_path).as_ref().to_path_buf());
+        match open_file(pending.temp_path()).await {
+            // Files are compressed as they are finalized, rather than as they are written.
+            Ok(file) => Some(OpenFile {
+                out: OutFile::new(file, Compression::None),
+                pending: Some(pending),
+            }),
+            Err(error) => {
+                emit!(FileIoError {
+                    code: "failed_opening_file",
+                    message: "Unable to open the file.",
+                    error,
+                    path: Some(path),
+                });
+                None
+            }
+        }
+    }
+
     async fn process_event(&mut self, mut event: Event) {
         let path = match self.partition_event(&event) {
             Some(path) => path,
@@ -312,6 +467,16 @@ impl FileSink {
             }
         };
 
+        let rotation = self.rotation;
+        let expired = self.files.get(&path).map_or(false, |file| {
+            file.pending
+                .as_ref()
+                .map_or(false, |pending| pending.should_rotate(rotation))
+        });
+        if expired {
+            self.rotate(&path).await;
+        }
+
         let next_deadline = self.deadline_at();
         trace!(message = "Computed next deadline.", next_deadline = ?next_deadline, path = ?path);
 
@@ -320,26 +485,18 @@ impl FileSink {
             file
         } else {
             trace!(message = "Opening new file.", ?path);
-            let file = match open_file(BytesPath::new(path.clone())).await {
-                Ok(file) => file,
-                Err(error) => {
+            let file = match self.open(&path, &event).await {
+                Some(file) => file,
+                None => {
                     // We couldn't open the file for this event.
-                    // Maybe other events will work though! Just log
-                    // the error and skip this event.
-                    emit!(FileIoError {
-                        code: "failed_opening_file",
-                        message: "Unable to open the file.",
-                        error,
-                        path: Some(&path),
-                    });
+                    // Maybe other events will work though! The error
+                    // is already logged, so just skip this event.
                     event.metadata().update_status(EventStatus::Errored);
                     return;
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
-
-            self.files.insert_at(path.clone(), outfile, next_deadline);
+            self.files.insert_at(path.clone(), file, next_deadline);
             emit!(FileOpen {
                 count: self.files.len()
             });
@@ -349,7 +506,8 @@ impl FileSink {
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
+        match write_event_to_file(&mut file.out, event, &self.transformer, &mut self.encoder).await
+        {
             Ok(byte_size) => {
                 finalizers.update_status(EventStatus::Delivered);
                 emit!(EventsSent {
@@ -361,6 +519,14 @@ impl FileSink {
                     byte_size,
                     file: String::from_utf8_lossy(&path),
                 });
+
+                let full = file.pending.as_mut().map_or(false, |pending| {
+                    pending.wrote(byte_size);
+                    pending.should_rotate(rotation)
+                });
+                if full {
+                    self.rotate(&path).await;
+                }
             }
             Err(error) => {
                 finalizers.update_status(EventStatus::Errored);
@@ -449,6 +615,10 @@ mod tests {
             idle_timeout_secs: None,
             encoding: EncodingConfig::from(Encoding::Text).into(),
             compression: Compression::None,
+            max_size_bytes: None,
+            max_age_secs: None,
+            final_path: None,
+            atomic_rename: false,
             acknowledgements: Default::default(),
         };
 
@@ -480,6 +650,10 @@ mod tests {
             idle_timeout_secs: None,
             encoding: EncodingConfig::from(Encoding::Text).into(),
             compression: Compression::Gzip,
+            max_size_bytes: None,
+            max_age_secs: None,
+            final_path: None,
+            atomic_rename: false,
             acknowledgements: Default::default(),
         };
 
@@ -516,6 +690,10 @@ mod tests {
             idle_timeout_secs: None,
             encoding: EncodingConfig::from(Encoding::Text).into(),
             compression: Compression::None,
+            max_size_bytes: None,
+            max_age_secs: None,
+            final_path: None,
+            atomic_rename: false,
             acknowledgements: Default::default(),
         };
 
@@ -590,6 +768,134 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn rotates_by_size() {
+        trace_init();
+
+        let directory = temp_dir();
+        let config = FileSinkConfig {
+            path: directory.join("out.log").try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: EncodingConfig::from(Encoding::Text).into(),
+            compression: Compression::None,
+            max_size_bytes: Some(100),
+            max_age_secs: None,
+            final_path: None,
+            atomic_rename: false,
+            acknowledgements: Default::default(),
+        };
+
+        let sink = FileSink::new(&config, Acker::passthrough());
+        // Each line is 32 bytes with its newline, so that a file fills up after 4 lines.
+        let (input, _events) = random_lines_with_stream(31, 10, None);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        run_and_assert_sink_compliance(
+            VectorSink::from_event_streamsink(sink),
+            events,
+            &FILE_SINK_TAGS,
+        )
+        .await;
+
+        let output = vec![
+            lines_from_file(directory.join("out.log")),
+            lines_from_file(directory.join("out.log.1")),
+            lines_from_file(directory.join("out.log.2")),
+        ];
+        assert_eq!(output[0], input[..4]);
+        assert_eq!(output[1], input[4..8]);
+        assert_eq!(output[2], input[8..]);
+        assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn renames_atomically() {
+        trace_init();
+
+        let directory = temp_dir();
+        let mut final_path = directory.to_string_lossy().to_string();
+        final_path.push_str("/{{level}}.log");
+
+        let config = FileSinkConfig {
+            path: directory.join("{{level}}").try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: EncodingConfig::from(Encoding::Text).into(),
+            compression: Compression::None,
+            max_size_bytes: None,
+            max_age_secs: None,
+            final_path: Some(final_path.try_into().unwrap()),
+            atomic_rename: true,
+            acknowledgements: Default::default(),
+        };
+
+        let sink = FileSink::new(&config, Acker::passthrough());
+        let (input, _events) = random_lines_with_stream(10, 4, None);
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(0);
+        let sink_handle = tokio::spawn(async move {
+            run_and_assert_sink_compliance(
+                VectorSink::from_event_streamsink(sink),
+                Box::pin(rx),
+                &FILE_SINK_TAGS,
+            )
+            .await
+        });
+
+        for line in input.clone() {
+            let mut event = Event::from(line);
+            event.as_mut_log().insert("level", "info");
+            tx.send(event).await.unwrap();
+        }
+
+        // Until the file is finalized, it's only written to its temporary path.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let names = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names.len(), 1);
+        assert!(names[0].starts_with(".info.log."));
+
+        drop(tx);
+        sink_handle.await.unwrap();
+
+        assert_eq!(lines_from_file(directory.join("info.log")), input);
+        assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn compresses_finalized_files() {
+        trace_init();
+
+        let directory = temp_dir();
+        let config = FileSinkConfig {
+            path: directory.join("out.log").try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: EncodingConfig::from(Encoding::Text).into(),
+            compression: Compression::Gzip,
+            max_size_bytes: Some(100),
+            max_age_secs: None,
+            final_path: None,
+            atomic_rename: true,
+            acknowledgements: Default::default(),
+        };
+
+        let sink = FileSink::new(&config, Acker::passthrough());
+        let (input, _events) = random_lines_with_stream(31, 6, None);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        run_and_assert_sink_compliance(
+            VectorSink::from_event_streamsink(sink),
+            events,
+            &FILE_SINK_TAGS,
+        )
+        .await;
+
+        let mut output = lines_from_gzip_file(directory.join("out.log.gz"));
+        output.extend(lines_from_gzip_file(directory.join("out.log.1.gz")));
+        assert_eq!(output, input);
+    }
+
     #[tokio::test]
     async fn reopening() {
         trace_init();
@@ -601,6 +907,10 @@ mod tests {
             idle_timeout_secs: Some(1),
             encoding: EncodingConfig::from(Encoding::Text).into(),
             compression: Compression::None,
+            max_size_bytes: None,
+            max_age_secs: None,
+            final_path: None,
+            atomic_rename: false,
             acknowledgements: Default::default(),
         };
 
@@ -0,0 +1,189 @@
+//! Files written to a hidden temporary file, and finalized by compressing them if need be and
+//! renaming them into place, so that whatever picks up the files never reads a partial one.
+
+use std::{
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use tokio::{
+    fs::{self, File},
+    io::AsyncReadExt,
+};
+
+use super::{Compression, OutFile};
+
+/// When files are rotated, as they are finalized and written anew.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct Rotation {
+    pub(super) max_size: Option<u64>,
+    pub(super) max_age: Option<Duration>,
+}
+
+/// A file being written to a temporary path, in place of its final one.
+#[derive(Debug)]
+pub(super) struct PendingFile {
+    temp_path: PathBuf,
+    final_path: PathBuf,
+    opened_at: Instant,
+    written: u64,
+}
+
+impl PendingFile {
+    /// Creates the temporary file for a file that's finalized to the given path, hidden in the
+    /// same directory so that the rename is atomic.
+    pub(super) fn new(final_path: PathBuf) -> Self {
+        let mut name = OsString::from(".");
+        name.push(final_path.file_name().unwrap_or_default());
+        name.push(format!(".{:08x}.tmp", rand::random::<u32>()));
+        Self {
+            temp_path: final_path.with_file_name(name),
+            final_path,
+            opened_at: Instant::now(),
+            written: 0,
+        }
+    }
+
+    pub(super) fn temp_path(&self) -> &Path {
+        &self.temp_path
+    }
+
+    pub(super) fn wrote(&mut self, bytes: usize) {
+        self.written += bytes as u64;
+    }
+
+    pub(super) fn should_rotate(&self, rotation: Rotation) -> bool {
+        rotation
+            .max_size
+            .map_or(false, |max_size| self.written >= max_size)
+            || rotation
+                .max_age
+                .map_or(false, |max_age| self.opened_at.elapsed() >= max_age)
+    }
+
+    /// Compresses the file, then renames it to the first of its final path, or of that path
+    /// suffixed with `.1`, `.2` and so on, that isn't taken. Returns where the file ended up.
+    pub(super) async fn finalize(self, compression: Compression) -> io::Result<PathBuf> {
+        let extension = compression.extension();
+        let finished = match extension {
+            Some(extension) => {
+                let mut compressed = self.temp_path.clone().into_os_string();
+                compressed.push(".");
+                compressed.push(extension);
+                let compressed = PathBuf::from(compressed);
+                compress(&self.temp_path, &compressed, compression).await?;
+                fs::remove_file(&self.temp_path).await?;
+                compressed
+            }
+            None => self.temp_path,
+        };
+
+        let final_path = free_path(&self.final_path, extension).await;
+        fs::rename(&finished, &final_path).await?;
+        Ok(final_path)
+    }
+}
+
+async fn compress(from: &Path, to: &Path, compression: Compression) -> io::Result<()> {
+    let mut input = File::open(from).await?;
+    let mut output = OutFile::new(File::create(to).await?, compression);
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = input.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        output.write_all(&buffer[..read]).await?;
+    }
+    output.close().await
+}
+
+/// The final path of a file, with the extension of its compression, suffixed with a number
+/// before that extension if a file is already there.
+async fn free_path(final_path: &Path, extension: Option<&str>) -> PathBuf {
+    let mut base = final_path.as_os_str().to_owned();
+    let suffix = match extension {
+        Some(extension) if final_path.extension() == Some(extension.as_ref()) => {
+            base = final_path.with_extension("").into_os_string();
+            format!(".{}", extension)
+        }
+        Some(extension) => format!(".{}", extension),
+        None => String::new(),
+    };
+
+    let mut path = base.clone();
+    path.push(&suffix);
+    let mut n = 1;
+    while fs::metadata(&path).await.is_ok() {
+        path = base.clone();
+        path.push(format!(".{}{}", n, suffix));
+        n += 1;
+    }
+    path.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[tokio::test]
+    async fn finalizes_to_free_paths() {
+        let directory = temp_dir();
+        fs::create_dir_all(&directory).await.unwrap();
+
+        let mut finals = Vec::new();
+        for content in ["first", "second"] {
+            let file = PendingFile::new(directory.join("out.log"));
+            assert!(file
+                .temp_path()
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with(".out.log."));
+            fs::write(file.temp_path(), content).await.unwrap();
+            finals.push(file.finalize(Compression::None).await.unwrap());
+        }
+        assert_eq!(
+            finals,
+            vec![directory.join("out.log"), directory.join("out.log.1")]
+        );
+        assert_eq!(fs::read_to_string(&finals[1]).await.unwrap(), "second");
+
+        let file = PendingFile::new(directory.join("out.log.gz"));
+        fs::write(file.temp_path(), "third").await.unwrap();
+        let compressed = file.finalize(Compression::Gzip).await.unwrap();
+        assert_eq!(compressed, directory.join("out.log.gz"));
+
+        let mut entries = fs::read_dir(&directory).await.unwrap();
+        let mut names = Vec::new();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            names.push(entry.file_name().to_string_lossy().into_owned());
+        }
+        names.sort();
+        assert_eq!(names, vec!["out.log", "out.log.1", "out.log.gz"]);
+    }
+
+    #[test]
+    fn rotates_by_size_and_age() {
+        let mut file = PendingFile::new(PathBuf::from("/tmp/out.log"));
+        let rotation = Rotation {
+            max_size: Some(10),
+            max_age: None,
+        };
+        file.wrote(6);
+        assert!(!file.should_rotate(rotation));
+        file.wrote(4);
+        assert!(file.should_rotate(rotation));
+
+        let file = PendingFile::new(PathBuf::from("/tmp/out.log"));
+        let rotation = Rotation {
+            max_size: None,
+            max_age: Some(Duration::ZERO),
+        };
+        assert!(file.should_rotate(rotation));
+        assert!(!file.should_rotate(Rotation::default()));
+    }
+}
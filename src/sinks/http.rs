@@ -1,4 +1,4 @@
-use std::io::Write;
+use std::{io::Write, path::PathBuf};
 
 use bytes::{BufMut, Bytes, BytesMut};
 use codecs::encoding::{
@@ -19,6 +19,7 @@ use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
 
 use crate::{
+    aws::sigv4::{AwsSigV4Config, AwsSigV4Signer},
     codecs::Encoder,
     config::{
         AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
@@ -79,6 +80,9 @@ pub struct HttpSinkConfig {
     pub uri: UriSerde,
     pub method: Option<HttpMethod>,
     pub auth: Option<Auth>,
+    /// Sign requests with AWS Signature Version 4, for AWS services without a sink of their own.
+    #[serde(default)]
+    pub aws: Option<AwsSigV4Config>,
     // Deprecated, moved to request.
     pub headers: Option<IndexMap<String, String>>,
     #[serde(default)]
@@ -90,6 +94,9 @@ pub struct HttpSinkConfig {
     #[serde(default)]
     pub request: RequestConfig,
     pub tls: Option<TlsConfig>,
+    /// Send requests over this Unix domain socket, or Windows named pipe, rather than connecting
+    /// to the host of the URI.
+    pub socket_path: Option<PathBuf>,
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -138,7 +145,10 @@ impl GenerateConfig for HttpSinkConfig {
 impl HttpSinkConfig {
     fn build_http_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
         let tls = TlsSettings::from_options(&self.tls)?;
-        Ok(HttpClient::new(tls, cx.proxy())?)
+        Ok(match &self.socket_path {
+            Some(socket_path) => HttpClient::new_with_socket(tls, socket_path)?,
+            None => HttpClient::new(tls, cx.proxy())?,
+        })
     }
 }
 
@@ -146,6 +156,7 @@ struct HttpSink {
     pub uri: UriSerde,
     pub method: Option<HttpMethod>,
     pub auth: Option<Auth>,
+    pub signer: Option<AwsSigV4Signer>,
     pub compression: Compression,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
@@ -169,6 +180,7 @@ fn default_sink(encoding: Encoding) -> HttpSink {
         uri: Default::default(),
         method: Default::default(),
         auth: Default::default(),
+        signer: Default::default(),
         compression: Default::default(),
         transformer: Default::default(),
         encoder,
@@ -185,11 +197,25 @@ impl SinkConfig for HttpSinkConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let client = self.build_http_client(&cx)?;
+        if self.auth.is_some() && self.aws.is_some() {
+            return Err("`auth` and `aws` can't both be set, as both authorize requests.".into());
+        }
+        if let Some(auth) = &self.auth {
+            auth.prepare(&client).await?;
+        }
+        let signer = match &self.aws {
+            Some(aws) => Some(aws.signer().await?),
+            None => None,
+        };
 
         let healthcheck = match cx.healthcheck.uri.clone() {
-            Some(healthcheck_uri) => {
-                healthcheck(healthcheck_uri, self.auth.clone(), client.clone()).boxed()
-            }
+            Some(healthcheck_uri) => healthcheck(
+                healthcheck_uri,
+                self.auth.clone(),
+                signer.clone(),
+                client.clone(),
+            )
+            .boxed(),
             None => future::ok(()).boxed(),
         };
 
@@ -208,6 +234,7 @@ impl SinkConfig for HttpSinkConfig {
             uri: self.uri.with_default_parts(),
             method: self.method.clone(),
             auth: self.auth.choose_one(&self.uri.auth)?,
+            signer,
             compression: self.compression,
             transformer: self.encoding.transformer(),
             encoder,
@@ -358,12 +385,21 @@ impl util::http::HttpSink for HttpSink {
         if let Some(auth) = &self.auth {
             auth.apply(&mut request);
         }
+        if let Some(signer) = &self.signer {
+            let body = request.body().clone();
+            signer.sign(&mut request, &body).await?;
+        }
 
         Ok(request)
     }
 }
 
-async fn healthcheck(uri: UriSerde, auth: Option<Auth>, client: HttpClient) -> crate::Result<()> {
+async fn healthcheck(
+    uri: UriSerde,
+    auth: Option<Auth>,
+    signer: Option<AwsSigV4Signer>,
+    client: HttpClient,
+) -> crate::Result<()> {
     let auth = auth.choose_one(&uri.auth)?;
     let uri = uri.with_default_parts();
     let mut request = Request::head(&uri.uri).body(Body::empty()).unwrap();
@@ -371,6 +407,9 @@ async fn healthcheck(uri: UriSerde, auth: Option<Auth>, client: HttpClient) -> c
     if let Some(auth) = auth {
         auth.apply(&mut request);
     }
+    if let Some(signer) = signer {
+        signer.sign(&mut request, &[]).await?;
+    }
 
     let response = client.send(request).await?;
 
@@ -556,6 +595,27 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn http_signs_requests_for_aws() {
+        run_sink(
+            r#"
+        [aws]
+        service = "execute-api"
+        region = "us-west-2"
+        auth.access_key_id = "AKIDEXAMPLE"
+        auth.secret_access_key = "secret"
+    "#,
+            |parts| {
+                assert_eq!(Method::POST, parts.method);
+                let authorization = parts.headers["authorization"].to_str().unwrap();
+                assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+                assert!(authorization.contains("/us-west-2/execute-api/aws4_request"));
+                assert!(parts.headers.contains_key("x-amz-date"));
+            },
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn http_passes_custom_headers() {
         run_sink(
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
 use futures::FutureExt;
 use rdkafka::ClientConfig;
@@ -30,7 +30,8 @@ pub(crate) struct KafkaSinkConfig {
     pub key_field: Option<String>,
     pub(crate) encoding:
         EncodingConfigAdapter<EncodingConfig<StandardEncodings>, StandardEncodingsMigrator>,
-    /// These batching options will **not** override librdkafka_options values.
+    /// These batching options will **not** override librdkafka_options values. `timeout_secs`
+    /// sets the linger (`queue.buffering.max.ms`), and `max_bytes` sets `batch.size`.
     #[serde(default)]
     pub batch: BatchConfig<NoDefaultsBatchSettings>,
     #[serde(default)]
@@ -51,6 +52,41 @@ pub(crate) struct KafkaSinkConfig {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     pub acknowledgements: AcknowledgementsConfig,
+    /// When set, events are produced in transactions with this transactional ID, committing the
+    /// offsets of `kafka` sources in exactly-once mode along with them.
+    pub transactional_id: Option<String>,
+    /// Produce events idempotently, so that retries neither duplicate nor reorder them. Always
+    /// enabled along with transactions.
+    #[serde(default)]
+    pub idempotent: bool,
+    /// The partitioner used to choose the partition of events with a key.
+    pub partitioner: Option<KafkaPartitioner>,
+    /// How long events without a key keep going to the same partition, in milliseconds.
+    pub sticky_partitioning_linger_ms: Option<u64>,
+    /// The level of the compression codec, whose range depends on the codec.
+    pub compression_level: Option<u32>,
+}
+
+/// The partitioners of librdkafka.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum KafkaPartitioner {
+    /// The CRC32 hash of the key, or a random partition for events without a key.
+    ConsistentRandom,
+    /// The CRC32 hash of the key, or the same partition for events without a key.
+    Consistent,
+    /// The murmur2 hash of the key, as the Java client's partitioner, or a random partition for
+    /// events without a key.
+    Murmur2Random,
+    /// The murmur2 hash of the key, or the same partition for events without a key.
+    Murmur2,
+    /// The FNV-1a hash of the key, as the partitioner of Sarama, or a random partition for events
+    /// without a key.
+    Fnv1aRandom,
+    /// The FNV-1a hash of the key, or the same partition for events without a key.
+    Fnv1a,
+    /// A random partition.
+    Random,
 }
 
 const fn default_socket_timeout_ms() -> u64 {
@@ -86,6 +122,54 @@ impl KafkaSinkConfig {
                     .set("compression.codec", &to_string(self.compression))
                     .set("message.timeout.ms", &self.message_timeout_ms.to_string());
 
+                if let Some(transactional_id) = &self.transactional_id {
+                    // librdkafka requires messages to time out no later than the transaction
+                    // they're part of.
+                    client_config.set("transactional.id", transactional_id).set(
+                        "transaction.timeout.ms",
+                        &self.message_timeout_ms.to_string(),
+                    );
+                }
+
+                // Transactional producers are always idempotent.
+                if self.idempotent || self.transactional_id.is_some() {
+                    self.check_not_overridden("idempotent", "enable.idempotence", &true)?;
+                    client_config.set("enable.idempotence", "true");
+                }
+                if let Some(partitioner) = self.partitioner {
+                    let value = to_string(partitioner);
+                    self.check_not_overridden("partitioner", "partitioner", &value)?;
+                    client_config.set("partitioner", &value);
+                }
+                if let Some(value) = self.sticky_partitioning_linger_ms {
+                    let key = "sticky.partitioning.linger.ms";
+                    self.check_not_overridden("sticky_partitioning_linger_ms", key, &value)?;
+                    client_config.set(key, &value.to_string());
+                }
+                if let Some(value) = self.compression_level {
+                    let max = match self.compression {
+                        KafkaCompression::None => {
+                            return Err(
+                                "`compression_level` requires `compression` to be set.".into()
+                            )
+                        }
+                        KafkaCompression::Gzip => 9,
+                        KafkaCompression::Snappy => 0,
+                        KafkaCompression::Lz4 | KafkaCompression::Zstd => 12,
+                    };
+                    if value > max {
+                        return Err(format!(
+                            "`compression_level` must be at most {} for `{}` compression, got {}.",
+                            max,
+                            to_string(self.compression),
+                            value
+                        )
+                        .into());
+                    }
+                    self.check_not_overridden("compression_level", "compression.level", &value)?;
+                    client_config.set("compression.level", &value.to_string());
+                }
+
                 if let Some(value) = self.batch.timeout_secs {
                     // Delay in milliseconds to wait for messages in the producer queue to accumulate before
                     // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -159,6 +243,25 @@ impl KafkaSinkConfig {
 
         Ok(client_config)
     }
+
+    /// Errors if the librdkafka option set by the given sink option is also set in
+    /// `librdkafka_options`.
+    fn check_not_overridden(
+        &self,
+        option: &str,
+        key: &str,
+        value: &dyn Display,
+    ) -> crate::Result<()> {
+        match self.librdkafka_options.get(key) {
+            Some(val) => Err(format!(
+                "Setting `{}` sets `librdkafka_options.{}={}`. The config already sets this as \
+                 `librdkafka_options.{}={}`. Please delete one.",
+                option, key, value, key, val
+            )
+            .into()),
+            None => Ok(()),
+        }
+    }
 }
 
 impl GenerateConfig for KafkaSinkConfig {
@@ -176,6 +279,11 @@ impl GenerateConfig for KafkaSinkConfig {
             librdkafka_options: Default::default(),
             headers_key: None,
             acknowledgements: Default::default(),
+            transactional_id: None,
+            idempotent: false,
+            partitioner: None,
+            sticky_partitioning_linger_ms: None,
+            compression_level: None,
         })
         .unwrap()
     }
@@ -201,6 +309,10 @@ impl SinkConfig for KafkaSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn is_transactional(&self) -> bool {
+        self.transactional_id.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -211,4 +323,60 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    fn producer_config(config: &str) -> crate::Result<ClientConfig> {
+        let config: KafkaSinkConfig = toml::from_str(config).unwrap();
+        config.to_rdkafka(KafkaRole::Producer)
+    }
+
+    #[test]
+    fn sets_producer_options() {
+        let client_config = producer_config(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            compression = "zstd"
+            compression_level = 6
+            partitioner = "murmur2_random"
+            sticky_partitioning_linger_ms = 5
+            transactional_id = "vector"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(client_config.get("partitioner"), Some("murmur2_random"));
+        assert_eq!(
+            client_config.get("sticky.partitioning.linger.ms"),
+            Some("5")
+        );
+        assert_eq!(client_config.get("compression.level"), Some("6"));
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+    }
+
+    #[test]
+    fn rejects_invalid_producer_options() {
+        // Out of range for the codec.
+        assert!(producer_config(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            compression = "gzip"
+            compression_level = 12
+            "#,
+        )
+        .is_err());
+
+        // Set twice.
+        assert!(producer_config(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            partitioner = "fnv1a_random"
+            librdkafka_options.partitioner = "random"
+            "#,
+        )
+        .is_err());
+    }
 }
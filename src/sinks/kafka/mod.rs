@@ -5,6 +5,7 @@ pub(crate) mod request_builder;
 pub(crate) mod service;
 pub(crate) mod sink;
 pub(crate) mod tests;
+mod transaction;
 
 use self::config::KafkaSinkConfig;
 
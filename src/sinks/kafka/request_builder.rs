@@ -32,6 +32,7 @@ impl KafkaRequestBuilder {
             timestamp_millis: get_timestamp_millis(&event, self.log_schema),
             headers: get_headers(&event, &self.headers_key),
             topic,
+            kafka_offset: event.metadata().kafka_offset().clone(),
         };
         let event_byte_size = event.size_of();
         self.transformer.transform(&mut event);
@@ -1,4 +1,7 @@
-use std::task::{Context, Poll};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
@@ -16,7 +19,7 @@ use vector_core::{
 };
 
 use crate::{
-    event::{EventFinalizers, EventStatus, Finalizable},
+    event::{EventFinalizers, EventStatus, Finalizable, KafkaOffset},
     kafka::KafkaStatisticsContext,
 };
 
@@ -32,6 +35,29 @@ pub struct KafkaRequestMetadata {
     pub timestamp_millis: Option<i64>,
     pub headers: Option<OwnedHeaders>,
     pub topic: String,
+    pub kafka_offset: Option<Arc<KafkaOffset>>,
+}
+
+impl KafkaRequest {
+    /// Builds the record to produce for this request.
+    pub(super) fn to_record(&self) -> FutureRecord<'_, [u8], [u8]> {
+        let mut record = FutureRecord::to(&self.metadata.topic).payload(self.body.as_ref());
+        if let Some(key) = &self.metadata.key {
+            record = record.key(&key[..]);
+        }
+        if let Some(timestamp) = self.metadata.timestamp_millis {
+            record = record.timestamp(timestamp);
+        }
+        if let Some(headers) = &self.metadata.headers {
+            record = record.headers(headers.clone());
+        }
+        record
+    }
+
+    /// Gets the number of bytes sent to Kafka for this request.
+    pub(super) fn byte_size(&self) -> usize {
+        self.body.len() + self.metadata.key.as_ref().map(|x| x.len()).unwrap_or(0)
+    }
 }
 
 pub struct KafkaResponse {
@@ -90,24 +116,13 @@ impl Service<KafkaRequest> for KafkaService {
         let kafka_producer = self.kafka_producer.clone();
 
         Box::pin(async move {
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
-            }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
-            }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
-            }
+            let record = request.to_record();
 
             //rdkafka will internally retry forever if the queue is full
             let result = match kafka_producer.send(record, Timeout::Never).await {
                 Ok((_partition, _offset)) => {
                     emit!(BytesSent {
-                        byte_size: request.body.len()
-                            + request.metadata.key.map(|x| x.len()).unwrap_or(0),
+                        byte_size: request.byte_size(),
                         protocol: "kafka"
                     });
                     Ok(KafkaResponse {
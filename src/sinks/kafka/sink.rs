@@ -11,17 +11,22 @@ use rdkafka::{
 use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use tower::limit::ConcurrencyLimit;
-use vector_core::{buffers::Acker, config::log_schema};
+use vector_core::{
+    buffers::Acker,
+    config::log_schema,
+    internal_event::{BytesSent, EventsSent},
+};
 
 use super::config::{KafkaRole, KafkaSinkConfig};
 use crate::{
     codecs::Encoder,
-    event::Event,
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::KafkaTransactionError,
     kafka::KafkaStatisticsContext,
     sinks::{
         kafka::{
             config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder,
-            service::KafkaService,
+            service::KafkaService, transaction::KafkaTransactions,
         },
         util::{builder::SinkBuilderExt, encoding::Transformer, StreamSink},
     },
@@ -44,6 +49,7 @@ pub struct KafkaSink {
     topic: Template,
     key_field: Option<String>,
     headers_key: Option<String>,
+    transactions: Option<KafkaTransactions>,
 }
 
 pub(crate) fn create_producer(
@@ -59,6 +65,14 @@ impl KafkaSink {
     pub(crate) fn new(config: KafkaSinkConfig, acker: Acker) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
         let producer = create_producer(producer_config)?;
+        let transactions = match config.transactional_id {
+            Some(_) => Some(KafkaTransactions::new(
+                producer.clone(),
+                config.to_rdkafka(KafkaRole::Consumer)?,
+                Duration::from_millis(config.message_timeout_ms),
+            )),
+            None => None,
+        };
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.encoding();
         let encoder = Encoder::<()>::new(serializer);
@@ -71,10 +85,15 @@ impl KafkaSink {
             service: KafkaService::new(producer),
             topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
             key_field: config.key_field,
+            transactions,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        if self.transactions.is_some() {
+            return self.run_transactional(input).await;
+        }
+
         // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
         let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
         let mut request_builder = KafkaRequestBuilder {
@@ -90,6 +109,64 @@ impl KafkaSink {
             .into_driver(service, self.acker);
         sink.run().await
     }
+
+    /// Produces events in transactions, each made up of the events that are ready when the
+    /// previous one completes.
+    async fn run_transactional(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut transactions = self
+            .transactions
+            .take()
+            .expect("transactions must be enabled");
+        if let Err(error) = transactions.init().await {
+            error!(message = "Failed to initialize Kafka transactions.", %error);
+            return Err(());
+        }
+
+        let mut request_builder = KafkaRequestBuilder {
+            key_field: self.key_field,
+            headers_key: self.headers_key,
+            topic_template: self.topic,
+            transformer: self.transformer,
+            encoder: self.encoder,
+            log_schema: log_schema(),
+        };
+        let mut input = input.ready_chunks(QUEUED_MIN_MESSAGES as usize);
+        while let Some(events) = input.next().await {
+            let count = events.len();
+            let mut requests: Vec<_> = events
+                .into_iter()
+                .filter_map(|event| request_builder.build_request(event))
+                .collect();
+            let mut finalizers = EventFinalizers::default();
+            for request in &mut requests {
+                finalizers.merge(request.take_finalizers());
+            }
+
+            match transactions.send(&requests).await {
+                Ok(()) => {
+                    emit!(BytesSent {
+                        byte_size: requests.iter().map(|request| request.byte_size()).sum(),
+                        protocol: "kafka",
+                    });
+                    emit!(EventsSent {
+                        count: requests.len(),
+                        byte_size: requests.iter().map(|request| request.event_byte_size).sum(),
+                        output: None,
+                    });
+                    finalizers.update_status(EventStatus::Delivered);
+                }
+                Err(error) => {
+                    emit!(KafkaTransactionError {
+                        error,
+                        count: requests.len(),
+                    });
+                    finalizers.update_status(EventStatus::Errored);
+                }
+            }
+            self.acker.ack(count);
+        }
+        Ok(())
+    }
 }
 
 pub(crate) async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
@@ -6,6 +6,7 @@ mod integration_test {
     use std::{
         collections::{BTreeMap, HashMap},
         future::ready,
+        sync::Arc,
         thread,
         time::Duration,
     };
@@ -19,7 +20,7 @@ mod integration_test {
     };
     use vector_core::{
         buffers::Acker,
-        event::{BatchNotifier, BatchStatus},
+        event::{BatchNotifier, BatchStatus, KafkaOffset},
     };
 
     use crate::{
@@ -71,10 +72,86 @@ mod integration_test {
             librdkafka_options: HashMap::new(),
             headers_key: None,
             acknowledgements: Default::default(),
+            transactional_id: None,
+            idempotent: false,
+            partitioner: None,
+            sticky_partitioning_linger_ms: None,
+            compression_level: None,
         };
         self::sink::healthcheck(config).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn kafka_transactions_commit_source_offsets() {
+        crate::test_util::trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let group_id = format!("group-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: kafka_address(9091),
+            topic: topic.clone(),
+            key_field: None,
+            encoding: EncodingConfig::from(StandardEncodings::Text).into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: 60000,
+            message_timeout_ms: 60000,
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            acknowledgements: Default::default(),
+            transactional_id: Some(format!("vector-{}", random_string(10))),
+            idempotent: false,
+            partitioner: None,
+            sticky_partitioning_linger_ms: None,
+            compression_level: None,
+        };
+        let (acker, ack_counter) = Acker::basic();
+        let sink = KafkaSink::new(config, acker).unwrap();
+        let sink = VectorSink::from_event_streamsink(sink);
+
+        // Pretend that the events were read from the sink's topic by a source in exactly-once
+        // mode, one event per message.
+        let num_events = 10;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (_input, events) = random_lines_with_stream(100, num_events, Some(batch));
+        let source_group_id: Arc<str> = Arc::from(group_id.as_str());
+        let source_topic: Arc<str> = Arc::from(topic.as_str());
+        let events = events.enumerate().map(move |(offset, mut events)| {
+            events.for_each_log(|log| {
+                log.metadata_mut()
+                    .set_kafka_offset(Some(Arc::new(KafkaOffset {
+                        group_id: Arc::clone(&source_group_id),
+                        topic: Arc::clone(&source_topic),
+                        partition: 0,
+                        offset: offset as i64,
+                    })));
+            });
+            events
+        });
+        sink.run(events).await.unwrap();
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        assert_eq!(
+            ack_counter.load(std::sync::atomic::Ordering::Relaxed),
+            num_events
+        );
+
+        // The offset following the last event was committed for the source consumer group.
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", kafka_address(9091).as_str());
+        client_config.set("group.id", &group_id);
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0);
+        let committed = consumer
+            .committed_offsets(tpl, Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(
+            committed.find_partition(&topic, 0).unwrap().offset(),
+            Offset::Offset(num_events as i64)
+        );
+    }
+
     #[tokio::test]
     async fn kafka_happy_path_plaintext() {
         crate::test_util::trace_init();
@@ -126,6 +203,11 @@ mod integration_test {
             librdkafka_options,
             headers_key: None,
             acknowledgements: Default::default(),
+            transactional_id: None,
+            idempotent: false,
+            partitioner: None,
+            sticky_partitioning_linger_ms: None,
+            compression_level: None,
         };
         let (acker, _ack_counter) = Acker::basic();
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
@@ -269,6 +351,11 @@ mod integration_test {
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
             acknowledgements: Default::default(),
+            transactional_id: None,
+            idempotent: false,
+            partitioner: None,
+            sticky_partitioning_linger_ms: None,
+            compression_level: None,
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
         println!("Topic name generated in test: {:?}", topic);
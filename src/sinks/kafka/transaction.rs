@@ -0,0 +1,156 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use futures::future::join_all;
+use rdkafka::{
+    consumer::{BaseConsumer, Consumer, ConsumerGroupMetadata},
+    error::KafkaError,
+    producer::{FutureProducer, Producer},
+    util::Timeout,
+    ClientConfig, Offset, TopicPartitionList,
+};
+
+use crate::{kafka::KafkaStatisticsContext, sinks::kafka::service::KafkaRequest};
+
+/// Produces batches of requests in Kafka transactions.
+///
+/// The offsets of the source consumer groups that the events were read from are committed in the
+/// same transaction, so that each event is either produced and marked as consumed, or neither.
+pub(super) struct KafkaTransactions {
+    producer: FutureProducer<KafkaStatisticsContext>,
+    consumer_config: ClientConfig,
+    group_metadata: HashMap<String, Arc<ConsumerGroupMetadata>>,
+    timeout: Duration,
+}
+
+impl KafkaTransactions {
+    pub(super) const fn new(
+        producer: FutureProducer<KafkaStatisticsContext>,
+        consumer_config: ClientConfig,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            producer,
+            consumer_config,
+            group_metadata: HashMap::new(),
+            timeout,
+        }
+    }
+
+    /// Registers the transactional ID of the producer with the cluster, fencing off any previous
+    /// producer with the same ID and aborting its pending transactions.
+    pub(super) async fn init(&self) -> Result<(), KafkaError> {
+        let producer = self.producer.clone();
+        let timeout = self.timeout;
+        blocking(move || producer.init_transactions(timeout)).await
+    }
+
+    /// Produces the given requests in a single transaction, committing the source offsets carried
+    /// by them along with it.
+    ///
+    /// If anything fails, the transaction is aborted, and none of the requests are visible to
+    /// consumers reading committed messages.
+    pub(super) async fn send(&mut self, requests: &[KafkaRequest]) -> Result<(), KafkaError> {
+        let offsets = self.offsets(requests)?;
+
+        self.producer.begin_transaction()?;
+        let result = self.send_in_transaction(requests, offsets).await;
+        if result.is_err() {
+            let producer = self.producer.clone();
+            let timeout = self.timeout;
+            if let Err(error) = blocking(move || producer.abort_transaction(timeout)).await {
+                warn!(message = "Failed to abort transaction.", %error);
+            }
+        }
+        result
+    }
+
+    async fn send_in_transaction(
+        &self,
+        requests: &[KafkaRequest],
+        offsets: Vec<(TopicPartitionList, Arc<ConsumerGroupMetadata>)>,
+    ) -> Result<(), KafkaError> {
+        // Unlike the non-transactional sink, the records of a transaction have to be in flight
+        // together, as it can only be committed once all of them are.
+        let results = join_all(
+            requests
+                .iter()
+                .map(|request| self.producer.send(request.to_record(), Timeout::Never)),
+        )
+        .await;
+        if let Some((error, _)) = results.into_iter().find_map(Result::err) {
+            return Err(error);
+        }
+
+        let producer = self.producer.clone();
+        let timeout = self.timeout;
+        blocking(move || {
+            for (offsets, group_metadata) in &offsets {
+                producer.send_offsets_to_transaction(offsets, group_metadata, timeout)?;
+            }
+            producer.commit_transaction(timeout)
+        })
+        .await
+    }
+
+    /// Gets the offsets to commit for each source consumer group, which are the offsets following
+    /// the last message each group read from each partition.
+    fn offsets(
+        &mut self,
+        requests: &[KafkaRequest],
+    ) -> Result<Vec<(TopicPartitionList, Arc<ConsumerGroupMetadata>)>, KafkaError> {
+        let mut positions = HashMap::<&str, HashMap<(&str, i32), i64>>::new();
+        for offset in requests
+            .iter()
+            .filter_map(|request| request.metadata.kafka_offset.as_ref())
+        {
+            let position = positions
+                .entry(offset.group_id.as_ref())
+                .or_default()
+                .entry((offset.topic.as_ref(), offset.partition))
+                .or_insert(offset.offset);
+            *position = (*position).max(offset.offset);
+        }
+
+        positions
+            .into_iter()
+            .map(|(group_id, positions)| {
+                let mut offsets = TopicPartitionList::new();
+                for ((topic, partition), offset) in positions {
+                    offsets.add_partition_offset(topic, partition, Offset::Offset(offset + 1))?;
+                }
+                Ok((offsets, self.group_metadata(group_id)?))
+            })
+            .collect()
+    }
+
+    /// Gets the metadata identifying a consumer group to the transaction coordinator.
+    ///
+    /// It can only be obtained from a consumer, but doesn't depend on it afterwards, so a
+    /// consumer that never joins the group is created once for each group.
+    fn group_metadata(&mut self, group_id: &str) -> Result<Arc<ConsumerGroupMetadata>, KafkaError> {
+        if let Some(group_metadata) = self.group_metadata.get(group_id) {
+            return Ok(Arc::clone(group_metadata));
+        }
+
+        let consumer: BaseConsumer = self
+            .consumer_config
+            .clone()
+            .set("group.id", group_id)
+            .create()?;
+        let group_metadata = consumer.group_metadata().map(Arc::new).ok_or_else(|| {
+            KafkaError::ClientCreation(format!("no metadata for consumer group {:?}", group_id))
+        })?;
+        self.group_metadata
+            .insert(group_id.to_string(), Arc::clone(&group_metadata));
+        Ok(group_metadata)
+    }
+}
+
+/// Runs one of the blocking transaction operations of the producer off of the async runtime.
+async fn blocking(
+    f: impl FnOnce() -> Result<(), KafkaError> + Send + 'static,
+) -> Result<(), KafkaError> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .expect("Kafka transaction operation panicked")
+}
@@ -134,6 +134,9 @@ impl SinkConfig for LokiConfig {
             auth: self.auth.choose_one(&self.endpoint.auth)?,
             ..self.clone()
         };
+        if let Some(auth) = &config.auth {
+            auth.prepare(&client).await?;
+        }
 
         let sink = LokiSink::new(config.clone(), client.clone(), cx)?;
 
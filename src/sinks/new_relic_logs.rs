@@ -162,6 +162,7 @@ impl NewRelicLogsConfig {
             uri: uri.into(),
             method: Some(HttpMethod::Post),
             auth: None,
+            aws: None,
             headers: None,
             compression: self.compression,
             encoding: EncodingConfig::<Encoding>::from(self.encoding.clone())
@@ -170,6 +171,7 @@ impl NewRelicLogsConfig {
             batch: batch_settings.into(),
             request,
             tls: None,
+            socket_path: None,
             acknowledgements: self.acknowledgements,
         })
     }
@@ -2,7 +2,10 @@ use std::{collections::BTreeMap, fmt::Write as _};
 
 use chrono::Utc;
 use indexmap::map::IndexMap;
-use prometheus_parser::{proto, METRIC_NAME_LABEL};
+use prometheus_parser::{
+    proto::{self, client},
+    METRIC_NAME_LABEL,
+};
 use vector_core::event::metric::{samples_to_buckets, MetricSketch, Quantile};
 
 use crate::{
@@ -418,6 +421,133 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+/// Collects metrics into the families of the protobuf exposition format, sorted by name.
+pub(super) struct MetricFamilies {
+    families: BTreeMap<String, (client::MetricFamily, IndexMap<Labels, client::Metric>)>,
+}
+
+impl MetricCollector for MetricFamilies {
+    type Output = Vec<client::MetricFamily>;
+
+    fn new() -> Self {
+        Self {
+            families: BTreeMap::new(),
+        }
+    }
+
+    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
+        if !self.families.contains_key(fullname) {
+            let r#type = match prometheus_metric_type(value) {
+                proto::MetricType::Counter => client::MetricType::Counter,
+                proto::MetricType::Gauge => client::MetricType::Gauge,
+                proto::MetricType::Histogram => client::MetricType::Histogram,
+                proto::MetricType::Summary => client::MetricType::Summary,
+                _ => client::MetricType::Untyped,
+            };
+            let family = client::MetricFamily {
+                name: Some(fullname.into()),
+                help: Some(name.into()),
+                r#type: Some(r#type as i32),
+                metric: Vec::new(),
+            };
+            self.families
+                .insert(fullname.into(), (family, IndexMap::new()));
+        }
+    }
+
+    fn emit_value(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        suffix: &str,
+        value: f64,
+        tags: Option<&BTreeMap<String, String>>,
+        extra: Option<(&str, String)>,
+    ) {
+        let (family, series) = self
+            .families
+            .get_mut(name)
+            .expect("metric metadata not encoded");
+
+        // The samples of a histogram or a summary are gathered in a single metric per set of
+        // labels, apart from the bucket or quantile ones.
+        let labels = tags
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| proto::Label {
+                name: name.clone(),
+                value: value.clone(),
+            })
+            .collect::<Labels>();
+        let metric = series.entry(labels).or_insert_with(|| client::Metric {
+            label: tags
+                .into_iter()
+                .flatten()
+                .map(|(name, value)| client::LabelPair {
+                    name: Some(name.clone()),
+                    value: Some(value.clone()),
+                })
+                .collect(),
+            timestamp_ms: timestamp_millis,
+            ..Default::default()
+        });
+
+        match family.r#type() {
+            client::MetricType::Counter => {
+                metric.counter = Some(client::Counter { value: Some(value) });
+            }
+            client::MetricType::Gauge => {
+                metric.gauge = Some(client::Gauge { value: Some(value) });
+            }
+            client::MetricType::Histogram => {
+                let histogram = metric.histogram.get_or_insert_with(Default::default);
+                match (suffix, extra) {
+                    ("_bucket", Some(("le", bound))) => {
+                        // The `+Inf` bucket is implied by the count.
+                        if let Ok(upper_bound) = bound.parse::<f64>() {
+                            histogram.bucket.push(client::Bucket {
+                                cumulative_count: Some(value as u64),
+                                upper_bound: Some(upper_bound),
+                            });
+                        }
+                    }
+                    ("_sum", _) => histogram.sample_sum = Some(value),
+                    ("_count", _) => histogram.sample_count = Some(value as u64),
+                    _ => {}
+                }
+            }
+            client::MetricType::Summary => {
+                let summary = metric.summary.get_or_insert_with(Default::default);
+                match (suffix, extra) {
+                    ("", Some(("quantile", quantile))) => {
+                        summary.quantile.push(client::Quantile {
+                            quantile: quantile.parse().ok(),
+                            value: Some(value),
+                        });
+                    }
+                    ("_sum", _) => summary.sample_sum = Some(value),
+                    ("_count", _) => summary.sample_count = Some(value as u64),
+                    // Summaries have no room for the minimum, maximum or average.
+                    _ => {}
+                }
+            }
+            client::MetricType::Untyped => {
+                metric.untyped = Some(client::Untyped { value: Some(value) });
+            }
+        }
+    }
+
+    fn finish(self) -> Vec<client::MetricFamily> {
+        self.families
+            .into_iter()
+            .map(|(_, (mut family, series))| {
+                family.metric = series.into_iter().map(|(_, metric)| metric).collect();
+                family
+            })
+            .collect()
+    }
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -520,6 +650,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encodes_counter_families() {
+        assert_eq!(
+            encode_counter::<MetricFamilies>(),
+            vec![client::MetricFamily {
+                name: Some("vector_hits".into()),
+                help: Some("hits".into()),
+                r#type: Some(client::MetricType::Counter as i32),
+                metric: vec![client::Metric {
+                    label: vec![client::LabelPair {
+                        name: Some("code".into()),
+                        value: Some("200".into()),
+                    }],
+                    counter: Some(client::Counter { value: Some(10.0) }),
+                    timestamp_ms: Some(1612325106789),
+                    ..Default::default()
+                }],
+            }]
+        );
+    }
+
     #[test]
     fn encodes_counter_request() {
         assert_eq!(
@@ -698,6 +849,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encodes_histogram_families() {
+        let families = encode_histogram::<MetricFamilies>(false);
+        assert_eq!(families.len(), 1);
+        assert_eq!(families[0].name(), "vector_requests");
+        assert_eq!(families[0].r#type(), client::MetricType::Histogram);
+
+        let metric = &families[0].metric[0];
+        assert_eq!(metric.timestamp_ms, Some(1612325106789));
+        let histogram = metric.histogram.as_ref().unwrap();
+        assert_eq!(histogram.sample_count, Some(6));
+        assert_eq!(histogram.sample_sum, Some(11.5));
+        assert_eq!(
+            histogram
+                .bucket
+                .iter()
+                .map(|bucket| (bucket.upper_bound(), bucket.cumulative_count()))
+                .collect::<Vec<_>>(),
+            vec![(1.0, 1), (2.1, 3), (3.0, 6)]
+        );
+    }
+
     #[test]
     fn encodes_histogram_request() {
         assert_eq!(
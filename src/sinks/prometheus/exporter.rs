@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     convert::Infallible,
     hash::Hash,
     mem::{discriminant, Discriminant},
@@ -15,6 +16,9 @@ use hyper::{
     Body, Method, Request, Response, Server, StatusCode,
 };
 use indexmap::IndexMap;
+use prometheus_parser::{proto::client, METRIC_NAME_LABEL};
+use prost::Message;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use snafu::Snafu;
@@ -27,7 +31,10 @@ use vector_core::{
     ByteSizeOf,
 };
 
-use super::collector::{MetricCollector, StringCollector};
+use super::{
+    collector::{MetricCollector, MetricFamilies, StringCollector},
+    relabel::{anchored, Relabel, RelabelConfig, RelabelError, Selector},
+};
 use crate::{
     config::{
         AcknowledgementsConfig, GenerateConfig, Input, Resource, SinkConfig, SinkContext,
@@ -41,6 +48,7 @@ use crate::{
     sinks::{
         util::{
             buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet},
+            encode_namespace,
             statistic::validate_quantiles,
             StreamSink,
         },
@@ -75,6 +83,30 @@ pub struct PrometheusExporterConfig {
     #[serde(default = "default_flush_period_secs")]
     #[serde_as(as = "serde_with::DurationSeconds<u64>")]
     pub flush_period_secs: Duration,
+    /// How long the metrics whose names match a pattern are exposed without being updated, in
+    /// place of `flush_period_secs`. The first matching rule applies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expiration: Vec<ExpirationConfig>,
+    /// Patterns the names of the exposed metrics must match one of. All metrics are exposed if
+    /// empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_metrics: Vec<String>,
+    /// Rules rewriting the labels of the metrics, or dropping them, as they are scraped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relabel: Vec<RelabelConfig>,
+    /// Whether to serve the protobuf exposition format to the scrapers asking for it.
+    #[serde(default)]
+    pub protobuf: bool,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpirationConfig {
+    /// A pattern matching the names of the metrics, including their namespace.
+    pub name: String,
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub ttl_secs: Duration,
 }
 
 impl Default for PrometheusExporterConfig {
@@ -87,6 +119,10 @@ impl Default for PrometheusExporterConfig {
             quantiles: super::default_summary_quantiles(),
             distributions_as_summaries: default_distributions_as_summaries(),
             flush_period_secs: default_flush_period_secs(),
+            expiration: Vec::new(),
+            allowed_metrics: Vec::new(),
+            relabel: Vec::new(),
+            protobuf: false,
         }
     }
 }
@@ -131,7 +167,7 @@ impl SinkConfig for PrometheusExporterConfig {
 
         validate_quantiles(&self.quantiles)?;
 
-        let sink = PrometheusExporter::new(self.clone(), cx.acker());
+        let sink = PrometheusExporter::new(self.clone(), cx.acker())?;
         let healthcheck = future::ok(()).boxed();
 
         Ok((VectorSink::from_event_streamsink(sink), healthcheck))
@@ -189,10 +225,114 @@ impl SinkConfig for PrometheusCompatConfig {
 struct PrometheusExporter {
     server_shutdown_trigger: Option<Trigger>,
     config: PrometheusExporterConfig,
+    exposition: Arc<Exposition>,
+    expiration: Vec<(Regex, Duration)>,
     metrics: Arc<RwLock<IndexMap<MetricRef, (Metric, MetricMetadata)>>>,
     acker: Acker,
 }
 
+/// How the metrics are exposed to scrapers.
+#[derive(Debug)]
+struct Exposition {
+    default_namespace: Option<String>,
+    buckets: Vec<f64>,
+    quantiles: Vec<f64>,
+    allowed_metrics: Vec<Regex>,
+    relabel: Vec<Relabel>,
+    protobuf: bool,
+}
+
+impl Exposition {
+    fn new(config: &PrometheusExporterConfig) -> Result<Self, RelabelError> {
+        Ok(Self {
+            default_namespace: config.default_namespace.clone(),
+            buckets: config.buckets.clone(),
+            quantiles: config.quantiles.clone(),
+            allowed_metrics: config
+                .allowed_metrics
+                .iter()
+                .map(|pattern| anchored(pattern))
+                .collect::<Result<_, _>>()?,
+            relabel: config
+                .relabel
+                .iter()
+                .map(Relabel::new)
+                .collect::<Result<_, _>>()?,
+            protobuf: config.protobuf,
+        })
+    }
+
+    /// The metric as it's exposed, or none if it's filtered out. Relabeled metrics carry their
+    /// full name, without a namespace.
+    fn expose<'a>(&self, metric: &'a Metric, selectors: &[Selector]) -> Option<Cow<'a, Metric>> {
+        let name = encode_namespace(
+            metric.namespace().or(self.default_namespace.as_deref()),
+            '_',
+            metric.name(),
+        );
+        if !self.allowed_metrics.is_empty()
+            && !self
+                .allowed_metrics
+                .iter()
+                .any(|pattern| pattern.is_match(&name))
+        {
+            return None;
+        }
+        if self.relabel.is_empty() && selectors.is_empty() {
+            return Some(Cow::Borrowed(metric));
+        }
+
+        let mut labels = metric.tags().cloned().unwrap_or_default();
+        labels.insert(METRIC_NAME_LABEL.to_owned(), name);
+        for rule in &self.relabel {
+            if !rule.apply(&mut labels) {
+                return None;
+            }
+        }
+        if !selectors.is_empty() && !selectors.iter().any(|selector| selector.matches(&labels)) {
+            return None;
+        }
+        if self.relabel.is_empty() {
+            return Some(Cow::Borrowed(metric));
+        }
+
+        let name = labels
+            .remove(METRIC_NAME_LABEL)
+            .filter(|name| !name.is_empty())?;
+        let tags = (!labels.is_empty()).then(|| labels);
+        Some(Cow::Owned(
+            metric
+                .clone()
+                .with_namespace(None::<String>)
+                .with_name(name)
+                .with_tags(tags),
+        ))
+    }
+
+    fn collect<'a, T: MetricCollector>(
+        &self,
+        metrics: impl Iterator<Item = &'a Metric>,
+        selectors: &[Selector],
+    ) -> T::Output {
+        let mut collector = T::new();
+        for metric in metrics {
+            match self.expose(metric, selectors) {
+                Some(Cow::Borrowed(metric)) => collector.encode_metric(
+                    self.default_namespace.as_deref(),
+                    &self.buckets,
+                    &self.quantiles,
+                    metric,
+                ),
+                Some(Cow::Owned(metric)) => {
+                    collector.encode_metric(None, &self.buckets, &self.quantiles, &metric)
+                }
+                None => {}
+            }
+        }
+        collector.finish()
+    }
+}
+
 /// Expiration metadata for a metric.
 #[derive(Clone, Copy, Debug)]
 struct MetricMetadata {
@@ -215,7 +355,7 @@ impl MetricMetadata {
 
     /// Whether or not the referenced metric has expired yet.
     pub fn has_expired(&self, now: Instant) -> bool {
-        self.expires_at >= now
+        self.expires_at < now
     }
 }
 
@@ -321,30 +461,64 @@ impl MetricNormalize for PrometheusExporterMetricNormalizer {
 
 fn handle(
     req: Request<Body>,
-    default_namespace: Option<&str>,
-    buckets: &[f64],
-    quantiles: &[f64],
+    exposition: &Exposition,
     metrics: &IndexMap<MetricRef, (Metric, MetricMetadata)>,
 ) -> Response<Body> {
     let mut response = Response::new(Body::empty());
 
     match (req.method(), req.uri().path()) {
         (&Method::GET, "/metrics") => {
-            let mut collector = StringCollector::new();
-
-            for (_, (metric, _)) in metrics {
-                collector.encode_metric(default_namespace, buckets, quantiles, metric);
-            }
-
-            let body = collector.finish();
-            let body_size = body.size_of();
-
-            *response.body_mut() = body.into();
-
-            response.headers_mut().insert(
-                "Content-Type",
-                HeaderValue::from_static("text/plain; version=0.0.4"),
-            );
+            // Federation-style `match[]` selectors, any of which the exposed metrics must match.
+            let selectors = url::form_urlencoded::parse(req.uri().query().unwrap_or("").as_bytes())
+                .filter(|(key, _)| key == "match[]")
+                .map(|(_, selector)| Selector::parse(&selector))
+                .collect::<Result<Vec<_>, _>>();
+            let selectors = match selectors {
+                Ok(selectors) => selectors,
+                Err(error) => {
+                    *response.status_mut() = StatusCode::BAD_REQUEST;
+                    *response.body_mut() = error.to_string().into();
+                    return response;
+                }
+            };
+
+            let now = Instant::now();
+            let live = metrics
+                .values()
+                .filter(|(_, metadata)| !metadata.has_expired(now))
+                .map(|(metric, _)| metric);
+
+            let accepts_protobuf = req
+                .headers()
+                .get_all(hyper::header::ACCEPT)
+                .iter()
+                .filter_map(|accept| accept.to_str().ok())
+                .any(|accept| accept.contains("proto=io.prometheus.client.MetricFamily"));
+
+            let body_size = if exposition.protobuf && accepts_protobuf {
+                let mut body = Vec::new();
+                for family in exposition.collect::<MetricFamilies>(live, &selectors) {
+                    family
+                        .encode_length_delimited(&mut body)
+                        .expect("encoding to a vec can't fail");
+                }
+                let body_size = body.len();
+                *response.body_mut() = body.into();
+                response.headers_mut().insert(
+                    "Content-Type",
+                    HeaderValue::from_static(client::CONTENT_TYPE),
+                );
+                body_size
+            } else {
+                let body = exposition.collect::<StringCollector>(live, &selectors);
+                let body_size = body.size_of();
+                *response.body_mut() = body.into();
+                response.headers_mut().insert(
+                    "Content-Type",
+                    HeaderValue::from_static("text/plain; version=0.0.4"),
+                );
+                body_size
+            };
 
             emit!(BytesSent {
                 byte_size: body_size,
@@ -360,13 +534,39 @@ fn handle(
 }
 
 impl PrometheusExporter {
-    fn new(config: PrometheusExporterConfig, acker: Acker) -> Self {
-        Self {
+    fn new(config: PrometheusExporterConfig, acker: Acker) -> Result<Self, RelabelError> {
+        let exposition = Arc::new(Exposition::new(&config)?);
+        let expiration = config
+            .expiration
+            .iter()
+            .map(|rule| Ok((anchored(&rule.name)?, rule.ttl_secs)))
+            .collect::<Result<_, RelabelError>>()?;
+        Ok(Self {
             server_shutdown_trigger: None,
             config,
+            exposition,
+            expiration,
             metrics: Arc::new(RwLock::new(IndexMap::new())),
             acker,
+        })
+    }
+
+    /// How long a metric is exposed without being updated.
+    fn expiration_window(&self, metric: &Metric) -> Duration {
+        if self.expiration.is_empty() {
+            return self.config.flush_period_secs;
         }
+        let name = encode_namespace(
+            metric
+                .namespace()
+                .or(self.config.default_namespace.as_deref()),
+            '_',
+            metric.name(),
+        );
+        self.expiration
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(&name))
+            .map_or(self.config.flush_period_secs, |(_, ttl)| *ttl)
     }
 
     async fn start_server_if_needed(&mut self) {
@@ -376,16 +576,12 @@ impl PrometheusExporter {
 
         let span = Span::current();
         let metrics = Arc::clone(&self.metrics);
-        let default_namespace = self.config.default_namespace.clone();
-        let buckets = self.config.buckets.clone();
-        let quantiles = self.config.quantiles.clone();
+        let exposition = Arc::clone(&self.exposition);
 
         let new_service = make_service_fn(move |_| {
             let span = Span::current();
             let metrics = Arc::clone(&metrics);
-            let default_namespace = default_namespace.clone();
-            let buckets = buckets.clone();
-            let quantiles = quantiles.clone();
+            let exposition = Arc::clone(&exposition);
 
             async move {
                 Ok::<_, Infallible>(service_fn(move |req| {
@@ -398,13 +594,7 @@ impl PrometheusExporter {
                             .map(|(_, (metric, _))| metric.size_of())
                             .sum();
 
-                        let response = handle(
-                            req,
-                            default_namespace.as_deref(),
-                            &buckets,
-                            &quantiles,
-                            &metrics,
-                        );
+                        let response = handle(req, &exposition, &metrics);
 
                         emit!(EventsSent {
                             count,
@@ -458,7 +648,12 @@ impl StreamSink<Event> for PrometheusExporter {
         self.start_server_if_needed().await;
 
         let mut last_flush = Instant::now();
-        let flush_period = self.config.flush_period_secs;
+        // Metrics expiring sooner than the flush period are swept as often as they expire.
+        let sweep_period = self
+            .expiration
+            .iter()
+            .map(|(_, ttl)| *ttl)
+            .fold(self.config.flush_period_secs, Duration::min);
         let mut normalizer = MetricNormalizer::from(PrometheusExporterMetricNormalizer {
             distributions_as_summaries: self.config.distributions_as_summaries,
             buckets: self.config.buckets.clone(),
@@ -473,14 +668,14 @@ impl StreamSink<Event> for PrometheusExporter {
             // remove an expired metric depending on how things line up.  It'd be cool to _check_
             // for expired metrics more often, but we also don't want to check _way_ too often, like
             // every second, since then we're constantly iterating through every metric, etc etc.
-            if last_flush.elapsed() > self.config.flush_period_secs {
+            if last_flush.elapsed() > sweep_period {
                 last_flush = Instant::now();
 
                 let mut metrics = self.metrics.write().unwrap();
 
                 let metrics_to_expire = metrics
                     .iter()
-                    .filter(|(_, (_, metadata))| !metadata.has_expired(last_flush))
+                    .filter(|(_, (_, metadata))| metadata.has_expired(last_flush))
                     .map(|(metric_ref, _)| metric_ref.clone())
                     .collect::<Vec<_>>();
 
@@ -495,6 +690,7 @@ impl StreamSink<Event> for PrometheusExporter {
             if let Some(normalized) = normalizer.normalize(metric) {
                 // We have a normalized metric, in absolute form.  If we're already aware of this
                 // metric, update its expiration deadline, otherwise, start tracking it.
+                let expiration_window = self.expiration_window(&normalized);
                 let mut metrics = self.metrics.write().unwrap();
 
                 let metric_ref = MetricRef::from_metric(&normalized);
@@ -504,7 +700,10 @@ impl StreamSink<Event> for PrometheusExporter {
                         metadata.refresh();
                     }
                     None => {
-                        metrics.insert(metric_ref, (normalized, MetricMetadata::new(flush_period)));
+                        metrics.insert(
+                            metric_ref,
+                            (normalized, MetricMetadata::new(expiration_window)),
+                        );
                     }
                 }
             }
@@ -681,6 +880,141 @@ mod tests {
         (name, event)
     }
 
+    fn scrape(config: PrometheusExporterConfig, uri: &str, metrics: Vec<Metric>) -> Response<Body> {
+        let exporter = PrometheusExporter::new(config, Acker::passthrough()).unwrap();
+        let metrics = metrics
+            .into_iter()
+            .map(|metric| {
+                let metadata = MetricMetadata::new(exporter.expiration_window(&metric));
+                (MetricRef::from_metric(&metric), (metric, metadata))
+            })
+            .collect();
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        handle(request, &exporter.exposition, &metrics)
+    }
+
+    async fn body_text(response: Response<Body>) -> String {
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn gauge(name: &str, tags: &[(&str, &str)]) -> Metric {
+        Metric::new(
+            name,
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        )
+        .with_tags(Some(
+            tags.iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn filters_and_relabels_scrapes() {
+        let config: PrometheusExporterConfig = toml::from_str(
+            r#"
+            default_namespace = "app"
+            allowed_metrics = ["app_http_.*"]
+
+            [[relabel]]
+            source_labels = ["pod"]
+            regex = "(.*)-[0-9a-f]+"
+            target_label = "deployment"
+
+            [[relabel]]
+            action = "labeldrop"
+            regex = "pod"
+            "#,
+        )
+        .unwrap();
+        let metrics = vec![
+            gauge("http_requests", &[("pod", "web-7d9f"), ("code", "200")]),
+            gauge("http_requests", &[("pod", "api-01ab"), ("code", "500")]),
+            gauge("queue_depth", &[]),
+        ];
+
+        let body = body_text(scrape(config.clone(), "/metrics", metrics.clone())).await;
+        assert_eq!(
+            body,
+            indoc! {r#"
+                # HELP app_http_requests app_http_requests
+                # TYPE app_http_requests gauge
+                app_http_requests{code="200",deployment="web"} 1
+                app_http_requests{code="500",deployment="api"} 1
+            "#}
+        );
+
+        let body = body_text(scrape(
+            config.clone(),
+            "/metrics?match%5B%5D=%7Bcode%3D~%225..%22%7D",
+            metrics.clone(),
+        ))
+        .await;
+        assert!(body.contains(r#"app_http_requests{code="500",deployment="api"} 1"#));
+        assert!(!body.contains(r#"code="200""#));
+
+        let response = scrape(config, "/metrics?match%5B%5D=%7Bcode", metrics);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn expires_metrics_by_name() {
+        let config: PrometheusExporterConfig = toml::from_str(
+            r#"
+            [[expiration]]
+            name = "ephemeral_.*"
+            ttl_secs = 0
+            "#,
+        )
+        .unwrap();
+        let metrics = vec![gauge("ephemeral_jobs", &[]), gauge("lasting_jobs", &[])];
+
+        let exporter = PrometheusExporter::new(config.clone(), Acker::passthrough()).unwrap();
+        assert_eq!(
+            exporter.expiration_window(&metrics[0]),
+            time::Duration::from_secs(0)
+        );
+        assert_eq!(
+            exporter.expiration_window(&metrics[1]),
+            default_flush_period_secs()
+        );
+
+        time::sleep(time::Duration::from_millis(10)).await;
+        let body = body_text(scrape(config, "/metrics", metrics)).await;
+        assert!(!body.contains("ephemeral_jobs"));
+        assert!(body.contains("lasting_jobs 1"));
+    }
+
+    #[tokio::test]
+    async fn serves_protobuf_exposition() {
+        let config = PrometheusExporterConfig {
+            protobuf: true,
+            ..Default::default()
+        };
+        let exporter = PrometheusExporter::new(config, Acker::passthrough()).unwrap();
+        let metric = gauge("jobs", &[("queue", "default")]);
+        let metrics = vec![(
+            MetricRef::from_metric(&metric),
+            (metric, MetricMetadata::new(default_flush_period_secs())),
+        )]
+        .into_iter()
+        .collect();
+        let request = Request::get("/metrics")
+            .header("Accept", client::CONTENT_TYPE)
+            .body(Body::empty())
+            .unwrap();
+
+        let response = handle(request, &exporter.exposition, &metrics);
+        assert_eq!(response.headers()["Content-Type"], client::CONTENT_TYPE);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let family = client::MetricFamily::decode_length_delimited(body).unwrap();
+        assert_eq!(family.name(), "jobs");
+        assert_eq!(family.metric[0].gauge.as_ref().unwrap().value(), 1.0);
+        assert_eq!(family.metric[0].label[0].name(), "queue");
+    }
+
     #[tokio::test]
     async fn sink_absolute() {
         let config = PrometheusExporterConfig {
@@ -690,7 +1024,7 @@ mod tests {
         };
         let cx = SinkContext::new_test();
 
-        let sink = PrometheusExporter::new(config, cx.acker());
+        let sink = PrometheusExporter::new(config, cx.acker()).unwrap();
 
         let m1 = Metric::new(
             "absolute",
@@ -753,7 +1087,7 @@ mod tests {
         let buckets = config.buckets.clone();
         let cx = SinkContext::new_test();
 
-        let sink = PrometheusExporter::new(config, cx.acker());
+        let sink = PrometheusExporter::new(config, cx.acker()).unwrap();
 
         // Define a series of incremental distribution updates.
         let base_summary_metric = Metric::new(
@@ -873,7 +1207,7 @@ mod tests {
         };
         let cx = SinkContext::new_test();
 
-        let sink = PrometheusExporter::new(config, cx.acker());
+        let sink = PrometheusExporter::new(config, cx.acker()).unwrap();
 
         // Define a series of incremental distribution updates.
         let base_summary_metric = Metric::new(
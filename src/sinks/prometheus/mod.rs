@@ -3,6 +3,7 @@ use vector_core::event::Metric;
 
 mod collector;
 pub(crate) mod exporter;
+mod relabel;
 pub(crate) mod remote_write;
 
 fn default_histogram_buckets() -> Vec<f64> {
@@ -0,0 +1,366 @@
+//! Rules rewriting and filtering the metrics of the `prometheus_exporter` sink as they are
+//! scraped, along the lines of Prometheus' own `metric_relabel_configs` and of the `match[]`
+//! selectors of its federation endpoint.
+
+use std::{collections::BTreeMap, iter::Peekable, str::Chars};
+
+use prometheus_parser::METRIC_NAME_LABEL;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum RelabelError {
+    #[snafu(display("Invalid pattern {:?}: {}", pattern, source))]
+    InvalidPattern {
+        pattern: String,
+        source: regex::Error,
+    },
+    #[snafu(display("Relabel rules replacing labels need a `target_label`"))]
+    MissingTargetLabel,
+    #[snafu(display("Invalid selector {:?}: {}", selector, reason))]
+    InvalidSelector {
+        selector: String,
+        reason: &'static str,
+    },
+}
+
+/// Compiles a pattern matching whole values, as Prometheus' patterns do.
+pub fn anchored(pattern: &str) -> Result<Regex, RelabelError> {
+    Regex::new(&format!("^(?:{})$", pattern)).context(InvalidPatternSnafu { pattern })
+}
+
+fn default_separator() -> String {
+    ";".to_owned()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_owned()
+}
+
+fn default_replacement() -> String {
+    "$1".to_owned()
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    /// The labels whose values, joined by the separator, the pattern is matched against. The
+    /// metric name is the `__name__` label.
+    #[serde(default)]
+    pub source_labels: Vec<String>,
+    #[serde(default = "default_separator")]
+    pub separator: String,
+    #[serde(default = "default_regex")]
+    pub regex: String,
+    /// The label set by the `replace` action.
+    #[serde(default)]
+    pub target_label: Option<String>,
+    /// The value of the target label, with the groups of the pattern expanded.
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+    #[serde(default)]
+    pub action: RelabelAction,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Sets the target label to the replacement if the pattern matches, removing it if the
+    /// replacement is empty.
+    #[derivative(Default)]
+    Replace,
+    /// Drops the metrics the pattern doesn't match.
+    Keep,
+    /// Drops the metrics the pattern matches.
+    Drop,
+    /// Removes the labels whose names the pattern matches.
+    LabelDrop,
+    /// Removes the labels whose names the pattern doesn't match.
+    LabelKeep,
+}
+
+#[derive(Clone, Debug)]
+pub struct Relabel {
+    source_labels: Vec<String>,
+    separator: String,
+    regex: Regex,
+    target_label: Option<String>,
+    replacement: String,
+    action: RelabelAction,
+}
+
+impl Relabel {
+    pub fn new(config: &RelabelConfig) -> Result<Self, RelabelError> {
+        if config.action == RelabelAction::Replace && config.target_label.is_none() {
+            return Err(RelabelError::MissingTargetLabel);
+        }
+        Ok(Self {
+            source_labels: config.source_labels.clone(),
+            separator: config.separator.clone(),
+            regex: anchored(&config.regex)?,
+            target_label: config.target_label.clone(),
+            replacement: config.replacement.clone(),
+            action: config.action,
+        })
+    }
+
+    /// Applies the rule to the labels of a metric, returning whether the metric is kept.
+    pub fn apply(&self, labels: &mut BTreeMap<String, String>) -> bool {
+        match self.action {
+            RelabelAction::Replace => {
+                let value = self.source_value(labels);
+                if let Some(captures) = self.regex.captures(&value) {
+                    let mut replaced = String::new();
+                    captures.expand(&self.replacement, &mut replaced);
+                    let target = self.target_label.clone().expect("validated target label");
+                    if replaced.is_empty() {
+                        labels.remove(&target);
+                    } else {
+                        labels.insert(target, replaced);
+                    }
+                }
+                true
+            }
+            RelabelAction::Keep => self.regex.is_match(&self.source_value(labels)),
+            RelabelAction::Drop => !self.regex.is_match(&self.source_value(labels)),
+            RelabelAction::LabelDrop => {
+                labels.retain(|name, _| name == METRIC_NAME_LABEL || !self.regex.is_match(name));
+                true
+            }
+            RelabelAction::LabelKeep => {
+                labels.retain(|name, _| name == METRIC_NAME_LABEL || self.regex.is_match(name));
+                true
+            }
+        }
+    }
+
+    fn source_value(&self, labels: &BTreeMap<String, String>) -> String {
+        self.source_labels
+            .iter()
+            .map(|label| labels.get(label).map_or("", String::as_str))
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Matcher {
+    Equal(String),
+    NotEqual(String),
+    Regex(Regex),
+    NotRegex(Regex),
+}
+
+impl Matcher {
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            Matcher::Equal(expected) => value == expected,
+            Matcher::NotEqual(expected) => value != expected,
+            Matcher::Regex(regex) => regex.is_match(value),
+            Matcher::NotRegex(regex) => !regex.is_match(value),
+        }
+    }
+}
+
+/// A series selector, as in `http_requests_total{code=~"5..",method!="GET"}`.
+#[derive(Clone, Debug)]
+pub struct Selector {
+    matchers: Vec<(String, Matcher)>,
+}
+
+impl Selector {
+    pub fn parse(selector: &str) -> Result<Self, RelabelError> {
+        let invalid = |reason| RelabelError::InvalidSelector {
+            selector: selector.to_owned(),
+            reason,
+        };
+
+        let mut chars = selector.trim().chars().peekable();
+        let mut matchers = Vec::new();
+        let name = identifier(&mut chars);
+        if !name.is_empty() {
+            matchers.push((METRIC_NAME_LABEL.to_owned(), Matcher::Equal(name)));
+        }
+
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            loop {
+                skip_whitespace(&mut chars);
+                if chars.peek() == Some(&'}') {
+                    chars.next();
+                    break;
+                }
+                let label = identifier(&mut chars);
+                if label.is_empty() {
+                    return Err(invalid("expected a label name"));
+                }
+                skip_whitespace(&mut chars);
+                let operator = match (chars.next(), chars.peek()) {
+                    (Some('='), Some('~')) => "=~",
+                    (Some('='), _) => "=",
+                    (Some('!'), Some('=')) => "!=",
+                    (Some('!'), Some('~')) => "!~",
+                    _ => return Err(invalid("expected one of `=`, `!=`, `=~` and `!~`")),
+                };
+                if operator.len() == 2 {
+                    chars.next();
+                }
+                skip_whitespace(&mut chars);
+                let value = string(&mut chars).ok_or_else(|| invalid("expected a quoted value"))?;
+                let matcher = match operator {
+                    "=" => Matcher::Equal(value),
+                    "!=" => Matcher::NotEqual(value),
+                    "=~" => Matcher::Regex(anchored(&value)?),
+                    _ => Matcher::NotRegex(anchored(&value)?),
+                };
+                matchers.push((label, matcher));
+
+                skip_whitespace(&mut chars);
+                match chars.next() {
+                    Some(',') => {}
+                    Some('}') => break,
+                    _ => return Err(invalid("expected `,` or `}`")),
+                }
+            }
+        }
+
+        if chars.next().is_some() {
+            return Err(invalid("unexpected characters after the selector"));
+        }
+        if matchers.is_empty() {
+            return Err(invalid("expected a metric name or label matchers"));
+        }
+        Ok(Self { matchers })
+    }
+
+    /// Whether the selector matches a metric, by its labels. Missing labels have empty values.
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.matchers
+            .iter()
+            .all(|(label, matcher)| matcher.matches(labels.get(label).map_or("", String::as_str)))
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.peek().map_or(false, |c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn identifier(chars: &mut Peekable<Chars>) -> String {
+    let mut identifier = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+            identifier.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    identifier
+}
+
+fn string(chars: &mut Peekable<Chars>) -> Option<String> {
+    let quote = chars.next().filter(|c| *c == '"' || *c == '\'')?;
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                c => value.push(c),
+            },
+            c if c == quote => return Some(value),
+            c => value.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(labels: &[(&str, &str)]) -> BTreeMap<String, String> {
+        labels
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn relabel(config: &str) -> Relabel {
+        Relabel::new(&toml::from_str(config).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn relabels() {
+        let mut metric = labels(&[
+            ("__name__", "http_requests_total"),
+            ("pod", "web-7d9f"),
+            ("code", "200"),
+        ]);
+
+        let rule = relabel(
+            r#"
+            source_labels = ["pod"]
+            regex = "(.*)-[0-9a-f]+"
+            target_label = "app"
+            "#,
+        );
+        assert!(rule.apply(&mut metric));
+        assert_eq!(metric["app"], "web");
+
+        assert!(relabel("action = 'labeldrop'\nregex = 'pod'").apply(&mut metric));
+        assert!(!metric.contains_key("pod"));
+
+        assert!(relabel("action = 'labelkeep'\nregex = 'app'").apply(&mut metric));
+        assert_eq!(
+            metric,
+            labels(&[("__name__", "http_requests_total"), ("app", "web")])
+        );
+
+        let drop = relabel(
+            r#"
+            action = "drop"
+            source_labels = ["__name__"]
+            regex = "http_.*"
+            "#,
+        );
+        assert!(!drop.apply(&mut metric));
+
+        assert!(matches!(
+            Relabel::new(&toml::from_str("source_labels = ['pod']").unwrap()),
+            Err(RelabelError::MissingTargetLabel)
+        ));
+    }
+
+    #[test]
+    fn selects_series() {
+        let metric = labels(&[
+            ("__name__", "http_requests_total"),
+            ("code", "503"),
+            ("method", "GET"),
+        ]);
+
+        for (selector, matches) in [
+            ("http_requests_total", true),
+            ("http_requests", false),
+            (r#"{code=~"5.."}"#, true),
+            (r#"http_requests_total{code=~"5..", method!="GET"}"#, false),
+            (r#"{method='GET',code!~"2..",}"#, true),
+            (r#"{__name__=~"http_.*", missing=""}"#, true),
+        ] {
+            assert_eq!(
+                Selector::parse(selector).unwrap().matches(&metric),
+                matches,
+                "{}",
+                selector
+            );
+        }
+
+        for selector in ["", "{}", r#"{code="5"#, r#"{code=5}"#, "name{} extra"] {
+            assert!(Selector::parse(selector).is_err(), "{}", selector);
+        }
+    }
+}
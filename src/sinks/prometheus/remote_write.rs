@@ -91,6 +91,9 @@ impl SinkConfig for RemoteWriteConfig {
         let client = HttpClient::new(tls_settings, cx.proxy())?;
         let tenant_id = self.tenant_id.clone();
         let auth = self.auth.clone();
+        if let Some(auth) = &auth {
+            auth.prepare(&client).await?;
+        }
 
         let healthcheck = healthcheck(endpoint.clone(), client.clone()).boxed();
         let service = RemoteWriteService {
@@ -139,7 +142,8 @@ impl SinkConfig for RemoteWriteConfig {
     }
 
     fn input(&self) -> Input {
-        Input::metric()
+        // The sink turns batches of metrics in columns into rows itself, once they reach it.
+        Input::metric().with_columnar()
     }
 
     fn sink_type(&self) -> &'static str {
@@ -28,6 +28,7 @@ pub(super) struct Controller<L> {
     concurrency: Option<usize>,
     settings: AdaptiveConcurrencySettings,
     logic: L,
+    endpoint: Option<String>,
     pub(super) inner: Arc<Mutex<Inner>>,
     #[cfg(test)]
     pub(super) stats: Arc<Mutex<ControllerStatistics>>,
@@ -58,17 +59,19 @@ impl<L> Controller<L> {
         concurrency: Option<usize>,
         settings: AdaptiveConcurrencySettings,
         logic: L,
+        endpoint: Option<String>,
     ) -> Self {
         // If a `concurrency` is specified, it becomes both the
         // current limit and the maximum, effectively bypassing all the
-        // mechanisms. Otherwise, the current limit is set to 1 and the
-        // maximum to MAX_CONCURRENCY.
-        let current_limit = concurrency.unwrap_or(1);
+        // mechanisms. Otherwise, the current limit is set to the
+        // minimum and the maximum to the configured ceiling.
+        let current_limit = concurrency.unwrap_or_else(|| settings.bounds().0);
         Self {
             semaphore: Arc::new(ShrinkableSemaphore::new(current_limit)),
             concurrency,
             settings,
             logic,
+            endpoint,
             inner: Arc::new(Mutex::new(Inner {
                 current_limit,
                 in_flight: 0,
@@ -205,34 +208,53 @@ impl<L> Controller<L> {
     ) {
         let past_rtt_deviation = past_rtt.variance.sqrt();
         let threshold = past_rtt_deviation * self.settings.rtt_deviation_scale;
+        let (min_concurrency, max_concurrency) = self.settings.bounds();
+        let rtt_fell = !inner.had_back_pressure
+            && current_rtt.map_or(false, |current_rtt| current_rtt <= past_rtt.mean);
+        let rtt_rose = current_rtt.unwrap_or(0.0) >= past_rtt.mean + threshold;
 
         // Normal quick responses trigger an increase in the
         // concurrency limit. Note that we only check this if we had
         // requests to go beyond the current limit to prevent
         // increasing the limit beyond what we have evidence for.
-        if inner.current_limit < super::MAX_CONCURRENCY
+        let (decision, reason) = if inner.current_limit < max_concurrency
             && inner.reached_limit
-            && !inner.had_back_pressure
-            && current_rtt.is_some()
-            && current_rtt.unwrap() <= past_rtt.mean
+            && rtt_fell
         {
             // Increase (additive) the current concurrency limit
             self.semaphore.add_permits(1);
             inner.current_limit += 1;
+            ("increase", "rtt_fell")
         }
         // Back pressure responses, either explicit or implicit due
         // to increasing response times, trigger a decrease in the
         // concurrency limit.
-        else if inner.current_limit > 1
-            && (inner.had_back_pressure || current_rtt.unwrap_or(0.0) >= past_rtt.mean + threshold)
-        {
+        else if inner.current_limit > min_concurrency && (inner.had_back_pressure || rtt_rose) {
             // Decrease (multiplicative) the current concurrency limit
-            let to_forget = inner.current_limit
-                - (inner.current_limit as f64 * self.settings.decrease_ratio) as usize;
+            let decreased = (inner.current_limit as f64 * self.settings.decrease_ratio) as usize;
+            let to_forget = inner.current_limit - decreased.max(min_concurrency);
             self.semaphore.forget_permits(to_forget);
             inner.current_limit -= to_forget;
-        }
+            if inner.had_back_pressure {
+                ("decrease", "back_pressure")
+            } else {
+                ("decrease", "rtt_rose")
+            }
+        } else if inner.had_back_pressure || rtt_rose {
+            ("hold", "at_min_concurrency")
+        } else if !inner.reached_limit {
+            ("hold", "limit_not_reached")
+        } else if rtt_fell {
+            ("hold", "at_max_concurrency")
+        } else if current_rtt.is_none() {
+            ("hold", "no_rtt")
+        } else {
+            ("hold", "rtt_within_deviation")
+        };
         emit!(AdaptiveConcurrencyLimit {
+            endpoint: self.endpoint.as_deref(),
+            decision,
+            reason,
             concurrency: inner.current_limit as u64,
             reached_limit: inner.reached_limit,
             had_back_pressure: inner.had_back_pressure,
@@ -0,0 +1,268 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, ready, FutureExt};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::{Layer, Service, ServiceExt};
+
+use super::{controller::Controller, future::ResponseFuture, AdaptiveConcurrencySettings};
+use crate::sinks::util::retries::RetryLogic;
+
+/// A request sent to one of several endpoints, such as the nodes of a
+/// cluster or the paths of an API, whose concurrency is limited
+/// separately.
+///
+/// The set of endpoints must be small and stable, as a limit is kept
+/// for each endpoint a request was ever sent to.
+pub trait Endpoint {
+    fn endpoint(&self) -> &str;
+}
+
+/// Enforces a limit on the concurrent number of requests the underlying
+/// service can handle for each endpoint, so that a slow endpoint doesn't
+/// lower the limit of the others. The limit of each endpoint expands and
+/// contracts depending on the responses to the requests sent to it, and
+/// the requests in flight across all endpoints are capped by the maximum
+/// concurrency.
+pub struct EndpointConcurrencyLimit<S, L> {
+    inner: S,
+    concurrency: Option<usize>,
+    settings: AdaptiveConcurrencySettings,
+    logic: L,
+    controllers: Arc<Mutex<HashMap<String, Arc<Controller<L>>>>>,
+    ceiling: Arc<Semaphore>,
+    state: State,
+}
+
+enum State {
+    Waiting(BoxFuture<'static, OwnedSemaphorePermit>),
+    Ready(OwnedSemaphorePermit),
+    Empty,
+}
+
+impl<S, L> EndpointConcurrencyLimit<S, L> {
+    /// Create a new per-endpoint concurrency limiter.
+    pub(crate) fn new(
+        inner: S,
+        logic: L,
+        concurrency: Option<usize>,
+        settings: AdaptiveConcurrencySettings,
+    ) -> Self {
+        let ceiling = match concurrency {
+            Some(concurrency) => concurrency.max(settings.bounds().1),
+            None => settings.bounds().1,
+        };
+        Self {
+            inner,
+            concurrency,
+            settings,
+            logic,
+            controllers: Default::default(),
+            ceiling: Arc::new(Semaphore::new(ceiling)),
+            state: State::Empty,
+        }
+    }
+}
+
+impl<S, L> EndpointConcurrencyLimit<S, L>
+where
+    L: Clone,
+{
+    fn controller(&self, endpoint: &str) -> Arc<Controller<L>> {
+        let mut controllers = self
+            .controllers
+            .lock()
+            .expect("Controllers mutex is poisoned");
+        match controllers.get(endpoint) {
+            Some(controller) => Arc::clone(controller),
+            None => {
+                let controller = Arc::new(Controller::new(
+                    self.concurrency,
+                    self.settings,
+                    self.logic.clone(),
+                    Some(endpoint.to_owned()),
+                ));
+                controllers.insert(endpoint.to_owned(), Arc::clone(&controller));
+                controller
+            }
+        }
+    }
+}
+
+impl<S, L, Request> Service<Request> for EndpointConcurrencyLimit<S, L>
+where
+    S: Service<Request> + Clone + Send + 'static,
+    S::Error: Into<crate::Error>,
+    S::Future: Send,
+    L: RetryLogic<Response = S::Response>,
+    Request: Endpoint + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<S::Response, crate::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        loop {
+            self.state = match self.state {
+                State::Ready(_) => return Poll::Ready(Ok(())),
+                State::Waiting(ref mut fut) => State::Ready(ready!(fut.poll_unpin(cx))),
+                State::Empty => State::Waiting(Box::pin(
+                    Arc::clone(&self.ceiling)
+                        .acquire_owned()
+                        .map(|permit| permit.expect("Semaphore has been closed")),
+                )),
+            };
+        }
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Make sure a permit has been acquired
+        let ceiling_permit = match std::mem::replace(&mut self.state, State::Empty) {
+            State::Ready(permit) => permit,
+            _ => panic!("Maximum requests in-flight; poll_ready must be called first"),
+        };
+
+        // The request waits for a permit of its endpoint before it is
+        // sent, so the inner service is called once it has one.
+        let controller = self.controller(request.endpoint());
+        let inner = self.inner.clone();
+        Box::pin(async move {
+            let _ceiling_permit = ceiling_permit;
+            let permit = Arc::clone(&controller).acquire().await;
+            controller.start_request();
+            ResponseFuture::new(inner.oneshot(request), permit, controller).await
+        })
+    }
+}
+
+impl<S, L> Clone for EndpointConcurrencyLimit<S, L>
+where
+    S: Clone,
+    L: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            concurrency: self.concurrency,
+            settings: self.settings,
+            logic: self.logic.clone(),
+            controllers: Arc::clone(&self.controllers),
+            ceiling: Arc::clone(&self.ceiling),
+            state: State::Empty,
+        }
+    }
+}
+
+impl<S, L> fmt::Debug for EndpointConcurrencyLimit<S, L>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EndpointConcurrencyLimit")
+            .field("inner", &self.inner)
+            .field("concurrency", &self.concurrency)
+            .field("settings", &self.settings)
+            .finish()
+    }
+}
+
+/// Enforces a limit on the concurrent number of requests the underlying
+/// service can handle for each endpoint.
+#[derive(Debug, Clone)]
+pub struct EndpointConcurrencyLimitLayer<L> {
+    concurrency: Option<usize>,
+    options: AdaptiveConcurrencySettings,
+    logic: L,
+}
+
+impl<L> EndpointConcurrencyLimitLayer<L> {
+    /// Create a new per-endpoint concurrency limit layer.
+    pub const fn new(
+        concurrency: Option<usize>,
+        options: AdaptiveConcurrencySettings,
+        logic: L,
+    ) -> Self {
+        EndpointConcurrencyLimitLayer {
+            concurrency,
+            options,
+            logic,
+        }
+    }
+}
+
+impl<S, L: RetryLogic> Layer<S> for EndpointConcurrencyLimitLayer<L> {
+    type Service = EndpointConcurrencyLimit<S, L>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        EndpointConcurrencyLimit::new(service, self.logic.clone(), self.concurrency, self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snafu::Snafu;
+    use tokio_test::{assert_pending, task};
+    use tower_test::mock;
+
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Snafu)]
+    enum TestError {
+        Deferral,
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    struct TestRetryLogic;
+    impl RetryLogic for TestRetryLogic {
+        type Error = TestError;
+        type Response = String;
+        fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+            true
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestRequest(&'static str, usize);
+    impl Endpoint for TestRequest {
+        fn endpoint(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_each_endpoint_separately() {
+        let (mock, mut handle) = mock::pair::<TestRequest, String>();
+        let mut service = EndpointConcurrencyLimitLayer::new(
+            Some(1),
+            AdaptiveConcurrencySettings::default(),
+            TestRetryLogic,
+        )
+        .layer(mock);
+
+        let first = tokio::spawn(service.ready().await.unwrap().call(TestRequest("slow", 1)));
+        let second = tokio::spawn(service.ready().await.unwrap().call(TestRequest("slow", 2)));
+        let other = tokio::spawn(service.ready().await.unwrap().call(TestRequest("fast", 1)));
+
+        let (request, first_response) = handle.next_request().await.unwrap();
+        assert_eq!(request, TestRequest("slow", 1));
+        // The second request to the slow endpoint waits for the first,
+        // while the request to the other endpoint doesn't.
+        let (request, other_response) = handle.next_request().await.unwrap();
+        assert_eq!(request, TestRequest("fast", 1));
+        assert_pending!(task::spawn(handle.next_request()).poll());
+
+        other_response.send_response("fast".into());
+        assert_eq!(other.await.unwrap().unwrap(), "fast");
+        first_response.send_response("slow".into());
+        assert_eq!(first.await.unwrap().unwrap(), "slow");
+
+        let (request, second_response) = handle.next_request().await.unwrap();
+        assert_eq!(request, TestRequest("slow", 2));
+        second_response.send_response("slow".into());
+        assert_eq!(second.await.unwrap().unwrap(), "slow");
+    }
+}
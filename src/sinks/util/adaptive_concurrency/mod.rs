@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 mod controller;
+mod endpoint;
 mod future;
 mod layer;
 mod semaphore;
@@ -11,6 +12,7 @@ mod tests;
 
 pub(super) const MAX_CONCURRENCY: usize = 200;
 
+pub(crate) use endpoint::{Endpoint, EndpointConcurrencyLimit, EndpointConcurrencyLimitLayer};
 pub(crate) use layer::AdaptiveConcurrencyLimitLayer;
 pub(crate) use service::AdaptiveConcurrencyLimit;
 
@@ -35,6 +37,15 @@ pub struct AdaptiveConcurrencySettings {
     // This value avoided changing concurrency too aggressively when
     // there is fluctuation in the RTT measurements.
     pub(super) rtt_deviation_scale: f64,
+
+    // The limit never goes below this value, which is also the limit
+    // it starts from.
+    pub(super) min_concurrency: usize,
+
+    // The limit never goes above this value. When the limit is tracked
+    // per endpoint, this also caps the requests in flight across all
+    // endpoints.
+    pub(super) max_concurrency: usize,
 }
 
 impl AdaptiveConcurrencySettings {
@@ -43,8 +54,17 @@ impl AdaptiveConcurrencySettings {
             decrease_ratio: 0.9,
             ewma_alpha: 0.4,
             rtt_deviation_scale: 2.5,
+            min_concurrency: 1,
+            max_concurrency: MAX_CONCURRENCY,
         }
     }
+
+    /// The lower and upper bounds of the limit, with the upper bound
+    /// raised to the lower one if they are inverted.
+    pub(super) fn bounds(&self) -> (usize, usize) {
+        let min = self.min_concurrency.max(1);
+        (min, self.max_concurrency.max(min))
+    }
 }
 
 impl Default for AdaptiveConcurrencySettings {
@@ -39,7 +39,7 @@ impl<S, L> AdaptiveConcurrencyLimit<S, L> {
     ) -> Self {
         AdaptiveConcurrencyLimit {
             inner,
-            controller: Arc::new(Controller::new(concurrency, options, logic)),
+            controller: Arc::new(Controller::new(concurrency, options, logic, None)),
             state: State::Empty,
         }
     }
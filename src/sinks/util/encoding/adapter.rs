@@ -479,7 +479,9 @@ mod tests {
         type Codec = FooLegacyEncoding;
 
         fn migrate(_: &Self::Codec) -> SerializerConfig {
-            SerializerConfig::Json
+            SerializerConfig::Json {
+                json: Default::default(),
+            }
         }
     }
 
@@ -492,7 +494,9 @@ mod tests {
         fn migrate(_: &Self::Codec) -> (Option<FramingConfig>, SerializerConfig) {
             (
                 Some(FramingConfig::NewlineDelimited),
-                SerializerConfig::Json,
+                SerializerConfig::Json {
+                    json: Default::default(),
+                },
             )
         }
     }
@@ -583,7 +587,7 @@ mod tests {
 
             let serializer = adapter.config();
 
-            assert!(matches!(serializer, SerializerConfig::Json));
+            assert!(matches!(serializer, SerializerConfig::Json { .. }));
         }
     }
 
@@ -604,7 +608,7 @@ mod tests {
             let (framing, serializer) = adapter.config();
 
             assert!(matches!(framing, Some(FramingConfig::NewlineDelimited)));
-            assert!(matches!(serializer, SerializerConfig::Json));
+            assert!(matches!(serializer, SerializerConfig::Json { .. }));
         }
     }
 
@@ -625,7 +629,7 @@ mod tests {
             let (framing, serializer) = adapter.config();
 
             assert!(matches!(framing, Some(FramingConfig::Bytes)));
-            assert!(matches!(serializer, SerializerConfig::Json));
+            assert!(matches!(serializer, SerializerConfig::Json { .. }));
         }
     }
 
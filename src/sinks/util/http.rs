@@ -454,6 +454,10 @@ impl RetryLogic for HttpRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn response_status_code(&self, response: &Self::Response) -> Option<u16> {
+        Some(response.status().as_u16())
+    }
 }
 
 /// A more generic version of `HttpRetryLogic` that accepts anything that can be converted
@@ -504,6 +508,10 @@ where
             _ => RetryAction::DontRetry(format!("Http status: {}", status).into()),
         }
     }
+
+    fn response_status_code(&self, response: &T) -> Option<u16> {
+        Some((self.func)(response).as_u16())
+    }
 }
 
 impl<F, T> Clone for HttpStatusRetryLogic<F, T>
@@ -44,8 +44,8 @@ pub use normalizer::Normalizer;
 pub use request_builder::{IncrementalRequestBuilder, RequestBuilder};
 use serde::{Deserialize, Serialize};
 pub use service::{
-    Concurrency, ServiceBuilderExt, TowerBatchedSink, TowerPartitionSink, TowerRequestConfig,
-    TowerRequestLayer, TowerRequestSettings,
+    Concurrency, ServiceBuilderExt, TowerBatchedSink, TowerEndpointRequestLayer,
+    TowerPartitionSink, TowerRequestConfig, TowerRequestLayer, TowerRequestSettings,
 };
 pub use sink::{BatchSink, PartitionBatchSink, StreamSink};
 use snafu::Snafu;
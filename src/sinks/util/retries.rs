@@ -3,13 +3,19 @@ use std::{
     cmp,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
 
 use futures::FutureExt;
-use tokio::time::{sleep, Sleep};
-use tower::{retry::Policy, timeout::error::Elapsed};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep, Instant, Sleep};
+use tower::{
+    retry::{budget::Budget, Policy},
+    timeout::error::Elapsed,
+};
 
 use crate::Error;
 
@@ -32,6 +38,243 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
         // Treat the default as the request is successful
         RetryAction::Successful
     }
+
+    /// Returns the HTTP status code of the given response, if the service has one.
+    ///
+    /// This is what `request.retry.retryable_http_statuses` is matched against.
+    fn response_status_code(&self, _response: &Self::Response) -> Option<u16> {
+        None
+    }
+
+    /// Returns the gRPC status code of the given error, if the service has one.
+    ///
+    /// This is what `request.retry.retryable_grpc_codes` is matched against.
+    fn error_grpc_code(&self, _error: &Self::Error) -> Option<i32> {
+        None
+    }
+}
+
+/// How the delay between retries grows.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryBackoff {
+    /// Each delay is the sum of the previous two delays.
+    Fibonacci,
+    /// Each delay is double the previous delay.
+    Exponential,
+}
+
+impl Default for RetryBackoff {
+    fn default() -> Self {
+        Self::Fibonacci
+    }
+}
+
+/// How the delay between retries is randomized.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryJitter {
+    /// The delay is used as-is.
+    None,
+    /// The delay is picked at random between zero and the delay.
+    Full,
+    /// The delay is picked at random between half the delay and the delay.
+    Equal,
+}
+
+impl Default for RetryJitter {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl RetryJitter {
+    fn apply(self, delay: Duration) -> Duration {
+        let millis = delay.as_millis() as u64;
+        let millis = match self {
+            Self::None => return delay,
+            Self::Full => rand::thread_rng().gen_range(0..=millis),
+            Self::Equal => millis / 2 + rand::thread_rng().gen_range(0..=millis - millis / 2),
+        };
+        Duration::from_millis(millis)
+    }
+}
+
+/// Limits the number of retries relative to the number of requests made, so that retries can't
+/// pile on to an already struggling service.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(try_from = "RetryBudgetConfigShadow")]
+pub struct RetryBudgetConfig {
+    /// The number of retries allowed per request, on top of `min_per_sec`.
+    pub ratio: f32,
+    /// The number of retries allowed per second regardless of how many requests were made.
+    pub min_per_sec: u32,
+    /// How long, in seconds, a request counts towards the budget.
+    pub ttl_secs: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RetryBudgetConfigShadow {
+    #[serde(default = "default_budget_ratio")]
+    ratio: f32,
+    #[serde(default = "default_budget_min_per_sec")]
+    min_per_sec: u32,
+    #[serde(default = "default_budget_ttl_secs")]
+    ttl_secs: u64,
+}
+
+const fn default_budget_ratio() -> f32 {
+    0.2
+}
+
+const fn default_budget_min_per_sec() -> u32 {
+    10
+}
+
+const fn default_budget_ttl_secs() -> u64 {
+    10
+}
+
+impl TryFrom<RetryBudgetConfigShadow> for RetryBudgetConfig {
+    type Error = String;
+
+    fn try_from(shadow: RetryBudgetConfigShadow) -> Result<Self, Self::Error> {
+        // These mirror the limits enforced by `Budget::new`.
+        if !(0.0..=1000.0).contains(&shadow.ratio) {
+            return Err("`ratio` must be between 0 and 1000".into());
+        }
+        if shadow.min_per_sec >= i32::MAX as u32 {
+            return Err(format!("`min_per_sec` must be less than {}", i32::MAX));
+        }
+        if !(1..=60).contains(&shadow.ttl_secs) {
+            return Err("`ttl_secs` must be between 1 and 60".into());
+        }
+
+        Ok(Self {
+            ratio: shadow.ratio,
+            min_per_sec: shadow.min_per_sec,
+            ttl_secs: shadow.ttl_secs,
+        })
+    }
+}
+
+impl RetryBudgetConfig {
+    pub fn build(&self) -> Budget {
+        Budget::new(
+            Duration::from_secs(self.ttl_secs),
+            self.min_per_sec,
+            self.ratio,
+        )
+    }
+}
+
+/// A set of HTTP status codes.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(try_from = "Vec<u16>", into = "Vec<u16>")]
+pub struct HttpStatusSet([u64; 10]);
+
+impl HttpStatusSet {
+    pub fn contains(&self, status: u16) -> bool {
+        let status = status as usize;
+        status < 640 && self.0[status / 64] & (1 << (status % 64)) != 0
+    }
+}
+
+impl TryFrom<Vec<u16>> for HttpStatusSet {
+    type Error = String;
+
+    fn try_from(statuses: Vec<u16>) -> Result<Self, Self::Error> {
+        let mut set = [0; 10];
+        for status in statuses {
+            if !(100..=599).contains(&status) {
+                return Err(format!("invalid HTTP status code: {}", status));
+            }
+            set[status as usize / 64] |= 1 << (status % 64);
+        }
+        Ok(Self(set))
+    }
+}
+
+impl From<HttpStatusSet> for Vec<u16> {
+    fn from(set: HttpStatusSet) -> Self {
+        (100..=599).filter(|status| set.contains(*status)).collect()
+    }
+}
+
+/// A set of gRPC status codes.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(try_from = "Vec<u8>", into = "Vec<u8>")]
+pub struct GrpcCodeSet(u32);
+
+impl GrpcCodeSet {
+    pub fn contains(&self, code: i32) -> bool {
+        (0..=16).contains(&code) && self.0 & (1 << code) != 0
+    }
+}
+
+impl TryFrom<Vec<u8>> for GrpcCodeSet {
+    type Error = String;
+
+    fn try_from(codes: Vec<u8>) -> Result<Self, Self::Error> {
+        let mut set = 0;
+        for code in codes {
+            if code > 16 {
+                return Err(format!("invalid gRPC status code: {}", code));
+            }
+            set |= 1 << code;
+        }
+        Ok(Self(set))
+    }
+}
+
+impl From<GrpcCodeSet> for Vec<u8> {
+    fn from(set: GrpcCodeSet) -> Self {
+        (0..=16).filter(|code| set.contains(*code as i32)).collect()
+    }
+}
+
+/// Additional retry behavior configured through `request.retry`.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RetryPolicyConfig {
+    /// The maximum amount of time, in seconds, to keep retrying a request for, measured from the
+    /// first failed attempt.
+    #[serde(default)]
+    pub max_elapsed_secs: Option<u64>,
+    #[serde(default)]
+    pub backoff: RetryBackoff,
+    #[serde(default)]
+    pub jitter: RetryJitter,
+    #[serde(default)]
+    pub budget: Option<RetryBudgetConfig>,
+    /// When set, requests whose non-successful response status is in this set are retried, and
+    /// all others are not, regardless of the sink's own retry logic.
+    #[serde(default)]
+    pub retryable_http_statuses: Option<HttpStatusSet>,
+    /// When set, requests failing with a gRPC status code in this set are retried, and all others
+    /// are not, regardless of the sink's own retry logic.
+    #[serde(default)]
+    pub retryable_grpc_codes: Option<GrpcCodeSet>,
+}
+
+impl RetryPolicyConfig {
+    pub const fn const_default() -> Self {
+        Self {
+            max_elapsed_secs: None,
+            backoff: RetryBackoff::Fibonacci,
+            jitter: RetryJitter::None,
+            budget: None,
+            retryable_http_statuses: None,
+            retryable_grpc_codes: None,
+        }
+    }
+}
+
+impl Default for RetryPolicyConfig {
+    fn default() -> Self {
+        Self::const_default()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +283,9 @@ pub struct FixedRetryPolicy<L> {
     previous_duration: Duration,
     current_duration: Duration,
     max_duration: Duration,
+    options: RetryPolicyConfig,
+    budget: Option<Arc<Budget>>,
+    retrying_since: Option<Instant>,
     logic: L,
 }
 
@@ -60,18 +306,40 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
             previous_duration: Duration::from_secs(0),
             current_duration: initial_backoff,
             max_duration,
+            options: RetryPolicyConfig::default(),
+            budget: None,
+            retrying_since: None,
             logic,
         }
     }
 
+    /// Applies the given retry policy options.
+    ///
+    /// The budget, if any, should be built once and shared by every request of the sink, as that
+    /// is what it limits retries across.
+    pub fn with_options(mut self, options: RetryPolicyConfig, budget: Option<Arc<Budget>>) -> Self {
+        self.options = options;
+        self.budget = budget;
+        self
+    }
+
     fn advance(&self) -> FixedRetryPolicy<L> {
-        let next_duration: Duration = self.previous_duration + self.current_duration;
+        let next_duration: Duration = match self.options.backoff {
+            RetryBackoff::Fibonacci => self.previous_duration + self.current_duration,
+            RetryBackoff::Exponential => self
+                .current_duration
+                .checked_mul(2)
+                .unwrap_or(self.max_duration),
+        };
 
         FixedRetryPolicy {
             remaining_attempts: self.remaining_attempts - 1,
             previous_duration: self.current_duration,
             current_duration: cmp::min(next_duration, self.max_duration),
             max_duration: self.max_duration,
+            options: self.options,
+            budget: self.budget.clone(),
+            retrying_since: Some(self.retrying_since.unwrap_or_else(Instant::now)),
             logic: self.logic.clone(),
         }
     }
@@ -80,12 +348,60 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
         self.current_duration
     }
 
-    fn build_retry(&self) -> RetryPolicyFuture<L> {
+    fn build_retry(&self) -> Option<RetryPolicyFuture<L>> {
+        if let (Some(max_elapsed_secs), Some(retrying_since)) =
+            (self.options.max_elapsed_secs, self.retrying_since)
+        {
+            if retrying_since.elapsed() >= Duration::from_secs(max_elapsed_secs) {
+                error!(
+                    message = "Retries exceeded the maximum elapsed time; dropping the request.",
+                    max_elapsed_secs
+                );
+                return None;
+            }
+        }
+
+        if let Some(budget) = &self.budget {
+            if budget.withdraw().is_err() {
+                error!(message = "Retry budget exhausted; dropping the request.");
+                return None;
+            }
+        }
+
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        let backoff = self.options.jitter.apply(self.backoff());
+        let delay = Box::pin(sleep(backoff));
+
+        debug!(message = "Retrying request.", delay_ms = %backoff.as_millis());
+        Some(RetryPolicyFuture { delay, policy })
+    }
+
+    fn response_action(&self, response: &L::Response) -> RetryAction {
+        let action = self.logic.should_retry_response(response);
+
+        match (
+            self.options.retryable_http_statuses,
+            self.logic.response_status_code(response),
+        ) {
+            (Some(statuses), Some(status)) if !(200..300).contains(&status) => {
+                if statuses.contains(status) {
+                    RetryAction::Retry(format!("response status: {}", status).into())
+                } else {
+                    RetryAction::DontRetry(format!("response status: {}", status).into())
+                }
+            }
+            _ => action,
+        }
+    }
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
-        RetryPolicyFuture { delay, policy }
+    fn is_retriable_error(&self, error: &L::Error) -> bool {
+        match (
+            self.options.retryable_grpc_codes,
+            self.logic.error_grpc_code(error),
+        ) {
+            (Some(codes), Some(code)) => codes.contains(code),
+            _ => self.logic.is_retriable_error(error),
+        }
     }
 }
 
@@ -97,8 +413,15 @@ where
     type Future = RetryPolicyFuture<L>;
 
     fn retry(&self, _: &Req, result: Result<&Res, &Error>) -> Option<Self::Future> {
+        // Every request, as opposed to every retry, pays into the budget.
+        if self.retrying_since.is_none() {
+            if let Some(budget) = &self.budget {
+                budget.deposit();
+            }
+        }
+
         match result {
-            Ok(response) => match self.logic.should_retry_response(response) {
+            Ok(response) => match self.response_action(response) {
                 RetryAction::Retry(reason) => {
                     if self.remaining_attempts == 0 {
                         error!(
@@ -109,7 +432,7 @@ where
                     }
 
                     warn!(message = "Retrying after response.", reason = %reason);
-                    Some(self.build_retry())
+                    self.build_retry()
                 }
 
                 RetryAction::DontRetry(reason) => {
@@ -126,9 +449,9 @@ where
                 }
 
                 if let Some(expected) = error.downcast_ref::<L::Error>() {
-                    if self.logic.is_retriable_error(expected) {
+                    if self.is_retriable_error(expected) {
                         warn!(message = "Retrying after error.", error = %expected);
-                        Some(self.build_retry())
+                        self.build_retry()
                     } else {
                         error!(
                             message = "Non-retriable error; dropping the request.",
@@ -138,7 +461,7 @@ where
                     }
                 } else if error.downcast_ref::<Elapsed>().is_some() {
                     warn!("Request timed out. If this happens often while the events are actually reaching their destination, try decreasing `batch.max_bytes` and/or using `compression` if applicable. Alternatively `request.timeout_secs` can be increased.");
-                    Some(self.build_retry())
+                    self.build_retry()
                 } else {
                     error!(
                         message = "Unexpected error type; dropping the request.",
@@ -380,6 +703,175 @@ mod tests {
         assert_eq!(Duration::from_secs(10), policy.backoff());
     }
 
+    #[test]
+    fn exponential_backoff_grows_to_max() {
+        let mut policy = FixedRetryPolicy::new(
+            10,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        )
+        .with_options(
+            RetryPolicyConfig {
+                backoff: RetryBackoff::Exponential,
+                ..Default::default()
+            },
+            None,
+        );
+        assert_eq!(Duration::from_secs(1), policy.backoff());
+
+        policy = policy.advance();
+        assert_eq!(Duration::from_secs(2), policy.backoff());
+
+        policy = policy.advance();
+        assert_eq!(Duration::from_secs(4), policy.backoff());
+
+        policy = policy.advance();
+        assert_eq!(Duration::from_secs(8), policy.backoff());
+
+        policy = policy.advance();
+        assert_eq!(Duration::from_secs(10), policy.backoff());
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let delay = Duration::from_millis(1000);
+        for _ in 0..100 {
+            assert!(RetryJitter::Full.apply(delay) <= delay);
+
+            let jittered = RetryJitter::Equal.apply(delay);
+            assert!(jittered >= delay / 2 && jittered <= delay);
+        }
+        assert_eq!(RetryJitter::None.apply(delay), delay);
+    }
+
+    #[tokio::test]
+    async fn retryable_http_statuses_override_logic() {
+        trace_init();
+
+        time::pause();
+
+        let options = RetryPolicyConfig {
+            retryable_http_statuses: Some(HttpStatusSet::try_from(vec![404]).unwrap()),
+            ..Default::default()
+        };
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            StatusRetryLogic,
+        )
+        .with_options(options, None);
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_response(404);
+        assert_pending!(fut.poll());
+
+        time::advance(Duration::from_secs(2)).await;
+        assert_pending!(fut.poll());
+
+        // The sink's own logic would retry this, but it isn't in the configured set.
+        assert_request_eq!(handle, "hello").send_response(503);
+        assert_eq!(fut.await.unwrap(), 503);
+    }
+
+    #[tokio::test]
+    async fn exhausted_budget_stops_retries() {
+        trace_init();
+
+        let budget = RetryBudgetConfig {
+            ratio: 0.0,
+            min_per_sec: 0,
+            ttl_secs: 1,
+        };
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        )
+        .with_options(RetryPolicyConfig::default(), Some(Arc::new(budget.build())));
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_error(Error(true));
+        assert_ready_err!(fut.poll());
+    }
+
+    #[tokio::test]
+    async fn max_elapsed_stops_retries() {
+        trace_init();
+
+        time::pause();
+
+        let options = RetryPolicyConfig {
+            max_elapsed_secs: Some(1),
+            ..Default::default()
+        };
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        )
+        .with_options(options, None);
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_error(Error(true));
+        assert_pending!(fut.poll());
+
+        time::advance(Duration::from_secs(2)).await;
+        assert_pending!(fut.poll());
+
+        assert_request_eq!(handle, "hello").send_error(Error(true));
+        assert_ready_err!(fut.poll());
+    }
+
+    #[test]
+    fn parses_retry_policy_config() {
+        let config = toml::from_str::<RetryPolicyConfig>(
+            r#"
+            max_elapsed_secs = 300
+            backoff = "exponential"
+            jitter = "full"
+            budget.ratio = 0.5
+            retryable_http_statuses = [408, 429, 503]
+            retryable_grpc_codes = [14]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.max_elapsed_secs, Some(300));
+        assert_eq!(config.backoff, RetryBackoff::Exponential);
+        assert_eq!(config.jitter, RetryJitter::Full);
+        assert_eq!(
+            config.budget,
+            Some(RetryBudgetConfig {
+                ratio: 0.5,
+                min_per_sec: 10,
+                ttl_secs: 10,
+            })
+        );
+        let statuses = config.retryable_http_statuses.unwrap();
+        assert!(statuses.contains(408) && statuses.contains(503));
+        assert!(!statuses.contains(500));
+        assert_eq!(Vec::from(statuses), vec![408, 429, 503]);
+        assert!(config.retryable_grpc_codes.unwrap().contains(14));
+
+        toml::from_str::<RetryPolicyConfig>("retryable_http_statuses = [700]").unwrap_err();
+        toml::from_str::<RetryPolicyConfig>("retryable_grpc_codes = [17]").unwrap_err();
+        toml::from_str::<RetryPolicyConfig>("budget.ttl_secs = 0").unwrap_err();
+        toml::from_str::<RetryPolicyConfig>("budget.ratio = -1.0").unwrap_err();
+    }
+
     #[derive(Debug, Clone)]
     struct SvcRetryLogic;
 
@@ -392,6 +884,32 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone)]
+    struct StatusRetryLogic;
+
+    impl RetryLogic for StatusRetryLogic {
+        type Error = Error;
+        type Response = u16;
+
+        fn is_retriable_error(&self, error: &Self::Error) -> bool {
+            error.0
+        }
+
+        fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+            if *response >= 500 {
+                RetryAction::Retry("server error".into())
+            } else if *response >= 400 {
+                RetryAction::DontRetry("client error".into())
+            } else {
+                RetryAction::Successful
+            }
+        }
+
+        fn response_status_code(&self, response: &Self::Response) -> Option<u16> {
+            Some(*response)
+        }
+    }
+
     #[derive(Debug)]
     struct Error(bool);
 
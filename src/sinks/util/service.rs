@@ -17,8 +17,9 @@ pub use crate::sinks::util::service::{
 use crate::sinks::util::{
     adaptive_concurrency::{
         AdaptiveConcurrencyLimit, AdaptiveConcurrencyLimitLayer, AdaptiveConcurrencySettings,
+        EndpointConcurrencyLimit, EndpointConcurrencyLimitLayer,
     },
-    retries::{FixedRetryPolicy, RetryLogic},
+    retries::{FixedRetryPolicy, RetryLogic, RetryPolicyConfig},
     service::map::MapLayer,
     sink::Response,
     Batch, BatchSink, Partition, PartitionBatchSink,
@@ -28,6 +29,8 @@ mod concurrency;
 mod map;
 
 pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
+pub type EndpointSvc<S, L> =
+    RateLimit<EndpointConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
 pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
 pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
 
@@ -41,6 +44,14 @@ pub trait ServiceBuilderExt<L> {
         settings: TowerRequestSettings,
         retry_logic: RL,
     ) -> ServiceBuilder<Stack<TowerRequestLayer<RL, Request>, L>>;
+
+    /// Like `settings`, with the concurrency limited separately for each
+    /// endpoint the requests are sent to.
+    fn endpoint_settings<RL, Request>(
+        self,
+        settings: TowerRequestSettings,
+        retry_logic: RL,
+    ) -> ServiceBuilder<Stack<TowerEndpointRequestLayer<RL, Request>, L>>;
 }
 
 impl<L> ServiceBuilderExt<L> for ServiceBuilder<L> {
@@ -62,6 +73,18 @@ impl<L> ServiceBuilderExt<L> for ServiceBuilder<L> {
             _pd: std::marker::PhantomData,
         })
     }
+
+    fn endpoint_settings<RL, Request>(
+        self,
+        settings: TowerRequestSettings,
+        retry_logic: RL,
+    ) -> ServiceBuilder<Stack<TowerEndpointRequestLayer<RL, Request>, L>> {
+        self.layer(TowerEndpointRequestLayer {
+            settings,
+            retry_logic,
+            _pd: std::marker::PhantomData,
+        })
+    }
 }
 
 /// Tower Request based configuration
@@ -76,6 +99,11 @@ pub struct TowerRequestConfig {
     pub retry_attempts: Option<usize>,         // isize::MAX
     pub retry_max_duration_secs: Option<u64>,
     pub retry_initial_backoff_secs: Option<u64>, // 1
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub retry: RetryPolicyConfig,
     #[serde(default)]
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
 }
@@ -104,6 +132,7 @@ impl TowerRequestConfig {
             retry_attempts: Some(RETRY_ATTEMPTS_DEFAULT),
             retry_max_duration_secs: Some(RETRY_MAX_DURATION_SECONDS_DEFAULT),
             retry_initial_backoff_secs: Some(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
+            retry: RetryPolicyConfig::const_default(),
             adaptive_concurrency: AdaptiveConcurrencySettings::const_default(),
         }
     }
@@ -173,6 +202,7 @@ impl TowerRequestConfig {
                     .or(defaults.retry_initial_backoff_secs)
                     .unwrap_or(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             ),
+            retry: self.retry,
             adaptive_concurrency: self.adaptive_concurrency,
         }
     }
@@ -187,17 +217,20 @@ pub struct TowerRequestSettings {
     pub retry_attempts: usize,
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
+    pub retry: RetryPolicyConfig,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
 }
 
 impl TowerRequestSettings {
     pub fn retry_policy<L: RetryLogic>(&self, logic: L) -> FixedRetryPolicy<L> {
+        let budget = self.retry.budget.map(|budget| Arc::new(budget.build()));
         FixedRetryPolicy::new(
             self.retry_attempts,
             self.retry_initial_backoff_secs,
             self.retry_max_duration_secs,
             logic,
         )
+        .with_options(self.retry, budget)
     }
 
     pub fn partition_sink<B, RL, S, K>(
@@ -285,6 +318,42 @@ where
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct TowerEndpointRequestLayer<L, Request> {
+    settings: TowerRequestSettings,
+    retry_logic: L,
+    _pd: PhantomData<Request>,
+}
+
+impl<S, RL, Request> Layer<S> for TowerEndpointRequestLayer<RL, Request>
+where
+    S: Service<Request> + Clone + Send + 'static,
+    S::Response: Send + 'static,
+    S::Error: Into<crate::Error> + Send + Sync + 'static,
+    S::Future: Send + 'static,
+    RL: RetryLogic<Response = S::Response> + Send + 'static,
+    Request: Clone + Send + 'static,
+{
+    type Service = EndpointSvc<S, RL>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        let policy = self.settings.retry_policy(self.retry_logic.clone());
+        ServiceBuilder::new()
+            .rate_limit(
+                self.settings.rate_limit_num,
+                self.settings.rate_limit_duration,
+            )
+            .layer(EndpointConcurrencyLimitLayer::new(
+                self.settings.concurrency,
+                self.settings.adaptive_concurrency,
+                self.retry_logic.clone(),
+            ))
+            .retry(policy)
+            .timeout(self.settings.timeout)
+            .service(inner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{
@@ -29,6 +29,7 @@ use crate::{
         ConnectionOpen, OpenGauge, SocketMode, TcpSocketConnectionError,
         TcpSocketConnectionEstablished, TcpSocketConnectionShutdown, TcpSocketError,
     },
+    proxy_protocol::ProxyProtocolVersion,
     sinks::{
         util::{
             encoding::Transformer,
@@ -60,6 +61,9 @@ pub struct TcpSinkConfig {
     keepalive: Option<TcpKeepaliveConfig>,
     tls: Option<TlsEnableableConfig>,
     send_buffer_bytes: Option<usize>,
+    /// The version of the PROXY protocol header to send at the start of each connection, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    proxy_protocol: Option<ProxyProtocolVersion>,
 }
 
 impl TcpSinkConfig {
@@ -74,6 +78,7 @@ impl TcpSinkConfig {
             keepalive,
             tls,
             send_buffer_bytes,
+            proxy_protocol: None,
         }
     }
 
@@ -83,6 +88,7 @@ impl TcpSinkConfig {
             keepalive: None,
             tls: None,
             send_buffer_bytes: None,
+            proxy_protocol: None,
         }
     }
 
@@ -96,7 +102,14 @@ impl TcpSinkConfig {
         let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
         let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
+        let connector = TcpConnector::new(
+            host,
+            port,
+            self.keepalive,
+            tls,
+            self.send_buffer_bytes,
+            self.proxy_protocol,
+        );
         let sink = TcpSink::new(connector.clone(), cx.acker(), transformer, encoder);
 
         Ok((
@@ -113,6 +126,7 @@ struct TcpConnector {
     keepalive: Option<TcpKeepaliveConfig>,
     tls: MaybeTlsSettings,
     send_buffer_bytes: Option<usize>,
+    proxy_protocol: Option<ProxyProtocolVersion>,
 }
 
 impl TcpConnector {
@@ -122,6 +136,7 @@ impl TcpConnector {
         keepalive: Option<TcpKeepaliveConfig>,
         tls: MaybeTlsSettings,
         send_buffer_bytes: Option<usize>,
+        proxy_protocol: Option<ProxyProtocolVersion>,
     ) -> Self {
         Self {
             host,
@@ -129,12 +144,13 @@ impl TcpConnector {
             keepalive,
             tls,
             send_buffer_bytes,
+            proxy_protocol,
         }
     }
 
     #[cfg(test)]
     fn from_host_port(host: String, port: u16) -> Self {
-        Self::new(host, port, None, None.into(), None)
+        Self::new(host, port, None, None.into(), None, None)
     }
 
     const fn fresh_backoff() -> ExponentialBackoff {
@@ -154,7 +170,7 @@ impl TcpConnector {
 
         let addr = SocketAddr::new(ip, self.port);
         self.tls
-            .connect(&self.host, &addr)
+            .connect(&self.host, &addr, self.proxy_protocol)
             .await
             .context(ConnectSnafu)
             .map(|mut maybe_tls| {
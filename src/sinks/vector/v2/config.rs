@@ -210,4 +210,11 @@ impl RetryLogic for VectorGrpcRetryLogic {
             _ => true,
         }
     }
+
+    fn error_grpc_code(&self, err: &Self::Error) -> Option<i32> {
+        match err {
+            VectorSinkError::Request { source } => Some(source.code() as i32),
+            _ => None,
+        }
+    }
 }
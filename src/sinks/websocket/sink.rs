@@ -113,7 +113,7 @@ impl WebSocketConnector {
 
         let addr = SocketAddr::new(ip, self.port);
         self.tls
-            .connect(&self.host, &addr)
+            .connect(&self.host, &addr, None)
             .await
             .context(ConnectSnafu)
     }
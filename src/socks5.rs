@@ -0,0 +1,251 @@
+//! The client side of the SOCKS5 protocol, with which connections to hosts are made through a
+//! proxy.
+//!
+//! See <https://www.rfc-editor.org/rfc/rfc1928> and, for authenticating with a username and
+//! password, <https://www.rfc-editor.org/rfc/rfc1929>.
+
+use std::{
+    io,
+    net::{IpAddr, SocketAddr},
+};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use vector_core::config::proxy::ProxyAuth;
+
+const VERSION: u8 = 5;
+const NO_AUTHENTICATION: u8 = 0;
+const USERNAME_PASSWORD: u8 = 2;
+const NO_ACCEPTABLE_METHODS: u8 = 0xff;
+const CONNECT: u8 = 1;
+
+/// The host to connect to through the proxy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TargetAddr {
+    /// An address resolved by Vector.
+    Ip(SocketAddr),
+    /// A host name for the proxy to resolve.
+    Domain(String, u16),
+}
+
+/// Asks the proxy at the other end of the stream to connect to the target, after which the stream
+/// carries the data of the connection.
+pub async fn connect<S>(
+    stream: &mut S,
+    target: &TargetAddr,
+    auth: Option<&ProxyAuth>,
+) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let method = if auth.is_some() {
+        USERNAME_PASSWORD
+    } else {
+        NO_AUTHENTICATION
+    };
+    stream.write_all(&[VERSION, 1, method]).await?;
+    let mut reply = [0; 2];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != VERSION {
+        return Err(invalid("invalid SOCKS5 version in proxy reply"));
+    }
+    match (reply[1], auth) {
+        (NO_AUTHENTICATION, _) => {}
+        (USERNAME_PASSWORD, Some(auth)) => authenticate(stream, auth).await?,
+        (NO_ACCEPTABLE_METHODS, _) => {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "SOCKS5 proxy requires an unsupported authentication method",
+            ))
+        }
+        _ => {
+            return Err(invalid(
+                "SOCKS5 proxy chose an unoffered authentication method",
+            ))
+        }
+    }
+
+    let mut request = vec![VERSION, CONNECT, 0];
+    let port = match target {
+        TargetAddr::Ip(addr) => {
+            match addr.ip() {
+                IpAddr::V4(ip) => {
+                    request.push(1);
+                    request.extend_from_slice(&ip.octets());
+                }
+                IpAddr::V6(ip) => {
+                    request.push(4);
+                    request.extend_from_slice(&ip.octets());
+                }
+            }
+            addr.port()
+        }
+        TargetAddr::Domain(host, port) => {
+            request.push(3);
+            request.push(length(host, "host name")?);
+            request.extend_from_slice(host.as_bytes());
+            *port
+        }
+    };
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut reply = [0; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != VERSION {
+        return Err(invalid("invalid SOCKS5 version in proxy reply"));
+    }
+    if reply[1] != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            format!("SOCKS5 proxy failed to connect: {}", reply_error(reply[1])),
+        ));
+    }
+    // The address the proxy bound to connect to the target, which isn't of any use here.
+    let bound_length = match reply[3] {
+        1 => 4,
+        4 => 16,
+        3 => stream.read_u8().await? as usize,
+        _ => return Err(invalid("invalid SOCKS5 address type in proxy reply")),
+    };
+    let mut bound = vec![0; bound_length + 2];
+    stream.read_exact(&mut bound).await?;
+    Ok(())
+}
+
+async fn authenticate<S>(stream: &mut S, auth: &ProxyAuth) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut request = vec![1, length(&auth.username, "username")?];
+    request.extend_from_slice(auth.username.as_bytes());
+    request.push(length(&auth.password, "password")?);
+    request.extend_from_slice(auth.password.as_bytes());
+    stream.write_all(&request).await?;
+
+    let mut reply = [0; 2];
+    stream.read_exact(&mut reply).await?;
+    if reply[1] != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "SOCKS5 proxy rejected the username and password",
+        ));
+    }
+    Ok(())
+}
+
+fn length(field: &str, name: &str) -> io::Result<u8> {
+    u8::try_from(field.len()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("SOCKS5 {} is longer than 255 bytes", name),
+        )
+    })
+}
+
+const fn reply_error(code: u8) -> &'static str {
+    match code {
+        1 => "general failure",
+        2 => "connection not allowed by ruleset",
+        3 => "network unreachable",
+        4 => "host unreachable",
+        5 => "connection refused",
+        6 => "TTL expired",
+        7 => "command not supported",
+        8 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::duplex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn connects_to_host_names() {
+        let (mut client, mut proxy) = duplex(1024);
+        let target = TargetAddr::Domain("example.com".into(), 443);
+        let handshake = tokio::spawn(async move {
+            connect(&mut client, &target, None).await?;
+            Ok::<_, io::Error>(client)
+        });
+
+        let mut greeting = [0; 3];
+        proxy.read_exact(&mut greeting).await.unwrap();
+        assert_eq!(greeting, [5, 1, 0]);
+        proxy.write_all(&[5, 0]).await.unwrap();
+
+        let mut request = [0; 18];
+        proxy.read_exact(&mut request).await.unwrap();
+        assert_eq!(request[..5], [5, 1, 0, 3, 11]);
+        assert_eq!(&request[5..16], b"example.com");
+        assert_eq!(request[16..], 443u16.to_be_bytes());
+        proxy
+            .write_all(&[5, 0, 0, 1, 127, 0, 0, 1, 0x1f, 0x90])
+            .await
+            .unwrap();
+        proxy.write_all(b"data").await.unwrap();
+
+        let mut client = handshake.await.unwrap().unwrap();
+        let mut data = [0; 4];
+        client.read_exact(&mut data).await.unwrap();
+        assert_eq!(&data, b"data");
+    }
+
+    #[tokio::test]
+    async fn authenticates_with_username_and_password() {
+        let (mut client, mut proxy) = duplex(1024);
+        let target = TargetAddr::Ip("192.0.2.1:80".parse().unwrap());
+        let auth = ProxyAuth {
+            username: "user".into(),
+            password: "pass".into(),
+        };
+        let handshake =
+            tokio::spawn(async move { connect(&mut client, &target, Some(&auth)).await });
+
+        let mut greeting = [0; 3];
+        proxy.read_exact(&mut greeting).await.unwrap();
+        assert_eq!(greeting, [5, 1, 2]);
+        proxy.write_all(&[5, 2]).await.unwrap();
+
+        let mut credentials = [0; 11];
+        proxy.read_exact(&mut credentials).await.unwrap();
+        assert_eq!(&credentials, b"\x01\x04user\x04pass");
+        proxy.write_all(&[1, 0]).await.unwrap();
+
+        let mut request = [0; 10];
+        proxy.read_exact(&mut request).await.unwrap();
+        assert_eq!(request, [5, 1, 0, 1, 192, 0, 2, 1, 0, 80]);
+        proxy
+            .write_all(&[5, 0, 0, 1, 127, 0, 0, 1, 0x1f, 0x90])
+            .await
+            .unwrap();
+
+        handshake.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn fails_when_the_proxy_refuses() {
+        let (mut client, mut proxy) = duplex(1024);
+        let target = TargetAddr::Domain("example.com".into(), 443);
+        let handshake = tokio::spawn(async move { connect(&mut client, &target, None).await });
+
+        let mut greeting = [0; 3];
+        proxy.read_exact(&mut greeting).await.unwrap();
+        proxy.write_all(&[5, 0]).await.unwrap();
+        let mut request = [0; 18];
+        proxy.read_exact(&mut request).await.unwrap();
+        proxy
+            .write_all(&[5, 5, 0, 1, 0, 0, 0, 0, 0, 0])
+            .await
+            .unwrap();
+
+        let error = handshake.await.unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionRefused);
+    }
+}
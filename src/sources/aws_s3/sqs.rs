@@ -1,4 +1,10 @@
-use std::{cmp, future::ready, panic, sync::Arc};
+use std::{
+    cmp,
+    future::ready,
+    panic,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use aws_sdk_s3::error::GetObjectError;
 use aws_sdk_s3::Client as S3Client;
@@ -18,12 +24,13 @@ use snafu::{ResultExt, Snafu};
 use tokio::{pin, select};
 use tokio_util::codec::FramedRead;
 use tracing::Instrument;
+use vector_common::internal_event::{AcknowledgementReceived, AcknowledgementTimedOut};
 use vector_core::ByteSizeOf;
 
 use crate::tls::TlsConfig;
 use crate::{
     config::{log_schema, AcknowledgementsConfig, SourceContext},
-    event::{BatchNotifier, BatchStatus, LogEvent},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, LogEvent},
     internal_events::{
         BytesReceived, OldEventsReceived, SqsMessageDeleteBatchError, SqsMessageDeletePartialError,
         SqsMessageDeleteSucceeded, SqsMessageProcessingError, SqsMessageProcessingSucceeded,
@@ -194,6 +201,7 @@ impl Ingestor {
         cx: SourceContext,
         acknowledgements: AcknowledgementsConfig,
     ) -> Result<(), ()> {
+        let acknowledgements_timeout = cx.acknowledgements_timeout(&acknowledgements);
         let acknowledgements = cx.do_acknowledgements(&acknowledgements);
         let mut handles = Vec::new();
         for _ in 0..self.state.client_concurrency {
@@ -202,6 +210,7 @@ impl Ingestor {
                 cx.out.clone(),
                 cx.shutdown.clone(),
                 acknowledgements,
+                acknowledgements_timeout,
             );
             let fut = process.run();
             let handle = tokio::spawn(fut.in_current_span());
@@ -227,6 +236,7 @@ pub struct IngestorProcess {
     out: SourceSender,
     shutdown: ShutdownSignal,
     acknowledgements: bool,
+    acknowledgements_timeout: Option<Duration>,
 }
 
 impl IngestorProcess {
@@ -235,12 +245,14 @@ impl IngestorProcess {
         out: SourceSender,
         shutdown: ShutdownSignal,
         acknowledgements: bool,
+        acknowledgements_timeout: Option<Duration>,
     ) -> Self {
         Self {
             state,
             out,
             shutdown,
             acknowledgements,
+            acknowledgements_timeout,
         }
     }
 
@@ -523,7 +535,7 @@ impl IngestorProcess {
         } else {
             match receiver {
                 None => Ok(()),
-                Some(receiver) => match receiver.await {
+                Some(receiver) => match self.wait_for_acknowledgement(receiver).await {
                     BatchStatus::Delivered => Ok(()),
                     BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
                     BatchStatus::Rejected => {
@@ -539,6 +551,28 @@ impl IngestorProcess {
         }
     }
 
+    /// Waits for the events of an object to be acknowledged, treating them as errored if that
+    /// takes longer than the acknowledgement timeout, so the SQS message becomes visible again
+    /// and the object is retried.
+    async fn wait_for_acknowledgement(&self, receiver: BatchStatusReceiver) -> BatchStatus {
+        let started = Instant::now();
+        let status = match self.acknowledgements_timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, receiver).await {
+                Ok(status) => status,
+                Err(_) => {
+                    emit!(AcknowledgementTimedOut { timeout });
+                    return BatchStatus::Errored;
+                }
+            },
+            None => receiver.await,
+        };
+        emit!(AcknowledgementReceived {
+            status: status.as_str(),
+            latency: started.elapsed(),
+        });
+        status
+    }
+
     async fn receive_messages(&mut self) -> Result<Vec<Message>, SdkError<ReceiveMessageError>> {
         self.state
             .sqs_client
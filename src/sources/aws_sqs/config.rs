@@ -61,6 +61,7 @@ impl SourceConfig for AwsSqsConfig {
         let client = self.build_client(&cx).await?;
         let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build();
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let acknowledgements_timeout = cx.acknowledgements_timeout(&self.acknowledgements);
 
         Ok(Box::pin(
             SqsSource {
@@ -72,6 +73,7 @@ impl SourceConfig for AwsSqsConfig {
                 visibility_timeout_secs: self.visibility_timeout_secs,
                 delete_message: self.delete_message,
                 acknowledgements,
+                acknowledgements_timeout,
             }
             .run(cx.out, cx.shutdown),
         ))
@@ -33,13 +33,15 @@ pub struct SqsSource {
     pub delete_message: bool,
     pub concurrency: u32,
     pub(super) acknowledgements: bool,
+    pub(super) acknowledgements_timeout: Option<Duration>,
 }
 
 impl SqsSource {
     pub async fn run(self, out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
         let mut task_handles = vec![];
         let finalizer = self.acknowledgements.then(|| {
-            let (finalizer, mut ack_stream) = Finalizer::new(shutdown.clone());
+            let (finalizer, mut ack_stream) =
+                Finalizer::new(shutdown.clone(), self.acknowledgements_timeout);
             let client = self.client.clone();
             let queue_url = self.queue_url.clone();
             tokio::spawn(async move {
@@ -156,7 +156,7 @@ impl SourceConfig for DatadogAgentConfig {
             // JSON deserializer can overwrite existing fields at runtime, so we have to treat
             // those events as if there is no known type details we can provide, other than the
             // details provided by the generic JSON schema definition.
-            DeserializerConfig::Json => self.decoding.schema_definition(),
+            DeserializerConfig::Json { .. } => self.decoding.schema_definition(),
 
             // Syslog deserializer allows for arbritrary "structured data" that can overwrite
             // existing fields, similar to the JSON deserializer.
@@ -1,4 +1,7 @@
-use std::task::Poll;
+mod replay;
+mod schema;
+
+use std::{path::PathBuf, task::Poll};
 
 use bytes::Bytes;
 use chrono::Utc;
@@ -8,13 +11,15 @@ use codecs::{
 };
 use fakedata::logs::*;
 use futures::StreamExt;
+use indexmap::IndexMap;
 use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
-use tokio::time::{self, Duration};
+use tokio::time::{self, Duration, Instant};
 use tokio_util::codec::FramedRead;
 use vector_core::ByteSizeOf;
 
+pub use self::schema::{FieldKind, FieldSchema};
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{log_schema, Output, SourceConfig, SourceContext, SourceDescription},
@@ -49,10 +54,35 @@ const fn default_count() -> usize {
     isize::MAX as usize
 }
 
-#[derive(Debug, PartialEq, Snafu)]
+#[derive(Debug, Snafu)]
 pub enum DemoLogsConfigError {
     #[snafu(display("A non-empty list of lines is required for the shuffle format"))]
     ShuffleDemoLogsItemsEmpty,
+    #[snafu(display("A non-empty table of fields is required for the schema format"))]
+    SchemaFieldsEmpty,
+    #[snafu(display(
+        "Field {:?} of the schema has an empty or inverted range of values",
+        field
+    ))]
+    InvalidSchemaField { field: String },
+    #[snafu(display("Template refers to field {:?}, which isn't in the schema", field))]
+    UnknownTemplateField { field: String },
+    #[snafu(display("The speed of the replay must be positive"))]
+    InvalidReplaySpeed,
+    #[snafu(display("Could not read replay file {:?}: {}", path, source))]
+    ReadReplay {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Replay file {:?} has no lines", path))]
+    ReplayEmpty { path: PathBuf },
+}
+
+// I/O errors can't be compared, so the errors are compared by their messages.
+impl PartialEq for DemoLogsConfigError {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_string() == other.to_string()
+    }
 }
 
 #[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
@@ -72,6 +102,28 @@ pub enum OutputFormat {
     BsdSyslog,
     #[derivative(Default)]
     Json,
+    /// Lines generated from the given fields, as JSON objects or rendered in a template.
+    Schema {
+        fields: IndexMap<String, FieldSchema>,
+        #[serde(default)]
+        template: Option<String>,
+    },
+    /// The lines of a capture of NDJSON events, replayed with their original timing.
+    Replay {
+        path: PathBuf,
+        #[serde(default = "default_timestamp_field")]
+        timestamp_field: String,
+        #[serde(default = "default_speed")]
+        speed: f64,
+    },
+}
+
+fn default_timestamp_field() -> String {
+    "timestamp".to_owned()
+}
+
+const fn default_speed() -> f64 {
+    1.0
 }
 
 impl OutputFormat {
@@ -88,6 +140,8 @@ impl OutputFormat {
             Self::Syslog => syslog_5424_log_line(),
             Self::BsdSyslog => syslog_3164_log_line(),
             Self::Json => json_log_line(),
+            Self::Schema { fields, template } => schema::generate_line(fields, template.as_deref()),
+            Self::Replay { .. } => unreachable!("replayed lines aren't generated"),
         }
     }
 
@@ -102,7 +156,8 @@ impl OutputFormat {
         }
     }
 
-    // Ensures that the `lines` list is non-empty if `Shuffle` is chosen
+    // Ensures that the `lines` list is non-empty if `Shuffle` is chosen, and that the schema or
+    // the replay are sound
     pub(self) fn validate(&self) -> Result<(), DemoLogsConfigError> {
         match self {
             Self::Shuffle { lines, .. } => {
@@ -112,6 +167,10 @@ impl OutputFormat {
                     Ok(())
                 }
             }
+            Self::Schema { fields, template } => schema::validate(fields, template.as_deref()),
+            Self::Replay { speed, .. } if *speed <= 0.0 || !speed.is_finite() => {
+                Err(DemoLogsConfigError::InvalidReplaySpeed)
+            }
             _ => Ok(()),
         }
     }
@@ -159,36 +218,80 @@ async fn demo_logs_source(
         });
 
         let line = format.generate_line(n);
+        send_line(&line, &decoder, &mut out).await?;
+    }
 
-        let mut stream = FramedRead::new(line.as_bytes(), decoder.clone());
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
-                    let count = events.len();
-                    emit!(EventsReceived {
-                        count,
-                        byte_size: events.size_of()
-                    });
-                    let now = Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        let log = event.as_mut_log();
-
-                        log.try_insert(log_schema().source_type_key(), Bytes::from("demo_logs"));
-                        log.try_insert(log_schema().timestamp_key(), now);
-
-                        event
-                    });
-                    out.send_batch(events).await.map_err(|error| {
-                        emit!(StreamClosedError { error, count });
-                    })?;
-                }
-                Err(error) => {
-                    // Error is logged by `crate::codecs::Decoder`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
-                    }
+    Ok(())
+}
+
+/// Replays the lines of a capture, over and over until `count` lines are sent, waiting between
+/// them as long as they originally were apart, divided by the speed of the replay.
+async fn replay_source(
+    lines: Vec<replay::ReplayedLine>,
+    speed: f64,
+    count: usize,
+    decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    // The lines are sent at their scheduled times, rather than after the delays, so that the
+    // time taken to send them doesn't add up.
+    let mut scheduled = Instant::now();
+    let mut previous: Option<&replay::ReplayedLine> = None;
+
+    for line in lines.iter().cycle().take(count) {
+        if let Some(previous) = previous {
+            scheduled += Duration::from_secs_f64(replay::delay(previous, line, speed));
+            tokio::select! {
+                _ = time::sleep_until(scheduled) => {},
+                _ = &mut shutdown => break,
+            }
+        }
+        if matches!(futures::poll!(&mut shutdown), Poll::Ready(_)) {
+            break;
+        }
+        previous = Some(line);
+
+        emit!(DemoLogsEventProcessed);
+        emit!(BytesReceived {
+            byte_size: 0,
+            protocol: "none",
+        });
+        send_line(&line.line, &decoder, &mut out).await?;
+    }
+
+    Ok(())
+}
+
+async fn send_line(line: &str, decoder: &Decoder, out: &mut SourceSender) -> Result<(), ()> {
+    let mut stream = FramedRead::new(line.as_bytes(), decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                let count = events.len();
+                emit!(EventsReceived {
+                    count,
+                    byte_size: events.size_of()
+                });
+                let now = Utc::now();
+
+                let events = events.into_iter().map(|mut event| {
+                    let log = event.as_mut_log();
+
+                    log.try_insert(log_schema().source_type_key(), Bytes::from("demo_logs"));
+                    log.try_insert(log_schema().timestamp_key(), now);
+
+                    event
+                });
+                out.send_batch(events).await.map_err(|error| {
+                    emit!(StreamClosedError { error, count });
+                })?;
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs::Decoder`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
                 }
             }
         }
@@ -213,6 +316,22 @@ impl SourceConfig for DemoLogsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         self.format.validate()?;
         let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build();
+        if let OutputFormat::Replay {
+            path,
+            timestamp_field,
+            speed,
+        } = &self.format
+        {
+            let lines = replay::load(path, timestamp_field)?;
+            return Ok(Box::pin(replay_source(
+                lines,
+                *speed,
+                self.count,
+                decoder,
+                cx.shutdown,
+                cx.out,
+            )));
+        }
         Ok(Box::pin(demo_logs_source(
             self.interval,
             self.count,
@@ -470,4 +589,94 @@ mod tests {
         }
         assert_eq!(poll!(rx.next()), Poll::Ready(None));
     }
+
+    #[tokio::test]
+    async fn schema_format_generates_output() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "schema"
+            count = 5
+            template = "{{ host }} {{ status }}"
+            fields.host = { type = "ipv4", cardinality = 1 }
+            fields.status = { type = "one_of", values = [200] }"#,
+        )
+        .await;
+
+        let mut hosts = Vec::new();
+        for _ in 0..5 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let message = event.as_log()[&message_key].to_string_lossy();
+            let (host, status) = message.split_once(' ').unwrap();
+            assert_eq!(status, "200");
+            hosts.push(host.to_owned());
+        }
+        hosts.dedup();
+        assert_eq!(hosts.len(), 1);
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[test]
+    fn config_replay_speed_positive() {
+        let config: DemoLogsConfig = toml::from_str(
+            r#"format = "replay"
+            path = "capture.ndjson"
+            speed = 0.0"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.format.validate(),
+            Err(DemoLogsConfigError::InvalidReplaySpeed)
+        );
+    }
+
+    #[tokio::test]
+    async fn replay_format_keeps_timing() {
+        let message_key = log_schema().message_key();
+        let path = crate::test_util::temp_file();
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"timestamp":"2022-06-01T12:00:00Z","message":"first"}"#,
+                "\n",
+                r#"{"timestamp":"2022-06-01T12:00:01Z","message":"second"}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        let (tx, mut rx) = SourceSender::new_test();
+        let decoder = DecodingConfig::new(
+            default_framing_message_based(),
+            DeserializerConfig::Json {
+                json: Default::default(),
+            },
+        )
+        .build();
+        replay_source(
+            replay::load(&path, "timestamp").unwrap(),
+            4.0,
+            4,
+            decoder,
+            ShutdownSignal::noop(),
+            tx,
+        )
+        .await
+        .unwrap();
+
+        // The capture starts over right away once it's replayed.
+        assert!(start.elapsed() >= Duration::from_millis(500));
+        for expected in ["first", "second", "first", "second"] {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            assert_eq!(event.as_log()[&message_key].to_string_lossy(), expected);
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
 }
@@ -0,0 +1,93 @@
+use std::path::Path;
+
+use chrono::DateTime;
+use snafu::ResultExt;
+
+use super::{DemoLogsConfigError, ReadReplaySnafu};
+
+/// A line of a replayed capture, along with the time it was originally captured at, in
+/// seconds since the Unix epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ReplayedLine {
+    pub(super) time: Option<f64>,
+    pub(super) line: String,
+}
+
+/// Reads a capture of NDJSON events, taking the time each event was captured at from the given
+/// field, either an RFC 3339 timestamp or a number of seconds since the Unix epoch.
+pub(super) fn load(
+    path: &Path,
+    timestamp_field: &str,
+) -> Result<Vec<ReplayedLine>, DemoLogsConfigError> {
+    let content = std::fs::read_to_string(path).context(ReadReplaySnafu { path })?;
+    let lines = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| ReplayedLine {
+            time: serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .and_then(|event| capture_time(&event, timestamp_field)),
+            line: line.to_owned(),
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return Err(DemoLogsConfigError::ReplayEmpty {
+            path: path.to_owned(),
+        });
+    }
+    Ok(lines)
+}
+
+fn capture_time(event: &serde_json::Value, timestamp_field: &str) -> Option<f64> {
+    let value = timestamp_field
+        .split('.')
+        .try_fold(event, |value, segment| value.get(segment))?;
+    match value {
+        serde_json::Value::String(timestamp) => DateTime::parse_from_rfc3339(timestamp)
+            .ok()
+            .map(|timestamp| timestamp.timestamp_nanos() as f64 / 1e9),
+        value => value.as_f64(),
+    }
+}
+
+/// The time to wait between sending two lines, scaled by the speed of the replay, or none if
+/// either wasn't timed or they are out of order, as when the capture starts over.
+pub(super) fn delay(previous: &ReplayedLine, next: &ReplayedLine, speed: f64) -> f64 {
+    match (previous.time, next.time) {
+        (Some(previous), Some(next)) if next > previous => (next - previous) / speed,
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_capture_times() {
+        let path = crate::test_util::temp_file();
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"timestamp":"2022-06-01T12:00:00Z","message":"first"}"#,
+                "\n\n",
+                r#"{"timestamp":"2022-06-01T12:00:01.5Z","message":"second"}"#,
+                "\n",
+                "not json\n",
+            ),
+        )
+        .unwrap();
+
+        let lines = load(&path, "timestamp").unwrap();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].time, Some(1654084800.0));
+        assert_eq!(lines[2].time, None);
+        assert_eq!(delay(&lines[0], &lines[1], 1.0), 1.5);
+        assert_eq!(delay(&lines[0], &lines[1], 3.0), 0.5);
+        assert_eq!(delay(&lines[1], &lines[0], 1.0), 0.0);
+        assert_eq!(delay(&lines[1], &lines[2], 1.0), 0.0);
+
+        let event = serde_json::json!({ "http": { "time": 12.25 } });
+        assert_eq!(capture_time(&event, "http.time"), Some(12.25));
+    }
+}
@@ -0,0 +1,263 @@
+use std::fmt::Write;
+
+use chrono::{Duration, Utc};
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::DemoLogsConfigError;
+
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*([^{}\s]+)\s*\}\}").unwrap());
+
+const fn default_length() -> usize {
+    8
+}
+
+const fn default_max() -> i64 {
+    i64::MAX
+}
+
+const fn default_float_max() -> f64 {
+    1.0
+}
+
+/// How the values of a field of the `schema` format are generated.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FieldSchema {
+    #[serde(flatten)]
+    pub kind: FieldKind,
+    /// The number of distinct values of the field, which are otherwise drawn at random.
+    #[serde(default)]
+    pub cardinality: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FieldKind {
+    String {
+        #[serde(default = "default_length")]
+        length: usize,
+    },
+    Integer {
+        #[serde(default)]
+        min: i64,
+        #[serde(default = "default_max")]
+        max: i64,
+    },
+    Float {
+        #[serde(default)]
+        min: f64,
+        #[serde(default = "default_float_max")]
+        max: f64,
+    },
+    Boolean,
+    /// The current time, shifted at random by up to `skew_secs` seconds either way.
+    Timestamp {
+        #[serde(default)]
+        skew_secs: f64,
+        /// A `strftime` format, RFC 3339 by default.
+        #[serde(default)]
+        format: Option<String>,
+    },
+    Ipv4,
+    OneOf {
+        values: Vec<serde_json::Value>,
+    },
+}
+
+/// Checks the fields and the template of the `schema` format.
+pub(super) fn validate(
+    fields: &IndexMap<String, FieldSchema>,
+    template: Option<&str>,
+) -> Result<(), DemoLogsConfigError> {
+    if fields.is_empty() {
+        return Err(DemoLogsConfigError::SchemaFieldsEmpty);
+    }
+    for (name, field) in fields {
+        let valid = match &field.kind {
+            FieldKind::Integer { min, max } => min <= max,
+            FieldKind::Float { min, max } => min <= max,
+            FieldKind::Timestamp { skew_secs, .. } => *skew_secs >= 0.0,
+            FieldKind::OneOf { values } => !values.is_empty(),
+            _ => true,
+        };
+        if !valid || field.cardinality == Some(0) {
+            return Err(DemoLogsConfigError::InvalidSchemaField {
+                field: name.clone(),
+            });
+        }
+    }
+    if let Some(template) = template {
+        for captures in PLACEHOLDER.captures_iter(template) {
+            if !fields.contains_key(&captures[1]) {
+                return Err(DemoLogsConfigError::UnknownTemplateField {
+                    field: captures[1].to_owned(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Generates a line with a value for each of the fields, as a JSON object, or rendered in the
+/// template.
+pub(super) fn generate_line(
+    fields: &IndexMap<String, FieldSchema>,
+    template: Option<&str>,
+) -> String {
+    let mut rng = rand::thread_rng();
+    let values = fields
+        .values()
+        .enumerate()
+        .map(|(index, field)| match field.cardinality {
+            // The values are drawn from a fixed set by seeding them with their index in the set.
+            Some(cardinality) => {
+                let seed = ((index as u64) << 48) | rng.gen_range(0..cardinality);
+                field.kind.generate(&mut SmallRng::seed_from_u64(seed))
+            }
+            None => field.kind.generate(&mut rng),
+        })
+        .collect::<Vec<_>>();
+
+    match template {
+        Some(template) => PLACEHOLDER
+            .replace_all(template, |captures: &regex::Captures| {
+                let index = fields.get_index_of(&captures[1]).expect("validated field");
+                match &values[index] {
+                    serde_json::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                }
+            })
+            .into_owned(),
+        None => {
+            let mut line = String::from("{");
+            for (i, (name, value)) in fields.keys().zip(values).enumerate() {
+                if i > 0 {
+                    line.push(',');
+                }
+                let name = serde_json::Value::from(name.as_str());
+                write!(line, "{}:{}", name, value).expect("writing to a string");
+            }
+            line.push('}');
+            line
+        }
+    }
+}
+
+impl FieldKind {
+    fn generate<R: Rng>(&self, rng: &mut R) -> serde_json::Value {
+        match self {
+            Self::String { length } => rng
+                .sample_iter(rand::distributions::Alphanumeric)
+                .take(*length)
+                .map(char::from)
+                .collect::<String>()
+                .into(),
+            Self::Integer { min, max } => rng.gen_range(*min..=*max).into(),
+            Self::Float { min, max } if min < max => rng.gen_range(*min..*max).into(),
+            Self::Float { min, .. } => (*min).into(),
+            Self::Boolean => rng.gen::<bool>().into(),
+            Self::Timestamp { skew_secs, format } => {
+                let skew = skew_secs * rng.gen_range(-1.0..=1.0);
+                let timestamp = Utc::now() + Duration::milliseconds((skew * 1000.0) as i64);
+                match format {
+                    Some(format) => timestamp.format(format).to_string().into(),
+                    None => timestamp.to_rfc3339().into(),
+                }
+            }
+            Self::Ipv4 => std::net::Ipv4Addr::from(rng.gen::<u32>())
+                .to_string()
+                .into(),
+            Self::OneOf { values } => values.choose(rng).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn fields(config: &str) -> IndexMap<String, FieldSchema> {
+        toml::from_str(config).unwrap()
+    }
+
+    #[test]
+    fn generates_json_objects() {
+        let fields = fields(
+            r#"
+            status = { type = "one_of", values = [200, 404] }
+            user = { type = "string", length = 4 }
+            ratio = { type = "float", min = 0.5, max = 0.75 }
+            "#,
+        );
+        validate(&fields, None).unwrap();
+
+        let line = generate_line(&fields, None);
+        assert!(line.starts_with(r#"{"status":"#));
+        let object: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert!(object["status"] == 200 || object["status"] == 404);
+        assert_eq!(object["user"].as_str().unwrap().len(), 4);
+        let ratio = object["ratio"].as_f64().unwrap();
+        assert!((0.5..0.75).contains(&ratio));
+    }
+
+    #[test]
+    fn limits_cardinality() {
+        let fields = fields(
+            r#"
+            user = { type = "string", cardinality = 3 }
+            host = { type = "ipv4", cardinality = 2 }
+            "#,
+        );
+
+        let mut users = HashSet::new();
+        let mut hosts = HashSet::new();
+        for _ in 0..200 {
+            let line = generate_line(&fields, Some("{{ user }} {{host}}"));
+            let (user, host) = line.split_once(' ').unwrap();
+            users.insert(user.to_owned());
+            hosts.insert(host.to_owned());
+        }
+        assert_eq!(users.len(), 3);
+        assert_eq!(hosts.len(), 2);
+        assert!(users.iter().all(|user| user.len() == 8));
+    }
+
+    #[test]
+    fn skews_timestamps() {
+        let fields = fields(r#"time = { type = "timestamp", skew_secs = 60.0 }"#);
+        for _ in 0..20 {
+            let line = generate_line(&fields, Some("{{ time }}"));
+            let time = chrono::DateTime::parse_from_rfc3339(&line).unwrap();
+            let skew = (Utc::now() - time.with_timezone(&Utc)).num_seconds().abs();
+            assert!(skew <= 61, "{} is skewed by {}s", line, skew);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        assert_eq!(
+            validate(&IndexMap::new(), None),
+            Err(DemoLogsConfigError::SchemaFieldsEmpty)
+        );
+        assert_eq!(
+            validate(
+                &fields(r#"n = { type = "integer", min = 2, max = 1 }"#),
+                None
+            ),
+            Err(DemoLogsConfigError::InvalidSchemaField {
+                field: "n".to_owned()
+            })
+        );
+        assert_eq!(
+            validate(&fields(r#"n = { type = "integer" }"#), Some("{{ m }}")),
+            Err(DemoLogsConfigError::UnknownTemplateField {
+                field: "m".to_owned()
+            })
+        );
+    }
+}
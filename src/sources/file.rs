@@ -18,6 +18,7 @@ use vector_core::finalizer::OrderedFinalizer;
 
 use super::util::{EncodingConfig, MultilineConfig};
 use crate::{
+    checkpoint::CheckpointMirror,
     config::{
         log_schema, AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext,
         SourceDescription,
@@ -96,6 +97,7 @@ pub struct FileConfig {
     pub remove_after_secs: Option<u64>,
     pub line_delimiter: String,
     pub encoding: Option<EncodingConfig>,
+    pub io_uring: bool,
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
 }
@@ -200,6 +202,7 @@ impl Default for FileConfig {
             remove_after_secs: None,
             line_delimiter: "\n".to_string(),
             encoding: None,
+            io_uring: false,
             acknowledgements: Default::default(),
         }
     }
@@ -237,15 +240,31 @@ impl SourceConfig for FileConfig {
             }
         }
 
+        let mirror = CheckpointMirror::new(
+            &cx.globals,
+            &cx.key,
+            data_dir.join(file_source::CHECKPOINT_FILE_NAME),
+        )
+        .await?;
+        if let Some(mirror) = &mirror {
+            mirror.restore().await?;
+        }
+
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let acknowledgements_timeout = cx.acknowledgements_timeout(&self.acknowledgements);
 
-        Ok(file_source(
+        let source = file_source(
             self,
             data_dir,
             cx.shutdown,
             cx.out,
             acknowledgements,
-        ))
+            acknowledgements_timeout,
+        );
+        Ok(match mirror {
+            Some(mirror) => mirror.wrap(source),
+            None => source,
+        })
     }
 
     fn outputs(&self) -> Vec<Output> {
@@ -267,6 +286,7 @@ pub fn file_source(
     shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
+    acknowledgements_timeout: Option<Duration>,
 ) -> super::Source {
     let ignore_before = config
         .ignore_older_secs
@@ -315,6 +335,7 @@ pub fn file_source(
         remove_after: config.remove_after_secs.map(Duration::from_secs),
         emitter: FileSourceInternalEventsEmitter,
         handle: tokio::runtime::Handle::current(),
+        io_uring: config.io_uring,
     };
 
     let file_key = config.file_key.clone();
@@ -342,7 +363,8 @@ pub fn file_source(
         // The shutdown sent in to the finalizer is the global
         // shutdown handle used to tell it to stop accepting new batch
         // statuses and just wait for the remaining acks to come in.
-        let (finalizer, mut ack_stream) = OrderedFinalizer::<FinalizerEntry>::new(shutdown.clone());
+        let (finalizer, mut ack_stream) =
+            OrderedFinalizer::<FinalizerEntry>::new(shutdown.clone(), acknowledgements_timeout);
         // We set up a separate shutdown signal to tie together the
         // finalizer and the checkpoint writer task in the file
         // server, to make it continue to write out updated
@@ -1829,7 +1851,7 @@ mod tests {
                         )
                 })
             };
-            tokio::spawn(file::file_source(config, data_dir, shutdown, tx, acks));
+            tokio::spawn(file::file_source(config, data_dir, shutdown, tx, acks, None));
 
             inner.await;
 
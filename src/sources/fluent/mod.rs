@@ -35,6 +35,8 @@ pub struct FluentConfig {
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
     connection_limit: Option<u32>,
+    #[serde(default)]
+    proxy_protocol: bool,
 }
 
 inventory::submit! {
@@ -50,6 +52,7 @@ impl GenerateConfig for FluentConfig {
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: Some(2),
+            proxy_protocol: false,
         })
         .unwrap()
     }
@@ -71,6 +74,7 @@ impl SourceConfig for FluentConfig {
             cx,
             self.acknowledgements,
             self.connection_limit,
+            self.proxy_protocol,
         )
     }
 
@@ -857,6 +861,7 @@ mod tests {
             receive_buffer_bytes: None,
             acknowledgements: true.into(),
             connection_limit: None,
+            proxy_protocol: false,
         }
         .build(SourceContext::new_test(sender, None))
         .await
@@ -1106,6 +1111,7 @@ mod integration_tests {
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
+                proxy_protocol: false,
             }
             .build(SourceContext::new_test(sender, None))
             .await
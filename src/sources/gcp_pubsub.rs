@@ -160,6 +160,7 @@ impl SourceConfig for PubsubConfig {
             ),
             decoder: DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build(),
             acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            acknowledgements_timeout: cx.acknowledgements_timeout(&self.acknowledgements),
             tls: TlsSettings::from_options(&self.tls)?,
             shutdown: cx.shutdown,
             out: cx.out,
@@ -194,6 +195,7 @@ struct PubsubSource {
     subscription: String,
     decoder: Decoder,
     acknowledgements: bool,
+    acknowledgements_timeout: Option<Duration>,
     tls: TlsSettings,
     ack_deadline_seconds: i32,
     shutdown: ShutdownSignal,
@@ -289,8 +291,11 @@ impl PubsubSource {
         };
         let mut stream = stream.into_inner();
 
-        let (finalizer, mut ack_stream) =
-            Finalizer::maybe_new(self.acknowledgements, self.shutdown.clone());
+        let (finalizer, mut ack_stream) = Finalizer::maybe_new(
+            self.acknowledgements,
+            self.shutdown.clone(),
+            self.acknowledgements_timeout,
+        );
 
         loop {
             tokio::select! {
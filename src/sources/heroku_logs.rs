@@ -99,7 +99,8 @@ impl SourceConfig for LogplexConfig {
             decoder,
         };
         source.run(
-            self.address,
+            self.address.into(),
+            None,
             "events",
             HttpMethod::Post,
             true,
@@ -15,10 +15,14 @@ use tokio::{
 };
 use vector_common::btreemap;
 
-use super::{filter_result_sync, FilterList, HostMetrics};
+use super::{
+    filter_result_sync,
+    pressure::{self, PressureError, PressureStat},
+    FilterList, HostMetrics,
+};
 use crate::event::metric::Metric;
 
-const MICROSECONDS: f64 = 1.0 / 1_000_000.0;
+pub(super) const MICROSECONDS: f64 = 1.0 / 1_000_000.0;
 
 #[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
 #[derivative(Default)]
@@ -47,6 +51,11 @@ enum CGroupsError {
         filename: PathBuf,
         source: ParseIntError,
     },
+    #[snafu(display("Could not parse cgroup pressure file {:?}.", filename))]
+    ParsingPressure {
+        filename: PathBuf,
+        source: PressureError,
+    },
 }
 
 type CGroupsResult<T> = Result<T, CGroupsError>;
@@ -72,10 +81,13 @@ impl HostMetrics {
         buffer: &'a mut String,
     ) -> BoxFuture<'a, ()> {
         Box::pin(async move {
-            let tags = btreemap! {
+            let mut tags = btreemap! {
                 "cgroup" => cgroup.name.to_string_lossy(),
                 "collector" => "cgroups",
             };
+            if let Some(container_id) = cgroup.container_id() {
+                tags.insert("container_id".into(), container_id);
+            }
             if let Some(cpu) = filter_result_sync(
                 cgroup.load_cpu(buffer).await,
                 "Failed to load cgroups CPU statistics.",
@@ -98,6 +110,20 @@ impl HostMetrics {
                     cpu.system_usec as f64 * MICROSECONDS,
                     tags.clone(),
                 ));
+                if !cgroup.is_root() {
+                    result.push(self.counter(
+                        "cgroup_cpu_throttled_periods_total",
+                        now,
+                        cpu.nr_throttled as f64,
+                        tags.clone(),
+                    ));
+                    result.push(self.counter(
+                        "cgroup_cpu_throttled_seconds_total",
+                        now,
+                        cpu.throttled_usec as f64 * MICROSECONDS,
+                        tags.clone(),
+                    ));
+                }
             }
 
             if cgroup.has_memory_controller && !cgroup.is_root() {
@@ -127,9 +153,68 @@ impl HostMetrics {
                         "cgroup_memory_file_bytes",
                         now,
                         stat.file as f64,
-                        tags,
+                        tags.clone(),
+                    ));
+                }
+
+                // Unlimited groups have no maximum to report.
+                if let Some(Some(max)) = filter_result_sync(
+                    cgroup.load_memory_max(buffer).await,
+                    "Failed to load cgroups memory limit.",
+                ) {
+                    result.push(self.gauge(
+                        "cgroup_memory_max_bytes",
+                        now,
+                        max as f64,
+                        tags.clone(),
                     ));
                 }
+
+                if let Some(events) = filter_result_sync(
+                    cgroup.load_memory_events(buffer).await,
+                    "Failed to load cgroups memory events.",
+                ) {
+                    result.push(self.counter(
+                        "cgroup_memory_oom_kills_total",
+                        now,
+                        events.oom_kill as f64,
+                        tags.clone(),
+                    ));
+                }
+            }
+
+            // The I/O controller and pressure stall information are only accounted for
+            // where the kernel enables them, and their files are missing otherwise.
+            if cgroup.has_file(IO_STAT) {
+                if let Some(devices) = filter_result_sync(
+                    cgroup.load_io_stat(buffer).await,
+                    "Failed to load cgroups I/O statistics.",
+                ) {
+                    for io in devices {
+                        let mut tags = tags.clone();
+                        tags.insert("device".into(), io.device);
+                        for (name, value) in [
+                            ("cgroup_io_read_bytes_total", io.rbytes),
+                            ("cgroup_io_written_bytes_total", io.wbytes),
+                            ("cgroup_io_reads_completed_total", io.rios),
+                            ("cgroup_io_writes_completed_total", io.wios),
+                        ] {
+                            result.push(self.counter(name, now, value as f64, tags.clone()));
+                        }
+                    }
+                }
+            }
+
+            for resource in pressure::RESOURCES {
+                let filename = format!("{}.pressure", resource);
+                if cgroup.has_file(&filename) {
+                    if let Some(stats) = filter_result_sync(
+                        cgroup.load_pressure(&filename, buffer).await,
+                        "Failed to load cgroups pressure stall information.",
+                    ) {
+                        self.push_pressure(result, "cgroup_pressure", now, resource, &stats, &tags);
+                    }
+                }
             }
 
             if level < self.config.cgroups.levels {
@@ -157,6 +242,8 @@ pub(super) struct CGroup {
 
 const CGROUP_CONTROLLERS: &str = "cgroup.controllers";
 
+const IO_STAT: &str = "io.stat";
+
 impl CGroup {
     pub(super) fn root<P: AsRef<Path>>(base_group: Option<P>) -> Option<CGroup> {
         // There are three standard possibilities for cgroups setups
@@ -233,6 +320,20 @@ impl CGroup {
         self.name == Path::new("/")
     }
 
+    fn has_file(&self, filename: impl AsRef<Path>) -> bool {
+        is_file(self.make_path(filename))
+    }
+
+    /// The ID of the container the group is for, as named by Docker, containerd, CRI-O and
+    /// Podman under either the `systemd` or the `cgroupfs` driver, i.e. `docker-<id>.scope` or
+    /// `<id>`.
+    fn container_id(&self) -> Option<String> {
+        let name = self.name.file_name()?.to_str()?;
+        let name = name.strip_suffix(".scope").unwrap_or(name);
+        let id = name.rsplit('-').next()?;
+        (id.len() == 64 && id.bytes().all(|byte| byte.is_ascii_hexdigit())).then(|| id.to_owned())
+    }
+
     async fn load_cpu(&self, buffer: &mut String) -> CGroupsResult<CpuStat> {
         self.open_read_parse("cpu.stat", buffer).await
     }
@@ -281,6 +382,35 @@ impl CGroup {
         self.open_read_parse("memory.stat", buffer).await
     }
 
+    async fn load_memory_max(&self, buffer: &mut String) -> CGroupsResult<Option<u64>> {
+        let filename = self.open_read("memory.max", buffer).await?;
+        match buffer.trim() {
+            "max" => Ok(None),
+            max => max
+                .parse()
+                .map(Some)
+                .with_context(|_| ParsingSnafu { filename }),
+        }
+    }
+
+    async fn load_memory_events(&self, buffer: &mut String) -> CGroupsResult<MemoryEvents> {
+        self.open_read_parse("memory.events", buffer).await
+    }
+
+    async fn load_io_stat(&self, buffer: &mut String) -> CGroupsResult<Vec<IoStat>> {
+        let filename = self.open_read(IO_STAT, buffer).await?;
+        parse_io_stat(buffer).with_context(|_| ParsingSnafu { filename })
+    }
+
+    async fn load_pressure(
+        &self,
+        filename: &str,
+        buffer: &mut String,
+    ) -> CGroupsResult<Vec<PressureStat>> {
+        let filename = self.open_read(filename, buffer).await?;
+        pressure::parse_pressure(buffer).with_context(|_| ParsingPressureSnafu { filename })
+    }
+
     async fn children(&self) -> io::Result<Vec<CGroup>> {
         let mut result = Vec::new();
         let mut dir = fs::read_dir(&self.root).await?;
@@ -340,6 +470,9 @@ define_stat_struct! { CpuStat(
     usage_usec,
     user_usec,
     system_usec,
+    // Only present where the CPU controller is enabled.
+    nr_throttled,
+    throttled_usec,
 )}
 
 define_stat_struct! { MemoryStat(
@@ -352,6 +485,47 @@ define_stat_struct! { MemoryStat(
     file,
 )}
 
+define_stat_struct! { MemoryEvents(
+    oom_kill,
+)}
+
+/// The I/O of a group on a device, named by its `major:minor` numbers.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct IoStat {
+    device: String,
+    rbytes: u64,
+    wbytes: u64,
+    rios: u64,
+    wios: u64,
+}
+
+/// Parses the lines of `io.stat`, as in `8:0 rbytes=90112 wbytes=0 rios=22 wios=0 dbytes=0 dios=0`.
+fn parse_io_stat(text: &str) -> Result<Vec<IoStat>, ParseIntError> {
+    let mut result = Vec::new();
+    for line in text.lines() {
+        let mut fields = line.split_whitespace();
+        let device = match fields.next() {
+            Some(device) => device,
+            None => continue,
+        };
+        let mut stat = IoStat {
+            device: device.to_owned(),
+            ..Default::default()
+        };
+        for (key, value) in fields.filter_map(|field| field.split_once('=')) {
+            match key {
+                "rbytes" => stat.rbytes = value.parse()?,
+                "wbytes" => stat.wbytes = value.parse()?,
+                "rios" => stat.rios = value.parse()?,
+                "wios" => stat.wios = value.parse()?,
+                _ => {}
+            }
+        }
+        result.push(stat);
+    }
+    Ok(result)
+}
+
 fn is_dir(path: impl AsRef<Path>) -> bool {
     std::fs::metadata(path.as_ref())
         .map(|metadata| metadata.is_dir())
@@ -399,7 +573,7 @@ mod tests {
             tests::{count_name, count_tag},
             HostMetrics, HostMetricsConfig,
         },
-        join_name, join_path,
+        join_name, join_path, parse_io_stat, CGroup, IoStat,
     };
 
     #[test]
@@ -412,6 +586,55 @@ mod tests {
         assert_eq!(join_path("/sys", "/"), PathBuf::from("/sys"));
     }
 
+    #[test]
+    fn parses_io_stat() {
+        assert_eq!(
+            parse_io_stat(
+                "8:0 rbytes=90112 wbytes=4096 rios=22 wios=1 dbytes=0 dios=0\n\
+                 253:0 rbytes=0 wbytes=0 rios=0 wios=0\n"
+            )
+            .unwrap(),
+            vec![
+                IoStat {
+                    device: "8:0".into(),
+                    rbytes: 90112,
+                    wbytes: 4096,
+                    rios: 22,
+                    wios: 1,
+                },
+                IoStat {
+                    device: "253:0".into(),
+                    ..Default::default()
+                },
+            ]
+        );
+        assert!(parse_io_stat("8:0 rbytes=lots").is_err());
+    }
+
+    #[test]
+    fn finds_container_ids() {
+        let id = "3f4e8a0b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f";
+        let container_id = |name: String| {
+            CGroup {
+                root: PathBuf::new(),
+                name: name.into(),
+                has_memory_controller: true,
+            }
+            .container_id()
+        };
+
+        assert_eq!(
+            container_id(format!("system.slice/docker-{}.scope", id)),
+            Some(id.to_owned())
+        );
+        assert_eq!(
+            container_id(format!("kubepods/burstable/pod1234/{}", id)),
+            Some(id.to_owned())
+        );
+        assert_eq!(container_id("system.slice/sshd.service".into()), None);
+        assert_eq!(container_id("/".into()), None);
+    }
+
     #[tokio::test]
     async fn generates_cgroups_metrics() {
         let config: HostMetricsConfig = toml::from_str(r#"collectors = ["cgroups"]"#).unwrap();
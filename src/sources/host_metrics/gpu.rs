@@ -0,0 +1,195 @@
+//! The utilization, memory and power draw of NVIDIA GPUs, as reported by `nvidia-smi`.
+
+use std::{io, path::PathBuf, process::ExitStatus, time::Duration};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::{process::Command, time};
+use vector_common::btreemap;
+
+use super::{filter_result_sync, HostMetrics};
+use crate::event::metric::Metric;
+
+const QUERY: &str = "--query-gpu=index,uuid,name,utilization.gpu,utilization.memory,memory.used,memory.total,temperature.gpu,power.draw";
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+const MEBIBYTES: f64 = 1024.0 * 1024.0;
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
+#[derivative(Default)]
+#[serde(default)]
+pub struct GpuConfig {
+    /// The `nvidia-smi` executable, looked up in the `PATH` unless it's a path.
+    #[derivative(Default(value = "PathBuf::from(\"nvidia-smi\")"))]
+    nvidia_smi_path: PathBuf,
+}
+
+#[derive(Debug, Snafu)]
+enum GpuError {
+    #[snafu(display("Could not run {:?}: {}.", path, source))]
+    Running { path: PathBuf, source: io::Error },
+    #[snafu(display("{:?} did not finish within {:?}.", path, TIMEOUT))]
+    TimedOut { path: PathBuf },
+    #[snafu(display("{:?} failed with {}: {}", path, status, stderr))]
+    Failed {
+        path: PathBuf,
+        status: ExitStatus,
+        stderr: String,
+    },
+    #[snafu(display("Could not parse GPU statistics {:?}.", line))]
+    Parsing { line: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct GpuStat {
+    index: String,
+    uuid: String,
+    name: String,
+    utilization: Option<f64>,
+    memory_utilization: Option<f64>,
+    memory_used: Option<f64>,
+    memory_total: Option<f64>,
+    temperature: Option<f64>,
+    power_draw: Option<f64>,
+}
+
+impl HostMetrics {
+    pub async fn gpu_metrics(&self) -> Vec<Metric> {
+        let now = Utc::now();
+        let mut result = Vec::new();
+        let gpus =
+            match filter_result_sync(self.load_gpus().await, "Failed to load GPU statistics.") {
+                Some(gpus) => gpus,
+                None => return result,
+            };
+        for gpu in gpus {
+            let tags = btreemap! {
+                "gpu" => gpu.index,
+                "uuid" => gpu.uuid,
+                "name" => gpu.name,
+            };
+            for (name, value) in [
+                ("gpu_utilization_ratio", gpu.utilization.map(|v| v / 100.0)),
+                (
+                    "gpu_memory_utilization_ratio",
+                    gpu.memory_utilization.map(|v| v / 100.0),
+                ),
+                (
+                    "gpu_memory_used_bytes",
+                    gpu.memory_used.map(|v| v * MEBIBYTES),
+                ),
+                (
+                    "gpu_memory_total_bytes",
+                    gpu.memory_total.map(|v| v * MEBIBYTES),
+                ),
+                ("gpu_temperature_celsius", gpu.temperature),
+                ("gpu_power_draw_watts", gpu.power_draw),
+            ] {
+                if let Some(value) = value {
+                    result.push(self.gauge(name, now, value, tags.clone()));
+                }
+            }
+        }
+        result
+    }
+
+    async fn load_gpus(&self) -> Result<Vec<GpuStat>, GpuError> {
+        let path = &self.config.gpu.nvidia_smi_path;
+        let output = Command::new(path)
+            .arg(QUERY)
+            .arg("--format=csv,noheader,nounits")
+            .kill_on_drop(true)
+            .output();
+        let output = time::timeout(TIMEOUT, output)
+            .await
+            .map_err(|_| GpuError::TimedOut { path: path.clone() })?
+            .with_context(|_| RunningSnafu { path: path.clone() })?;
+        if !output.status.success() {
+            return Err(GpuError::Failed {
+                path: path.clone(),
+                status: output.status,
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            });
+        }
+        parse_gpus(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Parses the rows of the `nvidia-smi` query, leaving out the values it doesn't support for a
+/// GPU, which it reports as `[N/A]` or `[Not Supported]`.
+fn parse_gpus(text: &str) -> Result<Vec<GpuStat>, GpuError> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+            if fields.len() != 9 {
+                return Err(GpuError::Parsing {
+                    line: line.to_owned(),
+                });
+            }
+            let value = |index: usize| fields[index].parse::<f64>().ok();
+            Ok(GpuStat {
+                index: fields[0].to_owned(),
+                uuid: fields[1].to_owned(),
+                name: fields[2].to_owned(),
+                utilization: value(3),
+                memory_utilization: value(4),
+                memory_used: value(5),
+                memory_total: value(6),
+                temperature: value(7),
+                power_draw: value(8),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        super::{HostMetrics, HostMetricsConfig},
+        parse_gpus, GpuStat,
+    };
+
+    #[test]
+    fn parses_gpus() {
+        let gpus = parse_gpus(
+            "0, GPU-5fd4, NVIDIA A100-SXM4-40GB, 87, 40, 30012, 40960, 61, 254.10\n\
+             1, GPU-9ab2, Tesla T4, 0, 0, 0, 15360, [N/A], [Not Supported]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            gpus[0],
+            GpuStat {
+                index: "0".into(),
+                uuid: "GPU-5fd4".into(),
+                name: "NVIDIA A100-SXM4-40GB".into(),
+                utilization: Some(87.0),
+                memory_utilization: Some(40.0),
+                memory_used: Some(30012.0),
+                memory_total: Some(40960.0),
+                temperature: Some(61.0),
+                power_draw: Some(254.1),
+            }
+        );
+        assert_eq!(gpus[1].temperature, None);
+        assert_eq!(gpus[1].power_draw, None);
+
+        assert!(parse_gpus("0, GPU-5fd4, 87").is_err());
+    }
+
+    #[tokio::test]
+    async fn skips_missing_nvidia_smi() {
+        let config: HostMetricsConfig = toml::from_str(
+            r#"
+            collectors = ["gpu"]
+            gpu.nvidia_smi_path = "/nonexistent/nvidia-smi"
+            "#,
+        )
+        .unwrap();
+        assert!(HostMetrics::new(config).gpu_metrics().await.is_empty());
+    }
+}
@@ -27,8 +27,11 @@ mod cgroups;
 mod cpu;
 mod disk;
 mod filesystem;
+mod gpu;
 mod memory;
 mod network;
+#[cfg(target_os = "linux")]
+mod pressure;
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -42,6 +45,10 @@ pub enum Collector {
     Host,
     Memory,
     Network,
+    #[cfg(target_os = "linux")]
+    Pressure,
+    /// NVIDIA GPUs, only collected when listed explicitly.
+    Gpu,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -84,6 +91,8 @@ pub struct HostMetricsConfig {
     pub filesystem: filesystem::FilesystemConfig,
     #[serde(default)]
     pub network: network::NetworkConfig,
+    #[serde(default)]
+    pub gpu: gpu::GpuConfig,
 }
 
 const fn default_scrape_interval() -> f64 {
@@ -155,7 +164,7 @@ impl HostMetricsConfig {
 
     fn has_collector(&self, collector: Collector) -> bool {
         match &self.collectors {
-            None => true,
+            None => collector != Collector::Gpu,
             Some(collectors) => collectors.iter().any(|&c| c == collector),
         }
     }
@@ -212,6 +221,13 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             metrics.extend(add_collector("network", self.network_metrics().await));
         }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Pressure) {
+            metrics.extend(add_collector("pressure", self.pressure_metrics().await));
+        }
+        if self.config.has_collector(Collector::Gpu) {
+            metrics.extend(add_collector("gpu", self.gpu_metrics().await));
+        }
         if let Ok(hostname) = &hostname {
             for metric in &mut metrics {
                 metric.insert_tag("host".into(), hostname.into());
@@ -552,6 +568,9 @@ pub(self) mod tests {
             Collector::Host,
             Collector::Memory,
             Collector::Network,
+            #[cfg(target_os = "linux")]
+            Collector::Pressure,
+            Collector::Gpu,
         ] {
             let some_metrics = HostMetrics::new(HostMetricsConfig {
                 collectors: Some(vec![*collector]),
@@ -0,0 +1,207 @@
+//! Pressure stall information (PSI): the share of time tasks were stalled waiting on the CPU,
+//! memory or I/O, over the whole host and within each cgroup. See
+//! https://www.kernel.org/doc/html/latest/accounting/psi.html for details.
+
+use std::{collections::BTreeMap, io};
+
+use chrono::{DateTime, Utc};
+use snafu::Snafu;
+use tokio::fs;
+
+use super::{cgroups::MICROSECONDS, HostMetrics};
+use crate::event::metric::Metric;
+
+/// The resources stalls are accounted for, named as their pressure files are.
+pub(super) const RESOURCES: [&str; 3] = ["cpu", "memory", "io"];
+
+#[derive(Debug, Snafu)]
+pub(super) enum PressureError {
+    #[snafu(display("Invalid pressure stall line {:?}.", line))]
+    InvalidLine { line: String },
+}
+
+/// A line of a pressure file, for either the time `some` tasks were stalled or the time `full`ly
+/// all of them were.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct PressureStat {
+    kind: String,
+    avg10: f64,
+    avg60: f64,
+    avg300: f64,
+    total_usec: u64,
+}
+
+/// Parses the lines of a pressure file, as in
+/// `some avg10=0.12 avg60=0.05 avg300=0.01 total=123456`.
+pub(super) fn parse_pressure(text: &str) -> Result<Vec<PressureStat>, PressureError> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let invalid = || PressureError::InvalidLine {
+                line: line.to_owned(),
+            };
+            let mut fields = line.split_whitespace();
+            let kind = fields.next().ok_or_else(invalid)?;
+            let mut stat = PressureStat {
+                kind: kind.to_owned(),
+                avg10: 0.0,
+                avg60: 0.0,
+                avg300: 0.0,
+                total_usec: 0,
+            };
+            for field in fields {
+                let (key, value) = field.split_once('=').ok_or_else(invalid)?;
+                match key {
+                    "avg10" => stat.avg10 = value.parse().map_err(|_| invalid())?,
+                    "avg60" => stat.avg60 = value.parse().map_err(|_| invalid())?,
+                    "avg300" => stat.avg300 = value.parse().map_err(|_| invalid())?,
+                    "total" => stat.total_usec = value.parse().map_err(|_| invalid())?,
+                    _ => {}
+                }
+            }
+            Ok(stat)
+        })
+        .collect()
+}
+
+impl HostMetrics {
+    pub async fn pressure_metrics(&self) -> Vec<Metric> {
+        let now = Utc::now();
+        let root = heim::os::linux::procfs_root().join("pressure");
+        let mut result = Vec::new();
+        for resource in RESOURCES {
+            let filename = root.join(resource);
+            let text = match fs::read_to_string(&filename).await {
+                Ok(text) => text,
+                // Kernels built without PSI have no pressure files.
+                Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+                Err(error) => {
+                    error!(message = "Failed to load pressure stall information.", %error, ?filename, internal_log_rate_secs = 60);
+                    continue;
+                }
+            };
+            match parse_pressure(&text) {
+                Ok(stats) => self.push_pressure(
+                    &mut result,
+                    "pressure",
+                    now,
+                    resource,
+                    &stats,
+                    &BTreeMap::new(),
+                ),
+                Err(error) => {
+                    error!(message = "Failed to parse pressure stall information.", %error, ?filename, internal_log_rate_secs = 60)
+                }
+            }
+        }
+        result
+    }
+
+    /// Adds the metrics of the lines of a pressure file, named with the given prefix.
+    pub(super) fn push_pressure(
+        &self,
+        result: &mut Vec<Metric>,
+        prefix: &str,
+        now: DateTime<Utc>,
+        resource: &str,
+        stats: &[PressureStat],
+        tags: &BTreeMap<String, String>,
+    ) {
+        for stat in stats {
+            let mut tags = tags.clone();
+            tags.insert("resource".into(), resource.into());
+            tags.insert("kind".into(), stat.kind.clone());
+            for (window, value) in [
+                ("avg10", stat.avg10),
+                ("avg60", stat.avg60),
+                ("avg300", stat.avg300),
+            ] {
+                // The averages are percentages.
+                result.push(self.gauge(
+                    &format!("{}_{}_ratio", prefix, window),
+                    now,
+                    value / 100.0,
+                    tags.clone(),
+                ));
+            }
+            result.push(self.counter(
+                &format!("{}_stalled_seconds_total", prefix),
+                now,
+                stat.total_usec as f64 * MICROSECONDS,
+                tags,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        super::{
+            tests::{all_gauges, count_name, count_tag},
+            HostMetrics, HostMetricsConfig,
+        },
+        parse_pressure, PressureStat,
+    };
+
+    #[test]
+    fn parses_pressure() {
+        let stats = parse_pressure(
+            "some avg10=1.50 avg60=0.25 avg300=0.00 total=2500000\n\
+             full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n",
+        )
+        .unwrap();
+        assert_eq!(
+            stats,
+            vec![
+                PressureStat {
+                    kind: "some".into(),
+                    avg10: 1.5,
+                    avg60: 0.25,
+                    avg300: 0.0,
+                    total_usec: 2_500_000,
+                },
+                PressureStat {
+                    kind: "full".into(),
+                    avg10: 0.0,
+                    avg60: 0.0,
+                    avg300: 0.0,
+                    total_usec: 0,
+                },
+            ]
+        );
+
+        assert!(parse_pressure("some avg10=high").is_err());
+        assert!(parse_pressure("some avg10").is_err());
+    }
+
+    #[tokio::test]
+    async fn generates_pressure_metrics() {
+        let config: HostMetricsConfig = toml::from_str(r#"collectors = ["pressure"]"#).unwrap();
+        let host = HostMetrics::new(config);
+        let mut metrics = Vec::new();
+        let stats = parse_pressure("some avg10=50.00 avg60=0.00 avg300=0.00 total=1000").unwrap();
+        host.push_pressure(
+            &mut metrics,
+            "pressure",
+            chrono::Utc::now(),
+            "cpu",
+            &stats,
+            &Default::default(),
+        );
+
+        assert_eq!(metrics.len(), 4);
+        assert_eq!(count_tag(&metrics, "resource"), 4);
+        assert_eq!(count_name(&metrics, "pressure_stalled_seconds_total"), 1);
+        assert!(all_gauges(&metrics[..3]));
+        assert_eq!(
+            metrics[0].value(),
+            &crate::event::metric::MetricValue::Gauge { value: 0.5 }
+        );
+
+        // Hosts without PSI generate nothing, rather than failing.
+        host.pressure_metrics().await;
+    }
+}
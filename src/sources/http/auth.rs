@@ -0,0 +1,307 @@
+//! Authentication of the requests to the routes of the `http` source, with credentials in the
+//! `Authorization` header or with a signature of the request body, as sent by webhooks.
+
+use std::convert::TryFrom;
+
+use chrono::Utc;
+use headers::{Authorization, HeaderMapExt};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use warp::http::{HeaderMap, StatusCode};
+
+use crate::sources::util::ErrorMessage;
+
+const fn default_timestamp_tolerance_secs() -> u64 {
+    300
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+pub(super) enum HttpRouteAuthConfig {
+    Basic {
+        username: String,
+        password: String,
+    },
+    Bearer {
+        token: String,
+    },
+    /// Validates the HMAC-SHA256 signature of the request body, keyed with a secret shared with
+    /// the sender.
+    Hmac {
+        secret: String,
+        /// The header holding the signature, such as `X-Hub-Signature-256` for GitHub.
+        header: String,
+        #[serde(default)]
+        format: SignatureFormat,
+        /// For the `hex` format, the prefix of the signature, such as `sha256=` for GitHub.
+        #[serde(default)]
+        prefix: String,
+        /// For the `stripe` format, how far the timestamp of a signature may be from the current
+        /// time, to prevent replay attacks.
+        #[serde(default = "default_timestamp_tolerance_secs")]
+        timestamp_tolerance_secs: u64,
+    },
+}
+
+/// How the signature is written in its header.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum SignatureFormat {
+    /// The hex-encoded signature of the body, as sent by GitHub.
+    Hex,
+    /// The `t=<timestamp>,v1=<signature>` header sent by Stripe, the signature being that of the
+    /// timestamp and body, joined by a `.`.
+    Stripe,
+}
+
+impl Default for SignatureFormat {
+    fn default() -> Self {
+        Self::Hex
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) enum HttpRouteAuth {
+    /// The expected value of the `Authorization` header.
+    Authorization(String),
+    Hmac {
+        secret: String,
+        header: String,
+        format: SignatureFormat,
+        prefix: String,
+        timestamp_tolerance_secs: u64,
+    },
+}
+
+impl TryFrom<&HttpRouteAuthConfig> for HttpRouteAuth {
+    type Error = String;
+
+    fn try_from(config: &HttpRouteAuthConfig) -> Result<Self, Self::Error> {
+        Ok(match config {
+            HttpRouteAuthConfig::Basic { username, password } => {
+                let mut headers = HeaderMap::new();
+                headers.typed_insert(Authorization::basic(username, password));
+                let token = headers
+                    .get("authorization")
+                    .ok_or_else(|| "Authorization headers wasn't generated".to_owned())?
+                    .to_str()
+                    .map_err(|error| format!("Failed stringify HeaderValue: {:?}", error))?;
+                Self::Authorization(token.to_owned())
+            }
+            HttpRouteAuthConfig::Bearer { token } => {
+                Self::Authorization(format!("Bearer {}", token))
+            }
+            HttpRouteAuthConfig::Hmac {
+                secret,
+                header,
+                format,
+                prefix,
+                timestamp_tolerance_secs,
+            } => {
+                if secret.is_empty() {
+                    return Err("The HMAC secret must not be empty".to_owned());
+                }
+                Self::Hmac {
+                    secret: secret.clone(),
+                    header: header.clone(),
+                    format: *format,
+                    prefix: prefix.clone(),
+                    timestamp_tolerance_secs: *timestamp_tolerance_secs,
+                }
+            }
+        })
+    }
+}
+
+impl HttpRouteAuth {
+    pub(super) fn validate(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), ErrorMessage> {
+        match self {
+            Self::Authorization(token) => match headers.get("authorization") {
+                Some(header) if header.as_bytes() == token.as_bytes() => Ok(()),
+                Some(_) => Err(unauthorized("Invalid authorization credentials")),
+                None => Err(unauthorized("No authorization header")),
+            },
+            Self::Hmac {
+                secret,
+                header,
+                format,
+                prefix,
+                timestamp_tolerance_secs,
+            } => {
+                let signature = headers
+                    .get(header.as_str())
+                    .and_then(|value| value.to_str().ok())
+                    .ok_or_else(|| unauthorized("No signature header"))?;
+                let valid = match format {
+                    SignatureFormat::Hex => signature
+                        .strip_prefix(prefix.as_str())
+                        .map_or(false, |signature| verify(secret, &[body], signature)),
+                    SignatureFormat::Stripe => {
+                        verify_stripe(secret, body, signature, *timestamp_tolerance_secs)?
+                    }
+                };
+                if valid {
+                    Ok(())
+                } else {
+                    Err(unauthorized("Invalid signature"))
+                }
+            }
+        }
+    }
+}
+
+/// Verifies the hex-encoded signature of the concatenated parts, in constant time.
+fn verify(secret: &str, parts: &[&[u8]], signature: &str) -> bool {
+    let signature = match hex::decode(signature.trim()) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any size");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn verify_stripe(
+    secret: &str,
+    body: &[u8],
+    header: &str,
+    timestamp_tolerance_secs: u64,
+) -> Result<bool, ErrorMessage> {
+    let mut timestamp = None;
+    let mut signatures = vec![];
+    for (key, value) in header.split(',').filter_map(|item| item.split_once('=')) {
+        match key.trim() {
+            "t" => timestamp = Some(value.trim()),
+            "v1" => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let timestamp = timestamp.ok_or_else(|| unauthorized("No signature timestamp"))?;
+    let age = timestamp
+        .parse::<i64>()
+        .map(|timestamp| (Utc::now().timestamp() - timestamp).unsigned_abs())
+        .map_err(|_| unauthorized("Invalid signature timestamp"))?;
+    if age > timestamp_tolerance_secs {
+        return Err(unauthorized(
+            "Signature timestamp is outside of the tolerance",
+        ));
+    }
+
+    Ok(signatures
+        .into_iter()
+        .any(|signature| verify(secret, &[timestamp.as_bytes(), b".", body], signature)))
+}
+
+fn unauthorized(message: &str) -> ErrorMessage {
+    ErrorMessage::new(StatusCode::UNAUTHORIZED, message.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, parts: &[&[u8]]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        for part in parts {
+            mac.update(part);
+        }
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn auth(config: &str) -> HttpRouteAuth {
+        let config: HttpRouteAuthConfig = toml::from_str(config).unwrap();
+        HttpRouteAuth::try_from(&config).unwrap()
+    }
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn validates_authorization_header() {
+        let auth = auth(
+            r#"
+            strategy = "bearer"
+            token = "token"
+            "#,
+        );
+
+        assert!(auth
+            .validate(&headers("authorization", "Bearer token"), b"")
+            .is_ok());
+        assert_eq!(
+            auth.validate(&headers("authorization", "Bearer other"), b"")
+                .unwrap_err()
+                .message(),
+            "Invalid authorization credentials"
+        );
+        assert_eq!(
+            auth.validate(&HeaderMap::new(), b"").unwrap_err().message(),
+            "No authorization header"
+        );
+    }
+
+    #[test]
+    fn validates_hex_signatures() {
+        let auth = auth(
+            r#"
+            strategy = "hmac"
+            secret = "secret"
+            header = "X-Hub-Signature-256"
+            prefix = "sha256="
+            "#,
+        );
+        let body = br#"{"action":"opened"}"#;
+        let signature = format!("sha256={}", sign("secret", &[body]));
+
+        assert!(auth
+            .validate(&headers("x-hub-signature-256", &signature), body)
+            .is_ok());
+        assert!(auth
+            .validate(&headers("x-hub-signature-256", &signature), b"{}")
+            .is_err());
+        assert!(auth
+            .validate(
+                &headers("x-hub-signature-256", &sign("secret", &[body])),
+                body
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn validates_stripe_signatures() {
+        let auth = auth(
+            r#"
+            strategy = "hmac"
+            secret = "whsec_secret"
+            header = "Stripe-Signature"
+            format = "stripe"
+            "#,
+        );
+        let body = br#"{"type":"charge.succeeded"}"#;
+        let timestamp = Utc::now().timestamp().to_string();
+        let signature = sign("whsec_secret", &[timestamp.as_bytes(), b".", body]);
+
+        let header = format!("t={},v1=invalid,v1={}", timestamp, signature);
+        assert!(auth
+            .validate(&headers("stripe-signature", &header), body)
+            .is_ok());
+
+        let expired = (Utc::now().timestamp() - 600).to_string();
+        let signature = sign("whsec_secret", &[expired.as_bytes(), b".", body]);
+        let header = format!("t={},v1={}", expired, signature);
+        assert_eq!(
+            auth.validate(&headers("stripe-signature", &header), body)
+                .unwrap_err()
+                .message(),
+            "Signature timestamp is outside of the tolerance"
+        );
+    }
+}
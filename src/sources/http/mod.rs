@@ -1,4 +1,7 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -8,6 +11,7 @@ use codecs::{
     NewlineDelimitedDecoderConfig,
 };
 use http::StatusCode;
+use indexmap::IndexMap;
 use lookup::path;
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::Decoder as _;
@@ -22,12 +26,17 @@ use crate::{
     event::{Event, Value},
     serde::{bool_or_struct, default_decoding},
     sources::util::{
-        add_query_parameters, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        add_query_parameters, run_routes, Encoding, ErrorMessage, HttpListenAddr, HttpSource,
+        HttpSourceAuth, HttpSourceAuthConfig, HttpSourceRoute,
     },
     tls::TlsEnableableConfig,
 };
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize)]
+mod auth;
+
+use auth::{HttpRouteAuth, HttpRouteAuthConfig};
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[derivative(Default)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum HttpMethod {
@@ -42,7 +51,9 @@ pub enum HttpMethod {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub(super) struct SimpleHttpConfig {
-    address: SocketAddr,
+    address: HttpListenAddr,
+    /// The file mode of the Unix domain socket, when listening on one.
+    socket_file_mode: Option<u32>,
     #[serde(default)]
     encoding: Option<Encoding>,
     #[serde(default)]
@@ -63,6 +74,25 @@ pub(super) struct SimpleHttpConfig {
     decoding: Option<DeserializerConfig>,
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+    /// The paths served on the same listener instead of `path`, by the name of the output their
+    /// events are sent to.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    routes: IndexMap<String, HttpRouteConfig>,
+}
+
+/// A path served by the source, with its own decoding and authentication. The framing, decoding
+/// and `auth` of the source apply to the routes that don't set their own.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct HttpRouteConfig {
+    path: String,
+    #[serde(default)]
+    method: HttpMethod,
+    #[serde(default = "crate::serde::default_true")]
+    strict_path: bool,
+    framing: Option<FramingConfig>,
+    decoding: Option<DeserializerConfig>,
+    auth: Option<HttpRouteAuthConfig>,
 }
 
 inventory::submit! {
@@ -72,7 +102,8 @@ inventory::submit! {
 impl GenerateConfig for SimpleHttpConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            address: "0.0.0.0:8080".parse().unwrap(),
+            address: HttpListenAddr::SocketAddr("0.0.0.0:8080".parse().unwrap()),
+            socket_file_mode: None,
             encoding: None,
             headers: Vec::new(),
             query_parameters: Vec::new(),
@@ -85,6 +116,7 @@ impl GenerateConfig for SimpleHttpConfig {
             framing: None,
             decoding: Some(default_decoding()),
             acknowledgements: AcknowledgementsConfig::default(),
+            routes: IndexMap::new(),
         })
         .unwrap()
     }
@@ -104,6 +136,7 @@ struct SimpleHttpSource {
     query_parameters: Vec<String>,
     path_key: String,
     decoder: Decoder,
+    auth: Option<HttpRouteAuth>,
 }
 
 impl HttpSource for SimpleHttpSource {
@@ -114,6 +147,10 @@ impl HttpSource for SimpleHttpSource {
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
+        if let Some(auth) = &self.auth {
+            auth.validate(&header_map, &body)?;
+        }
+
         let mut decoder = self.decoder.clone();
         let mut events = Vec::new();
         let mut bytes = BytesMut::new();
@@ -158,8 +195,110 @@ impl SourceConfig for SimpleHttpConfig {
             return Err("Using `encoding` is deprecated and does not have any effect when `decoding` or `framing` is provided. Configure `framing` and `decoding` instead.".into());
         }
 
-        let (framing, decoding) = if let Some(encoding) = self.encoding {
-            match encoding {
+        if self.routes.is_empty() {
+            let source = self.source(None, None, None)?;
+            return source.run(
+                self.address.clone(),
+                self.socket_file_mode,
+                self.path.as_str(),
+                self.method,
+                self.strict_path,
+                &self.tls,
+                &self.auth,
+                cx,
+                self.acknowledgements,
+            );
+        }
+
+        if self.encoding.is_some() {
+            return Err("Using `encoding` is deprecated and not supported with `routes`. Configure `framing` and `decoding` instead.".into());
+        }
+
+        let mut paths = HashSet::new();
+        let mut routes = Vec::with_capacity(self.routes.len());
+        for (name, route) in &self.routes {
+            let segments = route
+                .path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>();
+            if !paths.insert((route.method, segments)) {
+                return Err(format!(
+                    "Route {:?} serves the same method and path as another route",
+                    name
+                )
+                .into());
+            }
+
+            // Routes with their own authentication don't fall back on the one of the source.
+            let auth = match &route.auth {
+                Some(_) => HttpSourceAuth::try_from(None::<&HttpSourceAuthConfig>)?,
+                None => HttpSourceAuth::try_from(self.auth.as_ref())?,
+            };
+            routes.push(HttpSourceRoute {
+                source: self.source(
+                    route.framing.as_ref(),
+                    route.decoding.as_ref(),
+                    route.auth.as_ref(),
+                )?,
+                path: route.path.clone(),
+                method: route.method,
+                strict_path: route.strict_path,
+                auth,
+                output: Some(name.clone()),
+            });
+        }
+
+        run_routes(
+            routes,
+            self.address.clone(),
+            self.socket_file_mode,
+            &self.tls,
+            cx,
+            self.acknowledgements,
+        )
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        if self.routes.is_empty() {
+            return vec![Output::default(output_type(self.decoding.as_ref()))];
+        }
+
+        self.routes
+            .iter()
+            .map(|(name, route)| {
+                let decoding = route.decoding.as_ref().or(self.decoding.as_ref());
+                Output::default(output_type(decoding)).with_port(name.clone())
+            })
+            .collect()
+    }
+
+    fn source_type(&self) -> &'static str {
+        "http"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.address.resources()
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl SimpleHttpConfig {
+    /// Builds the source of a route with the given framing, decoding and authentication, the
+    /// framing and decoding defaulting to those of the source.
+    fn source(
+        &self,
+        framing: Option<&FramingConfig>,
+        decoding: Option<&DeserializerConfig>,
+        auth: Option<&HttpRouteAuthConfig>,
+    ) -> crate::Result<SimpleHttpSource> {
+        let framing = framing.or(self.framing.as_ref()).cloned();
+        let decoding = decoding.or(self.decoding.as_ref()).cloned();
+        let (framing, decoding) = match (self.encoding, framing, decoding) {
+            (Some(encoding), None, None) => match encoding {
                 Encoding::Text => (
                     NewlineDelimitedDecoderConfig::new().into(),
                     BytesDeserializerConfig::new().into(),
@@ -176,55 +315,26 @@ impl SourceConfig for SimpleHttpConfig {
                     BytesDecoderConfig::new().into(),
                     BytesDeserializerConfig::new().into(),
                 ),
+            },
+            (_, framing, decoding) => {
+                let decoding = decoding.unwrap_or_else(default_decoding);
+                let framing = framing.unwrap_or_else(|| decoding.default_stream_framing());
+                (framing, decoding)
             }
-        } else {
-            let decoding = self.decoding.clone().unwrap_or_else(default_decoding);
-            let framing = self
-                .framing
-                .clone()
-                .unwrap_or_else(|| decoding.default_stream_framing());
-            (framing, decoding)
         };
 
-        let decoder = DecodingConfig::new(framing, decoding).build();
-        let source = SimpleHttpSource {
+        Ok(SimpleHttpSource {
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
             path_key: self.path_key.clone(),
-            decoder,
-        };
-        source.run(
-            self.address,
-            self.path.as_str(),
-            self.method,
-            self.strict_path,
-            &self.tls,
-            &self.auth,
-            cx,
-            self.acknowledgements,
-        )
-    }
-
-    fn outputs(&self) -> Vec<Output> {
-        vec![Output::default(
-            self.decoding
-                .as_ref()
-                .map(|d| d.output_type())
-                .unwrap_or(DataType::Log),
-        )]
-    }
-
-    fn source_type(&self) -> &'static str {
-        "http"
-    }
-
-    fn resources(&self) -> Vec<Resource> {
-        vec![Resource::tcp(self.address)]
+            decoder: DecodingConfig::new(framing, decoding).build(),
+            auth: auth.map(HttpRouteAuth::try_from).transpose()?,
+        })
     }
+}
 
-    fn can_acknowledge(&self) -> bool {
-        true
-    }
+fn output_type(decoding: Option<&DeserializerConfig>) -> DataType {
+    decoding.map_or(DataType::Log, |decoding| decoding.output_type())
 }
 
 fn add_path(events: &mut [Event], key: &str, path: &str) {
@@ -262,18 +372,20 @@ mod tests {
         write::{GzEncoder, ZlibEncoder},
         Compression,
     };
-    use futures::Stream;
+    use futures::{Stream, StreamExt};
+    use hmac::{Hmac, Mac};
     use http::{HeaderMap, Method};
     use pretty_assertions::assert_eq;
+    use sha2::Sha256;
 
     use super::SimpleHttpConfig;
     use crate::sources::http::HttpMethod;
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
-        event::{Event, EventStatus, Value},
+        event::{into_event_stream, Event, EventStatus, Value},
         test_util::{
             components::{self, assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
-            next_addr, spawn_collect_n, trace_init, wait_for_tcp,
+            next_addr, spawn_collect_n, trace_init, wait_for, wait_for_tcp,
         },
         SourceSender,
     };
@@ -310,7 +422,8 @@ mod tests {
 
         tokio::spawn(async move {
             SimpleHttpConfig {
-                address,
+                address: address.into(),
+                socket_file_mode: None,
                 headers,
                 encoding: None,
                 query_parameters,
@@ -323,6 +436,7 @@ mod tests {
                 framing,
                 decoding,
                 acknowledgements: acknowledgements.into(),
+                routes: Default::default(),
             }
             .build(context)
             .await
@@ -1056,4 +1170,162 @@ mod tests {
 
         assert_eq!(200, send_request(addr, "GET", "", "/").await);
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn http_over_unix_socket() {
+        use hyper::{Body, Request};
+
+        use crate::http::HttpClient;
+
+        trace_init();
+        let (sender, mut recv) = SourceSender::new_test();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vector.sock");
+        let config: SimpleHttpConfig =
+            toml::from_str(&format!(r#"address = "unix:{}""#, path.display())).unwrap();
+        tokio::spawn(async move {
+            config
+                .build(SourceContext::new_test(sender, None))
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+        });
+        let socket_path = path.clone();
+        wait_for(move || {
+            let exists = socket_path.exists();
+            async move { exists }
+        })
+        .await;
+
+        let client = HttpClient::<Body>::new_with_socket(None, &path).unwrap();
+        let request = Request::post("http://localhost/")
+            .body(Body::from("hello"))
+            .unwrap();
+        assert_eq!(200, client.send(request).await.unwrap().status());
+
+        let event = recv.next().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "hello".into());
+    }
+
+    #[tokio::test]
+    async fn http_routes() {
+        trace_init();
+
+        let (mut sender, _recv) = SourceSender::new_test();
+        let mut github = sender
+            .add_outputs(EventStatus::Delivered, "github".to_owned())
+            .flat_map(into_event_stream);
+        let mut text = sender
+            .add_outputs(EventStatus::Delivered, "text".to_owned())
+            .flat_map(into_event_stream);
+        let address = next_addr();
+        let config: SimpleHttpConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+
+            [routes.github]
+            path = "/webhooks/github"
+            decoding.codec = "json"
+            auth.strategy = "hmac"
+            auth.secret = "secret"
+            auth.header = "X-Hub-Signature-256"
+            auth.prefix = "sha256="
+
+            [routes.text]
+            path = "/text"
+            auth.strategy = "bearer"
+            auth.token = "token"
+            "#,
+            address
+        ))
+        .unwrap();
+        assert_eq!(
+            config
+                .outputs()
+                .into_iter()
+                .map(|output| output.port)
+                .collect::<Vec<_>>(),
+            vec![Some("github".to_owned()), Some("text".to_owned())]
+        );
+
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move {
+            config.build(context).await.unwrap().await.unwrap();
+        });
+        wait_for_tcp(address).await;
+
+        let body = r#"{"action":"opened"}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(body.as_bytes());
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-Hub-Signature-256",
+            format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            200,
+            send_with_path_and_headers(address, body, "/webhooks/github", headers).await
+        );
+        assert_eq!(401, send_with_path(address, body, "/webhooks/github").await);
+
+        let event = github.next().await.unwrap();
+        assert_eq!(event.as_log()["action"], "opened".into());
+        assert_eq!(event.as_log()["path"], "/webhooks/github".into());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Authorization", "Bearer token".parse().unwrap());
+        assert_eq!(
+            200,
+            send_with_path_and_headers(address, "hello", "/text", headers).await
+        );
+        assert_eq!(401, send_with_path(address, "hello", "/text").await);
+        assert_eq!(404, send_with_path(address, "hello", "/other").await);
+
+        let event = text.next().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "hello".into());
+    }
+
+    #[tokio::test]
+    async fn http_duplicate_routes() {
+        let config: SimpleHttpConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:8080"
+            routes.first.path = "/webhooks"
+            routes.second.path = "webhooks/"
+            "#,
+        )
+        .unwrap();
+        let (sender, _recv) = SourceSender::new_test();
+
+        let error = config
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Route "second" serves the same method and path as another route"#
+        );
+    }
+
+    async fn send_with_path_and_headers(
+        address: SocketAddr,
+        body: &str,
+        path: &str,
+        headers: HeaderMap,
+    ) -> u16 {
+        reqwest::Client::new()
+            .post(&format!("http://{}{}", address, path))
+            .headers(headers)
+            .body(body.to_owned())
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
 }
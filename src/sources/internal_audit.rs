@@ -0,0 +1,144 @@
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use vector_core::ByteSizeOf;
+
+use crate::{
+    audit,
+    config::{log_schema, DataType, Output, SourceConfig, SourceContext, SourceDescription},
+    event::{Event, LogEvent},
+    internal_events::{EventsReceived, InternalAuditEventsLost, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct InternalAuditConfig {
+    pub host_key: Option<String>,
+    pub pid_key: Option<String>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<InternalAuditConfig>("internal_audit")
+}
+
+impl_generate_config_from_default!(InternalAuditConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "internal_audit")]
+impl SourceConfig for InternalAuditConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let host_key = self
+            .host_key
+            .as_deref()
+            .unwrap_or_else(|| log_schema().host_key())
+            .to_owned();
+        let pid_key = self.pid_key.as_deref().unwrap_or("pid").to_owned();
+
+        // Subscribing while building lets the sources of the first topology receive the actions
+        // recorded before it started.
+        let (buffered, rx) = audit::subscribe();
+
+        Ok(Box::pin(run(
+            host_key,
+            pid_key,
+            buffered,
+            BroadcastStream::new(rx),
+            cx.out,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "internal_audit"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn run(
+    host_key: String,
+    pid_key: String,
+    buffered: Vec<LogEvent>,
+    rx: BroadcastStream<LogEvent>,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let hostname = crate::get_hostname();
+    let pid = std::process::id();
+
+    let mut rx = stream::iter(buffered.into_iter().map(Ok))
+        .chain(rx)
+        .take_until(shutdown);
+
+    while let Some(result) = rx.next().await {
+        let mut log = match result {
+            Ok(log) => log,
+            Err(BroadcastStreamRecvError::Lagged(count)) => {
+                emit!(InternalAuditEventsLost { count });
+                continue;
+            }
+        };
+        emit!(EventsReceived {
+            count: 1,
+            byte_size: log.size_of(),
+        });
+        if let Ok(hostname) = &hostname {
+            log.insert(host_key.as_str(), hostname.to_owned());
+        }
+        log.insert(pid_key.as_str(), pid);
+        if let Err(error) = out.send_event(Event::from(log)).await {
+            emit!(StreamClosedError { error, count: 1 });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::Value;
+
+    use super::*;
+    use crate::{audit::AuditAction, test_util::collect_ready};
+
+    #[test]
+    fn generates_config() {
+        crate::test_util::test_generate_config::<InternalAuditConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_recorded_actions() {
+        let (tx, rx) = SourceSender::new_test();
+        let source = InternalAuditConfig::default()
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        audit::record(AuditAction::ApiMutation {
+            mutation: "setLogLevel",
+            arguments: vec![("level", "vector=debug".into())],
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        let events = collect_ready(rx).await;
+        let log = events
+            .iter()
+            .map(Event::as_log)
+            .find(|log| log.get("mutation") == Some(&Value::from("setLogLevel")))
+            .expect("the mutation wasn't received");
+        assert_eq!(log["action"], "api_mutation".into());
+        assert_eq!(log["arguments.level"], "vector=debug".into());
+        assert_eq!(log[log_schema().source_type_key()], "internal_audit".into());
+        assert!(log.get("pid").is_some());
+    }
+}
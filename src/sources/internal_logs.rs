@@ -1,12 +1,14 @@
 use bytes::Bytes;
-use chrono::Utc;
-use futures::{stream, StreamExt};
+use chrono::{DateTime, Utc};
+use futures::{future, stream, StreamExt};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use tokio::time::{self, Duration, Instant, Interval};
 use vector_core::ByteSizeOf;
 
 use crate::{
     config::{log_schema, DataType, Output, SourceConfig, SourceContext, SourceDescription},
-    event::Event,
+    event::{Event, LogEvent},
     internal_events::{InternalLogsBytesReceived, InternalLogsEventsReceived, StreamClosedError},
     shutdown::ShutdownSignal,
     trace::TraceSubscription,
@@ -18,6 +20,44 @@ use crate::{
 pub struct InternalLogsConfig {
     pub host_key: Option<String>,
     pub pid_key: Option<String>,
+    /// Summarizes the errors and warnings of each component on the `errors` output.
+    pub errors: Option<ErrorsConfig>,
+}
+
+/// The output the summaries of the errors and warnings of components are sent to.
+pub const ERRORS: &str = "errors";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ErrorsConfig {
+    /// The window over which repeated errors are counted into a single event.
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+    /// The least severe level summarized.
+    #[serde(default)]
+    pub level: ErrorLevel,
+}
+
+const fn default_window_secs() -> u64 {
+    10
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorLevel {
+    #[derivative(Default)]
+    Warn,
+    Error,
+}
+
+impl ErrorLevel {
+    fn includes(self, level: &str) -> bool {
+        match self {
+            ErrorLevel::Warn => level == "WARN" || level == "ERROR",
+            ErrorLevel::Error => level == "ERROR",
+        }
+    }
 }
 
 inventory::submit! {
@@ -42,6 +82,7 @@ impl SourceConfig for InternalLogsConfig {
         Ok(Box::pin(run(
             host_key,
             pid_key,
+            self.errors.clone(),
             subscription,
             cx.out,
             cx.shutdown,
@@ -49,7 +90,11 @@ impl SourceConfig for InternalLogsConfig {
     }
 
     fn outputs(&self) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        let mut outputs = vec![Output::default(DataType::Log)];
+        if self.errors.is_some() {
+            outputs.push(Output::default(DataType::Log).with_port(ERRORS));
+        }
+        outputs
     }
 
     fn source_type(&self) -> &'static str {
@@ -64,12 +109,27 @@ impl SourceConfig for InternalLogsConfig {
 async fn run(
     host_key: String,
     pid_key: String,
+    errors: Option<ErrorsConfig>,
     mut subscription: TraceSubscription,
     mut out: SourceSender,
     shutdown: ShutdownSignal,
 ) -> Result<(), ()> {
     let hostname = crate::get_hostname();
     let pid = std::process::id();
+    let annotate = |log: &mut LogEvent| {
+        if let Ok(hostname) = &hostname {
+            log.insert(host_key.as_str(), hostname.to_owned());
+        }
+        log.insert(pid_key.as_str(), pid);
+        log.try_insert(log_schema().source_type_key(), Bytes::from("internal_logs"));
+        log.try_insert(log_schema().timestamp_key(), Utc::now());
+    };
+
+    let mut summary = errors.as_ref().map(ErrorSummary::new);
+    let mut flush = errors.map(|errors| {
+        let window = Duration::from_secs(errors.window_secs.max(1));
+        time::interval_at(Instant::now() + window, window)
+    });
 
     // Chain any log events that were captured during early buffering to the front,
     // and then continue with the normal stream of internal log events.
@@ -81,30 +141,157 @@ async fn run(
     // Note: This loop, or anything called within it, MUST NOT generate
     // any logs that don't break the loop, as that could cause an
     // infinite loop since it receives all such logs.
-    while let Some(mut log) = rx.next().await {
-        let byte_size = log.size_of();
-        // This event doesn't emit any log
-        emit!(InternalLogsBytesReceived { byte_size });
-        emit!(InternalLogsEventsReceived {
-            count: 1,
-            byte_size,
-        });
-        if let Ok(hostname) = &hostname {
-            log.insert(host_key.as_str(), hostname.to_owned());
-        }
-        log.insert(pid_key.as_str(), pid);
-        log.try_insert(log_schema().source_type_key(), Bytes::from("internal_logs"));
-        log.try_insert(log_schema().timestamp_key(), Utc::now());
-        if let Err(error) = out.send_event(Event::from(log)).await {
-            // this wont trigger any infinite loop considering it stops the component
-            emit!(StreamClosedError { error, count: 1 });
-            return Err(());
+    loop {
+        tokio::select! {
+            log = rx.next() => {
+                let mut log = match log {
+                    Some(log) => log,
+                    None => break,
+                };
+                let byte_size = log.size_of();
+                // This event doesn't emit any log
+                emit!(InternalLogsBytesReceived { byte_size });
+                emit!(InternalLogsEventsReceived {
+                    count: 1,
+                    byte_size,
+                });
+                if let Some(summary) = &mut summary {
+                    summary.record(&log);
+                }
+                annotate(&mut log);
+                if let Err(error) = out.send_event(Event::from(log)).await {
+                    // this wont trigger any infinite loop considering it stops the component
+                    emit!(StreamClosedError { error, count: 1 });
+                    return Err(());
+                }
+            }
+            _ = tick(&mut flush) => {
+                if let Some(summary) = &mut summary {
+                    send_summary(summary, &annotate, &mut out).await?;
+                }
+            }
         }
     }
 
+    if let Some(summary) = &mut summary {
+        send_summary(summary, &annotate, &mut out).await?;
+    }
+
     Ok(())
 }
 
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => future::pending().await,
+    }
+}
+
+async fn send_summary(
+    summary: &mut ErrorSummary,
+    annotate: impl Fn(&mut LogEvent),
+    out: &mut SourceSender,
+) -> Result<(), ()> {
+    let mut events = summary.flush();
+    if events.is_empty() {
+        return Ok(());
+    }
+    events.iter_mut().for_each(annotate);
+    let count = events.len();
+    out.send_batch_named(ERRORS, events).await.map_err(|error| {
+        emit!(StreamClosedError { error, count });
+    })
+}
+
+/// What the errors and warnings are grouped by: the component logging them, and what they are.
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct ErrorKey {
+    component_id: Option<String>,
+    component_kind: Option<String>,
+    component_type: Option<String>,
+    level: String,
+    message: String,
+}
+
+#[derive(Debug)]
+struct ErrorCount {
+    count: u64,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    error_type: Option<String>,
+    stage: Option<String>,
+}
+
+/// The errors and warnings logged over the current window, counted by component and message.
+#[derive(Debug)]
+struct ErrorSummary {
+    level: ErrorLevel,
+    counts: IndexMap<ErrorKey, ErrorCount>,
+}
+
+impl ErrorSummary {
+    fn new(config: &ErrorsConfig) -> Self {
+        Self {
+            level: config.level,
+            counts: IndexMap::new(),
+        }
+    }
+
+    fn record(&mut self, log: &LogEvent) {
+        let field = |key: &str| log.get(key).map(|value| value.to_string_lossy());
+        let level = match field("metadata.level") {
+            Some(level) if self.level.includes(&level) => level,
+            _ => return,
+        };
+        let key = ErrorKey {
+            component_id: field("vector.component_id"),
+            component_kind: field("vector.component_kind"),
+            component_type: field("vector.component_type"),
+            level,
+            message: field(log_schema().message_key()).unwrap_or_default(),
+        };
+        let now = Utc::now();
+        let count = self.counts.entry(key).or_insert_with(|| ErrorCount {
+            count: 0,
+            first_seen: now,
+            last_seen: now,
+            error_type: field("error_type"),
+            stage: field("stage"),
+        });
+        count.count += 1;
+        count.last_seen = now;
+    }
+
+    /// An event per distinct error or warning of the window, starting the next one.
+    fn flush(&mut self) -> Vec<LogEvent> {
+        self.counts
+            .drain(..)
+            .map(|(key, count)| {
+                let mut log = LogEvent::default();
+                log.insert(log_schema().message_key(), key.message);
+                log.insert("level", key.level.to_lowercase());
+                log.insert("count", count.count as i64);
+                log.insert("first_seen", count.first_seen);
+                log.insert("last_seen", count.last_seen);
+                for (name, value) in [
+                    ("component_id", key.component_id),
+                    ("component_kind", key.component_kind),
+                    ("component_type", key.component_type),
+                    ("error_type", count.error_type),
+                    ("stage", count.stage),
+                ] {
+                    if let Some(value) = value {
+                        log.insert(name, value);
+                    }
+                }
+                log
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::Stream;
@@ -214,6 +401,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn summarizes_errors() {
+        let log = |level: &str, component_id: Option<&str>, message: &str| {
+            let mut log = LogEvent::default();
+            log.insert("metadata.level", level);
+            log.insert("message", message);
+            if let Some(component_id) = component_id {
+                log.insert("vector.component_id", component_id);
+                log.insert("vector.component_type", "http");
+                log.insert("error_type", "request_failed");
+            }
+            log
+        };
+
+        let mut summary = ErrorSummary::new(&ErrorsConfig {
+            window_secs: 10,
+            level: ErrorLevel::Warn,
+        });
+        for _ in 0..3 {
+            summary.record(&log("ERROR", Some("out"), "HTTP request failed."));
+        }
+        summary.record(&log("WARN", None, "Retrying."));
+        summary.record(&log("INFO", Some("out"), "Healthcheck passed."));
+
+        let events = summary.flush();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["message"], "HTTP request failed.".into());
+        assert_eq!(events[0]["level"], "error".into());
+        assert_eq!(events[0]["count"], 3.into());
+        assert_eq!(events[0]["component_id"], "out".into());
+        assert_eq!(events[0]["component_type"], "http".into());
+        assert_eq!(events[0]["error_type"], "request_failed".into());
+        assert_eq!(events[1]["count"], 1.into());
+        assert!(events[1].get("component_id").is_none());
+        assert!(summary.flush().is_empty());
+
+        assert!(!ErrorLevel::Error.includes("WARN"));
+    }
+
     async fn start_source() -> impl Stream<Item = Event> {
         let (tx, rx) = SourceSender::new_test();
 
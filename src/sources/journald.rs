@@ -34,6 +34,7 @@ use tokio_util::codec::FramedRead;
 use vector_core::{finalizer::OrderedFinalizer, ByteSizeOf};
 
 use crate::{
+    checkpoint::CheckpointMirror,
     config::{
         log_schema, AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext,
         SourceDescription,
@@ -173,6 +174,11 @@ impl SourceConfig for JournaldConfig {
         let mut checkpoint_path = data_dir;
         checkpoint_path.push(CHECKPOINT_FILENAME);
 
+        let mirror = CheckpointMirror::new(&cx.globals, &cx.key, checkpoint_path.clone()).await?;
+        if let Some(mirror) = &mirror {
+            mirror.restore().await?;
+        }
+
         let journalctl_path = self
             .journalctl_path
             .clone()
@@ -187,8 +193,9 @@ impl SourceConfig for JournaldConfig {
 
         let batch_size = self.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let acknowledgements_timeout = cx.acknowledgements_timeout(&self.acknowledgements);
 
-        Ok(Box::pin(
+        let source: super::Source = Box::pin(
             JournaldSource {
                 include_matches,
                 exclude_matches,
@@ -197,10 +204,15 @@ impl SourceConfig for JournaldConfig {
                 remap_priority: self.remap_priority,
                 out: cx.out,
                 acknowledgements,
+                acknowledgements_timeout,
                 starter,
             }
             .run_shutdown(cx.shutdown),
-        ))
+        );
+        Ok(match mirror {
+            Some(mirror) => mirror.wrap(source),
+            None => source,
+        })
     }
 
     fn outputs(&self) -> Vec<Output> {
@@ -224,6 +236,7 @@ struct JournaldSource {
     remap_priority: bool,
     out: SourceSender,
     acknowledgements: bool,
+    acknowledgements_timeout: Option<Duration>,
     starter: StartJournalctl,
 }
 
@@ -242,6 +255,7 @@ impl JournaldSource {
         let checkpointer = SharedCheckpointer::new(checkpointer);
         let (finalizer, shutdown) = Finalizer::new(
             self.acknowledgements,
+            self.acknowledgements_timeout,
             checkpointer.clone(),
             shutdown.clone(),
         );
@@ -648,14 +662,17 @@ impl Finalizer {
     /// `acknowledgements` are enabled, the checkpointer is written in
     /// a background task as acknowledgements are received from sinks,
     /// and shutdown can be triggered by either the system shutdown or
-    /// a negative acknowledgement.
+    /// a negative acknowledgement, including one for a batch that was
+    /// not acknowledged within `acknowledgements_timeout`.
     fn new(
         acknowledgements: bool,
+        acknowledgements_timeout: Option<Duration>,
         checkpointer: SharedCheckpointer,
         shutdown: ShutdownSignal,
     ) -> (Self, BoxFuture<'static, ()>) {
         if acknowledgements {
-            let (finalizer, mut ack_stream) = OrderedFinalizer::new(shutdown.clone());
+            let (finalizer, mut ack_stream) =
+                OrderedFinalizer::new(shutdown.clone(), acknowledgements_timeout);
             let (trigger, tripwire) = oneshot::channel();
             tokio::spawn(async move {
                 while let Some((status, cursor)) = ack_stream.next().await {
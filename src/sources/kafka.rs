@@ -2,6 +2,7 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     io::Cursor,
     sync::Arc,
+    time::Duration,
 };
 
 use async_stream::stream;
@@ -28,7 +29,7 @@ use crate::{
         log_schema, AcknowledgementsConfig, LogSchema, Output, SourceConfig, SourceContext,
         SourceDescription,
     },
-    event::{BatchNotifier, BatchStatus, Event, Value},
+    event::{BatchNotifier, BatchStatus, Event, KafkaOffset, Value},
     internal_events::{
         KafkaBytesReceived, KafkaEventsReceived, KafkaNegativeAcknowledgmentError,
         KafkaOffsetUpdateError, KafkaReadError, StreamClosedError,
@@ -54,6 +55,9 @@ pub struct KafkaSourceConfig {
     bootstrap_servers: String,
     topics: Vec<String>,
     group_id: String,
+    /// Makes this consumer a static member of the group, so that restarting it within the session
+    /// timeout doesn't rebalance the group.
+    group_instance_id: Option<String>,
     #[serde(default = "default_auto_offset_reset")]
     auto_offset_reset: String,
     #[serde(default = "default_session_timeout_ms")]
@@ -85,6 +89,10 @@ pub struct KafkaSourceConfig {
     decoding: DeserializerConfig,
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+    /// Leave committing the consumer offsets to a transactional Kafka sink, which commits them in
+    /// the same transaction as it produces the events.
+    #[serde(default)]
+    exactly_once: bool,
 }
 
 const fn default_session_timeout_ms() -> u64 {
@@ -139,7 +147,10 @@ impl SourceConfig for KafkaSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let consumer = create_consumer(self)?;
         let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build();
-        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        // Failed transactions are reported back to the source as negative acknowledgements, so
+        // they're always needed in exactly-once mode.
+        let acknowledgements = self.exactly_once || cx.do_acknowledgements(&self.acknowledgements);
+        let acknowledgements_timeout = cx.acknowledgements_timeout(&self.acknowledgements);
 
         Ok(Box::pin(kafka_source(
             self.clone(),
@@ -148,6 +159,7 @@ impl SourceConfig for KafkaSourceConfig {
             cx.shutdown,
             cx.out,
             acknowledgements,
+            acknowledgements_timeout,
         )))
     }
 
@@ -162,6 +174,10 @@ impl SourceConfig for KafkaSourceConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn requires_transactional_sinks(&self) -> bool {
+        self.exactly_once
+    }
 }
 
 async fn kafka_source(
@@ -171,10 +187,14 @@ async fn kafka_source(
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
+    acknowledgements_timeout: Option<Duration>,
 ) -> Result<(), ()> {
     let consumer = Arc::new(consumer);
-    let (finalizer, mut ack_stream) =
-        OrderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, shutdown.clone());
+    let (finalizer, mut ack_stream) = OrderedFinalizer::<FinalizerEntry>::maybe_new(
+        acknowledgements,
+        shutdown.clone(),
+        acknowledgements_timeout,
+    );
     let mut stream = consumer.stream();
     let keys = Keys::from(log_schema(), &config);
 
@@ -184,7 +204,7 @@ async fn kafka_source(
         tokio::select! {
             _ = &mut shutdown => break,
             entry = ack_stream.next() => if let Some((status, entry)) = entry {
-                handle_ack(&mut topics, status, entry, &consumer);
+                handle_ack(&mut topics, status, entry, &consumer, config.exactly_once);
             },
             message = stream.next() => match message {
                 None => break,  // WHY?
@@ -225,11 +245,18 @@ fn handle_ack(
     status: BatchStatus,
     entry: FinalizerEntry,
     consumer: &StreamConsumer<KafkaStatisticsContext>,
+    exactly_once: bool,
 ) {
     if !topics.failed.contains(&entry.topic) {
         if status == BatchStatus::Delivered {
-            if let Err(error) = consumer.store_offset(&entry.topic, entry.partition, entry.offset) {
-                emit!(KafkaOffsetUpdateError { error });
+            // In exactly-once mode, the sink has already committed the offset as part of its
+            // transaction.
+            if !exactly_once {
+                if let Err(error) =
+                    consumer.store_offset(&entry.topic, entry.partition, entry.offset)
+                {
+                    emit!(KafkaOffsetUpdateError { error });
+                }
             }
         } else {
             emit!(KafkaNegativeAcknowledgmentError {
@@ -318,6 +345,14 @@ fn parse_stream<'a>(
     let payload = msg.payload()?; // skip messages with empty payload
 
     let rmsg = ReceivedMessage::from(msg);
+    let kafka_offset = keys.group_id.map(|group_id| {
+        Arc::new(KafkaOffset {
+            group_id: Arc::from(group_id),
+            topic: Arc::from(msg.topic()),
+            partition: msg.partition(),
+            offset: msg.offset(),
+        })
+    });
 
     let payload = Cursor::new(Bytes::copy_from_slice(payload));
 
@@ -335,6 +370,7 @@ fn parse_stream<'a>(
                     });
                     for mut event in events {
                         rmsg.apply(&keys, &mut event);
+                        event.metadata_mut().set_kafka_offset(kafka_offset.clone());
                         yield event;
                     }
                 },
@@ -361,6 +397,8 @@ struct Keys<'a> {
     partition: &'a str,
     offset: &'a str,
     headers: &'a str,
+    /// The consumer group, if the position of the consumer is to be attached to events.
+    group_id: Option<&'a str>,
 }
 
 impl<'a> Keys<'a> {
@@ -373,6 +411,7 @@ impl<'a> Keys<'a> {
             partition: config.partition_key.as_str(),
             offset: config.offset_key.as_str(),
             headers: config.headers_key.as_str(),
+            group_id: config.exactly_once.then(|| config.group_id.as_str()),
         }
     }
 }
@@ -466,7 +505,7 @@ fn create_consumer(
         .set("socket.timeout.ms", &config.socket_timeout_ms.to_string())
         .set("fetch.wait.max.ms", &config.fetch_wait_max_ms.to_string())
         .set("enable.partition.eof", "false")
-        .set("enable.auto.commit", "true")
+        .set("enable.auto.commit", &(!config.exactly_once).to_string())
         .set(
             "auto.commit.interval.ms",
             &config.commit_interval_ms.to_string(),
@@ -475,6 +514,10 @@ fn create_consumer(
         .set("statistics.interval.ms", "1000")
         .set("client.id", "vector");
 
+    if let Some(group_instance_id) = &config.group_instance_id {
+        client_config.set("group.instance.id", group_instance_id);
+    }
+
     config.auth.apply(&mut client_config)?;
 
     if let Some(librdkafka_options) = &config.librdkafka_options {
@@ -649,6 +692,7 @@ mod integration_test {
                 shutdown,
                 tx,
                 acknowledgements,
+                None,
             ));
             let events = collect_n(rx, SEND_COUNT).await;
             // Yield to the finalization task to let it collect the
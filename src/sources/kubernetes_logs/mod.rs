@@ -465,6 +465,8 @@ impl Source {
             emitter: FileSourceInternalEventsEmitter,
             // A handle to the current tokio runtime
             handle: tokio::runtime::Handle::current(),
+            // Not exposed for now, like most of the settings of the file server.
+            io_uring: false,
         };
 
         let (file_source_tx, file_source_rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
@@ -85,6 +85,9 @@ impl SourceConfig for NginxMetricsConfig {
     async fn build(&self, mut cx: SourceContext) -> crate::Result<super::Source> {
         let tls = TlsSettings::from_options(&self.tls)?;
         let http_client = HttpClient::new(tls, &cx.proxy)?;
+        if let Some(auth) = &self.auth {
+            auth.prepare(&http_client).await?;
+        }
 
         let namespace = Some(self.namespace.clone()).filter(|namespace| !namespace.is_empty());
         let mut sources = Vec::with_capacity(self.endpoints.len());
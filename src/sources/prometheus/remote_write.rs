@@ -59,7 +59,8 @@ impl SourceConfig for PrometheusRemoteWriteConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
         let source = RemoteWriteSource;
         source.run(
-            self.address,
+            self.address.into(),
+            None,
             "",
             HttpMethod::Post,
             true,
@@ -13,6 +13,8 @@ use vector_core::ByteSizeOf;
 
 use super::parser;
 use crate::{
+    aws::sigv4::{AwsSigV4Config, AwsSigV4Signer},
+    cluster::{self, SharedWork},
     config::{
         self, GenerateConfig, Output, ProxyConfig, SourceConfig, SourceContext, SourceDescription,
     },
@@ -55,6 +57,9 @@ struct PrometheusScrapeConfig {
     query: Option<HashMap<String, Vec<String>>>,
     tls: Option<TlsConfig>,
     auth: Option<Auth>,
+    /// Sign requests with AWS Signature Version 4, for endpoints behind AWS services.
+    #[serde(default)]
+    aws: Option<AwsSigV4Config>,
 }
 
 pub(crate) const fn default_scrape_interval_secs() -> u64 {
@@ -80,6 +85,7 @@ impl GenerateConfig for PrometheusScrapeConfig {
             query: None,
             tls: None,
             auth: None,
+            aws: None,
         })
         .unwrap()
     }
@@ -122,10 +128,29 @@ impl SourceConfig for PrometheusScrapeConfig {
             })
             .collect::<Result<Vec<http::Uri>, sources::BuildError>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
+        if self.auth.is_some() && self.aws.is_some() {
+            return Err("`auth` and `aws` can't both be set, as both authorize requests.".into());
+        }
+        if let Some(auth) = &self.auth {
+            let client = HttpClient::new(tls.clone(), &cx.proxy)?;
+            auth.prepare(&client).await?;
+        }
+        let signer = match &self.aws {
+            Some(aws) => Some(aws.signer().await?),
+            None => None,
+        };
+        // In a cluster, each endpoint is scraped by only the member owning it.
+        let urls = SharedWork::new(
+            cluster::global(&cx.globals.cluster)?,
+            cx.key.id(),
+            urls,
+            ToString::to_string,
+        );
         Ok(prometheus(
             self.clone(),
             urls,
             tls,
+            signer,
             cx.proxy.clone(),
             cx.shutdown,
             cx.out,
@@ -163,6 +188,8 @@ struct PrometheusCompatConfig {
     scrape_interval_secs: u64,
     tls: Option<TlsConfig>,
     auth: Option<Auth>,
+    #[serde(default)]
+    aws: Option<AwsSigV4Config>,
 }
 
 #[async_trait::async_trait]
@@ -180,6 +207,7 @@ impl SourceConfig for PrometheusCompatConfig {
             scrape_interval_secs: self.scrape_interval_secs,
             tls: self.tls.clone(),
             auth: self.auth.clone(),
+            aws: self.aws.clone(),
         };
         config.build(cx).await
     }
@@ -219,8 +247,9 @@ struct EndpointInfo {
 
 async fn prometheus(
     config: PrometheusScrapeConfig,
-    urls: Vec<http::Uri>,
+    urls: SharedWork<http::Uri>,
     tls: TlsSettings,
+    signer: Option<AwsSigV4Signer>,
     proxy: ProxyConfig,
     shutdown: ShutdownSignal,
     mut out: SourceSender,
@@ -229,7 +258,7 @@ async fn prometheus(
         config.scrape_interval_secs,
     )))
     .take_until(shutdown)
-    .map(move |_| stream::iter(urls.clone()))
+    .map(move |_| stream::iter(urls.owned()))
     .flatten()
     .map(move |url| {
         let client = HttpClient::new(tls.clone(), &proxy).expect("Building HTTP client failed");
@@ -266,9 +295,8 @@ async fn prometheus(
         });
 
         let start = Instant::now();
-        client
-            .send(request)
-            .map_err(crate::Error::from)
+        sign(signer.clone(), request)
+            .and_then(move |request| client.send(request).map_err(crate::Error::from))
             .and_then(|response| async move {
                 let (header, body) = response.into_parts();
                 let body = hyper::body::to_bytes(body).await?;
@@ -403,6 +431,17 @@ async fn prometheus(
     }
 }
 
+/// Signs the scrape request with AWS Signature Version 4, if configured to.
+async fn sign(
+    signer: Option<AwsSigV4Signer>,
+    mut request: Request<Body>,
+) -> crate::Result<Request<Body>> {
+    if let Some(signer) = signer {
+        signer.sign(&mut request, &[]).await?;
+    }
+    Ok(request)
+}
+
 #[cfg(all(test, feature = "sinks-prometheus"))]
 mod test {
     use hyper::{
@@ -451,6 +490,7 @@ mod test {
             honor_labels: true,
             query: None,
             auth: None,
+            aws: None,
             tls: None,
         };
 
@@ -483,6 +523,7 @@ mod test {
             honor_labels: true,
             query: None,
             auth: None,
+            aws: None,
             tls: None,
         };
 
@@ -533,6 +574,7 @@ mod test {
             honor_labels: false,
             query: None,
             auth: None,
+            aws: None,
             tls: None,
         };
 
@@ -602,6 +644,7 @@ mod test {
                 ),
             ])),
             auth: None,
+            aws: None,
             tls: None,
         };
 
@@ -702,6 +745,7 @@ mod test {
                 scrape_interval_secs: 1,
                 tls: None,
                 auth: None,
+                aws: None,
             },
         );
         config.add_sink(
@@ -715,6 +759,10 @@ mod test {
                 quantiles: vec![],
                 distributions_as_summaries: false,
                 flush_period_secs: Duration::from_secs(1),
+                expiration: Vec::new(),
+                allowed_metrics: Vec::new(),
+                relabel: Vec::new(),
+                protobuf: false,
             },
         );
 
@@ -789,6 +837,7 @@ mod integration_tests {
             honor_labels: false,
             query: None,
             auth: None,
+            aws: None,
             tls: None,
         };
 
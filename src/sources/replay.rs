@@ -0,0 +1,294 @@
+use std::{path::PathBuf, task::Poll};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::{
+    fs,
+    time::{self, Instant},
+};
+use vector_core::ByteSizeOf;
+
+use crate::{
+    archive::{self, Record},
+    config::{
+        DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext, SourceDescription,
+    },
+    internal_events::{ArchiveReadError, BytesReceived, EventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Re-injects the events of an archive written by the `archive` sink, such as to reprocess them
+/// after a bad deploy, then stops.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayConfig {
+    /// The directory of the archive.
+    pub path: PathBuf,
+    /// Replays only the events from this time on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<DateTime<Utc>>,
+    /// Replays only the events before this time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Utc>>,
+    /// Replays the events as far apart as they happened, divided by this speed, rather than as
+    /// fast as possible.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+}
+
+#[derive(Debug, Snafu)]
+pub enum ReplayConfigError {
+    #[snafu(display("The speed of the replay must be positive"))]
+    InvalidSpeed,
+    #[snafu(display("The start of the replay must be before its end"))]
+    InvalidRange,
+}
+
+inventory::submit! {
+    SourceDescription::new::<ReplayConfig>("replay")
+}
+
+impl GenerateConfig for ReplayConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            path: PathBuf::from("/var/lib/vector/archive"),
+            start: None,
+            end: None,
+            speed: None,
+        })
+        .unwrap()
+    }
+}
+
+impl ReplayConfig {
+    fn validate(&self) -> Result<(), ReplayConfigError> {
+        if matches!(self.speed, Some(speed) if speed <= 0.0 || !speed.is_finite()) {
+            return Err(ReplayConfigError::InvalidSpeed);
+        }
+        if matches!((self.start, self.end), (Some(start), Some(end)) if start >= end) {
+            return Err(ReplayConfigError::InvalidRange);
+        }
+        Ok(())
+    }
+
+    fn includes(&self, timestamp: DateTime<Utc>) -> bool {
+        self.start.map_or(true, |start| timestamp >= start)
+            && self.end.map_or(true, |end| timestamp < end)
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "replay")]
+impl SourceConfig for ReplayConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        self.validate()?;
+        Ok(Box::pin(replay(self.clone(), cx.shutdown, cx.out)))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::all())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "replay"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Sends the events of the archive within the range, in the order they were archived. When
+/// paced, each is sent at its offset from the first one, divided by the speed, so that the time
+/// taken to send them doesn't add up.
+async fn replay(
+    config: ReplayConfig,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let files = archive::files(&config.path).await.map_err(|error| {
+        emit!(ArchiveReadError {
+            error,
+            path: &config.path,
+        })
+    })?;
+    let mut first: Option<(Instant, DateTime<Utc>)> = None;
+
+    for path in files {
+        let (mut reader, metadata) =
+            match futures::try_join!(archive::Reader::open(&path), fs::metadata(&path)) {
+                Ok(opened) => opened,
+                Err(error) => {
+                    emit!(ArchiveReadError { error, path: &path });
+                    continue;
+                }
+            };
+        emit!(BytesReceived {
+            byte_size: metadata.len() as usize,
+            protocol: "file",
+        });
+        loop {
+            let record = match reader.next().await {
+                Ok(Some(record)) => record,
+                Ok(None) => break,
+                Err(error) => {
+                    // The rest of a corrupt or truncated file can't be read.
+                    emit!(ArchiveReadError { error, path: &path });
+                    break;
+                }
+            };
+            if !config.includes(record.timestamp) {
+                continue;
+            }
+
+            if let Some(speed) = config.speed {
+                let (started, start) = *first.get_or_insert((Instant::now(), record.timestamp));
+                let offset = (record.timestamp - start)
+                    .to_std()
+                    .unwrap_or_default()
+                    .div_f64(speed);
+                tokio::select! {
+                    _ = time::sleep_until(started + offset) => {},
+                    _ = &mut shutdown => return Ok(()),
+                }
+            }
+            if matches!(futures::poll!(&mut shutdown), Poll::Ready(_)) {
+                return Ok(());
+            }
+
+            send(record, &mut out).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn send(record: Record, out: &mut SourceSender) -> Result<(), ()> {
+    let event = record.into_event();
+    emit!(EventsReceived {
+        count: 1,
+        byte_size: event.size_of(),
+    });
+    out.send_event(event).await.map_err(|error| {
+        emit!(StreamClosedError { error, count: 1 });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_compression::tokio::write::ZstdEncoder;
+    use chrono::TimeZone;
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+    use crate::{
+        config::log_schema,
+        event::{Event, LogEvent},
+        test_util::{
+            collect_ready,
+            components::{assert_source_compliance, SOURCE_TAGS},
+            temp_dir,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ReplayConfig>();
+    }
+
+    async fn write_archive(minutes: &[u32]) -> PathBuf {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).await.unwrap();
+        let archived_at = Utc.ymd(2022, 6, 1).and_hms(13, 0, 0);
+        let path = dir.join(archive::file_name(archived_at, 0));
+        let mut out = ZstdEncoder::new(fs::File::create(path).await.unwrap());
+        for minute in minutes {
+            let mut log = LogEvent::from(format!("event {}", minute));
+            log.insert(
+                log_schema().timestamp_key(),
+                Utc.ymd(2022, 6, 1).and_hms(12, *minute, 0),
+            );
+            let mut line = serde_json::to_vec(&Record::new(log.into(), archived_at)).unwrap();
+            line.push(b'\n');
+            out.write_all(&line).await.unwrap();
+        }
+        out.shutdown().await.unwrap();
+        dir
+    }
+
+    async fn run(config: ReplayConfig) -> Vec<Event> {
+        assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test();
+            config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+            collect_ready(rx).await
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn replays_range() {
+        let path = write_archive(&[0, 10, 20, 30]).await;
+        let events = run(ReplayConfig {
+            path,
+            start: Some(Utc.ymd(2022, 6, 1).and_hms(12, 10, 0)),
+            end: Some(Utc.ymd(2022, 6, 1).and_hms(12, 30, 0)),
+            speed: None,
+        })
+        .await;
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["event 10", "event 20"]);
+    }
+
+    #[tokio::test]
+    async fn paces_replay() {
+        let path = write_archive(&[0, 1]).await;
+        let started = Instant::now();
+        let events = run(ReplayConfig {
+            path,
+            start: None,
+            end: None,
+            speed: Some(600.0),
+        })
+        .await;
+
+        assert_eq!(events.len(), 2);
+        // A minute apart, replayed six hundred times as fast.
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let config = |speed, end| ReplayConfig {
+            path: PathBuf::from("/archive"),
+            start: Some(Utc.ymd(2022, 6, 1).and_hms(12, 0, 0)),
+            end,
+            speed,
+        };
+        assert!(matches!(
+            config(Some(0.0), None).validate(),
+            Err(ReplayConfigError::InvalidSpeed)
+        ));
+        assert!(matches!(
+            config(None, Some(Utc.ymd(2022, 6, 1).and_hms(11, 0, 0))).validate(),
+            Err(ReplayConfigError::InvalidRange)
+        ));
+        assert!(config(Some(2.0), None).validate().is_ok());
+    }
+}
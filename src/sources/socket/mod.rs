@@ -130,6 +130,7 @@ impl SourceConfig for SocketConfig {
                     cx,
                     false.into(),
                     config.connection_limit,
+                    config.proxy_protocol(),
                 )
             }
             Mode::Udp(config) => {
@@ -272,6 +273,7 @@ mod test {
             log_schema, ComponentKey, GlobalOptions, SinkContext, SourceConfig, SourceContext,
         },
         event::Event,
+        proxy_protocol::{self, ProxyProtocolVersion},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
         test_util::{
@@ -316,6 +318,40 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn tcp_it_includes_host_from_proxy_protocol() {
+        use tokio::io::AsyncWriteExt;
+
+        let (tx, mut rx) = SourceSender::new_test();
+        let addr = next_addr();
+
+        let config: TcpConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            port_key = "port"
+            proxy_protocol = true
+            "#,
+            addr
+        ))
+        .unwrap();
+        let server = SocketConfig::from(config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        let client_addr = "192.0.2.1:56324".parse().unwrap();
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let header = proxy_protocol::encode_header(ProxyProtocolVersion::V2, client_addr, addr);
+        stream.write_all(&header).await.unwrap();
+        stream.write_all(b"test\n").await.unwrap();
+
+        let event = rx.next().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().host_key()], "192.0.2.1".into());
+        assert_eq!(event.as_log()["port"], 56324_u16.into());
+    }
+
     #[tokio::test]
     async fn tcp_splits_on_newline() {
         let (tx, rx) = SourceSender::new_test();
@@ -694,6 +730,7 @@ mod test {
                 out: sender,
                 proxy: Default::default(),
                 acknowledgements: false,
+                acknowledgements_timeout: None,
                 schema_definitions: HashMap::default(),
             })
             .await
@@ -29,6 +29,8 @@ pub struct TcpConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
     pub connection_limit: Option<u32>,
+    #[serde(default)]
+    proxy_protocol: bool,
 }
 
 const fn default_shutdown_timeout_secs() -> u64 {
@@ -49,6 +51,7 @@ impl TcpConfig {
             framing: None,
             decoding: default_decoding(),
             connection_limit: None,
+            proxy_protocol: false,
         }
     }
 
@@ -88,6 +91,10 @@ impl TcpConfig {
         self.receive_buffer_bytes
     }
 
+    pub const fn proxy_protocol(&self) -> bool {
+        self.proxy_protocol
+    }
+
     pub fn set_max_length(&mut self, val: Option<usize>) -> &mut Self {
         self.max_length = val;
         self
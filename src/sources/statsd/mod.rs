@@ -125,6 +125,7 @@ impl SourceConfig for StatsdConfig {
                     cx,
                     false.into(),
                     config.connection_limit,
+                    false,
                 )
             }
             #[cfg(unix)]
@@ -52,6 +52,8 @@ pub enum Mode {
         tls: Option<TlsEnableableConfig>,
         receive_buffer_bytes: Option<usize>,
         connection_limit: Option<u32>,
+        #[serde(default)]
+        proxy_protocol: bool,
     },
     Udp {
         address: SocketAddr,
@@ -87,6 +89,7 @@ impl GenerateConfig for SyslogConfig {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                proxy_protocol: false,
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
@@ -111,6 +114,7 @@ impl SourceConfig for SyslogConfig {
                 tls,
                 receive_buffer_bytes,
                 connection_limit,
+                proxy_protocol,
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
@@ -127,6 +131,7 @@ impl SourceConfig for SyslogConfig {
                     cx,
                     false.into(),
                     connection_limit,
+                    proxy_protocol,
                 )
             }
             Mode::Udp {
@@ -745,6 +750,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                proxy_protocol: false,
             });
 
             let key = ComponentKey::from("in");
@@ -888,6 +894,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                proxy_protocol: false,
             });
 
             let key = ComponentKey::from("in");
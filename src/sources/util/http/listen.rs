@@ -0,0 +1,126 @@
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::{fmt, net::SocketAddr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::config::Resource;
+
+/// The address an HTTP server listens on.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum HttpListenAddr {
+    /// A TCP socket address.
+    SocketAddr(SocketAddr),
+    /// The path of a Unix domain socket, written as `unix:<path>`.
+    #[cfg(unix)]
+    #[serde(deserialize_with = "parse_unix_path")]
+    Unix(PathBuf),
+    /// The name of a Windows named pipe, written as `\\.\pipe\<name>`.
+    #[cfg(windows)]
+    #[serde(deserialize_with = "parse_pipe_name")]
+    Pipe(String),
+}
+
+impl HttpListenAddr {
+    /// Whether or not connections are accepted over TCP, rather than over a local socket.
+    pub const fn is_tcp(&self) -> bool {
+        matches!(self, Self::SocketAddr(_))
+    }
+
+    pub fn resources(&self) -> Vec<Resource> {
+        match self {
+            Self::SocketAddr(addr) => vec![Resource::tcp(*addr)],
+            #[cfg(any(unix, windows))]
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for HttpListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SocketAddr(addr) => addr.fmt(f),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+            #[cfg(windows)]
+            Self::Pipe(name) => name.fmt(f),
+        }
+    }
+}
+
+impl Serialize for HttpListenAddr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl From<SocketAddr> for HttpListenAddr {
+    fn from(addr: SocketAddr) -> Self {
+        Self::SocketAddr(addr)
+    }
+}
+
+#[cfg(unix)]
+fn parse_unix_path<'de, D>(des: D) -> Result<PathBuf, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: &'de str = Deserialize::deserialize(des)?;
+    match s.strip_prefix("unix:") {
+        Some(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+        _ => Err(de::Error::custom("must start with \"unix:\"")),
+    }
+}
+
+#[cfg(windows)]
+fn parse_pipe_name<'de, D>(des: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: &'de str = Deserialize::deserialize(des)?;
+    if s.starts_with(r"\\.\pipe\") {
+        Ok(s.to_owned())
+    } else {
+        Err(de::Error::custom(r#"must start with "\\.\pipe\""#))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        address: HttpListenAddr,
+    }
+
+    #[test]
+    fn parse_http_listen_addr() {
+        let test: Config = toml::from_str(r#"address = "127.0.0.1:8080""#).unwrap();
+        assert_eq!(
+            test.address,
+            HttpListenAddr::SocketAddr("127.0.0.1:8080".parse().unwrap())
+        );
+        assert!(toml::from_str::<Config>(r#"address = "/tmp/vector.sock""#).is_err());
+
+        #[cfg(unix)]
+        {
+            let test: Config = toml::from_str(r#"address = "unix:/tmp/vector.sock""#).unwrap();
+            assert_eq!(
+                test.address,
+                HttpListenAddr::Unix(PathBuf::from("/tmp/vector.sock"))
+            );
+            assert_eq!(test.address.to_string(), "unix:/tmp/vector.sock");
+        }
+
+        #[cfg(windows)]
+        {
+            let test: Config = toml::from_str(r#"address = '\\.\pipe\vector'"#).unwrap();
+            assert_eq!(
+                test.address,
+                HttpListenAddr::Pipe(r"\\.\pipe\vector".into())
+            );
+        }
+    }
+}
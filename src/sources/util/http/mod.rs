@@ -5,6 +5,8 @@ mod encoding;
 #[cfg(feature = "sources-utils-http-error")]
 mod error;
 #[cfg(feature = "sources-utils-http-prelude")]
+mod listen;
+#[cfg(feature = "sources-utils-http-prelude")]
 mod prelude;
 #[cfg(any(
     feature = "sources-http",
@@ -20,6 +22,8 @@ pub use encoding::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use error::ErrorMessage;
 #[cfg(feature = "sources-utils-http-prelude")]
-pub use prelude::HttpSource;
+pub use listen::HttpListenAddr;
+#[cfg(feature = "sources-utils-http-prelude")]
+pub use prelude::{run_routes, HttpSource, HttpSourceRoute};
 #[cfg(feature = "sources-utils-http-query")]
 pub use query::add_query_parameters;
@@ -1,8 +1,16 @@
-use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr};
+#[cfg(unix)]
+use std::fs::remove_file;
+use std::{collections::HashMap, convert::TryFrom, fmt};
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{FutureExt, TryFutureExt};
+use futures::FutureExt;
+#[cfg(windows)]
+use tokio::net::windows::named_pipe::{NamedPipeServer, ServerOptions};
+#[cfg(unix)]
+use tokio::net::UnixListener;
+#[cfg(unix)]
+use tokio_stream::wrappers::UnixListenerStream;
 use tracing::Span;
 use warp::{
     filters::{
@@ -11,7 +19,7 @@ use warp::{
     },
     http::{HeaderMap, StatusCode},
     reject::Rejection,
-    Filter,
+    Filter, Reply,
 };
 
 use vector_core::{
@@ -31,7 +39,10 @@ use super::{
     auth::{HttpSourceAuth, HttpSourceAuthConfig},
     encoding::decode,
     error::ErrorMessage,
+    listen::HttpListenAddr,
 };
+#[cfg(unix)]
+use crate::{internal_events::UnixSocketFileDeleteError, sources::util::change_socket_permissions};
 
 #[async_trait]
 pub trait HttpSource: Clone + Send + Sync + 'static {
@@ -46,7 +57,8 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
-        address: SocketAddr,
+        address: HttpListenAddr,
+        socket_file_mode: Option<u32>,
         path: &str,
         method: HttpMethod,
         strict_path: bool,
@@ -55,103 +67,228 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         cx: SourceContext,
         acknowledgements: AcknowledgementsConfig,
     ) -> crate::Result<crate::sources::Source> {
-        let tls = MaybeTlsSettings::from_config(tls, true)?;
-        let protocol = tls.http_protocol_name();
-        let auth = HttpSourceAuth::try_from(auth.as_ref())?;
-        let path = path.to_owned();
-        let acknowledgements = cx.do_acknowledgements(&acknowledgements);
-        Ok(Box::pin(async move {
-            let span = Span::current();
-            let mut filter: BoxedFilter<()> = match method {
-                HttpMethod::Head => warp::head().boxed(),
-                HttpMethod::Get => warp::get().boxed(),
-                HttpMethod::Put => warp::put().boxed(),
-                HttpMethod::Post => warp::post().boxed(),
-                HttpMethod::Patch => warp::patch().boxed(),
-                HttpMethod::Delete => warp::delete().boxed(),
-            };
+        let route = HttpSourceRoute {
+            source: self,
+            path: path.to_owned(),
+            method,
+            strict_path,
+            auth: HttpSourceAuth::try_from(auth.as_ref())?,
+            output: None,
+        };
+        run_routes(
+            vec![route],
+            address,
+            socket_file_mode,
+            tls,
+            cx,
+            acknowledgements,
+        )
+    }
+}
 
-            for s in path.split('/').filter(|&x| !x.is_empty()) {
-                filter = filter.and(warp::path(s.to_string())).boxed()
-            }
-            let svc = filter
-                .and(warp::path::tail())
-                .and_then(move |tail: Tail| async move {
-                    if !strict_path || tail.as_str().is_empty() {
-                        Ok(())
-                    } else {
-                        debug!(message = "Path rejected.");
-                        Err(warp::reject::custom(ErrorMessage::new(
-                            StatusCode::NOT_FOUND,
-                            "Not found".to_string(),
-                        )))
-                    }
-                })
-                .untuple_one()
-                .and(warp::path::full())
-                .and(warp::header::optional::<String>("authorization"))
-                .and(warp::header::optional::<String>("content-encoding"))
-                .and(warp::header::headers_cloned())
-                .and(warp::body::bytes())
-                .and(warp::query::<HashMap<String, String>>())
-                .and_then(
-                    move |path: FullPath,
-                          auth_header,
-                          encoding_header,
-                          headers: HeaderMap,
-                          body: Bytes,
-                          query_parameters: HashMap<String, String>| {
-                        debug!(message = "Handling HTTP request.", headers = ?headers);
-                        let http_path = path.as_str();
-                        emit!(HttpBytesReceived {
-                            byte_size: body.len(),
-                            http_path,
-                            protocol,
-                        });
+/// A path served by an HTTP source, the events built from the requests to which are sent to the
+/// given output, or to the default one.
+pub struct HttpSourceRoute<S> {
+    pub source: S,
+    pub path: String,
+    pub method: HttpMethod,
+    pub strict_path: bool,
+    pub auth: HttpSourceAuth,
+    pub output: Option<String>,
+}
 
-                        let events = auth
-                            .is_valid(&auth_header)
-                            .and_then(|()| decode(&encoding_header, body))
-                            .and_then(|body| {
-                                self.build_events(body, headers, query_parameters, path.as_str())
-                            })
-                            .map(|events| {
-                                emit!(HttpEventsReceived {
-                                    count: events.len(),
-                                    byte_size: events.size_of(),
-                                    http_path,
-                                    protocol,
-                                });
-                                events
-                            });
+impl<S: HttpSource> HttpSourceRoute<S> {
+    fn filter(
+        self,
+        protocol: &'static str,
+        acknowledgements: bool,
+        out: SourceSender,
+    ) -> BoxedFilter<(Box<dyn Reply>,)> {
+        let Self {
+            source,
+            path,
+            method,
+            strict_path,
+            auth,
+            output,
+        } = self;
 
-                        handle_request(events, acknowledgements, cx.out.clone())
-                    },
-                )
-                .with(warp::trace(move |_info| span.clone()));
+        let mut filter: BoxedFilter<()> = match method {
+            HttpMethod::Head => warp::head().boxed(),
+            HttpMethod::Get => warp::get().boxed(),
+            HttpMethod::Put => warp::put().boxed(),
+            HttpMethod::Post => warp::post().boxed(),
+            HttpMethod::Patch => warp::patch().boxed(),
+            HttpMethod::Delete => warp::delete().boxed(),
+        };
 
-            let ping = warp::get().and(warp::path("ping")).map(|| "pong");
-            let routes = svc.or(ping).recover(|r: Rejection| async move {
-                if let Some(e_msg) = r.find::<ErrorMessage>() {
-                    let json = warp::reply::json(e_msg);
-                    Ok(warp::reply::with_status(json, e_msg.status_code()))
+        for s in path.split('/').filter(|&x| !x.is_empty()) {
+            filter = filter.and(warp::path(s.to_string())).boxed()
+        }
+        filter
+            .and(warp::path::tail())
+            .and_then(move |tail: Tail| async move {
+                if !strict_path || tail.as_str().is_empty() {
+                    Ok(())
                 } else {
-                    //other internal error - will return 500 internal server error
-                    Err(r)
+                    debug!(message = "Path rejected.");
+                    Err(warp::reject::custom(ErrorMessage::new(
+                        StatusCode::NOT_FOUND,
+                        "Not found".to_string(),
+                    )))
                 }
-            });
-
-            info!(message = "Building HTTP server.", address = %address);
-
-            let listener = tls.bind(&address).await.unwrap();
-            warp::serve(routes)
-                .serve_incoming_with_graceful_shutdown(
-                    listener.accept_stream(),
-                    cx.shutdown.map(|_| ()),
-                )
-                .await;
-            Ok(())
-        }))
+            })
+            .untuple_one()
+            .and(warp::path::full())
+            .and(warp::header::optional::<String>("authorization"))
+            .and(warp::header::optional::<String>("content-encoding"))
+            .and(warp::header::headers_cloned())
+            .and(warp::body::bytes())
+            .and(warp::query::<HashMap<String, String>>())
+            .and_then(
+                move |path: FullPath,
+                      auth_header,
+                      encoding_header,
+                      headers: HeaderMap,
+                      body: Bytes,
+                      query_parameters: HashMap<String, String>| {
+                    debug!(message = "Handling HTTP request.", headers = ?headers);
+                    let http_path = path.as_str();
+                    emit!(HttpBytesReceived {
+                        byte_size: body.len(),
+                        http_path,
+                        protocol,
+                    });
+
+                    let events = auth
+                        .is_valid(&auth_header)
+                        .and_then(|()| decode(&encoding_header, body))
+                        .and_then(|body| {
+                            source.build_events(body, headers, query_parameters, path.as_str())
+                        })
+                        .map(|events| {
+                            emit!(HttpEventsReceived {
+                                count: events.len(),
+                                byte_size: events.size_of(),
+                                http_path,
+                                protocol,
+                            });
+                            events
+                        });
+
+                    handle_request(events, acknowledgements, out.clone(), output.clone())
+                },
+            )
+            .map(|reply| Box::new(reply) as Box<dyn Reply>)
+            .boxed()
+    }
+}
+
+/// Serves the routes on a single listener, each request being handled by the first route matching
+/// its method and path.
+pub fn run_routes<S: HttpSource>(
+    routes: Vec<HttpSourceRoute<S>>,
+    address: HttpListenAddr,
+    socket_file_mode: Option<u32>,
+    tls: &Option<TlsEnableableConfig>,
+    cx: SourceContext,
+    acknowledgements: AcknowledgementsConfig,
+) -> crate::Result<crate::sources::Source> {
+    let tls = MaybeTlsSettings::from_config(tls, true)?;
+    if tls.is_tls() && !address.is_tcp() {
+        return Err("TLS is only supported when listening on a TCP socket address.".into());
+    }
+    let protocol = tls.http_protocol_name();
+    let acknowledgements = cx.do_acknowledgements(&acknowledgements);
+    Ok(Box::pin(async move {
+        let span = Span::current();
+        let svc = routes
+            .into_iter()
+            .map(|route| route.filter(protocol, acknowledgements, cx.out.clone()))
+            .reduce(|svc, filter| svc.or(filter).unify().boxed())
+            .expect("HTTP sources serve at least one route")
+            .with(warp::trace(move |_info| span.clone()));
+
+        let ping = warp::get().and(warp::path("ping")).map(|| "pong");
+        let routes = svc.or(ping).recover(|r: Rejection| async move {
+            if let Some(e_msg) = r.find::<ErrorMessage>() {
+                let json = warp::reply::json(e_msg);
+                Ok(warp::reply::with_status(json, e_msg.status_code()))
+            } else {
+                //other internal error - will return 500 internal server error
+                Err(r)
+            }
+        });
+
+        info!(message = "Building HTTP server.", address = %address);
+
+        let shutdown = cx.shutdown.map(|_| ());
+        match address {
+            HttpListenAddr::SocketAddr(address) => {
+                let listener = tls.bind(&address).await.unwrap();
+                warp::serve(routes)
+                    .serve_incoming_with_graceful_shutdown(listener.accept_stream(), shutdown)
+                    .await;
+            }
+            #[cfg(unix)]
+            HttpListenAddr::Unix(path) => {
+                let listener = UnixListener::bind(&path).map_err(|error| {
+                    error!(message = "Failed to bind to listener socket.", ?path, %error);
+                })?;
+                change_socket_permissions(&path, socket_file_mode).map_err(|error| {
+                    error!(message = "Failed to set socket permissions.", ?path, %error);
+                })?;
+                warp::serve(routes)
+                    .serve_incoming_with_graceful_shutdown(
+                        UnixListenerStream::new(listener),
+                        shutdown,
+                    )
+                    .await;
+                if let Err(error) = remove_file(&path) {
+                    emit!(UnixSocketFileDeleteError { path: &path, error });
+                }
+            }
+            #[cfg(windows)]
+            HttpListenAddr::Pipe(name) => {
+                // Named pipes are secured by their default access control list rather than
+                // by a file mode.
+                let _ = socket_file_mode;
+                warp::serve(routes)
+                    .serve_incoming_with_graceful_shutdown(accept_pipe(name), shutdown)
+                    .await;
+            }
+        }
+        Ok(())
+    }))
+}
+
+/// Accepts clients of the named pipe, creating a new instance of the pipe for the next client each
+/// time one connects.
+#[cfg(windows)]
+fn accept_pipe(
+    name: String,
+) -> impl futures::Stream<Item = std::io::Result<NamedPipeServer>> + Send {
+    async_stream::stream! {
+        let mut server = match ServerOptions::new().first_pipe_instance(true).create(&name) {
+            Ok(server) => server,
+            Err(error) => {
+                yield Err(error);
+                return;
+            }
+        };
+        loop {
+            if let Err(error) = server.connect().await {
+                yield Err(error);
+                return;
+            }
+            match ServerOptions::new().create(&name) {
+                Ok(next) => yield Ok(std::mem::replace(&mut server, next)),
+                Err(error) => {
+                    yield Err(error);
+                    return;
+                }
+            }
+        }
     }
 }
 
@@ -169,21 +306,24 @@ async fn handle_request(
     events: Result<Vec<Event>, ErrorMessage>,
     acknowledgements: bool,
     mut out: SourceSender,
+    output: Option<String>,
 ) -> Result<impl warp::Reply, Rejection> {
     match events {
         Ok(mut events) => {
             let receiver = BatchNotifier::maybe_apply_to_events(acknowledgements, &mut events);
 
-            out.send_batch(events)
-                .map_err(move |error: crate::source_sender::ClosedError| {
-                    // can only fail if receiving end disconnected, so we are shutting down,
-                    // probably not gracefully.
-                    error!(message = "Failed to forward events, downstream is closed.");
-                    error!(message = "Tried to send the following event.", %error);
-                    warp::reject::custom(RejectShuttingDown)
-                })
-                .and_then(|_| handle_batch_status(receiver))
-                .await
+            let sent = match &output {
+                Some(output) => out.send_batch_named(output, events).await,
+                None => out.send_batch(events).await,
+            };
+            if let Err(error) = sent {
+                // can only fail if receiving end disconnected, so we are shutting down,
+                // probably not gracefully.
+                error!(message = "Failed to forward events, downstream is closed.");
+                error!(message = "Tried to send the following event.", %error);
+                return Err(warp::reject::custom(RejectShuttingDown));
+            }
+            handle_batch_status(receiver).await
         }
         Err(error) => {
             emit!(HttpBadRequest::new(error.code(), error.message()));
@@ -61,8 +61,8 @@ pub use self::http::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use self::http::ErrorMessage;
 #[cfg(feature = "sources-utils-http-prelude")]
-pub use self::http::HttpSource;
+pub use self::http::{run_routes, HttpListenAddr, HttpSource, HttpSourceRoute};
 #[cfg(feature = "sources-utils-http-auth")]
-pub use self::http::HttpSourceAuthConfig;
+pub use self::http::{HttpSourceAuth, HttpSourceAuthConfig};
 #[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
 pub use self::message_decoding::decode_message;
@@ -124,6 +124,7 @@ where
         cx: SourceContext,
         acknowledgements: AcknowledgementsConfig,
         max_connections: Option<u32>,
+        proxy_protocol: bool,
     ) -> crate::Result<crate::sources::Source> {
         let acknowledgements = cx.do_acknowledgements(&acknowledgements);
 
@@ -132,7 +133,7 @@ where
         Ok(Box::pin(async move {
             let listener = match make_listener(addr, listenfd, &tls).await {
                 None => return Err(()),
-                Some(listener) => listener,
+                Some(listener) => listener.with_proxy_protocol(proxy_protocol),
             };
 
             info!(
@@ -203,7 +204,6 @@ where
                                 receive_buffer_bytes,
                                 source,
                                 tripwire,
-                                peer_addr,
                                 out,
                                 acknowledgements,
                                 request_limiter,
@@ -233,7 +233,6 @@ async fn handle_stream<T>(
     receive_buffer_bytes: Option<usize>,
     source: T,
     mut tripwire: BoxFuture<'static, ()>,
-    peer_addr: SocketAddr,
     mut out: SourceSender,
     acknowledgements: bool,
     request_limiter: RequestLimiter,
@@ -253,6 +252,9 @@ async fn handle_stream<T>(
         }
     };
 
+    // Read after the handshake, which takes it from the PROXY protocol header, if enabled.
+    let peer_addr = socket.peer_addr();
+
     if let Some(keepalive) = keepalive {
         if let Err(error) = socket.set_keepalive(keepalive) {
             warn!(message = "Failed configuring TCP keepalive.", %error);
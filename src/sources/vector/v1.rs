@@ -77,6 +77,7 @@ impl VectorConfig {
             cx,
             false.into(),
             None,
+            false,
         )
     }
 
@@ -281,6 +282,7 @@ mod test {
                 out: tx,
                 proxy: Default::default(),
                 acknowledgements: false,
+                acknowledgements_timeout: None,
                 schema_definitions: HashMap::default(),
             })
             .await
@@ -320,6 +322,7 @@ mod test {
                 out: tx,
                 proxy: Default::default(),
                 acknowledgements: false,
+                acknowledgements_timeout: None,
                 schema_definitions: HashMap::default(),
             })
             .await
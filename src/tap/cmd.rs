@@ -7,7 +7,7 @@ use vector_api_client::{
     connect_subscription_client,
     gql::{
         output_events_by_component_id_patterns_subscription::OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns,
-        TapEncodingFormat, TapSubscriptionExt,
+        TapEncodingFormat, TapFilter, TapSubscriptionExt,
     },
     Client,
 };
@@ -69,6 +69,8 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
                 if status == exitcode::UNAVAILABLE || status == exitcode::TEMPFAIL && !opts.no_reconnect {
                     eprintln!("[tap] Connection failed. Reconnecting in {:?} seconds.", RECONNECT_DELAY / 1000);
                     tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
+                } else if status == exitcode::USAGE {
+                    return status;
                 } else {
                     break;
                 }
@@ -103,6 +105,11 @@ async fn run(
             opts.format,
             opts.limit as i64,
             opts.interval as i64,
+            TapFilter {
+                predicate: opts.filter.clone(),
+                fields: opts.fields.clone(),
+                max_events_per_sec: opts.max_events_per_sec.map(i64::from),
+            },
         );
     };
 
@@ -112,6 +119,14 @@ async fn run(
     loop {
         let message = stream.next().await;
         if let Some(Some(res)) = message {
+            // Errors are only returned for invalid requests, such as a filter that doesn't compile,
+            // so retrying wouldn't help.
+            if let Some(errors) = res.errors {
+                for error in errors {
+                    eprintln!("[tap] {}", error.message);
+                }
+                return exitcode::USAGE;
+            }
             if let Some(d) = res.data {
                 for tap_event in d.output_events_by_component_id_patterns.iter() {
                     match tap_event {
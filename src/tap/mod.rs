@@ -36,6 +36,18 @@ pub struct Opts {
     #[clap(use_value_delimiter(true), long)]
     inputs_of: Vec<String>,
 
+    /// VRL predicate events must match to be tapped, evaluated by the Vector instance
+    #[clap(long)]
+    filter: Option<String>,
+
+    /// Fields to keep in tapped logs, dropping the others (comma-separated)
+    #[clap(use_value_delimiter(true), long)]
+    fields: Vec<String>,
+
+    /// Maximum number of events tapped per second by the Vector instance, across all components
+    #[clap(long)]
+    max_events_per_sec: Option<u32>,
+
     /// Quiet output includes only events
     #[clap(short, long)]
     quiet: bool,
@@ -18,8 +18,9 @@ use tokio_openssl::SslStream;
 
 use super::{
     CreateAcceptorSnafu, HandshakeSnafu, IncomingListenerSnafu, MaybeTlsSettings, MaybeTlsStream,
-    SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
+    ProxyProtocolHeaderSnafu, SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
 };
+use crate::internal_events::TlsCertificateReloadError;
 #[cfg(feature = "sources-utils-tcp-socket")]
 use crate::tcp;
 #[cfg(feature = "sources-utils-tcp-keepalive")]
@@ -27,13 +28,20 @@ use crate::tcp::TcpKeepaliveConfig;
 
 impl TlsSettings {
     pub(crate) fn acceptor(&self) -> crate::tls::Result<SslAcceptor> {
-        match self.identity {
+        self.reloaded_acceptor().map(|(acceptor, _)| acceptor)
+    }
+
+    /// Builds an acceptor with the certificates currently in use, along with the number of times
+    /// they were reloaded.
+    fn reloaded_acceptor(&self) -> crate::tls::Result<(SslAcceptor, u64)> {
+        let (settings, generation) = self.reloaded();
+        match settings.identity {
             None => Err(TlsError::MissingRequiredIdentity),
             Some(_) => {
                 let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls())
                     .context(CreateAcceptorSnafu)?;
-                self.apply_context(&mut acceptor)?;
-                Ok(acceptor.build())
+                settings.apply_certificates(&mut acceptor)?;
+                Ok((acceptor.build(), generation))
             }
         }
     }
@@ -43,29 +51,73 @@ impl MaybeTlsSettings {
     pub(crate) async fn bind(&self, addr: &SocketAddr) -> crate::tls::Result<MaybeTlsListener> {
         let listener = TcpListener::bind(addr).await.context(TcpBindSnafu)?;
 
-        let acceptor = match self {
-            Self::Tls(tls) => Some(tls.acceptor()?),
-            Self::Raw(()) => None,
+        let (acceptor, tls) = match self {
+            Self::Tls(tls) => {
+                let (acceptor, generation) = tls.reloaded_acceptor()?;
+                (Some(acceptor), Some((tls.clone(), generation)))
+            }
+            Self::Raw(()) => (None, None),
         };
 
-        Ok(MaybeTlsListener { listener, acceptor })
+        Ok(MaybeTlsListener {
+            listener,
+            acceptor,
+            tls,
+            proxy_protocol: false,
+        })
     }
 }
 
 pub struct MaybeTlsListener {
     listener: TcpListener,
     acceptor: Option<SslAcceptor>,
+    // The settings the acceptor was built from, and the number of times their certificates were
+    // reloaded when it was.
+    tls: Option<(TlsSettings, u64)>,
+    proxy_protocol: bool,
 }
 
 impl MaybeTlsListener {
+    /// Sets whether or not connections start with a PROXY protocol header, giving the address of
+    /// the client they're from.
+    #[cfg(feature = "listenfd")]
+    pub(crate) const fn with_proxy_protocol(mut self, proxy_protocol: bool) -> Self {
+        self.proxy_protocol = proxy_protocol;
+        self
+    }
+
     pub(crate) async fn accept(&mut self) -> crate::tls::Result<MaybeTlsIncomingStream<TcpStream>> {
-        self.listener
+        let (stream, peer_addr) = self
+            .listener
             .accept()
             .await
-            .map(|(stream, peer_addr)| {
-                MaybeTlsIncomingStream::new(stream, peer_addr, self.acceptor.clone())
-            })
-            .context(IncomingListenerSnafu)
+            .context(IncomingListenerSnafu)?;
+        self.reload_acceptor();
+        Ok(MaybeTlsIncomingStream::new(
+            stream,
+            peer_addr,
+            self.acceptor.clone(),
+            self.proxy_protocol,
+        ))
+    }
+
+    /// Rebuilds the acceptor if the certificates were reloaded since it was built.
+    fn reload_acceptor(&mut self) {
+        if let Some((settings, built)) = &mut self.tls {
+            if settings.reloaded().1 == *built {
+                return;
+            }
+            match settings.reloaded_acceptor() {
+                Ok((acceptor, generation)) => {
+                    self.acceptor = Some(acceptor);
+                    *built = generation;
+                }
+                Err(error) => emit!(TlsCertificateReloadError {
+                    error: &error,
+                    for_server: true,
+                }),
+            }
+        }
     }
 
     async fn into_accept(
@@ -137,6 +189,8 @@ impl From<TcpListener> for MaybeTlsListener {
         Self {
             listener,
             acceptor: None,
+            tls: None,
+            proxy_protocol: false,
         }
     }
 }
@@ -151,7 +205,8 @@ pub struct MaybeTlsIncomingStream<S> {
 
 enum StreamState<S> {
     Accepted(MaybeTlsStream<S>),
-    Accepting(BoxFuture<'static, Result<SslStream<S>, TlsError>>),
+    // Reads the PROXY protocol header, if any, and handshakes TLS, if enabled.
+    Accepting(BoxFuture<'static, Result<(MaybeTlsStream<S>, Option<SocketAddr>), TlsError>>),
     AcceptError(String),
     Closed,
 }
@@ -218,34 +273,58 @@ impl<S> MaybeTlsIncomingStream<S> {
 
 impl MaybeTlsIncomingStream<TcpStream> {
     pub(super) fn new(
-        stream: TcpStream,
+        mut stream: TcpStream,
         peer_addr: SocketAddr,
         acceptor: Option<SslAcceptor>,
+        proxy_protocol: bool,
     ) -> Self {
-        let state = match acceptor {
-            Some(acceptor) => StreamState::Accepting(
+        let state = match (acceptor, proxy_protocol) {
+            (None, false) => StreamState::Accepted(MaybeTlsStream::Raw(stream)),
+            (acceptor, proxy_protocol) => StreamState::Accepting(
                 async move {
-                    let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
-                    let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
-                    Pin::new(&mut stream)
-                        .accept()
-                        .await
-                        .context(HandshakeSnafu)?;
-                    Ok(stream)
+                    // The header is sent by the load balancer ahead of the TLS handshake.
+                    let client_addr = if proxy_protocol {
+                        crate::proxy_protocol::read_header(&mut stream)
+                            .await
+                            .context(ProxyProtocolHeaderSnafu)?
+                    } else {
+                        None
+                    };
+                    let stream = match acceptor {
+                        Some(acceptor) => {
+                            let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
+                            let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
+                            Pin::new(&mut stream)
+                                .accept()
+                                .await
+                                .context(HandshakeSnafu)?;
+                            MaybeTlsStream::Tls(stream)
+                        }
+                        None => MaybeTlsStream::Raw(stream),
+                    };
+                    Ok((stream, client_addr))
                 }
                 .boxed(),
             ),
-            None => StreamState::Accepted(MaybeTlsStream::Raw(stream)),
         };
         Self { state, peer_addr }
     }
 
+    /// Switches to the accepted stream, taking the address of the client from the PROXY protocol
+    /// header if there was one.
+    fn accepted(&mut self, (stream, client_addr): (MaybeTlsStream<TcpStream>, Option<SocketAddr>)) {
+        if let Some(client_addr) = client_addr {
+            self.peer_addr = client_addr;
+        }
+        self.state = StreamState::Accepted(stream);
+    }
+
     // Explicit handshake method
     #[cfg(feature = "listenfd")]
     pub(crate) async fn handshake(&mut self) -> crate::tls::Result<()> {
         if let StreamState::Accepting(fut) = &mut self.state {
-            let stream = fut.await?;
-            self.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+            let accepted = fut.await?;
+            self.accepted(accepted);
         }
 
         Ok(())
@@ -291,8 +370,8 @@ impl MaybeTlsIncomingStream<TcpStream> {
             return match &mut this.state {
                 StreamState::Accepted(stream) => poll_fn(Pin::new(stream), cx),
                 StreamState::Accepting(fut) => match futures::ready!(fut.as_mut().poll(cx)) {
-                    Ok(stream) => {
-                        this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                    Ok(accepted) => {
+                        this.accepted(accepted);
                         continue;
                     }
                     Err(error) => {
@@ -340,8 +419,8 @@ impl AsyncWrite for MaybeTlsIncomingStream<TcpStream> {
                 poll_result => poll_result,
             },
             StreamState::Accepting(fut) => match futures::ready!(fut.as_mut().poll(cx)) {
-                Ok(stream) => {
-                    this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                Ok(accepted) => {
+                    this.accepted(accepted);
                     Poll::Pending
                 }
                 Err(error) => {
@@ -1,20 +1,30 @@
 use std::{net::SocketAddr, pin::Pin};
 
 use snafu::ResultExt;
-use tokio::net::TcpStream;
+use tokio::{io::AsyncWriteExt, net::TcpStream};
 use tokio_openssl::SslStream;
 
 use super::{
     tls_connector, ConnectSnafu, HandshakeSnafu, MaybeTlsSettings, MaybeTlsStream, SslBuildSnafu,
 };
+use crate::proxy_protocol::{encode_header, ProxyProtocolVersion};
 
 impl MaybeTlsSettings {
+    /// Connects to the address, sending a PROXY protocol header of the given version first, if
+    /// any, with the local address of the connection as the client address.
     pub(crate) async fn connect(
         &self,
         host: &str,
         addr: &SocketAddr,
+        proxy_protocol: Option<ProxyProtocolVersion>,
     ) -> crate::tls::Result<MaybeTlsStream<TcpStream>> {
-        let stream = TcpStream::connect(addr).await.context(ConnectSnafu)?;
+        let mut stream = TcpStream::connect(addr).await.context(ConnectSnafu)?;
+
+        if let Some(version) = proxy_protocol {
+            let local_addr = stream.local_addr().context(ConnectSnafu)?;
+            let header = encode_header(version, local_addr, *addr);
+            stream.write_all(&header).await.context(ConnectSnafu)?;
+        }
 
         match self {
             MaybeTlsSettings::Raw(()) => Ok(MaybeTlsStream::Raw(stream)),
@@ -1,14 +1,21 @@
 use std::{
+    borrow::Cow,
     fmt,
-    fs::File,
+    fs::{self, File},
     io::Read,
+    num::NonZeroU64,
     path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant, SystemTime},
 };
 
 use openssl::{
+    asn1::Asn1Time,
     pkcs12::{ParsedPkcs12, Pkcs12},
     pkey::{PKey, Private},
-    ssl::{ConnectConfiguration, SslContextBuilder, SslVerifyMode},
+    ssl::{
+        ConnectConfiguration, SslConnector, SslContext, SslContextBuilder, SslMethod, SslVerifyMode,
+    },
     stack::Stack,
     x509::{store::X509StoreBuilder, X509},
 };
@@ -19,7 +26,11 @@ use super::{
     AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, DerExportSnafu,
     FileOpenFailedSnafu, FileReadFailedSnafu, MaybeTls, NewCaStackSnafu, NewStoreBuilderSnafu,
     ParsePkcs12Snafu, Pkcs12Snafu, PrivateKeyParseSnafu, Result, SetCertificateSnafu,
-    SetPrivateKeySnafu, SetVerifyCertSnafu, TlsError, TlsIdentitySnafu, X509ParseSnafu,
+    SetPrivateKeySnafu, SetVerifyCertSnafu, TlsBuildConnectorSnafu, TlsError, TlsIdentitySnafu,
+    X509ParseSnafu,
+};
+use crate::internal_events::{
+    TlsCertificateExpiry, TlsCertificateReloadError, TlsCertificateReloaded,
 };
 
 const PEM_START_MARKER: &str = "-----BEGIN ";
@@ -68,6 +79,9 @@ pub struct TlsConfig {
     #[serde(alias = "key_path")]
     pub key_file: Option<PathBuf>,
     pub key_pass: Option<String>,
+    /// How often the certificate, key and CA files are checked for changes, and loaded again if
+    /// they changed, in seconds.
+    pub reload_interval_secs: Option<NonZeroU64>,
 }
 
 impl TlsConfig {
@@ -89,6 +103,7 @@ pub struct TlsSettings {
     pub(super) verify_hostname: bool,
     authorities: Vec<X509>,
     pub(super) identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
+    reloader: Option<Arc<Reloader>>,
 }
 
 #[derive(Clone)]
@@ -117,14 +132,45 @@ impl TlsSettings {
             }
         }
 
+        let authorities = options.load_authorities()?;
+        let identity = options.load_identity()?;
+        emit_expiry(&identity);
+        let reloader = options.reload_interval_secs.map(|secs| {
+            Arc::new(Reloader::new(
+                options.clone(),
+                Duration::from_secs(secs.get()),
+                for_server,
+                authorities.clone(),
+                identity.clone(),
+            ))
+        });
+
         Ok(Self {
             verify_certificate: options.verify_certificate.unwrap_or(!for_server),
             verify_hostname: options.verify_hostname.unwrap_or(!for_server),
-            authorities: options.load_authorities()?,
-            identity: options.load_identity()?,
+            authorities,
+            identity,
+            reloader,
         })
     }
 
+    /// Gets the settings with the certificates currently in use, reloading them first if their
+    /// files changed, along with the number of times they were reloaded.
+    pub(super) fn reloaded(&self) -> (Cow<'_, Self>, u64) {
+        match &self.reloader {
+            None => (Cow::Borrowed(self), 0),
+            Some(reloader) => {
+                let state = reloader.refresh();
+                let settings = Self {
+                    authorities: state.authorities.clone(),
+                    identity: state.identity.clone(),
+                    ..self.clone()
+                };
+                (Cow::Owned(settings), state.generation)
+            }
+        }
+    }
+
     fn identity(&self) -> Option<ParsedPkcs12> {
         // This data was test-built previously, so we can just use it
         // here and expect the results will not fail. This can all be
@@ -140,7 +186,7 @@ impl TlsSettings {
 
     #[cfg(feature = "sources-gcp_pubsub")]
     pub fn identity_pem(&self) -> Option<(Vec<u8>, Vec<u8>)> {
-        self.identity().map(|identity| {
+        self.reloaded().0.identity().map(|identity| {
             let mut cert = identity.cert.to_pem().expect("Invalid stored identity");
             if let Some(chain) = identity.chain {
                 for authority in chain {
@@ -160,15 +206,25 @@ impl TlsSettings {
     }
 
     #[cfg(feature = "sources-gcp_pubsub")]
-    pub fn authorities_pem(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
-        self.authorities.iter().map(|authority| {
-            authority
-                .to_pem()
-                .expect("Invalid stored authority certificate")
-        })
+    pub fn authorities_pem(&self) -> Vec<Vec<u8>> {
+        let (settings, _) = self.reloaded();
+        settings
+            .authorities
+            .iter()
+            .map(|authority| {
+                authority
+                    .to_pem()
+                    .expect("Invalid stored authority certificate")
+            })
+            .collect()
+    }
+
+    /// Applies the certificates currently in use to the context.
+    pub(super) fn apply_context(&self, context: &mut SslContextBuilder) -> Result<()> {
+        self.reloaded().0.apply_certificates(context)
     }
 
-    pub(super) fn apply_context(&self, context: &mut SslContextBuilder) -> Result<()> {
+    pub(super) fn apply_certificates(&self, context: &mut SslContextBuilder) -> Result<()> {
         context.set_verify(if self.verify_certificate {
             SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT
         } else {
@@ -214,10 +270,166 @@ impl TlsSettings {
 
     pub fn apply_connect_configuration(&self, connection: &mut ConnectConfiguration) {
         connection.set_verify_hostname(self.verify_hostname);
+
+        // Connectors are built once, so connections switch to a context with the reloaded
+        // certificates once there are any.
+        if let Some(context) = self.reloaded_client_context() {
+            if let Err(error) = connection.set_ssl_context(&context) {
+                let error = TlsError::TlsBuildConnector { source: error };
+                emit!(TlsCertificateReloadError {
+                    error: &error,
+                    for_server: false,
+                });
+            }
+        }
+    }
+
+    fn reloaded_client_context(&self) -> Option<SslContext> {
+        let reloader = self.reloader.as_ref()?;
+        let (settings, generation) = self.reloaded();
+        if generation == 0 {
+            return None;
+        }
+
+        let mut cached = reloader
+            .client_context
+            .lock()
+            .expect("TLS client context lock poisoned");
+        match cached.as_ref() {
+            Some((built, context)) if *built == generation => Some(context.clone()),
+            _ => match settings.client_context() {
+                Ok(context) => {
+                    *cached = Some((generation, context.clone()));
+                    Some(context)
+                }
+                Err(error) => {
+                    emit!(TlsCertificateReloadError {
+                        error: &error,
+                        for_server: false,
+                    });
+                    cached.as_ref().map(|(_, context)| context.clone())
+                }
+            },
+        }
+    }
+
+    fn client_context(&self) -> Result<SslContext> {
+        let mut builder =
+            SslConnector::builder(SslMethod::tls()).context(TlsBuildConnectorSnafu)?;
+        self.apply_certificates(&mut builder)?;
+        Ok(builder.build().into_context())
+    }
+}
+
+/// Reloads the certificates of TLS settings when their files change.
+struct Reloader {
+    options: TlsConfig,
+    interval: Duration,
+    for_server: bool,
+    state: Mutex<ReloadState>,
+    client_context: Mutex<Option<(u64, SslContext)>>,
+}
+
+struct ReloadState {
+    checked: Instant,
+    modified: Vec<Option<SystemTime>>,
+    authorities: Vec<X509>,
+    identity: Option<IdentityStore>,
+    generation: u64,
+}
+
+impl Reloader {
+    fn new(
+        options: TlsConfig,
+        interval: Duration,
+        for_server: bool,
+        authorities: Vec<X509>,
+        identity: Option<IdentityStore>,
+    ) -> Self {
+        let modified = options.modified_times();
+        Self {
+            options,
+            interval,
+            for_server,
+            state: Mutex::new(ReloadState {
+                checked: Instant::now(),
+                modified,
+                authorities,
+                identity,
+                generation: 0,
+            }),
+            client_context: Mutex::new(None),
+        }
+    }
+
+    /// Loads the certificates again if the interval elapsed since the files were last checked and
+    /// any of them changed.
+    fn refresh(&self) -> MutexGuard<'_, ReloadState> {
+        let mut state = self.state.lock().expect("TLS reload lock poisoned");
+        if state.checked.elapsed() < self.interval {
+            return state;
+        }
+        state.checked = Instant::now();
+
+        let modified = self.options.modified_times();
+        if modified != state.modified {
+            let loaded = self
+                .options
+                .load_authorities()
+                .and_then(|authorities| Ok((authorities, self.options.load_identity()?)));
+            match loaded {
+                Ok((authorities, identity)) => {
+                    state.modified = modified;
+                    state.authorities = authorities;
+                    state.identity = identity;
+                    state.generation += 1;
+                    emit!(TlsCertificateReloaded {
+                        crt_file: self.options.crt_file.as_deref(),
+                    });
+                }
+                // The files are checked again at the next interval, in case they were caught in
+                // the middle of being written.
+                Err(error) => emit!(TlsCertificateReloadError {
+                    error: &error,
+                    for_server: self.for_server,
+                }),
+            }
+        }
+        emit_expiry(&state.identity);
+        state
+    }
+}
+
+/// Reports how long the identity certificate is valid for.
+fn emit_expiry(identity: &Option<IdentityStore>) {
+    let expiry = identity.as_ref().and_then(|identity| {
+        let parsed = Pkcs12::from_der(&identity.0)
+            .and_then(|pkcs12| pkcs12.parse(&identity.1))
+            .ok()?;
+        let diff = Asn1Time::days_from_now(0)
+            .and_then(|now| now.diff(parsed.cert.not_after()))
+            .ok()?;
+        Some(f64::from(diff.days) * 86_400.0 + f64::from(diff.secs))
+    });
+    if let Some(seconds_remaining) = expiry {
+        emit!(TlsCertificateExpiry { seconds_remaining });
     }
 }
 
 impl TlsConfig {
+    /// The modification times of the files the certificates are loaded from, skipping inline ones.
+    fn modified_times(&self) -> Vec<Option<SystemTime>> {
+        [&self.ca_file, &self.crt_file, &self.key_file]
+            .into_iter()
+            .flatten()
+            .filter(|path| {
+                path.to_str()
+                    .map_or(true, |path| !path.contains(PEM_START_MARKER))
+            })
+            .map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+            .collect()
+    }
+
     fn load_authorities(&self) -> Result<Vec<X509>> {
         match &self.ca_file {
             None => Ok(vec![]),
@@ -620,6 +832,58 @@ mod test {
         // Actual error is an ASN parse, doesn't really matter
     }
 
+    #[test]
+    fn reloads_changed_certificates() {
+        let dir = crate::test_util::temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let crt_file = dir.join("tls.crt");
+        let key_file = dir.join("tls.key");
+        fs::copy(TEST_PEM_CRT_PATH, &crt_file).unwrap();
+        fs::copy(TEST_PEM_KEY_PATH, &key_file).unwrap();
+
+        let options = TlsConfig {
+            crt_file: Some(crt_file.clone()),
+            key_file: Some(key_file.clone()),
+            reload_interval_secs: NonZeroU64::new(60),
+            ..Default::default()
+        };
+        let mut settings = TlsSettings::from_options(&Some(options.clone())).unwrap();
+        // Checks the files on every use, rather than once a minute.
+        settings.reloader = Some(Arc::new(Reloader::new(
+            options,
+            Duration::ZERO,
+            false,
+            settings.authorities.clone(),
+            settings.identity.clone(),
+        )));
+        let common_name = |settings: &TlsSettings| {
+            let (settings, generation) = settings.reloaded();
+            let cert = settings.identity().unwrap().cert;
+            let name = cert
+                .subject_name()
+                .entries()
+                .next()
+                .unwrap()
+                .data()
+                .as_slice()
+                .to_vec();
+            (String::from_utf8(name).unwrap(), generation)
+        };
+        assert_eq!(common_name(&settings), ("localhost".into(), 0));
+
+        std::thread::sleep(Duration::from_millis(10));
+        fs::copy("tests/data/postgres.crt", &crt_file).unwrap();
+        fs::copy("tests/data/postgres.key", &key_file).unwrap();
+        assert_eq!(common_name(&settings), ("postgres".into(), 1));
+
+        // Certificates that fail to load are skipped, keeping the current ones.
+        std::thread::sleep(Duration::from_millis(10));
+        fs::write(&crt_file, "not a certificate").unwrap();
+        assert_eq!(common_name(&settings), ("postgres".into(), 1));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
     #[test]
     fn from_config_none() {
         assert!(MaybeTlsSettings::from_config(&None, true).unwrap().is_raw());
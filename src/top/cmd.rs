@@ -55,6 +55,10 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
         })
         .expect("Couldn't build WebSocket URL. Please report.");
 
+    // Buffer sizes are polled over HTTP rather than subscribed to, independently of reconnects
+    let buffer_events =
+        metrics::poll_buffer_events(Client::new(url.clone()), tx.clone(), opts.interval as i64);
+
     let opts_clone = opts.clone();
     let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
     // This task handles reconnecting the subscription client and all
@@ -106,7 +110,9 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
     });
 
     // Initialize the dashboard
-    match init_dashboard(url.as_str(), opts, state_rx, shutdown_rx).await {
+    let result = init_dashboard(url.as_str(), opts, state_rx, shutdown_rx).await;
+    buffer_events.abort();
+    match result {
         Ok(_) => {
             connection.abort();
             exitcode::OK
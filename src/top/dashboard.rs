@@ -1,5 +1,6 @@
-use std::io::stdout;
+use std::{io::stdout, path::PathBuf};
 
+use chrono::{Duration, Utc};
 use crossterm::{
     cursor::Show,
     event::{DisableMouseCapture, EnableMouseCapture, KeyCode},
@@ -22,7 +23,8 @@ use tui::{
 
 use super::{
     events::capture_key_press,
-    state::{self, ConnectionStatus},
+    history::{History, Sample},
+    state::{self, SortBy},
 };
 
 /// Format metrics, with thousands separation
@@ -104,7 +106,7 @@ fn format_metric(total: i64, throughput: i64, human_metrics: bool) -> String {
     }
 }
 
-const NUM_COLUMNS: usize = 8;
+const NUM_COLUMNS: usize = 9;
 static HEADER: [&str; NUM_COLUMNS] = [
     "ID",
     "Output",
@@ -114,8 +116,46 @@ static HEADER: [&str; NUM_COLUMNS] = [
     "Events Out",
     "Bytes",
     "Errors",
+    "Buffered",
 ];
 
+/// What the dashboard is showing: the latest sample, or an earlier one while paused.
+struct View {
+    paused: bool,
+    /// Number of samples back from the latest one
+    offset: usize,
+    sort_by: SortBy,
+    /// Outcome of the last export
+    status: Option<String>,
+}
+
+impl View {
+    const fn new(sort_by: SortBy) -> Self {
+        Self {
+            paused: false,
+            offset: 0,
+            sort_by,
+            status: None,
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        if !self.paused {
+            self.offset = 0;
+        }
+    }
+
+    fn scroll_back(&mut self) {
+        self.paused = true;
+        self.offset += 1;
+    }
+
+    fn scroll_forward(&mut self) {
+        self.offset = self.offset.saturating_sub(1);
+    }
+}
+
 struct Widgets<'a> {
     constraints: Vec<Constraint>,
     url_string: &'a str,
@@ -138,22 +178,38 @@ impl<'a> Widgets<'a> {
         }
     }
 
-    /// Renders a title showing 'Vector', and the URL the dashboard is currently connected to.
-    fn title<B: Backend>(
-        &'a self,
-        f: &mut Frame<B>,
-        area: Rect,
-        connection_status: &ConnectionStatus,
-    ) {
-        let text = vec![Spans::from(vec![
+    /// Renders a title showing 'Vector', the URL the dashboard is currently connected to, and
+    /// the sample being shown if paused.
+    fn title<B: Backend>(&'a self, f: &mut Frame<B>, area: Rect, sample: &Sample, view: &View) {
+        let connection_status = &sample.state.connection_status;
+        let mut spans = vec![
             Span::from(self.url_string),
             Span::styled(
                 format!(" | Sampling @ {}ms", self.opts.interval.thousands_format()),
                 Style::default().fg(Color::Gray),
             ),
+            Span::styled(
+                format!(" | Sorted by {}", view.sort_by),
+                Style::default().fg(Color::Gray),
+            ),
             Span::from(" | "),
-            Span::styled(connection_status.to_string(), connection_status.style()),
-        ])];
+        ];
+        if view.paused {
+            spans.push(Span::styled(
+                format!(
+                    "Paused @ {} ({} samples back)",
+                    sample.timestamp.format("%H:%M:%S"),
+                    view.offset
+                ),
+                Style::default().fg(Color::Yellow),
+            ));
+        } else {
+            spans.push(Span::styled(
+                connection_status.to_string(),
+                connection_status.style(),
+            ));
+        }
+        let text = vec![Spans::from(spans)];
 
         let block = Block::default().borders(Borders::ALL).title(Span::styled(
             "Vector",
@@ -168,7 +224,13 @@ impl<'a> Widgets<'a> {
 
     /// Renders a components table, showing sources, transforms and sinks in tabular form, with
     /// statistics pulled from `ComponentsState`,
-    fn components_table<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, area: Rect) {
+    fn components_table<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        state: &state::State,
+        sort_by: SortBy,
+        area: Rect,
+    ) {
         // Header columns
         let header = HEADER
             .iter()
@@ -177,7 +239,7 @@ impl<'a> Widgets<'a> {
 
         // Data columns
         let mut items = Vec::new();
-        for (_, r) in state.components.iter() {
+        for r in state.sorted_components(sort_by) {
             let mut data = vec![
                 r.key.id().to_string(),
                 (!r.has_displayable_outputs())
@@ -204,10 +266,11 @@ impl<'a> Widgets<'a> {
                     r.processed_bytes_throughput_sec,
                     self.opts.human_metrics,
                 ),
+                format_metric(r.errors, r.errors_throughput_sec, self.opts.human_metrics),
                 if self.opts.human_metrics {
-                    r.errors.human_format()
+                    r.buffer_events.human_format()
                 } else {
-                    r.errors.thousands_format()
+                    r.buffer_events.thousands_format()
                 },
             ];
 
@@ -239,13 +302,14 @@ impl<'a> Widgets<'a> {
             .column_spacing(2)
             .widths(&[
                 Constraint::Percentage(15), // ID
-                Constraint::Percentage(15), // Output
+                Constraint::Percentage(10), // Output
                 Constraint::Percentage(10), // Kind
                 Constraint::Percentage(10), // Type
                 Constraint::Percentage(10), // Events In
                 Constraint::Percentage(10), // Events Out
                 Constraint::Percentage(10), // Bytes
                 Constraint::Percentage(10), // Errors
+                Constraint::Percentage(10), // Buffered
             ]);
 
         f.render_widget(w, area);
@@ -261,9 +325,19 @@ impl<'a> Widgets<'a> {
         f.render_widget(w, area);
     }
 
-    /// Renders a box showing instructions on how to exit from `vector top`.
-    fn quit_box<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
-        let text = vec![Spans::from("To quit, press ESC or 'q'")];
+    /// Renders a box showing the keys to control `vector top` with, and the outcome of the
+    /// last export.
+    fn help_box<B: Backend>(&self, f: &mut Frame<B>, area: Rect, view: &View) {
+        let mut spans = vec![Span::from(
+            "Quit: ESC or 'q' | Pause: 'p' | Scroll back: ←/→ | Sort: 's' | Export: 'e'",
+        )];
+        if let Some(status) = &view.status {
+            spans.push(Span::styled(
+                format!(" | {}", status),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        let text = vec![Spans::from(spans)];
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -277,22 +351,50 @@ impl<'a> Widgets<'a> {
     }
 
     /// Draw a single frame. Creates a layout and renders widgets into it.
-    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: state::State) {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, sample: &Sample, view: &View) {
         let size = f.size();
         let rects = Layout::default()
             .constraints(self.constraints.clone())
             .split(size);
 
-        self.title(f, rects[0], &state.connection_status);
+        self.title(f, rects[0], sample, view);
 
         // Require a minimum of 80 chars of line width to display the table
         if size.width >= 80 {
-            self.components_table(f, &state, rects[1]);
+            self.components_table(f, &sample.state, view.sort_by, rects[1]);
         } else {
             self.components_resize_window(f, rects[1]);
         }
 
-        self.quit_box(f, rects[2]);
+        self.help_box(f, rects[2], view);
+    }
+
+    /// Draws the sample picked by the view, keeping the view within the recorded window.
+    fn redraw<B: Backend>(
+        &self,
+        terminal: &mut Terminal<B>,
+        history: &History,
+        view: &mut View,
+    ) -> std::io::Result<()> {
+        view.offset = view.offset.min(history.len().saturating_sub(1));
+        if let Some(sample) = history.get(view.offset) {
+            terminal.draw(|f| self.draw(f, sample, view))?;
+        }
+        Ok(())
+    }
+
+    /// Exports the recorded window, returning the outcome to show.
+    fn export(&self, history: &History) -> String {
+        let path = self.opts.export_path.clone().unwrap_or_else(|| {
+            PathBuf::from(format!(
+                "vector-top-{}.json",
+                Utc::now().format("%Y%m%dT%H%M%SZ")
+            ))
+        });
+        match history.export(&path, self.url_string, self.opts.interval) {
+            Ok(()) => format!("Exported {} samples to {}", history.len(), path.display()),
+            Err(error) => format!("Couldn't export to {}: {}", path.display(), error),
+        }
     }
 }
 
@@ -331,17 +433,35 @@ pub async fn init_dashboard<'a>(
     terminal.clear()?;
 
     let widgets = Widgets::new(url, opts);
+    let mut history = History::new(
+        Duration::seconds(opts.history as i64),
+        Duration::milliseconds(opts.interval as i64),
+    );
+    let mut view = View::new(opts.sort);
 
     loop {
         tokio::select! {
             Some(state) = state_rx.recv() => {
-                terminal.draw(|f| widgets.draw(f, state))?;
+                // Keep showing the same sample while paused
+                if history.record(state, Utc::now()) && view.paused {
+                    view.offset += 1;
+                }
+                widgets.redraw(&mut terminal, &history, &mut view)?;
             },
             k = key_press_rx.recv() => {
-                if let KeyCode::Esc | KeyCode::Char('q') = k.unwrap() {
-                    let _ = key_press_kill_tx.send(());
-                    break
+                match k.unwrap() {
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        let _ = key_press_kill_tx.send(());
+                        break
+                    }
+                    KeyCode::Char('p') | KeyCode::Char(' ') => view.toggle_pause(),
+                    KeyCode::Left => view.scroll_back(),
+                    KeyCode::Right => view.scroll_forward(),
+                    KeyCode::Char('s') => view.sort_by = view.sort_by.next(),
+                    KeyCode::Char('e') => view.status = Some(widgets.export(&history)),
+                    _ => {}
                 }
+                widgets.redraw(&mut terminal, &history, &mut view)?;
             }
             _ = &mut shutdown_rx => {
                 let _ = key_press_kill_tx.send(());
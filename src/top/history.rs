@@ -0,0 +1,232 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs, io,
+    path::Path,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use super::state::{ComponentRow, OutputMetrics, State};
+
+/// Minimum span of time, in milliseconds, to measure error rates over. Error totals are only
+/// sent by the API when they change, so their rate is taken from the recorded samples.
+const ERROR_RATE_SPAN_MS: i64 = 1000;
+
+/// State of the dashboard at a point in time.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub timestamp: DateTime<Utc>,
+    pub state: State,
+}
+
+/// Rolling window of samples of the dashboard, taking at most one sample per sampling interval.
+#[derive(Debug)]
+pub struct History {
+    window: Duration,
+    resolution: Duration,
+    samples: VecDeque<Sample>,
+}
+
+impl History {
+    pub fn new(window: Duration, resolution: Duration) -> Self {
+        Self {
+            window,
+            resolution,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Records the latest state, measuring the error rates of its components. The state replaces
+    /// the latest sample if that one is younger than the sampling interval. Returns whether a new
+    /// sample was added.
+    pub fn record(&mut self, mut state: State, now: DateTime<Utc>) -> bool {
+        let replaced = match self.samples.back() {
+            Some(latest) if now - latest.timestamp < self.resolution => self.samples.pop_back(),
+            _ => None,
+        };
+
+        let then = self
+            .samples
+            .iter()
+            .rev()
+            .find(|s| (now - s.timestamp).num_milliseconds() >= ERROR_RATE_SPAN_MS)
+            .or_else(|| self.samples.front());
+        let elapsed_ms = then.map_or(0, |then| (now - then.timestamp).num_milliseconds());
+        if let Some(then) = then.filter(|_| elapsed_ms > 0) {
+            for (key, row) in state.components.iter_mut() {
+                if let Some(prev) = then.state.components.get(key) {
+                    let errors = (row.errors - prev.errors).max(0);
+                    row.errors_throughput_sec =
+                        (errors as f64 * (1000.0 / elapsed_ms as f64)) as i64;
+                }
+            }
+        }
+
+        let timestamp = replaced.as_ref().map_or(now, |s| s.timestamp);
+        self.samples.push_back(Sample { timestamp, state });
+        while matches!(self.samples.front(), Some(s) if now - s.timestamp > self.window) {
+            self.samples.pop_front();
+        }
+
+        replaced.is_none()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns the sample `offset` samples back from the latest one.
+    pub fn get(&self, offset: usize) -> Option<&Sample> {
+        self.samples
+            .len()
+            .checked_sub(offset + 1)
+            .and_then(|index| self.samples.get(index))
+    }
+
+    /// Writes the window as JSON, for attaching to incident reports.
+    pub fn export(&self, path: &Path, url: &str, interval_ms: u32) -> io::Result<()> {
+        let export = Export {
+            url,
+            interval_ms,
+            samples: self
+                .samples
+                .iter()
+                .map(|s| ExportSample {
+                    timestamp: s.timestamp,
+                    components: s.state.components.values().map(Into::into).collect(),
+                })
+                .collect(),
+        };
+
+        fs::write(path, serde_json::to_vec_pretty(&export)?)
+    }
+}
+
+#[derive(Serialize)]
+struct Export<'a> {
+    url: &'a str,
+    interval_ms: u32,
+    samples: Vec<ExportSample<'a>>,
+}
+
+#[derive(Serialize)]
+struct ExportSample<'a> {
+    timestamp: DateTime<Utc>,
+    components: Vec<ExportComponent<'a>>,
+}
+
+#[derive(Serialize)]
+struct ExportComponent<'a> {
+    id: &'a str,
+    kind: &'a str,
+    #[serde(rename = "type")]
+    component_type: &'a str,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    outputs: BTreeMap<&'a str, &'a OutputMetrics>,
+    received_events_total: i64,
+    received_events_throughput_sec: i64,
+    sent_events_total: i64,
+    sent_events_throughput_sec: i64,
+    processed_bytes_total: i64,
+    processed_bytes_throughput_sec: i64,
+    errors_total: i64,
+    errors_throughput_sec: i64,
+    buffer_events: i64,
+}
+
+impl<'a> From<&'a ComponentRow> for ExportComponent<'a> {
+    fn from(r: &'a ComponentRow) -> Self {
+        let outputs = if r.has_displayable_outputs() {
+            r.outputs.iter().map(|(id, o)| (id.as_str(), o)).collect()
+        } else {
+            BTreeMap::new()
+        };
+
+        Self {
+            id: r.key.id(),
+            kind: &r.kind,
+            component_type: &r.component_type,
+            outputs,
+            received_events_total: r.received_events_total,
+            received_events_throughput_sec: r.received_events_throughput_sec,
+            sent_events_total: r.sent_events_total,
+            sent_events_throughput_sec: r.sent_events_throughput_sec,
+            processed_bytes_total: r.processed_bytes_total,
+            processed_bytes_throughput_sec: r.processed_bytes_throughput_sec,
+            errors_total: r.errors,
+            errors_throughput_sec: r.errors_throughput_sec,
+            buffer_events: r.buffer_events,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::config::ComponentKey;
+
+    fn state(errors: i64) -> State {
+        let key = ComponentKey::from("out");
+        let row = ComponentRow {
+            key: key.clone(),
+            kind: "sink".to_string(),
+            component_type: "console".to_string(),
+            outputs: HashMap::new(),
+            processed_bytes_total: 0,
+            processed_bytes_throughput_sec: 0,
+            received_events_total: 0,
+            received_events_throughput_sec: 0,
+            sent_events_total: 0,
+            sent_events_throughput_sec: 0,
+            errors,
+            errors_throughput_sec: 0,
+            buffer_events: 0,
+        };
+        State::new(BTreeMap::from([(key, row)]))
+    }
+
+    fn errors_throughput_sec(sample: &Sample) -> i64 {
+        sample.state.components[&ComponentKey::from("out")].errors_throughput_sec
+    }
+
+    #[test]
+    fn keeps_one_sample_per_interval_within_the_window() {
+        let start = Utc::now();
+        let mut history = History::new(Duration::seconds(10), Duration::milliseconds(500));
+
+        assert!(history.record(state(0), start));
+        assert!(!history.record(state(5), start + Duration::milliseconds(100)));
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.get(0).unwrap().timestamp, start);
+
+        for i in 1..=30 {
+            assert!(history.record(state(0), start + Duration::milliseconds(500 * i)));
+        }
+        assert_eq!(history.len(), 21);
+        assert_eq!(
+            history.get(20).unwrap().timestamp,
+            start + Duration::seconds(5)
+        );
+        assert!(history.get(21).is_none());
+    }
+
+    #[test]
+    fn measures_error_rates_over_the_recorded_samples() {
+        let start = Utc::now();
+        let mut history = History::new(Duration::seconds(10), Duration::milliseconds(500));
+
+        history.record(state(0), start);
+        history.record(state(10), start + Duration::milliseconds(500));
+        assert_eq!(errors_throughput_sec(history.get(0).unwrap()), 20);
+
+        history.record(state(30), start + Duration::milliseconds(1500));
+        assert_eq!(errors_throughput_sec(history.get(0).unwrap()), 20);
+
+        // Totals that don't change bring the rate back down
+        history.record(state(30), start + Duration::milliseconds(2500));
+        assert_eq!(errors_throughput_sec(history.get(0).unwrap()), 0);
+    }
+}
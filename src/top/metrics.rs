@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap},
     sync::Arc,
+    time::Duration,
 };
 
 use tokio::task::JoinHandle;
@@ -36,6 +37,8 @@ async fn component_added(client: Arc<SubscriptionClient>, tx: state::EventTx) {
                     processed_bytes_total: 0,
                     processed_bytes_throughput_sec: 0,
                     errors: 0,
+                    errors_throughput_sec: 0,
+                    buffer_events: 0,
                 }))
                 .await;
         }
@@ -236,6 +239,38 @@ async fn errors_totals(client: Arc<SubscriptionClient>, tx: state::EventTx, inte
     }
 }
 
+/// Events held in the buffers of sinks. Buffer sizes are gauges, which aren't available as
+/// subscriptions, so these are polled over HTTP at the sampling interval instead.
+pub fn poll_buffer_events(client: Client, tx: state::EventTx, interval: i64) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(interval as u64));
+        loop {
+            interval.tick().await;
+
+            // Failed requests are skipped; the subscriptions report the connection status
+            let res = client
+                .component_buffer_events_query(i16::max_value() as i64)
+                .await;
+            let data = match res.ok().and_then(|res| res.data) {
+                Some(data) => data,
+                None => continue,
+            };
+            let rows = data
+                .components
+                .edges
+                .into_iter()
+                .flatten()
+                .filter_map(|edge| {
+                    let d = edge?.node;
+                    let buffer_events = d.on.buffer_events()?;
+                    Some((ComponentKey::from(d.component_id), buffer_events))
+                })
+                .collect();
+            let _ = tx.send(state::EventType::BufferEvents(rows)).await;
+        }
+    })
+}
+
 /// Subscribe to each metrics channel through a separate client. This is a temporary workaround
 /// until client multiplexing is fixed. In future, we should be able to use a single client
 pub fn subscribe(
@@ -322,6 +357,8 @@ pub async fn init_components(client: &Client) -> Result<state::State, ()> {
                         processed_bytes_total: d.on.processed_bytes_total(),
                         processed_bytes_throughput_sec: 0,
                         errors: 0,
+                        errors_throughput_sec: 0,
+                        buffer_events: 0,
                     },
                 ))
             })
@@ -1,9 +1,12 @@
 mod cmd;
 mod dashboard;
 mod events;
+mod history;
 mod metrics;
 mod state;
 
+use std::path::PathBuf;
+
 use clap::Parser;
 pub use cmd::cmd;
 use url::Url;
@@ -26,4 +29,17 @@ pub struct Opts {
     /// Whether to reconnect if the underlying Vector API connection drops. By default, top will attempt to reconnect if the connection drops.
     #[clap(short, long)]
     no_reconnect: bool,
+
+    /// How far back to keep metrics for scrolling back through and exporting, in seconds
+    #[clap(default_value = "300", long)]
+    history: u32,
+
+    /// Order of the components: by ID, by error rate or by the number of events held in buffers
+    #[clap(default_value = "id", short, long, possible_values = &["id", "errors", "buffer"])]
+    sort: state::SortBy,
+
+    /// File to export the recorded metrics to as JSON when pressing 'e'. Defaults to a
+    /// timestamped file in the current directory
+    #[clap(short, long)]
+    export_path: Option<PathBuf>,
 }
@@ -1,8 +1,11 @@
 use std::{
+    cmp::Reverse,
     collections::{BTreeMap, HashMap},
     fmt::Display,
+    str::FromStr,
 };
 
+use serde::Serialize;
 use tokio::sync::mpsc;
 use tui::style::{Color, Style};
 use vector_core::internal_event::DEFAULT_OUTPUT;
@@ -32,6 +35,8 @@ pub enum EventType {
     /// Interval + identified metric
     ProcessedBytesThroughputs(i64, Vec<IdentifiedMetric>),
     ErrorsTotals(Vec<IdentifiedMetric>),
+    /// Events held in the buffers of sinks
+    BufferEvents(Vec<IdentifiedMetric>),
     ComponentAdded(ComponentRow),
     ComponentRemoved(ComponentKey),
     ConnectionUpdated(ConnectionStatus),
@@ -86,12 +91,68 @@ impl State {
             components,
         }
     }
+
+    /// Components in the order to display them. Ties are broken by ID.
+    pub fn sorted_components(&self, sort_by: SortBy) -> Vec<&ComponentRow> {
+        let mut rows = self.components.values().collect::<Vec<_>>();
+        match sort_by {
+            SortBy::Id => {}
+            SortBy::ErrorRate => rows.sort_by_key(|r| Reverse(r.errors_throughput_sec)),
+            SortBy::BufferDepth => rows.sort_by_key(|r| Reverse(r.buffer_events)),
+        }
+        rows
+    }
+}
+
+/// Order of the rows of the components table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Id,
+    ErrorRate,
+    BufferDepth,
+}
+
+impl SortBy {
+    /// The order to switch to when cycling through them from the dashboard
+    pub const fn next(self) -> Self {
+        match self {
+            SortBy::Id => SortBy::ErrorRate,
+            SortBy::ErrorRate => SortBy::BufferDepth,
+            SortBy::BufferDepth => SortBy::Id,
+        }
+    }
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "id" => Ok(SortBy::Id),
+            "errors" => Ok(SortBy::ErrorRate),
+            "buffer" => Ok(SortBy::BufferDepth),
+            s => Err(format!(
+                "{} is not a valid option, expected `id`, `errors` or `buffer`",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for SortBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortBy::Id => write!(f, "ID"),
+            SortBy::ErrorRate => write!(f, "error rate"),
+            SortBy::BufferDepth => write!(f, "buffer depth"),
+        }
+    }
 }
 pub type EventTx = mpsc::Sender<EventType>;
 pub type EventRx = mpsc::Receiver<EventType>;
 pub type StateRx = mpsc::Receiver<State>;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct OutputMetrics {
     pub sent_events_total: i64,
     pub sent_events_throughput_sec: i64,
@@ -119,6 +180,8 @@ pub struct ComponentRow {
     pub sent_events_total: i64,
     pub sent_events_throughput_sec: i64,
     pub errors: i64,
+    pub errors_throughput_sec: i64,
+    pub buffer_events: i64,
 }
 
 impl ComponentRow {
@@ -208,6 +271,13 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                         }
                     }
                 }
+                EventType::BufferEvents(rows) => {
+                    for (key, v) in rows {
+                        if let Some(r) = state.components.get_mut(&key) {
+                            r.buffer_events = v;
+                        }
+                    }
+                }
                 EventType::ComponentAdded(c) => {
                     let _ = state.components.insert(c.key.clone(), c);
                 }
@@ -0,0 +1,310 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use tokio::time::Instant;
+use vector_core::{
+    config::{AdmissionConfig, WhenExceeded},
+    event::LogEvent,
+    ByteSizeOf,
+};
+
+use crate::{
+    event::{EventArray, EventContainer},
+    internal_events::{AdmissionEventsDropped, AdmissionEventsThrottled},
+};
+
+/// The admission controller for the global limits, shared by the sources of every topology built
+/// with the same limits, so that they keep applying across reloads.
+static GLOBAL: Lazy<Mutex<Option<Arc<AdmissionController>>>> = Lazy::new(Default::default);
+
+/// Gets the admission controller for the global limits, if any are set.
+pub(super) fn global(config: &AdmissionConfig) -> Option<Arc<AdmissionController>> {
+    if !config.is_enabled() {
+        return None;
+    }
+
+    let mut global = GLOBAL.lock().expect("admission controller lock poisoned");
+    match global.as_ref() {
+        Some(controller) if controller.config == *config => Some(Arc::clone(controller)),
+        _ => {
+            let controller = Arc::new(AdmissionController::new(config.clone()));
+            *global = Some(Arc::clone(&controller));
+            Some(controller)
+        }
+    }
+}
+
+/// Enforces limits on the rate at which events are admitted into the topology.
+///
+/// Each tenant gets a token bucket for the number of events and one for their size in bytes, which
+/// refill at the configured rate and hold up to one second's worth of tokens. Events are admitted
+/// while both buckets have tokens left, and a group of events larger than what a bucket can hold
+/// is admitted once the bucket is full, leaving it in debt.
+pub struct AdmissionController {
+    config: AdmissionConfig,
+    /// The tenant of all events, when the limits are those of a tenant.
+    tenant: Option<String>,
+    tenants: Mutex<HashMap<Option<String>, Limits>>,
+}
+
+impl AdmissionController {
+    pub fn new(config: AdmissionConfig) -> Self {
+        Self {
+            config,
+            tenant: None,
+            tenants: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a controller for the limits of the given tenant, which all events are reported
+    /// under.
+    pub fn for_tenant(config: AdmissionConfig, tenant: String) -> Self {
+        Self {
+            tenant: Some(tenant),
+            ..Self::new(config)
+        }
+    }
+
+    /// Admits the given events, either waiting until the limits allow them in or dropping the ones
+    /// that exceed them, depending on the configured policy.
+    ///
+    /// Returns the events that were admitted, if any.
+    pub async fn admit(&self, events: EventArray) -> Vec<EventArray> {
+        let mut admitted = Vec::new();
+        for (tenant, events) in self.split_by_tenant(events) {
+            let count = events.len();
+            let byte_size = events.size_of();
+            loop {
+                let wait = match self.try_admit(&tenant, count, byte_size) {
+                    Ok(()) => {
+                        admitted.push(events);
+                        break;
+                    }
+                    Err(wait) => wait,
+                };
+                match self.config.when_exceeded {
+                    WhenExceeded::Block => {
+                        emit!(AdmissionEventsThrottled {
+                            tenant: tenant.as_deref(),
+                            wait,
+                        });
+                        tokio::time::sleep(wait).await;
+                    }
+                    WhenExceeded::Drop => {
+                        emit!(AdmissionEventsDropped {
+                            tenant: tenant.as_deref(),
+                            count,
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+        admitted
+    }
+
+    fn try_admit(
+        &self,
+        tenant: &Option<String>,
+        count: usize,
+        byte_size: usize,
+    ) -> Result<(), Duration> {
+        let mut tenants = self
+            .tenants
+            .lock()
+            .expect("admission controller lock poisoned");
+        let limits = match tenants.get_mut(tenant) {
+            Some(limits) => limits,
+            None => tenants
+                .entry(tenant.clone())
+                .or_insert_with(|| Limits::new(&self.config)),
+        };
+
+        let now = Instant::now();
+        let events_wait = limits.events.as_mut().and_then(|events| {
+            events.refill(now);
+            events.wait_for(count as f64)
+        });
+        let bytes_wait = limits.bytes.as_mut().and_then(|bytes| {
+            bytes.refill(now);
+            bytes.wait_for(byte_size as f64)
+        });
+        if let Some(wait) = events_wait.max(bytes_wait) {
+            return Err(wait);
+        }
+
+        if let Some(events) = &mut limits.events {
+            events.take(count as f64);
+        }
+        if let Some(bytes) = &mut limits.bytes {
+            bytes.take(byte_size as f64);
+        }
+        Ok(())
+    }
+
+    /// Splits the events into groups of the same tenant, keeping the order of the events of each
+    /// tenant.
+    fn split_by_tenant(&self, events: EventArray) -> Vec<(Option<String>, EventArray)> {
+        match &self.config.tenant_key {
+            Some(tenant_key) => split_by_field(events, tenant_key),
+            None => vec![(self.tenant.clone(), events)],
+        }
+    }
+}
+
+/// Splits the events into groups with the same value of the given log field, keeping the order of
+/// the events of each group.
+pub(super) fn split_by_field(events: EventArray, field: &str) -> Vec<(Option<String>, EventArray)> {
+    let logs = match events {
+        EventArray::Logs(logs) => logs,
+        events => return vec![(None, events)],
+    };
+
+    let mut groups: Vec<(Option<String>, Vec<LogEvent>)> = Vec::new();
+    for log in logs {
+        let value = log.get(field).map(|value| value.to_string_lossy());
+        match groups.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, logs)) => logs.push(log),
+            None => groups.push((value, vec![log])),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(value, logs)| (value, EventArray::Logs(logs)))
+        .collect()
+}
+
+struct Limits {
+    events: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+}
+
+impl Limits {
+    fn new(config: &AdmissionConfig) -> Self {
+        Self {
+            events: config
+                .events_per_sec
+                .map(|rate| TokenBucket::new(rate.get())),
+            bytes: config
+                .bytes_per_sec
+                .map(|rate| TokenBucket::new(rate.get())),
+        }
+    }
+}
+
+struct TokenBucket {
+    /// Tokens added per second, which is also the most the bucket holds.
+    rate: f64,
+    /// Tokens in the bucket, which is negative while the bucket is in debt.
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64) -> Self {
+        let rate = rate as f64;
+        Self {
+            rate,
+            tokens: rate,
+            refilled: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.refilled = now;
+    }
+
+    /// Gets how long to wait before the given number of tokens can be taken, if at all.
+    fn wait_for(&self, amount: f64) -> Option<Duration> {
+        let needed = amount.min(self.rate);
+        (self.tokens < needed).then(|| Duration::from_secs_f64((needed - self.tokens) / self.rate))
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.tokens -= amount;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+    use crate::event::Event;
+
+    fn logs(tenants: &[&str]) -> EventArray {
+        EventArray::Logs(
+            tenants
+                .iter()
+                .map(|tenant| {
+                    let mut log = LogEvent::from("message");
+                    log.insert("tenant", *tenant);
+                    log
+                })
+                .collect(),
+        )
+    }
+
+    fn count(arrays: &[EventArray]) -> usize {
+        arrays.iter().map(|array| array.len()).sum()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn drops_events_over_the_limit() {
+        let controller = AdmissionController::new(AdmissionConfig {
+            events_per_sec: NonZeroU64::new(3),
+            when_exceeded: WhenExceeded::Drop,
+            ..Default::default()
+        });
+
+        assert_eq!(count(&controller.admit(logs(&["a", "a"])).await), 2);
+        assert_eq!(count(&controller.admit(logs(&["a", "a"])).await), 0);
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_eq!(count(&controller.admit(logs(&["a", "a"])).await), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn blocks_events_over_the_limit() {
+        let controller = AdmissionController::new(AdmissionConfig {
+            events_per_sec: NonZeroU64::new(2),
+            ..Default::default()
+        });
+
+        let start = Instant::now();
+        assert_eq!(count(&controller.admit(logs(&["a", "a"])).await), 2);
+        assert_eq!(count(&controller.admit(logs(&["a", "a"])).await), 2);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_each_tenant_separately() {
+        let controller = AdmissionController::new(AdmissionConfig {
+            events_per_sec: NonZeroU64::new(2),
+            tenant_key: Some("tenant".into()),
+            when_exceeded: WhenExceeded::Drop,
+            ..Default::default()
+        });
+
+        assert_eq!(count(&controller.admit(logs(&["a", "b", "a"])).await), 3);
+
+        // Tenant `a` has used up its limit, while tenant `b` has one event left.
+        let tenants: Vec<_> = controller
+            .admit(logs(&["a", "b", "a"]))
+            .await
+            .into_iter()
+            .flat_map(|array| array.into_events())
+            .map(|event| match event {
+                Event::Log(log) => log.get("tenant").unwrap().to_string_lossy(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(tenants, vec!["b"]);
+    }
+}
@@ -6,7 +6,12 @@ use std::{
     time::Instant,
 };
 
-use futures::{stream::FuturesOrdered, FutureExt, StreamExt};
+use futures::{
+    future,
+    stream::{self, BoxStream, FuturesOrdered},
+    FutureExt, StreamExt,
+};
+use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
@@ -28,15 +33,25 @@ use vector_core::{
 };
 
 use super::{
+    admission::{self, AdmissionController},
+    circuit_breaker::{self, CircuitBreaker},
+    dead_letter::DeadLetterTracker,
+    expiry,
     fanout::{self, Fanout},
+    health::{self, ComponentKind},
+    memory::{self, MemoryAccount},
+    prioritize::Prioritize,
     schema,
+    tags::EventTags,
     task::{Task, TaskOutput},
-    BuiltBuffer, ConfigDiff,
+    telemetry::{self, TracedComponent},
+    tenancy, BuiltBuffer, ConfigDiff,
 };
 use crate::{
+    conditions::{shared, AnyCondition},
     config::{
-        ComponentKey, DataType, Input, Output, OutputId, ProxyConfig, SinkContext, SourceContext,
-        TransformContext, TransformOuter,
+        ComponentKey, DataType, Input, LogNamespace, Output, OutputId, ProxyConfig, SinkContext,
+        SourceContext, TransformContext, TransformOuter, DEAD_LETTER_OUTPUT, FALLBACK_OUTPUT,
     },
     event::{EventArray, EventContainer},
     internal_events::EventsReceived,
@@ -45,7 +60,7 @@ use crate::{
     spawn_named,
     transforms::{SyncTransform, TaskTransform, Transform, TransformOutputs, TransformOutputsBuf},
     utilization::wrap,
-    SourceSender,
+    vrl_library, SourceSender,
 };
 
 static ENRICHMENT_TABLES: Lazy<enrichment::TableRegistry> =
@@ -120,6 +135,34 @@ pub(self) async fn load_enrichment_tables<'a>(
     (&ENRICHMENT_TABLES, errors)
 }
 
+/// Builds the top-level conditions of the config, for the `shared` conditions of the components
+/// built from here on to refer to.
+/// Compiles the top-level VRL library and shared conditions, which the components refer to by
+/// name, returning the errors, if any.
+///
+/// The library is loaded first, as shared conditions may themselves refer to its programs.
+pub(crate) fn load_shared_definitions(
+    vrl_library: &IndexMap<String, String>,
+    conditions: &IndexMap<String, AnyCondition>,
+    enrichment_tables: &enrichment::TableRegistry,
+) -> Vec<String> {
+    let mut errors = vrl_library::load(vrl_library, enrichment_tables);
+    let mut shared_conditions = HashMap::new();
+
+    for (name, condition) in conditions.iter() {
+        match condition.build(enrichment_tables) {
+            Ok(condition) => {
+                shared_conditions.insert(name.clone(), Arc::new(condition));
+            }
+            Err(error) => errors.push(format!("Condition \"{}\": {}", name, error)),
+        }
+    }
+
+    shared::load(shared_conditions);
+
+    errors
+}
+
 pub struct Pieces {
     pub(super) inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Vec<OutputId>)>,
     pub(crate) outputs: HashMap<ComponentKey, HashMap<Option<String>, fanout::ControlChannel>>,
@@ -146,9 +189,18 @@ pub async fn build_pieces(
 
     let mut errors = vec![];
 
+    // Components built from here on create their HTTP clients with the tuning of this config.
+    crate::http::set_client_config(config.global.http_client.clone());
+
     let (enrichment_tables, enrichment_errors) = load_enrichment_tables(config, diff).await;
     errors.extend(enrichment_errors);
 
+    errors.extend(load_shared_definitions(
+        &config.vrl_library,
+        &config.conditions,
+        enrichment_tables,
+    ));
+
     // Build sources
     for (key, source) in config
         .sources()
@@ -174,14 +226,93 @@ pub async fn build_pieces(
         let mut controls = HashMap::new();
         let mut schema_definitions = HashMap::with_capacity(source_outputs.len());
 
+        // Events are given their tenant and admitted by the limits of that tenant first, then by
+        // the limits of the source, so that a source over its own limits doesn't use up the
+        // global limits shared with the other sources. The events admitted are then charged to the
+        // memory budget, under the account of the source.
+        let tenancy = tenancy::global(&config.global.tenancy);
+        let memory = memory::global(&config.global.memory_limit)
+            .map(|budget| (budget, Arc::new(MemoryAccount::default())));
+        let traced = telemetry::global(&config.global.telemetry)
+            .map(|telemetry| telemetry.component(key, "source", typetag));
+        let admission: Vec<_> = [
+            source
+                .admission
+                .is_enabled()
+                .then(|| Arc::new(AdmissionController::new(source.admission.clone()))),
+            admission::global(&config.global.admission),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let log_namespace = source.log_namespace.unwrap_or(config.schema.log_namespace);
+        let tags = match EventTags::new(&config.global.tags, &source.tags) {
+            Ok(tags) => tags.map(Arc::new),
+            Err(error) => {
+                errors.push(format!("Source \"{}\": invalid tags: {}", key, error));
+                continue;
+            }
+        };
+
         for output in source_outputs {
+            let columnar = output.ty.contains(DataType::Metric)
+                && consumers_accept_columnar(
+                    config,
+                    &OutputId {
+                        component: key.clone(),
+                        port: output.port.clone(),
+                    },
+                );
             let mut rx = builder.add_output(output.clone());
+            let admission = admission.clone();
+            let tenancy = tenancy.clone();
+            let memory = memory.clone();
+            let traced = traced.clone();
+            let tags = tags.clone();
+            let key = key.clone();
 
             let (mut fanout, control) = Fanout::new();
             let pump = async move {
                 debug!("Source pump starting.");
-                while let Some(array) = rx.next().await {
-                    fanout.send(array).await;
+                while let Some(mut array) = rx.next().await {
+                    if log_namespace != LogNamespace::Legacy {
+                        array.for_each_log(|log| log_namespace.apply(log));
+                    }
+                    if let Some(tags) = &tags {
+                        tags.apply(&mut array);
+                    }
+                    if let Some(traced) = &traced {
+                        traced.sample(&mut array);
+                    }
+                    let mut arrays = match &tenancy {
+                        Some(tenancy) => tenancy.admit(&key, array).await,
+                        None => vec![array],
+                    };
+                    for controller in &admission {
+                        let mut admitted = Vec::with_capacity(arrays.len());
+                        for array in arrays {
+                            admitted.extend(controller.admit(array).await);
+                        }
+                        arrays = admitted;
+                    }
+                    if let Some((budget, account)) = &memory {
+                        let mut admitted = Vec::with_capacity(arrays.len());
+                        for array in arrays {
+                            admitted.extend(budget.admit(account, array).await);
+                        }
+                        arrays = admitted;
+                    }
+                    if let Some(traced) = &traced {
+                        traced.hand_over(&mut arrays);
+                    }
+                    for array in arrays {
+                        let array = if columnar {
+                            array.into_columnar()
+                        } else {
+                            array
+                        };
+                        fanout.send(array).await;
+                    }
                 }
                 debug!("Source pump finished.");
                 Ok(TaskOutput::Source)
@@ -226,6 +357,7 @@ pub async fn build_pieces(
             out: pipeline,
             proxy: ProxyConfig::merge_with_env(&config.global.proxy, &source.proxy),
             acknowledgements: source.sink_acknowledgements,
+            acknowledgements_timeout: source.sink_acknowledgements_timeout,
             schema_definitions,
         };
         let server = match source.inner.build(context).await {
@@ -261,6 +393,8 @@ pub async fn build_pieces(
         };
         let server = Task::new(key.clone(), typetag, server);
 
+        health::starting(key, ComponentKind::Source, typetag, Vec::new(), false);
+
         outputs.extend(controls);
         tasks.insert(key.clone(), pump);
         source_tasks.insert(key.clone(), server);
@@ -314,12 +448,56 @@ pub async fn build_pieces(
 
         inputs.insert(key.clone(), (input_tx, node.inputs.clone()));
 
-        let (transform_task, transform_outputs) = build_transform(transform, node, input_rx);
+        health::starting(
+            key,
+            ComponentKind::Transform,
+            node.typetag,
+            node.inputs
+                .iter()
+                .map(|input| input.component.clone())
+                .collect(),
+            false,
+        );
+
+        let traced = telemetry::global(&config.global.telemetry)
+            .map(|telemetry| telemetry.component(key, "transform", node.typetag));
+        let (transform_task, transform_outputs) =
+            build_transform(transform, node, input_rx, traced);
 
         outputs.extend(transform_outputs);
         tasks.insert(key.clone(), transform_task);
     }
 
+    // Build the buffers of the new sinks concurrently, so that the time taken to load large disk
+    // buffers doesn't add up across sinks.
+    let buffer_builds = config
+        .sinks()
+        .filter(|(key, _)| diff.sinks.contains_new(key) && !buffers.contains_key(key))
+        .map(|(key, sink)| {
+            let buffer_type = match sink.buffer.stages().first().expect("cant ever be empty") {
+                BufferType::Memory { .. } => "memory",
+                BufferType::DiskV1 { .. } | BufferType::DiskV2 { .. } => "disk",
+            };
+            let buffer_span = error_span!(
+                "sink",
+                component_kind = "sink",
+                component_id = %key.id(),
+                component_type = sink.inner.sink_type(),
+                component_name = %key.id(),
+                buffer_type = buffer_type,
+            );
+            let data_dir = config.global.data_dir.clone();
+            async move {
+                let buffer = sink
+                    .buffer
+                    .build(data_dir, key.to_string(), buffer_span)
+                    .await;
+                (key.clone(), buffer)
+            }
+        });
+    let mut new_buffers: HashMap<_, _> =
+        future::join_all(buffer_builds).await.into_iter().collect();
+
     // Build sinks
     for (key, sink) in config
         .sinks()
@@ -333,6 +511,10 @@ pub async fn build_pieces(
 
         let typetag = sink.inner.sink_type();
         let input_type = sink.inner.input().data_type();
+        let columnar = sink.inner.input().accepts_columnar();
+        let dead_letter_enabled = sink.dead_letter.enabled;
+        let fallback = sink.fallback.enabled.then(|| sink.fallback.clone());
+        let priority = sink.priority.enabled.then(|| sink.priority.max_events);
 
         if config.schema.enabled {
             // At this point, we've validated that all transforms are valid, including any
@@ -346,22 +528,9 @@ pub async fn build_pieces(
         let (tx, rx, acker) = if let Some(buffer) = buffers.remove(key) {
             buffer
         } else {
-            let buffer_type = match sink.buffer.stages().first().expect("cant ever be empty") {
-                BufferType::Memory { .. } => "memory",
-                BufferType::DiskV1 { .. } | BufferType::DiskV2 { .. } => "disk",
-            };
-            let buffer_span = error_span!(
-                "sink",
-                component_kind = "sink",
-                component_id = %key.id(),
-                component_type = typetag,
-                component_name = %key.id(),
-                buffer_type = buffer_type,
-            );
-            let buffer = sink
-                .buffer
-                .build(config.global.data_dir.clone(), key.to_string(), buffer_span)
-                .await;
+            let buffer = new_buffers
+                .remove(key)
+                .expect("buffer of new sink should have been built");
             match buffer {
                 Err(error) => {
                     errors.push(format!("Sink \"{}\": {}", key, error));
@@ -387,8 +556,32 @@ pub async fn build_pieces(
             Ok(built) => built,
         };
 
+        // If the sink has a dead letter output, we track the events going into the sink so that
+        // we can route any that it fails to deliver to that output.
+        let dead_letter = dead_letter_enabled.then(|| {
+            let (fanout, control) = Fanout::new();
+            outputs.insert(
+                OutputId::from((key, DEAD_LETTER_OUTPUT.to_owned())),
+                control,
+            );
+            DeadLetterTracker::new(key.clone(), typetag, fanout)
+        });
+
+        // If the sink has a fallback output, its circuit breaker reroutes events to that output
+        // while the sink is failing.
+        let fallback = fallback.map(|options| {
+            let (fanout, control) = Fanout::new();
+            outputs.insert(OutputId::from((key, FALLBACK_OUTPUT.to_owned())), control);
+            CircuitBreaker::new(key.clone(), &options, fanout, acker.clone())
+        });
+
+        let traced = telemetry::global(&config.global.telemetry)
+            .map(|telemetry| telemetry.component(key, "sink", typetag));
+
         let (trigger, tripwire) = Tripwire::new();
 
+        let shed_acker = acker.clone();
+        let expiry_acker = acker.clone();
         let sink = async move {
             // Why is this Arc<Mutex<Option<_>>> needed you ask.
             // In case when this function build_pieces errors
@@ -404,19 +597,85 @@ pub async fn build_pieces(
 
             let mut rx = wrap(rx);
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
-                    .inspect(|events| {
-                        emit!(EventsReceived {
-                            count: events.len(),
-                            byte_size: events.size_of(),
-                        })
+            let events = rx
+                .by_ref()
+                .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
+                .filter_map(move |events| ready(expiry::discard_expired(events, &expiry_acker)))
+                .map(move |events| into_accepted(events, columnar))
+                .inspect(|events| {
+                    emit!(EventsReceived {
+                        count: events.len(),
+                        byte_size: events.size_of(),
                     })
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
+                })
+                .take_until_if(tripwire);
+
+            let events = match priority {
+                Some(max_events) => {
+                    Box::pin(Prioritize::new(Box::pin(events), max_events, shed_acker))
+                        as BoxStream<'_, _>
+                }
+                None => Box::pin(events),
+            };
+
+            // Events are routed around the sink before dead letter tracking, so that rerouted events
+            // never end up in its dead letter output.
+            let (events, fallback_task) = match fallback {
+                Some((breaker, fallback_task)) => (
+                    Box::pin(
+                        breaker
+                            .route(events)
+                            .map(|(events, batch)| (events, Some(batch))),
+                    ) as BoxStream<'_, _>,
+                    Some(fallback_task),
+                ),
+                None => (
+                    Box::pin(events.map(|events| (events, None))) as BoxStream<'_, _>,
+                    None,
+                ),
+            };
+
+            let (events, dead_letter_task) = match dead_letter {
+                Some((tracker, dead_letter_task)) => (
+                    Box::pin(events.flat_map(move |(events, batch)| {
+                        stream::iter(
+                            tracker
+                                .track(events)
+                                .map(move |events| (events, batch.clone())),
+                        )
+                    })) as BoxStream<'_, _>,
+                    Some(dead_letter_task),
+                ),
+                None => (events, None),
+            };
+
+            // Dead letter tracking takes over the finalizers of the events, so the circuit breaker
+            // and telemetry only attach their own afterwards, to see how the sink itself finalizes
+            // them.
+            let events = events.map(move |(mut events, batch)| {
+                if let Some(batch) = batch {
+                    circuit_breaker::attach(&mut events, &batch);
+                }
+                if let Some(traced) = &traced {
+                    traced.track_delivery(&mut events);
+                }
+                events
+            });
+
+            let (result, (), ()) = futures::join!(
+                sink.run(events),
+                async {
+                    if let Some(task) = dead_letter_task {
+                        task.await;
+                    }
+                },
+                async {
+                    if let Some(task) = fallback_task {
+                        task.await;
+                    }
+                },
+            );
+            result.map(|_| {
                 debug!("Finished.");
                 TaskOutput::Sink(rx, acker)
             })
@@ -424,9 +683,20 @@ pub async fn build_pieces(
 
         let task = Task::new(key.clone(), typetag, sink);
 
+        health::starting(
+            key,
+            ComponentKind::Sink,
+            typetag,
+            sink_inputs
+                .iter()
+                .map(|input| input.component.clone())
+                .collect(),
+            enable_healthcheck,
+        );
+
         let component_key = key.clone();
         let healthcheck_task = async move {
-            if enable_healthcheck {
+            let result = if enable_healthcheck {
                 let duration = Duration::from_secs(10);
                 timeout(duration, healthcheck)
                     .map(|result| match result {
@@ -462,7 +732,9 @@ pub async fn build_pieces(
             } else {
                 info!("Healthcheck: Disabled.");
                 Ok(TaskOutput::Healthcheck)
-            }
+            };
+            health::healthcheck_completed(&component_key, result.is_ok());
+            result
         };
 
         let healthcheck_task = Task::new(key.clone(), typetag, healthcheck_task);
@@ -507,9 +779,37 @@ const fn filter_events_type(events: &EventArray, data_type: DataType) -> bool {
         EventArray::Logs(_) => data_type.contains(DataType::Log),
         EventArray::Metrics(_) => data_type.contains(DataType::Metric),
         EventArray::Traces(_) => data_type.contains(DataType::Trace),
+        EventArray::MetricColumns(_) => data_type.contains(DataType::Metric),
+    }
+}
+
+/// Turns batches of metrics in columns back into rows, for components that don't accept them.
+fn into_accepted(events: EventArray, columnar: bool) -> EventArray {
+    if columnar {
+        events
+    } else {
+        events.into_rows()
     }
 }
 
+/// Whether every component consuming the output accepts batches of metrics in columns, in which
+/// case the source hands its metrics over in columns. Components added to consume the output later
+/// turn the columns back into rows if they need to.
+fn consumers_accept_columnar(config: &super::Config, output: &OutputId) -> bool {
+    let mut consumers = config
+        .transforms()
+        .filter(|(_, transform)| transform.inputs.contains(output))
+        .map(|(_, transform)| transform.inner.input())
+        .chain(
+            config
+                .sinks()
+                .filter(|(_, sink)| sink.inputs.contains(output))
+                .map(|(_, sink)| sink.inner.input()),
+        )
+        .peekable();
+    consumers.peek().is_some() && consumers.all(|input| input.accepts_columnar())
+}
+
 #[derive(Debug, Clone)]
 struct TransformNode {
     key: ComponentKey,
@@ -541,17 +841,19 @@ fn build_transform(
     transform: Transform,
     node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
+    traced: Option<TracedComponent>,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     match transform {
         // TODO: avoid the double boxing for function transforms here
-        Transform::Function(t) => build_sync_transform(Box::new(t), node, input_rx),
-        Transform::Synchronous(t) => build_sync_transform(t, node, input_rx),
+        Transform::Function(t) => build_sync_transform(Box::new(t), node, input_rx, traced),
+        Transform::Synchronous(t) => build_sync_transform(t, node, input_rx, traced),
         Transform::Task(t) => build_task_transform(
             t,
             input_rx,
-            node.input_details.data_type(),
+            &node.input_details,
             node.typetag,
             &node.key,
+            traced,
         ),
     }
 }
@@ -560,10 +862,11 @@ fn build_sync_transform(
     t: Box<dyn SyncTransform>,
     node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
+    traced: Option<TracedComponent>,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (outputs, controls) = TransformOutputs::new(node.outputs);
 
-    let runner = Runner::new(t, input_rx, node.input_details.data_type(), outputs);
+    let runner = Runner::new(t, input_rx, &node.input_details, outputs, traced);
     let transform = if node.enable_concurrency {
         runner.run_concurrently().boxed()
     } else {
@@ -587,7 +890,9 @@ struct Runner {
     transform: Box<dyn SyncTransform>,
     input_rx: Option<BufferReceiver<EventArray>>,
     input_type: DataType,
+    columnar: bool,
     outputs: TransformOutputs,
+    traced: Option<TracedComponent>,
     timer: crate::utilization::Timer,
     last_report: Instant,
 }
@@ -596,14 +901,17 @@ impl Runner {
     fn new(
         transform: Box<dyn SyncTransform>,
         input_rx: BufferReceiver<EventArray>,
-        input_type: DataType,
+        input: &Input,
         outputs: TransformOutputs,
+        traced: Option<TracedComponent>,
     ) -> Self {
         Self {
             transform,
             input_rx: Some(input_rx),
-            input_type,
+            input_type: input.data_type(),
+            columnar: input.accepts_columnar(),
             outputs,
+            traced,
             timer: crate::utilization::Timer::new(),
             last_report: Instant::now(),
         }
@@ -624,6 +932,9 @@ impl Runner {
 
     async fn send_outputs(&mut self, outputs_buf: &mut TransformOutputsBuf) {
         self.timer.start_wait();
+        if let Some(traced) = &self.traced {
+            traced.hand_over(outputs_buf.arrays_mut());
+        }
         self.outputs.send(outputs_buf).await;
     }
 
@@ -633,12 +944,14 @@ impl Runner {
 
         let mut outputs_buf = self.outputs.new_buf_with_capacity(INLINE_BATCH_SIZE);
 
+        let columnar = self.columnar;
         let mut input_rx = self
             .input_rx
             .take()
             .expect("can't run runner twice")
             .into_stream()
-            .filter(move |events| ready(filter_events_type(events, self.input_type)));
+            .filter(move |events| ready(filter_events_type(events, self.input_type)))
+            .map(move |events| into_accepted(events, columnar));
 
         self.timer.start_wait();
         while let Some(events) = input_rx.next().await {
@@ -652,12 +965,14 @@ impl Runner {
     }
 
     async fn run_concurrently(mut self) -> Result<TaskOutput, ()> {
+        let columnar = self.columnar;
         let input_rx = self
             .input_rx
             .take()
             .expect("can't run runner twice")
             .into_stream()
-            .filter(move |events| ready(filter_events_type(events, self.input_type)));
+            .filter(move |events| ready(filter_events_type(events, self.input_type)))
+            .map(move |events| into_accepted(events, columnar));
 
         let mut input_rx =
             super::ready_arrays::ReadyArrays::with_capacity(input_rx, READY_ARRAY_CAPACITY);
@@ -722,16 +1037,20 @@ impl Runner {
 fn build_task_transform(
     t: Box<dyn TaskTransform<EventArray>>,
     input_rx: BufferReceiver<EventArray>,
-    input_type: DataType,
+    input: &Input,
     typetag: &str,
     key: &ComponentKey,
+    traced: Option<TracedComponent>,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (mut fanout, control) = Fanout::new();
 
     let input_rx = crate::utilization::wrap(input_rx.into_stream());
 
+    let input_type = input.data_type();
+    let columnar = input.accepts_columnar();
     let filtered = input_rx
         .filter(move |events| ready(filter_events_type(events, input_type)))
+        .map(move |events| into_accepted(events, columnar))
         .inspect(|events| {
             emit!(EventsReceived {
                 count: events.len(),
@@ -740,6 +1059,12 @@ fn build_task_transform(
         });
     let stream = t
         .transform(Box::pin(filtered))
+        .map(move |mut events: EventArray| {
+            if let Some(traced) = &traced {
+                traced.hand_over(Some(&mut events));
+            }
+            events
+        })
         .inspect(|events: &EventArray| {
             emit!(EventsSent {
                 count: events.len(),
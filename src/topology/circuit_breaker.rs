@@ -0,0 +1,443 @@
+//! Rerouting of the events of a sink to its fallback output while the sink is failing.
+//!
+//! Every batch of events handed to a sink with a fallback output is tracked with a batch notifier
+//! of its own, and the circuit breaker keeps a window of how the most recent events were finalized.
+//! Once too many of them failed, or took too long to be finalized, the breaker opens and events are
+//! sent to the fallback output instead of the sink. After a while, a single batch is let through to
+//! the sink to probe whether it has recovered, which closes the breaker again if it was delivered
+//! in time.
+//!
+//! Events sent to the fallback output keep their finalizers, so acknowledgements flow back to the
+//! source once whatever consumes the fallback output is done with them.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use async_stream::stream;
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, Stream, StreamExt};
+use tokio::{
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+use vector_buffers::Acker;
+use vector_core::{
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer},
+    internal_event::EventsSent,
+    ByteSizeOf,
+};
+
+use super::{fanout::Fanout, health};
+use crate::{
+    config::{ComponentKey, SinkFallbackOptions, FALLBACK_OUTPUT},
+    event::{EventArray, EventContainer},
+    internal_events::CircuitBreakerStateChanged,
+};
+
+/// The state of the circuit breaker of a sink.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitBreakerState {
+    /// Events are handed to the sink.
+    Closed,
+    /// A batch of events was handed to the sink to probe whether it has recovered, while any other
+    /// events are sent to the fallback output.
+    HalfOpen,
+    /// Events are sent to the fallback output.
+    Open,
+}
+
+impl CircuitBreakerState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::HalfOpen => "half_open",
+            Self::Open => "open",
+        }
+    }
+
+    /// The value of the `circuit_breaker_state` gauge for this state.
+    pub const fn as_gauge(self) -> f64 {
+        match self {
+            Self::Closed => 0.0,
+            Self::HalfOpen => 1.0,
+            Self::Open => 2.0,
+        }
+    }
+}
+
+/// Routes the events of a sink either to the sink itself or to its fallback output, depending on
+/// the state of its circuit breaker.
+pub(super) struct CircuitBreaker {
+    breaker: Arc<Mutex<Breaker>>,
+    // Like for dead letter tracking, the number of pending batches is bounded by the number of
+    // events the sink has in flight.
+    tx: mpsc::UnboundedSender<PendingBatch>,
+    fallback: Fanout,
+    acker: Acker,
+}
+
+struct PendingBatch {
+    count: usize,
+    probe: bool,
+    receiver: BatchStatusReceiver,
+}
+
+impl CircuitBreaker {
+    /// Creates a new circuit breaker sending events to the given fallback fanout while it is open,
+    /// along with the task that keeps track of how the sink finalizes the events handed to it.
+    ///
+    /// Events sent to the fallback output are acknowledged with the given `Acker`, the same as if
+    /// the sink had processed them.
+    pub(super) fn new(
+        key: ComponentKey,
+        options: &SinkFallbackOptions,
+        fallback: Fanout,
+        acker: Acker,
+    ) -> (Self, BoxFuture<'static, ()>) {
+        let breaker = Arc::new(Mutex::new(Breaker::new(key, options)));
+        let max_latency = options
+            .max_latency_secs
+            .map(|secs| Duration::from_secs(secs.get()));
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = run_monitor(rx, Arc::clone(&breaker), max_latency).boxed();
+
+        let breaker = Self {
+            breaker,
+            tx,
+            fallback,
+            acker,
+        };
+        (breaker, task)
+    }
+
+    /// Routes the given events, sending those that go around the sink to the fallback output.
+    ///
+    /// The events for the sink are returned along with a batch notifier, which is to be attached
+    /// to them with [`attach`] right before they are handed to the sink.
+    pub(super) fn route<'a>(
+        mut self,
+        mut events: impl Stream<Item = EventArray> + Send + Unpin + 'a,
+    ) -> impl Stream<Item = (EventArray, Arc<BatchNotifier>)> + Send + 'a {
+        stream! {
+            while let Some(events) = events.next().await {
+                let probe = match self.decide() {
+                    Some(probe) => probe,
+                    None => {
+                        let count = events.len();
+                        emit!(EventsSent {
+                            count,
+                            byte_size: events.size_of(),
+                            output: Some(FALLBACK_OUTPUT),
+                        });
+                        self.fallback.send(events).await;
+                        self.acker.ack(count);
+                        continue;
+                    }
+                };
+
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                // The monitor only goes away during shutdown, in which case the state of the
+                // breaker no longer matters.
+                let _ = self.tx.send(PendingBatch {
+                    count: events.len(),
+                    probe,
+                    receiver,
+                });
+                yield (events, batch);
+            }
+        }
+    }
+
+    /// Decides whether the next events go to the sink, returning whether they are a probe if so.
+    fn decide(&self) -> Option<bool> {
+        let mut breaker = self.breaker.lock().expect("circuit breaker lock poisoned");
+        match breaker.state {
+            State::Closed => Some(false),
+            State::Open { until } if Instant::now() >= until => {
+                breaker.transition(State::HalfOpen);
+                Some(true)
+            }
+            State::Open { .. } | State::HalfOpen => None,
+        }
+    }
+}
+
+/// Attaches the batch notifier of the circuit breaker to the given events.
+pub(super) fn attach(events: &mut EventArray, batch: &Arc<BatchNotifier>) {
+    events.for_each_event(|mut event| {
+        event
+            .metadata_mut()
+            .add_finalizer(EventFinalizer::new(Arc::clone(batch)));
+    });
+}
+
+async fn run_monitor(
+    mut rx: mpsc::UnboundedReceiver<PendingBatch>,
+    breaker: Arc<Mutex<Breaker>>,
+    max_latency: Option<Duration>,
+) {
+    let mut pending = FuturesUnordered::new();
+    let mut router_closed = false;
+
+    emit!(CircuitBreakerStateChanged {
+        state: CircuitBreakerState::Closed,
+    });
+
+    loop {
+        tokio::select! {
+            maybe_pending = rx.recv(), if !router_closed => match maybe_pending {
+                Some(PendingBatch { count, probe, receiver }) => {
+                    pending.push(async move {
+                        let failed = match max_latency {
+                            Some(max_latency) => !matches!(
+                                tokio::time::timeout(max_latency, receiver).await,
+                                Ok(BatchStatus::Delivered)
+                            ),
+                            None => receiver.await != BatchStatus::Delivered,
+                        };
+                        (count, probe, failed)
+                    });
+                }
+                None => router_closed = true,
+            },
+
+            Some((count, probe, failed)) = pending.next(), if !pending.is_empty() => {
+                breaker
+                    .lock()
+                    .expect("circuit breaker lock poisoned")
+                    .record(count, probe, failed);
+            }
+
+            else => break,
+        }
+    }
+
+    debug!("Circuit breaker monitoring finished.");
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    Closed,
+    HalfOpen,
+    Open { until: Instant },
+}
+
+impl State {
+    const fn public(self) -> CircuitBreakerState {
+        match self {
+            Self::Closed => CircuitBreakerState::Closed,
+            Self::HalfOpen => CircuitBreakerState::HalfOpen,
+            Self::Open { .. } => CircuitBreakerState::Open,
+        }
+    }
+}
+
+struct Breaker {
+    key: ComponentKey,
+    state: State,
+    failure_ratio: f64,
+    window_events: usize,
+    open_for: Duration,
+    /// The number of events and whether they failed, for each of the most recently finalized
+    /// batches while the breaker was closed.
+    window: VecDeque<(usize, bool)>,
+    events: usize,
+    failed: usize,
+}
+
+impl Breaker {
+    fn new(key: ComponentKey, options: &SinkFallbackOptions) -> Self {
+        Self {
+            key,
+            state: State::Closed,
+            failure_ratio: options.failure_ratio,
+            window_events: options.window_events.get(),
+            open_for: Duration::from_secs(options.open_secs.get()),
+            window: VecDeque::new(),
+            events: 0,
+            failed: 0,
+        }
+    }
+
+    fn record(&mut self, count: usize, probe: bool, failed: bool) {
+        match self.state {
+            State::HalfOpen if probe => {
+                if failed {
+                    self.open();
+                } else {
+                    self.transition(State::Closed);
+                }
+            }
+            State::Closed if !probe => {
+                self.window.push_back((count, failed));
+                self.events += count;
+                if failed {
+                    self.failed += count;
+                }
+                // Keep just enough of the most recent batches to cover the window.
+                while let Some(&(oldest, oldest_failed)) = self.window.front() {
+                    if self.events - oldest < self.window_events {
+                        break;
+                    }
+                    self.window.pop_front();
+                    self.events -= oldest;
+                    if oldest_failed {
+                        self.failed -= oldest;
+                    }
+                }
+
+                if self.events >= self.window_events
+                    && self.failed as f64 >= self.failure_ratio * self.events as f64
+                {
+                    self.open();
+                }
+            }
+            // Batches handed to the sink before the breaker last changed state no longer say
+            // anything about whether it should change again.
+            _ => {}
+        }
+    }
+
+    fn open(&mut self) {
+        self.transition(State::Open {
+            until: Instant::now() + self.open_for,
+        });
+    }
+
+    fn transition(&mut self, state: State) {
+        self.state = state;
+        self.window.clear();
+        self.events = 0;
+        self.failed = 0;
+        emit!(CircuitBreakerStateChanged {
+            state: state.public(),
+        });
+        health::circuit_breaker_changed(&self.key, state.public());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        num::{NonZeroU64, NonZeroUsize},
+        sync::atomic::Ordering,
+    };
+
+    use vector_core::{
+        buffers::{topology::builder::TopologyBuilder, WhenFull},
+        event::{EventStatus, LogEvent},
+    };
+
+    use super::*;
+    use crate::{
+        config::ComponentKey,
+        event::{Event, Value},
+        topology::{builder::TOPOLOGY_BUFFER_SIZE, fanout::ControlMessage},
+    };
+
+    fn options() -> SinkFallbackOptions {
+        SinkFallbackOptions {
+            enabled: true,
+            failure_ratio: 0.5,
+            window_events: NonZeroUsize::new(2).unwrap(),
+            max_latency_secs: None,
+            open_secs: NonZeroU64::new(30).unwrap(),
+        }
+    }
+
+    #[test]
+    fn opens_once_the_window_fails() {
+        let mut breaker = Breaker::new(ComponentKey::from("out"), &options());
+
+        breaker.record(1, false, true);
+        assert_eq!(breaker.state, State::Closed);
+        breaker.record(1, false, false);
+        assert!(matches!(breaker.state, State::Open { .. }));
+
+        // A failed probe opens the breaker again, while a delivered one closes it.
+        breaker.transition(State::HalfOpen);
+        breaker.record(1, true, true);
+        assert!(matches!(breaker.state, State::Open { .. }));
+        breaker.transition(State::HalfOpen);
+        breaker.record(1, true, false);
+        assert_eq!(breaker.state, State::Closed);
+    }
+
+    #[test]
+    fn only_counts_the_most_recent_events() {
+        let mut breaker = Breaker::new(
+            ComponentKey::from("out"),
+            &SinkFallbackOptions {
+                failure_ratio: 0.75,
+                window_events: NonZeroUsize::new(4).unwrap(),
+                ..options()
+            },
+        );
+
+        breaker.record(2, false, true);
+        for _ in 0..4 {
+            breaker.record(1, false, false);
+        }
+        assert_eq!((breaker.events, breaker.failed), (4, 0));
+
+        breaker.record(1, false, true);
+        breaker.record(1, false, true);
+        assert_eq!(breaker.state, State::Closed);
+        breaker.record(1, false, true);
+        assert!(matches!(breaker.state, State::Open { .. }));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reroutes_events_while_open() {
+        let (fanout, control) = Fanout::new();
+        let (tx, mut rx) =
+            TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::Block).await;
+        control
+            .send(ControlMessage::Add(ComponentKey::from("archive"), tx))
+            .unwrap();
+
+        let (acker, acked) = Acker::basic();
+        let (breaker, task) =
+            CircuitBreaker::new(ComponentKey::from("out"), &options(), fanout, acker);
+        let state = Arc::clone(&breaker.breaker);
+        let task = tokio::spawn(task);
+
+        let (input_tx, input_rx) = futures::channel::mpsc::unbounded();
+        let mut routed = Box::pin(breaker.route(input_rx));
+
+        // Both events handed to the sink fail, which opens the breaker.
+        for message in ["first", "second"] {
+            input_tx
+                .unbounded_send(LogEvent::from(message).into())
+                .unwrap();
+            let (mut events, batch) = routed.next().await.unwrap();
+            attach(&mut events, &batch);
+            drop(batch);
+            for mut event in events.into_events() {
+                event
+                    .metadata_mut()
+                    .take_finalizers()
+                    .update_status(EventStatus::Errored);
+            }
+        }
+        while state.lock().unwrap().state == State::Closed {
+            tokio::task::yield_now().await;
+        }
+
+        input_tx
+            .unbounded_send(LogEvent::from("third").into())
+            .unwrap();
+        drop(input_tx);
+        assert!(routed.next().await.is_none());
+
+        let rerouted = rx.next().await.unwrap().into_events().collect::<Vec<_>>();
+        assert!(
+            matches!(&rerouted[..], [Event::Log(log)] if log["message"] == Value::from("third"))
+        );
+        assert_eq!(acked.load(Ordering::Relaxed), 1);
+
+        drop(routed);
+        task.await.unwrap();
+    }
+}
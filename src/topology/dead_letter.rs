@@ -0,0 +1,265 @@
+//! Routing of events that a sink failed to deliver to its dead letter output.
+//!
+//! Sinks have no knowledge of the dead letter output themselves. Instead, every event handed to a
+//! sink with a dead letter output is tracked with a batch notifier of its own, and a copy of the
+//! event is held onto until the sink finalizes it. If the sink reports the event as rejected or
+//! errored, the copy is annotated with the reason and the sink that failed to deliver it, and is
+//! then sent to the dead letter output.
+//!
+//! The copy carries the original finalizers of the event, so acknowledgements flow back to the
+//! source only once the event has either been delivered by the sink, or been handled by whatever
+//! consumes the dead letter output.
+
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
+use tokio::sync::mpsc;
+use vector_core::{
+    config::log_schema,
+    event::{array::events_into_arrays, BatchNotifier, BatchStatus, BatchStatusReceiver},
+    internal_event::EventsSent,
+    ByteSizeOf,
+};
+
+use super::fanout::Fanout;
+use crate::{
+    config::{ComponentKey, DEAD_LETTER_OUTPUT},
+    event::{Event, EventArray, EventContainer},
+    internal_events::DeadLetterEventsRouted,
+};
+
+/// Tracks the events handed to a sink so that any it fails to deliver can be routed to its dead
+/// letter output.
+#[derive(Clone, Debug)]
+pub(super) struct DeadLetterTracker {
+    // The number of pending events is naturally bounded by the number of events the sink has in
+    // flight, as every entry is resolved once the sink finalizes or drops the event.
+    tx: mpsc::UnboundedSender<PendingEvent>,
+}
+
+struct PendingEvent {
+    event: Event,
+    receiver: BatchStatusReceiver,
+}
+
+impl DeadLetterTracker {
+    /// Creates a new tracker for the given sink, along with the task that routes failed events to
+    /// the given fanout.
+    ///
+    /// The task finishes once all clones of the tracker have been dropped and every tracked event
+    /// has been finalized.
+    pub(super) fn new(
+        key: ComponentKey,
+        typetag: &'static str,
+        fanout: Fanout,
+    ) -> (Self, BoxFuture<'static, ()>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = run_dead_letter(rx, fanout, key, typetag).boxed();
+
+        (Self { tx }, task)
+    }
+
+    /// Starts tracking the given events, returning them ready to be handed to the sink.
+    pub(super) fn track(&self, events: EventArray) -> impl Iterator<Item = EventArray> {
+        let events = events
+            .into_events()
+            .map(|event| self.track_event(event))
+            .collect::<Vec<_>>();
+
+        events_into_arrays(events, None)
+    }
+
+    fn track_event(&self, mut event: Event) -> Event {
+        // The copy we hold onto takes over the original finalizers, while the event given to the
+        // sink gets a fresh notifier so that we can see how it was finalized.
+        let finalizers = event.metadata_mut().take_finalizers();
+        let mut copy = event.clone();
+        copy.metadata_mut().merge_finalizers(finalizers);
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        event.add_batch_notifier(batch);
+
+        // The routing task only goes away during shutdown, in which case the copy is dropped
+        // here, finalizing the original event just as it would be without a dead letter output.
+        let _ = self.tx.send(PendingEvent {
+            event: copy,
+            receiver,
+        });
+
+        event
+    }
+}
+
+async fn run_dead_letter(
+    mut rx: mpsc::UnboundedReceiver<PendingEvent>,
+    mut fanout: Fanout,
+    key: ComponentKey,
+    typetag: &'static str,
+) {
+    let mut pending = FuturesUnordered::new();
+    let mut tracker_closed = false;
+
+    loop {
+        tokio::select! {
+            maybe_pending = rx.recv(), if !tracker_closed => match maybe_pending {
+                Some(PendingEvent { event, receiver }) => {
+                    pending.push(receiver.map(move |status| (status, event)));
+                }
+                None => tracker_closed = true,
+            },
+
+            Some((status, event)) = pending.next(), if !pending.is_empty() => {
+                // Pick up any other events that were finalized at the same time, so that we can
+                // send them onwards together.
+                let mut finalized = vec![(status, event)];
+                while let Some(Some(next)) = pending.next().now_or_never() {
+                    finalized.push(next);
+                }
+
+                let failed = finalized
+                    .into_iter()
+                    .filter_map(|(status, mut event)| {
+                        let reason = match status {
+                            // Dropping the copy finalizes the original event as delivered.
+                            BatchStatus::Delivered => return None,
+                            BatchStatus::Errored => "errored",
+                            BatchStatus::Rejected => "rejected",
+                        };
+                        annotate_dead_letter(&mut event, reason, &key, typetag);
+                        Some(event)
+                    })
+                    .collect::<Vec<_>>();
+
+                if !failed.is_empty() {
+                    emit!(DeadLetterEventsRouted {
+                        count: failed.len(),
+                    });
+                }
+                for events in events_into_arrays(failed, None) {
+                    emit!(EventsSent {
+                        count: events.len(),
+                        byte_size: events.size_of(),
+                        output: Some(DEAD_LETTER_OUTPUT),
+                    });
+                    fanout.send(events).await;
+                }
+            }
+
+            else => break,
+        }
+    }
+
+    debug!("Dead letter routing finished.");
+}
+
+fn annotate_dead_letter(event: &mut Event, reason: &str, key: &ComponentKey, typetag: &str) {
+    let message = match reason {
+        "rejected" => "Event was rejected by the sink.",
+        _ => "Event could not be delivered by the sink.",
+    };
+
+    match event {
+        Event::Log(ref mut log) => {
+            log.insert(
+                log_schema().metadata_key(),
+                annotation(reason, message, key, typetag),
+            );
+        }
+        Event::Metric(ref mut metric) => {
+            let m = log_schema().metadata_key();
+            metric.insert_tag(format!("{}.dropped.reason", m), reason.into());
+            metric.insert_tag(format!("{}.dropped.component_id", m), key.to_string());
+            metric.insert_tag(format!("{}.dropped.component_type", m), typetag.into());
+            metric.insert_tag(format!("{}.dropped.component_kind", m), "sink".into());
+        }
+        Event::Trace(ref mut trace) => {
+            trace.insert(
+                log_schema().metadata_key(),
+                annotation(reason, message, key, typetag),
+            );
+        }
+    }
+}
+
+// This mirrors the annotation the `remap` transform adds to the events it sends to its `dropped`
+// output, so that events failed by transforms and sinks can be handled the same way downstream.
+fn annotation(reason: &str, message: &str, key: &ComponentKey, typetag: &str) -> serde_json::Value {
+    serde_json::json!({
+        "dropped": {
+            "reason": reason,
+            "message": message,
+            "component_id": key.to_string(),
+            "component_type": typetag,
+            "component_kind": "sink",
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::{
+        buffers::{topology::builder::TopologyBuilder, WhenFull},
+        event::{EventStatus, LogEvent},
+    };
+
+    use super::*;
+    use crate::{
+        event::Value,
+        topology::{builder::TOPOLOGY_BUFFER_SIZE, fanout::ControlMessage},
+    };
+
+    #[tokio::test]
+    async fn routes_rejected_events_only() {
+        let (fanout, control) = Fanout::new();
+        let (tx, mut rx) =
+            TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::Block).await;
+        control
+            .send(ControlMessage::Add(ComponentKey::from("archive"), tx))
+            .unwrap();
+
+        let (tracker, task) = DeadLetterTracker::new(ComponentKey::from("out"), "test", fanout);
+        let task = tokio::spawn(task);
+
+        let (source_batch, source_receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            LogEvent::from("delivered").with_batch_notifier(&source_batch),
+            LogEvent::from("rejected").with_batch_notifier(&source_batch),
+        ];
+        drop(source_batch);
+
+        let tracked = tracker
+            .track(EventArray::from(events))
+            .flat_map(EventContainer::into_events)
+            .collect::<Vec<_>>();
+        drop(tracker);
+
+        for mut event in tracked {
+            let status = if event.as_log()["message"] == Value::from("rejected") {
+                EventStatus::Rejected
+            } else {
+                EventStatus::Delivered
+            };
+            event.metadata_mut().take_finalizers().update_status(status);
+        }
+
+        let routed = rx.next().await.expect("should route the rejected event");
+        let routed = routed.into_events().collect::<Vec<_>>();
+        assert_eq!(routed.len(), 1);
+
+        let log = routed[0].as_log();
+        assert_eq!(log["message"], Value::from("rejected"));
+        let metadata_key = log_schema().metadata_key();
+        assert_eq!(
+            log[format!("{}.dropped.reason", metadata_key).as_str()],
+            Value::from("rejected")
+        );
+        assert_eq!(
+            log[format!("{}.dropped.component_id", metadata_key).as_str()],
+            Value::from("out")
+        );
+
+        // The routed event still holds on to the original finalizers, so the source only hears
+        // back once it has been handled by the dead letter consumer.
+        drop(routed);
+        task.await.unwrap();
+        assert_eq!(source_receiver.await, BatchStatus::Delivered);
+    }
+}
@@ -0,0 +1,86 @@
+use chrono::Utc;
+use vector_buffers::Acker;
+use vector_core::event::array::events_into_arrays;
+
+use crate::{
+    event::{EventArray, EventContainer},
+    internal_events::ExpiredEventsDiscarded,
+};
+
+/// Discards the events that expired before the sink could deliver them, returning the rest, if
+/// any.
+///
+/// This applies to events as they are read from the buffer in front of the sink, so that events
+/// that expired while waiting in the buffer, such as during an outage of the sink's downstream,
+/// are never delivered late. Expired events are acknowledged with the given `Acker`, the same as
+/// if the sink had processed them.
+pub(super) fn discard_expired(events: EventArray, acker: &Acker) -> Option<EventArray> {
+    let now = Utc::now();
+    if !events
+        .iter_events()
+        .any(|event| event.metadata().is_expired_at(now))
+    {
+        return Some(events);
+    }
+
+    let (expired, events): (Vec<_>, Vec<_>) = events
+        .into_events()
+        .partition(|event| event.metadata().is_expired_at(now));
+    emit!(ExpiredEventsDiscarded {
+        count: expired.len()
+    });
+    acker.ack(expired.len());
+
+    events_into_arrays(events, None).next()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use chrono::Duration;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn logs(ttls: &[Option<Duration>]) -> EventArray {
+        EventArray::Logs(
+            ttls.iter()
+                .map(|ttl| {
+                    let mut log = LogEvent::from("message");
+                    let expires_at = ttl.map(|ttl| Utc::now() + ttl);
+                    log.metadata_mut().set_expires_at(expires_at);
+                    log
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn keeps_events_that_have_not_expired() {
+        let (acker, acked) = Acker::basic();
+        let events = logs(&[None, Some(Duration::minutes(5))]);
+
+        let kept = discard_expired(events, &acker).expect("events should be kept");
+        assert_eq!(kept.len(), 2);
+        assert_eq!(acked.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn discards_and_acknowledges_expired_events() {
+        let (acker, acked) = Acker::basic();
+        let events = logs(&[
+            Some(Duration::minutes(-5)),
+            None,
+            Some(Duration::minutes(-1)),
+        ]);
+
+        let kept = discard_expired(events, &acker).expect("an event should be kept");
+        assert_eq!(kept.len(), 1);
+        assert_eq!(acked.load(Ordering::Relaxed), 2);
+
+        let events = logs(&[Some(Duration::minutes(-5))]);
+        assert!(discard_expired(events, &acker).is_none());
+        assert_eq!(acked.load(Ordering::Relaxed), 3);
+    }
+}
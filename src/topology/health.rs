@@ -0,0 +1,449 @@
+//! Health of the components of the running topology.
+//!
+//! The state of each component is derived from what is known about it: whether it has been
+//! spawned, how its healthcheck went, the state of its circuit breaker, and whether its task
+//! failed. Components are also degraded by the failure of any component upstream of them, so that
+//! a sink that can't receive events is reported as such even though it is healthy itself.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::RwLock,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use super::CircuitBreakerState;
+use crate::{
+    config::{api::HealthPolicy, ComponentKey, ConfigDiff},
+    internal_events::ComponentHealthChanged,
+};
+
+/// The state of a component, from the healthiest to the least healthy.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthState {
+    /// The component is running normally.
+    Healthy,
+    /// The component has been built but isn't running yet, or its healthcheck hasn't completed.
+    Starting,
+    /// The component is running but not fully working, such as a sink whose circuit breaker is
+    /// probing whether it has recovered, or a component downstream of a failed one.
+    Degraded,
+    /// The component isn't working, such as a sink that failed its healthcheck.
+    Failed,
+}
+
+impl HealthState {
+    pub const ALL: [Self; 4] = [Self::Healthy, Self::Starting, Self::Degraded, Self::Failed];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Healthy => "healthy",
+            Self::Starting => "starting",
+            Self::Degraded => "degraded",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// The value of the `component_health_state` gauge for this state.
+    pub const fn as_gauge(self) -> f64 {
+        match self {
+            Self::Healthy => 0.0,
+            Self::Starting => 1.0,
+            Self::Degraded => 2.0,
+            Self::Failed => 3.0,
+        }
+    }
+}
+
+impl fmt::Display for HealthState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The kind of a component.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentKind {
+    Source,
+    Transform,
+    Sink,
+}
+
+impl ComponentKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Source => "source",
+            Self::Transform => "transform",
+            Self::Sink => "sink",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Healthcheck {
+    Pending,
+    Passed,
+    Failed,
+}
+
+/// What is known about a component, and the state derived from it.
+struct Component {
+    kind: ComponentKind,
+    typetag: &'static str,
+    inputs: Vec<ComponentKey>,
+    spawned: bool,
+    healthcheck: Healthcheck,
+    circuit_breaker: CircuitBreakerState,
+    task_failed: bool,
+    state: HealthState,
+    reason: Option<String>,
+    /// When the component last entered each state or a less healthy one, if it's in it.
+    at_least_since: [Option<DateTime<Utc>>; 4],
+}
+
+impl Component {
+    /// The state of the component itself, regardless of the components upstream of it.
+    fn own_state(&self) -> (HealthState, Option<String>) {
+        if self.task_failed {
+            (
+                HealthState::Failed,
+                Some("The component stopped with an error.".to_owned()),
+            )
+        } else if self.healthcheck == Healthcheck::Failed {
+            (
+                HealthState::Failed,
+                Some("The healthcheck failed.".to_owned()),
+            )
+        } else if self.circuit_breaker == CircuitBreakerState::Open {
+            (
+                HealthState::Failed,
+                Some("The circuit breaker is open.".to_owned()),
+            )
+        } else if !self.spawned || self.healthcheck == Healthcheck::Pending {
+            (HealthState::Starting, None)
+        } else if self.circuit_breaker == CircuitBreakerState::HalfOpen {
+            (
+                HealthState::Degraded,
+                Some("The circuit breaker is half open.".to_owned()),
+            )
+        } else {
+            (HealthState::Healthy, None)
+        }
+    }
+
+    fn since(&self) -> DateTime<Utc> {
+        self.at_least_since[self.state as usize].unwrap_or_else(Utc::now)
+    }
+}
+
+static COMPONENTS: Lazy<RwLock<HashMap<ComponentKey, Component>>> = Lazy::new(Default::default);
+
+const INVARIANT: &str = "component health lock poisoned";
+
+/// The health of a component, as reported by the API.
+#[derive(Clone, Debug)]
+pub struct ComponentHealth {
+    pub key: ComponentKey,
+    pub kind: ComponentKind,
+    pub state: HealthState,
+    pub reason: Option<String>,
+    pub since: DateTime<Utc>,
+}
+
+/// Starts tracking the health of a component that is being built, replacing what was known about
+/// any previous version of it. Sinks stay `starting` until their healthcheck completes, if it is to
+/// be run.
+pub(super) fn starting(
+    key: &ComponentKey,
+    kind: ComponentKind,
+    typetag: &'static str,
+    inputs: Vec<ComponentKey>,
+    awaiting_healthcheck: bool,
+) {
+    update(|components| {
+        components.insert(
+            key.clone(),
+            Component {
+                kind,
+                typetag,
+                inputs,
+                spawned: false,
+                healthcheck: if awaiting_healthcheck {
+                    Healthcheck::Pending
+                } else {
+                    Healthcheck::Passed
+                },
+                circuit_breaker: CircuitBreakerState::Closed,
+                task_failed: false,
+                state: HealthState::Starting,
+                reason: None,
+                at_least_since: [None; 4],
+            },
+        );
+    });
+}
+
+pub(super) fn spawned(key: &ComponentKey) {
+    update_component(key, |component| component.spawned = true);
+}
+
+pub(super) fn healthcheck_completed(key: &ComponentKey, passed: bool) {
+    update_component(key, |component| {
+        component.healthcheck = if passed {
+            Healthcheck::Passed
+        } else {
+            Healthcheck::Failed
+        }
+    });
+}
+
+pub(super) fn circuit_breaker_changed(key: &ComponentKey, state: CircuitBreakerState) {
+    update_component(key, |component| component.circuit_breaker = state);
+}
+
+pub(super) fn task_failed(key: &ComponentKey) {
+    update_component(key, |component| component.task_failed = true);
+}
+
+/// Stops tracking the health of the components that the diff removes.
+pub(super) fn remove(diff: &ConfigDiff) {
+    update(|components| {
+        for key in diff
+            .sources
+            .to_remove
+            .iter()
+            .chain(&diff.transforms.to_remove)
+            .chain(&diff.sinks.to_remove)
+        {
+            components.remove(key);
+        }
+    });
+}
+
+/// Returns the health of every component.
+pub fn components() -> Vec<ComponentHealth> {
+    COMPONENTS
+        .read()
+        .expect(INVARIANT)
+        .iter()
+        .map(|(key, component)| ComponentHealth {
+            key: key.clone(),
+            kind: component.kind,
+            state: component.state,
+            reason: component.reason.clone(),
+            since: component.since(),
+        })
+        .collect()
+}
+
+/// Returns the health of a component, if it is part of the topology.
+pub fn component(key: &ComponentKey) -> Option<ComponentHealth> {
+    let components = COMPONENTS.read().expect(INVARIANT);
+    components.get(key).map(|component| ComponentHealth {
+        key: key.clone(),
+        kind: component.kind,
+        state: component.state,
+        reason: component.reason.clone(),
+        since: component.since(),
+    })
+}
+
+/// Checks the health of the components against the given policies, returning why Vector isn't
+/// ready if any of them is violated.
+pub fn check_readiness(policies: &[HealthPolicy]) -> Result<(), Vec<String>> {
+    let now = Utc::now();
+    let components = COMPONENTS.read().expect(INVARIANT);
+    let mut violations = Vec::new();
+    for policy in policies {
+        let for_duration = Duration::seconds(policy.for_secs as i64);
+        let mut keys: Vec<_> = components
+            .iter()
+            .filter(|(_, component)| {
+                policy
+                    .component_kind
+                    .map_or(true, |kind| kind == component.kind)
+            })
+            .filter(|(_, component)| {
+                component.at_least_since[policy.state as usize]
+                    .map_or(false, |since| now - since >= for_duration)
+            })
+            .map(|(key, _)| key.id())
+            .collect();
+        if !keys.is_empty() {
+            keys.sort_unstable();
+            violations.push(format!(
+                "Components {} have been {} for at least {} seconds.",
+                keys.join(", "),
+                policy.state,
+                policy.for_secs
+            ));
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+fn update_component(key: &ComponentKey, f: impl FnOnce(&mut Component)) {
+    update(|components| {
+        if let Some(component) = components.get_mut(key) {
+            f(component);
+        }
+    });
+}
+
+/// Applies a change to the components, and derives their states again.
+fn update(f: impl FnOnce(&mut HashMap<ComponentKey, Component>)) {
+    let mut components = COMPONENTS.write().expect(INVARIANT);
+    f(&mut components);
+
+    let mut states = HashMap::with_capacity(components.len());
+    let keys: Vec<_> = components.keys().cloned().collect();
+    for key in &keys {
+        derive_state(key, &components, &mut states, &mut HashSet::new());
+    }
+
+    let now = Utc::now();
+    for (key, component) in components.iter_mut() {
+        let (state, reason) = match states.remove(key) {
+            Some(derived) => derived,
+            None => continue,
+        };
+        let changed = state != component.state || component.at_least_since[0].is_none();
+        component.reason = reason;
+        if !changed {
+            continue;
+        }
+
+        component.state = state;
+        for level in HealthState::ALL {
+            let since = &mut component.at_least_since[level as usize];
+            if level > state {
+                *since = None;
+            } else if since.is_none() {
+                *since = Some(now);
+            }
+        }
+        // The span has no parent, so that the labels of the gauge are those of the component even
+        // when its state changes from within another component.
+        let span = error_span!(
+            parent: None,
+            "health",
+            component_kind = component.kind.as_str(),
+            component_id = %key.id(),
+            component_type = component.typetag,
+            // maintained for compatibility
+            component_name = %key.id(),
+        );
+        let _enter = span.enter();
+        emit!(ComponentHealthChanged {
+            state,
+            reason: component.reason.as_deref(),
+        });
+    }
+}
+
+/// Derives the state of a component from its own state and the states of the components upstream
+/// of it.
+fn derive_state(
+    key: &ComponentKey,
+    components: &HashMap<ComponentKey, Component>,
+    states: &mut HashMap<ComponentKey, (HealthState, Option<String>)>,
+    visiting: &mut HashSet<ComponentKey>,
+) -> HealthState {
+    if let Some((state, _)) = states.get(key) {
+        return *state;
+    }
+    let component = match components.get(key) {
+        Some(component) => component,
+        None => return HealthState::Healthy,
+    };
+    // The topology can't have cycles, but a half reloaded one might look like it does.
+    if !visiting.insert(key.clone()) {
+        return HealthState::Healthy;
+    }
+
+    let (mut state, mut reason) = component.own_state();
+    if state < HealthState::Degraded {
+        let failed_input = component
+            .inputs
+            .iter()
+            .find(|input| derive_state(input, components, states, visiting) == HealthState::Failed);
+        if let Some(input) = failed_input {
+            state = HealthState::Degraded;
+            reason = Some(format!("The upstream component {} has failed.", input));
+        }
+    }
+
+    visiting.remove(key);
+    states.insert(key.clone(), (state, reason));
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(key: &ComponentKey) -> HealthState {
+        component(key).unwrap().state
+    }
+
+    #[test]
+    fn degrades_components_downstream_of_failed_ones() {
+        // The registry is shared by every topology of the process, so the keys are unique.
+        let source = ComponentKey::from("health_test_source");
+        let transform = ComponentKey::from("health_test_transform");
+        let sink = ComponentKey::from("health_test_sink");
+        starting(&source, ComponentKind::Source, "demo_logs", vec![], false);
+        starting(
+            &transform,
+            ComponentKind::Transform,
+            "remap",
+            vec![source.clone()],
+            false,
+        );
+        starting(
+            &sink,
+            ComponentKind::Sink,
+            "http",
+            vec![transform.clone()],
+            true,
+        );
+        for key in [&source, &transform, &sink] {
+            spawned(key);
+        }
+        assert_eq!(state(&transform), HealthState::Healthy);
+        assert_eq!(state(&sink), HealthState::Starting);
+
+        healthcheck_completed(&sink, true);
+        assert_eq!(state(&sink), HealthState::Healthy);
+
+        task_failed(&source);
+        assert_eq!(state(&source), HealthState::Failed);
+        assert_eq!(state(&transform), HealthState::Degraded);
+        // Only the failure of a direct input degrades a component.
+        assert_eq!(state(&sink), HealthState::Healthy);
+
+        let policy = HealthPolicy {
+            component_kind: Some(ComponentKind::Source),
+            state: HealthState::Failed,
+            for_secs: 0,
+        };
+        let violations = check_readiness(&[policy.clone()]).unwrap_err();
+        assert!(violations[0].contains("health_test_source"));
+
+        let policy = HealthPolicy {
+            for_secs: 3600,
+            ..policy
+        };
+        assert!(check_readiness(&[policy]).is_ok());
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use once_cell::sync::Lazy;
+use tokio::sync::Notify;
+use vector_core::{
+    config::{MemoryLimitConfig, ShedPolicy},
+    event::{array::events_into_arrays, EventPriority},
+    ByteSizeOf,
+};
+
+use super::tenancy::hold_until_finalized;
+use crate::{
+    event::{Event, EventArray, EventContainer, EventStatus},
+    internal_events::{ComponentMemoryBytes, MemoryLimitEventsShed, MemoryLimitThrottled},
+};
+
+/// The memory budget shared by the sources of every topology built with the same limits, so that
+/// the events still in flight from before a reload keep counting against it.
+static GLOBAL: Lazy<Mutex<Option<Arc<MemoryBudget>>>> = Lazy::new(Default::default);
+
+/// Gets the memory budget for the given limits, if any are set.
+pub(super) fn global(config: &MemoryLimitConfig) -> Option<Arc<MemoryBudget>> {
+    if !config.is_enabled() {
+        return None;
+    }
+
+    let mut global = GLOBAL.lock().expect("memory budget lock poisoned");
+    match global.as_ref() {
+        Some(budget) if budget.config == *config => Some(Arc::clone(budget)),
+        _ => {
+            let budget = Arc::new(MemoryBudget::new(config.clone()));
+            *global = Some(Arc::clone(&budget));
+            Some(budget)
+        }
+    }
+}
+
+/// Enforces a budget for the memory taken up by the events in flight.
+///
+/// Events reserve their in-memory size from the budget, and from the account of the component
+/// that admitted them, when they're admitted, and release it once they're finalized. Events are
+/// admitted while the memory in use is below the soft limit, wait for other events to be finalized
+/// while it is between the soft and hard limits, and are shed by the policy above the hard limit.
+/// A group of events is admitted as a whole once the memory in use is below the soft limit, even
+/// if it takes the memory in use above it.
+pub struct MemoryBudget {
+    config: MemoryLimitConfig,
+    used: AtomicUsize,
+    released: Notify,
+}
+
+/// The memory taken up by the events in flight that were admitted by one component.
+#[derive(Debug, Default)]
+pub struct MemoryAccount {
+    used: AtomicUsize,
+}
+
+impl MemoryAccount {
+    fn add(&self, bytes: usize) {
+        let used = self.used.fetch_add(bytes, Ordering::AcqRel) + bytes;
+        emit!(ComponentMemoryBytes { bytes: used });
+    }
+
+    fn sub(&self, bytes: usize) {
+        let used = self.used.fetch_sub(bytes, Ordering::AcqRel) - bytes;
+        emit!(ComponentMemoryBytes { bytes: used });
+    }
+}
+
+/// Memory reserved by events, released when they're all finalized.
+struct Reservation {
+    budget: Arc<MemoryBudget>,
+    account: Arc<MemoryAccount>,
+    bytes: usize,
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        self.budget.used.fetch_sub(self.bytes, Ordering::AcqRel);
+        self.account.sub(self.bytes);
+        self.budget.released.notify_waiters();
+    }
+}
+
+enum Limit {
+    Below,
+    Soft,
+    Hard,
+}
+
+impl MemoryBudget {
+    pub fn new(config: MemoryLimitConfig) -> Self {
+        Self {
+            config,
+            used: AtomicUsize::new(0),
+            released: Notify::new(),
+        }
+    }
+
+    /// Admits the given events against the budget, charging them to the given account, either
+    /// waiting until enough memory is released or shedding them, depending on how much memory is
+    /// in use.
+    ///
+    /// Returns the events that were admitted, if any.
+    pub async fn admit(
+        self: &Arc<Self>,
+        account: &Arc<MemoryAccount>,
+        mut events: EventArray,
+    ) -> Vec<EventArray> {
+        let mut waiting_since = None;
+        loop {
+            // Registered before the memory in use is looked at, so that memory released in between
+            // isn't missed.
+            let released = self.released.notified();
+            match self.limit() {
+                Limit::Below => break,
+                Limit::Hard if self.config.shed == ShedPolicy::All => {
+                    emit!(MemoryLimitEventsShed {
+                        count: events.len()
+                    });
+                    reject(events.into_events());
+                    return Vec::new();
+                }
+                Limit::Hard => match shed_below_high_priority(events) {
+                    Some(high) => events = high,
+                    None => return Vec::new(),
+                },
+                Limit::Soft => {}
+            }
+            waiting_since.get_or_insert_with(Instant::now);
+            released.await;
+        }
+        if let Some(since) = waiting_since {
+            emit!(MemoryLimitThrottled {
+                wait: since.elapsed()
+            });
+        }
+
+        let bytes = events.size_of();
+        self.used.fetch_add(bytes, Ordering::AcqRel);
+        account.add(bytes);
+        let reservation = Reservation {
+            budget: Arc::clone(self),
+            account: Arc::clone(account),
+            bytes,
+        };
+        hold_until_finalized(&mut events, reservation);
+        vec![events]
+    }
+
+    fn limit(&self) -> Limit {
+        let used = self.used.load(Ordering::Acquire);
+        let over = |limit: Option<NonZeroUsize>| limit.map_or(false, |l| used >= l.get());
+        if over(self.config.hard_limit_bytes) {
+            Limit::Hard
+        } else if over(self.config.soft_limit_bytes) {
+            Limit::Soft
+        } else {
+            Limit::Below
+        }
+    }
+}
+
+/// Sheds the events below high priority, returning the rest, if any.
+fn shed_below_high_priority(events: EventArray) -> Option<EventArray> {
+    let (high, shed): (Vec<_>, Vec<_>) = events
+        .into_events()
+        .partition(|event| event.metadata().priority() == EventPriority::High);
+    if !shed.is_empty() {
+        emit!(MemoryLimitEventsShed { count: shed.len() });
+    }
+    reject(shed);
+    events_into_arrays(high, None).next()
+}
+
+/// Reports the given shed events as rejected, as dropping their finalizers as they are would
+/// report them as delivered.
+fn reject(events: impl IntoIterator<Item = Event>) {
+    for mut event in events {
+        event
+            .metadata_mut()
+            .take_finalizers()
+            .update_status(EventStatus::Rejected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    fn logs(priorities: &[EventPriority]) -> EventArray {
+        EventArray::Logs(
+            priorities
+                .iter()
+                .map(|priority| {
+                    let mut log = LogEvent::from("message");
+                    log.metadata_mut().set_priority(*priority);
+                    log
+                })
+                .collect(),
+        )
+    }
+
+    fn budget(soft: usize, hard: usize, shed: ShedPolicy) -> Arc<MemoryBudget> {
+        Arc::new(MemoryBudget::new(MemoryLimitConfig {
+            soft_limit_bytes: NonZeroUsize::new(soft),
+            hard_limit_bytes: NonZeroUsize::new(hard),
+            shed,
+        }))
+    }
+
+    fn count(arrays: &[EventArray]) -> usize {
+        arrays.iter().map(|array| array.len()).sum()
+    }
+
+    #[tokio::test]
+    async fn waits_above_the_soft_limit_until_events_are_finalized() {
+        let size = logs(&[EventPriority::Normal]).size_of();
+        let budget = budget(size, 0, ShedPolicy::All);
+        let account = Arc::new(MemoryAccount::default());
+
+        let in_flight = budget.admit(&account, logs(&[EventPriority::Normal])).await;
+        assert_eq!(account.used.load(Ordering::Acquire), size);
+
+        let waiting = budget.admit(&account, logs(&[EventPriority::Normal]));
+        tokio::pin!(waiting);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), &mut waiting)
+                .await
+                .is_err()
+        );
+
+        drop(in_flight);
+        assert_eq!(count(&waiting.await), 1);
+    }
+
+    #[tokio::test]
+    async fn sheds_above_the_hard_limit_by_priority() {
+        let size = logs(&[EventPriority::Normal]).size_of();
+        let budget = budget(0, size, ShedPolicy::Priority);
+        let account = Arc::new(MemoryAccount::default());
+
+        let _in_flight = budget.admit(&account, logs(&[EventPriority::Normal])).await;
+        let admitted = budget.admit(&account, logs(&[EventPriority::Low, EventPriority::Normal]));
+        assert_eq!(count(&admitted.await), 0);
+
+        let all = budget(0, size, ShedPolicy::All);
+        let _in_flight = all.admit(&account, logs(&[EventPriority::Normal])).await;
+        let admitted = all.admit(&account, logs(&[EventPriority::High])).await;
+        assert_eq!(count(&admitted), 0);
+    }
+
+    #[tokio::test]
+    async fn reports_shed_events_as_rejected() {
+        let size = logs(&[EventPriority::Normal]).size_of();
+        let account = Arc::new(MemoryAccount::default());
+
+        for shed in [ShedPolicy::Priority, ShedPolicy::All] {
+            let budget = budget(0, size, shed);
+            let _in_flight = budget.admit(&account, logs(&[EventPriority::Normal])).await;
+
+            let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+            let events = EventArray::Logs(
+                [EventPriority::Low, EventPriority::Normal]
+                    .iter()
+                    .map(|priority| {
+                        let mut log = LogEvent::from("message").with_batch_notifier(&batch);
+                        log.metadata_mut().set_priority(*priority);
+                        log
+                    })
+                    .collect(),
+            );
+            drop(batch);
+            assert_eq!(count(&budget.admit(&account, events).await), 0);
+            assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+        }
+    }
+
+    #[tokio::test]
+    async fn releases_memory_once_events_are_finalized() {
+        let budget = budget(1, 0, ShedPolicy::All);
+        let account = Arc::new(MemoryAccount::default());
+
+        let in_flight = budget.admit(&account, logs(&[EventPriority::Normal])).await;
+        assert!(budget.used.load(Ordering::Acquire) > 0);
+        drop(in_flight);
+
+        // The memory is released by a task waiting for the events to be finalized.
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while budget.used.load(Ordering::Acquire) > 0 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(account.used.load(Ordering::Acquire), 0);
+    }
+}
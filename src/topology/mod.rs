@@ -8,11 +8,23 @@
 
 pub(super) use vector_core::fanout;
 
+mod admission;
 pub mod builder;
+mod circuit_breaker;
+mod dead_letter;
+mod expiry;
+pub mod health;
+mod memory;
+mod pools;
+mod prioritize;
 mod ready_arrays;
 mod running;
 mod schema;
+mod tags;
 mod task;
+#[cfg(feature = "telemetry")]
+mod telemetry;
+mod tenancy;
 
 #[cfg(test)]
 mod test;
@@ -23,7 +35,9 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+pub(crate) use circuit_breaker::CircuitBreakerState;
 use futures::{Future, FutureExt};
+pub(crate) use running::ReloadResult;
 pub(super) use running::RunningTopology;
 use tokio::sync::{mpsc, watch};
 use vector_buffers::{
@@ -40,6 +54,51 @@ use crate::{
     },
 };
 
+/// Batches are never traced in builds without the `telemetry` feature, so that they don't need the
+/// OpenTelemetry dependencies.
+#[cfg(not(feature = "telemetry"))]
+mod telemetry {
+    use std::sync::Arc;
+
+    use vector_core::config::TelemetryConfig;
+
+    use crate::{config::ComponentKey, event::EventArray};
+
+    pub(super) const fn global(_: &TelemetryConfig) -> Option<Arc<Telemetry>> {
+        None
+    }
+
+    pub enum Telemetry {}
+
+    impl Telemetry {
+        pub fn component(
+            self: &Arc<Self>,
+            _: &ComponentKey,
+            _: &'static str,
+            _: &'static str,
+        ) -> TracedComponent {
+            match **self {}
+        }
+    }
+
+    #[derive(Clone)]
+    pub enum TracedComponent {}
+
+    impl TracedComponent {
+        pub fn sample(&self, _: &mut EventArray) {
+            match *self {}
+        }
+
+        pub fn hand_over<'a>(&self, _: impl IntoIterator<Item = &'a mut EventArray>) {
+            match *self {}
+        }
+
+        pub fn track_delivery(&self, _: &mut EventArray) {
+            match *self {}
+        }
+    }
+}
+
 type TaskHandle = tokio::task::JoinHandle<Result<TaskOutput, ()>>;
 
 type BuiltBuffer = (
@@ -83,6 +142,16 @@ pub async fn start_validated(
 ) -> Option<(RunningTopology, mpsc::UnboundedReceiver<()>)> {
     let (abort_tx, abort_rx) = mpsc::unbounded_channel();
 
+    vector_core::metrics::set_max_label_values(config.global.internal_metrics.max_label_values);
+    value::json::set_simd(config.global.json_parsing.simd);
+    if config.global.json_parsing.simd && !value::json::simd_enabled() {
+        warn!("The `json_parsing.simd` option has no effect, as Vector was built without the `json-simd` feature.");
+    }
+    if let Err(error) = pools::init(&config.global.thread_pools) {
+        error!(message = "Could not start thread pools.", %error);
+        return None;
+    }
+
     let mut running_topology = RunningTopology::new(config, abort_tx);
 
     if !running_topology
@@ -125,6 +194,7 @@ pub(super) fn take_healthchecks(
 
 async fn handle_errors(
     task: impl Future<Output = Result<TaskOutput, ()>>,
+    key: ComponentKey,
     abort_tx: mpsc::UnboundedSender<()>,
 ) -> Result<TaskOutput, ()> {
     AssertUnwindSafe(task)
@@ -134,6 +204,7 @@ async fn handle_errors(
         .and_then(|res| res)
         .map_err(|_| {
             error!("An error occurred that Vector couldn't handle.");
+            health::task_failed(&key);
             let _ = abort_tx.send(());
         })
 }
@@ -0,0 +1,167 @@
+//! Dedicated runtimes for the tasks of sources, transforms and sinks, per the `thread_pools`
+//! global option, so that the CPU work of one class of components can't starve the others.
+//!
+//! The runtimes are built when the topology first starts and live as long as the process, since
+//! global options can't change on reload.
+
+use std::{collections::BTreeSet, future::Future};
+
+use once_cell::sync::OnceCell;
+use tokio::{runtime::Runtime, task::JoinHandle};
+use vector_core::config::{ThreadPoolConfig, ThreadPoolsConfig};
+
+use super::health::ComponentKind;
+use crate::spawn_named;
+
+static POOLS: OnceCell<Pools> = OnceCell::new();
+
+struct Pools {
+    config: ThreadPoolsConfig,
+    sources: Option<Runtime>,
+    transforms: Option<Runtime>,
+    sinks: Option<Runtime>,
+}
+
+/// Builds the runtimes of the configured pools, unless they were already built.
+pub(super) fn init(config: &ThreadPoolsConfig) -> crate::Result<()> {
+    if let Some(pools) = POOLS.get() {
+        if &pools.config != config {
+            warn!("Changes to the `thread_pools` option require a restart, keeping the existing thread pools.");
+        }
+        return Ok(());
+    }
+    if config == &ThreadPoolsConfig::default() {
+        return Ok(());
+    }
+
+    let pools = Pools {
+        config: config.clone(),
+        sources: build_runtime("source", config.sources.as_ref())?,
+        transforms: build_runtime("transform", config.transforms.as_ref())?,
+        sinks: build_runtime("sink", config.sinks.as_ref())?,
+    };
+    // Another topology can't be starting concurrently, so a lost race only drops equal pools.
+    let _ = POOLS.set(pools);
+    Ok(())
+}
+
+/// Spawns the task of a component on the pool of its kind, or on the current runtime if there is
+/// no pool for that kind.
+#[track_caller]
+pub(super) fn spawn<T>(
+    kind: ComponentKind,
+    task: impl Future<Output = T> + Send + 'static,
+    name: &str,
+) -> JoinHandle<T>
+where
+    T: Send + 'static,
+{
+    let runtime = POOLS.get().and_then(|pools| match kind {
+        ComponentKind::Source => pools.sources.as_ref(),
+        ComponentKind::Transform => pools.transforms.as_ref(),
+        ComponentKind::Sink => pools.sinks.as_ref(),
+    });
+    match runtime {
+        Some(runtime) => {
+            let _guard = runtime.enter();
+            spawn_named(task, name)
+        }
+        None => spawn_named(task, name),
+    }
+}
+
+fn build_runtime(kind: &str, config: Option<&ThreadPoolConfig>) -> crate::Result<Option<Runtime>> {
+    let config = match config {
+        Some(config) => config,
+        None => return Ok(None),
+    };
+
+    let mut cpus = config.cpus.iter().copied().collect::<BTreeSet<_>>();
+    if let Some(node) = config.numa_node {
+        let path = format!("/sys/devices/system/node/node{}/cpulist", node);
+        let cpulist = std::fs::read_to_string(&path)
+            .map_err(|error| format!("Could not read the CPUs of NUMA node {}: {}", node, error))?;
+        let node_cpus = parse_cpulist(&cpulist)
+            .ok_or_else(|| format!("Could not parse the CPUs of NUMA node {}.", node))?;
+        cpus = if cpus.is_empty() {
+            node_cpus
+        } else {
+            cpus.intersection(&node_cpus).copied().collect()
+        };
+        if cpus.is_empty() {
+            return Err(
+                format!("No CPUs of NUMA node {} to pin the {} pool to.", node, kind).into(),
+            );
+        }
+    }
+    let cpus = cpus.into_iter().collect::<Vec<_>>();
+
+    let threads = match config.threads {
+        Some(threads) => threads.get(),
+        None if cpus.is_empty() => num_cpus::get(),
+        None => cpus.len(),
+    };
+    info!(message = "Starting thread pool.", kind, threads, cpus = ?cpus);
+
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder
+        .worker_threads(threads)
+        .thread_name(format!("vector-{}-worker", kind))
+        .enable_all();
+    if !cpus.is_empty() {
+        builder.on_thread_start(move || pin_current_thread(&cpus));
+    }
+    Ok(Some(builder.build()?))
+}
+
+#[cfg(target_os = "linux")]
+fn pin_current_thread(cpus: &[usize]) {
+    use nix::{
+        sched::{sched_setaffinity, CpuSet},
+        unistd::Pid,
+    };
+
+    let mut set = CpuSet::new();
+    for &cpu in cpus {
+        if let Err(error) = set.set(cpu) {
+            warn!(message = "Could not pin thread to CPU.", cpu, %error);
+        }
+    }
+    if let Err(error) = sched_setaffinity(Pid::from_raw(0), &set) {
+        warn!(message = "Could not pin thread to CPUs.", %error);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_current_thread(_cpus: &[usize]) {
+    warn!("Pinning threads to CPUs is only supported on Linux.");
+}
+
+/// Parses a list of CPUs as the kernel writes them, such as `0-3,8-11`.
+fn parse_cpulist(cpulist: &str) -> Option<BTreeSet<usize>> {
+    let mut cpus = BTreeSet::new();
+    for range in cpulist.trim().split(',').filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => cpus.extend(start.parse::<usize>().ok()?..=end.parse().ok()?),
+            None => {
+                cpus.insert(range.parse().ok()?);
+            }
+        }
+    }
+    Some(cpus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cpulists() {
+        assert_eq!(
+            parse_cpulist("0-3,8-9,12\n"),
+            Some(BTreeSet::from([0, 1, 2, 3, 8, 9, 12]))
+        );
+        assert_eq!(parse_cpulist("\n"), Some(BTreeSet::new()));
+        assert_eq!(parse_cpulist("0-a"), None);
+    }
+}
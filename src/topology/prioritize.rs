@@ -0,0 +1,243 @@
+use std::{collections::VecDeque, num::NonZeroUsize, pin::Pin};
+
+use futures::{
+    task::{Context, Poll},
+    {Stream, StreamExt},
+};
+use vector_buffers::Acker;
+use vector_core::event::{array::events_into_arrays, EventPriority};
+
+use crate::{
+    event::{Event, EventArray, EventContainer, EventStatus},
+    internal_events::PrioritizedEventsShed,
+};
+
+/// A stream combinator that hands events to a sink from the highest priority class first.
+///
+/// Events are read from the inner stream into a window of up to `limit` events, with one queue per
+/// priority class, for as long as the inner stream has events ready. Whenever the stream is polled,
+/// all of the events of the highest priority class in the window are handed out together.
+///
+/// When the window is full, the newest events of the lowest priority class are shed to make room
+/// for events of a higher class. Events that would not displace anything are held back until the
+/// sink catches up, which leaves the inner stream, and so the buffer in front of the sink, to
+/// apply backpressure as usual.
+pub struct Prioritize<T> {
+    inner: T,
+    /// One queue per priority class, indexed by `EventPriority as usize`.
+    queues: [VecDeque<Event>; 3],
+    /// Distinct from `pending`, counts the number of events in `queues`.
+    queued: usize,
+    /// Limit for the number of events in `queues`.
+    limit: usize,
+    /// Events read from the inner stream that did not fit in the window yet.
+    pending: VecDeque<Event>,
+    /// Events taken from the window, ready to be handed out.
+    ready: VecDeque<EventArray>,
+    /// The number of events shed from each priority class since this was last reported.
+    shed: [usize; 3],
+    acker: Acker,
+    inner_done: bool,
+}
+
+impl<T> Prioritize<T>
+where
+    T: Stream<Item = EventArray> + Unpin,
+{
+    /// Creates a new `Prioritize` with a window of the given number of events.
+    ///
+    /// Events that are shed are acknowledged with the given `Acker`, the same as if the sink had
+    /// processed them, and reported to their sources as rejected.
+    pub fn new(inner: T, limit: NonZeroUsize, acker: Acker) -> Self {
+        Self {
+            inner,
+            queues: Default::default(),
+            queued: 0,
+            limit: limit.get(),
+            pending: VecDeque::new(),
+            ready: VecDeque::new(),
+            shed: [0; 3],
+            acker,
+            inner_done: false,
+        }
+    }
+
+    /// Moves as many pending events into the window as possible, returning whether all of them
+    /// fit.
+    fn enqueue_pending(&mut self) -> bool {
+        while let Some(event) = self.pending.pop_front() {
+            let priority = event.metadata().priority();
+            if self.queued >= self.limit && !self.shed_below(priority) {
+                self.pending.push_front(event);
+                return false;
+            }
+
+            self.queues[priority as usize].push_back(event);
+            self.queued += 1;
+        }
+
+        true
+    }
+
+    /// Sheds the newest event of the lowest priority class below the given one, returning whether
+    /// there was one.
+    fn shed_below(&mut self, priority: EventPriority) -> bool {
+        let lower = self.queues.iter_mut().enumerate().take(priority as usize);
+        for (class, queue) in lower {
+            if let Some(mut event) = queue.pop_back() {
+                // Dropping the finalizers as they are would report the event as delivered.
+                event
+                    .metadata_mut()
+                    .take_finalizers()
+                    .update_status(EventStatus::Rejected);
+                self.queued -= 1;
+                self.shed[class] += 1;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn report_shed(&mut self) {
+        let mut total = 0;
+        for (class, count) in self.shed.iter_mut().enumerate() {
+            if *count > 0 {
+                emit!(PrioritizedEventsShed {
+                    count: *count,
+                    priority: EventPriority::ALL[class],
+                });
+                total += *count;
+                *count = 0;
+            }
+        }
+
+        if total > 0 {
+            self.acker.ack(total);
+        }
+    }
+
+    /// Takes all of the events of the highest priority class in the window.
+    fn dequeue(&mut self) -> Option<Vec<Event>> {
+        let queue = self
+            .queues
+            .iter_mut()
+            .rev()
+            .find(|queue| !queue.is_empty())?;
+        self.queued -= queue.len();
+        Some(queue.drain(..).collect())
+    }
+}
+
+impl<T> Stream for Prioritize<T>
+where
+    T: Stream<Item = EventArray> + Unpin,
+{
+    type Item = EventArray;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(array) = self.ready.pop_front() {
+                return Poll::Ready(Some(array));
+            }
+
+            // Fill the window with whatever the inner stream has ready, so that we pick from as
+            // many events as possible.
+            while self.enqueue_pending() && !self.inner_done {
+                match self.inner.poll_next_unpin(cx) {
+                    Poll::Ready(Some(array)) => self.pending.extend(array.into_events()),
+                    Poll::Ready(None) => self.inner_done = true,
+                    Poll::Pending => break,
+                }
+            }
+            self.report_shed();
+
+            match self.dequeue() {
+                Some(events) => self.ready.extend(events_into_arrays(events, None)),
+                // The window can only be empty once all pending events have been moved into it, so
+                // there is nothing left to hand out until the inner stream has more.
+                None if self.inner_done => return Poll::Ready(None),
+                None => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use futures::{stream, StreamExt};
+
+    use super::*;
+    use crate::event::{BatchNotifier, BatchStatus, LogEvent, Value};
+
+    fn log(message: &str, priority: EventPriority) -> LogEvent {
+        let mut log = LogEvent::from(message);
+        log.metadata_mut().set_priority(priority);
+        log
+    }
+
+    fn messages(arrays: Vec<EventArray>) -> Vec<Value> {
+        arrays
+            .into_iter()
+            .flat_map(EventContainer::into_events)
+            .map(|event| event.as_log()["message"].clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn hands_out_higher_priority_first() {
+        let input = stream::iter(vec![
+            EventArray::from(vec![
+                log("low", EventPriority::Low),
+                log("normal", EventPriority::Normal),
+            ]),
+            EventArray::from(log("high", EventPriority::High)),
+        ]);
+
+        let output = Prioritize::new(input, NonZeroUsize::new(10).unwrap(), Acker::passthrough())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            messages(output),
+            vec![
+                Value::from("high"),
+                Value::from("normal"),
+                Value::from("low")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn sheds_lower_priority_when_full() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let input = stream::iter(vec![
+            EventArray::from(log("low 1", EventPriority::Low).with_batch_notifier(&batch)),
+            EventArray::from(log("low 2", EventPriority::Low).with_batch_notifier(&batch)),
+            EventArray::from(log("high", EventPriority::High)),
+            EventArray::from(log("normal 1", EventPriority::Normal)),
+            EventArray::from(log("normal 2", EventPriority::Normal)),
+        ]);
+        drop(batch);
+
+        let (acker, acked) = Acker::basic();
+        let output = Prioritize::new(input, NonZeroUsize::new(2).unwrap(), acker)
+            .collect::<Vec<_>>()
+            .await;
+
+        // The high and first normal priority events each displace a low priority event, while the
+        // second normal priority event has nothing to displace, and so waits for room instead.
+        assert_eq!(
+            messages(output),
+            vec![
+                Value::from("high"),
+                Value::from("normal 1"),
+                Value::from("normal 2")
+            ]
+        );
+        assert_eq!(acked.load(Ordering::Relaxed), 2);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+}
@@ -6,6 +6,7 @@ use std::{
     },
 };
 
+use chrono::{DateTime, Utc};
 use futures::{future, Future, FutureExt};
 use tokio::{
     sync::{mpsc, watch},
@@ -17,20 +18,57 @@ use vector_core::trigger::DisabledTrigger;
 
 use super::{TapOutput, TapResource};
 use crate::{
-    config::{ComponentKey, Config, ConfigDiff, HealthcheckOptions, OutputId, Resource},
+    audit::{self, AuditAction},
+    config::{
+        ComponentChanges, ComponentKey, Config, ConfigDiff, HealthcheckOptions, OutputId, Resource,
+    },
     event::EventArray,
     shutdown::SourceShutdownCoordinator,
-    spawn_named,
     topology::{
         build_or_log_errors, builder,
         builder::Pieces,
         fanout::{ControlChannel, ControlMessage},
-        handle_errors, retain, take_healthchecks,
+        handle_errors,
+        health::{self, ComponentKind},
+        pools, retain, take_healthchecks,
         task::TaskOutput,
         BuiltBuffer, TaskHandle, WatchRx, WatchTx,
     },
 };
 
+/// How long a changed sink is given to drain its in-flight events before its new version is
+/// spawned regardless.
+const SINK_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The outcome of a configuration reload.
+#[derive(Clone, Debug)]
+pub struct ReloadResult {
+    /// When the reload finished.
+    pub at: DateTime<Utc>,
+    /// Whether the new configuration was applied, rather than the old one restored.
+    pub applied: bool,
+    /// The components added, changed and removed by the new configuration.
+    pub changes: ComponentChanges,
+}
+
+impl ReloadResult {
+    fn new(applied: bool, changes: ComponentChanges) -> Self {
+        Self {
+            at: Utc::now(),
+            applied,
+            changes,
+        }
+    }
+}
+
+/// Joins the IDs of the given components, for logging.
+fn component_ids(keys: &[ComponentKey]) -> String {
+    keys.iter()
+        .map(ComponentKey::id)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[allow(dead_code)]
 pub struct RunningTopology {
     inputs: HashMap<ComponentKey, BufferSender<EventArray>>,
@@ -43,6 +81,7 @@ pub struct RunningTopology {
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
     pub(crate) running: Arc<AtomicBool>,
+    last_reload: Option<ReloadResult>,
 }
 
 impl RunningTopology {
@@ -58,6 +97,7 @@ impl RunningTopology {
             abort_tx,
             watch: watch::channel(TapResource::default()),
             running: Arc::new(AtomicBool::new(true)),
+            last_reload: None,
         }
     }
 
@@ -66,6 +106,11 @@ impl RunningTopology {
         &self.config
     }
 
+    /// Gets the outcome of the last reload of this running topology, if it was reloaded.
+    pub const fn last_reload(&self) -> Option<&ReloadResult> {
+        self.last_reload.as_ref()
+    }
+
     /// Creates a subscription to topology changes.
     ///
     /// This is used by the tap API to observe configuration changes, and re-wire tap sinks.
@@ -211,6 +256,11 @@ impl RunningTopology {
                 message =
                 "Global options can't be changed while reloading config file; reload aborted. Please restart Vector to reload the configuration file."
             );
+            audit::record(AuditAction::ConfigReload {
+                outcome: "rejected",
+                changes: &ComponentChanges::default(),
+                changed_options: &[],
+            });
             return Ok(false);
         }
 
@@ -220,6 +270,14 @@ impl RunningTopology {
         //
         // We also shutdown any component that is simply being removed entirely.
         let diff = ConfigDiff::new(&self.config, &new_config);
+        let changes = diff.component_changes();
+        info!(
+            message = "Applying configuration changes.",
+            added = %component_ids(&changes.added),
+            changed = %component_ids(&changes.changed),
+            removed = %component_ids(&changes.removed),
+        );
+        let changed_options = audit::changed_options(&self.config, &new_config, &changes.changed);
         let buffers = self.shutdown_diff(&diff, &new_config).await;
 
         // Gives windows some time to make available any port
@@ -246,7 +304,18 @@ impl RunningTopology {
                 self.spawn_diff(&diff, new_pieces);
                 self.config = new_config;
 
-                info!("New configuration loaded successfully.");
+                info!(
+                    message = "New configuration loaded successfully.",
+                    added = changes.added.len(),
+                    changed = changes.changed.len(),
+                    removed = changes.removed.len(),
+                );
+                audit::record(AuditAction::ConfigReload {
+                    outcome: "applied",
+                    changes: &changes,
+                    changed_options: &changed_options,
+                });
+                self.last_reload = Some(ReloadResult::new(true, changes));
 
                 return Ok(true);
             }
@@ -258,6 +327,8 @@ impl RunningTopology {
         warn!("Failed to completely load new configuration. Restoring old configuration.");
 
         let diff = diff.flip();
+        // The components that were being added never ran.
+        health::remove(&diff);
         if let Some(mut new_pieces) = build_or_log_errors(&self.config, &diff, buffers).await {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
@@ -267,12 +338,23 @@ impl RunningTopology {
                 self.spawn_diff(&diff, new_pieces);
 
                 info!("Old configuration restored successfully.");
+                audit::record(AuditAction::ConfigReload {
+                    outcome: "rolled_back",
+                    changes: &changes,
+                    changed_options: &changed_options,
+                });
+                self.last_reload = Some(ReloadResult::new(false, changes));
 
                 return Ok(false);
             }
         }
 
         error!("Failed to restore old configuration.");
+        audit::record(AuditAction::ConfigReload {
+            outcome: "failed",
+            changes: &changes,
+            changed_options: &changed_options,
+        });
 
         Err(())
     }
@@ -287,11 +369,10 @@ impl RunningTopology {
             let healthchecks = take_healthchecks(diff, pieces)
                 .into_iter()
                 .map(|(_, task)| task);
-            let healthchecks = future::try_join_all(healthchecks);
 
             info!("Running healthchecks.");
             if options.require_healthy {
-                let success = healthchecks.await;
+                let success = future::try_join_all(healthchecks).await;
 
                 if success.is_ok() {
                     info!("All healthchecks passed.");
@@ -301,7 +382,9 @@ impl RunningTopology {
                     false
                 }
             } else {
-                tokio::spawn(healthchecks);
+                // Every healthcheck runs to completion, so that the health of each sink is known
+                // even if another one fails.
+                tokio::spawn(future::join_all(healthchecks));
                 true
             }
         } else {
@@ -317,6 +400,8 @@ impl RunningTopology {
         diff: &ConfigDiff,
         new_config: &Config,
     ) -> HashMap<ComponentKey, BuiltBuffer> {
+        health::remove(diff);
+
         // First, we shutdown any changed/removed sources. This ensures that we can allow downstream
         // components to terminate naturally by virtue of the flow of events stopping.
         if diff.sources.any_changed_or_removed() {
@@ -436,6 +521,7 @@ impl RunningTopology {
         for key in &diff.sinks.to_remove {
             debug!(component = %key, "Removing sink.");
             self.remove_inputs(key, diff).await;
+            self.remove_outputs(key);
         }
 
         // After that, for any changed sinks, we temporarily detach their inputs (not remove) so
@@ -464,6 +550,7 @@ impl RunningTopology {
                 buffer_tx.insert(key.clone(), self.inputs.get(key).unwrap().clone());
             }
             self.remove_inputs(key, diff).await;
+            self.remove_outputs(key);
         }
 
         // Now that we've disconnected or temporarily detached the inputs to all changed/removed
@@ -484,8 +571,8 @@ impl RunningTopology {
 
         let mut buffers = HashMap::<ComponentKey, BuiltBuffer>::new();
         for key in &diff.sinks.to_change {
+            let previous = self.tasks.remove(key).unwrap();
             if wait_for_sinks.contains(key) {
-                let previous = self.tasks.remove(key).unwrap();
                 debug!(message = "Waiting for sink to shutdown.", %key);
                 let buffer = previous.await.unwrap().unwrap();
 
@@ -505,6 +592,20 @@ impl RunningTopology {
 
                     buffers.insert(key.clone(), (tx, Arc::new(Mutex::new(Some(rx))), acker));
                 }
+            } else {
+                // Let the old version of the sink drain its in-flight events, so that both
+                // versions don't run side by side, without holding up the reload indefinitely.
+                debug!(message = "Waiting for sink to drain.", %key);
+                if tokio::time::timeout(SINK_DRAIN_TIMEOUT, previous)
+                    .await
+                    .is_err()
+                {
+                    warn!(
+                        message = "Sink didn't drain in time; detaching it.",
+                        %key,
+                        timeout_secs = SINK_DRAIN_TIMEOUT.as_secs(),
+                    );
+                }
             }
         }
 
@@ -547,6 +648,15 @@ impl RunningTopology {
             self.setup_outputs(key, new_pieces).await;
         }
 
+        // Sinks only have outputs when they route failed events to a dead letter output, but when
+        // they do, those outputs need to be available to any transforms and sinks consuming them.
+        for key in diff.sinks.changed_and_added() {
+            if new_pieces.outputs.contains_key(key) {
+                debug!(component = %key, "Configuring outputs for sink.");
+                self.setup_outputs(key, new_pieces).await;
+            }
+        }
+
         // Now that all possible outputs are configured, we can start wiring up inputs, starting
         // with transforms.
         for key in diff.transforms.changed_and_added() {
@@ -787,8 +897,10 @@ impl RunningTopology {
             component_name = %task.id(),
         );
         let task_name = format!(">> {} ({})", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(span.or_current());
-        let spawned = spawn_named(task, task_name.as_ref());
+        let task =
+            handle_errors(task, key.clone(), self.abort_tx.clone()).instrument(span.or_current());
+        let spawned = pools::spawn(ComponentKind::Sink, task, task_name.as_ref());
+        health::spawned(key);
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
         }
@@ -805,8 +917,10 @@ impl RunningTopology {
             component_name = %task.id(),
         );
         let task_name = format!(">> {} ({}) >>", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(span.or_current());
-        let spawned = spawn_named(task, task_name.as_ref());
+        let task =
+            handle_errors(task, key.clone(), self.abort_tx.clone()).instrument(span.or_current());
+        let spawned = pools::spawn(ComponentKind::Transform, task, task_name.as_ref());
+        health::spawned(key);
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
         }
@@ -823,8 +937,9 @@ impl RunningTopology {
             component_name = %task.id(),
         );
         let task_name = format!("{} ({}) >>", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(span.clone().or_current());
-        let spawned = spawn_named(task, task_name.as_ref());
+        let task = handle_errors(task, key.clone(), self.abort_tx.clone())
+            .instrument(span.clone().or_current());
+        let spawned = pools::spawn(ComponentKind::Source, task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
         }
@@ -833,10 +948,13 @@ impl RunningTopology {
             .takeover_source(key, &mut new_pieces.shutdown_coordinator);
 
         let source_task = new_pieces.source_tasks.remove(key).unwrap();
-        let source_task =
-            handle_errors(source_task, self.abort_tx.clone()).instrument(span.or_current());
-        self.source_tasks
-            .insert(key.clone(), spawn_named(source_task, task_name.as_ref()));
+        let source_task = handle_errors(source_task, key.clone(), self.abort_tx.clone())
+            .instrument(span.or_current());
+        self.source_tasks.insert(
+            key.clone(),
+            pools::spawn(ComponentKind::Source, source_task, task_name.as_ref()),
+        );
+        health::spawned(key);
     }
 }
 
@@ -861,5 +979,14 @@ fn get_changed_outputs(diff: &ConfigDiff, output_ids: Vec<OutputId>) -> Vec<Outp
         );
     }
 
+    for sink_key in &diff.sinks.to_change {
+        changed_outputs.extend(
+            output_ids
+                .iter()
+                .filter(|id| &id.component == sink_key)
+                .cloned(),
+        );
+    }
+
     changed_outputs
 }
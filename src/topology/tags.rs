@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    event::{EventArray, EventRef},
+    internal_events::TemplateRenderingError,
+    template::{Template, TemplateParseError},
+};
+
+/// Attaches the configured tags to the events of a source, as fields of its logs and traces and
+/// as tags of its metrics.
+///
+/// Tags the events already carry are left as they are.
+#[derive(Debug)]
+pub(super) struct EventTags {
+    tags: Vec<(String, Template)>,
+}
+
+impl EventTags {
+    /// Builds the tags of a source, which are the global tags overridden by those of the source.
+    ///
+    /// A tag the source sets to an empty value isn't attached to its events at all.
+    pub(super) fn new(
+        global: &BTreeMap<String, String>,
+        source: &BTreeMap<String, String>,
+    ) -> Result<Option<Self>, TemplateParseError> {
+        let mut tags = global.clone();
+        tags.extend(source.clone());
+        let tags = tags
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| Template::try_from(value).map(|template| (name, template)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((!tags.is_empty()).then(|| Self { tags }))
+    }
+
+    pub(super) fn apply(&self, array: &mut EventArray) {
+        array.for_each_log(|log| {
+            for (name, template) in &self.tags {
+                if !log.contains(name.as_str()) {
+                    if let Some(value) = render(name, template, &*log) {
+                        log.insert(name.as_str(), value);
+                    }
+                }
+            }
+        });
+        array.for_each_metric(|metric| {
+            for (name, template) in &self.tags {
+                if metric.tag_value(name).is_none() {
+                    if let Some(value) = render(name, template, &*metric) {
+                        metric.insert_tag(name.clone(), value);
+                    }
+                }
+            }
+        });
+        array.for_each_trace(|trace| {
+            for (name, template) in &self.tags {
+                if !trace.contains(name) {
+                    if let Some(value) = render(name, template, &*trace) {
+                        trace.insert(name, value);
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn render<'a>(name: &str, template: &Template, event: impl Into<EventRef<'a>>) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(name),
+                drop_event: false,
+            });
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
+
+    fn tags(global: &[(&str, &str)], source: &[(&str, &str)]) -> Option<EventTags> {
+        let map = |tags: &[(&str, &str)]| {
+            tags.iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        };
+        EventTags::new(&map(global), &map(source)).unwrap()
+    }
+
+    #[test]
+    fn source_tags_override_global_tags() {
+        assert!(tags(&[], &[]).is_none());
+        assert!(tags(&[("env", "prod")], &[("env", "")]).is_none());
+
+        let tags = tags(
+            &[("env", "prod"), ("region", "eu-west-1")],
+            &[("region", "us-east-1"), ("node", "{{ host }}")],
+        )
+        .unwrap();
+
+        let mut log = LogEvent::from("hello");
+        log.insert("host", "example.com");
+        log.insert("env", "staging");
+        let mut array = EventArray::from(log);
+        tags.apply(&mut array);
+        let log = match array {
+            EventArray::Logs(mut logs) => logs.remove(0),
+            _ => unreachable!(),
+        };
+        assert_eq!(log["env"], "staging".into());
+        assert_eq!(log["region"], "us-east-1".into());
+        assert_eq!(log["node"], "example.com".into());
+
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+        let mut array = EventArray::from(metric);
+        tags.apply(&mut array);
+        let metric = match array {
+            EventArray::Metrics(mut metrics) => metrics.remove(0),
+            _ => unreachable!(),
+        };
+        assert_eq!(metric.tag_value("env"), Some("prod".to_owned()));
+        assert_eq!(metric.tag_value("region"), Some("us-east-1".to_owned()));
+        // The metric has no host to render the template with.
+        assert_eq!(metric.tag_value("node"), None);
+    }
+}
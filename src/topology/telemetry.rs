@@ -0,0 +1,280 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use once_cell::sync::Lazy;
+use opentelemetry::{
+    sdk::{self, trace::Tracer, Resource},
+    trace::{
+        Span as _, SpanBuilder, SpanContext, SpanId, StatusCode, TraceContextExt, TraceFlags,
+        TraceId, TraceState, Tracer as _,
+    },
+    Context, KeyValue,
+};
+use rand::Rng;
+use vector_core::{
+    config::TelemetryConfig,
+    event::{BatchNotifier, BatchStatus, BatchTrace, EventFinalizer},
+};
+
+use crate::{
+    config::ComponentKey,
+    event::{EventArray, EventContainer},
+};
+
+/// The telemetry of the topology, shared by every topology built with the same telemetry
+/// configuration, so that reloads don't start a new exporter.
+static GLOBAL: Lazy<Mutex<Option<Arc<Telemetry>>>> = Lazy::new(Default::default);
+
+/// Gets the telemetry for the given configuration, if batches are traced.
+pub(super) fn global(config: &TelemetryConfig) -> Option<Arc<Telemetry>> {
+    if !config.is_enabled() {
+        return None;
+    }
+
+    let mut global = GLOBAL.lock().expect("telemetry lock poisoned");
+    match global.as_ref() {
+        Some(telemetry) if telemetry.config == *config => Some(Arc::clone(telemetry)),
+        _ => match Telemetry::new(config.clone()) {
+            Ok(telemetry) => {
+                let telemetry = Arc::new(telemetry);
+                *global = Some(Arc::clone(&telemetry));
+                Some(telemetry)
+            }
+            Err(error) => {
+                warn!(message = "Couldn't start exporting telemetry.", %error);
+                None
+            }
+        },
+    }
+}
+
+/// Traces batches of events through the topology, exporting a span for each component a sampled
+/// batch goes through.
+///
+/// Each component that hands a batch over stamps its events with its own span, so that the span of
+/// the next component is a child of it. A span starts when the previous component hands the batch
+/// over, so it includes the time the batch waits in the input buffer of the component, and ends
+/// when the component hands the batch over in turn, or for sinks, when the batch is delivered.
+pub struct Telemetry {
+    config: TelemetryConfig,
+    tracer: Tracer,
+}
+
+impl Telemetry {
+    fn new(config: TelemetryConfig) -> Result<Self, opentelemetry::trace::TraceError> {
+        let endpoint = config.endpoint.clone().unwrap_or_default();
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .http()
+                    .with_endpoint(endpoint),
+            )
+            .with_trace_config(sdk::trace::config().with_resource(Resource::new(vec![
+                KeyValue::new("service.name", config.service_name.clone()),
+            ])))
+            .install_batch(opentelemetry::runtime::Tokio)?;
+
+        Ok(Self { config, tracer })
+    }
+
+    /// Gets the tracing of the batches going through the given component.
+    pub fn component(
+        self: &Arc<Self>,
+        key: &ComponentKey,
+        kind: &'static str,
+        typetag: &'static str,
+    ) -> TracedComponent {
+        TracedComponent {
+            telemetry: Arc::clone(self),
+            attributes: vec![
+                KeyValue::new("component_id", key.id().to_owned()),
+                KeyValue::new("component_kind", kind),
+                KeyValue::new("component_type", typetag),
+            ],
+        }
+    }
+}
+
+/// Traces the batches of events going through a component.
+#[derive(Clone)]
+pub struct TracedComponent {
+    telemetry: Arc<Telemetry>,
+    attributes: Vec<KeyValue>,
+}
+
+/// The events of a batch that a component received from the same upstream span.
+struct Group {
+    trace: BatchTrace,
+    count: usize,
+}
+
+impl TracedComponent {
+    /// Samples the batch into a new trace, by the sample rate of the configuration.
+    pub fn sample(&self, events: &mut EventArray) {
+        if !rand::thread_rng().gen_bool(self.telemetry.config.sample_rate) {
+            return;
+        }
+
+        let trace = Arc::new(BatchTrace {
+            trace_id: rand::thread_rng().gen(),
+            span_id: 0,
+            handed_over_at: SystemTime::now(),
+        });
+        events.for_each_event(|mut event| {
+            event
+                .metadata_mut()
+                .set_batch_trace(Some(Arc::clone(&trace)));
+        });
+    }
+
+    /// Ends the spans of the traced events that the component hands over, and stamps the events
+    /// with those spans.
+    pub fn hand_over<'a>(&self, arrays: impl IntoIterator<Item = &'a mut EventArray>) {
+        let arrays: Vec<_> = arrays.into_iter().collect();
+        let groups = groups(arrays.iter().map(|events| &**events));
+        if groups.is_empty() {
+            return;
+        }
+
+        let now = SystemTime::now();
+        let handed_over: HashMap<_, _> = groups
+            .into_iter()
+            .map(|(key, group)| {
+                let mut span = self.start(&group);
+                let span_id = span.span_context().span_id();
+                span.end_with_timestamp(now);
+
+                let trace = Arc::new(BatchTrace {
+                    trace_id: group.trace.trace_id,
+                    span_id: u64::from_be_bytes(span_id.to_bytes()),
+                    handed_over_at: now,
+                });
+                (key, trace)
+            })
+            .collect();
+
+        for events in arrays {
+            events.for_each_event(|mut event| {
+                let metadata = event.metadata_mut();
+                if let Some(upstream) = metadata.batch_trace().as_deref().map(key) {
+                    metadata.set_batch_trace(handed_over.get(&upstream).cloned());
+                }
+            });
+        }
+    }
+
+    /// Starts the spans of the traced events that the sink received, which end when the events
+    /// are delivered.
+    pub fn track_delivery(&self, events: &mut EventArray) {
+        let groups = groups(std::iter::once(&*events));
+        for (trace, group) in groups {
+            let mut span = self.start(&group);
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            events.for_each_event(|mut event| {
+                let metadata = event.metadata_mut();
+                if metadata.batch_trace().as_deref().map(key) == Some(trace) {
+                    metadata.add_finalizer(EventFinalizer::new(Arc::clone(&batch)));
+                }
+            });
+            drop(batch);
+
+            tokio::spawn(async move {
+                let status = receiver.await;
+                if status != BatchStatus::Delivered {
+                    span.set_status(StatusCode::Error, format!("{:?}", status));
+                }
+                span.end();
+            });
+        }
+    }
+
+    /// Starts the span of the component for a group of events, as a child of the upstream span.
+    fn start(&self, group: &Group) -> sdk::trace::Span {
+        let mut attributes = self.attributes.clone();
+        attributes.push(KeyValue::new("events", group.count as i64));
+        let builder = SpanBuilder::from_name("batch")
+            .with_trace_id(TraceId::from_u128(group.trace.trace_id))
+            .with_start_time(group.trace.handed_over_at)
+            .with_attributes(attributes);
+
+        let cx = match group.trace.span_id {
+            0 => Context::new(),
+            span_id => Context::new().with_remote_span_context(SpanContext::new(
+                TraceId::from_u128(group.trace.trace_id),
+                SpanId::from_u64(span_id),
+                TraceFlags::SAMPLED,
+                true,
+                TraceState::default(),
+            )),
+        };
+        self.telemetry.tracer.build_with_context(builder, &cx)
+    }
+}
+
+fn key(trace: &BatchTrace) -> (u128, u64) {
+    (trace.trace_id, trace.span_id)
+}
+
+/// Groups the traced events by the upstream span they were handed over with.
+fn groups<'a>(arrays: impl Iterator<Item = &'a EventArray>) -> HashMap<(u128, u64), Group> {
+    let mut groups = HashMap::new();
+    for events in arrays {
+        for event in events.iter_events() {
+            if let Some(trace) = event.metadata().batch_trace() {
+                groups
+                    .entry(key(trace))
+                    .or_insert(Group {
+                        trace: **trace,
+                        count: 0,
+                    })
+                    .count += 1;
+            }
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn logs(traces: &[Option<(u128, u64)>]) -> EventArray {
+        let now = SystemTime::now();
+        EventArray::Logs(
+            traces
+                .iter()
+                .map(|trace| {
+                    let mut log = LogEvent::from("message");
+                    log.metadata_mut()
+                        .set_batch_trace(trace.map(|(trace_id, span_id)| {
+                            Arc::new(BatchTrace {
+                                trace_id,
+                                span_id,
+                                handed_over_at: now,
+                            })
+                        }));
+                    log
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn groups_traced_events_by_upstream_span() {
+        let first = logs(&[Some((1, 0)), None, Some((2, 7))]);
+        let second = logs(&[Some((2, 7)), Some((2, 8)), None]);
+
+        let groups = groups([&first, &second].into_iter());
+        let mut counts: Vec<_> = groups
+            .iter()
+            .map(|(key, group)| (*key, group.count))
+            .collect();
+        counts.sort_unstable();
+        assert_eq!(counts, vec![((1, 0), 1), ((2, 7), 2), ((2, 8), 1)]);
+    }
+}
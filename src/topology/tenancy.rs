@@ -0,0 +1,291 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use once_cell::sync::Lazy;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::Instrument;
+use vector_core::{
+    config::{AdmissionConfig, TenancyConfig, WhenExceeded},
+    event::{BatchNotifier, EventFinalizer},
+    ByteSizeOf,
+};
+
+use super::admission::{split_by_field, AdmissionController};
+use crate::{
+    config::ComponentKey,
+    event::{EventArray, EventContainer},
+    internal_events::{TenantBufferedEvents, TenantEventsAdmitted, TenantQuotaEventsDropped},
+};
+
+/// The tenancy controller, shared by the sources of every topology built with the same tenancy
+/// configuration, so that the limits of the tenants keep applying across reloads.
+static GLOBAL: Lazy<Mutex<Option<Arc<TenancyController>>>> = Lazy::new(Default::default);
+
+/// Gets the tenancy controller for the given configuration, if tenancy is enabled.
+pub(super) fn global(config: &TenancyConfig) -> Option<Arc<TenancyController>> {
+    if !config.is_enabled() {
+        return None;
+    }
+
+    let mut global = GLOBAL.lock().expect("tenancy controller lock poisoned");
+    match global.as_ref() {
+        Some(controller) if controller.config == *config => Some(Arc::clone(controller)),
+        _ => {
+            let controller = Arc::new(TenancyController::new(config.clone()));
+            *global = Some(Arc::clone(&controller));
+            Some(controller)
+        }
+    }
+}
+
+/// Gives events their tenant and enforces the limits of each tenant.
+///
+/// The rate limits of a tenant are enforced as by an `AdmissionController`. The number of events
+/// of a tenant in flight is bounded by a semaphore, whose permits are held by the events from the
+/// time they're admitted until they're finalized, so that a tenant whose events pile up in buffers
+/// stops being admitted without holding back the other tenants.
+pub struct TenancyController {
+    config: TenancyConfig,
+    admission: HashMap<String, AdmissionController>,
+    quotas: HashMap<String, Quota>,
+}
+
+struct Quota {
+    permits: Arc<Semaphore>,
+    max: usize,
+}
+
+impl TenancyController {
+    pub fn new(config: TenancyConfig) -> Self {
+        let admission = config
+            .tenants
+            .iter()
+            .filter(|(_, tenant)| tenant.events_per_sec.is_some() || tenant.bytes_per_sec.is_some())
+            .map(|(name, tenant)| {
+                let controller = AdmissionController::for_tenant(
+                    AdmissionConfig {
+                        events_per_sec: tenant.events_per_sec,
+                        bytes_per_sec: tenant.bytes_per_sec,
+                        tenant_key: None,
+                        when_exceeded: config.when_exceeded,
+                    },
+                    name.clone(),
+                );
+                (name.clone(), controller)
+            })
+            .collect();
+        let quotas = config
+            .tenants
+            .iter()
+            .filter_map(|(name, tenant)| {
+                let max = tenant.max_buffered_events?.get();
+                let quota = Quota {
+                    permits: Arc::new(Semaphore::new(max)),
+                    max,
+                };
+                Some((name.clone(), quota))
+            })
+            .collect();
+
+        Self {
+            config,
+            admission,
+            quotas,
+        }
+    }
+
+    /// Gives the given events their tenant and admits them by the limits of their tenant, either
+    /// waiting until the limits allow them in or dropping the ones that exceed them, depending on
+    /// the configured policy.
+    ///
+    /// Returns the events that were admitted, if any.
+    pub async fn admit(&self, source: &ComponentKey, events: EventArray) -> Vec<EventArray> {
+        let source_tenant = self.config.tenant_of_source(source.id());
+        let groups = match &self.config.tenant_key {
+            Some(tenant_key) => split_by_field(events, tenant_key),
+            None => vec![(None, events)],
+        };
+
+        let mut admitted = Vec::new();
+        for (tenant, mut events) in groups {
+            let tenant = match tenant.as_deref().or(source_tenant) {
+                Some(tenant) => Arc::<str>::from(tenant),
+                None => {
+                    admitted.push(events);
+                    continue;
+                }
+            };
+            events.for_each_event(|mut event| {
+                event.metadata_mut().set_tenant(Some(Arc::clone(&tenant)))
+            });
+
+            let arrays = match self.admission.get(&*tenant) {
+                Some(controller) => controller.admit(events).await,
+                None => vec![events],
+            };
+            for mut events in arrays {
+                if let Some(quota) = self.quotas.get(&*tenant) {
+                    let permit = match self.reserve(&tenant, quota, events.len()).await {
+                        Some(permit) => permit,
+                        None => continue,
+                    };
+                    hold_until_finalized(&mut events, permit);
+                    emit!(TenantBufferedEvents {
+                        tenant: &tenant,
+                        count: quota.max - quota.permits.available_permits(),
+                    });
+                }
+                emit!(TenantEventsAdmitted {
+                    tenant: &tenant,
+                    count: events.len(),
+                    byte_size: events.size_of(),
+                });
+                admitted.push(events);
+            }
+        }
+        admitted
+    }
+
+    /// Reserves room for the given number of events in the quota of a tenant. A group of events
+    /// larger than the quota takes up all of it.
+    async fn reserve(
+        &self,
+        tenant: &str,
+        quota: &Quota,
+        count: usize,
+    ) -> Option<OwnedSemaphorePermit> {
+        let permits = count.min(quota.max) as u32;
+        match self.config.when_exceeded {
+            WhenExceeded::Block => Arc::clone(&quota.permits)
+                .acquire_many_owned(permits)
+                .await
+                .ok(),
+            WhenExceeded::Drop => {
+                let permit = Arc::clone(&quota.permits)
+                    .try_acquire_many_owned(permits)
+                    .ok();
+                if permit.is_none() {
+                    emit!(TenantQuotaEventsDropped { tenant, count });
+                }
+                permit
+            }
+        }
+    }
+}
+
+/// Holds the given value, such as a permit, until every one of the events is finalized.
+pub(super) fn hold_until_finalized<T: Send + 'static>(events: &mut EventArray, held: T) {
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    events.for_each_event(|mut event| {
+        event
+            .metadata_mut()
+            .add_finalizer(EventFinalizer::new(Arc::clone(&batch)))
+    });
+    drop(batch);
+
+    tokio::spawn(
+        async move {
+            receiver.await;
+            drop(held);
+        }
+        .in_current_span(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Event, LogEvent};
+
+    fn logs(tenants: &[Option<&str>]) -> EventArray {
+        EventArray::Logs(
+            tenants
+                .iter()
+                .map(|tenant| {
+                    let mut log = LogEvent::from("message");
+                    if let Some(tenant) = tenant {
+                        log.insert("team", *tenant);
+                    }
+                    log
+                })
+                .collect(),
+        )
+    }
+
+    fn tenants(arrays: Vec<EventArray>) -> Vec<Option<String>> {
+        arrays
+            .into_iter()
+            .flat_map(|array| array.into_events())
+            .map(|event| match event {
+                Event::Log(log) => log.metadata().tenant().as_deref().map(Into::into),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn gives_events_their_tenant() {
+        let controller = TenancyController::new(
+            toml::from_str(
+                r#"
+                tenant_key = "team"
+
+                [tenants.payments]
+                sources = ["payments_http"]
+                "#,
+            )
+            .unwrap(),
+        );
+
+        let admitted = controller
+            .admit(
+                &ComponentKey::from("payments_http"),
+                logs(&[Some("search"), None]),
+            )
+            .await;
+        assert_eq!(
+            tenants(admitted),
+            vec![Some("search".into()), Some("payments".into())]
+        );
+
+        let admitted = controller
+            .admit(&ComponentKey::from("other"), logs(&[None]))
+            .await;
+        assert_eq!(tenants(admitted), vec![None]);
+    }
+
+    #[tokio::test]
+    async fn bounds_the_events_of_a_tenant_in_flight() {
+        let controller = TenancyController::new(
+            toml::from_str(
+                r#"
+                when_exceeded = "drop"
+
+                [tenants.payments]
+                sources = ["payments_http"]
+                max_buffered_events = 2
+                "#,
+            )
+            .unwrap(),
+        );
+        let source = ComponentKey::from("payments_http");
+
+        let in_flight = controller.admit(&source, logs(&[None, None])).await;
+        assert_eq!(
+            tenants(controller.admit(&source, logs(&[None])).await).len(),
+            0
+        );
+
+        // Finalizing the events in flight makes room for more.
+        drop(in_flight);
+        while controller.quotas["payments"].permits.available_permits() < 2 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(
+            tenants(controller.admit(&source, logs(&[None])).await).len(),
+            1
+        );
+    }
+}
@@ -20,6 +20,7 @@ async fn topology_doesnt_reload_new_data_dir() {
         ConsoleSinkConfig {
             target: Target::Stdout,
             encoding: EncodingConfig::from(StandardEncodings::Text).into(),
+            display: None,
             acknowledgements: Default::default(),
         },
     );
@@ -39,6 +39,7 @@ async fn topology_reuse_old_port() {
         ConsoleSinkConfig {
             target: Target::Stdout,
             encoding: EncodingConfig::from(StandardEncodings::Text).into(),
+            display: None,
             acknowledgements: Default::default(),
         },
     );
@@ -51,6 +52,7 @@ async fn topology_reuse_old_port() {
         ConsoleSinkConfig {
             target: Target::Stdout,
             encoding: EncodingConfig::from(StandardEncodings::Text).into(),
+            display: None,
             acknowledgements: Default::default(),
         },
     );
@@ -60,6 +62,12 @@ async fn topology_reuse_old_port() {
         .reload_config_and_respawn(new_config.build().unwrap())
         .await
         .unwrap());
+
+    let reload = topology.last_reload().unwrap();
+    assert!(reload.applied);
+    assert_eq!(reload.changes.added, vec![ComponentKey::from("in2")]);
+    assert_eq!(reload.changes.changed, vec![ComponentKey::from("out")]);
+    assert_eq!(reload.changes.removed, vec![ComponentKey::from("in1")]);
 }
 
 #[tokio::test]
@@ -75,6 +83,7 @@ async fn topology_rebuild_old() {
         ConsoleSinkConfig {
             target: Target::Stdout,
             encoding: EncodingConfig::from(StandardEncodings::Text).into(),
+            display: None,
             acknowledgements: Default::default(),
         },
     );
@@ -87,6 +96,7 @@ async fn topology_rebuild_old() {
         ConsoleSinkConfig {
             target: Target::Stdout,
             encoding: EncodingConfig::from(StandardEncodings::Text).into(),
+            display: None,
             acknowledgements: Default::default(),
         },
     );
@@ -99,6 +109,10 @@ async fn topology_rebuild_old() {
         .reload_config_and_respawn(new_config.build().unwrap())
         .await
         .unwrap());
+
+    let reload = topology.last_reload().unwrap();
+    assert!(!reload.applied);
+    assert_eq!(reload.changes.changed, vec![ComponentKey::from("in1")]);
 }
 
 #[tokio::test]
@@ -113,6 +127,7 @@ async fn topology_old() {
         ConsoleSinkConfig {
             target: Target::Stdout,
             encoding: EncodingConfig::from(StandardEncodings::Text).into(),
+            display: None,
             acknowledgements: Default::default(),
         },
     );
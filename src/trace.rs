@@ -4,7 +4,7 @@ use std::{
     str::FromStr,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Mutex, MutexGuard,
+        Mutex, MutexGuard, RwLock,
     },
 };
 
@@ -20,6 +20,7 @@ use tokio_stream::wrappers::BroadcastStream;
 use tracing::{Event, Subscriber};
 use tracing_limit::RateLimitedLayer;
 use tracing_subscriber::{
+    filter::{filter_fn, Targets},
     layer::{Context, SubscriberExt},
     registry::LookupSpan,
     util::SubscriberInitExt,
@@ -50,15 +51,26 @@ static SUBSCRIBERS: OnceCell<Mutex<Option<Vec<oneshot::Sender<Vec<LogEvent>>>>>>
 /// has been initialized.
 static SENDER: OnceCell<Sender<LogEvent>> = OnceCell::new();
 
+/// LEVELS holds the filter internal log events are logged and broadcast by, which can be changed at runtime through
+/// `set_levels`.
+static LEVELS: OnceCell<RwLock<Targets>> = OnceCell::new();
+
 fn metrics_layer_enabled() -> bool {
     !matches!(std::env::var("DISABLE_INTERNAL_METRICS_TRACING_INTEGRATION"), Ok(x) if x == "true")
 }
 
 pub fn init(color: bool, json: bool, levels: &str) {
     let _ = BUFFER.set(Mutex::new(Some(Vec::new())));
-    let fmt_filter = tracing_subscriber::filter::Targets::from_str(levels).expect(
+    let targets = Targets::from_str(levels).expect(
         "logging filter targets were not formatted correctly or did not specify a valid level",
     );
+    let _ = LEVELS.set(RwLock::new(targets));
+    let fmt_filter = filter_fn(|metadata| {
+        get_levels()
+            .read()
+            .expect("log levels lock poisoned")
+            .would_enable(metadata.target(), metadata.level())
+    });
 
     let metrics_layer = metrics_layer_enabled()
         .then(|| MetricsLayer::new().with_filter(tracing_subscriber::filter::LevelFilter::INFO));
@@ -101,6 +113,22 @@ pub fn init(color: bool, json: bool, levels: &str) {
     }
 }
 
+fn get_levels() -> &'static RwLock<Targets> {
+    LEVELS.get().expect("log levels not initialized")
+}
+
+/// Replaces the filter internal log events are logged and broadcast by, given in the same format as `VECTOR_LOG`.
+pub fn set_levels(levels: &str) -> Result<(), String> {
+    let targets = Targets::from_str(levels).map_err(|error| error.to_string())?;
+    match LEVELS.get() {
+        Some(current) => {
+            *current.write().expect("log levels lock poisoned") = targets;
+            Ok(())
+        }
+        None => Err("Logging isn't initialized.".to_owned()),
+    }
+}
+
 #[cfg(test)]
 pub fn reset_early_buffer() -> Option<Vec<LogEvent>> {
     get_early_buffer().replace(Vec::new())
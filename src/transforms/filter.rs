@@ -9,7 +9,7 @@ use crate::{
         TransformDescription,
     },
     event::Event,
-    internal_events::FilterEventDiscarded,
+    internal_events::{FilterConditionMatched, FilterEventDiscarded},
     schema,
     transforms::{FunctionTransform, OutputBuffer, Transform},
 };
@@ -18,11 +18,18 @@ use crate::{
 #[serde(deny_unknown_fields)]
 pub struct FilterConfig {
     condition: AnyCondition,
+    /// Reports the number of events passing each of the disjuncts of the
+    /// condition, if it is a VRL disjunction such as `.a == 1 || .b == 2`.
+    #[serde(default)]
+    condition_metrics: bool,
 }
 
 impl From<AnyCondition> for FilterConfig {
     fn from(condition: AnyCondition) -> Self {
-        Self { condition }
+        Self {
+            condition,
+            condition_metrics: false,
+        }
     }
 }
 
@@ -44,9 +51,19 @@ impl GenerateConfig for FilterConfig {
 #[typetag::serde(name = "filter")]
 impl TransformConfig for FilterConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Filter::new(
-            self.condition.build(&context.enrichment_tables)?,
-        )))
+        let mut filter = Filter::new(self.condition.build(&context.enrichment_tables)?);
+
+        if self.condition_metrics {
+            match self.condition.build_disjuncts(&context.enrichment_tables)? {
+                Some(disjuncts) => filter.disjuncts = disjuncts,
+                None => warn!(
+                    message = "Condition isn't a VRL disjunction, no metrics are reported for its disjuncts.",
+                    condition = ?self.condition,
+                ),
+            }
+        }
+
+        Ok(Transform::function(filter))
     }
 
     fn input(&self) -> Input {
@@ -71,6 +88,10 @@ impl TransformConfig for FilterConfig {
 pub struct Filter {
     #[derivative(Debug = "ignore")]
     condition: Condition,
+    /// The disjuncts of the condition, checked in its place when reporting
+    /// the number of events passing each of them.
+    #[derivative(Debug = "ignore")]
+    disjuncts: Vec<Condition>,
     last_emission: Instant,
     emissions_max_delay: Duration,
     emissions_deferred: u64,
@@ -80,16 +101,35 @@ impl Filter {
     pub fn new(condition: Condition) -> Self {
         Self {
             condition,
+            disjuncts: Vec::new(),
             last_emission: Instant::now(),
             emissions_max_delay: Duration::new(2, 0),
             emissions_deferred: 0,
         }
     }
+
+    fn check(&self, event: &Event) -> bool {
+        if self.disjuncts.is_empty() {
+            return self.condition.check(event);
+        }
+
+        match self
+            .disjuncts
+            .iter()
+            .position(|disjunct| disjunct.check(event))
+        {
+            Some(index) => {
+                emit!(FilterConditionMatched { index });
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl FunctionTransform for Filter {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
-        if self.condition.check(&event) {
+        if self.check(&event) {
             output.push(event);
         } else if self.last_emission.elapsed() >= self.emissions_max_delay {
             emit!(FilterEventDiscarded {
@@ -109,6 +149,7 @@ mod test {
     use crate::{
         conditions::{is_log::IsLogConfig, ConditionConfig},
         event::Event,
+        log_event,
         transforms::test::transform_one,
     };
 
@@ -125,4 +166,23 @@ mod test {
         let result = transform_one(&mut filter, event).unwrap();
         assert_eq!(result.metadata(), &metadata);
     }
+
+    #[test]
+    fn checks_disjuncts_in_place_of_condition() {
+        let condition = AnyCondition::String(r#".status == 500 || .level == "error""#.to_owned());
+        let mut filter = Filter::new(condition.build(&Default::default()).unwrap());
+        filter.disjuncts = condition
+            .build_disjuncts(&Default::default())
+            .unwrap()
+            .unwrap();
+
+        let events = [
+            (log_event!["status" => 500, "level" => "info"], true),
+            (log_event!["status" => 200, "level" => "error"], true),
+            (log_event!["status" => 200, "level" => "info"], false),
+        ];
+        for (event, passes) in events {
+            assert_eq!(transform_one(&mut filter, event).is_some(), passes);
+        }
+    }
 }
@@ -1,32 +1,34 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     fs::File,
     io::{self, Read},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use value::Kind;
 use vector_common::TimeZone;
 use vector_vrl_functions::set_semantic_meaning::MeaningList;
 use vrl::{
-    diagnostic::{Formatter, Note},
+    diagnostic::{Diagnostic, Note},
     prelude::{DiagnosticMessage, ExpressionError},
     Program, Runtime, Terminate, VrlRuntime,
 };
 
 use crate::{
     config::{
-        log_schema, ComponentKey, DataType, Input, Output, TransformConfig, TransformContext,
-        TransformDescription,
+        log_schema, ComponentKey, ConfigPath, DataType, Input, Output, TransformConfig,
+        TransformContext, TransformDescription, CONFIG_PATHS,
     },
     event::{Event, TargetEvents, VrlTarget},
     internal_events::{RemapMappingAbort, RemapMappingError},
     schema,
     transforms::{SyncTransform, Transform, TransformOutputsBuf},
-    Result,
+    vrl_library, Result,
 };
 
 const DROPPED: &str = "dropped";
@@ -37,6 +39,9 @@ const DROPPED: &str = "dropped";
 pub struct RemapConfig {
     pub source: Option<String>,
     pub file: Option<PathBuf>,
+    /// The name of a program of the top-level `vrl_library` to run, in place of `source` or
+    /// `file`.
+    pub library: Option<String>,
     #[serde(default)]
     pub timezone: TimeZone,
     pub drop_on_error: bool,
@@ -45,6 +50,149 @@ pub struct RemapConfig {
     pub reroute_dropped: bool,
     #[serde(default)]
     pub runtime: VrlRuntime,
+    pub outputs: IndexMap<String, RemapOutputConfig>,
+}
+
+/// A named output of the transform, which the program sends events to by setting the `output`
+/// metadata field.
+#[derive(Deserialize, Serialize, Debug, Clone, Derivative)]
+#[serde(deny_unknown_fields, default)]
+#[derivative(Default)]
+pub struct RemapOutputConfig {
+    #[derivative(Default(value = "true"))]
+    pub logs: bool,
+    #[derivative(Default(value = "true"))]
+    pub metrics: bool,
+    #[derivative(Default(value = "true"))]
+    pub traces: bool,
+}
+
+impl RemapOutputConfig {
+    fn data_type(&self) -> DataType {
+        [
+            (self.logs, DataType::Log),
+            (self.metrics, DataType::Metric),
+            (self.traces, DataType::Trace),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .fold(DataType::none(), |data_type, (_, ty)| data_type | ty)
+    }
+}
+
+/// A program compiled by the transform, along with the outcome of its compilation.
+#[derive(Debug)]
+struct CompiledProgram {
+    program: Program,
+    warnings: String,
+    /// The schema definition of the events the program outputs.
+    schema_definition: Option<schema::Definition>,
+}
+
+/// The programs compiled by the transform, reused whenever a transform is built again with the
+/// same program and input schema, such as on configuration reloads.
+///
+/// The cache only lives as long as the process, so programs are still all compiled on startup.
+/// Compiled programs hold on to the state that the functions they call compiled their arguments
+/// into, such as regular expressions and enrichment table indexes, which has no serialized form to
+/// persist. As the cache never outlives the binary, it doesn't need to be keyed by the version of
+/// the standard library either.
+static PROGRAM_CACHE: Lazy<ProgramCache> = Lazy::new(Default::default);
+
+/// The maximum number of programs kept in the `PROGRAM_CACHE`, past which it's cleared.
+const PROGRAM_CACHE_CAPACITY: usize = 4096;
+
+#[derive(Default)]
+struct ProgramCache {
+    programs: Mutex<HashMap<String, Vec<(schema::Definition, Arc<CompiledProgram>)>>>,
+}
+
+impl ProgramCache {
+    fn get(
+        &self,
+        source: &str,
+        merged_schema_definition: &schema::Definition,
+    ) -> Option<Arc<CompiledProgram>> {
+        self.programs
+            .lock()
+            .expect("program cache lock poisoned")
+            .get(source)?
+            .iter()
+            .find(|(definition, _)| definition == merged_schema_definition)
+            .map(|(_, compiled)| Arc::clone(compiled))
+    }
+
+    fn insert(
+        &self,
+        source: String,
+        merged_schema_definition: schema::Definition,
+        compiled: Arc<CompiledProgram>,
+    ) {
+        let mut programs = self.programs.lock().expect("program cache lock poisoned");
+        if programs.len() >= PROGRAM_CACHE_CAPACITY {
+            programs.clear();
+        }
+
+        programs
+            .entry(source)
+            .or_default()
+            .push((merged_schema_definition, compiled));
+    }
+}
+
+/// Checks whether the program imports any modules. Programs that fail to parse are reported as
+/// such when they are compiled.
+fn imports_modules(source: &str) -> bool {
+    vrl::parser::parse(source).map_or(false, |program| {
+        program
+            .iter()
+            .any(|node| matches!(node.inner(), vrl::parser::ast::RootExpr::Import(_)))
+    })
+}
+
+/// Rejects the values passed to `return` outside of function definitions, as the transform doesn't
+/// use the value the program resolves to, and would otherwise discard them.
+fn reject_returned_values(program: &Program) -> std::result::Result<(), Vec<Diagnostic>> {
+    let diagnostics = program
+        .returned_values()
+        .iter()
+        .map(|span| {
+            Diagnostic::error(133, "unused return value")
+                .with_primary("the remap transform discards this value", *span)
+                .with_note(Note::Hint(
+                    "assign to the event instead, for example `. = value`, and then `return`"
+                        .to_owned(),
+                ))
+        })
+        .collect::<Vec<_>>();
+
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(diagnostics)
+    }
+}
+
+/// The schema definition of the events output by a program compiled against the given state.
+fn output_schema_definition(state: &vrl::state::ExternalEnv) -> Option<schema::Definition> {
+    let meaning = state
+        .get_external_context::<MeaningList>()
+        .cloned()
+        .expect("context exists")
+        .0;
+
+    state
+        .target_kind()
+        .clone()
+        .into_object()
+        .map(schema::Definition::from)
+        .map(|mut def| {
+            for (id, path) in meaning {
+                def.register_known_meaning(path, &id)
+            }
+
+            def
+        })
 }
 
 impl RemapConfig {
@@ -52,15 +200,10 @@ impl RemapConfig {
         &self,
         enrichment_tables: enrichment::TableRegistry,
         merged_schema_definition: schema::Definition,
-    ) -> Result<(
-        vrl::Program,
-        String,
-        Vec<Box<dyn vrl::Function>>,
-        vrl::state::ExternalEnv,
-    )> {
-        let source = match (&self.source, &self.file) {
-            (Some(source), None) => source.to_owned(),
-            (None, Some(path)) => {
+    ) -> Result<Arc<CompiledProgram>> {
+        let source = match (&self.source, &self.file, &self.library) {
+            (Some(source), None, None) => source.to_owned(),
+            (None, Some(path), None) => {
                 let mut buffer = String::new();
 
                 File::open(path)
@@ -70,9 +213,21 @@ impl RemapConfig {
 
                 buffer
             }
+            (None, None, Some(name)) => vrl_library::get(name)?.source.clone(),
             _ => return Err(Box::new(BuildError::SourceAndOrFile)),
         };
 
+        // Programs that import modules are never cached, as the modules may have changed since,
+        // and neither are the programs compiled against enrichment tables, as compiling them
+        // registers the indexes they need with the tables being loaded.
+        let cacheable = enrichment_tables.table_ids().is_empty() && !imports_modules(&source);
+        if cacheable {
+            if let Some(compiled) = PROGRAM_CACHE.get(&source, &merged_schema_definition) {
+                return Ok(compiled);
+            }
+        }
+        let cache_key = cacheable.then(|| (source.clone(), merged_schema_definition.clone()));
+
         let mut functions = vrl_stdlib::all();
         functions.append(&mut enrichment::vrl_functions());
         functions.append(&mut vector_vrl_functions::vrl_functions());
@@ -81,21 +236,55 @@ impl RemapConfig {
         state.set_external_context(enrichment_tables);
         state.set_external_context(MeaningList::default());
 
-        vrl::compile_with_state(&source, &functions, &mut state)
+        vrl::compile_with_imports(&source, &functions, &mut state, &self.import_directory())
             .map_err(|diagnostics| {
-                Formatter::new(&source, diagnostics)
+                vrl::formatter_with_imports(&source, diagnostics)
                     .colored()
                     .to_string()
                     .into()
             })
-            .map(|(program, diagnostics)| {
-                (
+            .and_then(|(program, diagnostics)| {
+                reject_returned_values(&program).map_err(|diagnostics| {
+                    vrl::formatter_with_imports(&source, diagnostics)
+                        .colored()
+                        .to_string()
+                })?;
+
+                let compiled = Arc::new(CompiledProgram {
                     program,
-                    Formatter::new(&source, diagnostics).to_string(),
-                    functions,
-                    state,
-                )
+                    warnings: vrl::formatter_with_imports(&source, diagnostics).to_string(),
+                    schema_definition: output_schema_definition(&state),
+                });
+
+                if let Some((source, merged_schema_definition)) = cache_key {
+                    PROGRAM_CACHE.insert(source, merged_schema_definition, Arc::clone(&compiled));
+                }
+
+                Ok(compiled)
+            })
+    }
+
+    /// The directory against which `import` statements are resolved.
+    ///
+    /// Imports are relative to the program file if one is configured, and to
+    /// the directory of the first config file otherwise.
+    fn import_directory(&self) -> PathBuf {
+        if let Some(directory) = self.file.as_ref().and_then(|path| path.parent()) {
+            return directory.to_owned();
+        }
+
+        CONFIG_PATHS
+            .lock()
+            .ok()
+            .and_then(|config_paths| {
+                config_paths.first().map(|config_path| match config_path {
+                    ConfigPath::File(path, _format) => {
+                        path.parent().map(Path::to_owned).unwrap_or_default()
+                    }
+                    ConfigPath::Dir(path) => path.clone(),
+                })
             })
+            .unwrap_or_default()
     }
 }
 
@@ -131,6 +320,32 @@ impl TransformConfig for RemapConfig {
         Input::all()
     }
 
+    fn validate(&self, _: &schema::Definition) -> std::result::Result<(), Vec<String>> {
+        let errors = self
+            .outputs
+            .iter()
+            .filter_map(|(name, output)| {
+                if name == DROPPED {
+                    Some(format!(
+                        "cannot have a named output with reserved name: `{DROPPED}`"
+                    ))
+                } else if output.data_type().is_none() {
+                    Some(format!(
+                        "named output `{name}` must accept at least one type of event"
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     fn outputs(&self, merged_definition: &schema::Definition) -> Vec<Output> {
         // We need to compile the VRL program in order to know the schema definition output of this
         // transform. We ignore any compilation errors, as those are caught by the transform build
@@ -141,26 +356,7 @@ impl TransformConfig for RemapConfig {
                 merged_definition.clone(),
             )
             .ok()
-            .and_then(|(_, _, _, state)| {
-                let meaning = state
-                    .get_external_context::<MeaningList>()
-                    .cloned()
-                    .expect("context exists")
-                    .0;
-
-                state
-                    .target_kind()
-                    .clone()
-                    .into_object()
-                    .map(schema::Definition::from)
-                    .map(|mut def| {
-                        for (id, path) in meaning {
-                            def.register_known_meaning(path, &id)
-                        }
-
-                        def
-                    })
-            })
+            .and_then(|compiled| compiled.schema_definition.clone())
             .unwrap_or_else(schema::Definition::empty);
 
         // When a message is dropped and re-routed, we keep the original event, but also annotate
@@ -177,19 +373,29 @@ impl TransformConfig for RemapConfig {
             Some("metadata"),
         );
 
+        // Events sent to a named output are processed by the same program as the ones sent to the
+        // default output, so they share its schema definition.
+        let named_outputs = self.outputs.iter().map(|(name, output)| {
+            Output::default(output.data_type())
+                .with_schema_definition(default_definition.clone())
+                .with_port(name)
+        });
+
         let default_output =
-            Output::default(DataType::all()).with_schema_definition(default_definition);
+            Output::default(DataType::all()).with_schema_definition(default_definition.clone());
+
+        let mut outputs = vec![default_output];
+        outputs.extend(named_outputs);
 
         if self.reroute_dropped {
-            vec![
-                default_output,
+            outputs.push(
                 Output::default(DataType::all())
                     .with_schema_definition(dropped_definition)
                     .with_port(DROPPED),
-            ]
-        } else {
-            vec![default_output]
+            );
         }
+
+        outputs
     }
 
     fn transform_type(&self) -> &'static str {
@@ -214,9 +420,16 @@ where
     reroute_dropped: bool,
     default_schema_definition: Arc<schema::Definition>,
     dropped_schema_definition: Arc<schema::Definition>,
+    outputs: HashMap<String, NamedOutput>,
     runner: Runner,
 }
 
+#[derive(Debug, Clone)]
+struct NamedOutput {
+    data_type: DataType,
+    schema_definition: Arc<schema::Definition>,
+}
+
 pub trait VrlRunner {
     fn run(
         &mut self,
@@ -257,10 +470,18 @@ impl Remap<AstRunner> {
         config: RemapConfig,
         context: &TransformContext,
     ) -> crate::Result<(Self, String)> {
-        let (program, warnings, _, _) = config.compile_vrl_program(
-            context.enrichment_tables.clone(),
-            context.merged_schema_definition.clone(),
-        )?;
+        // Library programs are compiled once, when the topology is built, and shared by all the
+        // transforms referring to them.
+        let (program, warnings) = match (&config.source, &config.file, &config.library) {
+            (None, None, Some(name)) => (vrl_library::get(name)?.program.clone(), String::new()),
+            _ => {
+                let compiled = config.compile_vrl_program(
+                    context.enrichment_tables.clone(),
+                    context.merged_schema_definition.clone(),
+                )?;
+                (compiled.program.clone(), compiled.warnings.clone())
+            }
+        };
 
         let runtime = Runtime::default();
         let runner = AstRunner { runtime };
@@ -292,6 +513,25 @@ where
             .expect("dropped schema required")
             .clone();
 
+        let outputs = config
+            .outputs
+            .iter()
+            .map(|(name, output)| {
+                let schema_definition = context
+                    .schema_definitions
+                    .get(&Some(name.clone()))
+                    .unwrap_or(&default_schema_definition)
+                    .clone();
+
+                let output = NamedOutput {
+                    data_type: output.data_type(),
+                    schema_definition: Arc::new(schema_definition),
+                };
+
+                (name.clone(), output)
+            })
+            .collect();
+
         Ok(Remap {
             component_key: context.key.clone(),
             program,
@@ -301,6 +541,7 @@ where
             reroute_dropped: config.reroute_dropped,
             default_schema_definition: Arc::new(default_schema_definition),
             dropped_schema_definition: Arc::new(dropped_schema_definition),
+            outputs,
             runner,
         })
     }
@@ -362,6 +603,42 @@ where
     fn run_vrl(&mut self, target: &mut VrlTarget) -> std::result::Result<value::Value, Terminate> {
         self.runner.run(target, &self.program, &self.timezone)
     }
+
+    /// Send an event the program processed to the named output set in its metadata, or to the
+    /// default output if none is set.
+    ///
+    /// An event sent to an output that doesn't exist, or doesn't accept its type, is handled as if
+    /// the program failed with an error.
+    fn push_output(&self, mut event: Event, output: &mut TransformOutputsBuf) {
+        let name = match event.metadata_mut().take_output() {
+            Some(name) => name,
+            None => return push_default(event, output, &self.default_schema_definition),
+        };
+
+        let error = match self.outputs.get(name.as_ref()) {
+            Some(named) if named.data_type.contains(data_type(&event)) => {
+                return push_named(&name, event, output, &named.schema_definition);
+            }
+            Some(_) => format!(
+                "output {:?} does not accept {} events",
+                name,
+                data_type(&event)
+            ),
+            None => format!("output {:?} does not exist", name),
+        };
+
+        emit!(RemapMappingError {
+            error: error.clone(),
+            event_dropped: self.drop_on_error,
+        });
+
+        if !self.drop_on_error {
+            push_default(event, output, &self.default_schema_definition);
+        } else if self.reroute_dropped {
+            self.annotate_dropped(&mut event, "error", error.into());
+            push_dropped(event, output, &self.dropped_schema_definition);
+        }
+    }
 }
 
 impl<Runner> SyncTransform for Remap<Runner>
@@ -393,14 +670,16 @@ where
         let result = self.run_vrl(&mut target);
 
         match result {
+            // The value the program resolves to isn't used, as programs change events by assigning
+            // to them. Values passed to `return` are rejected when the program is compiled.
             Ok(_) => match target.into_events() {
-                TargetEvents::One(event) => {
-                    push_default(event, output, &self.default_schema_definition)
+                TargetEvents::One(event) => self.push_output(event, output),
+                TargetEvents::Logs(events) => {
+                    events.for_each(|event| self.push_output(event, output))
+                }
+                TargetEvents::Traces(events) => {
+                    events.for_each(|event| self.push_output(event, output))
                 }
-                TargetEvents::Logs(events) => events
-                    .for_each(|event| push_default(event, output, &self.default_schema_definition)),
-                TargetEvents::Traces(events) => events
-                    .for_each(|event| push_default(event, output, &self.default_schema_definition)),
             },
             Err(reason) => {
                 let (reason, error, drop) = match reason {
@@ -449,6 +728,20 @@ fn push_default(
     output.push(event)
 }
 
+#[inline]
+fn push_named(
+    name: &str,
+    mut event: Event,
+    output: &mut TransformOutputsBuf,
+    schema_definition: &Arc<schema::Definition>,
+) {
+    event
+        .metadata_mut()
+        .set_schema_definition(schema_definition);
+
+    output.push_named(name, event)
+}
+
 #[inline]
 fn push_dropped(
     mut event: Event,
@@ -462,9 +755,17 @@ fn push_dropped(
     output.push_named(DROPPED, event)
 }
 
+const fn data_type(event: &Event) -> DataType {
+    match event {
+        Event::Log(_) => DataType::Log,
+        Event::Metric(_) => DataType::Metric,
+        Event::Trace(_) => DataType::Trace,
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum BuildError {
-    #[snafu(display("must provide exactly one of `source` or `file` configuration"))]
+    #[snafu(display("must provide exactly one of `source`, `file` or `library` configuration"))]
     SourceAndOrFile,
 
     #[snafu(display("Could not open vrl program {:?}: {}", path, source))]
@@ -535,7 +836,7 @@ mod tests {
         let err = remap(config).unwrap_err().to_string();
         assert_eq!(
             &err,
-            "must provide exactly one of `source` or `file` configuration"
+            "must provide exactly one of `source`, `file` or `library` configuration"
         )
     }
 
@@ -550,10 +851,66 @@ mod tests {
         let err = remap(config).unwrap_err().to_string();
         assert_eq!(
             &err,
-            "must provide exactly one of `source` or `file` configuration"
+            "must provide exactly one of `source`, `file` or `library` configuration"
         )
     }
 
+    #[test]
+    fn config_both_source_and_library() {
+        let config = RemapConfig {
+            source: Some("".to_owned()),
+            library: Some("parse_message".to_owned()),
+            ..Default::default()
+        };
+
+        let err = remap(config).unwrap_err().to_string();
+        assert_eq!(
+            &err,
+            "must provide exactly one of `source`, `file` or `library` configuration"
+        )
+    }
+
+    #[test]
+    fn config_unknown_library() {
+        let config = RemapConfig {
+            library: Some("unknown-remap-library-program".to_owned()),
+            ..Default::default()
+        };
+
+        let err = remap(config).unwrap_err().to_string();
+        assert_eq!(
+            &err,
+            r#"unknown VRL library program "unknown-remap-library-program""#
+        )
+    }
+
+    #[test]
+    fn reuses_compiled_programs() {
+        let config = RemapConfig {
+            source: Some(".reused = true".to_owned()),
+            ..Default::default()
+        };
+
+        let first = config
+            .compile_vrl_program(Default::default(), test_default_schema_definition())
+            .unwrap();
+        let second = config
+            .compile_vrl_program(Default::default(), test_default_schema_definition())
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let other_schema = config
+            .compile_vrl_program(Default::default(), schema::Definition::empty())
+            .unwrap();
+        assert!(!Arc::ptr_eq(&first, &other_schema));
+    }
+
+    #[test]
+    fn does_not_cache_programs_importing_modules() {
+        assert!(imports_modules("import \"module.vrl\"\n.foo = 1"));
+        assert!(!imports_modules(".foo = 1"));
+    }
+
     fn get_field_string(event: &Event, field: &str) -> String {
         event.as_log().get(field).unwrap().to_string_lossy()
     }
@@ -599,6 +956,120 @@ mod tests {
         assert!(tform.runner().runtime.is_empty());
     }
 
+    #[test]
+    fn check_remap_imports_relative_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("helpers.vrl"),
+            "suffix = \"!\"\nfn shout(value) { upcase(value) }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("program.vrl"),
+            "import \"helpers.vrl\"\n.message = shout(string!(.message)) + suffix\n",
+        )
+        .unwrap();
+
+        let conf = RemapConfig {
+            source: None,
+            file: Some(dir.path().join("program.vrl")),
+            drop_on_error: true,
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+        let result = transform_one(&mut tform, Event::from(LogEvent::from("hello"))).unwrap();
+        assert_eq!(get_field_string(&result, "message"), "HELLO!");
+    }
+
+    #[test]
+    fn check_remap_import_errors_point_into_module() {
+        let dir = tempfile::tempdir().unwrap();
+        // The module is longer than the importing program, so an error rendered against the
+        // importing program would point past its end.
+        std::fs::write(
+            dir.path().join("helpers.vrl"),
+            indoc! {r#"
+                # Helpers shared between programs.
+                #
+                # Each function expects to be passed a string.
+                suffix = "!"
+
+                fn shout(value) {
+                    uppercase(value) + suffix
+                }
+            "#},
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("program.vrl"),
+            "import \"helpers.vrl\"\n.message = shout(.message)\n",
+        )
+        .unwrap();
+
+        let conf = RemapConfig {
+            source: None,
+            file: Some(dir.path().join("program.vrl")),
+            ..Default::default()
+        };
+        let err = remap(conf).unwrap_err().to_string();
+        let err = String::from_utf8(strip_ansi_escapes::strip(err).unwrap()).unwrap();
+
+        let module = std::fs::canonicalize(dir.path().join("helpers.vrl")).unwrap();
+        assert!(
+            err.contains(&format!("{}:7:5", module.display())),
+            "{}",
+            err
+        );
+        assert!(err.contains("uppercase(value) + suffix"), "{}", err);
+    }
+
+    #[test]
+    fn check_remap_rejects_returned_values() {
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                fn skip(value) {
+                    return value
+                }
+                if .skip == true {
+                    return .
+                }
+                .message = skip(.message)
+            "#}
+                .to_owned(),
+            ),
+            ..Default::default()
+        };
+        let err = remap(conf).unwrap_err().to_string();
+        let err = String::from_utf8(strip_ansi_escapes::strip(err).unwrap()).unwrap();
+        assert!(err.contains("error[E133]: unused return value"), "{}", err);
+        assert!(err.contains("return ."), "{}", err);
+        assert_eq!(err.matches("E133").count(), 1, "{}", err);
+
+        // Returning without a value, or from a function, is fine.
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                fn skip(value) {
+                    return value
+                }
+                if .skip == true {
+                    .message = "skipped"
+                    return
+                }
+                .message = skip(.message)
+            "#}
+                .to_owned(),
+            ),
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+        let mut log = LogEvent::from("hello");
+        log.insert("skip", true);
+        let result = transform_one(&mut tform, Event::from(log)).unwrap();
+        assert_eq!(get_field_string(&result, "message"), "skipped");
+    }
+
     #[test]
     fn check_remap_adds() {
         let event = {
@@ -1208,6 +1679,97 @@ mod tests {
         assert!(out.named[DROPPED].is_empty());
     }
 
+    #[test]
+    fn check_remap_named_outputs() {
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                if .tags.level == "error" {
+                    set_metadata_field("output", "errors")
+                } else if .tags.level == "debug" {
+                    set_metadata_field("output", "debug")
+                }
+            "#}
+                .to_owned(),
+            ),
+            drop_on_error: true,
+            reroute_dropped: true,
+            outputs: IndexMap::from([(
+                "errors".to_owned(),
+                RemapOutputConfig {
+                    metrics: false,
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![None, Some("errors".to_owned()), Some(DROPPED.to_owned())],
+            conf.outputs(&schema::Definition::empty())
+                .into_iter()
+                .map(|output| output.port)
+                .collect::<Vec<_>>(),
+        );
+
+        let context = TransformContext {
+            key: Some(ComponentKey::from("remapper")),
+            ..Default::default()
+        };
+        let mut tform = Remap::new_ast(conf, &context).unwrap().0;
+
+        let mut transform = |event: serde_json::Value| {
+            let mut outputs = TransformOutputsBuf::new_with_capacity(
+                vec![
+                    Output::default(DataType::all()),
+                    Output::default(DataType::Log | DataType::Trace).with_port("errors"),
+                    Output::default(DataType::all()).with_port(DROPPED),
+                ],
+                1,
+            );
+
+            tform.transform(Event::try_from(event).unwrap(), &mut outputs);
+
+            CollectedOuput {
+                primary: outputs.take_primary(),
+                named: outputs.take_all_named(),
+            }
+        };
+
+        let out = transform(serde_json::json!({"tags": {"level": "info"}}));
+        assert_eq!(out.primary.len(), 1);
+        assert!(out.named["errors"].is_empty());
+
+        let out = transform(serde_json::json!({"tags": {"level": "error"}}));
+        assert!(out.primary.is_empty());
+        let event = out.named["errors"].clone().into_events().next().unwrap();
+        assert_eq!(event.metadata().output(), &None);
+
+        let out = transform(serde_json::json!({"tags": {"level": "debug"}}));
+        assert!(out.primary.is_empty());
+        assert!(out.named["errors"].is_empty());
+        let event = out.named[DROPPED].clone().into_events().next().unwrap();
+        assert_eq!(
+            event.as_log()["metadata.dropped.message"],
+            r#"output "debug" does not exist"#.into()
+        );
+
+        let metric = Event::Metric(
+            Metric::new(
+                "counter",
+                MetricKind::Absolute,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(BTreeMap::from([(
+                "level".to_owned(),
+                "error".to_owned(),
+            )]))),
+        );
+        let out = collect_outputs(&mut tform, metric);
+        assert!(out.primary.is_empty());
+        assert_eq!(out.named[DROPPED].len(), 1);
+    }
+
     #[tokio::test]
     async fn check_remap_branching_metrics_with_output() {
         init_test();
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{
+        runtime_options::{self, RuntimeOption},
         DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
         TransformDescription,
     },
@@ -17,6 +19,8 @@ use crate::{
 pub struct SampleConfig {
     pub rate: u64,
     pub key_field: Option<String>,
+    #[serde(default)]
+    pub hash_seed: Option<u64>,
     pub exclude: Option<AnyCondition>,
 }
 
@@ -33,6 +37,7 @@ impl GenerateConfig for SampleConfig {
         toml::Value::try_from(Self {
             rate: 10,
             key_field: None,
+            hash_seed: None,
             exclude: None::<AnyCondition>,
         })
         .unwrap()
@@ -43,14 +48,22 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
-            self.rate,
-            self.key_field.clone(),
-            self.exclude
-                .as_ref()
-                .map(|condition| condition.build(&context.enrichment_tables))
-                .transpose()?,
-        )))
+        let rate_updates = context
+            .key
+            .as_ref()
+            .map(|key| runtime_options::register(key, RuntimeOption::SampleRate, self.rate));
+        Ok(Transform::function(
+            Sample::new(
+                self.rate,
+                self.key_field.clone(),
+                self.exclude
+                    .as_ref()
+                    .map(|condition| condition.build(&context.enrichment_tables))
+                    .transpose()?,
+            )
+            .with_hash_seed(self.hash_seed)
+            .with_rate_updates(rate_updates),
+        ))
     }
 
     fn input(&self) -> Input {
@@ -94,8 +107,10 @@ impl TransformConfig for SampleCompatConfig {
 pub struct Sample {
     rate: u64,
     key_field: Option<String>,
+    hash_seed: Option<u64>,
     exclude: Option<Condition>,
     count: u64,
+    rate_updates: Option<watch::Receiver<u64>>,
 }
 
 impl Sample {
@@ -103,14 +118,40 @@ impl Sample {
         Self {
             rate,
             key_field,
+            hash_seed: None,
             exclude,
             count: 0,
+            rate_updates: None,
+        }
+    }
+
+    /// Seeds the hash of the `key_field` values, so that instances sharing the same seed sample the
+    /// same values, independently of the instances using other seeds.
+    pub fn with_hash_seed(mut self, hash_seed: Option<u64>) -> Self {
+        self.hash_seed = hash_seed;
+        self
+    }
+
+    fn hash(&self, value: &[u8]) -> u64 {
+        match self.hash_seed {
+            Some(seed) => seahash::hash_seeded(value, seed, seed, seed, seed),
+            None => seahash::hash(value),
         }
     }
+
+    /// Follows the rate set at runtime through the given receiver.
+    pub fn with_rate_updates(mut self, rate_updates: Option<watch::Receiver<u64>>) -> Self {
+        self.rate_updates = rate_updates;
+        self
+    }
 }
 
 impl FunctionTransform for Sample {
     fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        if let Some(rate_updates) = &self.rate_updates {
+            self.rate = *rate_updates.borrow();
+        }
+
         if let Some(condition) = self.exclude.as_ref() {
             if condition.check(&event) {
                 output.push(event);
@@ -125,7 +166,7 @@ impl FunctionTransform for Sample {
             .map(|v| v.to_string_lossy());
 
         let num = if let Some(value) = value {
-            seahash::hash(value.as_bytes())
+            self.hash(value.as_bytes())
         } else {
             self.count
         };
@@ -332,6 +373,62 @@ mod tests {
         }
     }
 
+    fn sampled_users(sampler: &mut Sample, events: &[Event]) -> Vec<String> {
+        events
+            .iter()
+            .filter_map(|event| transform_one(sampler, event.clone()))
+            .map(|event| event.as_log()["user_id"].to_string_lossy())
+            .collect()
+    }
+
+    fn user_events() -> Vec<Event> {
+        (0..3)
+            .flat_map(|_| 0..1000)
+            .map(|user| {
+                let mut event = Event::from("message");
+                event
+                    .as_mut_log()
+                    .insert("user_id", format!("user-{}", user));
+                event
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hash_samples_the_same_keys_across_instances() {
+        let events = user_events();
+
+        let mut sampled = (0..2).map(|_| {
+            let mut sampler =
+                Sample::new(10, Some("user_id".into()), None).with_hash_seed(Some(42));
+            sampled_users(&mut sampler, &events)
+        });
+        let first_instance = sampled.next().unwrap();
+        let second_instance = sampled.next().unwrap();
+        assert_eq!(first_instance, second_instance);
+
+        // All the events of a sampled user are kept together.
+        for user in &first_instance {
+            assert_eq!(first_instance.iter().filter(|u| *u == user).count(), 3);
+        }
+    }
+
+    #[test]
+    fn hash_seed_samples_other_keys() {
+        let events = user_events();
+
+        let mut unseeded = Sample::new(10, Some("user_id".into()), None);
+        let mut seeded = Sample::new(10, Some("user_id".into()), None).with_hash_seed(Some(42));
+        let mut other_seed = Sample::new(10, Some("user_id".into()), None).with_hash_seed(Some(7));
+
+        let unseeded = sampled_users(&mut unseeded, &events);
+        let seeded = sampled_users(&mut seeded, &events);
+        let other_seed = sampled_users(&mut other_seed, &events);
+        assert!(!seeded.is_empty());
+        assert_ne!(seeded, unseeded);
+        assert_ne!(seeded, other_seed);
+    }
+
     fn random_events(n: usize) -> Vec<Event> {
         random_lines(10).take(n).map(Event::from).collect()
     }
@@ -8,6 +8,7 @@ use std::{
 
 use bloom::{BloomFilter, ASMS};
 use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -36,6 +37,23 @@ pub struct TagCardinalityLimitConfig {
 
     #[serde(flatten)]
     pub mode: Mode,
+
+    #[serde(default)]
+    pub per_metric_limits: IndexMap<String, PerMetricLimitConfig>,
+
+    #[serde(default)]
+    pub allowed_values: IndexMap<String, Vec<String>>,
+
+    #[serde(default)]
+    pub denied_values: IndexMap<String, Vec<String>>,
+}
+
+/// The limit applying to the metrics whose name matches a pattern, in place of the default one.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PerMetricLimitConfig {
+    pub value_limit: Option<u32>,
+    pub limit_exceeded_action: Option<LimitExceededAction>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -51,7 +69,7 @@ pub struct BloomFilterConfig {
     pub cache_size_per_key: usize,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum LimitExceededAction {
     DropTag,
@@ -60,8 +78,11 @@ pub enum LimitExceededAction {
 
 #[derive(Debug)]
 pub struct TagCardinalityLimit {
-    config: TagCardinalityLimitConfig,
-    accepted_tags: HashMap<String, TagValueSet>,
+    mode: Mode,
+    accepted_tags: AcceptedTags,
+    per_metric_limits: Vec<(glob::Pattern, AcceptedTags)>,
+    allowed_values: HashMap<String, HashSet<String>>,
+    denied_values: HashMap<String, HashSet<String>>,
 }
 
 const fn default_limit_exceeded_action() -> LimitExceededAction {
@@ -86,6 +107,9 @@ impl GenerateConfig for TagCardinalityLimitConfig {
             mode: Mode::Exact,
             value_limit: default_value_limit(),
             limit_exceeded_action: default_limit_exceeded_action(),
+            per_metric_limits: IndexMap::new(),
+            allowed_values: IndexMap::new(),
+            denied_values: IndexMap::new(),
         })
         .unwrap()
     }
@@ -97,7 +121,7 @@ impl TransformConfig for TagCardinalityLimitConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
         Ok(Transform::event_task(TagCardinalityLimit::new(
             self.clone(),
-        )))
+        )?))
     }
 
     fn input(&self) -> Input {
@@ -178,11 +202,20 @@ impl TagValueSet {
     }
 }
 
-impl TagCardinalityLimit {
-    fn new(config: TagCardinalityLimitConfig) -> Self {
+/// The values accepted for each tag key of the metrics a limit applies to.
+#[derive(Debug)]
+struct AcceptedTags {
+    value_limit: u32,
+    limit_exceeded_action: LimitExceededAction,
+    tags: HashMap<String, TagValueSet>,
+}
+
+impl AcceptedTags {
+    fn new(value_limit: u32, limit_exceeded_action: LimitExceededAction) -> Self {
         Self {
-            config,
-            accepted_tags: HashMap::new(),
+            value_limit,
+            limit_exceeded_action,
+            tags: HashMap::new(),
         }
     }
 
@@ -194,14 +227,12 @@ impl TagCardinalityLimit {
     /// for the key and returns true, otherwise returns false.  A false return
     /// value indicates to the caller that the value is not accepted for this
     /// key, and the configured limit_exceeded_action should be taken.
-    fn try_accept_tag(&mut self, key: &str, value: Cow<'_, String>) -> bool {
-        if !self.accepted_tags.contains_key(key) {
-            self.accepted_tags.insert(
-                key.to_string(),
-                TagValueSet::new(self.config.value_limit, &self.config.mode),
-            );
+    fn try_accept_tag(&mut self, key: &str, value: Cow<'_, String>, mode: &Mode) -> bool {
+        if !self.tags.contains_key(key) {
+            self.tags
+                .insert(key.to_string(), TagValueSet::new(self.value_limit, mode));
         }
-        let tag_value_set = self.accepted_tags.get_mut(key).unwrap();
+        let tag_value_set = self.tags.get_mut(key).unwrap();
 
         if tag_value_set.contains(value.clone()) {
             // Tag value has already been accepted, nothing more to do.
@@ -209,11 +240,11 @@ impl TagCardinalityLimit {
         }
 
         // Tag value not yet part of the accepted set.
-        if tag_value_set.len() < self.config.value_limit as usize {
+        if tag_value_set.len() < self.value_limit as usize {
             // accept the new value
             tag_value_set.insert(value);
 
-            if tag_value_set.len() == self.config.value_limit as usize {
+            if tag_value_set.len() == self.value_limit as usize {
                 emit!(TagCardinalityValueLimitReached { key });
             }
 
@@ -223,15 +254,95 @@ impl TagCardinalityLimit {
             false
         }
     }
+}
+
+impl TagCardinalityLimit {
+    fn new(config: TagCardinalityLimitConfig) -> crate::Result<Self> {
+        let per_metric_limits = config
+            .per_metric_limits
+            .iter()
+            .map(|(pattern, limit)| {
+                let pattern = glob::Pattern::new(pattern).map_err(|error| {
+                    format!("invalid metric name pattern {:?}: {}", pattern, error)
+                })?;
+                let accepted_tags = AcceptedTags::new(
+                    limit.value_limit.unwrap_or(config.value_limit),
+                    limit
+                        .limit_exceeded_action
+                        .unwrap_or(config.limit_exceeded_action),
+                );
+                Ok((pattern, accepted_tags))
+            })
+            .collect::<crate::Result<_>>()?;
+
+        let value_sets = |values: IndexMap<String, Vec<String>>| {
+            values
+                .into_iter()
+                .map(|(key, values)| (key, values.into_iter().collect()))
+                .collect()
+        };
+
+        Ok(Self {
+            accepted_tags: AcceptedTags::new(config.value_limit, config.limit_exceeded_action),
+            per_metric_limits,
+            allowed_values: value_sets(config.allowed_values),
+            denied_values: value_sets(config.denied_values),
+            mode: config.mode,
+        })
+    }
+
+    /// Checks whether the tag value is accepted, either because it's explicitly allowed, or
+    /// because it fits within the limit of the tag key. Explicitly denied values are never
+    /// accepted, and allowed values don't count towards the limit.
+    fn try_accept_tag(
+        accepted_tags: &mut AcceptedTags,
+        allowed_values: &HashMap<String, HashSet<String>>,
+        denied_values: &HashMap<String, HashSet<String>>,
+        mode: &Mode,
+        key: &str,
+        value: Cow<'_, String>,
+    ) -> bool {
+        let listed = |values: &HashMap<String, HashSet<String>>| {
+            values
+                .get(key)
+                .map_or(false, |values| values.contains(value.as_str()))
+        };
+
+        if listed(denied_values) {
+            false
+        } else if listed(allowed_values) {
+            true
+        } else {
+            accepted_tags.try_accept_tag(key, value, mode)
+        }
+    }
 
     fn transform_one(&mut self, mut event: Event) -> Option<Event> {
         let metric = event.as_mut_metric();
+        let metric_name = metric.name();
+        let accepted_tags = match self
+            .per_metric_limits
+            .iter_mut()
+            .find(|(pattern, _)| pattern.matches(metric_name))
+        {
+            Some((_, accepted_tags)) => accepted_tags,
+            None => &mut self.accepted_tags,
+        };
+
         if let Some(tags_map) = metric.tags() {
-            match self.config.limit_exceeded_action {
+            match accepted_tags.limit_exceeded_action {
                 LimitExceededAction::DropEvent => {
                     for (key, value) in tags_map {
-                        if !self.try_accept_tag(key, Cow::Borrowed(value)) {
+                        if !Self::try_accept_tag(
+                            accepted_tags,
+                            &self.allowed_values,
+                            &self.denied_values,
+                            &self.mode,
+                            key,
+                            Cow::Borrowed(value),
+                        ) {
                             emit!(TagCardinalityLimitRejectingEvent {
+                                metric_name,
                                 tag_key: key,
                                 tag_value: value,
                             });
@@ -242,8 +353,16 @@ impl TagCardinalityLimit {
                 LimitExceededAction::DropTag => {
                     let mut to_delete = Vec::new();
                     for (key, value) in tags_map {
-                        if !self.try_accept_tag(key, Cow::Borrowed(value)) {
+                        if !Self::try_accept_tag(
+                            accepted_tags,
+                            &self.allowed_values,
+                            &self.denied_values,
+                            &self.mode,
+                            key,
+                            Cow::Borrowed(value),
+                        ) {
                             emit!(TagCardinalityLimitRejectingTag {
+                                metric_name,
                                 tag_key: key,
                                 tag_value: value,
                             });
@@ -289,9 +408,13 @@ mod tests {
     }
 
     fn make_metric(tags: BTreeMap<String, String>) -> Event {
+        make_named_metric("event", tags)
+    }
+
+    fn make_named_metric(name: &str, tags: BTreeMap<String, String>) -> Event {
         Event::Metric(
             Metric::new(
-                "event",
+                name,
                 metric::MetricKind::Incremental,
                 metric::MetricValue::Counter { value: 1.0 },
             )
@@ -307,7 +430,11 @@ mod tests {
             value_limit,
             limit_exceeded_action,
             mode: Mode::Exact,
+            per_metric_limits: IndexMap::new(),
+            allowed_values: IndexMap::new(),
+            denied_values: IndexMap::new(),
         })
+        .unwrap()
     }
 
     fn make_transform_bloom(
@@ -320,7 +447,11 @@ mod tests {
             mode: Mode::Probabilistic(BloomFilterConfig {
                 cache_size_per_key: default_cache_size(),
             }),
+            per_metric_limits: IndexMap::new(),
+            allowed_values: IndexMap::new(),
+            denied_values: IndexMap::new(),
         })
+        .unwrap()
     }
 
     #[test]
@@ -452,4 +583,91 @@ mod tests {
         assert_eq!(new_event2, event2);
         assert_eq!(new_event3, event3);
     }
+
+    #[test]
+    fn tag_cardinality_limit_per_metric_limits() {
+        let mut transform = TagCardinalityLimit::new(TagCardinalityLimitConfig {
+            value_limit: 1,
+            limit_exceeded_action: LimitExceededAction::DropTag,
+            mode: Mode::Exact,
+            per_metric_limits: IndexMap::from([(
+                "http_*".to_owned(),
+                PerMetricLimitConfig {
+                    value_limit: Some(2),
+                    limit_exceeded_action: Some(LimitExceededAction::DropEvent),
+                },
+            )]),
+            allowed_values: IndexMap::new(),
+            denied_values: IndexMap::new(),
+        })
+        .unwrap();
+
+        let tags = |value: &str| BTreeMap::from([("tag1".to_owned(), value.to_owned())]);
+
+        // Metrics matching the pattern accept two values, and are dropped past these.
+        for value in ["val1", "val2"] {
+            let event = make_named_metric("http_requests", tags(value));
+            assert_eq!(transform.transform_one(event.clone()), Some(event));
+        }
+        let event = make_named_metric("http_errors", tags("val3"));
+        assert_eq!(transform.transform_one(event), None);
+
+        // Other metrics accept a single value, separately from the ones matching the pattern.
+        let event = make_metric(tags("val3"));
+        assert_eq!(transform.transform_one(event.clone()), Some(event));
+        let event = transform.transform_one(make_metric(tags("val1"))).unwrap();
+        assert!(event.as_metric().tags().is_none());
+    }
+
+    #[test]
+    fn tag_cardinality_limit_allowed_and_denied_values() {
+        let mut transform = TagCardinalityLimit::new(TagCardinalityLimitConfig {
+            value_limit: 1,
+            limit_exceeded_action: LimitExceededAction::DropTag,
+            mode: Mode::Exact,
+            per_metric_limits: IndexMap::new(),
+            allowed_values: IndexMap::from([("tag1".to_owned(), vec!["allowed".to_owned()])]),
+            denied_values: IndexMap::from([("tag1".to_owned(), vec!["denied".to_owned()])]),
+        })
+        .unwrap();
+
+        let tags = |value: &str| BTreeMap::from([("tag1".to_owned(), value.to_owned())]);
+
+        // Denied values are rejected even though the limit isn't reached yet.
+        let event = transform
+            .transform_one(make_metric(tags("denied")))
+            .unwrap();
+        assert!(event.as_metric().tags().is_none());
+
+        // Allowed values don't count towards the limit.
+        for value in ["allowed", "val1", "allowed"] {
+            let event = make_metric(tags(value));
+            assert_eq!(transform.transform_one(event.clone()), Some(event));
+        }
+        let event = transform.transform_one(make_metric(tags("val2"))).unwrap();
+        assert!(event.as_metric().tags().is_none());
+    }
+
+    #[test]
+    fn tag_cardinality_limit_invalid_metric_name_pattern() {
+        let error = TagCardinalityLimit::new(TagCardinalityLimitConfig {
+            value_limit: 1,
+            limit_exceeded_action: LimitExceededAction::DropTag,
+            mode: Mode::Exact,
+            per_metric_limits: IndexMap::from([(
+                "http_[".to_owned(),
+                PerMetricLimitConfig {
+                    value_limit: None,
+                    limit_exceeded_action: None,
+                },
+            )]),
+            allowed_values: IndexMap::new(),
+            denied_values: IndexMap::new(),
+        })
+        .unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with(r#"invalid metric name pattern "http_[""#));
+    }
 }
@@ -5,10 +5,14 @@ use futures::{stream, Stream, StreamExt};
 use governor::{clock, Quota, RateLimiter};
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
+use tokio::sync::watch;
 
 use crate::{
     conditions::{AnyCondition, Condition},
-    config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
+    config::{
+        runtime_options::{self, RuntimeOption},
+        DataType, Input, Output, TransformConfig, TransformContext, TransformDescription,
+    },
     event::Event,
     internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
     schema,
@@ -58,6 +62,7 @@ pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     key_field: Option<Template>,
     exclude: Option<Condition>,
     clock: C,
+    threshold_updates: Option<watch::Receiver<u64>>,
 }
 
 impl<C, I> Throttle<C, I>
@@ -77,12 +82,17 @@ where
             None => return Err(Box::new(ConfigError::NonZero)),
         };
 
-        let quota = match Quota::with_period(Duration::from_secs_f64(
-            config.window_secs / threshold.get() as f64,
-        )) {
-            Some(quota) => quota.allow_burst(threshold),
+        let quota = match quota(config.window_secs, threshold) {
+            Some(quota) => quota,
             None => return Err(Box::new(ConfigError::NonZero)),
         };
+        let threshold_updates = context.key.as_ref().map(|key| {
+            runtime_options::register(
+                key,
+                RuntimeOption::ThrottleThreshold,
+                u64::from(threshold.get()),
+            )
+        });
         let exclude = config
             .exclude
             .as_ref()
@@ -95,17 +105,45 @@ where
             flush_keys_interval,
             key_field: config.key_field.clone(),
             exclude,
+            threshold_updates,
         })
     }
 }
 
+fn quota(window_secs: f64, threshold: NonZeroU32) -> Option<Quota> {
+    Quota::with_period(Duration::from_secs_f64(
+        window_secs / threshold.get() as f64,
+    ))
+    .map(|quota| quota.allow_burst(threshold))
+}
+
+/// Waits for the threshold to be changed at runtime, never completing if it can't be.
+async fn threshold_changed(updates: &mut Option<watch::Receiver<u64>>) -> NonZeroU32 {
+    loop {
+        let receiver = match updates {
+            Some(receiver) => receiver,
+            None => return futures::future::pending().await,
+        };
+        if receiver.changed().await.is_err() {
+            *updates = None;
+            continue;
+        }
+        let threshold = u32::try_from(*receiver.borrow())
+            .ok()
+            .and_then(NonZeroU32::new);
+        if let Some(threshold) = threshold {
+            return threshold;
+        }
+    }
+}
+
 impl<C, I> TaskTransform<Event> for Throttle<C, I>
 where
     C: clock::Clock<Instant = I> + Send + 'static,
     I: clock::Reference + Send + 'static,
 {
     fn transform(
-        self: Box<Self>,
+        mut self: Box<Self>,
         mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
@@ -115,7 +153,9 @@ where
 
         let mut flush_stream = tokio::time::interval(Duration::from_millis(1000));
 
-        let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
+        let mut limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
+        let window_secs = self.flush_keys_interval.as_secs_f64();
+        let mut threshold_updates = self.threshold_updates.take();
 
         Box::pin(
             stream! {
@@ -168,6 +208,13 @@ where
                     _ = flush_stream.tick() => {
                         false
                     }
+                    threshold = threshold_changed(&mut threshold_updates) => {
+                        // Keys start over with the new threshold.
+                        if let Some(quota) = quota(window_secs, threshold) {
+                            limiter = RateLimiter::dashmap_with_clock(quota, &self.clock);
+                        }
+                        false
+                    }
                 };
                 yield stream::iter(output.into_iter());
                 if done { break }
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use clap::Parser;
 use colored::*;
 
-use crate::config::{self, UnitTestResult};
+use crate::config::{self, GoldenMode, UnitTestResult};
 use crate::signal;
 
 #[derive(Parser, Debug)]
@@ -38,6 +38,11 @@ pub struct Opts {
         use_value_delimiter(true)
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// Overwrite the golden files of test outputs with the events the tests output, rather than
+    /// comparing the events against them.
+    #[clap(long)]
+    update_golden: bool,
 }
 
 impl Opts {
@@ -71,7 +76,12 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
     {
         println!("Running tests");
     }
-    match config::build_unit_tests_main(&paths, signal_handler).await {
+    let golden_mode = if opts.update_golden {
+        GoldenMode::Update
+    } else {
+        GoldenMode::Compare
+    };
+    match config::build_unit_tests_main(&paths, signal_handler, golden_mode).await {
         Ok(tests) => {
             if tests.is_empty() {
                 #[allow(clippy::print_stdout)]
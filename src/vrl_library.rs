@@ -0,0 +1,107 @@
+//! The VRL programs defined in the top-level `vrl_library` of the configuration.
+//!
+//! Each program is compiled once, when the topology is built, and shared by all the `remap`
+//! transforms and `shared` conditions referring to it by name.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use vrl::{diagnostic::Formatter, Program};
+
+static VRL_LIBRARY: Lazy<RwLock<HashMap<String, Arc<LibraryProgram>>>> =
+    Lazy::new(Default::default);
+
+/// A compiled program of the VRL library.
+#[derive(Debug)]
+pub(crate) struct LibraryProgram {
+    pub(crate) source: String,
+    pub(crate) program: Program,
+}
+
+/// Compiles the given programs and replaces the library with them, returning the compilation
+/// errors, if any.
+///
+/// Components that were already built keep the programs they were built with.
+pub(crate) fn load(
+    library: &IndexMap<String, String>,
+    enrichment_tables: &enrichment::TableRegistry,
+) -> Vec<String> {
+    let mut programs = HashMap::new();
+    let mut errors = vec![];
+
+    for (name, source) in library {
+        match compile(source, enrichment_tables) {
+            Ok(program) => {
+                programs.insert(
+                    name.clone(),
+                    Arc::new(LibraryProgram {
+                        source: source.clone(),
+                        program,
+                    }),
+                );
+            }
+            Err(error) => errors.push(format!("VRL library program \"{}\": {}", name, error)),
+        }
+    }
+
+    *VRL_LIBRARY.write().expect("VRL library lock poisoned") = programs;
+
+    errors
+}
+
+/// Returns the library program with the given name.
+pub(crate) fn get(name: &str) -> crate::Result<Arc<LibraryProgram>> {
+    VRL_LIBRARY
+        .read()
+        .expect("VRL library lock poisoned")
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("unknown VRL library program {:?}", name).into())
+}
+
+/// Compiles a library program against the same functions as the `remap` transform. As the
+/// program may run in several components, it is compiled without knowledge of their schema.
+fn compile(source: &str, enrichment_tables: &enrichment::TableRegistry) -> Result<Program, String> {
+    let mut functions = vrl_stdlib::all();
+    functions.append(&mut enrichment::vrl_functions());
+    functions.append(&mut vector_vrl_functions::vrl_functions());
+
+    let mut state = vrl::state::ExternalEnv::default();
+    state.set_external_context(enrichment_tables.clone());
+    state.set_external_context(MeaningList::default());
+
+    let (program, warnings) = vrl::compile_with_state(source, &functions, &mut state)
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+    if !warnings.is_empty() {
+        let warnings = Formatter::new(source, warnings).colored().to_string();
+        warn!(message = "VRL compilation warning.", %warnings);
+    }
+
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compiles_library_programs() {
+        assert!(compile(".foo = 1", &Default::default()).is_ok());
+        assert!(compile(".foo = ", &Default::default()).is_err());
+    }
+
+    #[test]
+    fn unknown_library_program() {
+        let error = get("unknown-vrl-library-program").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"unknown VRL library program "unknown-vrl-library-program""#
+        );
+    }
+}
@@ -289,6 +289,15 @@ mod tests {
         assert!(res.contains("ok"));
     }
 
+    #[tokio::test]
+    /// Tests the /health/live endpoint returns a 200 response regardless of the components
+    async fn api_health_live() {
+        let res = url_test(api_enabled_config(), "health/live").await;
+
+        assert!(res.status().is_success());
+        assert!(res.text().await.unwrap().contains("ok"));
+    }
+
     #[tokio::test]
     /// Tests that the API playground is enabled when playground = true (implicit)
     async fn api_playground_enabled() {
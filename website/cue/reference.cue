@@ -404,6 +404,11 @@ _values: {
 			description: "A [Datadog Search](\(urls.datadog_search_syntax)) query string."
 			example:     #"*stack"#
 		},
+		{
+			name:        "shared"
+			description: "The name of a condition defined once in the top-level `conditions`, or of a program of the top-level `vrl_library`, to be shared by many components."
+			example:     #"server_errors"#
+		},
 	]
 
 	options: {
@@ -47,6 +47,93 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		profiling: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether the `/debug/pprof` endpoints are enabled, to take CPU and heap
+				profiles of the running instance without restarting it. They require
+				`auth.token` to be set, and are only available on Unix builds of Vector
+				with the `api-profiling` feature, which isn't part of the default builds.
+				Enabling them turns on sampling of allocations, which adds a small
+				overhead.
+				"""
+		}
+		health: {
+			common:      false
+			required:    false
+			description: "How the health of the components makes up the readiness reported by the `/health` endpoint."
+			type: object: options: {
+				readiness: {
+					required: false
+					description: """
+						Policies that make Vector not ready while any of them is
+						violated, such as when a sink has been failed for more than a
+						minute. A policy is violated when any component it applies to
+						has been in its `state`, or a less healthy one, for at least
+						`for_secs` seconds. Components are `starting` until they run
+						and, for sinks, until their healthcheck passes, `degraded`
+						while their circuit breaker probes whether they recovered or
+						when a component upstream of them failed, and `failed` when
+						their healthcheck failed, their circuit breaker is open, or
+						they stopped with an error.
+						"""
+					type: array: {
+						default: []
+						items: type: object: {
+							examples: [{component_kind: "sink", state: "failed", for_secs: 60}]
+							options: {
+								component_kind: {
+									required:    false
+									description: "The kind of components the policy applies to. Applies to all components if unset."
+									type: string: enum: {
+										source:    "Sources."
+										transform: "Transforms."
+										sink:      "Sinks."
+									}
+								}
+								state: {
+									required:    false
+									description: "The state, or any less healthy one, that violates the policy."
+									type: string: {
+										default: "failed"
+										enum: {
+											starting: "Starting, degraded, or failed."
+											degraded: "Degraded or failed."
+											failed:   "Failed."
+										}
+									}
+								}
+								for_secs: {
+									required:    false
+									description: "How long a component must have been in the state for the policy to be violated."
+									type: uint: {
+										default: 0
+										unit:    "seconds"
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+		auth: {
+			common:      false
+			required:    false
+			description: "Authentication for the endpoints of the API that expose the internals of Vector."
+			type: object: options: {
+				token: {
+					required: false
+					description: """
+						The token that requests to the `/debug/pprof` endpoints must send
+						in an `Authorization: Bearer <token>` header.
+						"""
+					type: string: examples: ["${VECTOR_API_TOKEN}"]
+				}
+			}
+		}
 	}
 
 	endpoints: {
@@ -55,6 +142,15 @@ api: {
 				description: """
 					Main endpoint for receiving and processing
 					GraphQL queries.
+
+					The `config` field of each component holds its resolved
+					configuration as JSON, with secrets redacted. A few options
+					can be changed while Vector runs, without reloading the
+					configuration, through the `setSampleRate`,
+					`setThrottleThreshold`, and `setLogLevel` mutations.
+					Options changed this way go back to their configured
+					values when the component is rebuilt, such as when its
+					configuration changes.
 					"""
 				responses: {
 					"200": {
@@ -72,13 +168,77 @@ api: {
 		"/health": {
 			GET: {
 				description: """
-					Healthcheck endpoint. Useful to verify that
-					Vector is up and running.
+					Readiness endpoint. Useful to verify that
+					Vector is up and running, and that its components
+					are healthy enough per the `health.readiness`
+					policies. The response holds the health state of
+					each component, and the reasons Vector isn't ready.
+					"""
+				responses: {
+					"200": {
+						description: "Vector is initialized and running, and no readiness policy is violated."
+					}
+					"503": {
+						description: "Vector is shutting down, or a readiness policy is violated."
+					}
+				}
+			}
+		}
+		"/health/live": {
+			GET: {
+				description: """
+					Liveness endpoint. Useful to verify that
+					Vector is up and running, regardless of the
+					health of its components.
 					"""
 				responses: {
 					"200": {
 						description: "Vector is initialized and running."
 					}
+					"503": {
+						description: "Vector is shutting down."
+					}
+				}
+			}
+		}
+		"/debug/pprof/profile": {
+			GET: {
+				description: """
+					Takes a CPU profile of the running instance, in the protobuf
+					format of [pprof](https://github.com/google/pprof). The
+					`seconds` query parameter sets how long to profile for, from 1
+					to 300 and 30 by default, and `frequency` the sampling
+					frequency in Hz, from 1 to 1000 and 99 by default. Only one
+					CPU profile can be taken at a time. Available when `profiling`
+					is enabled.
+					"""
+				responses: {
+					"200": {
+						description: "The profile."
+					}
+					"401": {
+						description: "The request doesn't hold the token of `auth.token`."
+					}
+					"409": {
+						description: "Another CPU profile is being taken."
+					}
+				}
+			}
+		}
+		"/debug/pprof/heap": {
+			GET: {
+				description: """
+					Dumps a heap profile of the running instance, as sampled by
+					jemalloc since the API started, for use with `jeprof`.
+					Available when `profiling` is enabled.
+					"""
+				responses: {
+					"200": {
+						description: "The profile."
+					}
+					"401": {
+						description: "The request doesn't hold the token of `auth.token`."
+					}
 				}
 			}
 		}
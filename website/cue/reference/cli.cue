@@ -155,6 +155,11 @@ cli: {
 			enum:        env_vars.VECTOR_LOG_FORMAT.type.string.enum
 			env_var:     "VECTOR_LOG_FORMAT"
 		}
+		"profile": {
+			description: env_vars.VECTOR_PROFILE.description
+			type:        "string"
+			env_var:     "VECTOR_PROFILE"
+		}
 
 		"threads": {
 			_short:      "t"
@@ -167,17 +172,190 @@ cli: {
 	options: _core_options
 
 	commands: {
+		"bench": {
+			description: """
+				Benchmark a source, transform or sink of a configuration on its own, for capacity planning
+				without an external load generator. Transforms and sinks are sent generated access logs, or
+				counters for components that only accept metrics, or the events of a file given with
+				`--input`, replayed over and over, either as fast as the component takes them or at the
+				rate given with `--rate`. Sources run on their own inputs. Once the duration is up, or the
+				number of events given with `--events` is sent, the throughput is printed along with the
+				latency percentiles of the events, timed from when they are sent until they come out of a
+				transform or are delivered by a sink, and the memory allocated by Vector when it's built
+				with the jemalloc allocator.
+				"""
+
+			example: "vector bench parse_logs --config /etc/vector/vector.toml --input sample.log --rate 50000 --duration 30"
+
+			options: _core_options & {
+				"input": {
+					_short:      "i"
+					description: "A file of events to replay, one event per line, read as log messages or as JSON metrics"
+					type:        "string"
+				}
+				"rate": {
+					_short:      "r"
+					description: "The number of events to send per second, rather than as many as the component takes"
+					type:        "integer"
+				}
+				"duration": {
+					_short:      "d"
+					description: "How long to run the benchmark for, in seconds"
+					type:        "integer"
+					default:     10
+				}
+				"events": {
+					_short:      "n"
+					description: "The number of events to send, stopping before the duration is up once they are sent"
+					type:        "integer"
+				}
+			}
+
+			args: {
+				component: {
+					description: "The ID of the source, transform or sink to benchmark"
+					type:        "string"
+				}
+			}
+		}
+		"buffer": {
+			description: """
+				Inspect and manage the disk buffers of sinks. `vector buffer ls` lists the disk buffer of
+				every sink, along with its size, its number of data files, and the age of its oldest data.
+				`vector buffer purge <component>` deletes all of the events held in the disk buffer of a
+				sink, and `vector buffer drain <component>` reads them out to stdout as JSON, one event per
+				line, removing them from the buffer. Purging and draining refuse to operate on a buffer that
+				is in use by a running Vector process.
+				"""
+
+			example: "vector buffer drain my_sink --config /etc/vector/vector.toml > my_sink.json"
+
+			options: _core_options
+		}
+		"config render": {
+			description: """
+				Print the configuration as it is after merging all of the configuration files and the files
+				they include, without interpolating environment variables or secrets. This is useful to check
+				the result of overriding settings in included files.
+				"""
+
+			example: "vector config render --config /etc/vector/vector.toml --format yaml"
+
+			options: _core_options & {
+				"format": {
+					description: "The format to print the configuration in"
+					default:     "toml"
+					enum: {
+						json: "Print the configuration as JSON"
+						toml: "Print the configuration as TOML"
+						yaml: "Print the configuration as YAML"
+					}
+				}
+			}
+		}
+		"config lint": {
+			description: """
+				Check the configuration for likely mistakes that don't keep it from loading: components whose
+				events never reach a sink, sinks without acknowledgements fed by sources relying on them,
+				memory buffers over the size allowed by the policy, and deprecated options. A policy file can
+				also define rules of its own, such as requiring every source to feed a sink of a given type
+				(`check = "require_sink"` with `sink_types`) or forbidding component types
+				(`check = "forbid_type"` with `types`). Exits with an error when anything is found.
+				"""
+
+			example: "vector config lint --config /etc/vector/vector.toml --policy /etc/vector/policy.toml"
+
+			options: _core_options & {
+				"policy": {
+					description: "A TOML, YAML or JSON file of policy rules to check the configuration against"
+					type:        "string"
+				}
+			}
+		}
+		"convert": {
+			description: """
+				Convert the configuration of Logstash, Fluentd or Fluent Bit to a Vector configuration on a
+				best-effort basis. Inputs, filters and outputs with a Vector equivalent are converted to
+				sources, transforms and sinks, with the routing of Fluentd and Fluent Bit tags turned into
+				the inputs of components. Everything that couldn't be converted, such as conditionals and
+				plugins without an equivalent, is listed at the top of the configuration and printed to
+				stderr, to be converted by hand.
+				"""
+
+			example: "vector convert --from logstash pipeline.conf --output vector.toml"
+
+			options: {
+				"from": {
+					description: "The log shipper the configuration to convert is written for"
+					enum: {
+						logstash:     "Convert a Logstash pipeline configuration"
+						fluentd:      "Convert a Fluentd configuration"
+						"fluent-bit": "Convert a Fluent Bit configuration in the classic format"
+					}
+				}
+				"output": {
+					_short:      "o"
+					description: "A file to write the Vector configuration to rather than stdout, in the format given by its extension"
+					type:        "string"
+				}
+				"format": {
+					description: "The format of the Vector configuration, when it isn't given by the output file"
+					default:     "toml"
+					enum: {
+						json: "Write the configuration as JSON"
+						toml: "Write the configuration as TOML"
+						yaml: "Write the configuration as YAML"
+					}
+				}
+			}
+
+			args: {
+				input: {
+					description: "The configuration to convert"
+					type:        "string"
+				}
+			}
+		}
+
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
 				which can be rendered using [GraphViz](\(urls.graphviz)).
 
 				You can also visualize the output online at [webgraphviz.com](http://www.webgraphviz.com/).
+				The graph can be output as a [Mermaid](https://mermaid.js.org/) flowchart or a
+				[D2](https://d2lang.com/) diagram instead with `--format`.
+
+				Given the API endpoint of a running instance with `--url`, every edge is annotated with the
+				events per second flowing through it, and components running into errors are highlighted
+				along with their errors per second, which helps spot bottlenecks.
 				"""
 
 			example: "vector graph --config /etc/vector/vector.toml | dot -Tsvg > graph.svg"
 
-			options: _core_options
+			options: _core_options & {
+				"format": {
+					description: "The format to output the graph in"
+					default:     "dot"
+					enum: {
+						dot:     "Output the graph in the DOT format"
+						mermaid: "Output the graph as a Mermaid flowchart"
+						d2:      "Output the graph as a D2 diagram"
+					}
+				}
+				"url": {
+					_short:      "u"
+					description: "The Vector GraphQL API endpoint of a running instance to sample throughput and error rates from"
+					type:        "string"
+					example:     "http://localhost:8686/graphql"
+				}
+				"interval": {
+					_short:      "i"
+					description: "The interval to sample throughput and error rates over, in milliseconds"
+					type:        "integer"
+					default:     1000
+				}
+			}
 		}
 		"generate": {
 			description: "Generate a Vector configuration containing a list of components"
@@ -227,6 +405,69 @@ cli: {
 			}
 		}
 
+		"simulate": {
+			description: """
+				Run a configuration against sample input files offline, then exit. Every source is
+				replaced by one emitting the events of the file given with `--input`, one event per line,
+				and every sink by one capturing the events it receives, so that nothing is read from or
+				written to the outside world. Lines are read as the messages of logs, or as JSON metrics
+				for sources that only emit metrics. Once the inputs run out, the number of events each
+				component received and sent is printed as a table.
+				"""
+
+			example: "vector simulate --config /etc/vector/vector.toml --input my_source=sample.log --output-dir out"
+
+			options: _core_options & {
+				"input": {
+					_short:      "i"
+					description: "The events a source emits, as `<source>=<file>`, where `-` reads stdin. Can be given several times"
+					type:        "string"
+				}
+				"output-dir": {
+					_short:      "o"
+					description: "A directory to write the events each sink receives to, as JSON, one event per line, in `<sink>.ndjson`"
+					type:        "string"
+				}
+			}
+		}
+
+		"replay": {
+			description: """
+				Re-inject the events of an archive written by an `archive` sink into a configuration,
+				then exit, such as to reprocess them after a bad deploy. The source given with
+				`--source` is replaced by a replay of the archive, so that its events go through the
+				components downstream of it to the sinks of the configuration, and the other sources
+				emit no events. Once the archive is replayed, the number of events replayed is printed.
+				"""
+
+			example: "vector replay --config /etc/vector/vector.toml --archive /var/lib/vector/archive --source my_source --start 2022-06-01T12:00:00Z --end 2022-06-01T13:00:00Z"
+
+			options: _core_options & {
+				"archive": {
+					_short:      "a"
+					description: "The directory of the archive"
+					type:        "string"
+				}
+				"source": {
+					_short:      "s"
+					description: "The source the archived events are re-injected in place of"
+					type:        "string"
+				}
+				"start": {
+					description: "Replay only the events from this time on, as an RFC 3339 timestamp"
+					type:        "string"
+				}
+				"end": {
+					description: "Replay only the events before this time, as an RFC 3339 timestamp"
+					type:        "string"
+				}
+				"speed": {
+					description: "Replay the events as far apart as they happened, divided by this speed, rather than as fast as possible"
+					type:        "string"
+				}
+			}
+		}
+
 		"test": {
 			description: """
 				Run Vector config unit tests, then exit. This command is experimental and
@@ -234,6 +475,15 @@ cli: {
 				out the [unit testing documentation](\(urls.vector_unit_tests)).
 				"""
 
+			flags: _default_flags & {
+				"update-golden": {
+					description: """
+						Overwrite the golden files of test outputs with the events the tests output,
+						rather than comparing the events against them
+						"""
+				}
+			}
+
 			options: {
 				"config-toml": {
 					description: env_vars.VECTOR_CONFIG_TOML.description
@@ -267,6 +517,12 @@ cli: {
 				Observe events flowing into components (transforms, sinks) and
 				out of components (sources, transforms). Events are sampled at
 				a specified interval.
+
+				The running instance can narrow down events before sampling them,
+				so that tapping a busy component doesn't flood the terminal or the
+				API: `--filter` keeps only the events matching a VRL predicate,
+				`--fields` keeps only some fields of logs, and `--max-events-per-sec`
+				caps the number of events tapped each second.
 				"""
 
 			flags: _default_flags & {
@@ -321,6 +577,19 @@ cli: {
 					description: "Components (sources, transforms) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
 				}
+				"filter": {
+					description: "VRL predicate events must match to be tapped, evaluated by the Vector instance"
+					type:        "string"
+					example:     ".status >= 500"
+				}
+				"fields": {
+					description: "Fields to keep in tapped logs, dropping the others (comma-separated)"
+					type:        "list"
+				}
+				"max-events-per-sec": {
+					description: "Maximum number of events tapped per second by the Vector instance, across all components"
+					type:        "integer"
+				}
 			}
 
 			args: {
@@ -370,6 +639,33 @@ cli: {
 					description: "The URL for the GraphQL endpoint of the running Vector instance"
 					type:        "string"
 				}
+				"history": {
+					description: """
+						How far back to keep metrics, in seconds. Press 'p' to pause and the
+						left and right arrow keys to scroll back through them
+						"""
+					type:    "integer"
+					default: 300
+				}
+				"sort": {
+					_short:      "s"
+					description: "Order of the components, which 's' cycles through"
+					type:        "enum"
+					default:     "id"
+					enum: {
+						id:     "Sort components by ID"
+						errors: "Sort components by error rate, highest first"
+						buffer: "Sort components by the number of events held in their buffers, highest first"
+					}
+				}
+				"export-path": {
+					_short: "e"
+					description: """
+						File to export the recorded metrics to as JSON when pressing 'e'.
+						Defaults to a timestamped file in the current directory
+						"""
+					type: "string"
+				}
 			}
 		}
 
@@ -572,6 +868,16 @@ cli: {
 				}
 			}
 		}
+		VECTOR_PROFILE: {
+			description: """
+				The profile whose profile-scoped sections, such as `[profile.production]`, are applied over the
+				rest of the configuration.
+				"""
+			type: string: {
+				default: null
+				examples: ["production", "staging"]
+			}
+		}
 		VECTOR_REQUIRE_HEALTHY: {
 			description: "Exit on startup if any sinks fail healthchecks."
 			type: bool: default: false
@@ -576,6 +576,17 @@ components: {
 					examples: ["/path/to/credentials.json"]
 				}
 			}
+			_gcp_impersonation_chain: {
+				common:      false
+				description: "The service accounts to impersonate in turn, the last of which the token is issued for. Each account must be allowed to create tokens for the next one, and the credentials Vector authenticates with must be allowed to create tokens for the first one."
+				required:    false
+				type: array: {
+					default: []
+					items: type: string: {
+						examples: ["vector@my-project.iam.gserviceaccount.com"]
+					}
+				}
+			}
 			_source_acknowledgements: {
 				common:      true
 				description: "Controls how acknowledgements are handled by this source. These settings override the global `acknowledgement` settings. This setting is deprecated in favor of enabling `acknowledgements` in the destination sink."
@@ -645,6 +656,16 @@ components: {
 							examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 						}
 					}
+					reload_interval_secs: {
+						common:      false
+						description: "How often the certificate, key and CA files are checked for changes. Changed files are loaded again and used for the connections accepted from then on, so that rotated certificates, such as SPIFFE SVIDs written to files by a SPIFFE helper, are picked up without a restart. If loading them fails, the current certificates are kept. By default, the files are only loaded when the component starts."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [60]
+							unit: "seconds"
+						}
+					}
 
 					if Args.can_verify_certificate {
 						verify_certificate: {
@@ -712,6 +733,16 @@ components: {
 							examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 						}
 					}
+					reload_interval_secs: {
+						common:      false
+						description: "How often the certificate, key and CA files are checked for changes. Changed files are loaded again and used for the connections made from then on, so that rotated certificates, such as SPIFFE SVIDs written to files by a SPIFFE helper, are picked up without a restart. If loading them fails, the current certificates are kept. By default, the files are only loaded when the component starts."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [60]
+							unit: "seconds"
+						}
+					}
 
 					if Args.can_verify_certificate {
 						verify_certificate: {
@@ -735,7 +766,7 @@ components: {
 
 			_proxy: {
 				common:      false
-				description: "Configures an HTTP(S) proxy for Vector to use. By default, the globally configured proxy is used."
+				description: "Configures an HTTP(S) or SOCKS5 proxy for Vector to use. By default, the globally configured proxy is used."
 				required:    false
 				type: object: options: {
 					enabled: {
@@ -762,6 +793,19 @@ components: {
 							examples: ["http://foo.bar:3128"]
 						}
 					}
+					socks5: {
+						common:      false
+						description: """
+							The URL of a [SOCKS5](\(urls.socks5)) proxy to send requests through, for every scheme,
+							instead of the HTTP(S) proxies. Host names are resolved by Vector with the `socks5` scheme,
+							and by the proxy with the `socks5h` one.
+							"""
+						required:    false
+						type: string: {
+							default: null
+							examples: ["socks5://foo.bar:1080", "socks5h://foo.bar:1080"]
+						}
+					}
 					no_proxy: {
 						common:      false
 						description: """
@@ -772,7 +816,7 @@ components: {
 							Domain names | `example.com` matches requests to `example.com`
 							Wildcard domains | `.example.com` matches requests to `example.com` and its subdomains
 							IP addresses | `127.0.0.1` matches requests to 127.0.0.1
-							[CIDR](\(urls.cidr)) blocks | `192.168.0.0./16` matches requests to any IP addresses in this range
+							[CIDR](\(urls.cidr)) blocks | `192.168.0.0/16` matches requests to any IP addresses in this range
 							Splat | `*` matches all hosts
 							"""
 						required:    false
@@ -783,6 +827,134 @@ components: {
 							}
 						}
 					}
+					auth: {
+						common:      false
+						description: """
+							The credentials to authenticate to the proxies with, taking precedence over those in
+							their URLs. Unlike those, they don't need to be URL-encoded, so they can be read from a
+							secret with `SECRET[<backend>.<key>]`.
+							"""
+						required:    false
+						type: object: options: {
+							username: {
+								description: "The username to authenticate with."
+								required:    true
+								type: string: examples: ["${PROXY_USERNAME}", "username"]
+							}
+							password: {
+								description: "The password to authenticate with."
+								required:    true
+								type: string: examples: ["SECRET[backend.proxy_password]", "password"]
+							}
+						}
+					}
+				}
+			}
+
+			_aws_sigv4: {
+				common:      false
+				description: "Signs requests with [AWS Signature Version 4](\(urls.aws_sigv4)), for AWS services without a component of their own, such as API Gateway or OpenSearch Serverless. It can't be set together with `auth`."
+				required:    false
+				type: object: options: {
+					auth: components._aws.configuration.auth
+					region: {
+						common:      false
+						description: "The [AWS region](\(urls.aws_regions)) of the service. This defaults to the value of the `$AWS_REGION` or `$AWS_DEFAULT_REGION` environment variables, or the region of the instance Vector runs on."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["us-east-1"]
+						}
+					}
+					service: {
+						description: "The name of the service requests are signed for."
+						required:    true
+						type: string: examples: ["execute-api", "aoss", "es"]
+					}
+				}
+			}
+
+			_oauth2_auth: {
+				token_endpoint: {
+					description:   "The URL of the token endpoint of the [OAuth2](\(urls.oauth2)) authorization server."
+					relevant_when: "strategy = \"oauth2\""
+					required:      true
+					type: string: examples: ["https://auth.example.com/oauth2/token"]
+				}
+				client_id: {
+					description:   "The ID of the client Vector authenticates as."
+					relevant_when: "strategy = \"oauth2\""
+					required:      true
+					type: string: examples: ["vector"]
+				}
+				client_secret: {
+					common: false
+					description: """
+						The secret of the client, sent to the token endpoint with HTTP basic authentication
+						to be granted a token for the client credentials. It can be read from a secret with
+						`SECRET[<backend>.<key>]`.
+						"""
+					relevant_when: "strategy = \"oauth2\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["SECRET[backend.client_secret]", "${OAUTH2_CLIENT_SECRET}"]
+					}
+				}
+				assertion: {
+					common: false
+					description: """
+						Options for the [JWT assertion](\(urls.oauth2_jwt_bearer)) to be granted a token for,
+						rather than for the client credentials. The assertion is signed by Vector, issued
+						by the client, and valid for five minutes.
+						"""
+					relevant_when: "strategy = \"oauth2\""
+					required:      false
+					type: object: options: {
+						key_file: {
+							description: "The path of the RSA private key, in PEM format, to sign the assertion with using `RS256`."
+							required:    true
+							type: string: examples: ["/etc/vector/oauth2.key"]
+						}
+						key_id: {
+							common:      false
+							description: "The ID of the key, sent in the `kid` header of the assertion."
+							required:    false
+							type: string: {
+								default: null
+								examples: ["key-1"]
+							}
+						}
+						subject: {
+							common:      false
+							description: "The subject of the assertion."
+							required:    false
+							type: string: {
+								default: "The client ID."
+								examples: ["vector@example.com"]
+							}
+						}
+					}
+				}
+				scopes: {
+					common:        false
+					description:   "The scopes to request the token for."
+					relevant_when: "strategy = \"oauth2\""
+					required:      false
+					type: array: {
+						default: []
+						items: type: string: examples: ["logs.write"]
+					}
+				}
+				audience: {
+					common:        false
+					description:   "The audience to request the token for, as some authorization servers require. It's also the audience of the JWT assertion, which defaults to the token endpoint."
+					relevant_when: "strategy = \"oauth2\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["https://logs.example.com"]
+					}
 				}
 			}
 
@@ -796,7 +968,7 @@ components: {
 				common:      false
 				description: "Configures the authentication strategy."
 				required:    false
-				type: object: options: {
+				type: object: options: _oauth2_auth & {
 					password: {
 						description: "The basic authentication password."
 						required:    true
@@ -811,6 +983,7 @@ components: {
 							enum: {
 								basic:  "The [basic authentication strategy](\(urls.basic_auth))."
 								bearer: "The bearer token authentication strategy."
+								oauth2: "The [OAuth2](\(urls.oauth2)) authentication strategy, with a bearer token fetched from an authorization server and refreshed before it expires."
 							}
 						}
 					}
@@ -952,6 +1125,21 @@ components: {
 					examples: ["http://foo.bar:3128"]
 				}
 			}
+			_all_proxy: {
+				description: """
+					The global URL of a SOCKS5 proxy to send requests through. Only URLs with the
+					`socks5` or `socks5h` scheme are used.
+
+					If another SOCKS5 proxy is set in the configuration file or at a component level,
+					this one will be overridden.
+
+					The lowercase variant has priority over the uppercase one.
+					"""
+				type: string: {
+					default: null
+					examples: ["socks5://foo.bar:1080"]
+				}
+			}
 			_no_proxy: {
 				description: """
 					List of hosts to avoid proxying globally.
@@ -963,7 +1151,7 @@ components: {
 					Domain names | `example.com` matches requests to `example.com`
 					Wildcard domains | `.example.come` matches requests to `example.com` and its subdomains
 					IP addresses | `127.0.0.1` matches requests to `127.0.0.1`
-					[CIDR](\(urls.cidr)) blocks | `192.168.0.0./16` matches requests to any IP addresses in this range
+					[CIDR](\(urls.cidr)) blocks | `192.168.0.0/16` matches requests to any IP addresses in this range
 					Splat | `*` matches all hosts
 
 					If another `no_proxy` value is set in the configuration file or at a component level, this
@@ -973,7 +1161,7 @@ components: {
 					"""
 				type: string: {
 					default: null
-					examples: ["localhost,.example.com,192.168.0.0./16", "*"]
+					examples: ["localhost,.example.com,192.168.0.0/16", "*"]
 				}
 			}
 			if features.collect != _|_ {
@@ -983,6 +1171,8 @@ components: {
 						HTTP_PROXY:  env_vars._http_proxy
 						https_proxy: env_vars._https_proxy
 						HTTPS_PROXY: env_vars._https_proxy
+						all_proxy:   env_vars._all_proxy
+						ALL_PROXY:   env_vars._all_proxy
 						no_proxy:    env_vars._no_proxy
 						NO_PROXY:    env_vars._no_proxy
 					}
@@ -993,6 +1183,8 @@ components: {
 						HTTP_PROXY:  env_vars._http_proxy
 						https_proxy: env_vars._https_proxy
 						HTTPS_PROXY: env_vars._https_proxy
+						all_proxy:   env_vars._all_proxy
+						ALL_PROXY:   env_vars._all_proxy
 						no_proxy:    env_vars._no_proxy
 						NO_PROXY:    env_vars._no_proxy
 					}
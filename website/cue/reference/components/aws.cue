@@ -39,10 +39,66 @@ components: _aws: {
 							examples: ["arn:aws:iam::123456789098:role/my_role"]
 						}
 					}
+					chained_roles: {
+						category:    "Auth"
+						common:      false
+						description: "The [IAM roles](\(urls.aws_iam_role)) to assume in turn, each with the credentials of the role before it, after assuming `assume_role` or after loading credentials with `web_identity_token_file` or `container_credentials_uri`."
+						required:    false
+						type: array: {
+							default: []
+							items: type: object: options: {
+								assume_role: {
+									description: "The ARN of the role to assume."
+									required:    true
+									type: string: {
+										examples: ["arn:aws:iam::123456789098:role/cross_account_role"]
+									}
+								}
+								external_id: {
+									description: "The external ID required by the trust policy of the role."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["my-external-id"]
+									}
+								}
+							}
+						}
+					}
+					container_authorization_token_file: {
+						category:    "Auth"
+						common:      false
+						description: "The path of the token sent to `container_credentials_uri` in the `Authorization` header. The file is read again for every request, as the token is rotated."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["/var/run/secrets/pods.eks.amazonaws.com/serviceaccount/eks-pod-identity-token"]
+						}
+					}
+					container_credentials_uri: {
+						category:    "Auth"
+						common:      false
+						description: "The URI of a container credentials endpoint, such as the one of the EKS Pod Identity agent, to load credentials from. These are refreshed automatically before they expire."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["http://169.254.170.23/v1/credentials"]
+						}
+					}
+					external_id: {
+						category:    "Auth"
+						common:      false
+						description: "The external ID required by the trust policy of the `assume_role` role, which is commonly used for cross-account access."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["my-external-id"]
+						}
+					}
 					load_timeout_secs: {
 						category:    "Auth"
 						common:      false
-						description: "The timeout for loading credentials. Relevant when the default credentials chain is used or `assume_role`."
+						description: "The timeout for loading credentials. Relevant when the default credentials chain is used, or with `assume_role`, `web_identity_token_file` or `container_credentials_uri`."
 						required:    false
 						type: uint: {
 							unit:    "seconds"
@@ -50,6 +106,36 @@ components: _aws: {
 							examples: [30]
 						}
 					}
+					role_arn: {
+						category:    "Auth"
+						common:      false
+						description: "The ARN of the role to assume with the token of `web_identity_token_file`."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["arn:aws:iam::123456789098:role/my_role"]
+						}
+					}
+					session_name: {
+						category:    "Auth"
+						common:      false
+						description: "The name of the session of the role assumed with `web_identity_token_file`."
+						required:    false
+						type: string: {
+							default: "vector"
+							examples: ["my-session"]
+						}
+					}
+					web_identity_token_file: {
+						category:    "Auth"
+						common:      false
+						description: "The path of a web identity token, such as the token of an [IAM role for a Kubernetes service account](\(urls.aws_iam_role)) (IRSA), to assume `role_arn` with. The credentials are refreshed automatically before they expire."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["/var/run/secrets/eks.amazonaws.com/serviceaccount/token"]
+						}
+					}
 					profile: {
 						category:    "Auth"
 						common:      false
@@ -193,7 +279,22 @@ components: _aws: {
 					body: """
 						Vector can assume an AWS IAM role via the [`assume_role`](#auth.assume_role) option. This is an
 						optional setting that is helpful for a variety of use cases, such as cross
-						account access.
+						account access. The role can require an [`external_id`](#auth.external_id), and further
+						roles can be assumed in turn with [`chained_roles`](#auth.chained_roles), for instance to
+						assume a role of the account data is ingested from with a role of the account Vector runs in.
+						"""
+				},
+				{
+					title: "Kubernetes service accounts"
+					body: """
+						On EKS, Vector loads credentials with the token of an IAM role for service accounts (IRSA)
+						mounted in its pod, either from the `AWS_WEB_IDENTITY_TOKEN_FILE` and `AWS_ROLE_ARN`
+						environment variables of the default credentials chain, or from the
+						[`web_identity_token_file`](#auth.web_identity_token_file) and [`role_arn`](#auth.role_arn)
+						options. With EKS Pod Identity, set [`container_credentials_uri`](#auth.container_credentials_uri)
+						and [`container_authorization_token_file`](#auth.container_authorization_token_file) to the
+						values of the `AWS_CONTAINER_CREDENTIALS_FULL_URI` and `AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE`
+						environment variables, such as with `"${AWS_CONTAINER_CREDENTIALS_FULL_URI}"`.
 						"""
 				},
 			]
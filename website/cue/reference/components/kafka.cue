@@ -85,6 +85,25 @@ components: _kafka: {
 				this dependency is packaged with Vector, meaning you do not need to install it.
 				"""
 		}
+		exactly_once: {
+			title: "Exactly-once delivery"
+			body: """
+				By default, pipelines from a `kafka` source to a `kafka` sink deliver events
+				at least once, so events may be duplicated in the destination topic when Vector
+				restarts. For pipelines that only read from and write to topics of the same Kafka
+				cluster, enable `exactly_once` on the `kafka` source and set `transactional_id` on
+				the `kafka` sink. The sink then produces events in transactions, and commits the
+				consumer offsets of the source in the same transaction, so each event is either
+				written and marked as consumed, or neither. If a transaction fails, it is aborted
+				and the source stops reading from the affected topic, as with any other negative
+				acknowledgement. Consumers of the destination topic must use the `read_committed`
+				isolation level, which is the default for `librdkafka`.
+
+				As the source no longer commits its own offsets in this mode, all sinks it feeds,
+				directly or through transforms, must be `kafka` sinks with a `transactional_id`.
+				Vector rejects configurations in which it feeds any other sink.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -21,6 +21,23 @@ components: sinks: [Name=string]: {
 						required: false
 						type: bool: default: false
 					}
+					timeout_secs: {
+						common: false
+						description: """
+							How long connected sources wait for this sink to acknowledge events before
+							treating them as errored. Sources that support it then negatively acknowledge
+							the events, for example by not committing their offsets or checkpoints, so that
+							they are retried instead of being held indefinitely by a stuck sink. When a
+							source is connected to multiple sinks, the shortest timeout applies. By default,
+							sources wait indefinitely.
+							"""
+						required: false
+						type: uint: {
+							default: null
+							examples: [300]
+							unit: "seconds"
+						}
+					}
 				}
 			}
 		}
@@ -74,6 +91,41 @@ components: sinks: [Name=string]: {
 			type: object: {
 				examples: []
 				options: {
+					compression: {
+						common:        false
+						description:   "Compression applied to records before they are written to the disk buffer, trading CPU for disk usage."
+						required:      false
+						relevant_when: "type = \"disk\""
+						type: object: {
+							examples: [{algorithm: "zstd", level: 3}]
+							options: {
+								algorithm: {
+									description: "The compression algorithm to use."
+									required:    true
+									type: string: {
+										enum: {
+											none: "No compression."
+											zstd: "[Zstandard](\(urls.zstd)) compression."
+										}
+									}
+								}
+								level: {
+									common:        false
+									description:   "The compression level, from 1 (fastest) to 21 (smallest)."
+									required:      false
+									relevant_when: "algorithm = \"zstd\""
+									type: int: default: 3
+								}
+							}
+						}
+					}
+					io_uring: {
+						common:        false
+						description:   "Write the data files of the buffer through [io_uring](\(urls.io_uring)), so that the writes of all disk buffers reach the kernel together. Requires Linux 5.6 or later, and Vector built with the `io-uring` feature; otherwise data files are written as usual."
+						required:      false
+						relevant_when: "type = \"disk\""
+						type: bool: default: false
+					}
 					max_events: {
 						common:        true
 						description:   "The maximum number of [events](\(urls.vector_data_model)) allowed in the buffer."
@@ -113,13 +165,21 @@ components: sinks: [Name=string]: {
 					}
 					when_full: {
 						common:      false
-						description: "The behavior when the buffer becomes full."
-						required:    false
+						description: """
+							The behavior when the buffer becomes full.
+
+							Multiple buffer stages can be chained by configuring `buffer` as an array of stages, where
+							every stage but the last is set to `overflow`, such as an in-memory stage that overflows to
+							a disk stage, which in turn overflows to an in-memory stage that drops the oldest data.
+							"""
+						required: false
 						type: string: {
 							default: "block"
 							enum: {
 								block:       "Applies back pressure when the buffer is full. This prevents data loss, but will cause data to pile up on the edge."
 								drop_newest: "Drops new data as it's received. This data is lost. This should be used when performance is the highest priority."
+								drop_oldest: "Drops the oldest data in the buffer to make room for new data. This data is lost. This should be used when the most recent data is the most valuable. Only supported by `memory` buffers."
+								overflow:    "Sends new data to the next buffer stage. Only valid when the buffer has another stage after this one."
 							}
 						}
 					}
@@ -216,6 +276,31 @@ components: sinks: [Name=string]: {
 									}
 								}
 							}
+							for codec in features.send.encoding.codec.enum {
+								if codec == "json" {
+									options: json: {
+										description:   "Options for the `json` codec."
+										required:      false
+										common:        false
+										relevant_when: "codec = `json`"
+										type: object: options: {
+											non_finite_floats: {
+												description: "How floats that aren't finite are encoded in log and trace events, since JSON has no representation for them."
+												required:    false
+												common:      false
+												type: string: {
+													default: "null"
+													enum: {
+														error:  "Events holding them fail to encode."
+														null:   "They are encoded as `null`."
+														string: "They are encoded as the strings `\"NaN\"`, `\"Infinity\"` and `\"-Infinity\"`."
+													}
+												}
+											}
+										}
+									}
+								}
+							}
 						}
 						options: {
 							except_fields: {
@@ -262,6 +347,130 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		dead_letter: {
+			common:      false
+			description: "Configures routing of events the sink fails to deliver."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					enabled: {
+						common: false
+						description: """
+							Send any events that the sink rejects, or fails to deliver, to the `dead_letter`
+							output of the sink, which other components can then use as an input. Events sent to
+							the `dead_letter` output are annotated with the reason for the failure, and the ID
+							and type of the sink, under the `metadata.dropped` field (or as tags, for metrics).
+
+							Only sinks that support end-to-end acknowledgements report the delivery status of
+							events, so no events are routed for sinks that don't.
+							"""
+						required: false
+						type: bool: default: false
+					}
+				}
+			}
+		}
+
+		fallback: {
+			common:      false
+			description: "Configures rerouting of events around the sink while it is failing."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					enabled: {
+						common: false
+						description: """
+							Reroute events to the `fallback` output of the sink, which other components can
+							then use as an input, while the circuit breaker of the sink is open. The breaker
+							opens once `failure_ratio` of the most recent `window_events` events handed to the
+							sink failed to be delivered, or took longer than `max_latency_secs` to be. After
+							`open_secs`, a single batch of events is handed to the sink to probe whether it
+							has recovered, and the breaker closes again if that batch is delivered in time.
+
+							Only sinks that support end-to-end acknowledgements report the delivery status of
+							events, so the breaker never opens for sinks that don't.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					failure_ratio: {
+						common:      false
+						description: "The ratio of failed events, out of the most recent `window_events` events, at which the circuit breaker opens."
+						required:    false
+						type: float: {
+							default: 0.5
+							examples: [0.25]
+						}
+					}
+					window_events: {
+						common:      false
+						description: "The number of most recent events the failure ratio is computed over. The circuit breaker does not open before the sink has finalized at least this many events."
+						required:    false
+						type: uint: {
+							default: 100
+							unit:    "events"
+						}
+					}
+					max_latency_secs: {
+						common:      false
+						description: "How long the sink may take to deliver events before they are counted as failed. By default, events are only counted as failed when the sink fails to deliver them."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [60]
+							unit: "seconds"
+						}
+					}
+					open_secs: {
+						common:      false
+						description: "How long the circuit breaker stays open before events are handed to the sink again to probe whether it has recovered."
+						required:    false
+						type: uint: {
+							default: 30
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
+
+		priority: {
+			common:      false
+			description: "Configures scheduling of events by their priority."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					enabled: {
+						common: false
+						description: """
+							Send events to the sink from the highest priority class first. Events are held in
+							a window of up to `max_events` events in front of the sink, and when the window is
+							full, the newest events of the lowest priority class in it are shed to make room
+							for events of a higher class.
+
+							The priority of an event is set with the `set_metadata_field` function of the
+							`remap` transform, and is `normal` for events that were not given one. The priority
+							is not persisted by disk buffers.
+							"""
+						required: false
+						type: bool: default: false
+					}
+					max_events: {
+						common:      false
+						description: "The maximum number of events held for scheduling."
+						required:    false
+						type: uint: {
+							default: 1000
+							unit:    "events"
+						}
+					}
+				}
+			}
+		}
+
 		if features.healthcheck != _|_ {
 			if features.healthcheck.enabled {
 				healthcheck: {
@@ -328,6 +537,24 @@ components: sinks: [Name=string]: {
 											required: false
 											type: float: default: 2.0
 										}
+										min_concurrency: {
+											common:      false
+											description: "The lowest the concurrency limit is ever set to, which is also the limit it starts from."
+											required:    false
+											type: uint: {
+												default: 1
+												unit:    "requests"
+											}
+										}
+										max_concurrency: {
+											common:      false
+											description: "The highest the concurrency limit is ever set to. For sinks that limit the concurrency of each endpoint separately, such as the `datadog_metrics` sink, this also caps the requests in flight across all endpoints."
+											required:    false
+											type: uint: {
+												default: 200
+												unit:    "requests"
+											}
+										}
 									}
 								}
 							}
@@ -390,6 +617,108 @@ components: sinks: [Name=string]: {
 									unit:    "seconds"
 								}
 							}
+							retry: {
+								common:      false
+								description: "Configures how failed requests are retried, on top of the `retry_*` options."
+								required:    false
+								type: object: {
+									examples: []
+									options: {
+										backoff: {
+											common:      false
+											description: "How the delay between retries grows, from `retry_initial_backoff_secs` up to `retry_max_duration_secs`."
+											required:    false
+											type: string: {
+												default: "fibonacci"
+												enum: {
+													fibonacci:   "Each delay is the sum of the previous two delays."
+													exponential: "Each delay is double the previous delay."
+												}
+											}
+										}
+										budget: {
+											common:      false
+											description: "Limits the number of retries relative to the number of requests made, so that retries can't pile on to a service that is already struggling. Requests that would exceed the budget are dropped instead of retried."
+											required:    false
+											type: object: {
+												examples: []
+												options: {
+													min_per_sec: {
+														common:      false
+														description: "The number of retries allowed every second, regardless of how many requests were made."
+														required:    false
+														type: uint: {
+															default: 10
+															unit:    null
+														}
+													}
+													ratio: {
+														common:      false
+														description: "The number of retries allowed per request made, on top of `min_per_sec`. For example, `0.2` allows one retry for every five requests. Must be between 0 and 1000."
+														required:    false
+														type: float: default: 0.2
+													}
+													ttl_secs: {
+														common:      false
+														description: "How long a request counts towards the budget. Must be between 1 and 60."
+														required:    false
+														type: uint: {
+															default: 10
+															unit:    "seconds"
+														}
+													}
+												}
+											}
+										}
+										jitter: {
+											common:      false
+											description: "How the delay between retries is randomized, which spreads out the retries of requests that failed at the same time."
+											required:    false
+											type: string: {
+												default: "none"
+												enum: {
+													none:  "The delay is used as-is."
+													full:  "The delay is picked at random between zero and the delay."
+													equal: "The delay is picked at random between half the delay and the delay."
+												}
+											}
+										}
+										max_elapsed_secs: {
+											common:      false
+											description: "The maximum amount of time to keep retrying a request for, measured from its first failed attempt."
+											required:    false
+											type: uint: {
+												default: null
+												unit:    "seconds"
+											}
+										}
+										retryable_grpc_codes: {
+											common:      false
+											description: "The gRPC status codes to retry requests on. Requests failing with any other code are not retried. Only applies to sinks that use gRPC."
+											required:    false
+											type: array: {
+												default: null
+												items: type: uint: {
+													examples: [14]
+													unit: null
+												}
+											}
+										}
+										retryable_http_statuses: {
+											common:      false
+											description: "The HTTP response statuses to retry requests on. Requests receiving any other unsuccessful status are not retried. Only applies to sinks that report the HTTP status of their responses."
+											required:    false
+											type: array: {
+												default: null
+												items: type: uint: {
+													examples: [429, 503]
+													unit: null
+												}
+											}
+										}
+									}
+								}
+							}
 							timeout_secs: {
 								common:      true
 								description: "The maximum time a request can take before being aborted. It is highly recommended that you do not lower this value below the service's internal timeout, as this could create orphaned requests, pile on retries, and result in duplicate data downstream."
@@ -633,6 +962,13 @@ components: sinks: [Name=string]: {
 						Other responses will not be retried. You can control the number of
 						retry attempts and backoff rate with the `request.retry_attempts` and
 						`request.retry_backoff_secs` options.
+
+						The `request.retry` options further control how requests are retried:
+						`backoff` and `jitter` shape the delay between retries, `max_elapsed_secs`
+						bounds how long a request is retried for, and `budget` limits retries to a
+						fraction of the requests made. `retryable_http_statuses` and
+						`retryable_grpc_codes` replace the default set of retried responses and
+						errors.
 						"""
 				}
 			}
@@ -664,5 +1000,7 @@ components: sinks: [Name=string]: {
 		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
 		buffer_sent_event_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_sent_event_bytes_total
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		buffer_compressed_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_compressed_bytes_total
+		buffer_logical_bytes_total:           components.sources.internal_metrics.output.metrics.buffer_logical_bytes_total
 	}
 }
@@ -0,0 +1,107 @@
+package metadata
+
+components: sinks: archive: {
+	title: "Archive"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			compression: {
+				enabled: true
+				default: "zstd"
+				algorithms: ["zstd"]
+				levels: ["default"]
+			}
+			encoding: enabled: false
+			request: enabled:  false
+			tls: enabled:      false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		path: {
+			description: "The directory the archive is written to. It's created if it doesn't exist."
+			required:    true
+			type: string: {
+				examples: ["/var/lib/vector/archive"]
+			}
+		}
+		max_size_bytes: {
+			common:      false
+			description: "Complete a file once this many bytes of records, before compression, were written to it, and write the events that follow to a new one."
+			required:    false
+			type: uint: {
+				default: 268435456
+				unit:    "bytes"
+			}
+		}
+		max_age_secs: {
+			common:      false
+			description: "Complete a file once it has been open this many seconds, and write the events that follow to a new one."
+			required:    false
+			type: uint: {
+				default: 3600
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		format: {
+			title: "Archive format"
+			body: """
+				An archive is a directory of zstd-compressed files of newline-delimited JSON records.
+				Each record holds an event in Vector's native JSON encoding, the time of the event,
+				or when it was archived if it has none, when it was archived, and the metadata the
+				native encoding would otherwise lose, such as the priority and tenant of the event.
+				Archives are read back by the [`replay` source](\(urls.vector_sources)/replay) and
+				the `vector replay` command, to reprocess their events after a bad deploy.
+				"""
+		}
+		files: {
+			title: "Files"
+			body: """
+				Files are named after the time they were opened, as in
+				`20220601T120000.000Z-0000.ndjson.zst`, so that their names sort in the order they
+				were written. A file is written under a hidden name, starting with a `.`, until it's
+				complete, which readers of the archive skip.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
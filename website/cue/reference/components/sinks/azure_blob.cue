@@ -67,6 +67,92 @@ components: sinks: azure_blob: {
 	}
 
 	configuration: {
+		auth: {
+			common:        false
+			description:   "Options for the authentication strategy with the storage account."
+			required:      false
+			relevant_when: "storage_account != null"
+			type: object: {
+				examples: []
+				options: {
+					client_id: {
+						description:   "The client ID of the application or of the user-assigned managed identity. For workload identity, this defaults to the `AZURE_CLIENT_ID` environment variable. For managed identity, the system-assigned identity is used if this is unset."
+						required:      false
+						relevant_when: "strategy = \"client_secret\" or strategy = \"workload_identity\" or strategy = \"managed_identity\""
+						type: string: {
+							default: null
+							examples: ["00000000-0000-0000-0000-000000000000"]
+						}
+					}
+					client_secret: {
+						description:   "The client secret of the application, which may be retrieved from a secrets backend."
+						required:      true
+						relevant_when: "strategy = \"client_secret\""
+						type: string: {
+							examples: ["SECRET[backend.azure_client_secret]", "${AZURE_CLIENT_SECRET}"]
+						}
+					}
+					federated_token_file: {
+						description:   "The file holding the token of the Kubernetes service account that is federated with the application. Defaults to the `AZURE_FEDERATED_TOKEN_FILE` environment variable."
+						required:      false
+						relevant_when: "strategy = \"workload_identity\""
+						type: string: {
+							default: null
+							examples: ["/var/run/secrets/azure/tokens/azure-identity-token"]
+						}
+					}
+					refresh_secs: {
+						description:   "How often the SAS token file is read again, so that the token can be rotated without reloading Vector."
+						required:      false
+						relevant_when: "strategy = \"sas_token\""
+						type: uint: {
+							default: 300
+							unit:    "seconds"
+						}
+					}
+					sas_token: {
+						description:   "The SAS token to authenticate with. This or `sas_token_file` has to be provided."
+						required:      false
+						relevant_when: "strategy = \"sas_token\""
+						type: string: {
+							default: null
+							examples: ["SECRET[backend.azure_sas_token]", "sv=2021-06-08&ss=b&srt=co&sp=wac&sig=signature"]
+						}
+					}
+					sas_token_file: {
+						description:   "The file holding the SAS token to authenticate with, which is read again every `refresh_secs`. This or `sas_token` has to be provided."
+						required:      false
+						relevant_when: "strategy = \"sas_token\""
+						type: string: {
+							default: null
+							examples: ["/var/run/secrets/azure/sas-token"]
+						}
+					}
+					strategy: {
+						description: "The authentication strategy to use."
+						required:    true
+						type: string: {
+							enum: {
+								default:           "Tries workload identity, when the `AZURE_FEDERATED_TOKEN_FILE` environment variable is set, then the client secret of the `AZURE_*` environment variables, managed identity and the Azure CLI, in this order."
+								client_secret:     "Authenticates as an application with a client secret."
+								workload_identity: "Authenticates as the application federated with the Kubernetes service account of the pod, with [workload identity](\(urls.azure_workload_identity))."
+								managed_identity:  "Authenticates with the managed identity of the host."
+								sas_token:         "Authenticates with a shared access signature token, which can be rotated when read from a file."
+							}
+						}
+					}
+					tenant_id: {
+						description:   "The ID of the tenant of the application. For workload identity, this defaults to the `AZURE_TENANT_ID` environment variable."
+						required:      false
+						relevant_when: "strategy = \"client_secret\" or strategy = \"workload_identity\""
+						type: string: {
+							default: null
+							examples: ["00000000-0000-0000-0000-000000000000"]
+						}
+					}
+				}
+			}
+		}
 		connection_string: {
 			description: "The Azure Blob Storage Account connection string. Only authentication with access key supported. This or storage_account has to be provided."
 			required:    false
@@ -77,7 +163,7 @@ components: sinks: azure_blob: {
 			}
 		}
 		storage_account: {
-			description: "The Azure Blob Storage Account name. Credentials are read in this order: [EnvironmentCredential](https://docs.rs/azure_identity/latest/azure_identity/struct.DefaultAzureCredential.html), ManagedIdentityCredential, AzureCliCredential. This or connection_string has to be provided."
+			description: "The Azure Blob Storage Account name, which Vector authenticates with as set by `auth`. This or connection_string has to be provided."
 			required:    false
 			common:      true
 			type: string: {
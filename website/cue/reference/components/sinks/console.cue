@@ -40,6 +40,47 @@ components: sinks: console: {
 	}
 
 	configuration: {
+		display: {
+			common:      false
+			description: "Render events for people reading the console, in place of the `encoding`. Metrics are rendered as text."
+			required:    false
+			type: object: options: {
+				color: {
+					description: "Whether to color the output, by level and with dimmed keys."
+					required:    false
+					type: string: {
+						default: "auto"
+						enum: {
+							auto:   "Color the output if the target is a terminal."
+							always: "Always color the output."
+							never:  "Never color the output."
+						}
+					}
+				}
+				fields: {
+					description: "The fields rendered, in order. All of the fields of an event are rendered by default."
+					required:    false
+					type: array: {
+						default: null
+						items: type: string: examples: ["timestamp", "level", "message", "http.status"]
+					}
+				}
+				level_field: {
+					description: "The field holding the level of an event, which it's colored by."
+					required:    false
+					type: string: default: "level"
+				}
+				mode: {
+					description: "How events are rendered."
+					required:    true
+					type: string: enum: {
+						pretty: "The timestamp, level and message of an event on a line, followed by its other fields with their keys aligned."
+						table:  "A row per event, with a column per field, under a header taken from the first event."
+						logfmt: "The `key=value` pairs of the fields of an event on a line."
+					}
+				}
+			}
+		}
 		target: {
 			common:      true
 			description: "The [standard stream](\(urls.standard_streams)) to write to."
@@ -79,7 +79,7 @@ components: sinks: elasticsearch: {
 			required:    false
 			type: object: {
 				examples: []
-				options: components._aws.configuration.auth.type.object.options & {
+				options: components._aws.configuration.auth.type.object.options & configuration._oauth2_auth & {
 					password: {
 						description: "The basic authentication password."
 						required:    true
@@ -92,8 +92,9 @@ components: sinks: elasticsearch: {
 						required:    true
 						type: string: {
 							enum: {
-								aws:   "Authentication strategy used for [AWS' hosted Elasticsearch service](\(urls.aws_elasticsearch))."
-								basic: "The [basic authentication strategy](\(urls.basic_auth))."
+								aws:    "Authentication strategy used for [AWS' hosted Elasticsearch service](\(urls.aws_elasticsearch))."
+								basic:  "The [basic authentication strategy](\(urls.basic_auth))."
+								oauth2: "The [OAuth2](\(urls.oauth2)) authentication strategy, with a bearer token fetched from an authorization server and refreshed before it expires."
 							}
 						}
 					}
@@ -317,6 +318,16 @@ components: sinks: elasticsearch: {
 	}
 
 	how_it_works: {
+		batching: {
+			title: "Batching"
+			body:  """
+				Events are batched separately for each index and bulk action, so every bulk request
+				targets a single index. The size of a batch, against which `batch.max_bytes` is
+				checked, is estimated from the size of the encoded and compressed bulk requests sent
+				so far, rather than from the size of the events in memory.
+				"""
+		}
+
 		conflicts: {
 			title: "Conflicts"
 			body:  """
@@ -352,6 +363,7 @@ components: sinks: elasticsearch: {
 	}
 
 	telemetry: metrics: {
+		component_batches_flushed_total:  components.sources.internal_metrics.output.metrics.component_batches_flushed_total
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
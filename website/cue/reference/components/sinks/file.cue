@@ -20,7 +20,7 @@ components: sinks: file: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -42,6 +42,22 @@ components: sinks: file: {
 	}
 
 	configuration: {
+		atomic_rename: {
+			common:      false
+			description: "Write files to a hidden temporary file in the same directory, and rename them to their final path once they are finalized, so that readers never see a partial file. Implied by `max_size_bytes`, `max_age_secs` and `final_path`."
+			required:    false
+			type: bool: default: false
+		}
+		final_path: {
+			common:      false
+			description: "The path a file is renamed to once it's finalized, rendered with the first event written to it. Defaults to `path`. If a file is already there, a number is added to the name, as in `app.log.1`, and the extension of the compression is appended when compressed."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/log/archive/application-{{ application_id }}-%Y-%m-%d-%H%M%S.log"]
+				syntax: "template"
+			}
+		}
 		idle_timeout_secs: {
 			common:      false
 			description: "The amount of time a file can be idle  and stay open. After not receiving any events for this timeout, the file will be flushed and closed.\n"
@@ -51,6 +67,24 @@ components: sinks: file: {
 				unit:    null
 			}
 		}
+		max_age_secs: {
+			common:      false
+			description: "Finalize a file once it has been open for this long, writing the events that follow to a new one."
+			required:    false
+			type: uint: {
+				default: null
+				unit:    "seconds"
+			}
+		}
+		max_size_bytes: {
+			common:      false
+			description: "Finalize a file once it has grown to this size, writing the events that follow to a new one."
+			required:    false
+			type: uint: {
+				default: null
+				unit:    "bytes"
+			}
+		}
 		path: {
 			description: "File name to write events to."
 			required:    true
@@ -78,6 +112,18 @@ components: sinks: file: {
 				"""
 		}
 
+		rotation: {
+			title: "Rotation & Finalization"
+			body: """
+				When any of `max_size_bytes`, `max_age_secs`, `final_path` or
+				`atomic_rename` is set, events are written to a hidden
+				temporary file next to their final path. Once the file is
+				full, too old, idle for `idle_timeout_secs`, or Vector shuts
+				down, it's closed, compressed with `compression` if set, and
+				renamed into place. Downstream readers polling the directory
+				therefore only ever see complete files.
+				"""
+		}
 		durability: {
 			title: "Durability of Created Files"
 			body: """
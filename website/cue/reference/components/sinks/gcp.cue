@@ -32,6 +32,15 @@ components: sinks: [Name=string]: {
 
 						If credentials aren't found, Vector's health checks fail and an error is
 						[logged](\(urls.vector_monitoring)).
+
+						The credentials file may also hold the `external_account` credentials of
+						[workload identity federation](\(urls.gcp_workload_identity_federation)), generated with
+						`gcloud iam workload-identity-pools create-cred-config`. Vector then exchanges the token of the
+						other identity provider, read from a file, a URL or the AWS credentials of the environment, for
+						a GCP token.
+
+						When the `impersonation_chain` option is set, Vector impersonates the listed service accounts in
+						turn, and authenticates with a token issued for the last of them.
 						"""
 			}
 		}
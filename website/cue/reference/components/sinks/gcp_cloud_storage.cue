@@ -132,6 +132,7 @@ components: sinks: gcp_cloud_storage: {
 				default: "%s"
 			}
 		}
+		impersonation_chain: configuration._gcp_impersonation_chain
 		key_prefix: {
 			category:    "File Naming"
 			common:      true
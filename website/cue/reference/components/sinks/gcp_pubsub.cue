@@ -66,6 +66,7 @@ components: sinks: gcp_pubsub: {
 	configuration: {
 		api_key:          configuration._gcp_api_key
 		credentials_path: configuration._gcp_credentials_path
+		impersonation_chain: configuration._gcp_impersonation_chain
 		endpoint: {
 			common:      false
 			description: "The endpoint to which to send data."
@@ -110,6 +110,7 @@ components: sinks: gcp_stackdriver_logs: {
 				examples: ["My Folder"]
 			}
 		}
+		impersonation_chain: configuration._gcp_impersonation_chain
 		log_id: {
 			description: "The log ID to which to publish logs. This is a name you create to identify this log stream."
 			required:    true
@@ -73,6 +73,7 @@ components: sinks: gcp_stackdriver_metrics: {
 				examples: ["/path/to/credentials.json"]
 			}
 		}
+		impersonation_chain: configuration._gcp_impersonation_chain
 		project_id: {
 			description: "The project ID to which to publish logs. See the [Google Cloud Platform project management documentation](\(urls.gcp_projects)) for more details.\n\nExactly one of `billing_account_id`, `folder_id`, `organization_id`, or `project_id` must be set."
 			required:    true
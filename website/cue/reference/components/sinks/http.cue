@@ -77,6 +77,7 @@ components: sinks: http: {
 			password_example: "${HTTP_PASSWORD}"
 			username_example: "${HTTP_USERNAME}"
 		}}
+		aws: configuration._aws_sigv4
 		uri: {
 			description: """
 				The full URI to make HTTP requests to. This should include the protocol and host,
@@ -99,6 +100,19 @@ components: sinks: http: {
 				examples: ["https://10.22.212.22:9000/health"]
 			}
 		}
+		socket_path: {
+			common: false
+			description: """
+				Send requests over this Unix domain socket, or on Windows this named pipe, rather
+				than connecting to the host of `uri`. The host of `uri` is still sent in the `Host`
+				header, and proxy settings don't apply.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["/var/run/collector.sock", "\\\\.\\pipe\\collector"]
+			}
+		}
 	}
 
 	input: {
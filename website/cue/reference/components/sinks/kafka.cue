@@ -46,6 +46,29 @@ components: sinks: kafka: {
 
 	configuration: {
 		bootstrap_servers: components._kafka.configuration.bootstrap_servers
+		compression_level: {
+			common: false
+			description: """
+				The level of the compression codec. Higher levels compress better at the cost of
+				more CPU. The range depends on `compression`: `0` to `9` for `gzip`, `0` to `12`
+				for `lz4` and `zstd`, and only `0` for `snappy`.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [3, 9]
+				unit: null
+			}
+		}
+		idempotent: {
+			common: false
+			description: """
+				Produce events idempotently, so that retries neither duplicate nor reorder events
+				within a partition. Always enabled when `transactional_id` is set.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		key_field: {
 			common:      true
 			description: "The log field name or tags key to use for the topic key. If the field does not exist in the log or in tags, a blank value will be used. If unspecified, the key is not sent. Kafka uses a hash of the key to choose the partition or uses round-robin if the record has no key."
@@ -66,6 +89,27 @@ components: sinks: kafka: {
 				unit: null
 			}
 		}
+		partitioner: {
+			common: false
+			description: """
+				The partitioner used to choose the partition of events with a key. Use
+				`murmur2_random` to partition events the same way as the Java client, or
+				`fnv1a_random` to partition them the same way as Sarama.
+				"""
+			required: false
+			type: string: {
+				default: "consistent_random"
+				enum: {
+					consistent_random: "The CRC32 hash of the key, or a random partition for events without a key."
+					consistent:        "The CRC32 hash of the key, or the same partition for events without a key."
+					murmur2_random:    "The murmur2 hash of the key, or a random partition for events without a key."
+					murmur2:           "The murmur2 hash of the key, or the same partition for events without a key."
+					fnv1a_random:      "The FNV-1a hash of the key, or a random partition for events without a key."
+					fnv1a:             "The FNV-1a hash of the key, or the same partition for events without a key."
+					random:            "A random partition."
+				}
+			}
+		}
 		sasl: {
 			common:      false
 			description: "Options for SASL/SCRAM authentication support."
@@ -127,6 +171,33 @@ components: sinks: kafka: {
 				examples: ["headers"]
 			}
 		}
+		sticky_partitioning_linger_ms: {
+			common: false
+			description: """
+				How long events without a key keep going to the same partition before moving on to
+				another one, so that they fill larger batches.
+				"""
+			required: false
+			type: uint: {
+				default: 10
+				examples: [0, 100]
+				unit: "milliseconds"
+			}
+		}
+		transactional_id: {
+			common: false
+			description: """
+				When set, events are produced in transactions with this transactional ID, and the
+				offsets of connected `kafka` sources with `exactly_once` enabled are committed in
+				the same transactions. See [exactly-once delivery](#exactly-once-delivery). The ID
+				must be unique to this sink across all Vector instances.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["vector-kafka-out"]
+			}
+		}
 	}
 
 	input: {
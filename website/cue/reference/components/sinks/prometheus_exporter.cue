@@ -124,6 +124,96 @@ components: sinks: prometheus_exporter: {
 			required:    false
 			type: bool: default: false
 		}
+		allowed_metrics: {
+			common:      false
+			description: "Patterns the names of the exposed metrics, including their namespace, must match one of. All metrics are exposed if empty."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: examples: ["http_.*", "process_(cpu|memory)_.*"]
+			}
+		}
+		expiration: {
+			common:      false
+			description: "How long the metrics whose names match a pattern are exposed without being updated, in place of `flush_period_secs`. The first matching rule applies, and metrics are removed once expired, so that ephemeral label sets don't pile up."
+			required:    false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					name: {
+						description: "A pattern matching the names of the metrics, including their namespace."
+						required:    true
+						type: string: examples: ["kube_pod_.*"]
+					}
+					ttl_secs: {
+						description: "How long the metrics are exposed without being updated."
+						required:    true
+						type: uint: unit: "seconds"
+					}
+				}
+			}
+		}
+		protobuf: {
+			common:      false
+			description: "Whether to serve the Prometheus protobuf exposition format to the scrapers asking for it in their `Accept` header. Other scrapers get the text format."
+			required:    false
+			type: bool: default: false
+		}
+		relabel: {
+			common:      false
+			description: "Rules rewriting the labels of the metrics, or dropping them, as they are scraped, like Prometheus' `metric_relabel_configs`. The metric name is the `__name__` label."
+			required:    false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					action: {
+						description: "What the rule does."
+						required:    false
+						type: string: {
+							default: "replace"
+							enum: {
+								replace:   "Set `target_label` to `replacement` if `regex` matches, removing it if the replacement is empty."
+								keep:      "Drop the metrics `regex` doesn't match."
+								drop:      "Drop the metrics `regex` matches."
+								labeldrop: "Remove the labels whose names `regex` matches."
+								labelkeep: "Remove the labels whose names `regex` doesn't match."
+							}
+						}
+					}
+					regex: {
+						description: "The pattern matched against the whole of the joined source label values, or of the label names."
+						required:    false
+						type: string: default: "(.*)"
+					}
+					replacement: {
+						description: "The value of the target label, with the groups of `regex` expanded."
+						required:    false
+						type: string: default: "$1"
+					}
+					separator: {
+						description: "The separator the source label values are joined with."
+						required:    false
+						type: string: default: ";"
+					}
+					source_labels: {
+						description: "The labels whose values are matched against `regex`."
+						required:    false
+						type: array: {
+							default: []
+							items: type: string: examples: ["__name__", "pod"]
+						}
+					}
+					target_label: {
+						description: "The label set by the `replace` action."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["deployment"]
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -336,6 +426,15 @@ components: sinks: prometheus_exporter: {
 	]
 
 	how_it_works: {
+		federation: {
+			title: "Federation"
+			body: """
+				Like Prometheus' federation endpoint, the `/metrics` endpoint
+				takes `match[]` query parameters with series selectors, such as
+				`match[]={job=~"api.*"}`, and only exposes the metrics matching
+				any of them. Selectors are matched after `relabel` rules apply.
+				"""
+		}
 		histogram_buckets: {
 			title: "Histogram Buckets"
 			body:  """
@@ -84,6 +84,19 @@ components: sinks: socket: {
 				examples: ["/path/to/socket"]
 			}
 		}
+		proxy_protocol: {
+			common:        false
+			description:   "The version of the [PROXY protocol](\(urls.haproxy_proxy_protocol)) header to send at the start of each connection, with the local address of the connection as the client address. By default, no header is sent."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: string: {
+				default: null
+				enum: {
+					v1: "The human-readable version 1."
+					v2: "The binary version 2."
+				}
+			}
+		}
 	}
 
 	input: {
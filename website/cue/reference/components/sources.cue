@@ -5,6 +5,83 @@ components: sources: [Name=string]: {
 	features: _
 
 	configuration: {
+		tags: {
+			common:      false
+			description: "Tags attached to the events of this source, overriding the global `tags` of the same name. A tag set to an empty string is not attached to the events of this source."
+			required:    false
+			type: object: {
+				examples: [{team: "payments", env: "staging"}]
+				options: {}
+			}
+		}
+
+		log_namespace: {
+			common:      false
+			description: "Where this source places the host, timestamp, source type and metadata fields of its logs, overriding the global `schema.log_namespace` policy."
+			required:    false
+			type: string: {
+				default: null
+				enum: {
+					legacy: "At the root of the event, under the keys of the global `log_schema`."
+					vector: "Nested in the `vector` field."
+				}
+			}
+		}
+
+		admission: {
+			common:      false
+			description: "Limits on the rate at which events from this source are admitted into the topology. These apply in addition to the globally configured limits."
+			required:    false
+			type: object: options: {
+				events_per_sec: {
+					common:      true
+					description: "The maximum number of events admitted per second from this source."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [10_000]
+						unit: "events"
+					}
+				}
+				bytes_per_sec: {
+					common:      false
+					description: "The maximum number of bytes of events admitted per second from this source, as measured by their in-memory size."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [10_485_760]
+						unit: "bytes"
+					}
+				}
+				tenant_key: {
+					common:      false
+					description: """
+						A log field whose value identifies the tenant of an event. When set, the limits apply
+						to the events of each tenant separately, so that a single noisy tenant cannot use up
+						the limits of the others. Events without the field share the limits of one tenant.
+						"""
+					required:    false
+					type: string: {
+						default: null
+						examples: ["tenant", "kubernetes.pod_namespace"]
+						syntax: "field_path"
+					}
+				}
+				when_exceeded: {
+					common:      false
+					description: "What to do with events that exceed the limits."
+					required:    false
+					type: string: {
+						default: "block"
+						enum: {
+							block: "Wait until the events can be admitted, applying backpressure to the source."
+							drop:  "Drop the events."
+						}
+					}
+				}
+			}
+		}
+
 		if features.collect != _|_ {
 			if features.collect.checkpoint.enabled {
 				data_dir: {
@@ -174,6 +251,27 @@ components: sources: [Name=string]: {
 								}
 							}
 						}
+						json: {
+							description:   "Options for the `json` codec."
+							required:      false
+							common:        false
+							relevant_when: "codec = `json`"
+							type: object: options: {
+								non_finite_floats: {
+									description: "How the non-standard `NaN`, `Infinity` and `-Infinity` literals are decoded, since JSON has no representation for floats that aren't finite."
+									required:    false
+									common:      false
+									type: string: {
+										default: "error"
+										enum: {
+											error:  "Frames holding them fail to decode."
+											null:   "They are decoded as `null`."
+											string: "They are decoded as the strings `\"NaN\"`, `\"Infinity\"` and `\"-Infinity\"`."
+										}
+									}
+								}
+							}
+						}
 					}
 				}
 			}
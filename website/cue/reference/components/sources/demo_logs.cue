@@ -50,6 +50,8 @@ components: sources: demo_logs: {
 					"syslog":        "Randomly generated logs in Syslog format ([RFC 5424](\(urls.syslog_5424)))."
 					"bsd_syslog":    "Randomly generated logs in Syslog format ([RFC 3164](\(urls.syslog_3164)))."
 					"json":          "Randomly generated HTTP server logs in [JSON](\(urls.json)) format."
+					"schema":        "Lines generated from the `fields` schema, as JSON objects or rendered in `template`."
+					"replay":        "The lines of the NDJSON capture at `path`, replayed over and over with their original timing."
 				}
 			}
 		}
@@ -58,7 +60,8 @@ components: sources: demo_logs: {
 			description: """
 				The amount of time, in seconds, to pause between each batch of output lines. The
 				default is one batch per second. In order to remove the delay and output batches as
-				quickly as possible, set `interval` to `0.0`.
+				quickly as possible, set `interval` to `0.0`. Replayed lines are instead paced by the
+				times they were captured at.
 				"""
 			required: false
 			type: float: {
@@ -68,7 +71,7 @@ components: sources: demo_logs: {
 		}
 		count: {
 			common:      false
-			description: "The total number of lines to output. By default the source continuously prints logs (infinitely)."
+			description: "The total number of lines to output, starting replayed captures over until then. By default the source continuously prints logs (infinitely)."
 			required:    false
 			type: uint: {
 				default: null
@@ -94,6 +97,85 @@ components: sources: demo_logs: {
 			required:      false
 			type: bool: default: false
 		}
+		fields: {
+			common:        false
+			relevant_when: "`format` = `schema`"
+			description: """
+				The fields of the generated lines, in order, along with how their values are generated.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						"host":   {type: "ipv4", cardinality: 50}
+						"status": {type: "one_of", values: [200, 404, 500]}
+						"time":   {type: "timestamp", skew_secs: 30.0}
+					},
+				]
+				options: {
+					"*": {
+						common:      false
+						description: """
+							How the values of the field are generated. `type` is one of `string` (with a
+							`length`, 8 by default), `integer` (between `min` and `max`), `float` (between
+							`min` and `max`, 0 and 1 by default), `boolean`, `timestamp` (the current time
+							shifted at random by up to `skew_secs` seconds either way, formatted with the
+							`strftime` `format`, RFC 3339 by default), `ipv4`, or `one_of` (one of the
+							`values`). `cardinality` limits the number of distinct values of the field.
+							"""
+						required: false
+						type: object: {}
+					}
+				}
+			}
+		}
+		template: {
+			common:        false
+			relevant_when: "`format` = `schema`"
+			description: """
+				A template of the generated lines, in which `{{ field }}` is replaced by the value of
+				the field. By default, the lines are JSON objects of the fields.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["{{ host }} - - [{{ time }}] \"GET / HTTP/1.1\" {{ status }}"]
+			}
+		}
+		path: {
+			common:        false
+			relevant_when: "`format` = `replay`"
+			description: """
+				The path of the capture to replay, with one JSON event per line, such as a capture of
+				traffic exported to NDJSON. Each line is sent as it is, to be decoded by `decoding`.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["/var/lib/captures/traffic.ndjson"]
+			}
+		}
+		timestamp_field: {
+			common:        false
+			relevant_when: "`format` = `replay`"
+			description: """
+				The field of the replayed events holding the time they were captured at, as an
+				RFC 3339 timestamp or a number of seconds since the Unix epoch. Lines without it are
+				sent right after the previous one.
+				"""
+			required: false
+			type: string: default: "timestamp"
+		}
+		speed: {
+			common:        false
+			relevant_when: "`format` = `replay`"
+			description:   "How many times faster than it was captured the capture is replayed."
+			required:      false
+			type: float: {
+				default: 1.0
+				examples: [1.0, 10.0]
+			}
+		}
 	}
 
 	output: logs: {}
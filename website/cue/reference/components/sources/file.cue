@@ -161,6 +161,13 @@ components: sources: file: {
 				examples: ["\(_directory)/**/*.log"]
 			}
 		}
+		io_uring: {
+			category:    "Reading"
+			common:      false
+			description: "Read ahead uncompressed files through [io_uring](\(urls.io_uring)), with a single submission for all the watched files on each pass rather than one `read` system call per file, which reduces the overhead of watching many files. Requires Linux 5.6 or later, and Vector built with the `io-uring` feature; otherwise files are read as usual."
+			required:    false
+			type: bool: default: false
+		}
 		line_delimiter: {
 			common:      false
 			description: "String sequence used to separate one file line from another"
@@ -70,6 +70,7 @@ components: sources: gcp_pubsub: {
 		}
 		api_key:          configuration._gcp_api_key
 		credentials_path: configuration._gcp_credentials_path
+		impersonation_chain: configuration._gcp_impersonation_chain
 		endpoint: {
 			common:      false
 			description: "The endpoint from which to pull data."
@@ -68,11 +68,11 @@ components: sources: host_metrics: {
 
 	configuration: {
 		collectors: {
-			description: "The list of host metric collector services to use. Defaults to all collectors."
+			description: "The list of host metric collector services to use. Defaults to all collectors but `gpu`."
 			common:      true
 			required:    false
 			type: array: {
-				default: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				default: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network", "pressure"]
 				items: type: string: {
 					enum: {
 						cgroups:    "Metrics related to Linux control groups."
@@ -83,6 +83,8 @@ components: sources: host_metrics: {
 						host:       "Metrics related to host"
 						memory:     "Metrics related to memory utilization."
 						network:    "Metrics related to network utilization."
+						pressure:   "Pressure stall information, the share of time tasks waited on the CPU, memory or I/O (Linux only)."
+						gpu:        "Metrics related to NVIDIA GPU utilization, gathered with `nvidia-smi`."
 					}
 				}
 			}
@@ -353,6 +355,22 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		gpu: {
+			common:      false
+			description: #"Options for the "gpu" metrics collector."#
+			required:    false
+			type: object: options: {
+				nvidia_smi_path: {
+					common:      false
+					required:    false
+					description: "The `nvidia-smi` executable to query the GPUs with. Looked up in the `PATH` unless it is a path."
+					type: string: {
+						default: "nvidia-smi"
+						examples: ["/usr/bin/nvidia-smi"]
+					}
+				}
+			}
+		}
 		network: {
 			common:      false
 			description: #"Options for the "network" metrics collector."#
@@ -442,6 +460,21 @@ components: sources: host_metrics: {
 		cgroup_memory_current_bytes:     _host & _cgroup_memory & {description: "The total amount of memory currently being used by this cgroup and its descendants, in bytes."}
 		cgroup_memory_anon_bytes:        _host & _cgroup_memory & {description: "The total amount of memory used by this cgroup in anonymous mappings (normal program allocation), in bytes."}
 		cgroup_memory_file_bytes:        _host & _cgroup_memory & {description: "The total amount of memory used by this cgroup to cache filesystem data, including tmpfs and shared memory, in bytes."}
+		cgroup_memory_max_bytes:         _host & _cgroup_memory & {description: "The memory limit of this cgroup, in bytes. Not reported for unlimited cgroups."}
+		cgroup_memory_oom_kills_total:   _host & _cgroup_memory_counter & {description: "The number of processes of this cgroup killed by the out-of-memory killer."}
+		cgroup_cpu_throttled_periods_total: _host & _cgroup_cpu & {description: "The number of periods in which this cgroup was throttled for exceeding its CPU limit."}
+		cgroup_cpu_throttled_seconds_total: _host & _cgroup_cpu & {description: "The total amount of time this cgroup was throttled for exceeding its CPU limit, in seconds."}
+		cgroup_io_read_bytes_total:         _host & _cgroup_io & {description:  "The accumulated number of bytes read by this cgroup from the device."}
+		cgroup_io_reads_completed_total:    _host & _cgroup_io & {description:  "The accumulated number of read operations completed by this cgroup on the device."}
+		cgroup_io_written_bytes_total:      _host & _cgroup_io & {description:  "The accumulated number of bytes written by this cgroup to the device."}
+		cgroup_io_writes_completed_total:   _host & _cgroup_io & {description:  "The accumulated number of write operations completed by this cgroup on the device."}
+		cgroup_pressure_avg10_ratio:        _host & _cgroup_pressure & {type: "gauge", description: "The share of the last 10 seconds in which tasks of this cgroup were stalled on the resource."}
+		cgroup_pressure_avg60_ratio:        _host & _cgroup_pressure & {type: "gauge", description: "The share of the last 60 seconds in which tasks of this cgroup were stalled on the resource."}
+		cgroup_pressure_avg300_ratio:       _host & _cgroup_pressure & {type: "gauge", description: "The share of the last 300 seconds in which tasks of this cgroup were stalled on the resource."}
+		cgroup_pressure_stalled_seconds_total: _host & _cgroup_pressure & {
+			description: "The total amount of time tasks of this cgroup were stalled on the resource, in seconds."
+			type:        "counter"
+		}
 
 		// Host disk
 		disk_read_bytes_total:       _host & _disk_counter & {description: "The accumulated number of bytes read in."}
@@ -455,6 +488,14 @@ components: sources: host_metrics: {
 		filesystem_used_bytes:  _host & _filesystem_bytes & {description: "The number of bytes used on the named filesystem."}
 		filesystem_used_ratio:  _host & _filesystem_bytes & {description: "The ratio between used and total bytes on the named filesystem."}
 
+		// Host GPUs
+		gpu_utilization_ratio:        _host & _gpu_gauge & {description: "The share of time one or more kernels were running on the GPU."}
+		gpu_memory_utilization_ratio: _host & _gpu_gauge & {description: "The share of time the memory of the GPU was being read or written."}
+		gpu_memory_used_bytes:        _host & _gpu_gauge & {description: "The number of bytes of GPU memory allocated."}
+		gpu_memory_total_bytes:       _host & _gpu_gauge & {description: "The total number of bytes of GPU memory."}
+		gpu_temperature_celsius:      _host & _gpu_gauge & {description: "The temperature of the GPU core, in degrees Celsius."}
+		gpu_power_draw_watts:         _host & _gpu_gauge & {description: "The power drawn by the GPU, in watts."}
+
 		// Host load
 		load1:  _host & _loadavg & {description: "System load averaged over the last 1 second."}
 		load5:  _host & _loadavg & {description: "System load averaged over the last 5 seconds."}
@@ -485,6 +526,15 @@ components: sources: host_metrics: {
 		memory_used_bytes:       _host & _memory_linux & {description: "The number of bytes of main memory used by programs or caches."}
 		memory_wired_bytes:      _host & _memory_macos & {description: "The number of wired bytes of main memory."}
 
+		// Host pressure stall information
+		pressure_avg10_ratio:  _host & _pressure & {type: "gauge", description: "The share of the last 10 seconds in which tasks were stalled on the resource."}
+		pressure_avg60_ratio:  _host & _pressure & {type: "gauge", description: "The share of the last 60 seconds in which tasks were stalled on the resource."}
+		pressure_avg300_ratio: _host & _pressure & {type: "gauge", description: "The share of the last 300 seconds in which tasks were stalled on the resource."}
+		pressure_stalled_seconds_total: _host & _pressure & {
+			description: "The total amount of time tasks were stalled on the resource, in seconds."
+			type:        "counter"
+		}
+
 		// Host network
 		network_receive_bytes_total:         _host & _network_gauge & {description: "The number of bytes received on this interface."}
 		network_receive_errs_total:          _host & _network_gauge & {description: "The number of errors encountered during receives on this interface."}
@@ -503,14 +553,53 @@ components: sources: host_metrics: {
 			type: "counter"
 			tags: _host_metrics_tags & {
 				collector: examples: ["cgroups"]
-				cgroup: _cgroup_name
+				cgroup:       _cgroup_name
+				container_id: _cgroup_container_id
 			}
 		}
 		_cgroup_memory: {
 			type: "gauge"
 			tags: _host_metrics_tags & {
 				collector: examples: ["cgroups"]
-				cgroup: _cgroup_name
+				cgroup:       _cgroup_name
+				container_id: _cgroup_container_id
+			}
+		}
+		_cgroup_io: {
+			type: "counter"
+			tags: _host_metrics_tags & {
+				collector: examples: ["cgroups"]
+				cgroup:       _cgroup_name
+				container_id: _cgroup_container_id
+				device: {
+					description: "The `major:minor` numbers of the block device."
+					required:    true
+					examples: ["8:0", "253:1"]
+				}
+			}
+			relevant_when: "the I/O controller is enabled for the cgroup"
+		}
+		_cgroup_pressure: {
+			tags: _host_metrics_tags & {
+				collector: examples: ["cgroups"]
+				cgroup:       _cgroup_name
+				container_id: _cgroup_container_id
+				resource:     _pressure_resource
+				kind:         _pressure_kind
+			}
+			relevant_when: "the kernel supports pressure stall information"
+		}
+		_cgroup_container_id: {
+			description: "The ID of the container the cgroup is for, when it is named for one by Docker, containerd, CRI-O or Podman."
+			required:    false
+			examples: ["3f4e8a0b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f"]
+		}
+		_cgroup_memory_counter: {
+			type: "counter"
+			tags: _host_metrics_tags & {
+				collector: examples: ["cgroups"]
+				cgroup:       _cgroup_name
+				container_id: _cgroup_container_id
 			}
 		}
 		_cgroup_name: {
@@ -543,6 +632,28 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		_gpu_gauge: {
+			type: "gauge"
+			tags: _host_metrics_tags & {
+				collector: examples: ["gpu"]
+				gpu: {
+					description: "The index of the GPU."
+					required:    true
+					examples: ["0"]
+				}
+				uuid: {
+					description: "The UUID of the GPU."
+					required:    true
+					examples: ["GPU-5fd4a3b2-8c1e-4f0a-9b7d-2e6c1a0f3d94"]
+				}
+				name: {
+					description: "The product name of the GPU."
+					required:    true
+					examples: ["NVIDIA A100-SXM4-40GB"]
+				}
+			}
+			relevant_when: "the GPU reports the value"
+		}
 		_loadavg: {
 			type: "gauge"
 			tags: _host_metrics_tags & {
@@ -583,6 +694,31 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_pressure: {
+			tags: _host_metrics_tags & {
+				collector: examples: ["pressure"]
+				resource: _pressure_resource
+				kind:     _pressure_kind
+			}
+			relevant_when: "OS is Linux and the kernel supports pressure stall information"
+		}
+		_pressure_resource: {
+			description: "The resource tasks were stalled on."
+			required:    true
+			enum: {
+				cpu:    "Waiting for CPU time."
+				memory: "Waiting for memory, such as on reclaim or swapping in."
+				io:     "Waiting for block I/O."
+			}
+		}
+		_pressure_kind: {
+			description: "Whether the time is when some tasks were stalled, or when all non-idle tasks were."
+			required:    true
+			enum: {
+				some: "Some tasks were stalled."
+				full: "All non-idle tasks were stalled at once."
+			}
+		}
 	}
 
 	telemetry: metrics: {
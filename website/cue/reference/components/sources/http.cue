@@ -56,10 +56,28 @@ components: sources: http: {
 	configuration: {
 		acknowledgements: configuration._source_acknowledgements
 		address: {
-			description: "The address to accept connections on. The address _must_ include a port."
-			required:    true
+			description: """
+				The address to accept connections on. The address _must_ include a port. To accept
+				connections on a Unix domain socket instead, use `unix:` followed by the path of
+				the socket, or on Windows, the name of a named pipe. TLS is only supported on TCP
+				addresses.
+				"""
+			required: true
 			type: string: {
-				examples: ["0.0.0.0:\(_port)", "localhost:\(_port)"]
+				examples: ["0.0.0.0:\(_port)", "localhost:\(_port)", "unix:/var/run/vector/http.sock", "\\\\.\\pipe\\vector"]
+			}
+		}
+		socket_file_mode: {
+			common: false
+			description: """
+				Unix file mode bits to be applied to the Unix domain socket file as its designated
+				file permissions, when `address` is a Unix domain socket.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [0o777, 0o600, 508]
+				unit: null
 			}
 		}
 		encoding: {
@@ -144,6 +162,162 @@ components: sources: http: {
 				}
 			}
 		}
+		routes: {
+			common:      false
+			description: """
+				A table of route names to the paths served on the same listener instead of `path`, each with its own
+				decoding and authentication. The events received on a route are sent to the output named after it,
+				which other components reference as an input with the name `<source_name>.<route_name>`. Requests are
+				handled by the first route matching their method and path, and no two routes can serve the same
+				method and path.
+				"""
+			required:    false
+			type: object: {
+				examples: [
+					{
+						github: {
+							path: "/webhooks/github"
+							decoding: codec: "json"
+							auth: {
+								strategy: "hmac"
+								secret:   "${GITHUB_WEBHOOK_SECRET}"
+								header:   "X-Hub-Signature-256"
+								prefix:   "sha256="
+							}
+						}
+						stripe: {
+							path: "/webhooks/stripe"
+							decoding: codec: "json"
+							auth: {
+								strategy: "hmac"
+								secret:   "${STRIPE_WEBHOOK_SECRET}"
+								header:   "Stripe-Signature"
+								format:   "stripe"
+							}
+						}
+					},
+				]
+				options: {
+					"*": {
+						description: "A route of the source."
+						required:    true
+						type: object: options: {
+							path: {
+								description: "The URL path served by the route."
+								required:    true
+								type: string: examples: ["/webhooks/github"]
+							}
+							method: {
+								description: "The HTTP method served by the route."
+								common:      false
+								required:    false
+								type: string: {
+									default: "POST"
+									enum:    components.sources.http.configuration.method.type.string.enum
+								}
+							}
+							strict_path: {
+								description: "If set to `false`, requests sent to a URL path that starts with `path` are accepted too."
+								common:      false
+								required:    false
+								type: bool: default: true
+							}
+							framing: {
+								description: "The framing of the requests to the route, which defaults to the `framing` of the source."
+								common:      false
+								required:    false
+								type: object: options: components.sources.http.configuration.framing.type.object.options
+							}
+							decoding: {
+								description: "The decoding of the requests to the route, which defaults to the `decoding` of the source."
+								common:      false
+								required:    false
+								type: object: options: components.sources.http.configuration.decoding.type.object.options
+							}
+							auth: {
+								description: "The authentication of the requests to the route. Routes without one are authenticated with the `auth` of the source, if any."
+								common:      false
+								required:    false
+								type: object: options: {
+									strategy: {
+										description: "The authentication strategy to use."
+										required:    true
+										type: string: enum: {
+											basic:  "The [basic authentication strategy](\(urls.basic_auth))."
+											bearer: "Requests must have the `Authorization: Bearer <token>` header."
+											hmac:   "Requests must have a valid HMAC-SHA256 signature of their body, as sent by GitHub or Stripe webhooks."
+										}
+									}
+									username: {
+										description:   "The basic authentication user name."
+										required:      true
+										relevant_when: "strategy = \"basic\""
+										type: string: examples: ["${HTTP_USERNAME}", "username"]
+									}
+									password: {
+										description:   "The basic authentication password."
+										required:      true
+										relevant_when: "strategy = \"basic\""
+										type: string: examples: ["${HTTP_PASSWORD}", "password"]
+									}
+									token: {
+										description:   "The bearer token."
+										required:      true
+										relevant_when: "strategy = \"bearer\""
+										type: string: examples: ["${HTTP_TOKEN}", "SECRET[backend.http_token]"]
+									}
+									secret: {
+										description:   "The secret shared with the sender, which signatures are keyed with."
+										required:      true
+										relevant_when: "strategy = \"hmac\""
+										type: string: examples: ["${GITHUB_WEBHOOK_SECRET}", "SECRET[backend.webhook_secret]"]
+									}
+									header: {
+										description:   "The header holding the signature."
+										required:      true
+										relevant_when: "strategy = \"hmac\""
+										type: string: examples: ["X-Hub-Signature-256", "Stripe-Signature"]
+									}
+									format: {
+										description:   "How the signature is written in its header."
+										common:        false
+										required:      false
+										relevant_when: "strategy = \"hmac\""
+										type: string: {
+											default: "hex"
+											enum: {
+												hex:    "The hex-encoded signature of the body, after the `prefix`, as sent by GitHub."
+												stripe: "The `t=<timestamp>,v1=<signature>` header sent by Stripe, the signature being that of the timestamp and the body, joined by a `.`."
+											}
+										}
+									}
+									prefix: {
+										description:   "The prefix of `hex` signatures."
+										common:        false
+										required:      false
+										relevant_when: "format = \"hex\""
+										type: string: {
+											default: ""
+											examples: ["sha256="]
+										}
+									}
+									timestamp_tolerance_secs: {
+										description:   "How far the timestamp of `stripe` signatures may be from the current time, to prevent replay attacks."
+										common:        false
+										required:      false
+										relevant_when: "format = \"stripe\""
+										type: uint: {
+											default: 300
+											unit:    "seconds"
+										}
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 	output: logs: {
 		text: {
@@ -275,5 +449,34 @@ components: sources: http: {
 				Supported algorithms are `gzip`, `deflate`, and `snappy`.
 				"""
 		}
+		routes: {
+			title: "Routes"
+			body: """
+				A single listener can host many webhook integrations with `routes`. Each route serves its own path,
+				decodes the requests to it with its own `framing` and `decoding`, authenticates them with its own
+				`auth`, and sends its events to its own output, so that they can be processed separately:
+
+				```toml title="vector.toml"
+				[sources.webhooks]
+				type = "http"
+				address = "0.0.0.0:8080"
+
+				[sources.webhooks.routes.github]
+				path = "/webhooks/github"
+				decoding.codec = "json"
+				auth.strategy = "hmac"
+				auth.secret = "${GITHUB_WEBHOOK_SECRET}"
+				auth.header = "X-Hub-Signature-256"
+				auth.prefix = "sha256="
+
+				[transforms.github_events]
+				type = "remap"
+				inputs = ["webhooks.github"]
+				source = ".source = \"github\""
+				```
+
+				Signatures are checked against the body of the request once any `Content-Encoding` is decoded.
+				"""
+		}
 	}
 }
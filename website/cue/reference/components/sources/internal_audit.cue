@@ -0,0 +1,203 @@
+package metadata
+
+components: sources: internal_audit: {
+	title:       "Internal Audit"
+	description: "The internal audit source exposes the control-plane actions taken on the running Vector instance, such as configuration reloads, API mutations, tap sessions, and the resolution of secrets."
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: ">= 0.23.0"
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		host_key: {
+			category:    "Context"
+			common:      false
+			description: """
+				The key name added to each event representing the current host. This can also be globally set via the
+				[global `host_key` option](\(urls.vector_configuration)/global-options#log_schema.host_key).
+
+				Set to "" to suppress this key.
+				"""
+			required:    false
+			type: string: {
+				default: "host"
+			}
+		}
+		pid_key: {
+			category: "Context"
+			common:   false
+			description: """
+				The key name added to each event representing the current process ID.
+
+				Set to "" to suppress this key.
+				"""
+			required: false
+			type: string: {
+				default: "pid"
+			}
+		}
+	}
+
+	output: logs: action: {
+		description: "An action taken on the running Vector instance."
+		fields: {
+			action: {
+				description: "The kind of action."
+				required:    true
+				type: string: {
+					enum: {
+						config_reload:    "The configuration was reloaded."
+						api_mutation:     "A mutation of the API changed the running instance."
+						tap_started:      "A client started tapping events."
+						tap_stopped:      "A client stopped tapping events."
+						secrets_resolved: "Secrets were retrieved from a secret backend."
+					}
+				}
+			}
+			message: {
+				description: "A description of the action."
+				required:    true
+				type: string: {
+					examples: ["Configuration reloaded."]
+				}
+			}
+			outcome: {
+				description: """
+					For `config_reload`, whether the new configuration was `applied`, `rolled_back` to the
+					previous one, `rejected` as it changed global options, or `failed` altogether. For
+					`secrets_resolved`, whether the secrets were retrieved, as `success` or `failure`.
+					"""
+				required: false
+				type: string: {
+					examples: ["applied", "success"]
+				}
+			}
+			components: {
+				description: "For `config_reload`, the IDs of the components `added`, `changed` and `removed` by the new configuration."
+				required:    false
+				type: object: {
+					examples: [{added: ["new_sink"], changed: ["my_source"], removed: []}]
+					options: {}
+				}
+			}
+			changed_options: {
+				description: "For `config_reload`, the names of the options that changed, for each changed component. Their values aren't recorded."
+				required:    false
+				type: object: {
+					examples: [{my_source: ["address", "max_length"]}]
+					options: {}
+				}
+			}
+			mutation: {
+				description: "For `api_mutation`, the name of the mutation."
+				required:    false
+				type: string: {
+					examples: ["setLogLevel", "setSampleRate"]
+				}
+			}
+			arguments: {
+				description: "For `api_mutation`, the arguments of the mutation."
+				required:    false
+				type: object: {
+					examples: [{component_id: "sampler", rate: 10}]
+					options: {}
+				}
+			}
+			tap_id: {
+				description: "For `tap_started` and `tap_stopped`, the ID of the tap session."
+				required:    false
+				type: string: {
+					examples: ["c7c1a4e2-2c4a-4f7e-9f6a-1b1f8c0e4a2d"]
+				}
+			}
+			outputs_patterns: {
+				description: "For `tap_started`, the patterns of the components whose outputs are tapped."
+				required:    false
+				type: array: items: type: string: examples: ["my_source"]
+			}
+			inputs_patterns: {
+				description: "For `tap_started`, the patterns of the components whose inputs are tapped."
+				required:    false
+				type: array: items: type: string: examples: ["my_sink"]
+			}
+			backend: {
+				description: "For `secrets_resolved`, the name of the secret backend."
+				required:    false
+				type: string: {
+					examples: ["vault"]
+				}
+			}
+			secret_keys: {
+				description: "For `secrets_resolved`, the keys of the secrets retrieved. Their values are never recorded."
+				required:    false
+				type: array: items: type: string: examples: ["db_password"]
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The exact time the action was taken."
+			}
+			host: fields._local_host
+			pid: {
+				description: "The process ID of the Vector instance."
+				required:    true
+				type: uint: {
+					examples: [4232]
+					unit: null
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		early_actions: {
+			title: "Actions taken before startup"
+			body: """
+				Actions taken before the topology first starts, such as resolving the secrets of the
+				initial configuration, are buffered and emitted by the `internal_audit` sources of that
+				topology once they start. Sources added by a later reload only receive the actions taken
+				from then on.
+				"""
+		}
+		lagging: {
+			title: "Lagging"
+			body: """
+				Each source keeps up to 1000 actions that it hasn't emitted yet. When it falls further
+				behind, the oldest actions are lost and a `component_errors_total` error is recorded.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
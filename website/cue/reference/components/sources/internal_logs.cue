@@ -38,6 +38,37 @@ components: sources: internal_logs: {
 	}
 
 	configuration: {
+		errors: {
+			common:      false
+			description: """
+				Summarizes the errors and warnings logged by each component on the `errors` output, as an event
+				per component, level, and message counting how many times it was logged over each window.
+				"""
+			required:    false
+			type: object: options: {
+				level: {
+					common:      false
+					description: "The least severe level summarized."
+					required:    false
+					type: string: {
+						default: "warn"
+						enum: {
+							warn:  "Summarize both errors and warnings."
+							error: "Summarize errors only."
+						}
+					}
+				}
+				window_secs: {
+					common:      false
+					description: "The window over which repeated errors and warnings are counted into a single event."
+					required:    false
+					type: uint: {
+						default: 10
+						unit:    "seconds"
+					}
+				}
+			}
+		}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -67,6 +98,20 @@ components: sources: internal_logs: {
 		}
 	}
 
+	outputs: [
+		{
+			name: components._default_output.name
+			description: "Default output stream of the component, carrying every log and trace message."
+		},
+		{
+			name: "errors"
+			description: """
+				The summaries of the errors and warnings of components, if [errors](#errors) is set. Use
+				`<component_id>.errors` as an input to downstream transforms and sinks.
+				"""
+		},
+	]
+
 	output: logs: line: {
 		description: "An individual log or trace message."
 		fields: {
@@ -144,6 +189,17 @@ components: sources: internal_logs: {
 	}
 
 	how_it_works: {
+		error_summaries: {
+			title: "Error summaries"
+			body: """
+				With the [`errors`](#errors) option set, the `errors` output receives an event per
+				distinct error or warning of each window, with the `component_id`, `component_kind`, and
+				`component_type` of the component that logged it, its `level`, `message`, `error_type`,
+				and `stage`, the `count` of times it was logged, and when it was `first_seen` and
+				`last_seen`. Unlike Vector's own log output, these counts aren't rate limited, so
+				alerting on a specific sink starting to error needs no parsing of log messages.
+				"""
+		}
 		limited_logs: {
 			title: "Logs are limited by startup options"
 			body: """
@@ -104,7 +104,30 @@ components: sources: internal_metrics: {
 			}
 		}
 
+		_adaptive_concurrency_endpoint: {
+			description: "The endpoint the limit applies to, for sinks that limit the concurrency of each endpoint separately."
+			required:    false
+		}
+
+		_tenant_tags: _internal_metrics_tags & {
+			tenant: {
+				description: "The tenant of the events."
+				required:    true
+			}
+		}
+
 		// Instance-level "process" metrics
+		admission_wait_seconds: {
+			description:       "The time sources waited for events to be admitted under the configured admission limits, tagged with the tenant of the events when a `tenant_key` is set."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant: {
+					description: "The tenant of the events."
+					required:    false
+				}
+			}
+		}
 		aggregate_events_recorded_total: {
 			description:       "The number of events recorded by the aggregate transform."
 			type:              "counter"
@@ -222,11 +245,44 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		adaptive_concurrency_decisions_total: {
+			description:       "The total number of decisions the adaptive concurrency feature made about the concurrency limit, at the end of each window."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				decision: {
+					description: "Whether the limit was increased, decreased, or held."
+					required:    true
+					enum: {
+						increase: "The limit was increased."
+						decrease: "The limit was decreased."
+						hold:     "The limit was left unchanged."
+					}
+				}
+				reason: {
+					description: "Why the decision was made."
+					required:    true
+					enum: {
+						rtt_fell:             "The RTT was at most its past average, with the limit reached."
+						back_pressure:        "A response was treated as back pressure."
+						rtt_rose:             "The RTT rose beyond the deviation of its past average."
+						at_min_concurrency:   "The limit would have decreased, but is at `min_concurrency`."
+						at_max_concurrency:   "The limit would have increased, but is at `max_concurrency`."
+						limit_not_reached:    "The requests in flight never reached the limit."
+						no_rtt:               "No request succeeded during the window."
+						rtt_within_deviation: "The RTT stayed within the deviation of its past average."
+					}
+				}
+				endpoint: _adaptive_concurrency_endpoint
+			}
+		}
 		adaptive_concurrency_limit: {
 			description:       "The concurrency limit that the adaptive concurrency feature has decided on for this current window."
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _internal_metrics_tags & {
+				endpoint: _adaptive_concurrency_endpoint
+			}
 		}
 		adaptive_concurrency_observed_rtt: {
 			description:       "The observed round-trip time (RTT) for requests."
@@ -254,6 +310,18 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		circuit_breaker_opened_total: {
+			description:       "The total number of times the circuit breaker of a sink opened, rerouting its events to its `fallback` output."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_state: {
+			description:       "The state of the circuit breaker of a sink with a `fallback` output: `0` when closed, `1` while probing whether the sink has recovered, and `2` when open."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		collect_completed_total: {
 			description:       "The total number of metrics collections completed for this component."
 			type:              "counter"
@@ -452,6 +520,17 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags & {output: _output}
 		}
+		filter_condition_matches_total: {
+			description:       "The number of events passing each of the disjuncts of the condition of the filter transform, when `condition_metrics` is enabled. Only the first disjunct an event passes is counted."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				condition_index: {
+					description: "The position of the disjunct in the condition, starting at `0`."
+					required:    true
+				}
+			}
+		}
 		processed_events_total: {
 			description:       """
 				The total number of events processed by this component.
@@ -481,6 +560,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		buffer_compressed_bytes_total: {
+			description:       "The number of bytes written by this buffer after compression. Only emitted by disk buffers with compression enabled."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_logical_bytes_total: {
+			description:       "The number of bytes written by this buffer before compression. Only emitted by disk buffers with compression enabled."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		buffer_received_event_bytes_total: {
 			description:       "The number of bytes received by this buffer."
 			type:              "counter"
@@ -505,10 +596,59 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		component_batches_flushed_total: {
+			description:       "The number of batches flushed by this sink."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				reason: {
+					description: "Why the batch was flushed."
+					required:    true
+					enum: {
+						max_bytes:  "The batch reached its maximum size in bytes."
+						max_events: "The batch reached its maximum number of events."
+						timeout:    "The batch was open for its maximum time."
+						shutdown:   "The sink was stopping."
+					}
+				}
+			}
+		}
 		component_discarded_events_total: {
 			description:       "The number of events dropped by this component."
 			type:              "counter"
 			default_namespace: "vector"
+			tags:              _component_tags & {
+				reason: {
+					description: "Why the events were dropped."
+					required:    true
+					enum: {
+						buffer_full:      "A buffer or queue of the component was full."
+						codec_error:      "The component failed to decode, parse or encode the events."
+						dlq_routed:       "The sink failed to deliver the events, and routed them to its dead letter output."
+						expired:          "The events expired before the component could deliver them, as set by their time-to-live."
+						invalid_event:    "The events were of a kind or shape the component can't handle, such as too large or out of order."
+						memory_limit:     "The memory taken up by the events in flight was over the hard `memory_limit`."
+						processing_error: "The component failed to process the events, such as a script or template failing."
+						rate_limited:     "The events were over the rate limits of the component or their tenant."
+						stream_closed:    "The component downstream was closed."
+					}
+				}
+				tenant: {
+					description: "The tenant of the events, for events dropped by the limits of their tenant."
+					required:    false
+				}
+			}
+		}
+		component_health_state: {
+			description:       "The health state of the component: `0` when healthy, `1` while starting, `2` when degraded, such as when a component upstream of it failed, and `3` when failed."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		component_memory_bytes: {
+			description:       "The number of bytes of events admitted by the source still in flight in the topology, by their in-memory size, when a global `memory_limit` is set."
+			type:              "gauge"
+			default_namespace: "vector"
 			tags:              _component_tags
 		}
 		component_received_bytes_total: {
@@ -841,6 +981,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		memory_limit_wait_seconds: {
+			description:       "The time sources waited for events in flight to be delivered under the soft `memory_limit`."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		metadata_refresh_failed_total: {
 			description:       "The total number of failed efforts to refresh AWS EC2 metadata."
 			type:              "counter"
@@ -962,6 +1108,28 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		source_acknowledgement_latency_seconds: {
+			description:       "The time between a source sending a batch of events and the batch being acknowledged by all of the sinks it was delivered to, tagged with the status of the batch."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				status: {
+					description: "The status the batch was finalized with."
+					required:    true
+					enum: {
+						delivered: "The events were delivered."
+						errored:   "The events could not be delivered, and may be retried."
+						rejected:  "The events were rejected, and will not be retried."
+					}
+				}
+			}
+		}
+		source_acknowledgement_timeouts_total: {
+			description:       "The total number of batches of events that were not acknowledged within the acknowledgement timeout, and so were treated as errored."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		splunk_pending_acks: {
 			description:       "The number of outstanding Splunk HEC indexer acknowledgement acks."
 			type:              "gauge"
@@ -1046,11 +1214,38 @@ components: sources: internal_metrics: {
 		tag_value_limit_exceeded_total: {
 			description: """
 				The total number of events discarded because the tag has been rejected after
-				hitting the configured `value_limit`.
+				hitting the configured `value_limit`, or because its value is denied.
 				"""
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              _component_tags & {
+				metric_name: {
+					description: "The name of the metric whose tag was rejected."
+					required:    true
+				}
+				tag_key: {
+					description: "The key of the rejected tag."
+					required:    true
+				}
+			}
+		}
+		tenant_buffered_events: {
+			description:       "The number of events of a tenant in flight in the topology, for tenants with a `max_buffered_events` limit."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _tenant_tags
+		}
+		tenant_event_bytes_total: {
+			description:       "The number of bytes of events of a tenant admitted into the topology, as measured by their in-memory size."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _tenant_tags
+		}
+		tenant_events_total: {
+			description:       "The number of events of a tenant admitted into the topology."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _tenant_tags
 		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
@@ -1058,6 +1253,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tls_certificate_expiry_seconds: {
+			description:       "The number of seconds until the TLS certificate of the component expires, negative once it has expired."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		tls_certificate_reloads_total: {
+			description:       "The total number of times the TLS certificates of the component were reloaded after their files changed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		uptime_seconds: {
 			description:       "The total number of seconds the Vector instance has been up."
 			type:              "gauge"
@@ -1091,7 +1298,12 @@ components: sources: internal_metrics: {
 				"""
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              _component_tags & {
+				tag_key: {
+					description: "The key of the tag whose value limit has been reached."
+					required:    true
+				}
+			}
 		}
 
 		// Windows metrics
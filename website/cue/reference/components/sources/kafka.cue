@@ -58,6 +58,18 @@ components: sources: kafka: {
 				unit: "milliseconds"
 			}
 		}
+		exactly_once: {
+			common: false
+			description: """
+				Leave committing consumer offsets to connected `kafka` sinks with a
+				`transactional_id`, which commit them in the same transaction as they produce the
+				events. All sinks fed by the source must be transactional. See
+				[exactly-once delivery](#exactly-once-delivery). Enables end-to-end
+				acknowledgements.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		fetch_wait_max_ms: {
 			common:      false
 			description: "Maximum time the broker may wait to fill the response."
@@ -75,6 +87,19 @@ components: sources: kafka: {
 				examples: ["consumer-group-name"]
 			}
 		}
+		group_instance_id: {
+			common: false
+			description: """
+				The static membership ID of this consumer in the group. A static member that
+				restarts within `session_timeout_ms` gets its partitions back without rebalancing
+				the group. The ID must be unique within the group.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["vector-0"]
+			}
+		}
 		key_field: {
 			common:      true
 			description: "The log field name to use for the Kafka message key."
@@ -147,6 +147,7 @@ components: sources: prometheus_scrape: {
 			password_example: "${PROMETHEUS_PASSWORD}"
 			username_example: "${PROMETHEUS_USERNAME}"
 		}}
+		aws: configuration._aws_sigv4
 	}
 
 	output: metrics: {
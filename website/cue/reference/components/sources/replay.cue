@@ -0,0 +1,121 @@
+package metadata
+
+components: sources: replay: {
+	title:       "Replay"
+	description: "The replay source re-injects the events of an archive written by the `archive` sink, then stops."
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector archive"
+				thing:    "a \(name)"
+				url:      urls.vector_sinks + "/archive"
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		path: {
+			description: "The directory of the archive."
+			required:    true
+			type: string: {
+				examples: ["/var/lib/vector/archive"]
+			}
+		}
+		start: {
+			common:      true
+			description: "Replay only the events from this time on, as an RFC 3339 timestamp."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["2022-06-01T12:00:00Z"]
+			}
+		}
+		end: {
+			common:      true
+			description: "Replay only the events before this time, as an RFC 3339 timestamp."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["2022-06-01T13:00:00Z"]
+			}
+		}
+		speed: {
+			common:      false
+			description: "Replay the events as far apart as they happened, divided by this speed, rather than as fast as possible. For example, `10` replays an hour of events in six minutes."
+			required:    false
+			type: float: {
+				default: null
+				examples: [1.0, 10.0]
+			}
+		}
+	}
+
+	output: {
+		logs: event: {
+			description: "An archived event"
+			fields: {
+				"*": {
+					description: "The fields of the event as it was archived. The `replay` source will not modify or add fields."
+					required:    true
+					type: "*": {}
+				}
+			}
+		}
+		metrics: {
+			counter:      output._passthrough_counter
+			distribution: output._passthrough_distribution
+			gauge:        output._passthrough_gauge
+			histogram:    output._passthrough_histogram
+			set:          output._passthrough_set
+		}
+	}
+
+	how_it_works: {
+		replay: {
+			title: "Replaying"
+			body: """
+				The events are replayed in the order they were archived, with the metadata they had,
+				such as their priority and tenant. Events are filtered by their time, or the time they
+				were archived if they have none. Files that were cut short, as when Vector crashed
+				while writing them, are replayed up to their last complete record. Once the archive
+				is replayed, the source stops, and Vector shuts down if no other source is running.
+
+				To reprocess archived events through the components downstream of a source without
+				changing the configuration, use `vector replay`, which runs the configuration with
+				that source replaced by a replay of the archive.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
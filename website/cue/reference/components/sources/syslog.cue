@@ -108,6 +108,13 @@ components: sources: syslog: {
 				unit:    "concurrency"
 			}
 		}
+		proxy_protocol: {
+			common:        false
+			description:   "Whether or not connections start with a [PROXY protocol](\(urls.haproxy_proxy_protocol)) header, of either version, as sent by load balancers such as HAProxy or AWS Network Load Balancers. The client address in the header is used as the `source_ip` of the events instead of the address of the load balancer. Connections without a valid header are closed."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: bool: default: false
+		}
 	}
 
 	output: logs: line: {
@@ -33,6 +33,16 @@ components: transforms: filter: {
 			required: true
 			type: condition: {}
 		}
+		condition_metrics: {
+			common: false
+			description: """
+				Reports the number of events passing each of the disjuncts of the condition, when it is a VRL
+				disjunction such as `.status == 500 || .level == "error"`. The disjuncts are then checked one by one, in
+				order, until one of them passes, so that only the first disjunct an event passes is counted.
+				"""
+			required: false
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -81,5 +91,6 @@ components: transforms: filter: {
 
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+		filter_condition_matches_total: components.sources.internal_metrics.output.metrics.filter_condition_matches_total
 	}
 }
@@ -40,7 +40,7 @@ components: transforms: "remap": {
 			description: """
 				The [Vector Remap Language](\(urls.vrl_reference)) (VRL) program to execute for each event.
 
-				Required if `file` is missing.
+				Required if neither `file` nor `library` is set.
 				"""
 			common:      true
 			required:    false
@@ -64,7 +64,11 @@ components: transforms: "remap": {
 
 				If a relative path is provided, its root is the current working directory.
 
-				Required if `source` is missing.
+				Modules imported by the program using `import "path/to/module.vrl"` are resolved relative to the
+				directory of this file. Modules can only contain imports, function definitions, constants and
+				variable assignments, and each module is included once, even if it's imported multiple times.
+
+				Required if neither `source` nor `library` is set.
 				"""
 			common:      true
 			required:    false
@@ -75,6 +79,22 @@ components: transforms: "remap": {
 				]
 			}
 		}
+		library: {
+			description: """
+				The name of a program of the top-level `vrl_library` to execute for each event. The program is
+				compiled once and shared by all the components referring to it.
+
+				Required if neither `source` nor `file` is set.
+				"""
+			common:      false
+			required:    false
+			type: string: {
+				default: null
+				examples: [
+					"parse_message",
+				]
+			}
+		}
 		drop_on_error: {
 			common:   false
 			required: false
@@ -104,6 +124,41 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: false
 		}
+		outputs: {
+			common:   false
+			required: false
+			description: """
+				Additional named outputs the VRL program can send events to, by setting the `output` metadata
+				field with `set_metadata_field("output", "<name>")`. Events that don't set this field are sent to
+				the default output. Events that set it to an output that doesn't exist, or that doesn't accept
+				their type, are handled as runtime errors according to `drop_on_error` and `reroute_dropped`.
+				"""
+			type: object: {
+				options: {
+					"*": {
+						description: "The types of events accepted by this output."
+						required:    true
+						type: object: options: {
+							logs: {
+								description: "Whether log events are accepted by this output."
+								required:    false
+								type: bool: default: true
+							}
+							metrics: {
+								description: "Whether metric events are accepted by this output."
+								required:    false
+								type: bool: default: true
+							}
+							traces: {
+								description: "Whether trace events are accepted by this output."
+								required:    false
+								type: bool: default: true
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -146,6 +201,18 @@ components: transforms: "remap": {
 				[Vector Remap Language reference](\#(urls.vrl_reference)).
 				"""#
 		}
+		program_cache: {
+			title: "Program cache"
+			body: """
+				Compiled programs are cached for as long as Vector runs, so that reloading the configuration only
+				compiles the programs that changed, or whose input schema changed, since the last time they were
+				compiled. Programs that import modules, or that are compiled while enrichment tables are configured,
+				are compiled again on every reload.
+
+				The cache is kept in memory only. It isn't persisted to disk, so all programs are compiled again
+				when Vector restarts.
+				"""
+		}
 		event_data_model: {
 			title: "Event Data Model"
 			body:  """
@@ -223,6 +290,14 @@ components: transforms: "remap": {
 				omitting any partial modification that took place before the error or abort.
 				"""
 		},
+		{
+			name: "<output_name>"
+			description: """
+				Each output configured in `outputs` is exposed as an additional output of this transform. For a
+				transform component named `foo`, the output `bar` can be accessed by specifying `foo.bar` as the
+				input to another component.
+				"""
+		},
 	]
 
 	telemetry: metrics: {
@@ -33,11 +33,28 @@ components: transforms: sample: {
 				Consistently samples the same events. Actual rate of sampling may differ from the configured one if
 				values in the field are not uniformly distributed. If left unspecified, or if the event doesn't have
 				`key_field`, events will be count rated.
+
+				As the hash only depends on the value of the field, all the events sharing the same value, such as the
+				same `trace_id` or `user_id`, are either all passed or all dropped, including across multiple Vector
+				instances configured with the same `rate` and `hash_seed`.
 				"""
 			required: false
 			type: string: {
 				default: null
-				examples: ["message"]
+				examples: ["message", "trace_id"]
+			}
+		}
+		hash_seed: {
+			common: false
+			description: """
+				The seed of the hash of the `key_field` values. Instances configured with the same seed sample the same
+				values, while instances configured with different seeds sample values independently of each other.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [42]
+				unit: null
 			}
 		}
 		exclude: {
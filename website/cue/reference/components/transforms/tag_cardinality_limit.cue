@@ -27,6 +27,25 @@ components: transforms: tag_cardinality_limit: {
 	}
 
 	configuration: {
+		allowed_values: {
+			common:      false
+			description: "Tag values that are always accepted, by tag key. These values don't count towards the `value_limit` of their key."
+			required:    false
+			type: object: {
+				examples: [
+					{
+						environment: ["production", "staging"]
+					},
+				]
+				options: {
+					"*": {
+						description: "The values always accepted for the tag key."
+						required:    true
+						type: array: items: type: string: {}
+					}
+				}
+			}
+		}
 		cache_size_per_tag: {
 			common:        false
 			description:   "The size of the cache in bytes to use to detect duplicate tags. The bigger the cache the less likely it is to have a 'false positive' or a case where we allow a new value for tag even after we have reached the configured limits."
@@ -37,6 +56,25 @@ components: transforms: tag_cardinality_limit: {
 				unit:    "bytes"
 			}
 		}
+		denied_values: {
+			common:      false
+			description: "Tag values that are always rejected, by tag key, as if the `value_limit` of their key was reached."
+			required:    false
+			type: object: {
+				examples: [
+					{
+						user_id: ["unknown"]
+					},
+				]
+				options: {
+					"*": {
+						description: "The values always rejected for the tag key."
+						required:    true
+						type: array: items: type: string: {}
+					}
+				}
+			}
+		}
 		limit_exceeded_action: {
 			common:      true
 			description: "Controls what should happen when a metric comes in with a tag that would exceed the configured limit on cardinality."
@@ -59,6 +97,53 @@ components: transforms: tag_cardinality_limit: {
 				}
 			}
 		}
+		per_metric_limits: {
+			common: false
+			description: """
+				Limits applying to the metrics whose name matches a pattern, in place of the `value_limit` and
+				`limit_exceeded_action` of the transform. Patterns are glob patterns, such as `http_*`, and
+				metrics are subject to the first pattern their name matches. The values of the metrics matching
+				a pattern are counted separately from the values of all the other metrics.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						"http_*": {
+							value_limit:           1000
+							limit_exceeded_action: "drop_event"
+						}
+					},
+				]
+				options: {
+					"*": {
+						description: "The limit applying to the metrics whose name matches the pattern."
+						required:    true
+						type: object: options: {
+							limit_exceeded_action: {
+								description: "The action taken for these metrics, defaulting to the `limit_exceeded_action` of the transform."
+								required:    false
+								type: string: {
+									default: null
+									enum: {
+										drop_tag:   "Remove tags that would exceed the configured limit from the incoming metric"
+										drop_event: "Drop any metric events that contain tags that would exceed the configured limit"
+									}
+								}
+							}
+							value_limit: {
+								description: "How many distinct values to accept for any given key of these metrics, defaulting to the `value_limit` of the transform."
+								required:    false
+								type: uint: {
+									default: null
+									unit:    null
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 		value_limit: {
 			common:      true
 			description: "How many distinct values to accept for any given key."
@@ -203,6 +288,16 @@ components: transforms: tag_cardinality_limit: {
 				"""
 		}
 
+		finding_offending_producers: {
+			title: "Finding offending producers"
+			body: """
+				The `tag_value_limit_exceeded_total` internal metric is tagged with the name of the
+				metric and the key of the tag that were rejected, and `value_limit_reached_total`
+				with the key whose limit was reached, which points to the producers adding high
+				cardinality tags.
+				"""
+		}
+
 		restarts: {
 			title: "Restarts"
 			body: """
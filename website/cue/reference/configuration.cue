@@ -19,6 +19,343 @@ configuration: {
 					required: false
 					type: bool: default: false
 				}
+				timeout_secs: {
+					common: false
+					description: """
+						How long sources wait for events to be acknowledged by sinks before treating them
+						as errored, so that they are retried by the source rather than held indefinitely.
+						May be overridden in individual sinks. By default, sources wait indefinitely.
+						"""
+					required: false
+					type: uint: {
+						default: null
+						examples: [300]
+						unit: "seconds"
+					}
+				}
+			}
+		}
+
+		admission: {
+			common: false
+			description: """
+				Limits on the rate at which events are admitted into the topology, applied to the
+				events of all sources together. Sources may additionally set their own limits.
+				"""
+			required: false
+			type: object: options: {
+				events_per_sec: {
+					common:      true
+					description: "The maximum number of events admitted per second across all sources."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [10_000]
+						unit: "events"
+					}
+				}
+				bytes_per_sec: {
+					common:      false
+					description: "The maximum number of bytes of events admitted per second across all sources, as measured by their in-memory size."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [10_485_760]
+						unit: "bytes"
+					}
+				}
+				tenant_key: {
+					common:      false
+					description: """
+						A log field whose value identifies the tenant of an event. When set, the limits apply
+						to the events of each tenant separately, so that a single noisy tenant cannot use up
+						the limits of the others. Events without the field share the limits of one tenant.
+						"""
+					required:    false
+					type: string: {
+						default: null
+						examples: ["tenant", "kubernetes.pod_namespace"]
+						syntax: "field_path"
+					}
+				}
+				when_exceeded: {
+					common:      false
+					description: "What to do with events that exceed the limits."
+					required:    false
+					type: string: {
+						default: "block"
+						enum: {
+							block: "Wait until the events can be admitted, applying backpressure to the source."
+							drop:  "Drop the events."
+						}
+					}
+				}
+			}
+		}
+
+		checkpoints: {
+			common: false
+			description: """
+				Keeps the checkpoints of sources, such as the offsets of the `file` source and the
+				cursor of the `journald` source, in a store besides the data directory, so that an
+				instance rescheduled to another node without its volume, as stateless containers are,
+				resumes where it left off rather than reading everything again or skipping ahead. Each
+				source restores its checkpoints from the store when they are missing from its data
+				directory, and saves them to the store as they change.
+				"""
+			required: false
+			type: object: options: {
+				store: {
+					common:      true
+					description: "The store checkpoints are kept in."
+					required:    true
+					type: object: options: {
+						type: {
+							common:      true
+							description: "The kind of store."
+							required:    true
+							type: string: {
+								enum: {
+									disk:  "A directory, such as one on a network volume that every node mounts."
+									redis: "Redis keys, one per checkpoint."
+									s3:    "S3 objects, one per checkpoint, using the default AWS credentials."
+								}
+							}
+						}
+						path: {
+							common:        true
+							description:   "The directory checkpoints are kept in."
+							relevant_when: "type = \"disk\""
+							required:      false
+							type: string: {
+								default: null
+								examples: ["/mnt/shared/vector/checkpoints"]
+							}
+						}
+						url: {
+							common:        true
+							description:   "The URL of the Redis server."
+							relevant_when: "type = \"redis\""
+							required:      false
+							type: string: {
+								default: null
+								examples: ["redis://127.0.0.1:6379/0"]
+							}
+						}
+						bucket: {
+							common:        true
+							description:   "The bucket checkpoints are kept in."
+							relevant_when: "type = \"s3\""
+							required:      false
+							type: string: {
+								default: null
+								examples: ["vector-checkpoints"]
+							}
+						}
+						key_prefix: {
+							common:        false
+							description:   "The prefix of the keys of the checkpoints, which are followed by the ID of the source and the name of the checkpoint."
+							relevant_when: "type = \"redis\" or type = \"s3\""
+							required:      false
+							type: string: default: "vector/checkpoints"
+						}
+						region: {
+							common:        false
+							description:   "The AWS region of the bucket."
+							relevant_when: "type = \"s3\""
+							required:      false
+							type: string: {
+								default: null
+								examples: ["us-east-1"]
+							}
+						}
+						endpoint: {
+							common:        false
+							description:   "A custom endpoint, such as that of an S3-compatible store."
+							relevant_when: "type = \"s3\""
+							required:      false
+							type: string: {
+								default: null
+								examples: ["http://127.0.0.0:9000"]
+							}
+						}
+					}
+				}
+				sync_interval_secs: {
+					common:      false
+					description: "How often the checkpoints of each source are saved to the store when they changed. They are also saved when the source stops."
+					required:    false
+					type: uint: {
+						default: 5
+						unit:    "seconds"
+					}
+				}
+			}
+		}
+
+		cluster: {
+			common: false
+			description: """
+				Makes this instance a member of a cluster of Vector instances, typically aggregators,
+				sharing the work of pull-based sources rather than each doing all of it. The members
+				agree on which of them owns each piece of work, such as an endpoint scraped by the
+				`prometheus_scrape` source, by rendezvous hashing over the live members, so that when
+				a member joins or leaves, only the work it owned moves. Sources whose upstream already
+				spreads the work between consumers, such as `kafka` and the SQS queues of `aws_s3` and
+				`aws_sqs`, share it natively and are unaffected.
+				"""
+			required: false
+			type: object: options: {
+				node_id: {
+					common:      false
+					description: "The identifier of this instance in the cluster. Defaults to the hostname."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["aggregator-0"]
+					}
+				}
+				backend: {
+					common:      true
+					description: "How the live members of the cluster are found."
+					required:    true
+					type: object: options: {
+						type: {
+							common:      true
+							description: "The kind of backend."
+							required:    true
+							type: string: {
+								enum: {
+									static: "A fixed list of members, all of which are assumed to be live."
+									consul: "Members registered in Consul's key/value store under keys held by sessions, which expire when a member stops renewing them."
+									kubernetes_lease: """
+										A single member elected through a Kubernetes `Lease`, so that of the replicas of a
+										deployment, only the one holding the lease runs the work of pull-based sources, and
+										another takes it over once the holder stops renewing it or releases it on shutdown.
+										The service account of the pods needs to be allowed to `get`, `create` and `update`
+										`leases` in the `coordination.k8s.io` API group.
+										"""
+								}
+							}
+						}
+						members: {
+							common:        true
+							description:   "The identifiers of the members, including this instance."
+							relevant_when: "type = \"static\""
+							required:      false
+							type: array: {
+								default: null
+								items: type: string: examples: ["aggregator-0", "aggregator-1"]
+							}
+						}
+						address: {
+							common:        false
+							description:   "The address of the Consul agent."
+							relevant_when: "type = \"consul\""
+							required:      false
+							type: string: default: "http://127.0.0.1:8500"
+						}
+						key_prefix: {
+							common:        false
+							description:   "The key prefix the members are registered under."
+							relevant_when: "type = \"consul\""
+							required:      false
+							type: string: default: "vector/cluster"
+						}
+						session_ttl_secs: {
+							common:        false
+							description:   "The time after which a member that stops renewing its session leaves the cluster. Consul requires at least 10 seconds."
+							relevant_when: "type = \"consul\""
+							required:      false
+							type: uint: {
+								default: 15
+								unit:    "seconds"
+							}
+						}
+						lease_name: {
+							common:        false
+							description:   "The name of the lease."
+							relevant_when: "type = \"kubernetes_lease\""
+							required:      false
+							type: string: default: "vector-leader"
+						}
+						namespace: {
+							common:        false
+							description:   "The namespace of the lease. Defaults to the namespace of the pod."
+							relevant_when: "type = \"kubernetes_lease\""
+							required:      false
+							type: string: {
+								default: null
+								examples: ["observability"]
+							}
+						}
+						lease_duration_secs: {
+							common:        false
+							description:   "The time after which the lease can be taken over when its holder stops renewing it. Must be at least 5 seconds."
+							relevant_when: "type = \"kubernetes_lease\""
+							required:      false
+							type: uint: {
+								default: 15
+								unit:    "seconds"
+							}
+						}
+					}
+				}
+			}
+		}
+
+		conditions: {
+			common: false
+			description: """
+				Conditions defined once, by name, to be shared by many components. These are compiled once, and
+				components refer to them with conditions of the `shared` type, such as
+				`condition.type = "shared"` and `condition.source = "server_errors"`.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						server_errors: #".status >= 500 || .level == "error""#
+					},
+				]
+				options: {
+					"*": {
+						description: "A condition, in any of the syntaxes conditions support."
+						required:    true
+						type: condition: {}
+					}
+				}
+			}
+		}
+
+		vrl_library: {
+			common: false
+			description: """
+				[Vector Remap Language](\(urls.vrl_reference)) (VRL) programs defined once, by name, to be shared by
+				many components. Each program is compiled once, without knowledge of the schema of the components
+				using it, and all these components are rebuilt together whenever it changes.
+
+				The `remap` transform runs a program with its `library` option, and conditions of the `shared` type,
+				such as in the `filter` and `route` transforms and in unit tests, refer to programs by name the same
+				as to the top-level `conditions`.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						parse_message: #". = parse_json!(.message)"#
+						is_error:      #".level == "error""#
+					},
+				]
+				options: {
+					"*": {
+						description: "The source of a VRL program."
+						required:    true
+						type: string: {
+							syntax: "remap_program"
+						}
+					}
+				}
 			}
 		}
 
@@ -213,6 +550,59 @@ configuration: {
 			}
 		}
 
+		tags: {
+			common: false
+			description: """
+				Tags attached to every event entering the topology, as fields of logs and traces
+				and as tags of metrics. The values are templates, such as `{{ host }}`, so that
+				they can be taken from the events, and environment variables can be used to set
+				them per node. Tags the events already carry are left as they are. Sources may
+				override these with their own `tags`.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						env:    "production"
+						region: "${AWS_REGION}"
+						node:   "${HOSTNAME}"
+					},
+				]
+				options: {}
+			}
+		}
+
+		schema: {
+			common: false
+			description: """
+				Configures the schema of the events flowing through the topology.
+				"""
+			required: false
+			type: object: {
+				examples: []
+				options: {
+					log_namespace: {
+						common: false
+						description: """
+							Where sources place the host, timestamp, source type and metadata fields
+							of the logs they produce. Sources may set their own `log_namespace` to
+							override this policy, so that a fleet can move its sources to the Vector
+							namespace one at a time. A warning is logged for each source still pinned
+							to the legacy layout while this policy is `vector`.
+							"""
+						required: false
+						type: string: {
+							default: "legacy"
+							enum: {
+								legacy: "At the root of the event, under the keys of the global `log_schema`."
+								vector: "Nested in the `vector` field, as `vector.host`, `vector.timestamp`, `vector.source_type` and `vector.metadata`."
+							}
+						}
+					}
+				}
+			}
+		}
+
 		healthchecks: {
 			common: false
 			description: """
@@ -288,6 +678,454 @@ configuration: {
 			}
 		}
 
+		http_client: {
+			common: false
+			description: """
+				Tuning of the HTTP client used by sinks, and by the other components that send HTTP
+				requests. Applies to the components built, or rebuilt on reload, after it is set.
+				"""
+			required: false
+			type: object: options: {
+				version: {
+					common:      true
+					description: "The HTTP version requests are sent with."
+					required:    false
+					type: string: {
+						default: "http1"
+						enum: {
+							http1: "HTTP/1.1."
+							auto:  "HTTP/2 when the server agrees to it while negotiating TLS, and HTTP/1.1 otherwise, such as without TLS or through a proxy."
+							http2: "HTTP/2, even without TLS, for servers known to support it."
+						}
+					}
+				}
+				pool_max_idle_per_host: {
+					common:      false
+					description: "The largest number of idle connections kept open to each host. By default, there is no limit."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [16]
+						unit: null
+					}
+				}
+				pool_idle_timeout_secs: {
+					common:      false
+					description: "How long idle connections are kept open."
+					required:    false
+					type: uint: {
+						default: 90
+						unit:    "seconds"
+					}
+				}
+				tcp_keepalive_secs: {
+					common:      false
+					description: "The interval of TCP keepalive probes on idle connections. By default, TCP keepalive is disabled."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [60]
+						unit: "seconds"
+					}
+				}
+				http2_keepalive_secs: {
+					common:      false
+					description: "The interval of HTTP/2 pings on connections, which keep connections through load balancers open and detect dead ones. By default, no pings are sent."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [30]
+						unit: "seconds"
+					}
+				}
+				happy_eyeballs_timeout_ms: {
+					common: false
+					description: """
+						How long to wait for a connection to the first address of a host before also
+						trying the next one, such as an IPv4 address after an IPv6 one. Set to `0` to
+						try the addresses one after the other.
+						"""
+					required: false
+					type: uint: {
+						default: 300
+						unit:    "milliseconds"
+					}
+				}
+				dns_refresh_secs: {
+					common: false
+					description: """
+						How often connections are replaced by new ones, resolving the hosts again, so
+						that requests follow DNS changes, such as after a failover, rather than going
+						to the addresses of long-lived connections. By default, connections are kept
+						for as long as they stay open.
+						"""
+					required: false
+					type: uint: {
+						default: null
+						examples: [300]
+						unit: "seconds"
+					}
+				}
+			}
+		}
+
+		internal_metrics: {
+			common:      false
+			description: "Limits on the internal metrics of Vector."
+			required:    false
+			type: object: options: {
+				max_label_values: {
+					common: false
+					description: """
+						The maximum number of values of each label whose values come from events,
+						templates or the environment, such as `tenant`, `file` or `endpoint`, across
+						every internal metric. Values seen since Vector started keep their own series,
+						and new values past the maximum are recorded as `__overflow__`, so that
+						unbounded label values can't exhaust the memory of Vector.
+						"""
+					required: false
+					type: uint: {
+						default: 1000
+						examples: [10_000]
+					}
+				}
+			}
+		}
+
+		json_parsing: {
+			common:      false
+			description: "How JSON documents are parsed by the `json` codec and the `parse_json` function."
+			required:    false
+			type: object: options: {
+				simd: {
+					common: false
+					description: """
+						Whether JSON documents are parsed with [simd-json](\(urls.simd_json)),
+						which is faster on large documents. Documents it rejects are parsed
+						again with the default parser, so they are parsed, or rejected with
+						the same errors, either way. Only has an effect when Vector is built
+						with the `json-simd` feature.
+						"""
+					required: false
+					type: bool: default: false
+				}
+			}
+		}
+
+		memory_limit: {
+			common: false
+			description: """
+				A budget for the memory taken up by the events in flight, to keep Vector from being
+				killed for running out of memory when the size of events is unpredictable. The events
+				of every source count against the budget, by their in-memory size, from the time they
+				are admitted until they are delivered or dropped. Events fanned out to several sinks
+				count once.
+				"""
+			required: false
+			type: object: options: {
+				soft_limit_bytes: {
+					common:      true
+					description: "The number of bytes of events in flight above which sources wait for events to be delivered, applying backpressure."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [536_870_912]
+						unit: "bytes"
+					}
+				}
+				hard_limit_bytes: {
+					common:      true
+					description: """
+						The number of bytes of events in flight above which incoming events are shed. Events
+						created by transforms, such as `reduce`, can take the memory in use past the soft
+						limit. Must not be below `soft_limit_bytes`.
+						"""
+					required: false
+					type: uint: {
+						default: null
+						examples: [1_073_741_824]
+						unit: "bytes"
+					}
+				}
+				shed: {
+					common:      false
+					description: "Which incoming events to shed above the hard limit."
+					required:    false
+					type: string: {
+						default: "all"
+						enum: {
+							all:      "Shed every incoming event."
+							priority: "Shed incoming events of `low` and `normal` priority, and make events of `high` priority wait as they do above the soft limit."
+						}
+					}
+				}
+			}
+		}
+
+		thread_pools: {
+			common: false
+			description: """
+				Dedicated runtimes, each with its own worker threads, for the sources,
+				transforms, or sinks, so that heavy CPU work in transforms can't starve
+				the sockets of sources on large machines. Components of a class without
+				a pool run on the main runtime. The pools are started with the first
+				topology, and changes to them require a restart.
+				"""
+			required: false
+			type: object: options: {
+				sources: {
+					common:      false
+					description: "The pool running the sources."
+					required:    false
+					type: object: options: {
+						threads: {
+							common:      false
+							description: "The number of worker threads of the pool. Defaults to the number of CPUs the threads are pinned to, or to the number of CPUs of the machine."
+							required:    false
+							type: uint: {
+								default: null
+								examples: [4]
+								unit: null
+							}
+						}
+						cpus: {
+							common:      false
+							description: "The CPUs to pin the worker threads of the pool to. Pinning is only supported on Linux."
+							required:    false
+							type: array: {
+								default: []
+								items: type: uint: {
+									examples: [0, 1, 2, 3]
+									unit: null
+								}
+							}
+						}
+						numa_node: {
+							common:      false
+							description: "The NUMA node whose CPUs to pin the worker threads of the pool to. Only the CPUs of the node listed in `cpus` are used if both are set."
+							required:    false
+							type: uint: {
+								default: null
+								examples: [0]
+								unit: null
+							}
+						}
+					}
+				}
+				transforms: {
+					common:      false
+					description: "The pool running the transforms."
+					required:    false
+					type: object: options: {
+						threads: {
+							common:      false
+							description: "The number of worker threads of the pool. Defaults to the number of CPUs the threads are pinned to, or to the number of CPUs of the machine."
+							required:    false
+							type: uint: {
+								default: null
+								examples: [4]
+								unit: null
+							}
+						}
+						cpus: {
+							common:      false
+							description: "The CPUs to pin the worker threads of the pool to. Pinning is only supported on Linux."
+							required:    false
+							type: array: {
+								default: []
+								items: type: uint: {
+									examples: [0, 1, 2, 3]
+									unit: null
+								}
+							}
+						}
+						numa_node: {
+							common:      false
+							description: "The NUMA node whose CPUs to pin the worker threads of the pool to. Only the CPUs of the node listed in `cpus` are used if both are set."
+							required:    false
+							type: uint: {
+								default: null
+								examples: [0]
+								unit: null
+							}
+						}
+					}
+				}
+				sinks: {
+					common:      false
+					description: "The pool running the sinks."
+					required:    false
+					type: object: options: {
+						threads: {
+							common:      false
+							description: "The number of worker threads of the pool. Defaults to the number of CPUs the threads are pinned to, or to the number of CPUs of the machine."
+							required:    false
+							type: uint: {
+								default: null
+								examples: [4]
+								unit: null
+							}
+						}
+						cpus: {
+							common:      false
+							description: "The CPUs to pin the worker threads of the pool to. Pinning is only supported on Linux."
+							required:    false
+							type: array: {
+								default: []
+								items: type: uint: {
+									examples: [0, 1, 2, 3]
+									unit: null
+								}
+							}
+						}
+						numa_node: {
+							common:      false
+							description: "The NUMA node whose CPUs to pin the worker threads of the pool to. Only the CPUs of the node listed in `cpus` are used if both are set."
+							required:    false
+							type: uint: {
+								default: null
+								examples: [0]
+								unit: null
+							}
+						}
+					}
+				}
+			}
+		}
+
+		telemetry: {
+			common: false
+			description: """
+				Tracing of the batches of events going through the topology, exported over
+				[OTLP](https://opentelemetry.io/docs/reference/specification/protocol/otlp/) so that
+				their latency can be broken down per component in a tracing backend. A fraction of the
+				batches received by sources are sampled into traces, with a span for each component they
+				go through. A span starts when the previous component hands the batch over, so it
+				includes the time the batch waits in the input buffer of the component, and ends when
+				the component hands the batch over in turn or, for sinks, when the batch is delivered.
+				Only available in builds of Vector with the `telemetry` feature.
+				"""
+			required: false
+			type: object: options: {
+				endpoint: {
+					common:      true
+					description: "The OTLP/HTTP endpoint to export the spans to. Batches aren't traced unless it's set."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["http://localhost:4318/v1/traces"]
+					}
+				}
+				sample_rate: {
+					common:      true
+					description: "The fraction of the batches received by sources to trace, between 0 and 1."
+					required:    false
+					type: float: {
+						default: 0.01
+						examples: [0.001, 1.0]
+					}
+				}
+				service_name: {
+					common:      false
+					description: "The name of the service the spans are reported for."
+					required:    false
+					type: string: {
+						default: "vector"
+						examples: ["vector-aggregator"]
+					}
+				}
+			}
+		}
+
+		tenancy: {
+			common: false
+			description: """
+				Isolation of the tenants sharing the topology. Each event is given the tenant named by
+				its `tenant_key` field or, failing that, the tenant whose `sources` include the source
+				of the event. The tenant is kept with the event through the rest of the topology, and
+				the limits of the tenant apply to its events before any other admission limits.
+				"""
+			required: false
+			type: object: options: {
+				tenant_key: {
+					common:      true
+					description: "A log field whose value identifies the tenant of an event, taking precedence over the tenant of its source."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["tenant", "kubernetes.pod_namespace"]
+						syntax: "field_path"
+					}
+				}
+				when_exceeded: {
+					common:      false
+					description: "What to do with events that exceed the limits of their tenant."
+					required:    false
+					type: string: {
+						default: "block"
+						enum: {
+							block: "Wait until the events can be admitted, applying backpressure to the source."
+							drop:  "Drop the events."
+						}
+					}
+				}
+				tenants: {
+					common:      true
+					description: "The tenants, by name."
+					required:    false
+					type: object: options: "*": {
+						description: "The sources and limits of a tenant."
+						required:    true
+						type: object: options: {
+							sources: {
+								description: "The IDs of the sources whose events belong to the tenant, unless their `tenant_key` field says otherwise."
+								required:    false
+								common:      true
+								type: array: {
+									default: []
+									items: type: string: examples: ["payments_http"]
+								}
+							}
+							events_per_sec: {
+								description: "The maximum number of events of the tenant admitted per second."
+								required:    false
+								common:      true
+								type: uint: {
+									default: null
+									examples: [1_000]
+									unit: "events"
+								}
+							}
+							bytes_per_sec: {
+								description: "The maximum number of bytes of events of the tenant admitted per second, as measured by their in-memory size."
+								required:    false
+								common:      false
+								type: uint: {
+									default: null
+									examples: [1_048_576]
+									unit: "bytes"
+								}
+							}
+							max_buffered_events: {
+								description: """
+									The maximum number of events of the tenant in flight in the topology at once,
+									whether they are in buffers or being processed. Events count against the limit
+									until they're delivered or dropped.
+									"""
+								required: false
+								common:   false
+								type: uint: {
+									default: null
+									examples: [5_000]
+									unit: "events"
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		timezone: {
 			common:      false
 			description: """
@@ -304,7 +1142,7 @@ configuration: {
 
 		proxy: {
 			common:      false
-			description: "Configures an HTTP(S) proxy for Vector to use."
+			description: "Configures an HTTP(S) or SOCKS5 proxy for Vector to use."
 			required:    false
 			type: object: options: {
 				enabled: {
@@ -331,6 +1169,19 @@ configuration: {
 						examples: ["http://foo.bar:3128"]
 					}
 				}
+				socks5: {
+					common:      false
+					description: """
+						The URL of a [SOCKS5](\(urls.socks5)) proxy to send requests through, for every scheme,
+						instead of the HTTP(S) proxies. Host names are resolved by Vector with the `socks5` scheme,
+						and by the proxy with the `socks5h` one.
+						"""
+					required:    false
+					type: string: {
+						default: null
+						examples: ["socks5://foo.bar:1080", "socks5h://foo.bar:1080"]
+					}
+				}
 				no_proxy: {
 					common:      false
 					description: """
@@ -341,7 +1192,7 @@ configuration: {
 							Domain names | `example.com` matches requests to `example.com`
 							Wildcard domains | `.example.com` matches requests to `example.com` and its subdomains
 							IP addresses | `127.0.0.1` matches requests to 127.0.0.1
-							[CIDR](\(urls.cidr)) blocks | `192.168.0.0./16` matches requests to any IP addresses in this range
+							[CIDR](\(urls.cidr)) blocks | `192.168.0.0/16` matches requests to any IP addresses in this range
 							Splat | `*` matches all hosts
 							"""
 					required:    false
@@ -353,6 +1204,43 @@ configuration: {
 						}
 					}
 				}
+				auth: {
+					common:      false
+					description: """
+						The credentials to authenticate to the proxies with, taking precedence over those in
+						their URLs. Unlike those, they don't need to be URL-encoded, so they can be read from a
+						secret with `SECRET[<backend>.<key>]`.
+						"""
+					required:    false
+					type: object: options: {
+						username: {
+							description: "The username to authenticate with."
+							required:    true
+							type: string: examples: ["${PROXY_USERNAME}", "username"]
+						}
+						password: {
+							description: "The password to authenticate with."
+							required:    true
+							type: string: examples: ["SECRET[backend.proxy_password]", "password"]
+						}
+					}
+				}
+			}
+		}
+
+		includes: {
+			common: false
+			description: """
+				Paths of other configuration files to merge into this one, relative to the directory of this
+				file. Wildcard paths are supported. Included files can include other files in turn. See
+				[Includes](#includes) for how the files are merged.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["conf.d/*.toml", "sinks/s3.yaml"]
+				}
 			}
 		}
 	}
@@ -408,6 +1296,85 @@ configuration: {
 				},
 			]
 		}
+		includes: {
+			title: "Includes"
+			body: """
+				A configuration file can include other configuration files by listing them under `includes`.
+				The included files are merged over the including file in the order they're listed, with the
+				files matching a wildcard path merged in alphabetical order, so that files later in the order
+				override the settings of the files before them:
+
+				* Tables are merged key by key.
+				* Any other value, including a list, replaces the value set before it.
+				* A key suffixed with `+=` appends its list to the list set before it instead of replacing it.
+
+				```toml title="vector.toml"
+				includes = ["conf.d/*.toml"]
+
+				[sinks.out]
+				type = "console"
+				inputs = ["in"]
+				encoding.codec = "json"
+				```
+
+				```toml title="conf.d/10-more.toml"
+				[sinks.out]
+				"inputs+=" = ["more"]
+				encoding.codec = "text"
+				```
+
+				Run `vector config render` to print the configuration as it is after merging all of the files.
+				"""
+		}
+		profiles: {
+			title: "Profiles"
+			body: """
+				A configuration file can hold sections that only apply to a given profile under `profile`,
+				so that a single file can serve multiple environments. When a profile is selected with the
+				`--profile` flag or the `VECTOR_PROFILE` environment variable, its section is merged over
+				the rest of the file, following the same rules as [includes](#includes). The sections of
+				the other profiles are ignored.
+
+				```toml title="vector.toml"
+				[sinks.out]
+				type = "console"
+				inputs = ["in"]
+				encoding.codec = "json"
+
+				[profile.production.sinks.out]
+				type = "aws_s3"
+				bucket = "logs"
+				region = "us-east-1"
+				```
+				"""
+		}
+		providers: {
+			title: "Configuration providers"
+			body: """
+				Instead of reading its configuration from files, Vector can get it from a provider configured
+				in the `provider` section, and reload it when the provider reports a change.
+
+				The `http` provider fetches the configuration from `url` every `poll_interval_secs`, and
+				reloads it when its content changed. With the `signature` option, the configuration is only
+				applied if its detached signature, fetched from `signature.url` (the configuration URL with
+				`.sig` appended by default), verifies with the PEM public key at `signature.public_key_path`.
+
+				The `kubernetes` provider reads the configuration from the ConfigMap `name` under `key`
+				(`vector.toml` by default), or from the `spec` of a `VectorPipeline` custom resource when
+				`kind` is `vector_pipeline`, and watches it for changes. Each change is validated component by
+				component: invalid components, and the components consuming their outputs, are left out while
+				the rest of the pipeline is applied. The outcome is logged, and written to the status
+				conditions of a `VectorPipeline`.
+
+				```toml title="vector.toml"
+				[provider]
+				type = "kubernetes"
+				kind = "vector_pipeline"
+				name = "logs"
+				namespace = "observability"
+				```
+				"""
+		}
 		secrets_management: {
 			title: "Secrets management"
 			body: """
@@ -416,8 +1383,10 @@ configuration: {
 				sensitive token are configured in a dedicated section (`secret`). In the rest of the configuration you should use
 				the `SECRET[<backend_name>.<secret_key>]` notation to interpolate the secret. Interpolation will happen immediately after
 				environment variables interpolation. While Vector supports multiple commands to retrieve secrets, a
-				secret backend cannot use the secret interpolation feature for its own configuration. Currently the only supported
-				kind of secret backend is the the `exec` one that runs an external command to retrieve secrets.
+				secret backend cannot use the secret interpolation feature for its own configuration. The supported kinds of
+				secret backends are `exec`, which runs an external command to retrieve secrets, `vault`, which retrieves
+				secrets from HashiCorp Vault, and `aws_secrets_manager`, `aws_ssm_parameter_store` and `gcp_secret_manager`,
+				which retrieve secrets from the secret stores of cloud providers.
 
 				The following example shows a simple configuration with two backends defined:
 
@@ -478,6 +1447,85 @@ configuration: {
 						Currently Vector will always query backend with `"version": "1.0"`.
 						"""
 				},
+				{
+					title: "The `vault` backend"
+					body:  """
+						The `vault` backend retrieves secrets from [HashiCorp Vault](https://www.vaultproject.io/) over its
+						HTTP API, authenticating with the `token` option or the `VAULT_TOKEN` environment variable. The key of
+						a secret is its path under the secrets engine mounted at `mount`, with dots in place of slashes,
+						followed by the field holding the secret:
+
+						```toml title="vector.toml"
+						[secret.kv]
+						type = "vault"
+						address = "https://vault.example.com:8200"
+						mount = "secret" # a key/value version 2 engine
+
+						[secret.db]
+						type = "vault"
+						address = "https://vault.example.com:8200"
+						engine = "dynamic"
+						mount = "database"
+
+						[sinks.splunk]
+						type = "splunk_hec"
+						default_token = "SECRET[kv.splunk.token]" # the `token` field of `splunk`
+
+						[sinks.clickhouse]
+						type = "clickhouse"
+						auth.strategy = "basic"
+						auth.user = "SECRET[db.creds.vector.username]"
+						auth.password = "SECRET[db.creds.vector.password]"
+						```
+
+						All of the fields of a path are read at once, so that credentials issued together match. Vector
+						renews its token and the leases of dynamic credentials while they're in use. Once a lease can't be
+						renewed any longer, Vector reloads its configuration to retrieve new credentials before the old
+						ones expire, without restarting.
+						"""
+				},
+				{
+					title: "Cloud provider backends"
+					body:  """
+						The `aws_secrets_manager` and `aws_ssm_parameter_store` backends retrieve secrets from AWS Secrets
+						Manager and the AWS Systems Manager Parameter Store, authenticating like the AWS components do, with
+						the `auth` options or the default credentials chain, which includes IAM roles for service accounts.
+						The `gcp_secret_manager` backend retrieves secrets from GCP Secret Manager in the given `project`,
+						authenticating like the GCP components do, with `credentials_path` or the default credentials,
+						which include workload identity.
+
+						The key of a secret is the name of the secret or parameter, with dots in place of slashes. When the
+						`json` option of a secret manager backend is set, secrets hold JSON objects, and the last segment
+						of a key is the field holding the secret:
+
+						```toml title="vector.toml"
+						[secret.aws]
+						type = "aws_secrets_manager"
+						region = "us-east-1"
+						json = true
+
+						[secret.params]
+						type = "aws_ssm_parameter_store"
+						region = "us-east-1"
+
+						[secret.gcp]
+						type = "gcp_secret_manager"
+						project = "my-project"
+
+						[sinks.dd_logs]
+						type = "datadog_logs"
+						default_api_key = "SECRET[aws.prod.datadog.api_key]" # the `api_key` field of `prod/datadog`
+
+						[sinks.splunk]
+						type = "splunk_hec"
+						default_token = "SECRET[params.prod.splunk.token]" # the `/prod/splunk/token` parameter
+						```
+
+						These backends retrieve the secrets again every `refresh_interval_secs`, 300 seconds by default, and
+						reload the configuration when any of them has changed, so that rotated secrets are picked up without
+						restarting Vector. Setting `refresh_interval_secs` to `0` disables this.
+						"""
+				},
 			]
 		}
 		formats: {
@@ -538,6 +1586,51 @@ configuration: {
 				```
 				"""
 		}
+		templates: {
+			title: "Component templates"
+			body: """
+				Pipelines that only differ by a few settings can be declared once as a template under
+				`templates`, holding `sources`, `transforms` and `sinks`, defaults for its `params`, and the
+				`instances` to create with their own values of the parameters. Each instance gets a copy of
+				the components of the template when the configuration is loaded:
+
+				* The copies are named `<template>-<instance>-<component>`.
+				* Every `%{param}` in a string is replaced by the value of the parameter. A string holding
+				  nothing but the reference takes the value as is, keeping its type.
+				* Inputs naming components of the template point to the copies of the same instance. Other
+				  inputs are left untouched, and components outside the template can consume the copies by
+				  their names, including with [wildcards](#wildcards).
+
+				```toml title="vector.toml"
+				[templates.tenant.params]
+				port = 8080
+
+				[templates.tenant.sources.in]
+				type = "http"
+				address = "0.0.0.0:%{port}"
+
+				[templates.tenant.transforms.parse]
+				type = "remap"
+				inputs = ["in"]
+				source = '.tenant = "%{name}"'
+
+				[templates.tenant.sinks.out]
+				type = "loki"
+				inputs = ["parse"]
+				endpoint = "http://loki:3100"
+				labels.tenant = "%{name}"
+
+				[templates.tenant.instances.acme]
+				name = "acme"
+
+				[templates.tenant.instances.globex]
+				name = "globex"
+				port = 9000
+				```
+
+				Here the source `tenant-globex-in` listens on port 9000 and feeds `tenant-globex-parse`.
+				"""
+		}
 		wildcards: {
 			title: "Wildcards in component names"
 			body: """
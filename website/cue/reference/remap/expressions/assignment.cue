@@ -13,7 +13,7 @@ remap: expressions: assignment: {
 
 	grammar: {
 		source: """
-			target ~ ("," ~ error)? ~ operator ~ expression
+			target ~ (("," ~ error) | (":" ~ annotation))? ~ operator ~ expression
 			"""
 		definitions: {
 			target: {
@@ -28,6 +28,22 @@ remap: expressions: assignment: {
 					fallible. This is commonly used when invoking fallible functions.
 					"""
 			}
+			annotation: {
+				description: """
+					The `annotation` declares the types the assigned value must have, as one or more type names
+					separated by `|`: `string`, `integer`, `float`, `boolean`, `object`, `array`, `timestamp`,
+					`regex`, or `null`. Annotations are only allowed on variable targets using the `=` operator:
+
+					```coffee
+					status: integer | null = .status
+					```
+
+					The variable is typed as the declared types for the rest of the program, which avoids having to
+					coerce its value before every use. If the expression can't resolve to any of the declared types,
+					the program is rejected. If it can resolve to other types as well, the value is checked when the
+					assignment runs, and the program errors if it doesn't match.
+					"""
+			}
 			operator: {
 				description: """
 					The `operator` delimits the `target` and `expression` and defines assignment conditions.
@@ -115,6 +131,15 @@ remap: expressions: assignment: {
 				"""#
 			return: "Hello, World!"
 		},
+		{
+			title: "Annotated variable assignment"
+			input: log: status: 404
+			source: #"""
+				status: integer = .status
+				status + 1
+				"""#
+			return: 405
+		},
 		{
 			title: "Object merge assignment"
 			source: #"""
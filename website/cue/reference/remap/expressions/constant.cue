@@ -0,0 +1,61 @@
+package metadata
+
+remap: expressions: constant: {
+	title: "Constant"
+	description: """
+		A _constant_ expression binds a name to a value that is resolved once, when the program is compiled,
+		instead of every time the program runs. Any expression that follows the definition can refer to the
+		constant by its name.
+
+		The value of a constant has to be known at compile-time. It can be a literal, an array or object of such
+		values, another constant, or a call to a pure function, such as `sha2` or `parse_regex`, with arguments
+		that are known at compile-time. Calls to pure functions with such arguments are resolved at compile-time
+		anywhere in the program, not just in constant definitions.
+
+		Constants must be defined at the root of the program, and can't be reassigned or share a name with a
+		previously defined constant or variable.
+		"""
+	return: """
+		Defining a constant doesn't return a value. Referring to the constant returns its value.
+		"""
+
+	grammar: {
+		source: """
+			"const" ~ name ~ "=" ~ expression
+			"""
+		definitions: {
+			name: {
+				description: """
+					`name` is the name of the constant, which follows the same rules as a
+					[variable](#variable) name.
+					"""
+			}
+			expression: {
+				description: """
+					`expression` is the value of the constant, which has to be resolvable at compile-time.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Literal constant"
+			source: #"""
+				const LIMIT = 10
+
+				LIMIT * 2
+				"""#
+			return: 20
+		},
+		{
+			title: "Function call constant"
+			source: #"""
+				const DIGEST = sha2("static", variant: "SHA-256")
+
+				DIGEST
+				"""#
+			return: "2053dbbf6ec7135c4e994d3464c478db6f48d3ca21052c8f44915edc96e02c39"
+		},
+	]
+}
@@ -0,0 +1,82 @@
+package metadata
+
+remap: expressions: for_each: {
+	title: "For each"
+	description: """
+		A _for each_ loop evaluates a block once for every element of an array, or for every key/value pair of an
+		object. The loop always terminates, as it iterates over a copy of the value taken before the first iteration.
+
+		Unlike the [`for_each`](\(urls.vrl_functions)#for_each) function, the block of a loop can assign to any
+		variable in the surrounding scope, which makes it possible to accumulate results into multiple variables.
+		Variables assigned within the block are typed as the union of their type before the loop and after any
+		number of iterations.
+		"""
+	return: """
+		A for each loop always resolves to `null`.
+		"""
+
+	grammar: {
+		source: """
+			"for_each" ~ key ~ "," ~ value ~ "in" ~ subject ~ block
+			"""
+		definitions: {
+			key: {
+				description: """
+					The `key` is the variable the index of the element (for an array), or the key of the pair (for an
+					object), is assigned to. Use `_` to discard it.
+					"""
+			}
+			value: {
+				description: """
+					The `value` is the variable the element, or the value of the pair, is assigned to. Use `_` to
+					discard it.
+					"""
+			}
+			subject: {
+				description: """
+					The `subject` is the array or object to iterate over. It must be guaranteed to resolve to an
+					array or object at compile time, otherwise the program is rejected.
+					"""
+			}
+			block: {
+				description: """
+					The `block` is evaluated once per element. The `key` and `value` variables are only available
+					within the block, and any existing variables of the same name are restored once the loop ends.
+
+					Within the block, `break` stops the loop, and `continue` skips to the next element. Both are
+					only allowed within a loop, and don't apply to closures or function definitions within the
+					block.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Accumulate into multiple variables"
+			source: #"""
+				sum = 0
+				count = 0
+				for_each _, value in [1, 2, 3] {
+					sum = sum + value
+					count = count + 1
+				}
+				[sum, count]
+				"""#
+			return: [6, 3]
+		},
+		{
+			title: "Skip and stop iterating"
+			source: #"""
+				keys = []
+				for_each key, value in { "a": 1, "b": null, "c": 3, "d": 4 } {
+					if value == null { continue }
+					if value == 4 { break }
+					keys = push(keys, key)
+				}
+				keys
+				"""#
+			return: ["a", "c"]
+		},
+	]
+}
@@ -102,6 +102,10 @@ remap: expressions: function_call: {
 					```coffee
 					for_each([]) -> |index, value| { ... }
 					```
+
+					Variables the closure doesn't need can be ignored with `_` in their place, as in
+					`for_each([]) -> |_, value| { ... }`. Functions can also make trailing variables optional,
+					in which case the closure can leave them out, as in `for_each([]) -> |index| { ... }`.
 					"""
 			}
 		}
@@ -0,0 +1,71 @@
+package metadata
+
+remap: expressions: function_definition: {
+	title: "Function definition"
+	description: """
+		A _function definition_ expression defines a new function that can be called by any expression that
+		follows it in the program.
+
+		The body of the function only has access to its parameters, and any variables assigned within the body
+		are not visible to the caller. Parameter types are not declared. Instead, each call to the function is
+		type checked using the types of the arguments passed in at that call site, and the return type of the
+		call is inferred from the body.
+
+		Functions must be defined at the root of the program, can't share a name with a built-in function or a
+		previously defined function, and can't call themselves.
+		"""
+	return: """
+		Defining a function doesn't return a value. Calling the function returns the result of the last
+		evaluated expression within its body.
+		"""
+
+	grammar: {
+		source: """
+			"fn" ~ function ~ "(" ~ NEWLINE* ~ parameters? ~ NEWLINE* ~ ")" ~ block
+			"""
+		definitions: {
+			function: {
+				description: """
+					`function` is the name of the function being defined.
+					"""
+			}
+			parameters: {
+				description: """
+					`parameters` is a comma-separated list of parameter names. Arguments can be passed to the
+					function either by position, or by using the parameter name as a keyword, but each parameter can
+					only be passed a single argument.
+					"""
+			}
+			block: {
+				description: """
+					`block` is the body of the function, a [block](#block) expression.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Positional arguments"
+			source: #"""
+				fn add(a, b) {
+					a + b
+				}
+
+				add(1, 2)
+				"""#
+			return: 3
+		},
+		{
+			title: "Keyword arguments"
+			source: #"""
+				fn greet(greeting, name) {
+					greeting + ", " + name
+				}
+
+				greet(name: "world", greeting: "hello")
+				"""#
+			return: "hello, world"
+		},
+	]
+}
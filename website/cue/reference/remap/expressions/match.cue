@@ -0,0 +1,93 @@
+package metadata
+
+remap: expressions: match: {
+	title: "Match"
+	description: """
+		A _match_ expression compares a value against a list of patterns, and evaluates the expression of the
+		first arm with a matching pattern. If no pattern matches, the match expression resolves to `null`.
+
+		Patterns are checked at compile time. A pattern that can never match the type of the value is rejected, and
+		variables assigned within an arm are typed as the union of all arms, including the original type of the
+		variable unless one of the arms is guaranteed to match.
+		"""
+	return: """
+		The result of the expression of the matching arm, or null if no pattern matches.
+		"""
+
+	grammar: {
+		source: """
+			"match" ~ value ~ "{" ~ NEWLINE* ~ (arm ~ ("," | NEWLINE) ~ NEWLINE*)* ~ arm? ~ "}"
+			"""
+		definitions: {
+			value: {
+				description: """
+					The `value` is the expression that is evaluated once and compared against the patterns.
+					"""
+			}
+			arm: {
+				description: """
+					An `arm` is one or more patterns separated by `|`, followed by `=>` and the expression to evaluate
+					when any of the patterns match. Expressions that aren't a single value, such as assignments, must
+					be wrapped in a [block](#block).
+
+					A pattern can be:
+
+					* `_`, which matches any value.
+					* A string, integer, float, Boolean, timestamp, or `null` literal, which matches an equal value.
+					  Strings must be static, without templates.
+					* A regex literal, which matches a string that contains a match for the regex.
+					* An inclusive numeric range, such as `200..299`, which matches integers and floats within the
+					  range.
+					* One of the type names `string`, `integer`, `float`, `boolean`, `object`, `array`, `timestamp`
+					  or `regex`, which matches any value of that type.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Match literals and ranges"
+			source: #"""
+				match 404 {
+					200..299 => "success",
+					404 | 410 => "not found",
+					_ => "other"
+				}
+				"""#
+			return: "not found"
+		},
+		{
+			title: "Match a regex"
+			source: #"""
+				match "ERROR: connection refused" {
+					r'^ERROR' => "error",
+					r'^WARN' => "warning",
+					_ => "info"
+				}
+				"""#
+			return: "error"
+		},
+		{
+			title: "Match types"
+			source: #"""
+				value = parse_json!("1.5")
+
+				match value {
+					integer | float => "number"
+					string => "string"
+				}
+				"""#
+			return: "number"
+		},
+		{
+			title: "No matching pattern"
+			source: #"""
+				match "foo" {
+					"bar" => true
+				}
+				"""#
+			return: null
+		},
+	]
+}
@@ -0,0 +1,51 @@
+package metadata
+
+remap: expressions: return: {
+	title: "Return"
+	description: """
+		A `return` expression causes the VRL program to stop early, keeping any modifications made to the
+		event so far. Unlike `abort`, the event is emitted as-is, and the program resolves to the value of the
+		given expression, or `null` if there is none.
+
+		As with the value of the last expression of a program, the `remap` transform doesn't use the returned
+		value, so it rejects programs that pass a value other than `null` to `return` outside of a function
+		definition. To emit a different event, assign to it before returning, for example
+		`. = {"message": "skipped"}`.
+
+		Within the body of a [function definition](#function-definition), `return` only stops the function,
+		which resolves to the returned value instead.
+		"""
+	return: """
+		Does not return a value itself. The program, or function, it's part of resolves to the returned value,
+		and its type includes the types of all values that can be returned.
+		"""
+
+	grammar: {
+		source: "return ~ expression?"
+		definitions: {
+			expression: {
+				description: """
+					`expression` is an optional expression, whose value the program or function resolves to.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Skipping further processing"
+			input: log: message: "hello world"
+			source: #"""
+				.processed = true
+				if contains(string!(.message), "hello") {
+					return
+				}
+				.message = "not hello world"
+				"""#
+			output: log: {
+				message:   "hello world"
+				processed: true
+			}
+		},
+	]
+}
@@ -0,0 +1,37 @@
+package metadata
+
+remap: functions: bit_shl: {
+	category: "Number"
+	description: """
+		Shifts the bits of the `value` integer left by `amount` bits. Bits shifted past the most significant bit are dropped.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The integer to shift."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "amount"
+			description: "The number of bits to shift by, between 0 and 63."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`amount` is negative or greater than 63.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Shift left"
+			source: #"""
+				bit_shl!(1, 4)
+				"""#
+			return: 16
+		},
+	]
+}
@@ -0,0 +1,37 @@
+package metadata
+
+remap: functions: bit_shr: {
+	category: "Number"
+	description: """
+		Shifts the bits of the `value` integer right by `amount` bits. This is a logical shift, so the sign bit isn't extended and negative values become positive.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The integer to shift."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "amount"
+			description: "The number of bits to shift by, between 0 and 63."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`amount` is negative or greater than 63.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Shift right"
+			source: #"""
+				bit_shr!(256, 4)
+				"""#
+			return: 16
+		},
+	]
+}
@@ -0,0 +1,35 @@
+package metadata
+
+remap: functions: bit_xor: {
+	category: "Number"
+	description: """
+		Returns the bitwise XOR of the `value` and `other` integers.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The first integer."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "other"
+			description: "The second integer."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Bitwise XOR"
+			source: #"""
+				bit_xor(12, 10)
+				"""#
+			return: 6
+		},
+	]
+}
@@ -0,0 +1,53 @@
+package metadata
+
+remap: functions: crc16: {
+	category:    "Cryptography"
+	description: """
+		Calculates a 16-bit cyclic redundancy check (CRC) of the `value`.
+
+		This is not a cryptographic hash, but is commonly used to verify the integrity of payloads in
+		industrial and embedded protocols.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the checksum for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The CRC-16 variant to calculate."
+			enum: {
+				"arc":         "CRC-16/ARC, also known as CRC-16/IBM"
+				"ccitt_false": "CRC-16/CCITT-FALSE, also known as CRC-16/IBM-3740"
+				"kermit":      "CRC-16/KERMIT"
+				"modbus":      "CRC-16/MODBUS"
+				"xmodem":      "CRC-16/XMODEM"
+			}
+			required: false
+			default:  "arc"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Calculate CRC-16/ARC checksum"
+			source: #"""
+				crc16("123456789")
+				"""#
+			return: 47933
+		},
+		{
+			title: "Calculate CRC-16/MODBUS checksum"
+			source: #"""
+				crc16("123456789", algorithm: "modbus")
+				"""#
+			return: 19255
+		},
+	]
+}
@@ -0,0 +1,50 @@
+package metadata
+
+remap: functions: crc32: {
+	category:    "Cryptography"
+	description: """
+		Calculates a 32-bit cyclic redundancy check (CRC) of the `value`.
+
+		This is not a cryptographic hash, but is commonly used to verify the integrity of payloads, for example
+		the checksums embedded in gzip, PNG, and iSCSI data.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the checksum for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The CRC-32 variant to calculate."
+			enum: {
+				"ieee":       "CRC-32/ISO-HDLC, as used by gzip, zlib, and Ethernet"
+				"castagnoli": "CRC-32C, as used by iSCSI and SCTP"
+			}
+			required: false
+			default:  "ieee"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Calculate CRC-32 checksum"
+			source: #"""
+				crc32("123456789")
+				"""#
+			return: 3421780262
+		},
+		{
+			title: "Calculate CRC-32C checksum"
+			source: #"""
+				crc32("123456789", algorithm: "castagnoli")
+				"""#
+			return: 3808858755
+		},
+	]
+}
@@ -0,0 +1,54 @@
+package metadata
+
+remap: functions: detect_language: {
+	category:    "String"
+	description: """
+		Detects the natural language the `value` is written in, using [whatlang](\(urls.whatlang)).
+
+		Detection is based on the script and common character sequences of the text, and works best for
+		sentences rather than single words. Check the `reliable` field before acting on the result of short or
+		mixed-language texts.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The text to detect the language of."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["object", "null"]
+		rules: [
+			#"The `language` field is the [ISO 639-3](\(urls.iso_639_3)) code of the detected language, such as `eng`."#,
+			#"The `script` field is the name of the writing system of the text, such as `Latin` or `Cyrillic`."#,
+			#"The `confidence` field is a float between `0.0` and `1.0`."#,
+			#"The `reliable` field is `true` if the detected language is unlikely to be wrong."#,
+			#"Returns `null` if `value` contains no text a language can be detected for, such as only numbers or punctuation."#,
+		]
+	}
+
+	examples: [
+		{
+			title: "Detect English"
+			source: #"""
+				detect_language("The quick brown fox jumps over the lazy dog, and then it runs into the forest.")
+				"""#
+			return: {
+				language:   "eng"
+				script:     "Latin"
+				confidence: 1.0
+				reliable:   true
+			}
+		},
+		{
+			title: "No detectable language"
+			source: #"""
+				detect_language("1234")
+				"""#
+			return: null
+		},
+	]
+}
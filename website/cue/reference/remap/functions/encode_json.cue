@@ -3,7 +3,8 @@ package metadata
 remap: functions: encode_json: {
 	category: "Codec"
 	description: """
-		Encodes the `value` to JSON.
+		Encodes the `value` to JSON. The keys of objects are always written in lexicographical
+		order, so that the same value is always encoded to the same string.
 		"""
 
 	arguments: [
@@ -13,8 +14,40 @@ remap: functions: encode_json: {
 			required:    true
 			type: ["any"]
 		},
+		{
+			name:        "pretty"
+			description: "Whether to indent the JSON over several lines, for readability."
+			required:    false
+			type: ["boolean"]
+			default: false
+		},
+		{
+			name:        "float_precision"
+			description: """
+				The number of decimal places to write floats with, from 0 to 17. By default, floats
+				are written with the fewest digits that read back as the same float.
+				"""
+			required: false
+			type: ["integer"]
+		},
+		{
+			name: "non_finite_floats"
+			description: """
+				How floats that aren't finite are encoded, since JSON has no representation for them.
+				"""
+			enum: {
+				error:  "Fail to encode values holding them."
+				null:   "Encode them as `null`."
+				string: #"Encode them as the strings `"NaN"`, `"Infinity"` and `"-Infinity"`."#
+			}
+			required: false
+			default:  "null"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` holds a float that isn't finite, and `non_finite_floats` is `error`",
 	]
-	internal_failure_reasons: []
 	return: types: ["string"]
 
 	examples: [
@@ -25,5 +58,12 @@ remap: functions: encode_json: {
 				"""#
 			return: #"{"hello":"world"}"#
 		},
+		{
+			title: "Encode to JSON with fixed float precision"
+			source: #"""
+				.payload = encode_json({"ratio": 0.5, "total": 3}, float_precision: 2)
+				"""#
+			return: #"{"ratio":0.50,"total":3}"#
+		},
 	]
 }
@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: encode_syslog_priority: {
+	category:    "Codec"
+	description: """
+		Computes the [Syslog PRI](\(urls.syslog_5424)) value from a `facility` and a `severity`, as
+		`facility * 8 + severity`. Both arguments accept either a numeric code or a keyword, i.e. `"local0"`
+		for the facility or `"info"` for the severity.
+		"""
+
+	arguments: [
+		{
+			name:        "facility"
+			description: "The Syslog [facility](\(urls.syslog_facility)) code or keyword."
+			required:    true
+			type: ["integer", "string"]
+		},
+		{
+			name:        "severity"
+			description: "The Syslog [severity](\(urls.syslog_levels)) code or keyword."
+			required:    true
+			type: ["integer", "string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`facility` isn't a valid Syslog [facility](\(urls.syslog_facility)) code or keyword.",
+		"`severity` isn't a valid Syslog [severity](\(urls.syslog_levels)) code or keyword.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Encode priority from keywords"
+			source: """
+				encode_syslog_priority!("local0", "info")
+				"""
+			return: 134
+		},
+		{
+			title: "Encode priority from codes"
+			source: """
+				encode_syslog_priority!(4, 2)
+				"""
+			return: 34
+		},
+	]
+}
@@ -0,0 +1,44 @@
+package metadata
+
+remap: functions: encode_syslog_structured_data: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` object into [RFC 5424](\(urls.syslog_5424)) Syslog structured data. Each top-level
+		key becomes an SD-ID and its object value holds the SD-PARAMs of that element.
+
+		Param values are converted to strings, with `"`, `\\` and `]` escaped. Arrays are encoded as repeated
+		params and `null` params are skipped. An empty object is encoded as the NILVALUE, `-`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object of SD-IDs to their params to encode."
+			required:    true
+			type: ["object"]
+		},
+	]
+	internal_failure_reasons: [
+		"An SD-ID or param name is empty, longer than 32 characters, or contains `=`, `]`, `\"`, spaces or non-printable characters.",
+		"An SD-ID's value isn't an object.",
+		"A param value is a nested object or array.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode Syslog structured data"
+			source: """
+				encode_syslog_structured_data!({"exampleSDID@32473": {"iut": "3", "eventSource": "Application"}})
+				"""
+			return: #"[exampleSDID@32473 eventSource="Application" iut="3"]"#
+		},
+		{
+			title: "Encode repeated params"
+			source: """
+				encode_syslog_structured_data!({"origin": {"ip": ["192.0.2.1", "192.0.2.2"]}})
+				"""
+			return: #"[origin ip="192.0.2.1" ip="192.0.2.2"]"#
+		},
+	]
+}
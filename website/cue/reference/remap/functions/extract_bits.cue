@@ -0,0 +1,46 @@
+package metadata
+
+remap: functions: extract_bits: {
+	category: "Number"
+	description: """
+		Extracts `length` bits of the `value` integer, starting at `offset` bits from the least
+		significant bit, as an unsigned integer. This is useful to decode flag fields and packed
+		binary headers.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The integer to extract the bits from."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "offset"
+			description: "The position of the first bit to extract, counting from the least significant bit."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "length"
+			description: "The number of bits to extract."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`offset` is negative or greater than 63.",
+		"`length` is less than 1, or extends past the 64th bit.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Extract a nibble"
+			source: #"""
+				extract_bits!(171, 4, 4)
+				"""#
+			return: 10
+		},
+	]
+}
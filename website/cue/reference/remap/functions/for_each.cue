@@ -13,7 +13,8 @@ remap: functions: for_each: {
 
 		The function uses the "function closure syntax" to allow reading
 		the key/value or index/value combination for each item in the
-		collection.
+		collection. The value can be left out of the closure to only read
+		the keys or indexes, as in `|key|`.
 
 		The same scoping rules apply to closure blocks as they do for
 		regular blocks, meaning, any variable defined in parent scopes
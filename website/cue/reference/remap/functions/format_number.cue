@@ -45,5 +45,12 @@ remap: functions: format_number: {
 				"""#
 			return: "1,234,567.890"
 		},
+		{
+			title: "Format a negative number with grouping"
+			source: #"""
+				format_number(-123456, grouping_separator: ",")
+				"""#
+			return: "-123,456"
+		},
 	]
 }
@@ -24,6 +24,21 @@ remap: functions: get_metadata_field: {
 
 					This exists if the `store_hec_token` setting is true in the `splunk_hec` source.
 					"""
+				priority: """
+					The priority class of the event, one of `low`, `normal` or `high`.
+
+					This is `normal` unless it was set with `set_metadata_field`.
+					"""
+				output: """
+					The named output of the `remap` transform the event is sent to.
+
+					This exists if it was set with `set_metadata_field`.
+					"""
+				ttl: """
+					The remaining time-to-live of the event, as a number of seconds rounded up.
+
+					This exists if it was set with `set_metadata_field`.
+					"""
 			}
 			type: ["string"]
 		},
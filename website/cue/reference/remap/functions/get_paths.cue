@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: get_paths: {
+	category: "Path"
+	description: """
+		Returns the paths of all leaf values in the `value` object or array, in the same
+		syntax as [`match_path`](#match_path) patterns. Empty objects and arrays are
+		returned as leaves. Fields that aren't plain identifiers are quoted.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to enumerate the paths of."
+			required:    true
+			type: ["object", "array"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Get the leaf paths of an object"
+			source: #"""
+				get_paths({"foo": {"bar": true, "baz": [1, 2]}})
+				"""#
+			return: ["foo.bar", "foo.baz[0]", "foo.baz[1]"]
+		},
+	]
+}
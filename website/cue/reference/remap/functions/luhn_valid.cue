@@ -0,0 +1,45 @@
+package metadata
+
+remap: functions: luhn_valid: {
+	category:    "String"
+	description: """
+		Determines whether the `value` is a number with a valid [Luhn](\(urls.luhn)) check digit, such as a
+		payment card number.
+
+		Spaces and dashes are ignored. Any other non-digit character makes the number invalid.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The number to check."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["boolean"]
+		rules: [
+			#"Returns `true` if `value` contains at least two digits and passes the Luhn checksum."#,
+			#"Returns `false` otherwise."#,
+		]
+	}
+
+	examples: [
+		{
+			title: "Valid card number"
+			source: #"""
+				luhn_valid("4111 1111 1111 1111")
+				"""#
+			return: true
+		},
+		{
+			title: "Invalid card number"
+			source: #"""
+				luhn_valid("4111 1111 1111 1112")
+				"""#
+			return: false
+		},
+	]
+}
@@ -0,0 +1,54 @@
+package metadata
+
+remap: functions: match_path: {
+	category: "Path"
+	description: """
+		Determines whether the `path` string, as returned by [`get_paths`](#get_paths), matches
+		the `pattern`.
+
+		A string `pattern` is a glob matched against the whole path, where `*` matches any
+		characters within a single segment, `**` matches any characters across segments and
+		`?` matches a single character. A regex `pattern` is used as-is.
+		"""
+
+	arguments: [
+		{
+			name:        "path"
+			description: "The path to match."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "pattern"
+			description: "The glob or regular expression to match the path against."
+			required:    true
+			type: ["string", "regex"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "Match a path with a glob"
+			source: #"""
+				match_path("user.address.city", "user.*.city")
+				"""#
+			return: true
+		},
+		{
+			title: "Match a path across segments"
+			source: #"""
+				match_path("user.emails[1].domain", "user.**.domain")
+				"""#
+			return: true
+		},
+		{
+			title: "Match a path with a regex"
+			source: #"""
+				match_path("user.password", r'\.(password|token)$')
+				"""#
+			return: true
+		},
+	]
+}
@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: only_fields: {
+	category: "Path"
+	description: """
+		Keeps only the fields or elements of the `value` whose path matches any of the
+		`patterns`, along with everything nested below them. Objects and arrays left empty
+		are removed.
+
+		Patterns are globs or regular expressions, matched against paths as described in
+		[`match_path`](#match_path).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to keep data from."
+			required:    true
+			type: ["object", "array"]
+		},
+		{
+			name:        "patterns"
+			description: "An array of globs or regular expressions matching the paths to keep."
+			required:    true
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: [
+		"A pattern is neither a string nor a regex.",
+	]
+	return: types: ["object", "array"]
+
+	examples: [
+		{
+			title: "Keep an allow-list of paths"
+			source: #"""
+				only_fields!({"user": {"id": 1, "password": "foo"}, "message": "bar"}, ["user.id", "message"])
+				"""#
+			return: {"message": "bar", "user": {"id": 1}}
+		},
+	]
+}
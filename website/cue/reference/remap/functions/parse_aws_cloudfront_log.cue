@@ -0,0 +1,71 @@
+package metadata
+
+remap: functions: parse_aws_cloudfront_log: {
+	category:    "Parse"
+	description: """
+		Parses `value` in the [CloudFront standard log format](\(urls.aws_cloudfront_access_logs)).
+		"""
+	notices: [
+		"""
+			Fields logged as `-` are `null` in the result. Fields missing from lines written before they were
+			added to the format are omitted. Values are returned as CloudFront logs them, so the user agent and
+			query string are still URL-encoded.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "CloudFront standard log line."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a properly formatted AWS CloudFront standard log line",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse AWS CloudFront log"
+			source: #"""
+				parse_aws_cloudfront_log!("2019-12-04\t21:02:31\tLAX1\t392\t192.0.2.100\tGET\td111111abcdef8.cloudfront.net\t/index.html\t200\t-\tcurl/7.68.0\t-\t-\tHit\tSOX4xwn4XV6Q4rgb7XiVGOHms_BGlTAC4KyHmureZmBNrjGdRLiNIQ==\td111111abcdef8.cloudfront.net\thttps\t23\t0.001\t-\tTLSv1.2\tECDHE-RSA-AES128-GCM-SHA256\tHit\tHTTP/2.0\t-\t-\t11040\t0.001\tHit\ttext/html\t78\t-\t-")
+				"""#
+			return: {
+				"timestamp":                 "2019-12-04T21:02:31Z"
+				"edge_location":             "LAX1"
+				"sc_bytes":                  392
+				"c_ip":                      "192.0.2.100"
+				"cs_method":                 "GET"
+				"cs_host":                   "d111111abcdef8.cloudfront.net"
+				"cs_uri_stem":               "/index.html"
+				"sc_status":                 200
+				"cs_referer":                null
+				"cs_user_agent":             "curl/7.68.0"
+				"cs_uri_query":              null
+				"cs_cookie":                 null
+				"edge_result_type":          "Hit"
+				"edge_request_id":           "SOX4xwn4XV6Q4rgb7XiVGOHms_BGlTAC4KyHmureZmBNrjGdRLiNIQ=="
+				"host_header":               "d111111abcdef8.cloudfront.net"
+				"cs_protocol":               "https"
+				"cs_bytes":                  23
+				"time_taken":                0.001
+				"forwarded_for":             null
+				"ssl_protocol":              "TLSv1.2"
+				"ssl_cipher":                "ECDHE-RSA-AES128-GCM-SHA256"
+				"edge_response_result_type": "Hit"
+				"cs_protocol_version":       "HTTP/2.0"
+				"fle_status":                null
+				"fle_encrypted_fields":      null
+				"c_port":                    11040
+				"time_to_first_byte":        0.001
+				"edge_detailed_result_type": "Hit"
+				"sc_content_type":           "text/html"
+				"sc_content_len":            78
+				"sc_range_start":            null
+				"sc_range_end":              null
+			}
+		},
+	]
+}
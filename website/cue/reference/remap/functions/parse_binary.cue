@@ -0,0 +1,79 @@
+package metadata
+
+remap: functions: parse_binary: {
+	category:    "Parse"
+	description: """
+		Parses the binary `value` into an object, according to the fixed field `layout`.
+
+		The `layout` must be an object literal, mapping each field name to an object describing where the field
+		is stored in `value`:
+
+		* `type`: one of `u8`, `u16`, `u32`, `u64`, `i8`, `i16`, `i32`, `i64`, `f32`, `f64`, `bytes` or
+		  `string`. Integer types are returned as integers, float types as floats, and `bytes` and `string` as
+		  strings. Trailing NUL bytes are removed from `string` fields.
+		* `offset`: the position of the first byte of the field in `value`.
+		* `length`: the number of bytes of the field. Required for `bytes` and `string` fields, and not
+		  allowed for any other type.
+		* `endianness`: optional, overrides the `endianness` argument for this field.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The binary payload to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "layout"
+			description: "The layout of the fields in `value`."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "endianness"
+			description: "The byte order of multi-byte fields."
+			required:    false
+			enum: {
+				big:    "Most significant byte first (network byte order)."
+				little: "Least significant byte first."
+			}
+			default: "big"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"A field extends beyond the end of `value`.",
+		"A `u64` field doesn't fit in a signed 64-bit integer.",
+		"A float field isn't a number.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse a big-endian record"
+			source: #"""
+				parse_binary!(
+					decode_base64!("AAEAAAAqZm9vAA=="),
+					{
+						"version": { "type": "u16", "offset": 0 },
+						"count": { "type": "u32", "offset": 2 },
+						"name": { "type": "string", "offset": 6, "length": 4 }
+					}
+				)
+				"""#
+			return: {
+				version: 1
+				count:   42
+				name:    "foo"
+			}
+		},
+		{
+			title: "Parse a little-endian record"
+			source: #"""
+				parse_binary!(decode_base64!("/v8="), { "delta": { "type": "i16", "offset": 0 } }, endianness: "little")
+				"""#
+			return: delta: -2
+		},
+	]
+}
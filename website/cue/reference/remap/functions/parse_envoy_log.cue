@@ -0,0 +1,66 @@
+package metadata
+
+remap: functions: parse_envoy_log: {
+	category:    "Parse"
+	description: """
+		Parses Envoy access log lines in the [default format](\(urls.envoy_access_log)).
+		"""
+	notices: [
+		"""
+			Missing information in the log message may be indicated by `-`. These fields are omitted in the result.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "timestamp_format"
+			description: """
+				The [date/time format](\(urls.chrono_time_formats)) to use for encoding the timestamp. The time is parsed
+				in local time if the timestamp doesn't specify a timezone.
+				"""
+			required: false
+			default:  "%+"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't match the default Envoy access log format",
+		"`timestamp_format` isn't a valid format string",
+		"The timestamp in `value` fails to parse using the provided `timestamp_format`",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse Envoy access log"
+			source: #"""
+				parse_envoy_log!(
+				    s'[2016-04-15T20:17:00.310Z] "POST /api/v1/locations HTTP/2" 204 - 154 0 226 100 "10.0.35.28" "nsq2http" "cc21d9b0-cf5c-432b-8c7e-98aeb7988cd2" "locations" "tcp://10.0.2.1:80"'
+				)
+				"""#
+			return: {
+				timestamp:             "2016-04-15T20:17:00.310Z"
+				request:               "POST /api/v1/locations HTTP/2"
+				method:                "POST"
+				path:                  "/api/v1/locations"
+				protocol:              "HTTP/2"
+				status:                204
+				bytes_received:        154
+				bytes_sent:            0
+				duration:              226
+				upstream_service_time: 100
+				forwarded_for:         "10.0.35.28"
+				agent:                 "nsq2http"
+				request_id:            "cc21d9b0-cf5c-432b-8c7e-98aeb7988cd2"
+				authority:             "locations"
+				upstream_host:         "tcp://10.0.2.1:80"
+			}
+		},
+	]
+}
@@ -0,0 +1,82 @@
+package metadata
+
+remap: functions: parse_haproxy_log: {
+	category:    "Parse"
+	description: """
+		Parses HAProxy log lines in the [HTTP log format](\(urls.haproxy_http_log)), with or without the
+		syslog header that precedes them.
+		"""
+	notices: [
+		"""
+			Missing information in the log message may be indicated by `-`. These fields are omitted in the result.
+			""",
+		"""
+			Timers of steps that weren't reached are `-1`, as HAProxy logs them. The `+` that HAProxy prefixes
+			to values logged before the end of a request is dropped.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "timestamp_format"
+			description: """
+				The [date/time format](\(urls.chrono_time_formats)) to use for encoding the timestamp. The time is parsed
+				in local time if the timestamp doesn't specify a timezone.
+				"""
+			required: false
+			default:  "%d/%b/%Y:%H:%M:%S%.f"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't match the HAProxy HTTP log format",
+		"`timestamp_format` isn't a valid format string",
+		"The timestamp in `value` fails to parse using the provided `timestamp_format`",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse HAProxy HTTP log"
+			source: #"""
+				parse_haproxy_log!(
+				    s'10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 {1wt.eu} {} "GET /index.html HTTP/1.1"'
+				)
+				"""#
+			return: {
+				client:               "10.0.1.2"
+				client_port:          33317
+				timestamp:            "2009-02-06T12:14:14.655Z"
+				frontend:             "http-in"
+				backend:              "static"
+				server:               "srv1"
+				request_time:         10
+				queue_time:           0
+				connect_time:         30
+				response_time:        69
+				total_time:           109
+				status:               200
+				size:                 2750
+				termination_state:    "----"
+				active_connections:   1
+				frontend_connections: 1
+				backend_connections:  1
+				server_connections:   1
+				retries:              0
+				server_queue:         0
+				backend_queue:        0
+				request_headers:      "1wt.eu"
+				request:              "GET /index.html HTTP/1.1"
+				method:               "GET"
+				path:                 "/index.html"
+				protocol:             "HTTP/1.1"
+			}
+		},
+	]
+}
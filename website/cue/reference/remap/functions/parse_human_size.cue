@@ -0,0 +1,60 @@
+package metadata
+
+remap: functions: parse_human_size: {
+	category:    "Parse"
+	description: """
+		Parses the `value` into a number, from a human readable size, duration or count such as `1.5GiB`,
+		`300ms` or `2k`.
+
+		Units are case-sensitive. Sizes are returned in bytes, where `kB` (or `KB`), `MB`, `GB`, `TB` and `PB` are
+		powers of 1000, and `KiB`, `MiB`, `GiB`, `TiB` and `PiB` are powers of 1024. Durations (`ns`, `us`,
+		`ms`, `s`, `m`, `h`, `d` and `w`) are returned in seconds. Counts (`k` or `K`, `M`, `G`, `T` and `P`) are
+		returned as plain numbers.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string of the size, duration or count to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "unit"
+			description: "The unit to express the result in. Must be of the same kind as the unit of `value`."
+			required:    false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a properly formatted size.",
+		"`value` or `unit` uses an unknown unit.",
+		"`unit` is of a different kind than the unit of `value`.",
+		"`value` is too large to be represented.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Parse a binary size"
+			source: """
+				parse_human_size!("1.5GiB")
+				"""
+			return: 1610612736.0
+		},
+		{
+			title: "Parse a duration"
+			source: """
+				parse_human_size!("300ms")
+				"""
+			return: 0.3
+		},
+		{
+			title: "Parse a size into a unit"
+			source: """
+				parse_human_size!("1.5GiB", unit: "MiB")
+				"""
+			return: 1536.0
+		},
+	]
+}
@@ -10,6 +10,10 @@ remap: functions: parse_json: {
 			Only JSON types are returned. If you need to convert a `string` into a `timestamp`, consider the
 			[`parse_timestamp`](#parse_timestamp) function.
 			""",
+		"""
+			An optional closure can be provided to handle invalid JSON. The closure receives the error
+			message, and the function returns the value the closure resolves to instead of failing.
+			""",
 	]
 
 	arguments: [
@@ -28,6 +32,21 @@ remap: functions: parse_json: {
 			required: false
 			type: ["integer"]
 		},
+		{
+			name: "non_finite_floats"
+			description: """
+				How the non-standard `NaN`, `Infinity` and `-Infinity` literals are parsed, since JSON
+				has no representation for floats that aren't finite.
+				"""
+			enum: {
+				error:  "Fail to parse documents holding them."
+				null:   "Parse them as `null`."
+				string: #"Parse them as the strings `"NaN"`, `"Infinity"` and `"-Infinity"`."#
+			}
+			required: false
+			default:  "error"
+			type: ["string"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` isn't a valid JSON-formatted payload",
@@ -49,5 +68,19 @@ remap: functions: parse_json: {
 				"""#
 			return: top_level: "{\"key\": \"val\"}"
 		},
+		{
+			title: "Parse JSON with non-finite floats"
+			source: #"""
+				parse_json!("{\"rate\": NaN}", non_finite_floats: "null")
+				"""#
+			return: rate: null
+		},
+		{
+			title: "Parse JSON with an error handler"
+			source: #"""
+				parse_json("{ INVALID }") -> |err| { { "error": err } }
+				"""#
+			return: error: "unable to parse json: key must be a string at line 1 column 3"
+		},
 	]
 }
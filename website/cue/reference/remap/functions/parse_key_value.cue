@@ -5,12 +5,13 @@ remap: functions: parse_key_value: {
 	description: """
 		Parses the `value` in key/value format. Also known as [logfmt](\(urls.logfmt)).
 
-		* Keys and values can be wrapped with `"`.
-		* `"` characters can be escaped using `\\`.
+		* Keys and values can be wrapped with `"`, or any of the `quote_chars`.
+		* Quote characters can be escaped using `\\`.
 		"""
 	notices: [
 		"""
-			All values are returned as strings. We recommend manually coercing values to desired types as you see fit.
+			All values are returned as strings unless `infer_types` is set. We recommend manually coercing values
+			to desired types as you see fit.
 			""",
 	]
 
@@ -53,6 +54,45 @@ remap: functions: parse_key_value: {
 			type: ["boolean"]
 			default: true
 		},
+		{
+			name: "quote_chars"
+			description: """
+				The characters keys and values can be wrapped with. A value wrapped with one of them can contain the
+				others without escaping them, such as `msg='said "hi"'`.
+				"""
+			required: false
+			default:  "\""
+			type: ["string"]
+		},
+		{
+			name:        "unescape"
+			description: "Whether to remove the `\\` escaping `\\` or the quote character within wrapped keys and values."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+		{
+			name: "infer_types"
+			description: """
+				Whether to return values that aren't wrapped in quotes as integers, floats or booleans when they are
+				written as one. Wrapped values are always returned as strings.
+				"""
+			required: false
+			default:  false
+			type: ["boolean"]
+		},
+		{
+			name:        "duplicate_keys"
+			description: "What to return for a key that appears more than once."
+			required:    false
+			enum: {
+				first: "Keep the first value of the key."
+				last:  "Keep the last value of the key."
+				array: "Keep all the values of the key in an array."
+			}
+			default: "last"
+			type: ["string"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` isn't a properly formatted key/value string",
@@ -113,5 +153,22 @@ remap: functions: parse_key_value: {
 				beta:    true
 			}
 		},
+		{
+			title: "Parse log with nested quotes, typed values and repeated keys"
+			source: #"""
+				parse_key_value!(
+					"msg='said \"hi\"' status=200 cached=false tag=a tag=b",
+					quote_chars: "'\"",
+					infer_types: true,
+					duplicate_keys: "array",
+				)
+				"""#
+			return: {
+				msg:    #"said "hi""#
+				status: 200
+				cached: false
+				tag: ["a", "b"]
+			}
+		},
 	]
 }
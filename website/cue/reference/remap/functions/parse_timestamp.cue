@@ -4,25 +4,31 @@ remap: functions: parse_timestamp: {
 	category:    "Parse"
 	description: """
 		Parses the `value` in [strptime](\(urls.strptime_specifiers)) `format`.
+
+		`format` can also be an array of formats, which are tried in order until one of them
+		matches. Integer and float values are parsed as Unix epochs, where the unit (seconds,
+		milliseconds, microseconds or nanoseconds) is detected from the magnitude of the value.
 		"""
 
 	arguments: [
 		{
 			name:        "value"
-			description: "The text of the timestamp."
+			description: "The text of the timestamp, or a Unix epoch."
 			required:    true
-			type: ["string"]
+			type: ["string", "integer", "float"]
 		},
 		{
 			name:        "format"
-			description: "The [strptime](\(urls.strptime_specifiers)) format."
-			required:    true
-			type: ["string"]
+			description: "The [strptime](\(urls.strptime_specifiers)) format, or an array of formats to try in order. Required when `value` is a string."
+			required:    false
+			type: ["string", "array"]
 		},
 
 	]
 	internal_failure_reasons: [
 		"`value` fails to parse using the provided `format`",
+		"`value` fails to parse using any of the provided formats",
+		"`value` is a string and no `format` is provided",
 	]
 	return: types: ["timestamp"]
 
@@ -34,5 +40,19 @@ remap: functions: parse_timestamp: {
 				"""#
 			return: "2020-10-10T16:00:00Z"
 		},
+		{
+			title: "Parse timestamp with fallback formats"
+			source: #"""
+				parse_timestamp!("2020-10-10 16:00:00 +00:00", format: ["%v %R %:z", "%F %T %:z"])
+				"""#
+			return: "2020-10-10T16:00:00Z"
+		},
+		{
+			title: "Parse epoch milliseconds"
+			source: #"""
+				parse_timestamp!(1602345600000)
+				"""#
+			return: "2020-10-10T16:00:00Z"
+		},
 	]
 }
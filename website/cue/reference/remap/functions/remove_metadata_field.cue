@@ -24,6 +24,21 @@ remap: functions: remove_metadata_field: {
 
 					This field will be used by the  `splunk_*` sinks as the token to send the events with.
 					"""
+				priority: """
+					The priority class of the event.
+
+					Removing this field resets the priority of the event to `normal`.
+					"""
+				output: """
+					The named output of the `remap` transform the event is sent to.
+
+					Removing this field sends the event to the default output.
+					"""
+				ttl: """
+					The time-to-live of the event.
+
+					Removing this field keeps the event from expiring.
+					"""
 			}
 			type: ["string"]
 		},
@@ -0,0 +1,41 @@
+package metadata
+
+remap: functions: remove_paths: {
+	category: "Path"
+	description: """
+		Removes every field or element of the `value` whose path matches any of the `patterns`,
+		along with everything nested below it.
+
+		Patterns are globs or regular expressions, matched against paths as described in
+		[`match_path`](#match_path).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to remove data from."
+			required:    true
+			type: ["object", "array"]
+		},
+		{
+			name:        "patterns"
+			description: "An array of globs or regular expressions matching the paths to remove."
+			required:    true
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: [
+		"A pattern is neither a string nor a regex.",
+	]
+	return: types: ["object", "array"]
+
+	examples: [
+		{
+			title: "Remove paths matching globs"
+			source: #"""
+				remove_paths!({"user": {"name": "foo", "password": "bar"}, "token": "baz"}, ["**password", "token"])
+				"""#
+			return: {"user": {"name": "foo"}}
+		},
+	]
+}
@@ -22,6 +22,21 @@ remap: functions: set_metadata_field: {
 
 					This field will be used by the  `splunk_*` sinks as the token to send the events with.
 					"""
+				priority: """
+					The priority class of the event, one of `low`, `normal` or `high`.
+
+					This field will be used by sinks with `priority.enabled` set to send higher priority events first, and to shed lower priority events first when overloaded.
+					"""
+				output: """
+					The named output of the `remap` transform to send the event to.
+
+					The output has to be declared in the `outputs` setting of the `remap` transform running the program.
+					"""
+				ttl: """
+					The time-to-live of the event, as a whole number of seconds from when it's set.
+
+					Buffers and sinks discard the event instead of delivering it once it expires, such as after waiting in a buffer during an outage.
+					"""
 			}
 			type: ["string"]
 		},
@@ -33,6 +48,8 @@ remap: functions: set_metadata_field: {
 		},
 	]
 	internal_failure_reasons: [
+		"`key` is `priority` and `value` is not one of `low`, `normal` or `high`.",
+		"`key` is `ttl` and `value` is not a whole number of seconds.",
 	]
 	return: types: ["null"]
 
@@ -44,5 +61,26 @@ remap: functions: set_metadata_field: {
 				"""#
 			return: "null"
 		},
+		{
+			title: "Mark the event as high priority."
+			source: #"""
+				set_metadata_field!("priority", "high")
+				"""#
+			return: "null"
+		},
+		{
+			title: "Send the event to the `errors` output of the remap transform."
+			source: #"""
+				set_metadata_field("output", "errors")
+				"""#
+			return: "null"
+		},
+		{
+			title: "Discard the event if it isn't delivered within five minutes."
+			source: #"""
+				set_metadata_field!("ttl", "300")
+				"""#
+			return: "null"
+		},
 	]
 }
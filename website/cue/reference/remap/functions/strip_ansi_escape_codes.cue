@@ -4,6 +4,9 @@ remap: functions: strip_ansi_escape_codes: {
 	category:    "String"
 	description: """
 		Strips [ANSI escape codes](\(urls.ansi_escape_codes)) from the `value`.
+
+		Besides colors, this removes cursor movements, operating system commands such as window titles and
+		hyperlinks, and any other control characters except for tabs, carriage returns and line feeds.
 		"""
 
 	arguments: [
@@ -13,9 +16,21 @@ remap: functions: strip_ansi_escape_codes: {
 			required:    true
 			type: ["string"]
 		},
+		{
+			name: "severity_hint"
+			description: """
+				Whether to return an object with the stripped `message`, and a `severity_hint` derived from the most
+				severe foreground color in the `value`: red and magenta map to `error`, yellow to `warning`, green,
+				blue and cyan to `info`, and gray to `debug`. The `severity_hint` is `null` if none of these colors
+				are used.
+				"""
+			required: false
+			default:  false
+			type: ["boolean"]
+		},
 	]
 	internal_failure_reasons: []
-	return: types: ["string"]
+	return: types: ["string", "object"]
 
 	examples: [
 		{
@@ -25,5 +40,15 @@ remap: functions: strip_ansi_escape_codes: {
 				"""#
 			return: "foo bar"
 		},
+		{
+			title: "Strip ANSI escape codes with a severity hint"
+			source: #"""
+				strip_ansi_escape_codes("\e[1;31mERROR\e[0m connection refused", severity_hint: true)
+				"""#
+			return: {
+				message:       "ERROR connection refused"
+				severity_hint: "error"
+			}
+		},
 	]
 }
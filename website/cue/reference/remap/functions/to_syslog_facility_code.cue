@@ -0,0 +1,32 @@
+package metadata
+
+remap: functions: to_syslog_facility_code: {
+	category:    "Convert"
+	description: """
+		Converts the `value`, a Syslog [facility keyword](\(urls.syslog_facility)), into its corresponding
+		Syslog facility code. i.e. `"kern"` into 0, `"user"` into 1, etc.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The Syslog facility keyword to convert."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid Syslog [facility keyword](\(urls.syslog_facility)).",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Coerce to a Syslog facility code"
+			source: """
+				to_syslog_facility_code!("local0")
+				"""
+			return: 16
+		},
+	]
+}
@@ -0,0 +1,45 @@
+package metadata
+
+remap: functions: transliterate: {
+	category:    "String"
+	description: """
+		Transliterates the `value` to ASCII, by replacing non-ASCII characters with their closest Latin
+		equivalent using [deunicode](\(urls.deunicode)).
+
+		This is useful to normalize text for search backends that don't analyze non-Latin scripts or accented
+		characters. The transliteration is a best effort, and can't be reversed.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The text to transliterate."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["string"]
+		rules: [
+			"Characters without a known ASCII equivalent are replaced with `[?]`.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Transliterate Cyrillic"
+			source: #"""
+				transliterate("Жизнь прекрасна")
+				"""#
+			return: "Zhizn' prekrasna"
+		},
+		{
+			title: "Remove accents"
+			source: #"""
+				transliterate("Ærøskøbing straße")
+				"""#
+			return: "AEroskobing strasse"
+		},
+	]
+}
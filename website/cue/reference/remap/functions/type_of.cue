@@ -0,0 +1,41 @@
+package metadata
+
+remap: functions: type_of: {
+	category: "Type"
+	description: """
+		Returns the structural type signature of the `value`. Scalars are replaced by the name of
+		their type, objects keep their fields with the signature of each value, and arrays hold
+		the distinct signatures of their elements in the order they first appear.
+
+		As the signature doesn't depend on the values or on the length of arrays, it can be
+		compared between events to detect changes in the shape of a payload.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The value to return the type signature of."
+			required:    true
+			type: ["any"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string", "object", "array"]
+
+	examples: [
+		{
+			title: "Type of a scalar"
+			source: #"""
+				type_of(123)
+				"""#
+			return: "integer"
+		},
+		{
+			title: "Type signature of an object"
+			source: #"""
+				type_of({"a": "foo", "b": [1, 2, 3], "c": {"d": null}})
+				"""#
+			return: {"a": "string", "b": ["integer"], "c": {"d": "null"}}
+		},
+	]
+}
@@ -0,0 +1,43 @@
+package metadata
+
+remap: functions: verify_ean: {
+	category:    "String"
+	description: """
+		Determines whether the `value` is a valid EAN-8, UPC-A, EAN-13, or GTIN-14 product code, by
+		verifying its trailing check digit.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The product code to check."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["boolean"]
+		rules: [
+			#"Returns `true` if `value` consists of 8, 12, 13, or 14 digits and has a valid check digit."#,
+			#"Returns `false` otherwise."#,
+		]
+	}
+
+	examples: [
+		{
+			title: "Valid EAN-13"
+			source: #"""
+				verify_ean("4006381333931")
+				"""#
+			return: true
+		},
+		{
+			title: "Invalid check digit"
+			source: #"""
+				verify_ean("4006381333932")
+				"""#
+			return: false
+		},
+	]
+}
@@ -9,15 +9,20 @@ remap: syntax: keywords: {
 		* `abort`
 		* `as`
 		* `break`
+		* `const`
 		* `continue`
 		* `else`
 		* `false`
+		* `fn`
 		* `for`
+		* `for_each`
 		* `if`
 		* `impl`
+		* `import`
 		* `in`
 		* `let`
 		* `loop`
+		* `match`
 		* `null`
 		* `return`
 		* `self`
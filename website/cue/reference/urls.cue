@@ -20,6 +20,7 @@ urls: {
 	aws_athena:                                               "https://aws.amazon.com/athena/"
 	aws_athena_console:                                       "https://console.aws.amazon.com/athena/home"
 	aws_canonical_user_id:                                    "\(aws_docs)/general/latest/gr/acct-identifiers.html#FindingCanonicalId"
+	aws_cloudfront_access_logs:                               "\(aws_docs)/AmazonCloudFront/latest/DeveloperGuide/AccessLogs.html#BasicDistributionFileFormat"
 	aws_cloudwatch:                                           "https://aws.amazon.com/cloudwatch/"
 	aws_cloudwatch_logs:                                      "\(aws_docs)/AmazonCloudWatch/latest/logs/WhatIsCloudWatchLogs.html"
 	aws_cloudwatch_logs_api:                                  "\(aws_docs)/AmazonCloudWatchLogs/latest/APIReference/Welcome.html"
@@ -76,6 +77,7 @@ urls: {
 	aws_s3_sse:                                               "\(aws_docs)/AmazonS3/latest/dev/UsingServerSideEncryption.html"
 	aws_s3_storage_classes:                                   "https://aws.amazon.com/s3/storage-classes/"
 	aws_s3_tags:                                              "\(aws_docs)/AmazonS3/latest/user-guide/add-object-tags.html"
+	aws_sigv4:                                                "\(aws_docs)/general/latest/gr/signature-version-4.html"
 	aws_sqs:                                                  "https://aws.amazon.com/sqs/"
 	aws_sqs_api:                                              "\(aws_docs)/AWSSimpleQueueService/latest/APIReference/Welcome.html"
 	aws_sqs_create:                                           "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-configure-create-queue.html"
@@ -85,6 +87,7 @@ urls: {
 	azure_blob_endpoints:                                     "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
 	azure_monitor:                                            "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:                             "https://docs.microsoft.com/en-us/rest/api/monitor/"
+	azure_workload_identity:                                  "https://azure.github.io/azure-workload-identity/docs/"
 	base64:                                                   "\(wikipedia)/wiki/Base64"
 	base64_padding:                                           "\(wikipedia)/wiki/Base64#Output_padding"
 	base64_standard:                                          "https://tools.ietf.org/html/rfc4648#section-4"
@@ -131,6 +134,7 @@ urls: {
 	debian:                                                   "https://www.debian.org/"
 	debian_system_groups:                                     "https://wiki.debian.org/SystemGroups"
 	default_configuration:                                    "\(vector_repo)/blob/master/config/vector.toml"
+	deunicode:                                                "https://github.com/kornelski/deunicode"
 	dnstap:                                                   "http://dnstap.info/"
 	docker:                                                   "https://www.docker.com/"
 	docker_alpine:                                            "\(docker_hub)/_/alpine"
@@ -172,6 +176,7 @@ urls: {
 	encoding_standard:                                        "https://encoding.spec.whatwg.org/"
 	endler_dev:                                               "https://endler.dev/"
 	enrichment_tables_concept:                                "/docs/reference/glossary/#enrichment-tables"
+	envoy_access_log:                                         "https://www.envoyproxy.io/docs/envoy/latest/configuration/observability/access_log/usage#default-format-string"
 	eof:                                                      "\(wikipedia)/wiki/End-of-file#EOF_character"
 	etsy:                                                     "https://www.etsy.com"
 	event_proto:                                              "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
@@ -204,6 +209,7 @@ urls: {
 	gcp_stackdriver_metrics:                                  "\(gcp)/monitoring/api/ref_v3/rest/"
 	gcp_stackdriver_metrics_rest:                             "https://monitoring.googleapis.com/"
 	gcp_stackdriver_severity:                                 "\(gcp)/logging/docs/reference/v2/rest/v2/LogEntry#logseverity"
+	gcp_workload_identity_federation:                         "\(gcp)/iam/docs/workload-identity-federation"
 	gcp_xml_interface:                                        "\(gcp)/storage/docs/xml-api/overview"
 	gcra:                                                     "\(wikipedia)/wiki/Generic_cell_rate_algorithm"
 	gcs:                                                      "https://cloud.google.com/storage/"
@@ -224,6 +230,8 @@ urls: {
 	grok_patterns:                                            "\(github)/daschl/grok/tree/master/patterns"
 	gzip:                                                     "https://www.gzip.org/"
 	haproxy:                                                  "https://www.haproxy.org/"
+	haproxy_http_log:                                         "https://docs.haproxy.org/2.6/configuration.html#8.2.3"
+	haproxy_proxy_protocol:                                   "https://www.haproxy.org/download/2.6/doc/proxy-protocol.txt"
 	helm:                                                     "https://helm.sh/"
 	heroku:                                                   "https://www.heroku.com"
 	heroku_http_log_drain:                                    "https://devcenter.heroku.com/articles/log-drains#https-drains"
@@ -252,10 +260,12 @@ urls: {
 	influxdb_authentication_token:                            "https://v2.docs.influxdata.com/v2.0/security/tokens/"
 	influxdb_line_protocol:                                   "https://v2.docs.influxdata.com/v2.0/reference/syntax/line-protocol/"
 	inode:                                                    "\(wikipedia)/wiki/Inode"
+	io_uring:                                                 "https://man7.org/linux/man-pages/man7/io_uring.7.html"
 	ip_aton:                                                  "https://linux.die.net/man/3/inet_aton"
 	ip_ntoa:                                                  "https://linux.die.net/man/3/inet_ntoa"
 	ip_ntop:                                                  "https://linux.die.net/man/3/inet_ntop"
 	ip_pton:                                                  "https://linux.die.net/man/3/inet_pton"
+	iso_639_3:                                                "\(wikipedia)/wiki/ISO_639-3"
 	iso_8601:                                                 "\(wikipedia)/wiki/ISO_8601"
 	iso3166_2:                                                "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                               "\(vector_repo)/issues/1694"
@@ -317,6 +327,7 @@ urls: {
 	lua_sequence:                                             "\(lua)/pil/11.1.html"
 	lua_string:                                               "\(lua)/pil/2.4.html"
 	lua_tonumber:                                             "\(lua)/manual/5.4/manual.html#pdf-tonumber"
+	luhn:                                                     "https://en.wikipedia.org/wiki/Luhn_algorithm"
 	lz4:                                                      "https://lz4.github.io/lz4/"
 	macos:                                                    "\(wikipedia)/wiki/MacOS"
 	magic_bytes:                                              "\(wikipedia)/wiki/List_of_file_signatures"
@@ -357,6 +368,8 @@ urls: {
 	nix:                                                      "https://nixos.org/nix/"
 	nixos:                                                    "https://nixos.org/"
 	nixpkgs_9682:                                             "\(github)/NixOS/nixpkgs/issues/9682"
+	oauth2:                                                   "https://oauth.net/2/"
+	oauth2_jwt_bearer:                                        "https://www.rfc-editor.org/rfc/rfc7523#section-2.1"
 	openssl:                                                  "https://www.openssl.org/"
 	order_of_ops:                                             "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                               "https://www.papertrail.com/"
@@ -438,9 +451,11 @@ urls: {
 	sha2:                                                     "\(wikipedia)/wiki/SHA-2"
 	sha3:                                                     "\(wikipedia)/wiki/SHA-3"
 	signal:                                                   "\(wikipedia)/wiki/Signal_(IPC)"
+	simd_json:                                                "https://github.com/simd-lite/simd-json"
 	snake_case:                                               "\(wikipedia)/wiki/Snake_case"
 	snappy:                                                   "https://google.github.io/snappy/"
 	socket:                                                   "\(wikipedia)/wiki/Network_socket"
+	socks5:                                                   "https://www.rfc-editor.org/rfc/rfc1928"
 	splunk:                                                   "https://www.splunk.com"
 	splunk_hec:                                               "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
 	splunk_hec_channel_header:                                "https://docs.splunk.com/Documentation/Splunk/8.2.4/Data/FormateventsforHTTPEventCollector#Channel_identifier_header"
@@ -620,6 +635,7 @@ urls: {
 	wasm:                                                     "https://webassembly.org/"
 	wasm_languages:                                           "\(github)/appcypher/awesome-wasm-langs"
 	websocket:                                                "\(wikipedia)/wiki/WebSocket"
+	whatlang:                                                 "https://github.com/greyblake/whatlang-rs"
 	wikipedia:                                                "https://en.wikipedia.org"
 	windows:                                                  "https://www.microsoft.com/en-us/windows"
 	windows_installer:                                        "\(wikipedia)/wiki/Windows_Installer"